@@ -9,6 +9,9 @@ fn main() {
     }
     println!("cargo:rerun-if-changed=web/build/client");
 
+    // Migrations are embedded by `sqlx::migrate!`; rebuild when they change.
+    println!("cargo:rerun-if-changed=migrations");
+
     // Try to get Git commit hash from Railway environment variable first
     let git_hash = std::env::var("RAILWAY_GIT_COMMIT_SHA").unwrap_or_else(|_| {
         // Fallback to git command if not on Railway
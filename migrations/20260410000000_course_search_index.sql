@@ -0,0 +1,16 @@
+-- Term-scoped search index for course text search.
+--
+-- One row per course holding a weighted tsvector over code, title, and
+-- instructor names. Unlike a materialized view, rows are rebuilt one term
+-- at a time (DELETE + INSERT in a transaction) so a scrape burst on the
+-- current term never forces a refresh of every historical term.
+
+CREATE TABLE course_search_index (
+    course_id INTEGER PRIMARY KEY REFERENCES courses(id) ON DELETE CASCADE,
+    term_code VARCHAR NOT NULL,
+    document tsvector NOT NULL,
+    refreshed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_course_search_index_term ON course_search_index (term_code);
+CREATE INDEX idx_course_search_index_document ON course_search_index USING GIN (document);
@@ -0,0 +1,24 @@
+-- User-submitted data issue reports, triaged through the admin feedback queue.
+--
+-- Entity references use ON DELETE SET NULL so reports outlive course/instructor
+-- churn; `entity_label` keeps a human-readable snapshot for that case.
+
+CREATE TABLE user_feedback (
+    id SERIAL PRIMARY KEY,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('course', 'instructor')),
+    course_id INTEGER REFERENCES courses(id) ON DELETE SET NULL,
+    instructor_id INTEGER REFERENCES instructors(id) ON DELETE SET NULL,
+    entity_label TEXT NOT NULL,
+    category TEXT NOT NULL CHECK (category IN ('wrong_data', 'missing_data', 'other')),
+    message TEXT NOT NULL CHECK (char_length(message) BETWEEN 1 AND 2000),
+    page_url TEXT,
+    reporter_discord_id BIGINT REFERENCES users(discord_id) ON DELETE SET NULL,
+    status TEXT NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'triaged', 'resolved', 'dismissed')),
+    admin_note TEXT,
+    reviewed_by BIGINT REFERENCES users(discord_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_user_feedback_status_created ON user_feedback (status, created_at DESC);
@@ -0,0 +1,34 @@
+-- Per-section detail data scraped from Banner's class detail endpoints
+-- (restrictions, corequisites, linked sections) by the CrnList detail job.
+
+ALTER TABLE courses ADD COLUMN details_scraped_at TIMESTAMPTZ;
+
+CREATE TABLE course_restrictions (
+    id SERIAL PRIMARY KEY,
+    course_id INTEGER NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    -- Verbatim Banner heading, e.g. "Must be enrolled in one of the following Levels:"
+    heading TEXT NOT NULL,
+    value TEXT NOT NULL,
+    position SMALLINT NOT NULL
+);
+
+CREATE INDEX idx_course_restrictions_course ON course_restrictions (course_id);
+
+CREATE TABLE course_corequisites (
+    id SERIAL PRIMARY KEY,
+    course_id INTEGER NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    subject VARCHAR NOT NULL,
+    course_number VARCHAR NOT NULL,
+    title TEXT,
+    position SMALLINT NOT NULL
+);
+
+CREATE INDEX idx_course_corequisites_course ON course_corequisites (course_id);
+
+-- Each group is a set of CRNs that must be registered alongside the course.
+CREATE TABLE course_linked_sections (
+    course_id INTEGER NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    group_index SMALLINT NOT NULL,
+    linked_crn VARCHAR NOT NULL,
+    PRIMARY KEY (course_id, group_index, linked_crn)
+);
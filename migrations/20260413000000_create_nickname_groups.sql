@@ -0,0 +1,77 @@
+-- Admin-managed nickname equivalence groups for RMP name matching.
+--
+-- Each row is a set of interchangeable first names (normalized: lowercase,
+-- letters only). Seeded from the built-in list in `names.rs`.
+
+CREATE TABLE nickname_groups (
+    id SERIAL PRIMARY KEY,
+    names TEXT[] NOT NULL CHECK (cardinality(names) >= 2),
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO nickname_groups (names) VALUES
+    (ARRAY['christopher', 'chris']),
+    (ARRAY['kimberly', 'kim']),
+    (ARRAY['matthew', 'matt']),
+    (ARRAY['michael', 'mike']),
+    (ARRAY['william', 'will', 'bill', 'billy']),
+    (ARRAY['robert', 'rob', 'bob', 'bobby']),
+    (ARRAY['richard', 'rick', 'dick']),
+    (ARRAY['james', 'jim', 'jimmy']),
+    (ARRAY['thomas', 'tom', 'tommy']),
+    (ARRAY['joseph', 'joe', 'joey']),
+    (ARRAY['charles', 'charlie', 'chuck']),
+    (ARRAY['daniel', 'dan', 'danny']),
+    (ARRAY['edward', 'ed', 'eddie', 'ted']),
+    (ARRAY['elizabeth', 'liz', 'beth', 'betty']),
+    (ARRAY['jennifer', 'jen', 'jenny']),
+    (ARRAY['katherine', 'kate', 'kathy', 'katie', 'cathy']),
+    (ARRAY['patricia', 'pat', 'patty']),
+    (ARRAY['margaret', 'maggie', 'peggy']),
+    (ARRAY['stephanie', 'steph']),
+    (ARRAY['nicholas', 'nick']),
+    (ARRAY['timothy', 'tim']),
+    (ARRAY['benjamin', 'ben']),
+    (ARRAY['jonathan', 'jon']),
+    (ARRAY['alexander', 'alex']),
+    (ARRAY['samuel', 'sam']),
+    (ARRAY['gregory', 'greg']),
+    (ARRAY['steven', 'steve']),
+    (ARRAY['stephen', 'steve']),
+    (ARRAY['andrew', 'drew', 'andy']),
+    (ARRAY['anthony', 'tony']),
+    (ARRAY['kenneth', 'ken', 'kenny']),
+    (ARRAY['raymond', 'ray']),
+    (ARRAY['lawrence', 'larry']),
+    (ARRAY['jeffrey', 'jeff']),
+    (ARRAY['gerald', 'gerry', 'jerry']),
+    (ARRAY['theodore', 'theo', 'ted']),
+    (ARRAY['frederick', 'fred', 'freddy']),
+    (ARRAY['douglas', 'doug']),
+    (ARRAY['randolph', 'randy']),
+    (ARRAY['nathaniel', 'nate', 'nathan']),
+    (ARRAY['patrick', 'pat']),
+    (ARRAY['phillip', 'phil']),
+    (ARRAY['susanne', 'sue', 'susan', 'susie']),
+    (ARRAY['catherine', 'cathy', 'cat']),
+    (ARRAY['christina', 'chris', 'tina']),
+    (ARRAY['deborah', 'debbie', 'deb']),
+    (ARRAY['pamela', 'pam']),
+    (ARRAY['cynthia', 'cindy']),
+    (ARRAY['judith', 'judy']),
+    (ARRAY['virginia', 'ginny']),
+    (ARRAY['dorothy', 'dot']),
+    (ARRAY['victoria', 'vicki', 'vicky']),
+    (ARRAY['rebecca', 'becky']),
+    (ARRAY['valerie', 'val']),
+    (ARRAY['terrence', 'terry']),
+    (ARRAY['terence', 'terry']),
+    (ARRAY['leonard', 'leo', 'len']),
+    (ARRAY['donald', 'don']),
+    (ARRAY['ronald', 'ron']),
+    (ARRAY['harold', 'harry', 'hal']),
+    (ARRAY['eugene', 'gene']),
+    (ARRAY['clifford', 'cliff']),
+    (ARRAY['ricardo', 'ricky']);
@@ -0,0 +1,7 @@
+-- Compact per-run diff (courses added/removed/changed, fields touched) for completed subject scrapes.
+ALTER TABLE scrape_job_results ADD COLUMN diff_summary JSONB;
+
+-- Latest diff lookup per queued job payload (admin job list).
+CREATE INDEX idx_scrape_job_results_payload_latest
+    ON scrape_job_results (target_type, payload, completed_at DESC)
+    WHERE diff_summary IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Per-day enrollment series per course, rolled up from course_metrics so list
+-- views can render sparklines without reading raw snapshots.
+-- enrollment[1] is the value at the end of start_date; gaps carry the previous
+-- day's value forward, and the array always runs through the rollup date.
+CREATE TABLE course_enrollment_sparklines (
+    course_id  INTEGER PRIMARY KEY REFERENCES courses(id) ON DELETE CASCADE,
+    start_date DATE NOT NULL,
+    enrollment INTEGER[] NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
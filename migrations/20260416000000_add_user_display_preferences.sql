@@ -0,0 +1,6 @@
+-- Per-user display preferences for server-rendered times (ICS, bot replies).
+CREATE TYPE time_format AS ENUM ('TwelveHour', 'TwentyFourHour');
+
+ALTER TABLE users
+    ADD COLUMN timezone TEXT NOT NULL DEFAULT 'America/Chicago',
+    ADD COLUMN time_format time_format NOT NULL DEFAULT 'TwelveHour';
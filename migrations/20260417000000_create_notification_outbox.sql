@@ -0,0 +1,23 @@
+-- Outbound messages (Discord DMs, webhooks, email) written in the same
+-- transaction as the state change that triggers them, then delivered by the
+-- outbox dispatcher with leasing and retries.
+CREATE TABLE notification_outbox (
+    id              BIGSERIAL PRIMARY KEY,
+    channel         TEXT NOT NULL CHECK (channel IN ('discord_dm', 'webhook', 'email')),
+    payload         JSONB NOT NULL,
+    -- Optional producer-supplied key; duplicate enqueues are dropped.
+    dedupe_key      TEXT UNIQUE,
+    status          TEXT NOT NULL DEFAULT 'pending'
+                    CHECK (status IN ('pending', 'delivered', 'dead')),
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Set while a dispatcher holds the message; expired leases are reclaimed.
+    locked_until    TIMESTAMPTZ,
+    last_error      TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at    TIMESTAMPTZ
+);
+
+CREATE INDEX idx_notification_outbox_due
+    ON notification_outbox (next_attempt_at)
+    WHERE status = 'pending';
@@ -0,0 +1,19 @@
+-- Point-in-time RMP aggregates, recorded by the bulk sync whenever a
+-- professor's rating or rating count changes. Differencing two snapshots
+-- gives the average of the ratings received between them, which feeds the
+-- drift shown on instructor profiles and the recency signal in scoring.
+CREATE TABLE rmp_professor_history (
+    id          BIGSERIAL PRIMARY KEY,
+    legacy_id   INTEGER NOT NULL REFERENCES rmp_professors(legacy_id) ON DELETE CASCADE,
+    avg_rating  REAL,
+    num_ratings INTEGER NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_rmp_professor_history_legacy_recorded
+    ON rmp_professor_history (legacy_id, recorded_at DESC);
+
+-- Seed with the current values so drift has a baseline once history accrues.
+INSERT INTO rmp_professor_history (legacy_id, avg_rating, num_ratings, recorded_at)
+SELECT legacy_id, avg_rating, num_ratings, last_synced_at
+FROM rmp_professors;
@@ -0,0 +1,39 @@
+-- Grade distributions from UTSA's public (FOIA) releases, imported from CSV
+-- with `banner import-grades`. Releases report letter-grade counts per
+-- section; `avg_gpa` is computed at import from the +/- counts, which are
+-- then folded into the letter columns.
+CREATE TABLE grade_distributions (
+    id SERIAL PRIMARY KEY,
+    -- Section identification; `section` and `instructor_name` are '' when the
+    -- release aggregates above section level
+    term_code VARCHAR NOT NULL,
+    subject VARCHAR NOT NULL,
+    course_number VARCHAR NOT NULL,
+    section VARCHAR NOT NULL DEFAULT '',
+
+    -- Instructor (text from the release, linked after import)
+    instructor_name VARCHAR NOT NULL DEFAULT '',
+    instructor_id INTEGER REFERENCES instructors(id) ON DELETE SET NULL,
+
+    -- Grade counts
+    a_count INTEGER NOT NULL DEFAULT 0,
+    b_count INTEGER NOT NULL DEFAULT 0,
+    c_count INTEGER NOT NULL DEFAULT 0,
+    d_count INTEGER NOT NULL DEFAULT 0,
+    f_count INTEGER NOT NULL DEFAULT 0,
+    withdrawn_count INTEGER NOT NULL DEFAULT 0,
+    -- Pass/fail, incomplete, and other grades that carry no grade points
+    other_count INTEGER NOT NULL DEFAULT 0,
+    -- Students with an A-F grade, the denominator of `avg_gpa`
+    graded_count INTEGER NOT NULL DEFAULT 0,
+    avg_gpa REAL,
+
+    -- Metadata
+    source VARCHAR NOT NULL,
+    imported_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_grade_distribution UNIQUE (term_code, subject, course_number, section, instructor_name)
+);
+
+CREATE INDEX idx_grade_distributions_course ON grade_distributions (subject, course_number);
+CREATE INDEX idx_grade_distributions_instructor ON grade_distributions (instructor_id) WHERE instructor_id IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Watches created from the web can deliver to a webhook instead of a
+-- Discord DM. NULL keeps the DM behavior bot-created watches have always had.
+ALTER TABLE course_watches ADD COLUMN webhook_url TEXT;
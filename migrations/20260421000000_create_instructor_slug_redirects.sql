@@ -0,0 +1,14 @@
+-- Set when an instructor's display name changes after their slug was minted,
+-- so `POST /api/admin/instructors/rebuild-slugs` can regenerate it.
+ALTER TABLE instructors ADD COLUMN slug_stale BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Retired slugs, kept resolvable so old profile links redirect instead of 404.
+-- Rows point at the instructor rather than the replacement slug, so repeated
+-- renames always land on the current one.
+CREATE TABLE instructor_slug_redirects (
+    old_slug TEXT PRIMARY KEY,
+    instructor_id INTEGER NOT NULL REFERENCES instructors(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_instructor_slug_redirects_instructor ON instructor_slug_redirects (instructor_id);
@@ -0,0 +1,16 @@
+-- Per-user API keys for programmatic clients. Keys are shown once at
+-- creation; only a SHA-256 hash is stored. Requests carrying a key in
+-- `X-Api-Key` are rate limited per key rather than per IP.
+CREATE TABLE api_keys (
+    id SERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(discord_id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    -- Leading characters of the key, so owners can tell keys apart
+    key_prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_api_keys_user ON api_keys (user_id);
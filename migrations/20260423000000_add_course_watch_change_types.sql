@@ -0,0 +1,7 @@
+-- Watches can follow attribute and registration-restriction edits.
+ALTER TABLE course_watches DROP CONSTRAINT course_watches_watch_type_check;
+ALTER TABLE course_watches ADD CONSTRAINT course_watches_watch_type_check
+    CHECK (watch_type IN (
+        'seats_available', 'waitlist_open', 'any_change',
+        'attribute_change', 'restriction_change'
+    ));
@@ -0,0 +1,4 @@
+-- Prefix lookups on email local parts ("jane.doe") for directory search and
+-- identifier resolution.
+CREATE INDEX idx_instructors_email_local_part
+    ON instructors (LOWER(SPLIT_PART(email, '@', 1)) text_pattern_ops);
@@ -0,0 +1,34 @@
+-- Admin-managed subject code aliases for renamed departments.
+--
+-- Each row maps a retired code to the code that replaced it (e.g. STA -> STS).
+-- Chains are not allowed: a target is never itself an alias, so one lookup
+-- always reaches the current code.
+
+CREATE TABLE subject_aliases (
+    alias TEXT PRIMARY KEY,
+    subject TEXT NOT NULL CHECK (subject <> alias),
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_subject_aliases_subject ON subject_aliases (subject);
+
+-- Current code for a subject, or the input when it isn't an alias.
+CREATE FUNCTION canonical_subject(code TEXT) RETURNS TEXT
+LANGUAGE sql STABLE AS $$
+    SELECT COALESCE((SELECT subject FROM subject_aliases WHERE alias = code), code)
+$$;
+
+-- Every code connected to the inputs: their current codes plus all aliases
+-- of those codes. Used to match historical rows across renames.
+CREATE FUNCTION expand_subject_codes(codes TEXT[]) RETURNS TEXT[]
+LANGUAGE sql STABLE AS $$
+    WITH canon AS (
+        SELECT canonical_subject(c) AS subject FROM unnest(codes) AS c
+    )
+    SELECT ARRAY(
+        SELECT subject FROM canon
+        UNION
+        SELECT sa.alias FROM subject_aliases sa JOIN canon USING (subject)
+    )
+$$;
@@ -0,0 +1,7 @@
+-- Index documents now include subject and attribute descriptions (weight D).
+-- Existing rows are tagged with the old document shape; the startup backfill
+-- rebuilds each stale term in place, so search keeps serving the old rows
+-- until a term's new rows commit.
+
+ALTER TABLE course_search_index
+    ADD COLUMN document_version SMALLINT NOT NULL DEFAULT 1;
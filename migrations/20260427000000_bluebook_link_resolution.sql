@@ -0,0 +1,4 @@
+-- Record which admin approved, rejected, or assigned each BlueBook link.
+ALTER TABLE instructor_bluebook_links
+    ADD COLUMN resolved_by BIGINT REFERENCES users(discord_id),
+    ADD COLUMN resolved_at TIMESTAMPTZ;
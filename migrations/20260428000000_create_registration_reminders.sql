@@ -0,0 +1,25 @@
+-- Registration opening times from the academic calendar, one per student
+-- classification. Entered by admins; Banner doesn't expose them.
+CREATE TABLE registration_windows (
+    term_code VARCHAR(6) NOT NULL REFERENCES terms(code) ON DELETE CASCADE,
+    classification TEXT NOT NULL CHECK (classification IN (
+        'graduate', 'senior', 'junior', 'sophomore', 'freshman', 'open'
+    )),
+    opens_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (term_code, classification)
+);
+
+-- `/remindme` subscriptions: DM the user once their window opens.
+CREATE TABLE registration_reminders (
+    id SERIAL PRIMARY KEY,
+    discord_user_id BIGINT NOT NULL REFERENCES users(discord_id) ON DELETE CASCADE,
+    term_code VARCHAR(6) NOT NULL REFERENCES terms(code) ON DELETE CASCADE,
+    classification TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    notified_at TIMESTAMPTZ,
+    UNIQUE (discord_user_id, term_code, classification)
+);
+
+CREATE INDEX idx_registration_reminders_pending
+    ON registration_reminders(term_code, classification)
+    WHERE notified_at IS NULL;
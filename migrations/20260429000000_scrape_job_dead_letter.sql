@@ -0,0 +1,8 @@
+-- Jobs that exhaust their retries (or can't be parsed) are parked instead of
+-- deleted so admins can see why they failed and requeue them.
+ALTER TABLE scrape_jobs ADD COLUMN dead_lettered_at TIMESTAMPTZ;
+ALTER TABLE scrape_jobs ADD COLUMN last_error TEXT;
+
+CREATE INDEX idx_scrape_jobs_dead_letter
+    ON scrape_jobs(dead_lettered_at DESC)
+    WHERE dead_lettered_at IS NOT NULL;
@@ -0,0 +1,13 @@
+-- Temporary bans added from the admin API, enforced by the IP filter
+-- middleware ahead of rate limiting. Rows are kept after expiry for history.
+CREATE TABLE ip_bans (
+    id SERIAL PRIMARY KEY,
+    cidr TEXT NOT NULL,
+    reason TEXT,
+    created_by BIGINT REFERENCES users(discord_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    CONSTRAINT ip_bans_expiry_after_creation CHECK (expires_at > created_at)
+);
+
+CREATE INDEX idx_ip_bans_expires_at ON ip_bans (expires_at);
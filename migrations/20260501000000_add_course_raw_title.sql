@@ -0,0 +1,3 @@
+-- Titles are now title-cased at upsert; keep Banner's original alongside.
+-- NULL marks rows stored before casing, which the startup backfill normalizes.
+ALTER TABLE courses ADD COLUMN raw_title TEXT;
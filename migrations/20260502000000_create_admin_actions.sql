@@ -0,0 +1,20 @@
+-- Trail of mutating admin API calls, written by the admin audit middleware.
+-- The username is copied so entries stay readable after the user is deleted.
+CREATE TABLE admin_actions (
+    id BIGSERIAL PRIMARY KEY,
+    admin_id BIGINT REFERENCES users(discord_id) ON DELETE SET NULL,
+    admin_username TEXT NOT NULL,
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    -- Matched route template, e.g. /api/admin/users/{discord_id}/admin
+    route TEXT,
+    status SMALLINT NOT NULL,
+    -- Redacted request body
+    payload JSONB,
+    -- Redacted response body, for successful calls
+    result JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_admin_actions_created_at ON admin_actions (created_at DESC);
+CREATE INDEX idx_admin_actions_admin_id ON admin_actions (admin_id, created_at DESC);
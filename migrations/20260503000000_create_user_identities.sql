@@ -0,0 +1,22 @@
+-- Sign-in identities from each OAuth provider; a user can link several.
+CREATE TABLE user_identities (
+    provider TEXT NOT NULL,
+    -- The provider's stable account ID (Discord snowflake, OIDC `sub`)
+    subject TEXT NOT NULL,
+    user_id BIGINT NOT NULL REFERENCES users(discord_id) ON DELETE CASCADE,
+    email TEXT,
+    display_name TEXT,
+    linked_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_login_at TIMESTAMPTZ,
+    PRIMARY KEY (provider, subject),
+    UNIQUE (user_id, provider)
+);
+
+-- Users who first sign in with a provider other than Discord have no Discord
+-- ID to key them; they get a negative ID from this sequence instead.
+CREATE SEQUENCE users_local_id_seq AS BIGINT INCREMENT BY -1 MAXVALUE -1 START WITH -1;
+
+INSERT INTO user_identities (provider, subject, user_id, display_name)
+SELECT 'discord', discord_id::text, discord_id, discord_username
+FROM users
+WHERE discord_id > 0;
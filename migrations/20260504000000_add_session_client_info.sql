@@ -0,0 +1,9 @@
+-- Let users recognize and revoke their own sessions.
+-- `public_id` identifies a session in the API; the token in `id` never leaves the cookie.
+-- Client details are NULL for sessions created before this migration.
+ALTER TABLE user_sessions
+    ADD COLUMN public_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    ADD COLUMN user_agent TEXT,
+    ADD COLUMN ip_address TEXT;
+
+CREATE UNIQUE INDEX idx_user_sessions_public_id ON user_sessions(public_id);
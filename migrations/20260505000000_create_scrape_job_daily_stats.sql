@@ -0,0 +1,20 @@
+-- Per-day rollups of scrape_job_results rows older than the retention window.
+-- The originals are deleted once compacted, so these are the only record of
+-- older scrape activity.
+CREATE TABLE scrape_job_daily_stats (
+    day                 DATE NOT NULL,
+    target_type         target_type NOT NULL,
+
+    runs                INT NOT NULL,
+    successes           INT NOT NULL,
+    failures            INT NOT NULL,
+    total_duration_ms   BIGINT NOT NULL,
+
+    courses_fetched     BIGINT NOT NULL DEFAULT 0,
+    courses_changed     BIGINT NOT NULL DEFAULT 0,
+    courses_unchanged   BIGINT NOT NULL DEFAULT 0,
+    audits_generated    BIGINT NOT NULL DEFAULT 0,
+    metrics_generated   BIGINT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (day, target_type)
+);
@@ -0,0 +1,18 @@
+-- Precomputed "related instructors", rebuilt daily by the scheduler.
+-- Each instructor keeps its top candidates ranked by a blend of shared
+-- subjects, co-taught sections and rating similarity.
+CREATE TABLE instructor_related (
+    instructor_id       INT NOT NULL REFERENCES instructors(id) ON DELETE CASCADE,
+    related_id          INT NOT NULL REFERENCES instructors(id) ON DELETE CASCADE,
+    rank                SMALLINT NOT NULL,
+    score               REAL NOT NULL,
+    shared_subjects     TEXT[] NOT NULL,
+    co_taught_sections  INT NOT NULL,
+    -- 0..1, NULL when either instructor is unrated
+    rating_similarity   REAL,
+    computed_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (instructor_id, related_id)
+);
+
+CREATE INDEX idx_instructor_related_rank ON instructor_related (instructor_id, rank);
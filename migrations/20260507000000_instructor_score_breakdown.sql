@@ -0,0 +1,12 @@
+-- Per-source breakdown of each composite score, so the instructor profile can
+-- explain how RMP, BlueBook, the prior, and grading leniency combined.
+-- Weighted values are after time decay; see src/data/scoring.rs.
+ALTER TABLE instructor_scores
+    ADD COLUMN rmp_weighted       REAL,
+    ADD COLUMN rmp_weighted_count REAL NOT NULL DEFAULT 0,
+    ADD COLUMN rmp_share          REAL NOT NULL DEFAULT 0,
+    ADD COLUMN bb_weighted        REAL,
+    ADD COLUMN bb_weighted_count  REAL NOT NULL DEFAULT 0,
+    ADD COLUMN bb_share           REAL NOT NULL DEFAULT 0,
+    ADD COLUMN prior_share        REAL NOT NULL DEFAULT 1,
+    ADD COLUMN grade_shift        REAL NOT NULL DEFAULT 0;
@@ -0,0 +1,10 @@
+-- Incremental score recomputes look for inputs changed after a watermark.
+-- BlueBook evaluations are re-upserted on every scrape, so `scraped_at` can't
+-- tell a changed evaluation from an unchanged one; `updated_at` only moves
+-- when the instructor rating or response count does.
+ALTER TABLE bluebook_evaluations
+    ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE INDEX idx_bluebook_eval_updated_at ON bluebook_evaluations (updated_at);
+CREATE INDEX idx_rmp_professor_history_recorded_at ON rmp_professor_history (recorded_at);
+CREATE INDEX idx_rmp_reviews_scraped_at ON rmp_reviews (scraped_at);
@@ -0,0 +1,93 @@
+-- Building and room codes on denormalized meetings, so the schedule cache can
+-- answer per-room occupancy without parsing `meeting_times`.
+
+ALTER TABLE course_meetings
+    ADD COLUMN building TEXT,
+    ADD COLUMN room TEXT;
+
+CREATE INDEX idx_course_meetings_room ON course_meetings(building, room)
+    WHERE building IS NOT NULL AND room IS NOT NULL;
+
+-- Existing rows can't be matched back to their JSONB entry, so rebuild them
+-- with the same extraction as the original backfill.
+DELETE FROM course_meetings;
+
+INSERT INTO course_meetings (
+    course_id, day_bits, begin_minutes, end_minutes, start_date, end_date, building, room
+)
+SELECT
+    c.id,
+    COALESCE(
+        (SELECT bit_or(
+            CASE d
+                WHEN 'monday' THEN 1 WHEN 'tuesday' THEN 2 WHEN 'wednesday' THEN 4
+                WHEN 'thursday' THEN 8 WHEN 'friday' THEN 16 WHEN 'saturday' THEN 32
+                WHEN 'sunday' THEN 64
+            END
+        ) FROM jsonb_array_elements_text(mt.val->'days') AS d),
+        (CASE WHEN (mt.val->>'monday')::boolean THEN 1 ELSE 0 END |
+         CASE WHEN (mt.val->>'tuesday')::boolean THEN 2 ELSE 0 END |
+         CASE WHEN (mt.val->>'wednesday')::boolean THEN 4 ELSE 0 END |
+         CASE WHEN (mt.val->>'thursday')::boolean THEN 8 ELSE 0 END |
+         CASE WHEN (mt.val->>'friday')::boolean THEN 16 ELSE 0 END |
+         CASE WHEN (mt.val->>'saturday')::boolean THEN 32 ELSE 0 END |
+         CASE WHEN (mt.val->>'sunday')::boolean THEN 64 ELSE 0 END)
+    )::smallint,
+    CASE
+        WHEN mt.val->>'begin_time' IS NOT NULL THEN
+            (LEFT(mt.val->>'begin_time', 2)::int * 60 + RIGHT(mt.val->>'begin_time', 2)::int)::smallint
+        ELSE
+            (SPLIT_PART(mt.val->'timeRange'->>'start', ':', 1)::int * 60 +
+             SPLIT_PART(mt.val->'timeRange'->>'start', ':', 2)::int)::smallint
+    END,
+    CASE
+        WHEN mt.val->>'end_time' IS NOT NULL THEN
+            (LEFT(mt.val->>'end_time', 2)::int * 60 + RIGHT(mt.val->>'end_time', 2)::int)::smallint
+        ELSE
+            (SPLIT_PART(mt.val->'timeRange'->>'end', ':', 1)::int * 60 +
+             SPLIT_PART(mt.val->'timeRange'->>'end', ':', 2)::int)::smallint
+    END,
+    CASE
+        WHEN mt.val->>'start_date' IS NOT NULL THEN TO_DATE(mt.val->>'start_date', 'MM/DD/YYYY')
+        ELSE (mt.val->'dateRange'->>'start')::date
+    END,
+    CASE
+        WHEN mt.val->>'end_date' IS NOT NULL THEN TO_DATE(mt.val->>'end_date', 'MM/DD/YYYY')
+        ELSE (mt.val->'dateRange'->>'end')::date
+    END,
+    NULLIF(TRIM(COALESCE(mt.val->'location'->>'building', mt.val->>'building')), ''),
+    NULLIF(TRIM(COALESCE(mt.val->'location'->>'room', mt.val->>'room')), '')
+FROM courses c
+CROSS JOIN LATERAL jsonb_array_elements(c.meeting_times) AS mt(val)
+WHERE
+    -- Must have time data
+    COALESCE(mt.val->>'begin_time', mt.val->'timeRange'->>'start') IS NOT NULL
+    AND COALESCE(mt.val->>'end_time', mt.val->'timeRange'->>'end') IS NOT NULL
+    -- Must have at least one day
+    AND COALESCE(
+        (SELECT bit_or(
+            CASE d
+                WHEN 'monday' THEN 1 WHEN 'tuesday' THEN 2 WHEN 'wednesday' THEN 4
+                WHEN 'thursday' THEN 8 WHEN 'friday' THEN 16 WHEN 'saturday' THEN 32
+                WHEN 'sunday' THEN 64
+            END
+        ) FROM jsonb_array_elements_text(mt.val->'days') AS d),
+        (CASE WHEN (mt.val->>'monday')::boolean THEN 1 ELSE 0 END |
+         CASE WHEN (mt.val->>'tuesday')::boolean THEN 2 ELSE 0 END |
+         CASE WHEN (mt.val->>'wednesday')::boolean THEN 4 ELSE 0 END |
+         CASE WHEN (mt.val->>'thursday')::boolean THEN 8 ELSE 0 END |
+         CASE WHEN (mt.val->>'friday')::boolean THEN 16 ELSE 0 END |
+         CASE WHEN (mt.val->>'saturday')::boolean THEN 32 ELSE 0 END |
+         CASE WHEN (mt.val->>'sunday')::boolean THEN 64 ELSE 0 END)
+    ) > 0
+    -- Time must be valid (end > begin)
+    AND CASE
+        WHEN mt.val->>'begin_time' IS NOT NULL THEN
+            (LEFT(mt.val->>'end_time', 2)::int * 60 + RIGHT(mt.val->>'end_time', 2)::int) >
+            (LEFT(mt.val->>'begin_time', 2)::int * 60 + RIGHT(mt.val->>'begin_time', 2)::int)
+        ELSE
+            (SPLIT_PART(mt.val->'timeRange'->>'end', ':', 1)::int * 60 +
+             SPLIT_PART(mt.val->'timeRange'->>'end', ':', 2)::int) >
+            (SPLIT_PART(mt.val->'timeRange'->>'start', ':', 1)::int * 60 +
+             SPLIT_PART(mt.val->'timeRange'->>'start', ':', 2)::int)
+    END;
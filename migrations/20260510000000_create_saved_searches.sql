@@ -0,0 +1,18 @@
+-- Course searches users save from the web, stored as the search endpoint's
+-- query parameters. With `notify` set, sections that start matching after a
+-- subject scrape are sent to the user as a Discord DM.
+CREATE TABLE saved_searches (
+    id SERIAL PRIMARY KEY,
+    discord_user_id BIGINT NOT NULL REFERENCES users(discord_id) ON DELETE CASCADE,
+    name TEXT NOT NULL CHECK (length(name) BETWEEN 1 AND 100),
+    term_code VARCHAR(6) NOT NULL,
+    filters JSONB NOT NULL,
+    notify BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Sections matching at the last evaluation; anything else is new.
+    matched_course_ids INT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    notified_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_saved_searches_user ON saved_searches(discord_user_id);
+CREATE INDEX idx_saved_searches_notify_term ON saved_searches(term_code) WHERE notify = TRUE;
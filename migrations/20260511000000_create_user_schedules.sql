@@ -0,0 +1,10 @@
+-- The sections a signed-in user has planned for a term, synced across
+-- devices. CRNs are stored rather than course IDs so a plan survives a
+-- section being dropped and re-added by a later scrape.
+CREATE TABLE user_schedules (
+    discord_user_id BIGINT NOT NULL REFERENCES users(discord_id) ON DELETE CASCADE,
+    term_code VARCHAR(6) NOT NULL,
+    crns TEXT[] NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (discord_user_id, term_code)
+);
@@ -0,0 +1,24 @@
+-- Endpoints subscribed to course events. Deliveries are queued in
+-- notification_outbox, signed with the endpoint's secret at send time.
+CREATE TABLE webhooks (
+    id          SERIAL PRIMARY KEY,
+    url         TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    -- HMAC-SHA256 key shared with the receiver.
+    secret      TEXT NOT NULL,
+    -- Subscribed event types, e.g. 'course.created'.
+    event_types TEXT[] NOT NULL,
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by  BIGINT REFERENCES users(discord_id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE notification_outbox
+    DROP CONSTRAINT notification_outbox_channel_check,
+    ADD CONSTRAINT notification_outbox_channel_check
+        CHECK (channel IN ('discord_dm', 'webhook', 'email', 'webhook_subscription'));
+
+-- Per-endpoint delivery log.
+CREATE INDEX idx_notification_outbox_webhook
+    ON notification_outbox (((payload->>'webhook_id')::int), created_at DESC)
+    WHERE channel = 'webhook_subscription';
@@ -0,0 +1,2 @@
+-- Fields changed by an admin call, for handlers that report the prior state.
+ALTER TABLE admin_actions ADD COLUMN changes JSONB;
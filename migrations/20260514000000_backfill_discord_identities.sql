@@ -0,0 +1,8 @@
+-- Users created by bot commands or as the seed admin had no identity row.
+-- Discord DMs are now addressed through the user's `discord` identity, so
+-- record one for every user keyed by a Discord snowflake.
+INSERT INTO user_identities (provider, subject, user_id, display_name)
+SELECT 'discord', discord_id::text, discord_id, discord_username
+FROM users
+WHERE discord_id > 0
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,14 @@
+-- Users get their own ID instead of being keyed by a Discord snowflake; a
+-- Discord account is now only one linked identity. Existing users keep their
+-- IDs. New users of every provider draw negative IDs from the sequence, so
+-- they never collide with the snowflakes already in use.
+ALTER TABLE users RENAME COLUMN discord_id TO id;
+ALTER SEQUENCE users_local_id_seq RENAME TO users_id_seq;
+ALTER SEQUENCE users_id_seq OWNED BY users.id;
+ALTER TABLE users ALTER COLUMN id SET DEFAULT nextval('users_id_seq');
+
+ALTER TABLE course_watches RENAME COLUMN discord_user_id TO user_id;
+ALTER TABLE registration_reminders RENAME COLUMN discord_user_id TO user_id;
+ALTER TABLE saved_searches RENAME COLUMN discord_user_id TO user_id;
+ALTER TABLE user_schedules RENAME COLUMN discord_user_id TO user_id;
+ALTER TABLE user_feedback RENAME COLUMN reporter_discord_id TO reporter_id;
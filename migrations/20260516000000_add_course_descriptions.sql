@@ -0,0 +1,4 @@
+-- Catalog descriptions fetched by the detail enrichment job, indexed for
+-- course text search.
+
+ALTER TABLE courses ADD COLUMN description TEXT;
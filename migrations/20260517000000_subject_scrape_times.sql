@@ -0,0 +1,16 @@
+-- Latest successful scrape per term subject, kept current as results are
+-- logged, so status pages don't scan the whole result history and the time
+-- survives result compaction.
+CREATE TABLE subject_scrape_times (
+    term_code       VARCHAR NOT NULL,
+    subject_code    VARCHAR NOT NULL,
+    last_scraped_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (term_code, subject_code)
+);
+
+INSERT INTO subject_scrape_times (term_code, subject_code, last_scraped_at)
+SELECT payload->>'term', payload->>'subject', MAX(completed_at)
+FROM scrape_job_results
+WHERE target_type = 'Subject' AND success
+  AND payload->>'term' IS NOT NULL AND payload->>'subject' IS NOT NULL
+GROUP BY 1, 2;
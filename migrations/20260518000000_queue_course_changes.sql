@@ -0,0 +1,6 @@
+-- Course writes queue their committed changes in the same transaction, and
+-- the dispatcher fans them out to watch alerts and webhook deliveries.
+ALTER TABLE notification_outbox
+    DROP CONSTRAINT notification_outbox_channel_check,
+    ADD CONSTRAINT notification_outbox_channel_check
+        CHECK (channel IN ('discord_dm', 'webhook', 'email', 'webhook_subscription', 'course_changes'));
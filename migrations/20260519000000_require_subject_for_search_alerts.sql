@@ -0,0 +1,6 @@
+-- Saved search alerts now need a subject filter; searches without one would
+-- be re-run after every subject scrape.
+UPDATE saved_searches
+SET notify = FALSE
+WHERE notify
+  AND COALESCE(jsonb_array_length(filters->'subject'), 0) = 0;
@@ -0,0 +1,4 @@
+-- The sparkline rollup reads each course's metrics inside its window plus the
+-- last snapshot before it, instead of scanning the whole history.
+CREATE INDEX IF NOT EXISTS idx_course_metrics_course_timestamp
+    ON course_metrics (course_id, timestamp DESC);
@@ -0,0 +1,42 @@
+-- Queued course changes now carry the typed events themselves instead of
+-- audit IDs to re-read. Rewrite messages still waiting in the old shape.
+UPDATE notification_outbox o
+SET payload = jsonb_build_object(
+    'kind', 'course_changes',
+    'events', COALESCE((
+        SELECT jsonb_agg(
+            jsonb_build_object(
+                'auditId', a.id,
+                'at', a.timestamp,
+                'course', jsonb_build_object(
+                    'id', c.id,
+                    'term', c.term_code,
+                    'crn', c.crn,
+                    'subject', c.subject,
+                    'courseNumber', c.course_number,
+                    'title', c.title
+                ),
+                'change', CASE
+                    WHEN a.field_changed = 'initial' THEN
+                        jsonb_build_object('kind', 'created', 'snapshot', a.new_value)
+                    WHEN a.field_changed IN ('enrollment', 'max_enrollment', 'wait_count', 'wait_capacity') THEN
+                        jsonb_build_object('kind', 'seats', 'field', a.field_changed,
+                                           'old', a.old_value, 'new', a.new_value)
+                    WHEN a.field_changed = 'instructors' THEN
+                        jsonb_build_object('kind', 'instructors',
+                                           'old', COALESCE(a.old_value, '[]'::jsonb), 'new', a.new_value)
+                    ELSE
+                        jsonb_build_object('kind', 'field', 'field', a.field_changed,
+                                           'old', a.old_value, 'new', a.new_value)
+                END
+            )
+            ORDER BY a.id
+        )
+        FROM course_audits a
+        JOIN courses c ON c.id = a.course_id
+        WHERE a.id IN (SELECT jsonb_array_elements_text(o.payload -> 'audit_ids')::int)
+    ), '[]'::jsonb)
+)
+WHERE o.channel = 'course_changes'
+  AND o.status = 'pending'
+  AND o.payload ? 'audit_ids';
@@ -0,0 +1,14 @@
+-- Attribute and restriction watches that follow every section of a subject
+-- in a term, for advisors monitoring their department.
+CREATE TABLE department_watches (
+    id SERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    term_code VARCHAR NOT NULL,
+    subject VARCHAR NOT NULL,
+    watch_type TEXT NOT NULL CHECK (watch_type IN ('attribute_change', 'restriction_change')),
+    webhook_url TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, term_code, subject, watch_type)
+);
+
+CREATE INDEX idx_department_watches_scope ON department_watches(term_code, subject);
@@ -0,0 +1,4 @@
+-- Let first sign-in claim the identity row before its user exists, so two
+-- concurrent first logins resolve to one user instead of a unique violation.
+ALTER TABLE user_identities
+    ALTER CONSTRAINT user_identities_user_id_fkey DEFERRABLE INITIALLY IMMEDIATE;
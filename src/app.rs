@@ -1,19 +1,32 @@
 use crate::banner::BannerApi;
+use crate::bot::AdminAccess;
+use crate::bot::registration::RegistrationPlan;
 use crate::cli::ServiceName;
-use crate::config::Config;
+use crate::config::{self, Config};
+use crate::metrics::Metrics;
 use crate::scraper::ScraperService;
 use crate::scraper::scheduler::KV_TERM_SYNC;
+use crate::secrets;
 use crate::services::bot::BotService;
 use crate::services::manager::ServiceManager;
 use crate::services::notifications::NotificationService;
+use crate::services::outbox::OutboxService;
 use crate::services::web::WebService;
 use crate::state::AppState;
 use crate::utils::fmt_duration;
 use crate::web::auth::AuthConfig;
+use crate::web::auth::providers::OAuthProvider;
+use crate::web::ip_filter::IpFilter;
+use crate::web::middleware::client_ip::ProxyTrust;
+use crate::web::middleware::connection_limit::ConnectionLimiter;
 use anyhow::Context;
 use chrono::Utc;
 use figment::value::UncasedStr;
-use figment::{Figment, providers::Env};
+use figment::{
+    Figment,
+    providers::{Env, Serialized},
+};
+use secrecy::ExposeSecret;
 use sqlx::ConnectOptions;
 use sqlx::postgres::PgPoolOptions;
 use std::process::ExitCode;
@@ -44,62 +57,35 @@ impl App {
                     k.into()
                 }
             }))
+            .merge(Serialized::defaults(secrets::file_references(
+                config::SECRET_VARS,
+            )))
             .extract()
             .context("Failed to load config")?;
 
-        // Check if the database URL is via private networking
-        let is_private = config.database_url.contains("railway.internal");
-        let slow_threshold = Duration::from_millis(if is_private { 200 } else { 500 });
+        let rate_limit_quotas = config.rate_limit_quotas();
+        rate_limit_quotas
+            .validate()
+            .map_err(|e| anyhow::anyhow!("Invalid rate limit config: {e}"))?;
+        info!(profile = %config.rate_limit_profile, "Rate limit profile selected");
 
-        // Create database connection pool
-        let connect_options = sqlx::postgres::PgConnectOptions::from_str(&config.database_url)
-            .context("Failed to parse database URL")?
-            .log_statements(tracing::log::LevelFilter::Debug)
-            .log_slow_statements(tracing::log::LevelFilter::Warn, Duration::from_secs(1));
-
-        let db_pool = PgPoolOptions::new()
-            .min_connections(0)
-            .max_connections(4)
-            .acquire_slow_threshold(slow_threshold)
-            .acquire_timeout(Duration::from_secs(4))
-            .idle_timeout(Duration::from_secs(60 * 2))
-            .max_lifetime(Duration::from_secs(60 * 30))
-            .connect_with(connect_options)
-            .await
-            .context("Failed to create database pool")?;
-
-        info!(
-            is_private = is_private,
-            min_connections = 0,
-            max_connections = 4,
-            acquire_timeout = "4s",
-            idle_timeout = "2m",
-            max_lifetime = "30m",
-            acquire_slow_threshold = fmt_duration(slow_threshold),
-            "database pool established"
-        );
-
-        // Run database migrations
-        info!("Checking database migrations...");
-        sqlx::migrate!("./migrations")
-            .run(&db_pool)
-            .await
-            .context("Failed to run database migrations")?;
-        info!("Database migrations up to date");
+        let db_pool = Self::connect_database(&config).await?;
 
         // Create BannerApi early so we can use it for term sync
-        let banner_api = BannerApi::new_with_config(
+        let banner_api = BannerApi::new_with_limits(
             config.banner_base_url.clone(),
             config.rate_limiting.clone(),
+            config.banner_max_concurrency,
         )
         .context("Failed to create BannerApi")?;
         let banner_api_arc = Arc::new(banner_api);
 
         // Run startup DB operations in parallel
-        let (term_result, name_result, slug_result) = tokio::join!(
+        let (term_result, name_result, slug_result, title_result) = tokio::join!(
             Self::sync_terms_on_startup(&db_pool, &banner_api_arc),
             crate::data::names::backfill_instructor_names(&db_pool),
             crate::data::instructors::backfill_instructor_slugs(&db_pool),
+            crate::data::titles::backfill_course_titles(&db_pool),
         );
 
         // Persist term sync timestamp so the scheduler doesn't repeat this on its first cycle.
@@ -132,8 +118,12 @@ impl App {
             Err(e) => warn!(error = ?e, "Failed to backfill instructor slugs (non-fatal)"),
         }
 
+        if let Err(e) = title_result {
+            warn!(error = ?e, "Failed to backfill course titles (non-fatal)");
+        }
+
         // Compute instructor scores from RMP + BlueBook data
-        match crate::data::scoring::recompute_all_scores(&db_pool).await {
+        match crate::data::scoring::recompute_all_scores(&db_pool, &config.scoring_decay()).await {
             Ok(0) => info!("Computed instructor scores (none found - no RMP or BlueBook data)"),
             Ok(n) => info!(count = n, "Computed instructor scores"),
             Err(e) => warn!(error = ?e, "Failed to compute instructor scores (non-fatal)"),
@@ -151,11 +141,25 @@ impl App {
             bluebook_sync_notify,
             bluebook_force_flag.clone(),
             config.public_origin.clone(),
+            ConnectionLimiter::new(
+                config.max_stream_connections_per_ip,
+                config.max_stream_connections,
+            ),
+            config.stream_heartbeat_policy(),
+            ProxyTrust::new(config.trusted_proxies.clone(), config.trusted_proxy_hops),
+            IpFilter::new(config.trusted_cidrs.clone(), config.blocked_cidrs.clone()),
+            config.search_weights(),
+            config.attribution_config(),
+            config.scrape_history_retention(),
+            config.scoring_decay(),
+            &rate_limit_quotas,
+            Metrics::new(config.metrics_token.as_ref().map(|t| t.expose_secret())),
         );
 
-        // Load reference cache and schedule cache in parallel
+        // Load reference cache, schedule cache, and suggest index in parallel
         let schedule_cache = app_state.schedule_cache.clone();
-        let (ref_result, sched_result) = tokio::join!(
+        let suggest_index = app_state.suggest_index.clone();
+        let (ref_result, sched_result, suggest_result) = tokio::join!(
             async {
                 let entries = crate::data::reference::get_all(&db_pool).await?;
                 let count = entries.len();
@@ -165,6 +169,7 @@ impl App {
                 Ok::<_, anyhow::Error>(())
             },
             schedule_cache.load(),
+            suggest_index.load(),
         );
         if let Err(e) = ref_result {
             info!(error = ?e, "Could not load reference cache on startup (may be empty)");
@@ -172,6 +177,19 @@ impl App {
         if let Err(e) = sched_result {
             info!(error = ?e, "Could not load schedule cache on startup (may be empty)");
         }
+        if let Err(e) = suggest_result {
+            info!(error = ?e, "Could not load suggest index on startup (may be empty)");
+        }
+
+        if let Err(e) = app_state.crawler.load(&db_pool).await {
+            warn!(error = ?e, "Failed to load crawler config, using defaults");
+        }
+        if let Err(e) = app_state.maintenance.load(&db_pool).await {
+            warn!(error = ?e, "Failed to load maintenance config, assuming disabled");
+        }
+        if let Err(e) = app_state.ip_filter.load_bans(&db_pool).await {
+            warn!(error = ?e, "Failed to load IP bans");
+        }
 
         // Seed the initial admin user if configured
         if let Some(admin_id) = config.admin_discord_id {
@@ -198,13 +216,82 @@ impl App {
         })
     }
 
+    /// Create the database pool and bring the schema up to date.
+    pub async fn connect_database(config: &Config) -> Result<sqlx::PgPool, anyhow::Error> {
+        // Check if the database URL is via private networking
+        let is_private = config.database_url.contains("railway.internal");
+        let slow_threshold = Duration::from_millis(if is_private { 200 } else { 500 });
+
+        // Create database connection pool
+        let connect_options = sqlx::postgres::PgConnectOptions::from_str(&config.database_url)
+            .context("Failed to parse database URL")?
+            .log_statements(tracing::log::LevelFilter::Debug)
+            .log_slow_statements(tracing::log::LevelFilter::Warn, Duration::from_secs(1));
+
+        let db_pool = PgPoolOptions::new()
+            .min_connections(0)
+            .max_connections(4)
+            .acquire_slow_threshold(slow_threshold)
+            .acquire_timeout(Duration::from_secs(4))
+            .idle_timeout(Duration::from_secs(60 * 2))
+            .max_lifetime(Duration::from_secs(60 * 30))
+            .connect_with(connect_options)
+            .await
+            .context("Failed to create database pool")?;
+
+        info!(
+            is_private = is_private,
+            min_connections = 0,
+            max_connections = 4,
+            acquire_timeout = "4s",
+            idle_timeout = "2m",
+            max_lifetime = "30m",
+            acquire_slow_threshold = fmt_duration(slow_threshold),
+            "database pool established"
+        );
+
+        // Run database migrations
+        info!("Checking database migrations...");
+        crate::data::migrations::run(&db_pool).await?;
+        info!("Database migrations up to date");
+
+        Ok(db_pool)
+    }
+
+    /// Discord plus whichever optional sign-in providers are fully configured.
+    fn oauth_providers(&self) -> Vec<OAuthProvider> {
+        let config = &self.config;
+        let mut providers = vec![OAuthProvider::discord(
+            config.discord_client_id.clone(),
+            config.discord_client_secret.clone(),
+        )];
+        match (&config.google_client_id, &config.google_client_secret) {
+            (Some(id), Some(secret)) => {
+                providers.push(OAuthProvider::google(id.clone(), secret.clone()))
+            }
+            (Some(_), None) => warn!("GOOGLE_CLIENT_ID is set without GOOGLE_CLIENT_SECRET"),
+            _ => {}
+        }
+        match (&config.microsoft_client_id, &config.microsoft_client_secret) {
+            (Some(id), Some(secret)) => providers.push(OAuthProvider::microsoft(
+                id.clone(),
+                secret.clone(),
+                &config.microsoft_tenant,
+            )),
+            (Some(_), None) => {
+                warn!("MICROSOFT_CLIENT_ID is set without MICROSOFT_CLIENT_SECRET")
+            }
+            _ => {}
+        }
+        providers
+    }
+
     /// Setup and register services based on enabled service list
     pub fn setup_services(&mut self, services: &[ServiceName]) -> Result<(), anyhow::Error> {
         // Register enabled services with the manager
         if services.contains(&ServiceName::Web) {
             let auth_config = AuthConfig {
-                client_id: self.config.discord_client_id.clone(),
-                client_secret: self.config.discord_client_secret.clone(),
+                providers: self.oauth_providers(),
                 redirect_base: self.config.discord_redirect_uri.clone(),
             };
             let web_service = Box::new(WebService::new(
@@ -225,6 +312,12 @@ impl App {
                 self.app_state.events.clone(),
                 self.app_state.bluebook_sync_notify.clone(),
                 self.app_state.bluebook_force_flag.clone(),
+                self.app_state.sync_requests.clone(),
+                self.app_state.metrics.clone(),
+                self.config.scrape_retry_policy(),
+                self.config.scrape_pool_config(),
+                self.config.registration_calendar(),
+                self.config.scoring_decay(),
             ));
             self.service_manager
                 .register_service(ServiceName::Scraper.as_str(), scraper_service);
@@ -247,24 +340,43 @@ impl App {
         let (status_shutdown_tx, status_shutdown_rx) = broadcast::channel(1);
         let status_task_handle = Arc::new(Mutex::new(None));
 
-        // Build a standalone Discord HTTP client for the notification dispatcher.
-        // This avoids coupling the dispatcher to BotService internals while
-        // sharing the same bot token.
-        let discord_http = Arc::new(serenity::http::Http::new(&self.config.bot_token));
-
         let notification_service = Box::new(NotificationService::new(
             self.db_pool.clone(),
             self.app_state.events.clone(),
-            discord_http,
             self.config.public_origin.clone(),
         ));
 
         self.service_manager
             .register_service("notifications", notification_service);
 
+        // Build a standalone Discord HTTP client for the outbox dispatcher.
+        // This avoids coupling delivery to BotService internals while
+        // sharing the same bot token.
+        let discord_http = Arc::new(serenity::http::Http::new(
+            self.config.bot_token.expose_secret(),
+        ));
+
+        let outbox_service = Box::new(OutboxService::new(
+            self.db_pool.clone(),
+            discord_http,
+            self.config.email_relay_url.clone(),
+            self.config.public_origin.clone(),
+        ));
+
+        self.service_manager
+            .register_service("outbox", outbox_service);
+
         let bot_service = Box::new(BotService::new(
             self.config.bot_token.clone(),
-            self.config.bot_target_guild,
+            RegistrationPlan {
+                target_guild: self.config.bot_target_guild,
+                extra_guilds: self.config.bot_command_guilds.clone(),
+                staged: self.config.bot_command_staged,
+            },
+            AdminAccess {
+                user_ids: self.config.bot_admin_user_ids.clone(),
+                role_ids: self.config.bot_admin_role_ids.clone(),
+            },
             self.app_state.clone(),
             status_task_handle,
             status_shutdown_tx,
@@ -5,26 +5,35 @@ use std::sync::Arc;
 
 use crate::banner::{
     SessionPool,
+    circuit::BannerCircuit,
     errors::BannerApiError,
     json::parse_json_with_context,
-    middleware::{BannerRateLimiter, LoggingMiddleware, RateLimitMiddleware},
+    middleware::{
+        BannerRateLimiter, CircuitBreakerMiddleware, ConcurrencyLimitMiddleware, LoggingMiddleware,
+        RateLimitMiddleware, ThrottleMiddleware,
+    },
     models::*,
     nonce,
     query::SearchQuery,
     util::user_agent,
 };
 use crate::config::RateLimitingConfig;
+use crate::parse_pool::ParsePool;
 use anyhow::{Context, Result, anyhow};
 use http::HeaderValue;
 use reqwest::Client;
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use tracing::debug;
 
+/// Requests allowed in flight at once when no cap is configured.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
 /// Main Banner API client.
 pub struct BannerApi {
     pub sessions: SessionPool,
     http: ClientWithMiddleware,
     base_url: String,
+    circuit: Arc<BannerCircuit>,
 }
 
 impl BannerApi {
@@ -32,8 +41,19 @@ impl BannerApi {
     pub fn new_with_config(
         base_url: String,
         rate_limit_config: RateLimitingConfig,
+    ) -> Result<Self> {
+        Self::new_with_limits(base_url, rate_limit_config, DEFAULT_MAX_CONCURRENCY)
+    }
+
+    /// Creates a new Banner API client that also caps in-flight requests at
+    /// `max_concurrency`, across every caller sharing it.
+    pub fn new_with_limits(
+        base_url: String,
+        rate_limit_config: RateLimitingConfig,
+        max_concurrency: usize,
     ) -> Result<Self> {
         let rate_limiter = Arc::new(BannerRateLimiter::new(rate_limit_config));
+        let circuit = Arc::new(BannerCircuit::new());
 
         let http = ClientBuilder::new(
             Client::builder()
@@ -46,16 +66,25 @@ impl BannerApi {
                 .build()
                 .context("Failed to create HTTP client")?,
         )
+        .with(CircuitBreakerMiddleware::new(circuit.clone()))
+        .with(ThrottleMiddleware)
         .with(LoggingMiddleware)
         .with(RateLimitMiddleware::new(rate_limiter.clone()))
+        .with(ConcurrencyLimitMiddleware::new(max_concurrency))
         .build();
 
         Ok(Self {
             sessions: SessionPool::new(http.clone(), base_url.clone()),
             http,
             base_url,
+            circuit,
         })
     }
+
+    /// Circuit breaker shared by every request this client sends.
+    pub fn circuit(&self) -> &BannerCircuit {
+        &self.circuit
+    }
     /// Validates offset parameter for search methods.
     fn validate_offset(offset: i32) -> Result<()> {
         if offset <= 0 {
@@ -314,6 +343,132 @@ impl BannerApi {
             .collect())
     }
 
+    /// Fetches an HTML fragment from one of the class detail popup endpoints.
+    async fn get_detail_fragment(&self, endpoint: &str, term: &str, crn: &str) -> Result<String> {
+        let url = format!("{}/searchResults/{}", self.base_url, endpoint);
+        let params = [("term", term), ("courseReferenceNumber", crn)];
+
+        let response = self
+            .http
+            .post(&url)
+            .form(&params)
+            .send()
+            .await
+            .with_context(|| format!("Failed to get {endpoint}"))?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get {}: {}", endpoint, response.status()));
+        }
+
+        response
+            .text()
+            .await
+            .with_context(|| format!("Failed to read {endpoint} body"))
+    }
+
+    /// Retrieves live seat and waitlist counts for a section.
+    pub async fn get_enrollment_info(
+        &self,
+        term: &str,
+        crn: &str,
+    ) -> Result<Option<EnrollmentInfo>> {
+        let html = self
+            .get_detail_fragment("getEnrollmentInfo", term, crn)
+            .await?;
+        ParsePool::global()
+            .run(move || parse_enrollment_info(&html))
+            .await
+    }
+
+    /// Retrieves registration restrictions (level, major, classification, ...) for a section.
+    pub async fn get_restrictions(&self, term: &str, crn: &str) -> Result<Vec<Restriction>> {
+        let html = self
+            .get_detail_fragment("getRestrictions", term, crn)
+            .await?;
+        ParsePool::global()
+            .run(move || parse_restrictions(&html))
+            .await
+    }
+
+    /// Retrieves corequisite courses for a section.
+    pub async fn get_corequisites(&self, term: &str, crn: &str) -> Result<Vec<Corequisite>> {
+        let html = self
+            .get_detail_fragment("getCorequisites", term, crn)
+            .await?;
+        ParsePool::global()
+            .run(move || parse_corequisites(&html))
+            .await
+    }
+
+    /// Retrieves the catalog description for a section's course.
+    pub async fn get_course_description(&self, term: &str, crn: &str) -> Result<Option<String>> {
+        let html = self
+            .get_detail_fragment("getCourseDescription", term, crn)
+            .await?;
+        ParsePool::global()
+            .run(move || parse_course_description(&html))
+            .await
+    }
+
+    /// Retrieves linked section groups (e.g. lecture + lab) for a section.
+    ///
+    /// Returns CRN groups with the queried section itself removed.
+    pub async fn get_linked_sections(&self, term: &str, crn: &str) -> Result<Vec<Vec<String>>> {
+        let url = format!("{}/searchResults/fetchLinkedSections", self.base_url);
+        let params = [("term", term), ("courseReferenceNumber", crn)];
+
+        let response = self
+            .http
+            .get(&url)
+            .query(&params)
+            .send()
+            .await
+            .context("Failed to get linked sections")?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .with_context(|| format!("Failed to read body (status={status})"))?;
+        let parsed: LinkedSectionsResponse =
+            parse_json_with_context(&body).context("Failed to parse linked sections")?;
+
+        Ok(parsed
+            .linked_data
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|s| s.course_reference_number)
+                    .filter(|c| c != crn)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|group| !group.is_empty())
+            .collect())
+    }
+
+    /// Fetches all detail endpoints for a section.
+    ///
+    /// The requests are independent, so they are issued concurrently; the
+    /// Banner rate limiter middleware still paces them.
+    pub async fn get_course_details(&self, term: &str, crn: &str) -> Result<CourseDetails> {
+        let (enrollment, restrictions, corequisites, linked_groups, description) = tokio::try_join!(
+            self.get_enrollment_info(term, crn),
+            self.get_restrictions(term, crn),
+            self.get_corequisites(term, crn),
+            self.get_linked_sections(term, crn),
+            self.get_course_description(term, crn),
+        )?;
+
+        Ok(CourseDetails {
+            enrollment,
+            restrictions,
+            corequisites,
+            linked_groups,
+            description,
+        })
+    }
+
     /// Performs a search for courses.
     pub async fn search(
         &self,
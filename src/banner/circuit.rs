@@ -0,0 +1,262 @@
+//! Circuit breaker for the Banner API.
+//!
+//! When UTSA's Banner is down, every request times out or answers 5xx, and
+//! each scrape job would otherwise spend a retry discovering that. After
+//! [`FAILURE_THRESHOLD`] consecutive failures the circuit opens: requests fail
+//! immediately with [`BannerUnavailable`] and workers stop dispatching jobs.
+//! Once [`OPEN_COOLDOWN`] has passed, a single probe request is let through;
+//! if it succeeds the circuit closes, otherwise it opens for another cooldown.
+//!
+//! State changes are published on a watch channel so the scraper can report
+//! them to the service registry and the admin stream.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::watch;
+use tracing::{info, warn};
+use ts_rs::TS;
+
+use crate::banner::errors::BannerUnavailable;
+
+/// Consecutive failures that open the circuit.
+pub const FAILURE_THRESHOLD: u32 = 5;
+
+/// How long the circuit stays open before probing Banner again.
+pub const OPEN_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Suggested wait for requests turned away while a probe is in flight.
+const PROBE_RETRY_AFTER: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Banner is considered down; requests fail fast.
+    Open,
+    /// Cooldown elapsed; one probe request decides whether to close.
+    HalfOpen,
+}
+
+/// Banner's health as seen by the circuit breaker.
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BannerHealth {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// When the circuit entered its current state.
+    pub since: DateTime<Utc>,
+    /// The failure that most recently opened the circuit.
+    pub last_error: Option<String>,
+}
+
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    /// While open: when a probe may go out. While half-open: when the
+    /// in-flight probe is presumed lost and another may go out.
+    retry_at: Instant,
+    last_error: Option<String>,
+}
+
+pub struct BannerCircuit {
+    inner: Mutex<Inner>,
+    health: watch::Sender<BannerHealth>,
+}
+
+impl Default for BannerCircuit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BannerCircuit {
+    pub fn new() -> Self {
+        let (health, _) = watch::channel(BannerHealth {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            since: Utc::now(),
+            last_error: None,
+        });
+        Self {
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                retry_at: Instant::now(),
+                last_error: None,
+            }),
+            health,
+        }
+    }
+
+    /// Current health snapshot.
+    pub fn health(&self) -> BannerHealth {
+        self.health.borrow().clone()
+    }
+
+    /// Receive a new snapshot every time the circuit changes state.
+    pub fn subscribe(&self) -> watch::Receiver<BannerHealth> {
+        self.health.subscribe()
+    }
+
+    /// Whether a request sent now would be let through. Workers check this
+    /// before locking a job so none are dispatched while Banner is down.
+    pub fn accepting_requests(&self) -> bool {
+        let inner = self.inner.lock().expect("lock poisoned");
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open | CircuitState::HalfOpen => Instant::now() >= inner.retry_at,
+        }
+    }
+
+    /// Let a request through, or turn it away while the circuit is open.
+    pub fn admit(&self) -> Result<(), BannerUnavailable> {
+        self.admit_at(Instant::now())
+    }
+
+    fn admit_at(&self, now: Instant) -> Result<(), BannerUnavailable> {
+        let mut inner = self.inner.lock().expect("lock poisoned");
+        match inner.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open | CircuitState::HalfOpen if now >= inner.retry_at => {
+                // This request becomes the probe. A probe that never reports
+                // back (cancelled, or an inconclusive error) is replaced
+                // after another cooldown.
+                inner.retry_at = now + OPEN_COOLDOWN;
+                if inner.state == CircuitState::Open {
+                    self.transition(&mut inner, CircuitState::HalfOpen);
+                }
+                Ok(())
+            }
+            CircuitState::Open => Err(BannerUnavailable {
+                retry_after: inner.retry_at - now,
+            }),
+            CircuitState::HalfOpen => Err(BannerUnavailable {
+                retry_after: PROBE_RETRY_AFTER.min(inner.retry_at - now),
+            }),
+        }
+    }
+
+    /// Banner answered; close the circuit if it wasn't already.
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().expect("lock poisoned");
+        inner.consecutive_failures = 0;
+        if inner.state != CircuitState::Closed {
+            info!("Banner recovered, closing circuit");
+            self.transition(&mut inner, CircuitState::Closed);
+        }
+    }
+
+    /// Banner timed out or answered 5xx.
+    pub fn record_failure(&self, error: String) {
+        self.record_failure_at(error, Instant::now());
+    }
+
+    fn record_failure_at(&self, error: String, now: Instant) {
+        let mut inner = self.inner.lock().expect("lock poisoned");
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let trips = match inner.state {
+            CircuitState::Closed => inner.consecutive_failures >= FAILURE_THRESHOLD,
+            CircuitState::HalfOpen => true,
+            // Requests admitted before the circuit opened finishing late.
+            CircuitState::Open => false,
+        };
+        if trips {
+            warn!(
+                failures = inner.consecutive_failures,
+                error = %error,
+                "Banner unavailable, opening circuit"
+            );
+            inner.retry_at = now + OPEN_COOLDOWN;
+            inner.last_error = Some(error);
+            self.transition(&mut inner, CircuitState::Open);
+        }
+    }
+
+    fn transition(&self, inner: &mut Inner, state: CircuitState) {
+        inner.state = state;
+        if state == CircuitState::Closed {
+            inner.last_error = None;
+        }
+        self.health.send_replace(BannerHealth {
+            state,
+            consecutive_failures: inner.consecutive_failures,
+            since: Utc::now(),
+            last_error: inner.last_error.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tripped(now: Instant) -> BannerCircuit {
+        let circuit = BannerCircuit::new();
+        for _ in 0..FAILURE_THRESHOLD {
+            circuit.record_failure_at("timed out".into(), now);
+        }
+        circuit
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let now = Instant::now();
+        let circuit = BannerCircuit::new();
+        for _ in 1..FAILURE_THRESHOLD {
+            circuit.record_failure_at("HTTP 502".into(), now);
+        }
+        circuit.record_success();
+        circuit.record_failure_at("HTTP 502".into(), now);
+        assert_eq!(circuit.health().state, CircuitState::Closed);
+
+        let circuit = tripped(now);
+        let health = circuit.health();
+        assert_eq!(health.state, CircuitState::Open);
+        assert_eq!(health.last_error.as_deref(), Some("timed out"));
+        let rejected = circuit.admit_at(now).unwrap_err();
+        assert_eq!(rejected.retry_after, OPEN_COOLDOWN);
+    }
+
+    #[test]
+    fn lets_one_probe_through_after_cooldown() {
+        let now = Instant::now();
+        let circuit = tripped(now);
+        let later = now + OPEN_COOLDOWN;
+
+        assert!(circuit.admit_at(later).is_ok());
+        assert_eq!(circuit.health().state, CircuitState::HalfOpen);
+        assert!(circuit.admit_at(later).is_err());
+
+        circuit.record_success();
+        assert_eq!(circuit.health().state, CircuitState::Closed);
+        assert!(circuit.admit_at(later).is_ok());
+    }
+
+    #[test]
+    fn failed_probe_reopens() {
+        let now = Instant::now();
+        let circuit = tripped(now);
+        let later = now + OPEN_COOLDOWN;
+
+        circuit.admit_at(later).unwrap();
+        circuit.record_failure_at("HTTP 503".into(), later);
+        let health = circuit.health();
+        assert_eq!(health.state, CircuitState::Open);
+        assert_eq!(health.last_error.as_deref(), Some("HTTP 503"));
+        assert!(circuit.admit_at(later).is_err());
+    }
+
+    #[test]
+    fn lost_probe_is_replaced() {
+        let now = Instant::now();
+        let circuit = tripped(now);
+        circuit.admit_at(now + OPEN_COOLDOWN).unwrap();
+        assert!(circuit.admit_at(now + OPEN_COOLDOWN * 2).is_ok());
+    }
+}
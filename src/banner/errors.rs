@@ -1,5 +1,7 @@
 //! Error types for the Banner API client.
 
+use std::time::Duration;
+
 #[derive(Debug, thiserror::Error)]
 pub enum BannerApiError {
     #[error("Banner session is invalid or expired: {0}")]
@@ -14,3 +16,124 @@ pub enum BannerApiError {
     #[error(transparent)]
     RequestFailed(#[from] anyhow::Error),
 }
+
+/// Banner answered 429 or 503: it wants us to slow down.
+///
+/// Returned by [`ThrottleMiddleware`](super::middleware::ThrottleMiddleware)
+/// in place of the response, so every request path sees the same error.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Banner throttled the request (HTTP {status}, retry after {retry_after:?})")]
+pub struct BannerThrottled {
+    pub status: u16,
+    /// Parsed `Retry-After` header, if Banner sent a usable one.
+    pub retry_after: Option<Duration>,
+}
+
+impl BannerThrottled {
+    /// Find a throttle error anywhere in `err`'s chain.
+    pub fn find(err: &anyhow::Error) -> Option<&BannerThrottled> {
+        find_in_chain(err)
+    }
+}
+
+/// The circuit breaker is open: Banner has been failing, so the request was
+/// not sent.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Banner is unavailable (circuit open, retry after {retry_after:?})")]
+pub struct BannerUnavailable {
+    pub retry_after: Duration,
+}
+
+impl BannerUnavailable {
+    /// Find a circuit-open error anywhere in `err`'s chain.
+    pub fn find(err: &anyhow::Error) -> Option<&BannerUnavailable> {
+        find_in_chain(err)
+    }
+}
+
+/// Callers that already back off on throttling treat an open circuit like a
+/// 503 from Banner itself.
+impl From<&BannerUnavailable> for BannerThrottled {
+    fn from(unavailable: &BannerUnavailable) -> Self {
+        Self {
+            status: 503,
+            retry_after: Some(unavailable.retry_after),
+        }
+    }
+}
+
+/// Find an error of type `T` anywhere in `err`'s chain.
+///
+/// `reqwest_middleware::Error` and [`BannerApiError::RequestFailed`] are
+/// transparent wrappers whose `source()` skips the error they hold, so both
+/// are unwrapped by hand.
+fn find_in_chain<T>(err: &anyhow::Error) -> Option<&T>
+where
+    T: std::error::Error + 'static,
+{
+    err.chain().find_map(find_in::<T>)
+}
+
+fn find_in<'a, T>(cause: &'a (dyn std::error::Error + 'static)) -> Option<&'a T>
+where
+    T: std::error::Error + 'static,
+{
+    if let Some(found) = cause.downcast_ref::<T>() {
+        return Some(found);
+    }
+    if let Some(reqwest_middleware::Error::Middleware(inner)) =
+        cause.downcast_ref::<reqwest_middleware::Error>()
+    {
+        return find_in_chain(inner);
+    }
+    match cause.downcast_ref::<BannerApiError>() {
+        Some(BannerApiError::RequestFailed(inner)) => find_in_chain(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    fn throttled() -> BannerThrottled {
+        BannerThrottled {
+            status: 429,
+            retry_after: Some(Duration::from_secs(30)),
+        }
+    }
+
+    #[test]
+    fn finds_throttle_through_wrappers() {
+        let middleware = reqwest_middleware::Error::Middleware(throttled().into());
+        let err = Err::<(), _>(middleware)
+            .context("Failed to search courses")
+            .unwrap_err();
+        assert_eq!(BannerThrottled::find(&err), Some(&throttled()));
+
+        let middleware = reqwest_middleware::Error::Middleware(throttled().into());
+        let api = BannerApiError::RequestFailed(middleware.into());
+        let err = anyhow::Error::from(api).context("subject job failed");
+        assert_eq!(BannerThrottled::find(&err), Some(&throttled()));
+    }
+
+    #[test]
+    fn finds_circuit_open_through_wrappers() {
+        let unavailable = BannerUnavailable {
+            retry_after: Duration::from_secs(60),
+        };
+        let middleware = reqwest_middleware::Error::Middleware(unavailable.clone().into());
+        let err = Err::<(), _>(middleware)
+            .context("Failed to search courses")
+            .unwrap_err();
+        assert_eq!(BannerUnavailable::find(&err), Some(&unavailable));
+        assert_eq!(BannerThrottled::find(&err), None);
+    }
+
+    #[test]
+    fn ignores_other_errors() {
+        let err = anyhow::Error::from(BannerApiError::InvalidSession("expired".into()));
+        assert_eq!(BannerThrottled::find(&err), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Feeds request outcomes to the [`BannerCircuit`] and fails fast while it
+//! is open.
+//!
+//! Sits outermost in the middleware stack so a rejected request never waits
+//! on the rate limiter or concurrency cap, and so a 503 already turned into
+//! [`BannerThrottled`] is still seen as a failure.
+
+use std::sync::Arc;
+
+use http::Extensions;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+
+use crate::banner::circuit::BannerCircuit;
+use crate::banner::errors::BannerThrottled;
+
+pub struct CircuitBreakerMiddleware {
+    circuit: Arc<BannerCircuit>,
+}
+
+impl CircuitBreakerMiddleware {
+    pub fn new(circuit: Arc<BannerCircuit>) -> Self {
+        Self { circuit }
+    }
+}
+
+/// What a request's result says about Banner's health.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    /// Banner answered, even if with an error it meant to send.
+    Healthy,
+    /// Banner timed out, refused the connection, or answered 5xx.
+    Failed(String),
+    /// The request failed for a reason unrelated to Banner.
+    Unknown,
+}
+
+fn classify(result: &Result<Response, reqwest_middleware::Error>) -> Outcome {
+    match result {
+        Ok(response) if response.status().is_server_error() => {
+            Outcome::Failed(format!("HTTP {}", response.status().as_u16()))
+        }
+        Ok(_) => Outcome::Healthy,
+        Err(reqwest_middleware::Error::Reqwest(e)) => classify_reqwest(e),
+        Err(reqwest_middleware::Error::Middleware(e)) => match BannerThrottled::find(e) {
+            Some(throttled) if throttled.status >= 500 => {
+                Outcome::Failed(format!("HTTP {}", throttled.status))
+            }
+            Some(_) => Outcome::Healthy,
+            None => Outcome::Unknown,
+        },
+    }
+}
+
+fn classify_reqwest(e: &reqwest::Error) -> Outcome {
+    if e.is_timeout() {
+        Outcome::Failed("request timed out".to_owned())
+    } else if e.is_connect() {
+        Outcome::Failed("connection failed".to_owned())
+    } else {
+        match e.status() {
+            Some(status) if status.is_server_error() => {
+                Outcome::Failed(format!("HTTP {}", status.as_u16()))
+            }
+            _ => Outcome::Unknown,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for CircuitBreakerMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> std::result::Result<Response, reqwest_middleware::Error> {
+        self.circuit
+            .admit()
+            .map_err(|e| reqwest_middleware::Error::Middleware(e.into()))?;
+
+        let result = next.run(req, extensions).await;
+        match classify(&result) {
+            Outcome::Healthy => self.circuit.record_success(),
+            Outcome::Failed(error) => self.circuit.record_failure(error),
+            Outcome::Unknown => {}
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16) -> Result<Response, reqwest_middleware::Error> {
+        Ok(http::Response::builder()
+            .status(status)
+            .body("")
+            .unwrap()
+            .into())
+    }
+
+    fn throttled(status: u16) -> Result<Response, reqwest_middleware::Error> {
+        Err(reqwest_middleware::Error::Middleware(
+            BannerThrottled {
+                status,
+                retry_after: None,
+            }
+            .into(),
+        ))
+    }
+
+    #[test]
+    fn classifies_responses() {
+        assert_eq!(classify(&response(200)), Outcome::Healthy);
+        assert_eq!(classify(&response(404)), Outcome::Healthy);
+        assert_eq!(classify(&response(502)), Outcome::Failed("HTTP 502".into()));
+        assert_eq!(classify(&throttled(429)), Outcome::Healthy);
+        assert_eq!(
+            classify(&throttled(503)),
+            Outcome::Failed("HTTP 503".into())
+        );
+        let other = Err(reqwest_middleware::Error::Middleware(anyhow::anyhow!(
+            "rate limiter closed"
+        )));
+        assert_eq!(classify(&other), Outcome::Unknown);
+    }
+}
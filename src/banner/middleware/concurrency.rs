@@ -0,0 +1,55 @@
+//! Cap on simultaneous in-flight Banner requests.
+//!
+//! The rate limiter spaces requests out over time, but a burst of workers can
+//! still have many requests open at once while Banner is slow to answer. This
+//! bounds that across every caller sharing the client.
+
+use http::Extensions;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tracing::debug;
+
+use crate::utils::fmt_duration;
+
+/// Middleware that holds a permit for the duration of each request.
+pub struct ConcurrencyLimitMiddleware {
+    permits: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimitMiddleware {
+    /// Allows at most `max` requests in flight; zero is treated as one.
+    pub fn new(max: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max.max(1))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for ConcurrencyLimitMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> std::result::Result<Response, reqwest_middleware::Error> {
+        let start = std::time::Instant::now();
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("concurrency semaphore is never closed");
+        let waited = start.elapsed();
+        if waited >= Duration::from_secs(5) {
+            debug!(
+                wait = fmt_duration(waited),
+                "Banner concurrency cap caused significant delay"
+            );
+        }
+
+        next.run(req, extensions).await
+    }
+}
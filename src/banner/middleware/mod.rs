@@ -1,7 +1,13 @@
 //! HTTP middleware for the Banner API client.
 
+pub mod circuit_breaker;
+pub mod concurrency;
 pub mod logging;
 pub mod rate_limit;
+pub mod throttle;
 
+pub use circuit_breaker::CircuitBreakerMiddleware;
+pub use concurrency::ConcurrencyLimitMiddleware;
 pub use logging::LoggingMiddleware;
 pub use rate_limit::{BannerRateLimiter, RateLimitMiddleware};
+pub use throttle::ThrottleMiddleware;
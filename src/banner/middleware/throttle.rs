@@ -0,0 +1,97 @@
+//! Turns Banner's "slow down" responses into a typed error.
+//!
+//! Banner (or the load balancer in front of it) answers 429 or 503 when it is
+//! overloaded, sometimes with a `Retry-After` header. Callers otherwise treat
+//! these like any failed request and retry on their own schedule; surfacing
+//! [`BannerThrottled`] lets the scraper wait as long as Banner asked instead.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use http::{Extensions, StatusCode, header::RETRY_AFTER};
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+
+use crate::banner::errors::BannerThrottled;
+
+pub struct ThrottleMiddleware;
+
+/// Parse a `Retry-After` value: either delay-seconds or an HTTP-date.
+///
+/// Dates in the past yield a zero delay; unparseable values yield `None`.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[async_trait::async_trait]
+impl Middleware for ThrottleMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> std::result::Result<Response, reqwest_middleware::Error> {
+        let response = next.run(req, extensions).await?;
+        let status = response.status();
+        if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+            return Ok(response);
+        }
+
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| parse_retry_after(v, Utc::now()));
+        Err(reqwest_middleware::Error::Middleware(
+            BannerThrottled {
+                status: status.as_u16(),
+                retry_after,
+            }
+            .into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parses_delay_seconds() {
+        let now = Utc::now();
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn parses_http_dates() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap();
+        assert_eq!(
+            parse_retry_after("Sat, 01 Mar 2025 12:01:30 GMT", now),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_retry_after("Sat, 01 Mar 2025 11:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(parse_retry_after("soon", Utc::now()), None);
+        assert_eq!(parse_retry_after("-5", Utc::now()), None);
+    }
+}
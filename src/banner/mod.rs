@@ -6,6 +6,7 @@
 //! - Generate ICS files and calendar links
 
 pub mod api;
+pub mod circuit;
 pub mod errors;
 pub mod json;
 pub mod middleware;
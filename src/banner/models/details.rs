@@ -0,0 +1,255 @@
+//! Models and HTML parsers for the per-section detail endpoints.
+//!
+//! Banner's class detail popup is served as a handful of `searchResults/get*`
+//! endpoints that return HTML fragments rather than JSON. Linked sections are
+//! the exception and come back as JSON from `fetchLinkedSections`.
+
+use html_scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+use std::sync::LazyLock;
+
+/// Seat and waitlist counts from `getEnrollmentInfo`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnrollmentInfo {
+    pub enrollment: i32,
+    pub max_enrollment: i32,
+    pub wait_count: i32,
+    pub wait_capacity: i32,
+}
+
+/// A single restriction value under a heading, from `getRestrictions`.
+///
+/// `heading` is kept verbatim (e.g. "Must be enrolled in one of the following
+/// Levels:") since Banner's wording already encodes include vs. exclude.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Restriction {
+    pub heading: String,
+    pub value: String,
+}
+
+/// A corequisite course from `getCorequisites`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Corequisite {
+    pub subject: String,
+    pub course_number: String,
+    pub title: Option<String>,
+}
+
+/// Response body of `fetchLinkedSections`.
+///
+/// Each inner list is one group of sections that must be taken together.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedSectionsResponse {
+    #[serde(default)]
+    pub linked_data: Vec<Vec<LinkedSection>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedSection {
+    pub course_reference_number: String,
+}
+
+/// Everything the detail enrichment job fetches for one section.
+#[derive(Debug, Clone, Default)]
+pub struct CourseDetails {
+    pub enrollment: Option<EnrollmentInfo>,
+    pub restrictions: Vec<Restriction>,
+    pub corequisites: Vec<Corequisite>,
+    /// Groups of CRNs linked to this section (excluding the section itself).
+    pub linked_groups: Vec<Vec<String>>,
+    /// Catalog description from `getCourseDescription`, if Banner has one.
+    pub description: Option<String>,
+}
+
+static BOLD_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("span.status-bold").unwrap());
+static SPAN_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("span").unwrap());
+static ROW_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tbody tr").unwrap());
+static TD_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("td").unwrap());
+
+fn element_text(el: ElementRef<'_>) -> String {
+    el.text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse the `getEnrollmentInfo` fragment.
+///
+/// Each value is a `<span class="status-bold">Label:</span>` followed by a
+/// sibling `<span>` holding the number. Returns `None` if the enrollment
+/// counts are missing (Banner returns an empty body for unknown CRNs).
+pub fn parse_enrollment_info(html: &str) -> Option<EnrollmentInfo> {
+    let doc = Html::parse_fragment(html);
+    let mut info = EnrollmentInfo::default();
+    let mut found = false;
+
+    for label in doc.select(&BOLD_SEL) {
+        let key = element_text(label);
+        let Some(value) = label
+            .next_siblings()
+            .filter_map(ElementRef::wrap)
+            .find(|e| e.value().name() == "span")
+            .and_then(|e| element_text(e).parse::<i32>().ok())
+        else {
+            continue;
+        };
+
+        let slot = match key.trim_end_matches(':') {
+            "Enrollment Actual" => &mut info.enrollment,
+            "Enrollment Maximum" => &mut info.max_enrollment,
+            "Waitlist Actual" => &mut info.wait_count,
+            "Waitlist Capacity" => &mut info.wait_capacity,
+            _ => continue,
+        };
+        *slot = value;
+        found = true;
+    }
+
+    found.then_some(info)
+}
+
+/// Parse the `getRestrictions` fragment into (heading, value) pairs.
+///
+/// Headings are `span.status-bold`; every following non-bold span up to the
+/// next heading is a value under it.
+pub fn parse_restrictions(html: &str) -> Vec<Restriction> {
+    let doc = Html::parse_fragment(html);
+    let mut restrictions = Vec::new();
+    let mut heading: Option<String> = None;
+
+    for span in doc.select(&SPAN_SEL) {
+        let text = element_text(span);
+        if text.is_empty() {
+            continue;
+        }
+        if span.value().classes().any(|c| c == "status-bold") {
+            heading = Some(text);
+        } else if let Some(h) = &heading {
+            restrictions.push(Restriction {
+                heading: h.clone(),
+                value: text,
+            });
+        }
+    }
+
+    restrictions
+}
+
+/// Parse the `getCorequisites` fragment (a Subject / Course Number / Title table).
+pub fn parse_corequisites(html: &str) -> Vec<Corequisite> {
+    let doc = Html::parse_fragment(html);
+    doc.select(&ROW_SEL)
+        .filter_map(|row| {
+            let cells: Vec<String> = row.select(&TD_SEL).map(element_text).collect();
+            let subject = cells.first().filter(|s| !s.is_empty())?.clone();
+            let course_number = cells.get(1).filter(|s| !s.is_empty())?.clone();
+            let title = cells.get(2).filter(|s| !s.is_empty()).cloned();
+            Some(Corequisite {
+                subject,
+                course_number,
+                title,
+            })
+        })
+        .collect()
+}
+
+/// Parse the `getCourseDescription` fragment into whitespace-collapsed text.
+///
+/// Returns `None` when the fragment has no text, which Banner serves for
+/// courses without a catalog description.
+pub fn parse_course_description(html: &str) -> Option<String> {
+    let doc = Html::parse_fragment(html);
+    Some(element_text(doc.root_element())).filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_enrollment_info() {
+        let html = r#"
+            <span class="status-bold">Enrollment Actual:</span> <span dir="ltr"> 28 </span><br/>
+            <span class="status-bold">Enrollment Maximum:</span> <span dir="ltr"> 30 </span><br/>
+            <span class="status-bold">Enrollment Seats Available:</span> <span dir="ltr"> 2 </span><br/>
+            <span class="status-bold">Waitlist Capacity:</span> <span dir="ltr"> 10 </span><br/>
+            <span class="status-bold">Waitlist Actual:</span> <span dir="ltr"> 3 </span><br/>
+        "#;
+        assert_eq!(
+            parse_enrollment_info(html),
+            Some(EnrollmentInfo {
+                enrollment: 28,
+                max_enrollment: 30,
+                wait_count: 3,
+                wait_capacity: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn empty_enrollment_body_is_none() {
+        assert_eq!(parse_enrollment_info(""), None);
+    }
+
+    #[test]
+    fn parses_restrictions_under_headings() {
+        let html = r#"
+            <span class="status-bold">Must be enrolled in one of the following Levels:</span><br/>
+            <span class="detail-popup-indentation">Undergraduate (UG)</span><br/>
+            <span class="status-bold">Cannot be enrolled in one of the following Classifications:</span><br/>
+            <span class="detail-popup-indentation">Freshman (01)</span><br/>
+            <span class="detail-popup-indentation">Sophomore (02)</span><br/>
+        "#;
+        let parsed = parse_restrictions(html);
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].value, "Undergraduate (UG)");
+        assert!(parsed[0].heading.starts_with("Must be enrolled"));
+        assert!(parsed[2].heading.starts_with("Cannot be enrolled"));
+        assert_eq!(parsed[2].value, "Sophomore (02)");
+    }
+
+    #[test]
+    fn parses_corequisite_table() {
+        let html = r#"
+            <table class="basePreqTable">
+              <thead><tr><th>Subject</th><th>Course Number</th><th>Title</th></tr></thead>
+              <tbody>
+                <tr><td>CS</td><td>1081</td><td>Computer Programming I Lab</td></tr>
+              </tbody>
+            </table>
+        "#;
+        assert_eq!(
+            parse_corequisites(html),
+            vec![Corequisite {
+                subject: "CS".into(),
+                course_number: "1081".into(),
+                title: Some("Computer Programming I Lab".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn no_corequisites_message_yields_empty() {
+        let html = "<section>No corequisite course information available.</section>";
+        assert!(parse_corequisites(html).is_empty());
+    }
+
+    #[test]
+    fn parses_course_description() {
+        let html = r#"
+            <section aria-labelledby="courseDescription">
+                Introduction to   algorithm design and
+                analysis. <br/> (Formerly CS 3343.)
+            </section>
+        "#;
+        assert_eq!(
+            parse_course_description(html).as_deref(),
+            Some("Introduction to algorithm design and analysis. (Formerly CS 3343.)")
+        );
+        assert_eq!(parse_course_description("<section> </section>"), None);
+    }
+}
@@ -4,6 +4,7 @@ use extension_traits::extension;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{cmp::Ordering, str::FromStr};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 use super::terms::Term;
 
@@ -200,7 +201,7 @@ impl TryFrom<MeetingDays> for Weekday {
 }
 
 /// Time range for meetings
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, ToSchema)]
 #[ts(export)]
 pub struct TimeRange {
     pub start: NaiveTime,
@@ -235,29 +236,6 @@ impl TimeRange {
         NaiveTime::from_hms_opt(hours, minutes, 0)
     }
 
-    /// Format time in 12-hour format
-    pub fn format_12hr(&self) -> String {
-        format!(
-            "{}-{}",
-            Self::format_time_12hr(self.start),
-            Self::format_time_12hr(self.end)
-        )
-    }
-
-    /// Format a single time in 12-hour format
-    fn format_time_12hr(time: NaiveTime) -> String {
-        let hour = time.hour();
-        let minute = time.minute();
-
-        let meridiem = if hour < 12 { "AM" } else { "PM" };
-        let display_hour = match hour {
-            0 => 12,
-            13..=23 => hour - 12,
-            _ => hour,
-        };
-        format!("{display_hour}:{minute:02}{meridiem}")
-    }
-
     /// Get duration in minutes
     #[allow(dead_code)]
     pub fn duration_minutes(&self) -> i64 {
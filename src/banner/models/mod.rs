@@ -2,6 +2,7 @@
 
 pub mod common;
 pub mod courses;
+pub mod details;
 pub mod meetings;
 pub mod search;
 pub mod terms;
@@ -9,6 +10,7 @@ pub mod terms;
 // Re-export commonly used types
 pub use common::*;
 pub use courses::*;
+pub use details::*;
 pub use meetings::*;
 pub use search::*;
 pub use terms::*;
@@ -13,10 +13,10 @@ use crate::utils::fmt_duration;
 use std::mem::ManuallyDrop;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Notify};
-use tracing::trace;
+use tracing::{debug, trace, warn};
 use url::Url;
 
 const SESSION_EXPIRY: Duration = Duration::from_secs(25 * 60); // 25 minutes
@@ -134,6 +134,7 @@ impl DerefMut for PooledSession {
 
 impl Drop for PooledSession {
     fn drop(&mut self) {
+        self.pool.counters.in_use.fetch_sub(1, Ordering::Relaxed);
         // SAFETY: `drop` is called exactly once by Rust's drop semantics,
         // so `ManuallyDrop::take` is guaranteed to see a valid value.
         let session = unsafe { ManuallyDrop::take(&mut self.session) };
@@ -148,6 +149,31 @@ pub struct TermPool {
     sessions: Mutex<VecDeque<BannerSession>>,
     notifier: Notify,
     is_creating: AtomicBool,
+    counters: Arc<PoolCounters>,
+}
+
+/// Running totals shared by every term's pool.
+#[derive(Default)]
+struct PoolCounters {
+    in_use: AtomicUsize,
+    created: AtomicU64,
+    failed: AtomicU64,
+    reused: AtomicU64,
+}
+
+/// Snapshot of session pool usage, for the metrics endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionPoolStats {
+    /// Sessions waiting in the pool to be reused.
+    pub idle: usize,
+    /// Sessions currently checked out by a request.
+    pub in_use: usize,
+    /// Sessions created since startup.
+    pub created: u64,
+    /// Session creations that failed since startup.
+    pub failed: u64,
+    /// Acquisitions served by an existing session since startup.
+    pub reused: u64,
 }
 
 /// RAII guard ensuring `is_creating` is reset on drop for cancellation safety.
@@ -163,11 +189,20 @@ impl Drop for CreatingGuard {
 }
 
 impl TermPool {
-    fn new() -> Self {
+    fn new(counters: Arc<PoolCounters>) -> Self {
         Self {
             sessions: Mutex::new(VecDeque::new()),
             notifier: Notify::new(),
             is_creating: AtomicBool::new(false),
+            counters,
+        }
+    }
+
+    fn checkout(self: Arc<Self>, session: BannerSession) -> PooledSession {
+        self.counters.in_use.fetch_add(1, Ordering::Relaxed);
+        PooledSession {
+            session: ManuallyDrop::new(session),
+            pool: self,
         }
     }
 
@@ -193,6 +228,7 @@ pub struct SessionPool {
     sessions: DashMap<Term, Arc<TermPool>>,
     http: ClientWithMiddleware,
     base_url: String,
+    counters: Arc<PoolCounters>,
 }
 
 impl SessionPool {
@@ -201,18 +237,73 @@ impl SessionPool {
             sessions: DashMap::new(),
             http,
             base_url,
+            counters: Arc::default(),
         }
     }
 
+    fn term_pool(&self, term: Term) -> Arc<TermPool> {
+        self.sessions
+            .entry(term)
+            .or_insert_with(|| Arc::new(TermPool::new(self.counters.clone())))
+            .clone()
+    }
+
+    /// Current pool usage and running totals.
+    pub async fn stats(&self) -> SessionPoolStats {
+        let pools: Vec<Arc<TermPool>> = self.sessions.iter().map(|e| e.value().clone()).collect();
+        let mut idle = 0;
+        for pool in pools {
+            idle += pool.sessions.lock().await.len();
+        }
+        SessionPoolStats {
+            idle,
+            in_use: self.counters.in_use.load(Ordering::Relaxed),
+            created: self.counters.created.load(Ordering::Relaxed),
+            failed: self.counters.failed.load(Ordering::Relaxed),
+            reused: self.counters.reused.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Create up to `count` sessions for `term` ahead of time, so the first
+    /// requests don't wait on the cookie handshake. Stops at the first
+    /// failure and returns how many sessions were added.
+    pub async fn warm_up(&self, term: Term, count: usize) -> usize {
+        let term_pool = self.term_pool(term);
+        let mut created = 0;
+        for _ in 0..count {
+            match self.create_counted(&term).await {
+                Ok(session) => {
+                    term_pool.sessions.lock().await.push_back(session);
+                    term_pool.notifier.notify_one();
+                    created += 1;
+                }
+                Err(e) => {
+                    warn!(term = %term, error = ?e, "Session warm-up failed");
+                    break;
+                }
+            }
+        }
+        debug!(term = %term, created, "Session pool warmed up");
+        created
+    }
+
+    /// [`Self::create_session`], counted in the pool's totals.
+    async fn create_counted(&self, term: &Term) -> Result<BannerSession> {
+        let result = self.create_session(term).await;
+        let counter = if result.is_ok() {
+            &self.counters.created
+        } else {
+            &self.counters.failed
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
     /// Acquires a session from the pool.
     /// If no sessions are available, a new one is created on demand,
     /// respecting the global rate limit.
     pub async fn acquire(&self, term: Term) -> Result<PooledSession> {
-        let term_pool = self
-            .sessions
-            .entry(term)
-            .or_insert_with(|| Arc::new(TermPool::new()))
-            .clone();
+        let term_pool = self.term_pool(term);
 
         let start = Instant::now();
         let mut waited_for_creation = false;
@@ -232,10 +323,8 @@ impl SessionPool {
                             age_secs = age.as_secs(),
                             "Reused existing session from pool"
                         );
-                        return Ok(PooledSession {
-                            session: ManuallyDrop::new(session),
-                            pool: Arc::clone(&term_pool),
-                        });
+                        self.counters.reused.fetch_add(1, Ordering::Relaxed);
+                        return Ok(Arc::clone(&term_pool).checkout(session));
                     } else {
                         trace!(id = session.unique_session_id, "Discarded expired session");
                     }
@@ -265,7 +354,7 @@ impl SessionPool {
             let creating_guard = CreatingGuard(Arc::clone(&term_pool));
 
             trace!(term = %term, "Creating new session (pool empty)");
-            let new_session_result = self.create_session(&term).await;
+            let new_session_result = self.create_counted(&term).await;
             drop(creating_guard);
 
             match new_session_result {
@@ -276,10 +365,7 @@ impl SessionPool {
                         elapsed = fmt_duration(elapsed),
                         "Created new session"
                     );
-                    return Ok(PooledSession {
-                        session: ManuallyDrop::new(new_session),
-                        pool: term_pool,
-                    });
+                    return Ok(term_pool.checkout(new_session));
                 }
                 Err(e) => {
                     return Err(e.context("Failed to create new session in pool"));
@@ -2,12 +2,15 @@
 //!
 //! BlueBook is an ASP.NET WebForms application that requires stateful
 //! ViewState/EventValidation round-tripping and cookie-based sessions.
+//! Responses are parsed on the [`crate::parse_pool`] workers, so fetching the
+//! next results page overlaps with parsing the previous one.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use html_scraper::{Html, Selector};
 use std::sync::LazyLock;
 use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, info, warn};
 
 use sqlx::PgPool;
@@ -17,8 +20,8 @@ use crate::data::bluebook::{
     BlueBookEvaluation, batch_upsert_bluebook_evaluations, get_all_subject_scrape_times,
     get_subject_max_terms, mark_subject_scraped,
 };
+use crate::parse_pool::ParsePool;
 
-#[allow(dead_code)]
 const BASE_URL: &str = "https://bluebook.utsa.edu/Default.aspx";
 
 /// Re-scrape interval for subjects with evaluations in a recent term (within ~2 years).
@@ -115,6 +118,12 @@ impl FormFields {
     }
 }
 
+/// Form state carried from one results page to the next request.
+struct PageState {
+    fields: FormFields,
+    total_pages: u32,
+}
+
 /// A subject entry from the BlueBook ComboBox.
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -172,6 +181,7 @@ fn is_recent_subject(max_term: Option<&str>, current_term_code: &str) -> bool {
 #[allow(dead_code)]
 pub(crate) struct BlueBookClient {
     http: reqwest::Client,
+    base_url: String,
     delay: Duration,
 }
 
@@ -185,13 +195,21 @@ impl Default for BlueBookClient {
 #[allow(dead_code)]
 impl BlueBookClient {
     pub(crate) fn new() -> Self {
+        Self::with_base_url(BASE_URL, Duration::from_millis(1500))
+    }
+
+    /// Client for a BlueBook page other than the live one, e.g. a mock server.
+    ///
+    /// `delay` is the pause before each POST.
+    pub(crate) fn with_base_url(base_url: impl Into<String>, delay: Duration) -> Self {
         Self {
             http: reqwest::Client::builder()
                 .cookie_store(true)
                 .timeout(Duration::from_secs(30))
                 .build()
                 .expect("Failed to build reqwest client"),
-            delay: Duration::from_millis(1500),
+            base_url: base_url.into(),
+            delay,
         }
     }
 
@@ -285,15 +303,18 @@ impl BlueBookClient {
     async fn fetch_subjects(&self) -> Result<(Vec<SubjectEntry>, FormFields)> {
         let resp = self
             .http
-            .get(BASE_URL)
+            .get(&self.base_url)
             .send()
             .await
             .context("Failed to GET BlueBook page")?;
 
         let body = resp.text().await?;
-        let html = Html::parse_document(&body);
-        let fields = Self::extract_form_fields(&html)?;
-        let subjects = Self::parse_subjects(&html);
+        let (subjects, fields) = ParsePool::global()
+            .run(move || {
+                let html = Html::parse_document(&body);
+                Self::extract_form_fields(&html).map(|fields| (Self::parse_subjects(&html), fields))
+            })
+            .await??;
 
         info!(count = subjects.len(), "Fetched BlueBook subject list");
         Ok((subjects, fields))
@@ -333,12 +354,12 @@ impl BlueBookClient {
         subjects
     }
 
-    /// POST a search filtered by subject, returning the response HTML and updated form fields.
+    /// POST a search filtered by subject, returning the updated form fields.
     async fn search_subject(
         &self,
         subject: &SubjectEntry,
         fields: &FormFields,
-    ) -> Result<(Html, FormFields)> {
+    ) -> Result<FormFields> {
         tokio::time::sleep(self.delay).await;
 
         let index_str = subject.combo_index.to_string();
@@ -359,25 +380,20 @@ impl BlueBookClient {
 
         let resp = self
             .http
-            .post(BASE_URL)
+            .post(&self.base_url)
             .form(&params)
             .send()
             .await
             .context("Failed to POST BlueBook search")?;
 
         let body = resp.text().await?;
-        let html = Html::parse_document(&body);
-        let new_fields = Self::extract_form_fields(&html)?;
-
-        Ok((html, new_fields))
+        ParsePool::global()
+            .run(move || Self::extract_form_fields(&Html::parse_document(&body)))
+            .await?
     }
 
-    /// POST to switch the term filter (CURRENT/ALL/PAST/FUTURE) and return the updated page.
-    async fn switch_term_filter(
-        &self,
-        filter: &str,
-        fields: &FormFields,
-    ) -> Result<(Html, FormFields)> {
+    /// POST to switch the term filter (CURRENT/ALL/PAST/FUTURE) and return the page body.
+    async fn switch_term_filter(&self, filter: &str, fields: &FormFields) -> Result<String> {
         tokio::time::sleep(self.delay).await;
 
         let radio_index = match filter {
@@ -393,7 +409,7 @@ impl BlueBookClient {
 
         let resp = self
             .http
-            .post(BASE_URL)
+            .post(&self.base_url)
             .form(&params)
             .send()
             .await
@@ -407,14 +423,11 @@ impl BlueBookClient {
             body_len = body.len(),
             "switch_term_filter response"
         );
-        let html = Html::parse_document(&body);
-        let new_fields = Self::extract_form_fields(&html)?;
-
-        Ok((html, new_fields))
+        Ok(body)
     }
 
-    /// POST to navigate to the next page of results.
-    async fn next_page(&self, fields: &FormFields, top: bool) -> Result<(Html, FormFields)> {
+    /// POST to navigate to the next page of results, returning the page body.
+    async fn next_page(&self, fields: &FormFields, top: bool) -> Result<String> {
         tokio::time::sleep(self.delay).await;
 
         // Pager buttons are `<input type="image">`, which submit as `name.x=N&name.y=N`
@@ -433,17 +446,56 @@ impl BlueBookClient {
 
         let resp = self
             .http
-            .post(BASE_URL)
+            .post(&self.base_url)
             .form(&params)
             .send()
             .await
             .context("Failed to navigate BlueBook page")?;
 
-        let body = resp.text().await?;
-        let html = Html::parse_document(&body);
-        let new_fields = Self::extract_form_fields(&html)?;
+        resp.text()
+            .await
+            .context("Failed to read BlueBook page body")
+    }
+
+    /// Parse a results page on the worker pool.
+    ///
+    /// Returns once the form fields for the next request are extracted, so the
+    /// following fetch overlaps with parsing this page's evaluations, which are
+    /// sent to `evals` when done.
+    async fn parse_page(
+        body: String,
+        subject: &str,
+        evals: &mpsc::UnboundedSender<Vec<BlueBookEvaluation>>,
+    ) -> Result<PageState> {
+        let (state_tx, state_rx) = oneshot::channel();
+        let subject = subject.to_owned();
+        let evals = evals.clone();
+
+        tokio::spawn(async move {
+            let parsed = ParsePool::global()
+                .run(move || {
+                    let html = Html::parse_document(&body);
+                    let state = Self::extract_form_fields(&html).map(|fields| PageState {
+                        fields,
+                        total_pages: Self::parse_page_info(&html)
+                            .map(|(_, total)| total)
+                            .unwrap_or(1),
+                    });
+                    let ok = state.is_ok();
+                    let _ = state_tx.send(state);
+                    if ok {
+                        let _ = evals.send(Self::parse_evaluations(&html, &subject));
+                    }
+                })
+                .await;
+            if let Err(e) = parsed {
+                warn!(error = %e, "BlueBook page parse failed");
+            }
+        });
 
-        Ok((html, new_fields))
+        state_rx
+            .await
+            .context("BlueBook page parser exited early")?
     }
 
     /// Parse evaluation records from accordion panes on an HTML page.
@@ -707,9 +759,8 @@ impl BlueBookClient {
         for (i, subject) in eligible_subjects.iter().enumerate() {
             let progress = i + 1;
 
-            // Search for the subject (drop Html before next await -- Html is !Send)
             let fields = match self.search_subject(subject, &initial_fields).await {
-                Ok((_html, fields)) => fields,
+                Ok(fields) => fields,
                 Err(e) => {
                     warn!(
                         code = subject.code.as_str(),
@@ -743,17 +794,15 @@ impl BlueBookClient {
                 continue;
             }
 
-            // Switch to PAST courses to get completed evaluations
-            let mut subject_evals = Vec::new();
-            let (total_pages, mut fields) = match self.switch_term_filter("PAST", &fields).await {
-                Ok((html, fields)) => {
-                    let page_evals = Self::parse_evaluations(&html, &subject.code);
-                    subject_evals.extend(page_evals);
-                    let total_pages = Self::parse_page_info(&html)
-                        .map(|(_, total)| total)
-                        .unwrap_or(1);
-                    (total_pages, fields)
-                }
+            // Switch to PAST courses to get completed evaluations. Each page's
+            // evaluations arrive on `evals_rx` while later pages are fetched.
+            let (evals_tx, mut evals_rx) = mpsc::unbounded_channel();
+            let first_page = match self.switch_term_filter("PAST", &fields).await {
+                Ok(body) => Self::parse_page(body, &subject.code, &evals_tx).await,
+                Err(e) => Err(e),
+            };
+            let (total_pages, mut fields) = match first_page {
+                Ok(state) => (state.total_pages, state.fields),
                 Err(e) => {
                     warn!(
                         code = subject.code.as_str(),
@@ -773,12 +822,12 @@ impl BlueBookClient {
                     page, total_pages, "Fetching page"
                 );
 
-                match self.next_page(&fields, true).await {
-                    Ok((page_html, new_fields)) => {
-                        fields = new_fields;
-                        let page_evals = Self::parse_evaluations(&page_html, &subject.code);
-                        subject_evals.extend(page_evals);
-                    }
+                let next = match self.next_page(&fields, true).await {
+                    Ok(body) => Self::parse_page(body, &subject.code, &evals_tx).await,
+                    Err(e) => Err(e),
+                };
+                match next {
+                    Ok(state) => fields = state.fields,
                     Err(e) => {
                         warn!(
                             code = subject.code.as_str(),
@@ -791,6 +840,12 @@ impl BlueBookClient {
                 }
             }
 
+            // Wait for the parses still in flight.
+            drop(evals_tx);
+            let mut subject_evals = Vec::new();
+            while let Some(page_evals) = evals_rx.recv().await {
+                subject_evals.extend(page_evals);
+            }
             let subject_eval_count = subject_evals.len() as u32;
 
             // Upsert immediately so data is available without waiting for the full scrape
@@ -1561,14 +1616,16 @@ mod tests {
             .expect("CS subject must exist");
 
         // Search CS
-        let (_html, fields) = client.search_subject(cs, &initial_fields).await.unwrap();
+        let fields = client.search_subject(cs, &initial_fields).await.unwrap();
         assert!(
             fields.has(TERM_FILTER_RADIO),
             "CS search should render term filter radio"
         );
 
         // Switch to PAST
-        let (html, mut fields) = client.switch_term_filter("PAST", &fields).await.unwrap();
+        let body = client.switch_term_filter("PAST", &fields).await.unwrap();
+        let html = Html::parse_document(&body);
+        let mut fields = BlueBookClient::extract_form_fields(&html).unwrap();
         let page1_evals = BlueBookClient::parse_evaluations(&html, "CS");
         let (current_page, total_pages) =
             BlueBookClient::parse_page_info(&html).expect("PAST results should have pager");
@@ -1584,8 +1641,9 @@ mod tests {
         // starts around page 9-10. Paginate enough to reach them.
         let pages_to_check = 12.min(total_pages);
         for page in 2..=pages_to_check {
-            let (page_html, new_fields) = client.next_page(&fields, true).await.unwrap();
-            fields = new_fields;
+            let body = client.next_page(&fields, true).await.unwrap();
+            let page_html = Html::parse_document(&body);
+            fields = BlueBookClient::extract_form_fields(&page_html).unwrap();
             let page_evals = BlueBookClient::parse_evaluations(&page_html, "CS");
             // Log the semester of the first accordion to track pagination progress
             let semester_sel = Selector::parse("span[id*='SemYrLbl']").unwrap();
@@ -1638,7 +1696,7 @@ mod tests {
             .find(|s| s.code == "BAN")
             .expect("BAN subject must exist");
 
-        let (_html, fields) = client.search_subject(ban, &initial_fields).await.unwrap();
+        let fields = client.search_subject(ban, &initial_fields).await.unwrap();
         assert!(
             !fields.has(TERM_FILTER_RADIO),
             "BAN search should NOT have term filter radio (no current-term results)"
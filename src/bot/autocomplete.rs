@@ -1,60 +1,242 @@
 //! Autocomplete functions for Discord slash command parameters.
+//!
+//! Discord sends an autocomplete request on every keystroke, so results are
+//! cached for a short TTL in an [`AutocompleteCache`] shared by all guilds,
+//! keyed by parameter kind and normalized input. Subjects and terms come from
+//! the reference cache; instructors from the same trigram suggest query the
+//! web search box uses.
 
 use crate::bot::Context;
+use dashmap::DashMap;
 use poise::serenity_prelude as serenity;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How long a set of choices is reused.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Entry count above which expired entries are swept on insert.
+const SWEEP_THRESHOLD: usize = 1024;
+
+/// Discord's cap on autocomplete choices.
+const MAX_CHOICES: usize = 25;
+
+/// Which parameter is being completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AutocompleteKind {
+    Subject,
+    Term,
+    Instructor,
+}
+
+impl AutocompleteKind {
+    /// Reference cache category, or `None` for kinds not backed by it.
+    fn category(self) -> Option<&'static str> {
+        match self {
+            Self::Subject => Some("subject"),
+            Self::Term => Some("term"),
+            Self::Instructor => None,
+        }
+    }
+
+    fn label(self, code: &str, desc: &str) -> String {
+        match self {
+            Self::Subject => format!("{code} - {desc}"),
+            Self::Term | Self::Instructor => format!("{desc} ({code})"),
+        }
+    }
+}
+
+/// `(label, value)` pairs, ready to turn into choices.
+type Choices = Arc<[(String, String)]>;
+
+/// Hit/miss counters for the autocomplete cache.
+#[derive(Debug, Clone, Copy)]
+pub struct AutocompleteStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl AutocompleteStats {
+    /// Fraction of lookups served from cache, or `None` before any lookups.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// Short-lived cache of autocomplete results.
+#[derive(Default)]
+pub struct AutocompleteCache {
+    entries: DashMap<(AutocompleteKind, String), (Instant, Choices)>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl AutocompleteCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fresh choices for `(kind, key)`, counting the lookup as a hit or miss.
+    fn get(&self, kind: AutocompleteKind, key: &str) -> Option<Choices> {
+        let fresh = self
+            .entries
+            .get(&(kind, key.to_owned()))
+            .filter(|entry| entry.0.elapsed() < CACHE_TTL)
+            .map(|entry| entry.1.clone());
+        let counter = if fresh.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        fresh
+    }
+
+    fn insert(&self, kind: AutocompleteKind, key: String, choices: Choices) {
+        if self.entries.len() >= SWEEP_THRESHOLD {
+            self.entries
+                .retain(|_, (cached_at, _)| cached_at.elapsed() < CACHE_TTL);
+        }
+        self.entries.insert((kind, key), (Instant::now(), choices));
+    }
+
+    pub fn stats(&self) -> AutocompleteStats {
+        AutocompleteStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.len(),
+        }
+    }
+}
+
+/// Choices for `partial`, serving repeat inputs from cache. Reference-backed
+/// kinds keep entries whose code or description contains the input
+/// (case-insensitive).
+async fn lookup(ctx: Context<'_>, kind: AutocompleteKind, partial: &str) -> Choices {
+    let key = partial.trim().to_lowercase();
+    let cache = &ctx.data().autocomplete_cache;
+    if let Some(choices) = cache.get(kind, &key) {
+        return choices;
+    }
+
+    let choices: Choices = match kind.category() {
+        Some(category) => {
+            let reference = ctx.data().app_state.reference_cache.read().await;
+            reference
+                .entries_for_category(category)
+                .into_iter()
+                .filter(|(code, desc)| {
+                    key.is_empty()
+                        || code.to_lowercase().contains(&key)
+                        || desc.to_lowercase().contains(&key)
+                })
+                .take(MAX_CHOICES)
+                .map(|(code, desc)| (kind.label(code, desc), code.to_owned()))
+                .collect()
+        }
+        None => match suggest_instructors(ctx, &key).await {
+            Some(choices) => choices,
+            // Don't cache failures; the next keystroke retries.
+            None => return Arc::from([]),
+        },
+    };
+    cache.insert(kind, key, choices.clone());
+    choices
+}
+
+/// Instructor choices labeled "Doe, Jane (12 sections)" with the slug as value.
+async fn suggest_instructors(ctx: Context<'_>, key: &str) -> Option<Choices> {
+    if key.chars().count() < 2 {
+        return Some(Arc::from([]));
+    }
+    let pool = &ctx.data().app_state.db_pool;
+    match crate::data::courses::suggest_instructors_global(pool, None, key, MAX_CHOICES as i32)
+        .await
+    {
+        Ok(suggestions) => Some(
+            suggestions
+                .into_iter()
+                .map(|s| {
+                    let sections = format!("{} sections", s.section_count);
+                    (
+                        AutocompleteKind::Instructor.label(&sections, &s.display_name),
+                        s.slug,
+                    )
+                })
+                .collect(),
+        ),
+        Err(e) => {
+            warn!(error = ?e, "instructor autocomplete failed");
+            None
+        }
+    }
+}
+
+fn to_choices(choices: Choices) -> impl Iterator<Item = serenity::AutocompleteChoice> {
+    choices
+        .iter()
+        .map(|(label, value)| serenity::AutocompleteChoice::new(label.clone(), value.clone()))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
 
 /// Autocomplete for the subject parameter.
 ///
-/// Filters reference cache entries where code or description contains the
-/// partial input (case-insensitive). Returns up to 25 choices formatted as
-/// "CS - Computer Science" with the subject code as the value.
+/// Returns up to 25 choices formatted as "CS - Computer Science" with the
+/// subject code as the value.
 pub async fn autocomplete_subject<'a>(
     ctx: Context<'_>,
     partial: &'a str,
 ) -> impl Iterator<Item = serenity::AutocompleteChoice> + 'a {
-    let cache = ctx.data().app_state.reference_cache.read().await;
-    let entries = cache.entries_for_category("subject");
-    let partial_lower = partial.to_lowercase();
+    to_choices(lookup(ctx, AutocompleteKind::Subject, partial).await)
+}
 
-    entries
-        .into_iter()
-        .filter(move |(code, desc)| {
-            partial_lower.is_empty()
-                || code.to_lowercase().contains(&partial_lower)
-                || desc.to_lowercase().contains(&partial_lower)
-        })
-        .take(25)
-        .map(|(code, desc)| {
-            serenity::AutocompleteChoice::new(format!("{code} - {desc}"), code.to_owned())
-        })
-        .collect::<Vec<_>>()
-        .into_iter()
+/// Autocomplete for instructor parameters.
+///
+/// Returns up to 25 choices formatted as "Doe, Jane (12 sections)" with the
+/// instructor's slug as the value.
+pub async fn autocomplete_instructor<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = serenity::AutocompleteChoice> + 'a {
+    to_choices(lookup(ctx, AutocompleteKind::Instructor, partial).await)
 }
 
 /// Autocomplete for the term parameter.
 ///
-/// Filters reference cache entries where code or description contains the
-/// partial input (case-insensitive). Returns up to 25 choices formatted as
-/// "Spring 2026 (202620)" with the term code as the value.
+/// Returns up to 25 choices formatted as "Spring 2026 (202620)" with the
+/// term code as the value.
 pub async fn autocomplete_term<'a>(
     ctx: Context<'_>,
     partial: &'a str,
 ) -> impl Iterator<Item = serenity::AutocompleteChoice> + 'a {
-    let cache = ctx.data().app_state.reference_cache.read().await;
-    let entries = cache.entries_for_category("term");
-    let partial_lower = partial.to_lowercase();
+    to_choices(lookup(ctx, AutocompleteKind::Term, partial).await)
+}
 
-    entries
-        .into_iter()
-        .filter(move |(code, desc)| {
-            partial_lower.is_empty()
-                || code.to_lowercase().contains(&partial_lower)
-                || desc.to_lowercase().contains(&partial_lower)
-        })
-        .take(25)
-        .map(|(code, desc)| {
-            serenity::AutocompleteChoice::new(format!("{desc} ({code})"), code.to_owned())
-        })
-        .collect::<Vec<_>>()
-        .into_iter()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let cache = AutocompleteCache::new();
+        assert_eq!(cache.stats().hit_rate(), None);
+
+        assert!(cache.get(AutocompleteKind::Subject, "cs").is_none());
+        let choices: Choices = vec![("CS - Computer Science".to_owned(), "CS".to_owned())].into();
+        cache.insert(AutocompleteKind::Subject, "cs".to_owned(), choices);
+
+        assert_eq!(cache.get(AutocompleteKind::Subject, "cs").unwrap().len(), 1);
+        // Keys are per kind.
+        assert!(cache.get(AutocompleteKind::Term, "cs").is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+        assert_eq!(stats.hit_rate(), Some(1.0 / 3.0));
+    }
 }
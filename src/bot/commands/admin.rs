@@ -0,0 +1,143 @@
+//! Operator commands: /admin status, /admin scrape
+//!
+//! Restricted to the configured admin user/role IDs and to users flagged as
+//! admins for the web dashboard. Both subcommands call the same functions as
+//! the admin web API.
+
+use crate::banner::Term;
+use crate::bot::{Context, Error};
+use crate::data::DbContext;
+use crate::scraper::ManualScrapeError;
+use crate::state::ServiceStatus;
+use num_format::{Locale, ToFormattedString};
+use tracing::info;
+
+/// Allow the invocation if the author is a configured admin user, holds a
+/// configured admin role, or is a web admin.
+async fn require_admin(ctx: Context<'_>) -> Result<bool, Error> {
+    let access = &ctx.data().admin_access;
+    let author_id = ctx.author().id.get();
+
+    let mut allowed = access.user_ids.contains(&author_id);
+
+    if !allowed
+        && !access.role_ids.is_empty()
+        && let Some(member) = ctx.author_member().await
+    {
+        allowed = member
+            .roles
+            .iter()
+            .any(|role| access.role_ids.contains(&role.get()));
+    }
+
+    if !allowed {
+        let pool = &ctx.data().app_state.db_pool;
+        allowed = crate::data::users::get_user_by_discord(pool, author_id as i64)
+            .await?
+            .is_some_and(|user| user.is_admin);
+    }
+
+    if !allowed {
+        ctx.send(
+            poise::CreateReply::default()
+                .content("You don't have permission to use admin commands.")
+                .ephemeral(true),
+        )
+        .await?;
+    }
+    Ok(allowed)
+}
+
+/// Operator commands for managing the scraper.
+///
+/// The permission check lives on each subcommand so a denied user gets a
+/// single reply.
+#[poise::command(slash_command, subcommands("status", "scrape"), subcommand_required)]
+pub async fn admin(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Show system counts and service health.
+#[poise::command(slash_command, check = "require_admin")]
+pub async fn status(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let status = crate::web::admin::load_admin_status(&ctx.data().app_state).await;
+    let count =
+        |n: Option<i64>| n.map_or_else(|| "?".to_string(), |n| n.to_formatted_string(&Locale::en));
+
+    let mut services = status.services;
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+    let service_lines = services
+        .iter()
+        .map(|s| format!("- {} {}: {:?}", status_icon(&s.status), s.name, s.status))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let autocomplete = ctx.data().autocomplete_cache.stats();
+    let hit_rate = autocomplete
+        .hit_rate()
+        .map_or_else(|| "n/a".to_string(), |r| format!("{:.0}%", r * 100.0));
+
+    ctx.say(format!(
+        "**Courses:** {}\n**Scrape jobs queued:** {}\n**Users:** {} ({} active sessions)\n**Autocomplete cache:** {} hit rate ({} hits, {} misses, {} entries)\n\n**Services**\n{}",
+        count(status.course_count),
+        count(status.scrape_job_count),
+        count(status.user_count),
+        count(status.session_count),
+        hit_rate,
+        autocomplete.hits.to_formatted_string(&Locale::en),
+        autocomplete.misses.to_formatted_string(&Locale::en),
+        autocomplete.entries,
+        service_lines,
+    ))
+    .await?;
+    Ok(())
+}
+
+fn status_icon(status: &ServiceStatus) -> &'static str {
+    match status {
+        ServiceStatus::Active | ServiceStatus::Connected => "🟢",
+        ServiceStatus::Starting => "🟡",
+        ServiceStatus::Disabled => "⚪",
+        ServiceStatus::Error => "🔴",
+    }
+}
+
+/// Queue an immediate high-priority scrape of a subject.
+#[poise::command(slash_command, check = "require_admin")]
+pub async fn scrape(
+    ctx: Context<'_>,
+    #[description = "Subject code (e.g. CS)"] subject: String,
+    #[description = "Term code (e.g. 202620 -- defaults to current)"] term: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let state = &ctx.data().app_state;
+    let term_code = term.unwrap_or_else(|| Term::get_current().inner().to_string());
+    let db = DbContext::new(state.db_pool.clone(), state.events.clone());
+    let subject = subject.trim().to_uppercase();
+
+    let reply = match crate::scraper::enqueue_subject_scrape(&db, &term_code, &subject).await {
+        Ok(Some(job)) => format!(
+            "Queued scrape of **{subject}** for term **{term_code}** (job #{}).",
+            job.id
+        ),
+        Ok(None) => {
+            format!("A scrape of **{subject}** for term **{term_code}** is already queued.")
+        }
+        Err(e) => match e.downcast_ref::<ManualScrapeError>() {
+            Some(err) => err.to_string(),
+            None => return Err(e),
+        },
+    };
+
+    info!(
+        subject = %subject,
+        term = %term_code,
+        admin = %ctx.author().tag(),
+        "Bot admin requested subject scrape"
+    );
+    ctx.say(reply).await?;
+    Ok(())
+}
@@ -20,6 +20,7 @@ pub async fn gcal(
 
     let course = utils::get_course_by_crn(&ctx, crn).await?;
     let term = course.term.clone();
+    let prefs = utils::display_preferences(&ctx).await;
 
     // Get meeting times
     let meeting_times = ctx
@@ -48,7 +49,12 @@ pub async fn gcal(
                     let days = m.days_string().unwrap_or_else(|| "TBA".to_string());
                     let detail = match &m.time_range {
                         Some(range) => {
-                            format!("{days} {}", range.format_12hr())
+                            let times = prefs.format_campus_range(
+                                m.date_range.start,
+                                range.start,
+                                range.end,
+                            );
+                            format!("{days} {times}")
                         }
                         None => days,
                     };
@@ -2,6 +2,7 @@
 
 use crate::banner::{Course, MeetingDays, MeetingScheduleInfo, WeekdayExt};
 use crate::bot::{Context, Error, utils};
+use crate::display::DisplayPreferences;
 use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
 use serenity::all::CreateAttachment;
 use tracing::info;
@@ -87,6 +88,7 @@ pub async fn ics(
 
     let course = utils::get_course_by_crn(&ctx, crn).await?;
     let term = course.term.clone();
+    let prefs = utils::display_preferences(&ctx).await;
 
     // Get meeting times
     let meeting_times = ctx
@@ -107,7 +109,7 @@ pub async fn ics(
 
     // Generate ICS content
     let (ics_content, excluded_holidays) =
-        generate_ics_content(&course, &term, &sorted_meeting_times)?;
+        generate_ics_content(&course, &term, &sorted_meeting_times, &prefs)?;
 
     // Create file attachment
     let filename = format!(
@@ -131,7 +133,7 @@ pub async fn ics(
                     Some(range) => format!(
                         "{} {}",
                         m.days_string().unwrap_or("TBA".to_string()),
-                        range.format_12hr()
+                        prefs.format_campus_range(m.date_range.start, range.start, range.end)
                     ),
                     None => m.days_string().unwrap_or("TBA".to_string()),
                 };
@@ -175,6 +177,7 @@ fn generate_ics_content(
     course: &Course,
     term: &str,
     meeting_times: &[MeetingScheduleInfo],
+    prefs: &DisplayPreferences,
 ) -> Result<(String, Vec<String>), anyhow::Error> {
     let mut ics_content = String::new();
     let mut excluded_holidays = Vec::new();
@@ -192,10 +195,11 @@ fn generate_ics_content(
         course.display_title(),
         term
     ));
+    ics_content.push_str(&format!("X-WR-TIMEZONE:{}\r\n", prefs.tz().name()));
 
     // Generate events for each meeting time
     for (index, meeting_time) in meeting_times.iter().enumerate() {
-        let (event_content, holidays) = generate_event_content(course, meeting_time, index)?;
+        let (event_content, holidays) = generate_event_content(course, meeting_time, index, prefs)?;
         ics_content.push_str(&event_content);
         excluded_holidays.extend(holidays);
     }
@@ -211,6 +215,7 @@ fn generate_event_content(
     course: &Course,
     meeting_time: &MeetingScheduleInfo,
     index: usize,
+    prefs: &DisplayPreferences,
 ) -> Result<(String, Vec<String>), anyhow::Error> {
     let course_title = course.display_title();
     let instructor_name = course.primary_instructor_name();
@@ -223,12 +228,20 @@ fn generate_event_content(
         course_title
     };
 
+    let time_display = match &meeting_time.time_range {
+        Some(range) => {
+            prefs.format_campus_range(meeting_time.date_range.start, range.start, range.end)
+        }
+        None => "TBA".to_string(),
+    };
+
     // Create event description
     let description = format!(
-        "CRN: {}\\nInstructor: {}\\nDays: {}\\nMeeting Type: {}",
+        "CRN: {}\\nInstructor: {}\\nDays: {}\\nTime: {}\\nMeeting Type: {}",
         course.course_reference_number,
         instructor_name,
         meeting_time.days_string().unwrap_or("TBA".to_string()),
+        time_display,
         meeting_time.meeting_type.description()
     );
 
@@ -1,13 +1,19 @@
 //! Bot commands module.
 
+pub mod admin;
 pub mod gcal;
 pub mod ics;
+pub mod professor;
+pub mod remind;
 pub mod search;
 pub mod terms;
 pub mod watch;
 
+pub use admin::admin;
 pub use gcal::gcal;
 pub use ics::ics;
+pub use professor::professor;
+pub use remind::{reminders, remindme, unremind};
 pub use search::search;
 pub use terms::terms;
 pub use watch::{unwatch, watch, watches};
@@ -0,0 +1,285 @@
+//! Professor lookup command implementation.
+
+use crate::banner::Term;
+use crate::bot::autocomplete::autocomplete_instructor;
+use crate::bot::{Context, Error};
+use crate::data::courses::{InstructorSuggestion, suggest_instructors_global};
+use crate::data::instructors::{
+    PublicInstructorProfile, get_instructor_sections, get_public_instructor_by_slug,
+};
+use crate::data::models::Course;
+use crate::data::names::{MatchCandidate, find_best_candidate};
+use poise::serenity_prelude::{Color, CreateEmbed};
+use tracing::info;
+
+/// Candidates considered when the input isn't an exact slug.
+const CANDIDATE_LIMIT: i32 = 10;
+
+/// Sections listed before collapsing the rest into "and N more".
+const MAX_SECTIONS: usize = 10;
+
+/// Look up an instructor's ratings and current sections
+#[poise::command(slash_command, prefix_command)]
+pub async fn professor(
+    ctx: Context<'_>,
+    #[description = "Instructor name"]
+    #[autocomplete = "autocomplete_instructor"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let state = &ctx.data().app_state;
+    let name = name.trim();
+
+    // Autocomplete submits the slug; typed names go through matching.
+    let profile = match get_public_instructor_by_slug(&state.db_pool, name).await? {
+        Some(profile) => Some(profile),
+        None => {
+            let suggestions =
+                suggest_instructors_global(&state.db_pool, None, name, CANDIDATE_LIMIT).await?;
+            let slug = match resolve(name, &suggestions) {
+                Resolved::One(slug) => slug,
+                Resolved::None => {
+                    ctx.say(format!("No instructor found matching `{name}`."))
+                        .await?;
+                    return Ok(());
+                }
+                Resolved::Ambiguous(names) => {
+                    let list = names
+                        .iter()
+                        .map(|n| format!("- {n}"))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    ctx.say(format!(
+                        "Several instructors match `{name}`. Did you mean:\n{list}"
+                    ))
+                    .await?;
+                    return Ok(());
+                }
+            };
+            get_public_instructor_by_slug(&state.db_pool, &slug).await?
+        }
+    };
+
+    let Some(profile) = profile else {
+        ctx.say(format!("No instructor found matching `{name}`."))
+            .await?;
+        return Ok(());
+    };
+    let instructor = profile.instructor;
+
+    let term = Term::get_current().inner().to_string();
+    let sections = get_instructor_sections(&state.db_pool, instructor.id, &term).await?;
+
+    let profile_url = state.public_origin.as_deref().map(|origin| {
+        format!(
+            "{}/instructors/{}",
+            origin.trim_end_matches('/'),
+            instructor.slug
+        )
+    });
+    let embed = build_embed(&instructor, &sections, profile_url);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    info!(slug = %instructor.slug, "professor command completed");
+    Ok(())
+}
+
+/// Outcome of matching typed input against suggest results.
+#[derive(Debug, PartialEq)]
+enum Resolved {
+    One(String),
+    Ambiguous(Vec<String>),
+    None,
+}
+
+/// Pick a single instructor from suggestions, preferring a name match.
+///
+/// Falls back to the only suggestion when there is exactly one, and to a
+/// "did you mean" list when name matching can't break a tie.
+fn resolve(input: &str, suggestions: &[InstructorSuggestion]) -> Resolved {
+    let candidates: Vec<MatchCandidate> = suggestions
+        .iter()
+        .map(|s| MatchCandidate {
+            instructor_id: s.id,
+            display_name: s.display_name.clone(),
+        })
+        .collect();
+
+    if let Some(best) = find_best_candidate(&to_last_first(input), &candidates)
+        && let Some(s) = suggestions.iter().find(|s| s.id == best.instructor_id)
+    {
+        return Resolved::One(s.slug.clone());
+    }
+
+    match suggestions {
+        [] => Resolved::None,
+        [only] => Resolved::One(only.slug.clone()),
+        many => Resolved::Ambiguous(many.iter().map(|s| s.display_name.clone()).collect()),
+    }
+}
+
+/// Convert "First Last" input to the "Last, First" form name matching expects.
+/// Input that already has a comma is returned as-is.
+fn to_last_first(input: &str) -> String {
+    let input = input.trim();
+    if input.contains(',') {
+        return input.to_owned();
+    }
+    match input.rsplit_once(char::is_whitespace) {
+        Some((first, last)) => format!("{}, {}", last.trim(), first.trim()),
+        None => input.to_owned(),
+    }
+}
+
+/// Embed color from the composite score on a 1-5 scale.
+fn score_color(score: Option<f32>) -> Color {
+    match score {
+        Some(s) if s >= 4.0 => Color::new(0x22c55e),
+        Some(s) if s >= 3.0 => Color::new(0xeab308),
+        Some(_) => Color::new(0xef4444),
+        None => Color::new(0x6b7280),
+    }
+}
+
+fn format_section(course: &Course) -> String {
+    let section = course
+        .sequence_number
+        .as_deref()
+        .map(|seq| format!("-{seq}"))
+        .unwrap_or_default();
+    format!(
+        "`{}` {} {}{}: {} ({}/{})",
+        course.crn,
+        course.subject,
+        course.course_number,
+        section,
+        course.title,
+        course.enrollment,
+        course.max_enrollment
+    )
+}
+
+fn format_sections(sections: &[Course]) -> String {
+    if sections.is_empty() {
+        return "Not teaching this term.".to_owned();
+    }
+    let mut lines: Vec<String> = sections
+        .iter()
+        .take(MAX_SECTIONS)
+        .map(format_section)
+        .collect();
+    if sections.len() > MAX_SECTIONS {
+        lines.push(format!("and {} more", sections.len() - MAX_SECTIONS));
+    }
+    lines.join("\n")
+}
+
+fn build_embed(
+    instructor: &PublicInstructorProfile,
+    sections: &[Course],
+    profile_url: Option<String>,
+) -> CreateEmbed {
+    let rating = instructor.rating.as_ref();
+    let mut embed = CreateEmbed::new()
+        .title(&instructor.display_name)
+        .color(score_color(rating.map(|r| r.score)));
+    if let Some(url) = profile_url {
+        embed = embed.url(url);
+    }
+    if !instructor.subjects.is_empty() {
+        embed = embed.description(instructor.subjects.join(", "));
+    }
+
+    let score = match rating {
+        Some(r) => format!(
+            "**{:.1}** / 5 ({:.1}-{:.1}, {} responses, {})",
+            r.score,
+            r.ci_lower,
+            r.ci_upper,
+            r.total_responses,
+            r.source.as_str()
+        ),
+        None => "Not enough data".to_owned(),
+    };
+    embed = embed.field("Score", score, false);
+
+    if let Some(rmp) = &instructor.rmp {
+        let mut parts = Vec::new();
+        if let Some(avg) = rmp.avg_rating {
+            parts.push(format!("{avg:.1} rating"));
+        }
+        if let Some(difficulty) = rmp.avg_difficulty {
+            parts.push(format!("{difficulty:.1} difficulty"));
+        }
+        if let Some(pct) = rmp.would_take_again_pct {
+            parts.push(format!("{pct:.0}% would take again"));
+        }
+        if let Some(n) = rmp.num_ratings {
+            parts.push(format!("{n} ratings"));
+        }
+        parts.push(format!(
+            "[profile](https://www.ratemyprofessors.com/professor/{})",
+            rmp.legacy_id
+        ));
+        embed = embed.field("RateMyProfessors", parts.join(" · "), true);
+    }
+
+    if let Some(bb) = &instructor.bluebook {
+        let mut value = format!("{:.2} instructor", bb.avg_instructor_rating);
+        if let Some(course_rating) = bb.avg_course_rating {
+            value.push_str(&format!(" · {course_rating:.2} course"));
+        }
+        value.push_str(&format!(
+            " · {} responses over {} evaluations",
+            bb.total_responses, bb.eval_count
+        ));
+        embed = embed.field("BlueBook", value, true);
+    }
+
+    embed.field("This term", format_sections(sections), false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion(id: i32, slug: &str, name: &str) -> InstructorSuggestion {
+        InstructorSuggestion {
+            id,
+            slug: slug.to_owned(),
+            display_name: name.to_owned(),
+            section_count: 1,
+            score: 0.5,
+        }
+    }
+
+    #[test]
+    fn converts_first_last_for_matching() {
+        assert_eq!(to_last_first("Jane Doe"), "Doe, Jane");
+        assert_eq!(to_last_first("Mary Ann Smith"), "Smith, Mary Ann");
+        assert_eq!(to_last_first("Doe, Jane"), "Doe, Jane");
+        assert_eq!(to_last_first("Doe"), "Doe");
+    }
+
+    #[test]
+    fn resolves_name_match_over_other_suggestions() {
+        let suggestions = [
+            suggestion(1, "jane-doe", "Doe, Jane"),
+            suggestion(2, "john-doe", "Doe, John"),
+        ];
+        assert_eq!(
+            resolve("Jane Doe", &suggestions),
+            Resolved::One("jane-doe".to_owned())
+        );
+        assert_eq!(
+            resolve("Doe", &suggestions),
+            Resolved::Ambiguous(vec!["Doe, Jane".to_owned(), "Doe, John".to_owned()])
+        );
+        assert_eq!(resolve("Nobody", &[]), Resolved::None);
+        assert_eq!(
+            resolve("Doe", &suggestions[..1]),
+            Resolved::One("jane-doe".to_owned())
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Registration reminder commands: /remindme, /unremind, /reminders
+
+use crate::banner::Term;
+use crate::bot::utils::{ensure_invoking_user, invoking_user_id};
+use crate::bot::{Context, Error};
+use crate::data::reminders::{self, Classification};
+use crate::data::terms;
+
+/// Classification choices for Discord slash command parameters.
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum ClassificationChoice {
+    Graduate,
+    Senior,
+    Junior,
+    Sophomore,
+    Freshman,
+    #[name = "Open Registration"]
+    Open,
+}
+
+impl From<ClassificationChoice> for Classification {
+    fn from(c: ClassificationChoice) -> Self {
+        match c {
+            ClassificationChoice::Graduate => Classification::Graduate,
+            ClassificationChoice::Senior => Classification::Senior,
+            ClassificationChoice::Junior => Classification::Junior,
+            ClassificationChoice::Sophomore => Classification::Sophomore,
+            ClassificationChoice::Freshman => Classification::Freshman,
+            ClassificationChoice::Open => Classification::Open,
+        }
+    }
+}
+
+fn classification_label(classification: &str) -> &'static str {
+    classification
+        .parse::<Classification>()
+        .map(|c| c.label())
+        .unwrap_or("Unknown")
+}
+
+/// Get a DM when registration opens for your classification.
+#[poise::command(slash_command, prefix_command)]
+pub async fn remindme(
+    ctx: Context<'_>,
+    #[description = "Term code or slug (e.g. 202620 or spring-2026)"] term: String,
+    #[description = "Your classification"] classification: ClassificationChoice,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let pool = &ctx.data().app_state.db_pool;
+    let Some(term_code) = Term::resolve_to_code(&term) else {
+        ctx.say(format!("**{}** is not a valid term.", term))
+            .await?;
+        return Ok(());
+    };
+    let Some(db_term) = terms::get_term_by_code(pool, &term_code).await? else {
+        ctx.say(format!("Term **{}** is not available yet.", term_code))
+            .await?;
+        return Ok(());
+    };
+    let classification = Classification::from(classification);
+
+    let user_id = ensure_invoking_user(&ctx).await?;
+
+    let is_new = reminders::upsert_reminder(pool, user_id, &term_code, classification).await?;
+
+    let opens_at = reminders::list_windows(pool, &term_code)
+        .await?
+        .into_iter()
+        .find(|w| w.classification == classification.as_str())
+        .map(|w| w.opens_at);
+    let when = match opens_at {
+        Some(at) => format!(" Registration opens <t:{}:F>.", at.timestamp()),
+        None => " The registration date hasn't been published yet.".to_owned(),
+    };
+
+    let verb = if is_new {
+        "Reminder set!"
+    } else {
+        "Reminder re-armed."
+    };
+    ctx.say(format!(
+        "{} I'll DM you when **{}** registration opens for **{}**.{}",
+        verb,
+        classification.label(),
+        db_term.description,
+        when
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Cancel your registration reminders for a term.
+#[poise::command(slash_command, prefix_command)]
+pub async fn unremind(
+    ctx: Context<'_>,
+    #[description = "Term code or slug (e.g. 202620 or spring-2026)"] term: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let pool = &ctx.data().app_state.db_pool;
+    let Some(term_code) = Term::resolve_to_code(&term) else {
+        ctx.say(format!("**{}** is not a valid term.", term))
+            .await?;
+        return Ok(());
+    };
+    let removed = match invoking_user_id(&ctx).await? {
+        Some(user_id) => reminders::delete_reminders(pool, user_id, &term_code).await?,
+        None => 0,
+    };
+    if removed == 0 {
+        ctx.say(format!("No reminders found for term **{}**.", term_code))
+            .await?;
+    } else {
+        ctx.say(format!(
+            "Removed {} reminder(s) for term **{}**.",
+            removed, term_code
+        ))
+        .await?;
+    }
+    Ok(())
+}
+
+/// List your registration reminders.
+#[poise::command(slash_command, prefix_command)]
+pub async fn reminders(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let pool = &ctx.data().app_state.db_pool;
+    let items = match invoking_user_id(&ctx).await? {
+        Some(user_id) => reminders::list_reminders(pool, user_id).await?,
+        None => Vec::new(),
+    };
+    if items.is_empty() {
+        ctx.say("You have no registration reminders. Use `/remindme` to add one.")
+            .await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = items
+        .iter()
+        .map(|r| {
+            let status = match (r.notified_at, r.opens_at) {
+                (Some(sent), _) => format!("sent <t:{}:R>", sent.timestamp()),
+                (None, Some(at)) => format!("opens <t:{}:F>", at.timestamp()),
+                (None, None) => "date not published yet".to_owned(),
+            };
+            format!(
+                "**{}** ({}) -- {}, {}",
+                r.term_description,
+                r.term_code,
+                classification_label(&r.classification),
+                status
+            )
+        })
+        .collect();
+
+    ctx.say(format!(
+        "Your registration reminders ({}):\n{}",
+        items.len(),
+        lines.join("\n")
+    ))
+    .await?;
+    Ok(())
+}
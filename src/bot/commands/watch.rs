@@ -1,6 +1,7 @@
 //! Course watch commands: /watch, /unwatch, /watches
 
 use crate::banner::Term;
+use crate::bot::utils::{ensure_invoking_user, invoking_user_id};
 use crate::bot::{Context, Error};
 use crate::data::courses::get_id_by_crn;
 use crate::data::watches::{self, WatchType};
@@ -14,6 +15,10 @@ pub enum WatchTypeChoice {
     WaitlistOpen,
     #[name = "Any Change"]
     AnyChange,
+    #[name = "Attribute Change"]
+    AttributeChange,
+    #[name = "Restriction Change"]
+    RestrictionChange,
 }
 
 impl From<WatchTypeChoice> for WatchType {
@@ -22,6 +27,8 @@ impl From<WatchTypeChoice> for WatchType {
             WatchTypeChoice::SeatsAvailable => WatchType::SeatsAvailable,
             WatchTypeChoice::WaitlistOpen => WatchType::WaitlistOpen,
             WatchTypeChoice::AnyChange => WatchType::AnyChange,
+            WatchTypeChoice::AttributeChange => WatchType::AttributeChange,
+            WatchTypeChoice::RestrictionChange => WatchType::RestrictionChange,
         }
     }
 }
@@ -31,6 +38,8 @@ fn watch_type_label(watch_type: &str) -> &'static str {
         "seats_available" => "Seats Available",
         "waitlist_open" => "Waitlist Open",
         "any_change" => "Any Change",
+        "attribute_change" => "Attribute Change",
+        "restriction_change" => "Restriction Change",
         _ => "Unknown",
     }
 }
@@ -63,13 +72,9 @@ pub async fn watch(
         }
     };
 
-    let author = ctx.author();
-    let discord_user_id = author.id.get() as i64;
-    let discord_username = author.tag();
+    let user_id = ensure_invoking_user(&ctx).await?;
 
-    watches::ensure_user(pool, discord_user_id, &discord_username).await?;
-
-    let is_new = watches::upsert_watch(pool, discord_user_id, course_id, &watch_type).await?;
+    let is_new = watches::upsert_watch(pool, user_id, course_id, &watch_type, None).await?;
 
     let label = watch_type_label(watch_type.as_str());
     if is_new {
@@ -101,7 +106,7 @@ pub async fn unwatch(
 
     let pool = &ctx.data().app_state.db_pool;
     let term_code = term.unwrap_or_else(|| Term::get_current().inner().to_string());
-    let discord_user_id = ctx.author().id.get() as i64;
+    let user_id = invoking_user_id(&ctx).await?;
 
     let course_id = match get_id_by_crn(pool, &term_code, &crn).await? {
         Some(id) => id,
@@ -115,12 +120,16 @@ pub async fn unwatch(
         }
     };
 
-    let removed = if let Some(choice) = watch_type {
-        let wt = WatchType::from(choice);
-        let deleted = watches::delete_watch(pool, discord_user_id, course_id, &wt).await?;
-        if deleted { 1u64 } else { 0 }
-    } else {
-        watches::delete_all_watches_for_course(pool, discord_user_id, course_id).await?
+    let removed = match (user_id, watch_type) {
+        (None, _) => 0,
+        (Some(user_id), Some(choice)) => {
+            let wt = WatchType::from(choice);
+            let deleted = watches::delete_watch(pool, user_id, course_id, &wt).await?;
+            if deleted { 1u64 } else { 0 }
+        }
+        (Some(user_id), None) => {
+            watches::delete_all_watches_for_course(pool, user_id, course_id).await?
+        }
     };
 
     if removed == 0 {
@@ -146,9 +155,10 @@ pub async fn watches(ctx: Context<'_>) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
 
     let pool = &ctx.data().app_state.db_pool;
-    let discord_user_id = ctx.author().id.get() as i64;
-
-    let items = watches::list_active_watches(pool, discord_user_id).await?;
+    let items = match invoking_user_id(&ctx).await? {
+        Some(user_id) => watches::list_active_watches(pool, user_id).await?,
+        None => Vec::new(),
+    };
 
     if items.is_empty() {
         ctx.say("You have no active course watches. Use `/watch` to add one.")
@@ -4,11 +4,21 @@ use std::fmt::Write;
 
 pub mod autocomplete;
 pub mod commands;
+pub mod registration;
 pub mod utils;
 
 pub struct Data {
     pub app_state: AppState,
+    pub admin_access: AdminAccess,
+    pub autocomplete_cache: autocomplete::AutocompleteCache,
 } // User data, which is stored and accessible in all command invocations
+
+/// Discord users and roles allowed to run `/admin` commands.
+#[derive(Debug, Clone, Default)]
+pub struct AdminAccess {
+    pub user_ids: Vec<u64>,
+    pub role_ids: Vec<u64>,
+}
 pub type Context<'a> = poise::Context<'a, Data, Error>;
 
 /// Get all available commands
@@ -18,9 +28,14 @@ pub fn get_commands() -> Vec<poise::Command<Data, Error>> {
         commands::terms(),
         commands::ics(),
         commands::gcal(),
+        commands::professor(),
         commands::watch(),
         commands::unwatch(),
         commands::watches(),
+        commands::remindme(),
+        commands::unremind(),
+        commands::reminders(),
+        commands::admin(),
     ]
 }
 
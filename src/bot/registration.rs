@@ -0,0 +1,272 @@
+//! Per-scope slash command registration with change detection.
+//!
+//! Each scope (the target guild, extra guilds, and global) keeps its own
+//! registration record in `app_kv`, so a changed command set is only pushed
+//! where it is stale. With a staged rollout, only the target guild is updated
+//! until the flag is cleared. Guilds that were registered before but are no
+//! longer configured have their commands cleared.
+
+use chrono::{DateTime, Utc};
+use rapidhash::v3::rapidhash_v3;
+use serde::{Deserialize, Serialize};
+use serenity::all::{GuildId, Http};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use ts_rs::TS;
+
+use crate::bot::{Data, Error, commands_fingerprint};
+
+const KV_PREFIX: &str = "bot.commands.";
+
+/// Where a set of commands is registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationScope {
+    Global,
+    Guild(u64),
+}
+
+impl RegistrationScope {
+    fn kv_key(self) -> String {
+        match self {
+            Self::Global => format!("{KV_PREFIX}global"),
+            Self::Guild(id) => format!("{KV_PREFIX}guild.{id}"),
+        }
+    }
+
+    fn from_kv_key(key: &str) -> Option<Self> {
+        match key.strip_prefix(KV_PREFIX)? {
+            "global" => Some(Self::Global),
+            scope => scope.strip_prefix("guild.")?.parse().ok().map(Self::Guild),
+        }
+    }
+}
+
+/// Which scopes to register commands in, in rollout order.
+#[derive(Debug, Clone)]
+pub struct RegistrationPlan {
+    /// Test guild; always registered first.
+    pub target_guild: u64,
+    pub extra_guilds: Vec<u64>,
+    /// Stop after the target guild.
+    pub staged: bool,
+}
+
+impl RegistrationPlan {
+    pub fn scopes(&self) -> Vec<RegistrationScope> {
+        let mut scopes = vec![RegistrationScope::Guild(self.target_guild)];
+        if self.staged {
+            return scopes;
+        }
+        for &id in &self.extra_guilds {
+            if !scopes.contains(&RegistrationScope::Guild(id)) {
+                scopes.push(RegistrationScope::Guild(id));
+            }
+        }
+        scopes.push(RegistrationScope::Global);
+        scopes
+    }
+
+    /// Whether `guild` is configured, including guilds held back by a staged
+    /// rollout.
+    fn has_guild(&self, guild: u64) -> bool {
+        guild == self.target_guild || self.extra_guilds.contains(&guild)
+    }
+
+    /// Registered guilds, from their kv keys, that this plan no longer covers.
+    fn removed_guilds<'a>(&self, registered_keys: impl IntoIterator<Item = &'a str>) -> Vec<u64> {
+        registered_keys
+            .into_iter()
+            .filter_map(|key| match RegistrationScope::from_kv_key(key)? {
+                RegistrationScope::Guild(id) if !self.has_guild(id) => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Last successful registration for one scope.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CommandRegistration {
+    /// Guild ID, or `null` for the global registration.
+    pub guild_id: Option<String>,
+    /// Hash of the command definitions that were registered.
+    pub fingerprint: String,
+    pub command_count: usize,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// Short hash of the command definitions, used for change detection.
+pub fn fingerprint_hash(commands: &[poise::Command<Data, Error>]) -> String {
+    format!(
+        "{:016x}",
+        rapidhash_v3(commands_fingerprint(commands).as_bytes())
+    )
+}
+
+/// Register `commands` in every scope of `plan` whose record is stale.
+///
+/// A failure in the target guild halts the rollout so an unverified command
+/// set never reaches other guilds; failures elsewhere are logged and skipped.
+pub async fn sync_commands(
+    http: &Http,
+    pool: &PgPool,
+    commands: &[poise::Command<Data, Error>],
+    plan: &RegistrationPlan,
+) {
+    let fingerprint = fingerprint_hash(commands);
+    let mut registered = 0usize;
+    let mut unchanged = 0usize;
+
+    for scope in plan.scopes() {
+        let key = scope.kv_key();
+        let stored = match crate::data::kv::get(pool, &key).await {
+            Ok(value) => value.and_then(|v| serde_json::from_str::<CommandRegistration>(&v).ok()),
+            Err(e) => {
+                warn!(error = ?e, ?scope, "Failed to load command registration state");
+                None
+            }
+        };
+        if stored.is_some_and(|r| r.fingerprint == fingerprint) {
+            unchanged += 1;
+            continue;
+        }
+
+        let result = match scope {
+            RegistrationScope::Global => poise::builtins::register_globally(http, commands).await,
+            RegistrationScope::Guild(id) => {
+                poise::builtins::register_in_guild(http, commands, GuildId::new(id)).await
+            }
+        };
+        if let Err(e) = result {
+            warn!(error = ?e, ?scope, "Discord command registration failed");
+            if scope == RegistrationScope::Guild(plan.target_guild) {
+                warn!("Target guild registration failed, halting command rollout");
+                break;
+            }
+            continue;
+        }
+        registered += 1;
+
+        let record = CommandRegistration {
+            guild_id: match scope {
+                RegistrationScope::Global => None,
+                RegistrationScope::Guild(id) => Some(id.to_string()),
+            },
+            fingerprint: fingerprint.clone(),
+            command_count: commands.len(),
+            registered_at: Utc::now(),
+        };
+        let value = serde_json::to_string(&record).expect("registration record serializes");
+        if let Err(e) = crate::data::kv::set(pool, &key, &value).await {
+            warn!(error = ?e, ?scope, "Failed to persist command registration state");
+        }
+    }
+
+    let removed = clear_removed_guilds(http, pool, plan).await;
+
+    info!(
+        commands = commands.len(),
+        registered,
+        unchanged,
+        removed,
+        staged = plan.staged,
+        target_guild = plan.target_guild,
+        "Discord command registration synced"
+    );
+}
+
+/// Clear commands from guilds that have a registration record but are no
+/// longer configured, returning how many were cleared.
+///
+/// The record is only dropped once Discord accepts the empty command set, so a
+/// failed clear is retried on the next startup.
+async fn clear_removed_guilds(http: &Http, pool: &PgPool, plan: &RegistrationPlan) -> usize {
+    let keys = match crate::data::kv::list_prefix(pool, KV_PREFIX).await {
+        Ok(entries) => entries.into_iter().map(|(key, _)| key).collect::<Vec<_>>(),
+        Err(e) => {
+            warn!(error = ?e, "Failed to list command registrations");
+            return 0;
+        }
+    };
+
+    let mut removed = 0usize;
+    for id in plan.removed_guilds(keys.iter().map(String::as_str)) {
+        let scope = RegistrationScope::Guild(id);
+        if let Err(e) = GuildId::new(id).set_commands(http, Vec::new()).await {
+            warn!(error = ?e, ?scope, "Failed to clear commands from removed guild");
+            continue;
+        }
+        if let Err(e) = crate::data::kv::delete(pool, &scope.kv_key()).await {
+            warn!(error = ?e, ?scope, "Failed to delete command registration state");
+        }
+        info!(
+            guild = id,
+            "Cleared commands from guild no longer configured"
+        );
+        removed += 1;
+    }
+    removed
+}
+
+/// All persisted registration records, global first.
+pub async fn list_registrations(pool: &PgPool) -> anyhow::Result<Vec<CommandRegistration>> {
+    let mut records: Vec<CommandRegistration> = crate::data::kv::list_prefix(pool, KV_PREFIX)
+        .await?
+        .into_iter()
+        .filter_map(|(_, value)| serde_json::from_str(&value).ok())
+        .collect();
+    records.sort_by(|a, b| a.guild_id.cmp(&b.guild_id));
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staged_plan_only_targets_test_guild() {
+        let plan = RegistrationPlan {
+            target_guild: 1,
+            extra_guilds: vec![2, 3],
+            staged: true,
+        };
+        assert_eq!(plan.scopes(), vec![RegistrationScope::Guild(1)]);
+    }
+
+    #[test]
+    fn full_plan_registers_target_first_and_global_last() {
+        let plan = RegistrationPlan {
+            target_guild: 1,
+            extra_guilds: vec![2, 1],
+            staged: false,
+        };
+        assert_eq!(
+            plan.scopes(),
+            vec![
+                RegistrationScope::Guild(1),
+                RegistrationScope::Guild(2),
+                RegistrationScope::Global,
+            ]
+        );
+    }
+
+    #[test]
+    fn guilds_dropped_from_config_are_removed() {
+        let plan = RegistrationPlan {
+            target_guild: 1,
+            extra_guilds: vec![2],
+            staged: true,
+        };
+        let keys = [
+            RegistrationScope::Global.kv_key(),
+            RegistrationScope::Guild(1).kv_key(),
+            RegistrationScope::Guild(2).kv_key(),
+            RegistrationScope::Guild(3).kv_key(),
+            format!("{KV_PREFIX}guild.not-a-number"),
+        ];
+        // Guild 2 is held back by the staged rollout, not removed.
+        assert_eq!(plan.removed_guilds(keys.iter().map(String::as_str)), [3]);
+    }
+}
@@ -2,8 +2,9 @@
 
 use crate::banner::{Course, Term};
 use crate::bot::Context;
+use crate::display::DisplayPreferences;
 use anyhow::Result;
-use tracing::error;
+use tracing::{error, warn};
 
 /// Gets a course by its CRN for the current term.
 pub async fn get_course_by_crn(ctx: &Context<'_>, crn: i32) -> Result<Course> {
@@ -23,3 +24,36 @@ pub async fn get_course_by_crn(ctx: &Context<'_>, crn: i32) -> Result<Course> {
             e
         })
 }
+
+/// Display preferences for the invoking user, or campus defaults if they
+/// haven't signed in on the web.
+pub async fn display_preferences(ctx: &Context<'_>) -> DisplayPreferences {
+    let discord_id = ctx.author().id.get() as i64;
+    match crate::data::users::get_user_by_discord(&ctx.data().app_state.db_pool, discord_id).await {
+        Ok(user) => DisplayPreferences::for_user(user.as_ref()),
+        Err(e) => {
+            warn!(error = %e, discord_id, "failed to load display preferences");
+            DisplayPreferences::default()
+        }
+    }
+}
+
+/// The user the invoking Discord account is linked to, if any.
+pub async fn invoking_user_id(ctx: &Context<'_>) -> Result<Option<i64>> {
+    let discord_id = ctx.author().id.get() as i64;
+    let user =
+        crate::data::users::get_user_by_discord(&ctx.data().app_state.db_pool, discord_id).await?;
+    Ok(user.map(|user| user.id))
+}
+
+/// The user the invoking Discord account is linked to, creating one for an
+/// account never seen before.
+pub async fn ensure_invoking_user(ctx: &Context<'_>) -> Result<i64> {
+    let author = ctx.author();
+    crate::data::identities::ensure_discord_user(
+        &ctx.data().app_state.db_pool,
+        author.id.get() as i64,
+        &author.tag(),
+    )
+    .await
+}
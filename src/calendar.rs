@@ -3,6 +3,7 @@
 //! Used by both the Discord bot commands and the web API endpoints.
 
 use crate::data::models::{DayOfWeek, DbMeetingTime};
+use crate::display::{CAMPUS_TZ, DisplayPreferences};
 use chrono::{Datelike, Duration, NaiveDate, Weekday};
 
 /// Course metadata needed for calendar generation (shared interface between bot and web).
@@ -35,6 +36,26 @@ impl CalendarCourse {
     }
 }
 
+/// `VTIMEZONE` for [`CAMPUS_TZ`], which every timed `DTSTART`/`DTEND`/`EXDATE`
+/// references by `TZID`. Uses the US daylight saving rules in effect since 2007.
+const CAMPUS_VTIMEZONE: &str = "BEGIN:VTIMEZONE\r\n\
+TZID:America/Chicago\r\n\
+BEGIN:DAYLIGHT\r\n\
+TZOFFSETFROM:-0600\r\n\
+TZOFFSETTO:-0500\r\n\
+TZNAME:CDT\r\n\
+DTSTART:20070311T020000\r\n\
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\r\n\
+END:DAYLIGHT\r\n\
+BEGIN:STANDARD\r\n\
+TZOFFSETFROM:-0500\r\n\
+TZOFFSETTO:-0600\r\n\
+TZNAME:CST\r\n\
+DTSTART:20071104T020000\r\n\
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r\n\
+END:STANDARD\r\n\
+END:VTIMEZONE\r\n";
+
 /// Convert a `DayOfWeek` to a chrono `Weekday`.
 fn to_weekday(day: &DayOfWeek) -> Weekday {
     match day {
@@ -222,9 +243,43 @@ pub struct IcsResult {
 }
 
 /// Generate an ICS calendar file for a course.
+///
+/// Event times are anchored to campus time so recurrences survive DST; `prefs`
+/// sets the calendar's display timezone and how times read in descriptions.
 pub fn generate_ics(
     course: &CalendarCourse,
     meeting_times: &[DbMeetingTime],
+    prefs: &DisplayPreferences,
+) -> Result<IcsResult, anyhow::Error> {
+    build_ics(
+        &course.display_title(),
+        &course.filename_stem(),
+        [(course, meeting_times)],
+        prefs,
+    )
+}
+
+/// Generate one ICS calendar holding every course in a schedule, named
+/// `calendar_name` and saved as `{filename_stem}.ics`.
+pub fn generate_schedule_ics(
+    calendar_name: &str,
+    filename_stem: &str,
+    courses: &[(CalendarCourse, Vec<DbMeetingTime>)],
+    prefs: &DisplayPreferences,
+) -> Result<IcsResult, anyhow::Error> {
+    build_ics(
+        calendar_name,
+        filename_stem,
+        courses.iter().map(|(c, mts)| (c, mts.as_slice())),
+        prefs,
+    )
+}
+
+fn build_ics<'a>(
+    calendar_name: &str,
+    filename_stem: &str,
+    courses: impl IntoIterator<Item = (&'a CalendarCourse, &'a [DbMeetingTime])>,
+    prefs: &DisplayPreferences,
 ) -> Result<IcsResult, anyhow::Error> {
     let mut ics = String::new();
     let mut all_excluded = Vec::new();
@@ -235,22 +290,23 @@ pub fn generate_ics(
     ics.push_str("PRODID:-//Banner Bot//Course Calendar//EN\r\n");
     ics.push_str("CALSCALE:GREGORIAN\r\n");
     ics.push_str("METHOD:PUBLISH\r\n");
-    ics.push_str(&format!(
-        "X-WR-CALNAME:{}\r\n",
-        escape_ics(&course.display_title())
-    ));
-
-    for (index, mt) in meeting_times.iter().enumerate() {
-        let (event, holidays) = generate_ics_event(course, mt, index)?;
-        ics.push_str(&event);
-        all_excluded.extend(holidays);
+    ics.push_str(&format!("X-WR-CALNAME:{}\r\n", escape_ics(calendar_name)));
+    ics.push_str(&format!("X-WR-TIMEZONE:{}\r\n", prefs.tz().name()));
+    ics.push_str(CAMPUS_VTIMEZONE);
+
+    for (course, meeting_times) in courses {
+        for (index, mt) in meeting_times.iter().enumerate() {
+            let (event, holidays) = generate_ics_event(course, mt, index, prefs)?;
+            ics.push_str(&event);
+            all_excluded.extend(holidays);
+        }
     }
 
     ics.push_str("END:VCALENDAR\r\n");
 
     Ok(IcsResult {
         content: ics,
-        filename: format!("{}.ics", course.filename_stem()),
+        filename: format!("{filename_stem}.ics"),
         excluded_holidays: all_excluded,
     })
 }
@@ -260,6 +316,7 @@ fn generate_ics_event(
     course: &CalendarCourse,
     mt: &DbMeetingTime,
     index: usize,
+    prefs: &DisplayPreferences,
 ) -> Result<(String, Vec<String>), anyhow::Error> {
     let start_date = mt.date_range.start;
     let end_date = mt.date_range.end;
@@ -267,24 +324,28 @@ fn generate_ics_event(
     let start_time = mt.time_range.as_ref().map(|tr| tr.start);
     let end_time = mt.time_range.as_ref().map(|tr| tr.end);
 
-    // DTSTART/DTEND: first occurrence with time, or all-day on start_date
+    // DTSTART/DTEND: first occurrence with time (campus-local), or all-day on
+    // start_date. An all-day DTEND is exclusive, so it's the following day.
     let (dtstart, dtend) = match (start_time, end_time) {
-        (Some(st), Some(et)) => {
-            let s = start_date.and_time(st).and_utc();
-            let e = start_date.and_time(et).and_utc();
-            (
-                s.format("%Y%m%dT%H%M%SZ").to_string(),
-                e.format("%Y%m%dT%H%M%SZ").to_string(),
-            )
-        }
-        _ => {
-            let s = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
-            let e = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
-            (
-                s.format("%Y%m%dT%H%M%SZ").to_string(),
-                e.format("%Y%m%dT%H%M%SZ").to_string(),
-            )
-        }
+        (Some(st), Some(et)) => (
+            format!(
+                ";TZID={}:{}",
+                CAMPUS_TZ.name(),
+                start_date.and_time(st).format("%Y%m%dT%H%M%S")
+            ),
+            format!(
+                ";TZID={}:{}",
+                CAMPUS_TZ.name(),
+                start_date.and_time(et).format("%Y%m%dT%H%M%S")
+            ),
+        ),
+        _ => (
+            format!(";VALUE=DATE:{}", start_date.format("%Y%m%d")),
+            format!(
+                ";VALUE=DATE:{}",
+                (start_date + Duration::days(1)).format("%Y%m%d")
+            ),
+        ),
     };
 
     let event_title = if index > 0 {
@@ -295,11 +356,17 @@ fn generate_ics_event(
 
     let instructor = course.primary_instructor.as_deref().unwrap_or("Staff");
 
+    let time_display = match (start_time, end_time) {
+        (Some(st), Some(et)) => prefs.format_campus_range(start_date, st, et),
+        _ => "TBA".to_string(),
+    };
+
     let description = format!(
-        "CRN: {}\\nInstructor: {}\\nDays: {}\\nMeeting Type: {}",
+        "CRN: {}\\nInstructor: {}\\nDays: {}\\nTime: {}\\nMeeting Type: {}",
         course.crn,
         instructor,
         days_display(mt),
+        time_display,
         mt.meeting_type,
     );
 
@@ -319,8 +386,8 @@ fn generate_ics_event(
     let mut event = String::new();
     event.push_str("BEGIN:VEVENT\r\n");
     event.push_str(&format!("UID:{uid}\r\n"));
-    event.push_str(&format!("DTSTART:{dtstart}\r\n"));
-    event.push_str(&format!("DTEND:{dtend}\r\n"));
+    event.push_str(&format!("DTSTART{dtstart}\r\n"));
+    event.push_str(&format!("DTEND{dtend}\r\n"));
     event.push_str(&format!("SUMMARY:{}\r\n", escape_ics(&event_title)));
     event.push_str(&format!("DESCRIPTION:{}\r\n", escape_ics(&description)));
     event.push_str(&format!("LOCATION:{}\r\n", escape_ics(&location)));
@@ -341,17 +408,15 @@ fn generate_ics_event(
         // Holiday exceptions
         let exceptions = holiday_exceptions(start_date, end_date, &weekdays);
         if !exceptions.is_empty() {
-            let start_utc = start_date.and_time(st).and_utc();
             let exdates: Vec<String> = exceptions
                 .iter()
-                .map(|&d| {
-                    d.and_time(start_utc.time())
-                        .and_utc()
-                        .format("%Y%m%dT%H%M%SZ")
-                        .to_string()
-                })
+                .map(|&d| d.and_time(st).format("%Y%m%dT%H%M%S").to_string())
                 .collect();
-            event.push_str(&format!("EXDATE:{}\r\n", exdates.join(",")));
+            event.push_str(&format!(
+                "EXDATE;TZID={}:{}\r\n",
+                CAMPUS_TZ.name(),
+                exdates.join(",")
+            ));
         }
 
         holiday_names = excluded_holiday_names(start_date, end_date, &exceptions);
@@ -365,6 +430,7 @@ fn generate_ics_event(
 pub fn generate_gcal_url(
     course: &CalendarCourse,
     mt: &DbMeetingTime,
+    prefs: &DisplayPreferences,
 ) -> Result<String, anyhow::Error> {
     let start_date = mt.date_range.start;
     let end_date = mt.date_range.end;
@@ -382,19 +448,27 @@ pub fn generate_gcal_url(
                 e.format("%Y%m%dT%H%M%S")
             )
         }
-        _ => {
-            let s = start_date.format("%Y%m%d").to_string();
-            format!("{s}/{s}")
-        }
+        // All-day; the end date is exclusive.
+        _ => format!(
+            "{}/{}",
+            start_date.format("%Y%m%d"),
+            (start_date + Duration::days(1)).format("%Y%m%d")
+        ),
     };
 
     let instructor = course.primary_instructor.as_deref().unwrap_or("Staff");
 
+    let time_display = match (start_time, end_time) {
+        (Some(st), Some(et)) => prefs.format_campus_range(start_date, st, et),
+        _ => "TBA".to_string(),
+    };
+
     let details = format!(
-        "CRN: {}\nInstructor: {}\nDays: {}",
+        "CRN: {}\nInstructor: {}\nDays: {}\nTime: {}",
         course.crn,
         instructor,
         days_display(mt),
+        time_display,
     );
 
     let location = location_string(mt);
@@ -421,10 +495,104 @@ pub fn generate_gcal_url(
         ("details", &details),
         ("location", &location),
         ("trp", "true"),
-        ("ctz", "America/Chicago"),
+        ("ctz", CAMPUS_TZ.name()),
         ("recur", &recur),
     ];
 
     let url = url::Url::parse_with_params("https://calendar.google.com/calendar/render", &params)?;
     Ok(url.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::banner::models::meetings::TimeRange;
+    use crate::data::course_types::DateRange;
+    use chrono::NaiveTime;
+    use std::collections::BTreeSet;
+
+    fn course() -> CalendarCourse {
+        CalendarCourse {
+            crn: "12345".to_owned(),
+            subject: "CS".to_owned(),
+            course_number: "1083".to_owned(),
+            title: "Intro".to_owned(),
+            sequence_number: Some("001".to_owned()),
+            primary_instructor: None,
+        }
+    }
+
+    fn meeting(time_range: Option<TimeRange>, days: &[DayOfWeek]) -> DbMeetingTime {
+        DbMeetingTime {
+            time_range,
+            date_range: DateRange::new(
+                NaiveDate::from_ymd_opt(2026, 1, 12).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 5, 1).unwrap(),
+            )
+            .unwrap(),
+            days: days.iter().copied().collect::<BTreeSet<_>>(),
+            location: None,
+            meeting_type: "CLAS".to_owned(),
+            meeting_schedule_type: "LEC".to_owned(),
+        }
+    }
+
+    fn lines(ics: &str) -> Vec<&str> {
+        ics.split("\r\n").collect()
+    }
+
+    #[test]
+    fn calendar_defines_the_timezone_it_references() {
+        let timed = meeting(
+            Some(TimeRange {
+                start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(9, 50, 0).unwrap(),
+            }),
+            &[DayOfWeek::Monday, DayOfWeek::Wednesday],
+        );
+        let ics = generate_ics(&course(), &[timed], &DisplayPreferences::default())
+            .unwrap()
+            .content;
+        let lines = lines(&ics);
+
+        let vtimezone = lines.iter().position(|l| *l == "BEGIN:VTIMEZONE").unwrap();
+        let vevent = lines.iter().position(|l| *l == "BEGIN:VEVENT").unwrap();
+        assert!(vtimezone < vevent);
+        assert!(lines.contains(&"TZID:America/Chicago"));
+        assert!(lines.contains(&"DTSTART;TZID=America/Chicago:20260112T090000"));
+        assert!(lines.contains(&"DTEND;TZID=America/Chicago:20260112T095000"));
+        assert!(lines.contains(&"RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20260501T000000Z"));
+        assert_eq!(lines.iter().filter(|l| **l == "END:VTIMEZONE").count(), 1);
+    }
+
+    #[test]
+    fn all_day_events_end_the_next_day() {
+        let ics = generate_ics(
+            &course(),
+            &[meeting(None, &[])],
+            &DisplayPreferences::default(),
+        )
+        .unwrap()
+        .content;
+        let lines = lines(&ics);
+        assert!(lines.contains(&"DTSTART;VALUE=DATE:20260112"));
+        assert!(lines.contains(&"DTEND;VALUE=DATE:20260113"));
+        assert!(!lines.iter().any(|l| l.starts_with("RRULE:FREQ=WEEKLY")));
+    }
+
+    #[test]
+    fn all_day_gcal_links_end_the_next_day() {
+        let url = generate_gcal_url(
+            &course(),
+            &meeting(None, &[]),
+            &DisplayPreferences::default(),
+        )
+        .unwrap();
+        let url = url::Url::parse(&url).unwrap();
+        let dates = url
+            .query_pairs()
+            .find(|(k, _)| k == "dates")
+            .map(|(_, v)| v.into_owned());
+        assert_eq!(dates.as_deref(), Some("20260112/20260113"));
+    }
+}
@@ -1,4 +1,19 @@
-use clap::Parser;
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use tracing::{info, warn};
+
+use crate::app::App;
+use crate::banner::{BannerApi, Term};
+use crate::config::Config;
+use crate::data::DbContext;
+use crate::data::events::EventBuffer;
+use crate::data::fixtures::{self, FixtureOptions};
+use crate::data::unsigned::Count;
+use crate::data::{search_index, terms};
+use crate::scraper::jobs::subject::SubjectJob;
+use crate::scraper::worker;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Banner Discord Bot - Course availability monitoring
 ///
@@ -12,6 +27,222 @@ pub struct Args {
     /// Log formatter to use
     #[arg(long, value_enum, default_value_t = default_tracing_format())]
     pub tracing: TracingFormat,
+
+    /// Run a one-off command instead of starting the services
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Load a deterministic synthetic dataset into the database
+    Fixtures {
+        /// RNG seed; the same seed and options always produce the same data
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+        /// Most recent term code to generate (defaults to the current term)
+        #[arg(long)]
+        term: Option<Term>,
+        /// Number of consecutive terms ending at `--term`
+        #[arg(long, default_value_t = 3)]
+        terms: usize,
+        /// Number of subjects
+        #[arg(long, default_value_t = 6)]
+        subjects: usize,
+        /// Catalog courses per subject
+        #[arg(long, default_value_t = 10)]
+        courses_per_subject: usize,
+        /// Instructors per subject
+        #[arg(long, default_value_t = 5)]
+        instructors_per_subject: usize,
+    },
+    /// Import a grade distribution release (CSV) and relink it to instructors
+    ImportGrades {
+        /// Path to the CSV file
+        path: PathBuf,
+        /// Label stored with the rows (defaults to the file name)
+        #[arg(long)]
+        source: Option<String>,
+        /// Recompute instructor scores after importing
+        #[arg(long)]
+        rescore: bool,
+    },
+    /// Scrape one subject for a term in the foreground, then exit
+    Scrape {
+        /// Term code to scrape
+        #[arg(long)]
+        term: Term,
+        /// Subject code, e.g. `CS`
+        #[arg(long)]
+        subject: String,
+        /// Make a single attempt instead of retrying failures with backoff
+        #[arg(long)]
+        once: bool,
+    },
+    /// Run a database maintenance task
+    Admin {
+        #[command(subcommand)]
+        task: AdminTask,
+    },
+}
+
+/// Maintenance tasks otherwise run by the scheduler, at startup, or from the
+/// admin API.
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminTask {
+    /// Recompute every instructor's composite score
+    RecomputeScores,
+    /// Regenerate RMP match candidates, auto-linking confident matches
+    RegenerateRmpCandidates,
+    /// Assign slugs to instructors that don't have one
+    BackfillSlugs,
+    /// Fill in parsed first/last names for instructors missing them
+    BackfillNames,
+    /// Refresh materialized views and stale course search index terms
+    RefreshViews,
+}
+
+impl AdminTask {
+    async fn run(self, config: &Config) -> anyhow::Result<()> {
+        let db_pool = App::connect_database(config).await?;
+        match self {
+            AdminTask::RecomputeScores => {
+                let count =
+                    crate::data::scoring::recompute_all_scores(&db_pool, &config.scoring_decay())
+                        .await?;
+                info!(count, "Computed instructor scores");
+            }
+            AdminTask::RegenerateRmpCandidates => {
+                let stats =
+                    crate::data::rmp_matching::generate_candidates(&db_pool, &EventBuffer::new(64))
+                        .await?;
+                info!(
+                    total_processed = stats.total_processed,
+                    candidates_created = stats.candidates_created,
+                    auto_matched = stats.auto_matched,
+                    pending_review = stats.pending_review,
+                    "Regenerated RMP candidates"
+                );
+            }
+            AdminTask::BackfillSlugs => {
+                let count = crate::data::instructors::backfill_instructor_slugs(&db_pool).await?;
+                info!(count, "Backfilled instructor slugs");
+            }
+            AdminTask::BackfillNames => {
+                crate::data::names::backfill_instructor_names(&db_pool).await?;
+            }
+            AdminTask::RefreshViews => {
+                crate::data::rmp::refresh_rmp_summary(&db_pool).await?;
+                info!("Refreshed instructor_rmp_summary");
+
+                let terms = crate::data::search_index::stale_terms(&db_pool).await?;
+                let mut rows = 0;
+                for term in &terms {
+                    rows += crate::data::search_index::refresh_term(&db_pool, term).await?;
+                }
+                info!(terms = terms.len(), rows, "Refreshed course_search_index");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Attempts a foreground scrape gets without `--once`; matches the queue's
+/// default `max_retries`.
+const SCRAPE_ATTEMPTS: u32 = 5;
+
+impl Command {
+    /// Execute the command against the configured database.
+    pub async fn run(self, config: &Config) -> anyhow::Result<()> {
+        match self {
+            Command::Fixtures {
+                seed,
+                term,
+                terms,
+                subjects,
+                courses_per_subject,
+                instructors_per_subject,
+            } => {
+                let defaults = FixtureOptions::default();
+                let opts = FixtureOptions {
+                    seed,
+                    latest_term: term.unwrap_or(defaults.latest_term),
+                    terms,
+                    subjects,
+                    courses_per_subject,
+                    instructors_per_subject,
+                };
+
+                let db_pool = App::connect_database(config).await?;
+                let db = DbContext::new(db_pool, Arc::new(EventBuffer::new(64)));
+                let set = fixtures::generate(&opts);
+                fixtures::load(&db, &set).await?;
+                Ok(())
+            }
+            Command::ImportGrades {
+                path,
+                source,
+                rescore,
+            } => {
+                let db_pool = App::connect_database(config).await?;
+                crate::grades::import_file(&db_pool, &path, source.as_deref()).await?;
+                if rescore {
+                    crate::data::scoring::recompute_all_scores(&db_pool, &config.scoring_decay())
+                        .await?;
+                }
+                Ok(())
+            }
+            Command::Scrape {
+                term,
+                subject,
+                once,
+            } => {
+                let db_pool = App::connect_database(config).await?;
+                let banner_api = BannerApi::new_with_limits(
+                    config.banner_base_url.clone(),
+                    config.rate_limiting.clone(),
+                    config.banner_max_concurrency,
+                )
+                .context("Failed to create BannerApi")?;
+                let db = DbContext::new(db_pool, Arc::new(EventBuffer::new(64)));
+
+                let term_code = term.to_string();
+                let job = SubjectJob::new(subject.to_uppercase(), term_code.clone());
+                let attempts = if once { 1 } else { SCRAPE_ATTEMPTS };
+                let counts = worker::run_in_foreground(
+                    &job,
+                    &banner_api,
+                    &db,
+                    attempts,
+                    config.scrape_retry_policy(),
+                )
+                .await
+                .with_context(|| format!("Failed to scrape {} for {term_code}", job.subject))?;
+
+                info!(
+                    term = %term_code,
+                    subject = %job.subject,
+                    courses_fetched = %counts.courses_fetched,
+                    courses_changed = %counts.courses_changed,
+                    courses_unchanged = %counts.courses_unchanged,
+                    "Scrape completed"
+                );
+
+                // Keep the term's bookkeeping in step with what a worker would do
+                terms::update_last_scraped_at(db.pool(), &term_code).await?;
+                if counts.courses_changed > Count::default() {
+                    match search_index::refresh_term(db.pool(), &term_code).await {
+                        Ok(rows) => info!(term = %term_code, rows, "Refreshed search index"),
+                        Err(e) => {
+                            warn!(term = %term_code, error = ?e, "Failed to refresh search index")
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Command::Admin { task } => task.run(config).await,
+        }
+    }
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -68,6 +299,86 @@ mod tests {
         assert_eq!(ServiceName::Scraper.as_str(), "scraper");
     }
 
+    #[test]
+    fn test_fixtures_command_parses() {
+        let args = Args::parse_from(["banner", "fixtures", "--seed", "7", "--term", "202620"]);
+        match args.command {
+            Some(Command::Fixtures {
+                seed, term, terms, ..
+            }) => {
+                assert_eq!(seed, 7);
+                assert_eq!(term.map(|t| t.to_string()).as_deref(), Some("202620"));
+                assert_eq!(terms, 3);
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_import_grades_command_parses() {
+        let args = Args::parse_from(["banner", "import-grades", "fall-2024.csv", "--rescore"]);
+        match args.command {
+            Some(Command::ImportGrades {
+                path,
+                source,
+                rescore,
+            }) => {
+                assert_eq!(path, PathBuf::from("fall-2024.csv"));
+                assert_eq!(source, None);
+                assert!(rescore);
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_scrape_command_parses() {
+        let args = Args::parse_from([
+            "banner",
+            "scrape",
+            "--term",
+            "202620",
+            "--subject",
+            "CS",
+            "--once",
+        ]);
+        match args.command {
+            Some(Command::Scrape {
+                term,
+                subject,
+                once,
+            }) => {
+                assert_eq!(term.to_string(), "202620");
+                assert_eq!(subject, "CS");
+                assert!(once);
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+        assert!(Args::try_parse_from(["banner", "scrape", "--subject", "CS"]).is_err());
+    }
+
+    #[test]
+    fn test_admin_tasks_parse() {
+        let task = |name| match Args::parse_from(["banner", "admin", name]).command {
+            Some(Command::Admin { task }) => task,
+            other => panic!("unexpected command: {other:?}"),
+        };
+        assert_eq!(task("recompute-scores"), AdminTask::RecomputeScores);
+        assert_eq!(
+            task("regenerate-rmp-candidates"),
+            AdminTask::RegenerateRmpCandidates
+        );
+        assert_eq!(task("backfill-slugs"), AdminTask::BackfillSlugs);
+        assert_eq!(task("backfill-names"), AdminTask::BackfillNames);
+        assert_eq!(task("refresh-views"), AdminTask::RefreshViews);
+        assert!(Args::try_parse_from(["banner", "admin"]).is_err());
+    }
+
+    #[test]
+    fn test_no_command_runs_services() {
+        assert!(Args::parse_from(["banner"]).command.is_none());
+    }
+
     #[test]
     fn test_service_name_all() {
         let all = ServiceName::all();
@@ -4,10 +4,33 @@
 //! using the figment crate. It supports flexible duration parsing that accepts both
 //! numeric values (interpreted as seconds) and duration strings with units.
 
+use crate::banner::DEFAULT_MAX_CONCURRENCY;
+use crate::data::models::TargetType;
+use crate::data::scoring::ScoringDecay;
+use crate::data::scrape_history;
+use crate::data::search::SearchWeights;
+use crate::scraper::adaptive::{RegistrationCalendar, RegistrationWindow};
+use crate::scraper::pool::{self, PoolConfig};
+use crate::scraper::worker::RetryPolicy;
+use crate::secrets;
+use crate::web::attribution::AttributionConfig;
+use crate::web::middleware::client_ip::{DEFAULT_TRUSTED_PROXIES, IpCidr};
+use crate::web::middleware::rate_limit::{RateLimitOverrides, RateLimitProfile, RateLimitQuotas};
+use crate::web::ws::HeartbeatPolicy;
 use fundu::{DurationParser, TimeUnit};
+use secrecy::SecretString;
 use serde::{Deserialize, Deserializer};
 use std::time::Duration;
 
+/// Secret variables that can also be given as a path in `<NAME>_FILE`.
+pub const SECRET_VARS: &[&str] = &[
+    "BOT_TOKEN",
+    "DISCORD_CLIENT_SECRET",
+    "GOOGLE_CLIENT_SECRET",
+    "MICROSOFT_CLIENT_SECRET",
+    "METRICS_TOKEN",
+];
+
 /// Main application configuration containing all sub-configurations
 #[derive(Deserialize)]
 pub struct Config {
@@ -34,10 +57,26 @@ pub struct Config {
         deserialize_with = "deserialize_duration"
     )]
     pub shutdown_timeout: Duration,
-    /// Discord bot token for authentication
-    pub bot_token: String,
+    /// Discord bot token for authentication (accepts a secret reference; see [`crate::secrets`])
+    #[serde(deserialize_with = "secrets::deserialize")]
+    pub bot_token: SecretString,
     /// Target Discord guild ID where the bot operates
     pub bot_target_guild: u64,
+    /// Discord user IDs allowed to run `/admin` bot commands (comma-separated)
+    #[serde(default, deserialize_with = "deserialize_id_list")]
+    pub bot_admin_user_ids: Vec<u64>,
+    /// Discord role IDs allowed to run `/admin` bot commands (comma-separated)
+    #[serde(default, deserialize_with = "deserialize_id_list")]
+    pub bot_admin_role_ids: Vec<u64>,
+    /// Extra guild IDs that get guild-scoped commands (comma-separated)
+    ///
+    /// Guild registrations apply instantly, unlike global ones.
+    #[serde(default, deserialize_with = "deserialize_id_list")]
+    pub bot_command_guilds: Vec<u64>,
+    /// Register commands only to `bot_target_guild`, holding back the other
+    /// guilds and the global rollout until this is cleared
+    #[serde(default)]
+    pub bot_command_staged: bool,
 
     /// Base URL for banner generation service
     ///
@@ -47,19 +86,58 @@ pub struct Config {
     /// Rate limiting configuration for Banner API requests
     #[serde(default = "default_rate_limiting")]
     pub rate_limiting: RateLimitingConfig,
+    /// Most Banner API requests in flight at once, across the scraper,
+    /// scheduler and web handlers (default: 4)
+    #[serde(default = "default_banner_max_concurrency")]
+    pub banner_max_concurrency: usize,
+    /// Inbound HTTP rate-limit quota table: "dev", "staging", "production"
+    /// or "campus-event" (default: production)
+    #[serde(default)]
+    pub rate_limit_profile: RateLimitProfile,
+    /// Individual quota overrides on top of the profile, one `RATE_LIMIT_*`
+    /// variable each
+    #[serde(flatten)]
+    pub rate_limit_overrides: RateLimitOverrides,
 
     /// Discord OAuth2 client ID for web authentication
     #[serde(deserialize_with = "deserialize_string_or_uint")]
     pub discord_client_id: String,
-    /// Discord OAuth2 client secret for web authentication
-    pub discord_client_secret: String,
+    /// Discord OAuth2 client secret for web authentication (accepts a secret reference)
+    #[serde(deserialize_with = "secrets::deserialize")]
+    pub discord_client_secret: SecretString,
     /// Optional base URL override for OAuth2 redirect (e.g. "https://banner.xevion.dev").
     /// When unset, the redirect URI is derived from the incoming request's Origin/Host.
     #[serde(default)]
     pub discord_redirect_uri: Option<String>,
+    /// Google OAuth2 client ID; enables "Sign in with Google" together with
+    /// `GOOGLE_CLIENT_SECRET`
+    #[serde(default)]
+    pub google_client_id: Option<String>,
+    /// Google OAuth2 client secret (accepts a secret reference)
+    #[serde(default, deserialize_with = "secrets::deserialize_opt")]
+    pub google_client_secret: Option<SecretString>,
+    /// Microsoft Entra ID application ID; enables "Sign in with Microsoft"
+    /// (UTSA SSO) together with `MICROSOFT_CLIENT_SECRET`
+    #[serde(default)]
+    pub microsoft_client_id: Option<String>,
+    /// Microsoft Entra ID client secret (accepts a secret reference)
+    #[serde(default, deserialize_with = "secrets::deserialize_opt")]
+    pub microsoft_client_secret: Option<SecretString>,
+    /// Entra ID tenant allowed to sign in: a directory ID or domain such as
+    /// "utsa.edu", or "organizations" for any work account (default)
+    #[serde(default = "default_microsoft_tenant")]
+    pub microsoft_tenant: String,
+    /// HTTP relay for outbound email (optional). Receives `{to, subject, text}`
+    /// as JSON; email notifications are dead-lettered when unset.
+    #[serde(default)]
+    pub email_relay_url: Option<String>,
     /// Discord user ID to seed as initial admin on startup (optional)
     #[serde(default)]
     pub admin_discord_id: Option<u64>,
+    /// Bearer token required by `GET /api/metrics/prometheus` (accepts a
+    /// secret reference); the endpoint 404s when unset
+    #[serde(default, deserialize_with = "secrets::deserialize_opt")]
+    pub metrics_token: Option<SecretString>,
 
     /// URL of the SvelteKit SSR server (Vite in dev, Bun in production).
     /// Default: http://localhost:3001
@@ -70,12 +148,289 @@ pub struct Config {
     /// When unset, sitemap endpoints return 404.
     #[serde(default)]
     pub public_origin: Option<String>,
+
+    /// License the aggregated data is offered under (e.g. "CC BY-NC 4.0"),
+    /// reported by `/api/meta/attribution`. Unset means none is declared.
+    #[serde(default)]
+    pub data_license: Option<String>,
+    /// Extra attribution notice reported by `/api/meta/attribution`
+    #[serde(default)]
+    pub data_attribution_notice: Option<String>,
+
+    /// Maximum simultaneous stream (WebSocket/SSE/long-poll) connections from one IP (default: 4)
+    #[serde(default = "default_max_stream_connections_per_ip")]
+    pub max_stream_connections_per_ip: usize,
+    /// Maximum simultaneous stream connections across all clients (default: 512)
+    #[serde(default = "default_max_stream_connections")]
+    pub max_stream_connections: usize,
+    /// How often stream connections are pinged (default: 30s)
+    #[serde(
+        default = "default_stream_ping_interval",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub stream_ping_interval: Duration,
+    /// Close stream connections silent for this long, pongs included (default: 75s)
+    #[serde(
+        default = "default_stream_idle_timeout",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub stream_idle_timeout: Duration,
+    /// Close stream connections open longer than this; clients reconnect (default: 4h)
+    #[serde(
+        default = "default_stream_max_lifetime",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub stream_max_lifetime: Duration,
+
+    /// Proxies allowed to set client IP forwarding headers (comma-separated CIDRs).
+    ///
+    /// Defaults to loopback, private/CGNAT ranges and Cloudflare's edge ranges.
+    /// Set to an empty string to ignore forwarding headers entirely.
+    #[serde(
+        default = "default_trusted_proxies",
+        deserialize_with = "deserialize_cidr_list"
+    )]
+    pub trusted_proxies: Vec<IpCidr>,
+    /// Maximum `X-Forwarded-For` entries to walk from the right (default: 1)
+    #[serde(default = "default_trusted_proxy_hops")]
+    pub trusted_proxy_hops: usize,
+    /// Client ranges exempt from IP blocks, bans and rate limiting (comma-separated CIDRs)
+    #[serde(default, deserialize_with = "deserialize_cidr_list")]
+    pub trusted_cidrs: Vec<IpCidr>,
+    /// Client ranges refused with a 403 (comma-separated CIDRs)
+    #[serde(default, deserialize_with = "deserialize_cidr_list")]
+    pub blocked_cidrs: Vec<IpCidr>,
+
+    /// Course search relevance weight for course code matches (default: 1.0)
+    #[serde(
+        default = "default_search_weight_code",
+        deserialize_with = "deserialize_search_weight"
+    )]
+    pub search_weight_code: f32,
+    /// Course search relevance weight for title matches (default: 0.4)
+    #[serde(
+        default = "default_search_weight_title",
+        deserialize_with = "deserialize_search_weight"
+    )]
+    pub search_weight_title: f32,
+    /// Course search relevance weight for instructor name matches (default: 0.2)
+    #[serde(
+        default = "default_search_weight_instructor",
+        deserialize_with = "deserialize_search_weight"
+    )]
+    pub search_weight_instructor: f32,
+    /// Course search relevance weight for catalog, subject and attribute descriptions (default: 0.1)
+    #[serde(
+        default = "default_search_weight_extra",
+        deserialize_with = "deserialize_search_weight"
+    )]
+    pub search_weight_extra: f32,
+
+    /// Years after which an RMP review counts half as much toward an
+    /// instructor's score; 0 disables decay (default: 3)
+    #[serde(default = "default_scoring_rmp_half_life_years")]
+    pub scoring_rmp_half_life_years: f64,
+    /// Terms after which a BlueBook evaluation counts half as much toward an
+    /// instructor's score; 0 disables decay (default: 6)
+    #[serde(default = "default_scoring_bluebook_half_life_terms")]
+    pub scoring_bluebook_half_life_terms: f64,
+
+    /// Delay before the first retry of a failed scrape job, doubling per attempt (default: 10s)
+    #[serde(
+        default = "default_scrape_retry_base_delay",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub scrape_retry_base_delay: Duration,
+    /// Longest delay between scrape job retries (default: 5m)
+    #[serde(
+        default = "default_scrape_retry_max_delay",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub scrape_retry_max_delay: Duration,
+    /// Number of scrape workers (default: 4)
+    #[serde(default = "default_scrape_workers")]
+    pub scrape_workers: usize,
+    /// Most jobs of a target type running at once, e.g.
+    /// "subject=3,crn-list=1"; unlisted types may use every worker
+    #[serde(default, deserialize_with = "deserialize_type_limits")]
+    pub scrape_type_limits: Vec<(TargetType, usize)>,
+    /// Banner sessions pre-created per current or upcoming term when the
+    /// scraper starts; 0 disables warm-up (default: 2)
+    #[serde(default = "default_banner_session_warmup")]
+    pub banner_session_warmup: usize,
+    /// Registration and add/drop windows as comma-separated
+    /// `term:start..end` campus dates, e.g. "202620:2026-04-06..2026-04-24";
+    /// high-churn subjects are scraped more often while one is open
+    #[serde(default, deserialize_with = "deserialize_registration_windows")]
+    pub registration_windows: Vec<RegistrationWindow>,
+    /// Days of scrape job results kept before they are rolled into daily
+    /// stats, and of dead-lettered jobs before they are purged (default: 45,
+    /// minimum: 30)
+    #[serde(default = "default_scrape_history_retention_days")]
+    pub scrape_history_retention_days: u32,
+}
+
+impl Config {
+    /// Relevance weights for ranked course search.
+    pub fn search_weights(&self) -> SearchWeights {
+        SearchWeights {
+            code: self.search_weight_code,
+            title: self.search_weight_title,
+            instructor: self.search_weight_instructor,
+            extra: self.search_weight_extra,
+        }
+    }
+
+    /// Time decay applied to rating evidence when scoring instructors.
+    pub fn scoring_decay(&self) -> ScoringDecay {
+        ScoringDecay {
+            rmp_half_life_years: self.scoring_rmp_half_life_years,
+            bluebook_half_life_terms: self.scoring_bluebook_half_life_terms,
+        }
+    }
+
+    /// Inbound rate-limit quotas: the selected profile plus any overrides.
+    pub fn rate_limit_quotas(&self) -> RateLimitQuotas {
+        self.rate_limit_overrides
+            .apply(self.rate_limit_profile.quotas())
+    }
+
+    /// Keepalive and lifetime limits for stream connections.
+    ///
+    /// The idle timeout is kept above the ping interval so a healthy client
+    /// always has a chance to answer.
+    pub fn stream_heartbeat_policy(&self) -> HeartbeatPolicy {
+        HeartbeatPolicy {
+            ping_interval: self.stream_ping_interval,
+            idle_timeout: self.stream_idle_timeout.max(self.stream_ping_interval * 2),
+            max_lifetime: self.stream_max_lifetime,
+        }
+    }
+
+    /// Licensing details for the attribution endpoint.
+    pub fn attribution_config(&self) -> AttributionConfig {
+        AttributionConfig {
+            banner_url: self.banner_base_url.clone(),
+            license: self.data_license.clone(),
+            notice: self.data_attribution_notice.clone(),
+        }
+    }
+
+    /// Backoff policy for failed scrape jobs.
+    pub fn scrape_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base_delay: self.scrape_retry_base_delay,
+            max_delay: self
+                .scrape_retry_max_delay
+                .max(self.scrape_retry_base_delay),
+        }
+    }
+
+    /// Worker count and per-type limits for the scraper's worker pool.
+    pub fn scrape_pool_config(&self) -> PoolConfig {
+        PoolConfig {
+            workers: self.scrape_workers.max(1),
+            type_limits: self.scrape_type_limits.clone(),
+            warm_sessions: self.banner_session_warmup,
+        }
+    }
+
+    /// Registration windows used by the adaptive scheduler.
+    pub fn registration_calendar(&self) -> RegistrationCalendar {
+        RegistrationCalendar::new(self.registration_windows.clone())
+    }
+
+    /// Scrape job result retention in days, raised to the admin stats window.
+    pub fn scrape_history_retention(&self) -> u32 {
+        self.scrape_history_retention_days
+            .max(scrape_history::MIN_RETENTION_DAYS)
+    }
+}
+
+fn default_stream_ping_interval() -> Duration {
+    HeartbeatPolicy::default().ping_interval
+}
+
+fn default_stream_idle_timeout() -> Duration {
+    HeartbeatPolicy::default().idle_timeout
+}
+
+fn default_stream_max_lifetime() -> Duration {
+    HeartbeatPolicy::default().max_lifetime
+}
+
+fn default_scrape_history_retention_days() -> u32 {
+    45
+}
+
+fn default_scrape_workers() -> usize {
+    pool::DEFAULT_WORKERS
+}
+
+fn default_banner_session_warmup() -> usize {
+    pool::DEFAULT_WARM_SESSIONS
+}
+
+fn default_scrape_retry_base_delay() -> Duration {
+    RetryPolicy::default().base_delay
+}
+
+fn default_scrape_retry_max_delay() -> Duration {
+    RetryPolicy::default().max_delay
+}
+
+fn default_search_weight_code() -> f32 {
+    SearchWeights::default().code
+}
+
+fn default_search_weight_title() -> f32 {
+    SearchWeights::default().title
+}
+
+fn default_search_weight_instructor() -> f32 {
+    SearchWeights::default().instructor
+}
+
+fn default_search_weight_extra() -> f32 {
+    SearchWeights::default().extra
+}
+
+fn default_scoring_rmp_half_life_years() -> f64 {
+    ScoringDecay::default().rmp_half_life_years
+}
+
+fn default_scoring_bluebook_half_life_terms() -> f64 {
+    ScoringDecay::default().bluebook_half_life_terms
+}
+
+fn default_trusted_proxies() -> Vec<IpCidr> {
+    DEFAULT_TRUSTED_PROXIES
+        .iter()
+        .map(|s| s.parse().expect("default trusted proxy ranges are valid"))
+        .collect()
+}
+
+fn default_trusted_proxy_hops() -> usize {
+    1
+}
+
+fn default_max_stream_connections_per_ip() -> usize {
+    4
+}
+
+fn default_max_stream_connections() -> usize {
+    512
 }
 
 fn default_ssr_downstream() -> String {
     "http://localhost:3001".to_string()
 }
 
+/// Default Entra ID tenant: any work or school account
+fn default_microsoft_tenant() -> String {
+    "organizations".to_string()
+}
+
 /// Default log level of "info"
 fn default_log_level() -> String {
     "info".to_string()
@@ -116,6 +471,10 @@ pub struct RateLimitingConfig {
     pub burst_allowance: u32,
 }
 
+fn default_banner_max_concurrency() -> usize {
+    DEFAULT_MAX_CONCURRENCY
+}
+
 /// Default rate limiting configuration
 fn default_rate_limiting() -> RateLimitingConfig {
     RateLimitingConfig::default()
@@ -280,6 +639,131 @@ where
     deserializer.deserialize_any(StringOrUintVisitor)
 }
 
+/// Deserializes a list of Discord IDs from a comma-separated string.
+///
+/// A lone ID arrives as an integer (see [`deserialize_string_or_uint`]), so
+/// that form is accepted too.
+fn deserialize_id_list<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Visitor;
+
+    struct IdListVisitor;
+
+    impl<'de> Visitor<'de> for IdListVisitor {
+        type Value = Vec<u64>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a comma-separated list of IDs")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    s.parse::<u64>()
+                        .map_err(|_| serde::de::Error::custom(format!("Invalid ID '{s}'")))
+                })
+                .collect()
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(vec![value])
+        }
+    }
+
+    deserializer.deserialize_any(IdListVisitor)
+}
+
+/// Deserializes per-target-type limits from a comma-separated list of
+/// `type=limit` pairs, such as "subject=3,crn-list=1".
+fn deserialize_type_limits<'de, D>(deserializer: D) -> Result<Vec<(TargetType, usize)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|pair| {
+            let (target_type, limit) = pair.split_once('=').ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "Invalid type limit '{pair}': expected type=limit"
+                ))
+            })?;
+            let target_type = target_type
+                .trim()
+                .parse::<TargetType>()
+                .map_err(serde::de::Error::custom)?;
+            let limit = limit.trim().parse::<usize>().map_err(|_| {
+                serde::de::Error::custom(format!("Invalid limit '{}' for {pair}", limit.trim()))
+            })?;
+            Ok((target_type, limit))
+        })
+        .collect()
+}
+
+/// Deserializes a comma-separated list of `term:start..end` registration
+/// windows.
+fn deserialize_registration_windows<'de, D>(
+    deserializer: D,
+) -> Result<Vec<RegistrationWindow>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<RegistrationWindow>()
+                .map_err(|e| serde::de::Error::custom(format!("{e:#}")))
+        })
+        .collect()
+}
+
+/// Deserializes a search weight, rejecting values outside `[0, 1]`.
+///
+/// Postgres `ts_rank` raises an error for weights above 1, which would fail
+/// every default-sorted text search.
+fn deserialize_search_weight<'de, D>(deserializer: D) -> Result<f32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = f32::deserialize(deserializer)?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(serde::de::Error::custom(format!(
+            "search weight must be between 0 and 1, got {value}"
+        )));
+    }
+    Ok(value)
+}
+
+/// Deserializes a comma-separated list of CIDRs or bare IP addresses.
+fn deserialize_cidr_list<'de, D>(deserializer: D) -> Result<Vec<IpCidr>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<IpCidr>().map_err(serde::de::Error::custom))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,11 +844,149 @@ mod tests {
         );
     }
 
+    #[derive(Deserialize)]
+    struct IdListWrapper {
+        #[serde(deserialize_with = "deserialize_id_list")]
+        value: Vec<u64>,
+    }
+
+    #[test]
+    fn test_id_list_from_comma_separated_string() {
+        let w: IdListWrapper = serde_json::from_str(r#"{"value": "123, 456,"}"#).unwrap();
+        assert_eq!(w.value, vec![123, 456]);
+    }
+
+    #[test]
+    fn test_id_list_from_single_integer() {
+        let w: IdListWrapper = serde_json::from_str(r#"{"value": 789}"#).unwrap();
+        assert_eq!(w.value, vec![789]);
+    }
+
+    #[test]
+    fn test_id_list_rejects_garbage() {
+        assert!(serde_json::from_str::<IdListWrapper>(r#"{"value": "12,abc"}"#).is_err());
+    }
+
+    #[derive(Deserialize)]
+    struct CidrListWrapper {
+        #[serde(deserialize_with = "deserialize_cidr_list")]
+        value: Vec<IpCidr>,
+    }
+
+    #[test]
+    fn test_cidr_list_from_comma_separated_string() {
+        let w: CidrListWrapper =
+            serde_json::from_str(r#"{"value": "10.0.0.0/8, 2606:4700::/32, 203.0.113.7,"}"#)
+                .unwrap();
+        assert_eq!(w.value.len(), 3);
+        assert!(w.value[2].contains("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_cidr_list_empty_trusts_nothing() {
+        let w: CidrListWrapper = serde_json::from_str(r#"{"value": ""}"#).unwrap();
+        assert!(w.value.is_empty());
+    }
+
+    #[test]
+    fn test_cidr_list_rejects_garbage() {
+        assert!(serde_json::from_str::<CidrListWrapper>(r#"{"value": "10.0.0.0/99"}"#).is_err());
+    }
+
+    #[derive(Deserialize)]
+    struct SearchWeightWrapper {
+        #[serde(deserialize_with = "deserialize_search_weight")]
+        value: f32,
+    }
+
+    #[test]
+    fn test_search_weight_within_unit_range() {
+        for ok in [0.0, 0.25, 1.0] {
+            let json = format!(r#"{{"value": {ok}}}"#);
+            let w: SearchWeightWrapper = serde_json::from_str(&json).unwrap();
+            assert_eq!(w.value, ok);
+        }
+    }
+
+    #[test]
+    fn test_search_weight_rejects_out_of_range() {
+        for bad in ["1.5", "-0.1"] {
+            let json = format!(r#"{{"value": {bad}}}"#);
+            assert!(
+                serde_json::from_str::<SearchWeightWrapper>(&json).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct TypeLimitsWrapper {
+        #[serde(deserialize_with = "deserialize_type_limits")]
+        value: Vec<(TargetType, usize)>,
+    }
+
+    #[test]
+    fn test_type_limits_from_pairs() {
+        let w: TypeLimitsWrapper =
+            serde_json::from_str(r#"{"value": "subject=3, crn-list = 1,"}"#).unwrap();
+        assert_eq!(
+            w.value,
+            vec![(TargetType::Subject, 3), (TargetType::CrnList, 1)]
+        );
+    }
+
+    #[test]
+    fn test_type_limits_rejects_garbage() {
+        for bad in ["subject", "subject=x", "department=2"] {
+            let json = format!(r#"{{"value": "{bad}"}}"#);
+            assert!(
+                serde_json::from_str::<TypeLimitsWrapper>(&json).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct RegistrationWindowsWrapper {
+        #[serde(deserialize_with = "deserialize_registration_windows")]
+        value: Vec<RegistrationWindow>,
+    }
+
+    #[test]
+    fn test_registration_windows_from_list() {
+        let w: RegistrationWindowsWrapper = serde_json::from_str(
+            r#"{"value": "202620:2026-04-06..2026-04-24, 202620:2026-08-17..2026-08-28"}"#,
+        )
+        .unwrap();
+        assert_eq!(w.value.len(), 2);
+        assert_eq!(w.value[1].term, "202620");
+        assert!(
+            serde_json::from_str::<RegistrationWindowsWrapper>(r#"{"value": "202620:soon"}"#)
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_default_config_values() {
         assert_eq!(default_port(), 8080);
         assert_eq!(default_shutdown_timeout(), Duration::from_secs(8));
         assert_eq!(default_log_level(), "info");
+        assert_eq!(default_max_stream_connections_per_ip(), 4);
+        assert_eq!(default_max_stream_connections(), 512);
+        assert_eq!(default_trusted_proxy_hops(), 1);
+        assert_eq!(default_search_weight_code(), 1.0);
+        assert_eq!(default_search_weight_extra(), 0.1);
+        assert!(default_scrape_history_retention_days() >= scrape_history::MIN_RETENTION_DAYS);
+        assert!(!default_trusted_proxies().is_empty());
+    }
+
+    #[test]
+    fn test_rate_limit_profile_names() {
+        let parse = |name: &str| serde_json::from_value::<RateLimitProfile>(name.into()).ok();
+        assert_eq!(parse("campus-event"), Some(RateLimitProfile::CampusEvent));
+        assert_eq!(parse("prod"), Some(RateLimitProfile::Production));
+        assert_eq!(parse("dev"), Some(RateLimitProfile::Dev));
+        assert_eq!(parse("chaos"), None);
     }
 
     #[test]
@@ -0,0 +1,159 @@
+//! Database operations for the admin action trail.
+//!
+//! Rows are written by the admin audit middleware for every mutating admin
+//! API call, and read back through `GET /api/admin/actions`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+
+use crate::data::unsigned::Count;
+
+/// One recorded admin API call.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AdminAction {
+    #[ts(type = "number")]
+    pub id: i64,
+    /// Discord ID as a string; `null` once the admin's account is deleted.
+    pub admin_id: Option<String>,
+    pub admin_username: String,
+    pub method: String,
+    pub path: String,
+    /// Matched route template, e.g. `/api/admin/users/{id}/admin`.
+    pub route: Option<String>,
+    pub status: i16,
+    /// Redacted request body.
+    pub payload: Option<serde_json::Value>,
+    /// Redacted response body of a successful call.
+    pub result: Option<serde_json::Value>,
+    /// Fields the call changed, as `{"field": {"old": .., "new": ..}}`; only
+    /// recorded by handlers that report the target's prior state.
+    pub changes: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A call to record.
+pub struct NewAdminAction<'a> {
+    pub admin_id: i64,
+    pub admin_username: &'a str,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub route: Option<&'a str>,
+    pub status: u16,
+    pub payload: Option<serde_json::Value>,
+    pub result: Option<serde_json::Value>,
+    pub changes: Option<serde_json::Value>,
+}
+
+/// Filters for [`list_actions`]; `None` matches everything.
+#[derive(Debug, Default)]
+pub struct AdminActionFilter<'a> {
+    pub admin_id: Option<i64>,
+    pub method: Option<&'a str>,
+    /// Prefix of the request path, e.g. `/api/admin/users`.
+    pub path_prefix: Option<&'a str>,
+    /// Only successful (`true`) or only failed (`false`) calls.
+    pub success: Option<bool>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Response for the paginated admin action list.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListAdminActionsResponse {
+    pub items: Vec<AdminAction>,
+    pub total: Count,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+pub async fn insert_action(pool: &PgPool, action: &NewAdminAction<'_>) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO admin_actions
+            (admin_id, admin_username, method, path, route, status, payload, result, changes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+    )
+    .bind(action.admin_id)
+    .bind(action.admin_username)
+    .bind(action.method)
+    .bind(action.path)
+    .bind(action.route)
+    .bind(action.status as i16)
+    .bind(&action.payload)
+    .bind(&action.result)
+    .bind(&action.changes)
+    .execute(pool)
+    .await
+    .context("failed to record admin action")?;
+    Ok(())
+}
+
+const FILTER: &str = r#"
+    WHERE ($1::bigint IS NULL OR admin_id = $1)
+      AND ($2::text IS NULL OR method = $2)
+      AND ($3::text IS NULL OR starts_with(path, $3))
+      AND ($4::bool IS NULL OR (status < 400) = $4)
+      AND ($5::timestamptz IS NULL OR created_at >= $5)
+      AND ($6::timestamptz IS NULL OR created_at < $6)
+"#;
+
+/// Newest first, filtered and paginated.
+pub async fn list_actions(
+    pool: &PgPool,
+    filter: &AdminActionFilter<'_>,
+    page: i32,
+    per_page: i32,
+) -> Result<ListAdminActionsResponse> {
+    let page = page.max(1);
+    let per_page = per_page.clamp(1, 100);
+    let offset = (page - 1) * per_page;
+    let method = filter.method.map(str::to_ascii_uppercase);
+
+    let items = sqlx::query_as::<_, AdminAction>(&format!(
+        r#"
+        SELECT id, admin_id::text, admin_username, method, path, route, status,
+               payload, result, changes, created_at
+        FROM admin_actions
+        {FILTER}
+        ORDER BY created_at DESC, id DESC
+        LIMIT $7 OFFSET $8
+        "#
+    ))
+    .bind(filter.admin_id)
+    .bind(&method)
+    .bind(filter.path_prefix)
+    .bind(filter.success)
+    .bind(filter.since)
+    .bind(filter.until)
+    .bind(per_page)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+    .context("failed to list admin actions")?;
+
+    let (total,): (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM admin_actions {FILTER}"))
+        .bind(filter.admin_id)
+        .bind(&method)
+        .bind(filter.path_prefix)
+        .bind(filter.success)
+        .bind(filter.since)
+        .bind(filter.until)
+        .fetch_one(pool)
+        .await
+        .context("failed to count admin actions")?;
+
+    Ok(ListAdminActionsResponse {
+        items,
+        total: Count::try_from(total)?,
+        page,
+        per_page,
+    })
+}
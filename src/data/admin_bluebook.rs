@@ -4,14 +4,18 @@
 //! concerns only; all SQL lives here.
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use sqlx::PgPool;
 use tracing::info;
 use ts_rs::TS;
 
 use crate::data::unsigned::Count;
+use crate::data::util::escape_like;
 
-use crate::data::names::{MatchCandidate, NameMatchQuality, find_best_candidate};
+use crate::data::names::{
+    MatchCandidate, NameMatchQuality, compare_instructor_names, find_best_candidate,
+};
 
 /// Domain errors for BlueBook link operations.
 ///
@@ -27,8 +31,13 @@ pub enum BluebookError {
     NotRejectable,
     #[error("instructor not found")]
     NoSuchInstructor,
+    #[error("at most {max} links can be reviewed at once")]
+    TooManyLinks { max: usize },
 }
 
+/// Largest batch accepted by [`bulk_review_links`].
+pub const MAX_BULK_LINKS: usize = 500;
+
 /// A BlueBook link row in the paginated list view.
 #[derive(Debug, Clone, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -42,6 +51,18 @@ pub struct BluebookLinkListItem {
     pub instructor_id: Option<i32>,
     pub instructor_display_name: Option<String>,
     pub eval_count: Count,
+    /// How the BlueBook name compares to the proposed instructor's name.
+    pub name_match: Option<NameMatchQuality>,
+    /// Sorted distinct subjects on the evaluations covered by this link.
+    pub eval_subjects: Vec<String>,
+    /// Distinct evaluation terms, newest first.
+    pub eval_terms: Vec<String>,
+    /// Sorted distinct subjects the proposed instructor teaches in Banner.
+    pub instructor_subjects: Vec<String>,
+    /// Discord username of the admin who approved, rejected, or assigned the link.
+    pub resolved_by: Option<String>,
+    #[ts(type = "string | null")]
+    pub resolved_at: Option<DateTime<Utc>>,
 }
 
 /// Aggregate status counts for the link list.
@@ -90,6 +111,10 @@ pub struct BluebookLinkDetail {
     pub instructor_teaching_years: Vec<i16>,
     /// Total course count for the proposed/matched instructor.
     pub instructor_course_count: Option<Count>,
+    /// Discord username of the admin who approved, rejected, or assigned the link.
+    pub resolved_by: Option<String>,
+    #[ts(type = "string | null")]
+    pub resolved_at: Option<DateTime<Utc>>,
 }
 
 /// A course associated with a BlueBook link (via evaluations).
@@ -123,6 +148,25 @@ pub struct BluebookMatchResponse {
     pub deleted_stale: usize,
 }
 
+/// Review decision applied by [`bulk_review_links`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkDecision {
+    Approve,
+    Reject,
+}
+
+/// Response for the bulk approve/reject endpoints.
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BluebookBulkReviewResponse {
+    /// Links whose status changed.
+    pub updated: Vec<i32>,
+    /// Links left alone: unknown, already decided, or (when approving) with no
+    /// proposed instructor.
+    pub skipped: Vec<i32>,
+}
+
 /// Internal row type for the link list query.
 #[derive(sqlx::FromRow)]
 struct LinkListRow {
@@ -134,21 +178,77 @@ struct LinkListRow {
     instructor_id: Option<i32>,
     instructor_display_name: Option<String>,
     eval_count: Option<i64>,
+    eval_subjects: Vec<String>,
+    eval_terms: Vec<String>,
+    instructor_subjects: Vec<String>,
+    resolved_by: Option<String>,
+    resolved_at: Option<DateTime<Utc>>,
+}
+
+impl LinkListRow {
+    fn into_item(self) -> Result<BluebookLinkListItem> {
+        let name_match = self
+            .instructor_display_name
+            .as_deref()
+            .map(|name| compare_instructor_names(&self.instructor_name, name).quality);
+        Ok(BluebookLinkListItem {
+            id: self.id,
+            instructor_name: self.instructor_name,
+            subject: self.subject,
+            status: self.status,
+            confidence: self.confidence,
+            instructor_id: self.instructor_id,
+            instructor_display_name: self.instructor_display_name,
+            eval_count: Count::try_from(self.eval_count.unwrap_or(0))?,
+            name_match,
+            eval_subjects: self.eval_subjects,
+            eval_terms: self.eval_terms,
+            instructor_subjects: self.instructor_subjects,
+            resolved_by: self.resolved_by,
+            resolved_at: self.resolved_at,
+        })
+    }
 }
 
+/// Columns for [`LinkListRow`], with review context for the proposed match.
+const LINK_SELECT: &str = r#"
+        SELECT
+            bl.id,
+            bl.instructor_name,
+            bl.subject,
+            bl.status,
+            bl.confidence,
+            bl.instructor_id,
+            i.display_name AS instructor_display_name,
+            (SELECT COUNT(*) FROM bluebook_evaluations be
+             WHERE be.instructor_name = bl.instructor_name
+               AND (bl.subject IS NULL OR be.subject = bl.subject)
+            ) AS eval_count,
+            ARRAY(SELECT DISTINCT be.subject::text FROM bluebook_evaluations be
+                  WHERE be.instructor_name = bl.instructor_name
+                    AND (bl.subject IS NULL OR be.subject = bl.subject)
+                  ORDER BY 1) AS eval_subjects,
+            ARRAY(SELECT DISTINCT be.term::text FROM bluebook_evaluations be
+                  WHERE be.instructor_name = bl.instructor_name
+                    AND (bl.subject IS NULL OR be.subject = bl.subject)
+                  ORDER BY 1 DESC) AS eval_terms,
+            ARRAY(SELECT DISTINCT c.subject::text FROM course_instructors ci
+                  JOIN courses c ON c.id = ci.course_id
+                  WHERE ci.instructor_id = bl.instructor_id
+                  ORDER BY 1) AS instructor_subjects,
+            u.discord_username AS resolved_by,
+            bl.resolved_at
+        FROM instructor_bluebook_links bl
+        LEFT JOIN instructors i ON i.id = bl.instructor_id
+        LEFT JOIN users u ON u.id = bl.resolved_by
+"#;
+
 #[derive(sqlx::FromRow)]
 struct StatusCount {
     status: String,
     count: i64,
 }
 
-/// Escape LIKE/ILIKE metacharacters so user input is treated as literal text.
-fn escape_like(s: &str) -> String {
-    s.replace('\\', "\\\\")
-        .replace('%', "\\%")
-        .replace('_', "\\_")
-}
-
 /// Filter/sort/pagination params for listing links.
 pub struct ListBluebookLinksFilter {
     pub status: Option<String>,
@@ -188,20 +288,7 @@ pub async fn list_links(
 
     let query_str = format!(
         r#"
-        SELECT
-            bl.id,
-            bl.instructor_name,
-            bl.subject,
-            bl.status,
-            bl.confidence,
-            bl.instructor_id,
-            i.display_name AS instructor_display_name,
-            (SELECT COUNT(*) FROM bluebook_evaluations be
-             WHERE be.instructor_name = bl.instructor_name
-               AND (bl.subject IS NULL OR be.subject = bl.subject)
-            ) AS eval_count
-        FROM instructor_bluebook_links bl
-        LEFT JOIN instructors i ON i.id = bl.instructor_id
+        {LINK_SELECT}
         {where_clause}
         ORDER BY
             CASE bl.status WHEN 'pending' THEN 0 WHEN 'auto' THEN 1 WHEN 'approved' THEN 2 ELSE 3 END,
@@ -269,18 +356,7 @@ pub async fn list_links(
 
     let links = rows
         .into_iter()
-        .map(|r| {
-            Ok(BluebookLinkListItem {
-                id: r.id,
-                instructor_name: r.instructor_name,
-                subject: r.subject,
-                status: r.status,
-                confidence: r.confidence,
-                instructor_id: r.instructor_id,
-                instructor_display_name: r.instructor_display_name,
-                eval_count: Count::try_from(r.eval_count.unwrap_or(0))?,
-            })
-        })
+        .map(LinkListRow::into_item)
         .collect::<Result<Vec<_>>>()?;
 
     Ok(ListBluebookLinksResponse {
@@ -292,31 +368,140 @@ pub async fn list_links(
     })
 }
 
-/// Fetch detail for a single BlueBook link, including associated evaluations.
-pub async fn get_link_detail(pool: &PgPool, link_id: i32) -> Result<BluebookLinkDetail> {
-    let row: Option<LinkListRow> = sqlx::query_as(
+/// An instructor who taught a section evaluated under an unmatched name.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BluebookSuggestion {
+    pub instructor_id: i32,
+    pub display_name: String,
+    /// Evaluated sections (by CRN and term) this instructor taught.
+    pub shared_sections: Count,
+    pub name_match: NameMatchQuality,
+}
+
+/// A pending link with no proposed instructor, plus instructors worth checking.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BluebookUnmatchedLink {
+    pub link: BluebookLinkListItem,
+    /// Most shared sections first.
+    pub suggestions: Vec<BluebookSuggestion>,
+}
+
+/// Response for the unmatched link queue.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListBluebookUnmatchedResponse {
+    pub links: Vec<BluebookUnmatchedLink>,
+    pub total: Count,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+#[derive(sqlx::FromRow)]
+struct SuggestionRow {
+    instructor_name: String,
+    instructor_id: i32,
+    display_name: String,
+    shared_sections: i64,
+}
+
+/// List pending links that auto-matching couldn't pair with an instructor.
+///
+/// These are the names where no candidate's name matched. Each comes with the
+/// instructors Banner lists for the evaluated sections, so a reviewer can
+/// spot nicknames, maiden names, and other mismatches the name rules miss.
+pub async fn list_unmatched_links(
+    pool: &PgPool,
+    search: Option<&str>,
+    page: i32,
+    per_page: i32,
+) -> Result<ListBluebookUnmatchedResponse> {
+    let page = page.max(1);
+    let per_page = per_page.clamp(1, 100);
+    let offset = (page - 1) * per_page;
+    let pattern = search.map(|s| format!("%{}%", escape_like(s)));
+
+    let where_clause = "WHERE bl.instructor_id IS NULL AND bl.status = 'pending' \
+                        AND ($1::text IS NULL OR bl.instructor_name ILIKE $1)";
+    let rows: Vec<LinkListRow> = sqlx::query_as(&format!(
+        "{LINK_SELECT} {where_clause} ORDER BY eval_count DESC, bl.instructor_name LIMIT {per_page} OFFSET {offset}"
+    ))
+    .bind(&pattern)
+    .fetch_all(pool)
+    .await
+    .context("failed to list unmatched bluebook links")?;
+
+    let (total,): (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM instructor_bluebook_links bl {where_clause}"
+    ))
+    .bind(&pattern)
+    .fetch_one(pool)
+    .await
+    .context("failed to count unmatched bluebook links")?;
+
+    let names: Vec<&str> = rows.iter().map(|r| r.instructor_name.as_str()).collect();
+    let suggestions: Vec<SuggestionRow> = sqlx::query_as(
         r#"
-        SELECT
-            bl.id,
-            bl.instructor_name,
-            bl.subject,
-            bl.status,
-            bl.confidence,
-            bl.instructor_id,
-            i.display_name AS instructor_display_name,
-            (SELECT COUNT(*) FROM bluebook_evaluations be
-             WHERE be.instructor_name = bl.instructor_name
-               AND (bl.subject IS NULL OR be.subject = bl.subject)
-            ) AS eval_count
-        FROM instructor_bluebook_links bl
-        LEFT JOIN instructors i ON i.id = bl.instructor_id
-        WHERE bl.id = $1
+        SELECT be.instructor_name, i.id AS instructor_id, i.display_name,
+               COUNT(DISTINCT (be.term, be.crn)) AS shared_sections
+        FROM bluebook_evaluations be
+        JOIN courses c ON c.crn = be.crn AND c.term_code = be.term
+        JOIN course_instructors ci ON ci.course_id = c.id
+        JOIN instructors i ON i.id = ci.instructor_id
+        WHERE be.instructor_name = ANY($1)
+          AND be.crn IS NOT NULL
+          AND be.crn != ''
+        GROUP BY be.instructor_name, i.id, i.display_name
+        ORDER BY shared_sections DESC, i.display_name
         "#,
     )
-    .bind(link_id)
-    .fetch_optional(pool)
+    .bind(&names)
+    .fetch_all(pool)
     .await
-    .context("failed to fetch bluebook link")?;
+    .context("failed to fetch bluebook suggestions")?;
+
+    let links = rows
+        .into_iter()
+        .map(|row| {
+            let suggestions = suggestions
+                .iter()
+                .filter(|s| s.instructor_name == row.instructor_name)
+                .map(|s| {
+                    Ok(BluebookSuggestion {
+                        instructor_id: s.instructor_id,
+                        display_name: s.display_name.clone(),
+                        shared_sections: Count::try_from(s.shared_sections)?,
+                        name_match: compare_instructor_names(&row.instructor_name, &s.display_name)
+                            .quality,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(BluebookUnmatchedLink {
+                link: row.into_item()?,
+                suggestions,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ListBluebookUnmatchedResponse {
+        links,
+        total: Count::try_from(total)?,
+        page,
+        per_page,
+    })
+}
+
+/// Fetch detail for a single BlueBook link, including associated evaluations.
+pub async fn get_link_detail(pool: &PgPool, link_id: i32) -> Result<BluebookLinkDetail> {
+    let row: Option<LinkListRow> = sqlx::query_as(&format!("{LINK_SELECT} WHERE bl.id = $1"))
+        .bind(link_id)
+        .fetch_optional(pool)
+        .await
+        .context("failed to fetch bluebook link")?;
 
     let r = row.ok_or(BluebookError::NoSuchLink)?;
 
@@ -402,15 +587,18 @@ pub async fn get_link_detail(pool: &PgPool, link_id: i32) -> Result<BluebookLink
         instructor_subjects,
         instructor_teaching_years,
         instructor_course_count,
+        resolved_by: r.resolved_by,
+        resolved_at: r.resolved_at,
     })
 }
 
-/// Approve an auto or pending BlueBook link.
-pub async fn approve_link(pool: &PgPool, link_id: i32) -> Result<()> {
+/// Approve an auto or pending BlueBook link on behalf of `resolved_by`.
+pub async fn approve_link(pool: &PgPool, link_id: i32, resolved_by: i64) -> Result<()> {
     let result = sqlx::query(
-        "UPDATE instructor_bluebook_links SET status = 'approved', updated_at = NOW() WHERE id = $1 AND status IN ('auto', 'pending')",
+        "UPDATE instructor_bluebook_links SET status = 'approved', updated_at = NOW(), resolved_by = $2, resolved_at = NOW() WHERE id = $1 AND status IN ('auto', 'pending')",
     )
     .bind(link_id)
+    .bind(resolved_by)
     .execute(pool)
     .await
     .context("failed to approve bluebook link")?;
@@ -422,12 +610,13 @@ pub async fn approve_link(pool: &PgPool, link_id: i32) -> Result<()> {
     Ok(())
 }
 
-/// Reject an auto or pending BlueBook link.
-pub async fn reject_link(pool: &PgPool, link_id: i32) -> Result<()> {
+/// Reject an auto or pending BlueBook link on behalf of `resolved_by`.
+pub async fn reject_link(pool: &PgPool, link_id: i32, resolved_by: i64) -> Result<()> {
     let result = sqlx::query(
-        "UPDATE instructor_bluebook_links SET status = 'rejected', updated_at = NOW() WHERE id = $1 AND status IN ('auto', 'pending')",
+        "UPDATE instructor_bluebook_links SET status = 'rejected', updated_at = NOW(), resolved_by = $2, resolved_at = NOW() WHERE id = $1 AND status IN ('auto', 'pending')",
     )
     .bind(link_id)
+    .bind(resolved_by)
     .execute(pool)
     .await
     .context("failed to reject bluebook link")?;
@@ -439,8 +628,64 @@ pub async fn reject_link(pool: &PgPool, link_id: i32) -> Result<()> {
     Ok(())
 }
 
+/// Approve or reject many auto/pending links at once.
+///
+/// Approval only applies to links with a proposed instructor. Ids that can't
+/// be changed are reported as skipped rather than failing the batch.
+pub async fn bulk_review_links(
+    pool: &PgPool,
+    ids: &[i32],
+    decision: LinkDecision,
+    resolved_by: i64,
+) -> Result<BluebookBulkReviewResponse> {
+    if ids.len() > MAX_BULK_LINKS {
+        return Err(BluebookError::TooManyLinks {
+            max: MAX_BULK_LINKS,
+        }
+        .into());
+    }
+
+    let (status, needs_instructor) = match decision {
+        LinkDecision::Approve => ("approved", true),
+        LinkDecision::Reject => ("rejected", false),
+    };
+    let mut updated: Vec<i32> = sqlx::query_scalar(
+        r#"
+        UPDATE instructor_bluebook_links
+        SET status = $1, updated_at = NOW(), resolved_by = $4, resolved_at = NOW()
+        WHERE id = ANY($2)
+          AND status IN ('auto', 'pending')
+          AND (NOT $3 OR instructor_id IS NOT NULL)
+        RETURNING id
+        "#,
+    )
+    .bind(status)
+    .bind(ids)
+    .bind(needs_instructor)
+    .bind(resolved_by)
+    .fetch_all(pool)
+    .await
+    .context("failed to bulk review bluebook links")?;
+    updated.sort_unstable();
+
+    let mut skipped: Vec<i32> = ids
+        .iter()
+        .copied()
+        .filter(|id| updated.binary_search(id).is_err())
+        .collect();
+    skipped.sort_unstable();
+    skipped.dedup();
+
+    Ok(BluebookBulkReviewResponse { updated, skipped })
+}
+
 /// Manually assign an instructor to a BlueBook link and approve it.
-pub async fn assign_link(pool: &PgPool, link_id: i32, instructor_id: i32) -> Result<()> {
+pub async fn assign_link(
+    pool: &PgPool,
+    link_id: i32,
+    instructor_id: i32,
+    resolved_by: i64,
+) -> Result<()> {
     // Verify instructor exists
     let exists: Option<(i32,)> = sqlx::query_as("SELECT id FROM instructors WHERE id = $1")
         .bind(instructor_id)
@@ -457,13 +702,16 @@ pub async fn assign_link(pool: &PgPool, link_id: i32, instructor_id: i32) -> Res
         UPDATE instructor_bluebook_links
         SET instructor_id = $1,
             status = 'approved',
-            updated_at = NOW()
+            updated_at = NOW(),
+            resolved_by = $3,
+            resolved_at = NOW()
         WHERE id = $2
           AND status IN ('auto', 'pending')
         "#,
     )
     .bind(instructor_id)
     .bind(link_id)
+    .bind(resolved_by)
     .execute(pool)
     .await
     .context("failed to assign bluebook link")?;
@@ -720,36 +968,6 @@ async fn insert_link(
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_escape_like_no_metacharacters() {
-        assert_eq!(escape_like("John Smith"), "John Smith");
-    }
-
-    #[test]
-    fn test_escape_like_percent() {
-        assert_eq!(escape_like("100%"), "100\\%");
-    }
-
-    #[test]
-    fn test_escape_like_underscore() {
-        assert_eq!(escape_like("foo_bar"), "foo\\_bar");
-    }
-
-    #[test]
-    fn test_escape_like_backslash() {
-        assert_eq!(escape_like("path\\to"), "path\\\\to");
-    }
-
-    #[test]
-    fn test_escape_like_all_metacharacters() {
-        assert_eq!(escape_like("%_\\"), "\\%\\_\\\\");
-    }
-
-    #[test]
-    fn test_escape_like_empty_string() {
-        assert_eq!(escape_like(""), "");
-    }
-
     #[test]
     fn test_bluebook_error_downcast() {
         let err: anyhow::Error = BluebookError::NoSuchLink.into();
@@ -9,6 +9,7 @@ use sqlx::PgPool;
 use tracing::warn;
 use ts_rs::TS;
 
+use crate::data::events::EventBuffer;
 use crate::data::models::RmpMatchStatus;
 use crate::data::rmp_matching::ScoreBreakdown;
 
@@ -543,20 +544,37 @@ pub async fn accept_candidate(
     }
 
     let mut tx = pool.begin().await.context("failed to begin transaction")?;
+    confirm_link(&mut tx, instructor_id, rmp_legacy_id, resolved_by).await?;
+    tx.commit().await.context("failed to commit transaction")?;
+    crate::data::rmp::refresh_rmp_summary(pool)
+        .await
+        .context("failed to refresh rmp summary")?;
 
+    Ok(())
+}
+
+/// Link the pair manually, confirm the instructor, and mark the candidate accepted.
+async fn confirm_link(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    instructor_id: i32,
+    rmp_legacy_id: i32,
+    resolved_by: i64,
+) -> Result<()> {
     sqlx::query(
-        "INSERT INTO instructor_rmp_links (instructor_id, rmp_legacy_id, created_by, source) VALUES ($1, $2, $3, 'manual') ON CONFLICT (rmp_legacy_id) DO NOTHING",
+        "INSERT INTO instructor_rmp_links (instructor_id, rmp_legacy_id, created_by, source) VALUES ($1, $2, $3, 'manual') \
+         ON CONFLICT (rmp_legacy_id) DO UPDATE SET source = 'manual', created_by = EXCLUDED.created_by \
+         WHERE instructor_rmp_links.instructor_id = EXCLUDED.instructor_id",
     )
     .bind(instructor_id)
     .bind(rmp_legacy_id)
     .bind(resolved_by)
-    .execute(&mut *tx)
+    .execute(&mut **tx)
     .await
     .context("failed to insert rmp link")?;
 
     sqlx::query("UPDATE instructors SET rmp_match_status = 'confirmed' WHERE id = $1")
         .bind(instructor_id)
-        .execute(&mut *tx)
+        .execute(&mut **tx)
         .await
         .context("failed to update instructor match status")?;
 
@@ -566,15 +584,10 @@ pub async fn accept_candidate(
     .bind(resolved_by)
     .bind(instructor_id)
     .bind(rmp_legacy_id)
-    .execute(&mut *tx)
+    .execute(&mut **tx)
     .await
     .context("failed to accept candidate")?;
 
-    tx.commit().await.context("failed to commit transaction")?;
-    crate::data::rmp::refresh_rmp_summary(pool)
-        .await
-        .context("failed to refresh rmp summary")?;
-
     Ok(())
 }
 
@@ -656,9 +669,402 @@ pub async fn instructor_exists(pool: &PgPool, id: i32) -> Result<bool> {
     Ok(exists.is_some())
 }
 
+/// One candidate pair and the decision recorded for it, flattened for
+/// spreadsheet review.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct MatchDecisionRow {
+    pub instructor_id: i32,
+    pub instructor_name: String,
+    pub instructor_email: Option<String>,
+    pub rmp_legacy_id: i32,
+    pub rmp_name: String,
+    pub rmp_department: Option<String>,
+    pub score: f32,
+    pub name_score: f32,
+    pub department_score: f32,
+    pub review_courses_score: f32,
+    pub subject_score: f32,
+    pub uniqueness_score: f32,
+    pub volume_score: f32,
+    /// `auto`, `confirmed`, `rejected`, or `pending`.
+    pub decision: String,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub resolved_by: Option<i64>,
+}
+
+/// Stream every candidate pair with its score breakdown and current decision.
+pub fn stream_match_decisions(
+    pool: &PgPool,
+) -> impl futures::Stream<Item = Result<MatchDecisionRow, sqlx::Error>> + Send + Unpin + '_ {
+    sqlx::query_as::<_, MatchDecisionRow>(
+        r#"
+        SELECT mc.instructor_id, i.display_name AS instructor_name, i.email AS instructor_email,
+               mc.rmp_legacy_id, rp.first_name || ' ' || rp.last_name AS rmp_name,
+               rp.department AS rmp_department, mc.score,
+               COALESCE((mc.score_breakdown->>'name')::real, 0) AS name_score,
+               COALESCE((mc.score_breakdown->>'department')::real, 0) AS department_score,
+               COALESCE((mc.score_breakdown->>'reviewCourses')::real, 0) AS review_courses_score,
+               COALESCE((mc.score_breakdown->>'subject')::real, 0) AS subject_score,
+               COALESCE((mc.score_breakdown->>'uniqueness')::real, 0) AS uniqueness_score,
+               COALESCE((mc.score_breakdown->>'volume')::real, 0) AS volume_score,
+               CASE
+                   WHEN mc.status = 'accepted' AND l.source = 'auto' THEN 'auto'
+                   WHEN mc.status = 'accepted' THEN 'confirmed'
+                   WHEN mc.status = 'rejected' THEN 'rejected'
+                   ELSE 'pending'
+               END AS decision,
+               mc.resolved_at, mc.resolved_by
+        FROM rmp_match_candidates mc
+        JOIN instructors i ON i.id = mc.instructor_id
+        JOIN rmp_professors rp ON rp.legacy_id = mc.rmp_legacy_id
+        LEFT JOIN instructor_rmp_links l
+            ON l.instructor_id = mc.instructor_id AND l.rmp_legacy_id = mc.rmp_legacy_id
+        ORDER BY i.display_name, mc.instructor_id, mc.score DESC
+        "#,
+    )
+    .fetch(pool)
+}
+
+/// A reviewer's verdict on one candidate pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchDecision {
+    Confirm,
+    Reject,
+}
+
+/// Apply a decision to a candidate pair, returning whether anything changed.
+///
+/// Confirming an auto-link turns it into a manual one. Rejecting a linked pair
+/// removes the link first. Does not refresh the RMP summary; callers applying
+/// a batch should do that once at the end.
+pub async fn apply_match_decision(
+    pool: &PgPool,
+    instructor_id: i32,
+    rmp_legacy_id: i32,
+    decision: MatchDecision,
+    resolved_by: i64,
+) -> Result<bool> {
+    let mut tx = pool.begin().await.context("failed to begin transaction")?;
+
+    let current: Option<(String, Option<String>)> = sqlx::query_as(
+        "SELECT mc.status, l.source FROM rmp_match_candidates mc \
+         LEFT JOIN instructor_rmp_links l \
+             ON l.instructor_id = mc.instructor_id AND l.rmp_legacy_id = mc.rmp_legacy_id \
+         WHERE mc.instructor_id = $1 AND mc.rmp_legacy_id = $2 \
+         FOR UPDATE OF mc",
+    )
+    .bind(instructor_id)
+    .bind(rmp_legacy_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .context("failed to fetch candidate")?;
+
+    let (status, link_source) = current.ok_or_else(|| anyhow!("candidate not found"))?;
+
+    match decision {
+        MatchDecision::Confirm => {
+            if status == "accepted" && link_source.as_deref() == Some("manual") {
+                return Ok(false);
+            }
+            let conflict: Option<(i32,)> = sqlx::query_as(
+                "SELECT instructor_id FROM instructor_rmp_links WHERE rmp_legacy_id = $1 AND instructor_id != $2",
+            )
+            .bind(rmp_legacy_id)
+            .bind(instructor_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("failed to check rmp uniqueness")?;
+            if let Some((other_id,)) = conflict {
+                return Err(anyhow!(
+                    "RMP profile already linked to instructor {other_id}"
+                ));
+            }
+            confirm_link(&mut tx, instructor_id, rmp_legacy_id, resolved_by).await?;
+        }
+        MatchDecision::Reject => {
+            if status == "rejected" {
+                return Ok(false);
+            }
+            if link_source.is_some() {
+                sqlx::query(
+                    "DELETE FROM instructor_rmp_links WHERE instructor_id = $1 AND rmp_legacy_id = $2",
+                )
+                .bind(instructor_id)
+                .bind(rmp_legacy_id)
+                .execute(&mut *tx)
+                .await
+                .context("failed to delete rmp link")?;
+                sqlx::query(
+                    "UPDATE instructors SET rmp_match_status = 'unmatched' \
+                     WHERE id = $1 AND NOT EXISTS (SELECT 1 FROM instructor_rmp_links WHERE instructor_id = $1)",
+                )
+                .bind(instructor_id)
+                .execute(&mut *tx)
+                .await
+                .context("failed to update instructor match status")?;
+            }
+            sqlx::query(
+                "UPDATE rmp_match_candidates SET status = 'rejected', resolved_at = NOW(), resolved_by = $1 \
+                 WHERE instructor_id = $2 AND rmp_legacy_id = $3",
+            )
+            .bind(resolved_by)
+            .bind(instructor_id)
+            .bind(rmp_legacy_id)
+            .execute(&mut *tx)
+            .await
+            .context("failed to reject candidate")?;
+        }
+    }
+
+    tx.commit().await.context("failed to commit transaction")?;
+    Ok(true)
+}
+
+/// Most candidates accepted or rejected in one bulk request.
+pub const MAX_BULK_CANDIDATES: usize = 500;
+
+/// Request errors from [`review_candidates`], downcast by the web layer to
+/// pick a status code.
+#[derive(Debug, thiserror::Error)]
+pub enum CandidateReviewError {
+    #[error("at most {max} candidates can be reviewed at once")]
+    TooManyCandidates { max: usize },
+}
+
+/// A candidate in the cross-instructor review queue.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CandidateQueueItem {
+    pub id: i32,
+    pub instructor_id: i32,
+    pub instructor_name: String,
+    pub instructor_email: Option<String>,
+    #[ts(as = "RmpMatchStatus")]
+    pub instructor_status: String,
+    pub rmp_legacy_id: i32,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub department: Option<String>,
+    pub avg_rating: Option<f32>,
+    pub num_ratings: Option<i32>,
+    pub score: f32,
+    #[ts(as = "ScoreBreakdown")]
+    pub score_breakdown: sqlx::types::Json<ScoreBreakdown>,
+    pub status: String,
+    #[ts(type = "string")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[ts(type = "string | null")]
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Response for the paginated candidate queue.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListCandidatesResponse {
+    pub candidates: Vec<CandidateQueueItem>,
+    #[ts(as = "i32")]
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+/// Filter/pagination params for the candidate queue.
+pub struct ListCandidatesFilter {
+    /// Candidate status; `None` lists every status.
+    pub status: Option<String>,
+    /// Matched against the instructor's name and email and the RMP name.
+    pub search: Option<String>,
+    pub min_score: Option<f32>,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+/// List match candidates across all instructors, highest score first.
+pub async fn list_candidates(
+    pool: &PgPool,
+    filter: &ListCandidatesFilter,
+) -> Result<ListCandidatesResponse> {
+    let page = filter.page.max(1);
+    let per_page = filter.per_page.clamp(1, 100);
+    let offset = (page - 1) * per_page;
+
+    let mut conditions = Vec::new();
+    let mut bind_idx = 0u32;
+    if filter.status.is_some() {
+        bind_idx += 1;
+        conditions.push(format!("mc.status = ${bind_idx}"));
+    }
+    if filter.search.is_some() {
+        bind_idx += 1;
+        conditions.push(format!(
+            "(i.display_name ILIKE ${bind_idx} OR i.email ILIKE ${bind_idx} \
+             OR rp.first_name || ' ' || rp.last_name ILIKE ${bind_idx})"
+        ));
+    }
+    if filter.min_score.is_some() {
+        bind_idx += 1;
+        conditions.push(format!("mc.score >= ${bind_idx}"));
+    }
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let from_clause = format!(
+        "FROM rmp_match_candidates mc \
+         JOIN instructors i ON i.id = mc.instructor_id \
+         JOIN rmp_professors rp ON rp.legacy_id = mc.rmp_legacy_id \
+         {where_clause}"
+    );
+    let query_str = format!(
+        r#"
+        SELECT mc.id, mc.instructor_id, i.display_name AS instructor_name,
+               i.email AS instructor_email, i.rmp_match_status AS instructor_status,
+               mc.rmp_legacy_id, rp.first_name, rp.last_name, rp.department,
+               rp.avg_rating, rp.num_ratings, mc.score, mc.score_breakdown, mc.status,
+               mc.created_at, mc.resolved_at
+        {from_clause}
+        ORDER BY mc.score DESC, mc.id
+        LIMIT {per_page} OFFSET {offset}
+        "#
+    );
+    let count_query_str = format!("SELECT COUNT(*) {from_clause}");
+
+    let mut query = sqlx::query_as::<_, CandidateQueueItem>(&query_str);
+    let mut count_query = sqlx::query_as::<_, (i64,)>(&count_query_str);
+    if let Some(ref status) = filter.status {
+        query = query.bind(status);
+        count_query = count_query.bind(status);
+    }
+    if let Some(ref search) = filter.search {
+        query = query.bind(format!("%{search}%"));
+        count_query = count_query.bind(format!("%{search}%"));
+    }
+    if let Some(min_score) = filter.min_score {
+        query = query.bind(min_score);
+        count_query = count_query.bind(min_score);
+    }
+
+    let candidates = query
+        .fetch_all(pool)
+        .await
+        .context("failed to list candidates")?;
+    let (total,) = count_query
+        .fetch_one(pool)
+        .await
+        .context("failed to count candidates")?;
+
+    Ok(ListCandidatesResponse {
+        candidates,
+        total,
+        page,
+        per_page,
+    })
+}
+
+/// Outcome of accepting or rejecting a batch of candidates.
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CandidateReviewResponse {
+    /// Candidates that were pending and now carry the decision.
+    pub updated: Vec<i32>,
+    /// Candidates that don't exist or were already resolved.
+    pub skipped: Vec<i32>,
+    /// Candidates whose RMP profile is already linked to another instructor.
+    pub conflicts: Vec<i32>,
+}
+
+/// Accept or reject pending candidates by candidate id.
+///
+/// The whole batch runs in one transaction, so accepted candidates gain their
+/// link, their instructor is confirmed, and the candidate is resolved together
+/// or not at all. Accepting refreshes the RMP summary once at the end.
+pub async fn review_candidates(
+    pool: &PgPool,
+    ids: &[i32],
+    decision: MatchDecision,
+    resolved_by: i64,
+) -> Result<CandidateReviewResponse> {
+    if ids.len() > MAX_BULK_CANDIDATES {
+        return Err(CandidateReviewError::TooManyCandidates {
+            max: MAX_BULK_CANDIDATES,
+        }
+        .into());
+    }
+    let mut ids = ids.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let mut tx = pool.begin().await.context("failed to begin transaction")?;
+
+    let pending: Vec<(i32, i32, i32)> = sqlx::query_as(
+        "SELECT id, instructor_id, rmp_legacy_id FROM rmp_match_candidates \
+         WHERE id = ANY($1) AND status = 'pending' \
+         ORDER BY id \
+         FOR UPDATE",
+    )
+    .bind(&ids)
+    .fetch_all(&mut *tx)
+    .await
+    .context("failed to lock candidates")?;
+
+    let mut response = CandidateReviewResponse::default();
+    for (id, instructor_id, rmp_legacy_id) in &pending {
+        match decision {
+            MatchDecision::Confirm => {
+                let conflict: Option<(i32,)> = sqlx::query_as(
+                    "SELECT instructor_id FROM instructor_rmp_links WHERE rmp_legacy_id = $1 AND instructor_id != $2",
+                )
+                .bind(rmp_legacy_id)
+                .bind(instructor_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .context("failed to check rmp uniqueness")?;
+                if conflict.is_some() {
+                    response.conflicts.push(*id);
+                    continue;
+                }
+                confirm_link(&mut tx, *instructor_id, *rmp_legacy_id, resolved_by).await?;
+            }
+            MatchDecision::Reject => {
+                sqlx::query(
+                    "UPDATE rmp_match_candidates SET status = 'rejected', resolved_at = NOW(), resolved_by = $1 WHERE id = $2",
+                )
+                .bind(resolved_by)
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .context("failed to reject candidate")?;
+            }
+        }
+        response.updated.push(*id);
+    }
+
+    tx.commit().await.context("failed to commit transaction")?;
+
+    response.skipped = ids
+        .into_iter()
+        .filter(|id| !pending.iter().any(|(pending_id, _, _)| pending_id == id))
+        .collect();
+
+    if decision == MatchDecision::Confirm && !response.updated.is_empty() {
+        crate::data::rmp::refresh_rmp_summary(pool)
+            .await
+            .context("failed to refresh rmp summary")?;
+    }
+
+    Ok(response)
+}
+
 /// Re-run RMP candidate generation and return scoring statistics.
-pub async fn rescore(pool: &PgPool) -> Result<RescoreResponse> {
-    let stats = crate::data::rmp_matching::generate_candidates(pool)
+///
+/// Progress is streamed to admins over the `rmpMatching` stream via `events`.
+pub async fn rescore(pool: &PgPool, events: &EventBuffer) -> Result<RescoreResponse> {
+    let stats = crate::data::rmp_matching::generate_candidates(pool, events)
         .await
         .context("candidate generation failed")?;
 
@@ -57,7 +57,7 @@ pub async fn compute_stats(
 
     let queue_row = sqlx::query(
         "SELECT \
-            COUNT(*) FILTER (WHERE locked_at IS NULL) AS pending_jobs, \
+            COUNT(*) FILTER (WHERE locked_at IS NULL AND dead_lettered_at IS NULL) AS pending_jobs, \
             COUNT(*) FILTER (WHERE locked_at IS NOT NULL) AS locked_jobs \
          FROM scrape_jobs",
     )
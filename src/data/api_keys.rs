@@ -0,0 +1,166 @@
+//! Database operations for per-user API keys.
+//!
+//! Raw keys are only ever returned from [`create_key`] and never sent to
+//! Postgres: keys are hashed here and only the digest is stored or queried.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use ts_rs::TS;
+
+use crate::data::models::serialize_i64_as_string;
+
+/// Prefix on every issued key, so leaked keys are easy to grep for.
+pub const KEY_PREFIX: &str = "bnr_";
+
+/// Hex characters after [`KEY_PREFIX`].
+const KEY_HEX_LEN: usize = 64;
+
+/// Characters of the key kept in `key_prefix` for display.
+const DISPLAY_PREFIX_LEN: usize = KEY_PREFIX.len() + 8;
+
+/// An issued API key, without its secret.
+#[derive(sqlx::FromRow, Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ApiKey {
+    pub id: i32,
+    #[serde(serialize_with = "serialize_i64_as_string")]
+    #[ts(type = "string")]
+    pub user_id: i64,
+    pub name: String,
+    /// Leading characters of the key, e.g. `bnr_1a2b3c4d`.
+    pub key_prefix: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Generate a new raw key: [`KEY_PREFIX`] followed by 32 random bytes in hex.
+fn generate_key() -> String {
+    let bytes: [u8; 32] = rand::rng().random();
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("{KEY_PREFIX}{hex}")
+}
+
+/// Hex SHA-256 of a raw key, as stored in `key_hash`.
+pub fn hash_key(raw: &str) -> String {
+    Sha256::digest(raw.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Whether `raw` has the shape of an issued key, checked before any lookup.
+pub fn is_well_formed(raw: &str) -> bool {
+    raw.strip_prefix(KEY_PREFIX)
+        .is_some_and(|hex| hex.len() == KEY_HEX_LEN && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// Issue a key for `user_id`. Returns the stored row and the raw key, which
+/// is not recoverable afterwards.
+pub async fn create_key(pool: &PgPool, user_id: i64, name: &str) -> Result<(ApiKey, String)> {
+    let raw = generate_key();
+    let key = sqlx::query_as::<_, ApiKey>(
+        "INSERT INTO api_keys (user_id, name, key_prefix, key_hash) \
+         VALUES ($1, $2, $3, $4) \
+         RETURNING id, user_id, name, key_prefix, created_at, last_used_at, revoked_at",
+    )
+    .bind(user_id)
+    .bind(name)
+    .bind(&raw[..DISPLAY_PREFIX_LEN])
+    .bind(hash_key(&raw))
+    .fetch_one(pool)
+    .await
+    .context("failed to create API key")?;
+    Ok((key, raw))
+}
+
+/// List all keys, newest first, including revoked ones.
+pub async fn list_keys(pool: &PgPool) -> Result<Vec<ApiKey>> {
+    sqlx::query_as::<_, ApiKey>(
+        "SELECT id, user_id, name, key_prefix, created_at, last_used_at, revoked_at \
+         FROM api_keys ORDER BY created_at DESC",
+    )
+    .fetch_all(pool)
+    .await
+    .context("failed to list API keys")
+}
+
+/// Look up an unrevoked key by its [`hash_key`] digest.
+pub async fn find_active_key(pool: &PgPool, key_hash: &str) -> Result<Option<ApiKey>> {
+    sqlx::query_as::<_, ApiKey>(
+        "SELECT id, user_id, name, key_prefix, created_at, last_used_at, revoked_at \
+         FROM api_keys \
+         WHERE revoked_at IS NULL AND key_hash = $1",
+    )
+    .bind(key_hash)
+    .fetch_optional(pool)
+    .await
+    .context("failed to look up API key")
+}
+
+/// Rename a key. Returns the updated row, or `None` if it doesn't exist.
+pub async fn rename_key(pool: &PgPool, id: i32, name: &str) -> Result<Option<ApiKey>> {
+    sqlx::query_as::<_, ApiKey>(
+        "UPDATE api_keys SET name = $2 WHERE id = $1 \
+         RETURNING id, user_id, name, key_prefix, created_at, last_used_at, revoked_at",
+    )
+    .bind(id)
+    .bind(name)
+    .fetch_optional(pool)
+    .await
+    .context("failed to rename API key")
+}
+
+/// Revoke a key. Returns false if it doesn't exist or was already revoked.
+pub async fn revoke_key(pool: &PgPool, id: i32) -> Result<bool> {
+    let result =
+        sqlx::query("UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL")
+            .bind(id)
+            .execute(pool)
+            .await
+            .context("failed to revoke API key")?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Record that a key was used.
+pub async fn touch_key(pool: &PgPool, id: i32) -> Result<()> {
+    sqlx::query("UPDATE api_keys SET last_used_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("failed to touch API key")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_keys_are_well_formed() {
+        let key = generate_key();
+        assert!(is_well_formed(&key), "{key}");
+        assert_ne!(key, generate_key());
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert!(!is_well_formed(""));
+        assert!(!is_well_formed("bnr_abc"));
+        assert!(!is_well_formed(&format!("xyz_{}", "0".repeat(64))));
+        assert!(!is_well_formed(&format!("bnr_{}", "g".repeat(64))));
+    }
+
+    #[test]
+    fn hashes_keys_as_hex_sha256() {
+        assert_eq!(
+            hash_key("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -1,9 +1,13 @@
 //! Database query functions for the course audit log.
 
-use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
+use crate::data::events::{CourseChange, CourseEvent, CourseRef};
 use crate::data::models::AuditRow;
 
 const AUDIT_SELECT: &str = "SELECT a.id, a.course_id, a.timestamp, a.field_changed, a.old_value, a.new_value, \
@@ -11,6 +15,73 @@ const AUDIT_SELECT: &str = "SELECT a.id, a.course_id, a.timestamp, a.field_chang
      FROM course_audits a \
      LEFT JOIN courses c ON c.id = a.course_id";
 
+/// Export query; built once so the row stream can borrow it for `'static`.
+static EXPORT_QUERY: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "{AUDIT_SELECT} \
+         WHERE ($1::timestamptz IS NULL OR a.timestamp >= $1) \
+           AND ($2::timestamptz IS NULL OR a.timestamp < $2) \
+           AND ($3::text IS NULL OR c.term_code = $3) \
+         ORDER BY a.timestamp, a.id"
+    )
+});
+
+/// Record course changes as `course_audits` rows on `conn`, which should be
+/// the writer's transaction, and return them as events in the same order.
+///
+/// A course changes each field at most once per write, so rows are matched
+/// back to their change by `(course_id, field_changed)`.
+pub(crate) async fn record_course_changes(
+    conn: &mut PgConnection,
+    changes: Vec<(CourseRef, CourseChange)>,
+) -> Result<Vec<CourseEvent>> {
+    if changes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let course_ids: Vec<i32> = changes.iter().map(|(c, _)| c.id).collect();
+    let fields: Vec<&str> = changes.iter().map(|(_, ch)| ch.audit_field()).collect();
+    let (old_values, new_values): (Vec<Option<serde_json::Value>>, Vec<serde_json::Value>) =
+        changes.iter().map(|(_, ch)| ch.audit_values()).unzip();
+
+    let rows: Vec<(i32, i32, String, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        INSERT INTO course_audits (course_id, timestamp, field_changed, old_value, new_value)
+        SELECT v.course_id, NOW(), v.field_changed, v.old_value, v.new_value
+        FROM UNNEST($1::int4[], $2::text[], $3::jsonb[], $4::jsonb[])
+            AS v(course_id, field_changed, old_value, new_value)
+        RETURNING id, course_id, field_changed, timestamp
+        "#,
+    )
+    .bind(&course_ids)
+    .bind(&fields)
+    .bind(&old_values)
+    .bind(&new_values)
+    .fetch_all(&mut *conn)
+    .await
+    .context("failed to insert course audits")?;
+
+    let mut recorded: HashMap<(i32, String), (i32, DateTime<Utc>)> = rows
+        .into_iter()
+        .map(|(id, course_id, field, at)| ((course_id, field), (id, at)))
+        .collect();
+    changes
+        .into_iter()
+        .map(|(course, change)| {
+            let key = (course.id, change.audit_field().to_owned());
+            let (audit_id, at) = recorded
+                .remove(&key)
+                .with_context(|| format!("no audit recorded for {key:?}"))?;
+            Ok(CourseEvent {
+                audit_id,
+                at,
+                course,
+                change,
+            })
+        })
+        .collect()
+}
+
 /// Fetch the most recent audit log entries, newest first.
 pub async fn list_recent(pool: &PgPool, limit: i32) -> Result<Vec<AuditRow>> {
     let rows = sqlx::query_as::<_, AuditRow>(&format!(
@@ -22,6 +93,45 @@ pub async fn list_recent(pool: &PgPool, limit: i32) -> Result<Vec<AuditRow>> {
     Ok(rows)
 }
 
+/// Recent notable changes to one course, newest first.
+///
+/// Skips the initial snapshot and raw enrollment/waitlist counts, which the
+/// enrollment sparkline already covers.
+pub async fn list_course_highlights(
+    pool: &PgPool,
+    course_id: i32,
+    limit: i32,
+) -> Result<Vec<AuditRow>> {
+    let rows = sqlx::query_as::<_, AuditRow>(&format!(
+        "{AUDIT_SELECT} \
+         WHERE a.course_id = $1 \
+           AND a.field_changed NOT IN ('initial', 'enrollment', 'wait_count') \
+         ORDER BY a.timestamp DESC, a.id DESC LIMIT $2"
+    ))
+    .bind(course_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Stream audit entries oldest first, for bulk export.
+///
+/// Rows are decoded as they arrive, so exports of the full history don't
+/// buffer the table. All filters are optional.
+pub fn stream_export<'a>(
+    pool: &'a PgPool,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    term: Option<&'a str>,
+) -> impl futures::Stream<Item = Result<AuditRow, sqlx::Error>> + Send + Unpin + 'a {
+    sqlx::query_as::<_, AuditRow>(EXPORT_QUERY.as_str())
+        .bind(since)
+        .bind(until)
+        .bind(term)
+        .fetch(pool)
+}
+
 /// Fetch audit log entries with optional filters applied in SQL.
 ///
 /// All filter parameters are nullable -- passing `None` disables that filter.
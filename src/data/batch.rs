@@ -2,17 +2,19 @@
 
 use crate::banner::Course;
 use crate::banner::models::meetings::{FacultyItem, TimeRange};
+use crate::data::audit::record_course_changes;
 use crate::data::course_types::{DateRange, MeetingLocation};
-use crate::data::models::{DayOfWeek, DbMeetingTime, UpsertCounts};
+use crate::data::events::{CourseChange, CourseEvent, CourseRef, SeatField};
+use crate::data::models::{DayOfWeek, DbMeetingTime, ScrapeDiffSummary, UpsertCounts};
 use crate::data::names::{decode_html_entities, parse_banner_name};
+use crate::data::outbox;
+use crate::data::titles::normalize_course_title;
 use crate::data::unsigned::Count;
 use crate::utils::fmt_duration;
-use crate::web::audit::{AuditLogEntry, AuditRow};
 use anyhow::{Context, Result};
 use chrono::{NaiveDate, Timelike};
-use sqlx::PgConnection;
-use sqlx::PgPool;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use sqlx::{PgConnection, PgPool};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
@@ -21,6 +23,11 @@ fn parse_mm_dd_yyyy(s: &str) -> Option<NaiveDate> {
     NaiveDate::parse_from_str(s, "%m/%d/%Y").ok()
 }
 
+/// Trimmed location code, or `None` when Banner sent nothing useful.
+fn non_blank(code: Option<&str>) -> Option<&str> {
+    code.map(str::trim).filter(|c| !c.is_empty())
+}
+
 /// Convert a Banner API course's meeting times to the DB JSONB shape.
 fn to_db_meeting_times(course: &Course) -> serde_json::Value {
     let meetings: Vec<DbMeetingTime> = course
@@ -155,10 +162,15 @@ fn extract_campus_code(course: &Course) -> Option<String> {
 
 /// Row returned by the CTE-based upsert query, carrying both old and new values
 /// for every auditable field. `old_id` is `None` for fresh inserts.
+///
+/// Unchanged rows (`changed == false`) come back with only their new values;
+/// every `old_*` field is `None` for them.
 #[derive(sqlx::FromRow, Debug)]
 struct UpsertDiffRow {
     id: i32,
     old_id: Option<i32>,
+    /// Inserted, or at least one column differed from the stored row.
+    changed: bool,
     crn: String,
     term_code: String,
 
@@ -219,11 +231,43 @@ struct UpsertDiffRow {
     new_attributes: serde_json::Value,
 }
 
-struct AuditEntry {
-    course_id: i32,
-    field_changed: &'static str,
-    old_value: Option<serde_json::Value>,
-    new_value: serde_json::Value,
+impl UpsertDiffRow {
+    fn course_ref(&self) -> CourseRef {
+        CourseRef {
+            id: self.id,
+            term: self.term_code.clone(),
+            crn: self.crn.clone(),
+            subject: self.new_subject.clone(),
+            course_number: self.new_course_number.clone(),
+            title: self.new_title.clone(),
+        }
+    }
+
+    /// `(field, old, new)` for each seat count.
+    fn seats(&self) -> [(SeatField, Option<i32>, i32); 4] {
+        [
+            (
+                SeatField::Enrollment,
+                self.old_enrollment,
+                self.new_enrollment,
+            ),
+            (
+                SeatField::MaxEnrollment,
+                self.old_max_enrollment,
+                self.new_max_enrollment,
+            ),
+            (
+                SeatField::WaitCount,
+                self.old_wait_count,
+                self.new_wait_count,
+            ),
+            (
+                SeatField::WaitCapacity,
+                self.old_wait_capacity,
+                self.new_wait_capacity,
+            ),
+        ]
+    }
 }
 
 struct MetricEntry {
@@ -233,68 +277,75 @@ struct MetricEntry {
     seats_available: i32,
 }
 
-/// Compare old vs new for a single field, pushing an `AuditEntry` when they differ.
+/// Compare old vs new for a single field, pushing a [`CourseChange::Field`] when they differ.
 ///
 /// Three variants:
-/// - `diff_field!(audits, row, field_name, old_field, new_field)` -- `Option<T>` old vs `T` new
-/// - `diff_field!(opt audits, row, field_name, old_field, new_field)` -- `Option<T>` old vs `Option<T>` new
-/// - `diff_field!(json audits, row, field_name, old_field, new_field)` -- `Option<Value>` old vs `Value` new
+/// - `diff_field!(changes, row, field_name, old_field, new_field)` -- `Option<T>` old vs `T` new
+/// - `diff_field!(opt changes, row, field_name, old_field, new_field)` -- `Option<T>` old vs `Option<T>` new
+/// - `diff_field!(json changes, row, field_name, old_field, new_field)` -- `Option<Value>` old vs `Value` new
 ///
 /// All variants skip when `old_id` is None (fresh insert).
 macro_rules! diff_field {
     // Standard: Option<T> old vs T new (non-nullable columns)
-    ($audits:ident, $row:ident, $field:expr, $old:ident, $new:ident) => {
+    ($changes:ident, $row:ident, $field:expr, $old:ident, $new:ident) => {
         if $row.old_id.is_some() {
             let old_val = $row.$old.as_ref().map(|v| serde_json::json!(v));
             let new_val = serde_json::json!($row.$new);
             if old_val.as_ref() != Some(&new_val) {
-                $audits.push(AuditEntry {
-                    course_id: $row.id,
-                    field_changed: $field,
-                    old_value: old_val,
-                    new_value: new_val,
-                });
+                $changes.push((
+                    $row.id,
+                    CourseChange::Field {
+                        field: $field.to_owned(),
+                        old: old_val,
+                        new: new_val,
+                    },
+                ));
             }
         }
     };
     // Nullable: Option<T> old vs Option<T> new
-    (opt $audits:ident, $row:ident, $field:expr, $old:ident, $new:ident) => {
+    (opt $changes:ident, $row:ident, $field:expr, $old:ident, $new:ident) => {
         if $row.old_id.is_some() {
             let old_val = $row.$old.as_ref().map(|v| serde_json::json!(v));
             let new_val = $row.$new.as_ref().map(|v| serde_json::json!(v));
             if old_val != new_val {
-                $audits.push(AuditEntry {
-                    course_id: $row.id,
-                    field_changed: $field,
-                    old_value: old_val,
-                    new_value: new_val.unwrap_or(serde_json::Value::Null),
-                });
+                $changes.push((
+                    $row.id,
+                    CourseChange::Field {
+                        field: $field.to_owned(),
+                        old: old_val,
+                        new: new_val.unwrap_or(serde_json::Value::Null),
+                    },
+                ));
             }
         }
     };
     // JSONB: Option<Value> old vs Value new
-    (json $audits:ident, $row:ident, $field:expr, $old:ident, $new:ident) => {
+    (json $changes:ident, $row:ident, $field:expr, $old:ident, $new:ident) => {
         if $row.old_id.is_some() {
             let old_val = $row.$old.clone();
             let new_val = $row.$new.clone();
             if old_val.as_ref() != Some(&new_val) {
-                $audits.push(AuditEntry {
-                    course_id: $row.id,
-                    field_changed: $field,
-                    old_value: old_val,
-                    new_value: new_val,
-                });
+                $changes.push((
+                    $row.id,
+                    CourseChange::Field {
+                        field: $field.to_owned(),
+                        old: old_val,
+                        new: new_val,
+                    },
+                ));
             }
         }
     };
 }
 
-/// Compute audit entries (field-level diffs) and metric entries from upsert diff rows.
-fn compute_diffs(rows: &[UpsertDiffRow]) -> (Vec<AuditEntry>, Vec<MetricEntry>) {
-    let mut audits = Vec::new();
+/// Compute field-level changes, keyed by course ID, and metric entries from
+/// upsert diff rows.
+fn compute_diffs(rows: &[UpsertDiffRow]) -> (Vec<(i32, CourseChange)>, Vec<MetricEntry>) {
+    let mut changes = Vec::new();
     let mut metrics = Vec::new();
 
-    for row in rows {
+    for row in rows.iter().filter(|r| r.changed) {
         if row.old_id.is_none() {
             let snapshot = serde_json::json!({
                 "enrollment": row.new_enrollment,
@@ -308,64 +359,49 @@ fn compute_diffs(rows: &[UpsertDiffRow]) -> (Vec<AuditEntry>, Vec<MetricEntry>)
                 "campus": row.new_campus,
                 "creditHours": row.new_credit_hours,
             });
-            audits.push(AuditEntry {
-                course_id: row.id,
-                field_changed: "initial",
-                old_value: None,
-                new_value: snapshot,
-            });
+            changes.push((row.id, CourseChange::Created { snapshot }));
+        } else {
+            for (field, old, new) in row.seats() {
+                if old != Some(new) {
+                    changes.push((row.id, CourseChange::Seats { field, old, new }));
+                }
+            }
         }
 
         // Non-nullable fields
-        diff_field!(audits, row, "enrollment", old_enrollment, new_enrollment);
-        diff_field!(
-            audits,
-            row,
-            "max_enrollment",
-            old_max_enrollment,
-            new_max_enrollment
-        );
-        diff_field!(audits, row, "wait_count", old_wait_count, new_wait_count);
+        diff_field!(changes, row, "subject", old_subject, new_subject);
         diff_field!(
-            audits,
-            row,
-            "wait_capacity",
-            old_wait_capacity,
-            new_wait_capacity
-        );
-        diff_field!(audits, row, "subject", old_subject, new_subject);
-        diff_field!(
-            audits,
+            changes,
             row,
             "course_number",
             old_course_number,
             new_course_number
         );
-        diff_field!(audits, row, "title", old_title, new_title);
+        diff_field!(changes, row, "title", old_title, new_title);
 
         // Nullable text fields
-        diff_field!(opt audits, row, "sequence_number", old_sequence_number, new_sequence_number);
-        diff_field!(opt audits, row, "part_of_term", old_part_of_term, new_part_of_term);
-        diff_field!(opt audits, row, "instructional_method", old_instructional_method, new_instructional_method);
-        diff_field!(opt audits, row, "campus", old_campus, new_campus);
+        diff_field!(opt changes, row, "sequence_number", old_sequence_number, new_sequence_number);
+        diff_field!(opt changes, row, "part_of_term", old_part_of_term, new_part_of_term);
+        diff_field!(opt changes, row, "instructional_method", old_instructional_method, new_instructional_method);
+        diff_field!(opt changes, row, "campus", old_campus, new_campus);
 
         // Nullable int fields
-        diff_field!(opt audits, row, "credit_hours", old_credit_hours, new_credit_hours);
-        diff_field!(opt audits, row, "credit_hour_low", old_credit_hour_low, new_credit_hour_low);
-        diff_field!(opt audits, row, "credit_hour_high", old_credit_hour_high, new_credit_hour_high);
+        diff_field!(opt changes, row, "credit_hours", old_credit_hours, new_credit_hours);
+        diff_field!(opt changes, row, "credit_hour_low", old_credit_hour_low, new_credit_hour_low);
+        diff_field!(opt changes, row, "credit_hour_high", old_credit_hour_high, new_credit_hour_high);
 
         // Cross-list fields
-        diff_field!(opt audits, row, "cross_list", old_cross_list, new_cross_list);
-        diff_field!(opt audits, row, "cross_list_capacity", old_cross_list_capacity, new_cross_list_capacity);
-        diff_field!(opt audits, row, "cross_list_count", old_cross_list_count, new_cross_list_count);
+        diff_field!(opt changes, row, "cross_list", old_cross_list, new_cross_list);
+        diff_field!(opt changes, row, "cross_list_capacity", old_cross_list_capacity, new_cross_list_capacity);
+        diff_field!(opt changes, row, "cross_list_count", old_cross_list_count, new_cross_list_count);
 
         // Link fields
-        diff_field!(opt audits, row, "link_identifier", old_link_identifier, new_link_identifier);
-        diff_field!(opt audits, row, "is_section_linked", old_is_section_linked, new_is_section_linked);
+        diff_field!(opt changes, row, "link_identifier", old_link_identifier, new_link_identifier);
+        diff_field!(opt changes, row, "is_section_linked", old_is_section_linked, new_is_section_linked);
 
         // JSONB fields
-        diff_field!(json audits, row, "meeting_times", old_meeting_times, new_meeting_times);
-        diff_field!(json audits, row, "attributes", old_attributes, new_attributes);
+        diff_field!(json changes, row, "meeting_times", old_meeting_times, new_meeting_times);
+        diff_field!(json changes, row, "attributes", old_attributes, new_attributes);
 
         // Emit a metric entry on fresh insert (baseline) or when enrollment data changed
         let is_new = row.old_id.is_none();
@@ -384,38 +420,37 @@ fn compute_diffs(rows: &[UpsertDiffRow]) -> (Vec<AuditEntry>, Vec<MetricEntry>)
         }
     }
 
-    (audits, metrics)
+    (changes, metrics)
 }
 
-async fn insert_audits(audits: &[AuditEntry], conn: &mut PgConnection) -> Result<Vec<i32>> {
-    if audits.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    let course_ids: Vec<i32> = audits.iter().map(|a| a.course_id).collect();
-    let fields: Vec<&str> = audits.iter().map(|a| a.field_changed).collect();
-    let old_values: Vec<Option<serde_json::Value>> =
-        audits.iter().map(|a| a.old_value.clone()).collect();
-    let new_values: Vec<serde_json::Value> = audits.iter().map(|a| a.new_value.clone()).collect();
+/// Summarize added courses, changed courses, and touched fields for the job result log.
+///
+/// Removals aren't visible here (the upsert only sees fetched courses); the
+/// subject job fills them in.
+fn summarize_diff(rows: &[UpsertDiffRow], changes: &[(i32, CourseChange)]) -> ScrapeDiffSummary {
+    let added: HashSet<i32> = rows
+        .iter()
+        .filter(|r| r.old_id.is_none())
+        .map(|r| r.id)
+        .collect();
 
-    let rows: Vec<(i32,)> = sqlx::query_as(
-        r#"
-        INSERT INTO course_audits (course_id, timestamp, field_changed, old_value, new_value)
-        SELECT v.course_id, NOW(), v.field_changed, v.old_value, v.new_value
-        FROM UNNEST($1::int4[], $2::text[], $3::jsonb[], $4::jsonb[])
-            AS v(course_id, field_changed, old_value, new_value)
-        RETURNING id
-        "#,
-    )
-    .bind(&course_ids)
-    .bind(&fields)
-    .bind(&old_values)
-    .bind(&new_values)
-    .fetch_all(&mut *conn)
-    .await
-    .map_err(|e| anyhow::anyhow!("Failed to batch insert course_audits: {}", e))?;
+    let mut changed: HashSet<i32> = HashSet::new();
+    let mut fields: BTreeMap<String, Count> = BTreeMap::new();
+    for (course_id, change) in changes {
+        if added.contains(course_id) {
+            continue;
+        }
+        changed.insert(*course_id);
+        let count = fields.entry(change.audit_field().to_owned()).or_default();
+        *count = Count::new(count.get() + 1);
+    }
 
-    Ok(rows.into_iter().map(|(id,)| id).collect())
+    ScrapeDiffSummary {
+        added: Count::try_from(added.len()).unwrap_or_default(),
+        removed: Count::default(),
+        changed: Count::try_from(changed.len()).unwrap_or_default(),
+        fields,
+    }
 }
 
 async fn insert_metrics(metrics: &[MetricEntry], conn: &mut PgConnection) -> Result<()> {
@@ -449,17 +484,21 @@ async fn insert_metrics(metrics: &[MetricEntry], conn: &mut PgConnection) -> Res
 
 /// Batch upsert courses in a single database query.
 ///
-/// Performs a bulk INSERT...ON CONFLICT DO UPDATE for all courses, including
-/// new fields (meeting times, attributes, instructor data). Captures pre-update
-/// state for audit/metric tracking, all within a single transaction.
+/// Compares every incoming course against its stored row in SQL (`IS DISTINCT
+/// FROM` across all scraped columns): only new and changed rows are written in
+/// full, unchanged ones just get `last_scraped_at` bumped. Pre-update state
+/// comes back for the changed set alone and drives audit/metric tracking, all
+/// within a single transaction.
 ///
 /// # Performance
-/// - Reduces N database round-trips to 5 (old-data CTE + upsert, audits, metrics, instructors, junction)
+/// - Reduces N database round-trips to 5 (upsert CTE, audits, metrics, instructors, junction)
+/// - Unchanged rows aren't rewritten, so their JSONB columns keep their TOAST
+///   storage and meetings aren't re-derived for them
 /// - Typical usage: 50-200 courses per batch
 pub async fn batch_upsert_courses(
     courses: &[Course],
     db_pool: &PgPool,
-) -> Result<(UpsertCounts, Vec<AuditLogEntry>)> {
+) -> Result<(UpsertCounts, Vec<CourseEvent>)> {
     if courses.is_empty() {
         info!("No courses to upsert, skipping batch operation");
         return Ok((UpsertCounts::default(), Vec::new()));
@@ -485,22 +524,28 @@ pub async fn batch_upsert_courses(
         .map(|r| ((r.crn.as_str(), r.term_code.as_str()), r.id))
         .collect();
 
-    // Step 3: Compute audit/metric diffs
-    let (mut audits, metrics) = compute_diffs(&diff_rows);
+    // Step 3: Compute changes and metrics
+    let (mut changes, metrics) = compute_diffs(&diff_rows);
 
     // Step 4: Upsert instructors (returns lookup maps for email and display_name)
     let instructor_lookup = upsert_instructors(courses, &mut tx).await?;
 
-    // Step 5: Link courses to instructors via junction table, collecting instructor audits
-    let instructor_audits =
+    // Step 5: Link courses to instructors via junction table, collecting instructor changes
+    let instructor_changes =
         upsert_course_instructors(courses, &crn_term_to_id, &instructor_lookup, &mut tx).await?;
-    audits.extend(instructor_audits);
+    changes.extend(instructor_changes);
 
-    // Step 6: Sync denormalized course_meetings table for schedule cache
-    sync_course_meetings(courses, &crn_term_to_id, &mut tx).await?;
+    // Step 6: Sync denormalized course_meetings table for schedule cache.
+    // Meetings derive from `meeting_times`, so unchanged rows are already in sync.
+    let changed_crn_terms: HashMap<(&str, &str), i32> = diff_rows
+        .iter()
+        .filter(|r| r.changed)
+        .map(|r| ((r.crn.as_str(), r.term_code.as_str()), r.id))
+        .collect();
+    sync_course_meetings(courses, &changed_crn_terms, &mut tx).await?;
 
     // Count courses that had at least one field change (existing rows only)
-    let changed_ids: HashSet<i32> = audits.iter().map(|a| a.course_id).collect();
+    let changed_ids: HashSet<i32> = changes.iter().map(|(id, _)| *id).collect();
     let courses_changed = Count::try_from(changed_ids.len())?;
 
     let counts = UpsertCounts {
@@ -509,26 +554,25 @@ pub async fn batch_upsert_courses(
         courses_unchanged: Count::new(
             u32::try_from(course_count)?.saturating_sub(courses_changed.get()),
         ),
-        audits_generated: Count::try_from(audits.len())?,
+        audits_generated: Count::try_from(changes.len())?,
         metrics_generated: Count::try_from(metrics.len())?,
+        diff: Some(summarize_diff(&diff_rows, &changes)),
     };
 
-    // Step 7: Insert audits and metrics
-    let audit_ids = insert_audits(&audits, &mut tx).await?;
+    // Step 7: Record changes as audits, insert metrics, and queue the events
+    let refs: HashMap<i32, CourseRef> = diff_rows.iter().map(|r| (r.id, r.course_ref())).collect();
+    let changes = changes
+        .into_iter()
+        .map(|(id, change)| (refs[&id].clone(), change))
+        .collect();
+    let events = record_course_changes(&mut tx, changes).await?;
     insert_metrics(&metrics, &mut tx).await?;
+    outbox::enqueue_course_changes(&mut *tx, &events).await?;
 
     tx.commit()
         .await
         .context("failed to commit batch upsert transaction")?;
 
-    let audit_entries = if !audit_ids.is_empty() {
-        fetch_audit_entries_by_ids(db_pool, &audit_ids)
-            .await
-            .unwrap_or_default()
-    } else {
-        Vec::new()
-    };
-
     let duration = start.elapsed();
 
     if counts.courses_changed.get() > 0 {
@@ -573,29 +617,11 @@ pub async fn batch_upsert_courses(
         );
     }
 
-    Ok((counts, audit_entries))
+    Ok((counts, events))
 }
 
-async fn fetch_audit_entries_by_ids(
-    db_pool: &PgPool,
-    audit_ids: &[i32],
-) -> Result<Vec<AuditLogEntry>> {
-    let rows: Vec<AuditRow> = sqlx::query_as(
-        "SELECT a.id, a.course_id, a.timestamp, a.field_changed, a.old_value, a.new_value, \
-                c.subject, c.course_number, c.crn, c.title, c.term_code \
-         FROM course_audits a \
-         LEFT JOIN courses c ON c.id = a.course_id \
-         WHERE a.id = ANY($1)",
-    )
-    .bind(audit_ids)
-    .fetch_all(db_pool)
-    .await
-    .map_err(|e| anyhow::anyhow!("Failed to fetch audit entries: {}", e))?;
-
-    Ok(rows.into_iter().map(AuditLogEntry::from).collect())
-}
-
-/// Upsert all courses and return diff rows with old and new values for auditing.
+/// Upsert all courses and return a diff row per course: old and new values
+/// for the changed set, new values only for the rest.
 async fn upsert_courses(courses: &[Course], conn: &mut PgConnection) -> Result<Vec<UpsertDiffRow>> {
     let crns: Vec<&str> = courses
         .iter()
@@ -603,10 +629,14 @@ async fn upsert_courses(courses: &[Course], conn: &mut PgConnection) -> Result<V
         .collect();
     let subjects: Vec<&str> = courses.iter().map(|c| c.subject.as_str()).collect();
     let course_numbers: Vec<&str> = courses.iter().map(|c| c.course_number.as_str()).collect();
-    let titles: Vec<String> = courses
+    let raw_titles: Vec<String> = courses
         .iter()
         .map(|c| decode_html_entities(&c.course_title))
         .collect();
+    let titles: Vec<String> = raw_titles
+        .iter()
+        .map(|t| normalize_course_title(t))
+        .collect();
     let term_codes: Vec<&str> = courses.iter().map(|c| c.term.as_str()).collect();
     let enrollments: Vec<i32> = courses.iter().map(|c| c.enrollment).collect();
     let max_enrollments: Vec<i32> = courses.iter().map(|c| c.maximum_enrollment).collect();
@@ -651,19 +681,98 @@ async fn upsert_courses(courses: &[Course], conn: &mut PgConnection) -> Result<V
 
     let rows = sqlx::query_as::<_, UpsertDiffRow>(
         r#"
-        WITH old_data AS (
-            SELECT id, enrollment, max_enrollment, wait_count, wait_capacity,
-                   subject, course_number, title,
-                   sequence_number, part_of_term, instructional_method, campus,
-                   credit_hours, credit_hour_low, credit_hour_high,
-                   cross_list, cross_list_capacity, cross_list_count,
-                   link_identifier, is_section_linked,
-                   meeting_times, attributes,
-                   crn, term_code
-            FROM courses
-            WHERE (crn, term_code) IN (SELECT * FROM UNNEST($1::text[], $5::text[]))
+        WITH incoming AS (
+            SELECT * FROM UNNEST(
+                $1::text[], $2::text[], $3::text[], $4::text[], $5::text[],
+                $6::int4[], $7::int4[], $8::int4[], $9::int4[],
+                $10::text[], $11::text[], $12::text[], $13::text[],
+                $14::float8[], $15::float8[], $16::float8[],
+                $17::text[], $18::int4[], $19::int4[],
+                $20::text[], $21::bool[],
+                $22::jsonb[], $23::jsonb[], $24::text[]
+            ) AS v(
+                crn, subject, course_number, title, term_code,
+                enrollment, max_enrollment, wait_count, wait_capacity,
+                sequence_number, part_of_term, instructional_method, campus,
+                credit_hours, credit_hour_low, credit_hour_high,
+                cross_list, cross_list_capacity, cross_list_count,
+                link_identifier, is_section_linked,
+                meeting_times, attributes, raw_title
+            )
         ),
-        upserted AS (
+        old_data AS (
+            SELECT c.*
+            FROM courses c
+            JOIN incoming v ON v.crn = c.crn AND v.term_code = c.term_code
+        ),
+        -- Existing rows with at least one differing column get the full update
+        updated AS (
+            UPDATE courses c SET
+                subject = v.subject,
+                course_number = v.course_number,
+                title = v.title,
+                enrollment = v.enrollment,
+                max_enrollment = v.max_enrollment,
+                wait_count = v.wait_count,
+                wait_capacity = v.wait_capacity,
+                last_scraped_at = NOW(),
+                sequence_number = v.sequence_number,
+                part_of_term = v.part_of_term,
+                instructional_method = v.instructional_method,
+                campus = v.campus,
+                credit_hours = v.credit_hours,
+                credit_hour_low = v.credit_hour_low,
+                credit_hour_high = v.credit_hour_high,
+                cross_list = v.cross_list,
+                cross_list_capacity = v.cross_list_capacity,
+                cross_list_count = v.cross_list_count,
+                link_identifier = v.link_identifier,
+                is_section_linked = v.is_section_linked,
+                meeting_times = v.meeting_times,
+                attributes = v.attributes,
+                raw_title = v.raw_title
+            FROM incoming v
+            WHERE v.crn = c.crn AND v.term_code = c.term_code
+              AND (c.subject, c.course_number, c.title,
+                   c.enrollment, c.max_enrollment, c.wait_count, c.wait_capacity,
+                   c.sequence_number, c.part_of_term, c.instructional_method, c.campus,
+                   c.credit_hours, c.credit_hour_low, c.credit_hour_high,
+                   c.cross_list, c.cross_list_capacity, c.cross_list_count,
+                   c.link_identifier, c.is_section_linked,
+                   c.meeting_times, c.attributes, c.raw_title)
+                  IS DISTINCT FROM
+                  (v.subject, v.course_number, v.title,
+                   v.enrollment, v.max_enrollment, v.wait_count, v.wait_capacity,
+                   v.sequence_number, v.part_of_term, v.instructional_method, v.campus,
+                   v.credit_hours, v.credit_hour_low, v.credit_hour_high,
+                   v.cross_list, v.cross_list_capacity, v.cross_list_count,
+                   v.link_identifier, v.is_section_linked,
+                   v.meeting_times, v.attributes, v.raw_title)
+            RETURNING c.*
+        ),
+        -- The rest only record that they were seen
+        touched AS (
+            UPDATE courses c SET last_scraped_at = NOW()
+            FROM incoming v
+            WHERE v.crn = c.crn AND v.term_code = c.term_code
+              AND (c.subject, c.course_number, c.title,
+                   c.enrollment, c.max_enrollment, c.wait_count, c.wait_capacity,
+                   c.sequence_number, c.part_of_term, c.instructional_method, c.campus,
+                   c.credit_hours, c.credit_hour_low, c.credit_hour_high,
+                   c.cross_list, c.cross_list_capacity, c.cross_list_count,
+                   c.link_identifier, c.is_section_linked,
+                   c.meeting_times, c.attributes, c.raw_title)
+                  IS NOT DISTINCT FROM
+                  (v.subject, v.course_number, v.title,
+                   v.enrollment, v.max_enrollment, v.wait_count, v.wait_capacity,
+                   v.sequence_number, v.part_of_term, v.instructional_method, v.campus,
+                   v.credit_hours, v.credit_hour_low, v.credit_hour_high,
+                   v.cross_list, v.cross_list_capacity, v.cross_list_count,
+                   v.link_identifier, v.is_section_linked,
+                   v.meeting_times, v.attributes, v.raw_title)
+            RETURNING c.*
+        ),
+        inserted AS (
             INSERT INTO courses (
                 crn, subject, course_number, title, term_code,
                 enrollment, max_enrollment, wait_count, wait_capacity, last_scraped_at,
@@ -671,7 +780,7 @@ async fn upsert_courses(courses: &[Course], conn: &mut PgConnection) -> Result<V
                 credit_hours, credit_hour_low, credit_hour_high,
                 cross_list, cross_list_capacity, cross_list_count,
                 link_identifier, is_section_linked,
-                meeting_times, attributes
+                meeting_times, attributes, raw_title
             )
             SELECT
                 v.crn, v.subject, v.course_number, v.title, v.term_code,
@@ -680,26 +789,15 @@ async fn upsert_courses(courses: &[Course], conn: &mut PgConnection) -> Result<V
                 v.credit_hours, v.credit_hour_low, v.credit_hour_high,
                 v.cross_list, v.cross_list_capacity, v.cross_list_count,
                 v.link_identifier, v.is_section_linked,
-                v.meeting_times, v.attributes
-            FROM UNNEST(
-                $1::text[], $2::text[], $3::text[], $4::text[], $5::text[],
-                $6::int4[], $7::int4[], $8::int4[], $9::int4[],
-                $10::text[], $11::text[], $12::text[], $13::text[],
-                $14::float8[], $15::float8[], $16::float8[],
-                $17::text[], $18::int4[], $19::int4[],
-                $20::text[], $21::bool[],
-                $22::jsonb[], $23::jsonb[]
-            ) AS v(
-                crn, subject, course_number, title, term_code,
-                enrollment, max_enrollment, wait_count, wait_capacity,
-                sequence_number, part_of_term, instructional_method, campus,
-                credit_hours, credit_hour_low, credit_hour_high,
-                cross_list, cross_list_capacity, cross_list_count,
-                link_identifier, is_section_linked,
-                meeting_times, attributes
+                v.meeting_times, v.attributes, v.raw_title
+            FROM incoming v
+            WHERE NOT EXISTS (
+                SELECT 1 FROM old_data o WHERE o.crn = v.crn AND o.term_code = v.term_code
             )
-            ON CONFLICT (crn, term_code)
-            DO UPDATE SET
+            -- A concurrent writer can insert the same section after this
+            -- statement's snapshot. Overwrite its row rather than drop ours, so
+            -- the section keeps its ID, audits and instructor links here too.
+            ON CONFLICT (crn, term_code) DO UPDATE SET
                 subject = EXCLUDED.subject,
                 course_number = EXCLUDED.course_number,
                 title = EXCLUDED.title,
@@ -721,11 +819,20 @@ async fn upsert_courses(courses: &[Course], conn: &mut PgConnection) -> Result<V
                 link_identifier = EXCLUDED.link_identifier,
                 is_section_linked = EXCLUDED.is_section_linked,
                 meeting_times = EXCLUDED.meeting_times,
-                attributes = EXCLUDED.attributes
+                attributes = EXCLUDED.attributes,
+                raw_title = EXCLUDED.raw_title
             RETURNING *
+        ),
+        current AS (
+            SELECT TRUE AS changed, TRUE AS existed, * FROM updated
+            UNION ALL
+            SELECT FALSE, TRUE, * FROM touched
+            UNION ALL
+            SELECT TRUE, FALSE, * FROM inserted
         )
         SELECT u.id,
-               o.id AS old_id,
+               CASE WHEN u.existed THEN u.id END AS old_id,
+               u.changed,
                u.crn, u.term_code,
                o.enrollment AS old_enrollment, u.enrollment AS new_enrollment,
                o.max_enrollment AS old_max_enrollment, u.max_enrollment AS new_max_enrollment,
@@ -748,8 +855,9 @@ async fn upsert_courses(courses: &[Course], conn: &mut PgConnection) -> Result<V
                o.is_section_linked AS old_is_section_linked, u.is_section_linked AS new_is_section_linked,
                o.meeting_times AS old_meeting_times, u.meeting_times AS new_meeting_times,
                o.attributes AS old_attributes, u.attributes AS new_attributes
-        FROM upserted u
-        LEFT JOIN old_data o ON u.crn = o.crn AND u.term_code = o.term_code
+        FROM current u
+        -- Old values are only needed where something changed
+        LEFT JOIN old_data o ON o.id = u.id AND u.changed AND u.existed
         "#,
     )
     .bind(&crns)
@@ -775,6 +883,7 @@ async fn upsert_courses(courses: &[Course], conn: &mut PgConnection) -> Result<V
     .bind(&is_section_linkeds)
     .bind(&meeting_times_json)
     .bind(&attributes_json)
+    .bind(&raw_titles)
     .fetch_all(&mut *conn)
     .await
     .map_err(|e| anyhow::anyhow!("Failed to batch upsert courses: {}", e))?;
@@ -872,7 +981,10 @@ async fn upsert_instructors(
                 display_name = EXCLUDED.display_name,
                 first_name = EXCLUDED.first_name,
                 last_name = EXCLUDED.last_name,
-                slug = COALESCE(instructors.slug, EXCLUDED.slug)
+                slug = COALESCE(instructors.slug, EXCLUDED.slug),
+                slug_stale = instructors.slug_stale
+                    OR (instructors.slug IS NOT NULL
+                        AND instructors.display_name IS DISTINCT FROM EXCLUDED.display_name)
             RETURNING id, email
             "#,
         )
@@ -929,13 +1041,13 @@ async fn upsert_instructors(
 }
 
 /// Link courses to their instructors via the junction table.
-/// Returns audit entries for any instructor changes detected.
+/// Returns a change for each course whose instructors differ.
 async fn upsert_course_instructors(
     courses: &[Course],
     crn_term_to_id: &HashMap<(&str, &str), i32>,
     instructor_lookup: &InstructorLookup,
     conn: &mut PgConnection,
-) -> Result<Vec<AuditEntry>> {
+) -> Result<Vec<(i32, CourseChange)>> {
     let mut cids = Vec::new();
     let mut instructor_ids: Vec<i32> = Vec::new();
     let mut banner_ids: Vec<&str> = Vec::new();
@@ -1037,8 +1149,8 @@ async fn upsert_course_instructors(
     .await
     .map_err(|e| anyhow::anyhow!("Failed to batch upsert course_instructors: {}", e))?;
 
-    // Compare old vs new instructor names and emit audit entries
-    let mut audits = Vec::new();
+    // Compare old vs new instructor names
+    let mut changes = Vec::new();
     for &course_id in &unique_cids {
         let mut old = old_names.get(&course_id).cloned().unwrap_or_default();
         let mut new = new_names.get(&course_id).cloned().unwrap_or_default();
@@ -1046,16 +1158,11 @@ async fn upsert_course_instructors(
         new.sort();
 
         if old != new {
-            audits.push(AuditEntry {
-                course_id,
-                field_changed: "instructors",
-                old_value: Some(serde_json::to_value(&old).unwrap_or_default()),
-                new_value: serde_json::to_value(&new).unwrap_or_default(),
-            });
+            changes.push((course_id, CourseChange::Instructors { old, new }));
         }
     }
 
-    Ok(audits)
+    Ok(changes)
 }
 
 /// Sync the denormalized `course_meetings` table for the upserted courses.
@@ -1074,6 +1181,8 @@ async fn sync_course_meetings(
     let mut end_minutes_vec: Vec<i16> = Vec::new();
     let mut start_dates: Vec<NaiveDate> = Vec::new();
     let mut end_dates: Vec<NaiveDate> = Vec::new();
+    let mut buildings: Vec<Option<&str>> = Vec::new();
+    let mut rooms: Vec<Option<&str>> = Vec::new();
 
     for course in courses {
         let key = (
@@ -1149,6 +1258,8 @@ async fn sync_course_meetings(
             end_minutes_vec.push(end);
             start_dates.push(start_date);
             end_dates.push(end_date);
+            buildings.push(non_blank(mt.building.as_deref()));
+            rooms.push(non_blank(mt.room.as_deref()));
         }
     }
 
@@ -1168,8 +1279,12 @@ async fn sync_course_meetings(
 
     sqlx::query(
         r#"
-        INSERT INTO course_meetings (course_id, day_bits, begin_minutes, end_minutes, start_date, end_date)
-        SELECT * FROM UNNEST($1::int4[], $2::int2[], $3::int2[], $4::int2[], $5::date[], $6::date[])
+        INSERT INTO course_meetings
+            (course_id, day_bits, begin_minutes, end_minutes, start_date, end_date, building, room)
+        SELECT * FROM UNNEST(
+            $1::int4[], $2::int2[], $3::int2[], $4::int2[], $5::date[], $6::date[],
+            $7::text[], $8::text[]
+        )
         "#,
     )
     .bind(&course_ids)
@@ -1178,6 +1293,8 @@ async fn sync_course_meetings(
     .bind(&end_minutes_vec)
     .bind(&start_dates)
     .bind(&end_dates)
+    .bind(&buildings)
+    .bind(&rooms)
     .execute(&mut *conn)
     .await
     .map_err(|e| anyhow::anyhow!("Failed to batch insert course_meetings: {}", e))?;
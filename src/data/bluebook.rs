@@ -115,7 +115,15 @@ pub async fn batch_upsert_bluebook_evaluations(
             course_rating = EXCLUDED.course_rating,
             course_response_count = EXCLUDED.course_response_count,
             department = EXCLUDED.department,
-            scraped_at = EXCLUDED.scraped_at
+            scraped_at = EXCLUDED.scraped_at,
+            updated_at = CASE
+                WHEN (bluebook_evaluations.instructor_rating,
+                      bluebook_evaluations.instructor_response_count)
+                     IS DISTINCT FROM
+                     (EXCLUDED.instructor_rating, EXCLUDED.instructor_response_count)
+                THEN NOW()
+                ELSE bluebook_evaluations.updated_at
+            END
         "#,
     )
     .bind(&subjects)
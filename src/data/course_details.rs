@@ -0,0 +1,444 @@
+//! Database operations for per-section detail data (restrictions, corequisites,
+//! linked sections, catalog description) populated by the detail enrichment job.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::banner::{CourseDetails, EnrollmentInfo};
+use crate::data::audit::record_course_changes;
+use crate::data::events::{CourseChange, CourseEvent, CourseRef, SeatField};
+use crate::data::outbox;
+
+/// A registration restriction shown on course detail.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CourseRestriction {
+    pub heading: String,
+    pub value: String,
+}
+
+/// A corequisite course shown on course detail.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CourseCorequisite {
+    pub subject: String,
+    pub course_number: String,
+    pub title: Option<String>,
+}
+
+/// Seat fields that differ between the stored `[enrollment, max_enrollment,
+/// wait_count, wait_capacity]` and freshly fetched counts.
+fn diff_seats(old: [i32; 4], new: &EnrollmentInfo) -> Vec<CourseChange> {
+    let new = [
+        new.enrollment,
+        new.max_enrollment,
+        new.wait_count,
+        new.wait_capacity,
+    ];
+    SeatField::ALL
+        .into_iter()
+        .zip(old.into_iter().zip(new))
+        .filter(|(_, (old, new))| old != new)
+        .map(|(field, (old, new))| CourseChange::Seats {
+            field,
+            old: Some(old),
+            new,
+        })
+        .collect()
+}
+
+/// The stored course a detail fetch applies to, locked for the save.
+#[derive(sqlx::FromRow)]
+struct CurrentCourse {
+    details_scraped: bool,
+    enrollment: i32,
+    max_enrollment: i32,
+    wait_count: i32,
+    wait_capacity: i32,
+    term_code: String,
+    crn: String,
+    subject: String,
+    course_number: String,
+    title: String,
+}
+
+/// Outcome of [`save_course_details`].
+#[derive(Debug, Default)]
+pub struct SavedCourseDetails {
+    /// Seat counts differed from the stored values.
+    pub seats_changed: bool,
+    /// Changes recorded for edited details and seats.
+    pub events: Vec<CourseEvent>,
+}
+
+/// Persist scraped details for a course, replacing any previous values.
+///
+/// Seat counts from the enrollment endpoint are written through to `courses`
+/// when they differ from the stored values, with a `course_metrics` sample and
+/// one audit per changed field, matching what the search upsert records.
+/// Edited restrictions are recorded as a `restrictions` audit, unless this is
+/// the course's first detail fetch.
+pub async fn save_course_details(
+    pool: &PgPool,
+    course_id: i32,
+    details: &CourseDetails,
+) -> Result<SavedCourseDetails> {
+    let mut tx = pool
+        .begin()
+        .await
+        .context("failed to begin course details transaction")?;
+
+    let current: Option<CurrentCourse> = sqlx::query_as(
+        r#"
+        SELECT details_scraped_at IS NOT NULL AS details_scraped,
+               enrollment, max_enrollment, wait_count, wait_capacity,
+               term_code, crn, subject, course_number, title
+        FROM courses WHERE id = $1 FOR UPDATE
+        "#,
+    )
+    .bind(course_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .context("failed to load current course state")?;
+    let previously_scraped = current.as_ref().is_some_and(|c| c.details_scraped);
+
+    let mut changes = Vec::new();
+    if previously_scraped {
+        let old: Vec<CourseRestriction> = sqlx::query_as(
+            "SELECT heading, value FROM course_restrictions WHERE course_id = $1 ORDER BY position",
+        )
+        .bind(course_id)
+        .fetch_all(&mut *tx)
+        .await
+        .context("failed to fetch previous course restrictions")?;
+
+        let unchanged = old.len() == details.restrictions.len()
+            && old
+                .iter()
+                .zip(&details.restrictions)
+                .all(|(o, n)| o.heading == n.heading && o.value == n.value);
+        if !unchanged {
+            let new: Vec<CourseRestriction> = details
+                .restrictions
+                .iter()
+                .map(|r| CourseRestriction {
+                    heading: r.heading.clone(),
+                    value: r.value.clone(),
+                })
+                .collect();
+            changes.push(CourseChange::Field {
+                field: "restrictions".to_owned(),
+                old: Some(serde_json::to_value(&old)?),
+                new: serde_json::to_value(&new)?,
+            });
+        }
+    }
+
+    sqlx::query("DELETE FROM course_restrictions WHERE course_id = $1")
+        .bind(course_id)
+        .execute(&mut *tx)
+        .await
+        .context("failed to clear course restrictions")?;
+    if !details.restrictions.is_empty() {
+        let headings: Vec<&str> = details
+            .restrictions
+            .iter()
+            .map(|r| r.heading.as_str())
+            .collect();
+        let values: Vec<&str> = details
+            .restrictions
+            .iter()
+            .map(|r| r.value.as_str())
+            .collect();
+        sqlx::query(
+            r#"
+            INSERT INTO course_restrictions (course_id, heading, value, position)
+            SELECT $1, v.heading, v.value, (v.ord - 1)::smallint
+            FROM UNNEST($2::text[], $3::text[]) WITH ORDINALITY AS v(heading, value, ord)
+            "#,
+        )
+        .bind(course_id)
+        .bind(&headings)
+        .bind(&values)
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert course restrictions")?;
+    }
+
+    sqlx::query("DELETE FROM course_corequisites WHERE course_id = $1")
+        .bind(course_id)
+        .execute(&mut *tx)
+        .await
+        .context("failed to clear course corequisites")?;
+    if !details.corequisites.is_empty() {
+        let subjects: Vec<&str> = details
+            .corequisites
+            .iter()
+            .map(|c| c.subject.as_str())
+            .collect();
+        let numbers: Vec<&str> = details
+            .corequisites
+            .iter()
+            .map(|c| c.course_number.as_str())
+            .collect();
+        let titles: Vec<Option<&str>> = details
+            .corequisites
+            .iter()
+            .map(|c| c.title.as_deref())
+            .collect();
+        sqlx::query(
+            r#"
+            INSERT INTO course_corequisites (course_id, subject, course_number, title, position)
+            SELECT $1, v.subject, v.course_number, v.title, (v.ord - 1)::smallint
+            FROM UNNEST($2::text[], $3::text[], $4::text[])
+                WITH ORDINALITY AS v(subject, course_number, title, ord)
+            "#,
+        )
+        .bind(course_id)
+        .bind(&subjects)
+        .bind(&numbers)
+        .bind(&titles)
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert course corequisites")?;
+    }
+
+    sqlx::query("DELETE FROM course_linked_sections WHERE course_id = $1")
+        .bind(course_id)
+        .execute(&mut *tx)
+        .await
+        .context("failed to clear linked sections")?;
+    let (group_indexes, linked_crns): (Vec<i16>, Vec<&str>) = details
+        .linked_groups
+        .iter()
+        .enumerate()
+        .flat_map(|(i, group)| group.iter().map(move |crn| (i as i16, crn.as_str())))
+        .unzip();
+    if !linked_crns.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO course_linked_sections (course_id, group_index, linked_crn)
+            SELECT $1, v.group_index, v.linked_crn
+            FROM UNNEST($2::int2[], $3::text[]) AS v(group_index, linked_crn)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(course_id)
+        .bind(&group_indexes)
+        .bind(&linked_crns)
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert linked sections")?;
+    }
+
+    let seat_changes = match (&details.enrollment, &current) {
+        (Some(info), Some(c)) => diff_seats(
+            [
+                c.enrollment,
+                c.max_enrollment,
+                c.wait_count,
+                c.wait_capacity,
+            ],
+            info,
+        ),
+        _ => Vec::new(),
+    };
+    let seats_changed = !seat_changes.is_empty();
+    if let Some(info) = details.enrollment.as_ref().filter(|_| seats_changed) {
+        sqlx::query(
+            r#"
+            UPDATE courses
+            SET enrollment = $2, max_enrollment = $3, wait_count = $4, wait_capacity = $5
+            WHERE id = $1
+            "#,
+        )
+        .bind(course_id)
+        .bind(info.enrollment)
+        .bind(info.max_enrollment)
+        .bind(info.wait_count)
+        .bind(info.wait_capacity)
+        .execute(&mut *tx)
+        .await
+        .context("failed to update course enrollment")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO course_metrics (course_id, timestamp, enrollment, wait_count, seats_available)
+            VALUES ($1, NOW(), $2, $3, $4)
+            "#,
+        )
+        .bind(course_id)
+        .bind(info.enrollment)
+        .bind(info.wait_count)
+        .bind(info.max_enrollment - info.enrollment)
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert course metric")?;
+    }
+    changes.extend(seat_changes);
+
+    let events = match current {
+        Some(c) => {
+            let course = CourseRef {
+                id: course_id,
+                term: c.term_code,
+                crn: c.crn,
+                subject: c.subject,
+                course_number: c.course_number,
+                title: c.title,
+            };
+            let changes = changes
+                .into_iter()
+                .map(|change| (course.clone(), change))
+                .collect();
+            record_course_changes(&mut tx, changes).await?
+        }
+        None => Vec::new(),
+    };
+
+    sqlx::query("UPDATE courses SET description = $2, details_scraped_at = NOW() WHERE id = $1")
+        .bind(course_id)
+        .bind(&details.description)
+        .execute(&mut *tx)
+        .await
+        .context("failed to mark course details scraped")?;
+    outbox::enqueue_course_changes(&mut *tx, &events).await?;
+
+    tx.commit()
+        .await
+        .context("failed to commit course details")?;
+
+    Ok(SavedCourseDetails {
+        seats_changed,
+        events,
+    })
+}
+
+/// CRNs in a term whose details are missing or older than `max_age_hours`,
+/// excluding CRNs already queued in a pending `CrnList` job.
+pub async fn find_stale_detail_crns(
+    pool: &PgPool,
+    term_code: &str,
+    max_age_hours: i32,
+    limit: i64,
+) -> Result<Vec<String>> {
+    let rows: Vec<(String,)> = sqlx::query_as(
+        r#"
+        SELECT c.crn
+        FROM courses c
+        WHERE c.term_code = $1
+          AND (c.details_scraped_at IS NULL
+               OR c.details_scraped_at < NOW() - make_interval(hours => $2))
+          AND NOT EXISTS (
+              SELECT 1 FROM scrape_jobs j
+              WHERE j.target_type = 'CrnList'
+                AND j.dead_lettered_at IS NULL
+                AND j.target_payload->>'term' = c.term_code
+                AND j.target_payload->'crns' ? c.crn
+          )
+        ORDER BY c.details_scraped_at NULLS FIRST, c.crn
+        LIMIT $3
+        "#,
+    )
+    .bind(term_code)
+    .bind(max_age_hours)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("failed to find courses needing details")?;
+    Ok(rows.into_iter().map(|(crn,)| crn).collect())
+}
+
+/// Restrictions for a course, in Banner's display order.
+pub async fn get_restrictions(pool: &PgPool, course_id: i32) -> Result<Vec<CourseRestriction>> {
+    sqlx::query_as(
+        "SELECT heading, value FROM course_restrictions WHERE course_id = $1 ORDER BY position",
+    )
+    .bind(course_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch course restrictions")
+}
+
+/// Corequisites for a course, in Banner's display order.
+pub async fn get_corequisites(pool: &PgPool, course_id: i32) -> Result<Vec<CourseCorequisite>> {
+    sqlx::query_as(
+        "SELECT subject, course_number, title FROM course_corequisites \
+         WHERE course_id = $1 ORDER BY position",
+    )
+    .bind(course_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch course corequisites")
+}
+
+/// Linked section groups for a batch of courses, keyed by course ID.
+///
+/// Each group is a list of CRNs that must be registered together with the
+/// course; a student picks exactly one group. Courses without linked
+/// sections (or not yet enriched) are absent from the map.
+pub async fn get_linked_groups_for_courses(
+    pool: &PgPool,
+    course_ids: &[i32],
+) -> Result<HashMap<i32, Vec<Vec<String>>>> {
+    if course_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(i32, i16, Vec<String>)> = sqlx::query_as(
+        r#"
+        SELECT course_id, group_index, array_agg(linked_crn ORDER BY linked_crn)
+        FROM course_linked_sections
+        WHERE course_id = ANY($1)
+        GROUP BY course_id, group_index
+        ORDER BY course_id, group_index
+        "#,
+    )
+    .bind(course_ids)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch linked sections")?;
+
+    let mut map: HashMap<i32, Vec<Vec<String>>> = HashMap::new();
+    for (course_id, _, crns) in rows {
+        map.entry(course_id).or_default().push(crns);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_seats_reports_changed_fields() {
+        let fetched = EnrollmentInfo {
+            enrollment: 31,
+            max_enrollment: 35,
+            wait_count: 0,
+            wait_capacity: 10,
+        };
+        assert!(diff_seats([31, 35, 0, 10], &fetched).is_empty());
+        assert_eq!(
+            diff_seats([30, 35, 2, 10], &fetched),
+            [
+                CourseChange::Seats {
+                    field: SeatField::Enrollment,
+                    old: Some(30),
+                    new: 31,
+                },
+                CourseChange::Seats {
+                    field: SeatField::WaitCount,
+                    old: Some(2),
+                    new: 0,
+                },
+            ]
+        );
+    }
+}
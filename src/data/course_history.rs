@@ -0,0 +1,168 @@
+//! Point-in-time reconstruction of course fields from the audit log.
+//!
+//! Every scrape that changes a course writes one `course_audits` row per field
+//! with the old and new values (see [`super::batch`]). Starting from the
+//! current row and undoing every change made after a timestamp yields the
+//! values the course had at that moment.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+
+use crate::data::models::Course;
+
+/// A course's audited fields as they stood at `as_of`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseSnapshot {
+    pub as_of: DateTime<Utc>,
+    /// Field values keyed by audit field name.
+    pub fields: BTreeMap<String, Value>,
+    /// Fields whose value at `as_of` differs from the current one, sorted.
+    pub changed_fields: Vec<String>,
+    /// When the course was first scraped, if its initial snapshot was recorded.
+    pub first_seen: Option<DateTime<Utc>>,
+}
+
+fn opt<T: serde::Serialize>(value: &Option<T>) -> Value {
+    value.as_ref().map_or(Value::Null, |v| json!(v))
+}
+
+/// Current values of every audited field, encoded the way audits store them.
+pub fn current_fields(course: &Course) -> BTreeMap<String, Value> {
+    [
+        ("enrollment", json!(course.enrollment)),
+        ("max_enrollment", json!(course.max_enrollment)),
+        ("wait_count", json!(course.wait_count)),
+        ("wait_capacity", json!(course.wait_capacity)),
+        ("subject", json!(course.subject)),
+        ("course_number", json!(course.course_number)),
+        ("title", json!(course.title)),
+        ("sequence_number", opt(&course.sequence_number)),
+        ("part_of_term", opt(&course.part_of_term)),
+        ("instructional_method", opt(&course.instructional_method)),
+        ("campus", opt(&course.campus)),
+        ("credit_hours", opt(&course.credit_hours)),
+        ("credit_hour_low", opt(&course.credit_hour_low)),
+        ("credit_hour_high", opt(&course.credit_hour_high)),
+        ("cross_list", opt(&course.cross_list)),
+        ("cross_list_capacity", opt(&course.cross_list_capacity)),
+        ("cross_list_count", opt(&course.cross_list_count)),
+        ("link_identifier", opt(&course.link_identifier)),
+        ("is_section_linked", opt(&course.is_section_linked)),
+        ("meeting_times", course.meeting_times.clone()),
+        ("attributes", course.attributes.clone()),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_owned(), value))
+    .collect()
+}
+
+/// Undo `(field, old_value)` changes, given newest first, on top of `current`.
+///
+/// Returns the rewound fields and the names of those that differ from
+/// `current`. Fields that aren't tracked are ignored.
+pub fn rewind(
+    current: &BTreeMap<String, Value>,
+    changes_newest_first: impl IntoIterator<Item = (String, Option<Value>)>,
+) -> (BTreeMap<String, Value>, Vec<String>) {
+    let mut fields = current.clone();
+    for (field, old_value) in changes_newest_first {
+        if let Some(slot) = fields.get_mut(&field) {
+            *slot = old_value.unwrap_or(Value::Null);
+        }
+    }
+    let changed = fields
+        .iter()
+        .filter(|(name, value)| current.get(*name) != Some(value))
+        .map(|(name, _)| name.clone())
+        .collect();
+    (fields, changed)
+}
+
+/// Reconstruct `course` as of `as_of`.
+///
+/// Returns `None` when the audit log shows the course was first scraped
+/// after `as_of`.
+pub async fn course_as_of(
+    pool: &PgPool,
+    course: &Course,
+    as_of: DateTime<Utc>,
+) -> Result<Option<CourseSnapshot>> {
+    let first_seen: Option<DateTime<Utc>> = sqlx::query_scalar(
+        "SELECT MIN(timestamp) FROM course_audits WHERE course_id = $1 AND field_changed = 'initial'",
+    )
+    .bind(course.id)
+    .fetch_one(pool)
+    .await
+    .context("failed to fetch course first-seen time")?;
+    if first_seen.is_some_and(|seen| seen > as_of) {
+        return Ok(None);
+    }
+
+    let changes: Vec<(String, Option<Value>)> = sqlx::query_as(
+        r#"
+        SELECT field_changed, old_value
+        FROM course_audits
+        WHERE course_id = $1 AND timestamp > $2 AND field_changed <> 'initial'
+        ORDER BY timestamp DESC, id DESC
+        "#,
+    )
+    .bind(course.id)
+    .bind(as_of)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch course audits")?;
+
+    let (fields, changed_fields) = rewind(&current_fields(course), changes);
+    Ok(Some(CourseSnapshot {
+        as_of,
+        fields,
+        changed_fields,
+        first_seen,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn current() -> BTreeMap<String, Value> {
+        BTreeMap::from([
+            ("enrollment".to_owned(), json!(30)),
+            ("title".to_owned(), json!("Data Structures")),
+            ("campus".to_owned(), json!("MAIN")),
+        ])
+    }
+
+    #[test]
+    fn rewinds_to_the_oldest_undone_value() {
+        // Enrollment went 20 -> 25 -> 30; campus went null -> MAIN.
+        let changes = vec![
+            ("enrollment".to_owned(), Some(json!(25))),
+            ("campus".to_owned(), None),
+            ("enrollment".to_owned(), Some(json!(20))),
+        ];
+        let (fields, changed) = rewind(&current(), changes);
+
+        assert_eq!(fields["enrollment"], json!(20));
+        assert_eq!(fields["campus"], Value::Null);
+        assert_eq!(fields["title"], json!("Data Structures"));
+        assert_eq!(changed, vec!["campus", "enrollment"]);
+    }
+
+    #[test]
+    fn changes_that_round_trip_are_not_reported() {
+        let changes = vec![
+            ("enrollment".to_owned(), Some(json!(29))),
+            ("enrollment".to_owned(), Some(json!(30))),
+            ("retired_field".to_owned(), Some(json!("x"))),
+        ];
+        let (fields, changed) = rewind(&current(), changes);
+
+        assert_eq!(fields, current());
+        assert!(changed.is_empty());
+    }
+}
@@ -6,11 +6,12 @@
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 use crate::data::unsigned::Count;
 
 /// An inclusive date range with the invariant that `start <= end`.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct DateRange {
@@ -37,7 +38,7 @@ impl DateRange {
 }
 
 /// Physical location where a course section meets.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct MeetingLocation {
@@ -48,7 +49,7 @@ pub struct MeetingLocation {
 }
 
 /// Credit hours for a course section -- either a fixed value or a range.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase", tag = "type")]
 #[ts(export)]
 pub enum CreditHours {
@@ -72,7 +73,7 @@ impl CreditHours {
 }
 
 /// Cross-listed section information.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct CrossList {
@@ -82,21 +83,31 @@ pub struct CrossList {
 }
 
 /// A linked section reference (e.g. lab linked to a lecture).
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct SectionLink {
-    pub identifier: String,
+    /// Banner's link identifier, if the search results carried one.
+    pub identifier: Option<String>,
+    /// Alternative groups of CRNs that must be registered with this section.
+    ///
+    /// Exactly one group must be chosen (e.g. one lab for a lecture). Empty
+    /// until the detail enrichment job has fetched linked sections.
+    pub groups: Vec<Vec<String>>,
 }
 
 /// Enrollment counts for a course section.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct Enrollment {
+    #[schema(value_type = u32)]
     pub current: Count,
+    #[schema(value_type = u32)]
     pub max: Count,
+    #[schema(value_type = u32)]
     pub wait_count: Count,
+    #[schema(value_type = u32)]
     pub wait_capacity: Count,
 }
 
@@ -142,7 +153,7 @@ pub fn sanitize_rmp_ratings(
 ///
 /// Present whenever an RMP profile link exists. Rating fields are `None` when the
 /// profile has no reviews (0 ratings / 0.0 average).
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct RmpBrief {
@@ -152,28 +163,31 @@ pub struct RmpBrief {
 }
 
 /// Brief BlueBook evaluation data for an instructor.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct BlueBookBrief {
     pub avg_instructor_rating: f32,
+    #[schema(value_type = u32)]
     pub total_responses: Count,
 }
 
 /// Full BlueBook summary for instructor detail pages.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct BlueBookFull {
     pub calibrated_rating: f32,
     pub avg_instructor_rating: f32,
     pub avg_course_rating: Option<f32>,
+    #[schema(value_type = u32)]
     pub total_responses: Count,
+    #[schema(value_type = u32)]
     pub eval_count: Count,
 }
 
 /// Full RateMyProfessors summary for instructor detail pages.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct RmpFull {
@@ -185,7 +199,7 @@ pub struct RmpFull {
 }
 
 /// Data source for an instructor rating.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "lowercase")]
 #[ts(export)]
 pub enum RatingSource {
@@ -215,7 +229,7 @@ impl RatingSource {
 }
 
 /// Bayesian composite rating combining RMP and BlueBook via regression calibration.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct InstructorRating {
@@ -1,17 +1,21 @@
 //! Database query functions for courses, used by the web API.
 
 use super::context::DbContext;
-use super::events::{AuditLogEvent, DomainEvent};
-use crate::banner::Course as BannerCourse;
+use super::events::{CourseChangesEvent, CourseEvent, DomainEvent};
+use crate::banner::{Course as BannerCourse, CourseDetails};
 use crate::data::batch::batch_upsert_courses as batch_upsert_impl;
+use crate::data::course_details;
+use crate::data::cursor::{KeyKind, Keyset};
 use crate::data::models::{Course, CourseInstructorDetail, UpsertCounts};
+use crate::data::search::{SearchWeights, to_tsquery};
 use anyhow::{Context, Result};
-use sqlx::{PgPool, Postgres, QueryBuilder};
+use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
 use std::collections::HashMap;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 /// Column to sort search results by.
-#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, TS)]
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, TS, ToSchema)]
 #[serde(rename_all = "snake_case")]
 #[ts(export)]
 pub enum SortColumn {
@@ -23,7 +27,7 @@ pub enum SortColumn {
 }
 
 /// Sort direction.
-#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, TS)]
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, TS, ToSchema)]
 #[serde(rename_all = "snake_case")]
 #[ts(export)]
 pub enum SortDirection {
@@ -32,7 +36,7 @@ pub enum SortDirection {
 }
 
 /// Aggregate min/max ranges for filter sliders, computed per-term.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct FilterRanges {
@@ -54,6 +58,8 @@ pub struct SearchFilter<'a> {
     pub course_number_low: Option<i32>,
     pub course_number_high: Option<i32>,
     pub open_only: bool,
+    /// Only sections whose waitlist still has room.
+    pub waitlist_available: bool,
     pub instructional_method: Option<&'a [String]>,
     pub campus: Option<&'a [String]>,
     pub wait_count_max: Option<i32>,
@@ -65,32 +71,165 @@ pub struct SearchFilter<'a> {
     pub credit_hour_min: Option<f64>,
     pub credit_hour_max: Option<f64>,
     pub instructors: Option<&'a [String]>,
+    /// Relevance weights used to order text matches when no sort is given.
+    pub weights: SearchWeights,
+}
+
+/// A filter that also has facet counts, so it is left out of its own counts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Facet {
+    Subject,
+    Campus,
+    InstructionalMethod,
+    Attribute,
+    Open,
+}
+
+impl Facet {
+    const ALL: [Facet; 5] = [
+        Facet::Subject,
+        Facet::Campus,
+        Facet::InstructionalMethod,
+        Facet::Attribute,
+        Facet::Open,
+    ];
+
+    /// Column of the facet query's `matched` CTE holding this filter's result.
+    fn column(self) -> &'static str {
+        match self {
+            Facet::Subject => "subject_ok",
+            Facet::Campus => "campus_ok",
+            Facet::InstructionalMethod => "method_ok",
+            Facet::Attribute => "attribute_ok",
+            Facet::Open => "open_ok",
+        }
+    }
+
+    /// `AND` of every other facet's filter, for counting this facet's values.
+    fn others_match(self) -> String {
+        Facet::ALL
+            .iter()
+            .filter(|f| **f != self)
+            .map(|f| f.column())
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+
+    /// Whether the search filters on this facet.
+    fn is_set(self, filter: &SearchFilter<'_>) -> bool {
+        match self {
+            Facet::Subject => filter.subjects.is_some(),
+            Facet::Campus => filter.campus.is_some(),
+            Facet::InstructionalMethod => filter.instructional_method.is_some(),
+            Facet::Attribute => filter.attributes.is_some(),
+            Facet::Open => filter.open_only,
+        }
+    }
+}
+
+/// Push the condition for one facet's filter, or `TRUE` when it isn't set.
+fn push_facet_condition<'args>(
+    builder: &mut QueryBuilder<'args, Postgres>,
+    filter: &'args SearchFilter<'_>,
+    facet: Facet,
+) {
+    match facet {
+        // Renamed subjects match under either code.
+        Facet::Subject => push_any(
+            builder,
+            "subject = ANY(expand_subject_codes(",
+            filter.subjects,
+            "))",
+        ),
+        Facet::Campus => push_any(builder, "campus = ANY(", filter.campus, ")"),
+        Facet::InstructionalMethod => push_any(
+            builder,
+            "instructional_method = ANY(",
+            filter.instructional_method,
+            ")",
+        ),
+        Facet::Attribute => push_any(
+            builder,
+            "EXISTS (SELECT 1 FROM jsonb_array_elements_text(attributes) a WHERE a = ANY(",
+            filter.attributes,
+            "))",
+        ),
+        Facet::Open if filter.open_only => {
+            builder.push("max_enrollment > enrollment");
+        }
+        Facet::Open => {
+            builder.push("TRUE");
+        }
+    }
+}
+
+/// Push `prefix values suffix`, or `TRUE` without values.
+fn push_any<'args>(
+    builder: &mut QueryBuilder<'args, Postgres>,
+    prefix: &str,
+    values: Option<&'args [String]>,
+    suffix: &str,
+) {
+    match values {
+        Some(values) => {
+            builder.push(prefix);
+            builder.push_bind(values);
+            builder.push(suffix);
+        }
+        None => {
+            builder.push("TRUE");
+        }
+    }
 }
 
 /// Append search filter WHERE conditions to a QueryBuilder.
 ///
+/// See [`push_non_facet_conditions`].
+fn push_search_conditions<'args>(
+    builder: &mut QueryBuilder<'args, Postgres>,
+    filter: &'args SearchFilter<'_>,
+) {
+    push_non_facet_conditions(builder, filter);
+    for facet in Facet::ALL {
+        if facet.is_set(filter) {
+            builder.push(" AND ");
+            push_facet_condition(builder, filter, facet);
+        }
+    }
+}
+
+/// Append the WHERE conditions of every filter that isn't a [`Facet`].
+///
 /// Course number filtering extracts the numeric prefix to support alphanumeric
 /// course numbers (e.g., "015X", "399H"). The numeric part is compared against
 /// the range, so "399H" matches a search for courses 300-400.
-fn push_search_conditions<'args>(
+fn push_non_facet_conditions<'args>(
     builder: &mut QueryBuilder<'args, Postgres>,
     filter: &'args SearchFilter<'_>,
 ) {
     builder.push(" WHERE term_code = ");
     builder.push_bind(filter.term_code);
 
-    if let Some(subjects) = filter.subjects {
-        builder.push(" AND subject = ANY(");
-        builder.push_bind(subjects);
-        builder.push(")");
-    }
-
     if let Some(query) = filter.query {
-        builder.push(" AND (title_search @@ plainto_tsquery('simple_unaccent', ");
+        // Substring matches on the title catch fragments the word index can't.
+        builder.push(" AND (immutable_unaccent(title) ILIKE '%' || immutable_unaccent(");
         builder.push_bind(query);
-        builder.push(") OR immutable_unaccent(title) ILIKE '%' || immutable_unaccent(");
-        builder.push_bind(query);
-        builder.push(") || '%')");
+        builder.push(") || '%'");
+        if let Some(tsquery) = to_tsquery(query) {
+            // The title column is checked directly so freshly scraped courses
+            // match before the next index refresh.
+            builder.push(" OR title_search @@ to_tsquery('simple_unaccent', ");
+            builder.push_bind(tsquery.clone());
+            builder.push(
+                ") OR id IN (SELECT course_id FROM course_search_index \
+                 WHERE term_code = ",
+            );
+            builder.push_bind(filter.term_code);
+            builder.push(" AND document @@ to_tsquery('simple_unaccent', ");
+            builder.push_bind(tsquery);
+            builder.push("))");
+        }
+        builder.push(")");
     }
 
     if let Some(low) = filter.course_number_low {
@@ -103,20 +242,8 @@ fn push_search_conditions<'args>(
         builder.push_bind(high);
     }
 
-    if filter.open_only {
-        builder.push(" AND max_enrollment > enrollment");
-    }
-
-    if let Some(method) = filter.instructional_method {
-        builder.push(" AND instructional_method = ANY(");
-        builder.push_bind(method);
-        builder.push(")");
-    }
-
-    if let Some(campus) = filter.campus {
-        builder.push(" AND campus = ANY(");
-        builder.push_bind(campus);
-        builder.push(")");
+    if filter.waitlist_available {
+        builder.push(" AND wait_capacity > wait_count");
     }
 
     if let Some(wc) = filter.wait_count_max {
@@ -163,16 +290,6 @@ fn push_search_conditions<'args>(
         builder.push(")");
     }
 
-    if let Some(attrs) = filter.attributes {
-        builder.push(
-            " AND EXISTS (\
-             SELECT 1 FROM jsonb_array_elements_text(attributes) a \
-             WHERE a = ANY(",
-        );
-        builder.push_bind(attrs);
-        builder.push("))");
-    }
-
     if let Some(min) = filter.credit_hour_min {
         builder.push(" AND COALESCE(credit_hours, credit_hour_low, 0) >= ");
         builder.push_bind(min);
@@ -196,42 +313,120 @@ fn push_search_conditions<'args>(
     }
 }
 
-/// Build a safe ORDER BY clause from typed sort parameters.
+/// Select the sort keys for typed sort parameters, ending with `id` so the
+/// order is total.
 ///
 /// All column names are hardcoded string literals -- no caller input is interpolated.
-fn sort_clause(column: Option<SortColumn>, direction: Option<SortDirection>) -> String {
-    let dir = match direction.unwrap_or(SortDirection::Asc) {
-        SortDirection::Asc => "ASC",
-        SortDirection::Desc => "DESC",
-    };
+fn push_sort_keys(
+    keyset: &mut Keyset,
+    builder: &mut QueryBuilder<'_, Postgres>,
+    column: Option<SortColumn>,
+    direction: Option<SortDirection>,
+) {
+    let desc = matches!(direction, Some(SortDirection::Desc));
 
     match column {
         Some(SortColumn::CourseCode) => {
-            format!("subject {dir}, course_number {dir}, sequence_number {dir}")
-        }
-        Some(SortColumn::Title) => format!("title {dir}"),
-        Some(SortColumn::Instructor) => {
-            format!(
-                "(SELECT i.display_name FROM course_instructors ci \
-                 JOIN instructors i ON i.id = ci.instructor_id \
-                 WHERE ci.course_id = courses.id AND ci.is_primary = true \
-                 LIMIT 1) {dir} NULLS LAST"
-            )
-        }
-        Some(SortColumn::Time) => {
-            format!("(meeting_times->0->'timeRange'->>'start') {dir} NULLS LAST")
+            keyset.push_sql_key(builder, KeyKind::Text, desc, "subject");
+            keyset.push_sql_key(builder, KeyKind::Text, desc, "course_number");
+            keyset.push_sql_key(builder, KeyKind::Text, desc, "sequence_number");
         }
+        Some(SortColumn::Title) => keyset.push_sql_key(builder, KeyKind::Text, desc, "title"),
+        Some(SortColumn::Instructor) => keyset.push_sql_key(
+            builder,
+            KeyKind::Text,
+            desc,
+            "SELECT i.display_name FROM course_instructors ci \
+             JOIN instructors i ON i.id = ci.instructor_id \
+             WHERE ci.course_id = courses.id AND ci.is_primary = true \
+             LIMIT 1",
+        ),
+        Some(SortColumn::Time) => keyset.push_sql_key(
+            builder,
+            KeyKind::Text,
+            desc,
+            "meeting_times->0->'timeRange'->>'start'",
+        ),
         Some(SortColumn::Seats) => {
-            format!("(max_enrollment - enrollment) {dir}")
+            keyset.push_sql_key(builder, KeyKind::Int, desc, "max_enrollment - enrollment")
+        }
+        None => {
+            keyset.push_sql_key(builder, KeyKind::Text, false, "subject");
+            keyset.push_sql_key(builder, KeyKind::Text, false, "course_number");
+            keyset.push_sql_key(builder, KeyKind::Text, false, "sequence_number");
         }
-        None => "subject ASC, course_number ASC, sequence_number ASC".to_string(),
     }
+    keyset.push_sql_key(builder, KeyKind::Int, false, "id");
 }
 
-/// Search courses by term with optional filters.
+/// Number of matching sections sharing one raw Banner code.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct FacetCount {
+    pub code: String,
+    pub count: i64,
+}
+
+/// Per-value counts over the full filtered result set, ignoring pagination.
+///
+/// Each facet's counts apply every active filter except its own, so a
+/// selected subject still lists the other subjects' counts. `open` and
+/// `closed` likewise ignore the open-only filter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFacets {
+    pub subjects: Vec<FacetCount>,
+    pub campuses: Vec<FacetCount>,
+    pub instructional_methods: Vec<FacetCount>,
+    pub attributes: Vec<FacetCount>,
+    pub open: i64,
+    pub closed: i64,
+}
+
+/// One page of search results with the total count and facets.
+#[derive(Debug)]
+pub struct SearchResults {
+    pub courses: Vec<Course>,
+    pub total: i64,
+    /// Only computed for the first page; `None` for later pages.
+    pub facets: Option<SearchFacets>,
+    /// Cursor for the following page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Where a page of search results starts.
+#[derive(Debug, Clone, Copy)]
+pub enum SearchPage<'a> {
+    /// Skip this many matching sections.
+    Offset(i32),
+    /// Resume after the section a previous page's `next_cursor` names.
+    After(&'a str),
+}
+
+#[derive(sqlx::FromRow)]
+struct FacetRow {
+    total: i64,
+    open_count: i64,
+    closed_count: i64,
+    subjects: sqlx::types::Json<Vec<FacetCount>>,
+    campuses: sqlx::types::Json<Vec<FacetCount>>,
+    instructional_methods: sqlx::types::Json<Vec<FacetCount>>,
+    attributes: sqlx::types::Json<Vec<FacetCount>>,
+}
+
+/// Aggregate `(code, count)` pairs for one facet of the `matched` CTE, most
+/// common first. `from` names the source rows, `code` the expression to group
+/// by; rows must pass every facet filter except `facet`'s own.
+fn facet_select(code: &str, from: &str, facet: Facet, alias: &str) -> String {
+    let others = facet.others_match();
+    format!(
+        "COALESCE((SELECT jsonb_agg(jsonb_build_object('code', code, 'count', n) \
+         ORDER BY n DESC, code) FROM (SELECT {code} AS code, COUNT(*) AS n FROM {from} \
+         WHERE {code} IS NOT NULL AND {others} GROUP BY 1) f), '[]'::jsonb) AS {alias}"
+    )
+}
+
+/// Search courses by term with optional filters, skipping `offset` sections.
 ///
-/// Returns `(courses, total_count)` for pagination. Uses FTS tsvector for word
-/// search and falls back to trigram ILIKE for substring matching.
+/// See [`search_courses_page`].
 pub async fn search_courses(
     db_pool: &PgPool,
     filter: &SearchFilter<'_>,
@@ -239,26 +434,109 @@ pub async fn search_courses(
     offset: i32,
     sort_by: Option<SortColumn>,
     sort_dir: Option<SortDirection>,
-) -> Result<(Vec<Course>, i64)> {
-    let order_by = sort_clause(sort_by, sort_dir);
+) -> Result<SearchResults> {
+    search_courses_page(
+        db_pool,
+        filter,
+        limit,
+        SearchPage::Offset(offset),
+        sort_by,
+        sort_dir,
+    )
+    .await
+}
 
-    // Data query
-    let mut data_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM courses");
+/// Search courses by term with optional filters.
+///
+/// Returns one page of courses plus the total count and facet counts for
+/// pagination and filter sidebars. Text queries run against the search index
+/// (see [`crate::data::search`]) with a substring fallback on titles. Without
+/// an explicit sort, text matches are ordered by relevance. A malformed
+/// cursor fails with [`CursorError`](crate::data::cursor::CursorError).
+pub async fn search_courses_page(
+    db_pool: &PgPool,
+    filter: &SearchFilter<'_>,
+    limit: i32,
+    page: SearchPage<'_>,
+    sort_by: Option<SortColumn>,
+    sort_dir: Option<SortDirection>,
+) -> Result<SearchResults> {
+    let rank_query = match sort_by {
+        None => filter.query.and_then(to_tsquery),
+        Some(_) => None,
+    };
+
+    // Data query: sort keys are selected by an inner query so the keyset
+    // condition and ORDER BY can refer to them by name.
+    let mut keyset = Keyset::new();
+    let mut data_builder: QueryBuilder<Postgres> =
+        QueryBuilder::new("SELECT * FROM (SELECT courses.*");
+    if let Some(tsquery) = rank_query {
+        // Substring-only matches have no index hit and sort last.
+        keyset.push_key(&mut data_builder, KeyKind::Float, true, |b| {
+            b.push("SELECT ts_rank(CAST(");
+            b.push_bind(filter.weights.as_array());
+            b.push(" AS real[]), document, to_tsquery('simple_unaccent', ");
+            b.push_bind(tsquery);
+            b.push(")) FROM course_search_index WHERE course_id = courses.id");
+        });
+    }
+    push_sort_keys(&mut keyset, &mut data_builder, sort_by, sort_dir);
+    data_builder.push(" FROM courses");
     push_search_conditions(&mut data_builder, filter);
-    data_builder.push(" ORDER BY ");
-    data_builder.push(&order_by);
+    data_builder.push(") page");
+    if let SearchPage::After(cursor) = page {
+        data_builder.push(" WHERE ");
+        keyset.push_after(&mut data_builder, cursor)?;
+    }
+    keyset.push_order_by(&mut data_builder);
+    // One extra row tells whether another page follows.
     data_builder.push(" LIMIT ");
-    data_builder.push_bind(limit);
-    data_builder.push(" OFFSET ");
-    data_builder.push_bind(offset);
+    data_builder.push_bind(limit + 1);
+    if let SearchPage::Offset(offset) = page {
+        data_builder.push(" OFFSET ");
+        data_builder.push_bind(offset);
+    }
 
-    let courses = data_builder
-        .build_query_as::<Course>()
+    let mut rows = data_builder
+        .build()
         .fetch_all(db_pool)
         .await
         .context("failed to search courses")?;
+    let next_cursor = if rows.len() > limit as usize {
+        rows.truncate(limit as usize);
+        rows.last()
+            .map(|row| keyset.cursor_after(row))
+            .transpose()
+            .context("failed to read search sort keys")?
+    } else {
+        None
+    };
+    let courses = rows
+        .iter()
+        .map(Course::from_row)
+        .collect::<Result<Vec<_>, _>>()
+        .context("failed to decode searched courses")?;
+
+    // Facets only change with the filters, so later pages just count.
+    let (total, facets) = match page {
+        SearchPage::Offset(0) => {
+            let (total, facets) = search_facets(db_pool, filter).await?;
+            (total, Some(facets))
+        }
+        _ => (count_matches(db_pool, filter).await?, None),
+    };
 
-    // Count query
+    Ok(SearchResults {
+        courses,
+        total,
+        facets,
+        next_cursor,
+    })
+}
+
+/// Number of sections matching every filter.
+async fn count_matches(db_pool: &PgPool, filter: &SearchFilter<'_>) -> Result<i64> {
     let mut count_builder: QueryBuilder<Postgres> =
         QueryBuilder::new("SELECT COUNT(*) FROM courses");
     push_search_conditions(&mut count_builder, filter);
@@ -268,8 +546,70 @@ pub async fn search_courses(
         .fetch_one(db_pool)
         .await
         .context("failed to count search results")?;
+    Ok(total.0)
+}
+
+/// Total matching sections plus the facet counts.
+///
+/// The rows passing every non-facet filter are materialized once, with one
+/// column per facet filter. Each facet then counts the rows passing all the
+/// other facet filters, so selecting a value leaves its alternatives visible.
+async fn search_facets(db_pool: &PgPool, filter: &SearchFilter<'_>) -> Result<(i64, SearchFacets)> {
+    let mut facet_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+        "WITH matched AS MATERIALIZED (\
+         SELECT subject, campus, instructional_method, attributes, enrollment, max_enrollment",
+    );
+    for facet in Facet::ALL {
+        facet_builder.push(", ");
+        push_facet_condition(&mut facet_builder, filter, facet);
+        facet_builder.push(" AS ");
+        facet_builder.push(facet.column());
+    }
+    facet_builder.push(" FROM courses");
+    push_non_facet_conditions(&mut facet_builder, filter);
+
+    let all_match = Facet::ALL.map(Facet::column).join(" AND ");
+    let open_base = Facet::Open.others_match();
+    facet_builder.push(format!(
+        "), attrs AS (\
+         SELECT a AS attribute, matched.* \
+         FROM matched, jsonb_array_elements_text(matched.attributes) a) \
+         SELECT (SELECT COUNT(*) FROM matched WHERE {all_match}) AS total, \
+         (SELECT COUNT(*) FROM matched WHERE {open_base} AND max_enrollment > enrollment) \
+         AS open_count, \
+         (SELECT COUNT(*) FROM matched WHERE {open_base} AND NOT max_enrollment > enrollment) \
+         AS closed_count, "
+    ));
+    facet_builder.push(
+        [
+            facet_select("subject", "matched", Facet::Subject, "subjects"),
+            facet_select("campus", "matched", Facet::Campus, "campuses"),
+            facet_select(
+                "instructional_method",
+                "matched",
+                Facet::InstructionalMethod,
+                "instructional_methods",
+            ),
+            facet_select("attribute", "attrs", Facet::Attribute, "attributes"),
+        ]
+        .join(", "),
+    );
+
+    let row = facet_builder
+        .build_query_as::<FacetRow>()
+        .fetch_one(db_pool)
+        .await
+        .context("failed to count search facets")?;
 
-    Ok((courses, total.0))
+    let facets = SearchFacets {
+        subjects: row.subjects.0,
+        campuses: row.campuses.0,
+        instructional_methods: row.instructional_methods.0,
+        attributes: row.attributes.0,
+        open: row.open_count,
+        closed: row.closed_count,
+    };
+    Ok((row.total, facets))
 }
 
 /// Get a single course by CRN and term.
@@ -288,6 +628,27 @@ pub async fn get_course_by_crn(
     Ok(course)
 }
 
+/// Get the courses in a term with any of `crns`, ordered by subject and
+/// course number. CRNs with no stored course are skipped.
+pub async fn get_courses_by_crns(
+    db_pool: &PgPool,
+    term_code: &str,
+    crns: &[String],
+) -> Result<Vec<Course>> {
+    if crns.is_empty() {
+        return Ok(Vec::new());
+    }
+    sqlx::query_as::<_, Course>(
+        "SELECT * FROM courses WHERE term_code = $1 AND crn = ANY($2) \
+         ORDER BY subject, course_number, sequence_number, crn",
+    )
+    .bind(term_code)
+    .bind(crns)
+    .fetch_all(db_pool)
+    .await
+    .context("failed to fetch courses by crn")
+}
+
 /// Get instructors for a single course by course ID.
 pub async fn get_course_instructors(
     db_pool: &PgPool,
@@ -435,6 +796,118 @@ pub async fn get_related_sections(
     Ok(courses)
 }
 
+/// One section as written by the bulk export, flattened for CSV.
+#[derive(sqlx::FromRow, Debug, Clone, serde::Serialize)]
+pub struct CourseExportRow {
+    pub term_code: String,
+    pub crn: String,
+    pub subject: String,
+    pub course_number: String,
+    pub sequence_number: Option<String>,
+    pub title: String,
+    pub credit_hours: Option<f64>,
+    pub credit_hour_low: Option<f64>,
+    pub credit_hour_high: Option<f64>,
+    pub instructional_method: Option<String>,
+    pub campus: Option<String>,
+    pub part_of_term: Option<String>,
+    pub enrollment: i32,
+    pub max_enrollment: i32,
+    pub wait_count: i32,
+    pub wait_capacity: i32,
+    /// Display names, primary instructor first.
+    pub instructors: Vec<String>,
+    pub meeting_times: serde_json::Value,
+    pub attributes: serde_json::Value,
+    pub last_scraped_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl CourseExportRow {
+    /// Where the next export page resumes.
+    pub fn export_key(&self) -> CourseExportKey {
+        CourseExportKey {
+            term_code: self.term_code.clone(),
+            subject: self.subject.clone(),
+            course_number: self.course_number.clone(),
+            sequence_number: self.sequence_number.clone().unwrap_or_default(),
+            crn: self.crn.clone(),
+        }
+    }
+}
+
+/// Export-order position of a section: newest term first, then catalog order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CourseExportKey {
+    pub term_code: String,
+    pub subject: String,
+    pub course_number: String,
+    pub sequence_number: String,
+    pub crn: String,
+}
+
+/// One page of sections in a term (or in every term, newest first), in
+/// catalog order, resuming after `after`.
+///
+/// Exports read page by page so a connection is only held while a page is
+/// fetched, not while a slow client downloads it. The term and cursor
+/// predicates are only added when present, so each shape gets its own plan.
+pub async fn course_export_page(
+    db_pool: &PgPool,
+    term_code: Option<&str>,
+    after: Option<&CourseExportKey>,
+    limit: i64,
+) -> Result<Vec<CourseExportRow>> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+        r#"
+        SELECT c.term_code, c.crn, c.subject, c.course_number, c.sequence_number, c.title,
+               c.credit_hours, c.credit_hour_low, c.credit_hour_high,
+               c.instructional_method, c.campus, c.part_of_term,
+               c.enrollment, c.max_enrollment, c.wait_count, c.wait_capacity,
+               COALESCE((
+                   SELECT array_agg(i.display_name ORDER BY ci.is_primary DESC, i.display_name)
+                   FROM course_instructors ci
+                   JOIN instructors i ON i.id = ci.instructor_id
+                   WHERE ci.course_id = c.id
+               ), '{}') AS instructors,
+               c.meeting_times, c.attributes, c.last_scraped_at
+        FROM courses c
+        WHERE TRUE"#,
+    );
+    if let Some(term_code) = term_code {
+        builder
+            .push(" AND c.term_code = ")
+            .push_bind(term_code.to_owned());
+    }
+    if let Some(after) = after {
+        builder
+            .push(" AND (c.term_code < ")
+            .push_bind(after.term_code.clone())
+            .push(" OR (c.term_code = ")
+            .push_bind(after.term_code.clone())
+            .push(" AND (c.subject, c.course_number, COALESCE(c.sequence_number, ''), c.crn) > (")
+            .push_bind(after.subject.clone())
+            .push(", ")
+            .push_bind(after.course_number.clone())
+            .push(", ")
+            .push_bind(after.sequence_number.clone())
+            .push(", ")
+            .push_bind(after.crn.clone())
+            .push(")))");
+    }
+    builder
+        .push(
+            " ORDER BY c.term_code DESC, c.subject, c.course_number, \
+             COALESCE(c.sequence_number, ''), c.crn LIMIT ",
+        )
+        .push_bind(limit);
+
+    builder
+        .build_query_as::<CourseExportRow>()
+        .fetch_all(db_pool)
+        .await
+        .context("failed to fetch course export page")
+}
+
 /// Get all distinct term codes that have courses in the DB.
 pub async fn get_available_terms(db_pool: &PgPool) -> Result<Vec<String>> {
     let rows: Vec<(String,)> =
@@ -519,7 +992,7 @@ pub async fn get_filter_ranges(db_pool: &PgPool, term_code: &str) -> Result<Filt
 }
 
 /// A suggested course result for autocomplete.
-#[derive(Debug, serde::Serialize, TS)]
+#[derive(Debug, Clone, serde::Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct CourseSuggestion {
@@ -530,8 +1003,29 @@ pub struct CourseSuggestion {
     pub score: f32,
 }
 
+/// A course suggestion with all of its sections collapsed into one entry.
+#[derive(Debug, serde::Serialize, sqlx::FromRow, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CourseGroupSuggestion {
+    pub subject: String,
+    pub course_number: String,
+    /// The title most sections share.
+    pub title: String,
+    /// Distinct titles across sections; above 1 for topics courses.
+    pub title_count: i32,
+    pub section_count: i32,
+    /// Sections with at least one open seat.
+    pub open_sections: i32,
+    /// Open seats summed over all sections.
+    pub seats_available: i32,
+    /// Waitlisted students summed over all sections.
+    pub wait_count: i32,
+    pub score: f32,
+}
+
 /// A suggested instructor result for autocomplete.
-#[derive(Debug, serde::Serialize, TS)]
+#[derive(Debug, Clone, serde::Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct InstructorSuggestion {
@@ -542,6 +1036,31 @@ pub struct InstructorSuggestion {
     pub score: f32,
 }
 
+/// A suggested subject result for autocomplete.
+#[derive(Debug, Clone, serde::Serialize, sqlx::FromRow, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectSuggestion {
+    pub code: String,
+    /// The subject's name, or its code when no reference entry exists.
+    pub description: String,
+    pub section_count: i32,
+    pub score: f32,
+}
+
+/// A suggested building result for autocomplete.
+#[derive(Debug, Clone, serde::Serialize, sqlx::FromRow, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BuildingSuggestion {
+    pub code: String,
+    /// The building's name, or its code when no reference entry exists.
+    pub description: String,
+    /// Distinct rooms the term's sections meet in.
+    pub room_count: i32,
+    pub score: f32,
+}
+
 /// Get course title suggestions using trigram similarity.
 pub async fn suggest_courses(
     db_pool: &PgPool,
@@ -584,6 +1103,44 @@ pub async fn suggest_courses(
         .collect())
 }
 
+/// Get course suggestions grouped by subject and number, with a seat summary.
+///
+/// Unlike [`suggest_courses`], sections with different titles (topics
+/// courses) collapse into a single entry.
+pub async fn suggest_course_groups(
+    db_pool: &PgPool,
+    term_code: &str,
+    query: &str,
+    limit: i32,
+) -> Result<Vec<CourseGroupSuggestion>> {
+    sqlx::query_as::<_, CourseGroupSuggestion>(
+        r#"
+        SELECT subject, course_number,
+               mode() WITHIN GROUP (ORDER BY title) AS title,
+               COUNT(DISTINCT title)::int AS title_count,
+               COUNT(*)::int AS section_count,
+               COUNT(*) FILTER (WHERE enrollment < max_enrollment)::int AS open_sections,
+               COALESCE(SUM(GREATEST(max_enrollment - enrollment, 0)), 0)::int AS seats_available,
+               COALESCE(SUM(wait_count), 0)::int AS wait_count,
+               MAX(GREATEST(similarity(immutable_unaccent(title), immutable_unaccent($2)), similarity(subject || ' ' || course_number, $2))) AS score
+        FROM courses
+        WHERE term_code = $1
+          AND (immutable_unaccent(title) % immutable_unaccent($2) OR immutable_unaccent(title) ILIKE '%' || immutable_unaccent($2) || '%'
+               OR (subject || ' ' || course_number) % $2
+               OR (subject || ' ' || course_number) ILIKE '%' || $2 || '%')
+        GROUP BY subject, course_number
+        ORDER BY score DESC, subject, course_number
+        LIMIT $3
+        "#,
+    )
+    .bind(term_code)
+    .bind(query)
+    .bind(limit)
+    .fetch_all(db_pool)
+    .await
+    .context("failed to suggest course groups")
+}
+
 /// Get instructor suggestions using trigram similarity.
 pub async fn suggest_instructors(
     db_pool: &PgPool,
@@ -674,6 +1231,78 @@ pub async fn suggest_instructors_global(
         .collect())
 }
 
+/// Get subject suggestions by code prefix or name similarity.
+pub async fn suggest_subjects(
+    db_pool: &PgPool,
+    term_code: &str,
+    query: &str,
+    limit: i32,
+) -> Result<Vec<SubjectSuggestion>> {
+    sqlx::query_as::<_, SubjectSuggestion>(
+        r#"
+        WITH subjects AS (
+            SELECT c.subject AS code, COALESCE(rd.description, c.subject) AS description,
+                   COUNT(*)::int AS section_count
+            FROM courses c
+            LEFT JOIN reference_data rd ON rd.category = 'subject' AND rd.code = c.subject
+            WHERE c.term_code = $1
+            GROUP BY c.subject, rd.description
+        )
+        SELECT code, description, section_count,
+               GREATEST(similarity(code, $2), similarity(immutable_unaccent(description), immutable_unaccent($2))) AS score
+        FROM subjects
+        WHERE code ILIKE $2 || '%'
+           OR immutable_unaccent(description) % immutable_unaccent($2)
+           OR immutable_unaccent(description) ILIKE '%' || immutable_unaccent($2) || '%'
+        ORDER BY score DESC, code
+        LIMIT $3
+        "#,
+    )
+    .bind(term_code)
+    .bind(query)
+    .bind(limit)
+    .fetch_all(db_pool)
+    .await
+    .context("failed to suggest subjects")
+}
+
+/// Get building suggestions by code prefix or name similarity. Only buildings
+/// the term's sections meet in are considered.
+pub async fn suggest_buildings(
+    db_pool: &PgPool,
+    term_code: &str,
+    query: &str,
+    limit: i32,
+) -> Result<Vec<BuildingSuggestion>> {
+    sqlx::query_as::<_, BuildingSuggestion>(
+        r#"
+        WITH buildings AS (
+            SELECT cm.building AS code, COALESCE(rd.description, cm.building) AS description,
+                   COUNT(DISTINCT cm.room)::int AS room_count
+            FROM course_meetings cm
+            JOIN courses c ON c.id = cm.course_id
+            LEFT JOIN reference_data rd ON rd.category = 'building' AND rd.code = cm.building
+            WHERE c.term_code = $1 AND cm.building IS NOT NULL
+            GROUP BY cm.building, rd.description
+        )
+        SELECT code, description, room_count,
+               GREATEST(similarity(code, $2), similarity(immutable_unaccent(description), immutable_unaccent($2))) AS score
+        FROM buildings
+        WHERE code ILIKE $2 || '%'
+           OR immutable_unaccent(description) % immutable_unaccent($2)
+           OR immutable_unaccent(description) ILIKE '%' || immutable_unaccent($2) || '%'
+        ORDER BY score DESC, code
+        LIMIT $3
+        "#,
+    )
+    .bind(term_code)
+    .bind(query)
+    .bind(limit)
+    .fetch_all(db_pool)
+    .await
+    .context("failed to suggest buildings")
+}
+
 /// Course operations with automatic event emission.
 pub struct CourseOps<'a> {
     ctx: &'a DbContext,
@@ -684,22 +1313,35 @@ impl<'a> CourseOps<'a> {
         Self { ctx }
     }
 
-    /// Batch upsert courses and emit audit log events.
+    /// Batch upsert courses and emit their course changes.
     ///
     /// This wraps the existing `batch_upsert_courses` function but handles
     /// event emission automatically.
     pub async fn batch_upsert(&self, courses: &[BannerCourse]) -> Result<UpsertCounts> {
-        let (counts, audit_entries) = batch_upsert_impl(courses, self.ctx.pool()).await?;
+        let (counts, events) = batch_upsert_impl(courses, self.ctx.pool()).await?;
+        self.publish(events);
+        Ok(counts)
+    }
+
+    /// Save detail endpoint data for a course and emit course changes for
+    /// changed seats and edited details.
+    pub async fn save_details(
+        &self,
+        course_id: i32,
+        details: &CourseDetails,
+    ) -> Result<course_details::SavedCourseDetails> {
+        let saved =
+            course_details::save_course_details(self.ctx.pool(), course_id, details).await?;
+        self.publish(saved.events.clone());
+        Ok(saved)
+    }
 
-        if !audit_entries.is_empty() {
+    fn publish(&self, events: Vec<CourseEvent>) {
+        if !events.is_empty() {
             self.ctx
                 .events()
-                .publish(DomainEvent::AuditLog(AuditLogEvent {
-                    entries: audit_entries,
-                }));
+                .publish(DomainEvent::CourseChanges(CourseChangesEvent { events }));
         }
-
-        Ok(counts)
     }
 }
 
@@ -712,6 +1354,59 @@ pub async fn count_all(pool: &PgPool) -> Result<i64> {
     Ok(count)
 }
 
+/// List the CRNs stored for a subject in a term.
+pub async fn list_crns_for_subject(
+    pool: &PgPool,
+    term_code: &str,
+    subject: &str,
+) -> Result<Vec<String>> {
+    sqlx::query_scalar("SELECT crn FROM courses WHERE term_code = $1 AND subject = $2")
+        .bind(term_code)
+        .bind(subject)
+        .fetch_all(pool)
+        .await
+        .context("failed to list CRNs for subject")
+}
+
+/// Write a `removed` audit for each listed section that disappeared from Banner.
+///
+/// Sections whose latest audit is already a removal are skipped, so repeated
+/// scrapes of a subject don't log the same removal again. Returns the CRNs
+/// newly marked removed by this call.
+pub async fn record_removals(
+    pool: &PgPool,
+    term_code: &str,
+    crns: &[String],
+) -> Result<Vec<String>> {
+    if crns.is_empty() {
+        return Ok(Vec::new());
+    }
+    sqlx::query_scalar(
+        r#"
+        WITH removed AS (
+            INSERT INTO course_audits (course_id, timestamp, field_changed, old_value, new_value)
+            SELECT c.id, NOW(), 'removed', 'false'::jsonb, 'true'::jsonb
+            FROM courses c
+            WHERE c.term_code = $1
+              AND c.crn = ANY($2)
+              AND (
+                  SELECT a.field_changed FROM course_audits a
+                  WHERE a.course_id = c.id
+                  ORDER BY a.timestamp DESC, a.id DESC
+                  LIMIT 1
+              ) IS DISTINCT FROM 'removed'
+            RETURNING course_id
+        )
+        SELECT c.crn FROM removed JOIN courses c ON c.id = removed.course_id
+        "#,
+    )
+    .bind(term_code)
+    .bind(crns)
+    .fetch_all(pool)
+    .await
+    .context("failed to record course removals")
+}
+
 /// Look up a course's internal ID by term code and CRN.
 pub async fn get_id_by_crn(pool: &PgPool, term_code: &str, crn: &str) -> Result<Option<i32>> {
     let row: Option<(i32,)> =
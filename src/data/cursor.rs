@@ -0,0 +1,290 @@
+//! Opaque keyset cursors for deep pagination.
+//!
+//! OFFSET pagination makes Postgres walk every skipped row, and pages shift
+//! when rows are inserted while a client scrolls. A [`Keyset`] instead
+//! selects each sort key as a `sort_key_N` column of an inner query, orders
+//! the outer query by those columns, and resumes strictly after the last row
+//! a client saw. The cursor handed out is that row's key values as a JSON
+//! array, hex-encoded so clients treat it as opaque.
+//!
+//! Every key sorts with `NULLS LAST` regardless of direction, and callers
+//! end the key list with a unique column so no two rows compare equal.
+
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{Postgres, QueryBuilder, Row};
+
+/// A cursor that doesn't decode, or came from a different sort order.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid pagination cursor")]
+pub struct CursorError;
+
+/// SQL type a sort key is cast to, so cursor values bind back exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    Int,
+    /// Must stay finite: JSON has no infinities to carry in a cursor.
+    Float,
+    Text,
+}
+
+impl KeyKind {
+    fn cast(self) -> &'static str {
+        match self {
+            KeyKind::Int => "bigint",
+            KeyKind::Float => "float8",
+            KeyKind::Text => "text",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum KeyValue {
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl KeyValue {
+    /// Whether the value can stand in for a key of `kind`. Floats with no
+    /// fractional part may round-trip through JSON as integers.
+    fn fits(&self, kind: KeyKind) -> bool {
+        matches!(
+            (self, kind),
+            (KeyValue::Int(_), KeyKind::Int | KeyKind::Float)
+                | (KeyValue::Float(_), KeyKind::Float)
+                | (KeyValue::Text(_), KeyKind::Text)
+        )
+    }
+
+    fn push_bind(&self, builder: &mut QueryBuilder<'_, Postgres>, kind: KeyKind) {
+        match self {
+            KeyValue::Int(v) if kind == KeyKind::Float => builder.push_bind(*v as f64),
+            KeyValue::Int(v) => builder.push_bind(*v),
+            KeyValue::Float(v) => builder.push_bind(*v),
+            KeyValue::Text(v) => builder.push_bind(v.clone()),
+        };
+    }
+}
+
+/// The sort key values of the last row on a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor(Vec<Option<KeyValue>>);
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(&self.0).expect("cursor values serialize");
+        json.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    pub fn decode(s: &str) -> Result<Self, CursorError> {
+        if !s.len().is_multiple_of(2) || !s.is_ascii() {
+            return Err(CursorError);
+        }
+        let bytes = (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| CursorError)?;
+        serde_json::from_slice(&bytes)
+            .map(Cursor)
+            .map_err(|_| CursorError)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SortKey {
+    kind: KeyKind,
+    descending: bool,
+}
+
+/// The sort keys of one paginated query.
+#[derive(Debug, Default)]
+pub struct Keyset {
+    keys: Vec<SortKey>,
+}
+
+impl Keyset {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select `expr` as the next sort key: pushes `, (expr)::type AS sort_key_N`.
+    /// The expression is written by `push_expr`, so it may bind parameters.
+    pub fn push_key<'args>(
+        &mut self,
+        builder: &mut QueryBuilder<'args, Postgres>,
+        kind: KeyKind,
+        descending: bool,
+        push_expr: impl FnOnce(&mut QueryBuilder<'args, Postgres>),
+    ) {
+        builder.push(", (");
+        push_expr(builder);
+        builder.push(format!(
+            ")::{} AS sort_key_{}",
+            kind.cast(),
+            self.keys.len()
+        ));
+        self.keys.push(SortKey { kind, descending });
+    }
+
+    /// [`push_key`](Self::push_key) for a fixed SQL expression.
+    pub fn push_sql_key(
+        &mut self,
+        builder: &mut QueryBuilder<'_, Postgres>,
+        kind: KeyKind,
+        descending: bool,
+        expr: &str,
+    ) {
+        self.push_key(builder, kind, descending, |b| {
+            b.push(expr);
+        });
+    }
+
+    /// A condition matching rows that sort strictly after `cursor`.
+    pub fn push_after(
+        &self,
+        builder: &mut QueryBuilder<'_, Postgres>,
+        cursor: &str,
+    ) -> Result<(), CursorError> {
+        let Cursor(values) = Cursor::decode(cursor)?;
+        let fits = values.len() == self.keys.len()
+            && values
+                .iter()
+                .zip(&self.keys)
+                .all(|(value, key)| value.as_ref().is_none_or(|v| v.fits(key.kind)));
+        if !fits {
+            return Err(CursorError);
+        }
+
+        // (k0 after) OR (k0 = v0 AND k1 after) OR ...
+        builder.push("(");
+        for (i, (value, key)) in values.iter().zip(&self.keys).enumerate() {
+            if i > 0 {
+                builder.push(" OR ");
+            }
+            builder.push("(");
+            for (j, (prev, prev_key)) in values.iter().zip(&self.keys).take(i).enumerate() {
+                match prev {
+                    Some(v) => {
+                        builder.push(format!("sort_key_{j} = "));
+                        v.push_bind(builder, prev_key.kind);
+                    }
+                    None => {
+                        builder.push(format!("sort_key_{j} IS NULL"));
+                    }
+                }
+                builder.push(" AND ");
+            }
+            match value {
+                // Nulls sort last, so only other nulls follow a null.
+                None => {
+                    builder.push("FALSE");
+                }
+                Some(v) => {
+                    let op = if key.descending { "<" } else { ">" };
+                    builder.push(format!("(sort_key_{i} {op} "));
+                    v.push_bind(builder, key.kind);
+                    builder.push(format!(" OR sort_key_{i} IS NULL)"));
+                }
+            }
+            builder.push(")");
+        }
+        builder.push(")");
+        Ok(())
+    }
+
+    /// Push ` ORDER BY sort_key_0 ASC NULLS LAST, ...`.
+    pub fn push_order_by(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        builder.push(" ORDER BY ");
+        for (i, key) in self.keys.iter().enumerate() {
+            if i > 0 {
+                builder.push(", ");
+            }
+            let dir = if key.descending { "DESC" } else { "ASC" };
+            builder.push(format!("sort_key_{i} {dir} NULLS LAST"));
+        }
+    }
+
+    /// The cursor resuming after `row`, which must select the sort keys.
+    pub fn cursor_after(&self, row: &PgRow) -> Result<String, sqlx::Error> {
+        let values = self
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let column = format!("sort_key_{i}");
+                Ok(match key.kind {
+                    KeyKind::Int => row
+                        .try_get::<Option<i64>, _>(column.as_str())?
+                        .map(KeyValue::Int),
+                    KeyKind::Float => row
+                        .try_get::<Option<f64>, _>(column.as_str())?
+                        .map(KeyValue::Float),
+                    KeyKind::Text => row
+                        .try_get::<Option<String>, _>(column.as_str())?
+                        .map(KeyValue::Text),
+                })
+            })
+            .collect::<Result<Vec<_>, sqlx::Error>>()?;
+        Ok(Cursor(values).encode())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursors_round_trip() {
+        let cursor = Cursor(vec![
+            Some(KeyValue::Text("CS".to_owned())),
+            None,
+            Some(KeyValue::Float(0.5)),
+            Some(KeyValue::Int(42)),
+        ]);
+        let encoded = cursor.encode();
+        assert!(encoded.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(Cursor::decode(&encoded).unwrap(), cursor);
+    }
+
+    #[test]
+    fn malformed_cursors_are_rejected() {
+        assert!(Cursor::decode("abc").is_err());
+        assert!(Cursor::decode("zz").is_err());
+        assert!(
+            Cursor::decode(&"{}".bytes().map(|b| format!("{b:02x}")).collect::<String>()).is_err()
+        );
+    }
+
+    #[test]
+    fn cursors_from_another_sort_are_rejected() {
+        let mut keyset = Keyset::new();
+        let mut builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT 1");
+        keyset.push_sql_key(&mut builder, KeyKind::Text, false, "title");
+        keyset.push_sql_key(&mut builder, KeyKind::Int, false, "id");
+
+        let text_and_int = Cursor(vec![
+            Some(KeyValue::Text("Algorithms".to_owned())),
+            Some(KeyValue::Int(7)),
+        ]);
+        let mut after: QueryBuilder<Postgres> = QueryBuilder::new("");
+        assert!(
+            keyset
+                .push_after(&mut after, &text_and_int.encode())
+                .is_ok()
+        );
+        assert_eq!(
+            after.sql(),
+            "(((sort_key_0 > $1 OR sort_key_0 IS NULL)) OR (sort_key_0 = $2 AND (sort_key_1 > $3 OR sort_key_1 IS NULL)))"
+        );
+
+        let wrong_kind = Cursor(vec![Some(KeyValue::Int(1)), Some(KeyValue::Int(7))]);
+        let too_short = Cursor(vec![Some(KeyValue::Int(7))]);
+        for cursor in [wrong_kind, too_short] {
+            let mut after: QueryBuilder<Postgres> = QueryBuilder::new("");
+            assert!(keyset.push_after(&mut after, &cursor.encode()).is_err());
+        }
+    }
+}
@@ -36,7 +36,9 @@ impl EventBuffer {
         }
         events.push_back(event);
         let new_head = self.base_offset.load(Ordering::Acquire) + events.len() as u64;
-        let _ = self.head.send(new_head);
+        // `send` skips the update while nobody is subscribed, which would
+        // start later subscribers at a stale head.
+        self.head.send_replace(new_head);
     }
 
     /// Subscribe to the buffer, returning current head position and a watch receiver.
@@ -57,6 +59,12 @@ impl EventBuffer {
         events.get(index).cloned()
     }
 
+    /// Return the most recent buffered event that `f` maps to `Some`.
+    pub fn find_latest<T>(&self, f: impl Fn(&DomainEvent) -> Option<T>) -> Option<T> {
+        let events = self.events.read().expect("lock poisoned");
+        events.iter().rev().find_map(f)
+    }
+
     /// Get the current base offset (logical index of first event in buffer).
     pub fn base_offset(&self) -> u64 {
         self.base_offset.load(Ordering::Acquire)
@@ -69,7 +77,12 @@ mod tests {
     use crate::web::ws::ScrapeJobEvent;
 
     fn make_scrape_event(id: i32) -> DomainEvent {
-        DomainEvent::ScrapeJob(ScrapeJobEvent::Completed { id, subject: None })
+        DomainEvent::ScrapeJob(ScrapeJobEvent::Completed {
+            id,
+            subject: None,
+            term: None,
+            diff: None,
+        })
     }
 
     #[test]
@@ -133,6 +146,20 @@ mod tests {
         assert!(buffer.read(2).is_some()); // Event 3
     }
 
+    #[test]
+    fn find_latest_returns_most_recent_match() {
+        let buffer = EventBuffer::new(10);
+        buffer.publish(make_scrape_event(1));
+        buffer.publish(make_scrape_event(2));
+
+        let latest = buffer.find_latest(|event| match event {
+            DomainEvent::ScrapeJob(ScrapeJobEvent::Completed { id, .. }) => Some(*id),
+            _ => None,
+        });
+        assert_eq!(latest, Some(2));
+        assert_eq!(buffer.find_latest(|_| None::<()>), None);
+    }
+
     #[tokio::test]
     async fn subscribe_notifies_on_publish() {
         let buffer = EventBuffer::new(10);
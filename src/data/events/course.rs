@@ -0,0 +1,248 @@
+//! Typed course changes.
+//!
+//! Course writes compute [`CourseChange`]s where they diff old and new state,
+//! record each one as a `course_audits` row in the same transaction, and
+//! publish the resulting [`CourseEvent`]s as one [`CourseChangesEvent`]. The
+//! raw audit log is derived from the events, so consumers that act on what
+//! changed never match on audit field names.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::web::audit::AuditLogEntry;
+
+/// The section a change applies to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseRef {
+    pub id: i32,
+    pub term: String,
+    pub crn: String,
+    pub subject: String,
+    pub course_number: String,
+    pub title: String,
+}
+
+/// An enrollment or waitlist count. Serialized under its audit name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeatField {
+    Enrollment,
+    MaxEnrollment,
+    WaitCount,
+    WaitCapacity,
+}
+
+impl SeatField {
+    pub const ALL: [Self; 4] = [
+        Self::Enrollment,
+        Self::MaxEnrollment,
+        Self::WaitCount,
+        Self::WaitCapacity,
+    ];
+
+    /// The field's name in `course_audits`.
+    pub fn audit_name(self) -> &'static str {
+        match self {
+            Self::Enrollment => "enrollment",
+            Self::MaxEnrollment => "max_enrollment",
+            Self::WaitCount => "wait_count",
+            Self::WaitCapacity => "wait_capacity",
+        }
+    }
+
+    /// The field's name in API responses.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Enrollment => "enrollment",
+            Self::MaxEnrollment => "maxEnrollment",
+            Self::WaitCount => "waitCount",
+            Self::WaitCapacity => "waitCapacity",
+        }
+    }
+}
+
+/// What happened to a section.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CourseChange {
+    /// Stored for the first time, with a snapshot of its initial fields.
+    Created { snapshot: Value },
+    Seats {
+        field: SeatField,
+        old: Option<i32>,
+        new: i32,
+    },
+    /// The assigned instructors' names, sorted.
+    Instructors { old: Vec<String>, new: Vec<String> },
+    /// Any other field, under its audit name.
+    Field {
+        field: String,
+        old: Option<Value>,
+        new: Value,
+    },
+}
+
+impl CourseChange {
+    /// Instructors present after the change but not before.
+    pub fn assigned_instructors(&self) -> Vec<&str> {
+        match self {
+            Self::Instructors { old, new } => new
+                .iter()
+                .filter(|name| !old.contains(name))
+                .map(String::as_str)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The `field_changed` this change is recorded under.
+    pub fn audit_field(&self) -> &str {
+        match self {
+            Self::Created { .. } => "initial",
+            Self::Seats { field, .. } => field.audit_name(),
+            Self::Instructors { .. } => "instructors",
+            Self::Field { field, .. } => field,
+        }
+    }
+
+    /// The `(old_value, new_value)` this change is recorded with.
+    pub fn audit_values(&self) -> (Option<Value>, Value) {
+        match self {
+            Self::Created { snapshot } => (None, snapshot.clone()),
+            Self::Seats { old, new, .. } => (old.map(Value::from), Value::from(*new)),
+            Self::Instructors { old, new } => (
+                Some(Value::from(old.as_slice())),
+                Value::from(new.as_slice()),
+            ),
+            Self::Field { old, new, .. } => (old.clone(), new.clone()),
+        }
+    }
+}
+
+/// One change to one section, as recorded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseEvent {
+    pub audit_id: i32,
+    pub at: DateTime<Utc>,
+    pub course: CourseRef,
+    pub change: CourseChange,
+}
+
+impl CourseEvent {
+    /// The event as an audit log entry.
+    pub fn audit_entry(&self) -> AuditLogEntry {
+        let (old_value, new_value) = self.change.audit_values();
+        AuditLogEntry {
+            id: self.audit_id,
+            course_id: self.course.id,
+            timestamp: self.at.to_rfc3339(),
+            field_changed: self.change.audit_field().to_owned(),
+            old_value,
+            new_value,
+            subject: Some(self.course.subject.clone()),
+            course_number: Some(self.course.course_number.clone()),
+            crn: Some(self.course.crn.clone()),
+            course_title: Some(self.course.title.clone()),
+            term_code: Some(self.course.term.clone()),
+        }
+    }
+}
+
+/// Course changes committed by one write, in audit order.
+#[derive(Debug, Clone)]
+pub struct CourseChangesEvent {
+    pub events: Vec<CourseEvent>,
+}
+
+impl CourseChangesEvent {
+    /// The changes as audit log entries.
+    pub fn audit_entries(&self) -> Vec<AuditLogEntry> {
+        self.events.iter().map(CourseEvent::audit_entry).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(change: CourseChange) -> CourseEvent {
+        CourseEvent {
+            audit_id: 1,
+            at: DateTime::UNIX_EPOCH,
+            course: CourseRef {
+                id: 7,
+                term: "202710".to_owned(),
+                crn: "12345".to_owned(),
+                subject: "CS".to_owned(),
+                course_number: "1083".to_owned(),
+                title: "Programming I".to_owned(),
+            },
+            change,
+        }
+    }
+
+    #[test]
+    fn changes_map_to_audit_entries() {
+        let entry = event(CourseChange::Seats {
+            field: SeatField::WaitCount,
+            old: Some(1),
+            new: 2,
+        })
+        .audit_entry();
+        assert_eq!(entry.field_changed, "wait_count");
+        assert_eq!(entry.old_value, Some(json!(1)));
+        assert_eq!(entry.new_value, json!(2));
+        assert_eq!(entry.crn.as_deref(), Some("12345"));
+
+        let entry = event(CourseChange::Created {
+            snapshot: json!({ "enrollment": 0 }),
+        })
+        .audit_entry();
+        assert_eq!(entry.field_changed, "initial");
+        assert_eq!(entry.old_value, None);
+
+        let entry = event(CourseChange::Instructors {
+            old: vec!["Ada".to_owned()],
+            new: vec![],
+        })
+        .audit_entry();
+        assert_eq!(entry.field_changed, "instructors");
+        assert_eq!(entry.old_value, Some(json!(["Ada"])));
+        assert_eq!(entry.new_value, json!([]));
+    }
+
+    #[test]
+    fn events_round_trip_through_json() {
+        let original = event(CourseChange::Seats {
+            field: SeatField::MaxEnrollment,
+            old: Some(30),
+            new: 35,
+        });
+        let json = serde_json::to_value(&original).unwrap();
+        assert_eq!(json["change"]["kind"], "seats");
+        assert_eq!(json["change"]["field"], "max_enrollment");
+        assert_eq!(
+            serde_json::from_value::<CourseEvent>(json).unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn assigned_instructors_are_the_additions() {
+        let change = CourseChange::Instructors {
+            old: vec!["Ada".to_owned(), "Grace".to_owned()],
+            new: vec!["Grace".to_owned(), "Linus".to_owned()],
+        };
+        assert_eq!(change.assigned_instructors(), ["Linus"]);
+
+        let removal = CourseChange::Instructors {
+            old: vec!["Ada".to_owned()],
+            new: vec![],
+        };
+        assert!(removal.assigned_instructors().is_empty());
+    }
+}
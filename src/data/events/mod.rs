@@ -1,7 +1,21 @@
 //! Domain event infrastructure.
+//!
+//! Writes that other parts of the app react to publish a [`DomainEvent`] to
+//! the shared [`EventBuffer`], usually through [`DbContext`] so the write
+//! site doesn't know who is listening. Each consumer -- the notifier, the
+//! WebSocket hub, in-memory caches -- reads the buffer through its own
+//! [`EventSubscription`]. Webhooks don't subscribe: course changes reach
+//! them through the notification outbox, which fans them out in the same
+//! transaction that records the delivery.
+//!
+//! [`DbContext`]: crate::data::DbContext
 
 mod buffer;
+mod course;
+mod subscription;
 mod types;
 
 pub use buffer::EventBuffer;
-pub use types::{AuditLogEvent, DomainEvent};
+pub use course::{CourseChange, CourseChangesEvent, CourseEvent, CourseRef, SeatField};
+pub use subscription::EventSubscription;
+pub use types::{DomainEvent, InstructorEvent};
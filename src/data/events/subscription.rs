@@ -0,0 +1,118 @@
+//! A consumer's position in the [`EventBuffer`].
+
+use std::sync::Arc;
+use tokio::sync::watch;
+
+use super::{DomainEvent, EventBuffer};
+
+/// Events taken from the buffer in one [`EventSubscription::drain`].
+#[derive(Debug, Default)]
+pub struct Drained {
+    /// Events pruned before this consumer read them. When non-zero, anything
+    /// derived from the missed events should be rebuilt from scratch.
+    pub skipped: u64,
+    pub events: Vec<DomainEvent>,
+}
+
+impl Drained {
+    pub fn lagged(&self) -> bool {
+        self.skipped > 0
+    }
+}
+
+/// Cursor-based reader over an [`EventBuffer`], starting at the head.
+///
+/// Each consumer (notifier, webhook fan-out, WebSocket hub, caches) owns one
+/// and sees every event published after it subscribed, in order, unless it
+/// falls more than the buffer's capacity behind.
+pub struct EventSubscription {
+    buffer: Arc<EventBuffer>,
+    cursor: u64,
+    head: watch::Receiver<u64>,
+}
+
+impl EventSubscription {
+    pub fn new(buffer: Arc<EventBuffer>) -> Self {
+        let (cursor, head) = buffer.subscribe();
+        Self {
+            buffer,
+            cursor,
+            head,
+        }
+    }
+
+    /// Wait for the next publish. Returns false once the buffer is gone.
+    pub async fn changed(&mut self) -> bool {
+        self.head.changed().await.is_ok()
+    }
+
+    /// Take every event published since the last drain.
+    pub fn drain(&mut self) -> Drained {
+        let base = self.buffer.base_offset();
+        let skipped = base.saturating_sub(self.cursor);
+        self.cursor = self.cursor.max(base);
+
+        let mut events = Vec::new();
+        while let Some(event) = self.buffer.read(self.cursor) {
+            events.push(event);
+            self.cursor += 1;
+        }
+        Drained { skipped, events }
+    }
+
+    /// Wait for a publish, then drain. Returns `None` once the buffer is gone.
+    pub async fn recv(&mut self) -> Option<Drained> {
+        self.changed().await.then(|| self.drain())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::ws::ScrapeJobEvent;
+
+    fn event(id: i32) -> DomainEvent {
+        DomainEvent::ScrapeJob(ScrapeJobEvent::Deleted { id })
+    }
+
+    #[test]
+    fn drain_returns_events_since_last_drain() {
+        let buffer = Arc::new(EventBuffer::new(10));
+        buffer.publish(event(0));
+        let mut sub = EventSubscription::new(buffer.clone());
+
+        buffer.publish(event(1));
+        buffer.publish(event(2));
+        let drained = sub.drain();
+        assert!(!drained.lagged());
+        assert_eq!(drained.events.len(), 2);
+
+        assert!(sub.drain().events.is_empty());
+        buffer.publish(event(3));
+        assert_eq!(sub.drain().events.len(), 1);
+    }
+
+    #[test]
+    fn drain_reports_pruned_events() {
+        let buffer = Arc::new(EventBuffer::new(2));
+        let mut sub = EventSubscription::new(buffer.clone());
+
+        for id in 1..=5 {
+            buffer.publish(event(id));
+        }
+        let drained = sub.drain();
+        assert_eq!(drained.skipped, 3);
+        assert_eq!(drained.events.len(), 2);
+        assert!(!sub.drain().lagged());
+    }
+
+    #[tokio::test]
+    async fn recv_waits_for_a_publish() {
+        let buffer = Arc::new(EventBuffer::new(10));
+        let mut sub = EventSubscription::new(buffer.clone());
+
+        buffer.publish(event(1));
+        let drained = sub.recv().await.unwrap();
+        assert_eq!(drained.events.len(), 1);
+    }
+}
@@ -1,17 +1,31 @@
 //! Domain event types.
 
-use crate::web::audit::AuditLogEntry;
+use super::CourseChangesEvent;
+use crate::banner::circuit::BannerHealth;
+use crate::data::rmp_matching::RmpMatchingProgress;
 use crate::web::ws::ScrapeJobEvent;
 
 /// Unified enum for all domain events.
 #[derive(Debug, Clone)]
 pub enum DomainEvent {
     ScrapeJob(ScrapeJobEvent),
-    AuditLog(AuditLogEvent),
+    CourseChanges(CourseChangesEvent),
+    RmpMatching(RmpMatchingProgress),
+    Instructor(InstructorEvent),
+    /// The Banner circuit breaker changed state.
+    BannerHealth(BannerHealth),
 }
 
-/// Audit log event containing one or more entries.
-#[derive(Debug, Clone)]
-pub struct AuditLogEvent {
-    pub entries: Vec<AuditLogEntry>,
+/// Changes that make derived instructor data (profiles, ratings) stale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructorEvent {
+    /// `instructor_scores` was rebuilt, in full or for instructors whose
+    /// inputs changed.
+    ScoresRecomputed,
+    /// RMP or BlueBook links changed. `None` when a bulk operation may have
+    /// touched any instructor.
+    LinksChanged { instructor_id: Option<i32> },
+    /// Instructor slugs were regenerated; profiles cached under old slugs
+    /// must now redirect.
+    SlugsRebuilt,
 }
@@ -0,0 +1,375 @@
+//! Database operations for user-submitted data feedback.
+//!
+//! Reports reference a course or instructor and move through a small status
+//! workflow (`open` → `triaged` → `resolved`/`dismissed`) in the admin queue.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::str::FromStr;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::data::unsigned::Count;
+
+/// Maximum accepted message length, mirrored by a CHECK constraint.
+pub const MAX_MESSAGE_LEN: usize = 2000;
+
+/// Domain errors for feedback triage.
+///
+/// The web layer downcasts `anyhow::Error` to this type to pick the HTTP status.
+#[derive(Debug, thiserror::Error)]
+pub enum FeedbackError {
+    #[error("feedback not found")]
+    NotFound,
+    #[error("cannot move feedback from {from} to {to}")]
+    InvalidTransition {
+        from: FeedbackStatus,
+        to: FeedbackStatus,
+    },
+}
+
+/// Triage state of a feedback report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum FeedbackStatus {
+    Open,
+    Triaged,
+    Resolved,
+    Dismissed,
+}
+
+impl FeedbackStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Triaged => "triaged",
+            Self::Resolved => "resolved",
+            Self::Dismissed => "dismissed",
+        }
+    }
+
+    /// Whether an admin may move a report from `self` to `next`.
+    ///
+    /// Closed reports can only be reopened; everything else flows forward.
+    pub fn can_transition_to(self, next: FeedbackStatus) -> bool {
+        use FeedbackStatus::*;
+        matches!(
+            (self, next),
+            (Open, Triaged | Resolved | Dismissed)
+                | (Triaged, Resolved | Dismissed)
+                | (Resolved | Dismissed, Open)
+        )
+    }
+}
+
+impl fmt::Display for FeedbackStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FeedbackStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "open" => Ok(Self::Open),
+            "triaged" => Ok(Self::Triaged),
+            "resolved" => Ok(Self::Resolved),
+            "dismissed" => Ok(Self::Dismissed),
+            _ => Err(anyhow::anyhow!("unknown feedback status: {}", s)),
+        }
+    }
+}
+
+/// What kind of problem the user is reporting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum FeedbackCategory {
+    WrongData,
+    MissingData,
+    Other,
+}
+
+impl FeedbackCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::WrongData => "wrong_data",
+            Self::MissingData => "missing_data",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// The entity a report refers to, resolved to database IDs.
+#[derive(Debug, Clone)]
+pub enum FeedbackTarget {
+    Course { id: i32 },
+    Instructor { id: i32 },
+}
+
+impl FeedbackTarget {
+    fn entity_type(&self) -> &'static str {
+        match self {
+            Self::Course { .. } => "course",
+            Self::Instructor { .. } => "instructor",
+        }
+    }
+}
+
+/// A new report ready to be inserted.
+pub struct NewFeedback<'a> {
+    pub target: FeedbackTarget,
+    /// Human-readable snapshot of the entity, kept if the row is later deleted.
+    pub entity_label: String,
+    pub category: FeedbackCategory,
+    pub message: &'a str,
+    pub page_url: Option<&'a str>,
+    pub reporter_id: Option<i64>,
+}
+
+/// A feedback row in the admin queue.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FeedbackItem {
+    pub id: i32,
+    pub entity_type: String,
+    pub course_id: Option<i32>,
+    pub instructor_id: Option<i32>,
+    pub entity_label: String,
+    pub category: String,
+    pub message: String,
+    pub page_url: Option<String>,
+    /// User ID as text (exceeds JS safe integer range).
+    pub reporter_id: Option<String>,
+    pub status: String,
+    pub admin_note: Option<String>,
+    pub reviewed_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Per-status counts for the admin queue header.
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FeedbackStats {
+    pub open: Count,
+    pub triaged: Count,
+    pub resolved: Count,
+    pub dismissed: Count,
+}
+
+/// Response for the paginated admin feedback list.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListFeedbackResponse {
+    pub items: Vec<FeedbackItem>,
+    pub total: Count,
+    pub page: i32,
+    pub per_page: i32,
+    pub stats: FeedbackStats,
+}
+
+/// Insert a new report and return its ID.
+pub async fn insert_feedback(pool: &PgPool, new: &NewFeedback<'_>) -> Result<i32> {
+    let (course_id, instructor_id) = match new.target {
+        FeedbackTarget::Course { id } => (Some(id), None),
+        FeedbackTarget::Instructor { id } => (None, Some(id)),
+    };
+
+    let (id,): (i32,) = sqlx::query_as(
+        r#"
+        INSERT INTO user_feedback
+            (entity_type, course_id, instructor_id, entity_label, category,
+             message, page_url, reporter_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id
+        "#,
+    )
+    .bind(new.target.entity_type())
+    .bind(course_id)
+    .bind(instructor_id)
+    .bind(&new.entity_label)
+    .bind(new.category.as_str())
+    .bind(new.message)
+    .bind(new.page_url)
+    .bind(new.reporter_id)
+    .fetch_one(pool)
+    .await
+    .context("failed to insert feedback")?;
+
+    Ok(id)
+}
+
+/// List reports, optionally filtered by status, newest first.
+pub async fn list_feedback(
+    pool: &PgPool,
+    status: Option<FeedbackStatus>,
+    page: i32,
+    per_page: i32,
+) -> Result<ListFeedbackResponse> {
+    let page = page.max(1);
+    let per_page = per_page.clamp(1, 100);
+    let offset = (page - 1) * per_page;
+    let status = status.map(|s| s.as_str());
+
+    let items = sqlx::query_as::<_, FeedbackItem>(
+        r#"
+        SELECT id, entity_type, course_id, instructor_id, entity_label, category,
+               message, page_url, reporter_id::text, status, admin_note,
+               reviewed_by::text, created_at, updated_at
+        FROM user_feedback
+        WHERE $1::text IS NULL OR status = $1
+        ORDER BY created_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(status)
+    .bind(per_page)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+    .context("failed to list feedback")?;
+
+    let counts: Vec<(String, i64)> =
+        sqlx::query_as("SELECT status, COUNT(*) FROM user_feedback GROUP BY status")
+            .fetch_all(pool)
+            .await
+            .context("failed to count feedback by status")?;
+
+    let mut stats = FeedbackStats::default();
+    let mut total = 0i64;
+    for (row_status, count) in counts {
+        if status.is_none_or(|s| s == row_status) {
+            total += count;
+        }
+        let count = Count::try_from(count)?;
+        match row_status.parse::<FeedbackStatus>() {
+            Ok(FeedbackStatus::Open) => stats.open = count,
+            Ok(FeedbackStatus::Triaged) => stats.triaged = count,
+            Ok(FeedbackStatus::Resolved) => stats.resolved = count,
+            Ok(FeedbackStatus::Dismissed) => stats.dismissed = count,
+            Err(_) => {}
+        }
+    }
+
+    Ok(ListFeedbackResponse {
+        items,
+        total: Count::try_from(total)?,
+        page,
+        per_page,
+        stats,
+    })
+}
+
+/// Move a report to a new status, enforcing [`FeedbackStatus::can_transition_to`].
+///
+/// The update is conditional on the status read beforehand, so two admins
+/// racing on the same report cannot both apply a transition.
+pub async fn update_status(
+    pool: &PgPool,
+    id: i32,
+    next: FeedbackStatus,
+    admin_note: Option<&str>,
+    reviewer_id: i64,
+) -> Result<FeedbackItem> {
+    let current: Option<(String,)> =
+        sqlx::query_as("SELECT status FROM user_feedback WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .context("failed to fetch feedback status")?;
+    let current: FeedbackStatus = current.ok_or(FeedbackError::NotFound)?.0.parse()?;
+
+    if !current.can_transition_to(next) {
+        return Err(FeedbackError::InvalidTransition {
+            from: current,
+            to: next,
+        }
+        .into());
+    }
+
+    let updated = sqlx::query_as::<_, FeedbackItem>(
+        r#"
+        UPDATE user_feedback
+        SET status = $3,
+            admin_note = COALESCE($4, admin_note),
+            reviewed_by = $5,
+            updated_at = NOW()
+        WHERE id = $1 AND status = $2
+        RETURNING id, entity_type, course_id, instructor_id, entity_label, category,
+                  message, page_url, reporter_id::text, status, admin_note,
+                  reviewed_by::text, created_at, updated_at
+        "#,
+    )
+    .bind(id)
+    .bind(current.as_str())
+    .bind(next.as_str())
+    .bind(admin_note)
+    .bind(reviewer_id)
+    .fetch_optional(pool)
+    .await
+    .context("failed to update feedback status")?;
+
+    // Lost the race: someone else changed the status between our read and write.
+    updated.ok_or_else(|| {
+        FeedbackError::InvalidTransition {
+            from: current,
+            to: next,
+        }
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_can_move_anywhere_forward() {
+        let open = FeedbackStatus::Open;
+        assert!(open.can_transition_to(FeedbackStatus::Triaged));
+        assert!(open.can_transition_to(FeedbackStatus::Resolved));
+        assert!(open.can_transition_to(FeedbackStatus::Dismissed));
+        assert!(!open.can_transition_to(FeedbackStatus::Open));
+    }
+
+    #[test]
+    fn triaged_cannot_go_back_to_open() {
+        let triaged = FeedbackStatus::Triaged;
+        assert!(!triaged.can_transition_to(FeedbackStatus::Open));
+        assert!(triaged.can_transition_to(FeedbackStatus::Resolved));
+        assert!(triaged.can_transition_to(FeedbackStatus::Dismissed));
+    }
+
+    #[test]
+    fn closed_reports_only_reopen() {
+        for closed in [FeedbackStatus::Resolved, FeedbackStatus::Dismissed] {
+            assert!(closed.can_transition_to(FeedbackStatus::Open));
+            assert!(!closed.can_transition_to(FeedbackStatus::Triaged));
+            assert!(!closed.can_transition_to(FeedbackStatus::Resolved));
+            assert!(!closed.can_transition_to(FeedbackStatus::Dismissed));
+        }
+    }
+
+    #[test]
+    fn status_round_trips_through_str() {
+        for s in [
+            FeedbackStatus::Open,
+            FeedbackStatus::Triaged,
+            FeedbackStatus::Resolved,
+            FeedbackStatus::Dismissed,
+        ] {
+            assert_eq!(s.as_str().parse::<FeedbackStatus>().unwrap(), s);
+        }
+        assert!("closed".parse::<FeedbackStatus>().is_err());
+    }
+}
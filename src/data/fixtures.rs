@@ -0,0 +1,819 @@
+//! Deterministic synthetic dataset for local development and integration tests.
+//!
+//! [`generate`] builds terms, subjects, course sections, instructors, BlueBook
+//! evaluations, and RMP professors/reviews from a seeded RNG, so the same
+//! [`FixtureOptions`] always produce the same rows. [`load`] writes them
+//! through the same upsert paths the scrapers use, then runs name backfills,
+//! matching, and score recomputation so the result looks like a scraped
+//! database.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use tracing::info;
+
+use crate::banner::{
+    BannerTerm, Course, FacultyItem, MeetingTime, MeetingTimeResponse, Pair, Season, Term,
+};
+use crate::data::DbContext;
+use crate::data::batch::batch_upsert_courses;
+use crate::data::bluebook::{BlueBookEvaluation, batch_upsert_bluebook_evaluations};
+use crate::data::models::ReferenceData;
+use crate::data::unsigned::Count;
+use crate::rmp::{RmpProfessor, RmpReview};
+
+/// Subject codes and descriptions drawn from in order.
+const SUBJECTS: &[(&str, &str)] = &[
+    ("CS", "Computer Science"),
+    ("MAT", "Mathematics"),
+    ("PHY", "Physics"),
+    ("BIO", "Biology"),
+    ("CHE", "Chemistry"),
+    ("ENG", "English"),
+    ("HIS", "History"),
+    ("PSY", "Psychology"),
+    ("ECO", "Economics"),
+    ("ART", "Art"),
+    ("MUS", "Music"),
+    ("POL", "Political Science"),
+];
+
+const TITLE_PATTERNS: &[&str] = &[
+    "Introduction to {}",
+    "Foundations of {}",
+    "Topics in {}",
+    "Applied {}",
+    "Advanced {}",
+    "Seminar in {}",
+    "Research Methods in {}",
+    "{} Laboratory",
+    "History of {}",
+    "Special Problems in {}",
+];
+
+/// Includes formal names from the nickname dictionary so RMP profiles using
+/// the short form exercise nickname matching.
+const FIRST_NAMES: &[&str] = &[
+    "William",
+    "Margaret",
+    "Robert",
+    "Elizabeth",
+    "Christopher",
+    "Jennifer",
+    "Michael",
+    "Patricia",
+    "Daniel",
+    "Katherine",
+    "María",
+    "José",
+    "Wei",
+    "Priya",
+    "Ahmed",
+    "Olga",
+    "Hiroshi",
+    "Ana",
+    "Samuel",
+    "Deborah",
+    "Thomas",
+    "Rebecca",
+    "Luis",
+    "Fatima",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Smith",
+    "Garcia",
+    "Nguyen",
+    "Johnson",
+    "Martínez",
+    "O'Brien",
+    "Patel",
+    "Kim",
+    "Hernández",
+    "Williams",
+    "Chen",
+    "Brown",
+    "Rodriguez",
+    "LeBlanc",
+    "Davis",
+    "Okafor",
+    "Müller",
+    "Lopez",
+    "Wilson",
+    "Singh",
+    "Anderson",
+    "Ramírez",
+    "Thompson",
+    "Ivanova",
+];
+
+/// Short forms used for RMP first names, keyed by formal first name.
+const RMP_SHORT_NAMES: &[(&str, &str)] = &[
+    ("William", "Bill"),
+    ("Margaret", "Peggy"),
+    ("Robert", "Bob"),
+    ("Elizabeth", "Liz"),
+    ("Christopher", "Chris"),
+    ("Michael", "Mike"),
+    ("Daniel", "Dan"),
+    ("Katherine", "Kate"),
+    ("Samuel", "Sam"),
+    ("Thomas", "Tom"),
+];
+
+const BUILDINGS: &[&str] = &["NPB", "BSE", "MH", "FLN", "AET", "SP1", "HSU", "JPL"];
+
+const REVIEW_COMMENTS: &[&str] = &[
+    "Lectures are clear and well organized.",
+    "Tough grader but you learn a lot.",
+    "Exams were much harder than the homework.",
+    "Very approachable during office hours.",
+    "Reads straight from the slides.",
+    "Would take again, genuinely cares about students.",
+    "Heavy workload, start assignments early.",
+];
+
+const REVIEW_TAGS: &[&str] = &[
+    "Tough grader",
+    "Caring",
+    "Lots of homework",
+    "Clear grading criteria",
+    "Inspirational",
+    "Test heavy",
+    "Gives good feedback",
+];
+
+const GRADES: &[&str] = &["A", "A-", "B+", "B", "C", "Not sure yet"];
+
+/// Weekly meeting patterns as `[mon..sun]` with a session length in minutes.
+/// `None` means an online section with no scheduled meetings.
+const MEETING_PATTERNS: &[Option<([bool; 7], u32)>] = &[
+    Some(([true, false, true, false, true, false, false], 50)),
+    Some(([false, true, false, true, false, false, false], 75)),
+    Some(([true, false, true, false, false, false, false], 75)),
+    Some(([false, false, false, false, false, true, false], 170)),
+    None,
+];
+
+/// Start times (HHMM) sections are scheduled at.
+const START_TIMES: &[u32] = &[800, 900, 1000, 1130, 1300, 1430, 1600, 1730, 1900];
+
+/// Knobs for [`generate`].
+#[derive(Debug, Clone)]
+pub struct FixtureOptions {
+    pub seed: u64,
+    /// Most recent term to generate; earlier terms are derived from it.
+    pub latest_term: Term,
+    /// Number of consecutive terms, ending at `latest_term`.
+    pub terms: usize,
+    /// Number of subjects, capped at the built-in subject list.
+    pub subjects: usize,
+    /// Distinct catalog courses per subject.
+    pub courses_per_subject: usize,
+    /// Instructors per subject.
+    pub instructors_per_subject: usize,
+}
+
+impl Default for FixtureOptions {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            latest_term: *Term::get_current().inner(),
+            terms: 3,
+            subjects: 6,
+            courses_per_subject: 10,
+            instructors_per_subject: 5,
+        }
+    }
+}
+
+/// A generated dataset, ready to [`load`].
+#[derive(Debug, Clone)]
+pub struct FixtureSet {
+    /// Oldest first.
+    pub terms: Vec<BannerTerm>,
+    pub subjects: Vec<Pair>,
+    pub courses: Vec<Course>,
+    pub evaluations: Vec<BlueBookEvaluation>,
+    pub professors: Vec<RmpProfessor>,
+    /// Reviews keyed by RMP legacy ID.
+    pub reviews: Vec<(i32, Vec<RmpReview>)>,
+}
+
+/// Row counts written by [`load`].
+#[allow(dead_code)] // The CLI only logs these; tests read the counts
+#[derive(Debug, Clone, Default)]
+pub struct FixtureStats {
+    pub terms: usize,
+    pub subjects: usize,
+    pub courses: usize,
+    pub evaluations: usize,
+    pub professors: usize,
+    pub reviews: usize,
+}
+
+/// A synthetic instructor, shared between Banner, BlueBook, and RMP data.
+struct FakeInstructor {
+    first: String,
+    last: String,
+    email: String,
+    banner_id: String,
+    subject: usize,
+    /// Latent teaching quality in `[2.0, 5.0]`; RMP and BlueBook ratings
+    /// scatter around it so the two sources agree roughly.
+    quality: f32,
+}
+
+impl FakeInstructor {
+    fn display_name(&self) -> String {
+        format!("{}, {}", self.last, self.first)
+    }
+}
+
+struct CatalogCourse {
+    number: String,
+    title: String,
+}
+
+/// The term immediately before `term` in academic order.
+fn previous_term(term: Term) -> Term {
+    match term.season {
+        Season::Spring => Term {
+            year: term.year - 1,
+            season: Season::Fall,
+        },
+        Season::Summer => Term {
+            year: term.year,
+            season: Season::Spring,
+        },
+        Season::Fall => Term {
+            year: term.year,
+            season: Season::Summer,
+        },
+    }
+}
+
+/// First and last meeting dates (MM/DD/YYYY) for a term.
+fn term_dates(term: Term) -> (String, String) {
+    let year = term.year;
+    match term.season {
+        Season::Spring => (format!("01/20/{year}"), format!("05/13/{year}")),
+        Season::Summer => (format!("06/02/{year}"), format!("08/08/{year}")),
+        Season::Fall => (format!("08/25/{year}"), format!("12/10/{year}")),
+    }
+}
+
+/// ASCII-only lowercase form for synthetic email addresses.
+fn email_part(s: &str) -> String {
+    crate::data::names::normalize_for_matching(s)
+}
+
+fn add_minutes(hhmm: u32, minutes: u32) -> u32 {
+    let total = (hhmm / 100) * 60 + hhmm % 100 + minutes;
+    (total / 60) * 100 + total % 60
+}
+
+fn round_tenth(x: f32) -> f32 {
+    (x * 10.0).round() / 10.0
+}
+
+/// Generate a dataset. Output depends only on `opts`.
+pub fn generate(opts: &FixtureOptions) -> FixtureSet {
+    let mut rng = StdRng::seed_from_u64(opts.seed);
+
+    let mut term_list = vec![opts.latest_term];
+    while term_list.len() < opts.terms.max(1) {
+        let prev = previous_term(*term_list.last().expect("non-empty"));
+        term_list.push(prev);
+    }
+    term_list.reverse();
+
+    let subjects: Vec<Pair> = SUBJECTS
+        .iter()
+        .take(opts.subjects.clamp(1, SUBJECTS.len()))
+        .map(|(code, description)| Pair {
+            code: code.to_string(),
+            description: description.to_string(),
+        })
+        .collect();
+
+    // Instructors: unique (first, last) pairs per subject.
+    let mut instructors: Vec<FakeInstructor> = Vec::new();
+    for (subject_idx, _) in subjects.iter().enumerate() {
+        for _ in 0..opts.instructors_per_subject {
+            let (first, last) = loop {
+                let first = *FIRST_NAMES.choose(&mut rng).expect("non-empty");
+                let last = *LAST_NAMES.choose(&mut rng).expect("non-empty");
+                if !instructors
+                    .iter()
+                    .any(|i| i.first == first && i.last == last)
+                {
+                    break (first, last);
+                }
+            };
+            let id = instructors.len();
+            instructors.push(FakeInstructor {
+                first: first.to_string(),
+                last: last.to_string(),
+                email: format!("{}.{}{id}@utsa.edu", email_part(first), email_part(last)),
+                banner_id: format!("@{:08}", 1_000_000 + id),
+                subject: subject_idx,
+                quality: rng.random_range(2.0..5.0),
+            });
+        }
+    }
+
+    // Catalog: the same course numbers recur every term.
+    let catalogs: Vec<Vec<CatalogCourse>> = subjects
+        .iter()
+        .map(|subject| {
+            let mut numbers: Vec<String> = Vec::new();
+            while numbers.len() < opts.courses_per_subject {
+                let level = rng.random_range(1..=4);
+                let number = format!("{level}{:03}", rng.random_range(0..1000));
+                if !numbers.contains(&number) {
+                    numbers.push(number);
+                }
+            }
+            numbers.sort();
+            numbers
+                .into_iter()
+                .map(|number| {
+                    let pattern = TITLE_PATTERNS.choose(&mut rng).expect("non-empty");
+                    CatalogCourse {
+                        title: pattern.replace("{}", &subject.description),
+                        number,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut courses = Vec::new();
+    let mut evaluations = Vec::new();
+    let latest_code = opts.latest_term.to_string();
+
+    for term in &term_list {
+        let term_code = term.to_string();
+        let term_desc = term.description();
+        let (start_date, end_date) = term_dates(*term);
+        let mut next_crn = 10_000u32;
+
+        for (subject_idx, subject) in subjects.iter().enumerate() {
+            let faculty_pool: Vec<&FakeInstructor> = instructors
+                .iter()
+                .filter(|i| i.subject == subject_idx)
+                .collect();
+
+            for catalog in &catalogs[subject_idx] {
+                let sections = rng.random_range(1..=3);
+                for section in 1..=sections {
+                    let crn = next_crn;
+                    next_crn += rng.random_range(1..20);
+
+                    let instructor = faculty_pool.choose(&mut rng).copied();
+                    // A few current-term sections are still unassigned.
+                    let instructor =
+                        instructor.filter(|_| term_code != latest_code || rng.random_bool(0.9));
+
+                    let max_enrollment = *[20, 30, 35, 45, 60, 120]
+                        .choose(&mut rng)
+                        .expect("non-empty");
+                    let enrollment = rng.random_range(0..=max_enrollment + 3);
+                    let wait_capacity = if max_enrollment >= 45 { 10 } else { 5 };
+                    let wait_count = if enrollment >= max_enrollment {
+                        rng.random_range(0..=wait_capacity)
+                    } else {
+                        0
+                    };
+
+                    let faculty: Vec<FacultyItem> = instructor
+                        .map(|i| FacultyItem {
+                            banner_id: i.banner_id.clone(),
+                            category: Some("01".to_owned()),
+                            class: "net.hedtech.banner.student.faculty.FacultyResultDecorator"
+                                .to_owned(),
+                            course_reference_number: crn,
+                            display_name: Some(i.display_name()),
+                            email_address: Some(i.email.clone()),
+                            primary_indicator: true,
+                            term: term_code.clone(),
+                        })
+                        .into_iter()
+                        .collect();
+
+                    let pattern = *MEETING_PATTERNS.choose(&mut rng).expect("non-empty");
+                    let (method, method_desc) = match pattern {
+                        Some(_) => ("FF", "Face to Face"),
+                        None => ("OA", "Fully Online"),
+                    };
+                    let meeting = MeetingTimeResponse {
+                        category: Some("01".to_owned()),
+                        class: "net.hedtech.banner.general.overall.SectionMeetingTimeDecorator"
+                            .to_owned(),
+                        course_reference_number: crn.to_string(),
+                        faculty: faculty.clone(),
+                        meeting_time: build_meeting_time(
+                            &mut rng,
+                            *term,
+                            crn,
+                            pattern,
+                            &start_date,
+                            &end_date,
+                        ),
+                        term: term_code.clone(),
+                    };
+
+                    courses.push(Course {
+                        id: 0,
+                        term: term_code.clone(),
+                        term_desc: term_desc.clone(),
+                        course_reference_number: crn.to_string(),
+                        part_of_term: "1".to_owned(),
+                        course_number: catalog.number.clone(),
+                        subject: subject.code.clone(),
+                        subject_description: subject.description.clone(),
+                        sequence_number: format!("{section:03}"),
+                        campus_description: "Main Campus".to_owned(),
+                        schedule_type_description: Some("Lecture".to_owned()),
+                        course_title: catalog.title.clone(),
+                        credit_hours: Some(3.0),
+                        maximum_enrollment: max_enrollment,
+                        enrollment,
+                        seats_available: max_enrollment - enrollment,
+                        wait_capacity: Some(wait_capacity),
+                        wait_count: Some(wait_count),
+                        cross_list: None,
+                        cross_list_capacity: None,
+                        cross_list_count: None,
+                        cross_list_available: None,
+                        credit_hour_high: None,
+                        credit_hour_low: Some(3.0),
+                        credit_hour_indicator: None,
+                        open_section: enrollment < max_enrollment,
+                        link_identifier: None,
+                        is_section_linked: false,
+                        subject_course: format!("{}{}", subject.code, catalog.number),
+                        reserved_seat_summary: None,
+                        instructional_method: Some(method.to_owned()),
+                        instructional_method_description: Some(method_desc.to_owned()),
+                        section_attributes: vec![],
+                        faculty,
+                        meetings_faculty: vec![meeting],
+                    });
+
+                    // Evaluations exist only for finished terms.
+                    if let Some(instructor) = instructor
+                        && term_code != latest_code
+                        && rng.random_bool(0.8)
+                    {
+                        let responses = rng.random_range(3..=enrollment.max(3));
+                        let rating =
+                            (instructor.quality + rng.random_range(-0.5..0.5)).clamp(1.0, 5.0);
+                        let course_rating = (rating + rng.random_range(-0.4..0.3)).clamp(1.0, 5.0);
+                        evaluations.push(BlueBookEvaluation {
+                            subject: subject.code.clone(),
+                            course_number: catalog.number.clone(),
+                            section: format!("{section:03}"),
+                            crn: crn.to_string(),
+                            term: term_code.clone(),
+                            instructor_name: instructor.display_name(),
+                            instructor_rating: Some(round_tenth(rating)),
+                            instructor_response_count: Some(responses),
+                            course_rating: Some(round_tenth(course_rating)),
+                            course_response_count: Some(responses),
+                            department: Some(subject.description.clone()),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    // RMP: most instructors have a profile, some under a nickname.
+    let mut professors = Vec::new();
+    let mut reviews = Vec::new();
+    let review_epoch = Utc
+        .with_ymd_and_hms(2020, 1, 1, 0, 0, 0)
+        .single()
+        .expect("valid timestamp");
+
+    for (idx, instructor) in instructors.iter().enumerate() {
+        if !rng.random_bool(0.75) {
+            continue;
+        }
+        let legacy_id = 2_000_000 + idx as i32;
+        let first_name = RMP_SHORT_NAMES
+            .iter()
+            .find(|(formal, _)| *formal == instructor.first)
+            .filter(|_| rng.random_bool(0.5))
+            .map_or(instructor.first.clone(), |(_, short)| short.to_string());
+        let subject = &subjects[instructor.subject];
+
+        let review_count = rng.random_range(0..=15usize);
+        let prof_reviews: Vec<RmpReview> = (0..review_count)
+            .map(|_| {
+                generate_review(
+                    &mut rng,
+                    instructor.quality,
+                    &subject.code,
+                    &catalogs[instructor.subject],
+                    review_epoch,
+                )
+            })
+            .collect();
+
+        let mean = |f: fn(&RmpReview) -> Option<f32>| -> Option<f32> {
+            let values: Vec<f32> = prof_reviews.iter().filter_map(f).collect();
+            (!values.is_empty())
+                .then(|| round_tenth(values.iter().sum::<f32>() / values.len() as f32))
+        };
+        let would_take_again_pct = (!prof_reviews.is_empty()).then(|| {
+            let yes = prof_reviews
+                .iter()
+                .filter(|r| r.would_take_again == Some(1))
+                .count();
+            (yes as f32 / prof_reviews.len() as f32 * 100.0).round()
+        });
+
+        professors.push(RmpProfessor {
+            legacy_id,
+            graphql_id: format!("fixture-teacher-{legacy_id}"),
+            first_name,
+            last_name: instructor.last.clone(),
+            department: Some(subject.description.clone()),
+            avg_rating: mean(|r| r.clarity_rating),
+            avg_difficulty: mean(|r| r.difficulty_rating),
+            num_ratings: Count::new(prof_reviews.len() as u32),
+            would_take_again_pct,
+        });
+        reviews.push((legacy_id, prof_reviews));
+    }
+
+    FixtureSet {
+        terms: term_list
+            .iter()
+            .enumerate()
+            .map(|(i, term)| {
+                // Everything but the two most recent terms is view-only in Banner.
+                let archived = i + 2 < term_list.len();
+                Pair {
+                    code: term.to_string(),
+                    description: if archived {
+                        format!("{} (View Only)", term.description())
+                    } else {
+                        term.description()
+                    },
+                }
+            })
+            .collect(),
+        subjects,
+        courses,
+        evaluations,
+        professors,
+        reviews,
+    }
+}
+
+fn build_meeting_time(
+    rng: &mut StdRng,
+    term: Term,
+    crn: u32,
+    pattern: Option<([bool; 7], u32)>,
+    start_date: &str,
+    end_date: &str,
+) -> MeetingTime {
+    let [
+        monday,
+        tuesday,
+        wednesday,
+        thursday,
+        friday,
+        saturday,
+        sunday,
+    ] = pattern.map_or([false; 7], |(days, _)| days);
+    let times = pattern.map(|(_, minutes)| {
+        let begin = *START_TIMES.choose(rng).expect("non-empty");
+        (
+            format!("{begin:04}"),
+            format!("{:04}", add_minutes(begin, minutes)),
+        )
+    });
+    let location = pattern.map(|_| {
+        let building = BUILDINGS.choose(rng).expect("non-empty").to_string();
+        let room = format!(
+            "{}.{:03}",
+            rng.random_range(1..=4),
+            rng.random_range(100..400)
+        );
+        (building, room)
+    });
+
+    MeetingTime {
+        start_date: start_date.to_owned(),
+        end_date: end_date.to_owned(),
+        begin_time: times.as_ref().map(|(b, _)| b.clone()),
+        end_time: times.map(|(_, e)| e),
+        category: "01".to_owned(),
+        class: "net.hedtech.banner.general.overall.SectionMeetingTime".to_owned(),
+        monday,
+        tuesday,
+        wednesday,
+        thursday,
+        friday,
+        saturday,
+        sunday,
+        room: location.as_ref().map(|(_, r)| r.clone()),
+        term,
+        building: location.as_ref().map(|(b, _)| b.clone()),
+        building_description: location.map(|(b, _)| b),
+        campus: Some("11".to_owned()),
+        campus_description: Some("Main Campus".to_owned()),
+        course_reference_number: crn.to_string(),
+        credit_hour_session: Some(3.0),
+        hours_week: Some(0.0),
+        meeting_schedule_type: if pattern.is_some() { "AFF" } else { "AOA" }.to_owned(),
+        meeting_type: Some(if pattern.is_some() { "FF" } else { "OA" }.to_owned()),
+        meeting_type_description: Some(
+            if pattern.is_some() {
+                "Face to Face"
+            } else {
+                "Fully Online"
+            }
+            .to_owned(),
+        ),
+    }
+}
+
+fn generate_review(
+    rng: &mut StdRng,
+    quality: f32,
+    subject: &str,
+    catalog: &[CatalogCourse],
+    epoch: DateTime<Utc>,
+) -> RmpReview {
+    let clarity = (quality + rng.random_range(-1.0..1.0))
+        .round()
+        .clamp(1.0, 5.0);
+    let difficulty = rng.random_range(1..=5) as f32;
+    let class = catalog
+        .choose(rng)
+        .map(|c| format!("{subject}{}", c.number));
+    let tag_count = rng.random_range(0..=3);
+    let rating_tags = REVIEW_TAGS
+        .choose_multiple(rng, tag_count)
+        .map(|t| t.to_string())
+        .collect();
+
+    RmpReview {
+        comment: Some(REVIEW_COMMENTS.choose(rng).expect("non-empty").to_string()),
+        class,
+        grade: Some(GRADES.choose(rng).expect("non-empty").to_string()),
+        rating_tags,
+        helpful_rating: Some(clarity),
+        clarity_rating: Some(clarity),
+        difficulty_rating: Some(difficulty),
+        would_take_again: Some(i16::from(clarity >= 3.0)),
+        is_for_credit: Some(true),
+        is_for_online_class: Some(rng.random_bool(0.15)),
+        attendance_mandatory: None,
+        flag_status: "UNFLAGGED".to_owned(),
+        textbook_use: None,
+        thumbs_up_total: Count::new(rng.random_range(0..10)),
+        thumbs_down_total: Count::new(rng.random_range(0..4)),
+        posted_at: Some(epoch + Duration::days(rng.random_range(0..2000))),
+    }
+}
+
+/// Write a generated dataset and derive matches and scores from it.
+///
+/// Safe to re-run: every write is an upsert, and RMP reviews are replaced
+/// per professor.
+pub async fn load(db: &DbContext, set: &FixtureSet) -> Result<FixtureStats> {
+    let pool = db.pool();
+
+    crate::data::terms::sync_terms_from_banner(pool, set.terms.clone())
+        .await
+        .context("failed to load fixture terms")?;
+
+    let mut reference: Vec<ReferenceData> = set
+        .terms
+        .iter()
+        .map(|t| ReferenceData {
+            category: "term".to_string(),
+            code: t.code.clone(),
+            description: t.description.clone(),
+        })
+        .collect();
+    reference.extend(set.subjects.iter().map(|s| ReferenceData {
+        category: "subject".to_string(),
+        code: s.code.clone(),
+        description: s.description.clone(),
+    }));
+    crate::data::reference::batch_upsert(pool, &reference).await?;
+    for term in &set.terms {
+        crate::data::term_subjects::cache(&term.code, &set.subjects, pool).await?;
+    }
+
+    for chunk in set.courses.chunks(200) {
+        batch_upsert_courses(chunk, pool)
+            .await
+            .context("failed to load fixture courses")?;
+    }
+
+    batch_upsert_bluebook_evaluations(pool, &set.evaluations).await?;
+
+    crate::data::rmp::batch_upsert_rmp_professors(pool, &set.professors).await?;
+    let mut review_total = 0;
+    for (legacy_id, reviews) in &set.reviews {
+        crate::data::rmp::replace_professor_reviews(pool, *legacy_id, reviews).await?;
+        review_total += reviews.len();
+    }
+    crate::data::rmp::refresh_rmp_summary(pool).await?;
+
+    // Derived data, in the order the scrapers and startup produce it.
+    crate::data::names::backfill_instructor_names(pool).await?;
+    crate::data::instructors::backfill_instructor_slugs(pool).await?;
+    crate::data::rmp_matching::generate_candidates(pool, db.events()).await?;
+    crate::data::admin_bluebook::run_auto_matching(pool).await?;
+    let scored = crate::data::scoring::recompute_all_scores(pool, &Default::default()).await?;
+
+    let stats = FixtureStats {
+        terms: set.terms.len(),
+        subjects: set.subjects.len(),
+        courses: set.courses.len(),
+        evaluations: set.evaluations.len(),
+        professors: set.professors.len(),
+        reviews: review_total,
+    };
+    info!(?stats, scored, "Loaded fixture dataset");
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn opts(seed: u64) -> FixtureOptions {
+        FixtureOptions {
+            seed,
+            latest_term: Term {
+                year: 2026,
+                season: Season::Spring,
+            },
+            ..FixtureOptions::default()
+        }
+    }
+
+    #[test]
+    fn same_seed_produces_identical_data() {
+        let a = generate(&opts(7));
+        let b = generate(&opts(7));
+        assert_eq!(format!("{a:?}"), format!("{b:?}"));
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let a = generate(&opts(1));
+        let b = generate(&opts(2));
+        assert_ne!(format!("{:?}", a.courses), format!("{:?}", b.courses));
+    }
+
+    #[test]
+    fn terms_are_consecutive_and_end_at_latest() {
+        let set = generate(&FixtureOptions {
+            terms: 4,
+            ..opts(1)
+        });
+        let codes: Vec<&str> = set.terms.iter().map(|t| t.code.as_str()).collect();
+        assert_eq!(codes, vec!["202520", "202530", "202610", "202620"]);
+        assert!(set.terms[0].is_archived());
+        assert!(!set.terms[3].is_archived());
+    }
+
+    #[test]
+    fn crns_are_unique_per_term() {
+        let set = generate(&opts(3));
+        let mut seen = HashSet::new();
+        for course in &set.courses {
+            assert!(seen.insert((&course.term, &course.course_reference_number)));
+        }
+    }
+
+    #[test]
+    fn evaluations_skip_latest_term() {
+        let set = generate(&opts(5));
+        assert!(!set.evaluations.is_empty());
+        assert!(set.evaluations.iter().all(|e| e.term != "202620"));
+    }
+
+    #[test]
+    fn add_minutes_carries_hours() {
+        assert_eq!(add_minutes(900, 50), 950);
+        assert_eq!(add_minutes(1130, 75), 1245);
+        assert_eq!(add_minutes(1900, 170), 2150);
+    }
+}
@@ -0,0 +1,282 @@
+//! Database operations for imported grade distribution data.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::data::unsigned::Count;
+
+/// A parsed grade distribution row, one per section (or per course when the
+/// release doesn't break sections out).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GradeDistributionRecord {
+    pub term_code: String,
+    pub subject: String,
+    pub course_number: String,
+    /// Empty when the release aggregates above section level.
+    pub section: String,
+    /// Empty when the release doesn't name instructors.
+    pub instructor_name: String,
+    pub a_count: i32,
+    pub b_count: i32,
+    pub c_count: i32,
+    pub d_count: i32,
+    pub f_count: i32,
+    pub withdrawn_count: i32,
+    pub other_count: i32,
+    /// Students with an A-F grade.
+    pub graded_count: i32,
+    /// Mean grade points over `graded_count`, counting +/- grades.
+    pub avg_gpa: Option<f32>,
+}
+
+/// Aggregated grade distribution for a course or instructor.
+#[derive(Debug, Clone, Serialize, TS, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct GradeDistribution {
+    /// Sections (or course offerings) the counts are summed over.
+    #[schema(value_type = u32)]
+    pub sections: Count,
+    #[schema(value_type = u32)]
+    pub a: Count,
+    #[schema(value_type = u32)]
+    pub b: Count,
+    #[schema(value_type = u32)]
+    pub c: Count,
+    #[schema(value_type = u32)]
+    pub d: Count,
+    #[schema(value_type = u32)]
+    pub f: Count,
+    #[schema(value_type = u32)]
+    pub withdrawn: Count,
+    #[schema(value_type = u32)]
+    pub other: Count,
+    /// Mean GPA across all graded students.
+    pub avg_gpa: Option<f32>,
+}
+
+/// Deduplicate records by the unique constraint key, keeping the last
+/// occurrence so a corrected row later in a file wins.
+fn deduplicate(records: &[GradeDistributionRecord]) -> Vec<&GradeDistributionRecord> {
+    let mut latest: HashMap<(&str, &str, &str, &str, &str), usize> = HashMap::new();
+    for (i, r) in records.iter().enumerate() {
+        let key = (
+            r.term_code.as_str(),
+            r.subject.as_str(),
+            r.course_number.as_str(),
+            r.section.as_str(),
+            r.instructor_name.as_str(),
+        );
+        latest.insert(key, i);
+    }
+    let mut indices: Vec<usize> = latest.into_values().collect();
+    indices.sort_unstable();
+    indices.into_iter().map(|i| &records[i]).collect()
+}
+
+/// Bulk upsert grade distributions using the UNNEST pattern.
+///
+/// Re-importing a release replaces the counts for rows it contains; rows from
+/// other releases are left alone. Returns the number of rows written.
+pub async fn batch_upsert_grade_distributions(
+    pool: &PgPool,
+    records: &[GradeDistributionRecord],
+    source: &str,
+) -> Result<u64> {
+    if records.is_empty() {
+        return Ok(0);
+    }
+
+    let deduped = deduplicate(records);
+
+    let term_codes: Vec<&str> = deduped.iter().map(|r| r.term_code.as_str()).collect();
+    let subjects: Vec<&str> = deduped.iter().map(|r| r.subject.as_str()).collect();
+    let course_numbers: Vec<&str> = deduped.iter().map(|r| r.course_number.as_str()).collect();
+    let sections: Vec<&str> = deduped.iter().map(|r| r.section.as_str()).collect();
+    let instructor_names: Vec<&str> = deduped.iter().map(|r| r.instructor_name.as_str()).collect();
+    let a: Vec<i32> = deduped.iter().map(|r| r.a_count).collect();
+    let b: Vec<i32> = deduped.iter().map(|r| r.b_count).collect();
+    let c: Vec<i32> = deduped.iter().map(|r| r.c_count).collect();
+    let d: Vec<i32> = deduped.iter().map(|r| r.d_count).collect();
+    let f: Vec<i32> = deduped.iter().map(|r| r.f_count).collect();
+    let withdrawn: Vec<i32> = deduped.iter().map(|r| r.withdrawn_count).collect();
+    let other: Vec<i32> = deduped.iter().map(|r| r.other_count).collect();
+    let graded: Vec<i32> = deduped.iter().map(|r| r.graded_count).collect();
+    let avg_gpas: Vec<Option<f32>> = deduped.iter().map(|r| r.avg_gpa).collect();
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO grade_distributions (
+            term_code, subject, course_number, section, instructor_name,
+            a_count, b_count, c_count, d_count, f_count,
+            withdrawn_count, other_count, graded_count, avg_gpa,
+            source, imported_at
+        )
+        SELECT
+            v.term_code, v.subject, v.course_number, v.section, v.instructor_name,
+            v.a_count, v.b_count, v.c_count, v.d_count, v.f_count,
+            v.withdrawn_count, v.other_count, v.graded_count, v.avg_gpa,
+            $15, NOW()
+        FROM UNNEST(
+            $1::text[], $2::text[], $3::text[], $4::text[], $5::text[],
+            $6::int4[], $7::int4[], $8::int4[], $9::int4[], $10::int4[],
+            $11::int4[], $12::int4[], $13::int4[], $14::real[]
+        ) AS v(
+            term_code, subject, course_number, section, instructor_name,
+            a_count, b_count, c_count, d_count, f_count,
+            withdrawn_count, other_count, graded_count, avg_gpa
+        )
+        ON CONFLICT ON CONSTRAINT uq_grade_distribution
+        DO UPDATE SET
+            a_count = EXCLUDED.a_count,
+            b_count = EXCLUDED.b_count,
+            c_count = EXCLUDED.c_count,
+            d_count = EXCLUDED.d_count,
+            f_count = EXCLUDED.f_count,
+            withdrawn_count = EXCLUDED.withdrawn_count,
+            other_count = EXCLUDED.other_count,
+            graded_count = EXCLUDED.graded_count,
+            avg_gpa = EXCLUDED.avg_gpa,
+            source = EXCLUDED.source,
+            imported_at = EXCLUDED.imported_at
+        "#,
+    )
+    .bind(&term_codes)
+    .bind(&subjects)
+    .bind(&course_numbers)
+    .bind(&sections)
+    .bind(&instructor_names)
+    .bind(&a)
+    .bind(&b)
+    .bind(&c)
+    .bind(&d)
+    .bind(&f)
+    .bind(&withdrawn)
+    .bind(&other)
+    .bind(&graded)
+    .bind(&avg_gpas)
+    .bind(source)
+    .execute(pool)
+    .await
+    .context("Failed to batch upsert grade distributions")?;
+
+    Ok(result.rows_affected())
+}
+
+/// Link unlinked grade rows to instructors. Returns the number of rows linked.
+///
+/// Rows with a section take the primary instructor of the matching scraped
+/// section; the rest fall back to an approved BlueBook name link, since both
+/// sources use the registrar's "Last, First" form.
+pub async fn link_instructors(pool: &PgPool) -> Result<u64> {
+    let by_section = sqlx::query(
+        r#"
+        UPDATE grade_distributions gd
+        SET instructor_id = ci.instructor_id
+        FROM courses c
+        JOIN course_instructors ci ON ci.course_id = c.id AND ci.is_primary
+        WHERE gd.instructor_id IS NULL
+          AND gd.section <> ''
+          AND c.term_code = gd.term_code
+          AND c.subject = gd.subject
+          AND c.course_number = gd.course_number
+          AND c.sequence_number = gd.section
+        "#,
+    )
+    .execute(pool)
+    .await
+    .context("Failed to link grade distributions by section")?;
+
+    let by_name = sqlx::query(
+        r#"
+        UPDATE grade_distributions gd
+        SET instructor_id = ibl.instructor_id
+        FROM instructor_bluebook_links ibl
+        WHERE gd.instructor_id IS NULL
+          AND gd.instructor_name <> ''
+          AND ibl.instructor_name = gd.instructor_name
+          AND (ibl.subject IS NULL OR ibl.subject = gd.subject)
+          AND ibl.status IN ('approved', 'auto')
+          AND ibl.instructor_id IS NOT NULL
+        "#,
+    )
+    .execute(pool)
+    .await
+    .context("Failed to link grade distributions by name")?;
+
+    Ok(by_section.rows_affected() + by_name.rows_affected())
+}
+
+const SUMMARY_COLUMNS: &str = r#"
+    COUNT(*)::INTEGER AS sections,
+    SUM(a_count)::INTEGER AS a,
+    SUM(b_count)::INTEGER AS b,
+    SUM(c_count)::INTEGER AS c,
+    SUM(d_count)::INTEGER AS d,
+    SUM(f_count)::INTEGER AS f,
+    SUM(withdrawn_count)::INTEGER AS withdrawn,
+    SUM(other_count)::INTEGER AS other,
+    (SUM(avg_gpa * graded_count) FILTER (WHERE avg_gpa IS NOT NULL)
+        / NULLIF(SUM(graded_count) FILTER (WHERE avg_gpa IS NOT NULL), 0))::REAL AS avg_gpa
+"#;
+
+/// Grade distribution for a course across all imported terms and sections,
+/// including terms taught under a since-renamed subject code.
+pub async fn get_course_grades(
+    pool: &PgPool,
+    subject: &str,
+    course_number: &str,
+) -> Result<Option<GradeDistribution>> {
+    sqlx::query_as::<_, GradeDistribution>(&format!(
+        "SELECT {SUMMARY_COLUMNS} FROM grade_distributions
+         WHERE subject = ANY(expand_subject_codes(ARRAY[$1])) AND course_number = $2
+         HAVING COUNT(*) > 0"
+    ))
+    .bind(subject)
+    .bind(course_number)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch course grade distribution")
+}
+
+/// Grade distribution across all sections linked to an instructor.
+pub async fn get_instructor_grades(
+    pool: &PgPool,
+    instructor_id: i32,
+) -> Result<Option<GradeDistribution>> {
+    sqlx::query_as::<_, GradeDistribution>(&format!(
+        "SELECT {SUMMARY_COLUMNS} FROM grade_distributions
+         WHERE instructor_id = $1
+         HAVING COUNT(*) > 0"
+    ))
+    .bind(instructor_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch instructor grade distribution")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deduplicate_keeps_last_occurrence() {
+        let row = |a_count| GradeDistributionRecord {
+            term_code: "202510".into(),
+            subject: "CS".into(),
+            course_number: "1713".into(),
+            section: "001".into(),
+            a_count,
+            ..Default::default()
+        };
+        let records = vec![row(1), row(2)];
+        let deduped = deduplicate(&records);
+        assert_eq!(deduped.len(), 1);
+        assert_eq!(deduped[0].a_count, 2);
+    }
+}
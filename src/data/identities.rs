@@ -0,0 +1,294 @@
+//! Database operations for OAuth sign-in identities.
+//!
+//! Each row ties one provider account to a user. Users have their own IDs;
+//! a Discord account, whether seen at sign-in or through a bot command, is
+//! resolved to its user here and never used as a user ID.
+//!
+//! Discord DMs are addressed through the user's `discord` identity, since a
+//! user may link or unlink Discord at any time.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+
+use super::models::User;
+use super::users;
+
+pub const DISCORD: &str = "discord";
+
+#[derive(Debug, thiserror::Error)]
+pub enum IdentityError {
+    #[error("this {0} account is already linked to another user")]
+    LinkedElsewhere(String),
+    #[error("a {0} account is already linked; unlink it first")]
+    ProviderTaken(String),
+    #[error("cannot unlink the only sign-in method")]
+    LastIdentity,
+    #[error("no linked {0} account")]
+    NotLinked(String),
+}
+
+/// A provider account as reported at sign-in.
+#[derive(Debug, Clone)]
+pub struct ProviderAccount<'a> {
+    pub provider: &'a str,
+    pub subject: &'a str,
+    pub display_name: &'a str,
+    pub email: Option<&'a str>,
+    /// Discord avatar hash; other providers' pictures aren't stored.
+    pub avatar_hash: Option<&'a str>,
+}
+
+/// A linked sign-in method, as shown to its owner.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UserIdentity {
+    pub provider: String,
+    pub email: Option<String>,
+    pub display_name: Option<String>,
+    pub linked_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+/// Resolve a provider account to its user, creating the user on first sign-in.
+pub async fn sign_in(pool: &PgPool, account: &ProviderAccount<'_>) -> Result<User> {
+    let mut tx = pool.begin().await?;
+
+    // Claim the identity with a fresh user ID in one statement, so two
+    // concurrent first sign-ins agree on the user instead of racing to insert.
+    // The user row follows only if this statement created the identity.
+    sqlx::query("SET CONSTRAINTS user_identities_user_id_fkey DEFERRED")
+        .execute(&mut *tx)
+        .await
+        .context("failed to defer identity foreign key")?;
+    let (user_id, created): (i64, bool) = sqlx::query_as(
+        r#"
+        INSERT INTO user_identities
+            (provider, subject, user_id, email, display_name, last_login_at)
+        VALUES ($1, $2, nextval('users_id_seq'), $3, $4, now())
+        ON CONFLICT (provider, subject) DO UPDATE
+        SET email = EXCLUDED.email,
+            display_name = EXCLUDED.display_name,
+            last_login_at = now()
+        RETURNING user_id, (xmax = 0) AS created
+        "#,
+    )
+    .bind(account.provider)
+    .bind(account.subject)
+    .bind(account.email)
+    .bind(account.display_name)
+    .fetch_one(&mut *tx)
+    .await
+    .context("failed to record identity")?;
+
+    if created {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, discord_username, discord_avatar_hash)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(user_id)
+        .bind(account.display_name)
+        .bind(account.avatar_hash)
+        .execute(&mut *tx)
+        .await
+        .context("failed to create user")?;
+    }
+
+    // Discord still owns the username and avatar of users who first signed
+    // in with it.
+    if account.provider == DISCORD {
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET discord_username = $2, discord_avatar_hash = $3, updated_at = now()
+            WHERE id = $1
+              AND (SELECT provider FROM user_identities
+                   WHERE user_id = $1 ORDER BY linked_at LIMIT 1) = 'discord'
+            "#,
+        )
+        .bind(user_id)
+        .bind(account.display_name)
+        .bind(account.avatar_hash)
+        .execute(&mut *tx)
+        .await
+        .context("failed to refresh Discord profile")?;
+    }
+    let user = users::load_user(&mut *tx, user_id)
+        .await?
+        .context("signed-in user disappeared")?;
+
+    tx.commit().await?;
+    Ok(user)
+}
+
+async fn insert_identity(
+    tx: &mut sqlx::PgConnection,
+    user_id: i64,
+    account: &ProviderAccount<'_>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO user_identities
+            (provider, subject, user_id, email, display_name, last_login_at)
+        VALUES ($1, $2, $3, $4, $5, now())
+        "#,
+    )
+    .bind(account.provider)
+    .bind(account.subject)
+    .bind(user_id)
+    .bind(account.email)
+    .bind(account.display_name)
+    .execute(tx)
+    .await
+    .context("failed to insert identity")?;
+    Ok(())
+}
+
+/// Resolve a Discord account to its user ID outside sign-in (bot commands,
+/// the seed admin), creating a user with that identity on first sight.
+pub async fn ensure_discord_user(pool: &PgPool, discord_id: i64, username: &str) -> Result<i64> {
+    let subject = discord_id.to_string();
+    if let Some(user_id) = find_user(pool, DISCORD, &subject).await? {
+        return Ok(user_id);
+    }
+
+    let mut tx = pool.begin().await?;
+    let (user_id,): (i64,) =
+        sqlx::query_as("INSERT INTO users (discord_username) VALUES ($1) RETURNING id")
+            .bind(username)
+            .fetch_one(&mut *tx)
+            .await
+            .context("failed to create user")?;
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO user_identities (provider, subject, user_id, display_name)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(DISCORD)
+    .bind(&subject)
+    .bind(user_id)
+    .bind(username)
+    .execute(&mut *tx)
+    .await
+    .context("failed to record Discord identity")?;
+    if inserted.rows_affected() == 0 {
+        // Linked concurrently; drop the user created here.
+        tx.rollback().await?;
+        return find_user(pool, DISCORD, &subject)
+            .await?
+            .context("Discord identity disappeared");
+    }
+    tx.commit().await?;
+    Ok(user_id)
+}
+
+async fn find_user(pool: &PgPool, provider: &str, subject: &str) -> Result<Option<i64>> {
+    let user_id: Option<(i64,)> =
+        sqlx::query_as("SELECT user_id FROM user_identities WHERE provider = $1 AND subject = $2")
+            .bind(provider)
+            .bind(subject)
+            .fetch_optional(pool)
+            .await
+            .context("failed to look up identity")?;
+    Ok(user_id.map(|(user_id,)| user_id))
+}
+
+/// Whether the user has a linked Discord account to send DMs to.
+pub async fn has_discord(pool: &PgPool, user_id: i64) -> Result<bool> {
+    let (linked,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM user_identities WHERE user_id = $1 AND provider = $2)",
+    )
+    .bind(user_id)
+    .bind(DISCORD)
+    .fetch_one(pool)
+    .await
+    .context("failed to check for a Discord identity")?;
+    Ok(linked)
+}
+
+/// Link another provider account to an existing user.
+///
+/// Fails with [`IdentityError`] when the account belongs to someone else or
+/// the user already has an account from that provider.
+pub async fn link(pool: &PgPool, user_id: i64, account: &ProviderAccount<'_>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    let owner: Option<(i64,)> =
+        sqlx::query_as("SELECT user_id FROM user_identities WHERE provider = $1 AND subject = $2")
+            .bind(account.provider)
+            .bind(account.subject)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("failed to look up identity")?;
+    match owner {
+        Some((owner,)) if owner == user_id => return Ok(()),
+        Some(_) => return Err(IdentityError::LinkedElsewhere(account.provider.to_owned()).into()),
+        None => {}
+    }
+
+    let (taken,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM user_identities WHERE user_id = $1 AND provider = $2)",
+    )
+    .bind(user_id)
+    .bind(account.provider)
+    .fetch_one(&mut *tx)
+    .await
+    .context("failed to check linked providers")?;
+    if taken {
+        return Err(IdentityError::ProviderTaken(account.provider.to_owned()).into());
+    }
+
+    insert_identity(&mut tx, user_id, account).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Remove a linked provider, keeping at least one way to sign in.
+pub async fn unlink(pool: &PgPool, user_id: i64, provider: &str) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    let providers: Vec<(String,)> =
+        sqlx::query_as("SELECT provider FROM user_identities WHERE user_id = $1 FOR UPDATE")
+            .bind(user_id)
+            .fetch_all(&mut *tx)
+            .await
+            .context("failed to list identities")?;
+    if !providers.iter().any(|(p,)| p == provider) {
+        return Err(IdentityError::NotLinked(provider.to_owned()).into());
+    }
+    if providers.len() == 1 {
+        return Err(IdentityError::LastIdentity.into());
+    }
+
+    sqlx::query("DELETE FROM user_identities WHERE user_id = $1 AND provider = $2")
+        .bind(user_id)
+        .bind(provider)
+        .execute(&mut *tx)
+        .await
+        .context("failed to unlink identity")?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// A user's linked sign-in methods, in the order they were added.
+pub async fn list_for_user(pool: &PgPool, user_id: i64) -> Result<Vec<UserIdentity>> {
+    sqlx::query_as::<_, UserIdentity>(
+        r#"
+        SELECT provider, email, display_name, linked_at, last_login_at
+        FROM user_identities
+        WHERE user_id = $1
+        ORDER BY linked_at
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to list identities")
+}
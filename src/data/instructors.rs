@@ -4,6 +4,7 @@ use anyhow::{Context, Result};
 use serde::Serialize;
 use sqlx::PgPool;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::data::unsigned::Count;
 
@@ -78,9 +79,120 @@ pub async fn backfill_instructor_slugs(pool: &PgPool) -> Result<u64> {
     Ok(count)
 }
 
+/// Slug with its nanoid suffix removed: "doe-jane-x6k" -> "doe-jane".
+fn slug_base(slug: &str) -> &str {
+    slug.rsplit_once('-').map_or(slug, |(base, _)| base)
+}
+
+/// An instructor whose slug was replaced by [`rebuild_instructor_slugs`].
 #[derive(Debug, Clone, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
+pub struct SlugChange {
+    pub instructor_id: i32,
+    pub display_name: String,
+    pub old_slug: Option<String>,
+    pub new_slug: String,
+}
+
+/// Regenerate slugs for instructors flagged `slug_stale` plus any in `ids`.
+///
+/// Instructors whose name still produces the same slug keep it. Replaced
+/// slugs are recorded in `instructor_slug_redirects` so they keep resolving.
+/// Returns the instructors considered and the slugs that changed.
+pub async fn rebuild_instructor_slugs(
+    pool: &PgPool,
+    ids: &[i32],
+) -> Result<(usize, Vec<SlugChange>)> {
+    let mut tx = pool.begin().await?;
+
+    let rows: Vec<(i32, String, Option<String>)> = sqlx::query_as(
+        "SELECT id, display_name, slug FROM instructors \
+         WHERE slug_stale OR id = ANY($1) \
+         ORDER BY id FOR UPDATE",
+    )
+    .bind(ids)
+    .fetch_all(&mut *tx)
+    .await
+    .context("failed to fetch instructors for slug rebuild")?;
+
+    let checked = rows.len();
+    let changes: Vec<SlugChange> = rows
+        .into_iter()
+        .filter_map(|(instructor_id, display_name, old_slug)| {
+            let new_slug = generate_slug(&display_name);
+            if old_slug
+                .as_deref()
+                .is_some_and(|old| slug_base(old) == slug_base(&new_slug))
+            {
+                return None;
+            }
+            Some(SlugChange {
+                instructor_id,
+                display_name,
+                old_slug,
+                new_slug,
+            })
+        })
+        .collect();
+
+    if !changes.is_empty() {
+        let change_ids: Vec<i32> = changes.iter().map(|c| c.instructor_id).collect();
+        let new_slugs: Vec<&str> = changes.iter().map(|c| c.new_slug.as_str()).collect();
+        let (redirect_slugs, redirect_ids): (Vec<&str>, Vec<i32>) = changes
+            .iter()
+            .filter_map(|c| Some((c.old_slug.as_deref()?, c.instructor_id)))
+            .unzip();
+
+        // A fresh slug shadows any redirect that happens to share it
+        sqlx::query("DELETE FROM instructor_slug_redirects WHERE old_slug = ANY($1)")
+            .bind(&new_slugs)
+            .execute(&mut *tx)
+            .await
+            .context("failed to clear shadowed slug redirects")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO instructor_slug_redirects (old_slug, instructor_id)
+            SELECT * FROM UNNEST($1::text[], $2::int[])
+            ON CONFLICT (old_slug) DO UPDATE SET
+                instructor_id = EXCLUDED.instructor_id,
+                created_at = NOW()
+            "#,
+        )
+        .bind(&redirect_slugs)
+        .bind(&redirect_ids)
+        .execute(&mut *tx)
+        .await
+        .context("failed to record slug redirects")?;
+
+        sqlx::query(
+            r#"
+            UPDATE instructors SET slug = data.slug
+            FROM (SELECT UNNEST($1::int[]) AS id, UNNEST($2::text[]) AS slug) data
+            WHERE instructors.id = data.id
+            "#,
+        )
+        .bind(&change_ids)
+        .bind(&new_slugs)
+        .execute(&mut *tx)
+        .await
+        .context("failed to update instructor slugs")?;
+    }
+
+    sqlx::query("UPDATE instructors SET slug_stale = FALSE WHERE slug_stale OR id = ANY($1)")
+        .bind(ids)
+        .execute(&mut *tx)
+        .await
+        .context("failed to clear stale slug flags")?;
+
+    tx.commit().await?;
+    Ok((checked, changes))
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct PublicInstructorListItem {
     pub id: i32,
     pub slug: String,
@@ -92,7 +204,7 @@ pub struct PublicInstructorListItem {
     pub rating: Option<super::course_types::InstructorRating>,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct PublicInstructorListResponse {
@@ -101,9 +213,11 @@ pub struct PublicInstructorListResponse {
     pub total: i64,
     pub page: i32,
     pub per_page: i32,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct PublicInstructorProfile {
@@ -115,11 +229,17 @@ pub struct PublicInstructorProfile {
     pub last_name: Option<String>,
     pub subjects: Vec<String>,
     pub rmp: Option<super::course_types::RmpFull>,
+    /// Rating history and recent drift for the primary RMP profile.
+    pub rmp_trend: Option<super::rmp_history::RmpTrend>,
     pub bluebook: Option<super::course_types::BlueBookFull>,
+    /// Grades across the instructor's sections in imported grade releases.
+    pub grades: Option<super::grades::GradeDistribution>,
     pub rating: Option<super::course_types::InstructorRating>,
+    /// How RMP, BlueBook and the prior combined into `rating`.
+    pub rating_breakdown: Option<super::scoring::RatingBreakdown>,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct TeachingHistoryTerm {
@@ -128,17 +248,18 @@ pub struct TeachingHistoryTerm {
     pub courses: Vec<TeachingHistoryCourse>,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct TeachingHistoryCourse {
     pub subject: String,
     pub course_number: String,
     pub title: String,
+    #[schema(value_type = u32)]
     pub section_count: Count,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct PublicInstructorProfileResponse {
@@ -146,9 +267,10 @@ pub struct PublicInstructorProfileResponse {
     pub teaching_history: Vec<TeachingHistoryTerm>,
 }
 
-#[derive(Debug, serde::Deserialize, Serialize, TS)]
+#[derive(Debug, serde::Deserialize, Serialize, TS, IntoParams)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
+#[into_params(parameter_in = Query)]
 pub struct PublicInstructorListParams {
     pub search: Option<String>,
     pub subject: Option<String>,
@@ -158,6 +280,8 @@ pub struct PublicInstructorListParams {
     pub page: i32,
     #[serde(default = "default_per_page")]
     pub per_page: i32,
+    /// `nextCursor` from a previous page; takes precedence over `page`.
+    pub cursor: Option<String>,
 }
 
 fn default_sort() -> String {
@@ -170,31 +294,53 @@ fn default_per_page() -> i32 {
     24
 }
 
+/// The LIKE pattern prefix for matching `search` against email local parts
+/// (e.g. "jane.doe"), or `None` if it can't be one.
+fn email_prefix(search: &str) -> Option<String> {
+    let local = search.trim().split('@').next().unwrap_or_default();
+    if local.len() < 2 || local.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(super::util::escape_like(&local.to_lowercase()))
+}
+
 /// List instructors for the public directory: paginated, searchable, filterable.
+///
+/// `search` matches display names fuzzily and email local parts by prefix;
+/// name matches sort first. With `cursor` set, the page resumes after the
+/// instructor it names instead of at `page`.
 pub async fn list_public_instructors(
     pool: &PgPool,
     params: &PublicInstructorListParams,
 ) -> Result<PublicInstructorListResponse> {
-    use sqlx::{Postgres, QueryBuilder};
+    use sqlx::{FromRow, Postgres, QueryBuilder};
 
+    use super::cursor::{KeyKind, Keyset};
     use super::scoring::{self, UnratedPolicy};
 
     let page = params.page.max(1);
     let per_page = params.per_page.clamp(1, 100);
     let offset = (page - 1) * per_page;
 
-    // Determine sort clause and any additional filter it requires
+    // Determine the rating sort key and any additional filter it requires
     let mut extra_condition: Option<String> = None;
-    let sort_clause = match params.sort.as_str() {
-        "name_desc" => "i.display_name DESC".to_string(),
-        sort_key if sort_key.starts_with("score_") => {
-            let ascending = sort_key.ends_with("_asc");
-            let (order, filter) = scoring::rating_sort_sql(ascending, UnratedPolicy::AsPrior);
-            extra_condition = filter;
-            order
-        }
-        _ => "i.display_name ASC".to_string(),
-    };
+    let mut score_key: Option<(String, bool)> = None;
+    if params.sort.starts_with("score_") {
+        let ascending = params.sort.ends_with("_asc");
+        let (key, filter) = scoring::rating_sort_sql(ascending, UnratedPolicy::AsPrior);
+        extra_condition = filter;
+        score_key = Some((key, !ascending));
+    }
+    let name_descending = params.sort == "name_desc";
+
+    /// Fuzzy, accent-insensitive match of `search` against the display name.
+    fn push_name_match<'args>(builder: &mut QueryBuilder<'args, Postgres>, search: &'args str) {
+        builder.push("(immutable_unaccent(i.display_name) % immutable_unaccent(");
+        builder.push_bind(search);
+        builder.push(") OR immutable_unaccent(i.display_name) ILIKE '%' || immutable_unaccent(");
+        builder.push_bind(search);
+        builder.push(") || '%')");
+    }
 
     /// Append instructor list WHERE conditions to a QueryBuilder.
     fn push_instructor_conditions<'args>(
@@ -213,12 +359,14 @@ pub async fn list_public_instructors(
         }
 
         if let Some(ref search) = params.search {
-            builder.push(" AND (immutable_unaccent(i.display_name) % immutable_unaccent(");
-            builder.push_bind(search);
-            builder
-                .push(") OR immutable_unaccent(i.display_name) ILIKE '%' || immutable_unaccent(");
-            builder.push_bind(search);
-            builder.push(") || '%')");
+            builder.push(" AND (");
+            push_name_match(builder, search);
+            if let Some(prefix) = email_prefix(search) {
+                builder.push(" OR LOWER(SPLIT_PART(i.email, '@', 1)) LIKE ");
+                builder.push_bind(prefix);
+                builder.push(" || '%'");
+            }
+            builder.push(")");
         }
 
         if let Some(ref subject) = params.subject {
@@ -254,9 +402,11 @@ pub async fn list_public_instructors(
         sc_bb_count: Option<i32>,
     }
 
-    // Data query
+    // Data query: sort keys are selected by an inner query so the keyset
+    // condition and ORDER BY can refer to them by name.
+    let mut keyset = Keyset::new();
     let mut data_builder: QueryBuilder<Postgres> = QueryBuilder::new(
-        "SELECT \
+        "SELECT * FROM (SELECT \
             i.id, i.slug, i.display_name, i.email, \
             COALESCE(\
                 (SELECT array_agg(DISTINCT c.subject ORDER BY c.subject) \
@@ -268,8 +418,28 @@ pub async fn list_public_instructors(
             bb.bb_avg_instructor_rating, bb.bb_total_responses, \
             sc.display_score, sc.sort_score, sc.ci_lower, sc.ci_upper, \
             sc.confidence, sc.source as score_source, \
-            sc.rmp_count as sc_rmp_count, sc.bb_count as sc_bb_count \
-         FROM instructors i \
+            sc.rmp_count as sc_rmp_count, sc.bb_count as sc_bb_count",
+    );
+    if let Some(ref search) = params.search {
+        // Name matches rank above instructors found only by email.
+        keyset.push_key(&mut data_builder, KeyKind::Int, false, |b| {
+            b.push("CASE WHEN ");
+            push_name_match(b, search);
+            b.push(" THEN 0 ELSE 1 END");
+        });
+    }
+    if let Some((ref key, descending)) = score_key {
+        keyset.push_sql_key(&mut data_builder, KeyKind::Float, descending, key);
+    }
+    keyset.push_sql_key(
+        &mut data_builder,
+        KeyKind::Text,
+        name_descending,
+        "i.display_name",
+    );
+    keyset.push_sql_key(&mut data_builder, KeyKind::Int, false, "i.id");
+    data_builder.push(
+        " FROM instructors i \
          LEFT JOIN instructor_rmp_summary rmp ON rmp.instructor_id = i.id \
          LEFT JOIN (\
              SELECT ibl.instructor_id, \
@@ -286,18 +456,39 @@ pub async fn list_public_instructors(
          LEFT JOIN instructor_scores sc ON sc.instructor_id = i.id",
     );
     push_instructor_conditions(&mut data_builder, params, &extra_condition);
-    data_builder.push(" ORDER BY ");
-    data_builder.push(sort_clause.as_str());
+    data_builder.push(") page");
+    if let Some(ref cursor) = params.cursor {
+        data_builder.push(" WHERE ");
+        keyset.push_after(&mut data_builder, cursor)?;
+    }
+    keyset.push_order_by(&mut data_builder);
+    // One extra row tells whether another page follows.
     data_builder.push(" LIMIT ");
-    data_builder.push_bind(per_page);
-    data_builder.push(" OFFSET ");
-    data_builder.push_bind(offset);
+    data_builder.push_bind(per_page + 1);
+    if params.cursor.is_none() {
+        data_builder.push(" OFFSET ");
+        data_builder.push_bind(offset);
+    }
 
-    let rows = data_builder
-        .build_query_as::<Row>()
+    let mut rows = data_builder
+        .build()
         .fetch_all(pool)
         .await
         .context("failed to list public instructors")?;
+    let next_cursor = if rows.len() > per_page as usize {
+        rows.truncate(per_page as usize);
+        rows.last()
+            .map(|row| keyset.cursor_after(row))
+            .transpose()
+            .context("failed to read instructor sort keys")?
+    } else {
+        None
+    };
+    let rows = rows
+        .iter()
+        .map(Row::from_row)
+        .collect::<Result<Vec<_>, _>>()
+        .context("failed to decode public instructors")?;
 
     // Count query
     let mut count_builder: QueryBuilder<Postgres> =
@@ -375,6 +566,7 @@ pub async fn list_public_instructors(
         total,
         page,
         per_page,
+        next_cursor,
     })
 }
 
@@ -453,6 +645,14 @@ pub async fn get_public_instructor_by_slug(
         })
     });
 
+    let rmp_trend = match &rmp_summary {
+        Some(rmp) => {
+            let history = super::rmp_history::get_history(pool, rmp.legacy_id).await?;
+            (!history.is_empty()).then(|| super::rmp_history::trend(history, chrono::Utc::now()))
+        }
+        None => None,
+    };
+
     // BlueBook evaluations
     #[derive(sqlx::FromRow)]
     struct BlueBookRow {
@@ -495,10 +695,19 @@ pub async fn get_public_instructor_by_slug(
         rmp_count: i32,
         bb_count: i32,
         calibrated_bb: Option<f32>,
+        #[sqlx(flatten)]
+        breakdown: super::scoring::RatingBreakdown,
     }
 
     let score_row = sqlx::query_as::<_, DbScoreRow>(
-        "SELECT display_score, sort_score, ci_lower, ci_upper, confidence, source, rmp_count, bb_count, calibrated_bb FROM instructor_scores WHERE instructor_id = $1",
+        r#"
+        SELECT display_score, sort_score, ci_lower, ci_upper, confidence, source,
+               rmp_count, bb_count, calibrated_bb,
+               rmp_weighted, rmp_weighted_count, rmp_share,
+               bb_weighted, bb_weighted_count, bb_share, prior_share, grade_shift
+        FROM instructor_scores
+        WHERE instructor_id = $1
+        "#,
     )
     .bind(inst.id)
     .fetch_optional(pool)
@@ -545,6 +754,8 @@ pub async fn get_public_instructor_by_slug(
         None => None,
     };
 
+    let grades = super::grades::get_instructor_grades(pool, inst.id).await?;
+
     // Teaching history
     let teaching_history = get_teaching_history(pool, inst.id).await?;
 
@@ -558,13 +769,96 @@ pub async fn get_public_instructor_by_slug(
             last_name: inst.last_name,
             subjects: subjects.into_iter().map(|(s,)| s).collect(),
             rmp: rmp_summary,
+            rmp_trend,
             bluebook: bluebook_summary,
+            grades,
             rating,
+            rating_breakdown: score_row.map(|s| s.breakdown),
         },
         teaching_history,
     }))
 }
 
+/// Just enough of a profile to render the embeddable rating widget.
+#[derive(Debug, Clone)]
+pub struct InstructorRatingSummary {
+    pub slug: String,
+    pub display_name: String,
+    pub rating: Option<super::course_types::InstructorRating>,
+}
+
+/// Get an instructor's name and composite rating by slug, skipping the
+/// per-source summaries and teaching history the full profile loads.
+pub async fn get_instructor_rating_by_slug(
+    pool: &PgPool,
+    slug: &str,
+) -> Result<Option<InstructorRatingSummary>> {
+    #[derive(sqlx::FromRow)]
+    struct Row {
+        display_name: String,
+        display_score: Option<f32>,
+        sort_score: Option<f32>,
+        ci_lower: Option<f32>,
+        ci_upper: Option<f32>,
+        confidence: Option<f32>,
+        source: Option<String>,
+        rmp_count: Option<i32>,
+        bb_count: Option<i32>,
+    }
+
+    let row = sqlx::query_as::<_, Row>(
+        r#"
+        SELECT i.display_name,
+               s.display_score, s.sort_score, s.ci_lower, s.ci_upper, s.confidence,
+               s.source, s.rmp_count, s.bb_count
+        FROM instructors i
+        LEFT JOIN instructor_scores s ON s.instructor_id = i.id
+        WHERE i.slug = $1
+        "#,
+    )
+    .bind(slug)
+    .fetch_optional(pool)
+    .await
+    .context("failed to fetch instructor rating")?;
+
+    Ok(row.map(|r| {
+        let rating = match (
+            r.display_score,
+            r.sort_score,
+            r.ci_lower,
+            r.ci_upper,
+            r.confidence,
+            r.source,
+        ) {
+            (
+                Some(display_score),
+                Some(sort_score),
+                Some(ci_lower),
+                Some(ci_upper),
+                Some(confidence),
+                Some(source),
+            ) => Some(super::scoring::build_rating_from_score_row(
+                &super::scoring::ScoreRow {
+                    display_score,
+                    sort_score,
+                    ci_lower,
+                    ci_upper,
+                    confidence,
+                    source,
+                    rmp_count: r.rmp_count.unwrap_or(0),
+                    bb_count: r.bb_count.unwrap_or(0),
+                },
+            )),
+            _ => None,
+        };
+        InstructorRatingSummary {
+            slug: slug.to_owned(),
+            display_name: r.display_name,
+            rating,
+        }
+    }))
+}
+
 /// Get teaching history grouped by term for an instructor.
 async fn get_teaching_history(
     pool: &PgPool,
@@ -758,6 +1052,9 @@ pub fn classify_identifier(s: &str) -> IdentifierKind {
 
 /// Resolve any identifier form to (instructor_id, canonical_slug).
 /// Returns None if not found or if the instructor has no slug yet.
+///
+/// The canonical slug differs from `raw` for numeric IDs, email prefixes,
+/// and retired slugs; callers redirect in that case.
 pub async fn resolve_instructor_identifier(
     pool: &PgPool,
     raw: &str,
@@ -770,10 +1067,18 @@ pub async fn resolve_instructor_identifier(
 
     let row: Option<Row> = match classify_identifier(raw) {
         IdentifierKind::Slug => {
-            sqlx::query_as("SELECT id, slug FROM instructors WHERE slug = $1")
-                .bind(raw)
-                .fetch_optional(pool)
-                .await?
+            // Retired slugs resolve through their redirect to the current one
+            sqlx::query_as(
+                "SELECT id, slug FROM instructors WHERE slug = $1 \
+                 UNION ALL \
+                 SELECT i.id, i.slug FROM instructor_slug_redirects r \
+                 JOIN instructors i ON i.id = r.instructor_id \
+                 WHERE r.old_slug = $1 \
+                 LIMIT 1",
+            )
+            .bind(raw)
+            .fetch_optional(pool)
+            .await?
         }
         IdentifierKind::NumericId(id) => {
             sqlx::query_as("SELECT id, slug FROM instructors WHERE id = $1")
@@ -0,0 +1,84 @@
+//! Database operations for admin-issued IP bans.
+//!
+//! The table is the durable copy; [`IpFilter`](crate::web::ip_filter::IpFilter)
+//! holds the active bans in memory and is reloaded from here on startup.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+
+/// A ban on one address or CIDR range.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct IpBan {
+    pub id: i32,
+    pub cidr: String,
+    pub reason: Option<String>,
+    /// Username of the admin who added the ban, if they still exist.
+    pub created_by: Option<String>,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "string")]
+    pub expires_at: DateTime<Utc>,
+}
+
+const BAN_SELECT: &str = "SELECT b.id, b.cidr, b.reason, u.discord_username AS created_by, \
+            b.created_at, b.expires_at \
+     FROM ip_bans b \
+     LEFT JOIN users u ON u.id = b.created_by";
+
+/// Bans that haven't expired yet, soonest expiry first.
+pub async fn list_active(pool: &PgPool) -> Result<Vec<IpBan>> {
+    sqlx::query_as::<_, IpBan>(&format!(
+        "{BAN_SELECT} WHERE b.expires_at > NOW() ORDER BY b.expires_at, b.id"
+    ))
+    .fetch_all(pool)
+    .await
+    .context("failed to list active IP bans")
+}
+
+/// Record a ban. `cidr` should already be validated and normalized.
+pub async fn insert(
+    pool: &PgPool,
+    cidr: &str,
+    reason: Option<&str>,
+    created_by: i64,
+    expires_at: DateTime<Utc>,
+) -> Result<IpBan> {
+    let (id,): (i32,) = sqlx::query_as(
+        r#"
+        INSERT INTO ip_bans (cidr, reason, created_by, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+    )
+    .bind(cidr)
+    .bind(reason)
+    .bind(created_by)
+    .bind(expires_at)
+    .fetch_one(pool)
+    .await
+    .context("failed to insert IP ban")?;
+
+    sqlx::query_as::<_, IpBan>(&format!("{BAN_SELECT} WHERE b.id = $1"))
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .context("failed to fetch inserted IP ban")
+}
+
+/// Lift a ban early by expiring it now. Returns false if no active ban had that id.
+pub async fn expire(pool: &PgPool, id: i32) -> Result<bool> {
+    let result = sqlx::query(
+        "UPDATE ip_bans SET expires_at = GREATEST(NOW(), created_at + INTERVAL '1 microsecond') \
+         WHERE id = $1 AND expires_at > NOW()",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .context("failed to expire IP ban")?;
+    Ok(result.rows_affected() > 0)
+}
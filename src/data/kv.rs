@@ -35,6 +35,25 @@ pub async fn set(pool: &PgPool, key: &str, value: &str) -> Result<()> {
     Ok(())
 }
 
+/// Remove a key, if present.
+pub async fn delete(pool: &PgPool, key: &str) -> Result<()> {
+    sqlx::query("DELETE FROM app_kv WHERE key = $1")
+        .bind(key)
+        .execute(pool)
+        .await
+        .context("failed to delete kv value")?;
+    Ok(())
+}
+
+/// List all key-value pairs whose key starts with `prefix`, ordered by key.
+pub async fn list_prefix(pool: &PgPool, prefix: &str) -> Result<Vec<(String, String)>> {
+    sqlx::query_as("SELECT key, value FROM app_kv WHERE starts_with(key, $1) ORDER BY key")
+        .bind(prefix)
+        .fetch_all(pool)
+        .await
+        .context("failed to list kv values")
+}
+
 /// Retrieve a persisted UTC timestamp, or `None` if absent or unparseable.
 pub async fn get_timestamp(pool: &PgPool, key: &str) -> Result<Option<DateTime<Utc>>> {
     let value = get(pool, key).await?;
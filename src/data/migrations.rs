@@ -0,0 +1,385 @@
+//! Embedded schema migrations and drift detection.
+//!
+//! The SQL files under `migrations/` are compiled into the binary, so a
+//! deployed build always carries the exact schema history it was tested with.
+//! Startup checks two things, failing with a report of every problem found:
+//!
+//! - Before applying anything, the history in `_sqlx_migrations` is compared
+//!   against the embedded files, catching edited, unknown, or failed
+//!   migrations.
+//! - After migrating, the live catalog (tables, columns, constraints, indexes,
+//!   triggers and functions in `public`) is compared against
+//!   [`EXPECTED_SCHEMA`], a snapshot of what the embedded migrations produce.
+//!   This catches schema changed by hand, which the history can't show.
+//!
+//! The snapshot is kept current by the `db_schema_snapshot` test; run it with
+//! `UPDATE_SCHEMA_SNAPSHOT=1` after adding a migration.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use anyhow::{Context, Result, bail};
+use sqlx::PgPool;
+use sqlx::migrate::{Migration, Migrator};
+use tracing::info;
+
+/// Migrations embedded from `./migrations` at compile time.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// The catalog the embedded migrations produce, one [`schema_snapshot`] line
+/// per object.
+pub const EXPECTED_SCHEMA: &str = include_str!("schema.snapshot");
+
+/// Canonical description of every schema object in `public`, one sorted line
+/// each, leaving out the migration history and objects owned by extensions.
+const SCHEMA_SNAPSHOT_SQL: &str = r#"
+    WITH rels AS (
+        SELECT c.oid, c.relname, c.relkind
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = 'public'
+          AND c.relkind IN ('r', 'p', 'v', 'm')
+          AND c.relname <> '_sqlx_migrations'
+          AND NOT EXISTS (
+              SELECT 1 FROM pg_depend d
+              WHERE d.classid = 'pg_class'::regclass AND d.objid = c.oid AND d.deptype = 'e'
+          )
+    )
+    SELECT line FROM (
+        SELECT format('relation %s %s', r.relkind, r.relname) AS line
+        FROM rels r
+        UNION ALL
+        SELECT format(
+            'column %s.%s %s%s%s',
+            r.relname,
+            a.attname,
+            format_type(a.atttypid, a.atttypmod),
+            CASE WHEN a.attnotnull THEN ' not null' ELSE '' END,
+            COALESCE(' default ' || pg_get_expr(ad.adbin, ad.adrelid), '')
+        )
+        FROM rels r
+        JOIN pg_attribute a ON a.attrelid = r.oid AND a.attnum > 0 AND NOT a.attisdropped
+        LEFT JOIN pg_attrdef ad ON ad.adrelid = a.attrelid AND ad.adnum = a.attnum
+        UNION ALL
+        SELECT format('constraint %s.%s %s', r.relname, con.conname, pg_get_constraintdef(con.oid))
+        FROM rels r
+        JOIN pg_constraint con ON con.conrelid = r.oid
+        UNION ALL
+        SELECT format('index %s', pg_get_indexdef(i.indexrelid))
+        FROM rels r
+        JOIN pg_index i ON i.indrelid = r.oid
+        UNION ALL
+        SELECT format('trigger %s', pg_get_triggerdef(t.oid))
+        FROM rels r
+        JOIN pg_trigger t ON t.tgrelid = r.oid AND NOT t.tgisinternal
+        UNION ALL
+        SELECT format('view %s %s', r.relname, md5(pg_get_viewdef(r.oid)))
+        FROM rels r
+        WHERE r.relkind IN ('v', 'm')
+        UNION ALL
+        SELECT format(
+            'function %s(%s) %s',
+            p.proname,
+            pg_get_function_identity_arguments(p.oid),
+            md5(pg_get_functiondef(p.oid))
+        )
+        FROM pg_proc p
+        JOIN pg_namespace n ON n.oid = p.pronamespace
+        WHERE n.nspname = 'public'
+          AND p.prokind IN ('f', 'p')
+          AND NOT EXISTS (
+              SELECT 1 FROM pg_depend d
+              WHERE d.classid = 'pg_proc'::regclass AND d.objid = p.oid AND d.deptype = 'e'
+          )
+    ) objects
+    ORDER BY line COLLATE "C"
+"#;
+
+/// A row from `_sqlx_migrations`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub description: String,
+    pub checksum: Vec<u8>,
+    pub success: bool,
+}
+
+/// A way the database's migration history disagrees with the binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationDrift {
+    /// Applied, but the embedded SQL no longer has the recorded checksum.
+    Modified { version: i64, description: String },
+    /// Applied, but unknown to this binary (newer build, or a deleted file).
+    Unknown { version: i64, description: String },
+    /// Recorded as started but never completed.
+    Failed { version: i64, description: String },
+}
+
+impl fmt::Display for MigrationDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Modified {
+                version,
+                description,
+            } => write!(
+                f,
+                "{version} ({description}): checksum differs from the applied migration"
+            ),
+            Self::Unknown {
+                version,
+                description,
+            } => write!(
+                f,
+                "{version} ({description}): applied but not embedded in this build"
+            ),
+            Self::Failed {
+                version,
+                description,
+            } => write!(
+                f,
+                "{version} ({description}): previously failed and left the schema dirty"
+            ),
+        }
+    }
+}
+
+/// How the live schema differs from [`EXPECTED_SCHEMA`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDrift {
+    /// Expected objects missing or changed in the database.
+    pub missing: Vec<String>,
+    /// Objects in the database the migrations didn't create as they are.
+    pub unexpected: Vec<String>,
+}
+
+impl SchemaDrift {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.missing {
+            writeln!(f, "  - expected: {line}")?;
+        }
+        for line in &self.unexpected {
+            writeln!(f, "  - found:    {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare a live [`schema_snapshot`] against the expected one. A changed
+/// object shows up on both sides.
+pub fn find_schema_drift(expected: &str, live: &[String]) -> SchemaDrift {
+    let expected: BTreeSet<&str> = expected.lines().filter(|l| !l.is_empty()).collect();
+    let live: BTreeSet<&str> = live.iter().map(String::as_str).collect();
+    SchemaDrift {
+        missing: expected.difference(&live).map(|l| l.to_string()).collect(),
+        unexpected: live.difference(&expected).map(|l| l.to_string()).collect(),
+    }
+}
+
+/// Describe the live schema, in the format of [`EXPECTED_SCHEMA`].
+pub async fn schema_snapshot(pool: &PgPool) -> Result<Vec<String>> {
+    sqlx::query_scalar(SCHEMA_SNAPSHOT_SQL)
+        .fetch_all(pool)
+        .await
+        .context("failed to read the schema catalog")
+}
+
+/// Fail if the migrated schema differs from what the migrations produce.
+pub async fn verify_schema(pool: &PgPool) -> Result<()> {
+    let live = schema_snapshot(pool).await?;
+    let drift = find_schema_drift(EXPECTED_SCHEMA, &live);
+    if drift.is_empty() {
+        info!(objects = live.len(), "Database schema matches migrations");
+        return Ok(());
+    }
+    bail!(
+        "database schema has drifted from its migrations ({} missing or changed, {} unexpected):\n{drift}\
+         Revert the manual change, or capture it in a migration.",
+        drift.missing.len(),
+        drift.unexpected.len()
+    )
+}
+
+/// Compare applied migrations against the embedded set.
+///
+/// Embedded migrations that have not been applied yet are not drift; they are
+/// simply pending.
+pub fn find_drift<'a>(
+    embedded: impl IntoIterator<Item = &'a Migration>,
+    applied: &[AppliedMigration],
+) -> Vec<MigrationDrift> {
+    let embedded: Vec<&Migration> = embedded
+        .into_iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .collect();
+
+    applied
+        .iter()
+        .filter_map(|row| {
+            let version = row.version;
+            let description = row.description.clone();
+            if !row.success {
+                return Some(MigrationDrift::Failed {
+                    version,
+                    description,
+                });
+            }
+            match embedded.iter().find(|m| m.version == version) {
+                None => Some(MigrationDrift::Unknown {
+                    version,
+                    description,
+                }),
+                Some(m) if *m.checksum != *row.checksum => Some(MigrationDrift::Modified {
+                    version,
+                    description,
+                }),
+                Some(_) => None,
+            }
+        })
+        .collect()
+}
+
+/// Load the applied migration history, or nothing on a fresh database.
+async fn applied_migrations(pool: &PgPool) -> Result<Vec<AppliedMigration>> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT to_regclass('public._sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await
+            .context("failed to check for the migrations table")?;
+    if !exists {
+        return Ok(Vec::new());
+    }
+
+    sqlx::query_as::<_, AppliedMigration>(
+        "SELECT version, description, checksum, success FROM _sqlx_migrations ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await
+    .context("failed to load applied migrations")
+}
+
+/// Fail if the database's migration history has drifted from this build.
+pub async fn verify(pool: &PgPool) -> Result<()> {
+    let applied = applied_migrations(pool).await?;
+    let drift = find_drift(MIGRATOR.iter(), &applied);
+    if drift.is_empty() {
+        info!(
+            applied = applied.len(),
+            embedded = MIGRATOR.iter().count(),
+            "Migration checksums verified"
+        );
+        return Ok(());
+    }
+
+    let report = drift
+        .iter()
+        .map(|d| format!("  - {d}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    bail!(
+        "database migration history has drifted from this build ({} problem(s)):\n{report}\n\
+         Restore the original migration files or repair _sqlx_migrations before starting.",
+        drift.len()
+    )
+}
+
+/// Verify the applied history, apply any pending migrations, then verify
+/// the resulting schema.
+pub async fn run(pool: &PgPool) -> Result<()> {
+    verify(pool).await?;
+    MIGRATOR
+        .run(pool)
+        .await
+        .context("failed to run database migrations")?;
+    verify_schema(pool).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::migrate::MigrationType;
+    use std::borrow::Cow;
+
+    fn embedded(version: i64, sql: &'static str) -> Migration {
+        Migration::new(
+            version,
+            Cow::Borrowed("test"),
+            MigrationType::Simple,
+            Cow::Borrowed(sql),
+            false,
+        )
+    }
+
+    fn applied(m: &Migration) -> AppliedMigration {
+        AppliedMigration {
+            version: m.version,
+            description: m.description.to_string(),
+            checksum: m.checksum.to_vec(),
+            success: true,
+        }
+    }
+
+    #[test]
+    fn matching_and_pending_migrations_are_clean() {
+        let files = [
+            embedded(1, "CREATE TABLE a ()"),
+            embedded(2, "CREATE TABLE b ()"),
+        ];
+        assert!(find_drift(&files, &[applied(&files[0])]).is_empty());
+        assert!(find_drift(&files, &[]).is_empty());
+    }
+
+    #[test]
+    fn schema_drift_lists_both_sides_of_a_change() {
+        let expected =
+            "column t.a integer not null\nindex CREATE INDEX i ON public.t USING btree (a)\n";
+        let live = vec![
+            "column t.a bigint not null".to_owned(),
+            "index CREATE INDEX i ON public.t USING btree (a)".to_owned(),
+        ];
+        let drift = find_schema_drift(expected, &live);
+        assert_eq!(drift.missing, ["column t.a integer not null"]);
+        assert_eq!(drift.unexpected, ["column t.a bigint not null"]);
+        assert!(
+            find_schema_drift(expected, &live[1..])
+                .unexpected
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn reports_every_kind_of_drift() {
+        let files = [
+            embedded(1, "CREATE TABLE a ()"),
+            embedded(2, "CREATE TABLE b ()"),
+        ];
+        let edited = embedded(1, "CREATE TABLE a (id int)");
+        let mut failed = applied(&files[1]);
+        failed.success = false;
+        let unknown = applied(&embedded(3, "CREATE TABLE c ()"));
+
+        let drift = find_drift(&files, &[applied(&edited), failed, unknown]);
+        assert_eq!(
+            drift,
+            vec![
+                MigrationDrift::Modified {
+                    version: 1,
+                    description: "test".into()
+                },
+                MigrationDrift::Failed {
+                    version: 2,
+                    description: "test".into()
+                },
+                MigrationDrift::Unknown {
+                    version: 3,
+                    description: "test".into()
+                },
+            ]
+        );
+    }
+}
@@ -1,33 +1,62 @@
 //! Database models and schema.
 
+pub mod admin_actions;
 pub mod admin_bluebook;
 pub mod admin_rmp;
 pub mod admin_scraper;
+pub mod api_keys;
 pub mod audit;
 pub mod batch;
 pub mod bluebook;
 mod context;
+pub mod course_details;
+pub mod course_history;
 pub mod course_types;
 pub mod courses;
+pub mod cursor;
 pub mod events;
+pub mod feedback;
+pub mod fixtures;
+pub mod grades;
 pub mod health;
+pub mod identities;
 pub mod instructors;
+pub mod ip_bans;
 pub mod kv;
 pub mod metrics;
+pub mod migrations;
 pub mod models;
 pub mod names;
+pub mod nicknames;
+pub mod outbox;
+pub mod planner;
+pub mod recommendations;
 pub mod reference;
 pub mod reference_types;
+pub mod reminders;
 pub mod rmp;
+pub mod rmp_history;
 pub mod rmp_matching;
+pub mod rooms;
+pub mod saved_searches;
 pub mod scoring;
+pub mod scrape_history;
 pub mod scrape_jobs;
 pub mod scraper_stats;
+pub mod search;
+pub mod search_index;
 pub mod sessions;
+pub mod sparklines;
+pub mod subject_aliases;
+pub mod subjects;
+pub mod term_changes;
 pub mod term_subjects;
 pub mod terms;
+pub mod titles;
 pub mod unsigned;
 pub mod users;
+pub mod util;
 pub mod watches;
+pub mod webhooks;
 
 pub use context::DbContext;
@@ -1,6 +1,6 @@
 //! `sqlx` models for the database schema.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::str::FromStr;
 
@@ -8,18 +8,22 @@ use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 use crate::banner::models::meetings::TimeRange;
 use crate::data::course_types::{DateRange, MeetingLocation};
 use crate::data::unsigned::Count;
 
 /// Serialize an `i64` as a string to avoid JavaScript precision loss for values exceeding 2^53.
-fn serialize_i64_as_string<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+pub(crate) fn serialize_i64_as_string<S: Serializer>(
+    value: &i64,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
     serializer.serialize_str(&value.to_string())
 }
 
 /// Deserialize an `i64` from either a number or a string.
-fn deserialize_i64_from_string<'de, D: Deserializer<'de>>(
+pub(crate) fn deserialize_i64_from_string<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> Result<i64, D::Error> {
     use serde::de;
@@ -49,8 +53,31 @@ fn deserialize_i64_from_string<'de, D: Deserializer<'de>>(
     deserializer.deserialize_any(I64OrStringVisitor)
 }
 
+/// [`serialize_i64_as_string`] for an optional value.
+pub(crate) fn serialize_opt_i64_as_string<S: Serializer>(
+    value: &Option<i64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serialize_i64_as_string(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// [`deserialize_i64_from_string`] for an optional value.
+pub(crate) fn deserialize_opt_i64_from_string<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<i64>, D::Error> {
+    #[derive(Deserialize)]
+    struct Id(#[serde(deserialize_with = "deserialize_i64_from_string")] i64);
+
+    Ok(Option::<Id>::deserialize(deserializer)?.map(|Id(value)| value))
+}
+
 /// Day of the week.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TS)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TS, ToSchema,
+)]
 #[serde(rename_all = "lowercase")]
 #[ts(export)]
 pub enum DayOfWeek {
@@ -64,7 +91,7 @@ pub enum DayOfWeek {
 }
 
 /// Represents a meeting time stored as JSONB in the courses table.
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct DbMeetingTime {
@@ -300,6 +327,8 @@ pub struct Course {
     pub cross_list_count: Option<i32>,
     pub link_identifier: Option<String>,
     pub is_section_linked: Option<bool>,
+    /// When the detail enrichment job last fetched restrictions/coreqs/linked sections.
+    pub details_scraped_at: Option<DateTime<Utc>>,
     // JSONB fields
     pub meeting_times: Value,
     pub attributes: Value,
@@ -441,6 +470,30 @@ pub struct UpsertCounts {
     pub courses_unchanged: Count,
     pub audits_generated: Count,
     pub metrics_generated: Count,
+    /// Course-level diff; only set by course batch upserts.
+    pub diff: Option<ScrapeDiffSummary>,
+}
+
+/// Compact summary of what a subject scrape changed, stored on its result row.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ScrapeDiffSummary {
+    /// Courses stored for the first time.
+    pub added: Count,
+    /// Previously stored courses missing from this fetch.
+    pub removed: Count,
+    /// Existing courses with at least one field change.
+    pub changed: Count,
+    /// Number of changes per field (e.g. `"enrollment"`, `"instructors"`).
+    pub fields: BTreeMap<String, Count>,
+}
+
+impl ScrapeDiffSummary {
+    /// True when the run left every course as it was.
+    pub fn is_noop(&self) -> bool {
+        self.added.get() == 0 && self.removed.get() == 0 && self.changed.get() == 0
+    }
 }
 
 /// The priority level of a scrape job.
@@ -467,6 +520,30 @@ pub enum TargetType {
     SingleCrn,
 }
 
+impl TargetType {
+    pub const ALL: [TargetType; 4] = [
+        Self::Subject,
+        Self::CourseRange,
+        Self::CrnList,
+        Self::SingleCrn,
+    ];
+}
+
+impl FromStr for TargetType {
+    type Err = anyhow::Error;
+
+    /// Parses the kebab-case names used in configuration, e.g. `crn-list`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "subject" => Ok(Self::Subject),
+            "course-range" => Ok(Self::CourseRange),
+            "crn-list" => Ok(Self::CrnList),
+            "single-crn" => Ok(Self::SingleCrn),
+            other => Err(anyhow::anyhow!("unknown TargetType: {other:?}")),
+        }
+    }
+}
+
 /// Computed status for a scrape job, derived from existing fields.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -475,6 +552,8 @@ pub enum ScrapeJobStatus {
     Processing,
     StaleLock,
     Exhausted,
+    /// Parked after exhausting retries or failing unrecoverably; waits for an admin.
+    DeadLetter,
     Scheduled,
     Pending,
 }
@@ -500,12 +579,23 @@ pub struct ScrapeJob {
     /// When the job last entered the "ready to pick up" state.
     /// Set to NOW() on creation; updated to NOW() on retry.
     pub queued_at: DateTime<Utc>,
+    /// Set when the job was moved to the dead-letter queue instead of retried.
+    pub dead_lettered_at: Option<DateTime<Utc>>,
+    /// Error from the most recent failed attempt.
+    pub last_error: Option<String>,
+    /// Diff from the most recent completed run with the same payload.
+    /// Only populated by queries that join `scrape_job_results`.
+    #[sqlx(default)]
+    pub last_diff: Option<sqlx::types::Json<ScrapeDiffSummary>>,
 }
 
 impl ScrapeJob {
     /// Compute the current status of this job from its fields.
     pub fn status(&self) -> ScrapeJobStatus {
         let now = Utc::now();
+        if self.dead_lettered_at.is_some() {
+            return ScrapeJobStatus::DeadLetter;
+        }
         match self.locked_at {
             Some(locked) if (now - locked).num_seconds() < LOCK_EXPIRY_SECS => {
                 ScrapeJobStatus::Processing
@@ -520,7 +610,7 @@ impl ScrapeJob {
     }
 }
 
-/// A user authenticated via Discord OAuth.
+/// A user, signed in through one or more OAuth providers.
 #[derive(sqlx::FromRow, Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
@@ -530,14 +620,34 @@ pub struct User {
         deserialize_with = "deserialize_i64_from_string"
     )]
     #[ts(type = "string")]
-    pub discord_id: i64,
+    pub id: i64,
+    /// Snowflake of the linked Discord account, if any.
+    #[serde(
+        serialize_with = "serialize_opt_i64_as_string",
+        deserialize_with = "deserialize_opt_i64_from_string"
+    )]
+    #[ts(type = "string | null")]
+    pub discord_id: Option<i64>,
     pub discord_username: String,
     pub discord_avatar_hash: Option<String>,
     pub is_admin: bool,
+    /// IANA timezone used when rendering times for this user.
+    pub timezone: String,
+    pub time_format: TimeFormat,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Clock style for rendered times.
+#[derive(sqlx::Type, Copy, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "time_format", rename_all = "PascalCase")]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum TimeFormat {
+    TwelveHour,
+    TwentyFourHour,
+}
+
 /// A server-side session for an authenticated user.
 #[allow(dead_code)] // Fields read via sqlx::FromRow; some only used in DB queries
 #[derive(sqlx::FromRow, Debug, Clone)]
@@ -5,10 +5,13 @@
 //! from both sources (HTML entities, accents, nicknames, suffixes, junk).
 
 use std::collections::HashSet;
+use std::sync::LazyLock;
 
 use anyhow::Context;
+use serde::Serialize;
 use sqlx::PgPool;
 use tracing::{info, warn};
+use ts_rs::TS;
 use unicode_normalization::UnicodeNormalization;
 
 /// Known name suffixes to extract from the last-name portion.
@@ -19,7 +22,11 @@ const SUFFIXES: &[&str] = &["iv", "iii", "ii", "jr", "sr"];
 /// Each group is a set of equivalent names. When a first name matches any
 /// entry in a group, all other entries become nickname-derived matching keys.
 /// Ordered roughly by frequency at UTSA.
-const NICKNAME_GROUPS: &[&[&str]] = &[
+///
+/// This is the built-in default used by [`matching_keys`]. The
+/// `nickname_groups` table is seeded from it and is what RMP candidate
+/// generation reads, so admins can extend or correct it at runtime.
+pub const NICKNAME_GROUPS: &[&[&str]] = &[
     &["christopher", "chris"],
     &["kimberly", "kim"],
     &["matthew", "matt"],
@@ -344,21 +351,72 @@ pub struct MatchingKey {
     pub origin: KeyOrigin,
 }
 
-/// Look up common nickname expansions for a normalized first-name token.
-///
-/// Returns all other names in the same nickname group (not the input itself).
-fn nickname_expansions(normalized_first: &str) -> Vec<String> {
-    let mut results = Vec::new();
-    for group in NICKNAME_GROUPS {
-        if group.contains(&normalized_first) {
-            for &name in *group {
-                if name != normalized_first {
-                    results.push(name.to_string());
+/// Nickname-equivalence groups used to expand first names into extra keys.
+#[derive(Debug, Clone, Default)]
+pub struct NicknameDictionary {
+    groups: Vec<Vec<String>>,
+}
+
+static BUILTIN_NICKNAMES: LazyLock<NicknameDictionary> = LazyLock::new(|| {
+    NicknameDictionary::from_groups(
+        NICKNAME_GROUPS
+            .iter()
+            .map(|group| group.iter().map(|name| name.to_string()).collect()),
+    )
+});
+
+impl NicknameDictionary {
+    /// Build a dictionary from raw groups, normalizing every name with
+    /// [`normalize_for_matching`] and dropping groups left with fewer than two
+    /// distinct names.
+    pub fn from_groups(groups: impl IntoIterator<Item = Vec<String>>) -> Self {
+        let groups = groups
+            .into_iter()
+            .filter_map(|group| {
+                let mut names: Vec<String> = Vec::with_capacity(group.len());
+                for name in group {
+                    let norm = normalize_for_matching(&name);
+                    if !norm.is_empty() && !names.contains(&norm) {
+                        names.push(norm);
+                    }
+                }
+                (names.len() >= 2).then_some(names)
+            })
+            .collect();
+        Self { groups }
+    }
+
+    /// The compiled-in [`NICKNAME_GROUPS`].
+    pub fn builtin() -> &'static Self {
+        &BUILTIN_NICKNAMES
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    #[allow(dead_code)] // Pairs with `len`
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Look up nickname expansions for a normalized first-name token.
+    ///
+    /// Returns all other names in every group containing the token (not the
+    /// input itself).
+    pub fn expansions(&self, normalized_first: &str) -> Vec<String> {
+        let mut results = Vec::new();
+        for group in &self.groups {
+            if group.iter().any(|name| name == normalized_first) {
+                for name in group {
+                    if name != normalized_first {
+                        results.push(name.clone());
+                    }
                 }
             }
         }
+        results
     }
-    results
 }
 
 /// Generate all matching index keys for a parsed name.
@@ -374,7 +432,15 @@ fn nickname_expansions(normalized_first: &str) -> Vec<String> {
 /// - `("burchenal", "will")` -- common nickname expansion (Nickname)
 /// - `("burchenal", "bill")` -- common nickname expansion (Nickname)
 /// - `("burchenal", "billy")` -- common nickname expansion (Nickname)
+///
+/// Expansions come from the built-in [`NICKNAME_GROUPS`]; use
+/// [`matching_keys_with`] to supply a database-backed dictionary.
 pub fn matching_keys(parts: &NameParts) -> Vec<MatchingKey> {
+    matching_keys_with(parts, NicknameDictionary::builtin())
+}
+
+/// [`matching_keys`] with nickname expansions drawn from `nicknames`.
+pub fn matching_keys_with(parts: &NameParts, nicknames: &NicknameDictionary) -> Vec<MatchingKey> {
     let norm_last = normalize_for_matching(&parts.last);
     if norm_last.is_empty() {
         return Vec::new();
@@ -439,7 +505,7 @@ pub fn matching_keys(parts: &NameParts) -> Vec<MatchingKey> {
         if token.is_empty() {
             continue;
         }
-        for expansion in nickname_expansions(token) {
+        for expansion in nicknames.expansions(token) {
             if seen.insert(expansion.clone()) {
                 keys.push(MatchingKey {
                     last: norm_last.clone(),
@@ -454,7 +520,9 @@ pub fn matching_keys(parts: &NameParts) -> Vec<MatchingKey> {
 }
 
 /// How well two parsed names match each other.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub enum NameMatchQuality {
     /// No matching keys at all.
     None,
@@ -980,6 +1048,39 @@ mod tests {
         assert!(has_nickname_key(&keys, "packham", "chris"));
     }
 
+    #[test]
+    fn keys_with_custom_dictionary() {
+        let dict = NicknameDictionary::from_groups([vec!["Guillermo".into(), "Memo".into()]]);
+        let parts = parse_banner_name("Garza, Guillermo").unwrap();
+        let keys = matching_keys_with(&parts, &dict);
+        assert!(has_primary_key(&keys, "garza", "guillermo"));
+        assert!(has_nickname_key(&keys, "garza", "memo"));
+
+        // Built-in groups are not consulted when a dictionary is supplied.
+        let parts = parse_banner_name("Smith, William").unwrap();
+        let keys = matching_keys_with(&parts, &dict);
+        assert!(!has_nickname_key(&keys, "smith", "bill"));
+    }
+
+    #[test]
+    fn dictionary_normalizes_and_drops_degenerate_groups() {
+        let dict = NicknameDictionary::from_groups([
+            vec!["José".into(), "Pepe".into(), "jose".into()],
+            vec!["Solo".into(), "SOLO".into()],
+            vec!["".into(), "Ana".into()],
+        ]);
+        assert_eq!(dict.len(), 1);
+        assert_eq!(dict.expansions("jose"), vec!["pepe".to_string()]);
+        assert!(dict.expansions("solo").is_empty());
+    }
+
+    #[test]
+    fn builtin_dictionary_dedupes_repeated_names() {
+        // "dorothy" is listed twice in its group; it must not expand to itself.
+        let expansions = NicknameDictionary::builtin().expansions("dorothy");
+        assert_eq!(expansions, vec!["dot".to_string()]);
+    }
+
     #[test]
     fn keys_nickname_expansion_from_short_name() {
         // RMP professor "Chris" should generate "christopher" as a nickname key
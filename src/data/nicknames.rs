@@ -0,0 +1,161 @@
+//! Database operations for admin-managed nickname equivalence groups.
+//!
+//! Groups are stored normalized (see [`normalize_for_matching`]) so the
+//! admin view shows exactly what candidate generation will match on.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+
+use crate::data::names::{NicknameDictionary, normalize_for_matching};
+
+/// Domain errors for nickname group management.
+///
+/// The web layer downcasts `anyhow::Error` to this type to pick the HTTP status.
+#[derive(Debug, thiserror::Error)]
+pub enum NicknameError {
+    #[error("nickname group not found")]
+    NotFound,
+    #[error("a nickname group needs at least two distinct names")]
+    TooFewNames,
+}
+
+/// A nickname equivalence group row.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct NicknameGroup {
+    pub id: i32,
+    /// Normalized, interchangeable first names.
+    pub names: Vec<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Normalize and dedupe admin-supplied names, preserving input order.
+pub fn normalize_names(names: &[String]) -> Result<Vec<String>, NicknameError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(names.len());
+    for name in names {
+        let norm = normalize_for_matching(name);
+        if !norm.is_empty() && !normalized.contains(&norm) {
+            normalized.push(norm);
+        }
+    }
+    if normalized.len() < 2 {
+        return Err(NicknameError::TooFewNames);
+    }
+    Ok(normalized)
+}
+
+/// List all groups in creation order.
+pub async fn list_groups(pool: &PgPool) -> Result<Vec<NicknameGroup>> {
+    sqlx::query_as::<_, NicknameGroup>(
+        "SELECT id, names, note, created_at, updated_at FROM nickname_groups ORDER BY id",
+    )
+    .fetch_all(pool)
+    .await
+    .context("failed to list nickname groups")
+}
+
+/// Load every group into a [`NicknameDictionary`] for name matching.
+pub async fn load_dictionary(pool: &PgPool) -> Result<NicknameDictionary> {
+    let rows: Vec<(Vec<String>,)> = sqlx::query_as("SELECT names FROM nickname_groups")
+        .fetch_all(pool)
+        .await
+        .context("failed to load nickname groups")?;
+    Ok(NicknameDictionary::from_groups(
+        rows.into_iter().map(|(names,)| names),
+    ))
+}
+
+/// Insert a new group.
+pub async fn create_group(
+    pool: &PgPool,
+    names: &[String],
+    note: Option<&str>,
+) -> Result<NicknameGroup> {
+    let names = normalize_names(names)?;
+
+    sqlx::query_as::<_, NicknameGroup>(
+        r#"
+        INSERT INTO nickname_groups (names, note)
+        VALUES ($1, $2)
+        RETURNING id, names, note, created_at, updated_at
+        "#,
+    )
+    .bind(&names)
+    .bind(note)
+    .fetch_one(pool)
+    .await
+    .context("failed to insert nickname group")
+}
+
+/// Replace the names and note of an existing group.
+pub async fn update_group(
+    pool: &PgPool,
+    id: i32,
+    names: &[String],
+    note: Option<&str>,
+) -> Result<NicknameGroup> {
+    let names = normalize_names(names)?;
+
+    let updated = sqlx::query_as::<_, NicknameGroup>(
+        r#"
+        UPDATE nickname_groups
+        SET names = $2, note = $3, updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, names, note, created_at, updated_at
+        "#,
+    )
+    .bind(id)
+    .bind(&names)
+    .bind(note)
+    .fetch_optional(pool)
+    .await
+    .context("failed to update nickname group")?;
+
+    updated.ok_or_else(|| NicknameError::NotFound.into())
+}
+
+/// Delete a group.
+pub async fn delete_group(pool: &PgPool, id: i32) -> Result<()> {
+    let result = sqlx::query("DELETE FROM nickname_groups WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("failed to delete nickname group")?;
+
+    if result.rows_affected() == 0 {
+        return Err(NicknameError::NotFound.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_names_lowercases_and_dedupes() {
+        let names = vec!["Margaret".into(), "Peggy".into(), " peggy ".into()];
+        assert_eq!(normalize_names(&names).unwrap(), vec!["margaret", "peggy"]);
+    }
+
+    #[test]
+    fn normalize_names_strips_accents_and_punctuation() {
+        let names = vec!["José".into(), "Pepe!".into()];
+        assert_eq!(normalize_names(&names).unwrap(), vec!["jose", "pepe"]);
+    }
+
+    #[test]
+    fn normalize_names_rejects_single_name() {
+        let names = vec!["Bill".into(), "BILL".into(), "  ".into()];
+        assert!(matches!(
+            normalize_names(&names),
+            Err(NicknameError::TooFewNames)
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+//! Transactional outbox for outbound notifications.
+//!
+//! Producers call [`enqueue`] inside the same transaction as the change that
+//! warrants a message, so a message exists if and only if the change
+//! committed. The outbox dispatcher service claims due rows under a lease,
+//! delivers them, and records the outcome. A dispatcher that dies mid-send
+//! leaves its lease to expire and the message is retried, so delivery is
+//! at-least-once; receivers that care can dedupe on the message id.
+//!
+//! Each claim's `locked_until` doubles as its lease token: outcomes are only
+//! recorded while the row still carries it, so a dispatcher whose lease
+//! lapsed can't overwrite the state left by the one that reclaimed it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::{PgExecutor, PgPool};
+use std::time::Duration;
+
+use crate::data::events::CourseEvent;
+
+/// A Discord embed, stored as plain data so it survives a restart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiscordEmbed {
+    pub title: String,
+    pub description: String,
+    pub color: u32,
+    pub url: Option<String>,
+    /// `(name, value, inline)`
+    pub fields: Vec<(String, String, bool)>,
+}
+
+/// What to deliver, and where.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutboxPayload {
+    DiscordDm {
+        user_id: u64,
+        embed: DiscordEmbed,
+    },
+    Webhook {
+        url: String,
+        body: serde_json::Value,
+    },
+    Email {
+        to: String,
+        subject: String,
+        text: String,
+    },
+    /// An event for a registered endpoint (see [`crate::data::webhooks`]).
+    /// The URL and signing secret are looked up at delivery time, so edits
+    /// and deletions apply to queued messages.
+    WebhookSubscription {
+        webhook_id: i32,
+        event: String,
+        body: serde_json::Value,
+    },
+    /// Course changes a write just committed, queued in that write's
+    /// transaction. Delivering it means fanning the changes out to watch
+    /// alerts and webhook deliveries, which are enqueued in turn.
+    CourseChanges {
+        events: Vec<CourseEvent>,
+    },
+}
+
+impl OutboxPayload {
+    pub fn channel(&self) -> &'static str {
+        match self {
+            Self::DiscordDm { .. } => "discord_dm",
+            Self::Webhook { .. } => "webhook",
+            Self::Email { .. } => "email",
+            Self::WebhookSubscription { .. } => "webhook_subscription",
+            Self::CourseChanges { .. } => "course_changes",
+        }
+    }
+}
+
+/// A message to enqueue.
+#[derive(Debug, Clone)]
+pub struct NewOutboxMessage {
+    pub payload: OutboxPayload,
+    /// Drops the message if one with the same key was already enqueued.
+    pub dedupe_key: Option<String>,
+}
+
+/// A claimed message, leased to the caller until delivery is recorded.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OutboxMessage {
+    pub id: i64,
+    pub payload: Json<OutboxPayload>,
+    /// Attempts including the current one.
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+    /// When the lease expires; pass it back when recording the outcome.
+    pub locked_until: DateTime<Utc>,
+}
+
+/// Insert messages using `executor`, which should be the producer's transaction.
+///
+/// Returns the number of messages actually enqueued (after deduplication).
+pub async fn enqueue<'e>(
+    executor: impl PgExecutor<'e>,
+    messages: &[NewOutboxMessage],
+) -> Result<u64> {
+    if messages.is_empty() {
+        return Ok(0);
+    }
+    let channels: Vec<&str> = messages.iter().map(|m| m.payload.channel()).collect();
+    let payloads: Vec<serde_json::Value> = messages
+        .iter()
+        .map(|m| serde_json::to_value(&m.payload))
+        .collect::<Result<_, _>>()
+        .context("failed to serialize outbox payload")?;
+    let dedupe_keys: Vec<Option<&str>> = messages.iter().map(|m| m.dedupe_key.as_deref()).collect();
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO notification_outbox (channel, payload, dedupe_key)
+        SELECT * FROM UNNEST($1::text[], $2::jsonb[], $3::text[])
+        ON CONFLICT (dedupe_key) DO NOTHING
+        "#,
+    )
+    .bind(&channels)
+    .bind(&payloads)
+    .bind(&dedupe_keys)
+    .execute(executor)
+    .await
+    .context("failed to enqueue outbox messages")?;
+    Ok(result.rows_affected())
+}
+
+/// Queue `events` for watch and webhook fan-out. Call it in the
+/// transaction that recorded them; does nothing when there are none.
+pub async fn enqueue_course_changes<'e>(
+    executor: impl PgExecutor<'e>,
+    events: &[CourseEvent],
+) -> Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let message = NewOutboxMessage {
+        payload: OutboxPayload::CourseChanges {
+            events: events.to_vec(),
+        },
+        dedupe_key: None,
+    };
+    enqueue(executor, &[message]).await.map(drop)
+}
+
+/// Claim up to `limit` due messages, leasing them for `lease`.
+///
+/// Rows locked by a concurrent claim are skipped, so multiple dispatchers can
+/// run safely.
+pub async fn claim_due(pool: &PgPool, limit: i64, lease: Duration) -> Result<Vec<OutboxMessage>> {
+    sqlx::query_as::<_, OutboxMessage>(
+        r#"
+        UPDATE notification_outbox
+        SET locked_until = NOW() + make_interval(secs => $2),
+            attempts = attempts + 1
+        WHERE id IN (
+            SELECT id FROM notification_outbox
+            WHERE status = 'pending'
+              AND next_attempt_at <= NOW()
+              AND (locked_until IS NULL OR locked_until < NOW())
+            ORDER BY next_attempt_at
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, payload, attempts, created_at, locked_until
+        "#,
+    )
+    .bind(limit)
+    .bind(lease.as_secs_f64())
+    .fetch_all(pool)
+    .await
+    .context("failed to claim outbox messages")
+}
+
+/// Record a successful delivery under the lease from [`claim_due`].
+///
+/// Returns `false` when the lease was lost and nothing was recorded.
+pub async fn mark_delivered<'e>(
+    executor: impl PgExecutor<'e>,
+    id: i64,
+    lease: DateTime<Utc>,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        UPDATE notification_outbox
+        SET status = 'delivered', delivered_at = NOW(), locked_until = NULL, last_error = NULL
+        WHERE id = $1 AND status = 'pending' AND locked_until = $2
+        "#,
+    )
+    .bind(id)
+    .bind(lease)
+    .execute(executor)
+    .await
+    .context("failed to mark outbox message delivered")?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Record a failed attempt under the lease from [`claim_due`]. With
+/// `retry_at` the message is rescheduled; without it the message is
+/// dead-lettered.
+///
+/// Returns `false` when the lease was lost and nothing was recorded.
+pub async fn mark_failed(
+    pool: &PgPool,
+    id: i64,
+    lease: DateTime<Utc>,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        UPDATE notification_outbox
+        SET status = CASE WHEN $4::timestamptz IS NULL THEN 'dead' ELSE 'pending' END,
+            next_attempt_at = COALESCE($4, next_attempt_at),
+            locked_until = NULL,
+            last_error = $3
+        WHERE id = $1 AND status = 'pending' AND locked_until = $2
+        "#,
+    )
+    .bind(id)
+    .bind(lease)
+    .bind(error)
+    .bind(retry_at)
+    .execute(pool)
+    .await
+    .context("failed to record outbox failure")?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Delete delivered messages older than `retention`. Dead letters are kept
+/// for inspection. Returns the number of rows deleted.
+pub async fn prune_delivered(pool: &PgPool, retention: Duration) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM notification_outbox
+        WHERE status = 'delivered'
+          AND delivered_at < NOW() - make_interval(secs => $1)
+        "#,
+    )
+    .bind(retention.as_secs_f64())
+    .execute(pool)
+    .await
+    .context("failed to prune outbox")?;
+    Ok(result.rows_affected())
+}
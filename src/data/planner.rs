@@ -0,0 +1,84 @@
+//! Signed-in users' planned sections, one CRN list per term.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// The sections a user has planned for one term.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserSchedule {
+    pub term_code: String,
+    pub crns: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Every term a user has planned, most recently updated first.
+pub async fn list_schedules(pool: &PgPool, user_id: i64) -> Result<Vec<UserSchedule>> {
+    sqlx::query_as::<_, UserSchedule>(
+        r#"
+        SELECT term_code, crns, updated_at
+        FROM user_schedules
+        WHERE user_id = $1
+        ORDER BY updated_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to list user schedules")
+}
+
+/// A user's plan for one term, if they have one.
+pub async fn get_schedule(
+    pool: &PgPool,
+    user_id: i64,
+    term_code: &str,
+) -> Result<Option<UserSchedule>> {
+    sqlx::query_as::<_, UserSchedule>(
+        r#"
+        SELECT term_code, crns, updated_at
+        FROM user_schedules
+        WHERE user_id = $1 AND term_code = $2
+        "#,
+    )
+    .bind(user_id)
+    .bind(term_code)
+    .fetch_optional(pool)
+    .await
+    .context("failed to fetch user schedule")
+}
+
+/// Replace a user's plan for one term.
+pub async fn replace_schedule(
+    pool: &PgPool,
+    user_id: i64,
+    term_code: &str,
+    crns: &[String],
+) -> Result<UserSchedule> {
+    sqlx::query_as::<_, UserSchedule>(
+        r#"
+        INSERT INTO user_schedules (user_id, term_code, crns)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, term_code)
+        DO UPDATE SET crns = EXCLUDED.crns, updated_at = NOW()
+        RETURNING term_code, crns, updated_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(term_code)
+    .bind(crns)
+    .fetch_one(pool)
+    .await
+    .context("failed to replace user schedule")
+}
+
+/// Delete a user's plan for one term. Returns true if there was one.
+pub async fn delete_schedule(pool: &PgPool, user_id: i64, term_code: &str) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM user_schedules WHERE user_id = $1 AND term_code = $2")
+        .bind(user_id)
+        .bind(term_code)
+        .execute(pool)
+        .await
+        .context("failed to delete user schedule")?;
+    Ok(result.rows_affected() > 0)
+}
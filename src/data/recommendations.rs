@@ -0,0 +1,216 @@
+//! Related-instructor recommendations.
+//!
+//! Relatedness is computed offline by the scheduler (see
+//! [`recompute_related`]) and stored in `instructor_related`, so profile
+//! pages only do an indexed lookup. Candidates are instructors who teach in at
+//! least one of the same subjects; they are ranked by subject overlap, sections
+//! taught together, and how close their composite ratings are.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use super::course_types::InstructorRating;
+use super::scoring::{ScoreRow, build_rating_from_score_row};
+
+/// Related instructors kept per instructor.
+pub const RELATED_PER_INSTRUCTOR: i64 = 12;
+
+/// Weight of the Jaccard overlap of the two instructors' subject sets.
+const SUBJECT_OVERLAP_WEIGHT: f64 = 1.0;
+/// Weight of `ln(1 + sections taught together)`.
+const CO_TAUGHT_WEIGHT: f64 = 0.5;
+/// Weight of rating similarity (1 for identical scores, 0 for opposite ends
+/// of the 1-5 scale). Unrated pairs contribute nothing.
+const RATING_SIMILARITY_WEIGHT: f64 = 0.3;
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RelatedInstructor {
+    pub id: i32,
+    pub slug: String,
+    pub display_name: String,
+    /// Subjects both instructors have taught.
+    pub shared_subjects: Vec<String>,
+    /// Sections the two instructors were both listed on.
+    pub co_taught_sections: i32,
+    /// Blended relatedness; only meaningful for ordering.
+    pub score: f32,
+    pub rating: Option<InstructorRating>,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RelatedInstructorsResponse {
+    pub instructors: Vec<RelatedInstructor>,
+}
+
+/// Rebuild `instructor_related` for every instructor.
+///
+/// Replaces the whole table in one transaction so readers never see a
+/// half-built ranking. Returns the number of pairs written.
+pub async fn recompute_related(pool: &PgPool) -> Result<u64> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM instructor_related")
+        .execute(&mut *tx)
+        .await
+        .context("failed to clear related instructors")?;
+
+    let written = sqlx::query(
+        r#"
+        WITH subjects AS (
+            SELECT DISTINCT ci.instructor_id, c.subject
+            FROM course_instructors ci
+            JOIN courses c ON c.id = ci.course_id
+        ),
+        subject_counts AS (
+            SELECT instructor_id, COUNT(*) AS n FROM subjects GROUP BY instructor_id
+        ),
+        shared AS (
+            SELECT a.instructor_id, b.instructor_id AS related_id,
+                   array_agg(a.subject ORDER BY a.subject) AS shared_subjects
+            FROM subjects a
+            JOIN subjects b ON b.subject = a.subject AND b.instructor_id <> a.instructor_id
+            GROUP BY a.instructor_id, b.instructor_id
+        ),
+        co_taught AS (
+            SELECT a.instructor_id, b.instructor_id AS related_id, COUNT(*) AS sections
+            FROM course_instructors a
+            JOIN course_instructors b ON b.course_id = a.course_id AND b.instructor_id <> a.instructor_id
+            GROUP BY a.instructor_id, b.instructor_id
+        ),
+        signals AS (
+            SELECT s.instructor_id, s.related_id, s.shared_subjects,
+                   COALESCE(ct.sections, 0)::int AS co_taught_sections,
+                   cardinality(s.shared_subjects)::float8
+                       / (ca.n + cb.n - cardinality(s.shared_subjects)) AS subject_overlap,
+                   GREATEST(0, 1 - ABS(sa.display_score - sb.display_score) / 4.0)::float8
+                       AS rating_similarity
+            FROM shared s
+            JOIN subject_counts ca ON ca.instructor_id = s.instructor_id
+            JOIN subject_counts cb ON cb.instructor_id = s.related_id
+            LEFT JOIN co_taught ct
+                ON ct.instructor_id = s.instructor_id AND ct.related_id = s.related_id
+            LEFT JOIN instructor_scores sa ON sa.instructor_id = s.instructor_id
+            LEFT JOIN instructor_scores sb ON sb.instructor_id = s.related_id
+        ),
+        scored AS (
+            SELECT *,
+                   $1 * subject_overlap
+                       + $2 * LN(1 + co_taught_sections::float8)
+                       + $3 * COALESCE(rating_similarity, 0) AS score
+            FROM signals
+        ),
+        ranked AS (
+            SELECT *,
+                   ROW_NUMBER() OVER (
+                       PARTITION BY instructor_id ORDER BY score DESC, related_id
+                   ) AS rank
+            FROM scored
+        )
+        INSERT INTO instructor_related (
+            instructor_id, related_id, rank, score, shared_subjects,
+            co_taught_sections, rating_similarity
+        )
+        SELECT instructor_id, related_id, rank, score, shared_subjects,
+               co_taught_sections, rating_similarity
+        FROM ranked
+        WHERE rank <= $4
+        "#,
+    )
+    .bind(SUBJECT_OVERLAP_WEIGHT)
+    .bind(CO_TAUGHT_WEIGHT)
+    .bind(RATING_SIMILARITY_WEIGHT)
+    .bind(RELATED_PER_INSTRUCTOR)
+    .execute(&mut *tx)
+    .await
+    .context("failed to compute related instructors")?
+    .rows_affected();
+
+    tx.commit().await?;
+    Ok(written)
+}
+
+/// Related instructors for one instructor, best first.
+pub async fn get_related(
+    pool: &PgPool,
+    instructor_id: i32,
+    limit: i64,
+) -> Result<Vec<RelatedInstructor>> {
+    #[derive(sqlx::FromRow)]
+    struct Row {
+        id: i32,
+        slug: String,
+        display_name: String,
+        shared_subjects: Vec<String>,
+        co_taught_sections: i32,
+        score: f32,
+        display_score: Option<f32>,
+        sort_score: Option<f32>,
+        ci_lower: Option<f32>,
+        ci_upper: Option<f32>,
+        confidence: Option<f32>,
+        score_source: Option<String>,
+        sc_rmp_count: Option<i32>,
+        sc_bb_count: Option<i32>,
+    }
+
+    let rows = sqlx::query_as::<_, Row>(
+        "SELECT i.id, i.slug, i.display_name, r.shared_subjects, r.co_taught_sections, r.score, \
+                sc.display_score, sc.sort_score, sc.ci_lower, sc.ci_upper, sc.confidence, \
+                sc.source AS score_source, sc.rmp_count AS sc_rmp_count, sc.bb_count AS sc_bb_count \
+         FROM instructor_related r \
+         JOIN instructors i ON i.id = r.related_id \
+         LEFT JOIN instructor_scores sc ON sc.instructor_id = i.id \
+         WHERE r.instructor_id = $1 AND i.slug IS NOT NULL \
+         ORDER BY r.rank \
+         LIMIT $2",
+    )
+    .bind(instructor_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch related instructors")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let rating = match (
+                r.display_score,
+                r.sort_score,
+                r.ci_lower,
+                r.ci_upper,
+                r.confidence,
+                r.score_source,
+            ) {
+                (Some(ds), Some(ss), Some(cl), Some(cu), Some(conf), Some(src)) => {
+                    Some(build_rating_from_score_row(&ScoreRow {
+                        display_score: ds,
+                        sort_score: ss,
+                        ci_lower: cl,
+                        ci_upper: cu,
+                        confidence: conf,
+                        source: src,
+                        rmp_count: r.sc_rmp_count.unwrap_or(0),
+                        bb_count: r.sc_bb_count.unwrap_or(0),
+                    }))
+                }
+                _ => None,
+            };
+            RelatedInstructor {
+                id: r.id,
+                slug: r.slug,
+                display_name: r.display_name,
+                shared_subjects: r.shared_subjects,
+                co_taught_sections: r.co_taught_sections,
+                score: r.score,
+                rating,
+            }
+        })
+        .collect())
+}
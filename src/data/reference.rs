@@ -48,6 +48,18 @@ pub async fn get_by_category(pool: &PgPool, category: &str) -> Result<Vec<Refere
     Ok(rows)
 }
 
+/// Whether `code` exists in `category`.
+pub async fn has_code(pool: &PgPool, category: &str, code: &str) -> Result<bool> {
+    sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM reference_data WHERE category = $1 AND code = $2)",
+    )
+    .bind(category)
+    .bind(code)
+    .fetch_one(pool)
+    .await
+    .context("failed to look up reference data code")
+}
+
 /// Get all reference data entries (for cache initialization).
 pub async fn get_all(pool: &PgPool) -> Result<Vec<ReferenceData>> {
     let rows = sqlx::query_as::<_, ReferenceData>(
@@ -20,6 +20,7 @@ use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 /// Parse failure for Tier 1 enums that reject unknown codes.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -52,7 +53,7 @@ macro_rules! tier2_enum {
     ) => {
         $(#[$meta])*
         // Deliberately omits Deserialize -- see module docs.
-        #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, TS)]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, TS, ToSchema)]
         #[serde(tag = "type")]
         #[ts(export)]
         $vis enum $name {
@@ -143,7 +144,7 @@ macro_rules! tier2_enum {
 
 /// How a course section is delivered.
 // Deliberately omits Deserialize -- see module docs.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, TS, ToSchema)]
 #[serde(tag = "type", content = "variant")]
 #[ts(export)]
 pub enum InstructionalMethod {
@@ -154,7 +155,7 @@ pub enum InstructionalMethod {
 }
 
 /// Online delivery sub-variants.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, TS, ToSchema)]
 #[ts(export)]
 pub enum OnlineVariant {
     /// OA -- fully online, no scheduled meetings.
@@ -166,7 +167,7 @@ pub enum OnlineVariant {
 }
 
 /// Hybrid delivery sub-variants indicating in-person meeting frequency.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, TS, ToSchema)]
 #[ts(export)]
 pub enum HybridVariant {
     /// HB -- 1 of 2 meeting days in person.
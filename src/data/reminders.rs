@@ -0,0 +1,273 @@
+//! Registration reminders: DM a user when registration opens for their
+//! classification in a term.
+//!
+//! Opening times come from the academic calendar and are entered by admins as
+//! `registration_windows`; users subscribe with `/remindme`. The notification
+//! service polls [`find_due_reminders`] and queues one DM per reminder.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool};
+use std::fmt;
+use std::str::FromStr;
+use ts_rs::TS;
+
+/// Student classification, which determines when registration opens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum Classification {
+    Graduate,
+    Senior,
+    Junior,
+    Sophomore,
+    Freshman,
+    /// Open registration for all students.
+    Open,
+}
+
+impl Classification {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Graduate => "graduate",
+            Self::Senior => "senior",
+            Self::Junior => "junior",
+            Self::Sophomore => "sophomore",
+            Self::Freshman => "freshman",
+            Self::Open => "open",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Graduate => "Graduate",
+            Self::Senior => "Senior",
+            Self::Junior => "Junior",
+            Self::Sophomore => "Sophomore",
+            Self::Freshman => "Freshman",
+            Self::Open => "Open Registration",
+        }
+    }
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Classification {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "graduate" => Ok(Self::Graduate),
+            "senior" => Ok(Self::Senior),
+            "junior" => Ok(Self::Junior),
+            "sophomore" => Ok(Self::Sophomore),
+            "freshman" => Ok(Self::Freshman),
+            "open" => Ok(Self::Open),
+            _ => Err(anyhow::anyhow!("unknown classification: {}", s)),
+        }
+    }
+}
+
+/// When registration opens for one classification in a term.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RegistrationWindow {
+    #[ts(as = "Classification")]
+    pub classification: String,
+    #[ts(type = "string")]
+    pub opens_at: DateTime<Utc>,
+}
+
+/// A user's reminder, with the opening time if the calendar has one yet.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReminderListItem {
+    pub term_code: String,
+    pub term_description: String,
+    pub classification: String,
+    pub opens_at: Option<DateTime<Utc>>,
+    pub notified_at: Option<DateTime<Utc>>,
+}
+
+/// A reminder whose registration window has opened.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DueReminder {
+    pub reminder_id: i32,
+    pub user_id: i64,
+    /// The subscriber's linked Discord account, if any.
+    pub discord_id: Option<i64>,
+    pub term_code: String,
+    pub term_description: String,
+    pub classification: String,
+    pub opens_at: DateTime<Utc>,
+}
+
+/// Registration windows for a term, earliest first.
+pub async fn list_windows(pool: &PgPool, term_code: &str) -> Result<Vec<RegistrationWindow>> {
+    sqlx::query_as::<_, RegistrationWindow>(
+        r#"
+        SELECT classification, opens_at
+        FROM registration_windows
+        WHERE term_code = $1
+        ORDER BY opens_at, classification
+        "#,
+    )
+    .bind(term_code)
+    .fetch_all(pool)
+    .await
+    .context("failed to list registration windows")
+}
+
+/// Replace a term's registration windows.
+///
+/// Reminders that were already sent stay sent even if their window moves.
+pub async fn replace_windows(
+    pool: &PgPool,
+    term_code: &str,
+    windows: &[(Classification, DateTime<Utc>)],
+) -> Result<()> {
+    let classifications: Vec<&str> = windows.iter().map(|(c, _)| c.as_str()).collect();
+    let opens_at: Vec<DateTime<Utc>> = windows.iter().map(|(_, at)| *at).collect();
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM registration_windows WHERE term_code = $1")
+        .bind(term_code)
+        .execute(&mut *tx)
+        .await
+        .context("failed to clear registration windows")?;
+    sqlx::query(
+        r#"
+        INSERT INTO registration_windows (term_code, classification, opens_at)
+        SELECT $1, * FROM UNNEST($2::text[], $3::timestamptz[])
+        "#,
+    )
+    .bind(term_code)
+    .bind(&classifications)
+    .bind(&opens_at)
+    .execute(&mut *tx)
+    .await
+    .context("failed to insert registration windows")?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Create or re-arm a reminder. Returns true if newly created.
+///
+/// Re-arming a reminder that was already sent clears `notified_at`, so it
+/// fires again if the window is moved later.
+pub async fn upsert_reminder(
+    pool: &PgPool,
+    user_id: i64,
+    term_code: &str,
+    classification: Classification,
+) -> Result<bool> {
+    // xmax = 0 means the row was just inserted; non-zero means it was updated.
+    let (is_new,): (bool,) = sqlx::query_as(
+        r#"
+        INSERT INTO registration_reminders (user_id, term_code, classification)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, term_code, classification) DO UPDATE
+            SET notified_at = NULL
+        RETURNING (xmax::text::bigint = 0) AS is_new
+        "#,
+    )
+    .bind(user_id)
+    .bind(term_code)
+    .bind(classification.as_str())
+    .fetch_one(pool)
+    .await
+    .context("failed to upsert registration reminder")?;
+    Ok(is_new)
+}
+
+/// Delete a user's reminders for a term. Returns the number removed.
+pub async fn delete_reminders(pool: &PgPool, user_id: i64, term_code: &str) -> Result<u64> {
+    let result =
+        sqlx::query("DELETE FROM registration_reminders WHERE user_id = $1 AND term_code = $2")
+            .bind(user_id)
+            .bind(term_code)
+            .execute(pool)
+            .await
+            .context("failed to delete registration reminders")?;
+    Ok(result.rows_affected())
+}
+
+/// A user's reminders, newest term first.
+pub async fn list_reminders(pool: &PgPool, user_id: i64) -> Result<Vec<ReminderListItem>> {
+    sqlx::query_as::<_, ReminderListItem>(
+        r#"
+        SELECT r.term_code, t.description AS term_description, r.classification,
+               w.opens_at, r.notified_at
+        FROM registration_reminders r
+        JOIN terms t ON t.code = r.term_code
+        LEFT JOIN registration_windows w
+            ON w.term_code = r.term_code AND w.classification = r.classification
+        WHERE r.user_id = $1
+        ORDER BY r.term_code DESC, w.opens_at NULLS LAST
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to list registration reminders")
+}
+
+/// Unsent reminders whose registration window opened at or before `now`.
+pub async fn find_due_reminders(pool: &PgPool, now: DateTime<Utc>) -> Result<Vec<DueReminder>> {
+    sqlx::query_as::<_, DueReminder>(
+        r#"
+        SELECT r.id AS reminder_id, r.user_id, r.term_code,
+               t.description AS term_description, r.classification, w.opens_at,
+               ui.subject::bigint AS discord_id
+        FROM registration_reminders r
+        JOIN registration_windows w
+            ON w.term_code = r.term_code AND w.classification = r.classification
+        JOIN terms t ON t.code = r.term_code
+        LEFT JOIN user_identities ui
+            ON ui.user_id = r.user_id AND ui.provider = 'discord'
+        WHERE r.notified_at IS NULL AND w.opens_at <= $1
+        ORDER BY w.opens_at, r.id
+        "#,
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await
+    .context("failed to find due registration reminders")
+}
+
+/// Mark reminders as sent.
+///
+/// Takes an executor so it can share the transaction that enqueues the DMs.
+pub async fn mark_notified<'e>(executor: impl PgExecutor<'e>, reminder_ids: &[i32]) -> Result<()> {
+    sqlx::query("UPDATE registration_reminders SET notified_at = NOW() WHERE id = ANY($1)")
+        .bind(reminder_ids)
+        .execute(executor)
+        .await
+        .context("failed to mark registration reminders as notified")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classification_round_trips() {
+        for c in [
+            Classification::Graduate,
+            Classification::Senior,
+            Classification::Junior,
+            Classification::Sophomore,
+            Classification::Freshman,
+            Classification::Open,
+        ] {
+            assert_eq!(c.as_str().parse::<Classification>().unwrap(), c);
+        }
+        assert!("alumni".parse::<Classification>().is_err());
+    }
+}
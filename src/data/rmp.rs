@@ -2,13 +2,15 @@
 
 use crate::rmp::{RmpProfessor, RmpProfessorDetail, RmpReview};
 use anyhow::{Context, Result};
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use std::collections::HashSet;
 
 /// Bulk upsert RMP professors using the UNNEST pattern.
 ///
 /// Deduplicates by `legacy_id` before inserting -- the RMP API can return
-/// the same professor on multiple pages.
+/// the same professor on multiple pages. Professors whose rating or rating
+/// count changed get a new `rmp_professor_history` snapshot in the same
+/// transaction.
 pub async fn batch_upsert_rmp_professors(pool: &PgPool, professors: &[RmpProfessor]) -> Result<()> {
     if professors.is_empty() {
         return Ok(());
@@ -44,6 +46,8 @@ pub async fn batch_upsert_rmp_professors(pool: &PgPool, professors: &[RmpProfess
     let would_take_again_pcts: Vec<Option<f32>> =
         deduped.iter().map(|p| p.would_take_again_pct).collect();
 
+    let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
     sqlx::query(
         r#"
         INSERT INTO rmp_professors (
@@ -84,10 +88,45 @@ pub async fn batch_upsert_rmp_professors(pool: &PgPool, professors: &[RmpProfess
     .bind(&avg_difficulties)
     .bind(&num_ratings)
     .bind(&would_take_again_pcts)
-    .execute(pool)
+    .execute(&mut *tx)
     .await
     .context("Failed to batch upsert RMP professors")?;
 
+    record_professor_snapshots(&mut *tx, &legacy_ids).await?;
+
+    tx.commit().await.context("Failed to commit transaction")?;
+
+    Ok(())
+}
+
+/// Snapshot current aggregates for `legacy_ids` into `rmp_professor_history`,
+/// skipping professors whose latest snapshot already matches.
+async fn record_professor_snapshots<'e>(
+    executor: impl PgExecutor<'e>,
+    legacy_ids: &[i32],
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO rmp_professor_history (legacy_id, avg_rating, num_ratings)
+        SELECT p.legacy_id, p.avg_rating, p.num_ratings
+        FROM rmp_professors p
+        LEFT JOIN LATERAL (
+            SELECT h.avg_rating, h.num_ratings
+            FROM rmp_professor_history h
+            WHERE h.legacy_id = p.legacy_id
+            ORDER BY h.recorded_at DESC
+            LIMIT 1
+        ) last ON true
+        WHERE p.legacy_id = ANY($1)
+          AND (last.num_ratings IS NULL
+               OR last.num_ratings IS DISTINCT FROM p.num_ratings
+               OR last.avg_rating IS DISTINCT FROM p.avg_rating)
+        "#,
+    )
+    .bind(legacy_ids)
+    .execute(executor)
+    .await
+    .context("Failed to record RMP professor snapshots")?;
     Ok(())
 }
 
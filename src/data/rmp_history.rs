@@ -0,0 +1,163 @@
+//! RMP rating history and drift.
+//!
+//! The bulk sync snapshots each professor's aggregate rating whenever it
+//! changes. RMP only exposes the running average, so the ratings received
+//! between two snapshots are recovered by differencing:
+//! `(avg1 * n1 - avg0 * n0) / (n1 - n0)`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// How far back "recent" ratings reach.
+pub const DRIFT_WINDOW_DAYS: i32 = 365;
+
+/// One recorded RMP aggregate.
+#[derive(Debug, Clone, PartialEq, Serialize, TS, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RmpHistoryPoint {
+    pub recorded_at: DateTime<Utc>,
+    pub avg_rating: Option<f32>,
+    pub num_ratings: i32,
+}
+
+/// Rating trend for an instructor's RMP profile.
+#[derive(Debug, Clone, PartialEq, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RmpTrend {
+    /// Snapshots, oldest first.
+    pub history: Vec<RmpHistoryPoint>,
+    pub window_days: i32,
+    /// Ratings received within the window.
+    pub recent_num_ratings: i32,
+    /// Average of the ratings received within the window.
+    pub recent_avg_rating: Option<f32>,
+    /// `recent_avg_rating` minus the current overall average; positive means
+    /// recent students rate the instructor higher than the all-time average.
+    pub drift: Option<f32>,
+}
+
+/// Average and count of the ratings received between two aggregates.
+///
+/// Returns `None` when no ratings were added, the count went down (ratings
+/// removed by RMP), or either average is missing.
+pub fn ratings_between(
+    before: (Option<f32>, i32),
+    after: (Option<f32>, i32),
+) -> Option<(f32, i32)> {
+    let n0 = before.1;
+    // A baseline with no ratings has no average, and needs none.
+    let avg0 = match before {
+        (_, 0) => 0.0,
+        (Some(avg), _) => avg,
+        (None, _) => return None,
+    };
+    let (Some(avg1), n1) = after else {
+        return None;
+    };
+    let added = n1 - n0;
+    if added <= 0 {
+        return None;
+    }
+    let sum = avg1 as f64 * n1 as f64 - avg0 as f64 * n0 as f64;
+    Some(((sum / added as f64).clamp(1.0, 5.0) as f32, added))
+}
+
+/// Summarize `history` (oldest first) into a trend as of `now`.
+///
+/// The baseline is the latest snapshot at least a window old; without one we
+/// can't tell when ratings arrived, so no recent figures are reported.
+pub fn trend(history: Vec<RmpHistoryPoint>, now: DateTime<Utc>) -> RmpTrend {
+    let cutoff = now - TimeDelta::days(DRIFT_WINDOW_DAYS.into());
+    let baseline = history.iter().rev().find(|p| p.recorded_at <= cutoff);
+    let recent = match (baseline, history.last()) {
+        (Some(base), Some(current)) => ratings_between(
+            (base.avg_rating, base.num_ratings),
+            (current.avg_rating, current.num_ratings),
+        ),
+        _ => None,
+    };
+    let drift = recent
+        .zip(history.last().and_then(|p| p.avg_rating))
+        .map(|((recent_avg, _), overall)| recent_avg - overall);
+
+    RmpTrend {
+        window_days: DRIFT_WINDOW_DAYS,
+        recent_num_ratings: recent.map_or(0, |(_, n)| n),
+        recent_avg_rating: recent.map(|(avg, _)| avg),
+        drift,
+        history,
+    }
+}
+
+/// All snapshots for an RMP professor, oldest first.
+pub async fn get_history(pool: &PgPool, legacy_id: i32) -> Result<Vec<RmpHistoryPoint>> {
+    sqlx::query_as::<_, RmpHistoryPoint>(
+        r#"
+        SELECT recorded_at, avg_rating, num_ratings
+        FROM rmp_professor_history
+        WHERE legacy_id = $1
+        ORDER BY recorded_at
+        "#,
+    )
+    .bind(legacy_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch rmp history")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn point(days_ago: i64, avg: f32, n: i32, now: DateTime<Utc>) -> RmpHistoryPoint {
+        RmpHistoryPoint {
+            recorded_at: now - TimeDelta::days(days_ago),
+            avg_rating: Some(avg),
+            num_ratings: n,
+        }
+    }
+
+    #[test]
+    fn differences_running_averages() {
+        // 10 ratings at 4.0, then 10 more at 2.0 -> overall 3.0.
+        assert_eq!(
+            ratings_between((Some(4.0), 10), (Some(3.0), 20)),
+            Some((2.0, 10))
+        );
+        // First ratings ever: the baseline has no average yet.
+        assert_eq!(ratings_between((None, 0), (Some(4.5), 2)), Some((4.5, 2)));
+        assert_eq!(ratings_between((Some(4.0), 10), (Some(4.0), 10)), None);
+        assert_eq!(ratings_between((Some(4.0), 10), (Some(4.1), 8)), None);
+    }
+
+    #[test]
+    fn trend_uses_snapshot_older_than_window() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        let history = vec![
+            point(800, 4.5, 4, now),
+            point(400, 4.0, 10, now),
+            point(100, 3.5, 15, now),
+            point(1, 3.0, 20, now),
+        ];
+        let t = trend(history, now);
+        assert_eq!(t.recent_num_ratings, 10);
+        assert_eq!(t.recent_avg_rating, Some(2.0));
+        assert_eq!(t.drift, Some(-1.0));
+    }
+
+    #[test]
+    fn trend_without_old_baseline_has_no_recent_figures() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        let t = trend(vec![point(30, 4.0, 10, now)], now);
+        assert_eq!(t.recent_num_ratings, 0);
+        assert_eq!(t.recent_avg_rating, None);
+        assert_eq!(t.drift, None);
+    }
+}
@@ -1,7 +1,10 @@
 //! Confidence scoring and candidate generation for RMP instructor matching.
 
-use crate::data::names::{KeyOrigin, matching_keys, parse_banner_name, parse_rmp_name};
+use crate::data::events::{DomainEvent, EventBuffer, InstructorEvent};
+use crate::data::names::{KeyOrigin, matching_keys_with, parse_banner_name, parse_rmp_name};
+use crate::data::nicknames;
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::collections::{HashMap, HashSet};
@@ -369,6 +372,77 @@ pub struct MatchingStats {
     pub skipped_no_candidates: usize,
 }
 
+/// Publish a scoring progress event every this many instructors.
+const PROGRESS_INTERVAL: usize = 250;
+
+/// Stage of a candidate generation run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum RmpMatchingPhase {
+    /// Clearing previous candidates and loading instructors/professors.
+    Loading,
+    /// Scoring instructor-professor pairs in memory.
+    Scoring,
+    /// Writing candidates and auto-links.
+    Writing,
+    Completed,
+    Failed,
+}
+
+/// Cumulative progress of a candidate generation run, streamed to admins.
+///
+/// Every event carries the full state so a client that joins mid-run can
+/// render the latest one without replaying history.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RmpMatchingProgress {
+    pub phase: RmpMatchingPhase,
+    pub started_at: DateTime<Utc>,
+    /// Eligible instructors (excludes confirmed/rejected).
+    pub instructors_total: usize,
+    pub instructors_scored: usize,
+    pub professors_loaded: usize,
+    pub candidates_created: usize,
+    pub auto_matched: usize,
+    pub pending_review: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+/// Accumulates [`RmpMatchingProgress`] and publishes it as a domain event.
+struct ProgressReporter<'a> {
+    events: &'a EventBuffer,
+    progress: RmpMatchingProgress,
+}
+
+impl<'a> ProgressReporter<'a> {
+    fn new(events: &'a EventBuffer) -> Self {
+        Self {
+            events,
+            progress: RmpMatchingProgress {
+                phase: RmpMatchingPhase::Loading,
+                started_at: Utc::now(),
+                instructors_total: 0,
+                instructors_scored: 0,
+                professors_loaded: 0,
+                candidates_created: 0,
+                auto_matched: 0,
+                pending_review: 0,
+                error: None,
+            },
+        }
+    }
+
+    fn publish(&mut self, update: impl FnOnce(&mut RmpMatchingProgress)) {
+        update(&mut self.progress);
+        self.events
+            .publish(DomainEvent::RmpMatching(self.progress.clone()));
+    }
+}
+
 /// Candidate row tuple: (instructor_id, rmp_legacy_id, score, breakdown, review_subjects, review_years).
 type CandidateRow = (i32, i32, f32, serde_json::Value, Vec<String>, Vec<i16>);
 
@@ -435,7 +509,42 @@ fn extract_review_subjects(course_codes: Option<&serde_json::Value>) -> Vec<Stri
 ///    instructor status to `'auto'`.
 /// 9. Set remaining instructors that received at least one candidate (but no
 ///    auto-link) to `'pending'`.
-pub async fn generate_candidates(db_pool: &PgPool) -> Result<MatchingStats> {
+///
+/// Progress is published to `events` as [`DomainEvent::RmpMatching`] so the
+/// admin matching page can show a live indicator.
+pub async fn generate_candidates(db_pool: &PgPool, events: &EventBuffer) -> Result<MatchingStats> {
+    let mut progress = ProgressReporter::new(events);
+    progress.publish(|_| {});
+
+    let result = run_candidate_generation(db_pool, &mut progress).await;
+    match &result {
+        Ok(stats) => {
+            progress.publish(|p| {
+                p.phase = RmpMatchingPhase::Completed;
+                p.instructors_scored = stats.total_processed;
+                p.candidates_created = stats.candidates_created;
+                p.auto_matched = stats.auto_matched;
+                p.pending_review = stats.pending_review;
+            });
+            events.publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+                instructor_id: None,
+            }));
+        }
+        Err(e) => progress.publish(|p| {
+            p.phase = RmpMatchingPhase::Failed;
+            p.error = Some(format!("{e:#}"));
+        }),
+    }
+    result
+}
+
+async fn run_candidate_generation(
+    db_pool: &PgPool,
+    progress: &mut ProgressReporter<'_>,
+) -> Result<MatchingStats> {
+    let nickname_dict = nicknames::load_dictionary(db_pool).await?;
+    debug!(groups = nickname_dict.len(), "Loaded nickname dictionary");
+
     let mut tx = db_pool.begin().await?;
 
     // Step 1: Delete all algorithm-generated candidates.
@@ -490,13 +599,17 @@ pub async fn generate_candidates(db_pool: &PgPool) -> Result<MatchingStats> {
 
     let instructor_ids: Vec<i32> = instructors.iter().map(|(id, _)| *id).collect();
     let total_processed = instructors.len();
+    progress.publish(|p| p.instructors_total = total_processed);
 
-    // Step 5a: Load instructor subjects (for department scoring).
+    // Step 5a: Load instructor subjects (for department scoring). Renamed
+    // subjects contribute every connected code, so the abbreviation table and
+    // reviews written under the old code still line up.
     let subject_rows: Vec<(i32, String)> = sqlx::query_as(
         r#"
-        SELECT DISTINCT ci.instructor_id, c.subject
+        SELECT DISTINCT ci.instructor_id, s.code
         FROM course_instructors ci
         JOIN courses c ON c.id = ci.course_id
+        CROSS JOIN LATERAL unnest(expand_subject_codes(ARRAY[c.subject])) AS s(code)
         WHERE ci.instructor_id = ANY($1)
         "#,
     )
@@ -567,7 +680,7 @@ pub async fn generate_candidates(db_pool: &PgPool) -> Result<MatchingStats> {
                 } else {
                     extract_review_subjects(row.course_codes.as_ref())
                 };
-                let keys = matching_keys(&parts);
+                let keys = matching_keys_with(&parts, &nickname_dict);
                 for key in keys {
                     name_index
                         .entry((key.last, key.first))
@@ -593,6 +706,8 @@ pub async fn generate_candidates(db_pool: &PgPool) -> Result<MatchingStats> {
         }
     }
 
+    progress.publish(|p| p.professors_loaded = prof_rows.len());
+
     if rmp_parse_failures > 0 {
         debug!(
             count = rmp_parse_failures,
@@ -622,7 +737,13 @@ pub async fn generate_candidates(db_pool: &PgPool) -> Result<MatchingStats> {
     let mut skipped_unparseable = 0usize;
     let mut skipped_no_candidates = 0usize;
 
-    for (instructor_id, display_name) in &instructors {
+    progress.publish(|p| p.phase = RmpMatchingPhase::Scoring);
+
+    for (scored, (instructor_id, display_name)) in instructors.iter().enumerate() {
+        if scored > 0 && scored % PROGRESS_INTERVAL == 0 {
+            progress.publish(|p| p.instructors_scored = scored);
+        }
+
         let Some(instructor_parts) = parse_banner_name(display_name) else {
             skipped_unparseable += 1;
             debug!(
@@ -637,7 +758,7 @@ pub async fn generate_candidates(db_pool: &PgPool) -> Result<MatchingStats> {
         // Collect candidate RMP professors across all key variants (deduplicated
         // by legacy_id). Track the best key origin per professor: Primary if any
         // key pair is fully Primary on both sides, Nickname otherwise.
-        let instructor_keys = matching_keys(&instructor_parts);
+        let instructor_keys = matching_keys_with(&instructor_parts, &nickname_dict);
         let mut prof_best_origin: HashMap<i32, KeyOrigin> = HashMap::new();
         let mut matched_profs_map: HashMap<i32, &RmpProfForMatching> = HashMap::new();
 
@@ -734,6 +855,11 @@ pub async fn generate_candidates(db_pool: &PgPool) -> Result<MatchingStats> {
 
     // Step 7: Batch-insert all new candidates.
     let candidates_created = new_candidates.len();
+    progress.publish(|p| {
+        p.phase = RmpMatchingPhase::Writing;
+        p.instructors_scored = total_processed;
+        p.candidates_created = candidates_created;
+    });
 
     if !new_candidates.is_empty() {
         let c_instructor_ids: Vec<i32> = new_candidates
@@ -0,0 +1,103 @@
+//! Building and room codes collected from course meeting locations.
+//!
+//! Banner has no endpoint listing classrooms, so the reference data for them
+//! is derived from where a term's sections meet. Buildings are stored under
+//! [`BUILDING_CATEGORY`] keyed by building code, and rooms under
+//! [`ROOM_CATEGORY`] keyed by [`room_code`].
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+
+use crate::data::models::ReferenceData;
+
+/// Reference category for building codes, e.g. `NPB` -> `North Paseo Building`.
+pub const BUILDING_CATEGORY: &str = "building";
+
+/// Reference category for rooms, e.g. `NPB 1.238` -> `North Paseo Building 1.238`.
+pub const ROOM_CATEGORY: &str = "room";
+
+/// Reference data code for a room.
+pub fn room_code(building: &str, room: &str) -> String {
+    format!("{building} {room}")
+}
+
+#[derive(sqlx::FromRow)]
+struct LocationRow {
+    building: String,
+    room: String,
+    building_description: Option<String>,
+}
+
+/// Building and room reference entries for every location a term's sections
+/// meet in. Meetings without both a building and a room are skipped.
+pub async fn reference_entries(pool: &PgPool, term_code: &str) -> Result<Vec<ReferenceData>> {
+    let rows = sqlx::query_as::<_, LocationRow>(
+        r#"
+        SELECT DISTINCT ON (building, room) building, room, building_description
+        FROM (
+            SELECT
+                NULLIF(TRIM(COALESCE(mt->'location'->>'building', mt->>'building')), '') AS building,
+                NULLIF(TRIM(COALESCE(mt->'location'->>'room', mt->>'room')), '') AS room,
+                NULLIF(TRIM(COALESCE(
+                    mt->'location'->>'buildingDescription', mt->>'building_description'
+                )), '') AS building_description
+            FROM courses c
+            CROSS JOIN LATERAL jsonb_array_elements(c.meeting_times) AS mt
+            WHERE c.term_code = $1
+        ) locations
+        WHERE building IS NOT NULL AND room IS NOT NULL
+        ORDER BY building, room, building_description NULLS LAST
+        "#,
+    )
+    .bind(term_code)
+    .fetch_all(pool)
+    .await
+    .context("failed to collect meeting locations")?;
+
+    let mut entries = Vec::with_capacity(rows.len());
+    let mut last_building: Option<String> = None;
+    for row in rows {
+        let building_name = row
+            .building_description
+            .unwrap_or_else(|| row.building.clone());
+        entries.push(ReferenceData {
+            category: ROOM_CATEGORY.to_owned(),
+            code: room_code(&row.building, &row.room),
+            description: format!("{building_name} {}", row.room),
+        });
+        // Rows are ordered by building, so each appears in one run.
+        if last_building.as_deref() != Some(row.building.as_str()) {
+            entries.push(ReferenceData {
+                category: BUILDING_CATEGORY.to_owned(),
+                code: row.building.clone(),
+                description: building_name,
+            });
+            last_building = Some(row.building);
+        }
+    }
+    Ok(entries)
+}
+
+/// The course fields shown next to a meeting in a room's schedule.
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct RoomSection {
+    pub id: i32,
+    pub crn: String,
+    pub subject: String,
+    pub course_number: String,
+    pub title: String,
+}
+
+/// Look up sections by course id.
+pub async fn sections_by_id(pool: &PgPool, ids: &[i32]) -> Result<Vec<RoomSection>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    sqlx::query_as::<_, RoomSection>(
+        "SELECT id, crn, subject, course_number, title FROM courses WHERE id = ANY($1)",
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch room sections")
+}
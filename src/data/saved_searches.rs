@@ -0,0 +1,162 @@
+//! Saved course searches and their new-match alerts.
+//!
+//! A saved search stores the query parameters of `GET /api/courses/search`
+//! as JSON. Searches with `notify` set are re-run by the notification service
+//! after each scrape of a subject they filter to; sections missing from
+//! `matched_course_ids` are new matches and are DMed to the owner. Notifying
+//! searches must filter by subject, or every subject scrape would re-run them.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgExecutor, PgPool};
+
+/// Most sections a notifying search may match. Broader searches would turn
+/// every scrape into an alert, so they can be saved but not notify.
+pub const MAX_NOTIFY_MATCHES: i32 = 200;
+
+/// A saved search as shown to its owner.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SavedSearch {
+    pub id: i32,
+    pub name: String,
+    pub filters: Json<serde_json::Value>,
+    pub notify: bool,
+    pub created_at: DateTime<Utc>,
+    pub notified_at: Option<DateTime<Utc>>,
+}
+
+/// A notifying search due for evaluation.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct NotifyingSearch {
+    pub id: i32,
+    pub user_id: i64,
+    /// The owner's linked Discord account, if any.
+    pub discord_id: Option<i64>,
+    pub name: String,
+    pub term_code: String,
+    pub filters: Json<serde_json::Value>,
+    pub matched_course_ids: Vec<i32>,
+}
+
+/// Save a search. `matched_course_ids` is the baseline new matches are
+/// measured against.
+pub async fn create_saved_search(
+    pool: &PgPool,
+    user_id: i64,
+    name: &str,
+    term_code: &str,
+    filters: &serde_json::Value,
+    notify: bool,
+    matched_course_ids: &[i32],
+) -> Result<SavedSearch> {
+    sqlx::query_as::<_, SavedSearch>(
+        r#"
+        INSERT INTO saved_searches
+            (user_id, name, term_code, filters, notify, matched_course_ids)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, name, filters, notify, created_at, notified_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(name)
+    .bind(term_code)
+    .bind(filters)
+    .bind(notify)
+    .bind(matched_course_ids)
+    .fetch_one(pool)
+    .await
+    .context("failed to create saved search")
+}
+
+/// A user's saved searches, newest first.
+pub async fn list_saved_searches(pool: &PgPool, user_id: i64) -> Result<Vec<SavedSearch>> {
+    sqlx::query_as::<_, SavedSearch>(
+        r#"
+        SELECT id, name, filters, notify, created_at, notified_at
+        FROM saved_searches
+        WHERE user_id = $1
+        ORDER BY created_at DESC, id DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to list saved searches")
+}
+
+/// Number of searches a user has saved.
+pub async fn count_saved_searches(pool: &PgPool, user_id: i64) -> Result<i64> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM saved_searches WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .context("failed to count saved searches")
+}
+
+/// Delete one of a user's saved searches. Returns true if it existed.
+pub async fn delete_saved_search(pool: &PgPool, user_id: i64, id: i32) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM saved_searches WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .context("failed to delete saved search")?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Notifying searches in `term_code` that a scrape of `subject` could affect:
+/// those filtered to the subject, under any of its codes.
+pub async fn find_notifying_searches(
+    pool: &PgPool,
+    term_code: &str,
+    subject: &str,
+) -> Result<Vec<NotifyingSearch>> {
+    sqlx::query_as::<_, NotifyingSearch>(
+        r#"
+        SELECT s.id, s.user_id, ui.subject::bigint AS discord_id, s.name,
+               s.term_code, s.filters, s.matched_course_ids
+        FROM saved_searches s
+        LEFT JOIN user_identities ui
+            ON ui.user_id = s.user_id AND ui.provider = 'discord'
+        WHERE notify = TRUE
+          AND term_code = $1
+          AND $2 = ANY(expand_subject_codes(
+                ARRAY(SELECT jsonb_array_elements_text(filters->'subject'))
+          ))
+        ORDER BY id
+        "#,
+    )
+    .bind(term_code)
+    .bind(subject)
+    .fetch_all(pool)
+    .await
+    .context("failed to find notifying saved searches")
+}
+
+/// Replace a search's matched sections after an evaluation, stamping
+/// `notified_at` when an alert was queued for it.
+///
+/// Takes an executor so it can share the transaction that enqueues the alert.
+pub async fn record_matches<'e>(
+    executor: impl PgExecutor<'e>,
+    id: i32,
+    matched_course_ids: &[i32],
+    notified: bool,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE saved_searches
+        SET matched_course_ids = $2,
+            notified_at = CASE WHEN $3 THEN NOW() ELSE notified_at END
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(matched_course_ids)
+    .bind(notified)
+    .execute(executor)
+    .await
+    .context("failed to record saved search matches")?;
+    Ok(())
+}
@@ -3,10 +3,28 @@
 //! Pipeline: Raw BB -> Regression calibration -> Bayesian posterior -> CI lower bound as sort key.
 //!
 //! Parameters are locked from prototype validation (scripts/scoring-prototype.ts).
+//!
+//! Older evidence is discounted with an exponential decay: an RMP review
+//! counts half as much after [`ScoringDecay::rmp_half_life_years`], a
+//! BlueBook term after [`ScoringDecay::bluebook_half_life_terms`]. Decay
+//! moves each source's average toward its recent values and shrinks its
+//! evidence weight, so an instructor known only from old terms gets a wider
+//! interval.
+//!
+//! Scores are rebuilt in full on startup. After syncs only instructors whose
+//! inputs changed since the previous run's watermark are recomputed, with a
+//! full run at least every [`FULL_RECOMPUTE_INTERVAL`] since decay and the
+//! recency baseline shift with time alone.
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
 use sqlx::PgPool;
 use tracing::{info, instrument};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::banner::Term;
 
 use super::course_types::{InstructorRating, RatingSource};
 
@@ -28,6 +46,40 @@ const BB_NOISE_VAR: f64 = 1.036;
 const RMP_N_FACTOR: f64 = 2.0;
 const BB_N_FACTOR: f64 = 1.0;
 
+/// How far the RMP estimate moves toward the mean of recent ratings (see
+/// [`rmp_history`](super::rmp_history)) once enough recent ratings exist.
+const RECENCY_WEIGHT: f64 = 0.25;
+/// Recent ratings needed for the full recency weight; fewer scale it down.
+const RECENCY_FULL_N: f64 = 10.0;
+
+/// Rating points subtracted per GPA point an instructor's sections run above
+/// the course average, correcting for students rating lenient graders higher.
+const GRADE_LENIENCY_COEF: f64 = 0.3;
+/// Graded students needed for the full leniency correction; fewer scale it down.
+const GRADE_FULL_N: f64 = 200.0;
+
+/// `app_kv` key that opts scoring into the grade leniency signal (`"true"`).
+/// Off by default: grade releases are sparse and lag several terms behind.
+pub const KV_GRADE_SIGNAL: &str = "scoring.grade_signal";
+
+/// RMP review age at which its weight halves, when none is configured.
+const DEFAULT_RMP_HALF_LIFE_YEARS: f64 = 3.0;
+/// BlueBook term age at which its weight halves, when none is configured.
+/// Three terms per year, so two years.
+const DEFAULT_BLUEBOOK_HALF_LIFE_TERMS: f64 = 6.0;
+
+/// Seconds per year used to age RMP reviews.
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 3600.0;
+
+/// `app_kv` key holding when the last recompute started. Inputs changed after
+/// it are picked up by the next incremental run.
+const KV_WATERMARK: &str = "scoring.watermark";
+/// `app_kv` key holding when the last full recompute started.
+const KV_LAST_FULL: &str = "scoring.last_full";
+
+/// Longest incremental runs are trusted before a full recompute is forced.
+pub const FULL_RECOMPUTE_INTERVAL: TimeDelta = TimeDelta::hours(24);
+
 /// z-score for 80% credible interval.
 const CI_Z: f64 = 1.2816;
 
@@ -39,6 +91,88 @@ const CI_Z: f64 = 1.2816;
 /// had exactly this score (~= 3.775 - 1.2816 x 1.02225 ~= 2.465).
 pub const PRIOR_RANK_SENTINEL: f32 = 2.465;
 
+/// Half-lives for the time decay applied to rating evidence. A half-life of
+/// zero or less disables decay for that source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoringDecay {
+    pub rmp_half_life_years: f64,
+    pub bluebook_half_life_terms: f64,
+}
+
+impl Default for ScoringDecay {
+    fn default() -> Self {
+        Self {
+            rmp_half_life_years: DEFAULT_RMP_HALF_LIFE_YEARS,
+            bluebook_half_life_terms: DEFAULT_BLUEBOOK_HALF_LIFE_TERMS,
+        }
+    }
+}
+
+/// Exponential decay rate for a half-life: weight is `exp(-rate * age)`.
+fn decay_rate(half_life: f64) -> f64 {
+    if half_life > 0.0 {
+        std::f64::consts::LN_2 / half_life
+    } else {
+        0.0
+    }
+}
+
+/// Position of a term in the sequence of terms, so that subtracting two
+/// ordinals gives their distance in terms. Codes run `YYYY10`, `YYYY20`,
+/// `YYYY30` in chronological order within a code year.
+fn term_ordinal(term: &Term) -> i32 {
+    let code = term.to_string();
+    let year: i32 = code[..4].parse().unwrap_or(0);
+    let season: i32 = code[4..].parse().unwrap_or(10);
+    year * 3 + season / 10
+}
+
+/// How each input contributed to an instructor's composite score.
+#[derive(Debug, Clone, PartialEq, Serialize, TS, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RatingBreakdown {
+    /// RMP rating as scored, after recency and decay weighting.
+    pub rmp_weighted: Option<f32>,
+    /// RMP ratings counted after decay; old reviews count for less.
+    pub rmp_weighted_count: f32,
+    /// Fraction of the score's evidence contributed by RMP.
+    pub rmp_share: f32,
+    /// BlueBook rating as scored, after decay and calibration to the RMP scale.
+    pub bb_weighted: Option<f32>,
+    /// BlueBook responses counted after decay.
+    pub bb_weighted_count: f32,
+    /// Fraction of the score's evidence contributed by BlueBook.
+    pub bb_share: f32,
+    /// Fraction held by the prior; large when there is little evidence.
+    pub prior_share: f32,
+    /// Rating points added for grading leniency (negative for lenient graders).
+    pub grade_shift: f32,
+}
+
+/// Which instructors a recompute covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum RecomputeMode {
+    /// Every instructor; the table is rebuilt from scratch.
+    Full,
+    /// Only instructors whose RMP, BlueBook or grade inputs changed since the
+    /// last run. Falls back to a full run when one is due.
+    Incremental,
+}
+
+/// Outcome of a score recompute.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RecomputeStats {
+    /// The mode that actually ran.
+    pub mode: RecomputeMode,
+    /// Instructors whose scores were rewritten.
+    pub recomputed: usize,
+}
+
 /// How to handle instructors with no computed score when sorting by rating.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -53,13 +187,12 @@ pub enum UnratedPolicy {
 
 /// Build SQL fragments for rating-based sorting.
 ///
-/// Returns `(ORDER BY clause, optional WHERE filter)`.
+/// Returns `(sort key expression, optional WHERE filter)`. The key sorts in
+/// the requested direction; callers break ties by display name.
 pub fn rating_sort_sql(ascending: bool, policy: UnratedPolicy) -> (String, Option<String>) {
-    let dir = if ascending { "ASC" } else { "DESC" };
-
     match policy {
         UnratedPolicy::AsPrior => (
-            format!("COALESCE(sc.sort_score, {PRIOR_RANK_SENTINEL}) {dir}, i.display_name ASC"),
+            format!("COALESCE(sc.sort_score, {PRIOR_RANK_SENTINEL})"),
             None,
         ),
         UnratedPolicy::Last => {
@@ -68,13 +201,10 @@ pub fn rating_sort_sql(ascending: bool, policy: UnratedPolicy) -> (String, Optio
             } else {
                 "'-Infinity'"
             };
-            (
-                format!("COALESCE(sc.sort_score, {sentinel}) {dir}, i.display_name ASC"),
-                None,
-            )
+            (format!("COALESCE(sc.sort_score, {sentinel})"), None)
         }
         UnratedPolicy::Exclude => (
-            format!("sc.sort_score {dir}, i.display_name ASC"),
+            "sc.sort_score".to_string(),
             Some("sc.sort_score IS NOT NULL".to_string()),
         ),
     }
@@ -86,8 +216,18 @@ struct RawInstructorData {
     instructor_id: i32,
     rmp_rating: Option<f32>,
     rmp_num_ratings: i32,
+    /// Average and count of RMP ratings received within the drift window.
+    rmp_recent: Option<(f32, i32)>,
+    /// Decay-weighted mean of the stored RMP reviews, and the fraction of their
+    /// weight left after decay. `None` when no dated reviews are stored.
+    rmp_decayed: Option<(f32, f32)>,
     bb_avg_instructor_rating: Option<f32>,
     bb_total_responses: i32,
+    /// Decay-weighted BlueBook average and response count.
+    bb_decayed: Option<(f32, f32)>,
+    /// GPA above the course average across the instructor's graded sections,
+    /// and the number of graded students. `None` unless the grade signal is on.
+    grade_excess: Option<(f32, i32)>,
 }
 
 /// Computed score for a single instructor, ready for DB insertion.
@@ -105,6 +245,47 @@ struct ComputedScore {
     bb_rating: Option<f32>,
     calibrated_bb: Option<f32>,
     bb_count: i32,
+    breakdown: RatingBreakdown,
+}
+
+/// Shift the RMP average toward the mean of recent ratings, so instructors
+/// whose recent students rate them differently than the all-time average
+/// move in that direction. Evidence weight is unchanged.
+fn recency_adjusted_rmp(rating: f32, recent: Option<(f32, i32)>) -> f64 {
+    let Some((recent_avg, recent_n)) = recent else {
+        return rating as f64;
+    };
+    let weight = RECENCY_WEIGHT * (recent_n as f64 / RECENCY_FULL_N).min(1.0);
+    rating as f64 + weight * (recent_avg as f64 - rating as f64)
+}
+
+/// Rating shift correcting for grading leniency: instructors who grade above
+/// the course average are nudged down, harsh graders up.
+fn grade_leniency_shift(grade_excess: Option<(f32, i32)>) -> f64 {
+    let Some((excess_gpa, graded)) = grade_excess else {
+        return 0.0;
+    };
+    let weight = (graded as f64 / GRADE_FULL_N).min(1.0);
+    -GRADE_LENIENCY_COEF * excess_gpa as f64 * weight
+}
+
+/// Calibrate a BlueBook rating to the RMP scale, clamped to [1.0, 5.0].
+fn calibrate_bb(bb: f32) -> f32 {
+    (REG_ALPHA + REG_BETA * bb as f64).clamp(1.0, 5.0) as f32
+}
+
+/// The RMP rating and rating count to score with.
+///
+/// With dated reviews stored, their decay-weighted mean replaces the
+/// aggregate and the count shrinks by the weight lost to decay. Otherwise
+/// the aggregate is used, shifted toward recent ratings.
+fn weighted_rmp(data: &RawInstructorData) -> Option<(f64, f64)> {
+    let rating = data.rmp_rating?;
+    let count = data.rmp_num_ratings as f64;
+    Some(match data.rmp_decayed {
+        Some((mean, freshness)) => (mean as f64, count * freshness as f64),
+        None => (recency_adjusted_rmp(rating, data.rmp_recent), count),
+    })
 }
 
 /// Compute the Bayesian posterior score for a single instructor.
@@ -116,45 +297,51 @@ fn compute_score(data: &RawInstructorData) -> ComputedScore {
     let has_rmp = data.rmp_rating.is_some() && data.rmp_num_ratings > 0;
     let has_bb = data.bb_avg_instructor_rating.is_some() && data.bb_total_responses > 0;
 
-    // Calibrate BB to RMP scale, clamped to [1.0, 5.0]
-    let calibrated_bb = data.bb_avg_instructor_rating.map(|bb| {
-        let raw = REG_ALPHA + REG_BETA * bb as f64;
-        raw.clamp(1.0, 5.0) as f32
+    let calibrated_bb = data.bb_avg_instructor_rating.map(calibrate_bb);
+
+    let rmp = weighted_rmp(data);
+    let bb = data.bb_avg_instructor_rating.map(|avg| {
+        let (avg, count) = data
+            .bb_decayed
+            .unwrap_or((avg, data.bb_total_responses as f32));
+        (calibrate_bb(avg) as f64, count as f64)
     });
 
     // Effective sample sizes with diminishing returns
-    let rmp_n_eff = if data.rmp_num_ratings > 0 {
-        (data.rmp_num_ratings as f64).sqrt() * RMP_N_FACTOR
-    } else {
-        0.0
+    let rmp_n_eff = match rmp {
+        Some((_, count)) if count > 0.0 => count.sqrt() * RMP_N_FACTOR,
+        _ => 0.0,
     };
-    let bb_n_eff = if data.bb_total_responses > 0 {
-        (data.bb_total_responses as f64).sqrt() * BB_N_FACTOR
-    } else {
-        0.0
+    let bb_n_eff = match bb {
+        Some((_, count)) if count > 0.0 => count.sqrt() * BB_N_FACTOR,
+        _ => 0.0,
     };
 
     // Bayesian posterior: conjugate normal-normal update
-    let mut precision = 1.0 / PRIOR_VAR;
+    let prior_precision = 1.0 / PRIOR_VAR;
+    let mut precision = prior_precision;
     let mut weighted_sum = PRIOR_MEAN / PRIOR_VAR;
 
-    if let Some(rmp) = data.rmp_rating
+    let mut rmp_precision = 0.0;
+    if let Some((rating, _)) = rmp
         && rmp_n_eff > 0.0
     {
-        let rmp_precision = rmp_n_eff / RMP_NOISE_VAR;
+        rmp_precision = rmp_n_eff / RMP_NOISE_VAR;
         precision += rmp_precision;
-        weighted_sum += rmp as f64 * rmp_precision;
+        weighted_sum += rating * rmp_precision;
     }
 
-    if let Some(cal_bb) = calibrated_bb
+    let mut bb_precision = 0.0;
+    if let Some((rating, _)) = bb
         && bb_n_eff > 0.0
     {
-        let bb_precision = bb_n_eff / BB_NOISE_VAR;
+        bb_precision = bb_n_eff / BB_NOISE_VAR;
         precision += bb_precision;
-        weighted_sum += cal_bb as f64 * bb_precision;
+        weighted_sum += rating * bb_precision;
     }
 
-    let posterior_mean_raw = weighted_sum / precision;
+    let grade_shift = grade_leniency_shift(data.grade_excess);
+    let posterior_mean_raw = weighted_sum / precision + grade_shift;
     let posterior_stddev = (1.0 / precision).sqrt();
 
     let score = posterior_mean_raw.clamp(1.0, 5.0) as f32;
@@ -182,55 +369,275 @@ fn compute_score(data: &RawInstructorData) -> ComputedScore {
         bb_rating: data.bb_avg_instructor_rating,
         calibrated_bb,
         bb_count: data.bb_total_responses,
+        breakdown: RatingBreakdown {
+            rmp_weighted: rmp.map(|(rating, _)| rating.clamp(1.0, 5.0) as f32),
+            rmp_weighted_count: rmp.map_or(0.0, |(_, count)| count as f32),
+            rmp_share: (rmp_precision / precision) as f32,
+            bb_weighted: bb.map(|(rating, _)| rating as f32),
+            bb_weighted_count: bb.map_or(0.0, |(_, count)| count as f32),
+            bb_share: (bb_precision / precision) as f32,
+            prior_share: (prior_precision / precision) as f32,
+            grade_shift: grade_shift as f32,
+        },
     }
 }
 
 /// Recompute all instructor scores from raw RMP and BlueBook data.
 ///
 /// Truncates the `instructor_scores` table and bulk-inserts fresh scores.
-/// Should be called on startup and after scrape completions.
+/// Should be called on startup; after syncs prefer [`recompute_scores`] with
+/// [`RecomputeMode::Incremental`].
+pub async fn recompute_all_scores(pool: &PgPool, decay: &ScoringDecay) -> Result<usize> {
+    let stats = recompute_scores(pool, decay, RecomputeMode::Full).await?;
+    Ok(stats.recomputed)
+}
+
+/// Recompute instructor scores, either all of them or only those whose
+/// inputs changed since the last run.
 #[instrument(skip(pool))]
-pub async fn recompute_all_scores(pool: &PgPool) -> Result<usize> {
+pub async fn recompute_scores(
+    pool: &PgPool,
+    decay: &ScoringDecay,
+    mode: RecomputeMode,
+) -> Result<RecomputeStats> {
+    let started_at = Utc::now();
+    let watermark = match mode {
+        RecomputeMode::Full => None,
+        RecomputeMode::Incremental => incremental_watermark(pool, started_at).await?,
+    };
+
+    let changed = match watermark {
+        Some(since) => Some(changed_instructors(pool, since).await?),
+        None => None,
+    };
+    let recomputed = match changed.as_deref() {
+        Some([]) => 0,
+        only => write_scores(pool, decay, only).await?,
+    };
+
+    super::kv::set_timestamp(pool, KV_WATERMARK, started_at).await?;
+    let mode = if changed.is_some() {
+        RecomputeMode::Incremental
+    } else {
+        super::kv::set_timestamp(pool, KV_LAST_FULL, started_at).await?;
+        RecomputeMode::Full
+    };
+
+    Ok(RecomputeStats { mode, recomputed })
+}
+
+/// The watermark an incremental run should start from, or `None` when a
+/// full run is due: no run has been recorded yet, or the last full run is
+/// older than [`FULL_RECOMPUTE_INTERVAL`].
+async fn incremental_watermark(pool: &PgPool, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+    let last_full = super::kv::get_timestamp(pool, KV_LAST_FULL).await?;
+    if last_full.is_none_or(|at| now - at >= FULL_RECOMPUTE_INTERVAL) {
+        return Ok(None);
+    }
+    super::kv::get_timestamp(pool, KV_WATERMARK).await
+}
+
+/// Instructors whose scoring inputs changed after `since`, plus scored
+/// instructors whose links have since been removed.
+async fn changed_instructors(pool: &PgPool, since: DateTime<Utc>) -> Result<Vec<i32>> {
+    let ids: Vec<i32> = sqlx::query_scalar(
+        r#"
+        SELECT irl.instructor_id
+        FROM instructor_rmp_links irl
+        WHERE irl.created_at > $1
+        UNION
+        SELECT irl.instructor_id
+        FROM rmp_professor_history h
+        JOIN instructor_rmp_links irl ON irl.rmp_legacy_id = h.legacy_id
+        WHERE h.recorded_at > $1
+        UNION
+        SELECT irl.instructor_id
+        FROM rmp_reviews r
+        JOIN instructor_rmp_links irl ON irl.rmp_legacy_id = r.rmp_legacy_id
+        WHERE r.scraped_at > $1
+        UNION
+        SELECT ibl.instructor_id
+        FROM instructor_bluebook_links ibl
+        WHERE ibl.updated_at > $1
+          AND ibl.instructor_id IS NOT NULL
+        UNION
+        SELECT ibl.instructor_id
+        FROM bluebook_evaluations be
+        JOIN instructor_bluebook_links ibl ON ibl.instructor_name = be.instructor_name
+        WHERE be.updated_at > $1
+          AND ibl.instructor_id IS NOT NULL
+        UNION
+        SELECT gd.instructor_id
+        FROM grade_distributions gd
+        WHERE gd.imported_at > $1
+          AND gd.instructor_id IS NOT NULL
+        UNION
+        SELECT sc.instructor_id
+        FROM instructor_scores sc
+        WHERE (sc.rmp_count > 0 AND NOT EXISTS (
+                  SELECT 1 FROM instructor_rmp_links irl
+                  WHERE irl.instructor_id = sc.instructor_id))
+           OR (sc.bb_count > 0 AND NOT EXISTS (
+                  SELECT 1 FROM instructor_bluebook_links ibl
+                  WHERE ibl.instructor_id = sc.instructor_id
+                    AND ibl.status IN ('approved', 'auto')))
+        "#,
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .context("Failed to find instructors with changed score inputs")?;
+    Ok(ids)
+}
+
+/// Compute and store scores for `only` the given instructors, or for every
+/// instructor when `None`. Returns how many scores were written.
+async fn write_scores(pool: &PgPool, decay: &ScoringDecay, only: Option<&[i32]>) -> Result<usize> {
     let start = std::time::Instant::now();
 
-    // Load all instructors that have at least one rating source
-    let rows = sqlx::query!(
+    let use_grades = super::kv::get(pool, KV_GRADE_SIGNAL)
+        .await?
+        .is_some_and(|v| v == "true");
+
+    // Load all instructors that have at least one rating source, with the
+    // latest RMP snapshot from before the drift window as a recency baseline
+    #[derive(sqlx::FromRow)]
+    struct Row {
+        instructor_id: i32,
+        rmp_rating: Option<f32>,
+        rmp_num_ratings: i32,
+        rmp_base_rating: Option<f32>,
+        rmp_base_num_ratings: Option<i32>,
+        rmp_decayed_rating: Option<f32>,
+        rmp_review_weight: Option<f32>,
+        rmp_review_count: i32,
+        bb_avg: Option<f32>,
+        bb_responses: i32,
+        bb_decayed_avg: Option<f32>,
+        bb_decayed_responses: Option<f32>,
+        gpa_excess: Option<f32>,
+        graded_count: Option<i32>,
+    }
+
+    let rows = sqlx::query_as::<_, Row>(
         r#"
-        WITH bluebook_agg AS (
+        WITH bluebook_weighted AS (
             SELECT
                 ibl.instructor_id,
-                AVG(be.instructor_rating)::REAL AS bb_avg,
-                SUM(be.instructor_response_count)::INTEGER AS bb_responses
+                be.instructor_rating,
+                be.instructor_response_count,
+                exp(-$4 * GREATEST(
+                    $5 - (LEFT(be.term, 4)::INTEGER * 3 + RIGHT(be.term, 2)::INTEGER / 10),
+                    0
+                )) AS weight
             FROM instructor_bluebook_links ibl
             JOIN bluebook_evaluations be ON be.instructor_name = ibl.instructor_name
             WHERE ibl.status IN ('approved', 'auto')
               AND ibl.instructor_id IS NOT NULL
               AND be.instructor_rating IS NOT NULL
-            GROUP BY ibl.instructor_id
+              AND ($7::INT4[] IS NULL OR ibl.instructor_id = ANY($7))
+        ),
+        bluebook_agg AS (
+            SELECT
+                instructor_id,
+                AVG(instructor_rating)::REAL AS bb_avg,
+                SUM(instructor_response_count)::INTEGER AS bb_responses,
+                (SUM(weight * instructor_rating) / SUM(weight))::REAL AS bb_decayed_avg,
+                SUM(weight * instructor_response_count)::REAL AS bb_decayed_responses
+            FROM bluebook_weighted
+            GROUP BY instructor_id
         ),
         rmp_data AS (
             SELECT DISTINCT ON (irl.instructor_id)
                 irl.instructor_id,
+                rp.legacy_id,
                 rp.avg_rating::REAL AS rmp_rating,
                 rp.num_ratings AS rmp_num_ratings
             FROM instructor_rmp_links irl
             JOIN rmp_professors rp ON irl.rmp_legacy_id = rp.legacy_id
             WHERE rp.avg_rating IS NOT NULL
               AND rp.num_ratings > 0
+              AND ($7::INT4[] IS NULL OR irl.instructor_id = ANY($7))
             ORDER BY irl.instructor_id, rp.num_ratings DESC
+        ),
+        course_gpa AS (
+            SELECT
+                canonical_subject(subject) AS subject, course_number,
+                SUM(avg_gpa * graded_count) / NULLIF(SUM(graded_count), 0) AS course_avg
+            FROM grade_distributions
+            WHERE $2 AND avg_gpa IS NOT NULL
+            GROUP BY canonical_subject(subject), course_number
+        ),
+        grade_excess AS (
+            SELECT
+                gd.instructor_id,
+                (SUM((gd.avg_gpa - cg.course_avg) * gd.graded_count)
+                    / SUM(gd.graded_count))::REAL AS gpa_excess,
+                SUM(gd.graded_count)::INTEGER AS graded_count
+            FROM grade_distributions gd
+            JOIN course_gpa cg
+              ON cg.subject = canonical_subject(gd.subject)
+             AND cg.course_number = gd.course_number
+            WHERE $2
+              AND gd.instructor_id IS NOT NULL
+              AND gd.avg_gpa IS NOT NULL
+              AND gd.graded_count > 0
+              AND ($7::INT4[] IS NULL OR gd.instructor_id = ANY($7))
+            GROUP BY gd.instructor_id
         )
         SELECT
             i.id AS instructor_id,
-            rd.rmp_rating AS "rmp_rating: f32",
-            COALESCE(rd.rmp_num_ratings, 0) AS "rmp_num_ratings!: i32",
-            bb.bb_avg AS "bb_avg: f32",
-            COALESCE(bb.bb_responses, 0) AS "bb_responses!: i32"
+            rd.rmp_rating,
+            COALESCE(rd.rmp_num_ratings, 0) AS rmp_num_ratings,
+            base.avg_rating AS rmp_base_rating,
+            base.num_ratings AS rmp_base_num_ratings,
+            rv.decayed_rating AS rmp_decayed_rating,
+            rv.weight AS rmp_review_weight,
+            COALESCE(rv.review_count, 0) AS rmp_review_count,
+            bb.bb_avg,
+            COALESCE(bb.bb_responses, 0) AS bb_responses,
+            bb.bb_decayed_avg,
+            bb.bb_decayed_responses,
+            ge.gpa_excess,
+            ge.graded_count
         FROM instructors i
         LEFT JOIN rmp_data rd ON i.id = rd.instructor_id
+        LEFT JOIN LATERAL (
+            SELECT h.avg_rating, h.num_ratings
+            FROM rmp_professor_history h
+            WHERE h.legacy_id = rd.legacy_id
+              AND h.recorded_at <= NOW() - make_interval(days => $1)
+            ORDER BY h.recorded_at DESC
+            LIMIT 1
+        ) base ON true
+        LEFT JOIN LATERAL (
+            SELECT
+                (SUM(w.weight * r.clarity_rating) / SUM(w.weight))::REAL AS decayed_rating,
+                SUM(w.weight)::REAL AS weight,
+                COUNT(*)::INTEGER AS review_count
+            FROM rmp_reviews r
+            CROSS JOIN LATERAL (
+                SELECT exp(-$3 * GREATEST(
+                    EXTRACT(EPOCH FROM NOW() - r.posted_at)::FLOAT8 / $6, 0
+                )) AS weight
+            ) w
+            WHERE r.rmp_legacy_id = rd.legacy_id
+              AND r.clarity_rating IS NOT NULL
+              AND r.posted_at IS NOT NULL
+        ) rv ON true
         LEFT JOIN bluebook_agg bb ON i.id = bb.instructor_id
-        WHERE rd.rmp_rating IS NOT NULL OR bb.bb_avg IS NOT NULL
-        "#
+        LEFT JOIN grade_excess ge ON i.id = ge.instructor_id
+        WHERE (rd.rmp_rating IS NOT NULL OR bb.bb_avg IS NOT NULL)
+          AND ($7::INT4[] IS NULL OR i.id = ANY($7))
+        "#,
     )
+    .bind(super::rmp_history::DRIFT_WINDOW_DAYS)
+    .bind(use_grades)
+    .bind(decay_rate(decay.rmp_half_life_years))
+    .bind(decay_rate(decay.bluebook_half_life_terms))
+    .bind(term_ordinal(Term::get_current().inner()))
+    .bind(SECONDS_PER_YEAR)
+    .bind(only)
     .fetch_all(pool)
     .await
     .context("Failed to load instructor rating data")?;
@@ -238,12 +645,27 @@ pub async fn recompute_all_scores(pool: &PgPool) -> Result<usize> {
     let scores: Vec<ComputedScore> = rows
         .iter()
         .map(|r| {
+            let rmp_recent = r.rmp_base_num_ratings.and_then(|base_n| {
+                super::rmp_history::ratings_between(
+                    (r.rmp_base_rating, base_n),
+                    (r.rmp_rating, r.rmp_num_ratings),
+                )
+            });
+            let rmp_decayed = r
+                .rmp_decayed_rating
+                .zip(r.rmp_review_weight)
+                .filter(|_| r.rmp_review_count > 0)
+                .map(|(mean, weight)| (mean, weight / r.rmp_review_count as f32));
             compute_score(&RawInstructorData {
                 instructor_id: r.instructor_id,
                 rmp_rating: r.rmp_rating,
                 rmp_num_ratings: r.rmp_num_ratings,
+                rmp_recent,
+                rmp_decayed,
                 bb_avg_instructor_rating: r.bb_avg,
                 bb_total_responses: r.bb_responses,
+                bb_decayed: r.bb_decayed_avg.zip(r.bb_decayed_responses),
+                grade_excess: r.gpa_excess.zip(r.graded_count),
             })
         })
         .collect();
@@ -266,24 +688,48 @@ pub async fn recompute_all_scores(pool: &PgPool) -> Result<usize> {
     let bb_ratings: Vec<Option<f32>> = scores.iter().map(|s| s.bb_rating).collect();
     let calibrated_bbs: Vec<Option<f32>> = scores.iter().map(|s| s.calibrated_bb).collect();
     let bb_counts: Vec<i32> = scores.iter().map(|s| s.bb_count).collect();
+    let breakdowns: Vec<&RatingBreakdown> = scores.iter().map(|s| &s.breakdown).collect();
+    let rmp_weighted: Vec<Option<f32>> = breakdowns.iter().map(|b| b.rmp_weighted).collect();
+    let rmp_weighted_counts: Vec<f32> = breakdowns.iter().map(|b| b.rmp_weighted_count).collect();
+    let rmp_shares: Vec<f32> = breakdowns.iter().map(|b| b.rmp_share).collect();
+    let bb_weighted: Vec<Option<f32>> = breakdowns.iter().map(|b| b.bb_weighted).collect();
+    let bb_weighted_counts: Vec<f32> = breakdowns.iter().map(|b| b.bb_weighted_count).collect();
+    let bb_shares: Vec<f32> = breakdowns.iter().map(|b| b.bb_share).collect();
+    let prior_shares: Vec<f32> = breakdowns.iter().map(|b| b.prior_share).collect();
+    let grade_shifts: Vec<f32> = breakdowns.iter().map(|b| b.grade_shift).collect();
 
-    // Truncate + insert in a single transaction
+    // Clear + insert in a single transaction
     let mut tx = pool.begin().await.context("Failed to begin transaction")?;
 
-    sqlx::query!("TRUNCATE instructor_scores")
-        .execute(&mut *tx)
-        .await
-        .context("Failed to truncate instructor_scores")?;
+    match only {
+        Some(ids) => {
+            sqlx::query("DELETE FROM instructor_scores WHERE instructor_id = ANY($1)")
+                .bind(ids)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to clear changed instructor_scores")?;
+        }
+        None => {
+            sqlx::query!("TRUNCATE instructor_scores")
+                .execute(&mut *tx)
+                .await
+                .context("Failed to truncate instructor_scores")?;
+        }
+    }
 
     sqlx::query!(
         r#"
         INSERT INTO instructor_scores (
             instructor_id, display_score, sort_score, ci_lower, ci_upper,
-            confidence, source, rmp_rating, rmp_count, bb_rating, calibrated_bb, bb_count
+            confidence, source, rmp_rating, rmp_count, bb_rating, calibrated_bb, bb_count,
+            rmp_weighted, rmp_weighted_count, rmp_share,
+            bb_weighted, bb_weighted_count, bb_share, prior_share, grade_shift
         )
         SELECT * FROM UNNEST(
             $1::int[], $2::real[], $3::real[], $4::real[], $5::real[],
-            $6::real[], $7::text[], $8::real[], $9::int[], $10::real[], $11::real[], $12::int[]
+            $6::real[], $7::text[], $8::real[], $9::int[], $10::real[], $11::real[], $12::int[],
+            $13::real[], $14::real[], $15::real[],
+            $16::real[], $17::real[], $18::real[], $19::real[], $20::real[]
         )
         "#,
         &instructor_ids,
@@ -298,6 +744,14 @@ pub async fn recompute_all_scores(pool: &PgPool) -> Result<usize> {
         &bb_ratings as &[Option<f32>],
         &calibrated_bbs as &[Option<f32>],
         &bb_counts,
+        &rmp_weighted as &[Option<f32>],
+        &rmp_weighted_counts,
+        &rmp_shares,
+        &bb_weighted as &[Option<f32>],
+        &bb_weighted_counts,
+        &bb_shares,
+        &prior_shares,
+        &grade_shifts,
     )
     .execute(&mut *tx)
     .await
@@ -308,6 +762,8 @@ pub async fn recompute_all_scores(pool: &PgPool) -> Result<usize> {
     let elapsed = start.elapsed();
     info!(
         count,
+        incremental = only.is_some(),
+        use_grades,
         elapsed_ms = elapsed.as_millis() as u64,
         "Recomputed instructor scores"
     );
@@ -350,8 +806,12 @@ mod tests {
             instructor_id: 1,
             rmp_rating: Some(4.5),
             rmp_num_ratings: 25,
+            rmp_recent: None,
+            rmp_decayed: None,
             bb_avg_instructor_rating: Some(4.8),
             bb_total_responses: 100,
+            bb_decayed: None,
+            grade_excess: None,
         };
         let score = compute_score(&data);
         assert_eq!(score.source, RatingSource::Both);
@@ -368,8 +828,12 @@ mod tests {
             instructor_id: 2,
             rmp_rating: Some(3.0),
             rmp_num_ratings: 10,
+            rmp_recent: None,
+            rmp_decayed: None,
             bb_avg_instructor_rating: None,
             bb_total_responses: 0,
+            bb_decayed: None,
+            grade_excess: None,
         };
         let score = compute_score(&data);
         assert_eq!(score.source, RatingSource::Rmp);
@@ -382,8 +846,12 @@ mod tests {
             instructor_id: 3,
             rmp_rating: None,
             rmp_num_ratings: 0,
+            rmp_recent: None,
+            rmp_decayed: None,
             bb_avg_instructor_rating: Some(4.2),
             bb_total_responses: 50,
+            bb_decayed: None,
+            grade_excess: None,
         };
         let score = compute_score(&data);
         assert_eq!(score.source, RatingSource::BlueBook);
@@ -397,15 +865,23 @@ mod tests {
             instructor_id: 1,
             rmp_rating: Some(3.9),
             rmp_num_ratings: 100,
+            rmp_recent: None,
+            rmp_decayed: None,
             bb_avg_instructor_rating: Some(4.5),
             bb_total_responses: 500,
+            bb_decayed: None,
+            grade_excess: None,
         });
         let low_evidence = compute_score(&RawInstructorData {
             instructor_id: 2,
             rmp_rating: None,
             rmp_num_ratings: 0,
+            rmp_recent: None,
+            rmp_decayed: None,
             bb_avg_instructor_rating: Some(4.5),
             bb_total_responses: 10,
+            bb_decayed: None,
+            grade_excess: None,
         });
         assert!(
             high_evidence.rank_score > low_evidence.rank_score,
@@ -420,20 +896,154 @@ mod tests {
             instructor_id: 1,
             rmp_rating: None,
             rmp_num_ratings: 0,
+            rmp_recent: None,
+            rmp_decayed: None,
             bb_avg_instructor_rating: Some(3.0),
             bb_total_responses: 50,
+            bb_decayed: None,
+            grade_excess: None,
         });
         let high_bb = compute_score(&RawInstructorData {
             instructor_id: 2,
             rmp_rating: None,
             rmp_num_ratings: 0,
+            rmp_recent: None,
+            rmp_decayed: None,
             bb_avg_instructor_rating: Some(4.5),
             bb_total_responses: 50,
+            bb_decayed: None,
+            grade_excess: None,
         });
         assert!(high_bb.calibrated_bb.unwrap() > low_bb.calibrated_bb.unwrap());
         assert!(high_bb.score > low_bb.score);
     }
 
+    #[test]
+    fn test_recent_ratings_pull_score_toward_recent_mean() {
+        let input = |rmp_recent| RawInstructorData {
+            instructor_id: 1,
+            rmp_rating: Some(4.0),
+            rmp_num_ratings: 40,
+            rmp_recent,
+            rmp_decayed: None,
+            bb_avg_instructor_rating: None,
+            bb_total_responses: 0,
+            bb_decayed: None,
+            grade_excess: None,
+        };
+        let steady = compute_score(&input(None));
+        let declining = compute_score(&input(Some((2.5, 12))));
+        assert!(declining.score < steady.score);
+        // Evidence weight is unchanged, so the interval width is too.
+        let width = |s: &ComputedScore| s.ci_upper - s.ci_lower;
+        assert!((width(&declining) - width(&steady)).abs() < 1e-5);
+        // The raw RMP average is stored as-is.
+        assert_eq!(declining.rmp_rating, Some(4.0));
+    }
+
+    #[test]
+    fn test_lenient_graders_are_nudged_down() {
+        let input = |grade_excess| RawInstructorData {
+            instructor_id: 1,
+            rmp_rating: Some(4.0),
+            rmp_num_ratings: 40,
+            rmp_recent: None,
+            rmp_decayed: None,
+            bb_avg_instructor_rating: None,
+            bb_total_responses: 0,
+            bb_decayed: None,
+            grade_excess,
+        };
+        let baseline = compute_score(&input(None));
+        let lenient = compute_score(&input(Some((0.5, 400))));
+        let harsh = compute_score(&input(Some((-0.5, 400))));
+        assert!(lenient.score < baseline.score);
+        assert!(harsh.score > baseline.score);
+        // Few graded students scale the correction down.
+        let sparse = compute_score(&input(Some((0.5, 20))));
+        assert!(sparse.score > lenient.score && sparse.score < baseline.score);
+    }
+
+    #[test]
+    fn test_decayed_reviews_shift_rating_and_widen_interval() {
+        let input = |rmp_decayed| RawInstructorData {
+            instructor_id: 1,
+            rmp_rating: Some(4.0),
+            rmp_num_ratings: 40,
+            rmp_recent: Some((4.5, 12)),
+            rmp_decayed,
+            bb_avg_instructor_rating: None,
+            bb_total_responses: 0,
+            bb_decayed: None,
+            grade_excess: None,
+        };
+        let fresh = compute_score(&input(Some((4.0, 1.0))));
+        let stale = compute_score(&input(Some((3.0, 0.25))));
+        assert!(stale.score < fresh.score);
+        let width = |s: &ComputedScore| s.ci_upper - s.ci_lower;
+        assert!(width(&stale) > width(&fresh));
+        assert_eq!(stale.breakdown.rmp_weighted, Some(3.0));
+        assert_eq!(stale.breakdown.rmp_weighted_count, 10.0);
+        // Raw inputs are stored as-is.
+        assert_eq!((stale.rmp_rating, stale.rmp_count), (Some(4.0), 40));
+    }
+
+    #[test]
+    fn test_stale_bluebook_counts_for_less() {
+        let input = |bb_decayed| RawInstructorData {
+            instructor_id: 1,
+            rmp_rating: Some(4.0),
+            rmp_num_ratings: 20,
+            rmp_recent: None,
+            rmp_decayed: None,
+            bb_avg_instructor_rating: Some(4.5),
+            bb_total_responses: 200,
+            bb_decayed,
+            grade_excess: None,
+        };
+        let fresh = compute_score(&input(None));
+        let stale = compute_score(&input(Some((4.5, 25.0))));
+        assert!(stale.breakdown.bb_share < fresh.breakdown.bb_share);
+        assert!(stale.confidence < fresh.confidence);
+        assert_eq!(stale.calibrated_bb, fresh.calibrated_bb);
+    }
+
+    #[test]
+    fn test_breakdown_shares_sum_to_one() {
+        let score = compute_score(&RawInstructorData {
+            instructor_id: 1,
+            rmp_rating: Some(4.0),
+            rmp_num_ratings: 30,
+            rmp_recent: None,
+            rmp_decayed: Some((3.8, 0.6)),
+            bb_avg_instructor_rating: Some(4.4),
+            bb_total_responses: 120,
+            bb_decayed: Some((4.3, 80.0)),
+            grade_excess: Some((0.4, 300)),
+        });
+        let b = &score.breakdown;
+        assert!((b.rmp_share + b.bb_share + b.prior_share - 1.0).abs() < 1e-5);
+        assert!(b.rmp_share > 0.0 && b.bb_share > 0.0);
+        assert!(b.grade_shift < 0.0);
+    }
+
+    #[test]
+    fn test_decay_rate_halves_weight_at_half_life() {
+        let weight = (-decay_rate(3.0) * 3.0).exp();
+        assert!((weight - 0.5).abs() < 1e-9);
+        assert_eq!(decay_rate(0.0), 0.0);
+        assert_eq!(decay_rate(-1.0), 0.0);
+    }
+
+    #[test]
+    fn test_term_ordinal_counts_terms() {
+        let ordinal = |code: &str| term_ordinal(&code.parse().unwrap());
+        // Summer 2025 -> Fall 2025
+        assert_eq!(ordinal("202610") - ordinal("202530"), 1);
+        assert_eq!(ordinal("202710") - ordinal("202610"), 3);
+        assert_eq!(ordinal("202620") - ordinal("202610"), 1);
+    }
+
     #[test]
     fn test_prior_rank_sentinel_matches_computation() {
         let computed = (PRIOR_MEAN - CI_Z * PRIOR_VAR.sqrt()) as f32;
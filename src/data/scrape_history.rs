@@ -0,0 +1,166 @@
+//! Retention for `scrape_job_results` and dead-lettered `scrape_jobs`.
+//!
+//! Results older than the retention window are rolled up into one
+//! `scrape_job_daily_stats` row per day and target type, then deleted, so the
+//! results table only holds the recent history the admin views query. Each
+//! subject's latest result is kept however old it is. Jobs dead-lettered
+//! before the window are discarded.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::data::kv;
+
+/// Shortest allowed retention: the admin scraper stats reach back 30 days
+/// and read `scrape_job_results` directly.
+pub const MIN_RETENTION_DAYS: u32 = 30;
+
+/// KV key for when compaction last ran.
+pub const KV_LAST_COMPACTION: &str = "scrape_history.last_compaction";
+/// KV key for how many results the last compaction rolled up.
+pub const KV_LAST_COMPACTED_ROWS: &str = "scrape_history.last_compacted_rows";
+
+/// Result of the most recent compaction, read back from KV.
+#[derive(Debug, Clone, Default)]
+pub struct CompactionStatus {
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_compacted_rows: Option<i64>,
+}
+
+/// Rows deleted per statement, so a large backlog never becomes one huge
+/// transaction.
+const BATCH_SIZE: i64 = 5_000;
+
+/// What one compaction removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Compaction {
+    /// Results rolled into daily stats.
+    pub results: i64,
+    /// Dead-lettered jobs purged from the queue.
+    pub dead_letters: i64,
+}
+
+/// Roll every result completed before `cutoff` into daily stats and delete
+/// it, and purge jobs dead-lettered before `cutoff`.
+///
+/// The latest result for each subject and term is kept regardless of age.
+/// Days that were already partly compacted are added to, so repeated runs
+/// never double count. Works in batches of [`BATCH_SIZE`] rows.
+pub async fn compact(pool: &PgPool, cutoff: DateTime<Utc>) -> Result<Compaction> {
+    let mut compaction = Compaction::default();
+    loop {
+        let moved = compact_batch(pool, cutoff).await?;
+        compaction.results += moved;
+        if moved < BATCH_SIZE {
+            break;
+        }
+    }
+    loop {
+        let purged = purge_dead_letter_batch(pool, cutoff).await?;
+        compaction.dead_letters += purged;
+        if purged < BATCH_SIZE {
+            break;
+        }
+    }
+    Ok(compaction)
+}
+
+/// Roll up and delete up to [`BATCH_SIZE`] old results.
+async fn compact_batch(pool: &PgPool, cutoff: DateTime<Utc>) -> Result<i64> {
+    // Data-modifying CTEs always run to completion, so the INSERT applies
+    // even though the outer SELECT only reads the DELETE's output.
+    let compacted: i64 = sqlx::query_scalar(
+        r#"
+        WITH batch AS (
+            SELECT r.id FROM scrape_job_results r
+            WHERE r.completed_at < $1
+              AND (r.target_type <> 'Subject' OR EXISTS (
+                  SELECT 1 FROM scrape_job_results newer
+                  WHERE newer.target_type = 'Subject'
+                    AND newer.payload->>'subject' = r.payload->>'subject'
+                    AND newer.payload->>'term' IS NOT DISTINCT FROM r.payload->>'term'
+                    AND (newer.completed_at, newer.id) > (r.completed_at, r.id)
+              ))
+            ORDER BY r.id
+            LIMIT $2
+        ),
+        moved AS (
+            DELETE FROM scrape_job_results
+            WHERE id IN (SELECT id FROM batch)
+            RETURNING *
+        ),
+        rolled AS (
+            INSERT INTO scrape_job_daily_stats (
+                day, target_type, runs, successes, failures, total_duration_ms,
+                courses_fetched, courses_changed, courses_unchanged,
+                audits_generated, metrics_generated
+            )
+            SELECT (completed_at AT TIME ZONE 'UTC')::date, target_type,
+                   COUNT(*),
+                   COUNT(*) FILTER (WHERE success),
+                   COUNT(*) FILTER (WHERE NOT success),
+                   COALESCE(SUM(duration_ms), 0),
+                   COALESCE(SUM(courses_fetched), 0),
+                   COALESCE(SUM(courses_changed), 0),
+                   COALESCE(SUM(courses_unchanged), 0),
+                   COALESCE(SUM(audits_generated), 0),
+                   COALESCE(SUM(metrics_generated), 0)
+            FROM moved
+            GROUP BY 1, 2
+            ON CONFLICT (day, target_type) DO UPDATE SET
+                runs = scrape_job_daily_stats.runs + EXCLUDED.runs,
+                successes = scrape_job_daily_stats.successes + EXCLUDED.successes,
+                failures = scrape_job_daily_stats.failures + EXCLUDED.failures,
+                total_duration_ms = scrape_job_daily_stats.total_duration_ms + EXCLUDED.total_duration_ms,
+                courses_fetched = scrape_job_daily_stats.courses_fetched + EXCLUDED.courses_fetched,
+                courses_changed = scrape_job_daily_stats.courses_changed + EXCLUDED.courses_changed,
+                courses_unchanged = scrape_job_daily_stats.courses_unchanged + EXCLUDED.courses_unchanged,
+                audits_generated = scrape_job_daily_stats.audits_generated + EXCLUDED.audits_generated,
+                metrics_generated = scrape_job_daily_stats.metrics_generated + EXCLUDED.metrics_generated
+        )
+        SELECT COUNT(*) FROM moved
+        "#,
+    )
+    .bind(cutoff)
+    .bind(BATCH_SIZE)
+    .fetch_one(pool)
+    .await
+    .context("failed to compact scrape job results")?;
+
+    Ok(compacted)
+}
+
+/// Delete up to [`BATCH_SIZE`] jobs dead-lettered before `cutoff`.
+async fn purge_dead_letter_batch(pool: &PgPool, cutoff: DateTime<Utc>) -> Result<i64> {
+    let purged = sqlx::query(
+        "DELETE FROM scrape_jobs WHERE id IN (\
+         SELECT id FROM scrape_jobs WHERE dead_lettered_at < $1 ORDER BY id LIMIT $2)",
+    )
+    .bind(cutoff)
+    .bind(BATCH_SIZE)
+    .execute(pool)
+    .await
+    .context("failed to purge dead-lettered scrape jobs")?
+    .rows_affected();
+
+    Ok(purged as i64)
+}
+
+/// Record a finished compaction for admin status.
+pub async fn record_compaction(pool: &PgPool, at: DateTime<Utc>, compacted: i64) -> Result<()> {
+    kv::set_timestamp(pool, KV_LAST_COMPACTION, at).await?;
+    kv::set(pool, KV_LAST_COMPACTED_ROWS, &compacted.to_string()).await
+}
+
+/// Read back the last recorded compaction.
+pub async fn compaction_status(pool: &PgPool) -> Result<CompactionStatus> {
+    let (last_run_at, rows) = tokio::try_join!(
+        kv::get_timestamp(pool, KV_LAST_COMPACTION),
+        kv::get(pool, KV_LAST_COMPACTED_ROWS),
+    )?;
+    Ok(CompactionStatus {
+        last_run_at,
+        last_compacted_rows: rows.and_then(|v| v.parse().ok()),
+    })
+}
@@ -9,7 +9,8 @@ use tracing::debug;
 use super::context::DbContext;
 use super::events::DomainEvent;
 use crate::data::models::{
-    ScrapeJob, ScrapeJobStatus, ScrapePriority, SubjectResultStats, TargetType, UpsertCounts,
+    ScrapeDiffSummary, ScrapeJob, ScrapeJobStatus, ScrapePriority, SubjectResultStats, TargetType,
+    UpsertCounts,
 };
 use crate::data::unsigned::{Count, DurationMs};
 use crate::web::ws::{ScrapeJobDto, ScrapeJobEvent};
@@ -28,12 +29,26 @@ pub struct SubjectResultRow {
     pub courses_unchanged: Option<i32>,
     pub audits_generated: Option<i32>,
     pub metrics_generated: Option<i32>,
+    pub diff_summary: Option<sqlx::types::Json<ScrapeDiffSummary>>,
 }
 
-/// List scrape jobs ordered by priority descending, then execute_at ascending.
+/// List live scrape jobs ordered by priority descending, then execute_at ascending.
+///
+/// Dead-lettered jobs are excluded; see [`list_dead_letters`]. Each job carries the diff summary of the latest completed run with the same payload.
 pub async fn list_ordered(pool: &PgPool, limit: i64) -> Result<Vec<ScrapeJob>> {
     sqlx::query_as::<_, ScrapeJob>(
-        "SELECT * FROM scrape_jobs ORDER BY priority DESC, execute_at ASC LIMIT $1",
+        "SELECT j.*, r.diff_summary AS last_diff \
+         FROM scrape_jobs j \
+         LEFT JOIN LATERAL ( \
+             SELECT diff_summary FROM scrape_job_results \
+             WHERE target_type = j.target_type AND payload = j.target_payload \
+               AND diff_summary IS NOT NULL \
+             ORDER BY completed_at DESC \
+             LIMIT 1 \
+         ) r ON TRUE \
+         WHERE j.dead_lettered_at IS NULL \
+         ORDER BY j.priority DESC, j.execute_at ASC \
+         LIMIT $1",
     )
     .bind(limit)
     .fetch_all(pool)
@@ -50,6 +65,20 @@ pub async fn count_all(pool: &PgPool) -> Result<i64> {
     Ok(count)
 }
 
+/// List dead-lettered jobs, most recently parked first.
+pub async fn list_dead_letters(pool: &PgPool, limit: i64) -> Result<Vec<ScrapeJob>> {
+    sqlx::query_as::<_, ScrapeJob>(
+        "SELECT * FROM scrape_jobs \
+         WHERE dead_lettered_at IS NOT NULL \
+         ORDER BY dead_lettered_at DESC \
+         LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("failed to list dead-lettered scrape jobs")
+}
+
 /// Fetch a single scrape job by ID.
 pub async fn get_by_id(pool: &PgPool, id: i32) -> Result<Option<ScrapeJob>> {
     sqlx::query_as::<_, ScrapeJob>("SELECT * FROM scrape_jobs WHERE id = $1")
@@ -68,7 +97,7 @@ pub async fn list_results_for_subject(
     sqlx::query_as::<_, SubjectResultRow>(
         "SELECT id, completed_at, duration_ms, success, error_message, \
                 courses_fetched, courses_changed, courses_unchanged, \
-                audits_generated, metrics_generated \
+                audits_generated, metrics_generated, diff_summary \
          FROM scrape_job_results \
          WHERE target_type = 'Subject' AND payload->>'subject' = $1 \
          ORDER BY completed_at DESC \
@@ -81,6 +110,17 @@ pub async fn list_results_for_subject(
     .context("failed to list results for subject")
 }
 
+/// Result of [`ScrapeJobOps::requeue`].
+#[derive(Debug)]
+pub enum RequeueOutcome {
+    /// The job is back in the queue.
+    Requeued(ScrapeJob),
+    /// A live job for the same target already existed; the dead letter was dropped.
+    Duplicate,
+    /// No dead-lettered job with that ID.
+    NotFound,
+}
+
 /// Lock expiry duration in seconds.
 const LOCK_EXPIRY_SECS: i32 = 10 * 60;
 
@@ -97,7 +137,17 @@ impl<'a> ScrapeJobOps<'a> {
     /// Fetch and lock the next available job.
     ///
     /// Emits a `ScrapeJobEvent::Locked` event on success.
+    #[allow(dead_code)] // Used by tests; the worker pool always passes its saturated types
     pub async fn lock_next(&self) -> Result<Option<ScrapeJob>> {
+        self.lock_next_excluding(&[]).await
+    }
+
+    /// Fetch and lock the next available job whose type isn't in `excluded`.
+    ///
+    /// Among jobs of the highest priority, terms with fewer jobs already
+    /// running go first, so a large backfill of one term can't starve another.
+    /// Emits a `ScrapeJobEvent::Locked` event on success.
+    pub async fn lock_next_excluding(&self, excluded: &[TargetType]) -> Result<Option<ScrapeJob>> {
         let mut tx = self
             .ctx
             .pool()
@@ -106,14 +156,24 @@ impl<'a> ScrapeJobOps<'a> {
             .context("failed to begin transaction for lock_next")?;
 
         let job = sqlx::query_as::<_, ScrapeJob>(
-            "SELECT * FROM scrape_jobs \
-             WHERE (locked_at IS NULL OR locked_at < NOW() - make_interval(secs => $1::double precision)) \
-             AND execute_at <= NOW() \
-             ORDER BY priority DESC, execute_at ASC \
+            "WITH running AS ( \
+                 SELECT target_payload->>'term' AS term, COUNT(*) AS jobs \
+                 FROM scrape_jobs \
+                 WHERE locked_at >= NOW() - make_interval(secs => $1::double precision) \
+                 GROUP BY 1 \
+             ) \
+             SELECT j.* FROM scrape_jobs j \
+             LEFT JOIN running r ON r.term = j.target_payload->>'term' \
+             WHERE (j.locked_at IS NULL OR j.locked_at < NOW() - make_interval(secs => $1::double precision)) \
+             AND j.execute_at <= NOW() \
+             AND j.dead_lettered_at IS NULL \
+             AND NOT (j.target_type = ANY($2)) \
+             ORDER BY j.priority DESC, COALESCE(r.jobs, 0) ASC, j.execute_at ASC \
              LIMIT 1 \
-             FOR UPDATE SKIP LOCKED",
+             FOR UPDATE OF j SKIP LOCKED",
         )
         .bind(LOCK_EXPIRY_SECS)
+        .bind(excluded)
         .fetch_optional(&mut *tx)
         .await
         .context("failed to fetch next lockable scrape job")?;
@@ -166,23 +226,26 @@ impl<'a> ScrapeJobOps<'a> {
 
     /// Mark a job as completed (deletes it).
     ///
-    /// Emits a `ScrapeJobEvent::Completed` event with the subject extracted from
-    /// the job's target payload.
-    pub async fn complete(&self, job_id: i32) -> Result<()> {
-        let subject: Option<String> = sqlx::query_scalar(
-            "DELETE FROM scrape_jobs WHERE id = $1 RETURNING target_payload->>'subject'",
+    /// Emits a `ScrapeJobEvent::Completed` event with the subject and term
+    /// extracted from the job's target payload and the run's diff summary, if any.
+    pub async fn complete(&self, job_id: i32, diff: Option<ScrapeDiffSummary>) -> Result<()> {
+        let (subject, term): (Option<String>, Option<String>) = sqlx::query_as(
+            "DELETE FROM scrape_jobs WHERE id = $1 \
+             RETURNING target_payload->>'subject', target_payload->>'term'",
         )
         .bind(job_id)
         .fetch_optional(self.ctx.pool())
         .await
         .context("failed to complete scrape job")?
-        .flatten();
+        .unwrap_or_default();
 
         self.ctx
             .events()
             .publish(DomainEvent::ScrapeJob(ScrapeJobEvent::Completed {
                 id: job_id,
                 subject,
+                term,
+                diff,
             }));
 
         Ok(())
@@ -220,15 +283,23 @@ impl<'a> ScrapeJobOps<'a> {
         Ok(())
     }
 
-    /// Mark a job as exhausted (max retries exceeded).
+    /// Move a job to the dead-letter queue, recording its final error.
     ///
-    /// Emits `ScrapeJobEvent::Exhausted` then `ScrapeJobEvent::Deleted`.
-    pub async fn exhaust(&self, job_id: i32) -> Result<()> {
-        sqlx::query("DELETE FROM scrape_jobs WHERE id = $1")
-            .bind(job_id)
-            .execute(self.ctx.pool())
-            .await
-            .context("failed to exhaust scrape job")?;
+    /// The row is kept for inspection but never picked up again until
+    /// [`requeue`](Self::requeue)d. Emits `ScrapeJobEvent::Exhausted` then
+    /// `ScrapeJobEvent::Deleted`, since the job leaves the live queue.
+    pub async fn dead_letter(&self, job_id: i32, retry_count: Count, error: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE scrape_jobs \
+             SET locked_at = NULL, retry_count = $2, last_error = $3, dead_lettered_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(job_id)
+        .bind(retry_count)
+        .bind(error)
+        .execute(self.ctx.pool())
+        .await
+        .context("failed to dead-letter scrape job")?;
 
         self.ctx
             .events()
@@ -244,6 +315,80 @@ impl<'a> ScrapeJobOps<'a> {
         Ok(())
     }
 
+    /// Return a dead-lettered job to the queue with its retries reset.
+    ///
+    /// If the scheduler has since queued a live job for the same target, the
+    /// dead letter is dropped instead so the target isn't scraped twice.
+    /// Emits `ScrapeJobEvent::Created` for a requeued job, or
+    /// `ScrapeJobEvent::Deleted` for a dropped one.
+    pub async fn requeue(&self, job_id: i32) -> Result<RequeueOutcome> {
+        let mut tx = self
+            .ctx
+            .pool()
+            .begin()
+            .await
+            .context("failed to begin transaction for requeue")?;
+
+        let job = sqlx::query_as::<_, ScrapeJob>(
+            "SELECT * FROM scrape_jobs WHERE id = $1 AND dead_lettered_at IS NOT NULL FOR UPDATE",
+        )
+        .bind(job_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("failed to fetch dead-lettered scrape job")?;
+        let Some(job) = job else {
+            return Ok(RequeueOutcome::NotFound);
+        };
+
+        let duplicate: bool = sqlx::query_scalar(
+            "SELECT EXISTS ( \
+                 SELECT 1 FROM scrape_jobs \
+                 WHERE target_type = $1 AND target_payload = $2 AND dead_lettered_at IS NULL \
+             )",
+        )
+        .bind(job.target_type)
+        .bind(&job.target_payload)
+        .fetch_one(&mut *tx)
+        .await
+        .context("failed to check for a live duplicate job")?;
+
+        let outcome = if duplicate {
+            sqlx::query("DELETE FROM scrape_jobs WHERE id = $1")
+                .bind(job_id)
+                .execute(&mut *tx)
+                .await
+                .context("failed to drop duplicate dead-lettered job")?;
+            RequeueOutcome::Duplicate
+        } else {
+            let job = sqlx::query_as::<_, ScrapeJob>(
+                "UPDATE scrape_jobs \
+                 SET dead_lettered_at = NULL, last_error = NULL, retry_count = 0, \
+                     execute_at = NOW(), queued_at = NOW() \
+                 WHERE id = $1 \
+                 RETURNING *",
+            )
+            .bind(job_id)
+            .fetch_one(&mut *tx)
+            .await
+            .context("failed to requeue scrape job")?;
+            RequeueOutcome::Requeued(job)
+        };
+
+        tx.commit()
+            .await
+            .context("failed to commit requeue transaction")?;
+
+        let event = match &outcome {
+            RequeueOutcome::Requeued(job) => ScrapeJobEvent::Created {
+                job: ScrapeJobDto::from(job),
+            },
+            _ => ScrapeJobEvent::Deleted { id: job_id },
+        };
+        self.ctx.events().publish(DomainEvent::ScrapeJob(event));
+
+        Ok(outcome)
+    }
+
     /// Force-unlock all jobs that have a non-NULL `locked_at`.
     ///
     /// Intended to be called once at startup to recover jobs left locked by
@@ -271,6 +416,9 @@ impl<'a> ScrapeJobOps<'a> {
     }
 
     /// Insert a scrape job result log entry.
+    ///
+    /// A successful subject scrape also advances the subject's
+    /// `subject_scrape_times` row in the same statement.
     #[allow(clippy::too_many_arguments)]
     pub async fn insert_result(
         &self,
@@ -287,13 +435,25 @@ impl<'a> ScrapeJobOps<'a> {
     ) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO scrape_job_results (
-                target_type, payload, priority,
-                queued_at, started_at, duration_ms,
-                success, error_message, retry_count,
-                courses_fetched, courses_changed, courses_unchanged,
-                audits_generated, metrics_generated
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            WITH inserted AS (
+                INSERT INTO scrape_job_results (
+                    target_type, payload, priority,
+                    queued_at, started_at, duration_ms,
+                    success, error_message, retry_count,
+                    courses_fetched, courses_changed, courses_unchanged,
+                    audits_generated, metrics_generated, diff_summary
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                RETURNING target_type, payload, success, completed_at
+            )
+            INSERT INTO subject_scrape_times (term_code, subject_code, last_scraped_at)
+            SELECT payload->>'term', payload->>'subject', completed_at
+            FROM inserted
+            WHERE target_type = 'Subject' AND success
+              AND payload->>'term' IS NOT NULL AND payload->>'subject' IS NOT NULL
+            ON CONFLICT (term_code, subject_code) DO UPDATE
+                SET last_scraped_at = GREATEST(
+                    subject_scrape_times.last_scraped_at, EXCLUDED.last_scraped_at
+                )
             "#,
         )
         .bind(target_type)
@@ -310,6 +470,7 @@ impl<'a> ScrapeJobOps<'a> {
         .bind(counts.map(|c| c.courses_unchanged))
         .bind(counts.map(|c| c.audits_generated))
         .bind(counts.map(|c| c.metrics_generated))
+        .bind(counts.and_then(|c| c.diff.as_ref()).map(sqlx::types::Json))
         .execute(self.ctx.pool())
         .await
         .context("failed to insert scrape job result")?;
@@ -320,7 +481,8 @@ impl<'a> ScrapeJobOps<'a> {
     /// Find existing job payloads matching the given target type and candidates.
     ///
     /// Returns a set of stringified JSON payloads that already exist in the queue,
-    /// used for deduplication when scheduling new jobs.
+    /// used for deduplication when scheduling new jobs. Dead-lettered jobs don't
+    /// count, so a target that failed once is still scheduled normally.
     pub async fn find_existing_payloads(
         &self,
         target_type: TargetType,
@@ -328,7 +490,7 @@ impl<'a> ScrapeJobOps<'a> {
     ) -> Result<HashSet<String>> {
         let existing_jobs: Vec<(serde_json::Value,)> = sqlx::query_as(
             "SELECT target_payload FROM scrape_jobs
-             WHERE target_type = $1 AND target_payload = ANY($2)",
+             WHERE target_type = $1 AND target_payload = ANY($2) AND dead_lettered_at IS NULL",
         )
         .bind(target_type)
         .bind(candidate_payloads)
@@ -0,0 +1,196 @@
+//! Query parsing and relevance ranking for course text search.
+//!
+//! User input is compiled into a Postgres `tsquery` that runs against the
+//! weighted documents in `course_search_index` (see [`super::search_index`]).
+//! The syntax is deliberately small:
+//!
+//! - bare words must all match (`data structures`);
+//! - `"quoted text"` matches the words in sequence;
+//! - a trailing `*` matches any word with that prefix (`algo*`), and the last
+//!   bare word is always prefix-matched so results keep up with typing.
+//!
+//! Anything else is stripped, so no input can produce a `tsquery` syntax error.
+
+/// Relative weights for the four `tsvector` classes, used by `ts_rank`.
+///
+/// Index documents put course codes in class A, titles in B, instructor names
+/// in C, and catalog, subject and attribute descriptions in D. Weights must
+/// lie in `[0, 1]`; `ts_rank` rejects anything larger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchWeights {
+    pub code: f32,
+    pub title: f32,
+    pub instructor: f32,
+    pub extra: f32,
+}
+
+impl Default for SearchWeights {
+    /// Postgres' built-in `ts_rank` weights.
+    fn default() -> Self {
+        Self {
+            code: 1.0,
+            title: 0.4,
+            instructor: 0.2,
+            extra: 0.1,
+        }
+    }
+}
+
+impl SearchWeights {
+    /// Weights in the `{D, C, B, A}` order `ts_rank` expects.
+    pub fn as_array(&self) -> Vec<f32> {
+        vec![self.extra, self.instructor, self.title, self.code]
+    }
+}
+
+/// One unit of a parsed query: a run of words that must appear in order.
+#[derive(Debug, PartialEq)]
+struct Term {
+    words: Vec<String>,
+    prefix: bool,
+}
+
+impl Term {
+    /// A prefix marker applies to the final word only.
+    fn render(&self) -> String {
+        let mut out = self.words.join(" <-> ");
+        if self.prefix {
+            out.push_str(":*");
+        }
+        out
+    }
+}
+
+/// Split text into lowercase alphanumeric words.
+fn split_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn parse_terms(input: &str) -> Vec<Term> {
+    let mut terms = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        rest = rest.trim_start();
+        if let Some(quoted) = rest.strip_prefix('"') {
+            // An unclosed quote runs to the end of the input.
+            let (phrase, tail) = quoted.split_once('"').unwrap_or((quoted, ""));
+            let words = split_words(phrase);
+            if !words.is_empty() {
+                terms.push(Term {
+                    words,
+                    prefix: false,
+                });
+            }
+            rest = tail;
+            continue;
+        }
+
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '"')
+            .unwrap_or(rest.len());
+        let (token, tail) = rest.split_at(end);
+        rest = tail;
+
+        // Punctuation inside a word ("o'brien", "c++") splits it the same
+        // way the index parser does, so treat the pieces as a phrase.
+        let words = split_words(token);
+        if !words.is_empty() {
+            terms.push(Term {
+                words,
+                prefix: token.ends_with('*'),
+            });
+        }
+    }
+
+    // Type-ahead: the word being typed is probably incomplete.
+    if let Some(last) = terms.last_mut()
+        && !input.trim_end().ends_with('"')
+    {
+        last.prefix = true;
+    }
+    terms
+}
+
+/// Compile user input into `tsquery` text for `to_tsquery('simple_unaccent', ...)`.
+///
+/// Returns `None` when the input has no searchable words.
+pub fn to_tsquery(input: &str) -> Option<String> {
+    let terms = parse_terms(input);
+    if terms.is_empty() {
+        return None;
+    }
+    Some(
+        terms
+            .iter()
+            .map(|t| {
+                if t.words.len() > 1 {
+                    format!("({})", t.render())
+                } else {
+                    t.render()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" & "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_words_are_anded_with_last_as_prefix() {
+        assert_eq!(
+            to_tsquery("data struct").as_deref(),
+            Some("data & struct:*")
+        );
+        assert_eq!(to_tsquery("Calculus").as_deref(), Some("calculus:*"));
+    }
+
+    #[test]
+    fn quoted_phrases_match_in_order() {
+        assert_eq!(
+            to_tsquery("\"linear algebra\" honors").as_deref(),
+            Some("(linear <-> algebra) & honors:*")
+        );
+        // A closing quote ends the phrase without a prefix.
+        assert_eq!(
+            to_tsquery("\"operating systems\"").as_deref(),
+            Some("(operating <-> systems)")
+        );
+        assert_eq!(
+            to_tsquery("\"intro to").as_deref(),
+            Some("(intro <-> to:*)")
+        );
+    }
+
+    #[test]
+    fn explicit_prefixes() {
+        assert_eq!(
+            to_tsquery("algo* design").as_deref(),
+            Some("algo:* & design:*")
+        );
+    }
+
+    #[test]
+    fn operators_and_punctuation_are_stripped() {
+        assert_eq!(
+            to_tsquery("o'brien & !(cs) |").as_deref(),
+            Some("(o <-> brien) & cs:*")
+        );
+        assert_eq!(to_tsquery("  &| !  "), None);
+        assert_eq!(to_tsquery("\"\""), None);
+    }
+
+    #[test]
+    fn weights_are_ordered_d_to_a() {
+        assert_eq!(
+            SearchWeights::default().as_array(),
+            vec![0.1, 0.2, 0.4, 1.0]
+        );
+    }
+}
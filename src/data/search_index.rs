@@ -0,0 +1,102 @@
+//! Database functions for the term-scoped course search index.
+//!
+//! Backed by the `course_search_index` table. Each refresh rebuilds the rows
+//! for a single term inside a transaction, so readers never observe a
+//! partially rebuilt term. Documents weight course codes (A), titles (B),
+//! instructor names (C), and catalog, subject and attribute descriptions (D);
+//! see [`super::search`] for how queries are ranked against them.
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+
+/// Version of the document shape built by [`refresh_term`].
+///
+/// Bump it whenever the document changes so the startup backfill rebuilds
+/// rows built with an older shape.
+const DOCUMENT_VERSION: i16 = 2;
+
+/// Rebuild the search index rows for a single term.
+///
+/// Returns the number of courses indexed.
+pub async fn refresh_term(pool: &PgPool, term_code: &str) -> Result<u64> {
+    let mut tx = pool
+        .begin()
+        .await
+        .context("failed to begin search index transaction")?;
+
+    sqlx::query("DELETE FROM course_search_index WHERE term_code = $1")
+        .bind(term_code)
+        .execute(&mut *tx)
+        .await
+        .context("failed to clear search index for term")?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO course_search_index (course_id, term_code, document, document_version, refreshed_at)
+        SELECT
+            c.id,
+            c.term_code,
+            setweight(to_tsvector('simple_unaccent', c.subject || ' ' || c.course_number), 'A')
+                || setweight(to_tsvector('simple_unaccent', coalesce(c.title, '')), 'B')
+                || setweight(
+                    to_tsvector('simple_unaccent', coalesce(string_agg(i.display_name, ' '), '')),
+                    'C'
+                )
+                || setweight(
+                    to_tsvector(
+                        'simple_unaccent',
+                        concat_ws(' ', c.description, subj.description, attrs.descriptions)
+                    ),
+                    'D'
+                ),
+            $2,
+            NOW()
+        FROM courses c
+        LEFT JOIN course_instructors ci ON ci.course_id = c.id
+        LEFT JOIN instructors i ON i.id = ci.instructor_id
+        LEFT JOIN reference_data subj ON subj.category = 'subject' AND subj.code = c.subject
+        LEFT JOIN LATERAL (
+            SELECT string_agg(rd.description, ' ') AS descriptions
+            FROM jsonb_array_elements_text(c.attributes) AS a(code)
+            JOIN reference_data rd ON rd.category = 'attribute' AND rd.code = a.code
+        ) attrs ON true
+        WHERE c.term_code = $1
+        GROUP BY c.id, subj.description, attrs.descriptions
+        "#,
+    )
+    .bind(term_code)
+    .bind(DOCUMENT_VERSION)
+    .execute(&mut *tx)
+    .await
+    .context("failed to rebuild search index for term")?;
+
+    tx.commit()
+        .await
+        .context("failed to commit search index refresh")?;
+
+    Ok(result.rows_affected())
+}
+
+/// Term codes that have courses but no search index rows, or rows built with
+/// an older document shape.
+///
+/// Used on startup to backfill terms scraped before the index existed and to
+/// rebuild terms in place after the document changes.
+pub async fn stale_terms(pool: &PgPool) -> Result<Vec<String>> {
+    let rows: Vec<(String,)> = sqlx::query_as(
+        r#"
+        SELECT DISTINCT c.term_code
+        FROM courses c
+        WHERE NOT EXISTS (
+            SELECT 1 FROM course_search_index s
+            WHERE s.term_code = c.term_code AND s.document_version = $1
+        )
+        ORDER BY c.term_code DESC
+        "#,
+    )
+    .bind(DOCUMENT_VERSION)
+    .fetch_all(pool)
+    .await
+    .context("failed to list stale search index terms")?;
+    Ok(rows.into_iter().map(|(code,)| code).collect())
+}
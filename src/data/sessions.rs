@@ -1,8 +1,17 @@
 //! Database query functions for user sessions.
+//!
+//! Sessions slide: once less than half of [`SESSION_DURATION_SECS`] remains,
+//! the next request renews the expiry and re-issues the cookie, up to
+//! [`SESSION_MAX_LIFETIME_SECS`] after login.
+
+use std::net::IpAddr;
 
 use anyhow::Context;
+use chrono::{DateTime, Utc};
 use rand::Rng;
+use serde::Serialize;
 use sqlx::PgPool;
+use ts_rs::TS;
 
 use super::models::UserSession;
 use anyhow::Result;
@@ -10,31 +19,82 @@ use anyhow::Result;
 /// Session lifetime: 7 days (in seconds).
 pub const SESSION_DURATION_SECS: u64 = 7 * 24 * 3600;
 
+/// Absolute cap on a session's lifetime, however often it is renewed: 30 days.
+pub const SESSION_MAX_LIFETIME_SECS: u64 = 30 * 24 * 3600;
+
+/// Longest user agent stored with a session.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// One of a user's unexpired sessions, as shown to its owner.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ActiveSession {
+    /// Public session ID; the session token itself is never exposed.
+    pub id: String,
+    /// Browser and OS, when recognizable from the user agent.
+    #[sqlx(skip)]
+    pub device: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_active_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Whether this is the session making the request.
+    pub current: bool,
+}
+
+/// Whether a session is past its half-life and can still be extended.
+pub fn renewal_due(
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> bool {
+    let half_life = chrono::Duration::seconds(SESSION_DURATION_SECS as i64 / 2);
+    let hard_limit = created_at + chrono::Duration::seconds(SESSION_MAX_LIFETIME_SECS as i64);
+    expires_at > now && expires_at - now < half_life && expires_at < hard_limit
+}
+
 /// Generate a cryptographically random 32-byte hex token.
 fn generate_token() -> String {
     let bytes: [u8; 32] = rand::rng().random();
     bytes.iter().map(|b| format!("{b:02x}")).collect()
 }
 
-/// Create a new session for a user with the given duration.
+/// Whether `token` has the shape [`generate_token`] produces, so anything else
+/// can be turned away without a query.
+pub fn is_well_formed_token(token: &str) -> bool {
+    token.len() == 64
+        && token
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Create a new session for a user with the given duration, recording the
+/// client it was created from.
 pub async fn create_session(
     pool: &PgPool,
     user_id: i64,
     duration: std::time::Duration,
+    user_agent: Option<&str>,
+    ip: Option<IpAddr>,
 ) -> Result<UserSession> {
     let token = generate_token();
     let duration_secs = duration.as_secs() as i64;
+    let user_agent = user_agent.map(|ua| truncate(ua, MAX_USER_AGENT_LEN));
 
     sqlx::query_as::<_, UserSession>(
         r#"
-        INSERT INTO user_sessions (id, user_id, expires_at)
-        VALUES ($1, $2, now() + make_interval(secs => $3::double precision))
+        INSERT INTO user_sessions (id, user_id, expires_at, user_agent, ip_address)
+        VALUES ($1, $2, now() + make_interval(secs => $3::double precision), $4, $5)
         RETURNING *
         "#,
     )
     .bind(&token)
     .bind(user_id)
     .bind(duration_secs as f64)
+    .bind(user_agent)
+    .bind(ip.map(|ip| ip.to_string()))
     .fetch_one(pool)
     .await
     .context("failed to create session")
@@ -51,22 +111,42 @@ pub async fn get_session(pool: &PgPool, token: &str) -> Result<Option<UserSessio
     .context("failed to get session")
 }
 
-/// Update the last-active timestamp and extend session expiry (sliding window).
+/// Update the last-active timestamp.
+///
+/// Expiry is only extended by [`renew_session`], which the caller pairs with
+/// a fresh cookie so the browser and database agree on when it ends.
 pub async fn touch_session(pool: &PgPool, token: &str) -> Result<()> {
-    sqlx::query(
+    sqlx::query("UPDATE user_sessions SET last_active_at = now() WHERE id = $1")
+        .bind(token)
+        .execute(pool)
+        .await
+        .context("failed to touch session")?;
+    Ok(())
+}
+
+/// Extend an unexpired session by a full [`SESSION_DURATION_SECS`], capped at
+/// [`SESSION_MAX_LIFETIME_SECS`] after creation.
+///
+/// Returns the new expiry, or `None` if the session is gone or expired.
+pub async fn renew_session(pool: &PgPool, token: &str) -> Result<Option<DateTime<Utc>>> {
+    sqlx::query_scalar(
         r#"
         UPDATE user_sessions
         SET last_active_at = now(),
-            expires_at = now() + make_interval(secs => $2::double precision)
-        WHERE id = $1
+            expires_at = LEAST(
+                now() + make_interval(secs => $2::double precision),
+                created_at + make_interval(secs => $3::double precision)
+            )
+        WHERE id = $1 AND expires_at > now()
+        RETURNING expires_at
         "#,
     )
     .bind(token)
     .bind(SESSION_DURATION_SECS as f64)
-    .execute(pool)
+    .bind(SESSION_MAX_LIFETIME_SECS as f64)
+    .fetch_optional(pool)
     .await
-    .context("failed to touch session")?;
-    Ok(())
+    .context("failed to renew session")
 }
 
 /// Delete a session by token.
@@ -79,6 +159,96 @@ pub async fn delete_session(pool: &PgPool, token: &str) -> Result<()> {
     Ok(())
 }
 
+fn truncate(s: &str, max: usize) -> &str {
+    match s.char_indices().nth(max) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
+
+/// A user's unexpired sessions, most recently active first.
+///
+/// `current_token` marks the session making the request.
+pub async fn list_user_sessions(
+    pool: &PgPool,
+    user_id: i64,
+    current_token: Option<&str>,
+) -> Result<Vec<ActiveSession>> {
+    let mut sessions = sqlx::query_as::<_, ActiveSession>(
+        r#"
+        SELECT public_id::text AS id, user_agent, ip_address,
+               created_at, last_active_at, expires_at,
+               id IS NOT DISTINCT FROM $2 AS current
+        FROM user_sessions
+        WHERE user_id = $1 AND expires_at > now()
+        ORDER BY last_active_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .bind(current_token)
+    .fetch_all(pool)
+    .await
+    .context("failed to list user sessions")?;
+
+    for session in &mut sessions {
+        session.device = session.user_agent.as_deref().and_then(describe_user_agent);
+    }
+    Ok(sessions)
+}
+
+/// Delete one of a user's sessions by its public ID.
+///
+/// Returns the deleted session's token so the caller can evict it from
+/// caches, or `None` if the user has no such session.
+pub async fn revoke_user_session(
+    pool: &PgPool,
+    user_id: i64,
+    public_id: i64,
+) -> Result<Option<String>> {
+    sqlx::query_scalar(
+        "DELETE FROM user_sessions WHERE user_id = $1 AND public_id = $2 RETURNING id",
+    )
+    .bind(user_id)
+    .bind(public_id)
+    .fetch_optional(pool)
+    .await
+    .context("failed to revoke session")
+}
+
+/// Summarize a user agent as "Browser on OS", when either is recognizable.
+pub fn describe_user_agent(ua: &str) -> Option<String> {
+    // Order matters: Edge and Opera also claim Chrome, Chrome claims Safari.
+    let browser = [
+        ("Edg/", "Edge"),
+        ("OPR/", "Opera"),
+        ("Firefox/", "Firefox"),
+        ("Chrome/", "Chrome"),
+        ("Safari/", "Safari"),
+    ]
+    .into_iter()
+    .find(|(marker, _)| ua.contains(marker))
+    .map(|(_, name)| name);
+    // Android and ChromeOS also claim Linux; iPads may claim Mac OS X.
+    let os = [
+        ("Android", "Android"),
+        ("iPhone", "iOS"),
+        ("iPad", "iPadOS"),
+        ("CrOS", "ChromeOS"),
+        ("Windows", "Windows"),
+        ("Mac OS X", "macOS"),
+        ("Linux", "Linux"),
+    ]
+    .into_iter()
+    .find(|(marker, _)| ua.contains(marker))
+    .map(|(_, name)| name);
+
+    match (browser, os) {
+        (Some(browser), Some(os)) => Some(format!("{browser} on {os}")),
+        (Some(name), None) | (None, Some(name)) => Some(name.to_owned()),
+        (None, None) => None,
+    }
+}
+
 /// Delete all sessions for a user. Returns the number of sessions deleted.
 #[allow(dead_code)] // Available for admin user-deletion flow
 pub async fn delete_user_sessions(pool: &PgPool, user_id: i64) -> Result<u64> {
@@ -107,3 +277,57 @@ pub async fn cleanup_expired(pool: &PgPool) -> Result<u64> {
         .context("failed to cleanup expired sessions")?;
     Ok(result.rows_affected())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn renewal_waits_for_half_life() {
+        let now = Utc::now();
+        let created = now - Duration::days(1);
+        assert!(!renewal_due(created, now + Duration::days(6), now));
+        assert!(renewal_due(created, now + Duration::days(3), now));
+        assert!(!renewal_due(created, now - Duration::seconds(1), now));
+    }
+
+    #[test]
+    fn user_agents_are_summarized() {
+        let firefox =
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:128.0) Gecko/20100101 Firefox/128.0";
+        assert_eq!(
+            describe_user_agent(firefox).as_deref(),
+            Some("Firefox on Windows")
+        );
+
+        let edge = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 \
+                    (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36 Edg/126.0.0.0";
+        assert_eq!(describe_user_agent(edge).as_deref(), Some("Edge on macOS"));
+
+        let android = "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 \
+                       (KHTML, like Gecko) Chrome/126.0.0.0 Mobile Safari/537.36";
+        assert_eq!(
+            describe_user_agent(android).as_deref(),
+            Some("Chrome on Android")
+        );
+
+        assert_eq!(describe_user_agent("curl/8.5.0"), None);
+    }
+
+    #[test]
+    fn renewal_stops_at_max_lifetime() {
+        let now = Utc::now();
+        let created = now - Duration::days(29);
+        // Already extended to the cap; nothing more to give.
+        assert!(!renewal_due(created, created + Duration::days(30), now));
+        assert!(renewal_due(created, now + Duration::hours(12), now));
+    }
+
+    #[test]
+    fn generated_tokens_are_well_formed() {
+        assert!(is_well_formed_token(&generate_token()));
+        assert!(!is_well_formed_token("dev-admin"));
+        assert!(!is_well_formed_token(&"G".repeat(64)));
+    }
+}
@@ -0,0 +1,107 @@
+//! Downsampled per-day enrollment series, rolled up from `course_metrics`.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// Number of days covered by each sparkline, including the rollup day.
+pub const WINDOW_DAYS: i64 = 30;
+
+/// Rebuild every course's sparkline from the last [`WINDOW_DAYS`] of metrics,
+/// ending at `today` (UTC).
+///
+/// Each day takes the last snapshot recorded on it; days without a snapshot
+/// repeat the previous day. Only courses scraped within the window are rolled
+/// up; series start at the first day with data, so newly added courses get
+/// shorter arrays. Courses that fall out of the window lose their row.
+/// Returns the number of sparklines written.
+pub async fn rollup(pool: &PgPool, today: NaiveDate) -> Result<u64> {
+    let window_start = today - chrono::Days::new(WINDOW_DAYS as u64 - 1);
+    let mut tx = pool.begin().await?;
+
+    let written = sqlx::query(
+        r#"
+        WITH active AS (
+            SELECT id FROM courses WHERE last_scraped_at >= $1::date
+        ),
+        samples AS (
+            SELECT m.course_id, m.timestamp, m.enrollment,
+                   (m.timestamp AT TIME ZONE 'UTC')::date AS day
+            FROM active c
+            JOIN course_metrics m ON m.course_id = c.id
+            WHERE m.timestamp >= ($1::date::timestamp AT TIME ZONE 'UTC')
+              AND m.timestamp < (($2::date + 1)::timestamp AT TIME ZONE 'UTC')
+            UNION ALL
+            -- The last snapshot before the window counts toward its first
+            -- day, so courses whose enrollment hasn't moved still get a flat
+            -- series. Only that one row is read, not the whole history.
+            SELECT c.id, seed.timestamp, seed.enrollment, $1::date
+            FROM active c
+            CROSS JOIN LATERAL (
+                SELECT m.timestamp, m.enrollment
+                FROM course_metrics m
+                WHERE m.course_id = c.id
+                  AND m.timestamp < ($1::date::timestamp AT TIME ZONE 'UTC')
+                ORDER BY m.timestamp DESC
+                LIMIT 1
+            ) seed
+        ),
+        daily AS (
+            SELECT DISTINCT ON (course_id, day) course_id, day, enrollment
+            FROM samples
+            ORDER BY course_id, day, timestamp DESC
+        ),
+        bounds AS (
+            SELECT course_id, MIN(day) AS start_date FROM daily GROUP BY course_id
+        ),
+        grid AS (
+            SELECT b.course_id, b.start_date, g.day::date AS day, d.enrollment,
+                   COUNT(d.enrollment) OVER (PARTITION BY b.course_id ORDER BY g.day) AS grp
+            FROM bounds b
+            CROSS JOIN LATERAL generate_series(b.start_date, $2::date, interval '1 day') AS g(day)
+            LEFT JOIN daily d ON d.course_id = b.course_id AND d.day = g.day::date
+        ),
+        filled AS (
+            SELECT course_id, start_date, day,
+                   FIRST_VALUE(enrollment) OVER (PARTITION BY course_id, grp ORDER BY day) AS enrollment
+            FROM grid
+        )
+        INSERT INTO course_enrollment_sparklines (course_id, start_date, enrollment, updated_at)
+        SELECT course_id, start_date, array_agg(enrollment ORDER BY day), NOW()
+        FROM filled
+        GROUP BY course_id, start_date
+        ON CONFLICT (course_id) DO UPDATE SET
+            start_date = EXCLUDED.start_date,
+            enrollment = EXCLUDED.enrollment,
+            updated_at = EXCLUDED.updated_at
+        "#,
+    )
+    .bind(window_start)
+    .bind(today)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    // NOW() is fixed for the transaction, so anything older wasn't rewritten above.
+    sqlx::query("DELETE FROM course_enrollment_sparklines WHERE updated_at < NOW()")
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(written)
+}
+
+/// Batch-fetch sparklines for a set of courses, keyed by course id.
+pub async fn get_for_courses(pool: &PgPool, course_ids: &[i32]) -> Result<HashMap<i32, Vec<i32>>> {
+    if course_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows: Vec<(i32, Vec<i32>)> = sqlx::query_as(
+        "SELECT course_id, enrollment FROM course_enrollment_sparklines WHERE course_id = ANY($1)",
+    )
+    .bind(course_ids)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().collect())
+}
@@ -0,0 +1,162 @@
+//! Database operations for admin-managed subject code aliases.
+//!
+//! When a department is renamed (e.g. STA becomes STS), an alias row maps the
+//! retired code to its replacement. The `canonical_subject` and
+//! `expand_subject_codes` SQL functions read this table so search, course
+//! grade history, and RMP subject scoring see both codes as one subject.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+
+/// Domain errors for subject alias management.
+///
+/// The web layer downcasts `anyhow::Error` to this type to pick the HTTP status.
+#[derive(Debug, thiserror::Error)]
+pub enum SubjectAliasError {
+    #[error("subject alias not found")]
+    NotFound,
+    #[error("subject codes must be 1-8 letters or digits")]
+    InvalidCode,
+    #[error("an alias cannot point to itself")]
+    SelfAlias,
+    #[error("{0} is itself an alias; point at its current code instead")]
+    ChainedTarget(String),
+    #[error("{0} is the target of other aliases and cannot be retired")]
+    AliasHasAliases(String),
+}
+
+/// A subject alias row.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectAlias {
+    /// Retired subject code.
+    pub alias: String,
+    /// Current code the alias resolves to.
+    pub subject: String,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Uppercase and validate an admin-supplied subject code.
+pub fn normalize_code(code: &str) -> Result<String, SubjectAliasError> {
+    let code = code.trim().to_uppercase();
+    if code.is_empty() || code.len() > 8 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(SubjectAliasError::InvalidCode);
+    }
+    Ok(code)
+}
+
+/// List all aliases, grouped by current code.
+pub async fn list_aliases(pool: &PgPool) -> Result<Vec<SubjectAlias>> {
+    sqlx::query_as::<_, SubjectAlias>(
+        "SELECT alias, subject, note, created_at FROM subject_aliases ORDER BY subject, alias",
+    )
+    .fetch_all(pool)
+    .await
+    .context("failed to list subject aliases")
+}
+
+/// Create or repoint the alias for `alias`.
+pub async fn upsert_alias(
+    pool: &PgPool,
+    alias: &str,
+    subject: &str,
+    note: Option<&str>,
+) -> Result<SubjectAlias> {
+    let alias = normalize_code(alias)?;
+    let subject = normalize_code(subject)?;
+    if alias == subject {
+        return Err(SubjectAliasError::SelfAlias.into());
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("failed to begin subject alias transaction")?;
+
+    // Row locks can't cover aliases that don't exist yet, so two concurrent
+    // writes (STA -> STS and STS -> STA) could each pass the chain checks and
+    // insert a cycle. Serialize alias writers on the table (readers aren't
+    // blocked), then resolve both codes from their locked rows.
+    sqlx::query("LOCK TABLE subject_aliases IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut *tx)
+        .await
+        .context("failed to lock subject aliases")?;
+    let linked: Vec<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT alias, subject FROM subject_aliases
+        WHERE alias IN ($1, $2) OR subject IN ($1, $2)
+        FOR UPDATE
+        "#,
+    )
+    .bind(&alias)
+    .bind(&subject)
+    .fetch_all(&mut *tx)
+    .await
+    .context("failed to check subject alias chains")?;
+    if linked.iter().any(|(a, _)| *a == subject) {
+        return Err(SubjectAliasError::ChainedTarget(subject).into());
+    }
+    if linked.iter().any(|(_, s)| *s == alias) {
+        return Err(SubjectAliasError::AliasHasAliases(alias).into());
+    }
+
+    let row = sqlx::query_as::<_, SubjectAlias>(
+        r#"
+        INSERT INTO subject_aliases (alias, subject, note)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (alias) DO UPDATE SET subject = EXCLUDED.subject, note = EXCLUDED.note
+        RETURNING alias, subject, note, created_at
+        "#,
+    )
+    .bind(&alias)
+    .bind(&subject)
+    .bind(note)
+    .fetch_one(&mut *tx)
+    .await
+    .context("failed to upsert subject alias")?;
+
+    tx.commit()
+        .await
+        .context("failed to commit subject alias")?;
+    Ok(row)
+}
+
+/// Delete the alias for `alias`.
+pub async fn delete_alias(pool: &PgPool, alias: &str) -> Result<()> {
+    let alias = normalize_code(alias)?;
+    let result = sqlx::query("DELETE FROM subject_aliases WHERE alias = $1")
+        .bind(&alias)
+        .execute(pool)
+        .await
+        .context("failed to delete subject alias")?;
+
+    if result.rows_affected() == 0 {
+        return Err(SubjectAliasError::NotFound.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_code_uppercases_and_trims() {
+        assert_eq!(normalize_code(" sta ").unwrap(), "STA");
+    }
+
+    #[test]
+    fn normalize_code_rejects_bad_codes() {
+        for code in ["", "C S", "TOOLONGCODE", "C-S"] {
+            assert!(matches!(
+                normalize_code(code),
+                Err(SubjectAliasError::InvalidCode)
+            ));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Per-subject landing page aggregates.
+//!
+//! Every query matches the subject through `expand_subject_codes`, so a
+//! renamed department's history (see [`super::subject_aliases`]) is counted
+//! under its current code.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use super::course_types::InstructorRating;
+use super::scoring::{ScoreRow, build_rating_from_score_row};
+use super::unsigned::Count;
+use crate::banner::models::terms::Term;
+
+/// Top-rated instructors listed per subject.
+const TOP_INSTRUCTOR_LIMIT: i64 = 10;
+
+/// Distinct courses at one level (1000, 2000, ...), across all terms.
+#[derive(Debug, Clone, Serialize, TS, ToSchema, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectLevelCount {
+    /// Leading digit of the course number followed by "000".
+    pub level: String,
+    #[schema(value_type = u32)]
+    pub course_count: Count,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectInstructor {
+    pub id: i32,
+    pub slug: String,
+    pub display_name: String,
+    pub rating: InstructorRating,
+}
+
+/// Response-weighted BlueBook course rating across the subject's evaluations.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectBlueBook {
+    pub avg_course_rating: f32,
+    #[schema(value_type = u32)]
+    pub total_responses: Count,
+}
+
+/// One term the subject has sections in.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectTerm {
+    pub code: String,
+    pub slug: String,
+    pub description: String,
+    #[schema(value_type = u32)]
+    pub course_count: Count,
+    #[schema(value_type = u32)]
+    pub section_count: Count,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectOverview {
+    /// Current subject code; aliases resolve to it.
+    pub code: String,
+    pub description: Option<String>,
+    pub levels: Vec<SubjectLevelCount>,
+    pub top_instructors: Vec<SubjectInstructor>,
+    pub bluebook: Option<SubjectBlueBook>,
+    /// Newest first.
+    pub terms: Vec<SubjectTerm>,
+}
+
+/// Gather landing page stats for a subject, or `None` if it has no sections.
+///
+/// `description` is left empty for the caller to fill from reference data.
+pub async fn get_overview(pool: &PgPool, code: &str) -> Result<Option<SubjectOverview>> {
+    let code: String = sqlx::query_scalar("SELECT canonical_subject(UPPER($1))")
+        .bind(code)
+        .fetch_one(pool)
+        .await
+        .context("failed to resolve subject code")?;
+
+    let (terms, levels, top_instructors, bluebook) = tokio::try_join!(
+        subject_terms(pool, &code),
+        subject_levels(pool, &code),
+        top_instructors(pool, &code),
+        bluebook_summary(pool, &code),
+    )?;
+
+    if terms.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(SubjectOverview {
+        code,
+        description: None,
+        levels,
+        top_instructors,
+        bluebook,
+        terms,
+    }))
+}
+
+async fn subject_terms(pool: &PgPool, code: &str) -> Result<Vec<SubjectTerm>> {
+    let rows: Vec<(String, Count, Count)> = sqlx::query_as(
+        "SELECT term_code, COUNT(DISTINCT course_number)::int, COUNT(*)::int \
+         FROM courses \
+         WHERE subject = ANY(expand_subject_codes(ARRAY[$1])) \
+         GROUP BY term_code \
+         ORDER BY term_code DESC",
+    )
+    .bind(code)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch subject terms")?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(term_code, course_count, section_count)| {
+            let term: Term = term_code.parse().ok()?;
+            Some(SubjectTerm {
+                slug: term.slug(),
+                description: term.description(),
+                code: term_code,
+                course_count,
+                section_count,
+            })
+        })
+        .collect())
+}
+
+async fn subject_levels(pool: &PgPool, code: &str) -> Result<Vec<SubjectLevelCount>> {
+    sqlx::query_as::<_, SubjectLevelCount>(
+        "SELECT LEFT(course_number, 1) || '000' AS level, \
+                COUNT(DISTINCT course_number)::int AS course_count \
+         FROM courses \
+         WHERE subject = ANY(expand_subject_codes(ARRAY[$1])) \
+           AND course_number ~ '^[0-9]' \
+         GROUP BY 1 \
+         ORDER BY 1",
+    )
+    .bind(code)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch subject levels")
+}
+
+async fn top_instructors(pool: &PgPool, code: &str) -> Result<Vec<SubjectInstructor>> {
+    #[derive(sqlx::FromRow)]
+    struct Row {
+        id: i32,
+        slug: String,
+        display_name: String,
+        display_score: f32,
+        sort_score: f32,
+        ci_lower: f32,
+        ci_upper: f32,
+        confidence: f32,
+        source: String,
+        rmp_count: i32,
+        bb_count: i32,
+    }
+
+    let rows = sqlx::query_as::<_, Row>(
+        "SELECT i.id, i.slug, i.display_name, \
+                sc.display_score, sc.sort_score, sc.ci_lower, sc.ci_upper, \
+                sc.confidence, sc.source, sc.rmp_count, sc.bb_count \
+         FROM instructors i \
+         JOIN instructor_scores sc ON sc.instructor_id = i.id \
+         WHERE i.slug IS NOT NULL \
+           AND EXISTS ( \
+               SELECT 1 FROM course_instructors ci \
+               JOIN courses c ON c.id = ci.course_id \
+               WHERE ci.instructor_id = i.id \
+                 AND c.subject = ANY(expand_subject_codes(ARRAY[$1])) \
+           ) \
+         ORDER BY sc.sort_score DESC, i.display_name \
+         LIMIT $2",
+    )
+    .bind(code)
+    .bind(TOP_INSTRUCTOR_LIMIT)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch subject top instructors")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| SubjectInstructor {
+            rating: build_rating_from_score_row(&ScoreRow {
+                display_score: r.display_score,
+                sort_score: r.sort_score,
+                ci_lower: r.ci_lower,
+                ci_upper: r.ci_upper,
+                confidence: r.confidence,
+                source: r.source,
+                rmp_count: r.rmp_count,
+                bb_count: r.bb_count,
+            }),
+            id: r.id,
+            slug: r.slug,
+            display_name: r.display_name,
+        })
+        .collect())
+}
+
+async fn bluebook_summary(pool: &PgPool, code: &str) -> Result<Option<SubjectBlueBook>> {
+    let (avg, total): (Option<f32>, Option<i64>) = sqlx::query_as(
+        "SELECT (SUM(course_rating * course_response_count) / SUM(course_response_count))::real, \
+                SUM(course_response_count)::bigint \
+         FROM bluebook_evaluations \
+         WHERE subject = ANY(expand_subject_codes(ARRAY[$1])) \
+           AND course_rating IS NOT NULL \
+           AND course_response_count > 0",
+    )
+    .bind(code)
+    .fetch_one(pool)
+    .await
+    .context("failed to fetch subject BlueBook summary")?;
+
+    Ok(match (avg, total) {
+        (Some(avg_course_rating), Some(n)) => {
+            Count::try_from(n)
+                .ok()
+                .map(|total_responses| SubjectBlueBook {
+                    avg_course_rating,
+                    total_responses,
+                })
+        }
+        _ => None,
+    })
+}
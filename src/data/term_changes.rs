@@ -0,0 +1,374 @@
+//! Day-by-day summary of catalog changes in a term, from `course_audits`.
+//!
+//! Only the changes a student would notice in a "what's new" feed are
+//! reported: sections first listed or dropped, instructor reassignments, and
+//! changed meeting days or times. Enrollment churn and other field edits are
+//! left to the audit log.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use super::models::{DayOfWeek, DbMeetingTime};
+use super::unsigned::Count;
+use crate::banner::models::meetings::TimeRange;
+use crate::display::CAMPUS_TZ;
+
+/// Sections listed per kind per day; the counts cover the rest.
+pub const MAX_SECTIONS_PER_KIND: usize = 100;
+
+/// Audit fields the feed reads.
+const FEED_FIELDS: [&str; 4] = ["initial", "removed", "instructors", "meeting_times"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ChangedSection {
+    pub crn: String,
+    pub subject: String,
+    pub course_number: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct InstructorReassignment {
+    pub section: ChangedSection,
+    /// Instructor names before the first change that day.
+    pub from: Vec<String>,
+    /// Instructor names after the last change that day.
+    pub to: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct MeetingTimeChange {
+    pub section: ChangedSection,
+    pub from: Vec<DbMeetingTime>,
+    pub to: Vec<DbMeetingTime>,
+}
+
+/// How many changes of each kind happened, including any not listed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TermChangeCounts {
+    #[schema(value_type = u32)]
+    pub added: Count,
+    #[schema(value_type = u32)]
+    pub removed: Count,
+    #[schema(value_type = u32)]
+    pub instructor_changes: Count,
+    #[schema(value_type = u32)]
+    pub time_changes: Count,
+}
+
+/// Changes on one calendar day, in campus time.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TermChangeDay {
+    pub date: NaiveDate,
+    /// When the last change that day was recorded.
+    pub last_changed: DateTime<Utc>,
+    pub counts: TermChangeCounts,
+    pub added: Vec<ChangedSection>,
+    pub removed: Vec<ChangedSection>,
+    pub instructor_changes: Vec<InstructorReassignment>,
+    pub time_changes: Vec<MeetingTimeChange>,
+}
+
+/// One audit row of a feed field, joined with its section.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FeedAudit {
+    pub course_id: i32,
+    pub timestamp: DateTime<Utc>,
+    pub date: NaiveDate,
+    pub field_changed: String,
+    pub old_value: Option<Value>,
+    pub new_value: Value,
+    pub crn: String,
+    pub subject: String,
+    pub course_number: String,
+    pub title: String,
+}
+
+/// Summarize changes to `term_code` sections since `since`, newest day first.
+///
+/// With `subject`, only that subject's sections are considered.
+pub async fn list_changes(
+    pool: &PgPool,
+    term_code: &str,
+    subject: Option<&str>,
+    since: DateTime<Utc>,
+) -> Result<Vec<TermChangeDay>> {
+    let rows = sqlx::query_as::<_, FeedAudit>(
+        "SELECT a.course_id, a.timestamp, (a.timestamp AT TIME ZONE $3)::date AS date, \
+                a.field_changed, a.old_value, a.new_value, \
+                c.crn, c.subject, c.course_number, c.title \
+         FROM course_audits a \
+         JOIN courses c ON c.id = a.course_id \
+         WHERE c.term_code = $1 \
+           AND a.timestamp >= $2 \
+           AND a.field_changed = ANY($4) \
+           AND ($5::text IS NULL OR c.subject = $5) \
+         ORDER BY a.timestamp, a.id",
+    )
+    .bind(term_code)
+    .bind(since)
+    .bind(CAMPUS_TZ.name())
+    .bind(&FEED_FIELDS[..])
+    .bind(subject)
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch term change audits")?;
+
+    Ok(summarize(rows))
+}
+
+/// Per-day state for one section's instructor or meeting changes: the value
+/// before the day's first change and after its last.
+struct Span {
+    section: ChangedSection,
+    from: Value,
+    to: Value,
+}
+
+#[derive(Default)]
+struct DayBuilder {
+    last_changed: DateTime<Utc>,
+    added: Vec<ChangedSection>,
+    removed: Vec<ChangedSection>,
+    instructors: BTreeMap<i32, Span>,
+    meetings: BTreeMap<i32, Span>,
+}
+
+impl FeedAudit {
+    fn section(&self) -> ChangedSection {
+        ChangedSection {
+            crn: self.crn.clone(),
+            subject: self.subject.clone(),
+            course_number: self.course_number.clone(),
+            title: self.title.clone(),
+        }
+    }
+}
+
+fn merge_span(spans: &mut BTreeMap<i32, Span>, row: FeedAudit) {
+    match spans.entry(row.course_id) {
+        Entry::Occupied(mut span) => span.get_mut().to = row.new_value,
+        Entry::Vacant(slot) => {
+            slot.insert(Span {
+                section: row.section(),
+                from: row.old_value.unwrap_or(Value::Null),
+                to: row.new_value,
+            });
+        }
+    }
+}
+
+/// Days and times of each meeting; locations and date ranges are ignored.
+fn schedule_of(meetings: &[DbMeetingTime]) -> Vec<(&BTreeSet<DayOfWeek>, Option<&TimeRange>)> {
+    meetings
+        .iter()
+        .map(|m| (&m.days, m.time_range.as_ref()))
+        .collect()
+}
+
+fn parse_meetings(value: Value) -> Vec<DbMeetingTime> {
+    serde_json::from_value(value).unwrap_or_default()
+}
+
+fn parse_names(value: Value) -> Vec<String> {
+    serde_json::from_value(value).unwrap_or_default()
+}
+
+/// Group audit rows (oldest first) into days, newest day first.
+///
+/// A section edited several times in a day is reported once, from its value
+/// before the first edit to its value after the last; edits that cancel out
+/// are dropped.
+pub fn summarize(rows: Vec<FeedAudit>) -> Vec<TermChangeDay> {
+    let mut days: BTreeMap<NaiveDate, DayBuilder> = BTreeMap::new();
+    for mut row in rows {
+        let day = days.entry(row.date).or_default();
+        day.last_changed = day.last_changed.max(row.timestamp);
+        let field = std::mem::take(&mut row.field_changed);
+        match field.as_str() {
+            "initial" => day.added.push(row.section()),
+            "removed" => day.removed.push(row.section()),
+            "instructors" => merge_span(&mut day.instructors, row),
+            "meeting_times" => merge_span(&mut day.meetings, row),
+            _ => {}
+        }
+    }
+
+    days.into_iter()
+        .rev()
+        .map(|(date, day)| build_day(date, day))
+        .collect()
+}
+
+fn build_day(date: NaiveDate, day: DayBuilder) -> TermChangeDay {
+    let instructor_changes: Vec<InstructorReassignment> = day
+        .instructors
+        .into_values()
+        .filter_map(|span| {
+            let (from, to) = (parse_names(span.from), parse_names(span.to));
+            (from != to).then_some(InstructorReassignment {
+                section: span.section,
+                from,
+                to,
+            })
+        })
+        .collect();
+
+    let time_changes: Vec<MeetingTimeChange> = day
+        .meetings
+        .into_values()
+        .filter_map(|span| {
+            let (from, to) = (parse_meetings(span.from), parse_meetings(span.to));
+            (schedule_of(&from) != schedule_of(&to)).then_some(MeetingTimeChange {
+                section: span.section,
+                from,
+                to,
+            })
+        })
+        .collect();
+
+    let count = |n: usize| Count::try_from(n).unwrap_or_default();
+    let counts = TermChangeCounts {
+        added: count(day.added.len()),
+        removed: count(day.removed.len()),
+        instructor_changes: count(instructor_changes.len()),
+        time_changes: count(time_changes.len()),
+    };
+
+    TermChangeDay {
+        date,
+        last_changed: day.last_changed,
+        counts,
+        added: truncated(day.added),
+        removed: truncated(day.removed),
+        instructor_changes: truncated(instructor_changes),
+        time_changes: truncated(time_changes),
+    }
+}
+
+fn truncated<T>(mut items: Vec<T>) -> Vec<T> {
+    items.truncate(MAX_SECTIONS_PER_KIND);
+    items
+}
+
+/// Sum the per-day counts.
+pub fn total_counts(days: &[TermChangeDay]) -> TermChangeCounts {
+    let sum = |f: fn(&TermChangeCounts) -> Count| {
+        Count::new(days.iter().map(|d| f(&d.counts).get()).sum())
+    };
+    TermChangeCounts {
+        added: sum(|c| c.added),
+        removed: sum(|c| c.removed),
+        instructor_changes: sum(|c| c.instructor_changes),
+        time_changes: sum(|c| c.time_changes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn row(course_id: i32, day: u32, field: &str, old: Option<Value>, new: Value) -> FeedAudit {
+        FeedAudit {
+            course_id,
+            timestamp: DateTime::from_timestamp(1_772_400_000 + i64::from(day) * 86_400, 0)
+                .unwrap(),
+            date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            field_changed: field.to_owned(),
+            old_value: old,
+            new_value: new,
+            crn: format!("{}", 10000 + course_id),
+            subject: "CS".to_owned(),
+            course_number: "1083".to_owned(),
+            title: "Intro".to_owned(),
+        }
+    }
+
+    fn meeting(days: &[&str], start: &str) -> Value {
+        json!([{
+            "timeRange": { "start": start, "end": "11:15:00" },
+            "dateRange": { "start": "2026-01-20", "end": "2026-05-06" },
+            "days": days,
+            "location": null,
+            "meetingType": "FF",
+            "meetingScheduleType": "LEC",
+        }])
+    }
+
+    #[test]
+    fn groups_by_day_newest_first() {
+        let days = summarize(vec![
+            row(1, 2, "initial", None, json!({})),
+            row(2, 2, "initial", None, json!({})),
+            row(1, 4, "removed", Some(json!(false)), json!(true)),
+        ]);
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date.to_string(), "2026-03-04");
+        assert_eq!(days[0].counts.removed.get(), 1);
+        assert_eq!(days[1].counts.added.get(), 2);
+        assert_eq!(total_counts(&days).added.get(), 2);
+    }
+
+    #[test]
+    fn collapses_same_day_reassignments() {
+        let days = summarize(vec![
+            row(1, 2, "instructors", Some(json!(["A"])), json!(["B"])),
+            row(1, 2, "instructors", Some(json!(["B"])), json!(["C"])),
+            // Reverted within the day: not reported.
+            row(2, 2, "instructors", Some(json!(["A"])), json!(["B"])),
+            row(2, 2, "instructors", Some(json!(["B"])), json!(["A"])),
+        ]);
+
+        let changes = &days[0].instructor_changes;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].from, ["A"]);
+        assert_eq!(changes[0].to, ["C"]);
+    }
+
+    #[test]
+    fn time_changes_ignore_location_only_edits() {
+        let mut moved = meeting(&["monday"], "10:00:00");
+        moved[0]["location"] = json!({ "building": "NPB", "room": "1.238" });
+
+        let days = summarize(vec![
+            row(
+                1,
+                2,
+                "meeting_times",
+                Some(meeting(&["monday"], "10:00:00")),
+                meeting(&["monday"], "13:00:00"),
+            ),
+            row(
+                2,
+                2,
+                "meeting_times",
+                Some(meeting(&["monday"], "10:00:00")),
+                moved,
+            ),
+        ]);
+
+        let changes = &days[0].time_changes;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].section.crn, "10001");
+    }
+}
@@ -166,6 +166,115 @@ pub async fn update_last_scraped_at(db_pool: &PgPool, code: &str) -> Result<()>
     Ok(())
 }
 
+/// When any term was last scraped successfully.
+pub async fn get_latest_scrape(db_pool: &PgPool) -> Result<Option<DateTime<Utc>>> {
+    sqlx::query_scalar("SELECT MAX(last_scraped_at) FROM terms")
+        .fetch_one(db_pool)
+        .await
+        .context("failed to fetch latest scrape time")
+}
+
+/// Scrape recency and subject coverage for one enabled term.
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct TermFreshness {
+    pub code: String,
+    pub description: String,
+    /// When any scrape job for this term last succeeded.
+    pub last_scraped_at: Option<DateTime<Utc>>,
+    /// Subjects Banner lists for the term.
+    pub subjects_total: i64,
+    /// Of those, subjects with a successful subject scrape inside the window.
+    pub subjects_scraped: i64,
+}
+
+/// Freshness of every scrape-enabled term, newest first.
+///
+/// A subject counts toward coverage if a subject job for it succeeded within
+/// `window` of now.
+pub async fn get_term_freshness(
+    db_pool: &PgPool,
+    window: chrono::Duration,
+) -> Result<Vec<TermFreshness>> {
+    sqlx::query_as::<_, TermFreshness>(
+        r#"
+        WITH scraped AS (
+            SELECT DISTINCT payload->>'term' AS term_code, payload->>'subject' AS subject_code
+            FROM scrape_job_results
+            WHERE target_type = 'Subject' AND success AND completed_at > $1
+        )
+        SELECT t.code, t.description, t.last_scraped_at,
+               COUNT(ts.subject_code) AS subjects_total,
+               COUNT(s.subject_code) AS subjects_scraped
+        FROM terms t
+        LEFT JOIN term_subjects ts ON ts.term_code = t.code
+        LEFT JOIN scraped s ON s.term_code = ts.term_code AND s.subject_code = ts.subject_code
+        WHERE t.scrape_enabled = true
+        GROUP BY t.code, t.description, t.last_scraped_at
+        ORDER BY t.code DESC
+        "#,
+    )
+    .bind(Utc::now() - window)
+    .fetch_all(db_pool)
+    .await
+    .context("failed to fetch term freshness")
+}
+
+/// Scrape activity for one subject in a term.
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct SubjectScrapeActivity {
+    pub subject_code: String,
+    /// When a subject job for this subject last succeeded.
+    pub last_scraped_at: Option<DateTime<Utc>>,
+    /// Successful subject scrapes inside the window.
+    pub scrapes: i64,
+    /// Courses changed by those scrapes.
+    pub courses_changed: i64,
+}
+
+/// Per-subject scrape activity for a term, alphabetical by subject.
+///
+/// Covers every subject Banner lists for the term plus any that have been
+/// scraped without appearing in that list. Only results inside `window` are
+/// read; the last scrape time comes from `subject_scrape_times`.
+pub async fn get_subject_scrape_activity(
+    db_pool: &PgPool,
+    term_code: &str,
+    window: chrono::Duration,
+) -> Result<Vec<SubjectScrapeActivity>> {
+    sqlx::query_as::<_, SubjectScrapeActivity>(
+        r#"
+        WITH recent AS (
+            SELECT payload->>'subject' AS subject_code,
+                   COUNT(*) AS scrapes,
+                   SUM(COALESCE(courses_changed, 0)) AS courses_changed
+            FROM scrape_job_results
+            WHERE target_type = 'Subject' AND success AND payload->>'term' = $1
+              AND completed_at > $2
+            GROUP BY 1
+        ),
+        subjects AS (
+            SELECT subject_code FROM term_subjects WHERE term_code = $1
+            UNION
+            SELECT subject_code FROM subject_scrape_times WHERE term_code = $1
+        )
+        SELECT s.subject_code::TEXT AS subject_code,
+               t.last_scraped_at,
+               COALESCE(r.scrapes, 0) AS scrapes,
+               COALESCE(r.courses_changed, 0)::BIGINT AS courses_changed
+        FROM subjects s
+        LEFT JOIN subject_scrape_times t
+            ON t.term_code = $1 AND t.subject_code = s.subject_code
+        LEFT JOIN recent r ON r.subject_code = s.subject_code
+        ORDER BY s.subject_code
+        "#,
+    )
+    .bind(term_code)
+    .bind(Utc::now() - window)
+    .fetch_all(db_pool)
+    .await
+    .context("failed to fetch subject scrape activity")
+}
+
 /// Parse a 6-digit term code into (display_year, season).
 ///
 /// Returns the **display year** -- the year shown in the term description -- not the raw
@@ -0,0 +1,246 @@
+//! Course title casing.
+//!
+//! Banner sends titles in whatever case they were entered: mostly ALL CAPS
+//! ("INTRO TO PROGRAMMING II"), sometimes lowercase or a mix. Titles are
+//! normalized to title case at upsert; the decoded original is kept in
+//! `courses.raw_title`.
+//!
+//! Words that already carry intentional mixed case ("iOS", "McNair") are left
+//! alone, as are short all-caps words in a title that isn't all caps ("Intro
+//! to CS"); longer ones are taken as emphasis ("calculus for BIOLOGY").
+//! Everything else is capitalized, except minor words mid-phrase and entries
+//! in [`EXCEPTIONS`], which always take their listed form.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use anyhow::Context;
+use sqlx::PgPool;
+use tracing::info;
+
+/// Words with a fixed spelling regardless of input case.
+const EXCEPTIONS: &[&str] = &[
+    // Roman numerals (course sequences)
+    "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII", // Acronyms
+    "AI", "CS", "US", "USA", "UTSA", "STEM", "GIS", "HIV", "AIDS", "ROTC", "ESL", "CAD", "UX",
+    "HTML", "SQL", "DNA", "RNA", "EMS", "NASA", "LGBTQ", "MATLAB", "ASL", "EKG", "CPR",
+    // Fixed mixed case
+    "iOS", "PhD", "MBA", "DevOps", "eSports",
+];
+
+/// Lowercased in the middle of a phrase.
+const MINOR_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on",
+    "or", "the", "to", "via", "vs", "with",
+];
+
+static EXCEPTION_MAP: LazyLock<HashMap<String, &'static str>> = LazyLock::new(|| {
+    EXCEPTIONS
+        .iter()
+        .map(|word| (word.to_lowercase(), *word))
+        .collect()
+});
+
+/// Title-case a course title.
+///
+/// `"INTRO TO PROGRAMMING II"` -> `"Intro to Programming II"`
+/// `"ETHICS OF AI: THE US CONTEXT"` -> `"Ethics of AI: The US Context"`
+pub fn normalize_course_title(title: &str) -> String {
+    let mut out = String::with_capacity(title.len());
+    let mut phrase_start = true;
+    // In an all-caps title, capitals carry no information.
+    let shouting = !title.chars().any(char::is_lowercase);
+
+    for (i, token) in title.split_whitespace().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        // A lone dash separates phrases ("BIOLOGY - LAB").
+        if token == "-" || token == "--" {
+            out.push_str(token);
+            phrase_start = true;
+            continue;
+        }
+
+        // Hyphen and slash compounds are cased part by part ("NON-WESTERN").
+        let starts_phrase = phrase_start || token.starts_with('(');
+        let mut first_part = true;
+        for part in token.split_inclusive(['-', '/']) {
+            let minor_allowed = !starts_phrase && first_part;
+            out.push_str(&case_word(part, minor_allowed, shouting));
+            first_part = false;
+        }
+
+        phrase_start = token.ends_with([':', ';']);
+    }
+
+    out
+}
+
+/// Case one word, keeping any surrounding punctuation in place.
+fn case_word(token: &str, minor_allowed: bool, shouting: bool) -> String {
+    let start = token.find(char::is_alphanumeric).unwrap_or(token.len());
+    let end = token.rfind(char::is_alphanumeric).map_or(start, |i| {
+        i + token[i..].chars().next().map_or(1, char::len_utf8)
+    });
+    if start >= end {
+        return token.to_owned();
+    }
+    let (prefix, core, suffix) = (&token[..start], &token[start..end], &token[end..]);
+
+    let lower = core.to_lowercase();
+    let cased = if let Some(fixed) = EXCEPTION_MAP.get(&lower) {
+        (*fixed).to_owned()
+    } else if core.chars().any(|c| c.is_ascii_digit()) {
+        // Course codes and ordinals ("3D", "1ST") are left as sent.
+        core.to_owned()
+    } else if is_intentional_mixed_case(core) || (!shouting && is_acronym(core)) {
+        core.to_owned()
+    } else if minor_allowed && MINOR_WORDS.contains(&lower.as_str()) {
+        lower
+    } else {
+        capitalize(&lower)
+    };
+
+    format!("{prefix}{cased}{suffix}")
+}
+
+/// Mixed case that isn't just a leading capital: "iOS", "McNair", "LaTeX".
+fn is_intentional_mixed_case(word: &str) -> bool {
+    let mut chars = word.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let rest: Vec<char> = chars.collect();
+    let rest_has_upper = rest.iter().any(|c| c.is_uppercase());
+    let rest_has_lower = rest.iter().any(|c| c.is_lowercase());
+    rest_has_upper && (rest_has_lower || first.is_lowercase())
+}
+
+/// Two to five letters, all capitals: "CS", "NAFTA".
+fn is_acronym(word: &str) -> bool {
+    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+    (2..=5).contains(&letters) && !word.chars().any(char::is_lowercase)
+}
+
+/// Uppercase the first letter of an already-lowercased word, and the letter
+/// after an apostrophe only for short prefixes ("O'Neil", but "Women's").
+fn capitalize(lower: &str) -> String {
+    let mut out = String::with_capacity(lower.len());
+    let mut upper_next = true;
+    let mut chars = lower.chars();
+    let mut letters_seen = 0;
+    while let Some(c) = chars.next() {
+        if upper_next && c.is_alphabetic() {
+            out.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            out.push(c);
+        }
+        if c.is_alphabetic() {
+            letters_seen += 1;
+        }
+        if (c == '\'' || c == '\u{2019}')
+            && letters_seen == 1
+            && chars.clone().filter(|c| c.is_alphabetic()).count() > 1
+        {
+            upper_next = true;
+        }
+    }
+    out
+}
+
+/// Normalize titles of courses stored before casing was applied at upsert.
+///
+/// Rows with a NULL `raw_title` have never been through the normalizer: their
+/// current title is copied to `raw_title` and replaced with the cased form.
+/// Doing this up front keeps the next scrape from logging a title change for
+/// every course.
+pub async fn backfill_course_titles(db_pool: &PgPool) -> anyhow::Result<()> {
+    let rows: Vec<(i32, String)> =
+        sqlx::query_as("SELECT id, title FROM courses WHERE raw_title IS NULL")
+            .fetch_all(db_pool)
+            .await
+            .context("failed to fetch courses for title backfill")?;
+
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<i32> = rows.iter().map(|(id, _)| *id).collect();
+    let titles: Vec<String> = rows
+        .iter()
+        .map(|(_, title)| normalize_course_title(title))
+        .collect();
+
+    sqlx::query(
+        r#"
+        UPDATE courses c
+        SET raw_title = c.title, title = v.title
+        FROM UNNEST($1::int4[], $2::text[]) AS v(id, title)
+        WHERE c.id = v.id
+        "#,
+    )
+    .bind(&ids)
+    .bind(&titles)
+    .execute(db_pool)
+    .await
+    .context("failed to update course titles")?;
+
+    info!(updated = ids.len(), "Course title backfill complete");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_caps_titles() {
+        assert_eq!(
+            normalize_course_title("INTRO TO PROGRAMMING II"),
+            "Intro to Programming II"
+        );
+        assert_eq!(
+            normalize_course_title("ETHICS OF AI: THE US CONTEXT"),
+            "Ethics of AI: The US Context"
+        );
+        assert_eq!(
+            normalize_course_title("THE HISTORY OF NON-WESTERN ART"),
+            "The History of Non-Western Art"
+        );
+    }
+
+    #[test]
+    fn odd_casing_and_punctuation() {
+        assert_eq!(
+            normalize_course_title("calculus for BIOLOGY - lab (online)"),
+            "Calculus for Biology - Lab (Online)"
+        );
+        assert_eq!(
+            normalize_course_title("WOMEN'S STUDIES IN O'NEIL"),
+            "Women's Studies in O'Neil"
+        );
+        assert_eq!(
+            normalize_course_title("INTRO TO IOS DEVELOPMENT"),
+            "Intro to iOS Development"
+        );
+        assert_eq!(
+            normalize_course_title("Intro To NAFTA and trade"),
+            "Intro to NAFTA and Trade"
+        );
+    }
+
+    #[test]
+    fn keeps_intentional_casing_and_codes() {
+        assert_eq!(
+            normalize_course_title("McNair Scholars 3D Modeling"),
+            "McNair Scholars 3D Modeling"
+        );
+        assert_eq!(
+            normalize_course_title("ESPAÑOL PARA NIÑOS"),
+            "Español Para Niños"
+        );
+    }
+}
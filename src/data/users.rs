@@ -1,52 +1,69 @@
 //! Database query functions for users.
 
+use std::sync::LazyLock;
+
 use anyhow::Context;
 use sqlx::PgPool;
 
 use super::models::User;
+use crate::display::DisplayPreferences;
 use anyhow::Result;
 
-/// Insert a new user or update username/avatar on conflict.
-pub async fn upsert_user(
-    pool: &PgPool,
-    discord_id: i64,
-    username: &str,
-    avatar_hash: Option<&str>,
-) -> Result<User> {
-    sqlx::query_as::<_, User>(
-        r#"
-        INSERT INTO users (discord_id, discord_username, discord_avatar_hash)
-        VALUES ($1, $2, $3)
-        ON CONFLICT (discord_id) DO UPDATE
-            SET discord_username = EXCLUDED.discord_username,
-                discord_avatar_hash = EXCLUDED.discord_avatar_hash,
-                updated_at = now()
-        RETURNING *
-        "#,
-    )
-    .bind(discord_id)
-    .bind(username)
-    .bind(avatar_hash)
-    .fetch_one(pool)
+/// Columns of [`User`] for a `users` row named `u`, including the snowflake of
+/// its linked Discord account.
+const USER_COLUMNS: &str = "u.*, \
+     (SELECT ui.subject::bigint FROM user_identities ui \
+      WHERE ui.user_id = u.id AND ui.provider = 'discord') AS discord_id";
+
+/// Load the user with `id` inside any executor, e.g. a sign-in transaction.
+pub(crate) async fn load_user<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    id: i64,
+) -> Result<Option<User>> {
+    sqlx::query_as::<_, User>(&format!(
+        "SELECT {USER_COLUMNS} FROM users u WHERE u.id = $1"
+    ))
+    .bind(id)
+    .fetch_optional(executor)
     .await
-    .context("failed to upsert user")
+    .context("failed to get user")
 }
 
-/// Fetch a user by Discord ID.
-pub async fn get_user(pool: &PgPool, discord_id: i64) -> Result<Option<User>> {
-    sqlx::query_as::<_, User>("SELECT * FROM users WHERE discord_id = $1")
-        .bind(discord_id)
-        .fetch_optional(pool)
-        .await
-        .context("failed to get user")
+/// Fetch a user by ID.
+pub async fn get_user(pool: &PgPool, id: i64) -> Result<Option<User>> {
+    load_user(pool, id).await
+}
+
+/// Fetch the user a Discord account is linked to, by its snowflake.
+pub async fn get_user_by_discord(pool: &PgPool, discord_id: i64) -> Result<Option<User>> {
+    sqlx::query_as::<_, User>(&format!(
+        "SELECT {USER_COLUMNS} FROM users u \
+         JOIN user_identities link ON link.user_id = u.id \
+         WHERE link.provider = 'discord' AND link.subject = $1"
+    ))
+    .bind(discord_id.to_string())
+    .fetch_optional(pool)
+    .await
+    .context("failed to get user by Discord ID")
 }
 
 /// List all users ordered by creation date (newest first).
 pub async fn list_users(pool: &PgPool) -> Result<Vec<User>> {
-    sqlx::query_as::<_, User>("SELECT * FROM users ORDER BY created_at DESC")
-        .fetch_all(pool)
-        .await
-        .context("failed to list users")
+    sqlx::query_as::<_, User>(&format!(
+        "SELECT {USER_COLUMNS} FROM users u ORDER BY u.created_at DESC"
+    ))
+    .fetch_all(pool)
+    .await
+    .context("failed to list users")
+}
+
+/// Stream every user oldest first, for bulk export.
+pub fn stream_users(
+    pool: &PgPool,
+) -> impl futures::Stream<Item = Result<User, sqlx::Error>> + Send + Unpin + '_ {
+    static SQL: LazyLock<String> =
+        LazyLock::new(|| format!("SELECT {USER_COLUMNS} FROM users u ORDER BY u.created_at, u.id"));
+    sqlx::query_as::<_, User>(SQL.as_str()).fetch(pool)
 }
 
 /// Count all registered users.
@@ -58,37 +75,55 @@ pub async fn count_all(pool: &PgPool) -> Result<i64> {
 }
 
 /// Set the admin flag for a user, returning the updated user if found.
-pub async fn set_admin(pool: &PgPool, discord_id: i64, is_admin: bool) -> Result<Option<User>> {
-    sqlx::query_as::<_, User>(
+pub async fn set_admin(pool: &PgPool, id: i64, is_admin: bool) -> Result<Option<User>> {
+    sqlx::query_as::<_, User>(&format!(
         r#"
-        UPDATE users
-        SET is_admin = $2, updated_at = now()
-        WHERE discord_id = $1
-        RETURNING *
-        "#,
-    )
-    .bind(discord_id)
+        WITH u AS (
+            UPDATE users
+            SET is_admin = $2, updated_at = now()
+            WHERE id = $1
+            RETURNING *
+        )
+        SELECT {USER_COLUMNS} FROM u
+        "#
+    ))
+    .bind(id)
     .bind(is_admin)
     .fetch_optional(pool)
     .await
     .context("failed to set admin status")
 }
 
-/// Ensure a seed admin exists. Upserts with `is_admin = true` and a placeholder
-/// username that will be replaced on first OAuth login.
-pub async fn ensure_seed_admin(pool: &PgPool, discord_id: i64) -> Result<User> {
-    sqlx::query_as::<_, User>(
+/// Update a user's display preferences, returning the updated user if found.
+pub async fn set_display_preferences(
+    pool: &PgPool,
+    id: i64,
+    prefs: &DisplayPreferences,
+) -> Result<Option<User>> {
+    sqlx::query_as::<_, User>(&format!(
         r#"
-        INSERT INTO users (discord_id, discord_username, is_admin)
-        VALUES ($1, 'seed-admin', true)
-        ON CONFLICT (discord_id) DO UPDATE
-            SET is_admin = true,
-                updated_at = now()
-        RETURNING *
-        "#,
-    )
-    .bind(discord_id)
-    .fetch_one(pool)
+        WITH u AS (
+            UPDATE users
+            SET timezone = $2, time_format = $3, updated_at = now()
+            WHERE id = $1
+            RETURNING *
+        )
+        SELECT {USER_COLUMNS} FROM u
+        "#
+    ))
+    .bind(id)
+    .bind(&prefs.timezone)
+    .bind(prefs.time_format)
+    .fetch_optional(pool)
     .await
-    .context("failed to ensure seed admin")
+    .context("failed to set display preferences")
+}
+
+/// Ensure the user behind a Discord account exists and is an admin. A new
+/// user gets a placeholder username that is replaced on first OAuth login.
+pub async fn ensure_seed_admin(pool: &PgPool, discord_id: i64) -> Result<User> {
+    let id = crate::data::identities::ensure_discord_user(pool, discord_id, "seed-admin").await?;
+    set_admin(pool, id, true)
+        .await?
+        .context("seed admin disappeared")
 }
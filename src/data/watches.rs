@@ -2,16 +2,24 @@
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool};
 use std::fmt;
 use std::str::FromStr;
+use ts_rs::TS;
+
+use crate::data::events::{CourseChange, SeatField};
 
 /// What condition to watch for on a course.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub enum WatchType {
     SeatsAvailable,
     WaitlistOpen,
     AnyChange,
+    AttributeChange,
+    RestrictionChange,
 }
 
 impl WatchType {
@@ -20,8 +28,16 @@ impl WatchType {
             Self::SeatsAvailable => "seats_available",
             Self::WaitlistOpen => "waitlist_open",
             Self::AnyChange => "any_change",
+            Self::AttributeChange => "attribute_change",
+            Self::RestrictionChange => "restriction_change",
         }
     }
+
+    /// Whether a watch of this type can follow a whole department instead
+    /// of a single section.
+    pub fn is_department_scoped(&self) -> bool {
+        matches!(self, Self::AttributeChange | Self::RestrictionChange)
+    }
 }
 
 impl fmt::Display for WatchType {
@@ -38,6 +54,8 @@ impl FromStr for WatchType {
             "seats_available" => Ok(Self::SeatsAvailable),
             "waitlist_open" => Ok(Self::WaitlistOpen),
             "any_change" => Ok(Self::AnyChange),
+            "attribute_change" => Ok(Self::AttributeChange),
+            "restriction_change" => Ok(Self::RestrictionChange),
             _ => Err(anyhow::anyhow!("unknown watch type: {}", s)),
         }
     }
@@ -48,6 +66,7 @@ impl FromStr for WatchType {
 pub struct WatchListItem {
     pub watch_type: String,
     pub notified_at: Option<DateTime<Utc>>,
+    pub webhook_url: Option<String>,
     pub crn: String,
     pub term_code: String,
     pub subject: String,
@@ -55,11 +74,23 @@ pub struct WatchListItem {
     pub title: String,
 }
 
+/// A department watch, for listing.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DepartmentWatchItem {
+    pub term_code: String,
+    pub subject: String,
+    pub watch_type: String,
+    pub webhook_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// A watch that has been triggered and should receive a notification.
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct TriggeredWatch {
     pub watch_id: i32,
-    pub discord_user_id: i64,
+    pub user_id: i64,
+    /// The owner's linked Discord account, if any.
+    pub discord_id: Option<i64>,
     pub watch_type: String,
     pub crn: String,
     pub term_code: String,
@@ -70,54 +101,34 @@ pub struct TriggeredWatch {
     pub max_enrollment: i32,
     pub wait_count: i32,
     pub wait_capacity: i32,
-}
-
-/// Upsert a minimal user record so the FK on course_watches is satisfied.
-///
-/// Discord bot users may not have logged in via the web, so we create a thin
-/// record from the information available in the bot context.
-pub async fn ensure_user(
-    pool: &PgPool,
-    discord_user_id: i64,
-    discord_username: &str,
-) -> Result<()> {
-    sqlx::query(
-        r#"
-        INSERT INTO users (discord_id, discord_username)
-        VALUES ($1, $2)
-        ON CONFLICT (discord_id) DO UPDATE
-            SET discord_username = EXCLUDED.discord_username,
-                updated_at = NOW()
-        "#,
-    )
-    .bind(discord_user_id)
-    .bind(discord_username)
-    .execute(pool)
-    .await
-    .context("failed to upsert user")?;
-    Ok(())
+    /// Deliver here instead of by DM when set.
+    pub webhook_url: Option<String>,
 }
 
 /// Create or reactivate a watch. Returns true if newly created, false if it already existed.
+///
+/// `webhook_url` replaces any previous delivery target; `None` means a DM.
 pub async fn upsert_watch(
     pool: &PgPool,
-    discord_user_id: i64,
+    user_id: i64,
     course_id: i32,
     watch_type: &WatchType,
+    webhook_url: Option<&str>,
 ) -> Result<bool> {
     // xmax = 0 means the row was just inserted; non-zero means it was updated.
     let row: (bool,) = sqlx::query_as(
         r#"
-        INSERT INTO course_watches (discord_user_id, course_id, watch_type)
-        VALUES ($1, $2, $3)
-        ON CONFLICT (discord_user_id, course_id, watch_type)
-        DO UPDATE SET active = TRUE, notified_at = NULL
+        INSERT INTO course_watches (user_id, course_id, watch_type, webhook_url)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id, course_id, watch_type)
+        DO UPDATE SET active = TRUE, notified_at = NULL, webhook_url = EXCLUDED.webhook_url
         RETURNING (xmax::text::bigint = 0) AS is_new
         "#,
     )
-    .bind(discord_user_id)
+    .bind(user_id)
     .bind(course_id)
     .bind(watch_type.as_str())
+    .bind(webhook_url)
     .fetch_one(pool)
     .await
     .context("failed to upsert watch")?;
@@ -127,17 +138,17 @@ pub async fn upsert_watch(
 /// Delete a specific watch. Returns true if a watch was found and deleted.
 pub async fn delete_watch(
     pool: &PgPool,
-    discord_user_id: i64,
+    user_id: i64,
     course_id: i32,
     watch_type: &WatchType,
 ) -> Result<bool> {
     let result = sqlx::query(
         r#"
         DELETE FROM course_watches
-        WHERE discord_user_id = $1 AND course_id = $2 AND watch_type = $3
+        WHERE user_id = $1 AND course_id = $2 AND watch_type = $3
         "#,
     )
-    .bind(discord_user_id)
+    .bind(user_id)
     .bind(course_id)
     .bind(watch_type.as_str())
     .execute(pool)
@@ -149,16 +160,16 @@ pub async fn delete_watch(
 /// Delete all watches for a user on a specific course. Returns count deleted.
 pub async fn delete_all_watches_for_course(
     pool: &PgPool,
-    discord_user_id: i64,
+    user_id: i64,
     course_id: i32,
 ) -> Result<u64> {
     let result = sqlx::query(
         r#"
         DELETE FROM course_watches
-        WHERE discord_user_id = $1 AND course_id = $2
+        WHERE user_id = $1 AND course_id = $2
         "#,
     )
-    .bind(discord_user_id)
+    .bind(user_id)
     .bind(course_id)
     .execute(pool)
     .await
@@ -166,16 +177,28 @@ pub async fn delete_all_watches_for_course(
     Ok(result.rows_affected())
 }
 
+/// Number of active watches a user holds, department watches included.
+pub async fn count_active_watches(pool: &PgPool, user_id: i64) -> Result<i64> {
+    sqlx::query_scalar(
+        r#"
+        SELECT (SELECT COUNT(*) FROM course_watches WHERE user_id = $1 AND active = TRUE)
+             + (SELECT COUNT(*) FROM department_watches WHERE user_id = $1)
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .context("failed to count active watches")
+}
+
 /// List all active watches for a user with course info.
-pub async fn list_active_watches(
-    pool: &PgPool,
-    discord_user_id: i64,
-) -> Result<Vec<WatchListItem>> {
+pub async fn list_active_watches(pool: &PgPool, user_id: i64) -> Result<Vec<WatchListItem>> {
     let items = sqlx::query_as::<_, WatchListItem>(
         r#"
         SELECT
             cw.watch_type,
             cw.notified_at,
+            cw.webhook_url,
             c.crn,
             c.term_code,
             c.subject,
@@ -183,36 +206,171 @@ pub async fn list_active_watches(
             c.title
         FROM course_watches cw
         JOIN courses c ON c.id = cw.course_id
-        WHERE cw.discord_user_id = $1
+        WHERE cw.user_id = $1
           AND cw.active = TRUE
         ORDER BY c.subject, c.course_number, c.crn, cw.watch_type
         "#,
     )
-    .bind(discord_user_id)
+    .bind(user_id)
     .fetch_all(pool)
     .await
     .context("failed to list active watches")?;
     Ok(items)
 }
 
-/// Find all watches that should fire given the set of changed course IDs.
+/// Create or update a watch on every section of `subject` in `term_code`.
+/// Returns true if newly created.
 ///
-/// Applies a 15-minute cooldown via `notified_at`. Each parameter is the set of
-/// course IDs that changed in the relevant way:
-/// - `enrollment_changed_ids`: courses where enrollment or max_enrollment changed
-/// - `waitlist_changed_ids`: courses where wait_count or wait_capacity changed
-/// - `any_change_ids`: courses with any non-initial field change
-pub async fn find_triggered_watches(
+/// Only [department-scoped](WatchType::is_department_scoped) types are
+/// accepted.
+pub async fn upsert_department_watch(
     pool: &PgPool,
-    enrollment_changed_ids: &[i32],
-    waitlist_changed_ids: &[i32],
-    any_change_ids: &[i32],
+    user_id: i64,
+    term_code: &str,
+    subject: &str,
+    watch_type: &WatchType,
+    webhook_url: Option<&str>,
+) -> Result<bool> {
+    anyhow::ensure!(
+        watch_type.is_department_scoped(),
+        "{watch_type} watches can't follow a department"
+    );
+    let row: (bool,) = sqlx::query_as(
+        r#"
+        INSERT INTO department_watches (user_id, term_code, subject, watch_type, webhook_url)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (user_id, term_code, subject, watch_type)
+        DO UPDATE SET webhook_url = EXCLUDED.webhook_url
+        RETURNING (xmax::text::bigint = 0) AS is_new
+        "#,
+    )
+    .bind(user_id)
+    .bind(term_code)
+    .bind(subject)
+    .bind(watch_type.as_str())
+    .bind(webhook_url)
+    .fetch_one(pool)
+    .await
+    .context("failed to upsert department watch")?;
+    Ok(row.0)
+}
+
+/// Delete a user's watches on a department, or only those of `watch_type`.
+/// Returns count deleted.
+pub async fn delete_department_watches(
+    pool: &PgPool,
+    user_id: i64,
+    term_code: &str,
+    subject: &str,
+    watch_type: Option<&WatchType>,
+) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM department_watches
+        WHERE user_id = $1 AND term_code = $2 AND subject = $3
+          AND ($4::text IS NULL OR watch_type = $4)
+        "#,
+    )
+    .bind(user_id)
+    .bind(term_code)
+    .bind(subject)
+    .bind(watch_type.map(WatchType::as_str))
+    .execute(pool)
+    .await
+    .context("failed to delete department watches")?;
+    Ok(result.rows_affected())
+}
+
+/// List all department watches for a user.
+pub async fn list_department_watches(
+    pool: &PgPool,
+    user_id: i64,
+) -> Result<Vec<DepartmentWatchItem>> {
+    sqlx::query_as::<_, DepartmentWatchItem>(
+        r#"
+        SELECT term_code, subject, watch_type, webhook_url, created_at
+        FROM department_watches
+        WHERE user_id = $1
+        ORDER BY term_code DESC, subject, watch_type
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .context("failed to list department watches")
+}
+
+/// Course IDs from a batch of course changes, grouped by the kind of change.
+#[derive(Debug, Default)]
+pub struct ChangedCourses {
+    /// Enrollment or max_enrollment changed.
+    pub enrollment: Vec<i32>,
+    /// wait_count or wait_capacity changed.
+    pub waitlist: Vec<i32>,
+    /// Course attributes changed.
+    pub attributes: Vec<i32>,
+    /// Registration restrictions changed.
+    pub restrictions: Vec<i32>,
+    /// Any non-initial field changed.
+    pub any: Vec<i32>,
+}
+
+impl ChangedCourses {
+    /// Classify one change. Creating a course isn't a change to it.
+    pub fn record(&mut self, course_id: i32, change: &CourseChange) {
+        match change {
+            CourseChange::Created { .. } => return,
+            CourseChange::Seats {
+                field: SeatField::Enrollment | SeatField::MaxEnrollment,
+                ..
+            } => self.enrollment.push(course_id),
+            CourseChange::Seats {
+                field: SeatField::WaitCount | SeatField::WaitCapacity,
+                ..
+            } => self.waitlist.push(course_id),
+            CourseChange::Field { field, .. } if field == "attributes" => {
+                self.attributes.push(course_id)
+            }
+            CourseChange::Field { field, .. } if field == "restrictions" => {
+                self.restrictions.push(course_id)
+            }
+            CourseChange::Instructors { .. } | CourseChange::Field { .. } => {}
+        }
+        self.any.push(course_id);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.any.is_empty()
+    }
+
+    /// Sort and deduplicate each set.
+    pub fn dedup(&mut self) {
+        for ids in [
+            &mut self.enrollment,
+            &mut self.waitlist,
+            &mut self.attributes,
+            &mut self.restrictions,
+            &mut self.any,
+        ] {
+            ids.sort_unstable();
+            ids.dedup();
+        }
+    }
+}
+
+/// Find all watches that should fire given the changed course IDs.
+///
+/// Applies a 15-minute cooldown via `notified_at`. Each watch type matches
+/// against its own set in `changed`.
+pub async fn find_triggered_watches<'e>(
+    executor: impl PgExecutor<'e>,
+    changed: &ChangedCourses,
 ) -> Result<Vec<TriggeredWatch>> {
     let watches = sqlx::query_as::<_, TriggeredWatch>(
         r#"
         SELECT
             cw.id AS watch_id,
-            cw.discord_user_id,
+            cw.user_id,
             cw.watch_type,
             c.crn,
             c.term_code,
@@ -222,9 +380,13 @@ pub async fn find_triggered_watches(
             c.enrollment,
             c.max_enrollment,
             c.wait_count,
-            c.wait_capacity
+            c.wait_capacity,
+            cw.webhook_url,
+            ui.subject::bigint AS discord_id
         FROM course_watches cw
         JOIN courses c ON c.id = cw.course_id
+        LEFT JOIN user_identities ui
+            ON ui.user_id = cw.user_id AND ui.provider = 'discord'
         WHERE cw.active = TRUE
           AND (cw.notified_at IS NULL OR cw.notified_at < NOW() - INTERVAL '15 minutes')
           AND (
@@ -238,24 +400,147 @@ pub async fn find_triggered_watches(
             OR
             (cw.watch_type = 'any_change'
                 AND c.id = ANY($3::int4[]))
+            OR
+            (cw.watch_type = 'attribute_change'
+                AND c.id = ANY($4::int4[]))
+            OR
+            (cw.watch_type = 'restriction_change'
+                AND c.id = ANY($5::int4[]))
           )
         "#,
     )
-    .bind(enrollment_changed_ids)
-    .bind(waitlist_changed_ids)
-    .bind(any_change_ids)
-    .fetch_all(pool)
+    .bind(&changed.enrollment)
+    .bind(&changed.waitlist)
+    .bind(&changed.any)
+    .bind(&changed.attributes)
+    .bind(&changed.restrictions)
+    .fetch_all(executor)
     .await
     .context("failed to find triggered watches")?;
     Ok(watches)
 }
 
-/// Update `notified_at` to NOW() for a watch after a notification is sent.
-pub async fn mark_notified(pool: &PgPool, watch_id: i32) -> Result<()> {
-    sqlx::query("UPDATE course_watches SET notified_at = NOW() WHERE id = $1")
-        .bind(watch_id)
-        .execute(pool)
+/// Find department watches covering the changed courses, one row per
+/// watch and matching section.
+///
+/// Department watches have no cooldown: each section's edit is its own alert.
+pub async fn find_triggered_department_watches<'e>(
+    executor: impl PgExecutor<'e>,
+    changed: &ChangedCourses,
+) -> Result<Vec<TriggeredWatch>> {
+    if changed.attributes.is_empty() && changed.restrictions.is_empty() {
+        return Ok(Vec::new());
+    }
+    let watches = sqlx::query_as::<_, TriggeredWatch>(
+        r#"
+        SELECT
+            dw.id AS watch_id,
+            dw.user_id,
+            dw.watch_type,
+            c.crn,
+            c.term_code,
+            c.subject,
+            c.course_number,
+            c.title,
+            c.enrollment,
+            c.max_enrollment,
+            c.wait_count,
+            c.wait_capacity,
+            dw.webhook_url,
+            ui.subject::bigint AS discord_id
+        FROM courses c
+        JOIN department_watches dw
+            ON dw.term_code = c.term_code AND dw.subject = c.subject
+        LEFT JOIN user_identities ui
+            ON ui.user_id = dw.user_id AND ui.provider = 'discord'
+        WHERE c.id = ANY($1::int4[] || $2::int4[])
+          AND (
+            (dw.watch_type = 'attribute_change' AND c.id = ANY($1::int4[]))
+            OR
+            (dw.watch_type = 'restriction_change' AND c.id = ANY($2::int4[]))
+          )
+        "#,
+    )
+    .bind(&changed.attributes)
+    .bind(&changed.restrictions)
+    .fetch_all(executor)
+    .await
+    .context("failed to find triggered department watches")?;
+    Ok(watches)
+}
+
+/// Update `notified_at` to NOW() for watches whose notifications were queued.
+///
+/// Takes an executor so it can share the transaction that enqueues the
+/// notifications.
+pub async fn mark_notified<'e>(executor: impl PgExecutor<'e>, watch_ids: &[i32]) -> Result<()> {
+    sqlx::query("UPDATE course_watches SET notified_at = NOW() WHERE id = ANY($1)")
+        .bind(watch_ids)
+        .execute(executor)
         .await
-        .context("failed to mark watch as notified")?;
+        .context("failed to mark watches as notified")?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_changes_by_field() {
+        let field = |name: &str| CourseChange::Field {
+            field: name.to_owned(),
+            old: None,
+            new: serde_json::Value::Null,
+        };
+        let seats = |field| CourseChange::Seats {
+            field,
+            old: Some(0),
+            new: 1,
+        };
+        let mut changed = ChangedCourses::default();
+        for (id, change) in [
+            (
+                1,
+                CourseChange::Created {
+                    snapshot: serde_json::Value::Null,
+                },
+            ),
+            (2, seats(SeatField::Enrollment)),
+            (2, seats(SeatField::MaxEnrollment)),
+            (3, field("attributes")),
+            (4, field("restrictions")),
+            (5, field("title")),
+        ] {
+            changed.record(id, &change);
+        }
+        changed.dedup();
+
+        assert_eq!(changed.enrollment, [2]);
+        assert!(changed.waitlist.is_empty());
+        assert_eq!(changed.attributes, [3]);
+        assert_eq!(changed.restrictions, [4]);
+        assert_eq!(changed.any, [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn watch_types_round_trip() {
+        for wt in [
+            WatchType::SeatsAvailable,
+            WatchType::WaitlistOpen,
+            WatchType::AnyChange,
+            WatchType::AttributeChange,
+            WatchType::RestrictionChange,
+        ] {
+            assert_eq!(wt.as_str().parse::<WatchType>().unwrap(), wt);
+        }
+    }
+
+    #[test]
+    fn only_detail_edits_can_follow_a_department() {
+        assert!(WatchType::AttributeChange.is_department_scoped());
+        assert!(WatchType::RestrictionChange.is_department_scoped());
+        assert!(!WatchType::SeatsAvailable.is_department_scoped());
+        assert!(!WatchType::AnyChange.is_department_scoped());
+    }
+}
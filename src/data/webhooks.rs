@@ -0,0 +1,375 @@
+//! Database operations for webhook subscriptions.
+//!
+//! A webhook is an HTTPS endpoint subscribed to one or more [`WebhookEvent`]
+//! types. Deliveries ride the notification outbox (see
+//! [`crate::data::outbox`]) as [`OutboxPayload::WebhookSubscription`]
+//! messages, which gives them the outbox's leasing, backoff, and dead
+//! letters; the outbox rows double as each endpoint's delivery log.
+//!
+//! Every delivery is signed with the endpoint's secret: the
+//! `X-Banner-Signature` header carries `sha256=` followed by the hex
+//! HMAC-SHA256 of `{timestamp}.{body}`, where the timestamp is the
+//! `X-Banner-Timestamp` header. See [`sign`].
+//!
+//! [`OutboxPayload::WebhookSubscription`]: crate::data::outbox::OutboxPayload::WebhookSubscription
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::{PgExecutor, PgPool};
+use ts_rs::TS;
+
+use crate::data::events::CourseChange;
+
+/// Prefix on every signing secret, so leaked secrets are easy to grep for.
+const SECRET_PREFIX: &str = "whsec_";
+
+/// Course events an endpoint can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum WebhookEvent {
+    /// A section was stored for the first time.
+    #[serde(rename = "course.created")]
+    CourseCreated,
+    /// Enrollment, capacity, or waitlist numbers changed.
+    #[serde(rename = "enrollment.changed")]
+    EnrollmentChanged,
+    /// A section gained an instructor.
+    #[serde(rename = "instructor.assigned")]
+    InstructorAssigned,
+}
+
+impl WebhookEvent {
+    pub const ALL: [Self; 3] = [
+        Self::CourseCreated,
+        Self::EnrollmentChanged,
+        Self::InstructorAssigned,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CourseCreated => "course.created",
+            Self::EnrollmentChanged => "enrollment.changed",
+            Self::InstructorAssigned => "instructor.assigned",
+        }
+    }
+
+    #[allow(dead_code)] // Inverse of `as_str`; the API deserializes events through serde
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.as_str() == s)
+    }
+
+    /// The event a course change raises, if any. Instructor changes only
+    /// count when someone was added.
+    pub fn for_change(change: &CourseChange) -> Option<Self> {
+        match change {
+            CourseChange::Created { .. } => Some(Self::CourseCreated),
+            CourseChange::Seats { .. } => Some(Self::EnrollmentChanged),
+            CourseChange::Instructors { .. } if !change.assigned_instructors().is_empty() => {
+                Some(Self::InstructorAssigned)
+            }
+            CourseChange::Instructors { .. } | CourseChange::Field { .. } => None,
+        }
+    }
+}
+
+/// Where a delivery stands in the outbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum DeliveryStatus {
+    /// Queued or waiting to retry.
+    Pending,
+    Delivered,
+    /// Rejected permanently or out of attempts.
+    Dead,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Dead => "dead",
+        }
+    }
+}
+
+/// A registered endpoint, without its secret.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct Webhook {
+    pub id: i32,
+    pub url: String,
+    pub description: String,
+    #[ts(type = "Array<WebhookEvent>")]
+    pub event_types: Vec<String>,
+    /// Disabled endpoints get no new deliveries; queued ones are dropped.
+    pub enabled: bool,
+    /// Username of the admin who registered the endpoint, if they still exist.
+    pub created_by: Option<String>,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// What the delivery worker needs to send to an endpoint.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SigningTarget {
+    pub url: String,
+    pub secret: String,
+    pub enabled: bool,
+}
+
+/// An enabled endpoint and the events it wants.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Subscriber {
+    pub id: i32,
+    pub event_types: Vec<String>,
+}
+
+impl Subscriber {
+    pub fn wants(&self, event: WebhookEvent) -> bool {
+        self.event_types.iter().any(|e| e == event.as_str())
+    }
+}
+
+/// One entry in an endpoint's delivery log.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhookDelivery {
+    /// Outbox message id, also sent as `X-Banner-Delivery`.
+    #[ts(type = "number")]
+    pub id: i64,
+    pub event: String,
+    #[ts(type = "DeliveryStatus")]
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub body: serde_json::Value,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+    /// When a pending delivery is next tried.
+    #[ts(type = "string")]
+    pub next_attempt_at: DateTime<Utc>,
+    #[ts(type = "string | null")]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+const WEBHOOK_SELECT: &str = "SELECT w.id, w.url, w.description, w.event_types, w.enabled, \
+            u.discord_username AS created_by, w.created_at \
+     FROM webhooks w \
+     LEFT JOIN users u ON u.id = w.created_by";
+
+/// Generate a signing secret: [`SECRET_PREFIX`] followed by 32 random bytes in hex.
+fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::rng().random();
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("{SECRET_PREFIX}{hex}")
+}
+
+/// Hex HMAC-SHA256 of `message` under `secret`.
+fn hmac_hex(secret: &str, message: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(message.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// The `X-Banner-Signature` value for `body` sent at `timestamp` (Unix seconds).
+///
+/// The timestamp is signed too, so receivers can reject stale replays.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    format!(
+        "sha256={}",
+        hmac_hex(secret, &format!("{timestamp}.{body}"))
+    )
+}
+
+/// Register an endpoint. Returns the stored row and its signing secret.
+pub async fn create_webhook(
+    pool: &PgPool,
+    url: &str,
+    description: &str,
+    events: &[WebhookEvent],
+    created_by: i64,
+) -> Result<(Webhook, String)> {
+    let secret = generate_secret();
+    let event_types: Vec<&str> = events.iter().map(|e| e.as_str()).collect();
+    let (id,): (i32,) = sqlx::query_as(
+        r#"
+        INSERT INTO webhooks (url, description, secret, event_types, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(url)
+    .bind(description)
+    .bind(&secret)
+    .bind(&event_types)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+    .context("failed to insert webhook")?;
+
+    let webhook = get_webhook(pool, id)
+        .await?
+        .context("inserted webhook disappeared")?;
+    Ok((webhook, secret))
+}
+
+/// Every registered endpoint, oldest first.
+pub async fn list_webhooks(pool: &PgPool) -> Result<Vec<Webhook>> {
+    sqlx::query_as::<_, Webhook>(&format!("{WEBHOOK_SELECT} ORDER BY w.id"))
+        .fetch_all(pool)
+        .await
+        .context("failed to list webhooks")
+}
+
+pub async fn get_webhook(pool: &PgPool, id: i32) -> Result<Option<Webhook>> {
+    sqlx::query_as::<_, Webhook>(&format!("{WEBHOOK_SELECT} WHERE w.id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .context("failed to fetch webhook")
+}
+
+/// Replace an endpoint's description, subscriptions, and enabled flag.
+/// Returns `None` if it doesn't exist.
+pub async fn update_webhook(
+    pool: &PgPool,
+    id: i32,
+    description: &str,
+    events: &[WebhookEvent],
+    enabled: bool,
+) -> Result<Option<Webhook>> {
+    let event_types: Vec<&str> = events.iter().map(|e| e.as_str()).collect();
+    let result = sqlx::query(
+        "UPDATE webhooks SET description = $2, event_types = $3, enabled = $4 WHERE id = $1",
+    )
+    .bind(id)
+    .bind(description)
+    .bind(&event_types)
+    .bind(enabled)
+    .execute(pool)
+    .await
+    .context("failed to update webhook")?;
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+    get_webhook(pool, id).await
+}
+
+/// Delete an endpoint. Returns true if it existed.
+///
+/// Queued deliveries stay in the outbox and are dead-lettered when the
+/// worker finds the endpoint gone.
+pub async fn delete_webhook(pool: &PgPool, id: i32) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("failed to delete webhook")?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Enabled endpoints subscribed to at least one of `events`.
+pub async fn find_subscribers<'e>(
+    executor: impl PgExecutor<'e>,
+    events: &[WebhookEvent],
+) -> Result<Vec<Subscriber>> {
+    let event_types: Vec<&str> = events.iter().map(|e| e.as_str()).collect();
+    sqlx::query_as::<_, Subscriber>(
+        "SELECT id, event_types FROM webhooks WHERE enabled AND event_types && $1 ORDER BY id",
+    )
+    .bind(&event_types)
+    .fetch_all(executor)
+    .await
+    .context("failed to find webhook subscribers")
+}
+
+/// URL and secret for signing a delivery, if the endpoint still exists.
+pub async fn get_signing_target(pool: &PgPool, id: i32) -> Result<Option<SigningTarget>> {
+    sqlx::query_as::<_, SigningTarget>("SELECT url, secret, enabled FROM webhooks WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .context("failed to fetch webhook signing target")
+}
+
+/// An endpoint's deliveries, newest first, optionally only those in `status`.
+///
+/// Delivered messages are pruned with the rest of the outbox; dead letters
+/// are kept.
+pub async fn list_deliveries(
+    pool: &PgPool,
+    webhook_id: i32,
+    status: Option<DeliveryStatus>,
+    limit: i64,
+) -> Result<Vec<WebhookDelivery>> {
+    sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        SELECT id, payload->>'event' AS event, status, attempts, last_error,
+               payload->'body' AS body, created_at, next_attempt_at, delivered_at
+        FROM notification_outbox
+        WHERE channel = 'webhook_subscription'
+          AND (payload->>'webhook_id')::int = $1
+          AND ($2::text IS NULL OR status = $2)
+        ORDER BY created_at DESC, id DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(webhook_id)
+    .bind(status.map(DeliveryStatus::as_str))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("failed to list webhook deliveries")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        assert_eq!(
+            hmac_hex("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let body = r#"{"event":"course.created"}"#;
+        let signature = sign("whsec_test", 1_700_000_000, body);
+        assert_eq!(
+            signature,
+            format!(
+                "sha256={}",
+                hmac_hex("whsec_test", &format!("1700000000.{body}"))
+            )
+        );
+        assert_ne!(signature, sign("whsec_test", 1_700_000_001, body));
+    }
+
+    #[test]
+    fn event_names_round_trip() {
+        for event in WebhookEvent::ALL {
+            assert_eq!(WebhookEvent::parse(event.as_str()), Some(event));
+            assert_eq!(
+                serde_json::to_value(event).unwrap(),
+                serde_json::Value::String(event.as_str().to_owned())
+            );
+        }
+        assert_eq!(WebhookEvent::parse("course.deleted"), None);
+    }
+}
@@ -0,0 +1,144 @@
+//! Per-user display preferences for rendering times.
+//!
+//! Course meeting times are stored as campus-local wall-clock times. These
+//! helpers convert them into the user's timezone and clock style for ICS
+//! descriptions, bot replies, and any other server-rendered text.
+
+use chrono::{NaiveDate, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::data::models::{TimeFormat, User};
+
+/// Timezone all Banner meeting times are expressed in.
+pub const CAMPUS_TZ: Tz = chrono_tz::America::Chicago;
+
+/// How a user wants times rendered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DisplayPreferences {
+    /// IANA timezone name, e.g. `America/Chicago`.
+    pub timezone: String,
+    pub time_format: TimeFormat,
+}
+
+impl Default for DisplayPreferences {
+    fn default() -> Self {
+        Self {
+            timezone: CAMPUS_TZ.name().to_owned(),
+            time_format: TimeFormat::TwelveHour,
+        }
+    }
+}
+
+impl From<&User> for DisplayPreferences {
+    fn from(user: &User) -> Self {
+        Self {
+            timezone: user.timezone.clone(),
+            time_format: user.time_format,
+        }
+    }
+}
+
+impl DisplayPreferences {
+    /// Preferences for an optional signed-in user, falling back to campus defaults.
+    pub fn for_user(user: Option<&User>) -> Self {
+        user.map(Self::from).unwrap_or_default()
+    }
+
+    /// The configured timezone, or campus time if the stored name is unknown.
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(CAMPUS_TZ)
+    }
+
+    /// Render a wall-clock time in the preferred clock style ("9:30 AM" / "09:30").
+    pub fn format_time(&self, time: NaiveTime) -> String {
+        match self.time_format {
+            TimeFormat::TwelveHour => time.format("%-I:%M %p").to_string(),
+            TimeFormat::TwentyFourHour => time.format("%H:%M").to_string(),
+        }
+    }
+
+    /// Render a campus-local meeting time range on `date` in the user's
+    /// timezone, suffixed with the zone abbreviation when it isn't campus time.
+    pub fn format_campus_range(&self, date: NaiveDate, start: NaiveTime, end: NaiveTime) -> String {
+        let tz = self.tz();
+        let convert = |time: NaiveTime| {
+            CAMPUS_TZ
+                .from_local_datetime(&date.and_time(time))
+                .earliest()
+                .map(|dt| dt.with_timezone(&tz))
+        };
+        match (convert(start), convert(end)) {
+            (Some(s), Some(e)) => {
+                let range = format!(
+                    "{}-{}",
+                    self.format_time(s.time()),
+                    self.format_time(e.time())
+                );
+                if tz == CAMPUS_TZ {
+                    range
+                } else {
+                    format!("{range} {}", s.format("%Z"))
+                }
+            }
+            _ => format!("{}-{}", self.format_time(start), self.format_time(end)),
+        }
+    }
+}
+
+/// Whether `name` is a timezone we can render in.
+pub fn is_valid_timezone(name: &str) -> bool {
+    name.parse::<Tz>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefs(timezone: &str, time_format: TimeFormat) -> DisplayPreferences {
+        DisplayPreferences {
+            timezone: timezone.into(),
+            time_format,
+        }
+    }
+
+    fn t(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn formats_clock_styles() {
+        let twelve = prefs("America/Chicago", TimeFormat::TwelveHour);
+        let twenty_four = prefs("America/Chicago", TimeFormat::TwentyFourHour);
+        assert_eq!(twelve.format_time(t(13, 5)), "1:05 PM");
+        assert_eq!(twenty_four.format_time(t(9, 30)), "09:30");
+    }
+
+    #[test]
+    fn converts_campus_times_to_user_zone() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let campus = DisplayPreferences::default();
+        assert_eq!(
+            campus.format_campus_range(date, t(9, 30), t(10, 45)),
+            "9:30 AM-10:45 AM"
+        );
+
+        let berlin = prefs("Europe/Berlin", TimeFormat::TwentyFourHour);
+        assert_eq!(
+            berlin.format_campus_range(date, t(9, 30), t(10, 45)),
+            "16:30-17:45 CET"
+        );
+    }
+
+    #[test]
+    fn unknown_timezone_falls_back_to_campus() {
+        assert!(!is_valid_timezone("Mars/Olympus"));
+        assert_eq!(
+            prefs("Mars/Olympus", TimeFormat::TwelveHour).tz(),
+            CAMPUS_TZ
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Importer for UTSA's publicly released grade distribution data.
+//!
+//! Releases arrive as CSV (usually exported from a FOIA spreadsheet) with one
+//! row per section and a column per grade. Column names vary between releases,
+//! so columns are matched by header name: the identity columns by a few known
+//! aliases, and grade columns by the letter grade they hold. Unknown columns
+//! are ignored.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::banner::models::terms::Term;
+use crate::data::grades::{GradeDistributionRecord, batch_upsert_grade_distributions};
+use crate::utils::csv_records::read_records;
+
+/// Letter grades that count toward GPA: header, the letter it folds into,
+/// and its grade points.
+const GRADE_POINTS: &[(&str, char, f64)] = &[
+    ("A+", 'A', 4.0),
+    ("A", 'A', 4.0),
+    ("A-", 'A', 3.67),
+    ("B+", 'B', 3.33),
+    ("B", 'B', 3.0),
+    ("B-", 'B', 2.67),
+    ("C+", 'C', 2.33),
+    ("C", 'C', 2.0),
+    ("C-", 'C', 1.67),
+    ("D+", 'D', 1.33),
+    ("D", 'D', 1.0),
+    ("D-", 'D', 0.67),
+    ("F", 'F', 0.0),
+];
+
+const WITHDRAWN_HEADERS: &[&str] = &["W", "WD", "WITHDRAWN", "WITHDRAW", "DROP", "DROPS"];
+
+/// Grades that carry no grade points (pass/fail, incomplete, audit, ...).
+const OTHER_HEADERS: &[&str] = &[
+    "P", "NP", "CR", "NC", "S", "U", "I", "IP", "AU", "NR", "OTHER",
+];
+
+const TERM_HEADERS: &[&str] = &["TERM", "TERM CODE", "SEMESTER"];
+const SUBJECT_HEADERS: &[&str] = &["SUBJECT", "SUBJ", "SUBJECT CODE"];
+const COURSE_HEADERS: &[&str] = &[
+    "COURSE",
+    "COURSE NUMBER",
+    "COURSE NO",
+    "CRSE",
+    "CATALOG",
+    "CATALOG NUMBER",
+];
+const SECTION_HEADERS: &[&str] = &["SECTION", "SECT", "SECTION NUMBER"];
+const INSTRUCTOR_HEADERS: &[&str] = &[
+    "INSTRUCTOR",
+    "INSTRUCTOR NAME",
+    "PRIMARY INSTRUCTOR",
+    "FACULTY",
+    "PROFESSOR",
+];
+
+/// What a CSV column holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Term,
+    Subject,
+    Course,
+    Section,
+    Instructor,
+    Graded { letter: char, points: f64 },
+    Withdrawn,
+    Other,
+}
+
+fn classify_header(header: &str) -> Option<Column> {
+    let h = header
+        .trim()
+        .trim_start_matches('\u{feff}')
+        .replace(['_', '.'], " ")
+        .to_ascii_uppercase();
+    let h = h.trim();
+    if TERM_HEADERS.contains(&h) {
+        return Some(Column::Term);
+    }
+    if SUBJECT_HEADERS.contains(&h) {
+        return Some(Column::Subject);
+    }
+    if COURSE_HEADERS.contains(&h) {
+        return Some(Column::Course);
+    }
+    if SECTION_HEADERS.contains(&h) {
+        return Some(Column::Section);
+    }
+    if INSTRUCTOR_HEADERS.contains(&h) {
+        return Some(Column::Instructor);
+    }
+    if let Some(&(_, letter, points)) = GRADE_POINTS.iter().find(|(g, _, _)| *g == h) {
+        return Some(Column::Graded { letter, points });
+    }
+    if WITHDRAWN_HEADERS.contains(&h) {
+        return Some(Column::Withdrawn);
+    }
+    if OTHER_HEADERS.contains(&h) {
+        return Some(Column::Other);
+    }
+    None
+}
+
+/// Resolve a release's term value to a Banner term code.
+///
+/// Accepts term codes (`"202510"`), slugs (`"fall-2024"`), and descriptions
+/// in either order (`"Fall 2024"`, `"2024 Fall"`).
+fn parse_term(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if let Some(code) = Term::resolve_to_code(raw) {
+        return Some(code);
+    }
+    let parts: Vec<&str> = raw.split_whitespace().collect();
+    let (season, year) = match parts.as_slice() {
+        [a, b] if a.chars().all(|c| c.is_ascii_digit()) => (*b, *a),
+        [a, b] => (*a, *b),
+        _ => return None,
+    };
+    Term::from_slug(&format!("{season}-{year}")).map(|t| t.to_string())
+}
+
+/// Parse a count cell; blanks and suppressed values (e.g. `"*"`, `"<5"`) are zero.
+fn parse_count(raw: &str) -> i32 {
+    raw.trim()
+        .replace(',', "")
+        .parse::<i32>()
+        .unwrap_or(0)
+        .max(0)
+}
+
+/// Result of parsing a release.
+#[derive(Debug, Default)]
+pub struct ParsedGrades {
+    pub records: Vec<GradeDistributionRecord>,
+    /// Data rows dropped for a missing or unrecognized term, subject, or course.
+    pub skipped: usize,
+}
+
+/// Parse a grade distribution CSV.
+pub fn parse_csv(input: &str) -> Result<ParsedGrades> {
+    let mut rows = read_records(input)
+        .context("failed to read CSV")?
+        .into_iter()
+        .map(|record| record.fields);
+    let header = rows.next().context("CSV is empty")?;
+    let columns: Vec<Option<Column>> = header.iter().map(|h| classify_header(h)).collect();
+
+    let has = |kind: Column| columns.contains(&Some(kind));
+    if !has(Column::Term) || !has(Column::Course) {
+        bail!(
+            "CSV must have term and course columns (found: {})",
+            header.join(", ")
+        );
+    }
+    if !columns
+        .iter()
+        .any(|c| matches!(c, Some(Column::Graded { .. })))
+    {
+        bail!("CSV has no letter grade columns");
+    }
+
+    let mut parsed = ParsedGrades::default();
+    for row in rows {
+        let mut record = GradeDistributionRecord::default();
+        let mut term_raw = "";
+        let mut points_sum = 0.0;
+        let mut letters: HashMap<char, i32> = HashMap::new();
+
+        for (column, value) in columns.iter().zip(row.iter()) {
+            match column {
+                Some(Column::Term) => term_raw = value.as_str(),
+                Some(Column::Subject) => record.subject = value.trim().to_ascii_uppercase(),
+                Some(Column::Course) => record.course_number = value.trim().to_ascii_uppercase(),
+                Some(Column::Section) => record.section = value.trim().to_owned(),
+                Some(Column::Instructor) => record.instructor_name = value.trim().to_owned(),
+                Some(Column::Graded { letter, points }) => {
+                    let n = parse_count(value);
+                    points_sum += points * n as f64;
+                    record.graded_count += n;
+                    *letters.entry(*letter).or_default() += n;
+                }
+                Some(Column::Withdrawn) => record.withdrawn_count += parse_count(value),
+                Some(Column::Other) => record.other_count += parse_count(value),
+                None => {}
+            }
+        }
+
+        // Some releases put "CS 1713" in the course column instead of splitting it.
+        if record.subject.is_empty()
+            && let Some((subject, number)) = record.course_number.split_once(' ')
+        {
+            record.subject = subject.to_owned();
+            record.course_number = number.trim().to_owned();
+        }
+
+        let Some(term_code) = parse_term(term_raw) else {
+            parsed.skipped += 1;
+            continue;
+        };
+        if record.subject.is_empty() || record.course_number.is_empty() {
+            parsed.skipped += 1;
+            continue;
+        }
+        record.term_code = term_code;
+        record.a_count = letters.get(&'A').copied().unwrap_or(0);
+        record.b_count = letters.get(&'B').copied().unwrap_or(0);
+        record.c_count = letters.get(&'C').copied().unwrap_or(0);
+        record.d_count = letters.get(&'D').copied().unwrap_or(0);
+        record.f_count = letters.get(&'F').copied().unwrap_or(0);
+        record.avg_gpa =
+            (record.graded_count > 0).then(|| (points_sum / record.graded_count as f64) as f32);
+        parsed.records.push(record);
+    }
+
+    Ok(parsed)
+}
+
+/// Parse a release from `path`, upsert it, and link rows to instructors.
+///
+/// `source` labels the rows (defaults to the file name) so a release can be
+/// traced back after import.
+pub async fn import_file(pool: &PgPool, path: &Path, source: Option<&str>) -> Result<()> {
+    let input = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let source = source.map(str::to_owned).unwrap_or_else(|| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "unknown".to_owned())
+    });
+
+    let parsed = parse_csv(&input)?;
+    if parsed.skipped > 0 {
+        warn!(
+            skipped = parsed.skipped,
+            "Skipped grade rows with a missing or unrecognized term, subject, or course"
+        );
+    }
+
+    let upserted = batch_upsert_grade_distributions(pool, &parsed.records, &source).await?;
+    let linked = crate::data::grades::link_instructors(pool).await?;
+
+    info!(
+        source,
+        upserted,
+        skipped = parsed.skipped,
+        linked,
+        "Imported grade distributions"
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_letter_columns_and_folds_plus_minus() {
+        let csv = "Term,Subject,Course,Section,Instructor,A,A-,B+,B,C,F,W,P\n\
+                   Fall 2024,cs,1713,001,\"Doe, Jane\",10,5,5,5,3,2,4,1\n";
+        let parsed = parse_csv(csv).unwrap();
+        assert_eq!(parsed.skipped, 0);
+        let r = &parsed.records[0];
+        assert_eq!(r.term_code, "202510");
+        assert_eq!(r.subject, "CS");
+        assert_eq!(r.instructor_name, "Doe, Jane");
+        assert_eq!((r.a_count, r.b_count, r.c_count, r.f_count), (15, 10, 3, 2));
+        assert_eq!(
+            (r.withdrawn_count, r.other_count, r.graded_count),
+            (4, 1, 30)
+        );
+        let expected = (10.0 * 4.0 + 5.0 * 3.67 + 5.0 * 3.33 + 5.0 * 3.0 + 3.0 * 2.0) / 30.0;
+        assert!((r.avg_gpa.unwrap() as f64 - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn accepts_header_aliases_and_combined_course() {
+        let csv = "TERM_CODE,Course No,Sect,A,B,C,D,F\r\n\
+                   202520,MAT 1214,002,1,1,1,1,1\r\n\
+                   ,MAT 1214,003,1,0,0,0,0\r\n";
+        let parsed = parse_csv(csv).unwrap();
+        assert_eq!(parsed.records.len(), 1);
+        assert_eq!(parsed.skipped, 1);
+        let r = &parsed.records[0];
+        assert_eq!(
+            (r.subject.as_str(), r.course_number.as_str()),
+            ("MAT", "1214")
+        );
+        assert_eq!(r.avg_gpa, Some(2.0));
+    }
+
+    #[test]
+    fn suppressed_counts_are_zero() {
+        assert_eq!(parse_count("*"), 0);
+        assert_eq!(parse_count("<5"), 0);
+        assert_eq!(parse_count("1,024"), 1024);
+    }
+
+    #[test]
+    fn rejects_files_without_grades() {
+        assert!(parse_csv("Term,Course\n202510,CS 1713\n").is_err());
+    }
+
+    #[test]
+    fn parses_term_formats() {
+        assert_eq!(parse_term("202510").as_deref(), Some("202510"));
+        assert_eq!(parse_term("Spring 2025").as_deref(), Some("202520"));
+        assert_eq!(parse_term("2025 Spring").as_deref(), Some("202520"));
+        assert_eq!(parse_term("spring-2025").as_deref(), Some("202520"));
+        assert_eq!(parse_term("Winter"), None);
+    }
+}
@@ -6,10 +6,17 @@ pub mod calendar;
 pub mod cli;
 pub mod config;
 pub mod data;
+pub mod display;
+pub mod grades;
 pub mod logging;
+pub mod metrics;
+pub mod parse_pool;
 pub mod rmp;
 pub mod scraper;
+pub mod secrets;
 pub mod services;
 pub mod state;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod utils;
 pub mod web;
@@ -13,10 +13,15 @@ mod calendar;
 mod cli;
 mod config;
 mod data;
+mod display;
 mod fmt;
+mod grades;
 mod logging;
+mod metrics;
+mod parse_pool;
 mod rmp;
 mod scraper;
+mod secrets;
 mod services;
 mod state;
 mod utils;
@@ -49,6 +54,16 @@ async fn main() -> ExitCode {
     };
     setup_logging(&early_config, args.tracing);
 
+    if let Some(command) = args.command {
+        return match command.run(&early_config).await {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                tracing::error!(error = ?e, "Command failed");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
     // Log application startup context before App::new() so these appear first
     info!(
         version = env!("CARGO_PKG_VERSION"),
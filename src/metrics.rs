@@ -0,0 +1,299 @@
+//! Process-wide operational metrics, exported in the Prometheus text format
+//! at `GET /api/metrics/prometheus`.
+//!
+//! Recording goes through the [`metrics`] facade into a
+//! [`PrometheusRecorder`] owned by [`Metrics`]. The recorder is installed
+//! locally around each call rather than globally, so every `Metrics` (and
+//! every test) has its own registry. Gauges like pool usage, and totals kept
+//! elsewhere (like the Banner session pool's), are passed in at render time.
+//! Everything resets on restart; Prometheus handles counter resets.
+//!
+//! The endpoint exposes internals, so it only answers requests bearing the
+//! configured `METRICS_TOKEN`, and 404s when no token is configured.
+
+use std::time::Duration;
+
+use metrics::{
+    Unit, counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram,
+    with_local_recorder,
+};
+use metrics_exporter_prometheus::{
+    Matcher, PrometheusBuilder, PrometheusHandle, PrometheusRecorder,
+};
+use sha2::{Digest, Sha256};
+
+use crate::data::models::TargetType;
+
+const HTTP_REQUESTS: &str = "banner_http_requests_total";
+const HTTP_DURATION: &str = "banner_http_request_duration_seconds";
+const RATE_LIMITED: &str = "banner_rate_limited_total";
+const SCRAPE_DURATION: &str = "banner_scrape_job_duration_seconds";
+const CACHE_LOOKUPS: &str = "banner_cache_lookups_total";
+const STREAM_CLOSES: &str = "banner_stream_closes_total";
+
+/// Histogram bucket upper bounds for HTTP request durations, in seconds.
+const HTTP_BUCKETS: &[f64] = &[0.005, 0.025, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0];
+
+/// Histogram bucket upper bounds for scrape job durations, in seconds.
+const SCRAPE_BUCKETS: &[f64] = &[0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0];
+/// How a scrape job attempt ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JobOutcome {
+    Success,
+    /// Failed and will be retried (or was exhausted).
+    Retry,
+    /// Failed permanently and was deleted.
+    Failed,
+    /// Banner asked us to back off; rescheduled without using a retry.
+    Throttled,
+}
+
+impl JobOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Retry => "retry",
+            Self::Failed => "failed",
+            Self::Throttled => "throttled",
+        }
+    }
+}
+
+fn target_label(target: TargetType) -> &'static str {
+    match target {
+        TargetType::Subject => "subject",
+        TargetType::CourseRange => "course_range",
+        TargetType::CrnList => "crn_list",
+        TargetType::SingleCrn => "single_crn",
+    }
+}
+
+fn status_class(status: u16) -> &'static str {
+    match status {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+/// Shared metrics registry, held in [`AppState`](crate::state::AppState).
+pub struct Metrics {
+    recorder: PrometheusRecorder,
+    handle: PrometheusHandle,
+    /// SHA-256 of the token scrapers must present; `None` disables the endpoint.
+    scrape_token: Option<[u8; 32]>,
+}
+
+impl Metrics {
+    pub fn new(scrape_token: Option<&str>) -> Self {
+        let recorder = PrometheusBuilder::new()
+            .set_buckets_for_metric(Matcher::Full(HTTP_DURATION.to_owned()), HTTP_BUCKETS)
+            .and_then(|b| {
+                b.set_buckets_for_metric(Matcher::Full(SCRAPE_DURATION.to_owned()), SCRAPE_BUCKETS)
+            })
+            .expect("histogram buckets are non-empty")
+            .build_recorder();
+        let handle = recorder.handle();
+
+        with_local_recorder(&recorder, || {
+            describe_counter!(
+                HTTP_REQUESTS,
+                "HTTP requests by route group and status class."
+            );
+            describe_histogram!(
+                HTTP_DURATION,
+                Unit::Seconds,
+                "HTTP request latency by route group."
+            );
+            describe_counter!(
+                RATE_LIMITED,
+                "Requests rejected by the rate limiter, by route group."
+            );
+            describe_histogram!(
+                SCRAPE_DURATION,
+                Unit::Seconds,
+                "Scrape job run time by target type and outcome."
+            );
+            describe_counter!(
+                CACHE_LOOKUPS,
+                "In-memory cache lookups by cache and result."
+            );
+            describe_counter!(
+                STREAM_CLOSES,
+                "Closed stream connections by reason, including idle and lifetime evictions."
+            );
+        });
+
+        Self {
+            recorder,
+            handle,
+            scrape_token: scrape_token.map(|token| Sha256::digest(token).into()),
+        }
+    }
+
+    /// Whether `token` matches the configured scrape token. Compares digests
+    /// so the check doesn't leak the token through timing.
+    pub fn authorizes(&self, token: Option<&str>) -> bool {
+        match (self.scrape_token, token) {
+            (Some(expected), Some(token)) => <[u8; 32]>::from(Sha256::digest(token)) == expected,
+            _ => false,
+        }
+    }
+
+    /// Whether a scrape token is configured at all.
+    pub fn enabled(&self) -> bool {
+        self.scrape_token.is_some()
+    }
+
+    /// Count a finished HTTP request.
+    pub fn record_request(&self, group: &'static str, status: u16, duration: Duration) {
+        with_local_recorder(&self.recorder, || {
+            counter!(HTTP_REQUESTS, "group" => group, "status" => status_class(status))
+                .increment(1);
+            histogram!(HTTP_DURATION, "group" => group).record(duration);
+        });
+    }
+
+    /// Count a request rejected by the rate limiter.
+    pub fn record_rate_limited(&self, group: &'static str) {
+        with_local_recorder(&self.recorder, || {
+            counter!(RATE_LIMITED, "group" => group).increment(1);
+        });
+    }
+
+    /// Record one scrape job attempt.
+    pub fn record_scrape_job(&self, target: TargetType, outcome: JobOutcome, duration: Duration) {
+        with_local_recorder(&self.recorder, || {
+            histogram!(
+                SCRAPE_DURATION,
+                "target" => target_label(target),
+                "outcome" => outcome.as_str()
+            )
+            .record(duration);
+        });
+    }
+
+    /// Count a lookup against a named in-memory cache.
+    pub fn record_cache_lookup(&self, cache: &'static str, hit: bool) {
+        let result = if hit { "hit" } else { "miss" };
+        with_local_recorder(&self.recorder, || {
+            counter!(CACHE_LOOKUPS, "cache" => cache, "result" => result).increment(1);
+        });
+    }
+
+    /// Count a closed stream connection by why it ended.
+    pub fn record_stream_close(&self, reason: &'static str) {
+        with_local_recorder(&self.recorder, || {
+            counter!(STREAM_CLOSES, "reason" => reason).increment(1);
+        });
+    }
+
+    /// Drain histogram buckets that have aged out. Called periodically.
+    pub fn run_upkeep(&self) {
+        self.handle.run_upkeep();
+    }
+
+    /// Render every recorded metric, plus the caller's counters and gauges,
+    /// as Prometheus text.
+    pub fn render(&self, counters: &[Counter], gauges: &[Gauge]) -> String {
+        with_local_recorder(&self.recorder, || {
+            for c in counters {
+                describe_counter!(c.name, c.help);
+                counter!(c.name).absolute(c.value);
+            }
+            for g in gauges {
+                describe_gauge!(g.name, g.help);
+                gauge!(g.name).set(g.value);
+            }
+        });
+        self.handle.render()
+    }
+}
+
+/// A running total kept outside the registry, supplied when rendering.
+pub struct Counter {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub value: u64,
+}
+
+/// A point-in-time value supplied when rendering.
+pub struct Gauge {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub value: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_counters_and_histograms() {
+        let metrics = Metrics::new(None);
+        metrics.record_request("api", 200, Duration::from_millis(20));
+        metrics.record_request("api", 200, Duration::from_millis(300));
+        metrics.record_request("api", 404, Duration::from_millis(1));
+        metrics.record_rate_limited("api");
+        metrics.record_stream_close("idle_timeout");
+        metrics.record_cache_lookup("search_options", true);
+        metrics.record_scrape_job(
+            TargetType::Subject,
+            JobOutcome::Success,
+            Duration::from_secs(3),
+        );
+
+        let text = metrics.render(
+            &[Counter {
+                name: "banner_sessions_created_total",
+                help: "Banner sessions created.",
+                value: 7,
+            }],
+            &[Gauge {
+                name: "banner_db_pool_connections",
+                help: "Open database connections.",
+                value: 4.0,
+            }],
+        );
+
+        assert!(text.contains("banner_http_requests_total{group=\"api\",status=\"2xx\"} 2\n"));
+        assert!(text.contains("banner_http_requests_total{group=\"api\",status=\"4xx\"} 1\n"));
+        assert!(text.contains(
+            "banner_http_request_duration_seconds_bucket{group=\"api\",le=\"0.025\"} 2\n"
+        ));
+        assert!(text.contains(
+            "banner_http_request_duration_seconds_bucket{group=\"api\",le=\"+Inf\"} 3\n"
+        ));
+        assert!(text.contains("banner_rate_limited_total{group=\"api\"} 1\n"));
+        assert!(text.contains("banner_stream_closes_total{reason=\"idle_timeout\"} 1\n"));
+        assert!(
+            text.contains(
+                "banner_cache_lookups_total{cache=\"search_options\",result=\"hit\"} 1\n"
+            )
+        );
+        assert!(text.contains(
+            "banner_scrape_job_duration_seconds_count{target=\"subject\",outcome=\"success\"} 1\n"
+        ));
+        assert!(
+            text.contains(
+                "# TYPE banner_db_pool_connections gauge\nbanner_db_pool_connections 4\n"
+            )
+        );
+        assert!(text.contains(
+            "# TYPE banner_sessions_created_total counter\nbanner_sessions_created_total 7\n"
+        ));
+    }
+
+    #[test]
+    fn scrape_token_gates_access() {
+        assert!(!Metrics::new(None).authorizes(Some("")));
+
+        let metrics = Metrics::new(Some("s3cret"));
+        assert!(metrics.enabled());
+        assert!(metrics.authorizes(Some("s3cret")));
+        assert!(!metrics.authorizes(Some("s3cre")));
+        assert!(!metrics.authorizes(None));
+    }
+}
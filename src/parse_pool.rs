@@ -0,0 +1,104 @@
+//! Bounded worker pool for CPU-heavy HTML parsing.
+//!
+//! `html_scraper::Html` is `!Send` and building one is pure CPU work, so
+//! parsing on the async runtime both stalls other tasks and forces callers to
+//! drop documents before every `.await`. Closures handed to the pool run on
+//! tokio's blocking threads, with a semaphore capping how many parse at once so
+//! a burst of pages can't starve the rest of the blocking pool.
+
+use std::sync::{Arc, LazyLock};
+
+use anyhow::{Context, Result};
+use tokio::sync::Semaphore;
+
+/// Shared pool sized to the machine's available parallelism.
+static GLOBAL: LazyLock<ParsePool> = LazyLock::new(|| {
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(2);
+    ParsePool::new(workers)
+});
+
+#[derive(Clone)]
+pub struct ParsePool {
+    permits: Arc<Semaphore>,
+}
+
+impl ParsePool {
+    /// Create a pool that runs at most `workers` parses concurrently.
+    pub fn new(workers: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(workers.max(1))),
+        }
+    }
+
+    /// The process-wide pool.
+    pub fn global() -> &'static ParsePool {
+        &GLOBAL
+    }
+
+    /// Run `parse` on a blocking thread once a worker slot is free.
+    ///
+    /// Parse the document and extract everything needed inside the closure;
+    /// only `Send` results come back to the caller.
+    pub async fn run<T, F>(&self, parse: F) -> Result<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = self
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .context("parse pool closed")?;
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            parse()
+        })
+        .await
+        .context("parse worker panicked")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn caps_concurrent_parses() {
+        let pool = ParsePool::new(2);
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let (pool, active, peak) = (pool.clone(), active.clone(), peak.clone());
+                tokio::spawn(async move {
+                    pool.run(move || {
+                        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(std::time::Duration::from_millis(10));
+                        active.fetch_sub(1, Ordering::SeqCst);
+                    })
+                    .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn reports_panics_as_errors() {
+        let pool = ParsePool::new(1);
+        let result = pool.run(|| panic!("bad markup")).await;
+        assert!(result.is_err());
+        // The slot is released after a panic.
+        assert_eq!(pool.run(|| 1).await.unwrap(), 1);
+    }
+}
@@ -81,6 +81,7 @@ const REVIEW_PAGE_SIZE: u32 = 20;
 /// Client for fetching professor data from RateMyProfessors.
 pub struct RmpClient {
     http: reqwest::Client,
+    endpoint: String,
 }
 
 impl Default for RmpClient {
@@ -91,8 +92,14 @@ impl Default for RmpClient {
 
 impl RmpClient {
     pub fn new() -> Self {
+        Self::with_endpoint(GRAPHQL_URL)
+    }
+
+    /// Client for a GraphQL endpoint other than RateMyProfessors' own, e.g. a mock server.
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
         Self {
             http: reqwest::Client::new(),
+            endpoint: endpoint.into(),
         }
     }
 
@@ -141,7 +148,7 @@ impl RmpClient {
 
             let resp = self
                 .http
-                .post(GRAPHQL_URL)
+                .post(&self.endpoint)
                 .header("Authorization", AUTH_HEADER)
                 .json(&body)
                 .send()
@@ -215,7 +222,7 @@ impl RmpClient {
 
         let resp = self
             .http
-            .post(GRAPHQL_URL)
+            .post(&self.endpoint)
             .header("Authorization", AUTH_HEADER)
             .json(&body)
             .send()
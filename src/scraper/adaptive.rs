@@ -2,12 +2,18 @@
 //!
 //! Assigns per-subject scrape intervals based on recent change rates,
 //! consecutive zero-change runs, failure patterns, and time of day.
+//!
+//! During a term's registration windows, high-churn subjects are boosted to a
+//! short fixed interval and high priority; the boost lapses on its own once
+//! the window's last day has passed.
 
-use chrono::{DateTime, Datelike, Timelike, Utc};
+use anyhow::{Context, bail};
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc};
 use chrono_tz::US::Central;
+use std::str::FromStr;
 use std::time::Duration;
 
-use crate::data::models::SubjectResultStats;
+use crate::data::models::{ScrapePriority, SubjectResultStats};
 
 const FLOOR_INTERVAL: Duration = Duration::from_secs(3 * 60);
 const MODERATE_HIGH_INTERVAL: Duration = Duration::from_secs(5 * 60);
@@ -21,6 +27,14 @@ pub(crate) const ARCHIVED_INTERVAL: Duration = Duration::from_secs(21 * 24 * 60
 const PAUSE_PROBE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
 const EMPTY_FETCH_PAUSE_THRESHOLD: i64 = 3;
 const FAILURE_PAUSE_THRESHOLD: i64 = 5;
+/// Interval for high-churn subjects while registration is open, regardless
+/// of time of day.
+const REGISTRATION_INTERVAL: Duration = Duration::from_secs(90);
+/// Change ratio at which a subject counts as high-churn.
+const HIGH_CHURN_RATIO: f64 = 0.05;
+/// Largest fraction an adaptive interval is stretched or shortened by, so
+/// subjects in the same tier don't all come due in the same cycle.
+const MAX_JITTER: f64 = 0.1;
 
 /// Scheduling tier for a term based on its temporal status and archive flag.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,7 +53,6 @@ pub enum TermCategory {
 #[derive(Debug, Clone)]
 pub struct SubjectStats {
     pub subject: String,
-    #[allow(dead_code)]
     pub term: String,
     pub recent_runs: i64,
     pub avg_change_ratio: f64,
@@ -77,6 +90,98 @@ impl From<SubjectResultStats> for SubjectStats {
     }
 }
 
+/// Days on which registration (or add/drop) for a term is open, in campus
+/// time, both ends inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrationWindow {
+    pub term: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl FromStr for RegistrationWindow {
+    type Err = anyhow::Error;
+
+    /// Parses `term:start..end`, e.g. `202620:2026-04-06..2026-04-24`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (term, range) = s
+            .split_once(':')
+            .with_context(|| format!("expected term:start..end, got {s:?}"))?;
+        let (start, end) = range
+            .split_once("..")
+            .with_context(|| format!("expected start..end, got {range:?}"))?;
+        let parse = |d: &str| {
+            NaiveDate::parse_from_str(d.trim(), "%Y-%m-%d")
+                .with_context(|| format!("invalid date {d:?}"))
+        };
+        let (start, end) = (parse(start)?, parse(end)?);
+        if end < start {
+            bail!("registration window {s:?} ends before it starts");
+        }
+        Ok(Self {
+            term: term.trim().to_owned(),
+            start,
+            end,
+        })
+    }
+}
+
+/// Configured registration windows across all terms.
+#[derive(Debug, Clone, Default)]
+pub struct RegistrationCalendar {
+    windows: Vec<RegistrationWindow>,
+}
+
+impl RegistrationCalendar {
+    pub fn new(windows: Vec<RegistrationWindow>) -> Self {
+        Self { windows }
+    }
+
+    /// Whether any of `term`'s windows covers the campus date of `now`.
+    pub fn is_open(&self, term: &str, now: DateTime<Utc>) -> bool {
+        let today = now.with_timezone(&Central).date_naive();
+        self.windows
+            .iter()
+            .any(|w| w.term == term && w.start <= today && today <= w.end)
+    }
+}
+
+/// Whether a subject changes enough to be boosted during registration.
+fn is_high_churn(stats: &SubjectStats) -> bool {
+    stats.recent_runs > 0 && stats.avg_change_ratio >= HIGH_CHURN_RATIO
+}
+
+/// Priority for a subject's scrape job: high for high-churn subjects while
+/// registration is open, low otherwise.
+pub fn subject_priority(stats: &SubjectStats, registration_open: bool) -> ScrapePriority {
+    if registration_open && is_high_churn(stats) {
+        ScrapePriority::High
+    } else {
+        ScrapePriority::Low
+    }
+}
+
+/// Stable per-subject offset in `[-MAX_JITTER, MAX_JITTER)`.
+///
+/// Uses FNV-1a over the subject and term so a subject keeps the same offset
+/// across cycles and restarts.
+fn subject_jitter(subject: &str, term: &str) -> f64 {
+    let hash = subject
+        .bytes()
+        .chain([b':'])
+        .chain(term.bytes())
+        .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+    let unit = (hash >> 11) as f64 / (1u64 << 53) as f64;
+    (unit * 2.0 - 1.0) * MAX_JITTER
+}
+
+/// Stretch or shorten `interval` by the subject's jitter.
+fn jittered(interval: Duration, stats: &SubjectStats) -> Duration {
+    interval.mul_f64(1.0 + subject_jitter(&stats.subject, &stats.term))
+}
+
 /// Compute the base interval tier from change-rate statistics.
 pub fn compute_base_interval(stats: &SubjectStats) -> Duration {
     if stats.recent_runs == 0 {
@@ -122,14 +227,27 @@ pub fn time_of_day_multiplier(now: DateTime<Utc>) -> u32 {
     }
 }
 
+/// Evaluate whether a subject should be scraped now, outside any
+/// registration window.
+pub fn evaluate_subject(
+    stats: &SubjectStats,
+    now: DateTime<Utc>,
+    category: TermCategory,
+) -> SubjectSchedule {
+    evaluate_subject_during(stats, now, category, false)
+}
+
 /// Evaluate whether a subject should be scraped now.
 ///
-/// Combines base interval, time-of-day multiplier, pause detection (empty
-/// fetches / consecutive failures), and term category scheduling tiers.
-pub fn evaluate_subject(
+/// Combines base interval, time-of-day multiplier, per-subject jitter, pause
+/// detection (empty fetches / consecutive failures), and term category
+/// scheduling tiers. While `registration_open`, high-churn subjects use
+/// [`REGISTRATION_INTERVAL`] instead.
+pub fn evaluate_subject_during(
     stats: &SubjectStats,
     now: DateTime<Utc>,
     category: TermCategory,
+    registration_open: bool,
 ) -> SubjectSchedule {
     let elapsed = (now - stats.last_completed)
         .to_std()
@@ -164,9 +282,14 @@ pub fn evaluate_subject(
         };
     }
 
-    let base = compute_base_interval(stats);
-    let multiplier = time_of_day_multiplier(now);
-    let effective = base * multiplier;
+    let effective = if registration_open && is_high_churn(stats) {
+        jittered(REGISTRATION_INTERVAL, stats)
+    } else {
+        jittered(
+            compute_base_interval(stats) * time_of_day_multiplier(now),
+            stats,
+        )
+    };
 
     if elapsed >= effective {
         SubjectSchedule::Eligible(effective)
@@ -364,4 +487,70 @@ mod tests {
         let dt = Utc.with_ymd_and_hms(2025, 7, 12, 17, 0, 0).unwrap();
         assert_eq!(time_of_day_multiplier(dt), 4);
     }
+
+    #[test]
+    fn test_jitter_is_stable_and_bounded() {
+        let a = subject_jitter("CS", "202620");
+        assert_eq!(a, subject_jitter("CS", "202620"));
+        assert_ne!(a, subject_jitter("MAT", "202620"));
+        for subject in ["CS", "MAT", "BIO", "ACC", "EE", "IS"] {
+            let j = subject_jitter(subject, "202620");
+            assert!((-MAX_JITTER..MAX_JITTER).contains(&j), "{subject}: {j}");
+        }
+    }
+
+    #[test]
+    fn test_registration_window_parse() {
+        let w: RegistrationWindow = "202620:2026-04-06..2026-04-24".parse().unwrap();
+        assert_eq!(w.term, "202620");
+        assert_eq!(w.start, NaiveDate::from_ymd_opt(2026, 4, 6).unwrap());
+        assert_eq!(w.end, NaiveDate::from_ymd_opt(2026, 4, 24).unwrap());
+
+        assert!("202620".parse::<RegistrationWindow>().is_err());
+        assert!("202620:2026-04-06".parse::<RegistrationWindow>().is_err());
+        assert!(
+            "202620:2026-04-24..2026-04-06"
+                .parse::<RegistrationWindow>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_registration_window_uses_campus_dates() {
+        let calendar =
+            RegistrationCalendar::new(vec!["202620:2026-04-06..2026-04-24".parse().unwrap()]);
+        // 11pm CT on the last day is 04:00 UTC the next day.
+        let last_evening = Utc.with_ymd_and_hms(2026, 4, 25, 4, 0, 0).unwrap();
+        assert!(calendar.is_open("202620", last_evening));
+        assert!(!calendar.is_open("202630", last_evening));
+        let after = Utc.with_ymd_and_hms(2026, 4, 25, 6, 0, 0).unwrap();
+        assert!(!calendar.is_open("202620", after));
+    }
+
+    #[test]
+    fn test_registration_boosts_high_churn_only() {
+        // Saturday night CT: the adaptive interval is 4x the floor.
+        let night = Utc.with_ymd_and_hms(2025, 7, 13, 4, 0, 0).unwrap();
+        let mut stats = make_stats("CS");
+        stats.avg_change_ratio = 0.15;
+        stats.last_completed = night - chrono::Duration::minutes(2);
+
+        assert!(matches!(
+            evaluate_subject(&stats, night, TermCategory::Current),
+            SubjectSchedule::Cooldown(_)
+        ));
+        assert!(matches!(
+            evaluate_subject_during(&stats, night, TermCategory::Current, true),
+            SubjectSchedule::Eligible(_)
+        ));
+        assert_eq!(subject_priority(&stats, true), ScrapePriority::High);
+        assert_eq!(subject_priority(&stats, false), ScrapePriority::Low);
+
+        stats.avg_change_ratio = 0.02;
+        assert!(matches!(
+            evaluate_subject_during(&stats, night, TermCategory::Current, true),
+            SubjectSchedule::Cooldown(_)
+        ));
+        assert_eq!(subject_priority(&stats, true), ScrapePriority::Low);
+    }
 }
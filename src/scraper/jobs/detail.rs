@@ -0,0 +1,76 @@
+use super::Job;
+use crate::banner::BannerApi;
+use crate::data::DbContext;
+use crate::data::courses;
+use crate::data::models::UpsertCounts;
+use crate::data::unsigned::Count;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace};
+
+/// Job implementation for enriching a batch of sections with detail endpoint data.
+///
+/// Stored with `TargetType::CrnList`. Each CRN costs five Banner requests, so
+/// the scheduler keeps batches small.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetailJob {
+    /// Term code (e.g., "202510").
+    pub term: String,
+    pub crns: Vec<String>,
+}
+
+impl DetailJob {
+    pub fn new(term: String, crns: Vec<String>) -> Self {
+        Self { term, crns }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for DetailJob {
+    #[tracing::instrument(skip(self, banner_api, db), fields(term = %self.term, crns = self.crns.len()))]
+    async fn process(&self, banner_api: &BannerApi, db: &DbContext) -> Result<UpsertCounts> {
+        let mut fetched = 0usize;
+        let mut changed = 0usize;
+        let mut audits = 0usize;
+
+        for crn in &self.crns {
+            let Some(course_id) = courses::get_id_by_crn(db.pool(), &self.term, crn).await? else {
+                trace!(crn = %crn, "Course no longer exists, skipping detail fetch");
+                continue;
+            };
+
+            let details = banner_api.get_course_details(&self.term, crn).await?;
+            debug!(
+                crn = %crn,
+                restrictions = details.restrictions.len(),
+                corequisites = details.corequisites.len(),
+                linked_groups = details.linked_groups.len(),
+                "Fetched course details"
+            );
+
+            let saved = db.courses().save_details(course_id, &details).await?;
+            if saved.seats_changed {
+                changed += 1;
+            }
+            audits += saved.events.len();
+            fetched += 1;
+        }
+
+        Ok(UpsertCounts {
+            courses_fetched: Count::try_from(fetched)?,
+            courses_changed: Count::try_from(changed)?,
+            courses_unchanged: Count::try_from(fetched - changed)?,
+            audits_generated: Count::try_from(audits)?,
+            metrics_generated: Count::try_from(changed)?,
+            diff: None,
+        })
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Fetch details: {} CRNs (term {})",
+            self.crns.len(),
+            self.term
+        )
+    }
+}
@@ -1,6 +1,7 @@
+pub mod detail;
 pub mod subject;
 
-use crate::banner::BannerApi;
+use crate::banner::{BannerApi, BannerThrottled, BannerUnavailable};
 use crate::data::DbContext;
 use crate::data::models::{TargetType, UpsertCounts};
 use anyhow::Result;
@@ -23,6 +24,23 @@ pub enum JobError {
     Recoverable(#[source] anyhow::Error),
     #[error("Unrecoverable error: {0}")]
     Unrecoverable(#[source] anyhow::Error),
+    /// Banner asked us to back off; not the job's fault, so not a failed attempt.
+    #[error(transparent)]
+    Throttled(BannerThrottled),
+}
+
+impl JobError {
+    /// Classify an error from [`Job::process`]: throttling and an open circuit
+    /// mean "come back later", anything else is worth retrying.
+    pub fn classify(err: anyhow::Error) -> Self {
+        if let Some(throttled) = BannerThrottled::find(&err) {
+            JobError::Throttled(throttled.clone())
+        } else if let Some(unavailable) = BannerUnavailable::find(&err) {
+            JobError::Throttled(unavailable.into())
+        } else {
+            JobError::Recoverable(err)
+        }
+    }
 }
 
 /// Common trait interface for all job types
@@ -41,6 +59,7 @@ pub trait Job: Send + Sync {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum JobType {
     Subject(subject::SubjectJob),
+    Detail(detail::DetailJob),
 }
 
 impl JobType {
@@ -55,6 +74,11 @@ impl JobType {
                     serde_json::from_value(payload).map_err(JobParseError::InvalidJson)?;
                 Ok(JobType::Subject(subject_job))
             }
+            TargetType::CrnList => {
+                let detail_job: detail::DetailJob =
+                    serde_json::from_value(payload).map_err(JobParseError::InvalidJson)?;
+                Ok(JobType::Detail(detail_job))
+            }
             _ => Err(JobParseError::UnsupportedTargetType(target_type)),
         }
     }
@@ -63,6 +87,7 @@ impl JobType {
     pub fn boxed(self) -> Box<dyn Job> {
         match self {
             JobType::Subject(job) => Box::new(job),
+            JobType::Detail(job) => Box::new(job),
         }
     }
 }
@@ -106,12 +131,24 @@ mod tests {
     }
 
     #[test]
-    fn test_from_target_unsupported_variants() {
-        let unsupported = [
-            TargetType::CourseRange,
+    fn test_from_target_crn_list_valid() {
+        let result = JobType::from_target_type_and_payload(
             TargetType::CrnList,
-            TargetType::SingleCrn,
-        ];
+            json!({"term": "202510", "crns": ["12345", "12346"]}),
+        );
+        assert!(matches!(result, Ok(JobType::Detail(ref job)) if job.crns.len() == 2));
+    }
+
+    #[test]
+    fn test_from_target_crn_list_missing_term() {
+        let result =
+            JobType::from_target_type_and_payload(TargetType::CrnList, json!({"crns": []}));
+        assert!(matches!(result, Err(JobParseError::InvalidJson(_))));
+    }
+
+    #[test]
+    fn test_from_target_unsupported_variants() {
+        let unsupported = [TargetType::CourseRange, TargetType::SingleCrn];
         for target_type in unsupported {
             let result =
                 JobType::from_target_type_and_payload(target_type, json!({"subject": "CS"}));
@@ -130,7 +167,7 @@ mod tests {
         assert!(display.contains("Invalid JSON"), "got: {display}");
 
         let unsupported_err =
-            JobType::from_target_type_and_payload(TargetType::CrnList, json!({})).unwrap_err();
+            JobType::from_target_type_and_payload(TargetType::SingleCrn, json!({})).unwrap_err();
         let display = unsupported_err.to_string();
         assert!(
             display.contains("Unsupported target type"),
@@ -1,9 +1,12 @@
 use super::Job;
 use crate::banner::{BannerApi, SearchQuery, Term};
 use crate::data::DbContext;
+use crate::data::courses;
 use crate::data::models::UpsertCounts;
+use crate::data::unsigned::Count;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use tracing::debug;
 
 /// Job implementation for scraping subject data.
@@ -50,12 +53,30 @@ impl Job for SubjectJob {
             .search(&term, &query, "subjectDescription", false)
             .await?;
 
-        let counts = if let Some(courses_from_api) = search_result.data {
-            debug!(count = courses_from_api.len(), "Found courses");
-            db.courses().batch_upsert(&courses_from_api).await?
-        } else {
+        let stored = courses::list_crns_for_subject(db.pool(), &term, subject_code).await?;
+        let fetched = search_result.data.unwrap_or_default();
+
+        let mut counts = if fetched.is_empty() {
             UpsertCounts::default()
+        } else {
+            debug!(count = fetched.len(), "Found courses");
+            db.courses().batch_upsert(&fetched).await?
         };
+
+        // The upsert only sees fetched courses; removals come from comparing
+        // against what was stored before this run.
+        let fetched_crns: HashSet<&str> = fetched
+            .iter()
+            .map(|c| c.course_reference_number.as_str())
+            .collect();
+        let missing: Vec<String> = stored
+            .into_iter()
+            .filter(|crn| !fetched_crns.contains(crn.as_str()))
+            .collect();
+        // Sections dropped in an earlier run stay missing; only count new ones.
+        let removed = courses::record_removals(db.pool(), &term, &missing).await?;
+        counts.diff.get_or_insert_with(Default::default).removed = Count::try_from(removed.len())?;
+
         Ok(counts)
     }
 
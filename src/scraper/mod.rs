@@ -1,23 +1,40 @@
 pub mod adaptive;
 pub mod jobs;
+pub mod pool;
 pub mod scheduler;
+pub mod search_index;
+pub mod syncs;
 pub mod worker;
 
-use crate::banner::BannerApi;
+use crate::banner::circuit::{BannerHealth, CircuitState};
+use crate::banner::{BannerApi, Term};
 use crate::data::DbContext;
-use crate::data::events::EventBuffer;
+use crate::data::events::{DomainEvent, EventBuffer};
+use crate::data::models::{ScrapeJob, ScrapePriority, TargetType};
+use crate::data::scoring::ScoringDecay;
+use crate::metrics::Metrics;
 use crate::services::Service;
 use crate::state::ReferenceCache;
 use crate::state::{ServiceStatus, ServiceStatusRegistry};
+use crate::utils::fmt_duration;
 use sqlx::PgPool;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use tokio::sync::{Notify, RwLock, broadcast};
 use tokio::task::JoinHandle;
+use tokio::time;
 use tracing::{info, warn};
 
+use self::adaptive::RegistrationCalendar;
+use self::jobs::subject::SubjectJob;
+use self::pool::{PoolConfig, WorkerPool};
 use self::scheduler::Scheduler;
-use self::worker::Worker;
+use self::search_index::SearchIndexRefresher;
+use self::syncs::SyncRequests;
+use self::worker::{RetryPolicy, Worker};
+
+/// Longest the scraper waits on session warm-up before starting anyway.
+const WARM_UP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
 
 /// The main service that will be managed by the application's `ServiceManager`.
 ///
@@ -31,13 +48,23 @@ pub struct ScraperService {
     events: Arc<EventBuffer>,
     bluebook_notify: Arc<Notify>,
     bluebook_force_flag: Arc<AtomicBool>,
+    sync_requests: Arc<SyncRequests>,
+    metrics: Arc<Metrics>,
+    retry_policy: RetryPolicy,
+    pool_config: PoolConfig,
+    registration: Arc<RegistrationCalendar>,
+    scoring_decay: ScoringDecay,
+    search_index: SearchIndexRefresher,
     scheduler_handle: Option<JoinHandle<()>>,
+    banner_health_handle: Option<JoinHandle<()>>,
+    search_index_handle: Option<JoinHandle<()>>,
     worker_handles: Vec<JoinHandle<()>>,
     shutdown_tx: Option<broadcast::Sender<()>>,
 }
 
 impl ScraperService {
     /// Creates a new `ScraperService`.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db_pool: PgPool,
         banner_api: Arc<BannerApi>,
@@ -46,6 +73,12 @@ impl ScraperService {
         events: Arc<EventBuffer>,
         bluebook_notify: Arc<Notify>,
         bluebook_force_flag: Arc<AtomicBool>,
+        sync_requests: Arc<SyncRequests>,
+        metrics: Arc<Metrics>,
+        retry_policy: RetryPolicy,
+        pool_config: PoolConfig,
+        registration: RegistrationCalendar,
+        scoring_decay: ScoringDecay,
     ) -> Self {
         Self {
             db_pool,
@@ -55,12 +88,56 @@ impl ScraperService {
             events,
             bluebook_notify,
             bluebook_force_flag,
+            sync_requests,
+            metrics,
+            retry_policy,
+            pool_config,
+            registration: Arc::new(registration),
+            scoring_decay,
+            search_index: SearchIndexRefresher::new(),
             scheduler_handle: None,
+            banner_health_handle: None,
+            search_index_handle: None,
             worker_handles: Vec::new(),
             shutdown_tx: None,
         }
     }
 
+    /// Pre-create Banner sessions for current and upcoming terms so the first
+    /// scheduling cycle doesn't wait on session handshakes.
+    async fn warm_up_sessions(&self) {
+        let count = self.pool_config.warm_sessions;
+        if count == 0 {
+            return;
+        }
+
+        let terms = match crate::data::terms::get_enabled_terms_for_scheduling(&self.db_pool).await
+        {
+            Ok(terms) => terms,
+            Err(e) => {
+                warn!(error = ?e, "Failed to list terms for session warm-up");
+                return;
+            }
+        };
+        let current = Term::get_current().inner().to_string();
+        let sessions = &self.banner_api.sessions;
+        let warm_ups = terms
+            .iter()
+            .filter(|t| t.code >= current)
+            .filter_map(|t| t.code.parse::<Term>().ok())
+            .map(|term| sessions.warm_up(term, count));
+
+        let start = std::time::Instant::now();
+        match time::timeout(WARM_UP_TIMEOUT, futures::future::join_all(warm_ups)).await {
+            Ok(created) => info!(
+                sessions = created.iter().sum::<usize>(),
+                elapsed = fmt_duration(start.elapsed()),
+                "Banner sessions warmed up"
+            ),
+            Err(_) => warn!("Banner session warm-up timed out, starting anyway"),
+        }
+    }
+
     /// Starts the scheduler and a pool of workers.
     ///
     /// Force-unlocks any jobs left locked by a previous unclean shutdown before
@@ -77,6 +154,8 @@ impl ScraperService {
 
         info!("ScraperService starting");
 
+        self.warm_up_sessions().await;
+
         // Create shutdown channel
         let (shutdown_tx, _) = broadcast::channel(1);
         self.shutdown_tx = Some(shutdown_tx.clone());
@@ -87,6 +166,9 @@ impl ScraperService {
             self.reference_cache.clone(),
             self.bluebook_notify.clone(),
             self.bluebook_force_flag.clone(),
+            self.sync_requests.clone(),
+            self.registration.clone(),
+            self.scoring_decay,
         );
         let shutdown_rx = shutdown_tx.subscribe();
         let scheduler_handle = tokio::spawn(async move {
@@ -95,10 +177,43 @@ impl ScraperService {
         self.scheduler_handle = Some(scheduler_handle);
         info!("Scheduler task spawned");
 
-        let worker_count = 4; // This could be configurable
-        for i in 0..worker_count {
+        let banner_api = self.banner_api.clone();
+        let service_statuses = self.service_statuses.clone();
+        let events = self.events.clone();
+        let shutdown_rx = shutdown_tx.subscribe();
+        self.banner_health_handle = Some(tokio::spawn(async move {
+            report_banner_health(&banner_api, &service_statuses, &events, shutdown_rx).await;
+        }));
+
+        // Backfill terms never indexed or indexed with an older document shape
+        match crate::data::search_index::stale_terms(&self.db_pool).await {
+            Ok(terms) => {
+                for term_code in &terms {
+                    self.search_index.request(term_code);
+                }
+            }
+            Err(e) => warn!(error = ?e, "Failed to list stale search index terms"),
+        }
+
+        let search_index = self.search_index.clone();
+        let search_index_pool = self.db_pool.clone();
+        let shutdown_rx = shutdown_tx.subscribe();
+        self.search_index_handle = Some(tokio::spawn(async move {
+            search_index.run(search_index_pool, shutdown_rx).await;
+        }));
+
+        let pool = WorkerPool::new(&self.pool_config);
+        for i in 0..pool.workers() {
             let worker_db = DbContext::new(self.db_pool.clone(), self.events.clone());
-            let worker = Worker::new(i, worker_db, self.banner_api.clone());
+            let worker = Worker::new(
+                i,
+                worker_db,
+                pool.clone(),
+                self.banner_api.clone(),
+                self.search_index.clone(),
+                self.metrics.clone(),
+                self.retry_policy,
+            );
             let shutdown_rx = shutdown_tx.subscribe();
             let worker_handle = tokio::spawn(async move {
                 worker.run(shutdown_rx).await;
@@ -143,6 +258,12 @@ impl Service for ScraperService {
         if let Some(handle) = self.scheduler_handle.take() {
             all_handles.push(handle);
         }
+        if let Some(handle) = self.search_index_handle.take() {
+            all_handles.push(handle);
+        }
+        if let Some(handle) = self.banner_health_handle.take() {
+            all_handles.push(handle);
+        }
         all_handles.append(&mut self.worker_handles);
 
         // Wait for all tasks to complete (no internal timeout - let ServiceManager handle it)
@@ -160,3 +281,84 @@ impl Service for ScraperService {
         Ok(())
     }
 }
+
+fn banner_service_status(health: &BannerHealth) -> ServiceStatus {
+    match health.state {
+        CircuitState::Closed => ServiceStatus::Connected,
+        CircuitState::Open | CircuitState::HalfOpen => ServiceStatus::Error,
+    }
+}
+
+/// Mirror the Banner circuit breaker into the service registry (as `banner`)
+/// and the admin event stream until shutdown.
+async fn report_banner_health(
+    banner_api: &BannerApi,
+    service_statuses: &ServiceStatusRegistry,
+    events: &EventBuffer,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) {
+    let mut health_rx = banner_api.circuit().subscribe();
+    let health = health_rx.borrow_and_update().clone();
+    service_statuses.set("banner", banner_service_status(&health));
+
+    loop {
+        tokio::select! {
+            _ = shutdown_rx.recv() => break,
+            changed = health_rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let health = health_rx.borrow_and_update().clone();
+                service_statuses.set("banner", banner_service_status(&health));
+                events.publish(DomainEvent::BannerHealth(health));
+            }
+        }
+    }
+}
+
+/// Errors from [`enqueue_subject_scrape`] that callers surface to the operator.
+#[derive(Debug, thiserror::Error)]
+pub enum ManualScrapeError {
+    #[error("Unknown term '{0}'")]
+    UnknownTerm(String),
+    #[error("Unknown subject '{0}'")]
+    UnknownSubject(String),
+}
+
+/// Enqueue a high-priority scrape of one subject, outside the scheduler's cadence.
+///
+/// Shared by the admin web API and the `/admin scrape` bot command. Returns
+/// `None` when an identical job is already queued.
+pub async fn enqueue_subject_scrape(
+    db: &DbContext,
+    term: &str,
+    subject: &str,
+) -> anyhow::Result<Option<ScrapeJob>> {
+    if crate::data::terms::get_term_by_code(db.pool(), term)
+        .await?
+        .is_none()
+    {
+        return Err(ManualScrapeError::UnknownTerm(term.to_owned()).into());
+    }
+    let subject = subject.to_uppercase();
+    if !crate::data::reference::has_code(db.pool(), "subject", &subject).await? {
+        return Err(ManualScrapeError::UnknownSubject(subject).into());
+    }
+
+    let job = SubjectJob::new(subject, term.to_owned());
+    let payload = serde_json::to_value(&job)?;
+    let existing = db
+        .scrape_jobs()
+        .find_existing_payloads(TargetType::Subject, std::slice::from_ref(&payload))
+        .await?;
+    if existing.contains(&payload.to_string()) {
+        return Ok(None);
+    }
+
+    let inserted = db
+        .scrape_jobs()
+        .batch_insert(&[(payload, TargetType::Subject, ScrapePriority::High)])
+        .await?;
+    info!(subject = %job.subject, term, "Manual subject scrape enqueued");
+    Ok(inserted.into_iter().next())
+}
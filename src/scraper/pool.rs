@@ -0,0 +1,160 @@
+//! Concurrency limits shared by the scraper's workers.
+//!
+//! Every worker polls the same queue, so how many jobs run at once is bounded
+//! by the worker count. [`WorkerPool`] adds per-target-type limits on top: a
+//! worker reserves a slot in each limited type that has one free, asks the
+//! queue only for jobs of types it holds a slot for (or that are unlimited),
+//! and keeps just the slot matching the job it got. A backfill of one type
+//! therefore can't occupy every worker.
+//!
+//! Fairness between terms is handled by the queue itself; see
+//! `ScrapeJobOps::lock_next_excluding`.
+
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::data::models::TargetType;
+
+/// Worker count when none is configured.
+pub const DEFAULT_WORKERS: usize = 4;
+
+/// Banner sessions pre-created per term when none is configured.
+pub const DEFAULT_WARM_SESSIONS: usize = 2;
+
+/// How many workers to run and how many of them one job type may occupy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolConfig {
+    pub workers: usize,
+    /// Most jobs of a type that may run at once; types not listed are only
+    /// bounded by `workers`.
+    pub type_limits: Vec<(TargetType, usize)>,
+    /// Banner sessions created for each current or upcoming term before the
+    /// workers start.
+    pub warm_sessions: usize,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            workers: DEFAULT_WORKERS,
+            type_limits: Vec::new(),
+            warm_sessions: DEFAULT_WARM_SESSIONS,
+        }
+    }
+}
+
+/// Per-type job slots shared by all workers.
+#[derive(Clone)]
+pub struct WorkerPool {
+    workers: usize,
+    limits: Arc<Vec<(TargetType, Arc<Semaphore>)>>,
+}
+
+impl WorkerPool {
+    pub fn new(config: &PoolConfig) -> Self {
+        let workers = config.workers.max(1);
+        let limits = config
+            .type_limits
+            .iter()
+            // A limit at or above the worker count can never bind.
+            .filter(|(_, limit)| *limit < workers)
+            .map(|(target_type, limit)| (*target_type, Arc::new(Semaphore::new(*limit))))
+            .collect();
+        Self {
+            workers,
+            limits: Arc::new(limits),
+        }
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Take a free slot in every limited type, so the caller can lock a job
+    /// of any type it isn't blocked from.
+    pub fn reserve(&self) -> Reservation {
+        let mut permits = Vec::new();
+        let mut blocked = Vec::new();
+        for (target_type, slots) in self.limits.iter() {
+            match slots.clone().try_acquire_owned() {
+                Ok(permit) => permits.push((*target_type, permit)),
+                Err(_) => blocked.push(*target_type),
+            }
+        }
+        Reservation { permits, blocked }
+    }
+}
+
+/// Slots held while a worker locks its next job.
+pub struct Reservation {
+    permits: Vec<(TargetType, OwnedSemaphorePermit)>,
+    blocked: Vec<TargetType>,
+}
+
+impl Reservation {
+    /// Types whose limit is reached; the next job must be of another type.
+    pub fn blocked(&self) -> &[TargetType] {
+        &self.blocked
+    }
+
+    /// Whether every job type is at its limit.
+    pub fn all_blocked(&self) -> bool {
+        TargetType::ALL.iter().all(|t| self.blocked.contains(t))
+    }
+
+    /// Keep the slot for the job that was locked and release the rest.
+    /// `None` when the type is unlimited.
+    pub fn keep(self, target_type: TargetType) -> Option<OwnedSemaphorePermit> {
+        self.permits
+            .into_iter()
+            .find(|(t, _)| *t == target_type)
+            .map(|(_, permit)| permit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(workers: usize, limits: &[(TargetType, usize)]) -> WorkerPool {
+        WorkerPool::new(&PoolConfig {
+            workers,
+            type_limits: limits.to_vec(),
+            ..PoolConfig::default()
+        })
+    }
+
+    #[test]
+    fn limited_type_blocks_once_its_slots_are_kept() {
+        let pool = pool(4, &[(TargetType::CrnList, 1)]);
+
+        let first = pool.reserve();
+        assert!(first.blocked().is_empty());
+        let held = first.keep(TargetType::CrnList);
+        assert!(held.is_some());
+
+        let second = pool.reserve();
+        assert_eq!(second.blocked(), [TargetType::CrnList]);
+        // A subject job doesn't consume the CRN slot.
+        assert!(second.keep(TargetType::Subject).is_none());
+
+        drop(held);
+        assert!(pool.reserve().blocked().is_empty());
+    }
+
+    #[test]
+    fn unused_reservations_release_their_slots() {
+        let pool = pool(4, &[(TargetType::Subject, 1)]);
+        drop(pool.reserve());
+        assert!(pool.reserve().blocked().is_empty());
+    }
+
+    #[test]
+    fn limits_at_worker_count_are_ignored() {
+        let pool = pool(2, &[(TargetType::Subject, 2), (TargetType::CrnList, 0)]);
+        let reservation = pool.reserve();
+        assert_eq!(reservation.blocked(), [TargetType::CrnList]);
+        assert!(!reservation.all_blocked());
+    }
+}
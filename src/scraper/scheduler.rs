@@ -1,14 +1,19 @@
 use crate::banner::{BannerApi, Term};
 use crate::bluebook::BlueBookClient;
 use crate::data::DbContext;
+use crate::data::events::{DomainEvent, InstructorEvent};
 use crate::data::models::{ReferenceData, ScrapePriority, TargetType};
+use crate::data::scoring::{self, RecomputeMode, ScoringDecay};
 use crate::data::unsigned::Count;
 use crate::data::{kv, term_subjects, terms};
 use crate::rmp::RmpClient;
 use crate::scraper::adaptive::{
-    ARCHIVED_INTERVAL, SubjectSchedule, SubjectStats, TermCategory, evaluate_subject,
+    ARCHIVED_INTERVAL, RegistrationCalendar, SubjectSchedule, SubjectStats, TermCategory,
+    evaluate_subject_during, subject_priority,
 };
+use crate::scraper::jobs::detail::DetailJob;
 use crate::scraper::jobs::subject::SubjectJob;
+use crate::scraper::syncs::{self, SyncKind, SyncRequests};
 use crate::state::ReferenceCache;
 use crate::utils::fmt_duration;
 use anyhow::Result;
@@ -25,26 +30,40 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, trace, warn};
 
 /// How often reference data is re-scraped (6 hours).
-const REFERENCE_DATA_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+pub(crate) const REFERENCE_DATA_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
 
 /// How often RMP data is synced (24 hours).
-const RMP_SYNC_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+pub(crate) const RMP_SYNC_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
 
 /// How often terms are synced from Banner API (8 hours).
-const TERM_SYNC_INTERVAL: Duration = Duration::from_secs(8 * 60 * 60);
+pub(crate) const TERM_SYNC_INTERVAL: Duration = Duration::from_secs(8 * 60 * 60);
 
 /// How often to check which BlueBook subjects need re-scraping (1 day).
 ///
 /// Per-subject re-scrape frequency is governed by `RECENT_SUBJECT_INTERVAL` (14 days)
 /// and `HISTORICAL_SUBJECT_INTERVAL` (90 days) in `src/bluebook.rs`.
-const BLUEBOOK_SYNC_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+pub(crate) const BLUEBOOK_SYNC_INTERVAL: Duration = Duration::from_secs(24 * 3600);
 
 /// How often to check for professors eligible for review scraping (15 minutes).
-const RMP_REVIEW_SCRAPE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+pub(crate) const RMP_REVIEW_SCRAPE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// How often related-instructor recommendations are rebuilt (1 day).
+const RECOMMENDATIONS_INTERVAL: Duration = Duration::from_secs(24 * 3600);
 
 /// Max professors to scrape reviews for per cycle.
 const RMP_REVIEW_SCRAPE_BATCH_SIZE: i64 = 50;
 
+/// How old a section's detail data (restrictions, coreqs, linked sections) may get
+/// before it is re-fetched.
+const DETAIL_REFRESH_HOURS: i32 = 24;
+
+/// CRNs per detail job. Each CRN costs four Banner requests.
+const DETAIL_BATCH_SIZE: usize = 10;
+
+/// Upper bound on detail jobs enqueued per term per scheduling cycle, so
+/// detail enrichment never crowds out subject scrapes.
+const DETAIL_MAX_BATCHES_PER_CYCLE: usize = 5;
+
 const SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(500);
 
 // app_kv keys for persisting scheduler timestamps across restarts.
@@ -53,6 +72,7 @@ pub const KV_RMP_SYNC: &str = "scheduler.rmp_sync";
 pub const KV_TERM_SYNC: &str = "scheduler.term_sync";
 pub const KV_BLUEBOOK_SYNC: &str = "scheduler.bluebook_sync";
 pub const KV_RMP_REVIEW_SCRAPE: &str = "scheduler.rmp_review_scrape";
+pub const KV_RECOMMENDATIONS: &str = "scheduler.recommendations";
 
 /// Convert a persisted UTC timestamp to an `Instant`, preserving remaining cooldown.
 ///
@@ -85,15 +105,25 @@ pub struct Scheduler {
     bluebook_notify: Arc<Notify>,
     /// When true, the next BlueBook sync ignores per-subject interval checks.
     bluebook_force_flag: Arc<AtomicBool>,
+    /// Admin-forced syncs, run on the next cycle regardless of interval.
+    sync_requests: Arc<SyncRequests>,
+    /// Per-term registration windows that boost high-churn subjects.
+    registration: Arc<RegistrationCalendar>,
+    /// Time decay used when recomputing instructor scores after syncs.
+    scoring_decay: ScoringDecay,
 }
 
 impl Scheduler {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: DbContext,
         banner_api: Arc<BannerApi>,
         reference_cache: Arc<RwLock<ReferenceCache>>,
         bluebook_notify: Arc<Notify>,
         bluebook_force_flag: Arc<AtomicBool>,
+        sync_requests: Arc<SyncRequests>,
+        registration: Arc<RegistrationCalendar>,
+        scoring_decay: ScoringDecay,
     ) -> Self {
         Self {
             db,
@@ -102,6 +132,9 @@ impl Scheduler {
             archived_eval_times: Arc::new(std::sync::Mutex::new(HashMap::new())),
             bluebook_notify,
             bluebook_force_flag,
+            sync_requests,
+            registration,
+            scoring_decay,
         }
     }
 
@@ -132,12 +165,16 @@ impl Scheduler {
         let persisted_rmp_reviews = kv::get_timestamp(pool, KV_RMP_REVIEW_SCRAPE)
             .await
             .unwrap_or(None);
+        let persisted_recommendations = kv::get_timestamp(pool, KV_RECOMMENDATIONS)
+            .await
+            .unwrap_or(None);
 
         if persisted_ref.is_some()
             || persisted_rmp.is_some()
             || persisted_term.is_some()
             || persisted_bb.is_some()
             || persisted_rmp_reviews.is_some()
+            || persisted_recommendations.is_some()
         {
             info!(
                 last_ref_scrape = persisted_ref.map(|v| v.to_rfc3339()).as_deref(),
@@ -145,6 +182,7 @@ impl Scheduler {
                 last_term_sync = persisted_term.map(|v| v.to_rfc3339()).as_deref(),
                 last_bluebook_sync = persisted_bb.map(|v| v.to_rfc3339()).as_deref(),
                 last_rmp_review_scrape = persisted_rmp_reviews.map(|v| v.to_rfc3339()).as_deref(),
+                last_recommendations = persisted_recommendations.map(|v| v.to_rfc3339()).as_deref(),
                 "Loaded persisted scheduler timestamps"
             );
         }
@@ -155,6 +193,8 @@ impl Scheduler {
         let mut last_bluebook_sync = persisted_to_instant(persisted_bb, BLUEBOOK_SYNC_INTERVAL);
         let mut last_rmp_review_scrape =
             persisted_to_instant(persisted_rmp_reviews, RMP_REVIEW_SCRAPE_INTERVAL);
+        let mut last_recommendations =
+            persisted_to_instant(persisted_recommendations, RECOMMENDATIONS_INTERVAL);
         let mut bluebook_notified = false;
 
         loop {
@@ -166,6 +206,11 @@ impl Scheduler {
                     next_run = time::Instant::now();
                     continue;
                 }
+                _ = self.sync_requests.notified() => {
+                    info!("Sync forced by admin request");
+                    next_run = time::Instant::now();
+                    continue;
+                }
                 _ = time::sleep_until(next_run) => {
                     // Skip this cycle if the previous one is still running.
                     if let Some((ref handle, _)) = current_work
@@ -178,20 +223,30 @@ impl Scheduler {
 
                     let cancel_token = CancellationToken::new();
 
-                    let should_scrape_ref = last_ref_scrape.elapsed() >= REFERENCE_DATA_INTERVAL;
-                    let should_sync_rmp = last_rmp_sync.elapsed() >= RMP_SYNC_INTERVAL;
-                    let should_sync_terms = last_term_sync.elapsed() >= TERM_SYNC_INTERVAL;
+                    let requests = &self.sync_requests;
+                    let should_scrape_ref = requests.take(SyncKind::Reference)
+                        || last_ref_scrape.elapsed() >= REFERENCE_DATA_INTERVAL;
+                    let should_sync_rmp = requests.take(SyncKind::Rmp)
+                        || last_rmp_sync.elapsed() >= RMP_SYNC_INTERVAL;
+                    let should_sync_terms = requests.take(SyncKind::Terms)
+                        || last_term_sync.elapsed() >= TERM_SYNC_INTERVAL;
+                    let bluebook_requested = requests.take(SyncKind::Bluebook);
                     let should_sync_bluebook = bluebook_notified
+                        || bluebook_requested
                         || last_bluebook_sync.elapsed() >= BLUEBOOK_SYNC_INTERVAL;
-                    let should_scrape_rmp_reviews =
-                        last_rmp_review_scrape.elapsed() >= RMP_REVIEW_SCRAPE_INTERVAL;
+                    let should_scrape_rmp_reviews = requests.take(SyncKind::RmpReviews)
+                        || last_rmp_review_scrape.elapsed() >= RMP_REVIEW_SCRAPE_INTERVAL;
+                    let should_compute_recommendations =
+                        last_recommendations.elapsed() >= RECOMMENDATIONS_INTERVAL;
                     bluebook_notified = false;
 
                     // Read and clear the force flag before spawning so the flag
                     // state at decision time is used by the spawned task.
+                    // A forced run from the syncs API re-checks every subject too.
                     let bluebook_force = self
                         .bluebook_force_flag
-                        .swap(false, Ordering::Relaxed);
+                        .swap(false, Ordering::Relaxed)
+                        || bluebook_requested;
 
                     // Spawn work in separate task to allow graceful cancellation during shutdown.
                     // Timestamps are persisted to DB on success so restarts don't redo recent work.
@@ -201,6 +256,8 @@ impl Scheduler {
                         let cancel_token = cancel_token.clone();
                         let reference_cache = self.reference_cache.clone();
                         let archived_eval_times = self.archived_eval_times.clone();
+                        let registration = self.registration.clone();
+                        let scoring_decay = self.scoring_decay;
 
                                 async move {
                                     tokio::select! {
@@ -209,7 +266,10 @@ impl Scheduler {
                                             // run them concurrently so they don't wait behind each other.
                                             let term_fut = async {
                                                 if should_sync_terms {
-                                                    match Self::sync_terms(db.pool(), &banner_api).await {
+                                                    let started_at = Utc::now();
+                                                    let result = Self::sync_terms(db.pool(), &banner_api).await;
+                                                    syncs::record_run(db.pool(), SyncKind::Terms, started_at, &result).await;
+                                                    match result {
                                                         Ok(()) => {
                                                             if let Err(e) = kv::set_timestamp(db.pool(), KV_TERM_SYNC, Utc::now()).await {
                                                                 warn!(error = ?e, "Failed to persist term sync timestamp");
@@ -222,7 +282,10 @@ impl Scheduler {
 
                                             let rmp_fut = async {
                                                 if should_sync_rmp {
-                                                    match Self::sync_rmp_data(db.pool()).await {
+                                                    let started_at = Utc::now();
+                                                    let result = Self::sync_rmp_data(&db).await;
+                                                    syncs::record_run(db.pool(), SyncKind::Rmp, started_at, &result).await;
+                                                    match result {
                                                         Ok(()) => {
                                                             if let Err(e) = kv::set_timestamp(db.pool(), KV_RMP_SYNC, Utc::now()).await {
                                                                 warn!(error = ?e, "Failed to persist RMP sync timestamp");
@@ -235,7 +298,10 @@ impl Scheduler {
 
                                             let ref_fut = async {
                                                 if should_scrape_ref {
-                                                    match Self::scrape_reference_data(db.pool(), &banner_api, &reference_cache).await {
+                                                    let started_at = Utc::now();
+                                                    let result = Self::scrape_reference_data(db.pool(), &banner_api, &reference_cache).await;
+                                                    syncs::record_run(db.pool(), SyncKind::Reference, started_at, &result).await;
+                                                    match result {
                                                         Ok(()) => {
                                                             if let Err(e) = kv::set_timestamp(db.pool(), KV_REF_SCRAPE, Utc::now()).await {
                                                                 warn!(error = ?e, "Failed to persist ref scrape timestamp");
@@ -248,7 +314,10 @@ impl Scheduler {
 
                                             let bb_fut = async {
                                                 if should_sync_bluebook {
-                                                    match Self::sync_bluebook(db.pool(), bluebook_force).await {
+                                                    let started_at = Utc::now();
+                                                    let result = Self::sync_bluebook(db.pool(), bluebook_force).await;
+                                                    syncs::record_run(db.pool(), SyncKind::Bluebook, started_at, &result).await;
+                                                    match result {
                                                         Ok(()) => {
                                                             if let Err(e) = kv::set_timestamp(db.pool(), KV_BLUEBOOK_SYNC, Utc::now()).await {
                                                                 warn!(error = ?e, "Failed to persist BlueBook sync timestamp");
@@ -261,7 +330,10 @@ impl Scheduler {
 
                                             let rmp_review_fut = async {
                                                 if should_scrape_rmp_reviews {
-                                                    match Self::sync_rmp_reviews(db.pool()).await {
+                                                    let started_at = Utc::now();
+                                                    let result = Self::sync_rmp_reviews(db.pool()).await;
+                                                    syncs::record_run(db.pool(), SyncKind::RmpReviews, started_at, &result).await;
+                                                    match result {
                                                         Ok(()) => {
                                                             if let Err(e) = kv::set_timestamp(db.pool(), KV_RMP_REVIEW_SCRAPE, Utc::now()).await {
                                                                 warn!(error = ?e, "Failed to persist RMP review scrape timestamp");
@@ -276,13 +348,29 @@ impl Scheduler {
 
                                             // Recompute instructor scores when rating data may have changed
                                             if should_sync_rmp || should_sync_bluebook || should_scrape_rmp_reviews {
-                                                match crate::data::scoring::recompute_all_scores(db.pool()).await {
-                                                    Ok(n) => info!(count = n, "Recomputed instructor scores after sync"),
+                                                match scoring::recompute_scores(db.pool(), &scoring_decay, RecomputeMode::Incremental).await {
+                                                    Ok(stats) => {
+                                                        info!(count = stats.recomputed, mode = ?stats.mode, "Recomputed instructor scores after sync");
+                                                        db.events().publish(DomainEvent::Instructor(InstructorEvent::ScoresRecomputed));
+                                                    }
                                                     Err(e) => error!(error = ?e, "Failed to recompute instructor scores after sync"),
                                                 }
                                             }
 
-                                            if let Err(e) = Self::schedule_jobs_impl(&db, &banner_api, &archived_eval_times).await {
+                                            // Runs after scores so rating similarity uses fresh ratings
+                                            if should_compute_recommendations {
+                                                match crate::data::recommendations::recompute_related(db.pool()).await {
+                                                    Ok(n) => {
+                                                        info!(pairs = n, "Recomputed related instructors");
+                                                        if let Err(e) = kv::set_timestamp(db.pool(), KV_RECOMMENDATIONS, Utc::now()).await {
+                                                            warn!(error = ?e, "Failed to persist recommendations timestamp");
+                                                        }
+                                                    }
+                                                    Err(e) => error!(error = ?e, "Failed to recompute related instructors"),
+                                                }
+                                            }
+
+                                            if let Err(e) = Self::schedule_jobs_impl(&db, &banner_api, &archived_eval_times, &registration).await {
                                                 error!(error = ?e, "Failed to schedule jobs");
                                             }
                                         } => {}
@@ -311,6 +399,9 @@ impl Scheduler {
                     if should_scrape_rmp_reviews {
                         last_rmp_review_scrape = Instant::now();
                     }
+                    if should_compute_recommendations {
+                        last_recommendations = Instant::now();
+                    }
 
                     current_work = Some((work_handle, cancel_token));
                     next_run = time::Instant::now() + work_interval;
@@ -340,13 +431,15 @@ impl Scheduler {
     ///
     /// Queries all enabled terms from the `terms` table and schedules jobs for each.
     /// Uses adaptive scheduling to determine per-subject scrape intervals based
-    /// on recent change rates, failure patterns, and time of day.
+    /// on recent change rates, failure patterns, time of day, and registration
+    /// windows.
     ///
     /// This is a static method (not &self) to allow it to be called from spawned tasks.
     async fn schedule_jobs_impl(
         db: &DbContext,
         banner_api: &BannerApi,
         archived_eval_times: &std::sync::Mutex<HashMap<String, Instant>>,
+        registration: &RegistrationCalendar,
     ) -> Result<()> {
         // Query enabled terms from database
         let start = Instant::now();
@@ -440,14 +533,35 @@ impl Scheduler {
             );
         }
 
+        let now = Utc::now();
         for (term, category) in active_terms {
-            if let Err(e) =
-                Self::schedule_term_jobs(db, banner_api, &term.code, category, &stats_map).await
+            let registration_open =
+                matches!(category, TermCategory::Current | TermCategory::Future)
+                    && registration.is_open(&term.code, now);
+            if registration_open {
+                debug!(term = %term.code, "Registration window open, boosting high-churn subjects");
+            }
+
+            if let Err(e) = Self::schedule_term_jobs(
+                db,
+                banner_api,
+                &term.code,
+                category,
+                registration_open,
+                &stats_map,
+            )
+            .await
             {
                 error!(term = %term.code, error = ?e, "Failed to schedule jobs for term");
                 continue;
             }
 
+            if matches!(category, TermCategory::Current | TermCategory::Future)
+                && let Err(e) = Self::schedule_detail_jobs(db, &term.code).await
+            {
+                error!(term = %term.code, error = ?e, "Failed to schedule detail jobs for term");
+            }
+
             // Record evaluation time for past/archived terms so we skip them next cycle.
             if category == TermCategory::Past || category == TermCategory::Archived {
                 archived_eval_times
@@ -471,9 +585,13 @@ impl Scheduler {
         banner_api: &BannerApi,
         term_code: &str,
         category: TermCategory,
+        registration_open: bool,
         stats_map: &HashMap<(String, String), SubjectStats>,
     ) -> Result<()> {
-        trace!(?category, "Enqueuing subject jobs for term");
+        trace!(
+            ?category,
+            registration_open, "Enqueuing subject jobs for term"
+        );
 
         let subjects = match category {
             TermCategory::Past | TermCategory::Archived => {
@@ -501,7 +619,7 @@ impl Scheduler {
 
         // Evaluate each subject using adaptive scheduling
         let now = Utc::now();
-        let mut eligible_subjects: Vec<String> = Vec::new();
+        let mut eligible_subjects: Vec<(String, ScrapePriority)> = Vec::new();
         let mut cooldown_count: usize = 0;
         let mut paused_count: usize = 0;
 
@@ -522,9 +640,10 @@ impl Scheduler {
                     last_completed: DateTime::<Utc>::MIN_UTC,
                 });
 
-            match evaluate_subject(&stats, now, category) {
+            match evaluate_subject_during(&stats, now, category, registration_open) {
                 SubjectSchedule::Eligible(_) => {
-                    eligible_subjects.push(subject.code.clone());
+                    let priority = subject_priority(&stats, registration_open);
+                    eligible_subjects.push((subject.code.clone(), priority));
                 }
                 SubjectSchedule::Cooldown(_) => cooldown_count += 1,
                 SubjectSchedule::Paused => paused_count += 1,
@@ -554,7 +673,7 @@ impl Scheduler {
         // Create payloads with term field for eligible subjects
         let subject_payloads: Vec<_> = eligible_subjects
             .iter()
-            .map(|code| json!({ "subject": code, "term": term_code }))
+            .map(|(code, _)| json!({ "subject": code, "term": term_code }))
             .collect();
 
         // Query existing jobs for eligible subjects only
@@ -575,7 +694,7 @@ impl Scheduler {
         let mut skipped_count = 0;
         let new_jobs: Vec<_> = eligible_subjects
             .into_iter()
-            .filter_map(|subject_code| {
+            .filter_map(|(subject_code, priority)| {
                 let job = SubjectJob::new(subject_code.clone(), term_code.to_string());
                 let payload = serde_json::to_value(&job).unwrap();
                 let payload_str = payload.to_string();
@@ -584,7 +703,7 @@ impl Scheduler {
                     skipped_count += 1;
                     None
                 } else {
-                    Some((payload, subject_code))
+                    Some((payload, subject_code, priority))
                 }
             })
             .collect();
@@ -595,13 +714,13 @@ impl Scheduler {
 
         // Insert all new jobs in a single batch (events emitted automatically)
         if !new_jobs.is_empty() {
-            for (_, subject_code) in &new_jobs {
-                debug!(subject = %subject_code, "New job enqueued for subject");
+            for (_, subject_code, priority) in &new_jobs {
+                debug!(subject = %subject_code, ?priority, "New job enqueued for subject");
             }
 
             let jobs: Vec<_> = new_jobs
                 .into_iter()
-                .map(|(payload, _)| (payload, TargetType::Subject, ScrapePriority::Low))
+                .map(|(payload, _, priority)| (payload, TargetType::Subject, priority))
                 .collect();
 
             let start = Instant::now();
@@ -619,6 +738,45 @@ impl Scheduler {
         Ok(())
     }
 
+    /// Enqueue detail enrichment jobs for sections with missing or stale details.
+    ///
+    /// Only current and future terms are enriched; restrictions and linked
+    /// sections for past terms are not worth the request volume.
+    #[tracing::instrument(skip_all, fields(term = %term_code))]
+    async fn schedule_detail_jobs(db: &DbContext, term_code: &str) -> Result<()> {
+        let crns = crate::data::course_details::find_stale_detail_crns(
+            db.pool(),
+            term_code,
+            DETAIL_REFRESH_HOURS,
+            (DETAIL_BATCH_SIZE * DETAIL_MAX_BATCHES_PER_CYCLE) as i64,
+        )
+        .await?;
+
+        if crns.is_empty() {
+            return Ok(());
+        }
+
+        let jobs: Vec<_> = crns
+            .chunks(DETAIL_BATCH_SIZE)
+            .map(|chunk| {
+                let job = DetailJob::new(term_code.to_string(), chunk.to_vec());
+                (
+                    serde_json::to_value(&job).unwrap(),
+                    TargetType::CrnList,
+                    ScrapePriority::Low,
+                )
+            })
+            .collect();
+
+        debug!(
+            crns = crns.len(),
+            jobs = jobs.len(),
+            "Enqueuing detail jobs"
+        );
+        db.scrape_jobs().batch_insert(&jobs).await?;
+        Ok(())
+    }
+
     /// Sync terms from Banner API to database (periodic background job).
     #[tracing::instrument(skip_all)]
     async fn sync_terms(db_pool: &PgPool, banner_api: &BannerApi) -> Result<()> {
@@ -646,10 +804,15 @@ impl Scheduler {
 
     /// Fetch all RMP professors, upsert to DB, and auto-match against Banner instructors.
     #[tracing::instrument(skip_all)]
-    async fn sync_rmp_data(db_pool: &PgPool) -> Result<()> {
+    async fn sync_rmp_data(db: &DbContext) -> Result<()> {
+        Self::sync_rmp_data_with(db, &RmpClient::new()).await
+    }
+
+    /// [`Self::sync_rmp_data`] against a specific client.
+    pub(crate) async fn sync_rmp_data_with(db: &DbContext, client: &RmpClient) -> Result<()> {
         info!("Starting RMP data sync");
+        let db_pool = db.pool();
 
-        let client = RmpClient::new();
         let professors = client.fetch_all_professors().await?;
         let total = professors.len();
 
@@ -667,7 +830,7 @@ impl Scheduler {
         crate::data::rmp::refresh_rmp_summary(db_pool).await?;
 
         let start = Instant::now();
-        let stats = crate::data::rmp_matching::generate_candidates(db_pool).await?;
+        let stats = crate::data::rmp_matching::generate_candidates(db_pool, db.events()).await?;
         let elapsed = start.elapsed();
         if elapsed > SLOW_QUERY_THRESHOLD {
             warn!(
@@ -877,6 +1040,16 @@ impl Scheduler {
             Err(e) => warn!(error = ?e, "Failed to fetch attributes"),
         }
 
+        // Buildings and rooms -- Banner has no listing, so collect them from
+        // where this term's sections meet
+        match crate::data::rooms::reference_entries(db_pool, &term).await {
+            Ok(entries) => {
+                debug!(count = entries.len(), "Collected buildings and rooms");
+                all_entries.extend(entries);
+            }
+            Err(e) => warn!(error = ?e, "Failed to collect buildings and rooms"),
+        }
+
         // Batch upsert all entries
         let total = all_entries.len();
         let start = Instant::now();
@@ -0,0 +1,108 @@
+//! Coalescing coordinator for term-scoped search index refreshes.
+//!
+//! Workers call [`SearchIndexRefresher::request`] whenever a subject job for a
+//! term changes data. Requests are collected into a pending set and drained
+//! after a short debounce window, so a burst of fifty subject completions
+//! for the same term results in a single refresh of that term only.
+
+use crate::data::search_index;
+use crate::utils::fmt_duration;
+use sqlx::PgPool;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, broadcast};
+use tracing::{debug, error, info};
+
+/// How long to wait after the first request before draining the pending set.
+const DEBOUNCE: Duration = Duration::from_secs(30);
+
+/// Shared handle for queuing term refreshes. Clone-cheap.
+#[derive(Clone, Default)]
+pub struct SearchIndexRefresher {
+    pending: Arc<Mutex<BTreeSet<String>>>,
+    notify: Arc<Notify>,
+}
+
+impl SearchIndexRefresher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a refresh for a term. Duplicate requests are coalesced.
+    pub fn request(&self, term_code: &str) {
+        let inserted = self.pending.lock().unwrap().insert(term_code.to_owned());
+        if inserted {
+            debug!(term = term_code, "Search index refresh queued");
+        }
+        self.notify.notify_one();
+    }
+
+    /// Take every pending term, leaving the set empty.
+    fn take_pending(&self) -> BTreeSet<String> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+
+    /// Runs the refresh loop until shutdown.
+    ///
+    /// Each cycle waits for a request, sleeps through the debounce window to
+    /// let the burst settle, then refreshes each distinct term once. Requests
+    /// arriving mid-refresh leave a stored permit on the `Notify`, so they
+    /// are picked up by the next cycle.
+    pub async fn run(&self, pool: PgPool, mut shutdown_rx: broadcast::Receiver<()>) {
+        loop {
+            tokio::select! {
+                _ = self.notify.notified() => {}
+                _ = shutdown_rx.recv() => break,
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(DEBOUNCE) => {}
+                _ = shutdown_rx.recv() => break,
+            }
+
+            for term_code in self.take_pending() {
+                let start = Instant::now();
+                match search_index::refresh_term(&pool, &term_code).await {
+                    Ok(count) => info!(
+                        term = %term_code,
+                        courses = count,
+                        duration = fmt_duration(start.elapsed()),
+                        "Search index refreshed"
+                    ),
+                    Err(e) => {
+                        error!(term = %term_code, error = ?e, "Failed to refresh search index")
+                    }
+                }
+            }
+        }
+
+        debug!("Search index refresher exiting");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_for_same_term_coalesce() {
+        let refresher = SearchIndexRefresher::new();
+        refresher.request("202510");
+        refresher.request("202510");
+        refresher.request("202520");
+
+        let pending = refresher.take_pending();
+        assert_eq!(pending.len(), 2);
+        assert!(pending.contains("202510"));
+        assert!(pending.contains("202520"));
+    }
+
+    #[test]
+    fn take_pending_drains_the_set() {
+        let refresher = SearchIndexRefresher::new();
+        refresher.request("202510");
+        assert_eq!(refresher.take_pending().len(), 1);
+        assert!(refresher.take_pending().is_empty());
+    }
+}
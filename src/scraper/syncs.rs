@@ -0,0 +1,209 @@
+//! Bookkeeping for the scheduler's periodic external-source syncs.
+//!
+//! The scheduler records every run of a sync (start, finish, outcome) under
+//! one `app_kv` key per sync so the admin API can show them side by side, and
+//! reads [`SyncRequests`] each cycle so admins can force a sync to run early.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tokio::sync::Notify;
+use tracing::warn;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use super::scheduler;
+use crate::data::kv;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export)]
+pub enum SyncKind {
+    Reference,
+    Terms,
+    Rmp,
+    RmpReviews,
+    Bluebook,
+}
+
+impl SyncKind {
+    pub const ALL: [SyncKind; 5] = [
+        Self::Reference,
+        Self::Terms,
+        Self::Rmp,
+        Self::RmpReviews,
+        Self::Bluebook,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reference => "reference",
+            Self::Terms => "terms",
+            Self::Rmp => "rmp",
+            Self::RmpReviews => "rmp-reviews",
+            Self::Bluebook => "bluebook",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.as_str() == s)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Reference => "Reference data",
+            Self::Terms => "Terms",
+            Self::Rmp => "RateMyProfessors",
+            Self::RmpReviews => "RateMyProfessors reviews",
+            Self::Bluebook => "BlueBook",
+        }
+    }
+
+    /// How often the scheduler runs this sync when nothing forces it.
+    pub fn interval(self) -> Duration {
+        match self {
+            Self::Reference => scheduler::REFERENCE_DATA_INTERVAL,
+            Self::Terms => scheduler::TERM_SYNC_INTERVAL,
+            Self::Rmp => scheduler::RMP_SYNC_INTERVAL,
+            Self::RmpReviews => scheduler::RMP_REVIEW_SCRAPE_INTERVAL,
+            Self::Bluebook => scheduler::BLUEBOOK_SYNC_INTERVAL,
+        }
+    }
+
+    /// KV key the scheduler persists the last successful run under.
+    pub fn success_key(self) -> &'static str {
+        match self {
+            Self::Reference => scheduler::KV_REF_SCRAPE,
+            Self::Terms => scheduler::KV_TERM_SYNC,
+            Self::Rmp => scheduler::KV_RMP_SYNC,
+            Self::RmpReviews => scheduler::KV_RMP_REVIEW_SCRAPE,
+            Self::Bluebook => scheduler::KV_BLUEBOOK_SYNC,
+        }
+    }
+
+    fn run_key(self) -> String {
+        format!("sync.{}.last_run", self.as_str())
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|k| *k == self)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum SyncOutcome {
+    Success,
+    Failed,
+}
+
+/// The most recent run of a sync, successful or not.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRun {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub outcome: SyncOutcome,
+    pub error: Option<String>,
+}
+
+/// Record a finished run. Failures to persist are logged, not returned, so
+/// bookkeeping never masks the sync's own result.
+pub async fn record_run(
+    pool: &PgPool,
+    kind: SyncKind,
+    started_at: DateTime<Utc>,
+    result: &Result<()>,
+) {
+    let run = SyncRun {
+        started_at,
+        finished_at: Utc::now(),
+        outcome: if result.is_ok() {
+            SyncOutcome::Success
+        } else {
+            SyncOutcome::Failed
+        },
+        error: result.as_ref().err().map(|e| format!("{e:#}")),
+    };
+    let value = match serde_json::to_string(&run) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!(sync = kind.as_str(), error = ?e, "Failed to serialize sync run");
+            return;
+        }
+    };
+    if let Err(e) = kv::set(pool, &kind.run_key(), &value).await {
+        warn!(sync = kind.as_str(), error = ?e, "Failed to persist sync run");
+    }
+}
+
+/// The last recorded run of a sync, if any.
+pub async fn last_run(pool: &PgPool, kind: SyncKind) -> Result<Option<SyncRun>> {
+    let value = kv::get(pool, &kind.run_key()).await?;
+    Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+}
+
+/// Admin requests to run a sync on the next scheduler cycle, regardless of
+/// its interval.
+#[derive(Debug, Default)]
+pub struct SyncRequests {
+    pending: [AtomicBool; SyncKind::ALL.len()],
+    notify: Notify,
+}
+
+impl SyncRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue `kind` and wake the scheduler.
+    pub fn request(&self, kind: SyncKind) {
+        self.pending[kind.index()].store(true, Ordering::Relaxed);
+        self.notify.notify_one();
+    }
+
+    /// Whether `kind` is queued but not yet picked up.
+    pub fn is_pending(&self, kind: SyncKind) -> bool {
+        self.pending[kind.index()].load(Ordering::Relaxed)
+    }
+
+    /// Clear and return the request for `kind`.
+    pub fn take(&self, kind: SyncKind) -> bool {
+        self.pending[kind.index()].swap(false, Ordering::Relaxed)
+    }
+
+    /// Resolves when a sync is requested.
+    pub async fn notified(&self) {
+        self.notify.notified().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds_round_trip_through_their_names() {
+        for kind in SyncKind::ALL {
+            assert_eq!(SyncKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(SyncKind::parse("rmp_reviews"), None);
+    }
+
+    #[test]
+    fn requests_are_taken_once() {
+        let requests = SyncRequests::new();
+        requests.request(SyncKind::Terms);
+        assert!(requests.is_pending(SyncKind::Terms));
+        assert!(!requests.is_pending(SyncKind::Rmp));
+        assert!(requests.take(SyncKind::Terms));
+        assert!(!requests.take(SyncKind::Terms));
+    }
+}
@@ -1,9 +1,12 @@
-use crate::banner::{BannerApi, BannerApiError};
+use crate::banner::{BannerApi, BannerApiError, BannerThrottled};
 use crate::data::DbContext;
-use crate::data::models::{ScrapeJob, UpsertCounts};
+use crate::data::models::{ScrapeJob, TargetType, UpsertCounts};
 use crate::data::terms;
 use crate::data::unsigned::{Count, DurationMs};
-use crate::scraper::jobs::{JobError, JobType};
+use crate::metrics::{JobOutcome, Metrics};
+use crate::scraper::jobs::{Job, JobError, JobType};
+use crate::scraper::pool::WorkerPool;
+use crate::scraper::search_index::SearchIndexRefresher;
 use crate::utils::fmt_duration;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -16,19 +19,82 @@ use tracing::{Instrument, debug, error, info, trace, warn};
 /// Maximum time a single job is allowed to run before being considered stuck.
 const JOB_TIMEOUT: Duration = Duration::from_secs(5 * 60);
 
+/// Delay for a throttled job when Banner didn't say how long to wait.
+const DEFAULT_THROTTLE_DELAY: Duration = Duration::from_secs(60);
+
+/// Longest we'll honor a `Retry-After`, so a bogus header can't park a job for days.
+const MAX_THROTTLE_DELAY: Duration = Duration::from_secs(30 * 60);
+
+/// Exponential backoff between attempts of a failed job.
+///
+/// How many attempts a job gets is per job (`scrape_jobs.max_retries`); after
+/// the last one it moves to the dead-letter queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Delay before the first retry; doubles with each further attempt.
+    pub base_delay: Duration,
+    /// Ceiling on the delay, before jitter.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(10),
+            max_delay: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the next retry: exponential by attempt, capped, with equal
+    /// jitter. Equal jitter keeps a guaranteed minimum spacing while spreading
+    /// simultaneous retries across a window (avoids a thundering herd).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let shift = attempt.saturating_sub(1).min(20);
+        let capped = self
+            .base_delay
+            .saturating_mul(1u32 << shift)
+            .min(self.max_delay);
+        let half = capped / 2;
+        half + half.mul_f64(rand::random_range(0.0..=1.0))
+    }
+}
+
 /// A single worker instance.
 ///
 /// Each worker runs in its own asynchronous task and continuously polls the
-/// database for scrape jobs to execute.
+/// database for scrape jobs to execute, within the per-type limits of its
+/// [`WorkerPool`].
 pub struct Worker {
     id: usize,
     db: DbContext,
+    pool: WorkerPool,
     banner_api: Arc<BannerApi>,
+    search_index: SearchIndexRefresher,
+    metrics: Arc<Metrics>,
+    retry_policy: RetryPolicy,
 }
 
 impl Worker {
-    pub fn new(id: usize, db: DbContext, banner_api: Arc<BannerApi>) -> Self {
-        Self { id, db, banner_api }
+    pub fn new(
+        id: usize,
+        db: DbContext,
+        pool: WorkerPool,
+        banner_api: Arc<BannerApi>,
+        search_index: SearchIndexRefresher,
+        metrics: Arc<Metrics>,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        Self {
+            id,
+            db,
+            pool,
+            banner_api,
+            search_index,
+            metrics,
+            retry_policy,
+        }
     }
 
     /// Runs the worker's main loop.
@@ -36,21 +102,38 @@ impl Worker {
         info!(worker_id = self.id, "Worker started");
 
         loop {
+            if !self.banner_api.circuit().accepting_requests() {
+                trace!(worker_id = self.id, "Banner circuit open, waiting");
+                time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+
+            let reservation = self.pool.reserve();
+            let blocked = reservation.blocked().to_vec();
+            if reservation.all_blocked() {
+                trace!(worker_id = self.id, "Every job type at its limit, waiting");
+                time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+
             // Fetch and lock a job, racing against shutdown signal
             let job = tokio::select! {
                 _ = shutdown_rx.recv() => {
                     info!(worker_id = self.id, "Worker received shutdown signal, exiting gracefully");
                     break;
                 }
-                result = self.fetch_and_lock_job() => {
+                result = self.fetch_and_lock_job(&blocked) => {
                     match result {
                         Ok(Some(job)) => job,
                         Ok(None) => {
+                            // Free the reserved slots for other workers while idle
+                            drop(reservation);
                             trace!(worker_id = self.id, "No jobs available, waiting");
                             time::sleep(Duration::from_secs(5)).await;
                             continue;
                         }
                         Err(e) => {
+                            drop(reservation);
                             warn!(worker_id = self.id, error = ?e, "Failed to fetch job, waiting");
                             time::sleep(Duration::from_secs(10)).await;
                             continue;
@@ -59,6 +142,9 @@ impl Worker {
                 }
             };
 
+            // Holds this job's type slot until the job is handled
+            let _slot = reservation.keep(job.target_type);
+
             let job_id = job.id;
             let retry_count = job.retry_count;
             let max_retries = job.max_retries;
@@ -112,10 +198,11 @@ impl Worker {
     /// Atomically fetches a job from the queue, locking it for processing.
     ///
     /// This uses a `FOR UPDATE SKIP LOCKED` query to ensure that multiple
-    /// workers can poll the queue concurrently without conflicts.
+    /// workers can poll the queue concurrently without conflicts. Jobs of
+    /// `blocked` types are skipped.
     /// Emits a `ScrapeJobEvent::Locked` event automatically via DbContext.
-    async fn fetch_and_lock_job(&self) -> Result<Option<ScrapeJob>> {
-        self.db.scrape_jobs().lock_next().await
+    async fn fetch_and_lock_job(&self, blocked: &[TargetType]) -> Result<Option<ScrapeJob>> {
+        self.db.scrape_jobs().lock_next_excluding(blocked).await
     }
 
     async fn process_job(&self, job: ScrapeJob) -> Result<UpsertCounts, JobError> {
@@ -132,11 +219,12 @@ impl Worker {
         async move {
             debug!(worker_id = self.id, "Processing job");
 
-            // Process the job - API errors are recoverable
+            // Process the job - API errors are recoverable, throttling and an
+            // open circuit are rescheduled rather than counted as an attempt
             job_impl
                 .process(&self.banner_api, &self.db)
                 .await
-                .map_err(JobError::Recoverable)
+                .map_err(JobError::classify)
         }
         .instrument(span)
         .await
@@ -184,6 +272,15 @@ impl Worker {
     ) {
         let duration_ms = DurationMs::new(u32::try_from(duration.as_millis()).unwrap_or(u32::MAX));
 
+        let outcome = match &result {
+            Ok(_) => JobOutcome::Success,
+            Err(JobError::Recoverable(_)) => JobOutcome::Retry,
+            Err(JobError::Unrecoverable(_)) => JobOutcome::Failed,
+            Err(JobError::Throttled(_)) => JobOutcome::Throttled,
+        };
+        self.metrics
+            .record_scrape_job(target_type, outcome, duration);
+
         const SLOW_THRESHOLD: Duration = Duration::from_secs(30);
         if duration > SLOW_THRESHOLD {
             warn!(
@@ -248,12 +345,21 @@ impl Worker {
                 }
 
                 // Mark job as completed (deletes it and emits Completed event)
-                if let Err(e) = self.db.scrape_jobs().complete(job_id).await {
+                if let Err(e) = self
+                    .db
+                    .scrape_jobs()
+                    .complete(job_id, counts.diff.clone())
+                    .await
+                {
                     error!(worker_id = self.id, job_id, error = ?e, "Failed to complete job");
                 }
 
-                // Update last_scraped_at for the term if this job has a term code
+                // Update last_scraped_at for the term if this job has a term code,
+                // and queue a search index refresh for it when data changed
                 if let Some(code) = term_code {
+                    if has_changes {
+                        self.search_index.request(&code);
+                    }
                     let _ = terms::update_last_scraped_at(self.db.pool(), &code).await
                         .map_err(|e| warn!(worker_id = self.id, job_id, term_code = code.as_str(), error = ?e, "Failed to update last_scraped_at"));
                 }
@@ -273,6 +379,10 @@ impl Worker {
                 )
                 .await;
             }
+            Err(JobError::Throttled(throttled)) => {
+                self.handle_throttled(job_id, retry_count, throttled, duration)
+                    .await;
+            }
             Err(JobError::Unrecoverable(e)) => {
                 // Log the failed result
                 let err_msg = format!("{e:#}");
@@ -301,16 +411,53 @@ impl Worker {
                     job_id,
                     duration = fmt_duration(duration),
                     error = ?e,
-                    "Job corrupted, deleting"
+                    "Job failed unrecoverably, moving to dead-letter queue"
                 );
-                // Delete job (emits Deleted event automatically)
-                if let Err(e) = self.db.scrape_jobs().delete(job_id).await {
-                    error!(worker_id = self.id, job_id, error = ?e, "Failed to delete corrupted job");
+                if let Err(e) = self
+                    .db
+                    .scrape_jobs()
+                    .dead_letter(job_id, retry_count, &err_msg)
+                    .await
+                {
+                    error!(worker_id = self.id, job_id, error = ?e, "Failed to dead-letter job");
                 }
             }
         }
     }
 
+    /// Reschedule a job Banner throttled for when Banner said to come back,
+    /// keeping its retry count unchanged.
+    async fn handle_throttled(
+        &self,
+        job_id: i32,
+        retry_count: Count,
+        throttled: BannerThrottled,
+        duration: std::time::Duration,
+    ) {
+        let delay = throttle_delay(&throttled);
+        let execute_at =
+            Utc::now() + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::seconds(60));
+
+        warn!(
+            worker_id = self.id,
+            job_id,
+            status = throttled.status,
+            retry_after = ?throttled.retry_after,
+            delay = fmt_duration(delay),
+            duration = fmt_duration(duration),
+            "Banner throttled job, rescheduling"
+        );
+
+        if let Err(e) = self
+            .db
+            .scrape_jobs()
+            .retry(job_id, retry_count, execute_at)
+            .await
+        {
+            error!(worker_id = self.id, job_id, error = ?e, "Failed to reschedule throttled job");
+        }
+    }
+
     /// Handle recoverable errors by logging appropriately and unlocking the job
     #[allow(clippy::too_many_arguments)]
     async fn handle_recoverable_error(
@@ -380,7 +527,9 @@ impl Worker {
         if next_attempt < max_retries {
             // Jittered exponential backoff so a mass failure (e.g. a whole term
             // timing out at once) doesn't re-hammer the upstream in lockstep.
-            let execute_at = Utc::now() + retry_backoff(next_attempt.get());
+            let delay = self.retry_policy.backoff(next_attempt.get());
+            let execute_at = Utc::now()
+                + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::seconds(60));
             match self
                 .db
                 .scrape_jobs()
@@ -433,36 +582,92 @@ impl Worker {
                 retry_count = %next_attempt,
                 max_retries = %max_retries,
                 error = ?e,
-                "Job failed permanently (max retries exceeded), deleting"
+                "Job failed permanently (max retries exceeded), moving to dead-letter queue"
             );
-            // Mark as exhausted (emits Exhausted + Deleted events automatically)
-            if let Err(e) = self.db.scrape_jobs().exhaust(job_id).await {
-                error!(worker_id = self.id, job_id, error = ?e, "Failed to exhaust job");
+            // Emits Exhausted + Deleted events automatically
+            if let Err(e) = self
+                .db
+                .scrape_jobs()
+                .dead_letter(job_id, next_attempt, &err_msg)
+                .await
+            {
+                error!(worker_id = self.id, job_id, error = ?e, "Failed to dead-letter job");
             }
         }
     }
 }
 
-/// Delay before the next retry: exponential by attempt, capped, with equal
-/// jitter. Equal jitter keeps a guaranteed minimum spacing while spreading
-/// simultaneous retries across a window (avoids a thundering herd).
-fn retry_backoff(attempt: u32) -> chrono::Duration {
-    const BASE_SECS: u64 = 10;
-    const MAX_SECS: u64 = 300;
-
-    let shift = attempt.saturating_sub(1).min(20);
-    let capped = BASE_SECS.saturating_mul(1u64 << shift).min(MAX_SECS);
-    let half = capped / 2;
-    let delay = half + rand::random_range(0..=half);
-    chrono::Duration::seconds(delay as i64)
+/// How long to wait before re-running a throttled job.
+fn throttle_delay(throttled: &BannerThrottled) -> Duration {
+    throttled
+        .retry_after
+        .unwrap_or(DEFAULT_THROTTLE_DELAY)
+        .min(MAX_THROTTLE_DELAY)
+}
+
+/// Run a job in the foreground, outside the queue, backing off between failed
+/// attempts the way a worker would. Throttling counts as an attempt here so
+/// the caller always gets an answer.
+pub async fn run_in_foreground(
+    job: &dyn Job,
+    banner_api: &BannerApi,
+    db: &DbContext,
+    max_attempts: u32,
+    retry_policy: RetryPolicy,
+) -> Result<UpsertCounts> {
+    let mut attempt = 1;
+    loop {
+        let e = match time::timeout(JOB_TIMEOUT, job.process(banner_api, db)).await {
+            Ok(Ok(counts)) => return Ok(counts),
+            Ok(Err(e)) => e,
+            Err(_) => anyhow::anyhow!("job timed out after {}", fmt_duration(JOB_TIMEOUT)),
+        };
+        if attempt >= max_attempts {
+            return Err(e);
+        }
+
+        let (delay, error): (Duration, anyhow::Error) = match JobError::classify(e) {
+            JobError::Throttled(throttled) => (throttle_delay(&throttled), throttled.into()),
+            JobError::Recoverable(e) | JobError::Unrecoverable(e) => {
+                (retry_policy.backoff(attempt), e)
+            }
+        };
+        warn!(
+            attempt,
+            max_attempts,
+            delay = fmt_duration(delay),
+            error = ?error,
+            "Job failed, retrying"
+        );
+        time::sleep(delay).await;
+        attempt += 1;
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::retry_backoff;
+    use super::*;
+
+    #[test]
+    fn test_throttle_delay_defaults_and_caps() {
+        let throttled = |retry_after| BannerThrottled {
+            status: 429,
+            retry_after,
+        };
+        assert_eq!(throttle_delay(&throttled(None)), DEFAULT_THROTTLE_DELAY);
+        assert_eq!(
+            throttle_delay(&throttled(Some(Duration::from_secs(5)))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            throttle_delay(&throttled(Some(Duration::from_secs(86_400)))),
+            MAX_THROTTLE_DELAY
+        );
+    }
 
     #[test]
     fn test_retry_backoff_grows_then_caps_within_jitter_bounds() {
+        let policy = RetryPolicy::default();
         // (attempt, expected capped ceiling in seconds)
         let cases = [
             (1, 10),
@@ -476,12 +681,26 @@ mod tests {
         for (attempt, ceiling) in cases {
             let floor = ceiling / 2;
             for _ in 0..1000 {
-                let secs = retry_backoff(attempt).num_seconds();
+                let secs = policy.backoff(attempt).as_secs_f64();
                 assert!(
-                    secs >= floor && secs <= ceiling,
+                    secs >= floor as f64 && secs <= ceiling as f64,
                     "attempt {attempt}: {secs}s outside [{floor}, {ceiling}]"
                 );
             }
         }
     }
+
+    #[test]
+    fn test_retry_backoff_follows_configured_delays() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(6),
+        };
+        for _ in 0..100 {
+            let first = policy.backoff(1);
+            assert!(first >= Duration::from_secs(1) && first <= Duration::from_secs(2));
+            let capped = policy.backoff(10);
+            assert!(capped >= Duration::from_secs(3) && capped <= Duration::from_secs(6));
+        }
+    }
 }
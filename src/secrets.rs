@@ -0,0 +1,239 @@
+//! Loading and holding sensitive credentials.
+//!
+//! A secret is configured under a name such as `DISCORD_CLIENT_SECRET`. The
+//! value can be given three ways:
+//!
+//! - literally, in the variable itself
+//! - as a reference in the variable: `file:/run/secrets/smtp`, `env:OTHER_VAR`,
+//!   or `cmd:<command>`, whose stdout is taken as the secret -- this is how KMS
+//!   and secret-manager CLIs plug in (e.g.
+//!   `cmd:gcloud secrets versions access latest --secret=smtp-password`).
+//!   The command is split into arguments with shell quoting rules, but is
+//!   not run through a shell: no pipes, globs, or variable expansion.
+//! - as a path in `<NAME>_FILE`, the Docker/Kubernetes secret-mount convention,
+//!   for the variables in [`crate::config::SECRET_VARS`]
+//!
+//! Loaded values are held as [`SecretString`], which redacts itself from
+//! `Debug` output and zeroes its buffer on drop. A literal value set in the
+//! environment is also copied through figment's own buffers while `Config`
+//! loads, and those are freed without zeroing; prefer a `file:`/`cmd:`
+//! reference or `<NAME>_FILE` where that matters.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+use secrecy::SecretString;
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Resolve a configured value, following a `file:`, `env:` or `cmd:`
+/// reference if it has one.
+pub fn resolve(value: &str) -> Result<SecretString, SecretError> {
+    match SecretSource::parse(value) {
+        SecretSource::Literal => Ok(SecretString::from(value)),
+        source => source.load(),
+    }
+}
+
+/// `deserialize_with` for a required secret field.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SecretString, D::Error> {
+    let value = Zeroizing::new(String::deserialize(deserializer)?);
+    resolve(&value).map_err(serde::de::Error::custom)
+}
+
+/// `deserialize_with` for an optional secret field.
+pub fn deserialize_opt<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<SecretString>, D::Error> {
+    let Some(value) = Option::<String>::deserialize(deserializer)?.map(Zeroizing::new) else {
+        return Ok(None);
+    };
+    resolve(&value).map(Some).map_err(serde::de::Error::custom)
+}
+
+/// `file:` references for secrets configured only through `<NAME>_FILE`,
+/// keyed by lowercase name to merge over the environment when loading config.
+pub fn file_references(names: &[&str]) -> BTreeMap<String, String> {
+    file_references_from(names, |var| std::env::var(var).ok())
+}
+
+fn file_references_from(
+    names: &[&str],
+    var: impl Fn(&str) -> Option<String>,
+) -> BTreeMap<String, String> {
+    names
+        .iter()
+        .filter(|name| var(name).is_none())
+        .filter_map(|name| {
+            let path = var(&format!("{name}_FILE"))?;
+            Some((name.to_ascii_lowercase(), format!("file:{path}")))
+        })
+        .collect()
+}
+
+#[derive(Debug, Error)]
+pub enum SecretError {
+    #[error("failed to read secret file {path}: {source}")]
+    File {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("secret variable {0} is not set")]
+    MissingEnv(String),
+    #[error("secret command `{command}` failed: {reason}")]
+    Command { command: String, reason: String },
+    #[error("secret from {0} is empty")]
+    Empty(String),
+}
+
+/// Where a configured secret value points.
+#[derive(Debug, PartialEq, Eq)]
+enum SecretSource<'a> {
+    Literal,
+    File(&'a str),
+    Env(&'a str),
+    Command(&'a str),
+}
+
+impl<'a> SecretSource<'a> {
+    fn parse(value: &'a str) -> Self {
+        if let Some(path) = value.strip_prefix("file:") {
+            SecretSource::File(path.trim())
+        } else if let Some(name) = value.strip_prefix("env:") {
+            SecretSource::Env(name.trim())
+        } else if let Some(command) = value.strip_prefix("cmd:") {
+            SecretSource::Command(command.trim())
+        } else {
+            SecretSource::Literal
+        }
+    }
+
+    fn load(&self) -> Result<SecretString, SecretError> {
+        let (mut raw, origin) = match *self {
+            SecretSource::Literal => unreachable!("literals are not loaded"),
+            SecretSource::File(path) => {
+                let raw = std::fs::read_to_string(path).map_err(|source| SecretError::File {
+                    path: PathBuf::from(path),
+                    source,
+                })?;
+                (Zeroizing::new(raw), format!("file {path}"))
+            }
+            SecretSource::Env(name) => {
+                let raw =
+                    std::env::var(name).map_err(|_| SecretError::MissingEnv(name.to_owned()))?;
+                (Zeroizing::new(raw), format!("variable {name}"))
+            }
+            SecretSource::Command(command) => (run_command(command)?, format!("`{command}`")),
+        };
+        // Files and command output usually end with a newline.
+        let trimmed_len = raw.trim_end_matches(['\n', '\r']).len();
+        raw.truncate(trimmed_len);
+        if raw.is_empty() {
+            return Err(SecretError::Empty(origin));
+        }
+        Ok(SecretString::from(raw.as_str()))
+    }
+}
+
+/// Run a secret-manager command and capture its stdout.
+fn run_command(command: &str) -> Result<Zeroizing<String>, SecretError> {
+    let fail = |reason: String| SecretError::Command {
+        command: command.to_owned(),
+        reason,
+    };
+    let args = shlex::split(command).ok_or_else(|| fail("unbalanced quotes".to_owned()))?;
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| fail("empty command".to_owned()))?;
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| fail(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(fail(format!("{}: {}", output.status, stderr.trim())));
+    }
+    String::from_utf8(output.stdout)
+        .map(Zeroizing::new)
+        .map_err(|e| {
+            drop(Zeroizing::new(e.into_bytes()));
+            fail("output is not UTF-8".to_owned())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::ExposeSecret;
+
+    #[test]
+    fn parses_references() {
+        assert_eq!(SecretSource::parse("hunter2"), SecretSource::Literal);
+        assert_eq!(
+            SecretSource::parse("file:/run/secrets/smtp"),
+            SecretSource::File("/run/secrets/smtp")
+        );
+        assert_eq!(
+            SecretSource::parse("env:SMTP_PW"),
+            SecretSource::Env("SMTP_PW")
+        );
+        assert_eq!(
+            SecretSource::parse("cmd:vault kv get -field=pw smtp"),
+            SecretSource::Command("vault kv get -field=pw smtp")
+        );
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let secret = resolve("hunter2").unwrap();
+        assert!(!format!("{secret:?}").contains("hunter2"));
+        assert_eq!(secret.expose_secret(), "hunter2");
+    }
+
+    #[test]
+    fn file_variables_become_file_references() {
+        let env = |var: &str| match var {
+            "BOT_TOKEN_FILE" => Some("/run/secrets/bot".to_owned()),
+            "METRICS_TOKEN" => Some("literal".to_owned()),
+            "METRICS_TOKEN_FILE" => Some("/run/secrets/metrics".to_owned()),
+            _ => None,
+        };
+        let refs =
+            file_references_from(&["BOT_TOKEN", "METRICS_TOKEN", "GOOGLE_CLIENT_SECRET"], env);
+        assert_eq!(
+            refs.into_iter().collect::<Vec<_>>(),
+            vec![("bot_token".to_owned(), "file:/run/secrets/bot".to_owned())],
+            "a set variable wins over its _FILE"
+        );
+    }
+
+    #[test]
+    fn file_secrets_drop_trailing_newline() {
+        let path = std::env::temp_dir().join(format!("banner-secret-{}", ulid::Ulid::new()));
+        std::fs::write(&path, "s3cret\n").unwrap();
+        let secret = resolve(&format!("file:{}", path.display())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(secret.expose_secret(), "s3cret");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn command_arguments_keep_their_quotes() {
+        let secret = resolve("cmd:printf '%s|%s' \"two words\" 'and more'").unwrap();
+        assert_eq!(secret.expose_secret(), "two words|and more");
+        assert!(matches!(
+            resolve("cmd:echo 'unterminated"),
+            Err(SecretError::Command { .. })
+        ));
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        assert!(matches!(
+            resolve("file:/nonexistent/banner-secret"),
+            Err(SecretError::File { .. })
+        ));
+    }
+}
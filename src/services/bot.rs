@@ -1,8 +1,11 @@
 use super::Service;
-use crate::bot::{Data, get_commands};
+use crate::bot::autocomplete::AutocompleteCache;
+use crate::bot::registration::RegistrationPlan;
+use crate::bot::{AdminAccess, Data, get_commands};
 use crate::state::AppState;
 use crate::state::{ServiceStatus, ServiceStatusRegistry};
 use num_format::{Locale, ToFormattedString};
+use secrecy::{ExposeSecret, SecretString};
 use serenity::Client;
 use serenity::all::{ActivityData, ClientBuilder, GatewayIntents};
 use std::sync::Arc;
@@ -13,8 +16,9 @@ use tracing::{debug, error, info, trace, warn};
 
 /// Discord bot service implementation
 pub struct BotService {
-    bot_token: String,
-    bot_target_guild: u64,
+    bot_token: SecretString,
+    registration: RegistrationPlan,
+    admin_access: AdminAccess,
     app_state: AppState,
     status_task_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
     /// Consumed once in `run()` and passed into `create_client`.
@@ -26,9 +30,11 @@ pub struct BotService {
 }
 
 impl BotService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        bot_token: String,
-        bot_target_guild: u64,
+        bot_token: SecretString,
+        registration: RegistrationPlan,
+        admin_access: AdminAccess,
         app_state: AppState,
         status_task_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
         status_shutdown_tx: broadcast::Sender<()>,
@@ -37,7 +43,8 @@ impl BotService {
     ) -> Self {
         Self {
             bot_token,
-            bot_target_guild,
+            registration,
+            admin_access,
             app_state,
             status_task_handle,
             status_shutdown_rx: Some(status_shutdown_rx),
@@ -50,7 +57,8 @@ impl BotService {
     /// Create a new Discord bot client with full configuration
     async fn create_client(
         bot_token: &str,
-        bot_target_guild: u64,
+        registration: RegistrationPlan,
+        admin_access: AdminAccess,
         app_state: AppState,
         status_task_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
         status_shutdown_rx: broadcast::Receiver<()>,
@@ -107,6 +115,8 @@ impl BotService {
             })
             .setup(move |ctx, ready, framework| {
                 let app_state = app_state.clone();
+                let admin_access = admin_access.clone();
+                let registration = registration.clone();
                 let status_task_handle = status_task_handle.clone();
                 Box::pin(async move {
                     let command_count = framework.options().commands.len();
@@ -119,46 +129,13 @@ impl BotService {
                         "Discord bot connected and ready"
                     );
 
-                    // Skip command registration if definitions haven't changed since last startup.
-                    let fingerprint =
-                        crate::bot::commands_fingerprint(&framework.options().commands);
-                    let stored =
-                        crate::data::kv::get(&app_state.db_pool, "bot.commands_fingerprint")
-                            .await
-                            .unwrap_or(None);
-
-                    if stored.as_deref() == Some(fingerprint.as_str()) {
-                        info!(
-                            commands = command_count,
-                            "Discord commands unchanged, skipping registration"
-                        );
-                    } else {
-                        poise::builtins::register_in_guild(
-                            ctx,
-                            &framework.options().commands,
-                            bot_target_guild.into(),
-                        )
-                        .await?;
-                        poise::builtins::register_globally(ctx, &framework.options().commands)
-                            .await?;
-
-                        if let Err(e) = crate::data::kv::set(
-                            &app_state.db_pool,
-                            "bot.commands_fingerprint",
-                            &fingerprint,
-                        )
-                        .await
-                        {
-                            warn!(error = ?e, "Failed to persist command fingerprint");
-                        }
-
-                        info!(
-                            guild_commands = command_count,
-                            global_commands = command_count,
-                            target_guild = %bot_target_guild,
-                            "Discord commands registered"
-                        );
-                    }
+                    crate::bot::registration::sync_commands(
+                        &ctx.http,
+                        &app_state.db_pool,
+                        &framework.options().commands,
+                        &registration,
+                    )
+                    .await;
 
                     // Start status update task with shutdown support
                     let handle = Self::start_status_update_task(
@@ -170,7 +147,11 @@ impl BotService {
 
                     app_state.service_statuses.set("bot", ServiceStatus::Active);
 
-                    Ok(Data { app_state })
+                    Ok(Data {
+                        app_state,
+                        admin_access,
+                        autocomplete_cache: AutocompleteCache::new(),
+                    })
                 })
             })
             .build();
@@ -268,8 +249,9 @@ impl Service for BotService {
             .ok_or_else(|| anyhow::anyhow!("BotService::run called more than once"))?;
 
         let mut client = Self::create_client(
-            &self.bot_token,
-            self.bot_target_guild,
+            self.bot_token.expose_secret(),
+            self.registration.clone(),
+            self.admin_access.clone(),
             self.app_state.clone(),
             self.status_task_handle.clone(),
             status_shutdown_rx,
@@ -5,8 +5,10 @@ use tracing::{error, info, warn};
 pub mod bot;
 pub mod manager;
 pub mod notifications;
+pub mod outbox;
 pub mod signals;
 pub mod web;
+pub mod webhooks;
 
 #[derive(Debug)]
 pub enum ServiceResult {
@@ -1,127 +1,317 @@
-//! Notification dispatcher: watches the event buffer for course changes and
-//! sends Discord DMs to users who have active course watches.
+//! Notification producer. Alerts for active course watches -- a Discord DM,
+//! or a webhook POST for watches created from the web with one -- are queued
+//! by [`queue_watch_alerts`] when the outbox dispatcher fans out a write's
+//! course changes.
+//!
+//! The service polls for registration reminders whose window has opened (see
+//! [`crate::data::reminders`]) and DMs each subscriber once, and re-runs
+//! notifying saved searches (see [`crate::data::saved_searches`]) after each
+//! subject scrape to DM their owners any new matches.
+//!
+//! Messages go through the outbox (see [`crate::data::outbox`]); delivery
+//! happens in [`OutboxService`](super::outbox::OutboxService).
 
-use crate::data::events::{AuditLogEvent, DomainEvent, EventBuffer};
-use crate::data::watches::{self, TriggeredWatch};
-use serenity::all::{Color, CreateEmbed, CreateMessage, UserId};
-use sqlx::PgPool;
+use crate::data::events::{CourseEvent, DomainEvent, EventBuffer, EventSubscription};
+use crate::data::models::Course;
+use crate::data::outbox::{self, DiscordEmbed, NewOutboxMessage, OutboxPayload};
+use crate::data::reminders::{self, Classification, DueReminder};
+use crate::data::saved_searches::{self, NotifyingSearch};
+use crate::data::watches::{self, ChangedCourses, TriggeredWatch, WatchType};
+use crate::web::courses::SearchParams;
+use crate::web::saved_searches::notify_matches;
+use crate::web::ws::ScrapeJobEvent;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
 use super::Service;
 
+/// How often to check for registration windows that have opened.
+const REMINDER_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// New matches listed in a saved search alert; the rest are counted.
+const MAX_LISTED_MATCHES: usize = 10;
+
 pub struct NotificationService {
     pool: PgPool,
     events: Arc<EventBuffer>,
-    http: Arc<serenity::http::Http>,
     base_url: Option<String>,
 }
 
 impl NotificationService {
-    pub fn new(
-        pool: PgPool,
-        events: Arc<EventBuffer>,
-        http: Arc<serenity::http::Http>,
-        base_url: Option<String>,
-    ) -> Self {
+    pub fn new(pool: PgPool, events: Arc<EventBuffer>, base_url: Option<String>) -> Self {
         Self {
             pool,
             events,
-            http,
             base_url,
         }
     }
 
-    async fn process_audit_event(&self, event: &AuditLogEvent) {
-        // Classify changed course IDs by change type
-        let mut enrollment_ids: Vec<i32> = Vec::new();
-        let mut waitlist_ids: Vec<i32> = Vec::new();
-        let mut any_change_ids: Vec<i32> = Vec::new();
-
-        for entry in &event.entries {
-            if entry.field_changed == "initial" {
-                continue;
-            }
-            any_change_ids.push(entry.course_id);
-            match entry.field_changed.as_str() {
-                "enrollment" | "max_enrollment" => enrollment_ids.push(entry.course_id),
-                "wait_count" | "wait_capacity" => waitlist_ids.push(entry.course_id),
-                _ => {}
-            }
+    /// Queue a DM for every reminder whose registration window has opened.
+    async fn dispatch_due_reminders(&self) -> anyhow::Result<()> {
+        let due = reminders::find_due_reminders(&self.pool, chrono::Utc::now()).await?;
+        if due.is_empty() {
+            return Ok(());
         }
 
-        if any_change_ids.is_empty() {
-            return;
-        }
+        debug!(count = due.len(), "queueing registration reminders");
 
-        // Deduplicate each set
-        enrollment_ids.sort_unstable();
-        enrollment_ids.dedup();
-        waitlist_ids.sort_unstable();
-        waitlist_ids.dedup();
-        any_change_ids.sort_unstable();
-        any_change_ids.dedup();
-
-        let triggered = match watches::find_triggered_watches(
-            &self.pool,
-            &enrollment_ids,
-            &waitlist_ids,
-            &any_change_ids,
-        )
-        .await
-        {
-            Ok(w) => w,
-            Err(e) => {
-                warn!(error = ?e, "failed to query triggered watches");
-                return;
-            }
-        };
+        let messages: Vec<NewOutboxMessage> = due
+            .iter()
+            .filter_map(|reminder| {
+                let Some(user_id) = dm_recipient(reminder.discord_id) else {
+                    debug!(
+                        reminder_id = reminder.reminder_id,
+                        user_id = reminder.user_id,
+                        "skipping reminder DM, no linked Discord account"
+                    );
+                    return None;
+                };
+                Some(NewOutboxMessage {
+                    payload: OutboxPayload::DiscordDm {
+                        user_id,
+                        embed: build_reminder_embed(reminder, self.base_url.as_deref()),
+                    },
+                    // One DM per opening time: re-arming a sent reminder only
+                    // sends again if the window has moved since.
+                    dedupe_key: Some(format!(
+                        "registration-reminder:{}:{}",
+                        reminder.reminder_id,
+                        reminder.opens_at.timestamp()
+                    )),
+                })
+            })
+            .collect();
+        let reminder_ids: Vec<i32> = due.iter().map(|r| r.reminder_id).collect();
+
+        let mut tx = self.pool.begin().await?;
+        outbox::enqueue(&mut *tx, &messages).await?;
+        reminders::mark_notified(&mut *tx, &reminder_ids).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        if triggered.is_empty() {
-            return;
+    /// Re-run the notifying saved searches a scrape of `subject` could
+    /// affect, and DM each owner the sections that started matching.
+    async fn evaluate_saved_searches(&self, term_code: &str, subject: &str) -> anyhow::Result<()> {
+        let searches =
+            saved_searches::find_notifying_searches(&self.pool, term_code, subject).await?;
+        for search in &searches {
+            if let Err(e) = self.evaluate_saved_search(search).await {
+                warn!(error = ?e, id = search.id, "failed to evaluate saved search");
+            }
         }
+        Ok(())
+    }
 
-        debug!(
-            count = triggered.len(),
-            "dispatching course watch notifications"
-        );
+    async fn evaluate_saved_search(&self, search: &NotifyingSearch) -> anyhow::Result<()> {
+        let params: SearchParams = serde_json::from_value(search.filters.0.clone())?;
+        let Some(matches) = notify_matches(&self.pool, &search.term_code, &params).await? else {
+            // Grew too broad since it was saved; keep the old baseline so
+            // narrower results later aren't all reported as new.
+            debug!(
+                id = search.id,
+                "saved search matches too many sections to notify"
+            );
+            return Ok(());
+        };
 
-        for watch in &triggered {
-            match self.send_notification(watch).await {
-                Ok(()) => {
-                    if let Err(e) = watches::mark_notified(&self.pool, watch.watch_id).await {
-                        warn!(watch_id = watch.watch_id, error = ?e, "failed to mark watch notified");
-                    }
-                }
-                Err(e) => {
-                    warn!(
-                        watch_id = watch.watch_id,
-                        discord_user_id = watch.discord_user_id,
-                        error = ?e,
-                        "failed to send watch notification"
+        let seen: HashSet<i32> = search.matched_course_ids.iter().copied().collect();
+        let new: Vec<&Course> = matches.iter().filter(|c| !seen.contains(&c.id)).collect();
+        // Sections that stop matching drop out, so they count as new if they
+        // match again.
+        let matched: Vec<i32> = matches.iter().map(|c| c.id).collect();
+
+        let mut tx = self.pool.begin().await?;
+        if !new.is_empty() {
+            match dm_recipient(search.discord_id) {
+                Some(user_id) => {
+                    debug!(
+                        id = search.id,
+                        count = new.len(),
+                        "queueing saved search alert"
                     );
+                    let message = NewOutboxMessage {
+                        payload: OutboxPayload::DiscordDm {
+                            user_id,
+                            embed: build_saved_search_embed(search, &new, self.base_url.as_deref()),
+                        },
+                        dedupe_key: None,
+                    };
+                    outbox::enqueue(&mut *tx, &[message]).await?;
                 }
+                None => debug!(
+                    id = search.id,
+                    user_id = search.user_id,
+                    "skipping saved search alert, no linked Discord account"
+                ),
             }
         }
+        saved_searches::record_matches(&mut *tx, search.id, &matched, !new.is_empty()).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// Queue alerts for the watches `events` trigger, and start each watch's
+/// cooldown, on `conn`.
+///
+/// Runs in the outbox dispatcher's transaction for a
+/// [`CourseChanges`](OutboxPayload::CourseChanges) message, so alerts are
+/// queued exactly when that message is marked delivered. DM watches whose
+/// owner has no linked Discord account are skipped but still cool down.
+/// Department watches fire for each matching section and don't cool down.
+pub(super) async fn queue_watch_alerts(
+    conn: &mut PgConnection,
+    base_url: Option<&str>,
+    events: &[CourseEvent],
+) -> anyhow::Result<()> {
+    let mut changed = ChangedCourses::default();
+    for event in events {
+        changed.record(event.course.id, &event.change);
+    }
+    if changed.is_empty() {
+        return Ok(());
     }
+    changed.dedup();
 
-    async fn send_notification(&self, watch: &TriggeredWatch) -> anyhow::Result<()> {
-        let user_id = UserId::new(watch.discord_user_id as u64);
-        let dm = user_id.create_dm_channel(&self.http).await?;
+    let triggered = watches::find_triggered_watches(&mut *conn, &changed).await?;
+    // A section already alerted through its own watch isn't alerted again
+    // through a department watch.
+    let alerted: HashSet<(i64, &str, &str, &str)> = triggered
+        .iter()
+        .map(|w| {
+            (
+                w.user_id,
+                w.term_code.as_str(),
+                w.crn.as_str(),
+                w.watch_type.as_str(),
+            )
+        })
+        .collect();
+    let department = watches::find_triggered_department_watches(&mut *conn, &changed).await?;
+    let department: Vec<&TriggeredWatch> = department
+        .iter()
+        .filter(|w| {
+            !alerted.contains(&(
+                w.user_id,
+                w.term_code.as_str(),
+                w.crn.as_str(),
+                w.watch_type.as_str(),
+            ))
+        })
+        .collect();
+    if triggered.is_empty() && department.is_empty() {
+        return Ok(());
+    }
+    debug!(
+        count = triggered.len(),
+        department = department.len(),
+        "queueing course watch notifications"
+    );
 
-        let course_link = self
-            .base_url
-            .as_deref()
-            .map(|base| format!("{}/courses/{}/{}", base, watch.term_code, watch.crn));
+    let messages: Vec<NewOutboxMessage> = triggered
+        .iter()
+        .chain(department.iter().copied())
+        .filter_map(|watch| watch_alert(watch, base_url))
+        .collect();
+    let watch_ids: Vec<i32> = triggered.iter().map(|w| w.watch_id).collect();
 
-        let embed = build_embed(watch, course_link.as_deref());
-        dm.send_message(&self.http, CreateMessage::new().embed(embed))
-            .await?;
-        Ok(())
+    outbox::enqueue(&mut *conn, &messages).await?;
+    watches::mark_notified(&mut *conn, &watch_ids).await?;
+    Ok(())
+}
+
+/// The outbox message alerting `watch`'s owner, or `None` for a DM watch
+/// whose owner has no linked Discord account.
+fn watch_alert(watch: &TriggeredWatch, base_url: Option<&str>) -> Option<NewOutboxMessage> {
+    let course_link =
+        base_url.map(|base| format!("{}/courses/{}/{}", base, watch.term_code, watch.crn));
+    let payload = match &watch.webhook_url {
+        Some(url) => OutboxPayload::Webhook {
+            url: url.clone(),
+            body: build_webhook_body(watch, course_link.as_deref()),
+        },
+        None => {
+            let Some(user_id) = dm_recipient(watch.discord_id) else {
+                debug!(
+                    watch_id = watch.watch_id,
+                    user_id = watch.user_id,
+                    "skipping watch DM, no linked Discord account"
+                );
+                return None;
+            };
+            OutboxPayload::DiscordDm {
+                user_id,
+                embed: build_embed(watch, course_link.as_deref()),
+            }
+        }
+    };
+    Some(NewOutboxMessage {
+        payload,
+        dedupe_key: None,
+    })
+}
+
+/// The Discord user to DM, from the owner's linked Discord identity. User IDs
+/// are not snowflakes for accounts created through other providers.
+fn dm_recipient(discord_id: Option<i64>) -> Option<u64> {
+    discord_id.and_then(|id| u64::try_from(id).ok())
+}
+
+fn build_saved_search_embed(
+    search: &NotifyingSearch,
+    new: &[&Course],
+    base_url: Option<&str>,
+) -> DiscordEmbed {
+    let mut lines: Vec<String> = new
+        .iter()
+        .take(MAX_LISTED_MATCHES)
+        .map(|c| {
+            format!(
+                "**{} {}** - {} (CRN {})",
+                c.subject, c.course_number, c.title, c.crn
+            )
+        })
+        .collect();
+    if new.len() > MAX_LISTED_MATCHES {
+        lines.push(format!("...and {} more", new.len() - MAX_LISTED_MATCHES));
+    }
+    DiscordEmbed {
+        title: format!("New matches for \"{}\"", search.name),
+        description: lines.join("\n"),
+        color: 0x0096c8,
+        url: base_url.map(str::to_owned),
+        fields: vec![
+            ("Term".to_owned(), search.term_code.clone(), true),
+            ("New sections".to_owned(), new.len().to_string(), true),
+        ],
+    }
+}
+
+fn build_reminder_embed(reminder: &DueReminder, base_url: Option<&str>) -> DiscordEmbed {
+    let classification = reminder
+        .classification
+        .parse::<Classification>()
+        .map(|c| c.label())
+        .unwrap_or("Your");
+    DiscordEmbed {
+        title: format!("Registration is open for {}", reminder.term_description),
+        description: format!(
+            "**{}** registration for {} opened <t:{}:R>.",
+            classification,
+            reminder.term_description,
+            reminder.opens_at.timestamp()
+        ),
+        color: 0x00c864,
+        url: base_url.map(str::to_owned),
+        fields: vec![("Term".to_owned(), reminder.term_code.clone(), true)],
     }
 }
 
-fn build_embed(watch: &TriggeredWatch, course_url: Option<&str>) -> CreateEmbed {
+fn build_embed(watch: &TriggeredWatch, course_url: Option<&str>) -> DiscordEmbed {
     let course_label = format!(
         "{} {} - {} (CRN {})",
         watch.subject, watch.course_number, watch.title, watch.crn
@@ -132,38 +322,64 @@ fn build_embed(watch: &TriggeredWatch, course_url: Option<&str>) -> CreateEmbed
             let seats = watch.max_enrollment - watch.enrollment;
             (
                 format!("A seat has opened up! {} seat(s) available.", seats),
-                Color::from_rgb(0, 200, 100),
+                0x00c864,
             )
         }
         "waitlist_open" => {
             let slots = watch.wait_capacity - watch.wait_count;
             (
                 format!("A waitlist spot is available! {} slot(s) open.", slots),
-                Color::from_rgb(0, 150, 200),
+                0x0096c8,
             )
         }
-        _ => (
-            "This course has been updated.".to_string(),
-            Color::from_rgb(150, 150, 150),
+        "attribute_change" => (
+            "This course's attributes have changed.".to_string(),
+            0xc89600,
         ),
+        "restriction_change" => (
+            "This course's registration restrictions have changed.".to_string(),
+            0xc86400,
+        ),
+        _ => ("This course has been updated.".to_string(), 0x969696),
     };
 
     let enrollment_field = format!("{}/{}", watch.enrollment, watch.max_enrollment);
     let waitlist_field = format!("{}/{}", watch.wait_count, watch.wait_capacity);
 
-    let mut embed = CreateEmbed::new()
-        .title(course_label)
-        .description(description)
-        .color(color)
-        .field("Term", &watch.term_code, true)
-        .field("Enrollment", enrollment_field, true)
-        .field("Waitlist", waitlist_field, true);
-
-    if let Some(url) = course_url {
-        embed = embed.url(url);
+    DiscordEmbed {
+        title: course_label,
+        description,
+        color,
+        url: course_url.map(str::to_owned),
+        fields: vec![
+            ("Term".to_owned(), watch.term_code.clone(), true),
+            ("Enrollment".to_owned(), enrollment_field, true),
+            ("Waitlist".to_owned(), waitlist_field, true),
+        ],
     }
+}
 
-    embed
+fn build_webhook_body(watch: &TriggeredWatch, course_url: Option<&str>) -> serde_json::Value {
+    let watch_type = watch
+        .watch_type
+        .parse::<WatchType>()
+        .ok()
+        .and_then(|t| serde_json::to_value(t).ok())
+        .unwrap_or_else(|| watch.watch_type.clone().into());
+    serde_json::json!({
+        "event": "courseWatch",
+        "watchType": watch_type,
+        "term": watch.term_code,
+        "crn": watch.crn,
+        "subject": watch.subject,
+        "courseNumber": watch.course_number,
+        "title": watch.title,
+        "enrollment": watch.enrollment,
+        "maxEnrollment": watch.max_enrollment,
+        "waitCount": watch.wait_count,
+        "waitCapacity": watch.wait_capacity,
+        "url": course_url,
+    })
 }
 
 #[async_trait::async_trait]
@@ -175,34 +391,49 @@ impl Service for NotificationService {
     async fn run(&mut self) -> Result<(), anyhow::Error> {
         info!("notification dispatcher started");
 
-        let (mut cursor, mut watch_rx) = self.events.subscribe();
+        let mut subscription = EventSubscription::new(self.events.clone());
+        let mut reminder_tick = tokio::time::interval(REMINDER_POLL_INTERVAL);
+        reminder_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
         loop {
-            watch_rx
-                .changed()
-                .await
-                .map_err(|_| anyhow::anyhow!("event buffer watch channel closed"))?;
-
-            // If the consumer fell behind and events were pruned, skip ahead to avoid a gap.
-            let base = self.events.base_offset();
-            if cursor < base {
+            tokio::select! {
+                changed = subscription.changed() => {
+                    if !changed {
+                        return Err(anyhow::anyhow!("event buffer watch channel closed"));
+                    }
+                }
+                _ = reminder_tick.tick() => {
+                    if let Err(e) = self.dispatch_due_reminders().await {
+                        warn!(error = ?e, "failed to dispatch registration reminders");
+                    }
+                    continue;
+                }
+            }
+
+            let drained = subscription.drain();
+            if drained.lagged() {
                 warn!(
-                    skipped = base - cursor,
+                    skipped = drained.skipped,
                     "notification dispatcher fell behind event buffer, skipping pruned events"
                 );
-                cursor = base;
             }
 
-            loop {
-                match self.events.read(cursor) {
-                    Some(DomainEvent::AuditLog(event)) => {
-                        cursor += 1;
-                        self.process_audit_event(&event).await;
-                    }
-                    Some(_) => {
-                        cursor += 1;
-                    }
-                    None => break,
+            for event in drained.events {
+                let DomainEvent::ScrapeJob(ScrapeJobEvent::Completed {
+                    subject: Some(subject),
+                    term: Some(term),
+                    diff,
+                    ..
+                }) = event
+                else {
+                    continue;
+                };
+                // A run that changed nothing can't have produced new matches.
+                if diff.as_ref().is_some_and(|d| d.is_noop()) {
+                    continue;
+                }
+                if let Err(e) = self.evaluate_saved_searches(&term, &subject).await {
+                    warn!(error = ?e, %term, %subject, "failed to evaluate saved searches");
                 }
             }
         }
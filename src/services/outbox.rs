@@ -0,0 +1,457 @@
+//! Outbox dispatcher: delivers queued notifications with leasing and retries.
+//!
+//! Polls `notification_outbox` for due messages, delivers a batch
+//! concurrently over each message's channel, and records the outcomes under
+//! the batch's lease. Transient failures back off
+//! exponentially; permanent ones (and messages out of attempts) are
+//! dead-lettered with the last error kept for inspection.
+//!
+//! Webhook subscription deliveries are signed here, just before sending,
+//! with the endpoint's current secret (see [`crate::data::webhooks`]).
+//!
+//! Course changes are queued by the writes that make them and "delivered" by
+//! fanning them out to watch alerts and webhook deliveries, enqueued in the
+//! same transaction that marks the change message delivered.
+//!
+//! User-supplied webhook URLs are only checked as strings when registered, so
+//! they're sent through a client that re-checks them, doesn't follow
+//! redirects, and refuses to connect to hosts resolving to non-public
+//! addresses.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serenity::all::{Color, CreateEmbed, CreateMessage, UserId};
+use sqlx::PgPool;
+use tracing::{debug, info, warn};
+
+use super::Service;
+use super::notifications::queue_watch_alerts;
+use super::webhooks::queue_webhook_deliveries;
+use crate::data::events::CourseEvent;
+use crate::data::outbox::{self, DiscordEmbed, OutboxMessage, OutboxPayload};
+use crate::data::webhooks;
+use crate::utils::webhook_url::{is_public_ip, validate_webhook_url};
+
+/// How often to look for due messages when the last batch was empty.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Messages claimed per batch.
+const BATCH_SIZE: i64 = 25;
+/// Lease on a claimed message; a dispatcher that dies mid-batch releases its
+/// messages for retry once this passes.
+const LEASE: Duration = Duration::from_secs(120);
+/// Deadline for one delivery, whatever its channel. Batches are delivered
+/// concurrently, so this bounds how long a batch holds its lease.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(45);
+const _: () = assert!(DELIVERY_TIMEOUT.as_secs() * 2 <= LEASE.as_secs());
+/// Attempts before a message is dead-lettered.
+const MAX_ATTEMPTS: i32 = 8;
+/// Delivered messages are kept this long before pruning.
+const RETENTION: Duration = Duration::from_secs(7 * 24 * 3600);
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// A successful delivery, and whether it still needs recording.
+enum Delivered {
+    Sent,
+    /// Recorded along with the delivery itself (course change fan-out).
+    Recorded,
+}
+
+/// Why a delivery failed, and whether retrying could help.
+enum DeliveryError {
+    Retryable(anyhow::Error),
+    Permanent(anyhow::Error),
+    /// The lease lapsed and another dispatcher reclaimed the message.
+    LeaseLost,
+}
+
+impl From<anyhow::Error> for DeliveryError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Retryable(e)
+    }
+}
+
+/// Delay before retry `attempt` (1-based): 30s doubling, capped at one hour.
+fn backoff(attempt: i32) -> Duration {
+    let exp = attempt.clamp(1, 8) as u32 - 1;
+    (Duration::from_secs(30) * 2u32.pow(exp)).min(Duration::from_secs(3600))
+}
+
+pub struct OutboxService {
+    pool: PgPool,
+    discord: Arc<serenity::http::Http>,
+    /// For the operator-configured email relay, which may be internal.
+    client: reqwest::Client,
+    /// For user-supplied webhook URLs; see the module docs.
+    webhook_client: reqwest::Client,
+    /// HTTP relay that accepts `{to, subject, text}` and sends the email.
+    email_relay_url: Option<String>,
+    /// Public origin for course links in alerts.
+    base_url: Option<String>,
+}
+
+impl OutboxService {
+    pub fn new(
+        pool: PgPool,
+        discord: Arc<serenity::http::Http>,
+        email_relay_url: Option<String>,
+        base_url: Option<String>,
+    ) -> Self {
+        Self {
+            pool,
+            discord,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(15))
+                .build()
+                .expect("reqwest client builds"),
+            webhook_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(15))
+                .redirect(reqwest::redirect::Policy::none())
+                // A proxy would resolve the host itself, past the resolver.
+                .no_proxy()
+                .dns_resolver(Arc::new(PublicOnlyResolver))
+                .build()
+                .expect("reqwest client builds"),
+            email_relay_url,
+            base_url,
+        }
+    }
+
+    /// Claim and deliver one batch. Returns how many messages were claimed.
+    async fn dispatch_batch(&self) -> anyhow::Result<usize> {
+        let batch = outbox::claim_due(&self.pool, BATCH_SIZE, LEASE).await?;
+        futures::future::join_all(batch.iter().map(|message| self.dispatch(message))).await;
+        Ok(batch.len())
+    }
+
+    /// Deliver one claimed message and record the outcome under its lease.
+    async fn dispatch(&self, message: &OutboxMessage) {
+        let result = tokio::time::timeout(DELIVERY_TIMEOUT, self.deliver(message))
+            .await
+            .unwrap_or_else(|_| {
+                Err(DeliveryError::Retryable(anyhow::anyhow!(
+                    "delivery timed out after {DELIVERY_TIMEOUT:?}"
+                )))
+            });
+        let lease = message.locked_until;
+        let outcome = match result {
+            Ok(Delivered::Sent) => outbox::mark_delivered(&self.pool, message.id, lease).await,
+            Ok(Delivered::Recorded) => Ok(true),
+            Err(DeliveryError::LeaseLost) => Ok(false),
+            Err(DeliveryError::Retryable(e)) if message.attempts < MAX_ATTEMPTS => {
+                let delay = backoff(message.attempts);
+                debug!(
+                    id = message.id,
+                    attempts = message.attempts,
+                    retry_in = ?delay,
+                    error = ?e,
+                    "outbox delivery failed, will retry"
+                );
+                let retry_at = Utc::now() + TimeDelta::from_std(delay).unwrap_or_default();
+                outbox::mark_failed(
+                    &self.pool,
+                    message.id,
+                    lease,
+                    &format!("{e:#}"),
+                    Some(retry_at),
+                )
+                .await
+            }
+            Err(DeliveryError::Retryable(e) | DeliveryError::Permanent(e)) => {
+                warn!(
+                    id = message.id,
+                    channel = message.payload.channel(),
+                    attempts = message.attempts,
+                    age = ?(Utc::now() - message.created_at),
+                    error = ?e,
+                    "outbox message dead-lettered"
+                );
+                outbox::mark_failed(&self.pool, message.id, lease, &format!("{e:#}"), None).await
+            }
+        };
+        match outcome {
+            Ok(true) => {}
+            // The dispatcher that reclaimed it records its own outcome.
+            Ok(false) => warn!(
+                id = message.id,
+                channel = message.payload.channel(),
+                "outbox lease lapsed before the outcome was recorded"
+            ),
+            // If recording fails the lease lapses and the message is retried.
+            Err(e) => {
+                warn!(id = message.id, error = ?e, "failed to record outbox delivery outcome")
+            }
+        }
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<Delivered, DeliveryError> {
+        let sent = match &message.payload.0 {
+            OutboxPayload::DiscordDm { user_id, embed } => self.send_dm(*user_id, embed).await,
+            OutboxPayload::Webhook { url, body } => {
+                check_webhook_url(url)?;
+                self.post_json(&self.webhook_client, message.id, url, body)
+                    .await
+            }
+            OutboxPayload::Email { to, subject, text } => {
+                let Some(relay) = self.email_relay_url.as_deref() else {
+                    return Err(DeliveryError::Permanent(anyhow::anyhow!(
+                        "email relay not configured"
+                    )));
+                };
+                let body = serde_json::json!({ "to": to, "subject": subject, "text": text });
+                self.post_json(&self.client, message.id, relay, &body).await
+            }
+            OutboxPayload::WebhookSubscription {
+                webhook_id,
+                event,
+                body,
+            } => self.post_signed(message.id, *webhook_id, event, body).await,
+            OutboxPayload::CourseChanges { events } => {
+                return self
+                    .fan_out_course_changes(message.id, message.locked_until, events)
+                    .await
+                    .map(|()| Delivered::Recorded);
+            }
+        };
+        sent.map(|()| Delivered::Sent)
+    }
+
+    /// Queue watch alerts and webhook deliveries for committed course
+    /// changes, marking the message delivered in the same transaction so the
+    /// fan-out happens once.
+    ///
+    /// The message is marked first: that fails if the lease was lost, and
+    /// otherwise locks the row so it can't be reclaimed before commit.
+    async fn fan_out_course_changes(
+        &self,
+        id: i64,
+        lease: DateTime<Utc>,
+        events: &[CourseEvent],
+    ) -> Result<(), DeliveryError> {
+        let mut tx = self.pool.begin().await.map_err(anyhow::Error::from)?;
+        if !outbox::mark_delivered(&mut *tx, id, lease).await? {
+            return Err(DeliveryError::LeaseLost);
+        }
+        queue_watch_alerts(&mut tx, self.base_url.as_deref(), events).await?;
+        queue_webhook_deliveries(&mut tx, self.base_url.as_deref(), events).await?;
+        tx.commit().await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    async fn send_dm(&self, user_id: u64, embed: &DiscordEmbed) -> Result<(), DeliveryError> {
+        let dm = UserId::new(user_id)
+            .create_dm_channel(&self.discord)
+            .await
+            .map_err(discord_error)?;
+        dm.send_message(
+            &self.discord,
+            CreateMessage::new().embed(to_serenity(embed)),
+        )
+        .await
+        .map_err(discord_error)?;
+        Ok(())
+    }
+
+    /// POST `body` as JSON, with the outbox id as an idempotency key so
+    /// receivers can drop redeliveries.
+    async fn post_json(
+        &self,
+        client: &reqwest::Client,
+        id: i64,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<(), DeliveryError> {
+        let response = client
+            .post(url)
+            .header("Idempotency-Key", format!("outbox-{id}"))
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| DeliveryError::Retryable(e.into()))?;
+        check_status(url, response.status())
+    }
+
+    /// POST an event to a registered endpoint, signed with its secret.
+    async fn post_signed(
+        &self,
+        id: i64,
+        webhook_id: i32,
+        event: &str,
+        body: &serde_json::Value,
+    ) -> Result<(), DeliveryError> {
+        let target = webhooks::get_signing_target(&self.pool, webhook_id)
+            .await?
+            .filter(|t| t.enabled)
+            .ok_or_else(|| {
+                DeliveryError::Permanent(anyhow::anyhow!(
+                    "webhook {webhook_id} was deleted or disabled"
+                ))
+            })?;
+        check_webhook_url(&target.url)?;
+
+        // Sign the exact bytes sent.
+        let body = serde_json::to_string(body).map_err(|e| DeliveryError::Permanent(e.into()))?;
+        let timestamp = Utc::now().timestamp();
+        let response = self
+            .webhook_client
+            .post(&target.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("Idempotency-Key", format!("outbox-{id}"))
+            .header("X-Banner-Delivery", id.to_string())
+            .header("X-Banner-Event", event)
+            .header("X-Banner-Timestamp", timestamp.to_string())
+            .header(
+                "X-Banner-Signature",
+                webhooks::sign(&target.secret, timestamp, &body),
+            )
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| DeliveryError::Retryable(e.into()))?;
+        check_status(&target.url, response.status())
+    }
+}
+
+/// Re-run registration checks on a stored URL, which catches IP-literal hosts
+/// the resolver never sees.
+fn check_webhook_url(url: &str) -> Result<(), DeliveryError> {
+    validate_webhook_url(url).map(drop).map_err(|_| {
+        DeliveryError::Permanent(anyhow::anyhow!("{url} is not a public https endpoint"))
+    })
+}
+
+/// Resolves webhook hosts, failing when any address is private, loopback, or
+/// link-local so a public name can't be pointed at internal services.
+struct PublicOnlyResolver;
+
+impl reqwest::dns::Resolve for PublicOnlyResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_owned();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+                return Err(format!("{host} resolves to non-public address {}", addr.ip()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// Client errors other than 429, and redirects (which webhooks don't follow),
+/// won't succeed on retry.
+fn check_status(url: &str, status: reqwest::StatusCode) -> Result<(), DeliveryError> {
+    if status.is_success() {
+        Ok(())
+    } else if status.is_redirection() {
+        Err(DeliveryError::Permanent(anyhow::anyhow!(
+            "{url} redirected with {status}; webhook redirects are not followed"
+        )))
+    } else if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        Err(DeliveryError::Permanent(anyhow::anyhow!(
+            "{url} rejected delivery with {status}"
+        )))
+    } else {
+        Err(DeliveryError::Retryable(anyhow::anyhow!(
+            "{url} responded {status}"
+        )))
+    }
+}
+
+/// Discord 4xx responses (DMs disabled, unknown user) won't succeed on retry.
+fn discord_error(e: serenity::Error) -> DeliveryError {
+    match &e {
+        serenity::Error::Http(serenity::http::HttpError::UnsuccessfulRequest(response))
+            if response.status_code.is_client_error() && response.status_code.as_u16() != 429 =>
+        {
+            DeliveryError::Permanent(e.into())
+        }
+        _ => DeliveryError::Retryable(e.into()),
+    }
+}
+
+fn to_serenity(embed: &DiscordEmbed) -> CreateEmbed {
+    let mut out = CreateEmbed::new()
+        .title(&embed.title)
+        .description(&embed.description)
+        .color(Color::new(embed.color));
+    for (name, value, inline) in &embed.fields {
+        out = out.field(name, value, *inline);
+    }
+    if let Some(url) = &embed.url {
+        out = out.url(url);
+    }
+    out
+}
+
+#[async_trait::async_trait]
+impl Service for OutboxService {
+    fn name(&self) -> &'static str {
+        "outbox"
+    }
+
+    async fn run(&mut self) -> Result<(), anyhow::Error> {
+        info!("outbox dispatcher started");
+        let mut last_prune = tokio::time::Instant::now();
+
+        loop {
+            let claimed = match self.dispatch_batch().await {
+                Ok(n) => n,
+                Err(e) => {
+                    warn!(error = ?e, "outbox dispatch failed");
+                    0
+                }
+            };
+
+            if last_prune.elapsed() >= PRUNE_INTERVAL {
+                last_prune = tokio::time::Instant::now();
+                match outbox::prune_delivered(&self.pool, RETENTION).await {
+                    Ok(0) => {}
+                    Ok(pruned) => debug!(pruned, "pruned delivered outbox messages"),
+                    Err(e) => warn!(error = ?e, "failed to prune outbox"),
+                }
+            }
+
+            // A full batch suggests a backlog; keep draining without sleeping.
+            if claimed < BATCH_SIZE as usize {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    async fn shutdown(&mut self) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        assert_eq!(backoff(1), Duration::from_secs(30));
+        assert_eq!(backoff(2), Duration::from_secs(60));
+        assert_eq!(backoff(5), Duration::from_secs(480));
+        assert_eq!(backoff(8), Duration::from_secs(3600));
+    }
+
+    #[tokio::test]
+    async fn resolver_refuses_loopback_hosts() {
+        use reqwest::dns::Resolve;
+
+        let name = "localhost".parse().unwrap();
+        assert!(PublicOnlyResolver.resolve(name).await.is_err());
+    }
+
+    #[test]
+    fn stored_private_urls_are_refused() {
+        assert!(matches!(
+            check_webhook_url("https://169.254.169.254/latest/meta-data"),
+            Err(DeliveryError::Permanent(_))
+        ));
+        assert!(check_webhook_url("https://hooks.example.com/x").is_ok());
+    }
+}
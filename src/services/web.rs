@@ -78,6 +78,73 @@ impl WebService {
         }
     }
 
+    /// Periodically rolls raw enrollment metrics up into per-day sparklines.
+    async fn sparkline_rollup_loop(state: AppState, mut shutdown_rx: broadcast::Receiver<()>) {
+        use std::time::Duration;
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let today = chrono::Utc::now().date_naive();
+                    match crate::data::sparklines::rollup(&state.db_pool, today).await {
+                        Ok(written) => {
+                            info!(courses = written, "enrollment sparklines rolled up");
+                        }
+                        Err(e) => {
+                            warn!(error = %e, "enrollment sparkline rollup failed");
+                        }
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Periodically rolls scrape job results past the retention window into
+    /// daily stats and purges old dead-lettered jobs.
+    async fn scrape_history_compaction_loop(
+        state: AppState,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        use std::time::Duration;
+        // Run every six hours; only results crossing the cutoff since the
+        // last run are touched, so frequent runs stay cheap.
+        let mut interval = tokio::time::interval(Duration::from_secs(6 * 3600));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let now = chrono::Utc::now();
+                    let cutoff = now - chrono::Duration::days(state.scrape_history_retention_days.into());
+                    let pool = &state.db_pool;
+                    match crate::data::scrape_history::compact(pool, cutoff).await {
+                        Ok(compaction) => {
+                            if compaction.results > 0 || compaction.dead_letters > 0 {
+                                info!(
+                                    compacted = compaction.results,
+                                    dead_letters = compaction.dead_letters,
+                                    "compacted scrape job history"
+                                );
+                            }
+                            if let Err(e) = crate::data::scrape_history::record_compaction(pool, now, compaction.results).await {
+                                warn!(error = %e, "failed to record scrape history compaction");
+                            }
+                        }
+                        Err(e) => {
+                            warn!(error = %e, "scrape history compaction failed");
+                        }
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    break;
+                }
+            }
+        }
+    }
+
     /// Periodically cleans up expired sessions from the database and in-memory cache.
     async fn session_cleanup_loop(state: AppState, mut shutdown_rx: broadcast::Receiver<()>) {
         use std::time::Duration;
@@ -104,6 +171,21 @@ impl WebService {
             }
         }
     }
+
+    /// Periodically drains aged-out histogram samples from the metrics recorder.
+    async fn metrics_upkeep_loop(state: AppState, mut shutdown_rx: broadcast::Receiver<()>) {
+        use std::time::Duration;
+        let mut interval = tokio::time::interval(Duration::from_secs(5));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => state.metrics.run_upkeep(),
+                _ = shutdown_rx.recv() => {
+                    break;
+                }
+            }
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -154,6 +236,27 @@ impl Service for WebService {
             Self::reference_cache_refresh_loop(refresh_state, refresh_shutdown_rx).await;
         });
 
+        // Spawn enrollment sparkline rollup task
+        let rollup_state = self.app_state.clone();
+        let rollup_shutdown_rx = shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            Self::sparkline_rollup_loop(rollup_state, rollup_shutdown_rx).await;
+        });
+
+        // Spawn scrape history compaction task
+        let compaction_state = self.app_state.clone();
+        let compaction_shutdown_rx = shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            Self::scrape_history_compaction_loop(compaction_state, compaction_shutdown_rx).await;
+        });
+
+        // Spawn metrics recorder upkeep task
+        let upkeep_state = self.app_state.clone();
+        let upkeep_shutdown_rx = shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            Self::metrics_upkeep_loop(upkeep_state, upkeep_shutdown_rx).await;
+        });
+
         // Use axum's graceful shutdown with the internal shutdown signal.
         // `into_make_service_with_connect_info` makes `ConnectInfo<SocketAddr>`
         // available to handlers (used by the SSR proxy for x-forwarded-for).
@@ -0,0 +1,195 @@
+//! Webhook fan-out: turns course events into deliveries for subscribed
+//! endpoints.
+//!
+//! The outbox dispatcher hands over each write's committed course changes;
+//! they're grouped by [`WebhookEvent`] and queued as one outbox message per
+//! subscribed endpoint and batch (see [`crate::data::webhooks`]). Signing and
+//! delivery happen in [`OutboxService`](super::outbox::OutboxService).
+
+use std::collections::BTreeMap;
+
+use sqlx::PgConnection;
+use tracing::debug;
+
+use crate::data::events::{CourseChange, CourseEvent};
+use crate::data::outbox::{self, NewOutboxMessage, OutboxPayload};
+use crate::data::webhooks::{self, WebhookEvent};
+
+/// Most changes in one webhook delivery; larger batches are split.
+const MAX_WEBHOOK_ITEMS: usize = 100;
+
+/// Queue a delivery per subscribed endpoint for each event type `events`
+/// raise, on `conn`.
+///
+/// Runs in the outbox dispatcher's transaction for a
+/// [`CourseChanges`](crate::data::outbox::OutboxPayload::CourseChanges)
+/// message, alongside [`queue_watch_alerts`](super::notifications::queue_watch_alerts).
+pub(super) async fn queue_webhook_deliveries(
+    conn: &mut PgConnection,
+    base_url: Option<&str>,
+    events: &[CourseEvent],
+) -> anyhow::Result<()> {
+    let events = group_webhook_events(events, base_url);
+    if events.is_empty() {
+        return Ok(());
+    }
+    let kinds: Vec<WebhookEvent> = events.keys().copied().collect();
+    let subscribers = webhooks::find_subscribers(&mut *conn, &kinds).await?;
+    if subscribers.is_empty() {
+        return Ok(());
+    }
+
+    let occurred_at = chrono::Utc::now().to_rfc3339();
+    let mut messages = Vec::new();
+    for (event, items) in &events {
+        for chunk in items.chunks(MAX_WEBHOOK_ITEMS) {
+            let body = serde_json::json!({
+                "event": event.as_str(),
+                "occurredAt": occurred_at,
+                "data": chunk,
+            });
+            for subscriber in subscribers.iter().filter(|s| s.wants(*event)) {
+                messages.push(NewOutboxMessage {
+                    payload: OutboxPayload::WebhookSubscription {
+                        webhook_id: subscriber.id,
+                        event: event.as_str().to_owned(),
+                        body: body.clone(),
+                    },
+                    dedupe_key: None,
+                });
+            }
+        }
+    }
+
+    debug!(count = messages.len(), "queueing webhook deliveries");
+    outbox::enqueue(&mut *conn, &messages).await?;
+    Ok(())
+}
+
+/// Group course changes into webhook event items. Each item names the
+/// section and what happened to it.
+fn group_webhook_events(
+    events: &[CourseEvent],
+    base_url: Option<&str>,
+) -> BTreeMap<WebhookEvent, Vec<serde_json::Value>> {
+    let mut grouped: BTreeMap<WebhookEvent, Vec<serde_json::Value>> = BTreeMap::new();
+    for event in events {
+        let Some(kind) = WebhookEvent::for_change(&event.change) else {
+            continue;
+        };
+        let change = match &event.change {
+            CourseChange::Created { snapshot } => serde_json::json!({ "snapshot": snapshot }),
+            CourseChange::Seats { field, old, new } => serde_json::json!({
+                "field": field.as_str(),
+                "old": old,
+                "new": new,
+            }),
+            CourseChange::Instructors { new, .. } => serde_json::json!({
+                "added": event.change.assigned_instructors(),
+                "instructors": new,
+            }),
+            CourseChange::Field { .. } => continue,
+        };
+
+        let course = &event.course;
+        let url = base_url.map(|base| format!("{base}/courses/{}/{}", course.term, course.crn));
+        let mut course_json = serde_json::to_value(course).unwrap_or_default();
+        course_json["url"] = url.into();
+
+        grouped.entry(kind).or_default().push(serde_json::json!({
+            "course": course_json,
+            "change": change,
+            "auditId": event.audit_id,
+        }));
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::events::{CourseRef, SeatField};
+    use serde_json::json;
+
+    fn event(audit_id: i32, change: CourseChange) -> CourseEvent {
+        CourseEvent {
+            audit_id,
+            at: chrono::DateTime::UNIX_EPOCH,
+            course: CourseRef {
+                id: 7,
+                term: "202710".to_owned(),
+                crn: "12345".to_owned(),
+                subject: "CS".to_owned(),
+                course_number: "1083".to_owned(),
+                title: "Programming I".to_owned(),
+            },
+            change,
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| (*n).to_owned()).collect()
+    }
+
+    #[test]
+    fn course_events_map_to_webhook_events() {
+        let events = [
+            event(
+                1,
+                CourseChange::Created {
+                    snapshot: json!({ "enrollment": 0 }),
+                },
+            ),
+            event(
+                2,
+                CourseChange::Seats {
+                    field: SeatField::WaitCount,
+                    old: Some(1),
+                    new: 2,
+                },
+            ),
+            event(
+                3,
+                CourseChange::Field {
+                    field: "title".to_owned(),
+                    old: Some(json!("Old")),
+                    new: json!("New"),
+                },
+            ),
+            event(
+                4,
+                CourseChange::Instructors {
+                    old: names(&["A"]),
+                    new: names(&["A", "B"]),
+                },
+            ),
+            // Only a removal; nobody was assigned.
+            event(
+                5,
+                CourseChange::Instructors {
+                    old: names(&["A", "B"]),
+                    new: names(&["A"]),
+                },
+            ),
+        ];
+        let events = group_webhook_events(&events, Some("https://example.com"));
+
+        assert_eq!(
+            events.keys().copied().collect::<Vec<_>>(),
+            WebhookEvent::ALL
+        );
+        let created = &events[&WebhookEvent::CourseCreated][0];
+        assert_eq!(
+            created["course"]["url"],
+            "https://example.com/courses/202710/12345"
+        );
+        assert_eq!(created["course"]["courseNumber"], "1083");
+        let enrollment = &events[&WebhookEvent::EnrollmentChanged];
+        assert_eq!(enrollment.len(), 1);
+        assert_eq!(enrollment[0]["change"]["field"], "waitCount");
+        assert_eq!(enrollment[0]["change"]["old"], 1);
+        let assigned = &events[&WebhookEvent::InstructorAssigned];
+        assert_eq!(assigned.len(), 1);
+        assert_eq!(assigned[0]["change"]["added"], json!(["B"]));
+    }
+}
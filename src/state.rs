@@ -3,12 +3,29 @@
 use crate::banner::BannerApi;
 use crate::data::events::EventBuffer;
 use crate::data::models::ReferenceData;
+use crate::data::scoring::ScoringDecay;
+use crate::data::search::SearchWeights;
+use crate::metrics::Metrics;
+use crate::scraper::syncs::SyncRequests;
+use crate::web::attribution::AttributionConfig;
+use crate::web::auth::api_keys::ApiKeyCache;
 use crate::web::auth::session::{OAuthStateStore, SessionCache};
-use crate::web::middleware::rate_limit::{RateLimitState, SharedRateLimitState};
+use crate::web::crawler::CrawlerPolicy;
+use crate::web::debug_capture::DebugCaptures;
+use crate::web::feed_cache::FeedCache;
+use crate::web::instructor_cache::InstructorProfileCache;
+use crate::web::ip_filter::IpFilter;
+use crate::web::maintenance::MaintenanceMode;
+use crate::web::middleware::client_ip::ProxyTrust;
+use crate::web::middleware::connection_limit::{ConnectionLimiter, SharedConnectionLimiter};
+use crate::web::middleware::rate_limit::{RateLimitQuotas, RateLimitState, SharedRateLimitState};
 use crate::web::schedule_cache::ScheduleCache;
 use crate::web::search_options_cache::SearchOptionsCache;
 use crate::web::sitemap_cache::SitemapCache;
+use crate::web::status_cache::StatusCache;
 use crate::web::stream::computed::ComputedStreamManager;
+use crate::web::suggest_index::SuggestIndex;
+use crate::web::ws::HeartbeatPolicy;
 use axum::extract::FromRef;
 use dashmap::DashMap;
 use serde::Serialize;
@@ -19,9 +36,10 @@ use std::sync::atomic::AtomicBool;
 use std::time::Instant;
 use tokio::sync::{Notify, RwLock};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 /// Health status of a service.
-#[derive(Debug, Clone, Serialize, PartialEq, TS)]
+#[derive(Debug, Clone, Serialize, PartialEq, TS, ToSchema)]
 #[serde(rename_all = "lowercase")]
 #[ts(export)]
 pub enum ServiceStatus {
@@ -142,8 +160,12 @@ pub struct AppState {
     pub service_statuses: ServiceStatusRegistry,
     pub reference_cache: Arc<RwLock<ReferenceCache>>,
     pub session_cache: SessionCache,
+    /// Resolves `X-Api-Key` headers for auth and per-key rate limiting.
+    pub api_key_cache: ApiKeyCache,
     pub oauth_state_store: OAuthStateStore,
     pub schedule_cache: ScheduleCache,
+    /// Prefix index answering `/api/suggest` without the database.
+    pub suggest_index: SuggestIndex,
     pub events: Arc<EventBuffer>,
     pub search_options_cache: SearchOptionsCache,
     pub computed_streams: ComputedStreamManager,
@@ -155,12 +177,44 @@ pub struct AppState {
     pub bluebook_sync_notify: Arc<Notify>,
     /// When set to true before notifying, the next BlueBook sync will skip interval checks.
     pub bluebook_force_flag: Arc<AtomicBool>,
+    /// Admin requests to run external-source syncs ahead of schedule.
+    pub sync_requests: Arc<SyncRequests>,
     /// Public origin for absolute URLs in sitemaps (e.g. "https://banner.xevion.dev").
     pub public_origin: Option<String>,
     /// In-memory cache for pre-rendered sitemap XML.
     pub sitemap_cache: SitemapCache,
+    /// In-memory cache for pre-rendered Atom feeds.
+    pub feed_cache: FeedCache,
+    /// Briefly cached results behind the public status endpoints.
+    pub status_cache: StatusCache,
+    /// Serialized public instructor profiles, invalidated by instructor events.
+    pub instructor_cache: InstructorProfileCache,
     /// Shared rate limiting state for inbound HTTP requests.
     pub rate_limit: SharedRateLimitState,
+    /// Admin-editable robots.txt rules and user-agent blocklist.
+    pub crawler: Arc<CrawlerPolicy>,
+    /// Admin switch that makes the public API read-only.
+    pub maintenance: Arc<MaintenanceMode>,
+    /// Admin-started request/response captures for debugging one route.
+    pub debug_captures: Arc<DebugCaptures>,
+    /// Counters for the Prometheus exporter.
+    pub metrics: Arc<Metrics>,
+    /// Caps concurrently open stream connections per IP and overall.
+    pub stream_connections: SharedConnectionLimiter,
+    /// Ping interval, idle timeout, and lifetime cap for stream connections.
+    pub stream_heartbeat: HeartbeatPolicy,
+    /// Which upstream proxies may set client IP forwarding headers.
+    pub proxy_trust: Arc<ProxyTrust>,
+    /// Trusted/blocked ranges and active admin bans.
+    pub ip_filter: Arc<IpFilter>,
+    /// Relevance weights for ranked course search.
+    pub search_weights: SearchWeights,
+    /// Operator licensing details for `/api/meta/attribution`.
+    pub attribution: Arc<AttributionConfig>,
+    /// Days of scrape job results kept before compaction into daily stats.
+    pub scrape_history_retention_days: u32,
+    /// Time decay for admin-triggered instructor score recomputes.
+    pub scoring_decay: ScoringDecay,
 }
 
 impl AppState {
@@ -169,6 +223,7 @@ impl AppState {
         self.rate_limit.internal_token()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         banner_api: Arc<BannerApi>,
         db_pool: PgPool,
@@ -176,12 +231,24 @@ impl AppState {
         bluebook_sync_notify: Arc<Notify>,
         bluebook_force_flag: Arc<AtomicBool>,
         public_origin: Option<String>,
+        stream_connections: ConnectionLimiter,
+        stream_heartbeat: HeartbeatPolicy,
+        proxy_trust: ProxyTrust,
+        ip_filter: IpFilter,
+        search_weights: SearchWeights,
+        attribution: AttributionConfig,
+        scrape_history_retention_days: u32,
+        scoring_decay: ScoringDecay,
+        rate_limit_quotas: &RateLimitQuotas,
+        metrics: Metrics,
     ) -> Self {
         let events = Arc::new(EventBuffer::new(1024));
         let schedule_cache = ScheduleCache::new(db_pool.clone());
+        let suggest_index = SuggestIndex::new(db_pool.clone());
         let reference_cache = Arc::new(RwLock::new(ReferenceCache::new()));
         let computed_streams =
             ComputedStreamManager::new(events.clone(), db_pool.clone(), reference_cache.clone());
+        let instructor_cache = InstructorProfileCache::new(events.clone());
         let ssr_client = reqwest::Client::builder()
             .redirect(reqwest::redirect::Policy::none())
             // Bound a down/hung localhost SSR; well under the outer 60s layer.
@@ -192,16 +259,18 @@ impl AppState {
 
         // Generate a random internal token for SSR -> API bypass.
         let internal_token = ulid::Ulid::new().to_string();
-        let rate_limit = Arc::new(RateLimitState::new(internal_token));
+        let rate_limit = Arc::new(RateLimitState::new(internal_token, rate_limit_quotas));
 
         Self {
             session_cache: SessionCache::new(db_pool.clone()),
+            api_key_cache: ApiKeyCache::new(db_pool.clone()),
             oauth_state_store: OAuthStateStore::new(),
             banner_api,
             db_pool,
             service_statuses: ServiceStatusRegistry::new(),
             reference_cache,
             schedule_cache,
+            suggest_index,
             events,
             search_options_cache: SearchOptionsCache::new(),
             computed_streams,
@@ -209,9 +278,25 @@ impl AppState {
             ssr_downstream,
             bluebook_sync_notify,
             bluebook_force_flag,
+            sync_requests: Arc::new(SyncRequests::new()),
             public_origin,
             sitemap_cache: SitemapCache::new(),
+            feed_cache: FeedCache::new(),
+            status_cache: StatusCache::new(),
+            instructor_cache,
             rate_limit,
+            crawler: Arc::new(CrawlerPolicy::new()),
+            maintenance: Arc::new(MaintenanceMode::new()),
+            debug_captures: Arc::new(DebugCaptures::new()),
+            metrics: Arc::new(metrics),
+            stream_connections: Arc::new(stream_connections),
+            stream_heartbeat,
+            proxy_trust: Arc::new(proxy_trust),
+            ip_filter: Arc::new(ip_filter),
+            search_weights,
+            attribution: Arc::new(attribution),
+            scrape_history_retention_days,
+            scoring_decay,
         }
     }
 }
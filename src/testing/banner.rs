@@ -0,0 +1,143 @@
+//! Mock Banner SSB server.
+
+use anyhow::Result;
+use wiremock::matchers::{method, path, path_regex, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::fixtures;
+use crate::banner::BannerApi;
+use crate::config::RateLimitingConfig;
+
+/// Path prefix of the SSB application, matching the production base URL.
+const SSB_PATH: &str = "/StudentRegistrationSsb/ssb";
+
+/// Banner SSB with the session handshake, reference lists and a CS search
+/// mounted. Searches for any other subject return no sections.
+pub struct MockBanner {
+    server: MockServer,
+}
+
+impl MockBanner {
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        let mock = Self { server };
+        mock.mount_session().await;
+        mock.mount_reference().await;
+        mock.mount_search("CS", fixtures::BANNER_SEARCH_CS).await;
+        Mock::given(method("GET"))
+            .and(path(format!("{SSB_PATH}/searchResults/searchResults")))
+            .respond_with(json(
+                ResponseTemplate::new(200),
+                fixtures::BANNER_SEARCH_EMPTY,
+            ))
+            .with_priority(u8::MAX)
+            .mount(&mock.server)
+            .await;
+        mock
+    }
+
+    /// Base URL to configure [`BannerApi`] with.
+    pub fn base_url(&self) -> String {
+        format!("{}{SSB_PATH}", self.server.uri())
+    }
+
+    /// A [`BannerApi`] pointed at this server, with rate limits loose enough
+    /// not to slow tests down.
+    pub fn api(&self) -> Result<BannerApi> {
+        BannerApi::new_with_config(
+            self.base_url(),
+            RateLimitingConfig {
+                session_rpm: 6000,
+                search_rpm: 6000,
+                metadata_rpm: 6000,
+                reset_rpm: 6000,
+                burst_allowance: 100,
+            },
+        )
+    }
+
+    /// Serve `body` for searches of `subject`, ahead of the empty fallback.
+    pub async fn mount_search(&self, subject: &str, body: &str) {
+        Mock::given(method("GET"))
+            .and(path(format!("{SSB_PATH}/searchResults/searchResults")))
+            .and(query_param("txt_subject", subject))
+            .respond_with(json(ResponseTemplate::new(200), body))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// The underlying server, for mounting extra mocks or inspecting requests.
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// Cookie handshake, term selection and its redirect, and form resets.
+    async fn mount_session(&self) {
+        Mock::given(method("GET"))
+            .and(path(format!("{SSB_PATH}/registration")))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .append_header(
+                        "Set-Cookie",
+                        "JSESSIONID=8F3C2A7B1D; Path=/StudentRegistrationSsb; HttpOnly",
+                    )
+                    .append_header("Set-Cookie", "SSB_COOKIE=!mock-ssb-cookie; Path=/"),
+            )
+            .mount(&self.server)
+            .await;
+
+        for page in ["/selfServiceMenu/data", "/term/termSelection"] {
+            Mock::given(method("GET"))
+                .and(path(format!("{SSB_PATH}{page}")))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&self.server)
+                .await;
+        }
+
+        Mock::given(method("POST"))
+            .and(path(format!("{SSB_PATH}/term/search")))
+            .respond_with(json(
+                ResponseTemplate::new(200),
+                r#"{"fwdURL":"/StudentRegistrationSsb/ssb/classSearch/classSearch"}"#,
+            ))
+            .mount(&self.server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(format!("{SSB_PATH}/classSearch/classSearch")))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&self.server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path(format!("{SSB_PATH}/classSearch/resetDataForm")))
+            .respond_with(json(ResponseTemplate::new(200), "true"))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Terms and subjects from fixtures; the other reference lists are empty.
+    async fn mount_reference(&self) {
+        for (endpoint, body) in [
+            ("getTerms", fixtures::BANNER_TERMS),
+            ("get_subject", fixtures::BANNER_SUBJECTS),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("{SSB_PATH}/classSearch/{endpoint}")))
+                .respond_with(json(ResponseTemplate::new(200), body))
+                .mount(&self.server)
+                .await;
+        }
+
+        Mock::given(method("GET"))
+            .and(path_regex(format!("^{SSB_PATH}/classSearch/get_")))
+            .respond_with(json(ResponseTemplate::new(200), "[]"))
+            .with_priority(u8::MAX)
+            .mount(&self.server)
+            .await;
+    }
+}
+
+fn json(template: ResponseTemplate, body: &str) -> ResponseTemplate {
+    template.set_body_raw(body.as_bytes().to_vec(), "application/json")
+}
@@ -0,0 +1,67 @@
+//! Mock BlueBook WebForms page.
+//!
+//! Every BlueBook request hits the same URL, so postbacks are told apart by
+//! their form fields: the search button's `__EVENTTARGET` plus the selected
+//! subject index, or the term filter value.
+
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::fixtures;
+
+const PAGE_PATH: &str = "/Default.aspx";
+
+/// BlueBook with CS evaluations on a single PAST page and no courses for MAT.
+pub struct MockBlueBook {
+    server: MockServer,
+}
+
+impl MockBlueBook {
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(PAGE_PATH))
+            .respond_with(html(fixtures::BLUEBOOK_LANDING))
+            .mount(&server)
+            .await;
+
+        // Subject search: the ComboBox index is 0 for CS and 1 for MAT.
+        for (index, page) in [
+            ("0", fixtures::BLUEBOOK_SEARCH_RESULTS),
+            ("1", fixtures::BLUEBOOK_SEARCH_EMPTY),
+        ] {
+            Mock::given(method("POST"))
+                .and(path(PAGE_PATH))
+                .and(body_string_contains("SearchBtn"))
+                .and(body_string_contains(format!(
+                    "CourseSubjectCombo%24HiddenField={index}"
+                )))
+                .respond_with(html(page))
+                .mount(&server)
+                .await;
+        }
+
+        Mock::given(method("POST"))
+            .and(path(PAGE_PATH))
+            .and(body_string_contains("CourseTermSelectRBL=PAST"))
+            .respond_with(html(fixtures::BLUEBOOK_PAST_CS))
+            .mount(&server)
+            .await;
+
+        Self { server }
+    }
+
+    /// Page URL to point the BlueBook client at.
+    pub fn url(&self) -> String {
+        format!("{}{PAGE_PATH}", self.server.uri())
+    }
+
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+}
+
+fn html(body: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(body.as_bytes().to_vec(), "text/html; charset=utf-8")
+}
@@ -0,0 +1,39 @@
+//! Mock upstreams for end-to-end tests, behind the `testing` feature.
+//!
+//! Each mock is a [`wiremock::MockServer`] preloaded with recorded payloads
+//! from `src/testing/fixtures`, shaped closely enough to the real services
+//! that the production clients run against them unchanged. The helpers in
+//! [`pipeline`] drive the same code paths the scheduler and workers use, so
+//! integration tests can exercise parsing and upserts without network access.
+
+pub mod banner;
+pub mod bluebook;
+pub mod pipeline;
+pub mod rmp;
+
+pub use banner::MockBanner;
+pub use bluebook::MockBlueBook;
+pub use rmp::MockRmp;
+
+/// Recorded upstream payloads served by the mocks.
+pub mod fixtures {
+    /// `classSearch/getTerms`: three terms, the oldest archived.
+    pub const BANNER_TERMS: &str = include_str!("fixtures/banner/terms.json");
+    /// `classSearch/get_subject`: CS and MAT.
+    pub const BANNER_SUBJECTS: &str = include_str!("fixtures/banner/subjects.json");
+    /// `searchResults/searchResults` for CS in 202620: three sections, two instructors.
+    pub const BANNER_SEARCH_CS: &str = include_str!("fixtures/banner/search_cs.json");
+    /// `searchResults/searchResults` with no matches.
+    pub const BANNER_SEARCH_EMPTY: &str = include_str!("fixtures/banner/search_empty.json");
+    /// RMP `newSearch.teachers`: the two CS instructors, on one page.
+    pub const RMP_TEACHERS: &str = include_str!("fixtures/rmp/teachers.json");
+    /// BlueBook landing page with the subject ComboBox.
+    pub const BLUEBOOK_LANDING: &str = include_str!("fixtures/bluebook/landing.html");
+    /// BlueBook search results for CS, before switching the term filter.
+    pub const BLUEBOOK_SEARCH_RESULTS: &str = include_str!("fixtures/bluebook/search_results.html");
+    /// BlueBook search with no matching courses (MAT); no term filter is rendered.
+    pub const BLUEBOOK_SEARCH_EMPTY: &str = include_str!("fixtures/bluebook/search_empty.html");
+    /// BlueBook CS evaluations under the PAST filter: three rated sections and
+    /// one with no evaluation data.
+    pub const BLUEBOOK_PAST_CS: &str = include_str!("fixtures/bluebook/past_cs.html");
+}
@@ -0,0 +1,50 @@
+//! Run scraper pipeline stages against the mocks.
+//!
+//! Each helper calls the same code the scheduler or a scrape worker would,
+//! only with the client pointed at a mock.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use sqlx::PgPool;
+
+use super::{MockBlueBook, MockRmp};
+use crate::banner::BannerApi;
+use crate::bluebook::BlueBookClient;
+use crate::data::DbContext;
+use crate::data::models::UpsertCounts;
+use crate::data::terms::{self, SyncResult};
+use crate::scraper::jobs::Job;
+use crate::scraper::jobs::subject::SubjectJob;
+use crate::scraper::scheduler::Scheduler;
+
+/// Fetch terms from Banner and sync them into `terms`, as the term sync does.
+pub async fn sync_terms(api: &BannerApi, pool: &PgPool) -> Result<SyncResult> {
+    let banner_terms = api.get_terms("", 1, 500).await?;
+    terms::sync_terms_from_banner(pool, banner_terms).await
+}
+
+/// Process a subject scrape job for `subject` in `term`.
+pub async fn scrape_subject(
+    api: &BannerApi,
+    db: &DbContext,
+    subject: &str,
+    term: &str,
+) -> Result<UpsertCounts> {
+    SubjectJob::new(subject.to_owned(), term.to_owned())
+        .process(api, db)
+        .await
+}
+
+/// Run the RMP professor sync, including auto-matching against instructors.
+pub async fn sync_rmp(db: &DbContext, mock: &MockRmp) -> Result<()> {
+    Scheduler::sync_rmp_data_with(db, &mock.client()).await
+}
+
+/// Scrape every BlueBook subject, ignoring per-subject intervals. Returns the
+/// number of evaluations upserted.
+pub async fn scrape_bluebook(pool: &PgPool, mock: &MockBlueBook) -> Result<u32> {
+    BlueBookClient::with_base_url(mock.url(), Duration::ZERO)
+        .scrape_all(pool, true)
+        .await
+}
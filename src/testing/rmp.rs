@@ -0,0 +1,44 @@
+//! Mock RateMyProfessors GraphQL endpoint.
+
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::fixtures;
+use crate::rmp::RmpClient;
+
+/// RMP GraphQL serving the teacher search from fixtures.
+///
+/// Per-teacher queries (details, reviews) aren't mounted; mount them on
+/// [`Self::server`] when a test needs them.
+pub struct MockRmp {
+    server: MockServer,
+}
+
+impl MockRmp {
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_string_contains("newSearch"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                fixtures::RMP_TEACHERS.as_bytes().to_vec(),
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+        Self { server }
+    }
+
+    pub fn endpoint(&self) -> String {
+        format!("{}/graphql", self.server.uri())
+    }
+
+    /// An [`RmpClient`] pointed at this server.
+    pub fn client(&self) -> RmpClient {
+        RmpClient::with_endpoint(self.endpoint())
+    }
+
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+}
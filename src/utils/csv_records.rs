@@ -0,0 +1,62 @@
+//! Reading uploaded CSV files, shared by the grade distribution importer and
+//! the admin RMP decision import.
+
+/// One CSV record and the line it starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRecord {
+    /// 1-based line of the record's first field. A quoted field spanning
+    /// lines makes later records start further down than their index.
+    pub line: u64,
+    pub fields: Vec<String>,
+}
+
+/// Read CSV text into records. Quoted fields may hold delimiters, doubled
+/// quotes, and line breaks; records may differ in length. A leading byte
+/// order mark is ignored, and blank lines and rows of empty fields are
+/// skipped.
+pub fn read_records(text: &str) -> Result<Vec<CsvRecord>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.trim_start_matches('\u{feff}').as_bytes());
+
+    let mut records = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        if record.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+        records.push(CsvRecord {
+            line: record.position().map_or(0, |p| p.line()),
+            fields: record.iter().map(str::to_owned).collect(),
+        });
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quoted_csv() {
+        let text = "\u{feff}a,b\r\n\"Smith, J\",\"say \"\"hi\"\"\"\n\n\"multi\nline\",\n";
+        let records = read_records(text).unwrap();
+        let fields: Vec<&[String]> = records.iter().map(|r| r.fields.as_slice()).collect();
+        assert_eq!(
+            fields,
+            [
+                &["a", "b"][..],
+                &["Smith, J", "say \"hi\""][..],
+                &["multi\nline", ""][..],
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_account_for_multiline_fields_and_blanks() {
+        let text = "h1,h2\n\"two\nlines\",x\n\n,\nlast,row\n";
+        let lines: Vec<u64> = read_records(text).unwrap().iter().map(|r| r.line).collect();
+        assert_eq!(lines, [1, 2, 6]);
+    }
+}
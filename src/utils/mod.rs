@@ -1,3 +1,6 @@
+pub mod csv_records;
+pub mod webhook_url;
+
 use std::time::{Duration, Instant};
 
 /// Format a `Duration` as a human-readable string with automatic unit scaling.
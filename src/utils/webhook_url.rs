@@ -0,0 +1,155 @@
+//! Checks for user-supplied webhook URLs, shared by registration (watches and
+//! admin webhooks) and delivery, which re-checks stored URLs before sending.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Longest webhook URL accepted.
+pub const MAX_WEBHOOK_URL_LEN: usize = 500;
+
+/// Why a webhook URL was refused. The messages are shown to users.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WebhookUrlError {
+    #[error("Webhook URL must be at most {MAX_WEBHOOK_URL_LEN} characters")]
+    TooLong,
+    #[error("Invalid webhook URL")]
+    Invalid,
+    #[error("Webhook URL must use https")]
+    NotHttps,
+    #[error("Webhook URL must be publicly reachable")]
+    NotPublic,
+}
+
+/// Check a user-supplied webhook URL, returning it normalized.
+///
+/// Only public HTTPS endpoints are accepted so a watch or webhook can't be
+/// pointed at services on the server's own network.
+pub fn validate_webhook_url(raw: &str) -> Result<String, WebhookUrlError> {
+    let raw = raw.trim();
+    if raw.len() > MAX_WEBHOOK_URL_LEN {
+        return Err(WebhookUrlError::TooLong);
+    }
+    let url = url::Url::parse(raw).map_err(|_| WebhookUrlError::Invalid)?;
+    if url.scheme() != "https" {
+        return Err(WebhookUrlError::NotHttps);
+    }
+    let private = match url.host() {
+        Some(url::Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost") || domain.ends_with(".internal")
+        }
+        Some(url::Host::Ipv4(ip)) => !is_public_ip(IpAddr::V4(ip)),
+        Some(url::Host::Ipv6(ip)) => !is_public_ip(IpAddr::V6(ip)),
+        None => true,
+    };
+    if private {
+        return Err(WebhookUrlError::NotPublic);
+    }
+    Ok(url.into())
+}
+
+/// Whether `ip` is routable on the public internet: not private, loopback,
+/// link-local, CGNAT, multicast, reserved, or in 0.0.0.0/8. IPv6 addresses that embed an IPv4
+/// address (mapped, NAT64, 6to4) are judged by the embedded address.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_multicast()
+                // 0.0.0.0/8 ("this network"; 0.0.0.0 reaches localhost)
+                || v4.octets()[0] == 0
+                // 240.0.0.0/4 (reserved, including broadcast)
+                || v4.octets()[0] >= 240
+                // 100.64.0.0/10 (CGNAT)
+                || (v4.octets()[0] == 100 && (v4.octets()[1] & 0xc0) == 64))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let bits = v6.to_bits();
+            // 64:ff9b::/96 (NAT64) carries the IPv4 address in the low 32 bits
+            if bits >> 32 == 0x0064_ff9b << 64 {
+                return is_public_ip(IpAddr::V4(Ipv4Addr::from_bits(bits as u32)));
+            }
+            // 2002::/16 (6to4) carries it in bits 16..48
+            if bits >> 112 == 0x2002 {
+                return is_public_ip(IpAddr::V4(Ipv4Addr::from_bits((bits >> 80) as u32)));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                // fc00::/7 (unique local) and fe80::/10 (link local)
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_public_https_webhooks() {
+        assert_eq!(
+            validate_webhook_url(" https://hooks.example.com/abc ").unwrap(),
+            "https://hooks.example.com/abc"
+        );
+        assert!(validate_webhook_url("https://203.0.113.7/hook").is_ok());
+    }
+
+    #[test]
+    fn rejects_private_or_insecure_webhooks() {
+        for url in [
+            "http://hooks.example.com/abc",
+            "https://localhost/hook",
+            "https://127.0.0.1/hook",
+            "https://10.1.2.3/hook",
+            "https://169.254.169.254/latest",
+            "https://100.100.0.1/hook",
+            "https://[::1]/hook",
+            "https://[fd00::1]/hook",
+            "https://[::ffff:192.168.0.1]/hook",
+            "https://0.0.0.0/hook",
+            "https://224.0.0.251/hook",
+            "https://240.0.0.1/hook",
+            "https://255.255.255.255/hook",
+            "https://[ff02::1]/hook",
+            "https://[64:ff9b::7f00:1]/hook",
+            "https://[2002:a9fe:a9fe::1]/hook",
+            "https://postgres.railway.internal/",
+            "not a url",
+        ] {
+            assert!(validate_webhook_url(url).is_err(), "{url}");
+        }
+    }
+
+    #[test]
+    fn judges_embedded_ipv4_by_the_ipv4_rules() {
+        for (ip, public) in [
+            ("0.0.0.0", false),
+            ("0.1.2.3", false),
+            ("224.0.0.1", false),
+            ("239.255.255.250", false),
+            ("240.0.0.1", false),
+            ("255.255.255.255", false),
+            ("ff0e::1", false),
+            ("203.0.113.7", true),
+            ("64:ff9b::10.0.0.1", false),
+            ("64:ff9b::127.0.0.1", false),
+            ("64:ff9b::0.0.0.0", false),
+            ("64:ff9b::224.0.0.1", false),
+            ("64:ff9b::203.0.113.7", true),
+            ("2002:0a00:0001::1", false),
+            ("2002:a9fe:a9fe::1", false),
+            ("2002:6440:0001::", false),
+            ("2002:f000:0001::1", false),
+            ("2002:cb00:7107::1", true),
+        ] {
+            assert_eq!(is_public_ip(ip.parse().unwrap()), public, "{ip}");
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Admin API handler for the admin action trail.
+//!
+//! Entries are written by the [`admin_audit`] middleware.
+//!
+//! [`admin_audit`]: crate::web::middleware::admin_audit
+
+use axum::extract::{Query, State};
+use axum::response::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{instrument, trace};
+use ts_rs::TS;
+
+use crate::data::admin_actions::{self, AdminActionFilter};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+
+pub use crate::data::admin_actions::ListAdminActionsResponse;
+
+/// Query params for `GET /api/admin/actions`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListAdminActionsParams {
+    /// Discord ID of the acting admin.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Request path prefix, e.g. `/api/admin/users`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Only successful (`true`) or only failed (`false`) calls.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<i32>,
+}
+
+/// `GET /api/admin/actions` -- List recorded admin calls, newest first.
+#[instrument(skip_all)]
+pub async fn list_admin_actions(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<ListAdminActionsParams>,
+) -> Result<Json<ListAdminActionsResponse>, ApiError> {
+    let admin_id = params
+        .admin_id
+        .as_deref()
+        .map(str::parse::<i64>)
+        .transpose()
+        .map_err(|_| ApiError::bad_request("adminId must be a Discord ID"))?;
+
+    let filter = AdminActionFilter {
+        admin_id,
+        method: params.method.as_deref(),
+        path_prefix: params.path.as_deref(),
+        success: params.success,
+        since: params.since,
+        until: params.until,
+    };
+    let response = admin_actions::list_actions(
+        &state.db_pool,
+        &filter,
+        params.page.unwrap_or(1),
+        params.per_page.unwrap_or(50),
+    )
+    .await
+    .map_err(|e| db_error("list admin actions", e))?;
+
+    trace!(count = response.items.len(), "Listed admin actions");
+
+    Ok(Json(response))
+}
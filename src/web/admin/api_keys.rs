@@ -0,0 +1,146 @@
+//! Admin API handlers for issuing and revoking per-user API keys.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::data::api_keys::{self, ApiKey};
+use crate::data::models::deserialize_i64_from_string;
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+
+const MAX_NAME_LEN: usize = 100;
+
+/// Response for `GET /api/admin/api-keys`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ApiKeysResponse {
+    pub keys: Vec<ApiKey>,
+}
+
+/// Body for `POST /api/admin/api-keys`.
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CreateApiKeyBody {
+    /// Discord ID of the user the key acts for.
+    #[serde(deserialize_with = "deserialize_i64_from_string")]
+    #[ts(type = "string")]
+    pub user_id: i64,
+    pub name: String,
+}
+
+/// Body for `PUT /api/admin/api-keys/{id}`.
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RenameApiKeyBody {
+    pub name: String,
+}
+
+/// Response for `POST /api/admin/api-keys`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CreatedApiKey {
+    pub key: ApiKey,
+    /// The raw key. Shown only in this response.
+    pub secret: String,
+}
+
+fn validate_name(name: &str) -> Result<&str, ApiError> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(ApiError::bad_request(format!(
+            "Key name must be 1-{MAX_NAME_LEN} characters"
+        )));
+    }
+    Ok(name)
+}
+
+/// `GET /api/admin/api-keys` -- List issued keys, including revoked ones.
+#[instrument(skip_all)]
+pub async fn list_api_keys(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<ApiKeysResponse>, ApiError> {
+    let keys = api_keys::list_keys(&state.db_pool)
+        .await
+        .map_err(|e| db_error("list API keys", e))?;
+
+    Ok(Json(ApiKeysResponse { keys }))
+}
+
+/// `POST /api/admin/api-keys` -- Issue a key for a user.
+#[instrument(skip_all)]
+pub async fn create_api_key(
+    AdminUser(admin): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<CreateApiKeyBody>,
+) -> Result<(StatusCode, Json<CreatedApiKey>), ApiError> {
+    let name = validate_name(&body.name)?;
+
+    crate::data::users::get_user(&state.db_pool, body.user_id)
+        .await
+        .map_err(|e| db_error("get user", e))?
+        .or_not_found("User", body.user_id)?;
+
+    let (key, secret) = api_keys::create_key(&state.db_pool, body.user_id, name)
+        .await
+        .map_err(|e| db_error("create API key", e))?;
+
+    info!(
+        key_id = key.id,
+        user_id = body.user_id,
+        admin = admin.id,
+        "API key issued"
+    );
+
+    Ok((StatusCode::CREATED, Json(CreatedApiKey { key, secret })))
+}
+
+/// `PUT /api/admin/api-keys/{id}` -- Rename a key.
+#[instrument(skip_all, fields(key_id = id))]
+pub async fn rename_api_key(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(body): Json<RenameApiKeyBody>,
+) -> Result<Json<ApiKey>, ApiError> {
+    let name = validate_name(&body.name)?;
+    let key = api_keys::rename_key(&state.db_pool, id, name)
+        .await
+        .map_err(|e| db_error("rename API key", e))?
+        .or_not_found("API key", id)?;
+
+    // Cached lookups carry the old name.
+    state.api_key_cache.invalidate_all();
+
+    Ok(Json(key))
+}
+
+/// `DELETE /api/admin/api-keys/{id}` -- Revoke a key.
+#[instrument(skip_all, fields(key_id = id))]
+pub async fn revoke_api_key(
+    AdminUser(admin): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    let revoked = api_keys::revoke_key(&state.db_pool, id)
+        .await
+        .map_err(|e| db_error("revoke API key", e))?;
+    if !revoked {
+        return Err(ApiError::not_found("API key not found or already revoked"));
+    }
+
+    state.api_key_cache.invalidate_all();
+
+    info!(key_id = id, admin = admin.id, "API key revoked");
+
+    Ok(StatusCode::NO_CONTENT)
+}
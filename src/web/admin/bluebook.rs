@@ -10,23 +10,26 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, instrument};
 use ts_rs::TS;
 
-use crate::data::admin_bluebook::{self, BluebookError, ListBluebookLinksFilter};
+use crate::data::admin_bluebook::{self, BluebookError, LinkDecision, ListBluebookLinksFilter};
+use crate::data::events::{DomainEvent, InstructorEvent};
+use crate::data::models::User;
 use crate::state::AppState;
 use crate::web::auth::extractors::AdminUser;
 use crate::web::error::{ApiError, db_error};
 
 pub use crate::data::admin_bluebook::{
-    BluebookLinkDetail, BluebookMatchResponse, ListBluebookLinksResponse,
+    BluebookBulkReviewResponse, BluebookLinkDetail, BluebookMatchResponse,
+    ListBluebookLinksResponse, ListBluebookUnmatchedResponse,
 };
 
-/// Check if an `anyhow::Error` chain contains a [`BluebookError`] variant that
-/// indicates a "not found" condition, and return the appropriate 404 response.
+/// Check if an `anyhow::Error` chain contains a [`BluebookError`] variant and
+/// return the matching 404 (or 400 for oversized batches).
 /// Falls back to a generic 500 via [`db_error`].
 fn bluebook_not_found_or_db(context: &str, e: anyhow::Error) -> ApiError {
-    if let Some(bb) = e.downcast_ref::<BluebookError>() {
-        ApiError::not_found(bb.to_string())
-    } else {
-        db_error(context, e)
+    match e.downcast_ref::<BluebookError>() {
+        Some(bb @ BluebookError::TooManyLinks { .. }) => ApiError::bad_request(bb.to_string()),
+        Some(bb) => ApiError::not_found(bb.to_string()),
+        None => db_error(context, e),
     }
 }
 
@@ -53,6 +56,19 @@ pub struct ListBluebookLinksParams {
     pub per_page: Option<i32>,
 }
 
+/// Query params for `GET /api/admin/bluebook/links/unmatched`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListUnmatchedLinksParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<i32>,
+}
+
 /// Body for `POST /api/admin/bluebook/links/{id}/assign`.
 #[derive(Deserialize, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -61,6 +77,14 @@ pub struct AssignBody {
     pub instructor_id: i32,
 }
 
+/// Body for `POST /api/admin/bluebook/links/bulk-approve` and `bulk-reject`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BulkLinkIdsBody {
+    pub ids: Vec<i32>,
+}
+
 /// Simple acknowledgement response for mutating operations.
 #[derive(Debug, Clone, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -107,6 +131,26 @@ pub async fn list_links(
     Ok(Json(response))
 }
 
+/// `GET /api/admin/bluebook/links/unmatched` -- Pending links auto-matching
+/// couldn't pair with an instructor, with suggested instructors.
+#[instrument(skip_all)]
+pub async fn list_unmatched_links(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<ListUnmatchedLinksParams>,
+) -> Result<Json<ListBluebookUnmatchedResponse>, ApiError> {
+    let response = admin_bluebook::list_unmatched_links(
+        &state.db_pool,
+        params.search.as_deref(),
+        params.page.unwrap_or(1),
+        params.per_page.unwrap_or(50),
+    )
+    .await
+    .map_err(|e| db_error("list unmatched bluebook links", e))?;
+
+    Ok(Json(response))
+}
+
 /// `GET /api/admin/bluebook/links/{id}` -- Detail for a specific BlueBook link.
 #[instrument(skip_all, fields(link_id = id))]
 pub async fn get_link(
@@ -124,15 +168,21 @@ pub async fn get_link(
 /// `POST /api/admin/bluebook/links/{id}/approve` -- Approve a pending link.
 #[instrument(skip_all, fields(link_id = id))]
 pub async fn approve_link(
-    AdminUser(_user): AdminUser,
+    AdminUser(user): AdminUser,
     State(state): State<AppState>,
     Path(id): Path<i32>,
 ) -> Result<Json<BluebookOkResponse>, ApiError> {
-    admin_bluebook::approve_link(&state.db_pool, id)
+    admin_bluebook::approve_link(&state.db_pool, id, user.id)
         .await
         .map_err(|e| bluebook_not_found_or_db("approve bluebook link", e))?;
 
-    info!(link_id = id, "BlueBook link approved");
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+            instructor_id: None,
+        }));
+
+    info!(link_id = id, admin = %user.discord_username, "BlueBook link approved");
 
     Ok(Json(BluebookOkResponse { ok: true }))
 }
@@ -140,34 +190,98 @@ pub async fn approve_link(
 /// `POST /api/admin/bluebook/links/{id}/reject` -- Reject a pending link.
 #[instrument(skip_all, fields(link_id = id))]
 pub async fn reject_link(
-    AdminUser(_user): AdminUser,
+    AdminUser(user): AdminUser,
     State(state): State<AppState>,
     Path(id): Path<i32>,
 ) -> Result<Json<BluebookOkResponse>, ApiError> {
-    admin_bluebook::reject_link(&state.db_pool, id)
+    admin_bluebook::reject_link(&state.db_pool, id, user.id)
         .await
         .map_err(|e| bluebook_not_found_or_db("reject bluebook link", e))?;
 
-    info!(link_id = id, "BlueBook link rejected");
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+            instructor_id: None,
+        }));
+
+    info!(link_id = id, admin = %user.discord_username, "BlueBook link rejected");
 
     Ok(Json(BluebookOkResponse { ok: true }))
 }
 
+/// Shared body of the bulk approve/reject handlers.
+async fn bulk_review(
+    state: &AppState,
+    admin: &User,
+    ids: &[i32],
+    decision: LinkDecision,
+) -> Result<Json<BluebookBulkReviewResponse>, ApiError> {
+    let response = admin_bluebook::bulk_review_links(&state.db_pool, ids, decision, admin.id)
+        .await
+        .map_err(|e| bluebook_not_found_or_db("bulk review bluebook links", e))?;
+
+    if !response.updated.is_empty() {
+        state
+            .events
+            .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+                instructor_id: None,
+            }));
+    }
+
+    info!(
+        ?decision,
+        updated = response.updated.len(),
+        skipped = response.skipped.len(),
+        admin = %admin.discord_username,
+        "BlueBook links bulk reviewed"
+    );
+
+    Ok(Json(response))
+}
+
+/// `POST /api/admin/bluebook/links/bulk-approve` -- Approve many links with a
+/// proposed instructor.
+#[instrument(skip_all)]
+pub async fn bulk_approve_links(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<BulkLinkIdsBody>,
+) -> Result<Json<BluebookBulkReviewResponse>, ApiError> {
+    bulk_review(&state, &user, &body.ids, LinkDecision::Approve).await
+}
+
+/// `POST /api/admin/bluebook/links/bulk-reject` -- Reject many links.
+#[instrument(skip_all)]
+pub async fn bulk_reject_links(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<BulkLinkIdsBody>,
+) -> Result<Json<BluebookBulkReviewResponse>, ApiError> {
+    bulk_review(&state, &user, &body.ids, LinkDecision::Reject).await
+}
+
 /// `POST /api/admin/bluebook/links/{id}/assign` -- Manually assign an instructor to a link.
 #[instrument(skip_all, fields(link_id = id))]
 pub async fn assign_link(
-    AdminUser(_user): AdminUser,
+    AdminUser(user): AdminUser,
     State(state): State<AppState>,
     Path(id): Path<i32>,
     Json(body): Json<AssignBody>,
 ) -> Result<Json<BluebookOkResponse>, ApiError> {
-    admin_bluebook::assign_link(&state.db_pool, id, body.instructor_id)
+    admin_bluebook::assign_link(&state.db_pool, id, body.instructor_id, user.id)
         .await
         .map_err(|e| bluebook_not_found_or_db("assign bluebook link", e))?;
 
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+            instructor_id: None,
+        }));
+
     info!(
         link_id = id,
         instructor_id = body.instructor_id,
+        admin = %user.discord_username,
         "BlueBook link manually assigned"
     );
 
@@ -184,6 +298,12 @@ pub async fn run_matching(
         .await
         .map_err(|e| db_error("bluebook auto-matching", e))?;
 
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+            instructor_id: None,
+        }));
+
     info!(
         total_names = response.total_names,
         auto_matched = response.auto_matched,
@@ -0,0 +1,60 @@
+//! Admin API handlers for the Discord bot.
+
+use axum::extract::State;
+use axum::response::Json;
+use serde::Serialize;
+use tracing::instrument;
+use ts_rs::TS;
+
+use crate::bot::registration::{self, CommandRegistration};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+
+/// A scope's registration record, compared against the running command set.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CommandRegistrationStatus {
+    #[serde(flatten)]
+    pub registration: CommandRegistration,
+    /// Whether this scope has the current command definitions.
+    pub in_sync: bool,
+}
+
+/// Response for `GET /api/admin/bot/commands`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BotCommandsResponse {
+    /// Hash of the command definitions in this build.
+    pub fingerprint: String,
+    pub command_count: usize,
+    pub registrations: Vec<CommandRegistrationStatus>,
+}
+
+/// `GET /api/admin/bot/commands` -- Slash command registration state per guild.
+#[instrument(skip_all)]
+pub async fn command_registrations(
+    _admin: AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<BotCommandsResponse>, ApiError> {
+    let commands = crate::bot::get_commands();
+    let fingerprint = registration::fingerprint_hash(&commands);
+
+    let registrations = registration::list_registrations(&state.db_pool)
+        .await
+        .map_err(|e| db_error("Failed to fetch command registrations", e))?
+        .into_iter()
+        .map(|registration| CommandRegistrationStatus {
+            in_sync: registration.fingerprint == fingerprint,
+            registration,
+        })
+        .collect();
+
+    Ok(Json(BotCommandsResponse {
+        fingerprint,
+        command_count: commands.len(),
+        registrations,
+    }))
+}
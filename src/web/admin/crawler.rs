@@ -0,0 +1,86 @@
+//! Admin API handlers for robots.txt rules and the crawler blocklist.
+
+use axum::extract::State;
+use axum::response::Json;
+use serde::Serialize;
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::crawler::CrawlerConfig;
+use crate::web::error::{ApiError, db_error};
+use crate::web::middleware::admin_audit::PriorState;
+
+/// Requests rejected for one blocklist pattern since startup.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct BlockedAgentCount {
+    pub pattern: String,
+    #[ts(type = "number")]
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CrawlerConfigResponse {
+    pub config: CrawlerConfig,
+    pub blocked: Vec<BlockedAgentCount>,
+    /// The robots.txt body as currently served.
+    pub robots_txt: String,
+}
+
+fn build_response(state: &AppState) -> CrawlerConfigResponse {
+    let config = state.crawler.config();
+    let robots_txt = config.render_robots(state.public_origin.as_deref());
+    let blocked = state
+        .crawler
+        .blocked_counts()
+        .into_iter()
+        .map(|(pattern, count)| BlockedAgentCount { pattern, count })
+        .collect();
+    CrawlerConfigResponse {
+        config,
+        blocked,
+        robots_txt,
+    }
+}
+
+/// `GET /api/admin/crawler` -- Current crawler policy and block counters.
+#[instrument(skip_all)]
+pub async fn get_crawler_config(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Json<CrawlerConfigResponse> {
+    Json(build_response(&state))
+}
+
+/// `PUT /api/admin/crawler` -- Replace the crawler policy.
+#[instrument(skip_all)]
+pub async fn update_crawler_config(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(config): Json<CrawlerConfig>,
+) -> Result<(PriorState, Json<CrawlerConfigResponse>), ApiError> {
+    config.validate().map_err(ApiError::bad_request)?;
+
+    let prior = PriorState::of(&build_response(&state));
+    let rules = config.rules.len();
+    let blocked = config.blocked_user_agents.len();
+    state
+        .crawler
+        .save(&state.db_pool, config)
+        .await
+        .map_err(|e| db_error("save crawler config", e))?;
+
+    info!(
+        rules,
+        blocked_agents = blocked,
+        admin = %user.discord_username,
+        "Crawler config updated"
+    );
+
+    Ok((prior, Json(build_response(&state))))
+}
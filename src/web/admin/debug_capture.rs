@@ -0,0 +1,121 @@
+//! Admin API handlers for starting and reading route debug captures.
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::debug_capture::{DebugCapture, is_capturable};
+use crate::web::error::ApiError;
+
+const DEFAULT_DURATION_SECS: u32 = 5 * 60;
+const MAX_DURATION_SECS: u32 = 30 * 60;
+const DEFAULT_MAX_ENTRIES: u32 = 50;
+const MAX_ENTRIES: u32 = 200;
+
+/// Response for `GET /api/admin/debug-captures`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DebugCapturesResponse {
+    pub captures: Vec<DebugCapture>,
+}
+
+/// Body for `POST /api/admin/debug-captures`.
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct StartCaptureBody {
+    /// Exact API path to record, without query string.
+    pub path: String,
+    /// Defaults to 5 minutes; at most 30.
+    #[serde(default)]
+    pub duration_secs: Option<u32>,
+    /// Defaults to 50; at most 200.
+    #[serde(default)]
+    pub max_entries: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StopCaptureParams {
+    pub path: String,
+}
+
+/// `GET /api/admin/debug-captures` -- Active and recently finished captures.
+#[instrument(skip_all)]
+pub async fn list_captures(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Json<DebugCapturesResponse> {
+    Json(DebugCapturesResponse {
+        captures: state.debug_captures.list(),
+    })
+}
+
+/// `POST /api/admin/debug-captures` -- Start recording a route.
+#[instrument(skip_all)]
+pub async fn start_capture(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<StartCaptureBody>,
+) -> Result<(StatusCode, Json<DebugCapture>), ApiError> {
+    let path = body.path.trim();
+    if !is_capturable(path) || path.contains('?') {
+        return Err(ApiError::bad_request(
+            "Path must be a public /api/ route without a query string",
+        ));
+    }
+    let duration_secs = body.duration_secs.unwrap_or(DEFAULT_DURATION_SECS);
+    if !(1..=MAX_DURATION_SECS).contains(&duration_secs) {
+        return Err(ApiError::bad_request(format!(
+            "durationSecs must be 1-{MAX_DURATION_SECS}"
+        )));
+    }
+    let max_entries = body.max_entries.unwrap_or(DEFAULT_MAX_ENTRIES);
+    if !(1..=MAX_ENTRIES).contains(&max_entries) {
+        return Err(ApiError::bad_request(format!(
+            "maxEntries must be 1-{MAX_ENTRIES}"
+        )));
+    }
+
+    let capture = state.debug_captures.start(
+        path.to_owned(),
+        chrono::Duration::seconds(duration_secs.into()),
+        max_entries,
+    );
+
+    info!(
+        path,
+        duration_secs,
+        max_entries,
+        admin = %user.discord_username,
+        "Debug capture started"
+    );
+
+    Ok((StatusCode::CREATED, Json(capture)))
+}
+
+/// `DELETE /api/admin/debug-captures?path=` -- Stop a capture and discard
+/// what it recorded.
+#[instrument(skip_all)]
+pub async fn stop_capture(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<StopCaptureParams>,
+) -> Result<StatusCode, ApiError> {
+    if !state.debug_captures.stop(&params.path) {
+        return Err(ApiError::not_found("No capture for this path"));
+    }
+
+    info!(
+        path = params.path,
+        admin = %user.discord_username,
+        "Debug capture stopped"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,208 @@
+//! Admin bulk exports of the audit log, courses, and users.
+//!
+//! Each export streams rows straight from the database into the response
+//! body (see [`crate::web::export`]), so a full-history download holds one
+//! chunk in memory rather than the whole table.
+
+use axum::extract::{Query, State};
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use serde::Deserialize;
+use tracing::{info, instrument};
+
+use crate::banner::models::terms::Term;
+use crate::data::models::{TimeFormat, User};
+use crate::state::AppState;
+use crate::web::audit::AuditLogEntry;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::ApiError;
+use crate::web::export::{ExportFormat, ExportRecord, export_response, write_course_pages};
+
+impl ExportRecord for AuditLogEntry {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "timestamp",
+        "course_id",
+        "term_code",
+        "crn",
+        "subject",
+        "course_number",
+        "course_title",
+        "field_changed",
+        "old_value",
+        "new_value",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        let opt = |v: &Option<String>| v.clone().unwrap_or_default();
+        vec![
+            self.id.to_string(),
+            self.timestamp.clone(),
+            self.course_id.to_string(),
+            opt(&self.term_code),
+            opt(&self.crn),
+            opt(&self.subject),
+            opt(&self.course_number),
+            opt(&self.course_title),
+            self.field_changed.clone(),
+            self.old_value
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            self.new_value.to_string(),
+        ]
+    }
+}
+
+impl ExportRecord for User {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "discord_id",
+        "discord_username",
+        "is_admin",
+        "timezone",
+        "time_format",
+        "created_at",
+        "updated_at",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        let time_format = match self.time_format {
+            TimeFormat::TwelveHour => "twelveHour",
+            TimeFormat::TwentyFourHour => "twentyFourHour",
+        };
+        vec![
+            self.id.to_string(),
+            self.discord_id.map(|id| id.to_string()).unwrap_or_default(),
+            self.discord_username.clone(),
+            self.is_admin.to_string(),
+            self.timezone.clone(),
+            time_format.to_owned(),
+            self.created_at.to_rfc3339(),
+            self.updated_at.to_rfc3339(),
+        ]
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditExportParams {
+    /// Inclusive lower bound on the change timestamp.
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on the change timestamp.
+    pub until: Option<DateTime<Utc>>,
+    pub term: Option<String>,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// `GET /api/admin/audit-log/export?since=&until=&term=&format=csv|jsonl`
+///
+/// The full audit history, oldest first, optionally narrowed by time and term.
+#[instrument(skip_all)]
+pub async fn export_audit_log(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<AuditExportParams>,
+) -> Result<Response, ApiError> {
+    let term = params
+        .term
+        .as_deref()
+        .map(|t| Term::resolve_to_code(t).ok_or_else(|| ApiError::invalid_term(t)))
+        .transpose()?;
+    let file_stem = match &term {
+        Some(code) => format!("audit-log-{code}"),
+        None => "audit-log".to_owned(),
+    };
+    let pool = state.db_pool.clone();
+
+    info!(admin = %user.discord_username, term = ?term, "Admin exporting audit log");
+
+    export_response(params.format, &file_stem, move |mut out| async move {
+        let mut rows =
+            crate::data::audit::stream_export(&pool, params.since, params.until, term.as_deref());
+        while let Some(row) = rows.try_next().await? {
+            out.write(&AuditLogEntry::from(row)).await?;
+        }
+        out.finish().await
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminCourseExportParams {
+    /// Limit to one term; every term when omitted.
+    pub term: Option<String>,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// `GET /api/admin/courses/export?term=&format=csv|jsonl`
+///
+/// Like the public course export, but the term is optional.
+#[instrument(skip_all)]
+pub async fn export_courses(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<AdminCourseExportParams>,
+) -> Result<Response, ApiError> {
+    let term = params
+        .term
+        .as_deref()
+        .map(|t| Term::resolve_to_code(t).ok_or_else(|| ApiError::invalid_term(t)))
+        .transpose()?;
+    let file_stem = match &term {
+        Some(code) => format!("courses-{code}"),
+        None => "courses-all".to_owned(),
+    };
+    let pool = state.db_pool.clone();
+
+    info!(admin = %user.discord_username, term = ?term, "Admin exporting courses");
+
+    export_response(params.format, &file_stem, move |mut out| async move {
+        write_course_pages(&pool, term.as_deref(), &mut out).await?;
+        out.finish().await
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserExportParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// `GET /api/admin/users/export?format=csv|jsonl`
+#[instrument(skip_all)]
+pub async fn export_users(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<UserExportParams>,
+) -> Result<Response, ApiError> {
+    let pool = state.db_pool.clone();
+
+    info!(admin = %user.discord_username, "Admin exporting users");
+
+    export_response(params.format, "users", move |mut out| async move {
+        let mut rows = crate::data::users::stream_users(&pool);
+        while let Some(row) = rows.try_next().await? {
+            out.write(&row).await?;
+        }
+        out.finish().await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audit_export_params_parse_bounds() {
+        let params: AuditExportParams = serde_json::from_str(
+            r#"{"since":"2026-01-01T00:00:00Z","term":"spring-2026","format":"jsonl"}"#,
+        )
+        .unwrap();
+        assert!(params.since.is_some());
+        assert!(params.until.is_none());
+        assert_eq!(params.term.as_deref(), Some("spring-2026"));
+        assert_eq!(params.format, ExportFormat::Jsonl);
+    }
+}
@@ -0,0 +1,94 @@
+//! Admin API handlers for the user feedback triage queue.
+
+use axum::extract::{Path, Query, State};
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::data::feedback::{self, FeedbackError, FeedbackStatus};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+
+pub use crate::data::feedback::{FeedbackItem, ListFeedbackResponse};
+
+/// Map [`FeedbackError`] variants to 404/409, falling back to [`db_error`].
+fn feedback_error(context: &str, e: anyhow::Error) -> ApiError {
+    match e.downcast_ref::<FeedbackError>() {
+        Some(FeedbackError::NotFound) => ApiError::not_found(e.to_string()),
+        Some(FeedbackError::InvalidTransition { .. }) => ApiError::conflict(e.to_string()),
+        None => db_error(context, e),
+    }
+}
+
+/// Query params for `GET /api/admin/feedback`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListFeedbackParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<FeedbackStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<i32>,
+}
+
+/// Body for `POST /api/admin/feedback/{id}/status`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UpdateFeedbackStatusBody {
+    pub status: FeedbackStatus,
+    /// Replaces the existing admin note when present.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// `GET /api/admin/feedback` -- List feedback reports with optional status filter.
+#[instrument(skip_all)]
+pub async fn list_feedback(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<ListFeedbackParams>,
+) -> Result<Json<ListFeedbackResponse>, ApiError> {
+    let response = feedback::list_feedback(
+        &state.db_pool,
+        params.status,
+        params.page.unwrap_or(1),
+        params.per_page.unwrap_or(50),
+    )
+    .await
+    .map_err(|e| db_error("list feedback", e))?;
+
+    Ok(Json(response))
+}
+
+/// `POST /api/admin/feedback/{id}/status` -- Move a report through the triage workflow.
+#[instrument(skip_all, fields(feedback_id = id))]
+pub async fn update_feedback_status(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(body): Json<UpdateFeedbackStatusBody>,
+) -> Result<Json<FeedbackItem>, ApiError> {
+    let note = body
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+
+    let item = feedback::update_status(&state.db_pool, id, body.status, note, user.id)
+        .await
+        .map_err(|e| feedback_error("update feedback status", e))?;
+
+    info!(
+        feedback_id = id,
+        status = body.status.as_str(),
+        admin = %user.discord_username,
+        "Feedback status updated"
+    );
+
+    Ok(Json(item))
+}
@@ -0,0 +1,172 @@
+//! Admin API handlers for temporary IP bans.
+//!
+//! Bans are written to `ip_bans` and applied to the live [`IpFilter`] in the
+//! same request, so they take effect immediately and survive restarts.
+//!
+//! [`IpFilter`]: crate::web::ip_filter::IpFilter
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::data::ip_bans::{self, IpBan};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+use crate::web::middleware::client_ip::{ClientIp, IpCidr};
+
+const MIN_BAN_SECS: i64 = 60;
+const MAX_BAN_SECS: i64 = 90 * 24 * 60 * 60;
+/// Shortest prefixes a ban may use; wider ranges would take out large parts
+/// of the internet at once.
+const MIN_V4_PREFIX: u8 = 8;
+const MIN_V6_PREFIX: u8 = 32;
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct IpBansResponse {
+    pub bans: Vec<IpBan>,
+}
+
+/// Body for `POST /api/admin/ip-bans`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CreateIpBanBody {
+    /// A single address or a CIDR range.
+    pub cidr: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Ban length in seconds, between one minute and 90 days.
+    #[ts(type = "number")]
+    pub duration_secs: i64,
+}
+
+/// `GET /api/admin/ip-bans` -- List bans that haven't expired.
+#[instrument(skip_all)]
+pub async fn list_ip_bans(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<IpBansResponse>, ApiError> {
+    let bans = ip_bans::list_active(&state.db_pool)
+        .await
+        .map_err(|e| db_error("list IP bans", e))?;
+    Ok(Json(IpBansResponse { bans }))
+}
+
+/// `POST /api/admin/ip-bans` -- Ban an address or range for a fixed duration.
+#[instrument(skip_all)]
+pub async fn create_ip_ban(
+    AdminUser(user): AdminUser,
+    ClientIp(caller): ClientIp,
+    State(state): State<AppState>,
+    Json(body): Json<CreateIpBanBody>,
+) -> Result<Json<IpBan>, ApiError> {
+    let cidr: IpCidr = body.cidr.parse().map_err(ApiError::bad_request)?;
+    check_ban_range(&cidr, caller).map_err(ApiError::bad_request)?;
+    if !(MIN_BAN_SECS..=MAX_BAN_SECS).contains(&body.duration_secs) {
+        return Err(ApiError::bad_request(format!(
+            "durationSecs must be between {MIN_BAN_SECS} and {MAX_BAN_SECS}"
+        )));
+    }
+    let reason = body
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    let expires_at = Utc::now() + Duration::seconds(body.duration_secs);
+
+    let ban = ip_bans::insert(
+        &state.db_pool,
+        &cidr.to_string(),
+        reason,
+        user.id,
+        expires_at,
+    )
+    .await
+    .map_err(|e| db_error("create IP ban", e))?;
+    state.ip_filter.add_ban(ban.id, cidr, ban.expires_at);
+
+    info!(
+        admin = %user.discord_username,
+        ban_id = ban.id,
+        cidr = %cidr,
+        expires_at = %ban.expires_at,
+        "Admin added IP ban"
+    );
+
+    Ok(Json(ban))
+}
+
+/// Refuse ranges too wide to be deliberate, and any covering the admin
+/// making the request: the ban filter runs ahead of the admin routes, so such
+/// a ban could not be lifted through the API.
+fn check_ban_range(cidr: &IpCidr, caller: IpAddr) -> Result<(), String> {
+    let min = if cidr.is_ipv4() {
+        MIN_V4_PREFIX
+    } else {
+        MIN_V6_PREFIX
+    };
+    if cidr.prefix() < min {
+        return Err(format!("Ranges wider than /{min} can't be banned"));
+    }
+    if cidr.contains(caller) {
+        return Err(format!("{cidr} includes your own address"));
+    }
+    Ok(())
+}
+
+/// `DELETE /api/admin/ip-bans/{id}` -- Lift a ban before it expires.
+#[instrument(skip_all, fields(ban_id = id))]
+pub async fn delete_ip_ban(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    let lifted = ip_bans::expire(&state.db_pool, id)
+        .await
+        .map_err(|e| db_error("lift IP ban", e))?;
+    if !lifted {
+        return Err(ApiError::not_found(format!(
+            "No active IP ban with id {id}"
+        )));
+    }
+    state.ip_filter.remove_ban(id);
+
+    info!(admin = %user.discord_username, ban_id = id, "Admin lifted IP ban");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(cidr: &str, caller: &str) -> Result<(), String> {
+        check_ban_range(&cidr.parse().unwrap(), caller.parse().unwrap())
+    }
+
+    #[test]
+    fn rejects_overly_wide_ranges() {
+        assert!(check("0.0.0.0/0", "203.0.113.9").is_err());
+        assert!(check("10.0.0.0/7", "203.0.113.9").is_err());
+        assert!(check("::/0", "2001:db8::1").is_err());
+        assert!(check("2001:d00::/24", "203.0.113.9").is_err());
+        assert!(check("10.0.0.0/8", "203.0.113.9").is_ok());
+        assert!(check("2001:db8::/32", "203.0.113.9").is_ok());
+    }
+
+    #[test]
+    fn rejects_ranges_covering_the_caller() {
+        assert!(check("203.0.113.0/24", "203.0.113.9").is_err());
+        assert!(check("203.0.113.9", "203.0.113.9").is_err());
+        assert!(check("2001:db8::/48", "2001:db8::1").is_err());
+        assert!(check("198.51.100.0/24", "203.0.113.9").is_ok());
+    }
+}
@@ -0,0 +1,46 @@
+//! Admin API handlers for the maintenance mode switch.
+
+use axum::extract::State;
+use axum::response::Json;
+use tracing::{info, instrument};
+
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+use crate::web::maintenance::MaintenanceConfig;
+use crate::web::middleware::admin_audit::PriorState;
+
+/// `GET /api/admin/maintenance` -- Current maintenance switch.
+#[instrument(skip_all)]
+pub async fn get_maintenance(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Json<MaintenanceConfig> {
+    Json(state.maintenance.config())
+}
+
+/// `PUT /api/admin/maintenance` -- Turn maintenance mode on or off.
+#[instrument(skip_all)]
+pub async fn update_maintenance(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(config): Json<MaintenanceConfig>,
+) -> Result<(PriorState, Json<MaintenanceConfig>), ApiError> {
+    config.validate().map_err(ApiError::bad_request)?;
+
+    let prior = PriorState::of(&state.maintenance.config());
+    let enabled = config.enabled;
+    state
+        .maintenance
+        .save(&state.db_pool, config)
+        .await
+        .map_err(|e| db_error("save maintenance config", e))?;
+
+    info!(
+        enabled,
+        admin = %user.discord_username,
+        "Maintenance mode updated"
+    );
+
+    Ok((prior, Json(state.maintenance.config())))
+}
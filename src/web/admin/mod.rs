@@ -2,10 +2,27 @@
 //!
 //! All endpoints require the `AdminUser` extractor, returning 401/403 as needed.
 
+pub mod actions;
+pub mod api_keys;
 pub mod bluebook;
+pub mod bot;
+pub mod crawler;
+pub mod debug_capture;
+pub mod export;
+pub mod feedback;
+pub mod ip_bans;
+pub mod maintenance;
+pub mod nicknames;
 pub mod rmp;
+pub mod scores;
 pub mod scraper;
+pub mod slugs;
+pub mod subject_aliases;
+pub mod syncs;
 pub mod terms;
+pub mod webhooks;
+
+use std::time::Instant;
 
 use axum::extract::{Path, State};
 use axum::http::{HeaderMap, StatusCode, header};
@@ -13,15 +30,19 @@ use axum::response::{IntoResponse, Json, Response};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use tracing::{info, instrument, trace};
+use tracing::{info, instrument, trace, warn};
 use ts_rs::TS;
 
+use crate::data::DbContext;
 use crate::data::models::User;
+use crate::data::scrape_jobs::RequeueOutcome;
+use crate::data::unsigned::DurationMs;
 use crate::state::AppState;
 use crate::state::ServiceStatus;
 use crate::web::audit::{AuditLogEntry, AuditLogResponse};
 use crate::web::auth::extractors::AdminUser;
 use crate::web::error::{ApiError, db_error};
+use crate::web::middleware::admin_audit::PriorState;
 use crate::web::ws::ScrapeJobDto;
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -31,50 +52,106 @@ pub struct ScrapeJobsResponse {
     pub jobs: Vec<ScrapeJobDto>,
 }
 
+/// Response for `POST /api/admin/scrape-jobs/{id}/requeue`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RequeueJobResponse {
+    /// False when a live job for the same target already existed and the
+    /// dead letter was dropped instead.
+    pub requeued: bool,
+    pub job: Option<ScrapeJobDto>,
+}
+
 #[derive(Debug, Clone, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct AdminServiceInfo {
-    name: String,
-    status: ServiceStatus,
+    pub name: String,
+    pub status: ServiceStatus,
 }
 
+/// Counts are `null` when their query failed; the rest of the payload is still served.
 #[derive(Debug, Clone, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct AdminStatusResponse {
-    #[ts(type = "number")]
-    user_count: i64,
-    #[ts(type = "number")]
-    session_count: i64,
-    #[ts(type = "number")]
-    course_count: i64,
-    #[ts(type = "number")]
-    scrape_job_count: i64,
-    services: Vec<AdminServiceInfo>,
+    #[ts(type = "number | null")]
+    pub user_count: Option<i64>,
+    #[ts(type = "number | null")]
+    pub session_count: Option<i64>,
+    #[ts(type = "number | null")]
+    pub course_count: Option<i64>,
+    #[ts(type = "number | null")]
+    pub scrape_job_count: Option<i64>,
+    pub services: Vec<AdminServiceInfo>,
+    pub latency: AdminStatusLatency,
+    pub scrape_history: ScrapeHistoryRetention,
 }
 
-/// `GET /api/admin/status` -- Enhanced system status for admins.
-#[instrument(skip_all)]
-pub async fn admin_status(
-    AdminUser(_user): AdminUser,
-    State(state): State<AppState>,
-) -> Result<Json<AdminStatusResponse>, ApiError> {
-    let user_count = crate::data::users::count_all(&state.db_pool)
-        .await
-        .map_err(|e| db_error("count users", e))?;
+/// Retention of `scrape_job_results` and the outcome of the last compaction.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ScrapeHistoryRetention {
+    /// Results older than this are rolled into daily stats.
+    pub retention_days: u32,
+    /// `null` until the first compaction, or when the lookup failed.
+    pub last_compacted_at: Option<String>,
+    #[ts(type = "number | null")]
+    pub last_compacted_rows: Option<i64>,
+}
 
-    let session_count = crate::data::sessions::count_active(&state.db_pool)
-        .await
-        .map_err(|e| db_error("count sessions", e))?;
+/// Wall time of each count query in [`AdminStatusResponse`].
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AdminStatusLatency {
+    pub users_ms: DurationMs,
+    pub sessions_ms: DurationMs,
+    pub courses_ms: DurationMs,
+    pub scrape_jobs_ms: DurationMs,
+}
 
-    let course_count = crate::data::courses::count_all(&state.db_pool)
-        .await
-        .map_err(|e| db_error("count courses", e))?;
+/// Await a status count, timing it and degrading failure to `None` with a warning.
+async fn timed_count(
+    query: &'static str,
+    fut: impl Future<Output = anyhow::Result<i64>>,
+) -> (Option<i64>, DurationMs) {
+    let start = Instant::now();
+    let result = fut.await;
+    let elapsed = DurationMs::new(u32::try_from(start.elapsed().as_millis()).unwrap_or(u32::MAX));
+    match result {
+        Ok(count) => (Some(count), elapsed),
+        Err(e) => {
+            warn!(query, error = ?e, "Admin status count failed");
+            (None, elapsed)
+        }
+    }
+}
 
-    let scrape_job_count = crate::data::scrape_jobs::count_all(&state.db_pool)
-        .await
-        .map_err(|e| db_error("count scrape jobs", e))?;
+/// Gather system status. Shared by the admin API and the `/admin status` bot command.
+///
+/// Count queries run concurrently and fail independently.
+pub async fn load_admin_status(state: &AppState) -> AdminStatusResponse {
+    let pool = &state.db_pool;
+    let (
+        (user_count, users_ms),
+        (session_count, sessions_ms),
+        (course_count, courses_ms),
+        (scrape_job_count, scrape_jobs_ms),
+        compaction,
+    ) = tokio::join!(
+        timed_count("users", crate::data::users::count_all(pool)),
+        timed_count("sessions", crate::data::sessions::count_active(pool)),
+        timed_count("courses", crate::data::courses::count_all(pool)),
+        timed_count("scrape_jobs", crate::data::scrape_jobs::count_all(pool)),
+        crate::data::scrape_history::compaction_status(pool),
+    );
+    let compaction = compaction.unwrap_or_else(|e| {
+        warn!(error = ?e, "Failed to load scrape history compaction status");
+        Default::default()
+    });
 
     let services: Vec<AdminServiceInfo> = state
         .service_statuses
@@ -83,22 +160,44 @@ pub async fn admin_status(
         .map(|(name, status)| AdminServiceInfo { name, status })
         .collect();
 
-    trace!(
+    AdminStatusResponse {
         user_count,
         session_count,
         course_count,
         scrape_job_count,
-        service_count = services.len(),
+        services,
+        latency: AdminStatusLatency {
+            users_ms,
+            sessions_ms,
+            courses_ms,
+            scrape_jobs_ms,
+        },
+        scrape_history: ScrapeHistoryRetention {
+            retention_days: state.scrape_history_retention_days,
+            last_compacted_at: compaction.last_run_at.map(|t| t.to_rfc3339()),
+            last_compacted_rows: compaction.last_compacted_rows,
+        },
+    }
+}
+
+/// `GET /api/admin/status` -- Enhanced system status for admins.
+#[instrument(skip_all)]
+pub async fn admin_status(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<AdminStatusResponse>, ApiError> {
+    let status = load_admin_status(&state).await;
+
+    trace!(
+        user_count = ?status.user_count,
+        session_count = ?status.session_count,
+        course_count = ?status.course_count,
+        scrape_job_count = ?status.scrape_job_count,
+        service_count = status.services.len(),
         "Fetched admin status"
     );
 
-    Ok(Json(AdminStatusResponse {
-        user_count,
-        session_count,
-        course_count,
-        scrape_job_count,
-        services,
-    }))
+    Ok(Json(status))
 }
 
 /// `GET /api/admin/users` -- List all users.
@@ -121,28 +220,32 @@ pub struct SetAdminBody {
     is_admin: bool,
 }
 
-/// `PUT /api/admin/users/{discord_id}/admin` -- Set admin status for a user.
-#[instrument(skip_all, fields(discord_id))]
+/// `PUT /api/admin/users/{id}/admin` -- Set admin status for a user.
+#[instrument(skip_all, fields(user_id))]
 pub async fn set_user_admin(
     AdminUser(_user): AdminUser,
     State(state): State<AppState>,
-    Path(discord_id): Path<i64>,
+    Path(user_id): Path<i64>,
     Json(body): Json<SetAdminBody>,
-) -> Result<Json<User>, ApiError> {
-    let user = crate::data::users::set_admin(&state.db_pool, discord_id, body.is_admin)
+) -> Result<(PriorState, Json<User>), ApiError> {
+    let prior = crate::data::users::get_user(&state.db_pool, user_id)
+        .await
+        .map_err(|e| db_error("get user", e))?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+    let user = crate::data::users::set_admin(&state.db_pool, user_id, body.is_admin)
         .await
         .map_err(|e| db_error("set admin status", e))?
         .ok_or_else(|| ApiError::not_found("User not found"))?;
 
-    state.session_cache.evict_user(discord_id);
+    state.session_cache.evict_user(user_id);
 
     info!(
-        discord_id,
+        user_id,
         is_admin = body.is_admin,
         "Updated user admin status"
     );
 
-    Ok(Json(user))
+    Ok((PriorState::of(&prior), Json(user)))
 }
 
 /// `GET /api/admin/scrape-jobs` -- List scrape jobs.
@@ -162,6 +265,90 @@ pub async fn list_scrape_jobs(
     Ok(Json(ScrapeJobsResponse { jobs }))
 }
 
+/// `GET /api/admin/scrape-jobs/dead-letter` -- List dead-lettered scrape jobs.
+#[instrument(skip_all)]
+pub async fn list_dead_letter_jobs(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<ScrapeJobsResponse>, ApiError> {
+    let rows = crate::data::scrape_jobs::list_dead_letters(&state.db_pool, 200)
+        .await
+        .map_err(|e| db_error("list dead-lettered scrape jobs", e))?;
+
+    let jobs: Vec<ScrapeJobDto> = rows.iter().map(ScrapeJobDto::from).collect();
+
+    trace!(count = jobs.len(), "Listed dead-lettered scrape jobs");
+
+    Ok(Json(ScrapeJobsResponse { jobs }))
+}
+
+/// `POST /api/admin/scrape-jobs/{id}/requeue` -- Return a dead-lettered job to the queue.
+#[instrument(skip_all, fields(job_id))]
+pub async fn requeue_scrape_job(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(job_id): Path<i32>,
+) -> Result<Json<RequeueJobResponse>, ApiError> {
+    let db = DbContext::new(state.db_pool.clone(), state.events.clone());
+    let outcome = db
+        .scrape_jobs()
+        .requeue(job_id)
+        .await
+        .map_err(|e| db_error("requeue scrape job", e))?;
+
+    let response = match outcome {
+        RequeueOutcome::NotFound => {
+            return Err(ApiError::not_found("Dead-lettered job not found"));
+        }
+        RequeueOutcome::Duplicate => RequeueJobResponse {
+            requeued: false,
+            job: None,
+        },
+        RequeueOutcome::Requeued(job) => RequeueJobResponse {
+            requeued: true,
+            job: Some(ScrapeJobDto::from(&job)),
+        },
+    };
+
+    info!(
+        job_id,
+        requeued = response.requeued,
+        admin = %user.discord_username,
+        "Admin requeued dead-lettered scrape job"
+    );
+
+    Ok(Json(response))
+}
+
+/// `DELETE /api/admin/scrape-jobs/{id}` -- Discard a dead-lettered job.
+#[instrument(skip_all, fields(job_id))]
+pub async fn discard_scrape_job(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(job_id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    let job = crate::data::scrape_jobs::get_by_id(&state.db_pool, job_id)
+        .await
+        .map_err(|e| db_error("fetch scrape job", e))?;
+    if job.is_none_or(|j| j.dead_lettered_at.is_none()) {
+        return Err(ApiError::not_found("Dead-lettered job not found"));
+    }
+
+    let db = DbContext::new(state.db_pool.clone(), state.events.clone());
+    db.scrape_jobs()
+        .delete(job_id)
+        .await
+        .map_err(|e| db_error("discard scrape job", e))?;
+
+    info!(
+        job_id,
+        admin = %user.discord_username,
+        "Admin discarded dead-lettered scrape job"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Format a `DateTime<Utc>` as an HTTP-date (RFC 2822) for Last-Modified headers.
 fn to_http_date(dt: &DateTime<Utc>) -> String {
     dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
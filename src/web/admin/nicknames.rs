@@ -0,0 +1,124 @@
+//! Admin API handlers for the nickname equivalence dictionary.
+//!
+//! Changes take effect on the next RMP candidate generation run.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::data::nicknames::{self, NicknameError, NicknameGroup};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+
+/// Map [`NicknameError`] variants to 404/400, falling back to [`db_error`].
+fn nickname_error(context: &str, e: anyhow::Error) -> ApiError {
+    match e.downcast_ref::<NicknameError>() {
+        Some(NicknameError::NotFound) => ApiError::not_found(e.to_string()),
+        Some(NicknameError::TooFewNames) => ApiError::bad_request(e.to_string()),
+        None => db_error(context, e),
+    }
+}
+
+/// Response for `GET /api/admin/nicknames`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct NicknameGroupsResponse {
+    pub groups: Vec<NicknameGroup>,
+}
+
+/// Body for `POST /api/admin/nicknames` and `PUT /api/admin/nicknames/{id}`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct NicknameGroupBody {
+    /// Interchangeable first names; normalized server-side.
+    pub names: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl NicknameGroupBody {
+    fn note(&self) -> Option<&str> {
+        self.note
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+    }
+}
+
+/// `GET /api/admin/nicknames` -- List all nickname groups.
+#[instrument(skip_all)]
+pub async fn list_nicknames(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<NicknameGroupsResponse>, ApiError> {
+    let groups = nicknames::list_groups(&state.db_pool)
+        .await
+        .map_err(|e| db_error("list nickname groups", e))?;
+
+    Ok(Json(NicknameGroupsResponse { groups }))
+}
+
+/// `POST /api/admin/nicknames` -- Add a nickname group.
+#[instrument(skip_all)]
+pub async fn create_nickname_group(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<NicknameGroupBody>,
+) -> Result<(StatusCode, Json<NicknameGroup>), ApiError> {
+    let group = nicknames::create_group(&state.db_pool, &body.names, body.note())
+        .await
+        .map_err(|e| nickname_error("create nickname group", e))?;
+
+    info!(
+        group_id = group.id,
+        names = ?group.names,
+        admin = %user.discord_username,
+        "Nickname group created"
+    );
+
+    Ok((StatusCode::CREATED, Json(group)))
+}
+
+/// `PUT /api/admin/nicknames/{id}` -- Replace a nickname group's names and note.
+#[instrument(skip_all, fields(group_id = id))]
+pub async fn update_nickname_group(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(body): Json<NicknameGroupBody>,
+) -> Result<Json<NicknameGroup>, ApiError> {
+    let group = nicknames::update_group(&state.db_pool, id, &body.names, body.note())
+        .await
+        .map_err(|e| nickname_error("update nickname group", e))?;
+
+    info!(
+        group_id = id,
+        names = ?group.names,
+        admin = %user.discord_username,
+        "Nickname group updated"
+    );
+
+    Ok(Json(group))
+}
+
+/// `DELETE /api/admin/nicknames/{id}` -- Remove a nickname group.
+#[instrument(skip_all, fields(group_id = id))]
+pub async fn delete_nickname_group(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    nicknames::delete_group(&state.db_pool, id)
+        .await
+        .map_err(|e| nickname_error("delete nickname group", e))?;
+
+    info!(group_id = id, admin = %user.discord_username, "Nickname group deleted");
+
+    Ok(StatusCode::NO_CONTENT)
+}
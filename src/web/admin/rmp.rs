@@ -4,19 +4,27 @@
 //! All SQL lives in the data layer; handlers handle HTTP concerns only.
 
 use axum::extract::{Path, Query, State};
-use axum::response::Json;
+use axum::response::{Json, Response};
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use tracing::{info, instrument};
 use ts_rs::TS;
 
-use crate::data::admin_rmp::{self, ListInstructorsFilter};
+use crate::data::admin_rmp::{
+    self, CandidateReviewError, ListCandidatesFilter, ListInstructorsFilter, MatchDecision,
+    MatchDecisionRow,
+};
+use crate::data::events::{DomainEvent, InstructorEvent};
 use crate::state::AppState;
+use crate::utils::csv_records::read_records;
 use crate::web::auth::extractors::AdminUser;
 use crate::web::error::{ApiError, db_error};
+use crate::web::export::{ExportFormat, ExportRecord, export_response};
 
 // Re-export response types so existing imports from `web::admin::rmp::*` still work.
 pub use crate::data::admin_rmp::{
-    InstructorDetailResponse, ListInstructorsResponse, RescoreResponse,
+    CandidateReviewResponse, InstructorDetailResponse, ListCandidatesResponse,
+    ListInstructorsResponse, RescoreResponse,
 };
 
 #[derive(Deserialize, Serialize, TS)]
@@ -49,6 +57,31 @@ pub struct RejectCandidateBody {
     pub rmp_legacy_id: i32,
 }
 
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListCandidatesParams {
+    /// Candidate status to list; defaults to `pending`, `all` lists every status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<i32>,
+}
+
+/// Candidate ids for a bulk accept or reject.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CandidateIdsBody {
+    pub ids: Vec<i32>,
+}
+
 /// Simple acknowledgement response for mutating operations.
 #[derive(Debug, Clone, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -115,7 +148,7 @@ pub async fn match_instructor(
     Path(id): Path<i32>,
     Json(body): Json<MatchBody>,
 ) -> Result<Json<InstructorDetailResponse>, ApiError> {
-    admin_rmp::accept_candidate(&state.db_pool, id, body.rmp_legacy_id, user.discord_id)
+    admin_rmp::accept_candidate(&state.db_pool, id, body.rmp_legacy_id, user.id)
         .await
         .map_err(|e| {
             let msg = format!("{e:#}");
@@ -128,6 +161,12 @@ pub async fn match_instructor(
             }
         })?;
 
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+            instructor_id: Some(id),
+        }));
+
     info!(
         instructor_id = id,
         rmp_legacy_id = body.rmp_legacy_id,
@@ -149,15 +188,20 @@ pub async fn reject_candidate(
     Path(id): Path<i32>,
     Json(body): Json<RejectCandidateBody>,
 ) -> Result<Json<OkResponse>, ApiError> {
-    let found =
-        admin_rmp::reject_candidate(&state.db_pool, id, body.rmp_legacy_id, user.discord_id)
-            .await
-            .map_err(|e| db_error("reject candidate", e))?;
+    let found = admin_rmp::reject_candidate(&state.db_pool, id, body.rmp_legacy_id, user.id)
+        .await
+        .map_err(|e| db_error("reject candidate", e))?;
 
     if !found {
         return Err(ApiError::not_found("pending candidate not found"));
     }
 
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+            instructor_id: Some(id),
+        }));
+
     info!(
         instructor_id = id,
         rmp_legacy_id = body.rmp_legacy_id,
@@ -174,7 +218,7 @@ pub async fn reject_all(
     State(state): State<AppState>,
     Path(id): Path<i32>,
 ) -> Result<Json<OkResponse>, ApiError> {
-    admin_rmp::reject_all_candidates(&state.db_pool, id, user.discord_id)
+    admin_rmp::reject_all_candidates(&state.db_pool, id, user.id)
         .await
         .map_err(|e| {
             let msg = format!("{e:#}");
@@ -189,6 +233,12 @@ pub async fn reject_all(
             }
         })?;
 
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+            instructor_id: Some(id),
+        }));
+
     info!(instructor_id = id, "all RMP candidates rejected");
 
     Ok(Json(OkResponse { ok: true }))
@@ -218,6 +268,12 @@ pub async fn unmatch_instructor(
         .await
         .map_err(|e| db_error("unmatch instructor", e))?;
 
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+            instructor_id: Some(id),
+        }));
+
     info!(
         instructor_id = id,
         ?rmp_legacy_id,
@@ -233,7 +289,7 @@ pub async fn rescore(
     AdminUser(_user): AdminUser,
     State(state): State<AppState>,
 ) -> Result<Json<RescoreResponse>, ApiError> {
-    let stats = admin_rmp::rescore(&state.db_pool)
+    let stats = admin_rmp::rescore(&state.db_pool, &state.events)
         .await
         .map_err(|e| db_error("rescore", e))?;
 
@@ -249,3 +305,385 @@ pub async fn rescore(
 
     Ok(Json(stats))
 }
+
+/// `GET /api/admin/rmp/candidates` -- Review queue of match candidates across instructors.
+#[instrument(skip_all)]
+pub async fn list_candidates(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<ListCandidatesParams>,
+) -> Result<Json<ListCandidatesResponse>, ApiError> {
+    let status = match params.status.as_deref() {
+        None => Some("pending".to_owned()),
+        Some("all") => None,
+        Some(status @ ("pending" | "accepted" | "rejected")) => Some(status.to_owned()),
+        Some(other) => {
+            return Err(ApiError::bad_request(format!(
+                "invalid status '{other}' (expected pending, accepted, rejected, or all)"
+            )));
+        }
+    };
+    let filter = ListCandidatesFilter {
+        status,
+        search: params.search,
+        min_score: params.min_score,
+        page: params.page.unwrap_or(1),
+        per_page: params.per_page.unwrap_or(50),
+    };
+
+    let response = admin_rmp::list_candidates(&state.db_pool, &filter)
+        .await
+        .map_err(|e| db_error("list candidates", e))?;
+
+    Ok(Json(response))
+}
+
+/// `POST /api/admin/rmp/candidates/accept` -- Accept pending candidates by id.
+#[instrument(skip_all)]
+pub async fn accept_candidates(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<CandidateIdsBody>,
+) -> Result<Json<CandidateReviewResponse>, ApiError> {
+    review_candidates(state, user.id, body.ids, MatchDecision::Confirm).await
+}
+
+/// `POST /api/admin/rmp/candidates/reject` -- Reject pending candidates by id.
+#[instrument(skip_all)]
+pub async fn reject_candidates(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<CandidateIdsBody>,
+) -> Result<Json<CandidateReviewResponse>, ApiError> {
+    review_candidates(state, user.id, body.ids, MatchDecision::Reject).await
+}
+
+async fn review_candidates(
+    state: AppState,
+    resolved_by: i64,
+    ids: Vec<i32>,
+    decision: MatchDecision,
+) -> Result<Json<CandidateReviewResponse>, ApiError> {
+    let response = admin_rmp::review_candidates(&state.db_pool, &ids, decision, resolved_by)
+        .await
+        .map_err(|e| match e.downcast_ref::<CandidateReviewError>() {
+            Some(err) => ApiError::bad_request(err.to_string()),
+            None => db_error("review candidates", e),
+        })?;
+
+    if !response.updated.is_empty() {
+        state
+            .events
+            .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+                instructor_id: None,
+            }));
+    }
+
+    info!(
+        ?decision,
+        updated = response.updated.len(),
+        skipped = response.skipped.len(),
+        conflicts = response.conflicts.len(),
+        "RMP candidates reviewed"
+    );
+
+    Ok(Json(response))
+}
+
+impl ExportRecord for MatchDecisionRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "instructor_id",
+        "instructor_name",
+        "instructor_email",
+        "rmp_legacy_id",
+        "rmp_name",
+        "rmp_department",
+        "score",
+        "name_score",
+        "department_score",
+        "review_courses_score",
+        "subject_score",
+        "uniqueness_score",
+        "volume_score",
+        "decision",
+        "resolved_at",
+        "resolved_by",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.instructor_id.to_string(),
+            self.instructor_name.clone(),
+            self.instructor_email.clone().unwrap_or_default(),
+            self.rmp_legacy_id.to_string(),
+            self.rmp_name.clone(),
+            self.rmp_department.clone().unwrap_or_default(),
+            self.score.to_string(),
+            self.name_score.to_string(),
+            self.department_score.to_string(),
+            self.review_courses_score.to_string(),
+            self.subject_score.to_string(),
+            self.uniqueness_score.to_string(),
+            self.volume_score.to_string(),
+            self.decision.clone(),
+            self.resolved_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            self.resolved_by
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DecisionFormatParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// `GET /api/admin/rmp/decisions` -- Download every candidate pair with its
+/// score breakdown and decision, as CSV (default) or JSONL.
+#[instrument(skip_all)]
+pub async fn export_decisions(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<DecisionFormatParams>,
+) -> Result<Response, ApiError> {
+    let pool = state.db_pool.clone();
+
+    export_response(params.format, "rmp-decisions", move |mut out| async move {
+        let mut rows = admin_rmp::stream_match_decisions(&pool);
+        while let Some(row) = rows.try_next().await? {
+            out.write(&row).await?;
+        }
+        out.finish().await
+    })
+}
+
+/// A row from an edited decisions file. Only the key columns and `decision`
+/// are read; everything else in the export is context for the reviewer.
+#[derive(Debug, Deserialize)]
+struct ImportedDecision {
+    instructor_id: i32,
+    rmp_legacy_id: i32,
+    decision: String,
+}
+
+/// A row that couldn't be applied.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportRowError {
+    /// Spreadsheet row (header is row 1) for CSV, line number for JSONL.
+    pub row: usize,
+    pub message: String,
+}
+
+/// Response for a bulk decision import.
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportDecisionsResponse {
+    /// Rows whose decision changed a candidate or link.
+    pub applied: usize,
+    /// Rows already in the requested state.
+    pub unchanged: usize,
+    /// Rows left as `auto` or `pending`, which aren't decisions.
+    pub skipped: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// Map a `decision` cell to a verdict. `auto`, `pending`, and blank mean
+/// "no decision" and yield `None`.
+fn parse_decision(value: &str) -> Result<Option<MatchDecision>, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "confirmed" | "confirm" => Ok(Some(MatchDecision::Confirm)),
+        "rejected" | "reject" => Ok(Some(MatchDecision::Reject)),
+        "auto" | "pending" | "" => Ok(None),
+        other => Err(format!(
+            "unknown decision '{other}' (expected confirmed or rejected)"
+        )),
+    }
+}
+
+/// An uploaded decision by its 1-based row, or why that row couldn't be read.
+type DecisionRow = (usize, Result<ImportedDecision, String>);
+
+/// Read `(row, decision)` pairs from an uploaded file. Per-row problems are
+/// returned inline; an `Err` means the file as a whole is unusable.
+fn read_decisions(format: ExportFormat, body: &str) -> Result<Vec<DecisionRow>, String> {
+    match format {
+        ExportFormat::Jsonl => Ok(body
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let parsed = serde_json::from_str::<ImportedDecision>(line)
+                    .map_err(|e| format!("invalid JSON: {e}"));
+                (i + 1, parsed)
+            })
+            .collect()),
+        ExportFormat::Csv => {
+            let mut records = read_records(body)
+                .map_err(|e| format!("invalid CSV: {e}"))?
+                .into_iter();
+            let header = records.next().ok_or("file is empty")?.fields;
+            let column = |name: &str| {
+                header
+                    .iter()
+                    .position(|h| h.trim() == name)
+                    .ok_or_else(|| format!("missing '{name}' column"))
+            };
+            let (instructor_col, rmp_col, decision_col) = (
+                column("instructor_id")?,
+                column("rmp_legacy_id")?,
+                column("decision")?,
+            );
+            let cell = |record: &[String], col: usize| {
+                record
+                    .get(col)
+                    .map(|v| v.trim().to_owned())
+                    .unwrap_or_default()
+            };
+            Ok(records
+                .map(|record| {
+                    let (line, record) = (record.line, record.fields);
+                    let id = |col: usize, name: &str| {
+                        cell(&record, col)
+                            .parse::<i32>()
+                            .map_err(|_| format!("invalid {name}"))
+                    };
+                    let parsed = id(instructor_col, "instructor_id").and_then(|instructor_id| {
+                        Ok(ImportedDecision {
+                            instructor_id,
+                            rmp_legacy_id: id(rmp_col, "rmp_legacy_id")?,
+                            decision: cell(&record, decision_col),
+                        })
+                    });
+                    (line as usize, parsed)
+                })
+                .collect())
+        }
+    }
+}
+
+/// `POST /api/admin/rmp/decisions` -- Apply an edited decisions export.
+///
+/// The body is the file itself, in the format given by `?format=` (CSV by
+/// default). Rows marked `confirmed` or `rejected` are applied; rows that
+/// fail are reported and don't stop the rest.
+#[instrument(skip_all)]
+pub async fn import_decisions(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<DecisionFormatParams>,
+    body: String,
+) -> Result<Json<ImportDecisionsResponse>, ApiError> {
+    let rows = read_decisions(params.format, &body).map_err(ApiError::bad_request)?;
+    let mut response = ImportDecisionsResponse::default();
+
+    for (row, parsed) in rows {
+        let outcome = parsed.and_then(|d| parse_decision(&d.decision).map(|verdict| (d, verdict)));
+        let (decision, verdict) = match outcome {
+            Ok((_, None)) => {
+                response.skipped += 1;
+                continue;
+            }
+            Ok((decision, Some(verdict))) => (decision, verdict),
+            Err(message) => {
+                response.errors.push(ImportRowError { row, message });
+                continue;
+            }
+        };
+
+        match admin_rmp::apply_match_decision(
+            &state.db_pool,
+            decision.instructor_id,
+            decision.rmp_legacy_id,
+            verdict,
+            user.id,
+        )
+        .await
+        {
+            Ok(true) => response.applied += 1,
+            Ok(false) => response.unchanged += 1,
+            Err(e) => response.errors.push(ImportRowError {
+                row,
+                message: format!("{e:#}"),
+            }),
+        }
+    }
+
+    if response.applied > 0 {
+        crate::data::rmp::refresh_rmp_summary(&state.db_pool)
+            .await
+            .map_err(|e| db_error("refresh rmp summary", e))?;
+        state
+            .events
+            .publish(DomainEvent::Instructor(InstructorEvent::LinksChanged {
+                instructor_id: None,
+            }));
+    }
+
+    info!(
+        applied = response.applied,
+        unchanged = response.unchanged,
+        skipped = response.skipped,
+        errors = response.errors.len(),
+        "RMP decisions imported"
+    );
+
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_decisions_by_header_name() {
+        let body = "decision,note,rmp_legacy_id,instructor_id\n\
+                    confirmed,\"looks right, same dept\",42,7\n\
+                    auto,,43,8\n\
+                    rejected,,x,9\n";
+        let rows = read_decisions(ExportFormat::Csv, body).unwrap();
+        assert_eq!(rows.len(), 3);
+
+        let (row, first) = &rows[0];
+        let first = first.as_ref().unwrap();
+        assert_eq!(*row, 2);
+        assert_eq!((first.instructor_id, first.rmp_legacy_id), (7, 42));
+        assert_eq!(
+            parse_decision(&first.decision),
+            Ok(Some(MatchDecision::Confirm))
+        );
+
+        let second = rows[1].1.as_ref().unwrap();
+        assert_eq!(parse_decision(&second.decision), Ok(None));
+        assert_eq!(rows[2].1.as_ref().unwrap_err(), "invalid rmp_legacy_id");
+
+        assert!(read_decisions(ExportFormat::Csv, "instructor_id,decision\n").is_err());
+    }
+
+    #[test]
+    fn csv_rows_are_numbered_by_line() {
+        let body = "instructor_id,rmp_legacy_id,decision,note\n\
+                    7,42,confirmed,\"spans\ntwo lines\"\n\
+                    8,x,rejected,\n";
+        let rows = read_decisions(ExportFormat::Csv, body).unwrap();
+        assert_eq!(rows[0].0, 2);
+        assert_eq!(rows[1].0, 4);
+        assert!(rows[1].1.is_err());
+    }
+
+    #[test]
+    fn reads_jsonl_decisions() {
+        let body =
+            "{\"instructor_id\":7,\"rmp_legacy_id\":42,\"decision\":\"rejected\"}\n\nnot json\n";
+        let rows = read_decisions(ExportFormat::Jsonl, body).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].1.as_ref().unwrap().rmp_legacy_id, 42);
+        assert_eq!(rows[1].0, 3);
+        assert!(rows[1].1.is_err());
+        assert!(parse_decision("maybe").is_err());
+    }
+}
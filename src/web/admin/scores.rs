@@ -0,0 +1,52 @@
+//! Admin API handler for recomputing instructor scores on demand.
+
+use axum::extract::{Query, State};
+use axum::response::Json;
+use serde::Deserialize;
+use tracing::{info, instrument};
+
+use crate::data::events::{DomainEvent, InstructorEvent};
+use crate::data::scoring::{self, RecomputeMode, RecomputeStats};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+
+#[derive(Debug, Deserialize)]
+pub struct RecomputeScoresParams {
+    /// Rebuild every score instead of only those with changed inputs.
+    #[serde(default)]
+    pub full: bool,
+}
+
+/// `POST /api/admin/scores/recompute` -- Recompute instructor scores now.
+///
+/// Incremental by default. `?full=true` rebuilds every score, which is needed
+/// after changes the watermark can't see, such as toggling the grade signal.
+#[instrument(skip_all)]
+pub async fn recompute_scores(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Query(params): Query<RecomputeScoresParams>,
+) -> Result<Json<RecomputeStats>, ApiError> {
+    let mode = if params.full {
+        RecomputeMode::Full
+    } else {
+        RecomputeMode::Incremental
+    };
+    let stats = scoring::recompute_scores(&state.db_pool, &state.scoring_decay, mode)
+        .await
+        .map_err(|e| db_error("recompute scores", e))?;
+
+    state
+        .events
+        .publish(DomainEvent::Instructor(InstructorEvent::ScoresRecomputed));
+
+    info!(
+        mode = ?stats.mode,
+        recomputed = stats.recomputed,
+        admin = %user.discord_username,
+        "Instructor scores recomputed"
+    );
+
+    Ok(Json(stats))
+}
@@ -9,13 +9,18 @@ use axum::extract::{Path, Query, State};
 use axum::response::Json;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use tracing::{error, instrument, trace};
+use tracing::{error, info, instrument, trace};
 use ts_rs::TS;
 
+use crate::banner::Term;
+use crate::data::DbContext;
+use crate::data::models::ScrapeDiffSummary;
 use crate::data::unsigned::{Count, DurationMs};
+use crate::scraper::ManualScrapeError;
 use crate::state::AppState;
 use crate::web::auth::extractors::AdminUser;
-use crate::web::error::ApiError;
+use crate::web::error::{ApiError, db_error};
+use crate::web::ws::ScrapeJobDto;
 
 const SLOW_OP_THRESHOLD: Duration = Duration::from_secs(1);
 
@@ -325,6 +330,7 @@ pub struct SubjectResultEntry {
     courses_unchanged: Option<Count>,
     audits_generated: Option<Count>,
     metrics_generated: Option<Count>,
+    diff: Option<ScrapeDiffSummary>,
 }
 
 #[instrument(skip_all, fields(%subject))]
@@ -360,6 +366,7 @@ pub async fn scraper_subject_detail(
             courses_unchanged: row.courses_unchanged.and_then(|v| Count::try_from(v).ok()),
             audits_generated: row.audits_generated.and_then(|v| Count::try_from(v).ok()),
             metrics_generated: row.metrics_generated.and_then(|v| Count::try_from(v).ok()),
+            diff: row.diff_summary.map(|d| d.0),
         })
         .collect();
 
@@ -367,3 +374,60 @@ pub async fn scraper_subject_detail(
 
     Ok(Json(SubjectDetailResponse { subject, results }))
 }
+
+/// Body for `POST /api/admin/scraper/subjects/{subject}/scrape`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct EnqueueScrapeBody {
+    /// Term code to scrape; defaults to the current term.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub term: Option<String>,
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct EnqueueScrapeResponse {
+    /// False when an identical job was already queued.
+    pub queued: bool,
+    pub job: Option<ScrapeJobDto>,
+}
+
+/// `POST /api/admin/scraper/subjects/{subject}/scrape` -- Enqueue a high-priority subject scrape.
+#[instrument(skip_all, fields(%subject))]
+pub async fn enqueue_subject_scrape(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(subject): Path<String>,
+    Json(body): Json<EnqueueScrapeBody>,
+) -> Result<Json<EnqueueScrapeResponse>, ApiError> {
+    let term = body
+        .term
+        .unwrap_or_else(|| Term::get_current().inner().to_string());
+    let db = DbContext::new(state.db_pool.clone(), state.events.clone());
+
+    let job = crate::scraper::enqueue_subject_scrape(&db, &term, &subject)
+        .await
+        .map_err(|e| match e.downcast_ref::<ManualScrapeError>() {
+            Some(ManualScrapeError::UnknownTerm(_)) => ApiError::invalid_term(&term),
+            Some(err @ ManualScrapeError::UnknownSubject(_)) => {
+                ApiError::not_found(err.to_string())
+            }
+            None => db_error("enqueue subject scrape", e),
+        })?;
+
+    info!(
+        subject = %subject,
+        term = %term,
+        queued = job.is_some(),
+        admin = %user.discord_username,
+        "Admin requested subject scrape"
+    );
+
+    Ok(Json(EnqueueScrapeResponse {
+        queued: job.is_some(),
+        job: job.as_ref().map(ScrapeJobDto::from),
+    }))
+}
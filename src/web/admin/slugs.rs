@@ -0,0 +1,67 @@
+//! Admin API handler for regenerating instructor slugs after name corrections.
+
+use axum::extract::State;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::data::events::{DomainEvent, InstructorEvent};
+use crate::data::instructors::{self, SlugChange};
+use crate::data::unsigned::Count;
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+
+/// Body for `POST /api/admin/instructors/rebuild-slugs`.
+#[derive(Debug, Default, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RebuildSlugsBody {
+    /// Instructors to rebuild in addition to those flagged by a name change.
+    #[serde(default)]
+    pub ids: Vec<i32>,
+}
+
+/// Response for `POST /api/admin/instructors/rebuild-slugs`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RebuildSlugsResponse {
+    /// Instructors considered; those whose name still yields the same slug
+    /// keep it.
+    pub checked: Count,
+    pub changes: Vec<SlugChange>,
+}
+
+/// `POST /api/admin/instructors/rebuild-slugs` -- Regenerate stale slugs,
+/// leaving redirects from the old ones.
+#[instrument(skip_all)]
+pub async fn rebuild_slugs(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<RebuildSlugsBody>,
+) -> Result<Json<RebuildSlugsResponse>, ApiError> {
+    let (checked, changes) = instructors::rebuild_instructor_slugs(&state.db_pool, &body.ids)
+        .await
+        .map_err(|e| db_error("rebuild instructor slugs", e))?;
+
+    if !changes.is_empty() {
+        state
+            .events
+            .publish(DomainEvent::Instructor(InstructorEvent::SlugsRebuilt));
+    }
+
+    info!(
+        checked,
+        changed = changes.len(),
+        requested = body.ids.len(),
+        admin = user.id,
+        "instructor slugs rebuilt"
+    );
+
+    Ok(Json(RebuildSlugsResponse {
+        checked: Count::new(checked as u32),
+        changes,
+    }))
+}
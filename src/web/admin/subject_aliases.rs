@@ -0,0 +1,100 @@
+//! Admin API handlers for subject code aliases.
+//!
+//! Search and course grade history pick up changes immediately; RMP subject
+//! scoring picks them up on the next candidate generation run.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::data::subject_aliases::{self, SubjectAlias, SubjectAliasError};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+
+/// Map [`SubjectAliasError`] variants to 404/400, falling back to [`db_error`].
+fn alias_error(context: &str, e: anyhow::Error) -> ApiError {
+    match e.downcast_ref::<SubjectAliasError>() {
+        Some(SubjectAliasError::NotFound) => ApiError::not_found(e.to_string()),
+        Some(_) => ApiError::bad_request(e.to_string()),
+        None => db_error(context, e),
+    }
+}
+
+/// Response for `GET /api/admin/subject-aliases`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectAliasesResponse {
+    pub aliases: Vec<SubjectAlias>,
+}
+
+/// Body for `PUT /api/admin/subject-aliases/{alias}`.
+#[derive(Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectAliasBody {
+    /// Current subject code the alias resolves to.
+    pub subject: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// `GET /api/admin/subject-aliases` -- List all subject aliases.
+#[instrument(skip_all)]
+pub async fn list_subject_aliases(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<SubjectAliasesResponse>, ApiError> {
+    let aliases = subject_aliases::list_aliases(&state.db_pool)
+        .await
+        .map_err(|e| db_error("list subject aliases", e))?;
+
+    Ok(Json(SubjectAliasesResponse { aliases }))
+}
+
+/// `PUT /api/admin/subject-aliases/{alias}` -- Create or repoint an alias.
+#[instrument(skip_all, fields(alias = %alias))]
+pub async fn put_subject_alias(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(alias): Path<String>,
+    Json(body): Json<SubjectAliasBody>,
+) -> Result<Json<SubjectAlias>, ApiError> {
+    let note = body
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    let row = subject_aliases::upsert_alias(&state.db_pool, &alias, &body.subject, note)
+        .await
+        .map_err(|e| alias_error("upsert subject alias", e))?;
+
+    info!(
+        alias = %row.alias,
+        subject = %row.subject,
+        admin = %user.discord_username,
+        "Subject alias saved"
+    );
+
+    Ok(Json(row))
+}
+
+/// `DELETE /api/admin/subject-aliases/{alias}` -- Remove an alias.
+#[instrument(skip_all, fields(alias = %alias))]
+pub async fn delete_subject_alias(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(alias): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    subject_aliases::delete_alias(&state.db_pool, &alias)
+        .await
+        .map_err(|e| alias_error("delete subject alias", e))?;
+
+    info!(alias = %alias, admin = %user.discord_username, "Subject alias deleted");
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,168 @@
+//! Admin API handlers for the scheduler's external-source syncs.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::data::kv;
+use crate::data::unsigned::DurationMs;
+use crate::scraper::syncs::{self, SyncKind, SyncOutcome, SyncRun};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, db_error};
+
+/// One periodic sync and its most recent run.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SyncStatus {
+    pub kind: SyncKind,
+    pub label: String,
+    #[ts(type = "number")]
+    pub interval_secs: u64,
+    pub last_started_at: Option<String>,
+    pub last_finished_at: Option<String>,
+    pub last_duration_ms: Option<DurationMs>,
+    pub last_outcome: Option<SyncOutcome>,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<String>,
+    /// When the scheduler will next run it; `null` means on its next cycle.
+    pub next_run_at: Option<String>,
+    /// A forced run was requested and the scheduler hasn't picked it up yet.
+    pub force_pending: bool,
+}
+
+/// Response for `GET /api/admin/syncs`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SyncsResponse {
+    pub syncs: Vec<SyncStatus>,
+}
+
+/// Response for `POST /api/admin/syncs/{kind}/run`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SyncRunResponse {
+    pub message: String,
+}
+
+fn build_status(
+    kind: SyncKind,
+    run: Option<SyncRun>,
+    last_success: Option<DateTime<Utc>>,
+    force_pending: bool,
+    now: DateTime<Utc>,
+) -> SyncStatus {
+    let interval = kind.interval();
+    // The scheduler restarts a sync's interval when it starts a run, win or
+    // lose; older deployments only have the success timestamp.
+    let next_run_at = run
+        .as_ref()
+        .map(|r| r.started_at)
+        .or(last_success)
+        .and_then(|last| chrono::Duration::from_std(interval).ok().map(|i| last + i))
+        .filter(|next| *next > now && !force_pending);
+
+    SyncStatus {
+        kind,
+        label: kind.label().to_owned(),
+        interval_secs: interval.as_secs(),
+        last_started_at: run.as_ref().map(|r| r.started_at.to_rfc3339()),
+        last_finished_at: run.as_ref().map(|r| r.finished_at.to_rfc3339()),
+        last_duration_ms: run.as_ref().map(|r| {
+            let ms = (r.finished_at - r.started_at).num_milliseconds().max(0);
+            DurationMs::new(u32::try_from(ms).unwrap_or(u32::MAX))
+        }),
+        last_outcome: run.as_ref().map(|r| r.outcome),
+        last_error: run.and_then(|r| r.error),
+        last_success_at: last_success.map(|t| t.to_rfc3339()),
+        next_run_at: next_run_at.map(|t| t.to_rfc3339()),
+        force_pending,
+    }
+}
+
+/// `GET /api/admin/syncs` -- Last run and next due time of each periodic sync.
+#[instrument(skip_all)]
+pub async fn list_syncs(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<SyncsResponse>, ApiError> {
+    let pool = &state.db_pool;
+    let now = Utc::now();
+    let mut statuses = Vec::with_capacity(SyncKind::ALL.len());
+    for kind in SyncKind::ALL {
+        let (run, last_success) = tokio::try_join!(
+            syncs::last_run(pool, kind),
+            kv::get_timestamp(pool, kind.success_key()),
+        )
+        .map_err(|e| db_error("Load sync status", e))?;
+        statuses.push(build_status(
+            kind,
+            run,
+            last_success,
+            state.sync_requests.is_pending(kind),
+            now,
+        ));
+    }
+    Ok(Json(SyncsResponse { syncs: statuses }))
+}
+
+/// `POST /api/admin/syncs/{kind}/run` -- Run a sync on the scheduler's next cycle.
+#[instrument(skip_all, fields(kind = %kind))]
+pub async fn run_sync(
+    AdminUser(user): AdminUser,
+    State(state): State<AppState>,
+    Path(kind): Path<String>,
+) -> Result<(StatusCode, Json<SyncRunResponse>), ApiError> {
+    let kind = SyncKind::parse(&kind)
+        .ok_or_else(|| ApiError::not_found(format!("Unknown sync '{kind}'")))?;
+    state.sync_requests.request(kind);
+    info!(admin = %user.discord_username, sync = kind.as_str(), "Admin forced sync");
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(SyncRunResponse {
+            message: format!("{} sync queued", kind.label()),
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        use chrono::TimeZone;
+        Utc.with_ymd_and_hms(2026, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn next_run_follows_last_start() {
+        let run = SyncRun {
+            started_at: at(1),
+            finished_at: at(2),
+            outcome: SyncOutcome::Failed,
+            error: Some("boom".into()),
+        };
+        let status = build_status(SyncKind::Terms, Some(run), Some(at(0)), false, at(3));
+        // Terms sync every 8 hours, counted from the failed run's start.
+        assert_eq!(status.next_run_at, Some(at(9).to_rfc3339()));
+        assert_eq!(status.last_duration_ms, Some(DurationMs::new(3_600_000)));
+        assert_eq!(status.last_error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn overdue_or_forced_syncs_run_next_cycle() {
+        let never = build_status(SyncKind::Rmp, None, None, false, at(3));
+        assert!(never.next_run_at.is_none());
+
+        let forced = build_status(SyncKind::Terms, None, Some(at(2)), true, at(3));
+        assert!(forced.next_run_at.is_none());
+        assert!(forced.force_pending);
+    }
+}
@@ -7,10 +7,12 @@ use std::time::{Duration, Instant};
 use crate::utils::log_if_slow;
 use axum::extract::{Path, State};
 use axum::response::Json;
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use tracing::{error, info, instrument, trace};
 use ts_rs::TS;
 
+use crate::data::reminders::{self, Classification, RegistrationWindow};
 use crate::data::terms::{self, DbTerm, SyncResult};
 use crate::state::AppState;
 use crate::web::auth::extractors::AdminUser;
@@ -53,6 +55,34 @@ impl From<SyncResult> for TermSyncResponse {
     }
 }
 
+/// Response for `GET`/`PUT /api/admin/terms/:code/registration-windows`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RegistrationWindowsResponse {
+    pub term: String,
+    pub windows: Vec<RegistrationWindow>,
+}
+
+/// One entry in a registration window update.
+#[derive(Debug, Clone, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RegistrationWindowInput {
+    pub classification: Classification,
+    #[ts(type = "string")]
+    pub opens_at: DateTime<Utc>,
+}
+
+/// Body for `PUT /api/admin/terms/:code/registration-windows`.
+#[derive(Debug, Clone, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UpdateRegistrationWindowsBody {
+    /// Replaces every window for the term.
+    pub windows: Vec<RegistrationWindowInput>,
+}
+
 /// `GET /api/admin/terms` -- List all terms with their scraping status.
 #[instrument(skip_all)]
 pub async fn list_terms(
@@ -160,3 +190,63 @@ pub async fn sync_terms(
 
     Ok(Json(result.into()))
 }
+
+/// `GET /api/admin/terms/:code/registration-windows` -- Registration opening
+/// times for a term.
+#[instrument(skip_all, fields(term_code = %code))]
+pub async fn get_registration_windows(
+    _admin: AdminUser,
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<Json<RegistrationWindowsResponse>, ApiError> {
+    let windows = reminders::list_windows(&state.db_pool, &code)
+        .await
+        .map_err(|e| db_error("Failed to fetch registration windows", e))?;
+
+    Ok(Json(RegistrationWindowsResponse {
+        term: code,
+        windows,
+    }))
+}
+
+/// `PUT /api/admin/terms/:code/registration-windows` -- Set registration
+/// opening times from the academic calendar.
+#[instrument(skip_all, fields(term_code = %code))]
+pub async fn update_registration_windows(
+    _admin: AdminUser,
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+    Json(body): Json<UpdateRegistrationWindowsBody>,
+) -> Result<Json<RegistrationWindowsResponse>, ApiError> {
+    let mut windows: Vec<(Classification, DateTime<Utc>)> = Vec::with_capacity(body.windows.len());
+    for input in &body.windows {
+        if windows.iter().any(|(c, _)| *c == input.classification) {
+            return Err(ApiError::bad_request(format!(
+                "Duplicate window for classification '{}'",
+                input.classification
+            )));
+        }
+        windows.push((input.classification, input.opens_at));
+    }
+
+    let found = terms::get_term_by_code(&state.db_pool, &code)
+        .await
+        .map_err(|e| db_error("Failed to fetch term", e))?;
+    if found.is_none() {
+        return Err(ApiError::not_found("Term not found"));
+    }
+
+    reminders::replace_windows(&state.db_pool, &code, &windows)
+        .await
+        .map_err(|e| db_error("Failed to update registration windows", e))?;
+    let windows = reminders::list_windows(&state.db_pool, &code)
+        .await
+        .map_err(|e| db_error("Failed to fetch registration windows", e))?;
+
+    info!(term_code = %code, count = windows.len(), "registration windows updated");
+
+    Ok(Json(RegistrationWindowsResponse {
+        term: code,
+        windows,
+    }))
+}
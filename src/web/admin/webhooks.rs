@@ -0,0 +1,217 @@
+//! Admin API handlers for webhook subscriptions and their delivery logs.
+//!
+//! Endpoints are registered here and fed by the webhook service; see
+//! [`crate::data::webhooks`] for the event types and signature scheme.
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::data::webhooks::{self, DeliveryStatus, Webhook, WebhookDelivery, WebhookEvent};
+use crate::state::AppState;
+use crate::utils::webhook_url::validate_webhook_url;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+use crate::web::middleware::admin_audit::PriorState;
+
+const MAX_DESCRIPTION_LEN: usize = 200;
+const DEFAULT_DELIVERY_LIMIT: i64 = 50;
+const MAX_DELIVERY_LIMIT: i64 = 200;
+
+/// Response for `GET /api/admin/webhooks`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhooksResponse {
+    pub webhooks: Vec<Webhook>,
+}
+
+/// Body for `POST /api/admin/webhooks`.
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CreateWebhookBody {
+    /// A public HTTPS endpoint.
+    pub url: String,
+    #[serde(default)]
+    pub description: String,
+    pub events: Vec<WebhookEvent>,
+}
+
+/// Body for `PUT /api/admin/webhooks/{id}`.
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UpdateWebhookBody {
+    #[serde(default)]
+    pub description: String,
+    pub events: Vec<WebhookEvent>,
+    pub enabled: bool,
+}
+
+/// Response for `POST /api/admin/webhooks`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CreatedWebhook {
+    pub webhook: Webhook,
+    /// HMAC signing secret. Shown only in this response.
+    pub secret: String,
+}
+
+/// Query params for `GET /api/admin/webhooks/{id}/deliveries`.
+#[derive(Debug, Deserialize, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ListWebhookDeliveriesParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<DeliveryStatus>,
+    /// At most 200; defaults to 50.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(type = "number")]
+    pub limit: Option<i64>,
+}
+
+/// Response for `GET /api/admin/webhooks/{id}/deliveries`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+fn validate_description(description: &str) -> Result<&str, ApiError> {
+    let description = description.trim();
+    if description.chars().count() > MAX_DESCRIPTION_LEN {
+        return Err(ApiError::bad_request(format!(
+            "Description must be at most {MAX_DESCRIPTION_LEN} characters"
+        )));
+    }
+    Ok(description)
+}
+
+/// Sorted and deduplicated; at least one is required.
+fn validate_events(mut events: Vec<WebhookEvent>) -> Result<Vec<WebhookEvent>, ApiError> {
+    events.sort();
+    events.dedup();
+    if events.is_empty() {
+        return Err(ApiError::bad_request(
+            "Subscribe to at least one event type",
+        ));
+    }
+    Ok(events)
+}
+
+/// `GET /api/admin/webhooks` -- List registered endpoints.
+#[instrument(skip_all)]
+pub async fn list_webhooks(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+) -> Result<Json<WebhooksResponse>, ApiError> {
+    let webhooks = webhooks::list_webhooks(&state.db_pool)
+        .await
+        .map_err(|e| db_error("list webhooks", e))?;
+    Ok(Json(WebhooksResponse { webhooks }))
+}
+
+/// `POST /api/admin/webhooks` -- Register an endpoint.
+#[instrument(skip_all)]
+pub async fn create_webhook(
+    AdminUser(admin): AdminUser,
+    State(state): State<AppState>,
+    Json(body): Json<CreateWebhookBody>,
+) -> Result<(StatusCode, Json<CreatedWebhook>), ApiError> {
+    let url = validate_webhook_url(&body.url).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    let description = validate_description(&body.description)?;
+    let events = validate_events(body.events)?;
+
+    let (webhook, secret) =
+        webhooks::create_webhook(&state.db_pool, &url, description, &events, admin.id)
+            .await
+            .map_err(|e| db_error("create webhook", e))?;
+
+    info!(
+        webhook_id = webhook.id,
+        admin = admin.id,
+        events = ?webhook.event_types,
+        "Webhook registered"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedWebhook { webhook, secret }),
+    ))
+}
+
+/// `PUT /api/admin/webhooks/{id}` -- Change an endpoint's subscriptions,
+/// description, or enabled flag. The URL and secret are fixed; register a
+/// new endpoint to change them.
+#[instrument(skip_all, fields(webhook_id = id))]
+pub async fn update_webhook(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(body): Json<UpdateWebhookBody>,
+) -> Result<(PriorState, Json<Webhook>), ApiError> {
+    let description = validate_description(&body.description)?;
+    let events = validate_events(body.events)?;
+    let prior = webhooks::get_webhook(&state.db_pool, id)
+        .await
+        .map_err(|e| db_error("get webhook", e))?
+        .or_not_found("Webhook", id)?;
+    let webhook = webhooks::update_webhook(&state.db_pool, id, description, &events, body.enabled)
+        .await
+        .map_err(|e| db_error("update webhook", e))?
+        .or_not_found("Webhook", id)?;
+    Ok((PriorState::of(&prior), Json(webhook)))
+}
+
+/// `DELETE /api/admin/webhooks/{id}` -- Remove an endpoint.
+#[instrument(skip_all, fields(webhook_id = id))]
+pub async fn delete_webhook(
+    AdminUser(admin): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<(PriorState, StatusCode), ApiError> {
+    let prior = webhooks::get_webhook(&state.db_pool, id)
+        .await
+        .map_err(|e| db_error("get webhook", e))?
+        .or_not_found("Webhook", id)?;
+    let deleted = webhooks::delete_webhook(&state.db_pool, id)
+        .await
+        .map_err(|e| db_error("delete webhook", e))?;
+    if !deleted {
+        return Err(ApiError::not_found("Webhook not found"));
+    }
+
+    info!(webhook_id = id, admin = admin.id, "Webhook deleted");
+
+    Ok((PriorState::of(&prior), StatusCode::NO_CONTENT))
+}
+
+/// `GET /api/admin/webhooks/{id}/deliveries` -- An endpoint's recent
+/// deliveries, newest first.
+#[instrument(skip_all, fields(webhook_id = id))]
+pub async fn list_webhook_deliveries(
+    AdminUser(_user): AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<ListWebhookDeliveriesParams>,
+) -> Result<Json<WebhookDeliveriesResponse>, ApiError> {
+    webhooks::get_webhook(&state.db_pool, id)
+        .await
+        .map_err(|e| db_error("get webhook", e))?
+        .or_not_found("Webhook", id)?;
+
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_DELIVERY_LIMIT)
+        .clamp(1, MAX_DELIVERY_LIMIT);
+    let deliveries = webhooks::list_deliveries(&state.db_pool, id, params.status, limit)
+        .await
+        .map_err(|e| db_error("list webhook deliveries", e))?;
+    Ok(Json(WebhookDeliveriesResponse { deliveries }))
+}
@@ -0,0 +1,185 @@
+//! Source attribution and data licensing for API consumers.
+//!
+//! `GET /api/meta/attribution` lists where the served data comes from, the
+//! terms each source is used under, and when each was last synced, so clients
+//! can show the attribution their users are owed. The operator's own license
+//! and notice come from config; sync times are read at request time.
+
+use axum::extract::State;
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::data::kv;
+use crate::scraper::scheduler::{KV_BLUEBOOK_SYNC, KV_RMP_SYNC};
+use crate::state::AppState;
+use crate::web::error::{ApiError, db_error};
+use crate::web::routes::{cache, with_cache_control};
+
+/// Operator-supplied licensing details, from config.
+#[derive(Debug, Clone)]
+pub struct AttributionConfig {
+    /// Banner instance the course data is scraped from.
+    pub banner_url: String,
+    /// License the operator offers the aggregated data under, if any.
+    pub license: Option<String>,
+    /// Free-form notice shown alongside the sources.
+    pub notice: Option<String>,
+}
+
+/// One upstream data source.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SourceAttribution {
+    /// Stable identifier: `banner`, `rmp`, or `bluebook`.
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    /// What the API serves from this source.
+    pub provides: String,
+    /// Terms the data is used under.
+    pub terms: String,
+    /// When this source was last synced successfully, if ever.
+    pub last_synced_at: Option<String>,
+}
+
+/// Response for `GET /api/meta/attribution`.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AttributionResponse {
+    pub sources: Vec<SourceAttribution>,
+    /// License for the aggregated data, when the operator declares one.
+    pub license: Option<String>,
+    pub notice: Option<String>,
+    pub timestamp: String,
+}
+
+/// Last successful sync of each source.
+struct SyncTimes {
+    banner: Option<DateTime<Utc>>,
+    rmp: Option<DateTime<Utc>>,
+    bluebook: Option<DateTime<Utc>>,
+}
+
+fn source(
+    id: &str,
+    name: &str,
+    url: &str,
+    provides: &str,
+    terms: &str,
+    last_synced_at: Option<DateTime<Utc>>,
+) -> SourceAttribution {
+    SourceAttribution {
+        id: id.to_owned(),
+        name: name.to_owned(),
+        url: url.to_owned(),
+        provides: provides.to_owned(),
+        terms: terms.to_owned(),
+        last_synced_at: last_synced_at.map(|t| t.to_rfc3339()),
+    }
+}
+
+fn build_response(config: &AttributionConfig, synced: SyncTimes) -> AttributionResponse {
+    AttributionResponse {
+        sources: vec![
+            source(
+                "banner",
+                "UTSA Banner (Ellucian)",
+                &config.banner_url,
+                "Course sections, meeting times, instructors, and enrollment",
+                "Public class schedule; published by UTSA, not endorsed by UTSA or Ellucian",
+                synced.banner,
+            ),
+            source(
+                "rmp",
+                "RateMyProfessors",
+                "https://www.ratemyprofessors.com",
+                "Instructor ratings, difficulty, and review counts",
+                "Ratings remain the property of RateMyProfessors and its contributors; credit and link back when displayed",
+                synced.rmp,
+            ),
+            source(
+                "bluebook",
+                "UTSA BlueBook",
+                "https://bluebook.utsa.edu",
+                "Course evaluation summaries",
+                "Aggregate evaluation results published by UTSA",
+                synced.bluebook,
+            ),
+        ],
+        license: config.license.clone(),
+        notice: config.notice.clone(),
+        timestamp: Utc::now().to_rfc3339(),
+    }
+}
+
+/// `GET /api/meta/attribution` -- Data sources, licensing, and last sync times.
+#[utoipa::path(
+    get,
+    path = "/meta/attribution",
+    tag = "status",
+    summary = "Data sources, licensing, and when each was last synced",
+    responses(
+        (status = 200, description = "Success", body = AttributionResponse),
+    ),
+)]
+pub async fn attribution(State(state): State<AppState>) -> Result<Response, ApiError> {
+    let pool = &state.db_pool;
+    let (banner, rmp, bluebook) = tokio::try_join!(
+        crate::data::terms::get_latest_scrape(pool),
+        kv::get_timestamp(pool, KV_RMP_SYNC),
+        kv::get_timestamp(pool, KV_BLUEBOOK_SYNC),
+    )
+    .map_err(|e| db_error("Attribution sync times", e))?;
+
+    Ok(with_cache_control(
+        build_response(
+            &state.attribution,
+            SyncTimes {
+                banner,
+                rmp,
+                bluebook,
+            },
+        ),
+        cache::FRESHNESS,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_carries_config_and_sync_times() {
+        let config = AttributionConfig {
+            banner_url: "https://banner.example.edu".to_owned(),
+            license: Some("CC BY 4.0".to_owned()),
+            notice: None,
+        };
+        let synced_at = DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+            .unwrap()
+            .to_utc();
+        let response = build_response(
+            &config,
+            SyncTimes {
+                banner: Some(synced_at),
+                rmp: None,
+                bluebook: None,
+            },
+        );
+
+        let ids: Vec<_> = response.sources.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["banner", "rmp", "bluebook"]);
+        assert_eq!(response.sources[0].url, "https://banner.example.edu");
+        assert_eq!(
+            response.sources[0].last_synced_at.as_deref(),
+            Some("2026-01-02T03:04:05+00:00")
+        );
+        assert!(response.sources[1].last_synced_at.is_none());
+        assert_eq!(response.license.as_deref(), Some("CC BY 4.0"));
+    }
+}
@@ -0,0 +1,109 @@
+//! In-memory cache resolving `X-Api-Key` headers to issued keys.
+
+use dashmap::DashMap;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::data::api_keys::{self, ApiKey};
+
+/// Header carrying an API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// How long a lookup (including a failed one) is reused.
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Most lookups held at once; the oldest is evicted past this.
+const CACHE_CAP: usize = 4096;
+
+/// Read-through cache of API key lookups, keyed by the key's hash so raw
+/// keys aren't held in memory.
+///
+/// Unknown keys are cached too, so a client retrying a bad key doesn't cost
+/// a query per request. Revocations call [`invalidate_all`](Self::invalidate_all).
+#[derive(Clone)]
+pub struct ApiKeyCache {
+    entries: Arc<DashMap<String, (Instant, Option<ApiKey>)>>,
+    db_pool: PgPool,
+}
+
+impl ApiKeyCache {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self {
+            entries: Arc::new(DashMap::new()),
+            db_pool,
+        }
+    }
+
+    /// Whether resolving `raw` would need a database lookup: it's well formed
+    /// and has no fresh cached result.
+    pub fn needs_lookup(&self, raw: &str) -> bool {
+        api_keys::is_well_formed(raw) && self.cached(&api_keys::hash_key(raw)).is_none()
+    }
+
+    fn cached(&self, hash: &str) -> Option<Option<ApiKey>> {
+        self.entries
+            .get(hash)
+            .filter(|entry| entry.0.elapsed() < CACHE_TTL)
+            .map(|entry| entry.1.clone())
+    }
+
+    /// Resolve a raw key to its unrevoked [`ApiKey`].
+    ///
+    /// Malformed keys are rejected without a lookup. On a cache miss the
+    /// database is queried and `last_used_at` is touched in the background.
+    pub async fn resolve(&self, raw: &str) -> Option<ApiKey> {
+        if !api_keys::is_well_formed(raw) {
+            return None;
+        }
+        let hash = api_keys::hash_key(raw);
+        if let Some(key) = self.cached(&hash) {
+            return key;
+        }
+
+        let key = match api_keys::find_active_key(&self.db_pool, &hash).await {
+            Ok(key) => key,
+            Err(e) => {
+                tracing::warn!(error = ?e, "API key lookup failed");
+                return None;
+            }
+        };
+
+        self.insert(hash, key.clone());
+
+        if let Some(key) = &key {
+            let pool = self.db_pool.clone();
+            let id = key.id;
+            tokio::spawn(async move {
+                if let Err(e) = api_keys::touch_key(&pool, id).await {
+                    tracing::warn!(error = %e, "failed to touch API key");
+                }
+            });
+        }
+
+        key
+    }
+
+    fn insert(&self, hash: String, key: Option<ApiKey>) {
+        if self.entries.len() >= CACHE_CAP {
+            self.entries
+                .retain(|_, (cached_at, _)| cached_at.elapsed() < CACHE_TTL);
+        }
+        if self.entries.len() >= CACHE_CAP {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|entry| entry.value().0)
+                .map(|entry| entry.key().clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(hash, (Instant::now(), key));
+    }
+
+    /// Drop every cached lookup, e.g. after a key is revoked.
+    pub fn invalidate_all(&self) {
+        self.entries.clear();
+    }
+}
@@ -1,13 +1,17 @@
 //! Axum extractors for authentication and authorization.
 
+use std::convert::Infallible;
+
 use axum::extract::FromRequestParts;
 use axum::http::{StatusCode, header};
 use axum::response::Json;
 use http::request::Parts;
 use serde_json::json;
 
+use crate::data::api_keys::ApiKey;
 use crate::data::models::User;
 use crate::state::AppState;
+use crate::web::auth::api_keys::API_KEY_HEADER;
 
 /// Extractor that resolves the session cookie to an authenticated [`User`].
 ///
@@ -50,7 +54,9 @@ impl FromRequestParts<AppState> for AuthUser {
 
 /// Extractor that requires an authenticated admin user.
 ///
-/// Returns 401 if not authenticated, 403 if not admin.
+/// Returns 401 if not authenticated, 403 if not admin. Reuses an `AdminUser`
+/// already resolved by the admin audit middleware.
+#[derive(Clone)]
 pub struct AdminUser(pub User);
 
 impl FromRequestParts<AppState> for AdminUser {
@@ -60,6 +66,9 @@ impl FromRequestParts<AppState> for AdminUser {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        if let Some(admin) = parts.extensions.get::<AdminUser>() {
+            return Ok(admin.clone());
+        }
         let AuthUser(user) = AuthUser::from_request_parts(parts, state).await?;
 
         if !user.is_admin {
@@ -72,3 +81,57 @@ impl FromRequestParts<AppState> for AdminUser {
         Ok(AdminUser(user))
     }
 }
+
+/// Extractor that resolves the session cookie if present, without rejecting.
+///
+/// Yields `None` for anonymous requests and invalid or expired sessions.
+pub struct OptionalUser(pub Option<User>);
+
+impl FromRequestParts<AppState> for OptionalUser {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state)
+            .await
+            .ok()
+            .map(|AuthUser(user)| user);
+        Ok(OptionalUser(user))
+    }
+}
+
+/// Extractor that resolves the `X-Api-Key` header to an unrevoked [`ApiKey`].
+///
+/// Returns 401 if the header is missing or the key is unknown or revoked.
+pub struct ApiKeyAuth(pub ApiKey);
+
+impl FromRequestParts<AppState> for ApiKeyAuth {
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let raw = parts
+            .headers
+            .get(API_KEY_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                (
+                    StatusCode::UNAUTHORIZED,
+                    Json(json!({"error": "unauthorized", "message": "No API key"})),
+                )
+            })?;
+
+        let key = state.api_key_cache.resolve(raw).await.ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({"error": "unauthorized", "message": "Invalid or revoked API key"})),
+            )
+        })?;
+
+        Ok(ApiKeyAuth(key))
+    }
+}
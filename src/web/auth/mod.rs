@@ -1,32 +1,50 @@
-//! Discord OAuth2 authentication handlers.
+//! OAuth2 authentication handlers.
 //!
-//! Provides login, callback, logout, and session introspection endpoints
-//! for Discord OAuth2 authentication flow.
+//! Provides login, callback, logout, and session introspection endpoints for
+//! the OAuth2 flow of each configured provider (see [`providers`]), plus
+//! linking further providers to a signed-in account.
 
+pub mod api_keys;
 pub mod extractors;
+pub mod providers;
 pub mod session;
 
-use axum::extract::{Extension, Query, State};
+use axum::extract::{Extension, Path, Query, State};
 use axum::http::{HeaderMap, StatusCode, header};
-use axum::response::{IntoResponse, Json, Redirect, Response};
-use serde::Deserialize;
+use axum::response::{AppendHeaders, IntoResponse, Json, Redirect, Response};
+use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
+use std::net::IpAddr;
 use std::time::Duration;
 use tracing::{error, info, instrument, warn};
+use ts_rs::TS;
 
+use crate::data::identities::{self, IdentityError, ProviderAccount, UserIdentity};
+use crate::data::models::User;
+use crate::data::sessions::{self, ActiveSession};
+use crate::display::DisplayPreferences;
 use crate::state::AppState;
+use crate::web::error::{ApiError, ApiErrorCode, db_error};
+use crate::web::middleware::client_ip::ClientIp;
+use extractors::{ApiKeyAuth, AuthUser, OptionalUser};
+use providers::{OAuthProvider, ProviderKind};
+use session::{OAuthFlow, random_token};
 
 /// OAuth configuration passed as an Axum Extension.
 #[derive(Clone)]
 pub struct AuthConfig {
-    pub client_id: String,
-    pub client_secret: String,
+    /// Configured sign-in providers; always includes Discord.
+    pub providers: Vec<OAuthProvider>,
     /// Optional base URL override (e.g. "https://banner.xevion.dev").
     /// When `None`, the redirect URI is derived from the request's Origin/Host header.
     pub redirect_base: Option<String>,
 }
 
-const CALLBACK_PATH: &str = "/api/auth/callback";
+impl AuthConfig {
+    pub fn provider(&self, kind: ProviderKind) -> Option<&OAuthProvider> {
+        self.providers.iter().find(|p| p.kind == kind)
+    }
+}
 
 /// Derive the origin (scheme + host + port) the user's browser is actually on.
 ///
@@ -70,32 +88,62 @@ pub struct CallbackParams {
 }
 
 #[derive(Deserialize)]
-struct TokenResponse {
-    access_token: String,
+pub struct LoginParams {
+    /// Link the provider to the signed-in account instead of signing in.
+    #[serde(default)]
+    link: bool,
 }
 
-#[derive(Deserialize)]
-struct DiscordUser {
-    id: String,
-    username: String,
-    avatar: Option<String>,
+/// Response for `GET /api/auth/providers`.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AuthProvidersResponse {
+    pub providers: Vec<ProviderKind>,
 }
 
-/// Extract the `session` cookie value from request headers.
-fn extract_session_token(headers: &HeaderMap) -> Option<String> {
+/// Response for `GET /api/auth/sessions`.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UserSessionsResponse {
+    pub sessions: Vec<ActiveSession>,
+}
+
+/// Response for `GET /api/auth/identities`.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UserIdentitiesResponse {
+    pub identities: Vec<UserIdentity>,
+}
+
+/// Name of the cookie binding an OAuth flow to the browser that started it.
+const OAUTH_FLOW_COOKIE: &str = "oauth_flow";
+
+/// How long a browser has to finish an OAuth flow; matches the state store TTL.
+const OAUTH_FLOW_MAX_AGE_SECS: i64 = 10 * 60;
+
+/// Extract a cookie value from request headers.
+fn extract_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
     headers
         .get(header::COOKIE)?
         .to_str()
         .ok()?
         .split(';')
         .find_map(|cookie| {
-            let cookie = cookie.trim();
-            cookie.strip_prefix("session=").map(|v| v.to_owned())
+            let (key, value) = cookie.trim().split_once('=')?;
+            (key == name).then(|| value.to_owned())
         })
 }
 
+/// Extract the `session` cookie value from request headers.
+pub(crate) fn extract_session_token(headers: &HeaderMap) -> Option<String> {
+    extract_cookie(headers, "session")
+}
+
 /// Build a `Set-Cookie` header value for the session cookie.
-fn session_cookie(token: &str, max_age: i64, secure: bool) -> String {
+pub(crate) fn session_cookie(token: &str, max_age: i64, secure: bool) -> String {
     let mut cookie = format!("session={token}; HttpOnly; SameSite=Lax; Path=/; Max-Age={max_age}");
     if secure {
         cookie.push_str("; Secure");
@@ -103,29 +151,102 @@ fn session_cookie(token: &str, max_age: i64, secure: bool) -> String {
     cookie
 }
 
+/// Build a `Set-Cookie` header value for the OAuth flow cookie.
+///
+/// Scoped to the auth routes; `SameSite=Lax` still sends it on the
+/// provider's top-level redirect back to the callback.
+fn oauth_flow_cookie(nonce: &str, max_age: i64, secure: bool) -> String {
+    let mut cookie = format!(
+        "{OAUTH_FLOW_COOKIE}={nonce}; HttpOnly; SameSite=Lax; Path=/api/auth; Max-Age={max_age}"
+    );
+    if secure {
+        cookie.push_str("; Secure");
+    }
+    cookie
+}
+
+/// Parse a provider path segment, 404ing on unknown or unconfigured ones.
+fn configured_provider(auth_config: &AuthConfig, raw: &str) -> Result<ProviderKind, ApiError> {
+    ProviderKind::parse(raw)
+        .filter(|kind| auth_config.provider(*kind).is_some())
+        .ok_or_else(|| ApiError::not_found(format!("Unknown sign-in provider: {raw}")))
+}
+
+/// Redirect to a provider's authorization page, remembering the flow and
+/// binding it to this browser with the flow cookie.
+fn start_login(
+    state: &AppState,
+    auth_config: &AuthConfig,
+    headers: &HeaderMap,
+    provider: &OAuthProvider,
+    link_user: Option<i64>,
+) -> Response {
+    let origin = resolve_origin(auth_config, headers);
+    let redirect_uri = format!("{origin}{}", provider.callback_path());
+    let browser_nonce = random_token();
+    let cookie = oauth_flow_cookie(
+        &browser_nonce,
+        OAUTH_FLOW_MAX_AGE_SECS,
+        redirect_uri.starts_with("https://"),
+    );
+    let csrf_state = state.oauth_state_store.generate(OAuthFlow {
+        origin,
+        provider: provider.kind,
+        link_user,
+        browser_nonce,
+    });
+    (
+        [(header::SET_COOKIE, cookie)],
+        Redirect::temporary(&provider.authorize_redirect(&redirect_uri, &csrf_state)),
+    )
+        .into_response()
+}
+
 /// `GET /api/auth/login` -- Redirect to Discord OAuth2 authorization page.
 #[instrument(skip_all)]
 pub async fn auth_login(
     State(state): State<AppState>,
     Extension(auth_config): Extension<AuthConfig>,
     headers: HeaderMap,
-) -> Redirect {
-    let origin = resolve_origin(&auth_config, &headers);
-    let redirect_uri = format!("{origin}{CALLBACK_PATH}");
-    let csrf_state = state.oauth_state_store.generate(origin);
-    let redirect_uri_encoded = urlencoding::encode(&redirect_uri);
-
-    let url = format!(
-        "https://discord.com/oauth2/authorize\
-         ?client_id={}\
-         &redirect_uri={redirect_uri_encoded}\
-         &response_type=code\
-         &scope=identify\
-         &state={csrf_state}",
-        auth_config.client_id,
-    );
+) -> Result<Response, ApiError> {
+    let provider = auth_config
+        .provider(ProviderKind::Discord)
+        .ok_or_else(|| ApiError::not_found("Discord sign-in is not configured"))?;
+    Ok(start_login(&state, &auth_config, &headers, provider, None))
+}
 
-    Redirect::temporary(&url)
+/// `GET /api/auth/{provider}/login?link=` -- Redirect to a provider's authorization page.
+///
+/// With `link=true`, the provider is linked to the signed-in account on return.
+#[instrument(skip_all, fields(provider = %raw))]
+pub async fn provider_login(
+    State(state): State<AppState>,
+    Extension(auth_config): Extension<AuthConfig>,
+    OptionalUser(user): OptionalUser,
+    Path(raw): Path<String>,
+    Query(params): Query<LoginParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let kind = configured_provider(&auth_config, &raw)?;
+    let link_user = if params.link {
+        let user = user.ok_or_else(|| {
+            ApiError::new(
+                ApiErrorCode::Unauthorized,
+                "Sign in before linking another account",
+            )
+        })?;
+        Some(user.id)
+    } else {
+        None
+    };
+    let provider = auth_config.provider(kind).expect("checked above");
+    Ok(start_login(
+        &state,
+        &auth_config,
+        &headers,
+        provider,
+        link_user,
+    ))
 }
 
 /// `GET /api/auth/callback` -- Handle Discord OAuth2 callback.
@@ -133,114 +254,176 @@ pub async fn auth_login(
 pub async fn auth_callback(
     State(state): State<AppState>,
     Extension(auth_config): Extension<AuthConfig>,
+    OptionalUser(signed_in): OptionalUser,
+    ClientIp(ip): ClientIp,
+    Query(params): Query<CallbackParams>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let client = SessionClient::new(&headers, ip, signed_in);
+    complete_login(&state, &auth_config, ProviderKind::Discord, params, client).await
+}
+
+/// `GET /api/auth/{provider}/callback` -- Handle a provider's OAuth2 callback.
+#[instrument(skip_all, fields(provider = %raw))]
+pub async fn provider_callback(
+    State(state): State<AppState>,
+    Extension(auth_config): Extension<AuthConfig>,
+    OptionalUser(signed_in): OptionalUser,
+    ClientIp(ip): ClientIp,
+    Path(raw): Path<String>,
     Query(params): Query<CallbackParams>,
+    headers: HeaderMap,
 ) -> Result<Response, (StatusCode, Json<Value>)> {
-    // 1. Validate CSRF state and recover the origin used during login
-    let origin = state
+    let kind = ProviderKind::parse(&raw).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Unknown sign-in provider" })),
+        )
+    })?;
+    let client = SessionClient::new(&headers, ip, signed_in);
+    complete_login(&state, &auth_config, kind, params, client).await
+}
+
+/// The browser completing a login flow.
+struct SessionClient<'a> {
+    user_agent: Option<&'a str>,
+    ip: IpAddr,
+    /// The flow cookie set when this browser started a login.
+    flow_nonce: Option<String>,
+    /// The user already signed in on this browser, if any.
+    signed_in: Option<User>,
+}
+
+impl<'a> SessionClient<'a> {
+    fn new(headers: &'a HeaderMap, ip: IpAddr, signed_in: Option<User>) -> Self {
+        Self {
+            user_agent: headers
+                .get(header::USER_AGENT)
+                .and_then(|v| v.to_str().ok()),
+            ip,
+            flow_nonce: extract_cookie(headers, OAUTH_FLOW_COOKIE),
+            signed_in,
+        }
+    }
+}
+
+async fn complete_login(
+    state: &AppState,
+    auth_config: &AuthConfig,
+    kind: ProviderKind,
+    params: CallbackParams,
+    client: SessionClient<'_>,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    // 1. Validate CSRF state and recover the flow this browser started
+    let flow = state
         .oauth_state_store
-        .validate(&params.state)
+        .validate(&params.state, client.flow_nonce.as_deref())
+        .filter(|flow| flow.provider == kind)
         .ok_or_else(|| {
-            warn!("OAuth callback with invalid CSRF state");
+            warn!(
+                provider = kind.as_str(),
+                "OAuth callback with invalid CSRF state"
+            );
             (
                 StatusCode::BAD_REQUEST,
                 Json(json!({ "error": "Invalid OAuth state" })),
             )
         })?;
+    let provider = auth_config.provider(kind).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Unknown sign-in provider" })),
+        )
+    })?;
 
-    // 2. Exchange authorization code for access token
-    let redirect_uri = format!("{origin}{CALLBACK_PATH}");
-    let client = reqwest::Client::new();
-    let token_response = client
-        .post("https://discord.com/api/oauth2/token")
-        .form(&[
-            ("client_id", auth_config.client_id.as_str()),
-            ("client_secret", auth_config.client_secret.as_str()),
-            ("grant_type", "authorization_code"),
-            ("code", params.code.as_str()),
-            ("redirect_uri", redirect_uri.as_str()),
-        ])
-        .send()
+    // 2. Exchange the authorization code and fetch the provider profile
+    let redirect_uri = format!("{}{}", flow.origin, provider.callback_path());
+    let http = reqwest::Client::new();
+    let profile = provider
+        .fetch_profile(&http, &params.code, &redirect_uri)
         .await
         .map_err(|e| {
-            error!(error = %e, "failed to exchange OAuth code for token");
+            error!(provider = kind.as_str(), error = %e, "OAuth sign-in failed");
             (
                 StatusCode::BAD_GATEWAY,
-                Json(json!({ "error": "Failed to exchange code with Discord" })),
+                Json(json!({ "error": format!("Failed to sign in with {}", kind.label()) })),
             )
         })?;
+    let account = ProviderAccount {
+        provider: kind.as_str(),
+        subject: &profile.subject,
+        display_name: &profile.display_name,
+        email: profile.email.as_deref(),
+        avatar_hash: profile.avatar_hash.as_deref(),
+    };
 
-    if !token_response.status().is_success() {
-        let status = token_response.status();
-        let body = token_response.text().await.unwrap_or_default();
-        error!(%status, %body, "Discord token exchange returned error");
-        return Err((
-            StatusCode::BAD_GATEWAY,
-            Json(json!({ "error": "Discord token exchange failed" })),
-        ));
-    }
-
-    let token_data: TokenResponse = token_response.json().await.map_err(|e| {
-        error!(error = %e, "failed to parse Discord token response");
-        (
-            StatusCode::BAD_GATEWAY,
-            Json(json!({ "error": "Invalid token response from Discord" })),
+    let secure = redirect_uri.starts_with("https://");
+    let clear_flow = oauth_flow_cookie("", 0, secure);
+
+    // 3a. Linking: attach the identity to the signed-in user and go back
+    if let Some(user_id) = flow.link_user {
+        // The browser must still be signed in as the user who asked to link.
+        if client.signed_in.as_ref().map(|u| u.id) != Some(user_id) {
+            warn!(
+                user_id,
+                provider = kind.as_str(),
+                "link callback from a different or signed-out user"
+            );
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(json!({ "error": "Sign in as the account you are linking to" })),
+            ));
+        }
+        identities::link(&state.db_pool, user_id, &account)
+            .await
+            .map_err(|e| match e.downcast_ref::<IdentityError>() {
+                Some(err) => (
+                    StatusCode::CONFLICT,
+                    Json(json!({ "error": err.to_string() })),
+                ),
+                None => {
+                    error!(error = ?e, "failed to link identity");
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({ "error": "Database error" })),
+                    )
+                }
+            })?;
+        // Cached sessions carry the user's Discord link.
+        state.session_cache.evict_user(user_id);
+        info!(user_id, provider = kind.as_str(), "linked sign-in provider");
+        return Ok((
+            [(header::SET_COOKIE, clear_flow)],
+            Redirect::temporary("/settings"),
         )
-    })?;
+            .into_response());
+    }
 
-    // 3. Fetch Discord user profile
-    let discord_user: DiscordUser = client
-        .get("https://discord.com/api/users/@me")
-        .bearer_auth(&token_data.access_token)
-        .send()
-        .await
-        .map_err(|e| {
-            error!(error = %e, "failed to fetch Discord user profile");
-            (
-                StatusCode::BAD_GATEWAY,
-                Json(json!({ "error": "Failed to fetch Discord profile" })),
-            )
-        })?
-        .json()
+    // 3b. Resolve (or create) the user behind this identity
+    let user = identities::sign_in(&state.db_pool, &account)
         .await
         .map_err(|e| {
-            error!(error = %e, "failed to parse Discord user profile");
+            error!(error = ?e, "failed to resolve signed-in user");
             (
-                StatusCode::BAD_GATEWAY,
-                Json(json!({ "error": "Invalid user profile from Discord" })),
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Database error" })),
             )
         })?;
 
-    let discord_id: i64 = discord_user.id.parse().map_err(|_| {
-        error!(id = %discord_user.id, "Discord user ID is not a valid i64");
-        (
-            StatusCode::BAD_GATEWAY,
-            Json(json!({ "error": "Invalid Discord user ID" })),
-        )
-    })?;
-
-    // 4. Upsert user
-    let user = crate::data::users::upsert_user(
-        &state.db_pool,
-        discord_id,
-        &discord_user.username,
-        discord_user.avatar.as_deref(),
-    )
-    .await
-    .map_err(|e| {
-        error!(error = %e, "failed to upsert user");
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": "Database error" })),
-        )
-    })?;
-
-    info!(discord_id, username = %user.discord_username, "user authenticated via OAuth");
+    info!(
+        user_id = user.id,
+        provider = kind.as_str(),
+        username = %user.discord_username,
+        "user authenticated via OAuth"
+    );
 
-    // 5. Create session
+    // 4. Create session
     let session = crate::data::sessions::create_session(
         &state.db_pool,
-        discord_id,
+        user.id,
         Duration::from_secs(crate::data::sessions::SESSION_DURATION_SECS),
+        client.user_agent,
+        Some(client.ip),
     )
     .await
     .map_err(|e| {
@@ -251,8 +434,7 @@ pub async fn auth_callback(
         )
     })?;
 
-    // 6. Build response with session cookie
-    let secure = redirect_uri.starts_with("https://");
+    // 5. Build response with session cookie
     let cookie = session_cookie(
         &session.id,
         crate::data::sessions::SESSION_DURATION_SECS as i64,
@@ -262,12 +444,94 @@ pub async fn auth_callback(
     let redirect_to = if user.is_admin { "/admin" } else { "/" };
 
     Ok((
-        [(header::SET_COOKIE, cookie)],
+        AppendHeaders([
+            (header::SET_COOKIE, cookie),
+            (header::SET_COOKIE, clear_flow),
+        ]),
         Redirect::temporary(redirect_to),
     )
         .into_response())
 }
 
+/// `GET /api/auth/providers` -- Sign-in providers configured on this server.
+pub async fn auth_providers(
+    Extension(auth_config): Extension<AuthConfig>,
+) -> Json<AuthProvidersResponse> {
+    Json(AuthProvidersResponse {
+        providers: auth_config.providers.iter().map(|p| p.kind).collect(),
+    })
+}
+
+/// `GET /api/auth/identities` -- The caller's linked sign-in providers.
+#[instrument(skip_all)]
+pub async fn list_identities(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<UserIdentitiesResponse>, ApiError> {
+    let identities = identities::list_for_user(&state.db_pool, user.id)
+        .await
+        .map_err(|e| db_error("List identities", e))?;
+    Ok(Json(UserIdentitiesResponse { identities }))
+}
+
+/// `DELETE /api/auth/identities/{provider}` -- Unlink a sign-in provider.
+#[instrument(skip_all, fields(provider = %raw))]
+pub async fn unlink_identity(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(raw): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let kind = ProviderKind::parse(&raw)
+        .ok_or_else(|| ApiError::not_found(format!("Unknown sign-in provider: {raw}")))?;
+    identities::unlink(&state.db_pool, user.id, kind.as_str())
+        .await
+        .map_err(|e| match e.downcast_ref::<IdentityError>() {
+            Some(IdentityError::NotLinked(_)) => ApiError::not_found(e.to_string()),
+            Some(_) => ApiError::conflict(e.to_string()),
+            None => db_error("Unlink identity", e),
+        })?;
+    state.session_cache.evict_user(user.id);
+    info!(
+        user_id = user.id,
+        provider = kind.as_str(),
+        "unlinked sign-in provider"
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/auth/sessions` -- The caller's active sessions.
+#[instrument(skip_all)]
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    headers: HeaderMap,
+) -> Result<Json<UserSessionsResponse>, ApiError> {
+    let token = extract_session_token(&headers);
+    let sessions = sessions::list_user_sessions(&state.db_pool, user.id, token.as_deref())
+        .await
+        .map_err(|e| db_error("List sessions", e))?;
+    Ok(Json(UserSessionsResponse { sessions }))
+}
+
+/// `DELETE /api/auth/sessions/{id}` -- Sign out one of the caller's sessions.
+#[instrument(skip_all, fields(session_id = %id))]
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let public_id: i64 = id
+        .parse()
+        .map_err(|_| ApiError::bad_request("Invalid session ID"))?;
+    let token = sessions::revoke_user_session(&state.db_pool, user.id, public_id)
+        .await
+        .map_err(|e| db_error("Revoke session", e))?
+        .ok_or_else(|| ApiError::not_found("Session not found"))?;
+    state.session_cache.evict(&token);
+    info!(user_id = user.id, "revoked session");
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// `POST /api/auth/logout` -- Destroy the current session.
 #[instrument(skip_all)]
 pub async fn auth_logout(State(state): State<AppState>, headers: HeaderMap) -> Response {
@@ -303,9 +567,43 @@ pub async fn auth_me(
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
     Ok(Json(json!({
-        "discordId": user.discord_id.to_string(),
+        "id": user.id.to_string(),
+        "discordId": user.discord_id.map(|id| id.to_string()),
         "username": user.discord_username,
         "avatarHash": user.discord_avatar_hash,
         "isAdmin": user.is_admin,
+        "timezone": user.timezone,
+        "timeFormat": user.time_format,
     })))
 }
+
+/// `GET /api/auth/key` -- Describe the API key the request was made with.
+#[instrument(skip_all)]
+pub async fn auth_key(ApiKeyAuth(key): ApiKeyAuth) -> Json<crate::data::api_keys::ApiKey> {
+    Json(key)
+}
+
+/// `PUT /api/auth/preferences/display` -- Set the caller's timezone and clock style.
+#[instrument(skip_all)]
+pub async fn auth_update_display_preferences(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(prefs): Json<DisplayPreferences>,
+) -> Result<Json<DisplayPreferences>, ApiError> {
+    if !crate::display::is_valid_timezone(&prefs.timezone) {
+        return Err(ApiError::bad_request(format!(
+            "Unknown timezone: {}",
+            prefs.timezone
+        )));
+    }
+
+    let updated = crate::data::users::set_display_preferences(&state.db_pool, user.id, &prefs)
+        .await
+        .map_err(|e| db_error("Display preferences update", e))?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+
+    // Cached sessions hold a copy of the user row.
+    state.session_cache.evict_user(user.id);
+
+    Ok(Json(DisplayPreferences::from(&updated)))
+}
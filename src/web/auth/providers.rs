@@ -0,0 +1,286 @@
+//! OAuth2 sign-in providers.
+//!
+//! Every provider runs the same authorization-code flow and differs only in
+//! its endpoints, scopes, and profile shape. Discord is always configured;
+//! Google and Microsoft (UTSA SSO through Entra ID) are enabled when their
+//! client credentials are set.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+
+use secrecy::{ExposeSecret, SecretString};
+
+/// Discord's callback predates the other providers and is registered with
+/// Discord at this path, so it keeps it.
+const DISCORD_CALLBACK_PATH: &str = "/api/auth/callback";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ProviderKind {
+    Discord,
+    Google,
+    Microsoft,
+}
+
+impl ProviderKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Discord => "discord",
+            Self::Google => "google",
+            Self::Microsoft => "microsoft",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Discord => "Discord",
+            Self::Google => "Google",
+            Self::Microsoft => "Microsoft",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "discord" => Some(Self::Discord),
+            "google" => Some(Self::Google),
+            "microsoft" => Some(Self::Microsoft),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    #[error("token exchange failed: {0}")]
+    Exchange(String),
+    #[error("profile request failed: {0}")]
+    Profile(String),
+}
+
+/// The signed-in account, normalized across providers.
+#[derive(Debug, Clone)]
+pub struct ProviderProfile {
+    /// Stable account ID: the Discord snowflake or the OIDC `sub` claim.
+    pub subject: String,
+    pub display_name: String,
+    pub email: Option<String>,
+    /// Discord avatar hash; `None` for other providers.
+    pub avatar_hash: Option<String>,
+}
+
+/// Client registration and endpoints for one provider.
+#[derive(Clone)]
+pub struct OAuthProvider {
+    pub kind: ProviderKind,
+    client_id: String,
+    client_secret: SecretString,
+    authorize_url: String,
+    token_url: String,
+    userinfo_url: &'static str,
+    scope: &'static str,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct DiscordUser {
+    id: String,
+    username: String,
+    avatar: Option<String>,
+}
+
+/// Standard OIDC userinfo claims (Google and Microsoft both serve these).
+#[derive(Deserialize)]
+struct OidcUserInfo {
+    sub: String,
+    name: Option<String>,
+    email: Option<String>,
+    preferred_username: Option<String>,
+}
+
+impl OAuthProvider {
+    pub fn discord(client_id: String, client_secret: SecretString) -> Self {
+        Self {
+            kind: ProviderKind::Discord,
+            client_id,
+            client_secret,
+            authorize_url: "https://discord.com/oauth2/authorize".to_owned(),
+            token_url: "https://discord.com/api/oauth2/token".to_owned(),
+            userinfo_url: "https://discord.com/api/users/@me",
+            scope: "identify",
+        }
+    }
+
+    pub fn google(client_id: String, client_secret: SecretString) -> Self {
+        Self {
+            kind: ProviderKind::Google,
+            client_id,
+            client_secret,
+            authorize_url: "https://accounts.google.com/o/oauth2/v2/auth".to_owned(),
+            token_url: "https://oauth2.googleapis.com/token".to_owned(),
+            userinfo_url: "https://openidconnect.googleapis.com/v1/userinfo",
+            scope: "openid email profile",
+        }
+    }
+
+    /// `tenant` is a directory ID or domain (e.g. "utsa.edu") to restrict
+    /// sign-in to one organization, or "organizations" for any work account.
+    pub fn microsoft(client_id: String, client_secret: SecretString, tenant: &str) -> Self {
+        Self {
+            kind: ProviderKind::Microsoft,
+            client_id,
+            client_secret,
+            authorize_url: format!(
+                "https://login.microsoftonline.com/{tenant}/oauth2/v2.0/authorize"
+            ),
+            token_url: format!("https://login.microsoftonline.com/{tenant}/oauth2/v2.0/token"),
+            userinfo_url: "https://graph.microsoft.com/oidc/userinfo",
+            scope: "openid email profile",
+        }
+    }
+
+    /// Path of this provider's callback, relative to the site origin.
+    pub fn callback_path(&self) -> String {
+        match self.kind {
+            ProviderKind::Discord => DISCORD_CALLBACK_PATH.to_owned(),
+            kind => format!("/api/auth/{}/callback", kind.as_str()),
+        }
+    }
+
+    /// The provider's consent page URL to send the browser to.
+    pub fn authorize_redirect(&self, redirect_uri: &str, csrf_state: &str) -> String {
+        format!(
+            "{}?client_id={}&redirect_uri={}&response_type=code&scope={}&state={csrf_state}",
+            self.authorize_url,
+            urlencoding::encode(&self.client_id),
+            urlencoding::encode(redirect_uri),
+            urlencoding::encode(self.scope),
+        )
+    }
+
+    /// Exchange an authorization code and fetch the account it grants.
+    pub async fn fetch_profile(
+        &self,
+        client: &reqwest::Client,
+        code: &str,
+        redirect_uri: &str,
+    ) -> Result<ProviderProfile, ProviderError> {
+        let response = client
+            .post(&self.token_url)
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.expose_secret()),
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+            ])
+            .send()
+            .await
+            .map_err(|e| ProviderError::Exchange(e.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ProviderError::Exchange(format!("{status}: {body}")));
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| ProviderError::Exchange(e.to_string()))?;
+
+        let response = client
+            .get(self.userinfo_url)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| ProviderError::Profile(e.to_string()))?;
+
+        match self.kind {
+            ProviderKind::Discord => {
+                let user: DiscordUser = response
+                    .json()
+                    .await
+                    .map_err(|e| ProviderError::Profile(e.to_string()))?;
+                if user.id.parse::<i64>().is_err() {
+                    return Err(ProviderError::Profile(format!(
+                        "Discord user ID {} is not a valid i64",
+                        user.id
+                    )));
+                }
+                Ok(ProviderProfile {
+                    subject: user.id,
+                    display_name: user.username,
+                    email: None,
+                    avatar_hash: user.avatar,
+                })
+            }
+            ProviderKind::Google | ProviderKind::Microsoft => {
+                let info: OidcUserInfo = response
+                    .json()
+                    .await
+                    .map_err(|e| ProviderError::Profile(e.to_string()))?;
+                Ok(oidc_profile(info))
+            }
+        }
+    }
+}
+
+fn oidc_profile(info: OidcUserInfo) -> ProviderProfile {
+    let email = info.email.or(info.preferred_username);
+    let display_name = info
+        .name
+        .filter(|n| !n.trim().is_empty())
+        .or_else(|| email.clone())
+        .unwrap_or_else(|| info.sub.clone());
+    ProviderProfile {
+        subject: info.sub,
+        display_name,
+        email,
+        avatar_hash: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn callback_paths_keep_discord_legacy_route() {
+        let discord = OAuthProvider::discord("id".into(), SecretString::from("s"));
+        let google = OAuthProvider::google("id".into(), SecretString::from("s"));
+        assert_eq!(discord.callback_path(), "/api/auth/callback");
+        assert_eq!(google.callback_path(), "/api/auth/google/callback");
+    }
+
+    #[test]
+    fn authorize_redirect_encodes_parameters() {
+        let provider = OAuthProvider::microsoft("abc".into(), SecretString::from("s"), "utsa.edu");
+        let url =
+            provider.authorize_redirect("https://example.com/api/auth/microsoft/callback", "xyz");
+        assert!(
+            url.starts_with("https://login.microsoftonline.com/utsa.edu/oauth2/v2.0/authorize?")
+        );
+        assert!(url.contains(
+            "redirect_uri=https%3A%2F%2Fexample.com%2Fapi%2Fauth%2Fmicrosoft%2Fcallback"
+        ));
+        assert!(url.contains("scope=openid%20email%20profile"));
+        assert!(url.ends_with("&state=xyz"));
+    }
+
+    #[test]
+    fn oidc_profile_falls_back_to_email_for_name() {
+        let profile = oidc_profile(OidcUserInfo {
+            sub: "123".into(),
+            name: None,
+            email: None,
+            preferred_username: Some("abc123@my.utsa.edu".into()),
+        });
+        assert_eq!(profile.display_name, "abc123@my.utsa.edu");
+        assert_eq!(profile.email.as_deref(), Some("abc123@my.utsa.edu"));
+    }
+}
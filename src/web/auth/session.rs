@@ -8,11 +8,13 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use crate::data::models::User;
+use crate::web::auth::providers::ProviderKind;
 
 /// Cached session entry with TTL.
 #[derive(Debug, Clone)]
 struct CachedSession {
     user: User,
+    session_created_at: DateTime<Utc>,
     session_expires_at: DateTime<Utc>,
     cached_at: Instant,
 }
@@ -82,6 +84,7 @@ impl SessionCache {
             token.to_owned(),
             CachedSession {
                 user: user.clone(),
+                session_created_at: session.created_at,
                 session_expires_at: session.expires_at,
                 cached_at: Instant::now(),
             },
@@ -99,6 +102,50 @@ impl SessionCache {
         Some(user)
     }
 
+    /// Resolve a session token from the in-memory cache only, never touching
+    /// the database. Returns `None` on a miss or a stale/expired entry.
+    pub fn peek_user(&self, token: &str) -> Option<User> {
+        let entry = self.cache.get(token)?;
+        let is_permanent = entry.session_expires_at == DateTime::<Utc>::MAX_UTC;
+        let cache_fresh = is_permanent || entry.cached_at + self.cache_ttl > Instant::now();
+        (cache_fresh && entry.session_expires_at > Utc::now()).then(|| entry.user.clone())
+    }
+
+    /// Extend a cached session that is past its half-life.
+    ///
+    /// Returns the new expiry when the session was renewed, so the caller can
+    /// re-issue the cookie to match. Sessions not in the cache are skipped;
+    /// they are picked up on a later request once resolved.
+    pub async fn renew_if_due(&self, token: &str) -> Option<DateTime<Utc>> {
+        let (created_at, expires_at) = {
+            let entry = self.cache.get(token)?;
+            (entry.session_created_at, entry.session_expires_at)
+        };
+        if !crate::data::sessions::renewal_due(created_at, expires_at, Utc::now()) {
+            return None;
+        }
+
+        let renewed = match crate::data::sessions::renew_session(&self.db_pool, token).await {
+            Ok(renewed) => renewed,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to renew session");
+                return None;
+            }
+        };
+        match renewed {
+            Some(new_expiry) => {
+                if let Some(mut entry) = self.cache.get_mut(token) {
+                    entry.session_expires_at = new_expiry;
+                }
+                Some(new_expiry)
+            }
+            None => {
+                self.cache.remove(token);
+                None
+            }
+        }
+    }
+
     /// Remove a single session from the cache (e.g. on logout).
     pub fn evict(&self, token: &str) {
         self.cache.remove(token);
@@ -115,6 +162,7 @@ impl SessionCache {
             token.to_owned(),
             CachedSession {
                 user,
+                session_created_at: Utc::now(),
                 session_expires_at: chrono::DateTime::<chrono::Utc>::MAX_UTC,
                 cached_at: std::time::Instant::now(),
             },
@@ -122,9 +170,8 @@ impl SessionCache {
     }
 
     /// Remove all cached sessions belonging to a user.
-    pub fn evict_user(&self, discord_id: i64) {
-        self.cache
-            .retain(|_, entry| entry.user.discord_id != discord_id);
+    pub fn evict_user(&self, user_id: i64) {
+        self.cache.retain(|_, entry| entry.user.id != user_id);
     }
 
     /// Delete expired sessions from the database and sweep the in-memory cache.
@@ -143,9 +190,29 @@ impl SessionCache {
 /// Data stored alongside each OAuth CSRF state token.
 struct OAuthStateEntry {
     created_at: Instant,
+    flow: OAuthFlow,
+}
+
+/// What a login flow was started for, recovered at the callback.
+#[derive(Debug, Clone)]
+pub struct OAuthFlow {
     /// The browser origin that initiated the login flow, so the callback
-    /// can reconstruct the exact redirect_uri Discord expects.
-    origin: String,
+    /// can reconstruct the exact redirect_uri the provider expects.
+    pub origin: String,
+    pub provider: ProviderKind,
+    /// Set when a signed-in user is linking this provider rather than
+    /// signing in with it.
+    pub link_user: Option<i64>,
+    /// Random value also set as an HttpOnly cookie on the browser that
+    /// started the flow, so a state token handed to another browser is
+    /// rejected at the callback.
+    pub browser_nonce: String,
+}
+
+/// A random 16-byte hex token.
+pub fn random_token() -> String {
+    let bytes: [u8; 16] = rand::rng().random();
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
 }
 
 /// Ephemeral store for OAuth CSRF state tokens.
@@ -174,26 +241,27 @@ impl OAuthStateStore {
     }
 
     /// Generate a random 16-byte hex CSRF token, store it with the given
-    /// origin, and return the token.
-    pub fn generate(&self, origin: String) -> String {
-        let bytes: [u8; 16] = rand::rng().random();
-        let token: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    /// flow, and return the token.
+    pub fn generate(&self, flow: OAuthFlow) -> String {
+        let token = random_token();
         self.states.insert(
             token.clone(),
             OAuthStateEntry {
                 created_at: Instant::now(),
-                origin,
+                flow,
             },
         );
         token
     }
 
-    /// Validate and consume a CSRF token. Returns the stored origin if the
-    /// token was present and not expired.
-    pub fn validate(&self, state: &str) -> Option<String> {
+    /// Validate and consume a CSRF token. Returns the stored flow if the
+    /// token was present, not expired, and presented by the browser that
+    /// started the flow.
+    pub fn validate(&self, state: &str, browser_nonce: Option<&str>) -> Option<OAuthFlow> {
         let (_, entry) = self.states.remove(state)?;
-        if entry.created_at.elapsed() < self.ttl {
-            Some(entry.origin)
+        let same_browser = browser_nonce == Some(entry.flow.browser_nonce.as_str());
+        if entry.created_at.elapsed() < self.ttl && same_browser {
+            Some(entry.flow)
         } else {
             None
         }
@@ -207,3 +275,31 @@ impl OAuthStateStore {
             .retain(|_, entry| entry.created_at.elapsed() < ttl);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow(nonce: &str) -> OAuthFlow {
+        OAuthFlow {
+            origin: "https://example.com".into(),
+            provider: ProviderKind::Google,
+            link_user: Some(1),
+            browser_nonce: nonce.into(),
+        }
+    }
+
+    #[test]
+    fn state_is_only_valid_in_the_starting_browser() {
+        let store = OAuthStateStore::new();
+        let state = store.generate(flow("abc"));
+        assert!(store.validate(&state, Some("abc")).is_some());
+        // Single use.
+        assert!(store.validate(&state, Some("abc")).is_none());
+
+        let state = store.generate(flow("abc"));
+        assert!(store.validate(&state, Some("other")).is_none());
+        let state = store.generate(flow("abc"));
+        assert!(store.validate(&state, None).is_none());
+    }
+}
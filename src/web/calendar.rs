@@ -9,8 +9,10 @@ use tracing::{error, instrument};
 
 use crate::banner::models::terms::Term;
 use crate::calendar::{CalendarCourse, generate_gcal_url, generate_ics};
-use crate::data::models::DbMeetingTime;
+use crate::data::models::{Course, CourseInstructorDetail, DbMeetingTime};
+use crate::display::DisplayPreferences;
 use crate::state::AppState;
+use crate::web::auth::extractors::OptionalUser;
 
 /// Fetch course + meeting times, build a `CalendarCourse`.
 async fn load_calendar_course(
@@ -39,6 +41,14 @@ async fn load_calendar_course(
             Vec::new()
         });
 
+    Ok(to_calendar_course(&course, &instructors))
+}
+
+/// Build a `CalendarCourse` and its meeting times from a stored course.
+pub(crate) fn to_calendar_course(
+    course: &Course,
+    instructors: &[CourseInstructorDetail],
+) -> (CalendarCourse, Vec<DbMeetingTime>) {
     let primary_instructor = instructors
         .iter()
         .find(|i| i.is_primary)
@@ -57,15 +67,30 @@ async fn load_calendar_course(
         primary_instructor,
     };
 
-    Ok((cal_course, meeting_times))
+    (cal_course, meeting_times)
 }
 
 /// `GET /api/courses/{term}/{crn}/calendar.ics`
 ///
-/// Returns an ICS file download for the course.
+/// Returns an ICS file download for the course. Signed-in users get times
+/// rendered per their display preferences.
+#[utoipa::path(
+    get,
+    path = "/courses/{term}/{crn}/calendar.ics",
+    tag = "courses",
+    summary = "A section's meetings as an iCalendar file",
+    params(
+        ("term" = String, Path, description = "Term code or slug (e.g. 202620 or spring-2026)"),
+        ("crn" = String, Path, description = "Course reference number"),
+    ),
+    responses(
+        (status = 200, description = "iCalendar file", body = String, content_type = "text/calendar"),
+    ),
+)]
 #[instrument(skip_all, fields(crn))]
 pub async fn course_ics(
     State(state): State<AppState>,
+    OptionalUser(user): OptionalUser,
     Path((term, crn)): Path<(String, String)>,
 ) -> Result<Response, (StatusCode, String)> {
     let (cal_course, meeting_times) = load_calendar_course(&state, &term, &crn).await?;
@@ -77,7 +102,8 @@ pub async fn course_ics(
         ));
     }
 
-    let result = generate_ics(&cal_course, &meeting_times).map_err(|e| {
+    let prefs = DisplayPreferences::for_user(user.as_ref());
+    let result = generate_ics(&cal_course, &meeting_times, &prefs).map_err(|e| {
         error!(%e, "ICS generation failed");
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -105,9 +131,23 @@ pub async fn course_ics(
 ///
 /// Redirects to Google Calendar with a pre-filled event for the first meeting time.
 /// If multiple meeting times exist, uses the first one with scheduled days/times.
+#[utoipa::path(
+    get,
+    path = "/courses/{term}/{crn}/gcal",
+    tag = "courses",
+    summary = "Redirect to Google Calendar with the section's first meeting pre-filled",
+    params(
+        ("term" = String, Path, description = "Term code or slug (e.g. 202620 or spring-2026)"),
+        ("crn" = String, Path, description = "Course reference number"),
+    ),
+    responses(
+        (status = 307, description = "Redirect to Google Calendar"),
+    ),
+)]
 #[instrument(skip_all, fields(crn))]
 pub async fn course_gcal(
     State(state): State<AppState>,
+    OptionalUser(user): OptionalUser,
     Path((term, crn)): Path<(String, String)>,
 ) -> Result<Response, (StatusCode, String)> {
     let (cal_course, meeting_times) = load_calendar_course(&state, &term, &crn).await?;
@@ -125,7 +165,8 @@ pub async fn course_gcal(
         .find(|mt| mt.time_range.is_some() && !mt.days.is_empty())
         .unwrap_or(&meeting_times[0]);
 
-    let url = generate_gcal_url(&cal_course, mt).map_err(|e| {
+    let prefs = DisplayPreferences::for_user(user.as_ref());
+    let url = generate_gcal_url(&cal_course, mt, &prefs).map_err(|e| {
         error!(%e, "Google Calendar URL generation failed");
         (
             StatusCode::INTERNAL_SERVER_ERROR,
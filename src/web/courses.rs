@@ -3,23 +3,30 @@
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::HeaderValue,
     response::{Json, Response},
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tracing::{error, warn};
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
+use crate::data::course_details::{CourseCorequisite, CourseRestriction};
 use crate::data::course_types::{CreditHours, CrossList, Enrollment, RmpBrief, SectionLink};
 use crate::data::courses::{SortColumn, SortDirection};
+use crate::data::grades::GradeDistribution;
 use crate::data::reference_types::{
-    Attribute, Campus, FilterValue, InstructionalMethod, PartOfTerm,
+    Attribute, Campus, FilterParseable, FilterValue, InstructionalMethod, PartOfTerm,
 };
+use crate::data::search::SearchWeights;
 use crate::data::unsigned::Count;
 use crate::data::{self, models};
-use crate::state::AppState;
-use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+use crate::state::{AppState, ReferenceCache};
+use crate::web::audit::AuditLogEntry;
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error, paged_db_error};
 use crate::web::routes::{cache, with_cache_control};
 
 fn default_limit() -> i32 {
@@ -45,7 +52,7 @@ pub fn code_to_filter_value(category: &str, code: &str, description: Option<&str
     }
 }
 
-#[derive(Serialize, TS)]
+#[derive(Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct CourseResponse {
@@ -74,9 +81,20 @@ pub struct CourseResponse {
     has_physical_location: bool,
     primary_instructor_id: Option<i32>,
     instructors: Vec<InstructorResponse>,
+    /// Daily enrollment over the last 30 days, oldest first. Only set on list views.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enrollment_history: Option<Vec<i32>>,
+}
+
+impl CourseResponse {
+    /// Attach a precomputed enrollment sparkline.
+    pub fn with_enrollment_history(mut self, history: Option<Vec<i32>>) -> Self {
+        self.enrollment_history = history;
+        self
+    }
 }
 
-#[derive(Serialize, TS)]
+#[derive(Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct InstructorResponse {
@@ -93,15 +111,98 @@ pub struct InstructorResponse {
     rating: Option<crate::data::course_types::InstructorRating>,
 }
 
-#[derive(Serialize, TS)]
+/// Course detail: the search-row shape plus detail-only data.
+#[derive(Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CourseDetailResponse {
+    #[serde(flatten)]
+    course: CourseResponse,
+    restrictions: Vec<CourseRestriction>,
+    corequisites: Vec<CourseCorequisite>,
+    /// Grades for this course across all imported terms and sections.
+    grades: Option<GradeDistribution>,
+}
+
+#[derive(Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct SearchResponse {
     courses: Vec<CourseResponse>,
+    #[schema(value_type = u32)]
     total_count: Count,
+    /// Only on the first page; later pages reuse the first page's facets.
+    facets: Option<SearchFacetsResponse>,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page.
+    next_cursor: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+/// One selectable filter value with the number of matching sections.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FacetOption {
+    pub code: String,
+    pub description: String,
+    /// Typed filter string for query params, as in [`CodeDescription`].
+    pub filter_value: String,
+    #[schema(value_type = u32)]
+    pub count: Count,
+}
+
+/// Facet counts over all sections matching the current search. Each facet
+/// ignores its own filter, so selected values keep their alternatives.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SearchFacetsResponse {
+    pub subjects: Vec<FacetOption>,
+    pub campuses: Vec<FacetOption>,
+    pub instructional_methods: Vec<FacetOption>,
+    pub attributes: Vec<FacetOption>,
+    /// Sections with at least one open seat.
+    #[schema(value_type = u32)]
+    pub open: Count,
+    #[schema(value_type = u32)]
+    pub closed: Count,
+}
+
+/// Attach descriptions and typed filter values to raw facet codes.
+fn build_facets(
+    facets: data::courses::SearchFacets,
+    ref_cache: &ReferenceCache,
+) -> SearchFacetsResponse {
+    let options = |category: &str, counts: Vec<data::courses::FacetCount>| {
+        counts
+            .into_iter()
+            .map(|facet| {
+                let description = ref_cache.lookup(category, &facet.code);
+                let filter_value = if category == "subject" {
+                    facet.code.clone()
+                } else {
+                    code_to_filter_value(category, &facet.code, description)
+                };
+                FacetOption {
+                    description: description.unwrap_or(&facet.code).to_owned(),
+                    filter_value,
+                    count: Count::try_from(facet.count).unwrap_or_default(),
+                    code: facet.code,
+                }
+            })
+            .collect()
+    };
+
+    SearchFacetsResponse {
+        subjects: options("subject", facets.subjects),
+        campuses: options("campus", facets.campuses),
+        instructional_methods: options("instructional_method", facets.instructional_methods),
+        attributes: options("attribute", facets.attributes),
+        open: Count::try_from(facets.open).unwrap_or_default(),
+        closed: Count::try_from(facets.closed).unwrap_or_default(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct CodeDescription {
@@ -111,7 +212,7 @@ pub struct CodeDescription {
     pub filter_value: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct TermResponse {
@@ -120,9 +221,10 @@ pub struct TermResponse {
     pub description: String,
 }
 
-#[derive(Deserialize, Serialize, TS)]
+#[derive(Deserialize, Serialize, TS, IntoParams)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
+#[into_params(parameter_in = Query)]
 pub struct SearchParams {
     pub term: String,
     #[serde(default)]
@@ -135,19 +237,28 @@ pub struct SearchParams {
     pub course_number_high: Option<i32>,
     #[serde(default, alias = "open_only")]
     pub open_only: bool,
+    #[serde(default, alias = "waitlist_available")]
+    pub waitlist_available: bool,
     #[serde(default, alias = "instructional_method")]
     #[ts(type = "Array<string>")]
+    #[param(value_type = Vec<String>)]
     pub instructional_method: Vec<FilterValue<InstructionalMethod>>,
     #[serde(default)]
     #[ts(type = "Array<string>")]
+    #[param(value_type = Vec<String>)]
     pub campus: Vec<FilterValue<Campus>>,
     #[serde(default = "default_limit")]
     pub limit: i32,
     #[serde(default)]
     pub offset: i32,
+    /// `nextCursor` from a previous page; takes precedence over `offset`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "sort_by")]
+    #[param(inline)]
     pub sort_by: Option<SortColumn>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "sort_dir")]
+    #[param(inline)]
     pub sort_dir: Option<SortDirection>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "wait_count_max")]
     pub wait_count_max: Option<i32>,
@@ -159,9 +270,11 @@ pub struct SearchParams {
     pub time_end: Option<String>,
     #[serde(default, alias = "part_of_term")]
     #[ts(type = "Array<string>")]
+    #[param(value_type = Vec<String>)]
     pub part_of_term: Vec<FilterValue<PartOfTerm>>,
     #[serde(default)]
     #[ts(type = "Array<string>")]
+    #[param(value_type = Vec<String>)]
     pub attributes: Vec<FilterValue<Attribute>>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "credit_hour_min")]
     pub credit_hour_min: Option<f64>,
@@ -171,10 +284,69 @@ pub struct SearchParams {
     pub instructor: Vec<String>,
 }
 
+/// Raw Banner codes for the typed filters of a [`SearchParams`].
+pub struct FilterCodes {
+    instructional_method: Vec<String>,
+    campus: Vec<String>,
+    part_of_term: Vec<String>,
+    attributes: Vec<String>,
+}
+
+fn codes_of<T: FilterParseable>(values: &[FilterValue<T>]) -> Vec<String> {
+    values.iter().map(|fv| fv.to_code().into_owned()).collect()
+}
+
+fn non_empty(values: &[String]) -> Option<&[String]> {
+    (!values.is_empty()).then_some(values)
+}
+
+impl SearchParams {
+    /// Convert typed filter values to raw Banner codes for SQL.
+    pub fn filter_codes(&self) -> FilterCodes {
+        FilterCodes {
+            instructional_method: codes_of(&self.instructional_method),
+            campus: codes_of(&self.campus),
+            part_of_term: codes_of(&self.part_of_term),
+            attributes: codes_of(&self.attributes),
+        }
+    }
+
+    /// The search these params describe. Paging and sort fields are ignored.
+    pub fn to_filter<'a>(
+        &'a self,
+        term_code: &'a str,
+        codes: &'a FilterCodes,
+        weights: SearchWeights,
+    ) -> data::courses::SearchFilter<'a> {
+        data::courses::SearchFilter {
+            term_code,
+            subjects: non_empty(&self.subject),
+            query: self.query.as_deref(),
+            course_number_low: self.course_number_low,
+            course_number_high: self.course_number_high,
+            open_only: self.open_only,
+            waitlist_available: self.waitlist_available,
+            instructional_method: non_empty(&codes.instructional_method),
+            campus: non_empty(&codes.campus),
+            wait_count_max: self.wait_count_max,
+            days: non_empty(&self.days),
+            time_start: self.time_start.as_deref(),
+            time_end: self.time_end.as_deref(),
+            part_of_term: non_empty(&codes.part_of_term),
+            attributes: non_empty(&codes.attributes),
+            credit_hour_min: self.credit_hour_min,
+            credit_hour_max: self.credit_hour_max,
+            instructors: non_empty(&self.instructor),
+            weights,
+        }
+    }
+}
+
 /// Build a `CourseResponse` from a DB course with pre-fetched instructor details.
 pub fn build_course_response(
     course: &models::Course,
     instructors: Vec<models::CourseInstructorDetail>,
+    linked_groups: Vec<Vec<String>>,
 ) -> CourseResponse {
     let instructors: Vec<InstructorResponse> = instructors
         .into_iter()
@@ -361,10 +533,11 @@ pub fn build_course_response(
         })
     });
 
-    let section_link = course
-        .link_identifier
-        .clone()
-        .map(|identifier| SectionLink { identifier });
+    let section_link =
+        (course.link_identifier.is_some() || !linked_groups.is_empty()).then(|| SectionLink {
+            identifier: course.link_identifier.clone(),
+            groups: linked_groups,
+        });
 
     use crate::banner::models::terms::Term;
     let term_slug = course
@@ -395,10 +568,23 @@ pub fn build_course_response(
         meeting_times,
         attributes,
         instructors,
+        enrollment_history: None,
     }
 }
 
 /// `GET /api/courses/search`
+#[utoipa::path(
+    get,
+    path = "/courses/search",
+    tag = "courses",
+    summary = "Search course sections",
+    params(
+        SearchParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = SearchResponse),
+    ),
+)]
 pub(super) async fn search_courses(
     State(state): State<AppState>,
     axum_extra::extract::Query(params): axum_extra::extract::Query<SearchParams>,
@@ -410,117 +596,209 @@ pub(super) async fn search_courses(
     let limit = params.limit.clamp(1, 100);
     let offset = params.offset.max(0);
 
-    // Convert typed filter values to raw Banner codes for SQL
-    let method_codes: Vec<String> = params
-        .instructional_method
-        .iter()
-        .map(|fv| fv.to_code().into_owned())
-        .collect();
-    let campus_codes: Vec<String> = params
-        .campus
-        .iter()
-        .map(|fv| fv.to_code().into_owned())
-        .collect();
-    let pot_codes: Vec<String> = params
-        .part_of_term
-        .iter()
-        .map(|fv| fv.to_code().into_owned())
-        .collect();
-    let attr_codes: Vec<String> = params
-        .attributes
-        .iter()
-        .map(|fv| fv.to_code().into_owned())
-        .collect();
+    let codes = params.filter_codes();
+    let filter = params.to_filter(&term_code, &codes, state.search_weights);
 
-    let filter = data::courses::SearchFilter {
-        term_code: &term_code,
-        subjects: if params.subject.is_empty() {
-            None
-        } else {
-            Some(&params.subject)
-        },
-        query: params.query.as_deref(),
-        course_number_low: params.course_number_low,
-        course_number_high: params.course_number_high,
-        open_only: params.open_only,
-        instructional_method: if method_codes.is_empty() {
-            None
-        } else {
-            Some(&method_codes)
-        },
-        campus: if campus_codes.is_empty() {
-            None
-        } else {
-            Some(&campus_codes)
-        },
-        wait_count_max: params.wait_count_max,
-        days: if params.days.is_empty() {
-            None
-        } else {
-            Some(&params.days)
-        },
-        time_start: params.time_start.as_deref(),
-        time_end: params.time_end.as_deref(),
-        part_of_term: if pot_codes.is_empty() {
-            None
-        } else {
-            Some(&pot_codes)
-        },
-        attributes: if attr_codes.is_empty() {
-            None
-        } else {
-            Some(&attr_codes)
-        },
-        credit_hour_min: params.credit_hour_min,
-        credit_hour_max: params.credit_hour_max,
-        instructors: if params.instructor.is_empty() {
-            None
-        } else {
-            Some(&params.instructor[..])
-        },
+    let page = match params.cursor.as_deref() {
+        Some(cursor) => data::courses::SearchPage::After(cursor),
+        None => data::courses::SearchPage::Offset(offset),
     };
-
-    let (courses, total_count) = data::courses::search_courses(
+    let results = data::courses::search_courses_page(
         &state.db_pool,
         &filter,
         limit,
-        offset,
+        page,
         params.sort_by,
         params.sort_dir,
     )
     .await
-    .map_err(|e| db_error("Course search", e))?;
+    .map_err(|e| paged_db_error("Course search", e))?;
 
+    let courses = results.courses;
     let course_ids: Vec<i32> = courses.iter().map(|c| c.id).collect();
-    let mut instructor_map =
-        data::courses::get_instructors_for_courses(&state.db_pool, &course_ids)
-            .await
-            .unwrap_or_else(|e| {
-                error!(error = %e, "Failed to fetch instructors for course search");
-                Default::default()
-            });
+    let (instructor_map, linked_map, sparkline_map) = tokio::join!(
+        data::courses::get_instructors_for_courses(&state.db_pool, &course_ids),
+        data::course_details::get_linked_groups_for_courses(&state.db_pool, &course_ids),
+        data::sparklines::get_for_courses(&state.db_pool, &course_ids),
+    );
+    let mut instructor_map = instructor_map.unwrap_or_else(|e| {
+        error!(error = %e, "Failed to fetch instructors for course search");
+        Default::default()
+    });
+    let mut linked_map = linked_map.unwrap_or_else(|e| {
+        error!(error = %e, "Failed to fetch linked sections for course search");
+        Default::default()
+    });
+    let mut sparkline_map = sparkline_map.unwrap_or_else(|e| {
+        error!(error = %e, "Failed to fetch enrollment sparklines for course search");
+        Default::default()
+    });
 
     let course_responses: Vec<CourseResponse> = courses
         .iter()
         .map(|course| {
             let instructors = instructor_map.remove(&course.id).unwrap_or_default();
-            build_course_response(course, instructors)
+            let linked = linked_map.remove(&course.id).unwrap_or_default();
+            build_course_response(course, instructors, linked)
+                .with_enrollment_history(sparkline_map.remove(&course.id))
         })
         .collect();
 
-    let total_count = Count::try_from(total_count)
+    let total_count = Count::try_from(results.total)
         .map_err(|_| ApiError::internal_error("total count overflow"))?;
+    let facets = match results.facets {
+        Some(facets) => Some(build_facets(facets, &*state.reference_cache.read().await)),
+        None => None,
+    };
 
     Ok(with_cache_control(
         SearchResponse {
             courses: course_responses,
             total_count,
+            facets,
+            next_cursor: results.next_cursor,
         },
         cache::SEARCH,
     ))
 }
 
+/// Query params for `GET /api/courses/{term}/{crn}/as-of`.
+#[derive(Deserialize, Serialize, TS, IntoParams)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct CourseAsOfParams {
+    /// RFC 3339 timestamp to reconstruct the course at.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A course's tracked fields as they stood at a past moment.
+#[derive(Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CourseAsOfResponse {
+    crn: String,
+    term_slug: String,
+    as_of: DateTime<Utc>,
+    /// Field values keyed by audit field name (e.g. `enrollment`, `meeting_times`).
+    #[ts(type = "Record<string, JsonValue>")]
+    fields: BTreeMap<String, serde_json::Value>,
+    /// Fields whose value then differs from the current one.
+    changed_fields: Vec<String>,
+    /// When the section was first scraped, if known.
+    first_seen: Option<DateTime<Utc>>,
+}
+
+/// `GET /api/courses/{term}/{crn}/as-of?timestamp=` -- Reconstruct a section
+/// from its audit history.
+#[utoipa::path(
+    get,
+    path = "/courses/{term}/{crn}/as-of",
+    tag = "courses",
+    summary = "A section reconstructed at a past moment",
+    params(
+        ("term" = String, Path, description = "Term code or slug (e.g. 202620 or spring-2026)"),
+        ("crn" = String, Path, description = "Course reference number"),
+        CourseAsOfParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = CourseAsOfResponse),
+    ),
+)]
+pub(super) async fn get_course_as_of(
+    State(state): State<AppState>,
+    Path((term, crn)): Path<(String, String)>,
+    Query(params): Query<CourseAsOfParams>,
+) -> Result<Json<CourseAsOfResponse>, ApiError> {
+    use crate::banner::models::terms::Term;
+    let term_code = Term::resolve_to_code(&term).ok_or_else(|| ApiError::invalid_term(&term))?;
+    let course = data::courses::get_course_by_crn(&state.db_pool, &crn, &term_code)
+        .await
+        .map_err(|e| db_error("Course lookup", e))?
+        .or_not_found("Course", &crn)?;
+
+    let snapshot = data::course_history::course_as_of(&state.db_pool, &course, params.timestamp)
+        .await
+        .map_err(|e| db_error("Course history", e))?
+        .ok_or_else(|| {
+            ApiError::not_found(format!(
+                "Course {crn} was not yet listed at {}",
+                params.timestamp.to_rfc3339()
+            ))
+        })?;
+
+    Ok(Json(CourseAsOfResponse {
+        term_slug: course
+            .term_code
+            .parse::<Term>()
+            .map(|t| t.slug())
+            .unwrap_or_else(|_| course.term_code.clone()),
+        crn: course.crn,
+        as_of: snapshot.as_of,
+        fields: snapshot.fields,
+        changed_fields: snapshot.changed_fields,
+        first_seen: snapshot.first_seen,
+    }))
+}
+
+/// Assemble the detail response for a course already looked up.
+///
+/// Restrictions and corequisites must load, since an empty list would read as
+/// "none". Other secondary lookups are best-effort: a failure logs and leaves
+/// that part empty.
+async fn load_course_detail(
+    state: &AppState,
+    course: &models::Course,
+) -> Result<CourseDetailResponse, ApiError> {
+    let instructors = data::courses::get_course_instructors(&state.db_pool, course.id)
+        .await
+        .unwrap_or_else(|e| {
+            error!(error = %e, course_id = course.id, "Failed to fetch instructors for course");
+            Vec::new()
+        });
+
+    let course_ids = [course.id];
+    let (restrictions, corequisites, linked, grades) = tokio::join!(
+        data::course_details::get_restrictions(&state.db_pool, course.id),
+        data::course_details::get_corequisites(&state.db_pool, course.id),
+        data::course_details::get_linked_groups_for_courses(&state.db_pool, &course_ids),
+        data::grades::get_course_grades(&state.db_pool, &course.subject, &course.course_number),
+    );
+    let restrictions = restrictions.map_err(|e| db_error("Course restrictions", e))?;
+    let corequisites = corequisites.map_err(|e| db_error("Course corequisites", e))?;
+
+    let linked = linked
+        .map(|mut m| m.remove(&course.id).unwrap_or_default())
+        .unwrap_or_else(|e| {
+            error!(error = %e, course_id = course.id, "Failed to fetch linked sections for course");
+            Vec::new()
+        });
+    let grades = grades.unwrap_or_else(|e| {
+        error!(error = %e, course_id = course.id, "Failed to fetch grades for course");
+        None
+    });
+
+    Ok(CourseDetailResponse {
+        course: build_course_response(course, instructors, linked),
+        restrictions,
+        corequisites,
+        grades,
+    })
+}
+
 /// `GET /api/courses/:term/:crn`
+#[utoipa::path(
+    get,
+    path = "/courses/{term}/{crn}",
+    tag = "courses",
+    summary = "One section with full detail",
+    params(
+        ("term" = String, Path, description = "Term code or slug (e.g. 202620 or spring-2026)"),
+        ("crn" = String, Path, description = "Course reference number"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = CourseDetailResponse),
+    ),
+)]
 pub(super) async fn get_course(
     State(state): State<AppState>,
     Path((term, crn)): Path<(String, String)>,
@@ -533,12 +811,13 @@ pub(super) async fn get_course(
         .map_err(|e| db_error("Course lookup", e))?
         .or_not_found("Course", &crn)?;
 
-    // ETag based on term, CRN, and last scrape timestamp
+    // ETag based on term, CRN, and last scrape/detail timestamps
     let etag = format!(
-        "\"c:{}:{}:{}\"",
+        "\"c:{}:{}:{}:{}\"",
         term_code,
         crn,
-        course.last_scraped_at.timestamp()
+        course.last_scraped_at.timestamp(),
+        course.details_scraped_at.map_or(0, |t| t.timestamp())
     );
 
     // 304 Not Modified if client ETag matches
@@ -557,14 +836,7 @@ pub(super) async fn get_course(
         return Ok(resp);
     }
 
-    let instructors = data::courses::get_course_instructors(&state.db_pool, course.id)
-        .await
-        .unwrap_or_else(|e| {
-            error!(error = %e, course_id = course.id, "Failed to fetch instructors for course");
-            Vec::new()
-        });
-
-    let mut resp = Json(build_course_response(&course, instructors)).into_response();
+    let mut resp = Json(load_course_detail(&state, &course).await?).into_response();
     resp.headers_mut().insert(
         axum::http::header::ETAG,
         HeaderValue::from_str(&etag).unwrap(),
@@ -576,9 +848,52 @@ pub(super) async fn get_course(
     Ok(resp)
 }
 
+/// Attach instructors and linked sections to a list of sections.
+async fn build_section_responses(
+    state: &AppState,
+    courses: &[models::Course],
+) -> Vec<CourseResponse> {
+    let course_ids: Vec<i32> = courses.iter().map(|c| c.id).collect();
+    let (instructor_map, linked_map) = tokio::join!(
+        data::courses::get_instructors_for_courses(&state.db_pool, &course_ids),
+        data::course_details::get_linked_groups_for_courses(&state.db_pool, &course_ids),
+    );
+    let mut instructor_map = instructor_map.unwrap_or_else(|e| {
+        error!(error = %e, "Failed to fetch instructors for related sections");
+        Default::default()
+    });
+    let mut linked_map = linked_map.unwrap_or_else(|e| {
+        error!(error = %e, "Failed to fetch linked sections for related sections");
+        Default::default()
+    });
+
+    courses
+        .iter()
+        .map(|course| {
+            let instructors = instructor_map.remove(&course.id).unwrap_or_default();
+            let linked = linked_map.remove(&course.id).unwrap_or_default();
+            build_course_response(course, instructors, linked)
+        })
+        .collect()
+}
+
 /// `GET /api/courses/:term/:subject/:course_number/sections`
 ///
 /// Returns all sections of the same course (same term, subject, course number).
+#[utoipa::path(
+    get,
+    path = "/courses/{term}/{subject}/{course_number}/sections",
+    tag = "courses",
+    summary = "All sections of a course",
+    params(
+        ("term" = String, Path, description = "Term code or slug (e.g. 202620 or spring-2026)"),
+        ("subject" = String, Path, description = "Subject code (e.g. CS)"),
+        ("course_number" = String, Path, description = "Course number (e.g. 3443)"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = Vec<CourseResponse>),
+    ),
+)]
 pub(super) async fn get_related_sections(
     State(state): State<AppState>,
     Path((term, subject, course_number)): Path<(String, String, String)>,
@@ -590,22 +905,96 @@ pub(super) async fn get_related_sections(
             .await
             .map_err(|e| db_error("Related sections lookup", e))?;
 
-    let course_ids: Vec<i32> = courses.iter().map(|c| c.id).collect();
-    let mut instructor_map =
-        data::courses::get_instructors_for_courses(&state.db_pool, &course_ids)
-            .await
-            .unwrap_or_else(|e| {
-                error!(error = %e, "Failed to fetch instructors for related sections");
-                Default::default()
-            });
+    let responses = build_section_responses(&state, &courses).await;
+    Ok(with_cache_control(responses, cache::DETAIL))
+}
 
-    let responses: Vec<CourseResponse> = courses
-        .iter()
-        .map(|course| {
-            let instructors = instructor_map.remove(&course.id).unwrap_or_default();
-            build_course_response(course, instructors)
-        })
-        .collect();
+/// Audit entries included in course page data.
+const PAGE_AUDIT_HIGHLIGHTS: i32 = 10;
 
-    Ok(with_cache_control(responses, cache::DETAIL))
+/// Everything the course page renders, in one response.
+#[derive(Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CoursePageData {
+    course: CourseDetailResponse,
+    /// Every section of the same course in the term, this one included.
+    sections: Vec<CourseResponse>,
+    /// Recent notable changes to this section, newest first.
+    audit_highlights: Vec<AuditLogEntry>,
+    /// Daily enrollment over the last 30 days, oldest first.
+    enrollment_history: Option<Vec<i32>>,
+}
+
+/// `GET /api/courses/:term/:crn/page-data`
+///
+/// Bundles the detail, sibling sections, audit highlights and enrollment
+/// sparkline so SSR renders the course page from a single request.
+#[utoipa::path(
+    get,
+    path = "/courses/{term}/{crn}/page-data",
+    tag = "courses",
+    summary = "Everything the course page renders, in one request",
+    params(
+        ("term" = String, Path, description = "Term code or slug (e.g. 202620 or spring-2026)"),
+        ("crn" = String, Path, description = "Course reference number"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = CoursePageData),
+    ),
+)]
+pub(super) async fn get_course_page_data(
+    State(state): State<AppState>,
+    Path((term, crn)): Path<(String, String)>,
+) -> Result<Response, ApiError> {
+    use crate::banner::models::terms::Term;
+    let term_code = Term::resolve_to_code(&term).ok_or_else(|| ApiError::invalid_term(&term))?;
+    let course = data::courses::get_course_by_crn(&state.db_pool, &crn, &term_code)
+        .await
+        .map_err(|e| db_error("Course lookup", e))?
+        .or_not_found("Course", &crn)?;
+
+    let course_ids = [course.id];
+    let (detail, sections, highlights, sparklines) = tokio::join!(
+        load_course_detail(&state, &course),
+        async {
+            let courses = data::courses::get_related_sections(
+                &state.db_pool,
+                &term_code,
+                &course.subject,
+                &course.course_number,
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(build_section_responses(&state, &courses).await)
+        },
+        data::audit::list_course_highlights(&state.db_pool, course.id, PAGE_AUDIT_HIGHLIGHTS),
+        data::sparklines::get_for_courses(&state.db_pool, &course_ids),
+    );
+    let detail = detail?;
+    let sections = sections.unwrap_or_else(|e| {
+        error!(error = %e, course_id = course.id, "Failed to fetch related sections for course");
+        Vec::new()
+    });
+    let audit_highlights = highlights
+        .map(|rows| rows.into_iter().map(AuditLogEntry::from).collect())
+        .unwrap_or_else(|e| {
+            error!(error = %e, course_id = course.id, "Failed to fetch audit highlights for course");
+            Vec::new()
+        });
+    let enrollment_history = sparklines
+        .map(|mut m| m.remove(&course.id))
+        .unwrap_or_else(|e| {
+            error!(error = %e, course_id = course.id, "Failed to fetch enrollment sparkline for course");
+            None
+        });
+
+    Ok(with_cache_control(
+        CoursePageData {
+            course: detail,
+            sections,
+            audit_highlights,
+            enrollment_history,
+        },
+        cache::DETAIL,
+    ))
 }
@@ -0,0 +1,321 @@
+//! Crawler controls: admin-editable robots.txt rules, user-agent blocking, and
+//! whether structured data is served.
+//!
+//! The policy is persisted as JSON in `app_kv` and cached in memory. The
+//! robots.txt handler renders from it, and [`BotBlockLayer`] rejects requests
+//! whose `User-Agent` matches a blocked pattern.
+//!
+//! [`BotBlockLayer`]: crate::web::middleware::bot_block::BotBlockLayer
+
+use std::sync::RwLock;
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+
+use crate::data::kv;
+
+/// `app_kv` key holding the serialized [`CrawlerConfig`].
+pub const KV_CRAWLER_CONFIG: &str = "crawler.config";
+
+/// A robots.txt group for one user-agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CrawlRule {
+    pub user_agent: String,
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub disallow: Vec<String>,
+}
+
+/// Admin-editable crawler policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CrawlerConfig {
+    pub rules: Vec<CrawlRule>,
+    /// Emitted as `Crawl-delay` under every group when set.
+    #[serde(default)]
+    pub crawl_delay_secs: Option<u32>,
+    /// Case-insensitive substrings; a matching `User-Agent` gets a 403.
+    #[serde(default)]
+    pub blocked_user_agents: Vec<String>,
+    /// Serve schema.org JSON-LD for course and instructor pages, including
+    /// instructor ratings.
+    #[serde(default = "default_structured_data")]
+    pub structured_data: bool,
+}
+
+fn default_structured_data() -> bool {
+    true
+}
+
+impl Default for CrawlerConfig {
+    fn default() -> Self {
+        Self {
+            rules: vec![CrawlRule {
+                user_agent: "*".to_owned(),
+                allow: Vec::new(),
+                disallow: vec!["/api/".to_owned(), "/admin/".to_owned()],
+            }],
+            crawl_delay_secs: None,
+            blocked_user_agents: Vec::new(),
+            structured_data: true,
+        }
+    }
+}
+
+impl CrawlerConfig {
+    /// Check that the config renders to a well-formed robots.txt.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rules.is_empty() {
+            return Err("At least one crawl rule is required".to_owned());
+        }
+        for rule in &self.rules {
+            if rule.user_agent.trim().is_empty() {
+                return Err("Crawl rule user agent must not be empty".to_owned());
+            }
+            // A line break would start a new directive.
+            if rule.user_agent.contains(char::is_control) {
+                return Err("Crawl rule user agent must not contain control characters".to_owned());
+            }
+            if let Some(path) = rule
+                .allow
+                .iter()
+                .chain(&rule.disallow)
+                .find(|p| !p.starts_with('/') || p.contains(char::is_whitespace))
+            {
+                return Err(format!(
+                    "Invalid path '{path}': must start with '/' and contain no whitespace"
+                ));
+            }
+        }
+        if self.blocked_user_agents.iter().any(|p| p.trim().is_empty()) {
+            return Err("Blocked user agent patterns must not be empty".to_owned());
+        }
+        Ok(())
+    }
+
+    /// Render robots.txt, appending a sitemap reference when the public origin is known.
+    pub fn render_robots(&self, public_origin: Option<&str>) -> String {
+        let mut body = String::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            if i > 0 {
+                body.push('\n');
+            }
+            // Configs saved before validation covered this may still hold line breaks.
+            let user_agent: String = rule
+                .user_agent
+                .chars()
+                .filter(|c| !c.is_control())
+                .collect();
+            body.push_str(&format!("User-agent: {}\n", user_agent.trim()));
+            for path in &rule.allow {
+                body.push_str(&format!("Allow: {path}\n"));
+            }
+            for path in &rule.disallow {
+                body.push_str(&format!("Disallow: {path}\n"));
+            }
+            if let Some(delay) = self.crawl_delay_secs {
+                body.push_str(&format!("Crawl-delay: {delay}\n"));
+            }
+        }
+        if let Some(origin) = public_origin {
+            body.push_str(&format!("\nSitemap: {origin}/sitemap.xml\n"));
+        }
+        body
+    }
+}
+
+/// In-memory crawler policy plus per-pattern block counters.
+#[derive(Default)]
+pub struct CrawlerPolicy {
+    inner: RwLock<PolicyInner>,
+    blocked_counts: DashMap<String, u64>,
+}
+
+#[derive(Default)]
+struct PolicyInner {
+    config: CrawlerConfig,
+    /// Lowercased `blocked_user_agents`, index-aligned with the originals.
+    blocked_lower: Vec<String>,
+}
+
+impl CrawlerPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the current config.
+    pub fn config(&self) -> CrawlerConfig {
+        self.inner.read().unwrap().config.clone()
+    }
+
+    /// Swap in a new config. Counters for patterns no longer blocked are dropped.
+    pub fn replace(&self, config: CrawlerConfig) {
+        let blocked_lower = config
+            .blocked_user_agents
+            .iter()
+            .map(|p| p.to_ascii_lowercase())
+            .collect();
+        self.blocked_counts
+            .retain(|pattern, _| config.blocked_user_agents.contains(pattern));
+        *self.inner.write().unwrap() = PolicyInner {
+            config,
+            blocked_lower,
+        };
+    }
+
+    /// Whether instructor JSON-LD is served.
+    pub fn structured_data(&self) -> bool {
+        self.inner.read().unwrap().config.structured_data
+    }
+
+    /// Return the configured pattern matching this user agent, if blocked.
+    ///
+    /// Also increments that pattern's block counter.
+    pub fn check_blocked(&self, user_agent: &str) -> Option<String> {
+        let pattern = {
+            let inner = self.inner.read().unwrap();
+            if inner.blocked_lower.is_empty() {
+                return None;
+            }
+            let ua = user_agent.to_ascii_lowercase();
+            let idx = inner
+                .blocked_lower
+                .iter()
+                .position(|p| ua.contains(p.as_str()))?;
+            inner.config.blocked_user_agents[idx].clone()
+        };
+        *self.blocked_counts.entry(pattern.clone()).or_insert(0) += 1;
+        Some(pattern)
+    }
+
+    /// Requests blocked per pattern since startup (or since the pattern was added).
+    pub fn blocked_counts(&self) -> Vec<(String, u64)> {
+        let mut counts: Vec<_> = self
+            .blocked_counts
+            .iter()
+            .map(|e| (e.key().clone(), *e.value()))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Load the persisted config, falling back to the default when absent.
+    pub async fn load(&self, pool: &PgPool) -> Result<()> {
+        if let Some(raw) = kv::get(pool, KV_CRAWLER_CONFIG).await? {
+            let config: CrawlerConfig =
+                serde_json::from_str(&raw).context("invalid persisted crawler config")?;
+            self.replace(config);
+        }
+        Ok(())
+    }
+
+    /// Persist and apply a new config.
+    pub async fn save(&self, pool: &PgPool, config: CrawlerConfig) -> Result<()> {
+        let raw = serde_json::to_string(&config).context("failed to serialize crawler config")?;
+        kv::set(pool, KV_CRAWLER_CONFIG, &raw).await?;
+        self.replace(config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_renders_legacy_robots() {
+        let body = CrawlerConfig::default().render_robots(Some("https://example.com"));
+        assert_eq!(
+            body,
+            "User-agent: *\nDisallow: /api/\nDisallow: /admin/\n\nSitemap: https://example.com/sitemap.xml\n"
+        );
+    }
+
+    #[test]
+    fn renders_multiple_groups_with_crawl_delay() {
+        let config = CrawlerConfig {
+            rules: vec![
+                CrawlRule {
+                    user_agent: "GPTBot".into(),
+                    allow: vec![],
+                    disallow: vec!["/".into()],
+                },
+                CrawlRule {
+                    user_agent: "*".into(),
+                    allow: vec!["/courses/".into()],
+                    disallow: vec!["/api/".into()],
+                },
+            ],
+            crawl_delay_secs: Some(10),
+            blocked_user_agents: vec![],
+            structured_data: true,
+        };
+        let body = config.render_robots(None);
+        assert_eq!(
+            body,
+            "User-agent: GPTBot\nDisallow: /\nCrawl-delay: 10\n\n\
+             User-agent: *\nAllow: /courses/\nDisallow: /api/\nCrawl-delay: 10\n"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_relative_paths() {
+        let mut config = CrawlerConfig::default();
+        config.rules[0].disallow.push("api".into());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn user_agents_cannot_inject_directives() {
+        let mut config = CrawlerConfig::default();
+        config.rules[0].user_agent = "*\r\nAllow: /admin/".into();
+        assert!(config.validate().is_err());
+        assert_eq!(
+            config.render_robots(None),
+            "User-agent: *Allow: /admin/\nDisallow: /api/\nDisallow: /admin/\n"
+        );
+    }
+
+    #[test]
+    fn blocking_is_case_insensitive_and_counted() {
+        let policy = CrawlerPolicy::new();
+        policy.replace(CrawlerConfig {
+            blocked_user_agents: vec!["BadBot".into()],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            policy.check_blocked("Mozilla/5.0 (compatible; badbot/2.1)"),
+            Some("BadBot".to_owned())
+        );
+        assert_eq!(policy.check_blocked("Mozilla/5.0 Firefox"), None);
+        assert_eq!(policy.blocked_counts(), vec![("BadBot".to_owned(), 1)]);
+    }
+
+    #[test]
+    fn replace_drops_counters_for_removed_patterns() {
+        let policy = CrawlerPolicy::new();
+        policy.replace(CrawlerConfig {
+            blocked_user_agents: vec!["a".into()],
+            ..Default::default()
+        });
+        policy.check_blocked("a");
+        policy.replace(CrawlerConfig::default());
+        assert!(policy.blocked_counts().is_empty());
+        assert_eq!(policy.check_blocked("a"), None);
+    }
+
+    #[test]
+    fn structured_data_defaults_on_for_older_configs() {
+        let config: CrawlerConfig =
+            serde_json::from_str(r#"{"rules":[{"userAgent":"*"}]}"#).unwrap();
+        assert!(config.structured_data);
+    }
+}
@@ -0,0 +1,422 @@
+//! Admin-triggered, time-boxed capture of request/response pairs for one
+//! API route, for reproducing frontend-reported bugs.
+//!
+//! Captures live in memory only and are lost on restart. Everything recorded
+//! passes through redaction first: credential and client-IP headers are
+//! blanked, and JSON fields, query parameters, and strings that look like
+//! personal data are replaced before they reach the buffer.
+//!
+//! [`DebugCaptureLayer`] records matching exchanges.
+//!
+//! [`DebugCaptureLayer`]: crate::web::middleware::debug_capture::DebugCaptureLayer
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::body::{Body, Bytes};
+use axum::http::HeaderMap;
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::data::unsigned::DurationMs;
+
+/// Bodies larger than this are not recorded.
+pub const MAX_CAPTURED_BODY: usize = 64 * 1024;
+
+/// How long a finished capture's entries stay readable.
+const RETENTION_AFTER_EXPIRY: Duration = Duration::hours(1);
+
+const REDACTED: &str = "[redacted]";
+
+/// Headers whose values are never recorded.
+const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-internal-token",
+    "forwarded",
+    "x-forwarded-for",
+    "x-real-ip",
+    "cf-connecting-ip",
+    "true-client-ip",
+];
+
+/// JSON fields and query parameters whose values are never recorded,
+/// compared lowercase with `_` and `-` removed.
+const REDACTED_FIELDS: &[&str] = &[
+    "email",
+    "discordid",
+    "discordusername",
+    "username",
+    "avatarhash",
+    "userid",
+    "webhookurl",
+    "secret",
+    "token",
+    "password",
+    "ip",
+    "clientip",
+];
+
+/// One recorded request/response pair, already redacted.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CapturedExchange {
+    pub at: DateTime<Utc>,
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub request_headers: BTreeMap<String, String>,
+    /// `null` when the request had no body; a placeholder when it was too
+    /// large or not text.
+    pub request_body: Option<String>,
+    pub status: u16,
+    pub response_headers: BTreeMap<String, String>,
+    pub response_body: Option<String>,
+    pub duration_ms: DurationMs,
+}
+
+/// A capture window on one route and what it has recorded so far.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DebugCapture {
+    /// Exact request path, e.g. `/api/courses/search`.
+    pub path: String,
+    pub started_at: DateTime<Utc>,
+    /// Recording stops at this time; entries remain readable for an hour.
+    pub expires_at: DateTime<Utc>,
+    /// Recording also stops once this many exchanges are held.
+    pub max_entries: u32,
+    pub entries: Vec<CapturedExchange>,
+}
+
+impl DebugCapture {
+    fn is_recording(&self, now: DateTime<Utc>) -> bool {
+        now < self.expires_at && self.entries.len() < self.max_entries as usize
+    }
+}
+
+/// Active and recently finished captures.
+#[derive(Default)]
+pub struct DebugCaptures {
+    captures: Mutex<Vec<DebugCapture>>,
+    /// Lets the middleware skip the lock when nothing is being captured.
+    any: AtomicBool,
+}
+
+impl DebugCaptures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start capturing `path`, replacing any earlier capture of it.
+    pub fn start(&self, path: String, duration: Duration, max_entries: u32) -> DebugCapture {
+        let now = Utc::now();
+        let capture = DebugCapture {
+            path,
+            started_at: now,
+            expires_at: now + duration,
+            max_entries,
+            entries: Vec::new(),
+        };
+        let mut captures = self.captures.lock().unwrap();
+        captures.retain(|c| c.path != capture.path);
+        captures.push(capture.clone());
+        self.any.store(true, Ordering::Release);
+        capture
+    }
+
+    /// Drop the capture of `path`. Returns false if there was none.
+    pub fn stop(&self, path: &str) -> bool {
+        let mut captures = self.captures.lock().unwrap();
+        let before = captures.len();
+        captures.retain(|c| c.path != path);
+        self.any.store(!captures.is_empty(), Ordering::Release);
+        captures.len() != before
+    }
+
+    /// All captures still within their retention window.
+    pub fn list(&self) -> Vec<DebugCapture> {
+        let mut captures = self.captures.lock().unwrap();
+        let now = Utc::now();
+        captures.retain(|c| c.expires_at + RETENTION_AFTER_EXPIRY > now);
+        self.any.store(!captures.is_empty(), Ordering::Release);
+        captures.clone()
+    }
+
+    /// Whether a request to `path` should be recorded.
+    pub(crate) fn is_recording(&self, path: &str) -> bool {
+        if !self.any.load(Ordering::Acquire) {
+            return false;
+        }
+        let now = Utc::now();
+        self.captures
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| c.path == path && c.is_recording(now))
+    }
+
+    /// Append an exchange to its route's capture, if still recording.
+    pub(crate) fn record(&self, exchange: CapturedExchange) {
+        let now = Utc::now();
+        let mut captures = self.captures.lock().unwrap();
+        if let Some(capture) = captures
+            .iter_mut()
+            .find(|c| c.path == exchange.path && c.is_recording(now))
+        {
+            capture.entries.push(exchange);
+        }
+    }
+}
+
+/// Whether `path` may be captured. Admin and auth routes carry credentials
+/// and user records, so they're off limits.
+pub fn is_capturable(path: &str) -> bool {
+    path.starts_with("/api/") && !path.starts_with("/api/admin/") && !path.starts_with("/api/auth/")
+}
+
+fn normalize_field(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_redacted_field(name: &str) -> bool {
+    REDACTED_FIELDS.contains(&normalize_field(name).as_str())
+}
+
+/// Loose email check: something before an `@` and a dotted domain after it.
+fn looks_like_email(s: &str) -> bool {
+    s.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty() && !local.contains(' ') && domain.contains('.') && !domain.contains(' ')
+    })
+}
+
+/// Copy headers, blanking credentials and client addresses.
+pub(crate) fn redact_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str().to_owned();
+            let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_owned()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+/// Redact a `key=value&...` string (query or form body).
+pub(crate) fn redact_query(query: &str) -> String {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => {
+                let decoded = urlencoding::decode(value).unwrap_or_default();
+                if is_redacted_field(key) || looks_like_email(&decoded) {
+                    format!("{key}={REDACTED}")
+                } else {
+                    pair.to_owned()
+                }
+            }
+            None => pair.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+pub(crate) fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_redacted_field(key) && !value.is_null() {
+                    *value = serde_json::Value::String(REDACTED.to_owned());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        serde_json::Value::String(s) if looks_like_email(s) => *s = REDACTED.to_owned(),
+        _ => {}
+    }
+}
+
+/// Render a body for the capture log, redacted according to its content type.
+pub(crate) fn redact_body(content_type: Option<&str>, body: &[u8]) -> Option<String> {
+    if body.is_empty() {
+        return None;
+    }
+    let mime = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|m| m.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if mime == "application/json" || mime.ends_with("+json") {
+        return Some(match serde_json::from_slice::<serde_json::Value>(body) {
+            Ok(mut json) => {
+                redact_json(&mut json);
+                json.to_string()
+            }
+            Err(_) => format!("[invalid JSON: {} bytes]", body.len()),
+        });
+    }
+    if mime == "application/x-www-form-urlencoded" {
+        return Some(redact_query(&String::from_utf8_lossy(body)));
+    }
+    if mime.starts_with("text/") {
+        // Free text can't be field-redacted; scrub anything email-shaped.
+        let text = String::from_utf8_lossy(body);
+        let scrubbed: Vec<&str> = text
+            .split(' ')
+            .map(|word| {
+                if looks_like_email(word) {
+                    REDACTED
+                } else {
+                    word
+                }
+            })
+            .collect();
+        return Some(scrubbed.join(" "));
+    }
+    Some(format!(
+        "[{} bytes of {}]",
+        body.len(),
+        mime_or_unknown(&mime)
+    ))
+}
+
+fn mime_or_unknown(mime: &str) -> &str {
+    if mime.is_empty() {
+        "unknown type"
+    } else {
+        mime
+    }
+}
+
+/// Placeholder for a body that wasn't buffered.
+pub(crate) fn omitted_body(len: Option<u64>) -> Option<String> {
+    Some(match len {
+        Some(len) => format!("[omitted: {len} bytes]"),
+        None => "[omitted: streamed body]".to_owned(),
+    })
+}
+
+/// Read a body whose exact length already fits [`MAX_CAPTURED_BODY`].
+///
+/// If the stream fails part-way, the error is a replacement body that
+/// replays the chunks already read and then fails the same way, so the
+/// client sees exactly what it would have without buffering.
+pub(crate) async fn buffer_body(body: Body) -> Result<Bytes, Body> {
+    let mut stream = body.into_data_stream();
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        match chunk {
+            Ok(chunk) => chunks.push(chunk),
+            Err(e) => {
+                let replay = chunks.into_iter().map(Ok).chain(std::iter::once(Err(e)));
+                return Err(Body::from_stream(futures::stream::iter(replay)));
+            }
+        }
+    }
+    Ok(chunks.concat().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_redacts_sensitive_fields_and_emails() {
+        let body = br#"{"discordId":"123","name":"Jane","contact":"jane@utsa.edu","nested":[{"webhook_url":"https://x.test"}],"email":null}"#;
+        let out = redact_body(Some("application/json; charset=utf-8"), body).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(json["discordId"], REDACTED);
+        assert_eq!(json["name"], "Jane");
+        assert_eq!(json["contact"], REDACTED);
+        assert_eq!(json["nested"][0]["webhook_url"], REDACTED);
+        assert!(json["email"].is_null());
+    }
+
+    #[test]
+    fn query_and_headers_are_redacted() {
+        assert_eq!(
+            redact_query("q=calc&email=a%40b.co&token=abc&x"),
+            "q=calc&email=[redacted]&token=[redacted]&x"
+        );
+        assert_eq!(
+            redact_query("instructor=jane%40utsa.edu"),
+            "instructor=[redacted]"
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("cookie", "session=abc".parse().unwrap());
+        headers.insert("x-forwarded-for", "203.0.113.9".parse().unwrap());
+        headers.insert("accept", "application/json".parse().unwrap());
+        let out = redact_headers(&headers);
+        assert_eq!(out["cookie"], REDACTED);
+        assert_eq!(out["x-forwarded-for"], REDACTED);
+        assert_eq!(out["accept"], "application/json");
+    }
+
+    #[test]
+    fn capture_stops_at_entry_limit() {
+        let captures = DebugCaptures::new();
+        captures.start("/api/terms".into(), Duration::minutes(5), 1);
+        assert!(captures.is_recording("/api/terms"));
+        assert!(!captures.is_recording("/api/subjects"));
+
+        let exchange = CapturedExchange {
+            at: Utc::now(),
+            method: "GET".into(),
+            path: "/api/terms".into(),
+            query: None,
+            request_headers: BTreeMap::new(),
+            request_body: None,
+            status: 200,
+            response_headers: BTreeMap::new(),
+            response_body: None,
+            duration_ms: DurationMs::new(3),
+        };
+        captures.record(exchange);
+        assert!(!captures.is_recording("/api/terms"));
+        assert_eq!(captures.list()[0].entries.len(), 1);
+
+        assert!(captures.stop("/api/terms"));
+        assert!(captures.list().is_empty());
+    }
+
+    #[test]
+    fn admin_and_auth_routes_are_not_capturable() {
+        assert!(is_capturable("/api/courses/search"));
+        assert!(!is_capturable("/api/admin/users"));
+        assert!(!is_capturable("/api/auth/me"));
+        assert!(!is_capturable("/courses"));
+    }
+
+    #[tokio::test]
+    async fn failed_reads_replay_the_body_unchanged() {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from_static(b"par")),
+            Ok(Bytes::from_static(b"tial")),
+            Err(std::io::Error::other("reset")),
+        ];
+        let body = Body::from_stream(futures::stream::iter(chunks));
+
+        let replay = buffer_body(body).await.expect_err("read should fail");
+        let mut stream = replay.into_data_stream();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "par");
+        assert_eq!(stream.next().await.unwrap().unwrap(), "tial");
+        assert!(stream.next().await.unwrap().is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Embeddable instructor rating widget.
+//!
+//! `GET /embed/instructors/{slug}` renders a self-contained badge with the
+//! instructor's composite rating for other student sites to drop into an
+//! `<iframe>` (HTML, the default) or an `<img>` (`?format=svg`). Responses
+//! carry no scripts, only inline styles, and a CSP that allows framing from
+//! anywhere but nothing else.
+
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use html_escape::encode_quoted_attribute;
+use serde::Deserialize;
+
+use crate::data;
+use crate::data::course_types::InstructorRating;
+use crate::data::instructors::InstructorRatingSummary;
+use crate::state::AppState;
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+
+/// Ratings only change when scores are recomputed, so edges can hold these
+/// far longer than the JSON detail endpoints.
+const CACHE_CONTROL: &str = "public, max-age=3600, s-maxage=86400, stale-while-revalidate=3600";
+
+const HTML_CSP: &str = "default-src 'none'; style-src 'unsafe-inline'; base-uri 'none'; form-action 'none'; frame-ancestors *";
+const SVG_CSP: &str = "default-src 'none'; style-src 'unsafe-inline'; frame-ancestors *";
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbedFormat {
+    #[default]
+    Html,
+    Svg,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmbedParams {
+    #[serde(default)]
+    pub format: EmbedFormat,
+}
+
+/// `GET /embed/instructors/{slug}?format=html|svg` -- Composite rating badge.
+pub async fn instructor_widget(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(params): Query<EmbedParams>,
+) -> Result<Response, ApiError> {
+    let summary = data::instructors::get_instructor_rating_by_slug(&state.db_pool, &slug)
+        .await
+        .map_err(|e| db_error("Get instructor rating", e))?
+        .or_not_found("Instructor", &slug)?;
+
+    let profile_url = format!(
+        "{}/instructors/{}",
+        state.public_origin.as_deref().unwrap_or_default(),
+        summary.slug
+    );
+
+    let (content_type, csp, body) = match params.format {
+        EmbedFormat::Html => (
+            "text/html; charset=utf-8",
+            HTML_CSP,
+            render_html(&summary, &profile_url),
+        ),
+        EmbedFormat::Svg => ("image/svg+xml", SVG_CSP, render_svg(&summary)),
+    };
+
+    let mut response = body.into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(
+        header::CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static(csp),
+    );
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(CACHE_CONTROL),
+    );
+    Ok(response)
+}
+
+/// Headline text and caption for a rating, e.g. `("4.3", "from 212 ratings")`.
+fn rating_text(rating: Option<&InstructorRating>) -> (String, String) {
+    match rating {
+        Some(r) => {
+            let noun = if r.total_responses == 1 {
+                "rating"
+            } else {
+                "ratings"
+            };
+            (
+                format!("{:.1}", r.score),
+                format!("from {} {noun}", r.total_responses),
+            )
+        }
+        None => ("--".to_owned(), "not yet rated".to_owned()),
+    }
+}
+
+/// Width of the 0-5 rating bar, in percent.
+fn bar_percent(rating: Option<&InstructorRating>) -> f32 {
+    rating.map_or(0.0, |r| (r.score / 5.0 * 100.0).clamp(0.0, 100.0))
+}
+
+fn render_html(summary: &InstructorRatingSummary, profile_url: &str) -> String {
+    let rating = summary.rating.as_ref();
+    let (score, caption) = rating_text(rating);
+    format!(
+        r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="robots" content="noindex">
+<title>{name} rating</title>
+<style>
+body{{margin:0;font:14px/1.3 system-ui,sans-serif;color:#111;background:#fff}}
+a{{display:block;padding:10px 12px;color:inherit;text-decoration:none;border:1px solid #ddd;border-radius:8px}}
+.name{{font-weight:600;white-space:nowrap;overflow:hidden;text-overflow:ellipsis}}
+.score{{font-size:22px;font-weight:700}}
+.caption{{color:#666;font-size:12px}}
+.bar{{height:4px;margin-top:6px;background:#eee;border-radius:2px}}
+.bar span{{display:block;height:100%;width:{bar:.0}%;background:#f59e0b;border-radius:2px}}
+@media (prefers-color-scheme:dark){{body{{color:#eee;background:#111}}a{{border-color:#333}}.caption{{color:#999}}.bar{{background:#333}}}}
+</style>
+</head>
+<body>
+<a href="{url}" target="_blank" rel="noopener">
+<div class="name">{name}</div>
+<div><span class="score">{score}</span> <span class="caption">/ 5 &middot; {caption}</span></div>
+<div class="bar"><span></span></div>
+</a>
+</body>
+</html>
+"#,
+        name = encode_quoted_attribute(&summary.display_name),
+        url = encode_quoted_attribute(profile_url),
+        bar = bar_percent(rating),
+    )
+}
+
+fn render_svg(summary: &InstructorRatingSummary) -> String {
+    let rating = summary.rating.as_ref();
+    let (score, caption) = rating_text(rating);
+    let bar_width = bar_percent(rating) / 100.0 * 216.0;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="240" height="72" viewBox="0 0 240 72" role="img" aria-label="{name}: {score} / 5">
+<style>text{{font-family:system-ui,sans-serif;fill:#111}}.caption{{fill:#666;font-size:11px}}</style>
+<rect x="0.5" y="0.5" width="239" height="71" rx="8" fill="#fff" stroke="#ddd"/>
+<text x="12" y="22" font-size="13" font-weight="600">{name}</text>
+<text x="12" y="48" font-size="20" font-weight="700">{score}</text>
+<text x="52" y="47" class="caption">/ 5 &#183; {caption}</text>
+<rect x="12" y="58" width="216" height="4" rx="2" fill="#eee"/>
+<rect x="12" y="58" width="{bar_width:.1}" height="4" rx="2" fill="#f59e0b"/>
+</svg>
+"##,
+        // Also lands in `aria-label`, so quotes are escaped along with markup.
+        name = encode_quoted_attribute(&summary.display_name),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::course_types::RatingSource;
+
+    fn summary(name: &str, score: Option<f32>) -> InstructorRatingSummary {
+        InstructorRatingSummary {
+            slug: "jane-doe".into(),
+            display_name: name.into(),
+            rating: score.map(|score| InstructorRating {
+                score,
+                rank_score: score - 0.5,
+                ci_lower: score - 0.5,
+                ci_upper: score + 0.3,
+                confidence: 0.8,
+                source: RatingSource::Both,
+                total_responses: 42,
+            }),
+        }
+    }
+
+    #[test]
+    fn escapes_names() {
+        let s = summary("<script>alert(1)</script>", Some(4.3));
+        let html = render_html(&s, "https://example.test/instructors/jane-doe");
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!render_svg(&s).contains("<script>"));
+    }
+
+    #[test]
+    fn escapes_quotes_in_attributes() {
+        let svg = render_svg(&summary("Jane \"O'Neil\" Doe", Some(4.3)));
+        assert!(svg.contains(r#"aria-label="Jane &quot;O&#x27;Neil&quot; Doe: 4.3 / 5""#));
+    }
+
+    #[test]
+    fn renders_score_and_placeholder() {
+        let rated = render_svg(&summary("Jane Doe", Some(4.3)));
+        assert!(rated.contains(">4.3<"));
+        assert!(rated.contains("from 42 ratings"));
+
+        let unrated = render_html(&summary("Jane Doe", None), "/instructors/jane-doe");
+        assert!(unrated.contains("not yet rated"));
+        assert!(unrated.contains("width:0%"));
+    }
+}
@@ -1,13 +1,17 @@
 //! Standardized API error responses.
 
 use axum::Json;
-use axum::http::StatusCode;
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
 use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::data::cursor::CursorError;
 
 /// Machine-readable error code for API responses.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS, ToSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[ts(export)]
 pub enum ApiErrorCode {
@@ -21,10 +25,11 @@ pub enum ApiErrorCode {
     Forbidden,
     NoTerms,
     RateLimited,
+    Maintenance,
 }
 
 /// Standardized error response for all API endpoints.
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct ApiError {
@@ -83,6 +88,14 @@ impl ApiError {
         }
     }
 
+    pub fn maintenance(message: impl Into<String>, retry_after_secs: u64) -> Self {
+        Self {
+            code: ApiErrorCode::Maintenance,
+            message: message.into(),
+            details: Some(serde_json::json!({ "retryAfter": retry_after_secs })),
+        }
+    }
+
     fn status_code(&self) -> StatusCode {
         match self.code {
             ApiErrorCode::NotFound => StatusCode::NOT_FOUND,
@@ -95,14 +108,27 @@ impl ApiError {
             ApiErrorCode::Forbidden => StatusCode::FORBIDDEN,
             ApiErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
             ApiErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ApiErrorCode::Maintenance => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 }
 
 impl IntoResponse for ApiError {
+    /// Errors that carry `details.retryAfter` also send it as `Retry-After`.
     fn into_response(self) -> Response {
         let status = self.status_code();
-        (status, Json(self)).into_response()
+        let retry_after = self
+            .details
+            .as_ref()
+            .and_then(|d| d.get("retryAfter"))
+            .and_then(serde_json::Value::as_u64);
+        let mut response = (status, Json(self)).into_response();
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
     }
 }
 
@@ -127,6 +153,14 @@ pub fn db_error(context: &str, error: anyhow::Error) -> ApiError {
     ApiError::internal_error(format!("{} failed", context))
 }
 
+/// Like [`db_error`], but a malformed pagination cursor is a 400.
+pub fn paged_db_error(context: &str, error: anyhow::Error) -> ApiError {
+    match error.downcast_ref::<CursorError>() {
+        Some(e) => ApiError::bad_request(e.to_string()),
+        None => db_error(context, error),
+    }
+}
+
 /// Extension trait for `Option<T>` to convert `None` into a 404 [`ApiError`].
 pub trait OptionNotFoundExt<T> {
     /// Convert `None` into [`ApiError::not_found`] with a message like
@@ -160,3 +194,21 @@ impl<T> SqlxResultExt<T> for Result<T, sqlx::Error> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_after_is_sent_as_a_header() {
+        let response = ApiError::rate_limited(30).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "30");
+
+        let response = ApiError::maintenance("down", 300).into_response();
+        assert_eq!(response.headers()[RETRY_AFTER], "300");
+
+        let response = ApiError::not_found("gone").into_response();
+        assert!(!response.headers().contains_key(RETRY_AFTER));
+    }
+}
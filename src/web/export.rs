@@ -0,0 +1,415 @@
+//! Streaming bulk exports as CSV or JSON Lines.
+//!
+//! Records are serialized on a spawned task and handed to the response body
+//! in chunks, so exporting a whole term never holds the dataset in memory. A
+//! client that disconnects closes the channel, which stops the producer at
+//! its next write. At most [`MAX_CONCURRENT_EXPORTS`] run at once.
+
+use std::borrow::Cow;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::{Arc, LazyLock};
+
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tokio::sync::{Semaphore, mpsc};
+use tracing::{debug, warn};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::banner::models::terms::Term;
+use crate::data::courses::{CourseExportKey, CourseExportRow, course_export_page};
+use crate::state::AppState;
+use crate::web::error::ApiError;
+
+/// Serialized bytes buffered before a chunk is sent to the client.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Chunks queued ahead of a slow client before the producer waits.
+const CHANNEL_DEPTH: usize = 8;
+
+/// Exports producing at once, across every export endpoint. Bounds the
+/// spawned producers and the pooled connections they borrow per page.
+const MAX_CONCURRENT_EXPORTS: usize = 4;
+
+/// Suggested wait before retrying when every export slot is taken.
+const EXPORT_BUSY_RETRY_SECS: u64 = 30;
+
+/// Sections read per query by the course export.
+const COURSE_PAGE_SIZE: i64 = 500;
+
+static EXPORT_SLOTS: LazyLock<Arc<Semaphore>> =
+    LazyLock::new(|| Arc::new(Semaphore::new(MAX_CONCURRENT_EXPORTS)));
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+impl ExportFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// A row type that can be exported in either format.
+///
+/// JSON Lines output uses the `Serialize` impl; CSV uses [`COLUMNS`] as the
+/// header and [`csv_fields`] for each line.
+///
+/// [`COLUMNS`]: ExportRecord::COLUMNS
+/// [`csv_fields`]: ExportRecord::csv_fields
+pub trait ExportRecord: Serialize {
+    const COLUMNS: &'static [&'static str];
+
+    /// Field values in [`COLUMNS`](ExportRecord::COLUMNS) order, unescaped.
+    fn csv_fields(&self) -> Vec<String>;
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break.
+///
+/// Text a spreadsheet would evaluate as a formula (leading `=`, `+`, `-`,
+/// `@`, tab, or carriage return) is prefixed with `'` so it opens as text.
+/// Plain numbers like `-1.5` are left alone.
+pub(crate) fn csv_escape(field: &str) -> Cow<'_, str> {
+    let field: Cow<'_, str> = if is_formula(field) {
+        format!("'{field}").into()
+    } else {
+        field.into()
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field
+    }
+}
+
+fn is_formula(field: &str) -> bool {
+    field.starts_with(['=', '+', '-', '@', '\t', '\r']) && field.parse::<f64>().is_err()
+}
+
+fn push_csv_line<'a>(buf: &mut String, fields: impl IntoIterator<Item = &'a str>) {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            buf.push(',');
+        }
+        buf.push_str(&csv_escape(field));
+    }
+    buf.push_str("\r\n");
+}
+
+/// Writes records into a streaming export response.
+pub struct ExportWriter<R> {
+    format: ExportFormat,
+    tx: mpsc::Sender<std::io::Result<String>>,
+    buf: String,
+    _record: PhantomData<fn(&R)>,
+}
+
+impl<R: ExportRecord> ExportWriter<R> {
+    fn new(format: ExportFormat, tx: mpsc::Sender<std::io::Result<String>>) -> Self {
+        let mut buf = String::with_capacity(CHUNK_SIZE);
+        if format == ExportFormat::Csv {
+            push_csv_line(&mut buf, R::COLUMNS.iter().copied());
+        }
+        Self {
+            format,
+            tx,
+            buf,
+            _record: PhantomData,
+        }
+    }
+
+    /// Append one record. Fails once the client has gone away.
+    pub async fn write(&mut self, record: &R) -> anyhow::Result<()> {
+        match self.format {
+            ExportFormat::Csv => {
+                let fields = record.csv_fields();
+                push_csv_line(&mut self.buf, fields.iter().map(String::as_str));
+            }
+            ExportFormat::Jsonl => {
+                self.buf.push_str(&serde_json::to_string(record)?);
+                self.buf.push('\n');
+            }
+        }
+        if self.buf.len() >= CHUNK_SIZE {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> anyhow::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buf, String::with_capacity(CHUNK_SIZE));
+        self.tx
+            .send(Ok(chunk))
+            .await
+            .map_err(|_| anyhow::anyhow!("export client disconnected"))
+    }
+
+    /// Send whatever is still buffered. Must be called once all records are written.
+    pub async fn finish(mut self) -> anyhow::Result<()> {
+        self.flush().await
+    }
+}
+
+/// Build a streaming download response and run `produce` on a spawned task
+/// to fill it.
+///
+/// If `produce` fails partway, the body is aborted so the client sees a
+/// truncated transfer rather than a file that looks complete. Fails with 429
+/// when [`MAX_CONCURRENT_EXPORTS`] are already running.
+pub fn export_response<R, F, Fut>(
+    format: ExportFormat,
+    file_stem: &str,
+    produce: F,
+) -> Result<Response, ApiError>
+where
+    R: ExportRecord + 'static,
+    F: FnOnce(ExportWriter<R>) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+    let permit = EXPORT_SLOTS
+        .clone()
+        .try_acquire_owned()
+        .map_err(|_| ApiError::rate_limited(EXPORT_BUSY_RETRY_SECS))?;
+    let (tx, mut rx) = mpsc::channel(CHANNEL_DEPTH);
+    let error_tx = tx.clone();
+    let task = produce(ExportWriter::new(format, tx));
+    tokio::spawn(async move {
+        let _permit = permit;
+        if let Err(e) = task.await {
+            if error_tx.is_closed() {
+                debug!(error = %e, "export abandoned by client");
+            } else {
+                warn!(error = ?e, "export failed");
+                let _ = error_tx.send(Err(std::io::Error::other(e))).await;
+            }
+        }
+    });
+
+    let body = Body::from_stream(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)));
+    let disposition = format!(
+        "attachment; filename=\"{file_stem}.{}\"",
+        format.extension()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_owned()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+impl ExportRecord for CourseExportRow {
+    const COLUMNS: &'static [&'static str] = &[
+        "term_code",
+        "crn",
+        "subject",
+        "course_number",
+        "sequence_number",
+        "title",
+        "credit_hours",
+        "credit_hour_low",
+        "credit_hour_high",
+        "instructional_method",
+        "campus",
+        "part_of_term",
+        "enrollment",
+        "max_enrollment",
+        "wait_count",
+        "wait_capacity",
+        "instructors",
+        "meeting_times",
+        "attributes",
+        "last_scraped_at",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        let opt = |v: &Option<String>| v.clone().unwrap_or_default();
+        let num = |v: Option<f64>| v.map(|n| n.to_string()).unwrap_or_default();
+        vec![
+            self.term_code.clone(),
+            self.crn.clone(),
+            self.subject.clone(),
+            self.course_number.clone(),
+            opt(&self.sequence_number),
+            self.title.clone(),
+            num(self.credit_hours),
+            num(self.credit_hour_low),
+            num(self.credit_hour_high),
+            opt(&self.instructional_method),
+            opt(&self.campus),
+            opt(&self.part_of_term),
+            self.enrollment.to_string(),
+            self.max_enrollment.to_string(),
+            self.wait_count.to_string(),
+            self.wait_capacity.to_string(),
+            self.instructors.join("; "),
+            self.meeting_times.to_string(),
+            self.attributes.to_string(),
+            self.last_scraped_at.to_rfc3339(),
+        ]
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CourseExportParams {
+    pub term: String,
+    #[serde(default)]
+    #[param(inline)]
+    pub format: ExportFormat,
+}
+
+/// `GET /api/courses/export?term=&format=csv|jsonl`
+///
+/// Every section of a term in one download. Nested values (meeting times,
+/// attributes) are JSON-encoded strings in CSV output.
+#[utoipa::path(
+    get,
+    path = "/courses/export",
+    tag = "courses",
+    summary = "Export a term's sections as CSV or JSON Lines",
+    params(
+        CourseExportParams,
+    ),
+    responses(
+        (status = 200, description = "One row per section", content(
+            (String = "text/csv"),
+            (String = "application/x-ndjson"),
+        )),
+    ),
+)]
+pub async fn export_courses(
+    State(state): State<AppState>,
+    Query(params): Query<CourseExportParams>,
+) -> Result<Response, ApiError> {
+    let term_code =
+        Term::resolve_to_code(&params.term).ok_or_else(|| ApiError::invalid_term(&params.term))?;
+    let file_stem = format!("courses-{term_code}");
+    let pool = state.db_pool.clone();
+
+    export_response(params.format, &file_stem, move |mut out| async move {
+        write_course_pages(&pool, Some(&term_code), &mut out).await?;
+        out.finish().await
+    })
+}
+
+/// Write every section of a term (or of every term) to `out`, a page at a time.
+pub(crate) async fn write_course_pages(
+    pool: &PgPool,
+    term_code: Option<&str>,
+    out: &mut ExportWriter<CourseExportRow>,
+) -> anyhow::Result<()> {
+    let mut after: Option<CourseExportKey> = None;
+    loop {
+        let page = course_export_page(pool, term_code, after.as_ref(), COURSE_PAGE_SIZE).await?;
+        for row in &page {
+            out.write(row).await?;
+        }
+        match page.last() {
+            Some(last) if page.len() as i64 == COURSE_PAGE_SIZE => after = Some(last.export_key()),
+            _ => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Pair {
+        name: String,
+        note: String,
+    }
+
+    impl ExportRecord for Pair {
+        const COLUMNS: &'static [&'static str] = &["name", "note"];
+
+        fn csv_fields(&self) -> Vec<String> {
+            vec![self.name.clone(), self.note.clone()]
+        }
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_escape("CS 1083"), "CS 1083");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn csv_formulas_are_neutralized() {
+        assert_eq!(
+            csv_escape("=HYPERLINK(\"x\")"),
+            "\"'=HYPERLINK(\"\"x\"\")\""
+        );
+        assert_eq!(csv_escape("+1+2"), "'+1+2");
+        assert_eq!(csv_escape("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_escape("-2+3"), "'-2+3");
+        assert_eq!(csv_escape("-1.5"), "-1.5");
+        assert_eq!(csv_escape("a=b"), "a=b");
+    }
+
+    #[tokio::test]
+    async fn writes_header_then_records() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut out = ExportWriter::<Pair>::new(ExportFormat::Csv, tx);
+        out.write(&Pair {
+            name: "Smith, J".into(),
+            note: "ok".into(),
+        })
+        .await
+        .unwrap();
+        out.finish().await.unwrap();
+        let chunk = rx.recv().await.unwrap().unwrap();
+        assert_eq!(chunk, "name,note\r\n\"Smith, J\",ok\r\n");
+    }
+
+    #[tokio::test]
+    async fn jsonl_has_no_header() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut out = ExportWriter::<Pair>::new(ExportFormat::Jsonl, tx);
+        out.write(&Pair {
+            name: "a".into(),
+            note: "b".into(),
+        })
+        .await
+        .unwrap();
+        out.finish().await.unwrap();
+        let chunk = rx.recv().await.unwrap().unwrap();
+        assert_eq!(chunk, "{\"name\":\"a\",\"note\":\"b\"}\n");
+    }
+
+    #[test]
+    fn format_defaults_to_csv() {
+        let params: CourseExportParams = serde_json::from_str(r#"{"term":"202620"}"#).unwrap();
+        assert_eq!(params.format, ExportFormat::Csv);
+        let params: CourseExportParams =
+            serde_json::from_str(r#"{"term":"202620","format":"jsonl"}"#).unwrap();
+        assert_eq!(params.format, ExportFormat::Jsonl);
+    }
+}
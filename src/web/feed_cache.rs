@@ -0,0 +1,109 @@
+//! TTL cache for pre-rendered Atom feeds.
+//!
+//! Same shape as `SitemapCache`: DashMap entries with a 15-minute TTL and
+//! singleflight dedup per feed, so a burst of feed readers polling at once
+//! renders each feed a single time. At most [`MAX_ENTRIES`] feeds are held;
+//! expired ones are dropped first, then the oldest.
+
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+const TTL: Duration = Duration::from_secs(15 * 60);
+/// Feeds held at once, enough for every subject of a few terms.
+const MAX_ENTRIES: usize = 2048;
+
+#[derive(Clone, Default)]
+pub struct FeedCache {
+    entries: Arc<DashMap<String, (Instant, Arc<String>)>>,
+    inflight: Arc<DashMap<String, Arc<AtomicBool>>>,
+}
+
+impl FeedCache {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return a cached feed if it exists and is fresh.
+    pub(crate) fn get(&self, key: &str) -> Option<Arc<String>> {
+        let entry = self.entries.get(key)?;
+        let (cached_at, ref value) = *entry;
+        if cached_at.elapsed() < TTL {
+            Some(value.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Return a cached feed even if stale (for singleflight contention fallback).
+    pub(crate) fn get_stale(&self, key: &str) -> Option<Arc<String>> {
+        self.entries.get(key).map(|e| e.1.clone())
+    }
+
+    /// Store a freshly rendered feed, returning the shared copy.
+    pub(crate) fn insert(&self, key: String, value: String) -> Arc<String> {
+        if !self.entries.contains_key(&key) && self.entries.len() >= MAX_ENTRIES {
+            self.evict();
+        }
+        let value = Arc::new(value);
+        self.entries.insert(key, (Instant::now(), value.clone()));
+        value
+    }
+
+    /// Make room for one entry: drop expired feeds, or the oldest if none are.
+    fn evict(&self) {
+        self.entries
+            .retain(|_, (cached_at, _)| cached_at.elapsed() < TTL);
+        if self.entries.len() < MAX_ENTRIES {
+            return;
+        }
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|e| e.value().0)
+            .map(|e| e.key().clone());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+
+    /// Try to claim the singleflight slot for a key.
+    /// Returns `true` if this caller should render the feed.
+    pub(crate) fn try_claim(&self, key: &str) -> bool {
+        let flag = self
+            .inflight
+            .entry(key.to_owned())
+            .or_insert_with(|| Arc::new(AtomicBool::new(false)))
+            .clone();
+        flag.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    /// Release the singleflight slot for a key (call after insert or on error).
+    pub(crate) fn release(&self, key: &str) {
+        if let Some(flag) = self.inflight.get(key) {
+            flag.store(false, Ordering::Release);
+        }
+        debug!(key, "feed cache slot released");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_full_cache_evicts_the_oldest_feed() {
+        let cache = FeedCache::new();
+        for i in 0..MAX_ENTRIES {
+            cache.insert(format!("feed-{i}"), String::new());
+        }
+        cache.insert("newest".to_owned(), String::new());
+
+        assert_eq!(cache.entries.len(), MAX_ENTRIES);
+        assert!(cache.get("feed-0").is_none());
+        assert!(cache.get("newest").is_some());
+    }
+}
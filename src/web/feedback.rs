@@ -0,0 +1,133 @@
+//! Public endpoint for reporting incorrect course or instructor data.
+//!
+//! Anonymous submissions are accepted; a valid session cookie attaches the
+//! reporter's Discord ID. Abuse is bounded by the `Feedback` rate limit bucket.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::banner::models::terms::Term;
+use crate::data::feedback::{self, FeedbackCategory, FeedbackTarget, NewFeedback};
+use crate::state::AppState;
+use crate::web::auth::extractors::OptionalUser;
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+
+/// Which page the report is about.
+#[derive(Debug, Deserialize, TS, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(export)]
+pub enum FeedbackEntityRef {
+    Course {
+        term: String,
+        crn: String,
+    },
+    /// Slug, numeric ID, or email prefix -- anything the instructor page accepts.
+    Instructor {
+        instructor: String,
+    },
+}
+
+/// Body for `POST /api/feedback`.
+#[derive(Debug, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubmitFeedbackBody {
+    pub entity: FeedbackEntityRef,
+    pub category: FeedbackCategory,
+    pub message: String,
+    #[serde(default)]
+    pub page_url: Option<String>,
+}
+
+/// Response for `POST /api/feedback`.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubmitFeedbackResponse {
+    pub id: i32,
+}
+
+/// `POST /api/feedback` -- Report wrong or missing data on a course or instructor.
+#[utoipa::path(
+    post,
+    path = "/feedback",
+    tag = "feedback",
+    summary = "Report a data problem",
+    request_body = SubmitFeedbackBody,
+    responses(
+        (status = 201, description = "Created", body = SubmitFeedbackResponse),
+    ),
+)]
+pub async fn submit_feedback(
+    OptionalUser(user): OptionalUser,
+    State(state): State<AppState>,
+    Json(body): Json<SubmitFeedbackBody>,
+) -> Result<(StatusCode, Json<SubmitFeedbackResponse>), ApiError> {
+    let message = body.message.trim();
+    if message.is_empty() {
+        return Err(ApiError::bad_request("Feedback message must not be empty"));
+    }
+    if message.chars().count() > feedback::MAX_MESSAGE_LEN {
+        return Err(ApiError::bad_request(format!(
+            "Feedback message must be at most {} characters",
+            feedback::MAX_MESSAGE_LEN
+        )));
+    }
+    let page_url = body
+        .page_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|u| !u.is_empty() && u.len() <= 500);
+
+    let (target, entity_label) = match &body.entity {
+        FeedbackEntityRef::Course { term, crn } => {
+            let term_code =
+                Term::resolve_to_code(term).ok_or_else(|| ApiError::invalid_term(term))?;
+            let course = crate::data::courses::get_course_by_crn(&state.db_pool, crn, &term_code)
+                .await
+                .map_err(|e| db_error("Course lookup", e))?
+                .or_not_found("Course", crn)?;
+            (
+                FeedbackTarget::Course { id: course.id },
+                format!(
+                    "{} {} (CRN {}, {})",
+                    course.subject, course.course_number, course.crn, course.term_code
+                ),
+            )
+        }
+        FeedbackEntityRef::Instructor { instructor } => {
+            let (id, slug) =
+                crate::data::instructors::resolve_instructor_identifier(&state.db_pool, instructor)
+                    .await
+                    .map_err(|e| db_error("Instructor lookup", e))?
+                    .or_not_found("Instructor", instructor)?;
+            (FeedbackTarget::Instructor { id }, slug)
+        }
+    };
+
+    let new = NewFeedback {
+        target,
+        entity_label,
+        category: body.category,
+        message,
+        page_url,
+        reporter_id: user.map(|u| u.id),
+    };
+    let id = feedback::insert_feedback(&state.db_pool, &new)
+        .await
+        .map_err(|e| db_error("Submit feedback", e))?;
+
+    info!(
+        feedback_id = id,
+        entity = %new.entity_label,
+        category = new.category.as_str(),
+        "Feedback submitted"
+    );
+
+    Ok((StatusCode::CREATED, Json(SubmitFeedbackResponse { id })))
+}
@@ -0,0 +1,530 @@
+//! Atom feeds of catalog changes, for following a term or subject in a feed reader.
+//!
+//! Three variants, each with one entry per term per campus-time day summarizing
+//! sections added and removed, instructor reassignments, and time changes:
+//!
+//! - `/feeds/changes.atom`: every term currently being scraped
+//! - `/feeds/changes/{term}.atom`: one term, by slug or code
+//! - `/feeds/changes/{term}/{subject}.atom`: one subject within a term
+//!
+//! Rendered feeds are cached in-memory with a 15-minute TTL via `FeedCache`.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::Write;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::banner::models::terms::Term;
+use crate::data;
+use crate::data::term_changes::{ChangedSection, TermChangeDay};
+use crate::state::AppState;
+
+/// How far back each feed looks.
+const WINDOW_DAYS: i64 = 14;
+/// Entries kept in the all-terms feed.
+const MAX_ENTRIES: usize = 50;
+
+fn atom_response(body: Arc<String>) -> Response {
+    let mut response = (*body).clone().into_response();
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/atom+xml; charset=utf-8"),
+    );
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=900, s-maxage=900, stale-while-revalidate=300"),
+    );
+    response
+}
+
+/// Try to serve from cache, or claim the singleflight slot and build.
+/// Returns `Ok(response)` on cache hit or contention fallback, `Err(())` if caller should build.
+fn try_cache_or_claim(state: &AppState, key: &str) -> Result<Response, ()> {
+    if let Some(cached) = state.feed_cache.get(key) {
+        return Ok(atom_response(cached));
+    }
+
+    if !state.feed_cache.try_claim(key) {
+        // Another request is rendering -- serve stale if available, else 503
+        if let Some(stale) = state.feed_cache.get_stale(key) {
+            return Ok(atom_response(stale));
+        }
+        let mut resp = StatusCode::SERVICE_UNAVAILABLE.into_response();
+        resp.headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from_static("5"));
+        return Ok(resp);
+    }
+
+    Err(())
+}
+
+/// Whether a feed may be served: it is already cached, or its term and
+/// subject exist. Unknown ones would only fill the cache with empty feeds.
+async fn is_cached_or_known(
+    state: &AppState,
+    key: &str,
+    term: Term,
+    subject: Option<&str>,
+) -> bool {
+    if state.feed_cache.get_stale(key).is_some() {
+        return true;
+    }
+    if let Some(subject) = subject
+        && state
+            .reference_cache
+            .read()
+            .await
+            .lookup("subject", subject)
+            .is_none()
+    {
+        return false;
+    }
+    match data::terms::get_term_by_code(&state.db_pool, &term.to_string()).await {
+        Ok(found) => found.is_some(),
+        Err(e) => {
+            warn!(key, error = ?e, "failed to look up feed term");
+            false
+        }
+    }
+}
+
+/// Insert into cache, release singleflight, return response.
+fn finish(state: &AppState, key: &str, xml: String) -> Response {
+    let cached = state.feed_cache.insert(key.to_owned(), xml);
+    state.feed_cache.release(key);
+    atom_response(cached)
+}
+
+/// Release the slot after a failed build.
+fn fail(state: &AppState, key: &str, error: anyhow::Error) -> Response {
+    warn!(key, error = ?error, "failed to build changes feed");
+    state.feed_cache.release(key);
+    StatusCode::INTERNAL_SERVER_ERROR.into_response()
+}
+
+/// One term's changes on one day.
+struct FeedEntry {
+    term: Term,
+    day: TermChangeDay,
+}
+
+/// What a feed covers, used for its id, title, and links.
+struct FeedScope<'a> {
+    term: Option<Term>,
+    subject: Option<&'a str>,
+}
+
+impl FeedScope<'_> {
+    fn path(&self) -> String {
+        match (self.term, self.subject) {
+            (Some(term), Some(subject)) => {
+                format!("/feeds/changes/{}/{subject}.atom", term.slug())
+            }
+            (Some(term), None) => format!("/feeds/changes/{}.atom", term.slug()),
+            _ => "/feeds/changes.atom".to_owned(),
+        }
+    }
+
+    fn page(&self) -> String {
+        match (self.term, self.subject) {
+            (_, Some(subject)) => format!("/subjects/{subject}"),
+            (Some(term), None) => format!("/?term={}", term.slug()),
+            _ => "/".to_owned(),
+        }
+    }
+
+    fn title(&self) -> String {
+        match (self.term, self.subject) {
+            (Some(term), Some(subject)) => {
+                format!("UTSA {subject} course changes, {}", term.description())
+            }
+            (Some(term), None) => format!("UTSA course changes, {}", term.description()),
+            _ => "UTSA course changes".to_owned(),
+        }
+    }
+}
+
+/// Escape text for XML element content and attribute values.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn plural(n: u32, one: &str, many: &str) -> String {
+    format!("{n} {}", if n == 1 { one } else { many })
+}
+
+fn entry_title(entry: &FeedEntry, subject: Option<&str>) -> String {
+    let counts = &entry.day.counts;
+    let parts: Vec<String> = [
+        (counts.added.get(), "section added", "sections added"),
+        (counts.removed.get(), "section removed", "sections removed"),
+        (
+            counts.instructor_changes.get(),
+            "instructor change",
+            "instructor changes",
+        ),
+        (counts.time_changes.get(), "time change", "time changes"),
+    ]
+    .into_iter()
+    .filter(|(n, ..)| *n > 0)
+    .map(|(n, one, many)| plural(n, one, many))
+    .collect();
+
+    let scope = match subject {
+        Some(subject) => format!("{subject}, {}", entry.term.description()),
+        None => entry.term.description(),
+    };
+    format!(
+        "{scope}, {}: {}",
+        entry.day.date.format("%b %-d"),
+        parts.join(", ")
+    )
+}
+
+/// HTML list of the day's changes, linking each section.
+fn entry_content(origin: &str, entry: &FeedEntry) -> String {
+    let slug = entry.term.slug();
+    let link = |s: &ChangedSection| {
+        format!(
+            "<a href=\"{origin}/courses/{slug}/{}\">{} {} ({})</a> {}",
+            escape(&s.crn),
+            escape(&s.subject),
+            escape(&s.course_number),
+            escape(&s.crn),
+            escape(&s.title),
+        )
+    };
+
+    let day = &entry.day;
+    let mut html = String::new();
+    let mut section = |heading: &str, items: Vec<String>, total: u32| {
+        if items.is_empty() {
+            return;
+        }
+        let _ = write!(html, "<h3>{heading}</h3><ul>");
+        let shown = items.len();
+        for item in items {
+            let _ = write!(html, "<li>{item}</li>");
+        }
+        if (total as usize) > shown {
+            let _ = write!(html, "<li>and {} more</li>", total as usize - shown);
+        }
+        html.push_str("</ul>");
+    };
+
+    section(
+        "Added",
+        day.added.iter().map(&link).collect(),
+        day.counts.added.get(),
+    );
+    section(
+        "Removed",
+        day.removed.iter().map(&link).collect(),
+        day.counts.removed.get(),
+    );
+    section(
+        "Instructor changes",
+        day.instructor_changes
+            .iter()
+            .map(|c| {
+                let names = |names: &[String]| match names {
+                    [] => "TBA".to_owned(),
+                    names => escape(&names.join("; ")),
+                };
+                format!(
+                    "{}: {} &#8594; {}",
+                    link(&c.section),
+                    names(&c.from),
+                    names(&c.to)
+                )
+            })
+            .collect(),
+        day.counts.instructor_changes.get(),
+    );
+    section(
+        "Time changes",
+        day.time_changes.iter().map(|c| link(&c.section)).collect(),
+        day.counts.time_changes.get(),
+    );
+    html
+}
+
+/// Render an Atom document with `entries`, newest first.
+fn render(origin: &str, scope: &FeedScope, entries: &[FeedEntry], now: DateTime<Utc>) -> String {
+    let feed_url = format!("{origin}{}", scope.path());
+    let updated = entries.first().map_or(now, |e| e.day.last_changed);
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+    );
+    let _ = writeln!(xml, "  <id>{}</id>", escape(&feed_url));
+    let _ = writeln!(xml, "  <title>{}</title>", escape(&scope.title()));
+    let _ = writeln!(xml, "  <updated>{}</updated>", updated.to_rfc3339());
+    let _ = writeln!(xml, "  <link rel=\"self\" href=\"{}\"/>", escape(&feed_url));
+    let _ = writeln!(
+        xml,
+        "  <link rel=\"alternate\" href=\"{}\"/>",
+        escape(&format!("{origin}{}", scope.page()))
+    );
+    xml.push_str("  <author><name>Banner</name></author>\n");
+
+    for entry in entries {
+        let entry_scope = FeedScope {
+            term: Some(entry.term),
+            subject: scope.subject,
+        };
+        let id = format!("{origin}{}#{}", entry_scope.path(), entry.day.date);
+        xml.push_str("  <entry>\n");
+        let _ = writeln!(xml, "    <id>{}</id>", escape(&id));
+        let _ = writeln!(
+            xml,
+            "    <title>{}</title>",
+            escape(&entry_title(entry, scope.subject))
+        );
+        let _ = writeln!(
+            xml,
+            "    <updated>{}</updated>",
+            entry.day.last_changed.to_rfc3339()
+        );
+        let _ = writeln!(
+            xml,
+            "    <link rel=\"alternate\" href=\"{}\"/>",
+            escape(&format!("{origin}{}", entry_scope.page()))
+        );
+        let _ = writeln!(
+            xml,
+            "    <content type=\"html\">{}</content>",
+            escape(&entry_content(origin, entry))
+        );
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// Fetch one term's changes over the feed window as entries, newest first.
+async fn term_entries(
+    state: &AppState,
+    term: Term,
+    subject: Option<&str>,
+    since: DateTime<Utc>,
+) -> anyhow::Result<Vec<FeedEntry>> {
+    let days =
+        data::term_changes::list_changes(&state.db_pool, &term.to_string(), subject, since).await?;
+    Ok(days
+        .into_iter()
+        .map(|day| FeedEntry { term, day })
+        .collect())
+}
+
+/// Resolve a `{term}.atom` style path segment to a term.
+fn parse_term_segment(segment: &str) -> Option<Term> {
+    let input = segment.strip_suffix(".atom").unwrap_or(segment);
+    Term::resolve_to_code(input)?.parse().ok()
+}
+
+/// `GET /feeds/changes.atom` -- changes across all terms being scraped.
+pub async fn changes_feed(State(state): State<AppState>) -> Response {
+    let Some(ref origin) = state.public_origin else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let key = "changes";
+    if let Ok(resp) = try_cache_or_claim(&state, key) {
+        return resp;
+    }
+
+    let now = Utc::now();
+    let since = now - Duration::days(WINDOW_DAYS);
+    let terms = match data::terms::get_enabled_terms(&state.db_pool).await {
+        Ok(t) => t,
+        Err(e) => return fail(&state, key, e),
+    };
+
+    let mut entries = Vec::new();
+    for term in terms.iter().filter_map(|t| t.code.parse::<Term>().ok()) {
+        match term_entries(&state, term, None, since).await {
+            Ok(e) => entries.extend(e),
+            Err(e) => return fail(&state, key, e),
+        }
+    }
+    entries.sort_by_key(|e| std::cmp::Reverse(e.day.last_changed));
+    entries.truncate(MAX_ENTRIES);
+
+    let scope = FeedScope {
+        term: None,
+        subject: None,
+    };
+    finish(&state, key, render(origin, &scope, &entries, now))
+}
+
+/// `GET /feeds/changes/{term}.atom` -- changes within one term.
+pub async fn term_changes_feed(
+    State(state): State<AppState>,
+    Path(rest): Path<String>,
+) -> Response {
+    let Some(ref origin) = state.public_origin else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let Some(term) = parse_term_segment(&rest) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let key = format!("changes-{term}");
+    if !is_cached_or_known(&state, &key, term, None).await {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    if let Ok(resp) = try_cache_or_claim(&state, &key) {
+        return resp;
+    }
+
+    let now = Utc::now();
+    let entries = match term_entries(&state, term, None, now - Duration::days(WINDOW_DAYS)).await {
+        Ok(e) => e,
+        Err(e) => return fail(&state, &key, e),
+    };
+
+    let scope = FeedScope {
+        term: Some(term),
+        subject: None,
+    };
+    finish(&state, &key, render(origin, &scope, &entries, now))
+}
+
+/// `GET /feeds/changes/{term}/{subject}.atom` -- changes to one subject's sections.
+pub async fn subject_changes_feed(
+    State(state): State<AppState>,
+    Path((term, rest)): Path<(String, String)>,
+) -> Response {
+    let Some(ref origin) = state.public_origin else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let Some(term) = parse_term_segment(&term) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let subject = rest.strip_suffix(".atom").unwrap_or(&rest).to_uppercase();
+
+    let key = format!("changes-{term}-{subject}");
+    if !is_cached_or_known(&state, &key, term, Some(&subject)).await {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    if let Ok(resp) = try_cache_or_claim(&state, &key) {
+        return resp;
+    }
+
+    let now = Utc::now();
+    let since = now - Duration::days(WINDOW_DAYS);
+    let entries = match term_entries(&state, term, Some(&subject), since).await {
+        Ok(e) => e,
+        Err(e) => return fail(&state, &key, e),
+    };
+
+    let scope = FeedScope {
+        term: Some(term),
+        subject: Some(&subject),
+    };
+    finish(&state, &key, render(origin, &scope, &entries, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::term_changes::{InstructorReassignment, TermChangeCounts};
+    use crate::data::unsigned::Count;
+    use chrono::NaiveDate;
+
+    fn section(crn: &str, title: &str) -> ChangedSection {
+        ChangedSection {
+            crn: crn.to_owned(),
+            subject: "CS".to_owned(),
+            course_number: "1083".to_owned(),
+            title: title.to_owned(),
+        }
+    }
+
+    fn entry() -> FeedEntry {
+        FeedEntry {
+            term: "202620".parse().unwrap(),
+            day: TermChangeDay {
+                date: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+                last_changed: DateTime::parse_from_rfc3339("2026-03-02T18:30:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+                counts: TermChangeCounts {
+                    added: Count::new(3),
+                    instructor_changes: Count::new(1),
+                    ..Default::default()
+                },
+                added: vec![section("21450", "Programming & Problem Solving")],
+                removed: vec![],
+                instructor_changes: vec![InstructorReassignment {
+                    section: section("21451", "Intro"),
+                    from: vec![],
+                    to: vec!["Nguyen, Ada".to_owned()],
+                }],
+                time_changes: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn titles_list_nonzero_counts() {
+        assert_eq!(
+            entry_title(&entry(), None),
+            "Spring 2026, Mar 2: 3 sections added, 1 instructor change"
+        );
+        assert_eq!(
+            entry_title(&entry(), Some("CS")),
+            "CS, Spring 2026, Mar 2: 3 sections added, 1 instructor change"
+        );
+    }
+
+    #[test]
+    fn renders_escaped_entries() {
+        let scope = FeedScope {
+            term: Some("202620".parse().unwrap()),
+            subject: Some("CS"),
+        };
+        let xml = render("https://example.test", &scope, &[entry()], Utc::now());
+
+        assert!(xml.contains(
+            "<id>https://example.test/feeds/changes/spring-2026/CS.atom#2026-03-02</id>"
+        ));
+        assert!(xml.contains("<updated>2026-03-02T18:30:00+00:00</updated>"));
+        // Content is HTML escaped once more for the XML document.
+        assert!(xml.contains("Programming &amp;amp; Problem Solving"));
+        assert!(xml.contains("and 2 more"));
+        assert!(xml.contains("TBA &amp;#8594; Nguyen, Ada"));
+    }
+
+    #[test]
+    fn term_segments_accept_slugs_and_codes() {
+        assert_eq!(
+            parse_term_segment("spring-2026.atom").map(|t| t.to_string()),
+            Some("202620".to_owned())
+        );
+        assert_eq!(
+            parse_term_segment("202620.atom").map(|t| t.to_string()),
+            Some("202620".to_owned())
+        );
+        assert!(parse_term_segment("nonsense.atom").is_none());
+    }
+}
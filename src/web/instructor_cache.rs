@@ -0,0 +1,226 @@
+//! Read-through TTL cache for public instructor profiles, keyed by slug.
+//!
+//! Entries hold the serialized JSON body and a content-hash ETag, so a hit
+//! serves the profile (or a 304) without touching the database. A background
+//! task watches [`InstructorEvent`]s and drops entries when scores are
+//! recomputed or links change; the TTL bounds staleness from anything else.
+//! At most [`MAX_ENTRIES`] profiles are held; expired ones are dropped first,
+//! then the oldest.
+
+use axum::body::Bytes;
+use dashmap::DashMap;
+use rapidhash::v3::rapidhash_v3;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::data::events::{DomainEvent, EventBuffer, EventSubscription, InstructorEvent};
+
+const TTL: Duration = Duration::from_secs(10 * 60);
+/// Profiles held at once, well above the instructors browsed in one TTL.
+const MAX_ENTRIES: usize = 4096;
+
+/// A cached, ready-to-send profile response.
+pub struct CachedProfile {
+    pub instructor_id: i32,
+    /// Quoted ETag derived from the body hash.
+    pub etag: String,
+    pub body: Bytes,
+}
+
+impl CachedProfile {
+    pub fn new(instructor_id: i32, body: Vec<u8>) -> Self {
+        Self {
+            instructor_id,
+            etag: format!("\"i:{:016x}\"", rapidhash_v3(&body)),
+            body: Bytes::from(body),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct InstructorProfileCache {
+    /// slug -> (cached_at, profile)
+    entries: Arc<DashMap<String, (Instant, Arc<CachedProfile>)>>,
+    /// Bumped on every invalidation; fills started before a bump are discarded.
+    generation: Arc<AtomicU64>,
+}
+
+impl InstructorProfileCache {
+    /// Create the cache and spawn its event-driven invalidation task.
+    pub(crate) fn new(events: Arc<EventBuffer>) -> Self {
+        let cache = Self::default();
+        tokio::spawn(run_invalidation_loop(events, cache.clone()));
+        cache
+    }
+
+    /// Return a fresh entry for `slug`, if any.
+    pub(crate) fn get(&self, slug: &str) -> Option<Arc<CachedProfile>> {
+        let entry = self.entries.get(slug)?;
+        let (cached_at, ref value) = *entry;
+        if cached_at.elapsed() < TTL {
+            return Some(value.clone());
+        }
+        drop(entry);
+        self.entries
+            .remove_if(slug, |_, (cached_at, _)| cached_at.elapsed() >= TTL);
+        None
+    }
+
+    /// Snapshot the generation before loading a profile from the database.
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Store a profile loaded under `generation`, unless an invalidation
+    /// happened in the meantime.
+    pub(crate) fn insert(&self, slug: String, generation: u64, profile: Arc<CachedProfile>) {
+        if self.generation() != generation {
+            debug!(
+                slug,
+                "instructor profile invalidated during load, not caching"
+            );
+            return;
+        }
+        if !self.entries.contains_key(&slug) && self.entries.len() >= MAX_ENTRIES {
+            self.evict();
+        }
+        self.entries.insert(slug, (Instant::now(), profile));
+    }
+
+    /// Make room for one entry: drop expired profiles, or the oldest if none are.
+    fn evict(&self) {
+        self.entries
+            .retain(|_, (cached_at, _)| cached_at.elapsed() < TTL);
+        if self.entries.len() < MAX_ENTRIES {
+            return;
+        }
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|e| e.value().0)
+            .map(|e| e.key().clone());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+
+    /// Drop cached profiles for one instructor.
+    pub(crate) fn invalidate_instructor(&self, instructor_id: i32) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.entries
+            .retain(|_, (_, profile)| profile.instructor_id != instructor_id);
+    }
+
+    /// Drop every cached profile.
+    pub(crate) fn invalidate_all(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.entries.clear();
+    }
+
+    fn apply(&self, event: &InstructorEvent) {
+        match event {
+            InstructorEvent::LinksChanged {
+                instructor_id: Some(id),
+            } => self.invalidate_instructor(*id),
+            InstructorEvent::LinksChanged {
+                instructor_id: None,
+            }
+            | InstructorEvent::ScoresRecomputed
+            | InstructorEvent::SlugsRebuilt => self.invalidate_all(),
+        }
+    }
+}
+
+async fn run_invalidation_loop(events: Arc<EventBuffer>, cache: InstructorProfileCache) {
+    let mut subscription = EventSubscription::new(events);
+
+    while let Some(drained) = subscription.recv().await {
+        if drained.lagged() {
+            warn!(
+                skipped = drained.skipped,
+                "Instructor cache lagged, clearing all entries"
+            );
+            cache.invalidate_all();
+        }
+        for event in &drained.events {
+            if let DomainEvent::Instructor(event) = event {
+                debug!(?event, "Invalidating instructor profiles");
+                cache.apply(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: i32, body: &str) -> Arc<CachedProfile> {
+        Arc::new(CachedProfile::new(id, body.as_bytes().to_vec()))
+    }
+
+    #[test]
+    fn etag_tracks_body_content() {
+        assert_eq!(profile(1, "{}").etag, profile(2, "{}").etag);
+        assert_ne!(profile(1, "{}").etag, profile(1, "{\"a\":1}").etag);
+    }
+
+    #[test]
+    fn invalidate_instructor_only_drops_that_instructor() {
+        let cache = InstructorProfileCache::default();
+        let generation = cache.generation();
+        cache.insert("jane-doe".into(), generation, profile(1, "a"));
+        cache.insert("john-roe".into(), generation, profile(2, "b"));
+
+        cache.apply(&InstructorEvent::LinksChanged {
+            instructor_id: Some(1),
+        });
+        assert!(cache.get("jane-doe").is_none());
+        assert!(cache.get("john-roe").is_some());
+
+        cache.apply(&InstructorEvent::ScoresRecomputed);
+        assert!(cache.get("john-roe").is_none());
+    }
+
+    #[test]
+    fn fill_racing_an_invalidation_is_discarded() {
+        let cache = InstructorProfileCache::default();
+        let generation = cache.generation();
+        cache.invalidate_all();
+        cache.insert("jane-doe".into(), generation, profile(1, "a"));
+        assert!(cache.get("jane-doe").is_none());
+    }
+
+    #[test]
+    fn a_full_cache_evicts_the_oldest_profile() {
+        let cache = InstructorProfileCache::default();
+        let generation = cache.generation();
+        for i in 0..MAX_ENTRIES {
+            cache.insert(
+                format!("instructor-{i}"),
+                generation,
+                profile(i as i32, "a"),
+            );
+        }
+        cache.insert("newcomer".into(), generation, profile(-1, "b"));
+
+        assert_eq!(cache.entries.len(), MAX_ENTRIES);
+        assert!(cache.get("instructor-0").is_none());
+        assert!(cache.get("instructor-1").is_some());
+        assert!(cache.get("newcomer").is_some());
+    }
+
+    #[test]
+    fn expired_profiles_are_dropped_on_read() {
+        let cache = InstructorProfileCache::default();
+        let stale = Instant::now() - TTL - Duration::from_secs(1);
+        cache
+            .entries
+            .insert("jane-doe".into(), (stale, profile(1, "a")));
+
+        assert!(cache.get("jane-doe").is_none());
+        assert!(cache.entries.is_empty());
+    }
+}
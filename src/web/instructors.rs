@@ -1,15 +1,30 @@
 //! Public instructor directory and profile HTTP handlers.
 
+use std::sync::Arc;
+
 use axum::extract::{Path, Query, State};
-use axum::response::Json;
+use utoipa::IntoParams;
 
 use crate::data;
 use crate::data::instructors::{IdentifierKind, PublicInstructorListParams, classify_identifier};
 use crate::state::AppState;
 use crate::web::courses::{CourseResponse, build_course_response};
-use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error, paged_db_error};
+use crate::web::instructor_cache::CachedProfile;
 
 /// `GET /api/instructors`
+#[utoipa::path(
+    get,
+    path = "/instructors",
+    tag = "instructors",
+    summary = "Instructor directory",
+    params(
+        PublicInstructorListParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = data::instructors::PublicInstructorListResponse),
+    ),
+)]
 pub async fn list_instructors(
     State(state): State<AppState>,
     Query(params): Query<PublicInstructorListParams>,
@@ -17,76 +32,125 @@ pub async fn list_instructors(
     use crate::web::routes::{cache, with_cache_control};
     let result = data::instructors::list_public_instructors(&state.db_pool, &params)
         .await
-        .map_err(|e| db_error("List instructors", e))?;
+        .map_err(|e| paged_db_error("List instructors", e))?;
     Ok(with_cache_control(result, cache::REFERENCE))
 }
 
 /// `GET /api/instructors/{slug}`
+///
+/// Served from [`InstructorProfileCache`](crate::web::instructor_cache::InstructorProfileCache) when possible; the ETag is a hash of
+/// the response body, so it changes exactly when the profile does.
+#[utoipa::path(
+    get,
+    path = "/instructors/{slug}",
+    tag = "instructors",
+    summary = "Instructor profile",
+    params(
+        ("slug" = String, Path, description = "Instructor slug"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = data::instructors::PublicInstructorProfileResponse),
+    ),
+)]
 pub async fn get_instructor(
     State(state): State<AppState>,
     Path(raw): Path<String>,
     headers: axum::http::HeaderMap,
 ) -> Result<axum::response::Response, ApiError> {
-    use crate::web::routes::cache;
-    use axum::http::{HeaderValue, StatusCode, header};
     use axum::response::{IntoResponse, Redirect};
 
+    let is_slug = matches!(classify_identifier(&raw), IdentifierKind::Slug);
+    if is_slug {
+        let cached = state.instructor_cache.get(&raw);
+        state
+            .metrics
+            .record_cache_lookup("instructor_profile", cached.is_some());
+        if let Some(cached) = cached {
+            return Ok(profile_response(&cached, &headers));
+        }
+    }
+
     let (instructor_id, slug) =
         data::instructors::resolve_instructor_identifier(&state.db_pool, &raw)
             .await
             .map_err(|e| db_error("Resolve instructor", e))?
             .or_not_found("Instructor", &raw)?;
 
-    // Non-canonical identifier: redirect to the canonical slug URL
-    if !matches!(classify_identifier(&raw), IdentifierKind::Slug) {
+    // Non-canonical identifier or retired slug: redirect to the canonical URL
+    if slug != raw {
         return Ok(Redirect::permanent(&format!("/api/instructors/{slug}")).into_response());
     }
 
-    // Build ETag from instructor score timestamp (most frequently changing data)
-    let score_ts: Option<chrono::DateTime<chrono::Utc>> =
-        sqlx::query_scalar("SELECT computed_at FROM instructor_scores WHERE instructor_id = $1")
-            .bind(instructor_id)
-            .fetch_optional(&state.db_pool)
-            .await
-            .unwrap_or(None);
-
-    let etag = format!("\"i:{}:{}\"", slug, score_ts.map_or(0, |ts| ts.timestamp()));
-
-    // 304 Not Modified if client ETag matches
-    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH)
-        && if_none_match.as_bytes() == etag.as_bytes()
-    {
-        let mut resp = StatusCode::NOT_MODIFIED.into_response();
-        resp.headers_mut()
-            .insert(header::ETAG, HeaderValue::from_str(&etag).unwrap());
-        resp.headers_mut().insert(
-            header::CACHE_CONTROL,
-            HeaderValue::from_static(cache::DETAIL),
-        );
-        return Ok(resp);
-    }
-
+    let generation = state.instructor_cache.generation();
     let profile = data::instructors::get_public_instructor_by_slug(&state.db_pool, &slug)
         .await
         .map_err(|e| db_error("Get instructor", e))?
         .or_not_found("Instructor", &slug)?;
 
-    let mut resp = Json(profile).into_response();
-    resp.headers_mut()
-        .insert(header::ETAG, HeaderValue::from_str(&etag).unwrap());
+    let body = serde_json::to_vec(&profile).map_err(|e| {
+        tracing::error!(error = %e, slug, "Failed to serialize instructor profile");
+        ApiError::internal_error("Failed to serialize instructor profile")
+    })?;
+    let cached = Arc::new(CachedProfile::new(instructor_id, body));
+    state
+        .instructor_cache
+        .insert(slug, generation, cached.clone());
+
+    Ok(profile_response(&cached, &headers))
+}
+
+/// Build a 200 (or 304 when `If-None-Match` matches) with ETag and cache headers.
+fn profile_response(
+    cached: &CachedProfile,
+    headers: &axum::http::HeaderMap,
+) -> axum::response::Response {
+    use crate::web::routes::cache;
+    use axum::http::{HeaderValue, StatusCode, header};
+    use axum::response::IntoResponse;
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|v| v.as_bytes() == cached.etag.as_bytes());
+
+    let mut resp = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (
+            [(header::CONTENT_TYPE, "application/json")],
+            cached.body.clone(),
+        )
+            .into_response()
+    };
+    if let Ok(etag) = HeaderValue::from_str(&cached.etag) {
+        resp.headers_mut().insert(header::ETAG, etag);
+    }
     resp.headers_mut().insert(
         header::CACHE_CONTROL,
         HeaderValue::from_static(cache::DETAIL),
     );
-    Ok(resp)
+    resp
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct InstructorSectionsParams {
     pub term: String,
 }
 
 /// `GET /api/instructors/{slug}/sections?term={code}`
+#[utoipa::path(
+    get,
+    path = "/instructors/{slug}/sections",
+    tag = "instructors",
+    summary = "Sections an instructor teaches in a term",
+    params(
+        ("slug" = String, Path, description = "Instructor slug"),
+        InstructorSectionsParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = Vec<CourseResponse>),
+    ),
+)]
 pub async fn get_instructor_sections(
     State(state): State<AppState>,
     Path(raw): Path<String>,
@@ -103,7 +167,7 @@ pub async fn get_instructor_sections(
 
     // Non-canonical: redirect, preserving the raw ?term= value so the redirect
     // target can still resolve "fall2025"-style aliases.
-    if !matches!(classify_identifier(&raw), IdentifierKind::Slug) {
+    if slug != raw {
         let uri = format!("/api/instructors/{slug}/sections?term={}", params.term);
         return Ok(Redirect::permanent(&uri).into_response());
     }
@@ -117,19 +181,25 @@ pub async fn get_instructor_sections(
             .map_err(|e| db_error("Instructor sections", e))?;
 
     let course_ids: Vec<i32> = courses.iter().map(|c| c.id).collect();
-    let mut instructor_map =
-        data::courses::get_instructors_for_courses(&state.db_pool, &course_ids)
-            .await
-            .unwrap_or_else(|e| {
-                tracing::error!(error = %e, "Failed to fetch instructors for instructor sections");
-                Default::default()
-            });
+    let (instructor_map, linked_map) = tokio::join!(
+        data::courses::get_instructors_for_courses(&state.db_pool, &course_ids),
+        data::course_details::get_linked_groups_for_courses(&state.db_pool, &course_ids),
+    );
+    let mut instructor_map = instructor_map.unwrap_or_else(|e| {
+        tracing::error!(error = %e, "Failed to fetch instructors for instructor sections");
+        Default::default()
+    });
+    let mut linked_map = linked_map.unwrap_or_else(|e| {
+        tracing::error!(error = %e, "Failed to fetch linked sections for instructor sections");
+        Default::default()
+    });
 
     let responses: Vec<CourseResponse> = courses
         .iter()
         .map(|course| {
             let instructors = instructor_map.remove(&course.id).unwrap_or_default();
-            build_course_response(course, instructors)
+            let linked = linked_map.remove(&course.id).unwrap_or_default();
+            build_course_response(course, instructors, linked)
         })
         .collect();
 
@@ -138,3 +208,48 @@ pub async fn get_instructor_sections(
         crate::web::routes::cache::DETAIL,
     ))
 }
+
+/// `GET /api/instructors/{slug}/related`
+///
+/// Precomputed by the scheduler; empty until the first recommendations run.
+#[utoipa::path(
+    get,
+    path = "/instructors/{slug}/related",
+    tag = "instructors",
+    summary = "Related instructors: shared subjects, co-taught sections, similar ratings",
+    params(
+        ("slug" = String, Path, description = "Instructor slug"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = data::recommendations::RelatedInstructorsResponse),
+    ),
+)]
+pub async fn get_related_instructors(
+    State(state): State<AppState>,
+    Path(raw): Path<String>,
+) -> Result<axum::response::Response, ApiError> {
+    use crate::data::recommendations::{RELATED_PER_INSTRUCTOR, RelatedInstructorsResponse};
+    use axum::response::{IntoResponse, Redirect};
+
+    let (instructor_id, slug) =
+        data::instructors::resolve_instructor_identifier(&state.db_pool, &raw)
+            .await
+            .map_err(|e| db_error("Resolve instructor", e))?
+            .or_not_found("Instructor", &raw)?;
+
+    if slug != raw {
+        return Ok(
+            Redirect::permanent(&format!("/api/instructors/{slug}/related")).into_response(),
+        );
+    }
+
+    let instructors =
+        data::recommendations::get_related(&state.db_pool, instructor_id, RELATED_PER_INSTRUCTOR)
+            .await
+            .map_err(|e| db_error("Related instructors", e))?;
+
+    Ok(crate::web::routes::with_cache_control(
+        RelatedInstructorsResponse { instructors },
+        crate::web::routes::cache::DETAIL,
+    ))
+}
@@ -0,0 +1,176 @@
+//! CIDR-based allow/deny policy for inbound requests.
+//!
+//! Static ranges come from config (`TRUSTED_CIDRS`, `BLOCKED_CIDRS`); temporary
+//! bans are added through the admin API, persisted in `ip_bans`, and held here
+//! in memory. Trusted ranges win over both blocks and bans, and also skip rate
+//! limiting. [`IpFilterLayer`] applies the verdict to every request.
+//!
+//! [`IpFilterLayer`]: crate::web::middleware::ip_filter::IpFilterLayer
+
+use std::net::IpAddr;
+use std::sync::RwLock;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::warn;
+
+use crate::data::ip_bans;
+use crate::web::middleware::client_ip::IpCidr;
+
+/// How the filter treats one client address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVerdict {
+    /// In a trusted range: let through and skip rate limiting.
+    Trusted,
+    Allowed,
+    /// In a configured blocked range.
+    Blocked,
+    /// Under an active admin ban.
+    Banned,
+}
+
+#[derive(Debug, Clone)]
+struct ActiveBan {
+    id: i32,
+    cidr: IpCidr,
+    expires_at: DateTime<Utc>,
+}
+
+/// Configured ranges plus the in-memory copy of active bans.
+#[derive(Debug, Default)]
+pub struct IpFilter {
+    trusted: Vec<IpCidr>,
+    blocked: Vec<IpCidr>,
+    bans: RwLock<Vec<ActiveBan>>,
+}
+
+impl IpFilter {
+    pub fn new(trusted: Vec<IpCidr>, blocked: Vec<IpCidr>) -> Self {
+        Self {
+            trusted,
+            blocked,
+            bans: RwLock::default(),
+        }
+    }
+
+    pub fn check(&self, ip: IpAddr) -> IpVerdict {
+        if self.trusted.iter().any(|cidr| cidr.contains(ip)) {
+            return IpVerdict::Trusted;
+        }
+        if self.blocked.iter().any(|cidr| cidr.contains(ip)) {
+            return IpVerdict::Blocked;
+        }
+        let bans = self.bans.read().unwrap();
+        if !bans.is_empty() {
+            let now = Utc::now();
+            if bans
+                .iter()
+                .any(|ban| ban.expires_at > now && ban.cidr.contains(ip))
+            {
+                return IpVerdict::Banned;
+            }
+        }
+        IpVerdict::Allowed
+    }
+
+    /// Start enforcing a ban. Expired bans are pruned on the way.
+    pub fn add_ban(&self, id: i32, cidr: IpCidr, expires_at: DateTime<Utc>) {
+        let now = Utc::now();
+        let mut bans = self.bans.write().unwrap();
+        bans.retain(|ban| ban.expires_at > now);
+        bans.push(ActiveBan {
+            id,
+            cidr,
+            expires_at,
+        });
+    }
+
+    /// Stop enforcing a ban.
+    pub fn remove_ban(&self, id: i32) {
+        self.bans.write().unwrap().retain(|ban| ban.id != id);
+    }
+
+    /// Replace the in-memory bans with the active ones in the database.
+    pub async fn load_bans(&self, pool: &PgPool) -> Result<()> {
+        let bans = ip_bans::list_active(pool)
+            .await?
+            .into_iter()
+            .filter_map(|ban| match ban.cidr.parse::<IpCidr>() {
+                Ok(cidr) => Some(ActiveBan {
+                    id: ban.id,
+                    cidr,
+                    expires_at: ban.expires_at,
+                }),
+                Err(e) => {
+                    warn!(
+                        ban_id = ban.id,
+                        cidr = ban.cidr,
+                        error = e,
+                        "Skipping unparseable IP ban"
+                    );
+                    None
+                }
+            })
+            .collect();
+        *self.bans.write().unwrap() = bans;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cidrs(list: &[&str]) -> Vec<IpCidr> {
+        list.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn trusted_ranges_override_blocks_and_bans() {
+        let filter = IpFilter::new(cidrs(&["10.1.2.3"]), cidrs(&["10.0.0.0/8"]));
+        filter.add_ban(
+            1,
+            "10.1.2.0/24".parse().unwrap(),
+            Utc::now() + chrono::Duration::hours(1),
+        );
+
+        assert_eq!(
+            filter.check("10.1.2.3".parse().unwrap()),
+            IpVerdict::Trusted
+        );
+        assert_eq!(
+            filter.check("10.9.9.9".parse().unwrap()),
+            IpVerdict::Blocked
+        );
+        assert_eq!(
+            filter.check("192.0.2.1".parse().unwrap()),
+            IpVerdict::Allowed
+        );
+    }
+
+    #[test]
+    fn bans_lapse_at_expiry_and_can_be_lifted() {
+        let filter = IpFilter::default();
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        filter.add_ban(
+            1,
+            "203.0.113.0/24".parse().unwrap(),
+            Utc::now() + chrono::Duration::hours(1),
+        );
+        filter.add_ban(
+            2,
+            "198.51.100.1".parse().unwrap(),
+            Utc::now() - chrono::Duration::seconds(1),
+        );
+
+        assert_eq!(filter.check(ip), IpVerdict::Banned);
+        assert_eq!(
+            filter.check("198.51.100.1".parse().unwrap()),
+            IpVerdict::Allowed
+        );
+
+        filter.remove_ban(1);
+        assert_eq!(filter.check(ip), IpVerdict::Allowed);
+    }
+}
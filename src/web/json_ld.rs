@@ -0,0 +1,440 @@
+//! schema.org structured data for course and instructor pages.
+//!
+//! `GET /api/courses/{term}/{crn}/json-ld` describes a section as a `Course`
+//! with one `CourseInstance`, and `GET /api/instructors/{slug}/json-ld`
+//! describes an instructor as a `Person`, with their composite rating as an
+//! `AggregateRating` once it has responses behind it. Pages embed the body
+//! in a `<script type="application/ld+json">` tag during SSR. Admins can turn
+//! both off through the crawler policy, in which case the endpoints 404.
+
+use axum::extract::{Path, State};
+use axum::http::{HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use chrono::NaiveDate;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::banner::models::terms::Term;
+use crate::data;
+use crate::data::instructors::InstructorRatingSummary;
+use crate::data::models::{Course, CourseInstructorDetail, DbMeetingTime};
+use crate::data::reference_types::InstructionalMethod;
+use crate::state::AppState;
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+use crate::web::routes::cache;
+
+const SCHEMA_CONTEXT: &str = "https://schema.org";
+const UNIVERSITY_NAME: &str = "The University of Texas at San Antonio";
+const UNIVERSITY_URL: &str = "https://www.utsa.edu";
+
+/// The university, as the provider of courses and employer of instructors.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct JsonLdOrganization {
+    #[serde(rename = "@type")]
+    #[ts(type = "\"CollegeOrUniversity\"")]
+    kind: &'static str,
+    #[ts(type = "string")]
+    name: &'static str,
+    #[ts(type = "string")]
+    url: &'static str,
+}
+
+impl JsonLdOrganization {
+    fn university() -> Self {
+        Self {
+            kind: "CollegeOrUniversity",
+            name: UNIVERSITY_NAME,
+            url: UNIVERSITY_URL,
+        }
+    }
+}
+
+/// An instructor's composite rating on a 1-5 scale.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct JsonLdAggregateRating {
+    #[serde(rename = "@type")]
+    #[ts(type = "\"AggregateRating\"")]
+    kind: &'static str,
+    /// Rounded to one decimal, e.g. `"4.3"`.
+    rating_value: String,
+    best_rating: i32,
+    worst_rating: i32,
+    rating_count: i32,
+}
+
+/// `Person` document for an instructor page.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct JsonLdPerson {
+    #[serde(rename = "@context")]
+    #[ts(type = "\"https://schema.org\"")]
+    context: &'static str,
+    #[serde(rename = "@type")]
+    #[ts(type = "\"Person\"")]
+    kind: &'static str,
+    name: String,
+    url: String,
+    #[ts(type = "string")]
+    job_title: &'static str,
+    works_for: JsonLdOrganization,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    aggregate_rating: Option<JsonLdAggregateRating>,
+}
+
+/// An instructor named inside a `CourseInstance`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct JsonLdInstructor {
+    #[serde(rename = "@type")]
+    #[ts(type = "\"Person\"")]
+    kind: &'static str,
+    name: String,
+    /// Profile page, when the instructor has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+/// One offering of a course: a section in a term.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct JsonLdCourseInstance {
+    #[serde(rename = "@type")]
+    #[ts(type = "\"CourseInstance\"")]
+    kind: &'static str,
+    /// `Onsite`, `Online`, or `Blended`; omitted when the method is unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    course_mode: Option<String>,
+    /// First day of class, `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    /// Last day of class, `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+    instructor: Vec<JsonLdInstructor>,
+}
+
+/// `Course` document for a section page.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct JsonLdCourse {
+    #[serde(rename = "@context")]
+    #[ts(type = "\"https://schema.org\"")]
+    context: &'static str,
+    #[serde(rename = "@type")]
+    #[ts(type = "\"Course\"")]
+    kind: &'static str,
+    name: String,
+    /// Subject and number, e.g. `CS 1083`.
+    course_code: String,
+    description: String,
+    url: String,
+    provider: JsonLdOrganization,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    number_of_credits: Option<f64>,
+    has_course_instance: Vec<JsonLdCourseInstance>,
+}
+
+/// Serve a JSON-LD document.
+fn ld_json_response(doc: &impl Serialize) -> Result<Response, ApiError> {
+    let body = serde_json::to_string(doc)
+        .map_err(|e| ApiError::internal_error(format!("Failed to encode JSON-LD: {e}")))?;
+    let mut response = body.into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/ld+json"),
+    );
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(cache::DETAIL),
+    );
+    Ok(response)
+}
+
+/// `GET /api/courses/{term}/{crn}/json-ld` -- `Course` structured data.
+#[utoipa::path(
+    get,
+    path = "/courses/{term}/{crn}/json-ld",
+    tag = "courses",
+    summary = "schema.org Course structured data for a section",
+    params(
+        ("term" = String, Path, description = "Term code or slug (e.g. 202620 or spring-2026)"),
+        ("crn" = String, Path, description = "Course reference number"),
+    ),
+    responses(
+        (status = 200, description = "JSON-LD document", body = Object, content_type = "application/ld+json"),
+    ),
+)]
+pub async fn course_json_ld(
+    State(state): State<AppState>,
+    Path((term, crn)): Path<(String, String)>,
+) -> Result<Response, ApiError> {
+    if !state.crawler.structured_data() {
+        return Err(ApiError::not_found("Structured data is disabled"));
+    }
+
+    let term_code = Term::resolve_to_code(&term).ok_or_else(|| ApiError::invalid_term(&term))?;
+    let course = data::courses::get_course_by_crn(&state.db_pool, &crn, &term_code)
+        .await
+        .map_err(|e| db_error("Course lookup", e))?
+        .or_not_found("Course", &crn)?;
+    let instructors = data::courses::get_course_instructors(&state.db_pool, course.id)
+        .await
+        .map_err(|e| db_error("Course instructors", e))?;
+
+    let origin = state.public_origin.as_deref().unwrap_or_default();
+    ld_json_response(&course_json(&course, &instructors, origin))
+}
+
+/// `GET /api/instructors/{slug}/json-ld` -- `Person` structured data.
+#[utoipa::path(
+    get,
+    path = "/instructors/{slug}/json-ld",
+    tag = "instructors",
+    summary = "schema.org Person structured data for an instructor",
+    params(
+        ("slug" = String, Path, description = "Instructor slug"),
+    ),
+    responses(
+        (status = 200, description = "JSON-LD document", body = Object, content_type = "application/ld+json"),
+    ),
+)]
+pub async fn instructor_json_ld(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Response, ApiError> {
+    if !state.crawler.structured_data() {
+        return Err(ApiError::not_found("Structured data is disabled"));
+    }
+
+    let summary = data::instructors::get_instructor_rating_by_slug(&state.db_pool, &slug)
+        .await
+        .map_err(|e| db_error("Get instructor rating", e))?
+        .or_not_found("Instructor", &slug)?;
+
+    let profile_url = format!(
+        "{}/instructors/{}",
+        state.public_origin.as_deref().unwrap_or_default(),
+        summary.slug
+    );
+
+    ld_json_response(&instructor_person(&summary, &profile_url))
+}
+
+/// Build the `Course` document for a section. `origin` prefixes page URLs.
+pub fn course_json(
+    course: &Course,
+    instructors: &[CourseInstructorDetail],
+    origin: &str,
+) -> JsonLdCourse {
+    let term = course.term_code.parse::<Term>().ok();
+    let term_slug = term
+        .as_ref()
+        .map(Term::slug)
+        .unwrap_or_else(|| course.term_code.clone());
+    let course_code = format!("{} {}", course.subject, course.course_number);
+
+    let section = course
+        .sequence_number
+        .as_deref()
+        .map(|seq| format!("Section {seq}"))
+        .unwrap_or_else(|| "A section".to_owned());
+    let description = match &term {
+        Some(term) => format!(
+            "{section} of {course_code}: {} at {UNIVERSITY_NAME}, {}. CRN {}.",
+            course.title,
+            term.description(),
+            course.crn
+        ),
+        None => format!(
+            "{section} of {course_code}: {} at {UNIVERSITY_NAME}. CRN {}.",
+            course.title, course.crn
+        ),
+    };
+
+    let course_mode = course
+        .instructional_method
+        .as_deref()
+        .and_then(|code| InstructionalMethod::from_code(code).ok())
+        .and_then(|method| match method {
+            InstructionalMethod::InPerson => Some("Onsite".to_owned()),
+            InstructionalMethod::Online(_) => Some("Online".to_owned()),
+            InstructionalMethod::Hybrid(_) => Some("Blended".to_owned()),
+            InstructionalMethod::Independent => None,
+        });
+
+    let meeting_times: Vec<DbMeetingTime> =
+        serde_json::from_value(course.meeting_times.clone()).unwrap_or_default();
+    let date = |d: NaiveDate| d.format("%Y-%m-%d").to_string();
+    let start_date = meeting_times.iter().map(|mt| mt.date_range.start).min();
+    let end_date = meeting_times.iter().map(|mt| mt.date_range.end).max();
+
+    // Primary instructor first, as the course page lists them.
+    let mut instructors: Vec<&CourseInstructorDetail> = instructors.iter().collect();
+    instructors.sort_by_key(|i| !i.is_primary);
+    let instructor = instructors
+        .into_iter()
+        .map(|i| JsonLdInstructor {
+            kind: "Person",
+            name: i.display_name.clone(),
+            url: i
+                .slug
+                .as_deref()
+                .map(|slug| format!("{origin}/instructors/{slug}")),
+        })
+        .collect();
+
+    JsonLdCourse {
+        context: SCHEMA_CONTEXT,
+        kind: "Course",
+        name: course.title.clone(),
+        course_code,
+        description,
+        url: format!("{origin}/courses/{term_slug}/{}", course.crn),
+        provider: JsonLdOrganization::university(),
+        number_of_credits: course.credit_hours.or(course.credit_hour_low),
+        has_course_instance: vec![JsonLdCourseInstance {
+            kind: "CourseInstance",
+            course_mode,
+            start_date: start_date.map(date),
+            end_date: end_date.map(date),
+            instructor,
+        }],
+    }
+}
+
+/// Build the `Person` document for an instructor.
+pub fn instructor_person(summary: &InstructorRatingSummary, profile_url: &str) -> JsonLdPerson {
+    // Search engines reject ratings without a count behind them.
+    let aggregate_rating = summary
+        .rating
+        .as_ref()
+        .filter(|rating| rating.total_responses > 0)
+        .map(|rating| JsonLdAggregateRating {
+            kind: "AggregateRating",
+            rating_value: format!("{:.1}", rating.score),
+            best_rating: 5,
+            worst_rating: 1,
+            rating_count: rating.total_responses,
+        });
+
+    JsonLdPerson {
+        context: SCHEMA_CONTEXT,
+        kind: "Person",
+        name: summary.display_name.clone(),
+        url: profile_url.to_owned(),
+        job_title: "Instructor",
+        works_for: JsonLdOrganization::university(),
+        aggregate_rating,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::course_types::{InstructorRating, RatingSource};
+
+    fn summary(rating: Option<InstructorRating>) -> InstructorRatingSummary {
+        InstructorRatingSummary {
+            slug: "jane-doe".to_owned(),
+            display_name: "Jane Doe".to_owned(),
+            rating,
+        }
+    }
+
+    fn person(summary: &InstructorRatingSummary, profile_url: &str) -> serde_json::Value {
+        serde_json::to_value(instructor_person(summary, profile_url)).unwrap()
+    }
+
+    fn rating(score: f32, total_responses: i32) -> InstructorRating {
+        InstructorRating {
+            score,
+            rank_score: score - 0.3,
+            ci_lower: score - 0.3,
+            ci_upper: score + 0.3,
+            confidence: 0.8,
+            source: RatingSource::Both,
+            total_responses,
+        }
+    }
+
+    #[test]
+    fn rated_instructor_has_aggregate_rating() {
+        let doc = person(
+            &summary(Some(rating(4.26, 57))),
+            "https://example.com/instructors/jane-doe",
+        );
+        assert_eq!(doc["@type"], "Person");
+        assert_eq!(doc["name"], "Jane Doe");
+        assert_eq!(doc["url"], "https://example.com/instructors/jane-doe");
+        assert_eq!(doc["aggregateRating"]["ratingValue"], "4.3");
+        assert_eq!(doc["aggregateRating"]["ratingCount"], 57);
+    }
+
+    #[test]
+    fn unrated_instructor_omits_aggregate_rating() {
+        let doc = person(&summary(None), "/instructors/jane-doe");
+        assert!(doc.get("aggregateRating").is_none());
+
+        let doc = person(&summary(Some(rating(4.0, 0))), "/instructors/jane-doe");
+        assert!(doc.get("aggregateRating").is_none());
+    }
+
+    fn course(instructional_method: Option<&str>) -> Course {
+        Course {
+            id: 1,
+            crn: "12345".to_owned(),
+            subject: "CS".to_owned(),
+            course_number: "1083".to_owned(),
+            title: "Programming I".to_owned(),
+            term_code: "202620".to_owned(),
+            enrollment: 20,
+            max_enrollment: 30,
+            wait_count: 0,
+            wait_capacity: 0,
+            last_scraped_at: chrono::Utc::now(),
+            sequence_number: Some("001".to_owned()),
+            part_of_term: None,
+            instructional_method: instructional_method.map(str::to_owned),
+            campus: None,
+            credit_hours: Some(3.0),
+            credit_hour_low: None,
+            credit_hour_high: None,
+            cross_list: None,
+            cross_list_capacity: None,
+            cross_list_count: None,
+            link_identifier: None,
+            is_section_linked: None,
+            details_scraped_at: None,
+            meeting_times: serde_json::json!([]),
+            attributes: serde_json::json!([]),
+        }
+    }
+
+    #[test]
+    fn course_describes_its_section() {
+        let doc =
+            serde_json::to_value(course_json(&course(Some("FF")), &[], "https://example.com"))
+                .unwrap();
+        assert_eq!(doc["@type"], "Course");
+        assert_eq!(doc["courseCode"], "CS 1083");
+        assert_eq!(doc["url"], "https://example.com/courses/spring-2026/12345");
+        assert_eq!(doc["provider"]["name"], UNIVERSITY_NAME);
+        assert_eq!(doc["numberOfCredits"], 3.0);
+        assert_eq!(doc["hasCourseInstance"][0]["courseMode"], "Onsite");
+        // No meetings, so no dates.
+        assert!(doc["hasCourseInstance"][0].get("startDate").is_none());
+    }
+
+    #[test]
+    fn unknown_method_omits_course_mode() {
+        let doc = serde_json::to_value(course_json(&course(None), &[], "")).unwrap();
+        assert!(doc["hasCourseInstance"][0].get("courseMode").is_none());
+    }
+}
@@ -0,0 +1,195 @@
+//! Maintenance mode: an admin switch that makes the public API read-only.
+//!
+//! The switch is persisted as JSON in `app_kv` and cached in memory.
+//! [`MaintenanceLayer`] consults it on every request: while enabled, reads
+//! keep working (served from in-memory caches where handlers have them) and
+//! writes or long-lived streams get a 503 with `Retry-After`. Admin, auth,
+//! and health routes are never blocked, so the switch can always be turned
+//! back off.
+//!
+//! [`MaintenanceLayer`]: crate::web::middleware::maintenance::MaintenanceLayer
+
+use std::sync::RwLock;
+
+use anyhow::{Context, Result};
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::data::kv;
+
+/// `app_kv` key holding the serialized [`MaintenanceConfig`].
+pub const KV_MAINTENANCE_CONFIG: &str = "maintenance.config";
+
+const DEFAULT_RETRY_AFTER_SECS: u32 = 300;
+
+/// Admin-editable maintenance switch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct MaintenanceConfig {
+    pub enabled: bool,
+    /// Shown to users alongside the 503; a generic message is used when unset.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Sent as `Retry-After` on rejected requests.
+    #[serde(default = "default_retry_after")]
+    pub retry_after_secs: u32,
+    /// When maintenance was last switched on. Set by the server.
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+}
+
+fn default_retry_after() -> u32 {
+    DEFAULT_RETRY_AFTER_SECS
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            message: None,
+            retry_after_secs: DEFAULT_RETRY_AFTER_SECS,
+            since: None,
+        }
+    }
+}
+
+impl MaintenanceConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=86_400).contains(&self.retry_after_secs) {
+            return Err("retryAfterSecs must be between 1 and 86400".to_owned());
+        }
+        if self.message.as_ref().is_some_and(|m| m.len() > 500) {
+            return Err("message must be at most 500 characters".to_owned());
+        }
+        Ok(())
+    }
+
+    pub fn user_message(&self) -> &str {
+        self.message
+            .as_deref()
+            .unwrap_or("Banner is undergoing maintenance. Please try again shortly.")
+    }
+}
+
+/// Whether a request may proceed while maintenance mode is on.
+pub fn allowed_during_maintenance(method: &Method, path: &str) -> bool {
+    // Pages, static assets, and the tooling needed to manage maintenance.
+    if !path.starts_with("/api/")
+        || path.starts_with("/api/admin/")
+        || path.starts_with("/api/auth/")
+        || path.starts_with("/api/health")
+        || path.starts_with("/api/status")
+        || path.starts_with("/api/metrics")
+    {
+        return true;
+    }
+    // Held-open connections would pin the service through the window.
+    if path == "/api/ws" || path == "/api/updates/poll" {
+        return false;
+    }
+    // The timeline queries are reads that take their filters as a POST body.
+    if path == "/api/timeline" || path == "/api/timeline/compare" {
+        return true;
+    }
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// In-memory maintenance state.
+#[derive(Default)]
+pub struct MaintenanceMode {
+    config: RwLock<MaintenanceConfig>,
+}
+
+impl MaintenanceMode {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the current config.
+    pub fn config(&self) -> MaintenanceConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// The config, if maintenance is currently on.
+    pub fn active(&self) -> Option<MaintenanceConfig> {
+        let config = self.config.read().unwrap();
+        config.enabled.then(|| config.clone())
+    }
+
+    /// Load the persisted config, falling back to disabled when absent.
+    pub async fn load(&self, pool: &PgPool) -> Result<()> {
+        if let Some(raw) = kv::get(pool, KV_MAINTENANCE_CONFIG).await? {
+            let config: MaintenanceConfig =
+                serde_json::from_str(&raw).context("invalid persisted maintenance config")?;
+            *self.config.write().unwrap() = config;
+        }
+        Ok(())
+    }
+
+    /// Persist and apply a new config, stamping `since` when switching on.
+    pub async fn save(&self, pool: &PgPool, mut config: MaintenanceConfig) -> Result<()> {
+        let current = self.config();
+        config.since = match (config.enabled, current.enabled) {
+            (true, true) => current.since,
+            (true, false) => Some(Utc::now()),
+            (false, _) => None,
+        };
+        let raw =
+            serde_json::to_string(&config).context("failed to serialize maintenance config")?;
+        kv::set(pool, KV_MAINTENANCE_CONFIG, &raw).await?;
+        *self.config.write().unwrap() = config;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn management_routes_stay_available() {
+        for path in [
+            "/api/admin/maintenance",
+            "/api/auth/login",
+            "/api/health",
+            "/api/status",
+            "/courses",
+        ] {
+            assert!(allowed_during_maintenance(&Method::POST, path), "{path}");
+        }
+    }
+
+    #[test]
+    fn public_api_is_read_only() {
+        assert!(allowed_during_maintenance(
+            &Method::GET,
+            "/api/courses/search"
+        ));
+        assert!(allowed_during_maintenance(&Method::POST, "/api/timeline"));
+        assert!(allowed_during_maintenance(
+            &Method::POST,
+            "/api/timeline/compare"
+        ));
+        assert!(!allowed_during_maintenance(&Method::POST, "/api/feedback"));
+        assert!(!allowed_during_maintenance(&Method::GET, "/api/ws"));
+        assert!(!allowed_during_maintenance(
+            &Method::GET,
+            "/api/updates/poll"
+        ));
+    }
+
+    #[test]
+    fn validates_retry_after() {
+        let config = MaintenanceConfig {
+            retry_after_secs: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(MaintenanceConfig::default().validate().is_ok());
+    }
+}
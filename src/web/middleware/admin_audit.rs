@@ -0,0 +1,208 @@
+//! Records every mutating admin API call in `admin_actions`.
+//!
+//! Applied to the admin router only. Safe methods pass straight through; for
+//! the rest, the caller is resolved from the session cookie, and the request
+//! body and (for successes) the JSON response are stored alongside the
+//! status, redacted the same way debug captures are. Bodies larger than
+//! [`MAX_CAPTURED_BODY`], or that fail while being read, are recorded as a
+//! placeholder and passed on untouched.
+//!
+//! Handlers that modify a single resource attach its state from before the
+//! change as a [`PriorState`] response part; the middleware compares it with
+//! the response and records the fields that changed.
+//!
+//! Requests from non-admins are not recorded; the handler rejects them anyway.
+
+use std::convert::Infallible;
+
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::{FromRequestParts, MatchedPath, Request, State};
+use axum::http::{HeaderMap, Method, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponseParts, Response, ResponseParts};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use tracing::warn;
+
+use crate::data::admin_actions::{self, NewAdminAction};
+use crate::state::AppState;
+use crate::web::auth::extractors::AdminUser;
+use crate::web::debug_capture::{
+    MAX_CAPTURED_BODY, buffer_body, omitted_body, redact_body, redact_json,
+};
+
+/// A handler's target as it was before the call, returned alongside the
+/// response, e.g. `Ok((PriorState::of(&before), Json(after)))`.
+#[derive(Debug, Clone)]
+pub struct PriorState(Value);
+
+impl PriorState {
+    pub fn of<T: Serialize>(value: &T) -> Self {
+        Self(serde_json::to_value(value).unwrap_or(Value::Null))
+    }
+}
+
+impl IntoResponseParts for PriorState {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        res.extensions_mut().insert(self);
+        Ok(res)
+    }
+}
+
+pub async fn record_admin_action(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return next.run(req).await;
+    }
+
+    let (mut parts, body) = req.into_parts();
+    let Ok(AdminUser(user)) = AdminUser::from_request_parts(&mut parts, &state).await else {
+        return next.run(Request::from_parts(parts, body)).await;
+    };
+    // The handler's own `AdminUser` picks this up instead of resolving again.
+    parts.extensions.insert(AdminUser(user.clone()));
+
+    let method = parts.method.to_string();
+    let path = parts.uri.path().to_owned();
+    let route = parts
+        .extensions
+        .get::<MatchedPath>()
+        .map(|m| m.as_str().to_owned());
+
+    let (payload, body) = match body.size_hint().exact() {
+        Some(0) => (None, body),
+        Some(len) if len as usize <= MAX_CAPTURED_BODY => match buffer_body(body).await {
+            Ok(bytes) => (redacted_value(&parts.headers, &bytes), Body::from(bytes)),
+            Err(body) => (omitted_body(Some(len)).map(Value::String), body),
+        },
+        len => (omitted_body(len).map(Value::String), body),
+    };
+
+    let resp = next.run(Request::from_parts(parts, body)).await;
+    let status = resp.status();
+
+    let (resp_parts, resp_body) = resp.into_parts();
+    let (result, resp_body) = match resp_body.size_hint().exact() {
+        Some(len) if status.is_success() && len > 0 && len as usize <= MAX_CAPTURED_BODY => {
+            match buffer_body(resp_body).await {
+                Ok(bytes) => (
+                    redacted_value(&resp_parts.headers, &bytes),
+                    Body::from(bytes),
+                ),
+                Err(body) => (None, body),
+            }
+        }
+        _ => (None, resp_body),
+    };
+    let changes = resp_parts.extensions.get::<PriorState>().map(|prior| {
+        let mut prior = prior.0.clone();
+        redact_json(&mut prior);
+        changed_fields(&prior, result.as_ref())
+    });
+
+    let action = NewAdminAction {
+        admin_id: user.id,
+        admin_username: &user.discord_username,
+        method: &method,
+        path: &path,
+        route: route.as_deref(),
+        status: status.as_u16(),
+        payload,
+        result,
+        changes,
+    };
+    if let Err(e) = admin_actions::insert_action(&state.db_pool, &action).await {
+        warn!(error = ?e, method, path, "Failed to record admin action");
+    }
+
+    Response::from_parts(resp_parts, resp_body)
+}
+
+/// JSON bodies are stored as JSON; anything else as its redacted rendering.
+fn redacted_value(headers: &HeaderMap, bytes: &Bytes) -> Option<Value> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+    let is_json = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|m| m.trim().to_ascii_lowercase())
+        .is_some_and(|m| m == "application/json" || m.ends_with("+json"));
+
+    if is_json && let Ok(mut json) = serde_json::from_slice::<Value>(bytes) {
+        redact_json(&mut json);
+        return Some(json);
+    }
+    redact_body(content_type, bytes).map(Value::String)
+}
+
+/// Top-level fields that differ between `prior` and `result`, as
+/// `{"field": {"old": .., "new": ..}}`. Without a result (a delete), every
+/// field becomes `null`. Non-object states are compared as a whole.
+fn changed_fields(prior: &Value, result: Option<&Value>) -> Value {
+    let null = Value::Null;
+    let result = result.unwrap_or(&null);
+    let Value::Object(old) = prior else {
+        return if prior == result {
+            json!({})
+        } else {
+            json!({ "old": prior, "new": result })
+        };
+    };
+    let new = result.as_object();
+
+    let mut changes = Map::new();
+    for (field, old_value) in old {
+        let new_value = new.and_then(|n| n.get(field)).unwrap_or(&null);
+        if old_value != new_value {
+            changes.insert(field.clone(), json!({ "old": old_value, "new": new_value }));
+        }
+    }
+    for (field, new_value) in new.into_iter().flatten() {
+        if !old.contains_key(field) {
+            changes.insert(field.clone(), json!({ "old": null, "new": new_value }));
+        }
+    }
+    Value::Object(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_fields_lists_only_differences() {
+        let prior = json!({ "isAdmin": false, "discordUsername": "ana", "note": "x" });
+        let result = json!({ "isAdmin": true, "discordUsername": "ana", "extra": 1 });
+        assert_eq!(
+            changed_fields(&prior, Some(&result)),
+            json!({
+                "isAdmin": { "old": false, "new": true },
+                "note": { "old": "x", "new": null },
+                "extra": { "old": null, "new": 1 },
+            })
+        );
+    }
+
+    #[test]
+    fn changed_fields_of_a_delete_clears_every_field() {
+        let prior = json!({ "id": 3, "enabled": true });
+        assert_eq!(
+            changed_fields(&prior, None),
+            json!({
+                "id": { "old": 3, "new": null },
+                "enabled": { "old": true, "new": null },
+            })
+        );
+    }
+
+    #[test]
+    fn changed_fields_of_an_unchanged_target_is_empty() {
+        let prior = json!({ "enabled": false });
+        assert_eq!(changed_fields(&prior, Some(&prior)), json!({}));
+    }
+}
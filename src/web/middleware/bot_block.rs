@@ -0,0 +1,84 @@
+//! Rejects requests from user agents on the crawler blocklist.
+//!
+//! Patterns come from [`CrawlerPolicy`] and are matched as case-insensitive
+//! substrings. `/robots.txt` is always served so well-behaved bots can still
+//! read the rules. Blocked requests get a 403 and bump a per-pattern counter
+//! surfaced on `GET /api/admin/crawler`.
+
+use crate::web::crawler::CrawlerPolicy;
+use crate::web::error::{ApiError, ApiErrorCode};
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::header::USER_AGENT;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::debug;
+
+#[derive(Clone)]
+pub struct BotBlockLayer {
+    policy: Arc<CrawlerPolicy>,
+}
+
+impl BotBlockLayer {
+    pub fn new(policy: Arc<CrawlerPolicy>) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for BotBlockLayer {
+    type Service = BotBlockService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BotBlockService {
+            inner,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BotBlockService<S> {
+    inner: S,
+    policy: Arc<CrawlerPolicy>,
+}
+
+impl<S, ResBody> Service<Request> for BotBlockService<S>
+where
+    S: Service<Request, Response = Response<ResBody>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send,
+    ResBody: Send + 'static,
+    Body: Into<ResBody>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        if req.uri().path() != "/robots.txt"
+            && let Some(ua) = req.headers().get(USER_AGENT).and_then(|v| v.to_str().ok())
+            && let Some(pattern) = self.policy.check_blocked(ua)
+        {
+            debug!(
+                user_agent = ua,
+                pattern = %pattern,
+                path = req.uri().path(),
+                "Blocked crawler request"
+            );
+            let resp = ApiError::new(ApiErrorCode::Forbidden, "Access denied")
+                .into_response()
+                .map(Into::into);
+            return Box::pin(async move { Ok(resp) });
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
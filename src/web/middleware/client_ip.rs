@@ -1,46 +1,200 @@
-//! On-demand client IP extraction from trusted proxy headers.
+//! Client IP resolution under a configurable proxy trust policy.
 //!
-//! Priority: `CF-Connecting-IP` (Cloudflare) -> rightmost `X-Forwarded-For`
-//! (Railway-appended) -> socket peer address.
+//! Forwarding headers are only honored when the socket peer is a trusted proxy
+//! (see [`ProxyTrust`]). Starting from the peer, `X-Forwarded-For` is walked
+//! right-to-left for at most `hops` entries; the first untrusted address is the
+//! client. If every hop walked was trusted, `CF-Connecting-IP` is used when
+//! present, otherwise the last hop. A direct client therefore always resolves
+//! to its socket address, whatever headers it sends.
 //!
-//! Use as an Axum extractor in handlers that need the client's real IP:
+//! [`ClientIpLayer`] resolves the address once per request and stores it in
+//! the request extensions. Handlers read it with the [`ClientIp`] extractor:
 //!
 //! ```ignore
 //! async fn handler(ClientIp(ip): ClientIp, ...) -> impl IntoResponse { ... }
 //! ```
 
-use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::extract::{ConnectInfo, FromRequestParts, Request};
 use axum::http::StatusCode;
 use http::request::Parts;
 use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Ranges trusted by default: loopback, private and CGNAT networks (Railway's
+/// edge proxy) plus Cloudflare's published edge ranges.
+pub const DEFAULT_TRUSTED_PROXIES: &[&str] = &[
+    "127.0.0.0/8",
+    "10.0.0.0/8",
+    "172.16.0.0/12",
+    "192.168.0.0/16",
+    "100.64.0.0/10",
+    "::1/128",
+    "fc00::/7",
+    // https://www.cloudflare.com/ips/
+    "173.245.48.0/20",
+    "103.21.244.0/22",
+    "103.22.200.0/22",
+    "103.31.4.0/22",
+    "141.101.64.0/18",
+    "108.162.192.0/18",
+    "190.93.240.0/20",
+    "188.114.96.0/20",
+    "197.234.240.0/22",
+    "198.41.128.0/17",
+    "162.158.0.0/15",
+    "104.16.0.0/13",
+    "104.24.0.0/14",
+    "172.64.0.0/13",
+    "131.0.72.0/22",
+    "2400:cb00::/32",
+    "2606:4700::/32",
+    "2803:f800::/32",
+    "2405:b500::/32",
+    "2405:8100::/32",
+    "2a06:98c0::/29",
+    "2c0f:f248::/32",
+];
+
+/// An IPv4 or IPv6 network in CIDR notation. A bare address is a single-host network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpCidr {
+    pub fn is_ipv4(&self) -> bool {
+        self.addr.is_ipv4()
+    }
+
+    /// Length of the network prefix in bits.
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                prefix_matches(u32::from(net).into(), u32::from(ip).into(), 32, self.prefix)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_matches(u128::from(net), u128::from(ip), 128, self.prefix)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+fn prefix_matches(net: u128, ip: u128, bits: u8, prefix: u8) -> bool {
+    if prefix == 0 {
+        return true;
+    }
+    let shift = u32::from(bits - prefix);
+    (net >> shift) == (ip >> shift)
+}
+
+impl FromStr for IpCidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("Invalid IP address in '{s}'"))?;
+        let addr = addr.to_canonical();
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(p) => p
+                .parse::<u8>()
+                .ok()
+                .filter(|p| *p <= max)
+                .ok_or_else(|| format!("Invalid prefix length in '{s}'"))?,
+            None => max,
+        };
+        Ok(Self { addr, prefix })
+    }
+}
+
+/// Which upstream addresses may set forwarding headers, and how far to trust them.
+#[derive(Debug, Clone)]
+pub struct ProxyTrust {
+    trusted: Vec<IpCidr>,
+    /// Maximum number of `X-Forwarded-For` entries to walk from the right.
+    hops: usize,
+}
+
+impl Default for ProxyTrust {
+    fn default() -> Self {
+        let trusted = DEFAULT_TRUSTED_PROXIES
+            .iter()
+            .map(|s| s.parse().expect("default trusted proxy ranges are valid"))
+            .collect();
+        Self::new(trusted, 1)
+    }
+}
+
+impl ProxyTrust {
+    pub fn new(trusted: Vec<IpCidr>, hops: usize) -> Self {
+        Self { trusted, hops }
+    }
+
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trusted.iter().any(|cidr| cidr.contains(ip))
+    }
+
+    /// Resolve the client address for a request received from `peer`.
+    pub fn resolve(&self, peer: IpAddr, headers: &http::HeaderMap) -> IpAddr {
+        let mut addr = peer.to_canonical();
+        if !self.is_trusted(addr) {
+            return addr;
+        }
+
+        if let Some(xff) = header_str(headers, "x-forwarded-for") {
+            for entry in xff.rsplit(',').take(self.hops) {
+                let Ok(ip) = entry.trim().parse::<IpAddr>() else {
+                    return addr;
+                };
+                addr = ip.to_canonical();
+                if !self.is_trusted(addr) {
+                    return addr;
+                }
+            }
+        }
+
+        // Every hop so far is a trusted proxy, so Cloudflare's header is genuine.
+        header_str(headers, "cf-connecting-ip")
+            .and_then(|s| s.trim().parse::<IpAddr>().ok())
+            .map_or(addr, |ip| ip.to_canonical())
+    }
+}
 
 /// The resolved client IP address.
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ClientIp(pub IpAddr);
 
 impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
     type Rejection = (StatusCode, &'static str);
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        // 1. CF-Connecting-IP -- set by Cloudflare, most trustworthy.
-        if let Some(ip) =
-            header_str(&parts.headers, "cf-connecting-ip").and_then(|s| s.parse::<IpAddr>().ok())
-        {
-            return Ok(ClientIp(ip));
-        }
-
-        // 2. Rightmost X-Forwarded-For -- appended by Railway's edge proxy.
-        if let Some(xff) = header_str(&parts.headers, "x-forwarded-for")
-            && let Some(ip) = xff
-                .rsplit(',')
-                .next()
-                .map(str::trim)
-                .and_then(|s| s.parse::<IpAddr>().ok())
-        {
-            return Ok(ClientIp(ip));
+        if let Some(ip) = parts.extensions.get::<ClientIp>() {
+            return Ok(*ip);
         }
 
-        // 3. Socket peer address (local dev fallback).
+        // Outside `ClientIpLayer` (tests, nested services): the socket peer is
+        // the only address we can vouch for.
         if let Some(ConnectInfo(addr)) = parts.extensions.get::<ConnectInfo<SocketAddr>>() {
             return Ok(ClientIp(addr.ip()));
         }
@@ -52,6 +206,166 @@ impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
     }
 }
 
+/// Resolves [`ClientIp`] from the socket peer and forwarding headers.
+///
+/// Must wrap every layer that reads the client IP (request logging, rate limiting).
+#[derive(Clone)]
+pub struct ClientIpLayer {
+    trust: Arc<ProxyTrust>,
+}
+
+impl ClientIpLayer {
+    pub fn new(trust: Arc<ProxyTrust>) -> Self {
+        Self { trust }
+    }
+}
+
+impl<S> Layer<S> for ClientIpLayer {
+    type Service = ClientIpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientIpService {
+            inner,
+            trust: self.trust.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ClientIpService<S> {
+    inner: S,
+    trust: Arc<ProxyTrust>,
+}
+
+impl<S> Service<Request> for ClientIpService<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        let peer = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        if let Some(peer) = peer {
+            let ip = self.trust.resolve(peer, req.headers());
+            req.extensions_mut().insert(ClientIp(ip));
+        }
+        self.inner.call(req)
+    }
+}
+
 pub fn header_str<'a>(headers: &'a http::HeaderMap, name: &str) -> Option<&'a str> {
     headers.get(name).and_then(|v| v.to_str().ok())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> http::HeaderMap {
+        let mut map = http::HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn cidr_contains() {
+        let net: IpCidr = "10.0.0.0/8".parse().unwrap();
+        assert!(net.contains(ip("10.255.1.2")));
+        assert!(!net.contains(ip("11.0.0.1")));
+        assert!(net.contains(ip("::ffff:10.1.2.3")));
+
+        let v6: IpCidr = "2606:4700::/32".parse().unwrap();
+        assert!(v6.contains(ip("2606:4700:1::1")));
+        assert!(!v6.contains(ip("10.0.0.1")));
+
+        let host: IpCidr = "203.0.113.7".parse().unwrap();
+        assert!(host.contains(ip("203.0.113.7")));
+        assert!(!host.contains(ip("203.0.113.8")));
+
+        assert!(
+            "0.0.0.0/0"
+                .parse::<IpCidr>()
+                .unwrap()
+                .contains(ip("8.8.8.8"))
+        );
+    }
+
+    #[test]
+    fn cidr_rejects_garbage() {
+        assert!("10.0.0.0/33".parse::<IpCidr>().is_err());
+        assert!("not-an-ip/8".parse::<IpCidr>().is_err());
+        assert!("::/129".parse::<IpCidr>().is_err());
+    }
+
+    #[test]
+    fn untrusted_peer_ignores_forwarding_headers() {
+        let trust = ProxyTrust::default();
+        let h = headers(&[
+            ("cf-connecting-ip", "1.1.1.1"),
+            ("x-forwarded-for", "2.2.2.2"),
+        ]);
+        assert_eq!(trust.resolve(ip("198.51.100.4"), &h), ip("198.51.100.4"));
+    }
+
+    #[test]
+    fn trusted_peer_uses_rightmost_forwarded_address() {
+        let trust = ProxyTrust::default();
+        let h = headers(&[("x-forwarded-for", "6.6.6.6, 198.51.100.4")]);
+        assert_eq!(trust.resolve(ip("100.64.0.2"), &h), ip("198.51.100.4"));
+    }
+
+    #[test]
+    fn cloudflare_header_requires_trusted_chain() {
+        let trust = ProxyTrust::default();
+
+        // Via Cloudflare: the hop Railway saw is a Cloudflare edge.
+        let via_cf = headers(&[
+            ("cf-connecting-ip", "198.51.100.4"),
+            ("x-forwarded-for", "198.51.100.4, 172.68.1.1"),
+        ]);
+        assert_eq!(trust.resolve(ip("100.64.0.2"), &via_cf), ip("198.51.100.4"));
+
+        // Bypassing Cloudflare: a spoofed header from an untrusted hop is ignored.
+        let spoofed = headers(&[
+            ("cf-connecting-ip", "1.1.1.1"),
+            ("x-forwarded-for", "203.0.113.9"),
+        ]);
+        assert_eq!(trust.resolve(ip("100.64.0.2"), &spoofed), ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn hop_count_limits_walk() {
+        let trust = ProxyTrust::new(vec!["10.0.0.0/8".parse().unwrap()], 2);
+        let h = headers(&[("x-forwarded-for", "203.0.113.9, 10.0.0.5, 10.0.0.6")]);
+        // Two hops walked, both trusted: the last one is the best we know.
+        assert_eq!(trust.resolve(ip("10.0.0.1"), &h), ip("10.0.0.5"));
+
+        let zero = ProxyTrust::new(vec!["10.0.0.0/8".parse().unwrap()], 0);
+        assert_eq!(zero.resolve(ip("10.0.0.1"), &h), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn malformed_forwarded_entry_stops_at_last_trusted_hop() {
+        let trust = ProxyTrust::default();
+        let h = headers(&[
+            ("cf-connecting-ip", "1.1.1.1"),
+            ("x-forwarded-for", "garbage"),
+        ]);
+        assert_eq!(trust.resolve(ip("127.0.0.1"), &h), ip("127.0.0.1"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Concurrent connection cap for long-lived streams (WebSocket/SSE).
+//!
+//! Token buckets in [`rate_limit`](super::rate_limit) bound how often a client
+//! may connect, not how many connections it holds open. This limiter counts
+//! live connections per IP and overall; a slot is held by a
+//! [`ConnectionGuard`] for the lifetime of the stream and released on drop.
+
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::web::error::{ApiError, ApiErrorCode};
+
+/// Why a connection slot could not be acquired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionLimitExceeded {
+    PerIp,
+    Total,
+}
+
+impl From<ConnectionLimitExceeded> for ApiError {
+    fn from(e: ConnectionLimitExceeded) -> Self {
+        let message = match e {
+            ConnectionLimitExceeded::PerIp => "Too many open stream connections from this address",
+            ConnectionLimitExceeded::Total => "Server is at its stream connection limit",
+        };
+        ApiError::new(ApiErrorCode::RateLimited, message)
+    }
+}
+
+pub struct ConnectionLimiter {
+    per_ip: usize,
+    total: usize,
+    counts: DashMap<IpAddr, usize>,
+    active: AtomicUsize,
+}
+
+pub type SharedConnectionLimiter = Arc<ConnectionLimiter>;
+
+impl ConnectionLimiter {
+    pub fn new(per_ip: usize, total: usize) -> Self {
+        Self {
+            per_ip,
+            total,
+            counts: DashMap::new(),
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    /// Reserve a connection slot for `ip`, held until the guard is dropped.
+    pub fn try_acquire(
+        self: &Arc<Self>,
+        ip: IpAddr,
+    ) -> Result<ConnectionGuard, ConnectionLimitExceeded> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < self.total).then_some(n + 1)
+            })
+            .map_err(|_| ConnectionLimitExceeded::Total)?;
+
+        let admitted = match self.counts.entry(ip) {
+            Entry::Occupied(mut e) if *e.get() < self.per_ip => {
+                *e.get_mut() += 1;
+                true
+            }
+            Entry::Vacant(e) if self.per_ip > 0 => {
+                e.insert(1);
+                true
+            }
+            _ => false,
+        };
+        if !admitted {
+            self.active.fetch_sub(1, Ordering::AcqRel);
+            return Err(ConnectionLimitExceeded::PerIp);
+        }
+
+        Ok(ConnectionGuard {
+            limiter: self.clone(),
+            ip,
+        })
+    }
+
+    /// Open connections currently held, across all IPs.
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// Open connections currently held by `ip`.
+    pub fn active_for(&self, ip: IpAddr) -> usize {
+        self.counts.get(&ip).map_or(0, |c| *c)
+    }
+
+    fn release(&self, ip: IpAddr) {
+        self.active.fetch_sub(1, Ordering::AcqRel);
+        if let Entry::Occupied(mut e) = self.counts.entry(ip) {
+            *e.get_mut() -= 1;
+            if *e.get() == 0 {
+                e.remove();
+            }
+        }
+    }
+}
+
+/// Holds one connection slot; releases it on drop.
+pub struct ConnectionGuard {
+    limiter: SharedConnectionLimiter,
+    ip: IpAddr,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.limiter.release(self.ip);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::from([10, 0, 0, last])
+    }
+
+    #[test]
+    fn per_ip_cap_is_enforced_and_released_on_drop() {
+        let limiter = Arc::new(ConnectionLimiter::new(2, 100));
+        let a = limiter.try_acquire(ip(1)).unwrap();
+        let _b = limiter.try_acquire(ip(1)).unwrap();
+        assert_eq!(
+            limiter.try_acquire(ip(1)).err(),
+            Some(ConnectionLimitExceeded::PerIp)
+        );
+        // Rejection must not leak a global slot.
+        assert_eq!(limiter.active(), 2);
+
+        drop(a);
+        assert_eq!(limiter.active_for(ip(1)), 1);
+        assert!(limiter.try_acquire(ip(1)).is_ok());
+    }
+
+    #[test]
+    fn total_cap_applies_across_ips() {
+        let limiter = Arc::new(ConnectionLimiter::new(5, 2));
+        let _a = limiter.try_acquire(ip(1)).unwrap();
+        let _b = limiter.try_acquire(ip(2)).unwrap();
+        assert_eq!(
+            limiter.try_acquire(ip(3)).err(),
+            Some(ConnectionLimitExceeded::Total)
+        );
+        assert_eq!(limiter.active_for(ip(3)), 0);
+    }
+
+    #[test]
+    fn idle_ips_are_evicted() {
+        let limiter = Arc::new(ConnectionLimiter::new(1, 10));
+        drop(limiter.try_acquire(ip(1)).unwrap());
+        assert!(limiter.counts.is_empty());
+        assert_eq!(limiter.active(), 0);
+    }
+}
@@ -0,0 +1,131 @@
+//! Records redacted request/response pairs for routes under an active
+//! [`DebugCaptures`] window.
+//!
+//! Only bodies with a known length up to [`MAX_CAPTURED_BODY`] are buffered;
+//! larger or streamed bodies, and bodies that fail mid-read, pass through
+//! untouched and are logged as omitted. Sits innermost so it sees uncompressed handler output.
+
+use crate::data::unsigned::DurationMs;
+use crate::web::debug_capture::{
+    CapturedExchange, DebugCaptures, MAX_CAPTURED_BODY, buffer_body, omitted_body, redact_body,
+    redact_headers, redact_query,
+};
+use axum::body::{Body, HttpBody};
+use axum::extract::Request;
+use axum::http::{HeaderMap, header};
+use axum::response::Response;
+use chrono::Utc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+#[derive(Clone)]
+pub struct DebugCaptureLayer {
+    captures: Arc<DebugCaptures>,
+}
+
+impl DebugCaptureLayer {
+    pub fn new(captures: Arc<DebugCaptures>) -> Self {
+        Self { captures }
+    }
+}
+
+impl<S> Layer<S> for DebugCaptureLayer {
+    type Service = DebugCaptureService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DebugCaptureService {
+            inner,
+            captures: self.captures.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DebugCaptureService<S> {
+    inner: S,
+    captures: Arc<DebugCaptures>,
+}
+
+impl<S> Service<Request> for DebugCaptureService<S>
+where
+    S: Service<Request, Response = Response> + Send + Clone + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        if !self.captures.is_recording(req.uri().path()) {
+            return Box::pin(self.inner.call(req));
+        }
+
+        let captures = self.captures.clone();
+        // Take the service `poll_ready` readied, leaving a clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let start = Instant::now();
+            let method = req.method().to_string();
+            let path = req.uri().path().to_owned();
+            let query = req.uri().query().map(redact_query);
+            let request_headers = redact_headers(req.headers());
+
+            let (parts, body) = req.into_parts();
+            let (request_body, body) = buffer(&parts.headers, body).await;
+            let response = inner.call(Request::from_parts(parts, body)).await?;
+
+            let (parts, body) = response.into_parts();
+            let (response_body, body) = buffer(&parts.headers, body).await;
+
+            captures.record(CapturedExchange {
+                at: Utc::now(),
+                method,
+                path,
+                query,
+                request_headers,
+                request_body,
+                status: parts.status.as_u16(),
+                response_headers: redact_headers(&parts.headers),
+                response_body,
+                duration_ms: DurationMs::new(
+                    u32::try_from(start.elapsed().as_millis()).unwrap_or(u32::MAX),
+                ),
+            });
+
+            Ok(Response::from_parts(parts, body))
+        })
+    }
+}
+
+/// Read a small body into memory, returning its redacted rendering and a
+/// replacement body. Bodies too large or of unknown length are passed
+/// through unread, as are bodies that fail while being read.
+async fn buffer(headers: &HeaderMap, body: Body) -> (Option<String>, Body) {
+    let len = body.size_hint().exact();
+    match len {
+        Some(0) => return (None, body),
+        Some(len) if len as usize <= MAX_CAPTURED_BODY => {}
+        _ => return (omitted_body(len), body),
+    }
+
+    match buffer_body(body).await {
+        Ok(bytes) => {
+            let content_type = headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok());
+            (redact_body(content_type, &bytes), Body::from(bytes))
+        }
+        Err(body) => (omitted_body(len), body),
+    }
+}
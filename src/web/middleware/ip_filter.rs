@@ -0,0 +1,89 @@
+//! Applies the [`IpFilter`] verdict to each request.
+//!
+//! Blocked and banned clients get a 403 before rate limiting runs, so they
+//! don't consume buckets. Trusted clients are tagged with [`TrustedClient`],
+//! which `RateLimitLayer` treats as a bypass.
+
+use crate::web::error::{ApiError, ApiErrorCode};
+use crate::web::ip_filter::{IpFilter, IpVerdict};
+use crate::web::middleware::client_ip::ClientIp;
+use axum::body::Body;
+use axum::extract::Request;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::debug;
+
+/// Request extension marking a client in a trusted range.
+#[derive(Debug, Clone, Copy)]
+pub struct TrustedClient;
+
+#[derive(Clone)]
+pub struct IpFilterLayer {
+    filter: Arc<IpFilter>,
+}
+
+impl IpFilterLayer {
+    pub fn new(filter: Arc<IpFilter>) -> Self {
+        Self { filter }
+    }
+}
+
+impl<S> Layer<S> for IpFilterLayer {
+    type Service = IpFilterService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        IpFilterService {
+            inner,
+            filter: self.filter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct IpFilterService<S> {
+    inner: S,
+    filter: Arc<IpFilter>,
+}
+
+impl<S, ResBody> Service<Request> for IpFilterService<S>
+where
+    S: Service<Request, Response = Response<ResBody>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send,
+    ResBody: Send + 'static,
+    Body: Into<ResBody>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        let Some(ClientIp(ip)) = req.extensions().get::<ClientIp>().copied() else {
+            return Box::pin(self.inner.call(req));
+        };
+
+        match self.filter.check(ip) {
+            IpVerdict::Trusted => {
+                req.extensions_mut().insert(TrustedClient);
+            }
+            IpVerdict::Allowed => {}
+            verdict @ (IpVerdict::Blocked | IpVerdict::Banned) => {
+                debug!(client_ip = %ip, ?verdict, path = req.uri().path(), "Rejected filtered IP");
+                let resp = ApiError::new(ApiErrorCode::Forbidden, "Access denied")
+                    .into_response()
+                    .map(Into::into);
+                return Box::pin(async move { Ok(resp) });
+            }
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
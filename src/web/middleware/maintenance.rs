@@ -0,0 +1,79 @@
+//! Rejects non-read-only public API requests while maintenance mode is on.
+//!
+//! See [`MaintenanceMode`] for what stays available. Rejected requests get a
+//! structured 503 with a `Retry-After` header.
+
+use crate::web::error::ApiError;
+use crate::web::maintenance::{MaintenanceMode, allowed_during_maintenance};
+use axum::body::Body;
+use axum::extract::Request;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::debug;
+
+#[derive(Clone)]
+pub struct MaintenanceLayer {
+    mode: Arc<MaintenanceMode>,
+}
+
+impl MaintenanceLayer {
+    pub fn new(mode: Arc<MaintenanceMode>) -> Self {
+        Self { mode }
+    }
+}
+
+impl<S> Layer<S> for MaintenanceLayer {
+    type Service = MaintenanceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MaintenanceService {
+            inner,
+            mode: self.mode.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MaintenanceService<S> {
+    inner: S,
+    mode: Arc<MaintenanceMode>,
+}
+
+impl<S, ResBody> Service<Request> for MaintenanceService<S>
+where
+    S: Service<Request, Response = Response<ResBody>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send,
+    ResBody: Send + 'static,
+    Body: Into<ResBody>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        if let Some(config) = self.mode.active()
+            && !allowed_during_maintenance(req.method(), req.uri().path())
+        {
+            debug!(
+                method = %req.method(),
+                path = req.uri().path(),
+                "Rejected request during maintenance"
+            );
+            let resp = ApiError::maintenance(config.user_message(), config.retry_after_secs.into())
+                .into_response()
+                .map(Into::into);
+            return Box::pin(async move { Ok(resp) });
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
@@ -0,0 +1,73 @@
+//! Request counting and latency for the Prometheus exporter.
+//!
+//! Requests are labeled by the same route groups the rate limiter uses, so
+//! label cardinality stays fixed no matter how many paths are hit.
+
+use crate::metrics::Metrics;
+use crate::web::middleware::rate_limit::classify_route;
+use axum::extract::Request;
+use axum::response::Response;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+#[derive(Clone)]
+pub struct MetricsLayer {
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsLayer {
+    pub fn new(metrics: Arc<Metrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService {
+            inner,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+    metrics: Arc<Metrics>,
+}
+
+impl<S, B> Service<Request> for MetricsService<S>
+where
+    S: Service<Request, Response = Response<B>> + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let group = classify_route(req.uri().path()).as_str();
+        let metrics = self.metrics.clone();
+        let start = Instant::now();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let result = future.await;
+            // Errors never reach the client as a response; count them as 500s.
+            let status = result.as_ref().map_or(500, |r| r.status().as_u16());
+            metrics.record_request(group, status, start.elapsed());
+            result
+        })
+    }
+}
@@ -1,4 +1,13 @@
+pub mod admin_audit;
+pub mod bot_block;
 pub mod client_ip;
+pub mod connection_limit;
+pub mod debug_capture;
+pub mod ip_filter;
+pub mod maintenance;
+pub mod metrics;
 pub mod rate_limit;
 pub mod request_id;
+pub mod response_cache;
 pub mod security_headers;
+pub mod session_renewal;
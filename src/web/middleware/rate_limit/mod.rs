@@ -1,37 +1,58 @@
-//! Inbound HTTP rate limiting with multi-window, per-IP token buckets.
+//! Inbound HTTP rate limiting with multi-window, per-IP (or per-API-key) token
+//! buckets.
 //!
 //! Four layers evaluated in order (first rejection wins):
 //!
 //! 1. **Global per-IP** -- burst (5s) + sustained (1min)
 //! 2. **Route-group** -- sustained (1min) + long-term (30min), different budgets for API/SSR/admin
 //! 3. **Endpoint-specific** -- all three windows on expensive endpoints
-//! 4. **Auth-aware multiplier** -- authenticated 2x, admin 10x
+//! 4. **Auth-aware multiplier** -- authenticated 2x, API key 5x, admin 10x
+//!
+//! The auth tier comes from the session cookie, resolved against the shared
+//! session cache. A session not yet cached is loaded only after the request
+//! passes the anonymous per-IP limits, and only if the token is well formed;
+//! tokens that fail to load are remembered briefly (in a bounded map) so a
+//! stale cookie doesn't cost a query on every request.
+//!
+//! Requests with a valid `X-Api-Key` are bucketed by key instead of by IP, so
+//! a keyed client gets its own quota wherever it runs. An unknown or revoked
+//! key falls back to the caller's IP.
 //!
 //! Requests carrying a valid `X-Internal-Token` header (set by the SSR proxy)
-//! bypass all rate limiting to avoid double-counting SSR -> API calls.
+//! bypass all rate limiting to avoid double-counting SSR -> API calls. So do
+//! clients in a trusted range (see [`crate::web::ip_filter`]).
 
-use crate::web::middleware::client_ip::header_str;
+use crate::data::models::User;
+use crate::data::sessions::is_well_formed_token;
+use crate::metrics::Metrics;
+use crate::web::auth::api_keys::{API_KEY_HEADER, ApiKeyCache};
+use crate::web::auth::extract_session_token;
+use crate::web::auth::session::SessionCache;
+use crate::web::middleware::client_ip::{ClientIp, header_str};
+use crate::web::middleware::ip_filter::TrustedClient;
 use axum::body::Body;
 use axum::extract::Request;
-use axum::http::HeaderValue;
 use axum::response::Response;
+use dashmap::DashMap;
 use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter, clock::Clock};
+use serde::Deserialize;
+use std::fmt;
 use std::net::IpAddr;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::task::{Context, Poll};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tower::{Layer, Service};
 use tracing::warn;
 
 // -- Route classification --
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum RouteGroup {
+pub(crate) enum RouteGroup {
     Api,
     Ssr,
     Admin,
-    /// Health/metrics endpoints -- no route-group limiting.
+    /// Health checks -- no route-group limiting.
     Internal,
     /// Static assets (JS, CSS, fonts, images) -- exempt from all rate limiting.
     Static,
@@ -43,15 +64,34 @@ enum TrackedEndpoint {
     CourseSearch,
     Suggest,
     Timeline,
+    Feedback,
+    Export,
+}
+
+impl RouteGroup {
+    /// Label used in metrics.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            RouteGroup::Api => "api",
+            RouteGroup::Ssr => "ssr",
+            RouteGroup::Admin => "admin",
+            RouteGroup::Internal => "internal",
+            RouteGroup::Static => "static",
+        }
+    }
 }
 
-fn classify_route(path: &str) -> RouteGroup {
+pub(crate) fn classify_route(path: &str) -> RouteGroup {
     if path.starts_with("/api/admin/") {
         RouteGroup::Admin
-    } else if path.starts_with("/api/health") || path.starts_with("/api/metrics") {
+    } else if path.starts_with("/api/health") {
         RouteGroup::Internal
     } else if path.starts_with("/api/") {
         RouteGroup::Api
+    } else if path.starts_with("/embed/") {
+        // Server-rendered like SSR pages; checked before the static-asset
+        // test so a slug with a file extension can't dodge limiting.
+        RouteGroup::Ssr
     } else if is_static_asset(path) {
         RouteGroup::Static
     } else {
@@ -105,6 +145,10 @@ fn classify_endpoint(path: &str) -> Option<TrackedEndpoint> {
         Some(TrackedEndpoint::Suggest)
     } else if path == "/api/timeline" {
         Some(TrackedEndpoint::Timeline)
+    } else if path == "/api/feedback" {
+        Some(TrackedEndpoint::Feedback)
+    } else if path == "/api/courses/export" {
+        Some(TrackedEndpoint::Export)
     } else {
         None
     }
@@ -112,64 +156,376 @@ fn classify_endpoint(path: &str) -> Option<TrackedEndpoint> {
 
 // -- Auth tier --
 
-#[derive(Debug, Clone, Copy)]
-#[allow(dead_code)] // Authenticated/Admin variants used once session-based tier detection is added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum AuthTier {
     Anonymous,
     Authenticated,
+    ApiKey,
     Admin,
 }
 
+/// Largest tier multiplier; quotas are scaled by this so every tier's cost
+/// per request is a whole number of cells.
+const MAX_MULTIPLIER: u32 = 10;
+
 impl AuthTier {
-    #[allow(dead_code)] // Used once session-based tier detection is added.
     fn multiplier(self) -> u32 {
         match self {
             AuthTier::Anonymous => 1,
             AuthTier::Authenticated => 2,
-            AuthTier::Admin => 10,
+            AuthTier::ApiKey => 5,
+            AuthTier::Admin => MAX_MULTIPLIER,
         }
     }
+
+    /// Cells consumed per request: anonymous 10, authenticated 5, API key 2,
+    /// admin 1.
+    fn cost(self) -> NonZeroU32 {
+        NonZeroU32::new(MAX_MULTIPLIER / self.multiplier()).expect("multiplier divides max")
+    }
+
+    fn from_user(user: Option<&User>) -> Self {
+        match user {
+            Some(user) if user.is_admin => AuthTier::Admin,
+            Some(_) => AuthTier::Authenticated,
+            None => AuthTier::Anonymous,
+        }
+    }
+}
+
+// -- Limit key --
+
+/// Who a request's buckets belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum LimitKey {
+    Ip(IpAddr),
+    /// An API key, by `api_keys.id`.
+    ApiKey(i32),
+}
+
+impl fmt::Display for LimitKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitKey::Ip(ip) => write!(f, "{ip}"),
+            LimitKey::ApiKey(id) => write!(f, "api-key:{id}"),
+        }
+    }
+}
+
+// -- Quotas --
+
+/// Anonymous-tier request budgets for every limiter, one count per window.
+///
+/// Burst windows are 5s, sustained 1min and long-term 30min.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitQuotas {
+    pub global_burst: u32,
+    pub global_sustained: u32,
+    pub api_sustained: u32,
+    pub api_long: u32,
+    pub ssr_sustained: u32,
+    pub ssr_long: u32,
+    pub admin_sustained: u32,
+    pub admin_long: u32,
+    pub search_burst: u32,
+    pub search_sustained: u32,
+    pub search_long: u32,
+    pub suggest_burst: u32,
+    pub suggest_sustained: u32,
+    pub timeline_burst: u32,
+    pub timeline_sustained: u32,
+    pub timeline_long: u32,
+    pub feedback_sustained: u32,
+    pub feedback_long: u32,
+    pub export_sustained: u32,
+    pub export_long: u32,
+}
+
+impl Default for RateLimitQuotas {
+    fn default() -> Self {
+        RateLimitProfile::Production.quotas()
+    }
+}
+
+/// A named quota table, picked per deployment with `RATE_LIMIT_PROFILE`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RateLimitProfile {
+    /// Local development -- loose enough that hot reloads never trip a limit.
+    #[serde(alias = "development")]
+    Dev,
+    /// Pre-release environment that load tests run against.
+    Staging,
+    #[default]
+    #[serde(alias = "prod")]
+    Production,
+    /// Registration days, when much of campus shares a few NAT addresses.
+    /// Read-heavy budgets triple; admin, feedback and export stay put.
+    CampusEvent,
+}
+
+impl RateLimitProfile {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RateLimitProfile::Dev => "dev",
+            RateLimitProfile::Staging => "staging",
+            RateLimitProfile::Production => "production",
+            RateLimitProfile::CampusEvent => "campus-event",
+        }
+    }
+
+    /// The profile's quota table, before any per-quota overrides.
+    pub fn quotas(self) -> RateLimitQuotas {
+        match self {
+            RateLimitProfile::Dev => RateLimitProfile::Production.quotas().scaled(20),
+            RateLimitProfile::Staging => RateLimitProfile::Production.quotas().scaled(10),
+            RateLimitProfile::Production => RateLimitQuotas {
+                global_burst: 15,
+                global_sustained: 120,
+                api_sustained: 60,
+                api_long: 600,
+                ssr_sustained: 20,
+                ssr_long: 200,
+                admin_sustained: 30,
+                admin_long: 300,
+                search_burst: 3,
+                search_sustained: 20,
+                search_long: 150,
+                suggest_burst: 10,
+                suggest_sustained: 60,
+                timeline_burst: 2,
+                timeline_sustained: 10,
+                timeline_long: 60,
+                feedback_sustained: 3,
+                feedback_long: 10,
+                export_sustained: 2,
+                export_long: 6,
+            },
+            RateLimitProfile::CampusEvent => RateLimitQuotas {
+                global_burst: 45,
+                global_sustained: 360,
+                api_sustained: 180,
+                api_long: 1800,
+                ssr_sustained: 60,
+                ssr_long: 600,
+                admin_sustained: 30,
+                admin_long: 300,
+                search_burst: 9,
+                search_sustained: 60,
+                search_long: 450,
+                suggest_burst: 30,
+                suggest_sustained: 180,
+                timeline_burst: 6,
+                timeline_sustained: 30,
+                timeline_long: 180,
+                feedback_sustained: 3,
+                feedback_long: 10,
+                export_sustained: 2,
+                export_long: 6,
+            },
+        }
+    }
+}
+
+impl fmt::Display for RateLimitProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Per-quota overrides applied on top of the selected profile, each read from
+/// a `RATE_LIMIT_*` environment variable (e.g. `RATE_LIMIT_SEARCH_BURST=5`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RateLimitOverrides {
+    #[serde(rename = "rate_limit_global_burst")]
+    pub global_burst: Option<u32>,
+    #[serde(rename = "rate_limit_global_sustained")]
+    pub global_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_api_sustained")]
+    pub api_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_api_long")]
+    pub api_long: Option<u32>,
+    #[serde(rename = "rate_limit_ssr_sustained")]
+    pub ssr_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_ssr_long")]
+    pub ssr_long: Option<u32>,
+    #[serde(rename = "rate_limit_admin_sustained")]
+    pub admin_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_admin_long")]
+    pub admin_long: Option<u32>,
+    #[serde(rename = "rate_limit_search_burst")]
+    pub search_burst: Option<u32>,
+    #[serde(rename = "rate_limit_search_sustained")]
+    pub search_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_search_long")]
+    pub search_long: Option<u32>,
+    #[serde(rename = "rate_limit_suggest_burst")]
+    pub suggest_burst: Option<u32>,
+    #[serde(rename = "rate_limit_suggest_sustained")]
+    pub suggest_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_timeline_burst")]
+    pub timeline_burst: Option<u32>,
+    #[serde(rename = "rate_limit_timeline_sustained")]
+    pub timeline_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_timeline_long")]
+    pub timeline_long: Option<u32>,
+    #[serde(rename = "rate_limit_feedback_sustained")]
+    pub feedback_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_feedback_long")]
+    pub feedback_long: Option<u32>,
+    #[serde(rename = "rate_limit_export_sustained")]
+    pub export_sustained: Option<u32>,
+    #[serde(rename = "rate_limit_export_long")]
+    pub export_long: Option<u32>,
+}
+
+impl RateLimitOverrides {
+    /// `base` with every set override replacing its quota.
+    pub fn apply(&self, base: RateLimitQuotas) -> RateLimitQuotas {
+        RateLimitQuotas {
+            global_burst: self.global_burst.unwrap_or(base.global_burst),
+            global_sustained: self.global_sustained.unwrap_or(base.global_sustained),
+            api_sustained: self.api_sustained.unwrap_or(base.api_sustained),
+            api_long: self.api_long.unwrap_or(base.api_long),
+            ssr_sustained: self.ssr_sustained.unwrap_or(base.ssr_sustained),
+            ssr_long: self.ssr_long.unwrap_or(base.ssr_long),
+            admin_sustained: self.admin_sustained.unwrap_or(base.admin_sustained),
+            admin_long: self.admin_long.unwrap_or(base.admin_long),
+            search_burst: self.search_burst.unwrap_or(base.search_burst),
+            search_sustained: self.search_sustained.unwrap_or(base.search_sustained),
+            search_long: self.search_long.unwrap_or(base.search_long),
+            suggest_burst: self.suggest_burst.unwrap_or(base.suggest_burst),
+            suggest_sustained: self.suggest_sustained.unwrap_or(base.suggest_sustained),
+            timeline_burst: self.timeline_burst.unwrap_or(base.timeline_burst),
+            timeline_sustained: self.timeline_sustained.unwrap_or(base.timeline_sustained),
+            timeline_long: self.timeline_long.unwrap_or(base.timeline_long),
+            feedback_sustained: self.feedback_sustained.unwrap_or(base.feedback_sustained),
+            feedback_long: self.feedback_long.unwrap_or(base.feedback_long),
+            export_sustained: self.export_sustained.unwrap_or(base.export_sustained),
+            export_long: self.export_long.unwrap_or(base.export_long),
+        }
+    }
+}
+
+/// Largest accepted quota; keeps `count * MAX_MULTIPLIER` cells well inside `u32`.
+const MAX_QUOTA: u32 = 1_000_000;
+
+impl RateLimitQuotas {
+    /// Every quota multiplied by `factor`.
+    fn scaled(&self, factor: u32) -> Self {
+        Self {
+            global_burst: self.global_burst * factor,
+            global_sustained: self.global_sustained * factor,
+            api_sustained: self.api_sustained * factor,
+            api_long: self.api_long * factor,
+            ssr_sustained: self.ssr_sustained * factor,
+            ssr_long: self.ssr_long * factor,
+            admin_sustained: self.admin_sustained * factor,
+            admin_long: self.admin_long * factor,
+            search_burst: self.search_burst * factor,
+            search_sustained: self.search_sustained * factor,
+            search_long: self.search_long * factor,
+            suggest_burst: self.suggest_burst * factor,
+            suggest_sustained: self.suggest_sustained * factor,
+            timeline_burst: self.timeline_burst * factor,
+            timeline_sustained: self.timeline_sustained * factor,
+            timeline_long: self.timeline_long * factor,
+            feedback_sustained: self.feedback_sustained * factor,
+            feedback_long: self.feedback_long * factor,
+            export_sustained: self.export_sustained * factor,
+            export_long: self.export_long * factor,
+        }
+    }
+
+    /// `(name, count)` for every quota, in layer order.
+    fn entries(&self) -> [(&'static str, u32); 20] {
+        [
+            ("global_burst", self.global_burst),
+            ("global_sustained", self.global_sustained),
+            ("api_sustained", self.api_sustained),
+            ("api_long", self.api_long),
+            ("ssr_sustained", self.ssr_sustained),
+            ("ssr_long", self.ssr_long),
+            ("admin_sustained", self.admin_sustained),
+            ("admin_long", self.admin_long),
+            ("search_burst", self.search_burst),
+            ("search_sustained", self.search_sustained),
+            ("search_long", self.search_long),
+            ("suggest_burst", self.suggest_burst),
+            ("suggest_sustained", self.suggest_sustained),
+            ("timeline_burst", self.timeline_burst),
+            ("timeline_sustained", self.timeline_sustained),
+            ("timeline_long", self.timeline_long),
+            ("feedback_sustained", self.feedback_sustained),
+            ("feedback_long", self.feedback_long),
+            ("export_sustained", self.export_sustained),
+            ("export_long", self.export_long),
+        ]
+    }
+
+    /// Reject zero or oversized quotas, which governor can't represent.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, count) in self.entries() {
+            if count == 0 || count > MAX_QUOTA {
+                return Err(format!(
+                    "RATE_LIMIT_{} must be between 1 and {MAX_QUOTA}, got {count}",
+                    name.to_ascii_uppercase()
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 // -- Shared rate limit state --
 
 /// Holds all keyed rate limiters for the multi-layer system.
 ///
-/// Each limiter is keyed by `IpAddr`. The auth multiplier is applied by
-/// allowing `multiplier` cells per check rather than maintaining separate
-/// buckets per auth tier.
+/// Each limiter is keyed by [`LimitKey`]. Quotas hold `MAX_MULTIPLIER` cells per
+/// nominal request, and each check consumes [`AuthTier::cost`] cells, so higher
+/// tiers stretch the same bucket further instead of getting separate buckets.
 pub struct RateLimitState {
     // Layer 1: global per-IP
-    global_burst: DefaultKeyedRateLimiter<IpAddr>, // 5s window
-    global_sustained: DefaultKeyedRateLimiter<IpAddr>, // 1min window
+    global_burst: DefaultKeyedRateLimiter<LimitKey>, // 5s window
+    global_sustained: DefaultKeyedRateLimiter<LimitKey>, // 1min window
 
     // Layer 2: route-group per-IP
-    api_sustained: DefaultKeyedRateLimiter<IpAddr>,
-    api_long: DefaultKeyedRateLimiter<IpAddr>,
-    ssr_sustained: DefaultKeyedRateLimiter<IpAddr>,
-    ssr_long: DefaultKeyedRateLimiter<IpAddr>,
-    admin_sustained: DefaultKeyedRateLimiter<IpAddr>,
-    admin_long: DefaultKeyedRateLimiter<IpAddr>,
+    api_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    api_long: DefaultKeyedRateLimiter<LimitKey>,
+    ssr_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    ssr_long: DefaultKeyedRateLimiter<LimitKey>,
+    admin_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    admin_long: DefaultKeyedRateLimiter<LimitKey>,
 
     // Layer 3: endpoint-specific per-IP
-    search_burst: DefaultKeyedRateLimiter<IpAddr>,
-    search_sustained: DefaultKeyedRateLimiter<IpAddr>,
-    search_long: DefaultKeyedRateLimiter<IpAddr>,
-    suggest_burst: DefaultKeyedRateLimiter<IpAddr>,
-    suggest_sustained: DefaultKeyedRateLimiter<IpAddr>,
-    timeline_burst: DefaultKeyedRateLimiter<IpAddr>,
-    timeline_sustained: DefaultKeyedRateLimiter<IpAddr>,
-    timeline_long: DefaultKeyedRateLimiter<IpAddr>,
+    search_burst: DefaultKeyedRateLimiter<LimitKey>,
+    search_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    search_long: DefaultKeyedRateLimiter<LimitKey>,
+    suggest_burst: DefaultKeyedRateLimiter<LimitKey>,
+    suggest_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    /// Suggest requests the in-memory index can't answer, which run the
+    /// trigram queries. Half the suggest quotas.
+    suggest_fallback_burst: DefaultKeyedRateLimiter<LimitKey>,
+    suggest_fallback_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    timeline_burst: DefaultKeyedRateLimiter<LimitKey>,
+    timeline_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    timeline_long: DefaultKeyedRateLimiter<LimitKey>,
+    feedback_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    feedback_long: DefaultKeyedRateLimiter<LimitKey>,
+    export_sustained: DefaultKeyedRateLimiter<LimitKey>,
+    export_long: DefaultKeyedRateLimiter<LimitKey>,
 
     /// Secret token for SSR -> API internal bypass.
     internal_token: String,
 }
 
-/// Quota helper: `count` requests per `period` with burst = count.
+/// Quota helper: `count` anonymous requests per `period` with burst = count.
 fn quota(count: u32, period: Duration) -> Quota {
-    Quota::with_period(period / count)
+    let cells = count * MAX_MULTIPLIER;
+    Quota::with_period(period / cells)
         .expect("non-zero period")
-        .allow_burst(NonZeroU32::new(count).expect("non-zero count"))
+        .allow_burst(NonZeroU32::new(cells).expect("non-zero count"))
 }
 
 impl RateLimitState {
@@ -178,28 +534,40 @@ impl RateLimitState {
         &self.internal_token
     }
 
-    pub fn new(internal_token: String) -> Self {
+    pub fn new(internal_token: String, quotas: &RateLimitQuotas) -> Self {
+        const BURST: Duration = Duration::from_secs(5);
+        const SUSTAINED: Duration = Duration::from_secs(60);
+        const LONG: Duration = Duration::from_secs(30 * 60);
+        let q = quotas;
+
         // Layer 1: global per-IP
-        let global_burst = RateLimiter::keyed(quota(15, Duration::from_secs(5)));
-        let global_sustained = RateLimiter::keyed(quota(120, Duration::from_secs(60)));
+        let global_burst = RateLimiter::keyed(quota(q.global_burst, BURST));
+        let global_sustained = RateLimiter::keyed(quota(q.global_sustained, SUSTAINED));
 
         // Layer 2: route-group
-        let api_sustained = RateLimiter::keyed(quota(60, Duration::from_secs(60)));
-        let api_long = RateLimiter::keyed(quota(600, Duration::from_secs(30 * 60)));
-        let ssr_sustained = RateLimiter::keyed(quota(20, Duration::from_secs(60)));
-        let ssr_long = RateLimiter::keyed(quota(200, Duration::from_secs(30 * 60)));
-        let admin_sustained = RateLimiter::keyed(quota(30, Duration::from_secs(60)));
-        let admin_long = RateLimiter::keyed(quota(300, Duration::from_secs(30 * 60)));
+        let api_sustained = RateLimiter::keyed(quota(q.api_sustained, SUSTAINED));
+        let api_long = RateLimiter::keyed(quota(q.api_long, LONG));
+        let ssr_sustained = RateLimiter::keyed(quota(q.ssr_sustained, SUSTAINED));
+        let ssr_long = RateLimiter::keyed(quota(q.ssr_long, LONG));
+        let admin_sustained = RateLimiter::keyed(quota(q.admin_sustained, SUSTAINED));
+        let admin_long = RateLimiter::keyed(quota(q.admin_long, LONG));
 
         // Layer 3: endpoint-specific
-        let search_burst = RateLimiter::keyed(quota(3, Duration::from_secs(5)));
-        let search_sustained = RateLimiter::keyed(quota(20, Duration::from_secs(60)));
-        let search_long = RateLimiter::keyed(quota(150, Duration::from_secs(30 * 60)));
-        let suggest_burst = RateLimiter::keyed(quota(5, Duration::from_secs(5)));
-        let suggest_sustained = RateLimiter::keyed(quota(30, Duration::from_secs(60)));
-        let timeline_burst = RateLimiter::keyed(quota(2, Duration::from_secs(5)));
-        let timeline_sustained = RateLimiter::keyed(quota(10, Duration::from_secs(60)));
-        let timeline_long = RateLimiter::keyed(quota(60, Duration::from_secs(30 * 60)));
+        let search_burst = RateLimiter::keyed(quota(q.search_burst, BURST));
+        let search_sustained = RateLimiter::keyed(quota(q.search_sustained, SUSTAINED));
+        let search_long = RateLimiter::keyed(quota(q.search_long, LONG));
+        let suggest_burst = RateLimiter::keyed(quota(q.suggest_burst, BURST));
+        let suggest_sustained = RateLimiter::keyed(quota(q.suggest_sustained, SUSTAINED));
+        let suggest_fallback_burst = RateLimiter::keyed(quota((q.suggest_burst / 2).max(1), BURST));
+        let suggest_fallback_sustained =
+            RateLimiter::keyed(quota((q.suggest_sustained / 2).max(1), SUSTAINED));
+        let timeline_burst = RateLimiter::keyed(quota(q.timeline_burst, BURST));
+        let timeline_sustained = RateLimiter::keyed(quota(q.timeline_sustained, SUSTAINED));
+        let timeline_long = RateLimiter::keyed(quota(q.timeline_long, LONG));
+        let feedback_sustained = RateLimiter::keyed(quota(q.feedback_sustained, SUSTAINED));
+        let feedback_long = RateLimiter::keyed(quota(q.feedback_long, LONG));
+        let export_sustained = RateLimiter::keyed(quota(q.export_sustained, SUSTAINED));
+        let export_long = RateLimiter::keyed(quota(q.export_long, LONG));
 
         Self {
             global_burst,
@@ -215,16 +583,22 @@ impl RateLimitState {
             search_long,
             suggest_burst,
             suggest_sustained,
+            suggest_fallback_burst,
+            suggest_fallback_sustained,
             timeline_burst,
             timeline_sustained,
             timeline_long,
+            feedback_sustained,
+            feedback_long,
+            export_sustained,
+            export_long,
             internal_token,
         }
     }
 
     /// Check all applicable rate limits for the request. Returns `Ok(())` if
     /// allowed, or `Err(retry_after_secs)` with the longest wait time.
-    fn check(&self, ip: IpAddr, path: &str, _tier: AuthTier) -> Result<(), u64> {
+    fn check(&self, key: LimitKey, path: &str, tier: AuthTier) -> Result<(), u64> {
         let group = classify_route(path);
 
         // Static assets are exempt from all rate limiting.
@@ -235,11 +609,14 @@ impl RateLimitState {
         // Layer 1: global
         let mut max_wait: Option<Duration> = None;
 
-        let check_limiter = |limiter: &DefaultKeyedRateLimiter<IpAddr>,
-                             ip: &IpAddr,
+        let cost = tier.cost();
+        let check_limiter = |limiter: &DefaultKeyedRateLimiter<LimitKey>,
+                             key: &LimitKey,
                              max_wait: &mut Option<Duration>|
          -> bool {
-            match limiter.check_key(ip) {
+            // Every quota's burst is at least MAX_MULTIPLIER cells, so the
+            // capacity error can't happen.
+            match limiter.check_key_n(key, cost).unwrap_or(Ok(())) {
                 Ok(()) => true,
                 Err(not_until) => {
                     let wait =
@@ -255,37 +632,37 @@ impl RateLimitState {
 
         let mut rejected = false;
 
-        // Global per-IP
-        if !check_limiter(&self.global_burst, &ip, &mut max_wait) {
+        // Global
+        if !check_limiter(&self.global_burst, &key, &mut max_wait) {
             rejected = true;
         }
-        if !check_limiter(&self.global_sustained, &ip, &mut max_wait) {
+        if !check_limiter(&self.global_sustained, &key, &mut max_wait) {
             rejected = true;
         }
 
         // Route-group (Static already short-circuited above)
         match group {
             RouteGroup::Api => {
-                if !check_limiter(&self.api_sustained, &ip, &mut max_wait) {
+                if !check_limiter(&self.api_sustained, &key, &mut max_wait) {
                     rejected = true;
                 }
-                if !check_limiter(&self.api_long, &ip, &mut max_wait) {
+                if !check_limiter(&self.api_long, &key, &mut max_wait) {
                     rejected = true;
                 }
             }
             RouteGroup::Ssr => {
-                if !check_limiter(&self.ssr_sustained, &ip, &mut max_wait) {
+                if !check_limiter(&self.ssr_sustained, &key, &mut max_wait) {
                     rejected = true;
                 }
-                if !check_limiter(&self.ssr_long, &ip, &mut max_wait) {
+                if !check_limiter(&self.ssr_long, &key, &mut max_wait) {
                     rejected = true;
                 }
             }
             RouteGroup::Admin => {
-                if !check_limiter(&self.admin_sustained, &ip, &mut max_wait) {
+                if !check_limiter(&self.admin_sustained, &key, &mut max_wait) {
                     rejected = true;
                 }
-                if !check_limiter(&self.admin_long, &ip, &mut max_wait) {
+                if !check_limiter(&self.admin_long, &key, &mut max_wait) {
                     rejected = true;
                 }
             }
@@ -296,32 +673,48 @@ impl RateLimitState {
         if let Some(endpoint) = classify_endpoint(path) {
             match endpoint {
                 TrackedEndpoint::CourseSearch => {
-                    if !check_limiter(&self.search_burst, &ip, &mut max_wait) {
+                    if !check_limiter(&self.search_burst, &key, &mut max_wait) {
                         rejected = true;
                     }
-                    if !check_limiter(&self.search_sustained, &ip, &mut max_wait) {
+                    if !check_limiter(&self.search_sustained, &key, &mut max_wait) {
                         rejected = true;
                     }
-                    if !check_limiter(&self.search_long, &ip, &mut max_wait) {
+                    if !check_limiter(&self.search_long, &key, &mut max_wait) {
                         rejected = true;
                     }
                 }
                 TrackedEndpoint::Suggest => {
-                    if !check_limiter(&self.suggest_burst, &ip, &mut max_wait) {
+                    if !check_limiter(&self.suggest_burst, &key, &mut max_wait) {
                         rejected = true;
                     }
-                    if !check_limiter(&self.suggest_sustained, &ip, &mut max_wait) {
+                    if !check_limiter(&self.suggest_sustained, &key, &mut max_wait) {
                         rejected = true;
                     }
                 }
                 TrackedEndpoint::Timeline => {
-                    if !check_limiter(&self.timeline_burst, &ip, &mut max_wait) {
+                    if !check_limiter(&self.timeline_burst, &key, &mut max_wait) {
+                        rejected = true;
+                    }
+                    if !check_limiter(&self.timeline_sustained, &key, &mut max_wait) {
+                        rejected = true;
+                    }
+                    if !check_limiter(&self.timeline_long, &key, &mut max_wait) {
+                        rejected = true;
+                    }
+                }
+                TrackedEndpoint::Feedback => {
+                    if !check_limiter(&self.feedback_sustained, &key, &mut max_wait) {
                         rejected = true;
                     }
-                    if !check_limiter(&self.timeline_sustained, &ip, &mut max_wait) {
+                    if !check_limiter(&self.feedback_long, &key, &mut max_wait) {
                         rejected = true;
                     }
-                    if !check_limiter(&self.timeline_long, &ip, &mut max_wait) {
+                }
+                TrackedEndpoint::Export => {
+                    if !check_limiter(&self.export_sustained, &key, &mut max_wait) {
+                        rejected = true;
+                    }
+                    if !check_limiter(&self.export_long, &key, &mut max_wait) {
                         rejected = true;
                     }
                 }
@@ -336,6 +729,25 @@ impl RateLimitState {
         }
     }
 
+    /// Charge a suggest request that falls through to the database.
+    ///
+    /// Requests without a [`RateLimitSubject`] (internal, trusted or unknown
+    /// IP) weren't limited by the middleware either, so they're allowed.
+    pub fn allow_suggest_fallback(&self, subject: Option<RateLimitSubject>) -> bool {
+        let Some(RateLimitSubject { key, tier }) = subject else {
+            return true;
+        };
+        let cost = tier.cost();
+        // Short-circuits, so a burst rejection doesn't also drain the
+        // sustained bucket.
+        [
+            &self.suggest_fallback_burst,
+            &self.suggest_fallback_sustained,
+        ]
+        .iter()
+        .all(|limiter| limiter.check_key_n(&key, cost).unwrap_or(Ok(())).is_ok())
+    }
+
     /// Returns true if the request carries a valid internal bypass token.
     fn is_internal(&self, headers: &http::HeaderMap) -> bool {
         header_str(headers, "x-internal-token").is_some_and(|v| v == self.internal_token)
@@ -344,16 +756,87 @@ impl RateLimitState {
 
 pub type SharedRateLimitState = Arc<RateLimitState>;
 
+/// Who a request was rate-limited as, inserted as a request extension once
+/// the middleware lets it through, so handlers can apply finer budgets.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitSubject {
+    key: LimitKey,
+    tier: AuthTier,
+}
+
+/// Pass the request on, tagged with the subject it was limited as.
+fn tag_subject(req: &mut Request, subject: Option<(LimitKey, AuthTier)>) {
+    if let Some((key, tier)) = subject {
+        req.extensions_mut().insert(RateLimitSubject { key, tier });
+    }
+}
+
+// -- Unknown session tokens --
+
+/// How long a session token that failed to resolve is treated as anonymous
+/// without another lookup.
+const UNKNOWN_SESSION_TTL: Duration = Duration::from_secs(60);
+
+/// Most tokens remembered at once. At the cap, expired tokens are swept and
+/// then the oldest is evicted.
+const UNKNOWN_SESSION_CAP: usize = 4096;
+
+/// Session tokens that recently failed to resolve (expired, logged out, or
+/// forged).
+#[derive(Clone, Default)]
+struct UnknownSessions(Arc<DashMap<String, Instant>>);
+
+impl UnknownSessions {
+    fn contains(&self, token: &str) -> bool {
+        self.0
+            .get(token)
+            .is_some_and(|seen| seen.elapsed() < UNKNOWN_SESSION_TTL)
+    }
+
+    fn insert(&self, token: String) {
+        if self.0.len() >= UNKNOWN_SESSION_CAP {
+            self.0
+                .retain(|_, seen| seen.elapsed() < UNKNOWN_SESSION_TTL);
+        }
+        if self.0.len() >= UNKNOWN_SESSION_CAP {
+            let oldest = self
+                .0
+                .iter()
+                .min_by_key(|entry| *entry.value())
+                .map(|entry| entry.key().clone());
+            if let Some(oldest) = oldest {
+                self.0.remove(&oldest);
+            }
+        }
+        self.0.insert(token, Instant::now());
+    }
+}
+
 // -- Tower Layer + Service --
 
 #[derive(Clone)]
 pub struct RateLimitLayer {
     state: SharedRateLimitState,
+    sessions: SessionCache,
+    unknown_sessions: UnknownSessions,
+    api_keys: ApiKeyCache,
+    metrics: Arc<Metrics>,
 }
 
 impl RateLimitLayer {
-    pub fn new(state: SharedRateLimitState) -> Self {
-        Self { state }
+    pub fn new(
+        state: SharedRateLimitState,
+        sessions: SessionCache,
+        api_keys: ApiKeyCache,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        Self {
+            state,
+            sessions,
+            unknown_sessions: UnknownSessions::default(),
+            api_keys,
+            metrics,
+        }
     }
 }
 
@@ -364,6 +847,10 @@ impl<S> Layer<S> for RateLimitLayer {
         RateLimitService {
             inner,
             state: self.state.clone(),
+            sessions: self.sessions.clone(),
+            unknown_sessions: self.unknown_sessions.clone(),
+            api_keys: self.api_keys.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -372,6 +859,10 @@ impl<S> Layer<S> for RateLimitLayer {
 pub struct RateLimitService<S> {
     inner: S,
     state: SharedRateLimitState,
+    sessions: SessionCache,
+    unknown_sessions: UnknownSessions,
+    api_keys: ApiKeyCache,
+    metrics: Arc<Metrics>,
 }
 
 impl<S, ResBody> Service<Request> for RateLimitService<S>
@@ -392,75 +883,286 @@ where
         self.inner.poll_ready(cx)
     }
 
-    fn call(&mut self, req: Request) -> Self::Future {
-        // Internal SSR -> API calls bypass rate limiting entirely.
-        if self.state.is_internal(req.headers()) {
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        // Internal SSR -> API calls and trusted ranges bypass rate limiting entirely.
+        if self.state.is_internal(req.headers())
+            || req.extensions().get::<TrustedClient>().is_some()
+        {
             let future = self.inner.call(req);
             return Box::pin(future);
         }
 
-        // Extract client IP from headers (same logic as ClientIp extractor).
-        let client_ip = extract_ip_from_headers(req.headers());
+        // Resolved by the outer `ClientIpLayer` under the proxy trust policy.
+        let client_ip = req.extensions().get::<ClientIp>().map(|ClientIp(ip)| *ip);
 
         let path = req.uri().path().to_string();
 
-        // TODO: auth tier detection from session cookie -- for now, anonymous.
-        let tier = AuthTier::Anonymous;
+        let token = extract_session_token(req.headers());
+        let user = token
+            .as_deref()
+            .and_then(|token| self.sessions.peek_user(token));
+        let api_key = header_str(req.headers(), API_KEY_HEADER).map(str::to_owned);
+        // Static assets are never limited, so their tier doesn't matter.
+        let session_lookup = token.filter(|token| {
+            user.is_none()
+                && is_well_formed_token(token)
+                && !self.unknown_sessions.contains(token)
+                && classify_route(&path) != RouteGroup::Static
+        });
 
-        match client_ip {
-            Some(ip) => match self.state.check(ip, &path, tier) {
-                Ok(()) => {
-                    let future = self.inner.call(req);
-                    Box::pin(future)
-                }
-                Err(retry_after) => {
-                    warn!(
-                        client_ip = %ip,
-                        path = %path,
-                        retry_after_secs = retry_after,
-                        "Rate limit exceeded"
-                    );
-                    let resp = rate_limit_response(retry_after).map(Into::into);
-                    Box::pin(async move { Ok(resp) })
+        // API keys and uncached sessions may need a database lookup, so
+        // they're resolved in the future.
+        if api_key.is_some() || session_lookup.is_some() {
+            let state = self.state.clone();
+            let sessions = self.sessions.clone();
+            let unknown_sessions = self.unknown_sessions.clone();
+            let api_keys = self.api_keys.clone();
+            let metrics = self.metrics.clone();
+            // Take the service `poll_ready` readied, leaving a clone in its place.
+            let clone = self.inner.clone();
+            let mut inner = std::mem::replace(&mut self.inner, clone);
+            return Box::pin(async move {
+                let key = match &api_key {
+                    Some(raw) => {
+                        // An uncached key costs a query, so it's charged to
+                        // the IP first, like an unknown session below.
+                        if api_keys.needs_lookup(raw) {
+                            let anonymous =
+                                client_ip.map(|ip| (LimitKey::Ip(ip), AuthTier::Anonymous));
+                            if let Some(resp) = enforce(&state, &metrics, anonymous, &path) {
+                                return Ok(resp.map(Into::into));
+                            }
+                        }
+                        api_keys.resolve(raw).await
+                    }
+                    None => None,
+                };
+                let subject = match (key, session_lookup) {
+                    (Some(key), _) => Some((LimitKey::ApiKey(key.id), AuthTier::ApiKey)),
+                    (None, Some(token)) => {
+                        // Charged as anonymous so an unknown cookie can't buy
+                        // a lookup once the IP is over its limit.
+                        let anonymous = client_ip.map(|ip| (LimitKey::Ip(ip), AuthTier::Anonymous));
+                        if let Some(resp) = enforce(&state, &metrics, anonymous, &path) {
+                            return Ok(resp.map(Into::into));
+                        }
+                        if sessions.get_user(&token).await.is_none() {
+                            unknown_sessions.insert(token);
+                        }
+                        None
+                    }
+                    (None, None) => {
+                        client_ip.map(|ip| (LimitKey::Ip(ip), AuthTier::from_user(user.as_ref())))
+                    }
+                };
+                match enforce(&state, &metrics, subject, &path) {
+                    None => {
+                        tag_subject(&mut req, subject);
+                        inner.call(req).await
+                    }
+                    Some(resp) => Ok(resp.map(Into::into)),
                 }
-            },
+            });
+        }
+
+        let by_ip = client_ip.map(|ip| (LimitKey::Ip(ip), AuthTier::from_user(user.as_ref())));
+        match enforce(&self.state, &self.metrics, by_ip, &path) {
             None => {
-                // Cannot determine IP -- allow but log.
+                tag_subject(&mut req, by_ip);
                 let future = self.inner.call(req);
                 Box::pin(future)
             }
+            Some(resp) => {
+                let resp = resp.map(Into::into);
+                Box::pin(async move { Ok(resp) })
+            }
         }
     }
 }
 
-/// Extract client IP from request headers without going through the full
-/// Axum extractor system (we're in a Tower middleware, not an Axum handler).
-fn extract_ip_from_headers(headers: &http::HeaderMap) -> Option<IpAddr> {
-    // CF-Connecting-IP (Cloudflare)
-    if let Some(ip) = header_str(headers, "cf-connecting-ip").and_then(|s| s.parse().ok()) {
-        return Some(ip);
-    }
-    // Rightmost X-Forwarded-For (Railway)
-    if let Some(xff) = header_str(headers, "x-forwarded-for")
-        && let Some(ip) = xff
-            .rsplit(',')
-            .next()
-            .map(str::trim)
-            .and_then(|s| s.parse().ok())
-    {
-        return Some(ip);
-    }
-    None
+/// Check a request against the limits for `subject`, returning the 429 to
+/// send if any is exceeded.
+///
+/// A `None` subject means the client IP couldn't be determined; those
+/// requests are allowed through.
+fn enforce(
+    state: &RateLimitState,
+    metrics: &Metrics,
+    subject: Option<(LimitKey, AuthTier)>,
+    path: &str,
+) -> Option<Response<Body>> {
+    let (key, tier) = subject?;
+    let retry_after = state.check(key, path, tier).err()?;
+    metrics.record_rate_limited(classify_route(path).as_str());
+    warn!(
+        limit_key = %key,
+        path,
+        ?tier,
+        retry_after_secs = retry_after,
+        "Rate limit exceeded"
+    );
+    Some(rate_limit_response(retry_after))
 }
 
 fn rate_limit_response(retry_after: u64) -> Response<Body> {
     use crate::web::error::ApiError;
     use axum::response::IntoResponse;
 
-    let mut response = ApiError::rate_limited(retry_after).into_response();
-    response.headers_mut().insert(
-        "retry-after",
-        HeaderValue::from_str(&retry_after.to_string()).unwrap(),
-    );
-    response
+    ApiError::rate_limited(retry_after).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(state: &RateLimitState, tier: AuthTier, attempts: usize) -> usize {
+        let key = LimitKey::Ip("203.0.113.9".parse().unwrap());
+        (0..attempts)
+            .filter(|_| state.check(key, "/api/timeline", tier).is_ok())
+            .count()
+    }
+
+    #[test]
+    fn embeds_are_classified_as_ssr() {
+        assert_eq!(
+            classify_route("/embed/instructors/jane-doe"),
+            RouteGroup::Ssr
+        );
+        assert_eq!(classify_route("/embed/instructors/x.svg"), RouteGroup::Ssr);
+        assert_eq!(classify_route("/instructors/x.svg"), RouteGroup::Static);
+    }
+
+    #[test]
+    fn tiers_scale_endpoint_burst() {
+        // Timeline burst is 2 requests per 5s for anonymous callers.
+        let anonymous = allowed(
+            &RateLimitState::new(String::new(), &RateLimitQuotas::default()),
+            AuthTier::Anonymous,
+            30,
+        );
+        let authenticated = allowed(
+            &RateLimitState::new(String::new(), &RateLimitQuotas::default()),
+            AuthTier::Authenticated,
+            30,
+        );
+        let admin = allowed(
+            &RateLimitState::new(String::new(), &RateLimitQuotas::default()),
+            AuthTier::Admin,
+            30,
+        );
+        assert_eq!(anonymous, 2);
+        assert_eq!(authenticated, 4);
+        assert_eq!(admin, 20);
+    }
+
+    #[test]
+    fn exports_are_tracked_as_expensive() {
+        let state = RateLimitState::new(String::new(), &RateLimitQuotas::default());
+        let key = LimitKey::Ip("203.0.113.9".parse().unwrap());
+        let allowed = (0..10)
+            .filter(|_| {
+                state
+                    .check(key, "/api/courses/export", AuthTier::Anonymous)
+                    .is_ok()
+            })
+            .count();
+        assert_eq!(allowed, 2);
+    }
+
+    #[test]
+    fn suggest_fallback_keeps_the_undoubled_budget() {
+        let state = RateLimitState::new(String::new(), &RateLimitQuotas::default());
+        let subject = RateLimitSubject {
+            key: LimitKey::Ip("203.0.113.9".parse().unwrap()),
+            tier: AuthTier::Anonymous,
+        };
+        // Suggest burst is 10 per 5s; database fallbacks get half.
+        let allowed = (0..20)
+            .filter(|_| state.allow_suggest_fallback(Some(subject)))
+            .count();
+        assert_eq!(allowed, 5);
+        assert!(state.allow_suggest_fallback(None));
+    }
+
+    #[test]
+    fn api_keys_get_their_own_buckets() {
+        let state = RateLimitState::new(String::new(), &RateLimitQuotas::default());
+        let ip: IpAddr = "203.0.113.9".parse().unwrap();
+        let check = |key| state.check(key, "/api/timeline", AuthTier::ApiKey).is_ok();
+
+        // Timeline burst is 2 anonymous requests; an API key stretches it to 10.
+        let first = (0..30).filter(|_| check(LimitKey::ApiKey(1))).count();
+        assert_eq!(first, 10);
+        // Another key, or the same IP without a key, is unaffected.
+        assert!(check(LimitKey::ApiKey(2)));
+        assert!(
+            state
+                .check(LimitKey::Ip(ip), "/api/timeline", AuthTier::Anonymous)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn quotas_come_from_config() {
+        let quotas = RateLimitQuotas {
+            timeline_burst: 5,
+            ..RateLimitQuotas::default()
+        };
+        let state = RateLimitState::new(String::new(), &quotas);
+        assert_eq!(allowed(&state, AuthTier::Anonymous, 30), 5);
+    }
+
+    #[test]
+    fn overrides_apply_on_top_of_profile() {
+        let overrides = RateLimitOverrides {
+            search_burst: Some(4),
+            ..RateLimitOverrides::default()
+        };
+        let quotas = overrides.apply(RateLimitProfile::CampusEvent.quotas());
+        assert_eq!(quotas.search_burst, 4);
+        assert_eq!(quotas.search_sustained, 60);
+
+        for profile in [
+            RateLimitProfile::Dev,
+            RateLimitProfile::Staging,
+            RateLimitProfile::Production,
+            RateLimitProfile::CampusEvent,
+        ] {
+            assert!(profile.quotas().validate().is_ok(), "{profile}");
+        }
+    }
+
+    #[test]
+    fn quota_validation_rejects_zero() {
+        assert!(RateLimitQuotas::default().validate().is_ok());
+        let quotas = RateLimitQuotas {
+            search_burst: 0,
+            ..RateLimitQuotas::default()
+        };
+        let err = quotas.validate().unwrap_err();
+        assert!(err.contains("RATE_LIMIT_SEARCH_BURST"), "{err}");
+    }
+
+    #[test]
+    fn unknown_sessions_expire() {
+        let unknown = UnknownSessions::default();
+        assert!(!unknown.contains("abc"));
+        unknown.insert("abc".to_owned());
+        assert!(unknown.contains("abc"));
+
+        unknown
+            .0
+            .insert("old".to_owned(), Instant::now() - UNKNOWN_SESSION_TTL);
+        assert!(!unknown.contains("old"));
+    }
+
+    #[test]
+    fn unknown_sessions_stay_bounded() {
+        let unknown = UnknownSessions::default();
+        for i in 0..UNKNOWN_SESSION_CAP + 10 {
+            unknown.insert(format!("token-{i}"));
+        }
+        assert_eq!(unknown.0.len(), UNKNOWN_SESSION_CAP);
+        assert!(unknown.contains(&format!("token-{}", UNKNOWN_SESSION_CAP + 9)));
+    }
 }
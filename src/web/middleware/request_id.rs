@@ -6,7 +6,7 @@
 //!
 //! Always sets an `X-Request-Id` response header with the resolved ID.
 
-use crate::web::middleware::client_ip::header_str;
+use crate::web::middleware::client_ip::{ClientIp, header_str};
 use axum::extract::Request;
 use axum::http::HeaderValue;
 use axum::response::Response;
@@ -70,14 +70,11 @@ where
             .map(String::from)
             .unwrap_or_default();
 
-        // Client IP for tracing correlation (same priority as ClientIp extractor).
-        let client_ip = header_str(req.headers(), "cf-connecting-ip")
-            .or_else(|| {
-                header_str(req.headers(), "x-forwarded-for")
-                    .and_then(|xff| xff.rsplit(',').next().map(str::trim))
-            })
-            .unwrap_or("-")
-            .to_string();
+        // Client IP for tracing correlation, resolved by the outer `ClientIpLayer`.
+        let client_ip = req
+            .extensions()
+            .get::<ClientIp>()
+            .map_or_else(|| "-".to_string(), |ClientIp(ip)| ip.to_string());
 
         let method = req.method().clone();
         let path = req.uri().path().to_string();
@@ -0,0 +1,425 @@
+//! In-memory caching of hot public GET endpoints, with weak ETags.
+//!
+//! Responses from the routes in [`CACHED_ROUTES`] are buffered and served
+//! from memory for the route's TTL, keyed by path and the route's known query
+//! parameters in sorted order, so junk parameters can't mint new entries.
+//! Each one carries a weak `ETag` hashed from its body (weak because the
+//! compression layer outside re-encodes it), and a matching `If-None-Match`
+//! gets a bodyless 304 -- the conditional-request handling the admin audit
+//! log does by hand with `If-Modified-Since`, generalized. The store is
+//! bounded by entry count and total bytes, evicting least recently used
+//! entries to make room.
+//!
+//! Only plain 200s are stored. Responses that set a cookie, are marked
+//! `private`/`no-store`, already carry their own ETag, or are too large pass
+//! through untouched.
+
+use crate::metrics::Metrics;
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use rapidhash::v3::rapidhash_v3;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+
+/// A public route whose GET responses are cached.
+struct CachedRoute {
+    path: &'static str,
+    /// Match any path under `path` rather than `path` exactly.
+    prefix: bool,
+    ttl: Duration,
+    /// Query parameters the handler reads; all others are left out of the key.
+    params: &'static [&'static str],
+}
+
+const CACHED_ROUTES: &[CachedRoute] = &[
+    // Term list and filter options for the search page.
+    CachedRoute {
+        path: "/api/search-options",
+        prefix: false,
+        ttl: Duration::from_secs(60),
+        params: &["term"],
+    },
+    CachedRoute {
+        path: "/api/reference/",
+        prefix: true,
+        ttl: Duration::from_secs(300),
+        params: &[],
+    },
+    // Instructor directory pages.
+    CachedRoute {
+        path: "/api/instructors",
+        prefix: false,
+        ttl: Duration::from_secs(60),
+        params: &["search", "subject", "sort", "page", "perPage", "cursor"],
+    },
+];
+
+/// Entry cap; past it, the least recently used entries are evicted.
+const MAX_ENTRIES: usize = 2048;
+
+/// Total body bytes held across all entries.
+const MAX_TOTAL_BYTES: usize = 32 * 1024 * 1024;
+
+/// Largest body worth holding in memory.
+const MAX_BODY: usize = 1024 * 1024;
+
+fn route_for(path: &str) -> Option<&'static CachedRoute> {
+    CACHED_ROUTES.iter().find(|route| {
+        if route.prefix {
+            path.starts_with(route.path)
+        } else {
+            path == route.path
+        }
+    })
+}
+
+/// Cache key for a request: the path plus the route's known parameters,
+/// decoded, sorted by name and re-encoded.
+fn cache_key(route: &CachedRoute, path: &str, query: Option<&str>) -> String {
+    let mut params: Vec<(String, String)> =
+        url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+            .filter(|(name, _)| route.params.contains(&name.as_ref()))
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+    if params.is_empty() {
+        return path.to_owned();
+    }
+    // Stable, so repeated parameters keep their relative order.
+    params.sort_by(|a, b| a.0.cmp(&b.0));
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    format!("{path}?{query}")
+}
+
+struct CachedResponse {
+    body: Bytes,
+    etag: HeaderValue,
+    content_type: Option<HeaderValue>,
+    cache_control: Option<HeaderValue>,
+    expires_at: Instant,
+    /// [`ResponseStore`] clock reading at the last hit, for LRU eviction.
+    last_used: AtomicU64,
+}
+
+impl CachedResponse {
+    /// Render for a request, answering 304 when `If-None-Match` matches.
+    fn respond(&self, request_headers: &HeaderMap) -> Response {
+        let mut resp = if if_none_match(request_headers, &self.etag) {
+            StatusCode::NOT_MODIFIED.into_response()
+        } else {
+            let mut resp = Response::new(Body::from(self.body.clone()));
+            if let Some(content_type) = &self.content_type {
+                resp.headers_mut()
+                    .insert(header::CONTENT_TYPE, content_type.clone());
+            }
+            resp
+        };
+        let headers = resp.headers_mut();
+        headers.insert(header::ETAG, self.etag.clone());
+        if let Some(cache_control) = &self.cache_control {
+            headers.insert(header::CACHE_CONTROL, cache_control.clone());
+        }
+        resp
+    }
+}
+
+/// Whether `If-None-Match` lists `etag` (weak comparison, per RFC 9110).
+fn if_none_match(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Some(value) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    let etag = etag.strip_prefix("W/").unwrap_or(etag);
+    value.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// Cached responses bounded by entry count and total body bytes.
+#[derive(Default)]
+struct ResponseStore {
+    entries: DashMap<String, Arc<CachedResponse>>,
+    bytes: AtomicUsize,
+    /// Logical clock stamped on entries as they're used.
+    clock: AtomicU64,
+}
+
+impl ResponseStore {
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// A live entry for `key`, marking it recently used.
+    fn get(&self, key: &str, now: Instant) -> Option<Arc<CachedResponse>> {
+        let entry = self.entries.get(key).map(|e| e.clone())?;
+        if entry.expires_at <= now {
+            self.remove(key);
+            return None;
+        }
+        entry.last_used.store(self.tick(), Ordering::Relaxed);
+        Some(entry)
+    }
+
+    fn remove(&self, key: &str) {
+        if let Some((_, old)) = self.entries.remove(key) {
+            self.bytes.fetch_sub(old.body.len(), Ordering::Relaxed);
+        }
+    }
+
+    /// Store `entry`, first dropping expired entries and then least recently
+    /// used ones until it fits.
+    fn insert(&self, key: String, entry: Arc<CachedResponse>) {
+        let len = entry.body.len();
+        let over = |store: &Self| {
+            store.entries.len() >= MAX_ENTRIES
+                || store.bytes.load(Ordering::Relaxed) + len > MAX_TOTAL_BYTES
+        };
+        if over(self) {
+            let now = Instant::now();
+            let expired: Vec<String> = self
+                .entries
+                .iter()
+                .filter(|e| e.expires_at <= now)
+                .map(|e| e.key().clone())
+                .collect();
+            for key in &expired {
+                self.remove(key);
+            }
+        }
+        while over(self) {
+            let lru = self
+                .entries
+                .iter()
+                .min_by_key(|e| e.last_used.load(Ordering::Relaxed))
+                .map(|e| e.key().clone());
+            match lru {
+                Some(key) => self.remove(&key),
+                None => break,
+            }
+        }
+        entry.last_used.store(self.tick(), Ordering::Relaxed);
+        self.bytes.fetch_add(len, Ordering::Relaxed);
+        if let Some(old) = self.entries.insert(key, entry) {
+            self.bytes.fetch_sub(old.body.len(), Ordering::Relaxed);
+        }
+    }
+}
+
+/// Whether a handler's response may be stored and shared between clients.
+fn is_storable(resp: &Response) -> bool {
+    let headers = resp.headers();
+    resp.status() == StatusCode::OK
+        && !headers.contains_key(header::SET_COOKIE)
+        && !headers.contains_key(header::ETAG)
+        && !headers
+            .get(header::CACHE_CONTROL)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("private") || v.contains("no-store"))
+        && resp
+            .body()
+            .size_hint()
+            .exact()
+            .is_some_and(|len| len as usize <= MAX_BODY)
+}
+
+#[derive(Clone)]
+pub struct ResponseCacheLayer {
+    store: Arc<ResponseStore>,
+    metrics: Arc<Metrics>,
+}
+
+impl ResponseCacheLayer {
+    pub fn new(metrics: Arc<Metrics>) -> Self {
+        Self {
+            store: Arc::default(),
+            metrics,
+        }
+    }
+}
+
+impl<S> Layer<S> for ResponseCacheLayer {
+    type Service = ResponseCacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseCacheService {
+            inner,
+            store: self.store.clone(),
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ResponseCacheService<S> {
+    inner: S,
+    store: Arc<ResponseStore>,
+    metrics: Arc<Metrics>,
+}
+
+impl<S> Service<Request> for ResponseCacheService<S>
+where
+    S: Service<Request, Response = Response> + Send + Clone + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let route = match *req.method() {
+            Method::GET => route_for(req.uri().path()),
+            _ => None,
+        };
+        let Some(route) = route else {
+            return Box::pin(self.inner.call(req));
+        };
+        let ttl = route.ttl;
+
+        let key = cache_key(route, req.uri().path(), req.uri().query());
+        if let Some(entry) = self.store.get(&key, Instant::now()) {
+            self.metrics.record_cache_lookup("response", true);
+            let resp = entry.respond(req.headers());
+            return Box::pin(async move { Ok(resp) });
+        }
+        self.metrics.record_cache_lookup("response", false);
+
+        let store = self.store.clone();
+        let request_headers = req.headers().clone();
+        // Take the service `poll_ready` readied, leaving a clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let resp = inner.call(req).await?;
+            if !is_storable(&resp) {
+                return Ok(resp);
+            }
+
+            let (mut parts, body) = resp.into_parts();
+            let body = match axum::body::to_bytes(body, MAX_BODY).await {
+                Ok(body) => body,
+                Err(e) => {
+                    // Hand the failure to the client as a body error, the same
+                    // way it would have surfaced without the cache.
+                    parts.headers.remove(header::CONTENT_LENGTH);
+                    let body =
+                        Body::from_stream(futures::stream::once(async move { Err::<Bytes, _>(e) }));
+                    return Ok(Response::from_parts(parts, body));
+                }
+            };
+            let Ok(etag) = HeaderValue::from_str(&format!("W/\"r:{:016x}\"", rapidhash_v3(&body)))
+            else {
+                return Ok(Response::from_parts(parts, Body::from(body)));
+            };
+            let entry = Arc::new(CachedResponse {
+                body,
+                etag,
+                content_type: parts.headers.get(header::CONTENT_TYPE).cloned(),
+                cache_control: parts.headers.get(header::CACHE_CONTROL).cloned(),
+                expires_at: Instant::now() + ttl,
+                last_used: AtomicU64::new(0),
+            });
+            store.insert(key, entry.clone());
+
+            Ok(entry.respond(&request_headers))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_match_exactly_or_by_prefix() {
+        assert!(route_for("/api/search-options").is_some());
+        assert!(route_for("/api/reference/subjects").is_some());
+        assert!(route_for("/api/instructors").is_some());
+        assert!(route_for("/api/instructors/jane-doe").is_none());
+        assert!(route_for("/api/courses/search").is_none());
+    }
+
+    #[test]
+    fn cache_key_keeps_only_known_params_in_order() {
+        let route = route_for("/api/instructors").unwrap();
+        let key = |query| cache_key(route, "/api/instructors", query);
+        assert_eq!(key(None), "/api/instructors");
+        assert_eq!(key(Some("junk=1&x")), "/api/instructors");
+        assert_eq!(
+            key(Some("sort=name_asc&search=jane%20doe&junk=1")),
+            "/api/instructors?search=jane+doe&sort=name_asc"
+        );
+        assert_eq!(
+            key(Some("search=jane+doe&sort=name_asc")),
+            key(Some("sort=name_asc&search=jane%20doe"))
+        );
+    }
+
+    fn response(len: usize) -> Arc<CachedResponse> {
+        Arc::new(CachedResponse {
+            body: Bytes::from(vec![b'x'; len]),
+            etag: HeaderValue::from_static("W/\"r:0\""),
+            content_type: None,
+            cache_control: None,
+            expires_at: Instant::now() + Duration::from_secs(60),
+            last_used: AtomicU64::new(0),
+        })
+    }
+
+    #[test]
+    fn store_evicts_least_recently_used_to_fit_bytes() {
+        let store = ResponseStore::default();
+        let third = MAX_TOTAL_BYTES / 3;
+        store.insert("a".into(), response(third));
+        store.insert("b".into(), response(third));
+        store.insert("c".into(), response(third));
+        assert!(store.get("a", Instant::now()).is_some());
+
+        store.insert("d".into(), response(third));
+        assert!(store.entries.contains_key("a"));
+        assert!(!store.entries.contains_key("b"));
+        assert!(store.bytes.load(Ordering::Relaxed) <= MAX_TOTAL_BYTES);
+    }
+
+    #[test]
+    fn if_none_match_accepts_lists_and_weak_tags() {
+        let etag = HeaderValue::from_static("\"r:00000000000000ff\"");
+        let check = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static(value));
+            if_none_match(&headers, &etag)
+        };
+        assert!(check("\"r:00000000000000ff\""));
+        assert!(check("\"other\", W/\"r:00000000000000ff\""));
+        assert!(check("*"));
+        assert!(!check("\"r:0000000000000000\""));
+        assert!(!if_none_match(&HeaderMap::new(), &etag));
+
+        let weak = HeaderValue::from_static("W/\"r:00000000000000ff\"");
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("\"r:00000000000000ff\""),
+        );
+        assert!(if_none_match(&headers, &weak));
+    }
+}
@@ -55,6 +55,9 @@ where
 
     fn call(&mut self, req: Request) -> Self::Future {
         let is_prod = req.headers().contains_key("x-railway-request-id");
+        // Embed widgets are meant to be framed by other sites; their own CSP
+        // (`frame-ancestors *`) governs framing instead.
+        let is_embed = req.uri().path().starts_with("/embed/");
         let future = self.inner.call(req);
 
         Box::pin(async move {
@@ -62,11 +65,15 @@ where
             let headers = response.headers_mut();
 
             headers.insert(axum::http::header::SERVER, SERVER.clone());
-            headers.insert("x-frame-options", XFO.clone());
+            if !is_embed {
+                headers.insert("x-frame-options", XFO.clone());
+            }
             headers.insert("x-content-type-options", XCTO.clone());
             headers.insert("referrer-policy", REFERRER.clone());
             headers.insert("permissions-policy", PERMISSIONS.clone());
-            headers.insert("cross-origin-opener-policy", COOP.clone());
+            if !is_embed {
+                headers.insert("cross-origin-opener-policy", COOP.clone());
+            }
 
             if is_prod {
                 headers.insert("strict-transport-security", HSTS.clone());
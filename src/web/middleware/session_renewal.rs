@@ -0,0 +1,147 @@
+//! Sliding session renewal.
+//!
+//! After a request carrying a session cookie completes, sessions past their
+//! half-life are extended and the response gets a refreshed `Set-Cookie`, so
+//! active users stay signed in without the cookie and database drifting apart.
+//! See [`crate::data::sessions`] for the lifetime rules.
+
+use crate::web::auth::session::SessionCache;
+use crate::web::auth::{extract_session_token, session_cookie};
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::Response;
+use chrono::Utc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::debug;
+
+#[derive(Clone)]
+pub struct SessionRenewalLayer {
+    sessions: SessionCache,
+    /// Whether cookies are `Secure`, when known from configuration.
+    secure: Option<bool>,
+}
+
+impl SessionRenewalLayer {
+    pub fn new(sessions: SessionCache, secure: Option<bool>) -> Self {
+        Self { sessions, secure }
+    }
+}
+
+impl<S> Layer<S> for SessionRenewalLayer {
+    type Service = SessionRenewalService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SessionRenewalService {
+            inner,
+            sessions: self.sessions.clone(),
+            secure: self.secure,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SessionRenewalService<S> {
+    inner: S,
+    sessions: SessionCache,
+    secure: Option<bool>,
+}
+
+/// Whether the browser reached us over HTTPS, judged from proxy and
+/// navigation headers.
+fn is_https(headers: &HeaderMap) -> bool {
+    if let Some(proto) = headers
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok())
+    {
+        return proto.eq_ignore_ascii_case("https");
+    }
+    [header::ORIGIN, header::REFERER].iter().any(|name| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("https://"))
+    })
+}
+
+/// Whether the handler already set or cleared the session cookie.
+fn sets_session_cookie(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .any(|v| v.as_bytes().starts_with(b"session="))
+}
+
+impl<S, B> Service<Request> for SessionRenewalService<S>
+where
+    S: Service<Request, Response = Response<B>> + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let Some(token) = extract_session_token(req.headers()) else {
+            return Box::pin(self.inner.call(req));
+        };
+        let secure = self.secure.unwrap_or_else(|| is_https(req.headers()));
+        let sessions = self.sessions.clone();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            // Renew after the handler so its extractor has cached the session.
+            if response.status() == StatusCode::UNAUTHORIZED
+                || sets_session_cookie(response.headers())
+            {
+                return Ok(response);
+            }
+            if let Some(expires_at) = sessions.renew_if_due(&token).await {
+                let max_age = (expires_at - Utc::now()).num_seconds().max(0);
+                if let Ok(value) = HeaderValue::from_str(&session_cookie(&token, max_age, secure)) {
+                    response.headers_mut().append(header::SET_COOKIE, value);
+                    debug!(%expires_at, "Session renewed");
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_https_from_proxy_then_navigation_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_https(&headers));
+
+        headers.insert(
+            header::REFERER,
+            "https://banner.test/courses".parse().unwrap(),
+        );
+        assert!(is_https(&headers));
+
+        // A proxy's word overrides what the page claims.
+        headers.insert("x-forwarded-proto", "http".parse().unwrap());
+        assert!(!is_https(&headers));
+    }
+
+    #[test]
+    fn leaves_handler_cookies_alone() {
+        let mut headers = HeaderMap::new();
+        headers.append(header::SET_COOKIE, "theme=dark".parse().unwrap());
+        assert!(!sets_session_cookie(&headers));
+        headers.append(header::SET_COOKIE, "session=; Max-Age=0".parse().unwrap());
+        assert!(sets_session_cookie(&headers));
+    }
+}
@@ -3,27 +3,49 @@
 pub mod admin;
 #[cfg(feature = "embed-assets")]
 pub mod assets;
+pub mod attribution;
 pub mod audit;
 pub mod auth;
 pub mod calendar;
 pub mod courses;
+pub mod crawler;
 pub mod csp_report;
+pub mod debug_capture;
+pub mod embed;
 #[cfg(feature = "embed-assets")]
 pub mod encoding;
 pub mod error;
+pub mod export;
+pub mod feed_cache;
+pub mod feedback;
+pub mod feeds;
+pub mod instructor_cache;
 pub mod instructors;
+pub mod ip_filter;
+pub mod json_ld;
+pub mod maintenance;
 pub mod middleware;
+pub mod openapi;
+pub mod planner;
 pub mod proxy;
+pub mod rooms;
 pub mod routes;
+pub mod saved_searches;
 pub mod schedule_cache;
 pub mod search_options;
 pub mod search_options_cache;
 pub mod sitemap;
 pub mod sitemap_cache;
 pub mod status;
+pub mod status_cache;
 pub mod stream;
+pub mod subjects;
 pub mod suggest;
+pub mod suggest_index;
+pub mod terms;
 pub mod timeline;
+pub mod updates;
+pub mod watchlist;
 pub mod ws;
 
 pub use routes::*;
@@ -0,0 +1,244 @@
+//! OpenAPI description of the public API, served at `/api/openapi.json` with
+//! a Swagger UI at `/api/docs`.
+//!
+//! Operations come from the `#[utoipa::path]` annotation on each handler, and
+//! component schemas from `ToSchema`, derived alongside `TS` on the same types
+//! so the document describes the shapes in `web/src/lib/bindings`.
+//!
+//! Admin, auth, session-bound, and operator (bearer-token) routes are
+//! deliberately left out.
+
+use std::sync::{Arc, LazyLock};
+
+use axum::extract::Path;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Redirect, Response};
+use utoipa::openapi::{ContentBuilder, OpenApi as OpenApiDoc, Ref, ResponseBuilder};
+use utoipa::{Modify, OpenApi};
+
+use crate::web::error::ApiError;
+use crate::web::routes::cache;
+use crate::web::{
+    attribution, calendar, courses, export, feedback, instructors, json_ld, rooms, search_options,
+    status, subjects, suggest, terms, timeline, updates,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Banner API",
+        description = "Public course, instructor, and enrollment data."
+    ),
+    servers((url = "/api")),
+    paths(
+        status::health,
+        status::status,
+        status::full_status,
+        status::freshness,
+        status::scraping_stats,
+        attribution::attribution,
+        status::metrics,
+        courses::search_courses,
+        export::export_courses,
+        courses::get_course,
+        courses::get_course_page_data,
+        courses::get_course_as_of,
+        calendar::course_ics,
+        calendar::course_gcal,
+        json_ld::course_json_ld,
+        courses::get_related_sections,
+        search_options::get_reference,
+        search_options::get_search_options,
+        subjects::get_subject,
+        terms::get_term_changes,
+        rooms::get_free_rooms,
+        rooms::get_room_schedule,
+        suggest::suggest,
+        instructors::list_instructors,
+        suggest::suggest_instructors,
+        suggest::resolve_instructors,
+        instructors::get_instructor,
+        instructors::get_instructor_sections,
+        json_ld::instructor_json_ld,
+        instructors::get_related_instructors,
+        timeline::timeline,
+        timeline::timeline_compare,
+        updates::poll_updates,
+        feedback::submit_feedback,
+    ),
+    components(schemas(ApiError)),
+    modifiers(&ErrorResponses)
+)]
+struct ApiDoc;
+
+/// Gives every operation a `default` response carrying [`ApiError`], the
+/// body of every failed request.
+struct ErrorResponses;
+
+impl Modify for ErrorResponses {
+    fn modify(&self, openapi: &mut OpenApiDoc) {
+        let error = ResponseBuilder::new()
+            .description("Error")
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ApiError")))
+                    .build(),
+            )
+            .build();
+        for item in openapi.paths.paths.values_mut() {
+            for operation in [&mut item.get, &mut item.post].into_iter().flatten() {
+                operation
+                    .responses
+                    .responses
+                    .insert("default".to_owned(), error.clone().into());
+            }
+        }
+    }
+}
+
+static DOCUMENT: LazyLock<String> = LazyLock::new(|| {
+    ApiDoc::openapi()
+        .to_json()
+        .expect("OpenAPI document serializes")
+});
+
+/// `GET /api/openapi.json`
+pub(super) async fn openapi_json() -> Response {
+    let mut resp = (
+        [(header::CONTENT_TYPE, "application/json")],
+        DOCUMENT.as_str(),
+    )
+        .into_response();
+    resp.headers_mut().insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(cache::REFERENCE),
+    );
+    resp
+}
+
+/// Swagger UI pointed at [`openapi_json`].
+static SWAGGER_CONFIG: LazyLock<Arc<utoipa_swagger_ui::Config<'static>>> =
+    LazyLock::new(|| Arc::new(utoipa_swagger_ui::Config::from("/api/openapi.json")));
+
+/// The UI loads its scripts and styles from `/api/docs/`, fetches the document,
+/// and sets inline styles and data-URI icons.
+const SWAGGER_CSP: &str = "default-src 'none'; script-src 'self'; style-src 'self' 'unsafe-inline'; \
+    img-src 'self' data:; connect-src 'self'; frame-ancestors 'none'";
+
+/// `GET /api/docs` -- redirects to the UI's index so its relative asset
+/// paths resolve under `/api/docs/`.
+pub(super) async fn swagger_ui_redirect() -> Redirect {
+    Redirect::permanent("/api/docs/")
+}
+
+/// `GET /api/docs/{*file}` -- Swagger UI assets, built into the binary.
+pub(super) async fn swagger_ui(file: Option<Path<String>>) -> Response {
+    let file = file.map(|Path(file)| file).unwrap_or_default();
+    match utoipa_swagger_ui::serve(&file, SWAGGER_CONFIG.clone()) {
+        Ok(Some(asset)) => (
+            [
+                (header::CONTENT_TYPE, asset.content_type),
+                (header::CACHE_CONTROL, cache::REFERENCE.to_owned()),
+                (header::CONTENT_SECURITY_POLICY, SWAGGER_CSP.to_owned()),
+            ],
+            asset.bytes.into_owned(),
+        )
+            .into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!(error = %e, file, "failed to serve Swagger UI asset");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn document() -> Value {
+        serde_json::from_str(&DOCUMENT).unwrap()
+    }
+
+    #[test]
+    fn document_references_resolve() {
+        let doc = document();
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+        for reference in DOCUMENT.split("#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.contains_key(name), "missing schema {name}");
+        }
+        let course = &doc["paths"]["/courses/{term}/{crn}"]["get"];
+        assert!(course["responses"]["default"].is_object());
+        assert_eq!(course["parameters"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn path_params_are_declared() {
+        let doc = document();
+        for (path, item) in doc["paths"].as_object().unwrap() {
+            let in_path: Vec<&str> = path
+                .split('/')
+                .filter_map(|seg| seg.strip_prefix('{')?.strip_suffix('}'))
+                .collect();
+            for operation in item.as_object().unwrap().values() {
+                let declared: Vec<&str> = operation["parameters"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|p| p["in"] == "path")
+                    .map(|p| p["name"].as_str().unwrap())
+                    .collect();
+                assert_eq!(declared, in_path, "{path}");
+            }
+        }
+    }
+
+    #[test]
+    fn schemas_follow_ts_bindings() {
+        let doc = document();
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+        let course = &schemas["CourseResponse"];
+        assert_eq!(course["type"], "object");
+        assert_eq!(course["properties"]["crn"]["type"], "string");
+        // serde's camelCase renames apply to both.
+        assert!(course["properties"]["courseNumber"].is_object());
+
+        let search = &doc["paths"]["/courses/search"]["get"]["parameters"];
+        assert!(
+            search
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|p| p["name"] == "openOnly" && p["in"] == "query")
+        );
+    }
+
+    #[test]
+    fn operator_endpoints_are_not_documented() {
+        let doc = document();
+        let paths = doc["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/metrics"));
+        assert!(!paths.contains_key("/metrics/prometheus"));
+        assert!(!paths.keys().any(|p| p.starts_with("/admin")));
+    }
+
+    #[test]
+    fn serves_vendored_swagger_ui() {
+        for file in ["", "swagger-ui.css", "swagger-ui-bundle.js"] {
+            assert!(
+                utoipa_swagger_ui::serve(file, SWAGGER_CONFIG.clone())
+                    .unwrap()
+                    .is_some(),
+                "{file}"
+            );
+        }
+        let initializer =
+            utoipa_swagger_ui::serve("swagger-initializer.js", SWAGGER_CONFIG.clone())
+                .unwrap()
+                .unwrap();
+        assert!(String::from_utf8_lossy(&initializer.bytes).contains("/api/openapi.json"));
+    }
+}
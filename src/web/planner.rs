@@ -0,0 +1,240 @@
+//! Signed-in users' planned schedules, synced across devices.
+//!
+//! A plan is a list of CRNs per term. Clients replace the whole list on
+//! every change, so the last device to save wins; `updatedAt` lets a client
+//! notice it is behind. A term's plan can be exported as one ICS calendar.
+
+use std::collections::HashSet;
+
+use axum::extract::{Path, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Json, Response};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, instrument};
+use ts_rs::TS;
+
+use crate::banner::models::terms::Term;
+use crate::calendar::generate_schedule_ics;
+use crate::data::courses;
+use crate::data::planner::{self, UserSchedule};
+use crate::display::DisplayPreferences;
+use crate::state::AppState;
+use crate::web::auth::extractors::AuthUser;
+use crate::web::calendar::to_calendar_course;
+use crate::web::error::{ApiError, db_error};
+
+/// Most sections one term's plan may hold.
+const MAX_SECTIONS_PER_TERM: usize = 30;
+
+/// The sections the caller has planned for a term.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UserScheduleResponse {
+    pub term: String,
+    pub crns: Vec<String>,
+    /// When the plan was last saved; `null` if it never was.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<UserSchedule> for UserScheduleResponse {
+    fn from(schedule: UserSchedule) -> Self {
+        Self {
+            term: schedule.term_code,
+            crns: schedule.crns,
+            updated_at: Some(schedule.updated_at),
+        }
+    }
+}
+
+/// Body for `PUT /api/me/schedule/{term}`.
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ReplaceScheduleBody {
+    /// Replaces the term's plan. Every CRN must exist in the term.
+    pub crns: Vec<String>,
+}
+
+fn resolve_term(term: &str) -> Result<String, ApiError> {
+    Term::resolve_to_code(term).ok_or_else(|| ApiError::invalid_term(term))
+}
+
+/// `GET /api/me/schedule` -- Every term the caller has planned.
+#[instrument(skip_all)]
+pub async fn list_schedules(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<Vec<UserScheduleResponse>>, ApiError> {
+    let schedules = planner::list_schedules(&state.db_pool, user.id)
+        .await
+        .map_err(|e| db_error("List schedules", e))?;
+    Ok(Json(schedules.into_iter().map(Into::into).collect()))
+}
+
+/// `GET /api/me/schedule/{term}` -- The caller's plan for a term, empty if
+/// they haven't saved one.
+#[instrument(skip_all)]
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(term): Path<String>,
+) -> Result<Json<UserScheduleResponse>, ApiError> {
+    let term_code = resolve_term(&term)?;
+    let schedule = planner::get_schedule(&state.db_pool, user.id, &term_code)
+        .await
+        .map_err(|e| db_error("Get schedule", e))?;
+    Ok(Json(schedule.map(Into::into).unwrap_or(
+        UserScheduleResponse {
+            term: term_code,
+            crns: Vec::new(),
+            updated_at: None,
+        },
+    )))
+}
+
+/// `PUT /api/me/schedule/{term}` -- Replace the caller's plan for a term.
+#[instrument(skip_all)]
+pub async fn replace_schedule(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(term): Path<String>,
+    Json(body): Json<ReplaceScheduleBody>,
+) -> Result<Json<UserScheduleResponse>, ApiError> {
+    let term_code = resolve_term(&term)?;
+
+    // Keep the client's order, dropping repeats.
+    let mut seen = HashSet::new();
+    let crns: Vec<String> = body
+        .crns
+        .into_iter()
+        .map(|crn| crn.trim().to_owned())
+        .filter(|crn| !crn.is_empty() && seen.insert(crn.clone()))
+        .collect();
+    if crns.len() > MAX_SECTIONS_PER_TERM {
+        return Err(ApiError::bad_request(format!(
+            "A plan can hold at most {MAX_SECTIONS_PER_TERM} sections"
+        )));
+    }
+
+    let found: HashSet<String> = courses::get_courses_by_crns(&state.db_pool, &term_code, &crns)
+        .await
+        .map_err(|e| db_error("Schedule course lookup", e))?
+        .into_iter()
+        .map(|c| c.crn)
+        .collect();
+    let unknown: Vec<&str> = crns
+        .iter()
+        .filter(|crn| !found.contains(*crn))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        return Err(ApiError::bad_request(format!(
+            "Unknown CRNs for term {term_code}: {}",
+            unknown.join(", ")
+        )));
+    }
+
+    let schedule = planner::replace_schedule(&state.db_pool, user.id, &term_code, &crns)
+        .await
+        .map_err(|e| db_error("Replace schedule", e))?;
+
+    info!(
+        user_id = user.id,
+        term = %term_code,
+        sections = crns.len(),
+        "Schedule saved"
+    );
+
+    Ok(Json(schedule.into()))
+}
+
+/// `DELETE /api/me/schedule/{term}` -- Clear the caller's plan for a term.
+#[instrument(skip_all)]
+pub async fn delete_schedule(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(term): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let term_code = resolve_term(&term)?;
+    let removed = planner::delete_schedule(&state.db_pool, user.id, &term_code)
+        .await
+        .map_err(|e| db_error("Delete schedule", e))?;
+    if !removed {
+        return Err(ApiError::not_found("No schedule saved for this term"));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/me/schedule/{term}/calendar.ics`
+///
+/// Every planned section's meetings in one ICS file, rendered per the
+/// caller's display preferences. Sections no longer offered are skipped.
+#[instrument(skip_all)]
+pub async fn schedule_ics(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(term): Path<String>,
+) -> Result<Response, ApiError> {
+    let term_code = resolve_term(&term)?;
+    let crns = planner::get_schedule(&state.db_pool, user.id, &term_code)
+        .await
+        .map_err(|e| db_error("Get schedule", e))?
+        .map(|s| s.crns)
+        .unwrap_or_default();
+
+    let planned = courses::get_courses_by_crns(&state.db_pool, &term_code, &crns)
+        .await
+        .map_err(|e| db_error("Schedule course lookup", e))?;
+    let course_ids: Vec<i32> = planned.iter().map(|c| c.id).collect();
+    let mut instructor_map = courses::get_instructors_for_courses(&state.db_pool, &course_ids)
+        .await
+        .unwrap_or_else(|e| {
+            error!(error = %e, "Failed to fetch instructors for schedule export");
+            Default::default()
+        });
+
+    let calendar_courses: Vec<_> = planned
+        .iter()
+        .map(|course| {
+            let instructors = instructor_map.remove(&course.id).unwrap_or_default();
+            to_calendar_course(course, &instructors)
+        })
+        .filter(|(_, meeting_times)| !meeting_times.is_empty())
+        .collect();
+    if calendar_courses.is_empty() {
+        return Err(ApiError::not_found(
+            "No planned sections with meeting times for this term",
+        ));
+    }
+
+    let term_name = term_code
+        .parse::<Term>()
+        .map(|t| t.description())
+        .unwrap_or_else(|_| term_code.clone());
+    let prefs = DisplayPreferences::for_user(Some(&user));
+    let result = generate_schedule_ics(
+        &format!("Schedule - {term_name}"),
+        &format!("schedule_{term_code}"),
+        &calendar_courses,
+        &prefs,
+    )
+    .map_err(|e| {
+        error!(%e, "Schedule ICS generation failed");
+        ApiError::internal_error("Failed to generate ICS file")
+    })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                &format!("attachment; filename=\"{}\"", result.filename),
+            ),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        result.content,
+    )
+        .into_response())
+}
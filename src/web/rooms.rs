@@ -0,0 +1,484 @@
+//! Classroom schedule and free-room HTTP handlers.
+//!
+//! Occupancy is read from the in-memory [`ScheduleCache`], which keeps each
+//! meeting's building and room; only the few sections that meet in the
+//! requested room are looked up in the database for display.
+//!
+//! [`ScheduleCache`]: crate::web::schedule_cache::ScheduleCache
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use axum::extract::{Path, Query, State};
+use axum::response::Response;
+use chrono::{NaiveTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::banner::models::meetings::TimeRange;
+use crate::banner::models::terms::Term;
+use crate::data;
+use crate::data::course_types::DateRange;
+use crate::data::models::DayOfWeek;
+use crate::data::rooms::{BUILDING_CATEGORY, ROOM_CATEGORY, room_code};
+use crate::state::AppState;
+use crate::web::error::{ApiError, db_error};
+use crate::web::routes::{cache, with_cache_control};
+use crate::web::schedule_cache::{ParsedSchedule, RoomKey, TermSchedule, weekday_bit};
+
+/// Weekdays in day-bitmask order: bit 0 = Monday, bit 6 = Sunday.
+const WEEKDAYS: [DayOfWeek; 7] = [
+    DayOfWeek::Monday,
+    DayOfWeek::Tuesday,
+    DayOfWeek::Wednesday,
+    DayOfWeek::Thursday,
+    DayOfWeek::Friday,
+    DayOfWeek::Saturday,
+    DayOfWeek::Sunday,
+];
+
+/// Query params for `GET /api/rooms/{building}/{room}/schedule`.
+#[derive(Debug, Deserialize, Serialize, TS, IntoParams)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct RoomScheduleParams {
+    /// Term code or slug.
+    pub term: String,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RoomScheduleResponse {
+    pub term_code: String,
+    pub building: String,
+    /// Building name from reference data, when known.
+    pub building_description: Option<String>,
+    pub room: String,
+    /// Weekdays with at least one meeting, Monday first.
+    pub days: Vec<RoomDay>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RoomDay {
+    pub day: DayOfWeek,
+    /// Meetings on this weekday, ordered by start time.
+    pub blocks: Vec<RoomBlock>,
+}
+
+/// One section occupying the room.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RoomBlock {
+    pub crn: String,
+    pub subject: String,
+    pub course_number: String,
+    pub title: String,
+    pub time_range: TimeRange,
+    /// The weeks this meeting pattern runs.
+    pub date_range: DateRange,
+}
+
+/// Query params for `GET /api/rooms/free`.
+#[derive(Debug, Deserialize, Serialize, TS, IntoParams)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct FreeRoomsParams {
+    /// Term code or slug.
+    pub term: String,
+    /// Weekday, e.g. `mon` or `monday`.
+    pub day: String,
+    /// Window start as `HH:MM`.
+    pub start: String,
+    /// Window end as `HH:MM`; must be after `start`.
+    pub end: String,
+    /// Only rooms in this building.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub building: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FreeRoomsResponse {
+    pub term_code: String,
+    pub day: DayOfWeek,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    /// Rooms with no meeting overlapping the window, by building then room.
+    pub rooms: Vec<FreeRoom>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FreeRoom {
+    pub building: String,
+    /// Building name from reference data, when known.
+    pub building_description: Option<String>,
+    pub room: String,
+    /// Start of the room's next meeting that day; `None` if it stays free.
+    pub free_until: Option<NaiveTime>,
+}
+
+/// Minutes since midnight as a time of day. A meeting ending at midnight is
+/// shown as ending at 23:59.
+fn minutes_to_time(minutes: u16) -> NaiveTime {
+    NaiveTime::from_hms_opt(u32::from(minutes / 60), u32::from(minutes % 60), 0)
+        .unwrap_or_else(|| NaiveTime::from_hms_opt(23, 59, 0).expect("valid time"))
+}
+
+/// Parse an `HH:MM` clock time as minutes since midnight.
+fn parse_clock(raw: &str) -> Option<u16> {
+    let time = NaiveTime::parse_from_str(raw.trim(), "%H:%M").ok()?;
+    Some((time.hour() * 60 + time.minute()) as u16)
+}
+
+/// Rooms used by the term that have no meeting on `day_bit` overlapping
+/// `[start, end)`, each with the start of its next meeting that day.
+///
+/// Date ranges are ignored: a room booked for any part of the term counts
+/// as busy, so a room listed here is free every week.
+fn free_rooms(
+    schedule: &TermSchedule,
+    day_bit: u8,
+    start: u16,
+    end: u16,
+) -> Vec<(&RoomKey, Option<u16>)> {
+    // Room -> start of its next meeting at or after `end`.
+    let mut rooms: BTreeMap<&RoomKey, Option<u16>> = BTreeMap::new();
+    let mut busy: BTreeSet<&RoomKey> = BTreeSet::new();
+    for meeting in schedule.courses.iter().flat_map(|c| c.schedules.iter()) {
+        let Some(key) = &meeting.room else {
+            continue;
+        };
+        let next = rooms.entry(key).or_default();
+        if meeting.days & day_bit == 0 {
+            continue;
+        }
+        if meeting.begin_minutes < end && meeting.end_minutes > start {
+            busy.insert(key);
+        } else if meeting.begin_minutes >= end {
+            *next = Some(next.map_or(meeting.begin_minutes, |n| n.min(meeting.begin_minutes)));
+        }
+    }
+    rooms
+        .into_iter()
+        .filter(|(key, _)| !busy.contains(key))
+        .collect()
+}
+
+/// Spread meetings, each paired with its day bitmask, across the weekdays
+/// they fall on.
+fn group_by_day(meetings: &[(u8, RoomBlock)]) -> Vec<RoomDay> {
+    let mut days: Vec<RoomDay> = Vec::new();
+    for (bit, day) in WEEKDAYS.into_iter().enumerate() {
+        let mut blocks: Vec<RoomBlock> = meetings
+            .iter()
+            .filter(|(day_bits, _)| day_bits & (1 << bit) != 0)
+            .map(|(_, block)| block.clone())
+            .collect();
+        if blocks.is_empty() {
+            continue;
+        }
+        blocks.sort_by(|a, b| {
+            a.time_range
+                .start
+                .cmp(&b.time_range.start)
+                .then_with(|| a.subject.cmp(&b.subject))
+                .then_with(|| a.course_number.cmp(&b.course_number))
+        });
+        days.push(RoomDay { day, blocks });
+    }
+    days
+}
+
+/// `GET /api/rooms/{building}/{room}/schedule?term=` -- Weekly occupancy of
+/// a classroom: every section meeting there, grouped by weekday.
+#[utoipa::path(
+    get,
+    path = "/rooms/{building}/{room}/schedule",
+    tag = "reference",
+    summary = "Weekly occupancy of a classroom",
+    params(
+        ("building" = String, Path, description = "Building code (e.g. NPB)"),
+        ("room" = String, Path, description = "Room code (e.g. 1.238)"),
+        RoomScheduleParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = RoomScheduleResponse),
+    ),
+)]
+pub async fn get_room_schedule(
+    State(state): State<AppState>,
+    Path((building, room)): Path<(String, String)>,
+    Query(params): Query<RoomScheduleParams>,
+) -> Result<Response, ApiError> {
+    let term_code =
+        Term::resolve_to_code(&params.term).ok_or_else(|| ApiError::invalid_term(&params.term))?;
+    let building = building.trim().to_uppercase();
+    let room = room.trim().to_uppercase();
+
+    state.schedule_cache.ensure_fresh();
+    let snapshot = state.schedule_cache.snapshot();
+    let meetings: Vec<(i32, ParsedSchedule)> = snapshot
+        .terms
+        .get(&term_code)
+        .map(|schedule| {
+            schedule
+                .room_meetings(&building, &room)
+                .map(|(course, meeting)| (course.id, meeting.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let building_description = {
+        let ref_cache = state.reference_cache.read().await;
+        if meetings.is_empty()
+            && ref_cache
+                .lookup(ROOM_CATEGORY, &room_code(&building, &room))
+                .is_none()
+        {
+            return Err(ApiError::not_found(format!(
+                "Room '{building} {room}' not found"
+            )));
+        }
+        ref_cache
+            .lookup(BUILDING_CATEGORY, &building)
+            .map(str::to_owned)
+    };
+
+    let mut ids: Vec<i32> = meetings.iter().map(|(id, _)| *id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let sections: HashMap<i32, data::rooms::RoomSection> =
+        data::rooms::sections_by_id(&state.db_pool, &ids)
+            .await
+            .map_err(|e| db_error("Room sections", e))?
+            .into_iter()
+            .map(|section| (section.id, section))
+            .collect();
+
+    let blocks: Vec<(u8, RoomBlock)> = meetings
+        .iter()
+        .filter_map(|(id, schedule)| {
+            let section = sections.get(id)?;
+            Some((
+                schedule.days,
+                RoomBlock {
+                    crn: section.crn.clone(),
+                    subject: section.subject.clone(),
+                    course_number: section.course_number.clone(),
+                    title: section.title.clone(),
+                    time_range: TimeRange {
+                        start: minutes_to_time(schedule.begin_minutes),
+                        end: minutes_to_time(schedule.end_minutes),
+                    },
+                    date_range: DateRange {
+                        start: schedule.start_date,
+                        end: schedule.end_date,
+                    },
+                },
+            ))
+        })
+        .collect();
+
+    Ok(with_cache_control(
+        RoomScheduleResponse {
+            term_code,
+            building,
+            building_description,
+            room,
+            days: group_by_day(&blocks),
+        },
+        cache::REFERENCE,
+    ))
+}
+
+/// `GET /api/rooms/free?term=&day=&start=&end=` -- Classrooms with no
+/// meeting during a weekday time window.
+#[utoipa::path(
+    get,
+    path = "/rooms/free",
+    tag = "reference",
+    summary = "Classrooms with no meeting during a weekday time window",
+    params(
+        FreeRoomsParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = FreeRoomsResponse),
+    ),
+)]
+pub async fn get_free_rooms(
+    State(state): State<AppState>,
+    Query(params): Query<FreeRoomsParams>,
+) -> Result<Response, ApiError> {
+    let term_code =
+        Term::resolve_to_code(&params.term).ok_or_else(|| ApiError::invalid_term(&params.term))?;
+    let weekday: Weekday = params.day.trim().parse().map_err(|_| {
+        ApiError::bad_request(format!(
+            "Invalid day '{}': expected a weekday like mon or monday",
+            params.day
+        ))
+    })?;
+    let (start, end) = match (parse_clock(&params.start), parse_clock(&params.end)) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        (Some(_), Some(_)) => return Err(ApiError::bad_request("end must be after start")),
+        _ => {
+            return Err(ApiError::bad_request(
+                "start and end must be times like 14:00",
+            ));
+        }
+    };
+    let building_filter = params.building.as_deref().map(str::trim);
+
+    state.schedule_cache.ensure_fresh();
+    let snapshot = state.schedule_cache.snapshot();
+    let ref_cache = state.reference_cache.read().await;
+    let rooms: Vec<FreeRoom> = snapshot
+        .terms
+        .get(&term_code)
+        .map(|schedule| {
+            free_rooms(schedule, weekday_bit(weekday), start, end)
+                .into_iter()
+                .filter(|(key, _)| {
+                    building_filter.is_none_or(|b| key.building.eq_ignore_ascii_case(b))
+                })
+                .map(|(key, next)| FreeRoom {
+                    building: key.building.to_string(),
+                    building_description: ref_cache
+                        .lookup(BUILDING_CATEGORY, &key.building)
+                        .map(str::to_owned),
+                    room: key.room.to_string(),
+                    free_until: next.map(minutes_to_time),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    drop(ref_cache);
+
+    Ok(with_cache_control(
+        FreeRoomsResponse {
+            term_code,
+            day: WEEKDAYS[weekday.num_days_from_monday() as usize],
+            start: minutes_to_time(start),
+            end: minutes_to_time(end),
+            rooms,
+        },
+        cache::REFERENCE,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn minutes_to_time_clamps_midnight() {
+        assert_eq!(
+            minutes_to_time(600),
+            NaiveTime::from_hms_opt(10, 0, 0).unwrap()
+        );
+        assert_eq!(
+            minutes_to_time(1440),
+            NaiveTime::from_hms_opt(23, 59, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn free_rooms_excludes_overlapping_meetings() {
+        use std::sync::Arc;
+
+        use crate::web::schedule_cache::CachedCourse;
+
+        let date = NaiveDate::from_ymd_opt(2025, 8, 25).unwrap();
+        let meeting = |room: &str, days: u8, begin: u16, end: u16| ParsedSchedule {
+            days,
+            begin_minutes: begin,
+            end_minutes: end,
+            start_date: date,
+            end_date: date,
+            room: Some(RoomKey {
+                building: Arc::from("NPB"),
+                room: Arc::from(room),
+            }),
+        };
+        let term = TermSchedule {
+            courses: vec![CachedCourse {
+                id: 1,
+                subject: Arc::from("CS"),
+                level: Arc::from("1000"),
+                campus: Arc::from("MAIN"),
+                enrollment: 10,
+                schedules: vec![
+                    // Busy Monday 14:30-15:45.
+                    meeting("1.100", 0b0000001, 870, 945),
+                    // Monday 16:00, right as the window ends.
+                    meeting("1.200", 0b0000001, 960, 1010),
+                    meeting("1.200", 0b0000001, 1100, 1150),
+                    // Only on Tuesdays.
+                    meeting("1.300", 0b0000010, 840, 900),
+                ],
+            }],
+            start_date: None,
+        };
+
+        let free: Vec<(&str, Option<u16>)> = free_rooms(&term, 0b0000001, 840, 960)
+            .into_iter()
+            .map(|(key, next)| (&*key.room, next))
+            .collect();
+        assert_eq!(free, vec![("1.200", Some(960)), ("1.300", None)]);
+    }
+
+    #[test]
+    fn parse_clock_accepts_hh_mm() {
+        assert_eq!(parse_clock("14:00"), Some(840));
+        assert_eq!(parse_clock(" 9:05 "), Some(545));
+        assert_eq!(parse_clock("2pm"), None);
+        assert_eq!(parse_clock("24:00"), None);
+    }
+
+    #[test]
+    fn group_by_day_orders_blocks_by_start() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 25).unwrap();
+        let meeting = |days: u8, begin: u16, subject: &str| {
+            let block = RoomBlock {
+                crn: "12345".to_owned(),
+                subject: subject.to_owned(),
+                course_number: "1100".to_owned(),
+                title: String::new(),
+                time_range: TimeRange {
+                    start: minutes_to_time(begin),
+                    end: minutes_to_time(begin + 50),
+                },
+                date_range: DateRange {
+                    start: date,
+                    end: date,
+                },
+            };
+            (days, block)
+        };
+        // MWF at 10:00 and MW at 09:00.
+        let days = group_by_day(&[
+            meeting(0b0010101, 600, "CS"),
+            meeting(0b0000101, 540, "MAT"),
+        ]);
+
+        let summary: Vec<(DayOfWeek, Vec<&str>)> = days
+            .iter()
+            .map(|d| (d.day, d.blocks.iter().map(|b| b.subject.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (DayOfWeek::Monday, vec!["MAT", "CS"]),
+                (DayOfWeek::Wednesday, vec!["MAT", "CS"]),
+                (DayOfWeek::Friday, vec!["CS"]),
+            ]
+        );
+    }
+}
@@ -5,7 +5,7 @@ use axum::{
     extract::{Request, State},
     http::HeaderValue,
     response::{IntoResponse, Response},
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
 };
 
 use std::time::Duration;
@@ -16,13 +16,22 @@ use axum::response::Json;
 
 use crate::state::AppState;
 use crate::web::auth::{self, AuthConfig};
-use crate::web::middleware::client_ip::ClientIp;
+use crate::web::middleware::admin_audit::record_admin_action;
+use crate::web::middleware::bot_block::BotBlockLayer;
+use crate::web::middleware::client_ip::{ClientIp, ClientIpLayer};
+use crate::web::middleware::debug_capture::DebugCaptureLayer;
+use crate::web::middleware::ip_filter::IpFilterLayer;
+use crate::web::middleware::maintenance::MaintenanceLayer;
+use crate::web::middleware::metrics::MetricsLayer;
 use crate::web::middleware::rate_limit::RateLimitLayer;
 use crate::web::middleware::request_id::RequestIdLayer;
+use crate::web::middleware::response_cache::ResponseCacheLayer;
 use crate::web::middleware::security_headers::SecurityHeadersLayer;
+use crate::web::middleware::session_renewal::SessionRenewalLayer;
 use crate::web::{
-    admin, calendar, courses, csp_report, instructors, search_options, status, stream, suggest,
-    timeline,
+    admin, attribution, calendar, courses, csp_report, embed, export, feedback, instructors,
+    json_ld, openapi, planner, rooms, saved_searches, search_options, status, stream, subjects,
+    suggest, terms, timeline, updates, watchlist,
 };
 use tower_http::{compression::CompressionLayer, timeout::TimeoutLayer};
 
@@ -42,6 +51,12 @@ pub mod cache {
     pub const DETAIL: &str = "public, max-age=60, s-maxage=300, stale-while-revalidate=120";
     /// Admin endpoints -- never cache.
     pub const ADMIN: &str = "private, no-store, must-revalidate";
+    /// Scrape freshness -- short-lived so the footer tracks the scraper.
+    pub const FRESHNESS: &str = "public, max-age=60, s-maxage=60";
+    /// Per-subject scrape stats -- a heavier query, so lean on the edge.
+    pub const SCRAPE_STATS: &str = "public, max-age=120, s-maxage=300, stale-while-revalidate=120";
+    /// Live update polling -- responses depend on the caller's cursor.
+    pub const LIVE: &str = "no-store";
 }
 
 /// Wraps a JSON response with a `Cache-Control` header.
@@ -58,10 +73,24 @@ pub fn with_cache_control<T: serde::Serialize>(value: T, header: &'static str) -
 pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
     let api_router = Router::new()
         .route("/health", get(status::health))
+        .route("/openapi.json", get(openapi::openapi_json))
+        .route("/docs", get(openapi::swagger_ui_redirect))
+        .route("/docs/", get(openapi::swagger_ui))
+        .route("/docs/{*file}", get(openapi::swagger_ui))
         .route("/status", get(status::status))
+        .route("/status/full", get(status::full_status))
+        .route("/freshness", get(status::freshness))
+        .route("/meta/attribution", get(attribution::attribution))
+        .route("/stats/scraping", get(status::scraping_stats))
         .route("/metrics", get(status::metrics))
+        .route("/metrics/prometheus", get(status::prometheus_metrics))
         .route("/courses/search", get(courses::search_courses))
+        .route("/courses/export", get(export::export_courses))
         .route("/courses/{term}/{crn}", get(courses::get_course))
+        .route(
+            "/courses/{term}/{crn}/page-data",
+            get(courses::get_course_page_data),
+        )
         .route(
             "/courses/{term}/{subject}/{course_number}/sections",
             get(courses::get_related_sections),
@@ -71,8 +100,23 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
             get(calendar::course_ics),
         )
         .route("/courses/{term}/{crn}/gcal", get(calendar::course_gcal))
+        .route(
+            "/courses/{term}/{crn}/json-ld",
+            get(json_ld::course_json_ld),
+        )
+        .route(
+            "/courses/{term}/{crn}/as-of",
+            get(courses::get_course_as_of),
+        )
         .route("/reference/{category}", get(search_options::get_reference))
         .route("/search-options", get(search_options::get_search_options))
+        .route("/subjects/{code}", get(subjects::get_subject))
+        .route("/terms/{term}/changes", get(terms::get_term_changes))
+        .route("/rooms/free", get(rooms::get_free_rooms))
+        .route(
+            "/rooms/{building}/{room}/schedule",
+            get(rooms::get_room_schedule),
+        )
         .route("/suggest", get(suggest::suggest))
         .route("/instructors/resolve", get(suggest::resolve_instructors))
         .route("/instructors/suggest", get(suggest::suggest_instructors))
@@ -82,29 +126,109 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
             "/instructors/{slug}/sections",
             get(instructors::get_instructor_sections),
         )
+        .route(
+            "/instructors/{slug}/json-ld",
+            get(json_ld::instructor_json_ld),
+        )
+        .route(
+            "/instructors/{slug}/related",
+            get(instructors::get_related_instructors),
+        )
         .route("/timeline", post(timeline::timeline))
+        .route("/timeline/compare", post(timeline::timeline_compare))
         .route("/ws", get(stream::stream_ws))
+        .route("/updates/poll", get(updates::poll_updates))
         .route("/csp-report", post(csp_report::csp_report))
+        .route("/feedback", post(feedback::submit_feedback))
+        .route(
+            "/watchlist",
+            get(watchlist::list_watches).post(watchlist::add_watch),
+        )
+        .route("/watchlist/{term}/{crn}", delete(watchlist::remove_watch))
+        .route(
+            "/watchlist/departments",
+            get(watchlist::list_department_watches).post(watchlist::add_department_watch),
+        )
+        .route(
+            "/watchlist/departments/{term}/{subject}",
+            delete(watchlist::remove_department_watch),
+        )
+        .route(
+            "/me/searches",
+            get(saved_searches::list_saved_searches).post(saved_searches::create_saved_search),
+        )
+        .route(
+            "/me/searches/{id}",
+            delete(saved_searches::delete_saved_search),
+        )
+        .route("/me/schedule", get(planner::list_schedules))
+        .route(
+            "/me/schedule/{term}",
+            get(planner::get_schedule)
+                .put(planner::replace_schedule)
+                .delete(planner::delete_schedule),
+        )
+        .route(
+            "/me/schedule/{term}/calendar.ics",
+            get(planner::schedule_ics),
+        )
         .with_state(app_state.clone());
 
     let auth_router = Router::new()
         .route("/auth/login", get(auth::auth_login))
         .route("/auth/callback", get(auth::auth_callback))
+        .route("/auth/providers", get(auth::auth_providers))
+        .route("/auth/{provider}/login", get(auth::provider_login))
+        .route("/auth/{provider}/callback", get(auth::provider_callback))
+        .route("/auth/identities", get(auth::list_identities))
+        .route("/auth/identities/{provider}", delete(auth::unlink_identity))
+        .route("/auth/sessions", get(auth::list_sessions))
+        .route("/auth/sessions/{id}", delete(auth::revoke_session))
         .route("/auth/logout", post(auth::auth_logout))
         .route("/auth/me", get(auth::auth_me))
-        .layer(Extension(auth_config))
+        .route("/auth/key", get(auth::auth_key))
+        .route(
+            "/auth/preferences/display",
+            put(auth::auth_update_display_preferences),
+        )
+        .layer(Extension(auth_config.clone()))
         .with_state(app_state.clone());
 
     let admin_router = Router::new()
         .route("/admin/status", get(admin::admin_status))
         .route("/admin/users", get(admin::list_users))
+        .route("/admin/actions", get(admin::actions::list_admin_actions))
+        .route("/admin/users/export", get(admin::export::export_users))
+        .route("/admin/users/{id}/admin", put(admin::set_user_admin))
         .route(
-            "/admin/users/{discord_id}/admin",
-            put(admin::set_user_admin),
+            "/admin/api-keys",
+            get(admin::api_keys::list_api_keys).post(admin::api_keys::create_api_key),
+        )
+        .route(
+            "/admin/api-keys/{id}",
+            put(admin::api_keys::rename_api_key).delete(admin::api_keys::revoke_api_key),
         )
         .route("/admin/scrape-jobs", get(admin::list_scrape_jobs))
+        .route(
+            "/admin/scrape-jobs/dead-letter",
+            get(admin::list_dead_letter_jobs),
+        )
+        .route(
+            "/admin/scrape-jobs/{id}/requeue",
+            post(admin::requeue_scrape_job),
+        )
+        .route("/admin/scrape-jobs/{id}", delete(admin::discard_scrape_job))
         .route("/admin/audit-log", get(admin::list_audit_log))
+        .route(
+            "/admin/audit-log/export",
+            get(admin::export::export_audit_log),
+        )
+        .route("/admin/courses/export", get(admin::export::export_courses))
         .route("/admin/instructors", get(admin::rmp::list_instructors))
+        .route(
+            "/admin/instructors/rebuild-slugs",
+            post(admin::slugs::rebuild_slugs),
+        )
         .route("/admin/instructors/{id}", get(admin::rmp::get_instructor))
         .route(
             "/admin/instructors/{id}/match",
@@ -123,6 +247,19 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
             post(admin::rmp::unmatch_instructor),
         )
         .route("/admin/rmp/rescore", post(admin::rmp::rescore))
+        .route("/admin/rmp/candidates", get(admin::rmp::list_candidates))
+        .route(
+            "/admin/rmp/candidates/accept",
+            post(admin::rmp::accept_candidates),
+        )
+        .route(
+            "/admin/rmp/candidates/reject",
+            post(admin::rmp::reject_candidates),
+        )
+        .route(
+            "/admin/rmp/decisions",
+            get(admin::rmp::export_decisions).post(admin::rmp::import_decisions),
+        )
         .route("/admin/scraper/stats", get(admin::scraper::scraper_stats))
         .route(
             "/admin/scraper/timeseries",
@@ -136,8 +273,26 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
             "/admin/scraper/subjects/{subject}",
             get(admin::scraper::scraper_subject_detail),
         )
+        .route(
+            "/admin/scraper/subjects/{subject}/scrape",
+            post(admin::scraper::enqueue_subject_scrape),
+        )
+        .route("/admin/syncs", get(admin::syncs::list_syncs))
+        .route("/admin/syncs/{kind}/run", post(admin::syncs::run_sync))
         .route("/admin/bluebook/sync", post(admin::bluebook::sync_bluebook))
         .route("/admin/bluebook/links", get(admin::bluebook::list_links))
+        .route(
+            "/admin/bluebook/links/bulk-approve",
+            post(admin::bluebook::bulk_approve_links),
+        )
+        .route(
+            "/admin/bluebook/links/bulk-reject",
+            post(admin::bluebook::bulk_reject_links),
+        )
+        .route(
+            "/admin/bluebook/links/unmatched",
+            get(admin::bluebook::list_unmatched_links),
+        )
         .route("/admin/bluebook/links/{id}", get(admin::bluebook::get_link))
         .route(
             "/admin/bluebook/links/{id}/approve",
@@ -152,6 +307,68 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
             post(admin::bluebook::assign_link),
         )
         .route("/admin/bluebook/match", post(admin::bluebook::run_matching))
+        .route(
+            "/admin/scores/recompute",
+            post(admin::scores::recompute_scores),
+        )
+        .route("/admin/feedback", get(admin::feedback::list_feedback))
+        .route(
+            "/admin/feedback/{id}/status",
+            post(admin::feedback::update_feedback_status),
+        )
+        .route(
+            "/admin/ip-bans",
+            get(admin::ip_bans::list_ip_bans).post(admin::ip_bans::create_ip_ban),
+        )
+        .route("/admin/ip-bans/{id}", delete(admin::ip_bans::delete_ip_ban))
+        .route(
+            "/admin/webhooks",
+            get(admin::webhooks::list_webhooks).post(admin::webhooks::create_webhook),
+        )
+        .route(
+            "/admin/webhooks/{id}",
+            put(admin::webhooks::update_webhook).delete(admin::webhooks::delete_webhook),
+        )
+        .route(
+            "/admin/webhooks/{id}/deliveries",
+            get(admin::webhooks::list_webhook_deliveries),
+        )
+        .route(
+            "/admin/crawler",
+            get(admin::crawler::get_crawler_config).put(admin::crawler::update_crawler_config),
+        )
+        .route(
+            "/admin/nicknames",
+            get(admin::nicknames::list_nicknames).post(admin::nicknames::create_nickname_group),
+        )
+        .route(
+            "/admin/nicknames/{id}",
+            put(admin::nicknames::update_nickname_group)
+                .delete(admin::nicknames::delete_nickname_group),
+        )
+        .route(
+            "/admin/subject-aliases",
+            get(admin::subject_aliases::list_subject_aliases),
+        )
+        .route(
+            "/admin/subject-aliases/{alias}",
+            put(admin::subject_aliases::put_subject_alias)
+                .delete(admin::subject_aliases::delete_subject_alias),
+        )
+        .route(
+            "/admin/bot/commands",
+            get(admin::bot::command_registrations),
+        )
+        .route(
+            "/admin/maintenance",
+            get(admin::maintenance::get_maintenance).put(admin::maintenance::update_maintenance),
+        )
+        .route(
+            "/admin/debug-captures",
+            get(admin::debug_capture::list_captures)
+                .post(admin::debug_capture::start_capture)
+                .delete(admin::debug_capture::stop_capture),
+        )
         .route("/admin/terms", get(admin::terms::list_terms))
         .route("/admin/terms/sync", post(admin::terms::sync_terms))
         .route(
@@ -162,6 +379,11 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
             "/admin/terms/{code}/disable",
             post(admin::terms::disable_term),
         )
+        .route(
+            "/admin/terms/{code}/registration-windows",
+            get(admin::terms::get_registration_windows)
+                .put(admin::terms::update_registration_windows),
+        )
         .layer(axum::middleware::map_response(
             |mut resp: Response| async move {
                 resp.headers_mut().insert(
@@ -171,11 +393,27 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
                 resp
             },
         ))
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            record_admin_action,
+        ))
         .with_state(app_state.clone());
 
-    use crate::web::sitemap;
+    use crate::web::{feeds, sitemap};
 
     let rate_limit_state = app_state.rate_limit.clone();
+    let session_cache = app_state.session_cache.clone();
+    let api_key_cache = app_state.api_key_cache.clone();
+    let proxy_trust = app_state.proxy_trust.clone();
+    let crawler_policy = app_state.crawler.clone();
+    let ip_filter = app_state.ip_filter.clone();
+    let maintenance = app_state.maintenance.clone();
+    let debug_captures = app_state.debug_captures.clone();
+    let metrics = app_state.metrics.clone();
+    let cookie_secure = auth_config
+        .redirect_base
+        .as_deref()
+        .map(|base| base.starts_with("https://"));
 
     let router = Router::new()
         .route("/robots.txt", get(robots_txt))
@@ -187,6 +425,13 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
         )
         .route("/sitemap-courses-{rest}", get(sitemap::sitemap_courses))
         .route("/sitemap-subjects.xml", get(sitemap::sitemap_subjects))
+        .route("/feeds/changes.atom", get(feeds::changes_feed))
+        .route("/feeds/changes/{rest}", get(feeds::term_changes_feed))
+        .route(
+            "/feeds/changes/{term}/{rest}",
+            get(feeds::subject_changes_feed),
+        )
+        .route("/embed/instructors/{slug}", get(embed::instructor_widget))
         .nest("/api", api_router)
         .nest("/api", auth_router)
         .nest("/api", admin_router)
@@ -194,8 +439,13 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
         .with_state(app_state);
 
     router.layer((
-        // Outermost: per-request ID span + severity-proportional response logging.
+        // Outermost: resolve the client IP once, honoring forwarding headers
+        // only from trusted proxies.
+        ClientIpLayer::new(proxy_trust),
+        // Per-request ID span + severity-proportional response logging.
         RequestIdLayer,
+        // Request counts and latency by route group, including rejections.
+        MetricsLayer::new(metrics.clone()),
         // Security headers on every response (HSTS is prod-only).
         SecurityHeadersLayer,
         // Compress API responses (gzip/brotli/zstd). Pre-compressed static
@@ -205,10 +455,30 @@ pub fn create_router(app_state: AppState, auth_config: AuthConfig) -> Router {
             .br(true)
             .gzip(true)
             .quality(tower_http::CompressionLevel::Fastest),
-        // Per-IP rate limiting (burst + sustained + long-term, multi-layer).
-        // Inside compression so 429 responses get compressed too.
-        RateLimitLayer::new(rate_limit_state),
+        // Blocked ranges and admin bans get a 403 before spending any rate
+        // limit budget; trusted ranges are tagged to skip rate limiting.
+        IpFilterLayer::new(ip_filter),
+        // Per-IP (or per-API-key) rate limiting (burst + sustained +
+        // long-term, multi-layer). Inside compression so 429 responses get
+        // compressed too.
+        RateLimitLayer::new(
+            rate_limit_state,
+            session_cache.clone(),
+            api_key_cache,
+            metrics.clone(),
+        ),
+        // Blocklisted user agents get a 403 before reaching any handler.
+        BotBlockLayer::new(crawler_policy),
+        // Read-only public API while maintenance mode is on.
+        MaintenanceLayer::new(maintenance),
+        // Extends sessions past their half-life and refreshes the cookie.
+        SessionRenewalLayer::new(session_cache, cookie_secure),
+        // Hot public GETs served from memory with ETags. Inside session
+        // renewal so stored responses never carry a user's cookie.
+        ResponseCacheLayer::new(metrics),
         TimeoutLayer::new(Duration::from_secs(60)),
+        // Innermost, so recorded responses are the handler's uncompressed output.
+        DebugCaptureLayer::new(debug_captures),
     ))
 }
 
@@ -263,17 +533,13 @@ async fn ssr_fallback(
 
 /// `GET /robots.txt`
 ///
-/// Blocks crawlers from API and admin paths. Includes sitemap directive when
-/// `PUBLIC_ORIGIN` is configured.
+/// Rendered from the admin-editable [`CrawlerConfig`](crate::web::crawler::CrawlerConfig).
+/// Includes sitemap directive when `PUBLIC_ORIGIN` is configured.
 async fn robots_txt(State(state): State<AppState>) -> Response {
-    let mut body = String::from(
-        "User-agent: *\n\
-         Disallow: /api/\n\
-         Disallow: /admin/\n",
-    );
-    if let Some(ref origin) = state.public_origin {
-        body.push_str(&format!("\nSitemap: {origin}/sitemap.xml\n"));
-    }
+    let body = state
+        .crawler
+        .config()
+        .render_robots(state.public_origin.as_deref());
     let mut resp = body.into_response();
     resp.headers_mut().insert(
         axum::http::header::CONTENT_TYPE,
@@ -281,7 +547,7 @@ async fn robots_txt(State(state): State<AppState>) -> Response {
     );
     resp.headers_mut().insert(
         axum::http::header::CACHE_CONTROL,
-        HeaderValue::from_static("public, max-age=86400"),
+        HeaderValue::from_static("public, max-age=3600"),
     );
     resp
 }
@@ -0,0 +1,220 @@
+//! Signed-in users' saved course searches.
+//!
+//! A saved search keeps the parameters of a course search so it can be
+//! re-opened later. With `notify` set, the notification service re-runs it
+//! after each scrape of a subject it filters to and DMs the owner any
+//! sections that have started matching.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::{info, instrument, warn};
+use ts_rs::TS;
+
+use crate::banner::models::terms::Term;
+use crate::data::courses::{self, SearchResults};
+use crate::data::models::Course;
+use crate::data::saved_searches::{self, MAX_NOTIFY_MATCHES, SavedSearch};
+use crate::data::search::SearchWeights;
+use crate::state::AppState;
+use crate::web::auth::extractors::AuthUser;
+use crate::web::courses::SearchParams;
+use crate::web::error::{ApiError, db_error};
+use crate::web::watchlist::require_discord_dm;
+
+/// Most searches one user may save.
+const MAX_SAVED_SEARCHES_PER_USER: i64 = 25;
+
+const MAX_NAME_LEN: usize = 100;
+
+/// One of the caller's saved searches.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SavedSearchResponse {
+    pub id: i32,
+    pub name: String,
+    /// Course search parameters; `term` is always a term code.
+    pub filters: SearchParams,
+    /// Whether new matches are sent as a Discord DM.
+    pub notify: bool,
+    pub created_at: DateTime<Utc>,
+    /// When new matches were last sent.
+    pub notified_at: Option<DateTime<Utc>>,
+}
+
+/// Body for `POST /api/me/searches`.
+#[derive(Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CreateSavedSearchBody {
+    pub name: String,
+    /// Course search parameters; paging fields are ignored.
+    pub filters: SearchParams,
+    /// DM new matches after each scrape. Only searches filtered by subject
+    /// and matching at most 200 sections may notify.
+    #[serde(default)]
+    pub notify: bool,
+}
+
+impl SavedSearchResponse {
+    /// Build from a stored row, skipping rows whose filters no longer parse.
+    fn from_row(row: SavedSearch) -> Option<Self> {
+        let filters = match serde_json::from_value(row.filters.0) {
+            Ok(filters) => filters,
+            Err(e) => {
+                warn!(id = row.id, error = %e, "Saved search filters failed to parse");
+                return None;
+            }
+        };
+        Some(Self {
+            id: row.id,
+            name: row.name,
+            filters,
+            notify: row.notify,
+            created_at: row.created_at,
+            notified_at: row.notified_at,
+        })
+    }
+}
+
+/// Every section matching `params` in `term_code`, or `None` when there are
+/// more than [`MAX_NOTIFY_MATCHES`].
+pub async fn notify_matches(
+    pool: &PgPool,
+    term_code: &str,
+    params: &SearchParams,
+) -> anyhow::Result<Option<Vec<Course>>> {
+    let codes = params.filter_codes();
+    // Weights only order results, and every match is fetched.
+    let filter = params.to_filter(term_code, &codes, SearchWeights::default());
+    let SearchResults { courses, total, .. } =
+        courses::search_courses(pool, &filter, MAX_NOTIFY_MATCHES, 0, None, None).await?;
+    Ok((total <= i64::from(MAX_NOTIFY_MATCHES)).then_some(courses))
+}
+
+/// `GET /api/me/searches` -- The caller's saved searches, newest first.
+#[instrument(skip_all)]
+pub async fn list_saved_searches(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<Vec<SavedSearchResponse>>, ApiError> {
+    let rows = saved_searches::list_saved_searches(&state.db_pool, user.id)
+        .await
+        .map_err(|e| db_error("List saved searches", e))?;
+    Ok(Json(
+        rows.into_iter()
+            .filter_map(SavedSearchResponse::from_row)
+            .collect(),
+    ))
+}
+
+/// `POST /api/me/searches` -- Save a search.
+#[instrument(skip_all)]
+pub async fn create_saved_search(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<CreateSavedSearchBody>,
+) -> Result<(StatusCode, Json<SavedSearchResponse>), ApiError> {
+    let name = body.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(ApiError::bad_request(format!(
+            "Name must be 1 to {MAX_NAME_LEN} characters"
+        )));
+    }
+
+    if body.notify {
+        require_discord_dm(&state, user.id).await?;
+    }
+
+    let mut params = body.filters;
+    let term_code =
+        Term::resolve_to_code(&params.term).ok_or_else(|| ApiError::invalid_term(&params.term))?;
+    params.term = term_code.clone();
+    params.offset = 0;
+    params.cursor = None;
+    if body.notify && params.subject.is_empty() {
+        return Err(ApiError::bad_request(
+            "Searches need a subject filter to notify",
+        ));
+    }
+
+    let saved = saved_searches::count_saved_searches(&state.db_pool, user.id)
+        .await
+        .map_err(|e| db_error("Count saved searches", e))?;
+    if saved >= MAX_SAVED_SEARCHES_PER_USER {
+        return Err(ApiError::conflict(format!(
+            "You can save at most {MAX_SAVED_SEARCHES_PER_USER} searches"
+        )));
+    }
+
+    // Sections matching now are the baseline; only later ones are news.
+    let baseline: Vec<i32> = if body.notify {
+        notify_matches(&state.db_pool, &term_code, &params)
+            .await
+            .map_err(|e| db_error("Saved search baseline", e))?
+            .ok_or_else(|| {
+                ApiError::bad_request(format!(
+                    "Searches matching more than {MAX_NOTIFY_MATCHES} sections can't notify; narrow the filters first"
+                ))
+            })?
+            .iter()
+            .map(|c| c.id)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let filters = serde_json::to_value(&params)
+        .map_err(|e| ApiError::internal_error(format!("Failed to encode filters: {e}")))?;
+    let row = saved_searches::create_saved_search(
+        &state.db_pool,
+        user.id,
+        name,
+        &term_code,
+        &filters,
+        body.notify,
+        &baseline,
+    )
+    .await
+    .map_err(|e| db_error("Create saved search", e))?;
+
+    info!(
+        user_id = user.id,
+        id = row.id,
+        term = %term_code,
+        notify = body.notify,
+        "Saved search created"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SavedSearchResponse {
+            id: row.id,
+            name: row.name,
+            filters: params,
+            notify: row.notify,
+            created_at: row.created_at,
+            notified_at: row.notified_at,
+        }),
+    ))
+}
+
+/// `DELETE /api/me/searches/{id}` -- Delete a saved search.
+#[instrument(skip_all)]
+pub async fn delete_saved_search(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    let removed = saved_searches::delete_saved_search(&state.db_pool, user.id, id)
+        .await
+        .map_err(|e| db_error("Delete saved search", e))?;
+    if !removed {
+        return Err(ApiError::not_found("Saved search not found"));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,8 +1,8 @@
 //! ISR-style schedule cache for timeline enrollment queries.
 //!
 //! Loads all courses with their pre-extracted meeting scalars from the
-//! `course_meetings` table into a compact in-memory representation, and caches
-//! the result. The cache is refreshed in the background every hour using a
+//! `course_meetings` table into a compact in-memory representation, grouped
+//! into one [`TermSchedule`] per term, and caches the result. The cache is refreshed in the background every hour using a
 //! stale-while-revalidate pattern with singleflight deduplication -- readers
 //! always get the current cached value instantly, never blocking on a refresh.
 //!
@@ -13,14 +13,15 @@
 //!   runtime JSONB parsing entirely.
 //! - **Streaming**: Rows are processed one at a time via `sqlx::fetch()`,
 //!   never materializing an intermediate `Vec<ScheduleRow>`.
-//! - **Subject interning**: Subjects are stored as `Arc<str>` and deduplicated
-//!   via a `HashSet`, eliminating per-request cloning in the timeline hot path.
+//! - **String interning**: Subjects, course levels, campuses, and
+//!   building/room codes are stored as `Arc<str>` and deduplicated via a
+//!   `HashSet`, eliminating per-request cloning in the timeline hot path.
 
 use crate::utils::fmt_duration;
 use chrono::NaiveDate;
 use futures::TryStreamExt;
 use sqlx::PgPool;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::watch;
@@ -28,36 +29,94 @@ use tracing::{debug, error, info};
 
 const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
 
+/// Level or campus key for courses that don't have one.
+pub(crate) const UNKNOWN_GROUP: &str = "unknown";
+
+/// Where a meeting takes place, e.g. building `NPB`, room `1.238`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct RoomKey {
+    pub(crate) building: Arc<str>,
+    pub(crate) room: Arc<str>,
+}
+
 /// A single meeting time block, pre-parsed for fast filtering.
 #[derive(Debug, Clone)]
 pub(crate) struct ParsedSchedule {
     /// Bitmask of days: bit 0 = Monday, bit 6 = Sunday.
-    days: u8,
+    pub(crate) days: u8,
     /// Minutes since midnight for start (e.g. 600 = 10:00).
-    begin_minutes: u16,
+    pub(crate) begin_minutes: u16,
     /// Minutes since midnight for end (e.g. 650 = 10:50).
-    end_minutes: u16,
+    pub(crate) end_minutes: u16,
     /// First day the meeting pattern is active.
-    start_date: NaiveDate,
+    pub(crate) start_date: NaiveDate,
     /// Last day the meeting pattern is active.
-    end_date: NaiveDate,
+    pub(crate) end_date: NaiveDate,
+    /// `None` for online meetings and rooms Banner hasn't assigned.
+    pub(crate) room: Option<RoomKey>,
 }
 
 /// A course with its enrollment and pre-parsed schedule blocks.
 #[derive(Debug, Clone)]
 pub(crate) struct CachedCourse {
+    /// `courses.id`, for looking up details the cache doesn't hold.
+    pub(crate) id: i32,
     pub(crate) subject: Arc<str>,
+    /// Course level from the first digit of the course number, e.g. `2000`.
+    pub(crate) level: Arc<str>,
+    /// Campus code, or [`UNKNOWN_GROUP`].
+    pub(crate) campus: Arc<str>,
     pub(crate) enrollment: i32,
     pub(crate) schedules: Vec<ParsedSchedule>,
 }
 
+/// All scheduled courses in one term.
+#[derive(Debug, Clone)]
+pub(crate) struct TermSchedule {
+    pub(crate) courses: Vec<CachedCourse>,
+    /// Most common meeting start date, i.e. the first day of the regular
+    /// session. `None` if no course has a meeting.
+    pub(crate) start_date: Option<NaiveDate>,
+}
+
+impl TermSchedule {
+    /// Every meeting held in a room, with the course it belongs to. Codes
+    /// are compared case-insensitively.
+    pub(crate) fn room_meetings<'a>(
+        &'a self,
+        building: &'a str,
+        room: &'a str,
+    ) -> impl Iterator<Item = (&'a CachedCourse, &'a ParsedSchedule)> + 'a {
+        self.courses.iter().flat_map(move |course| {
+            course
+                .schedules
+                .iter()
+                .filter(move |schedule| {
+                    schedule.room.as_ref().is_some_and(|key| {
+                        key.building.eq_ignore_ascii_case(building)
+                            && key.room.eq_ignore_ascii_case(room)
+                    })
+                })
+                .map(move |schedule| (course, schedule))
+        })
+    }
+}
+
 /// The immutable snapshot of all courses, swapped atomically on refresh.
 #[derive(Debug, Clone)]
 pub(crate) struct ScheduleSnapshot {
-    pub(crate) courses: Vec<CachedCourse>,
+    /// Term code -> that term's schedule.
+    pub(crate) terms: BTreeMap<String, Arc<TermSchedule>>,
     refreshed_at: std::time::Instant,
 }
 
+impl ScheduleSnapshot {
+    /// Every cached course, across all terms.
+    pub(crate) fn courses(&self) -> impl Iterator<Item = &CachedCourse> + Clone {
+        self.terms.values().flat_map(|term| term.courses.iter())
+    }
+}
+
 /// Shared schedule cache. Clone-cheap (all `Arc`-wrapped internals).
 #[derive(Clone)]
 pub struct ScheduleCache {
@@ -75,7 +134,7 @@ impl ScheduleCache {
     /// Create a new cache with an empty initial snapshot.
     pub(crate) fn new(pool: PgPool) -> Self {
         let empty = Arc::new(ScheduleSnapshot {
-            courses: Vec::new(),
+            terms: BTreeMap::new(),
             refreshed_at: std::time::Instant::now(),
         });
         let (tx, rx) = watch::channel(empty);
@@ -111,7 +170,7 @@ impl ScheduleCache {
         tokio::spawn(async move {
             match load_snapshot(&cache.pool).await {
                 Ok(snap) => {
-                    let count = snap.courses.len();
+                    let count = snap.courses().count();
                     let _ = cache.tx.send(Arc::new(snap));
                     info!(courses = count, "Schedule cache refreshed");
                 }
@@ -126,7 +185,7 @@ impl ScheduleCache {
     /// Force an initial load (blocking). Call once at startup.
     pub(crate) async fn load(&self) -> anyhow::Result<()> {
         let snap = load_snapshot(&self.pool).await?;
-        let count = snap.courses.len();
+        let count = snap.courses().count();
         let _ = self.tx.send(Arc::new(snap));
         info!(courses = count, "Schedule cache initially loaded");
         Ok(())
@@ -138,13 +197,18 @@ impl ScheduleCache {
 const SCHEDULE_QUERY: &str = r#"
 SELECT
     c.id,
+    c.term_code,
     c.subject,
+    c.course_number,
+    c.campus,
     c.enrollment,
     cm.day_bits,
     cm.begin_minutes,
     cm.end_minutes,
     cm.start_date,
-    cm.end_date
+    cm.end_date,
+    cm.building,
+    cm.room
 FROM courses c
 JOIN course_meetings cm ON cm.course_id = c.id
 ORDER BY c.id
@@ -155,29 +219,40 @@ ORDER BY c.id
 #[derive(sqlx::FromRow)]
 struct MeetingRow {
     id: i32,
+    term_code: String,
     subject: String,
+    course_number: String,
+    campus: Option<String>,
     enrollment: i32,
     day_bits: i16,
     begin_minutes: i16,
     end_minutes: i16,
     start_date: NaiveDate,
     end_date: NaiveDate,
+    building: Option<String>,
+    room: Option<String>,
 }
 
 /// Load all courses from the `course_meetings` table and build a snapshot.
 ///
 /// Rows arrive ordered by course `id`. We accumulate schedules for the
-/// current course and emit a `CachedCourse` when the id changes. Subject
-/// strings are interned into `Arc<str>` for cheap cloning downstream.
+/// current course and emit a `CachedCourse` into its term when the id
+/// changes. Subject, level, campus, building, and room strings are interned
+/// into `Arc<str>` for cheap cloning downstream.
 async fn load_snapshot(pool: &PgPool) -> anyhow::Result<ScheduleSnapshot> {
     let start = std::time::Instant::now();
 
     let mut subject_intern: HashSet<Arc<str>> = HashSet::new();
-    let mut courses: Vec<CachedCourse> = Vec::new();
+    let mut group_intern: HashSet<Arc<str>> = HashSet::new();
+    let mut location_intern: HashSet<Arc<str>> = HashSet::new();
+    let mut terms: BTreeMap<String, Vec<CachedCourse>> = BTreeMap::new();
 
     // Streaming state: accumulate schedules for the current course id.
     let mut current_id: Option<i32> = None;
+    let mut current_term = String::new();
     let mut current_subject: Arc<str> = Arc::from("");
+    let mut current_level: Arc<str> = Arc::from("");
+    let mut current_campus: Arc<str> = Arc::from("");
     let mut current_enrollment: i32 = 0;
     let mut current_schedules: Vec<ParsedSchedule> = Vec::new();
 
@@ -186,16 +261,31 @@ async fn load_snapshot(pool: &PgPool) -> anyhow::Result<ScheduleSnapshot> {
     while let Some(row) = stream.try_next().await? {
         if current_id != Some(row.id) {
             // Emit the previous course (if any).
-            if current_id.is_some() {
-                courses.push(CachedCourse {
-                    subject: Arc::clone(&current_subject),
-                    enrollment: current_enrollment,
-                    schedules: std::mem::take(&mut current_schedules),
-                });
+            if let Some(id) = current_id {
+                terms
+                    .entry(std::mem::take(&mut current_term))
+                    .or_default()
+                    .push(CachedCourse {
+                        id,
+                        subject: Arc::clone(&current_subject),
+                        level: Arc::clone(&current_level),
+                        campus: Arc::clone(&current_campus),
+                        enrollment: current_enrollment,
+                        schedules: std::mem::take(&mut current_schedules),
+                    });
             }
 
             current_id = Some(row.id);
-            current_subject = intern_subject(&mut subject_intern, &row.subject);
+            current_term = row.term_code;
+            current_subject = intern(&mut subject_intern, &row.subject);
+            current_level = intern(&mut group_intern, &course_level(&row.course_number));
+            current_campus = intern(
+                &mut group_intern,
+                row.campus
+                    .as_deref()
+                    .filter(|c| !c.is_empty())
+                    .unwrap_or(UNKNOWN_GROUP),
+            );
             current_enrollment = row.enrollment;
         }
 
@@ -205,33 +295,78 @@ async fn load_snapshot(pool: &PgPool) -> anyhow::Result<ScheduleSnapshot> {
             end_minutes: row.end_minutes as u16,
             start_date: row.start_date,
             end_date: row.end_date,
+            room: row.building.zip(row.room).map(|(building, room)| RoomKey {
+                building: intern(&mut location_intern, &building),
+                room: intern(&mut location_intern, &room),
+            }),
         });
     }
 
     // Emit the last course.
-    if current_id.is_some() {
-        courses.push(CachedCourse {
+    if let Some(id) = current_id {
+        terms.entry(current_term).or_default().push(CachedCourse {
+            id,
             subject: current_subject,
+            level: current_level,
+            campus: current_campus,
             enrollment: current_enrollment,
             schedules: current_schedules,
         });
     }
 
+    let terms: BTreeMap<String, Arc<TermSchedule>> = terms
+        .into_iter()
+        .map(|(code, courses)| {
+            let start_date = session_start(&courses);
+            (
+                code,
+                Arc::new(TermSchedule {
+                    courses,
+                    start_date,
+                }),
+            )
+        })
+        .collect();
+
     debug!(
-        courses = courses.len(),
+        terms = terms.len(),
+        courses = terms.values().map(|t| t.courses.len()).sum::<usize>(),
         subjects = subject_intern.len(),
         elapsed = fmt_duration(start.elapsed()),
         "Schedule snapshot built"
     );
 
     Ok(ScheduleSnapshot {
-        courses,
+        terms,
         refreshed_at: std::time::Instant::now(),
     })
 }
 
-/// Look up or insert a subject in the intern set, returning a cheap `Arc<str>`.
-fn intern_subject(set: &mut HashSet<Arc<str>>, subject: &str) -> Arc<str> {
+/// The most common meeting start date among a term's courses. Ties go to the
+/// earlier date.
+fn session_start(courses: &[CachedCourse]) -> Option<NaiveDate> {
+    let mut counts: HashMap<NaiveDate, usize> = HashMap::new();
+    for schedule in courses.iter().flat_map(|c| c.schedules.iter()) {
+        *counts.entry(schedule.start_date).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(a_date, a_count), (b_date, b_count)| {
+            a_count.cmp(b_count).then(b_date.cmp(a_date))
+        })
+        .map(|(date, _)| date)
+}
+
+/// Course level from the leading digit of a course number: `3443` -> `3000`.
+fn course_level(course_number: &str) -> String {
+    match course_number.chars().next() {
+        Some(digit) if digit.is_ascii_digit() => format!("{digit}000"),
+        _ => UNKNOWN_GROUP.to_owned(),
+    }
+}
+
+/// Look up or insert a string in the intern set, returning a cheap `Arc<str>`.
+fn intern(set: &mut HashSet<Arc<str>>, subject: &str) -> Arc<str> {
     if let Some(existing) = set.get(subject) {
         return Arc::clone(existing);
     }
@@ -278,18 +413,95 @@ mod tests {
     use chrono::NaiveDate;
 
     #[test]
-    fn intern_subject_deduplicates() {
+    fn intern_deduplicates() {
         let mut set = HashSet::new();
-        let a = intern_subject(&mut set, "CS");
-        let b = intern_subject(&mut set, "CS");
+        let a = intern(&mut set, "CS");
+        let b = intern(&mut set, "CS");
         assert!(Arc::ptr_eq(&a, &b));
         assert_eq!(set.len(), 1);
 
-        let c = intern_subject(&mut set, "MAT");
+        let c = intern(&mut set, "MAT");
         assert!(!Arc::ptr_eq(&a, &c));
         assert_eq!(set.len(), 2);
     }
 
+    #[test]
+    fn course_level_uses_the_leading_digit() {
+        assert_eq!(course_level("3443"), "3000");
+        assert_eq!(course_level("1013L"), "1000");
+        assert_eq!(course_level("TBA"), UNKNOWN_GROUP);
+        assert_eq!(course_level(""), UNKNOWN_GROUP);
+    }
+
+    #[test]
+    fn session_start_picks_the_most_common_start_date() {
+        let schedule = |start: NaiveDate| ParsedSchedule {
+            days: 0b0000001,
+            begin_minutes: 600,
+            end_minutes: 650,
+            start_date: start,
+            end_date: NaiveDate::from_ymd_opt(2025, 12, 13).unwrap(),
+            room: None,
+        };
+        let course = |starts: &[NaiveDate]| CachedCourse {
+            id: 1,
+            subject: Arc::from("CS"),
+            level: Arc::from("1000"),
+            campus: Arc::from("MAIN"),
+            enrollment: 10,
+            schedules: starts.iter().copied().map(schedule).collect(),
+        };
+        let regular = NaiveDate::from_ymd_opt(2025, 8, 26).unwrap();
+        let early = NaiveDate::from_ymd_opt(2025, 8, 4).unwrap();
+
+        let courses = vec![
+            course(&[regular, regular]),
+            course(&[early]),
+            course(&[regular]),
+        ];
+        assert_eq!(session_start(&courses), Some(regular));
+        assert_eq!(session_start(&[]), None);
+    }
+
+    #[test]
+    fn room_meetings_match_building_and_room() {
+        let key = |building: &str, room: &str| RoomKey {
+            building: Arc::from(building),
+            room: Arc::from(room),
+        };
+        let schedule = |room: Option<RoomKey>| ParsedSchedule {
+            days: 0b0000001,
+            begin_minutes: 600,
+            end_minutes: 650,
+            start_date: NaiveDate::from_ymd_opt(2025, 8, 26).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2025, 12, 13).unwrap(),
+            room,
+        };
+        let course = |id: i32, schedules: Vec<ParsedSchedule>| CachedCourse {
+            id,
+            subject: Arc::from("CS"),
+            level: Arc::from("1000"),
+            campus: Arc::from("MAIN"),
+            enrollment: 10,
+            schedules,
+        };
+        let term = TermSchedule {
+            courses: vec![
+                course(1, vec![schedule(Some(key("NPB", "1.238"))), schedule(None)]),
+                course(2, vec![schedule(Some(key("NPB", "1.226")))]),
+                course(3, vec![schedule(Some(key("NPB", "1.238")))]),
+            ],
+            start_date: None,
+        };
+
+        let ids: Vec<i32> = term
+            .room_meetings("npb", "1.238")
+            .map(|(course, _)| course.id)
+            .collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(term.room_meetings("NPB", "2.100").count(), 0);
+    }
+
     #[test]
     fn active_during_matching_slot() {
         let sched = ParsedSchedule {
@@ -298,6 +510,7 @@ mod tests {
             end_minutes: 650,
             start_date: NaiveDate::from_ymd_opt(2025, 8, 26).unwrap(),
             end_date: NaiveDate::from_ymd_opt(2025, 12, 13).unwrap(),
+            room: None,
         };
 
         // Monday Sept 1 2025, 10:00-10:15 slot
@@ -313,6 +526,7 @@ mod tests {
             end_minutes: 650,
             start_date: NaiveDate::from_ymd_opt(2025, 8, 26).unwrap(),
             end_date: NaiveDate::from_ymd_opt(2025, 12, 13).unwrap(),
+            room: None,
         };
 
         // Tuesday Sept 2 2025
@@ -328,6 +542,7 @@ mod tests {
             end_minutes: 650,   // 10:50
             start_date: NaiveDate::from_ymd_opt(2025, 8, 26).unwrap(),
             end_date: NaiveDate::from_ymd_opt(2025, 12, 13).unwrap(),
+            room: None,
         };
 
         let date = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap(); // Monday
@@ -345,6 +560,7 @@ mod tests {
             end_minutes: 650,
             start_date: NaiveDate::from_ymd_opt(2025, 8, 26).unwrap(),
             end_date: NaiveDate::from_ymd_opt(2025, 12, 13).unwrap(),
+            room: None,
         };
 
         // Monday Jan 6 2025 -- before semester
@@ -360,6 +576,7 @@ mod tests {
             end_minutes: 650,
             start_date: NaiveDate::from_ymd_opt(2025, 8, 26).unwrap(),
             end_date: NaiveDate::from_ymd_opt(2025, 12, 13).unwrap(),
+            room: None,
         };
 
         let date = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap();
@@ -3,7 +3,9 @@
 use axum::extract::{Path, Query, State};
 use axum::response::Response;
 use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::banner::models::terms::Term;
 use crate::data;
@@ -12,9 +14,10 @@ use crate::state::AppState;
 use crate::web::courses::{CodeDescription, TermResponse, code_to_filter_value};
 use crate::web::error::{ApiError, ApiErrorCode, db_error};
 use crate::web::routes::{cache, with_cache_control};
+use crate::web::search_options_cache::Lookup;
 
 /// Response for the consolidated search-options endpoint.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct SearchOptionsResponse {
@@ -24,7 +27,7 @@ pub struct SearchOptionsResponse {
     pub ranges: data::courses::FilterRanges,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct SearchOptionsReference {
@@ -34,12 +37,25 @@ pub struct SearchOptionsReference {
     pub attributes: Vec<CodeDescription>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchOptionsParams {
     pub term: Option<String>,
 }
 
 /// `GET /api/reference/:category`
+#[utoipa::path(
+    get,
+    path = "/reference/{category}",
+    tag = "reference",
+    summary = "Codes and descriptions for a reference category",
+    params(
+        ("category" = String, Path, description = "Reference category (e.g. subject, campus, instructional_method)"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = Vec<CodeDescription>),
+    ),
+)]
 pub(super) async fn get_reference(
     State(state): State<AppState>,
     Path(category): Path<String>,
@@ -82,6 +98,18 @@ pub(super) async fn get_reference(
 }
 
 /// `GET /api/search-options?term={slug}` (term optional, defaults to latest)
+#[utoipa::path(
+    get,
+    path = "/search-options",
+    tag = "reference",
+    summary = "Terms and filter options for search",
+    params(
+        SearchOptionsParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = SearchOptionsResponse),
+    ),
+)]
 pub(super) async fn get_search_options(
     State(state): State<AppState>,
     Query(params): Query<SearchOptionsParams>,
@@ -105,21 +133,63 @@ pub(super) async fn get_search_options(
     let term_code =
         Term::resolve_to_code(&term_slug).ok_or_else(|| ApiError::invalid_term(&term_slug))?;
 
-    if let Some(cached) = state.search_options_cache.get(&term_code) {
-        return Ok(with_cache_control((*cached).clone(), cache::REFERENCE));
-    }
+    let response = match state.search_options_cache.lookup(&term_code) {
+        Lookup::Fresh(cached) => {
+            state.metrics.record_cache_lookup("search_options", true);
+            (*cached).clone()
+        }
+        Lookup::Stale(cached) => {
+            state.metrics.record_cache_lookup("search_options", true);
+            if state.search_options_cache.try_claim(&term_code) {
+                tokio::spawn(refresh_search_options(state.clone(), term_code));
+            }
+            (*cached).clone()
+        }
+        Lookup::Missing => {
+            state.metrics.record_cache_lookup("search_options", false);
+            // Best-effort singleflight: if another request is already
+            // building this term, build it too rather than wait.
+            let claimed = state.search_options_cache.try_claim(&term_code);
+            let built = build_search_options(&state, &term_code).await;
+            if claimed {
+                state.search_options_cache.release(&term_code);
+            }
+            let response = built.map_err(|e| db_error("Search options", e))?;
+            state
+                .search_options_cache
+                .insert(term_code, response.clone());
+            response
+        }
+    };
+
+    Ok(with_cache_control(response, cache::REFERENCE))
+}
 
-    if !state.search_options_cache.try_claim(&term_code) {
-        // Another request is building this term's options -- fall through and build it too.
-        // (Acceptable: singleflight is best-effort, not strict.)
+/// Rebuild a stale term's options in the background. The caller holds the
+/// term's singleflight claim.
+async fn refresh_search_options(state: AppState, term_code: String) {
+    match build_search_options(&state, &term_code).await {
+        Ok(response) => {
+            state
+                .search_options_cache
+                .insert(term_code.clone(), response);
+            debug!(term = term_code, "Refreshed search options");
+        }
+        Err(e) => warn!(term = term_code, error = ?e, "Failed to refresh search options"),
     }
+    state.search_options_cache.release(&term_code);
+}
 
+/// Assemble search options for a term from the database and reference cache.
+async fn build_search_options(
+    state: &AppState,
+    term_code: &str,
+) -> anyhow::Result<SearchOptionsResponse> {
     let (all_terms, subject_rows, ranges) = tokio::try_join!(
         data::terms::get_all_terms(&state.db_pool),
-        data::courses::get_subjects_by_enrollment(&state.db_pool, &term_code),
-        data::courses::get_filter_ranges(&state.db_pool, &term_code),
-    )
-    .map_err(|e| db_error("Search options", e))?;
+        data::courses::get_subjects_by_enrollment(&state.db_pool, term_code),
+        data::courses::get_filter_ranges(&state.db_pool, term_code),
+    )?;
 
     let terms: Vec<TermResponse> = all_terms
         .into_iter()
@@ -168,17 +238,10 @@ pub(super) async fn get_search_options(
         attributes: build_ref("attribute"),
     };
 
-    let response = SearchOptionsResponse {
+    Ok(SearchOptionsResponse {
         terms,
         subjects,
         reference,
         ranges,
-    };
-
-    state
-        .search_options_cache
-        .insert(term_code.clone(), response.clone());
-    state.search_options_cache.release(&term_code);
-
-    Ok(with_cache_control(response, cache::REFERENCE))
+    })
 }
@@ -1,8 +1,16 @@
 //! TTL cache for search-options responses, one snapshot per term.
 //!
 //! Stores typed `Arc<SearchOptionsResponse>` -- no JSON round-trip on reads.
-//! Singleflight per term key prevents thundering-herd on cache miss.
+//!
+//! Each term ages independently. Current and upcoming terms go stale after
+//! [`ACTIVE_TTL`] since scrapes keep changing their subjects and ranges; past
+//! terms hardly change and keep for [`PAST_TTL`]. A stale entry is still
+//! served while one background refresh per term rebuilds it, so only a term
+//! that was never requested (or not for [`MAX_STALE`]) pays for a rebuild
+//! inline. Nothing is refreshed on a timer: past terms nobody asks for are
+//! never recomputed.
 
+use crate::banner::models::terms::Term;
 use crate::web::search_options::SearchOptionsResponse;
 use dashmap::DashMap;
 use std::sync::Arc;
@@ -10,7 +18,32 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use tracing::debug;
 
-const TTL: Duration = Duration::from_secs(10 * 60);
+/// Freshness window for the current and future terms.
+const ACTIVE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Freshness window for terms that have ended.
+const PAST_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Entries older than this are rebuilt inline instead of served stale.
+const MAX_STALE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Result of a cache lookup.
+pub(crate) enum Lookup {
+    Fresh(Arc<SearchOptionsResponse>),
+    /// Past its TTL but servable; the caller should trigger a refresh.
+    Stale(Arc<SearchOptionsResponse>),
+    Missing,
+}
+
+/// How long `term_code`'s options stay fresh, relative to `current_code`.
+fn ttl_for(term_code: &str, current_code: &str) -> Duration {
+    // Codes are YYYYSS, so string order is chronological.
+    if term_code < current_code {
+        PAST_TTL
+    } else {
+        ACTIVE_TTL
+    }
+}
 
 #[derive(Clone, Default)]
 pub struct SearchOptionsCache {
@@ -25,14 +58,24 @@ impl SearchOptionsCache {
         Self::default()
     }
 
-    /// Return a cached entry if it exists and is fresh.
-    pub(crate) fn get(&self, term_code: &str) -> Option<Arc<SearchOptionsResponse>> {
-        let entry = self.entries.get(term_code)?;
+    /// Look up a term's entry, classifying it by age.
+    pub(crate) fn lookup(&self, term_code: &str) -> Lookup {
+        let current = Term::get_current().inner().to_string();
+        self.lookup_at(term_code, ttl_for(term_code, &current))
+    }
+
+    fn lookup_at(&self, term_code: &str, ttl: Duration) -> Lookup {
+        let Some(entry) = self.entries.get(term_code) else {
+            return Lookup::Missing;
+        };
         let (cached_at, ref value) = *entry;
-        if cached_at.elapsed() < TTL {
-            Some(value.clone())
+        let age = cached_at.elapsed();
+        if age < ttl {
+            Lookup::Fresh(value.clone())
+        } else if age < MAX_STALE {
+            Lookup::Stale(value.clone())
         } else {
-            None
+            Lookup::Missing
         }
     }
 
@@ -62,3 +105,85 @@ impl SearchOptionsCache {
         debug!(term = term_code, "search-options cache slot released");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::courses::FilterRanges;
+    use crate::web::search_options::SearchOptionsReference;
+
+    fn response() -> SearchOptionsResponse {
+        SearchOptionsResponse {
+            terms: Vec::new(),
+            subjects: Vec::new(),
+            reference: SearchOptionsReference {
+                instructional_methods: Vec::new(),
+                campuses: Vec::new(),
+                parts_of_term: Vec::new(),
+                attributes: Vec::new(),
+            },
+            ranges: FilterRanges {
+                course_number_min: 1000,
+                course_number_max: 4999,
+                credit_hour_min: 0.0,
+                credit_hour_max: 6.0,
+                wait_count_max: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn past_terms_keep_longer() {
+        assert_eq!(ttl_for("202510", "202620"), PAST_TTL);
+        assert_eq!(ttl_for("202620", "202620"), ACTIVE_TTL);
+        assert_eq!(ttl_for("202710", "202620"), ACTIVE_TTL);
+    }
+
+    #[test]
+    fn entries_age_from_fresh_to_stale_to_missing() {
+        let cache = SearchOptionsCache::new();
+        assert!(matches!(
+            cache.lookup_at("202620", ACTIVE_TTL),
+            Lookup::Missing
+        ));
+
+        cache.insert("202620".to_owned(), response());
+        assert!(matches!(
+            cache.lookup_at("202620", ACTIVE_TTL),
+            Lookup::Fresh(_)
+        ));
+
+        let value = Arc::new(response());
+        cache.entries.insert(
+            "202620".to_owned(),
+            (Instant::now() - ACTIVE_TTL, value.clone()),
+        );
+        assert!(matches!(
+            cache.lookup_at("202620", ACTIVE_TTL),
+            Lookup::Stale(_)
+        ));
+        // The same age is still fresh for a past term.
+        assert!(matches!(
+            cache.lookup_at("202620", PAST_TTL),
+            Lookup::Fresh(_)
+        ));
+
+        cache
+            .entries
+            .insert("202620".to_owned(), (Instant::now() - MAX_STALE, value));
+        assert!(matches!(
+            cache.lookup_at("202620", PAST_TTL),
+            Lookup::Missing
+        ));
+    }
+
+    #[test]
+    fn claim_is_exclusive_until_released() {
+        let cache = SearchOptionsCache::new();
+        assert!(cache.try_claim("202620"));
+        assert!(!cache.try_claim("202620"));
+        assert!(cache.try_claim("202610"));
+        cache.release("202620");
+        assert!(cache.try_claim("202620"));
+    }
+}
@@ -1,37 +1,180 @@
 //! Health, status, and metrics handlers.
 
 use axum::extract::{Query, State};
-use axum::response::Json;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Json, Response};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use std::collections::BTreeMap;
 use tracing::trace;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
+use crate::data::kv;
+use crate::data::unsigned::DurationMs;
+use crate::metrics::{Counter, Gauge};
+use crate::scraper::syncs::SyncKind;
 use crate::state::{AppState, ServiceStatus};
 use crate::web::error::{ApiError, ApiErrorCode, db_error};
+use crate::web::maintenance::MaintenanceConfig;
+use crate::web::routes::{cache, with_cache_control};
 
 fn default_metrics_limit() -> i32 {
     500
 }
 
-#[derive(Serialize, TS)]
+#[derive(Serialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ServiceInfo {
     name: String,
     status: ServiceStatus,
 }
 
-#[derive(Serialize, TS)]
+#[derive(Serialize, TS, ToSchema)]
 #[ts(export)]
 pub struct StatusResponse {
     status: ServiceStatus,
     version: String,
     commit: String,
     services: BTreeMap<String, ServiceInfo>,
+    /// Present while maintenance mode is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maintenance: Option<MaintenanceConfig>,
+}
+
+/// How current the scraped data for one term is.
+#[derive(Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TermFreshness {
+    pub term: String,
+    pub description: String,
+    /// When any scrape job for this term last succeeded.
+    pub last_scraped_at: Option<String>,
+    pub subjects_total: i32,
+    pub subjects_scraped: i32,
+    /// Percentage (0-100) of subjects scraped within the window; null when
+    /// the term's subject list hasn't been fetched yet.
+    pub coverage_pct: Option<f64>,
+}
+
+/// Scrape recency and churn for one subject.
+#[derive(Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectScrapeStats {
+    pub subject: String,
+    pub description: Option<String>,
+    /// When this subject was last scraped successfully.
+    pub last_scraped_at: Option<String>,
+    /// Successful scrapes within the window.
+    pub scrapes: i32,
+    /// Courses changed by those scrapes.
+    pub courses_changed: i32,
+}
+
+#[derive(Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ScrapingStatsResponse {
+    pub term: String,
+    pub subjects: Vec<SubjectScrapeStats>,
+    /// Window for `scrapes` and `coursesChanged`, in hours.
+    pub window_hours: i32,
+    pub timestamp: String,
+}
+
+#[derive(Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FreshnessResponse {
+    pub terms: Vec<TermFreshness>,
+    /// Coverage window, in hours.
+    pub window_hours: i32,
+    pub timestamp: String,
+}
+
+/// How current a piece of scraped or synced data is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, TS, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum DataFreshness {
+    /// Never scraped or synced, so nothing to judge.
+    Unknown,
+    Fresh,
+    /// Older than usual; a run or two was likely missed.
+    Stale,
+    /// Well past its schedule; the scraper or sync is probably down.
+    Outdated,
+}
+
+#[derive(Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DatabaseHealth {
+    pub healthy: bool,
+    /// Round trip of a trivial query; null when it failed.
+    #[schema(value_type = Option<u32>)]
+    pub latency_ms: Option<DurationMs>,
+}
+
+#[derive(Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SubjectScrapeStatus {
+    pub subject: String,
+    pub description: Option<String>,
+    pub last_scraped_at: Option<String>,
+    pub freshness: DataFreshness,
 }
 
-#[derive(Serialize, TS)]
+#[derive(Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TermScrapeStatus {
+    pub term: String,
+    pub description: String,
+    /// When any scrape job for this term last succeeded.
+    pub last_scraped_at: Option<String>,
+    pub freshness: DataFreshness,
+    pub subjects: Vec<SubjectScrapeStatus>,
+}
+
+/// Age of an external-source sync, from the scheduler's success timestamps.
+#[derive(Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SourceSyncStatus {
+    pub kind: SyncKind,
+    pub label: String,
+    pub last_success_at: Option<String>,
+    #[ts(type = "number | null")]
+    pub age_secs: Option<u64>,
+    pub freshness: DataFreshness,
+}
+
+/// Response for `GET /api/status/full`: everything a public status page shows.
+#[derive(Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FullStatusResponse {
+    pub status: ServiceStatus,
+    pub version: String,
+    pub commit: String,
+    pub services: BTreeMap<String, ServiceInfo>,
+    /// Present while maintenance mode is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintenance: Option<MaintenanceConfig>,
+    pub database: DatabaseHealth,
+    /// Worst freshness across scrape-enabled terms and external syncs.
+    pub freshness: DataFreshness,
+    pub terms: Vec<TermScrapeStatus>,
+    pub syncs: Vec<SourceSyncStatus>,
+    pub timestamp: String,
+}
+
+#[derive(Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct MetricEntry {
@@ -43,7 +186,7 @@ pub struct MetricEntry {
     pub seats_available: i32,
 }
 
-#[derive(Serialize, TS)]
+#[derive(Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct MetricsResponse {
@@ -52,9 +195,10 @@ pub struct MetricsResponse {
     pub timestamp: String,
 }
 
-#[derive(Deserialize, Serialize, TS)]
+#[derive(Deserialize, Serialize, TS, IntoParams)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
+#[into_params(parameter_in = Query)]
 pub struct MetricsParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub course_id: Option<i32>,
@@ -69,6 +213,15 @@ pub struct MetricsParams {
 }
 
 /// Health check endpoint
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "status",
+    summary = "Liveness check",
+    responses(
+        (status = 200, description = "`status` and `timestamp`", body = Object),
+    ),
+)]
 pub(super) async fn health() -> Json<Value> {
     trace!("health check requested");
     Json(json!({
@@ -77,8 +230,8 @@ pub(super) async fn health() -> Json<Value> {
     }))
 }
 
-/// Status endpoint showing bot and system status
-pub(super) async fn status(State(state): State<AppState>) -> Json<StatusResponse> {
+/// Per-service statuses and the overall status derived from them.
+fn service_summary(state: &AppState) -> (ServiceStatus, BTreeMap<String, ServiceInfo>) {
     let mut services = BTreeMap::new();
 
     for (name, svc_status) in state.service_statuses.all() {
@@ -108,15 +261,325 @@ pub(super) async fn status(State(state): State<AppState>) -> Json<StatusResponse
         ServiceStatus::Active
     };
 
+    (overall_status, services)
+}
+
+/// Status endpoint showing bot and system status
+#[utoipa::path(
+    get,
+    path = "/status",
+    tag = "status",
+    summary = "Service status",
+    responses(
+        (status = 200, description = "Success", body = StatusResponse),
+    ),
+)]
+pub(super) async fn status(State(state): State<AppState>) -> Json<StatusResponse> {
+    let (status, services) = service_summary(&state);
+
     Json(StatusResponse {
-        status: overall_status,
+        status,
         version: env!("CARGO_PKG_VERSION").to_string(),
         commit: env!("GIT_COMMIT_HASH").to_string(),
         services,
+        maintenance: state.maintenance.active(),
     })
 }
 
+/// Subjects scraped within this many hours count toward a term's coverage.
+const FRESHNESS_WINDOW_HOURS: i32 = 24;
+
+/// `GET /api/freshness` -- Last successful scrape and subject coverage per enabled term.
+#[utoipa::path(
+    get,
+    path = "/freshness",
+    tag = "status",
+    summary = "When each term was last scraped",
+    responses(
+        (status = 200, description = "Success", body = FreshnessResponse),
+    ),
+)]
+pub(super) async fn freshness(State(state): State<AppState>) -> Result<Response, ApiError> {
+    let window = chrono::Duration::hours(i64::from(FRESHNESS_WINDOW_HOURS));
+    let rows = crate::data::terms::get_term_freshness(&state.db_pool, window)
+        .await
+        .map_err(|e| db_error("Term freshness query", e))?;
+
+    let terms = rows
+        .into_iter()
+        .map(|row| TermFreshness {
+            coverage_pct: (row.subjects_total > 0)
+                .then(|| row.subjects_scraped as f64 * 100.0 / row.subjects_total as f64),
+            term: row.code,
+            description: row.description,
+            last_scraped_at: row.last_scraped_at.map(|t| t.to_rfc3339()),
+            subjects_total: row.subjects_total as i32,
+            subjects_scraped: row.subjects_scraped as i32,
+        })
+        .collect();
+
+    Ok(with_cache_control(
+        FreshnessResponse {
+            terms,
+            window_hours: FRESHNESS_WINDOW_HOURS,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        },
+        cache::FRESHNESS,
+    ))
+}
+
+/// `GET /api/stats/scraping` -- Per-subject scrape recency and change counts for the current term.
+///
+/// Cached server-side for as long as the response's `max-age`.
+#[utoipa::path(
+    get,
+    path = "/stats/scraping",
+    tag = "status",
+    summary = "Per-subject scrape activity for the current term",
+    responses(
+        (status = 200, description = "Success", body = ScrapingStatsResponse),
+    ),
+)]
+pub(super) async fn scraping_stats(State(state): State<AppState>) -> Result<Response, ApiError> {
+    use crate::banner::models::terms::Term;
+
+    let term = Term::get_current().inner().to_string();
+    let stats = state
+        .status_cache
+        .scraping
+        .get_or_try_build(term.clone(), || build_scraping_stats(&state, term))
+        .await?;
+    Ok(with_cache_control(&*stats, cache::SCRAPE_STATS))
+}
+
+async fn build_scraping_stats(
+    state: &AppState,
+    term: String,
+) -> Result<ScrapingStatsResponse, ApiError> {
+    let window = chrono::Duration::hours(i64::from(FRESHNESS_WINDOW_HOURS));
+    let rows = crate::data::terms::get_subject_scrape_activity(&state.db_pool, &term, window)
+        .await
+        .map_err(|e| db_error("Subject scrape activity query", e))?;
+
+    let ref_cache = state.reference_cache.read().await;
+    let subjects = rows
+        .into_iter()
+        .map(|row| SubjectScrapeStats {
+            description: ref_cache
+                .lookup("subject", &row.subject_code)
+                .map(|s| s.to_string()),
+            subject: row.subject_code,
+            last_scraped_at: row.last_scraped_at.map(|t| t.to_rfc3339()),
+            scrapes: row.scrapes as i32,
+            courses_changed: row.courses_changed as i32,
+        })
+        .collect();
+    drop(ref_cache);
+
+    Ok(ScrapingStatsResponse {
+        term,
+        subjects,
+        window_hours: FRESHNESS_WINDOW_HOURS,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Age limits for one kind of data: fresh up to `fresh`, stale up to `stale`.
+#[derive(Clone, Copy)]
+struct Thresholds {
+    fresh: chrono::Duration,
+    stale: chrono::Duration,
+}
+
+impl Thresholds {
+    const fn hours(fresh: i64, stale: i64) -> Self {
+        Self {
+            fresh: chrono::Duration::hours(fresh),
+            stale: chrono::Duration::hours(stale),
+        }
+    }
+
+    /// Syncs get one missed run of slack before going stale.
+    fn for_interval(interval: std::time::Duration) -> Self {
+        let scaled =
+            |n: u32| chrono::Duration::from_std(interval * n).unwrap_or(chrono::Duration::MAX);
+        Self {
+            fresh: scaled(2),
+            stale: scaled(4),
+        }
+    }
+
+    fn classify(self, last: Option<DateTime<Utc>>, now: DateTime<Utc>) -> DataFreshness {
+        match last.map(|t| now - t) {
+            None => DataFreshness::Unknown,
+            Some(age) if age <= self.fresh => DataFreshness::Fresh,
+            Some(age) if age <= self.stale => DataFreshness::Stale,
+            Some(_) => DataFreshness::Outdated,
+        }
+    }
+}
+
+/// Some subject of an enabled term finishes every few minutes during the day;
+/// overnight and weekend backoff can stretch that to a few hours.
+const TERM_THRESHOLDS: Thresholds = Thresholds::hours(6, 24);
+/// Quiet subjects back off to a scrape every 16 hours at most.
+const SUBJECT_THRESHOLDS: Thresholds = Thresholds::hours(FRESHNESS_WINDOW_HOURS as i64, 72);
+
+/// Worst of the known classifications; `Unknown` only if none are known.
+fn overall_freshness(items: impl IntoIterator<Item = DataFreshness>) -> DataFreshness {
+    items.into_iter().max().unwrap_or(DataFreshness::Unknown)
+}
+
+async fn database_health(pool: &sqlx::PgPool) -> DatabaseHealth {
+    let started = std::time::Instant::now();
+    let healthy = crate::data::health::ping(pool).await.is_ok();
+    let elapsed = u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX);
+    DatabaseHealth {
+        healthy,
+        latency_ms: healthy.then(|| DurationMs::new(elapsed)),
+    }
+}
+
+/// The database-derived part of `/api/status/full`, cached briefly in
+/// [`StatusCache`](crate::web::status_cache::StatusCache).
+pub struct StatusSnapshot {
+    database: DatabaseHealth,
+    terms: Vec<TermScrapeStatus>,
+    syncs: Vec<SourceSyncStatus>,
+}
+
+async fn status_snapshot(state: &AppState) -> Result<StatusSnapshot, ApiError> {
+    let pool = &state.db_pool;
+    let database = database_health(pool).await;
+    let now = Utc::now();
+
+    let mut terms = Vec::new();
+    let mut syncs = Vec::with_capacity(SyncKind::ALL.len());
+    // Without a database there's nothing to report freshness from; still
+    // answer so the page can show the outage.
+    if database.healthy {
+        let window = chrono::Duration::hours(i64::from(FRESHNESS_WINDOW_HOURS));
+        let rows = crate::data::terms::get_term_freshness(pool, window)
+            .await
+            .map_err(|e| db_error("Term freshness query", e))?;
+
+        let activity =
+            futures::future::try_join_all(rows.iter().map(|row| {
+                crate::data::terms::get_subject_scrape_activity(pool, &row.code, window)
+            }))
+            .await
+            .map_err(|e| db_error("Subject scrape activity query", e))?;
+
+        let ref_cache = state.reference_cache.read().await;
+        for (row, subjects) in rows.into_iter().zip(activity) {
+            let subjects = subjects
+                .into_iter()
+                .map(|s| SubjectScrapeStatus {
+                    description: ref_cache
+                        .lookup("subject", &s.subject_code)
+                        .map(|d| d.to_string()),
+                    subject: s.subject_code,
+                    last_scraped_at: s.last_scraped_at.map(|t| t.to_rfc3339()),
+                    freshness: SUBJECT_THRESHOLDS.classify(s.last_scraped_at, now),
+                })
+                .collect();
+            terms.push(TermScrapeStatus {
+                freshness: TERM_THRESHOLDS.classify(row.last_scraped_at, now),
+                term: row.code,
+                description: row.description,
+                last_scraped_at: row.last_scraped_at.map(|t| t.to_rfc3339()),
+                subjects,
+            });
+        }
+        drop(ref_cache);
+
+        let lasts = futures::future::try_join_all(
+            SyncKind::ALL
+                .iter()
+                .map(|kind| kv::get_timestamp(pool, kind.success_key())),
+        )
+        .await
+        .map_err(|e| db_error("Sync timestamp lookup", e))?;
+        for (kind, last) in SyncKind::ALL.into_iter().zip(lasts) {
+            syncs.push(SourceSyncStatus {
+                kind,
+                label: kind.label().to_owned(),
+                last_success_at: last.map(|t| t.to_rfc3339()),
+                age_secs: last
+                    .and_then(|t| (now - t).to_std().ok())
+                    .map(|d| d.as_secs()),
+                freshness: Thresholds::for_interval(kind.interval()).classify(last, now),
+            });
+        }
+    }
+
+    Ok(StatusSnapshot {
+        database,
+        terms,
+        syncs,
+    })
+}
+
+/// `GET /api/status/full` -- Service status plus database health and the
+/// freshness of scraped terms, subjects, and external syncs.
+///
+/// The database-derived part is cached for a minute, so a burst of status
+/// page loads runs its queries once.
+#[utoipa::path(
+    get,
+    path = "/status/full",
+    tag = "status",
+    summary = "Service status with database health and data freshness",
+    responses(
+        (status = 200, description = "Success", body = FullStatusResponse),
+    ),
+)]
+pub(super) async fn full_status(State(state): State<AppState>) -> Result<Response, ApiError> {
+    let (status, services) = service_summary(&state);
+    let snapshot = state
+        .status_cache
+        .full
+        .get_or_try_build((), || status_snapshot(&state))
+        .await?;
+
+    let freshness = overall_freshness(
+        snapshot
+            .terms
+            .iter()
+            .map(|t| t.freshness)
+            .chain(snapshot.syncs.iter().map(|s| s.freshness)),
+    );
+
+    Ok(with_cache_control(
+        FullStatusResponse {
+            status,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            commit: env!("GIT_COMMIT_HASH").to_string(),
+            services,
+            maintenance: state.maintenance.active(),
+            database: snapshot.database.clone(),
+            freshness,
+            terms: snapshot.terms.clone(),
+            syncs: snapshot.syncs.clone(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+        cache::FRESHNESS,
+    ))
+}
+
 /// Metrics endpoint for monitoring
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "status",
+    summary = "Enrollment metric history",
+    params(
+        MetricsParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = MetricsResponse),
+    ),
+)]
 pub(super) async fn metrics(
     State(state): State<AppState>,
     Query(params): Query<MetricsParams>,
@@ -180,3 +643,126 @@ pub(super) async fn metrics(
         timestamp: chrono::Utc::now().to_rfc3339(),
     }))
 }
+
+/// `GET /api/metrics/prometheus` -- Process counters in Prometheus text format.
+///
+/// Requires `Authorization: Bearer <METRICS_TOKEN>`; 404s when no token is
+/// configured so the endpoint's existence isn't advertised.
+pub(super) async fn prometheus_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Response {
+    if !state.metrics.enabled() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if !state.metrics.authorizes(token) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+        )
+            .into_response();
+    }
+
+    let pool = &state.db_pool;
+    let sessions = state.banner_api.sessions.stats().await;
+    let counters = [
+        Counter {
+            name: "banner_sessions_created_total",
+            help: "Banner sessions created.",
+            value: sessions.created,
+        },
+        Counter {
+            name: "banner_sessions_failed_total",
+            help: "Banner session creations that failed.",
+            value: sessions.failed,
+        },
+        Counter {
+            name: "banner_sessions_reused_total",
+            help: "Banner requests served by an existing pooled session.",
+            value: sessions.reused,
+        },
+    ];
+    let gauges = [
+        Gauge {
+            name: "banner_db_pool_connections",
+            help: "Open database connections.",
+            value: f64::from(pool.size()),
+        },
+        Gauge {
+            name: "banner_db_pool_idle_connections",
+            help: "Idle database connections.",
+            value: pool.num_idle() as f64,
+        },
+        Gauge {
+            name: "banner_db_pool_max_connections",
+            help: "Configured database connection limit.",
+            value: f64::from(pool.options().get_max_connections()),
+        },
+        Gauge {
+            name: "banner_stream_connections",
+            help: "Open stream (WebSocket) connections.",
+            value: state.stream_connections.active() as f64,
+        },
+        Gauge {
+            name: "banner_sessions_idle",
+            help: "Banner sessions waiting in the pool.",
+            value: sessions.idle as f64,
+        },
+        Gauge {
+            name: "banner_sessions_in_use",
+            help: "Banner sessions checked out by a request.",
+            value: sessions.in_use as f64,
+        },
+    ];
+
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/plain; version=0.0.4; charset=utf-8",
+            ),
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        state.metrics.render(&counters, &gauges),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn classifies_by_age() {
+        let t = Thresholds::hours(2, 6);
+        let now = at(12);
+        assert_eq!(t.classify(None, now), DataFreshness::Unknown);
+        assert_eq!(t.classify(Some(at(10)), now), DataFreshness::Fresh);
+        assert_eq!(t.classify(Some(at(7)), now), DataFreshness::Stale);
+        assert_eq!(t.classify(Some(at(5)), now), DataFreshness::Outdated);
+    }
+
+    #[test]
+    fn sync_thresholds_scale_with_interval() {
+        let t = Thresholds::for_interval(std::time::Duration::from_secs(3600));
+        assert_eq!(t.classify(Some(at(10)), at(12)), DataFreshness::Fresh);
+        assert_eq!(t.classify(Some(at(9)), at(12)), DataFreshness::Stale);
+    }
+
+    #[test]
+    fn overall_is_worst_known() {
+        use DataFreshness::*;
+        assert_eq!(overall_freshness([Fresh, Unknown, Stale]), Stale);
+        assert_eq!(overall_freshness([Unknown, Fresh]), Fresh);
+        assert_eq!(overall_freshness([]), Unknown);
+    }
+}
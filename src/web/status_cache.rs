@@ -0,0 +1,183 @@
+//! Short-lived caches for the public status endpoints.
+//!
+//! Each [`TtlSlot`] holds one computed value for one key. A miss takes an
+//! async build lock, so a burst of requests runs the status queries once
+//! instead of once each; while a refresh is underway, everyone else is served
+//! the previous value.
+
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+use crate::web::status::{ScrapingStatsResponse, StatusSnapshot};
+
+/// How long the database-derived part of `/api/status/full` is reused.
+/// Matches the endpoint's `max-age`.
+const FULL_STATUS_TTL: Duration = Duration::from_secs(60);
+
+/// How long `/api/stats/scraping` is reused. Matches the endpoint's `max-age`.
+const SCRAPING_STATS_TTL: Duration = Duration::from_secs(120);
+
+struct Entry<K, T> {
+    key: K,
+    built_at: Instant,
+    value: Arc<T>,
+}
+
+/// A single cached value with a TTL and singleflight rebuilds.
+///
+/// Holds one key at a time; asking for a different key is a miss, and the
+/// new value replaces the old.
+pub struct TtlSlot<K, T> {
+    ttl: Duration,
+    entry: RwLock<Option<Entry<K, T>>>,
+    build: Mutex<()>,
+}
+
+impl<K: PartialEq, T> TtlSlot<K, T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: RwLock::new(None),
+            build: Mutex::new(()),
+        }
+    }
+
+    /// The cached value for `key` and whether it's still within the TTL.
+    fn cached(&self, key: &K) -> Option<(Arc<T>, bool)> {
+        let entry = self.entry.read().unwrap_or_else(|e| e.into_inner());
+        entry
+            .as_ref()
+            .filter(|e| e.key == *key)
+            .map(|e| (e.value.clone(), e.built_at.elapsed() < self.ttl))
+    }
+
+    /// Return the value for `key`, building it if missing or expired.
+    ///
+    /// Only one caller builds at a time. Callers arriving during a rebuild
+    /// get the expired value when there is one, and otherwise wait for the
+    /// build in progress. A failed build caches nothing.
+    pub async fn get_or_try_build<E, Fut>(
+        &self,
+        key: K,
+        build: impl FnOnce() -> Fut,
+    ) -> Result<Arc<T>, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let cached = self.cached(&key);
+        if let Some((value, true)) = &cached {
+            return Ok(value.clone());
+        }
+        let _guard = match (self.build.try_lock(), cached) {
+            (Ok(guard), _) => guard,
+            (Err(_), Some((stale, _))) => return Ok(stale),
+            (Err(_), None) => self.build.lock().await,
+        };
+        // Someone else may have finished a build while we waited.
+        if let Some((value, true)) = self.cached(&key) {
+            return Ok(value);
+        }
+
+        let value = Arc::new(build().await?);
+        *self.entry.write().unwrap_or_else(|e| e.into_inner()) = Some(Entry {
+            key,
+            built_at: Instant::now(),
+            value: value.clone(),
+        });
+        Ok(value)
+    }
+}
+
+/// Caches behind the public status endpoints.
+#[derive(Clone)]
+pub struct StatusCache {
+    /// Database health and freshness for `/api/status/full`.
+    pub full: Arc<TtlSlot<(), StatusSnapshot>>,
+    /// `/api/stats/scraping`, keyed by the current term.
+    pub scraping: Arc<TtlSlot<String, ScrapingStatsResponse>>,
+}
+
+impl StatusCache {
+    pub fn new() -> Self {
+        Self {
+            full: Arc::new(TtlSlot::new(FULL_STATUS_TTL)),
+            scraping: Arc::new(TtlSlot::new(SCRAPING_STATS_TTL)),
+        }
+    }
+}
+
+impl Default for StatusCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn concurrent_misses_build_once() {
+        let slot = Arc::new(TtlSlot::<(), usize>::new(Duration::from_secs(60)));
+        let builds = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let slot = slot.clone();
+                let builds = builds.clone();
+                tokio::spawn(async move {
+                    slot.get_or_try_build((), || async {
+                        builds.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        Ok::<_, ()>(7)
+                    })
+                    .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(*task.await.unwrap().unwrap(), 7);
+        }
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn rebuilds_after_ttl_or_key_change() {
+        let slot = TtlSlot::<&str, usize>::new(Duration::ZERO);
+        let v = slot
+            .get_or_try_build("a", || async { Ok::<_, ()>(1) })
+            .await;
+        assert_eq!(*v.unwrap(), 1);
+        let v = slot
+            .get_or_try_build("a", || async { Ok::<_, ()>(2) })
+            .await;
+        assert_eq!(*v.unwrap(), 2);
+
+        let slot = TtlSlot::<&str, usize>::new(Duration::from_secs(60));
+        slot.get_or_try_build("a", || async { Ok::<_, ()>(1) })
+            .await
+            .unwrap();
+        let v = slot
+            .get_or_try_build("b", || async { Ok::<_, ()>(2) })
+            .await;
+        assert_eq!(*v.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_builds_are_not_cached() {
+        let slot = TtlSlot::<(), usize>::new(Duration::from_secs(60));
+        assert!(
+            slot.get_or_try_build((), || async { Err("down") })
+                .await
+                .is_err()
+        );
+        let v = slot
+            .get_or_try_build((), || async { Ok::<_, &str>(3) })
+            .await;
+        assert_eq!(*v.unwrap(), 3);
+    }
+}
@@ -12,7 +12,7 @@ use tokio::sync::{RwLock, broadcast, mpsc};
 use tokio::time::Instant;
 use tracing::warn;
 
-use crate::data::events::{DomainEvent, EventBuffer};
+use crate::data::events::{DomainEvent, EventBuffer, EventSubscription};
 use crate::data::scraper_stats;
 use crate::state::ReferenceCache;
 use crate::web::admin::scraper::{ScraperStatsResponse, SubjectSummary, TimeseriesPoint};
@@ -119,7 +119,7 @@ async fn run_manager_loop(
 
     let mut cache: HashMap<ComputedCacheKey, CacheEntry> = HashMap::new();
     let mut debounce_deadline: Option<Instant> = None;
-    let (mut cursor, mut head_watch) = events.subscribe();
+    let mut subscription = EventSubscription::new(events.clone());
 
     loop {
         let sleep_future = async {
@@ -161,22 +161,19 @@ async fn run_manager_loop(
                 }
             }
 
-            _ = head_watch.changed() => {
-                // Process new events, mark affected keys stale
-                while let Some(event) = events.read(cursor) {
-                    mark_stale_for_event(&mut cache, &event);
-                    cursor += 1;
-                }
-                // Check if we lagged behind and reset
-                let base = events.base_offset();
-                if cursor < base {
-                    tracing::warn!(cursor, base, "ComputedStreamManager lagged, resetting cursor");
-                    cursor = base;
+            _ = subscription.changed() => {
+                let drained = subscription.drain();
+                if drained.lagged() {
+                    warn!(skipped = drained.skipped, "ComputedStreamManager lagged, marking all keys stale");
                     // Mark all keys stale since we missed events
                     for entry in cache.values_mut() {
                         entry.stale = true;
                     }
                 }
+                // Mark keys affected by the new events stale
+                for event in &drained.events {
+                    mark_stale_for_event(&mut cache, event);
+                }
                 // Reset debounce for any stale keys with subscribers
                 if cache.values().any(|e| e.stale && e.subscribers > 0) {
                     debounce_deadline = Some(Instant::now() + DEBOUNCE_DURATION);
@@ -194,7 +191,7 @@ async fn run_manager_loop(
 
 fn mark_stale_for_event(cache: &mut HashMap<ComputedCacheKey, CacheEntry>, event: &DomainEvent) {
     let DomainEvent::ScrapeJob(scrape_event) = event else {
-        return; // AuditLog events don't affect computed streams
+        return; // Only scrape jobs affect computed streams
     };
 
     match scrape_event {
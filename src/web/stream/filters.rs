@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::banner::models::terms::Term;
 use crate::data::models::{ScrapeJobStatus, ScrapePriority, TargetType};
 use crate::data::scraper_stats::{validate_bucket, validate_period};
 use crate::web::stream::protocol::{StreamError, StreamFilter};
@@ -63,6 +64,16 @@ pub struct ScraperTimeseriesFilter {
     pub term: Option<String>,
 }
 
+/// Identifies one section: `{term}:{crn}`. `term` may be a code or a slug
+/// ("spring-2026"); it is normalized to the code when parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CourseFilter {
+    pub term: String,
+    pub crn: String,
+}
+
 fn default_period() -> String {
     "24h".to_string()
 }
@@ -150,3 +161,22 @@ pub fn parse_scraper_timeseries_filter(
     }
     Ok(f)
 }
+
+pub fn parse_course_filter(filter: Option<StreamFilter>) -> Result<CourseFilter, StreamError> {
+    let f = match filter {
+        Some(StreamFilter::Course(f)) => f,
+        Some(_) => return Err(StreamError::invalid_filter("Invalid course filter")),
+        None => {
+            return Err(StreamError::invalid_filter(
+                "Course subscriptions require a term and CRN",
+            ));
+        }
+    };
+    let crn_valid =
+        !f.crn.is_empty() && f.crn.len() <= 10 && f.crn.bytes().all(|b| b.is_ascii_digit());
+    let term = Term::resolve_to_code(&f.term).filter(|_| crn_valid);
+    let Some(term) = term else {
+        return Err(StreamError::invalid_filter("Invalid term or CRN"));
+    };
+    Ok(CourseFilter { term, crn: f.crn })
+}
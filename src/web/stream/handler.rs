@@ -1,29 +1,63 @@
 //! Stream WebSocket handler.
 
 use axum::{
+    body::Bytes,
     extract::{
         State,
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
     },
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
 use futures::{SinkExt, StreamExt};
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
 use tokio::sync::broadcast::error::RecvError;
+use tokio::time::MissedTickBehavior;
 use tracing::{debug, trace, warn};
 
-use crate::data::events::{AuditLogEvent, DomainEvent};
+use crate::banner::circuit::BannerHealth;
+use crate::data::events::{DomainEvent, EventSubscription};
+use crate::data::rmp_matching::RmpMatchingProgress;
 use crate::data::scraper_stats::{compute_subjects, compute_timeseries, default_bucket_for_period};
 use crate::state::AppState;
 use crate::web::admin::scraper::{ScraperStatsResponse, SubjectSummary, TimeseriesPoint};
-use crate::web::auth::extractors::AdminUser;
+use crate::web::audit::AuditLogEntry;
+use crate::web::auth::extractors::OptionalUser;
+use crate::web::error::ApiError;
+use crate::web::middleware::client_ip::ClientIp;
 use crate::web::stream::computed::{ComputedCacheKey, ComputedUpdate};
 use crate::web::stream::protocol::{
     STREAM_PROTOCOL_VERSION, StreamClientMessage, StreamDelta, StreamError, StreamErrorCode,
     StreamKind, StreamServerMessage, StreamSnapshot,
 };
-use crate::web::stream::streams::{audit_log, scrape_jobs};
+use crate::web::stream::streams::{audit_log, course, scrape_jobs};
 use crate::web::stream::subscriptions::{Subscription, SubscriptionRegistry, build_subscription};
-use crate::web::ws::ScrapeJobEvent;
+use crate::web::ws::{CloseReason, ConnectionClock, ScrapeJobEvent};
+
+/// Course subscriptions allowed per connection; enough for a schedule page,
+/// small enough that one socket can't fan out across a whole term.
+const MAX_COURSE_SUBSCRIPTIONS: usize = 25;
+
+/// Subscribe/modify requests a connection may send back to back. Each one
+/// loads a snapshot, so this covers a full schedule page plus a few filter
+/// changes before [`SNAPSHOT_REFILL`] paces the rest.
+const SNAPSHOT_BURST: NonZeroU32 = NonZeroU32::new(30).unwrap();
+
+/// How often one more snapshot-loading request is allowed after the burst.
+const SNAPSHOT_REFILL: Duration = Duration::from_secs(1);
+
+/// Per-connection limiter on requests that load a snapshot, so a client
+/// can't churn subscribe/unsubscribe into unbounded database queries.
+type SnapshotLimiter = DefaultDirectRateLimiter;
+
+fn snapshot_limiter() -> SnapshotLimiter {
+    RateLimiter::direct(
+        Quota::with_period(SNAPSHOT_REFILL)
+            .expect("non-zero refill period")
+            .allow_burst(SNAPSHOT_BURST),
+    )
+}
 
 /// Outcome of processing a single client WebSocket message.
 enum ClientMessageResult {
@@ -67,12 +101,30 @@ fn subscription_to_cache_key(sub: &Subscription) -> Option<ComputedCacheKey> {
 }
 
 /// WebSocket endpoint for real-time streams.
+///
+/// Anyone may connect and subscribe to [`StreamKind::Course`]; every other
+/// stream requires an admin session. Each connection holds a slot in
+/// [`AppState::stream_connections`] until it closes; clients over the per-IP
+/// or global cap get a 429 before upgrading. Open connections are pinged and
+/// evicted per [`AppState::stream_heartbeat`].
 pub async fn stream_ws(
     ws: WebSocketUpgrade,
-    AdminUser(_user): AdminUser,
+    OptionalUser(user): OptionalUser,
+    ClientIp(ip): ClientIp,
     State(state): State<AppState>,
-) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_stream_ws(socket, state))
+) -> Response {
+    let is_admin = user.is_some_and(|u| u.is_admin);
+    let guard = match state.stream_connections.try_acquire(ip) {
+        Ok(guard) => guard,
+        Err(e) => {
+            warn!(client_ip = %ip, reason = ?e, "Stream connection rejected");
+            return ApiError::from(e).into_response();
+        }
+    };
+    ws.on_upgrade(move |socket| async move {
+        handle_stream_ws(socket, state, is_admin).await;
+        drop(guard);
+    })
 }
 
 async fn send_message(
@@ -99,7 +151,7 @@ async fn send_error(
     send_message(sink, &msg).await
 }
 
-async fn handle_stream_ws(socket: WebSocket, state: AppState) {
+async fn handle_stream_ws(socket: WebSocket, state: AppState, is_admin: bool) {
     trace!("stream WebSocket connected");
 
     let (mut sink, mut stream) = socket.split();
@@ -107,82 +159,123 @@ async fn handle_stream_ws(socket: WebSocket, state: AppState) {
         protocol_version: STREAM_PROTOCOL_VERSION,
     };
     if !send_message(&mut sink, &ready).await {
+        state
+            .metrics
+            .record_stream_close(CloseReason::SendFailed.as_str());
         return;
     }
 
     let mut registry = SubscriptionRegistry::new();
+    let snapshots = snapshot_limiter();
 
-    let (mut cursor, mut head_watch) = state.events.subscribe();
+    let mut subscription = EventSubscription::new(state.events.clone());
     let mut computed_rx = state.computed_streams.subscribe();
 
-    loop {
+    let policy = state.stream_heartbeat;
+    let mut clock = ConnectionClock::new(policy, Instant::now());
+    let mut heartbeat = tokio::time::interval_at(
+        tokio::time::Instant::now() + policy.ping_interval,
+        policy.ping_interval,
+    );
+    heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let reason = loop {
         tokio::select! {
             msg = stream.next() => {
+                if matches!(msg, Some(Ok(_))) {
+                    clock.touch(Instant::now());
+                }
                 match msg {
                     Some(Ok(Message::Text(text))) => {
                         if matches!(
-                            handle_client_message(&mut sink, &state, &mut registry, &text).await,
+                            handle_client_message(
+                                &mut sink,
+                                &state,
+                                &mut registry,
+                                &snapshots,
+                                is_admin,
+                                &text,
+                            )
+                                .await,
                             ClientMessageResult::Disconnected
                         ) {
-                            break;
+                            break CloseReason::SendFailed;
                         }
                     }
-                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break CloseReason::Client,
                     _ => {}
                 }
             }
-            result = head_watch.changed() => {
-                if result.is_err() {
-                    break;
+            _ = heartbeat.tick() => {
+                if let Some(reason) = clock.check(Instant::now()) {
+                    let frame = CloseFrame {
+                        code: close_code::AWAY,
+                        reason: reason.as_str().into(),
+                    };
+                    let _ = sink.send(Message::Close(Some(frame))).await;
+                    break reason;
+                }
+                if sink.send(Message::Ping(Bytes::new())).await.is_err() {
+                    break CloseReason::SendFailed;
                 }
-                // Check for lag - cursor fell behind the buffer's oldest event
-                if cursor < state.events.base_offset() {
+            }
+            changed = subscription.changed() => {
+                if !changed {
+                    break CloseReason::Shutdown;
+                }
+                let drained = subscription.drain();
+                // Events were pruned before we read them; resync subscriptions
+                // from scratch rather than applying a partial history
+                if drained.lagged() {
                     if !resync_all(&mut sink, &state, &mut registry).await {
-                        break;
+                        break CloseReason::SendFailed;
                     }
-                    // Start from oldest available event to avoid missing any
-                    cursor = state.events.base_offset();
                     continue;
                 }
-                // Process all events from cursor to head
                 let mut send_failed = false;
-                while let Some(event) = state.events.read(cursor) {
+                for event in drained.events {
                     if !dispatch_event(&mut sink, &state, &mut registry, event).await {
                         send_failed = true;
                         break;
                     }
-                    cursor += 1;
                 }
                 if send_failed {
-                    break;
+                    break CloseReason::SendFailed;
                 }
             }
             update = computed_rx.recv() => {
                 match update {
                     Ok(update) => {
                         if !dispatch_computed_update(&mut sink, &registry, update).await {
-                            break;
+                            break CloseReason::SendFailed;
                         }
                     }
                     Err(RecvError::Lagged(n)) => {
                         debug!(skipped = n, "Computed updates lagged, resyncing");
                         if !resync_computed(&mut sink, &state, &registry).await {
-                            break;
+                            break CloseReason::SendFailed;
                         }
                     }
-                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Closed) => break CloseReason::Shutdown,
                 }
             }
         }
-    }
+    };
 
-    trace!("stream WebSocket disconnected");
+    state.metrics.record_stream_close(reason.as_str());
+    if reason.is_eviction() {
+        debug!(reason = reason.as_str(), "stream WebSocket evicted");
+    } else {
+        trace!(reason = reason.as_str(), "stream WebSocket disconnected");
+    }
 }
 
 async fn handle_client_message(
     sink: &mut futures::stream::SplitSink<WebSocket, Message>,
     state: &AppState,
     registry: &mut SubscriptionRegistry,
+    snapshots: &SnapshotLimiter,
+    is_admin: bool,
     text: &str,
 ) -> ClientMessageResult {
     let parsed = match serde_json::from_str::<StreamClientMessage>(text) {
@@ -205,6 +298,22 @@ async fn handle_client_message(
             stream,
             filter,
         } => {
+            let admission = if stream != StreamKind::Course && !is_admin {
+                Err(StreamError::forbidden())
+            } else if stream == StreamKind::Course
+                && registry.count_kind(StreamKind::Course) >= MAX_COURSE_SUBSCRIPTIONS
+            {
+                Err(StreamError::subscription_limit(MAX_COURSE_SUBSCRIPTIONS))
+            } else if snapshots.check().is_err() {
+                Err(StreamError::rate_limited())
+            } else {
+                Ok(())
+            };
+            if let Err(StreamError { code, message }) = admission {
+                let sent = send_error(sink, Some(request_id), code, &message).await;
+                return ClientMessageResult::from_error_send(sent);
+            }
+
             let sub_id = registry.allocate_id();
 
             let subscription = match build_subscription(stream, filter) {
@@ -251,6 +360,12 @@ async fn handle_client_message(
                 return ClientMessageResult::from_error_send(sent);
             };
 
+            if snapshots.check().is_err() {
+                let StreamError { code, message } = StreamError::rate_limited();
+                let sent = send_error(sink, Some(request_id), code, &message).await;
+                return ClientMessageResult::from_error_send(sent);
+            }
+
             let stream = subscription.kind();
             let old_cache_key = subscription_to_cache_key(subscription);
 
@@ -398,6 +513,64 @@ async fn send_snapshot(
             )
             .await
         }
+        Subscription::Course { filter } => {
+            let seats = match course::build_snapshot(&state.db_pool, filter).await {
+                Ok(Some(seats)) => seats,
+                Ok(None) => {
+                    return send_error(
+                        sink,
+                        None,
+                        StreamErrorCode::InvalidFilter,
+                        "Unknown course",
+                    )
+                    .await;
+                }
+                Err(_) => {
+                    return send_error(
+                        sink,
+                        None,
+                        StreamErrorCode::InternalError,
+                        "Failed to load course snapshot",
+                    )
+                    .await;
+                }
+            };
+
+            send_message(
+                sink,
+                &StreamServerMessage::Snapshot {
+                    subscription_id: subscription_id.to_string(),
+                    snapshot: StreamSnapshot::Course { seats },
+                },
+            )
+            .await
+        }
+        Subscription::RmpMatching => {
+            let progress = state.events.find_latest(|event| match event {
+                DomainEvent::RmpMatching(progress) => Some(progress.clone()),
+                _ => None,
+            });
+            send_message(
+                sink,
+                &StreamServerMessage::Snapshot {
+                    subscription_id: subscription_id.to_string(),
+                    snapshot: StreamSnapshot::RmpMatching { progress },
+                },
+            )
+            .await
+        }
+        Subscription::BannerHealth => {
+            send_message(
+                sink,
+                &StreamServerMessage::Snapshot {
+                    subscription_id: subscription_id.to_string(),
+                    snapshot: StreamSnapshot::BannerHealth {
+                        health: state.banner_api.circuit().health(),
+                    },
+                },
+            )
+            .await
+        }
         Subscription::ScraperStats { filter } => {
             match crate::data::scraper_stats::compute_stats(
                 &state.db_pool,
@@ -550,9 +723,19 @@ async fn dispatch_event(
         DomainEvent::ScrapeJob(scrape_event) => {
             dispatch_scrape_job_event(sink, state, registry, scrape_event).await
         }
-        DomainEvent::AuditLog(audit_event) => {
-            dispatch_audit_log_event(sink, registry, audit_event).await
+        DomainEvent::CourseChanges(event) => {
+            let entries = event.audit_entries();
+            dispatch_audit_log_event(sink, registry, &entries).await
+                && dispatch_course_event(sink, registry, &entries).await
+        }
+        DomainEvent::RmpMatching(progress) => {
+            dispatch_rmp_matching_event(sink, registry, progress).await
         }
+        DomainEvent::BannerHealth(health) => {
+            dispatch_banner_health_event(sink, registry, health).await
+        }
+        // Cache invalidation signals; nothing to stream.
+        DomainEvent::Instructor(_) => true,
     }
 }
 
@@ -561,7 +744,11 @@ async fn resync_all(
     state: &AppState,
     registry: &mut SubscriptionRegistry,
 ) -> bool {
-    resync_scrape_jobs(sink, state, registry).await && resync_audit_log(sink, state, registry).await
+    resync_scrape_jobs(sink, state, registry).await
+        && resync_audit_log(sink, state, registry).await
+        && resync_rmp_matching(sink, state, registry).await
+        && resync_banner_health(sink, state, registry).await
+        && resync_courses(sink, state, registry).await
 }
 
 async fn dispatch_scrape_job_event(
@@ -600,14 +787,14 @@ async fn dispatch_scrape_job_event(
 async fn dispatch_audit_log_event(
     sink: &mut futures::stream::SplitSink<WebSocket, Message>,
     registry: &mut SubscriptionRegistry,
-    event: AuditLogEvent,
+    entries: &[AuditLogEntry],
 ) -> bool {
     for (subscription_id, subscription) in registry.iter_mut() {
         let Subscription::AuditLog { filter } = subscription else {
             continue;
         };
 
-        let entries = audit_log::filter_entries(filter, &event.entries);
+        let entries = audit_log::filter_entries(filter, entries);
         if entries.is_empty() {
             continue;
         }
@@ -624,6 +811,73 @@ async fn dispatch_audit_log_event(
     true
 }
 
+async fn dispatch_course_event(
+    sink: &mut futures::stream::SplitSink<WebSocket, Message>,
+    registry: &SubscriptionRegistry,
+    entries: &[AuditLogEntry],
+) -> bool {
+    for (subscription_id, subscription) in registry.iter() {
+        let Subscription::Course { filter } = subscription else {
+            continue;
+        };
+
+        let entries = course::filter_entries(filter, entries);
+        if entries.is_empty() {
+            continue;
+        }
+
+        let delta = StreamServerMessage::Delta {
+            subscription_id: subscription_id.clone(),
+            delta: StreamDelta::Course { entries },
+        };
+        if !send_message(sink, &delta).await {
+            return false;
+        }
+    }
+
+    true
+}
+
+async fn dispatch_rmp_matching_event(
+    sink: &mut futures::stream::SplitSink<WebSocket, Message>,
+    registry: &SubscriptionRegistry,
+    progress: RmpMatchingProgress,
+) -> bool {
+    for subscription_id in registry.ids_for_kind(StreamKind::RmpMatching) {
+        let delta = StreamServerMessage::Delta {
+            subscription_id,
+            delta: StreamDelta::RmpMatching {
+                progress: progress.clone(),
+            },
+        };
+        if !send_message(sink, &delta).await {
+            return false;
+        }
+    }
+
+    true
+}
+
+async fn dispatch_banner_health_event(
+    sink: &mut futures::stream::SplitSink<WebSocket, Message>,
+    registry: &SubscriptionRegistry,
+    health: BannerHealth,
+) -> bool {
+    for subscription_id in registry.ids_for_kind(StreamKind::BannerHealth) {
+        let delta = StreamServerMessage::Delta {
+            subscription_id,
+            delta: StreamDelta::BannerHealth {
+                health: health.clone(),
+            },
+        };
+        if !send_message(sink, &delta).await {
+            return false;
+        }
+    }
+
+    true
+}
+
 async fn resync_scrape_jobs(
     sink: &mut futures::stream::SplitSink<WebSocket, Message>,
     state: &AppState,
@@ -652,6 +906,48 @@ async fn resync_audit_log(
     true
 }
 
+async fn resync_rmp_matching(
+    sink: &mut futures::stream::SplitSink<WebSocket, Message>,
+    state: &AppState,
+    registry: &mut SubscriptionRegistry,
+) -> bool {
+    let ids = registry.ids_for_kind(StreamKind::RmpMatching);
+    for subscription_id in ids {
+        if !send_snapshot(sink, state, registry, &subscription_id).await {
+            return false;
+        }
+    }
+    true
+}
+
+async fn resync_banner_health(
+    sink: &mut futures::stream::SplitSink<WebSocket, Message>,
+    state: &AppState,
+    registry: &mut SubscriptionRegistry,
+) -> bool {
+    let ids = registry.ids_for_kind(StreamKind::BannerHealth);
+    for subscription_id in ids {
+        if !send_snapshot(sink, state, registry, &subscription_id).await {
+            return false;
+        }
+    }
+    true
+}
+
+async fn resync_courses(
+    sink: &mut futures::stream::SplitSink<WebSocket, Message>,
+    state: &AppState,
+    registry: &mut SubscriptionRegistry,
+) -> bool {
+    let ids = registry.ids_for_kind(StreamKind::Course);
+    for subscription_id in ids {
+        if !send_snapshot(sink, state, registry, &subscription_id).await {
+            return false;
+        }
+    }
+    true
+}
+
 async fn dispatch_computed_update(
     sink: &mut futures::stream::SplitSink<WebSocket, Message>,
     registry: &SubscriptionRegistry,
@@ -889,4 +1185,13 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn snapshot_limiter_allows_a_schedule_page_then_throttles() {
+        let limiter = snapshot_limiter();
+        for _ in 0..SNAPSHOT_BURST.get() {
+            assert!(limiter.check().is_ok());
+        }
+        assert!(limiter.check().is_err());
+    }
 }
@@ -3,10 +3,13 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::banner::circuit::BannerHealth;
+use crate::data::rmp_matching::RmpMatchingProgress;
 use crate::web::admin::scraper::{ScraperStatsResponse, SubjectSummary, TimeseriesPoint};
 use crate::web::stream::filters::{
-    AuditLogFilter, ScrapeJobsFilter, ScraperStatsFilter, ScraperTimeseriesFilter,
+    AuditLogFilter, CourseFilter, ScrapeJobsFilter, ScraperStatsFilter, ScraperTimeseriesFilter,
 };
+use crate::web::stream::streams::course::CourseSeats;
 use crate::web::ws::{ScrapeJobDto, ScrapeJobEvent};
 
 pub const STREAM_PROTOCOL_VERSION: u32 = 1;
@@ -20,6 +23,11 @@ pub enum StreamKind {
     ScraperStats,
     ScraperTimeseries,
     ScraperSubjects,
+    RmpMatching,
+    /// Banner circuit breaker state.
+    BannerHealth,
+    /// Live seat counts for one section. The only stream open to non-admins.
+    Course,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -31,6 +39,7 @@ pub enum StreamFilter {
     ScraperStats(ScraperStatsFilter),
     ScraperTimeseries(ScraperTimeseriesFilter),
     ScraperSubjects {},
+    Course(CourseFilter),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -69,6 +78,12 @@ pub enum StreamErrorCode {
     InvalidFilter,
     UnknownSubscription,
     InternalError,
+    /// The stream requires admin access.
+    Forbidden,
+    /// The connection already holds the maximum subscriptions of this kind.
+    SubscriptionLimit,
+    /// Too many subscribe/modify requests on this connection; retry later.
+    RateLimited,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -92,6 +107,16 @@ pub enum StreamSnapshot {
     ScraperSubjects {
         subjects: Vec<SubjectSummary>,
     },
+    RmpMatching {
+        /// Latest progress of the current or most recent run, if still buffered.
+        progress: Option<RmpMatchingProgress>,
+    },
+    BannerHealth {
+        health: BannerHealth,
+    },
+    Course {
+        seats: CourseSeats,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -114,6 +139,16 @@ pub enum StreamDelta {
         changed: Vec<SubjectSummary>,
         removed: Vec<String>,
     },
+    RmpMatching {
+        progress: RmpMatchingProgress,
+    },
+    BannerHealth {
+        health: BannerHealth,
+    },
+    Course {
+        /// Field changes for the subscribed section, e.g. `enrollment`.
+        entries: Vec<crate::web::audit::AuditLogEntry>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -172,6 +207,27 @@ impl StreamError {
         }
     }
 
+    pub fn forbidden() -> Self {
+        Self {
+            code: StreamErrorCode::Forbidden,
+            message: "This stream requires admin access".to_string(),
+        }
+    }
+
+    pub fn subscription_limit(limit: usize) -> Self {
+        Self {
+            code: StreamErrorCode::SubscriptionLimit,
+            message: format!("At most {limit} subscriptions of this kind per connection"),
+        }
+    }
+
+    pub fn rate_limited() -> Self {
+        Self {
+            code: StreamErrorCode::RateLimited,
+            message: "Too many subscription requests; slow down".to_string(),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn unknown_subscription() -> Self {
         Self {
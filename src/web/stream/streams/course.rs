@@ -0,0 +1,90 @@
+//! Per-section stream logic for live seat counters.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+
+use crate::web::audit::AuditLogEntry;
+use crate::web::stream::filters::CourseFilter;
+
+/// Current seat counts for one section.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CourseSeats {
+    pub term: String,
+    pub crn: String,
+    pub enrollment: i32,
+    pub max_enrollment: i32,
+    pub wait_count: i32,
+    pub wait_capacity: i32,
+    pub last_scraped_at: DateTime<Utc>,
+}
+
+/// Load the section's current seats, or `None` if it doesn't exist.
+pub async fn build_snapshot(
+    db_pool: &PgPool,
+    filter: &CourseFilter,
+) -> anyhow::Result<Option<CourseSeats>> {
+    let course =
+        crate::data::courses::get_course_by_crn(db_pool, &filter.crn, &filter.term).await?;
+    Ok(course.map(|c| CourseSeats {
+        term: c.term_code,
+        crn: c.crn,
+        enrollment: c.enrollment,
+        max_enrollment: c.max_enrollment,
+        wait_count: c.wait_count,
+        wait_capacity: c.wait_capacity,
+        last_scraped_at: c.last_scraped_at,
+    }))
+}
+
+/// Entries that changed the subscribed section.
+pub fn filter_entries(filter: &CourseFilter, entries: &[AuditLogEntry]) -> Vec<AuditLogEntry> {
+    entries
+        .iter()
+        .filter(|e| {
+            e.crn.as_deref() == Some(filter.crn.as_str())
+                && e.term_code.as_deref() == Some(filter.term.as_str())
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(term: &str, crn: &str) -> AuditLogEntry {
+        AuditLogEntry {
+            id: 1,
+            course_id: 1,
+            timestamp: String::new(),
+            field_changed: "enrollment".into(),
+            old_value: Some(serde_json::json!(10)),
+            new_value: serde_json::json!(11),
+            subject: Some("CS".into()),
+            course_number: None,
+            crn: Some(crn.into()),
+            course_title: None,
+            term_code: Some(term.into()),
+        }
+    }
+
+    #[test]
+    fn matches_term_and_crn() {
+        let filter = CourseFilter {
+            term: "202620".into(),
+            crn: "12345".into(),
+        };
+        let entries = [
+            entry("202620", "12345"),
+            entry("202610", "12345"),
+            entry("202620", "54321"),
+        ];
+        let matched = filter_entries(&filter, &entries);
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].term_code.as_deref(), Some("202620"));
+    }
+}
@@ -1,4 +1,5 @@
 //! Stream handlers for each stream kind.
 
 pub mod audit_log;
+pub mod course;
 pub mod scrape_jobs;
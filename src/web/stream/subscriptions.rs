@@ -3,9 +3,9 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::web::stream::filters::{
-    AuditLogFilter, ScrapeJobsFilter, ScraperStatsFilter, ScraperTimeseriesFilter,
-    parse_audit_log_filter, parse_scrape_jobs_filter, parse_scraper_stats_filter,
-    parse_scraper_timeseries_filter,
+    AuditLogFilter, CourseFilter, ScrapeJobsFilter, ScraperStatsFilter, ScraperTimeseriesFilter,
+    parse_audit_log_filter, parse_course_filter, parse_scrape_jobs_filter,
+    parse_scraper_stats_filter, parse_scraper_timeseries_filter,
 };
 use crate::web::stream::protocol::{StreamError, StreamFilter, StreamKind};
 
@@ -24,6 +24,11 @@ pub enum Subscription {
         filter: ScraperTimeseriesFilter,
     },
     ScraperSubjects,
+    RmpMatching,
+    BannerHealth,
+    Course {
+        filter: CourseFilter,
+    },
 }
 
 impl Subscription {
@@ -34,6 +39,9 @@ impl Subscription {
             Subscription::ScraperStats { .. } => StreamKind::ScraperStats,
             Subscription::ScraperTimeseries { .. } => StreamKind::ScraperTimeseries,
             Subscription::ScraperSubjects => StreamKind::ScraperSubjects,
+            Subscription::RmpMatching => StreamKind::RmpMatching,
+            Subscription::BannerHealth => StreamKind::BannerHealth,
+            Subscription::Course { .. } => StreamKind::Course,
         }
     }
 
@@ -94,6 +102,13 @@ impl SubscriptionRegistry {
         self.subscriptions.iter_mut()
     }
 
+    pub fn count_kind(&self, kind: StreamKind) -> usize {
+        self.subscriptions
+            .values()
+            .filter(|sub| sub.kind() == kind)
+            .count()
+    }
+
     pub fn ids_for_kind(&self, kind: StreamKind) -> Vec<String> {
         self.subscriptions
             .iter()
@@ -133,5 +148,11 @@ pub fn build_subscription(
             Ok(Subscription::ScraperTimeseries { filter })
         }
         StreamKind::ScraperSubjects => Ok(Subscription::ScraperSubjects),
+        StreamKind::RmpMatching => Ok(Subscription::RmpMatching),
+        StreamKind::BannerHealth => Ok(Subscription::BannerHealth),
+        StreamKind::Course => {
+            let filter = parse_course_filter(filter)?;
+            Ok(Subscription::Course { filter })
+        }
     }
 }
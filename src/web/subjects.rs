@@ -0,0 +1,42 @@
+//! Subject landing page HTTP handlers.
+
+use axum::extract::{Path, State};
+use axum::response::Response;
+
+use crate::data;
+use crate::state::AppState;
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+use crate::web::routes::{cache, with_cache_control};
+
+/// `GET /api/subjects/{code}` -- Course levels, top-rated instructors,
+/// BlueBook course rating, and terms offered for one subject.
+#[utoipa::path(
+    get,
+    path = "/subjects/{code}",
+    tag = "reference",
+    summary = "Subject overview: course levels, top instructors, ratings, terms offered",
+    params(
+        ("code" = String, Path, description = "Subject code (e.g. CS); aliases resolve"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = data::subjects::SubjectOverview),
+    ),
+)]
+pub async fn get_subject(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<Response, ApiError> {
+    let mut overview = data::subjects::get_overview(&state.db_pool, &code)
+        .await
+        .map_err(|e| db_error("Subject overview", e))?
+        .or_not_found("Subject", &code)?;
+
+    overview.description = state
+        .reference_cache
+        .read()
+        .await
+        .lookup("subject", &overview.code)
+        .map(str::to_owned);
+
+    Ok(with_cache_control(overview, cache::REFERENCE))
+}
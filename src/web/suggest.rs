@@ -1,39 +1,109 @@
 //! Suggest and instructor resolution handlers.
 
+use axum::Extension;
 use axum::extract::{Query, State};
 use axum::response::Response;
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 use std::collections::HashMap;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::data;
-use crate::data::courses::{CourseSuggestion, InstructorSuggestion};
+use crate::data::courses::{
+    BuildingSuggestion, CourseGroupSuggestion, CourseSuggestion, InstructorSuggestion,
+    SubjectSuggestion,
+};
 use crate::state::AppState;
 use crate::web::error::{ApiError, db_error};
+use crate::web::middleware::rate_limit::RateLimitSubject;
 use crate::web::routes::{cache, with_cache_control};
 
 fn default_suggest_limit() -> i32 {
     10
 }
 
-#[derive(Deserialize, Serialize, TS)]
+/// How course suggestions are grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, TS, ToSchema)]
+#[serde(rename_all = "lowercase")]
 #[ts(export)]
+pub enum SuggestGroupBy {
+    /// One entry per subject and course number, with a seat summary.
+    Course,
+}
+
+#[derive(Deserialize, Serialize, TS, IntoParams)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
 pub struct SuggestParams {
     pub term: String,
     pub q: String,
     #[serde(default = "default_suggest_limit")]
     pub limit: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[param(inline)]
+    pub group_by: Option<SuggestGroupBy>,
+    /// Per-group limits; each defaults to `limit`, and 0 omits the group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub course_limit: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructor_limit: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_limit: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub building_limit: Option<i32>,
 }
 
-#[derive(Serialize, TS)]
+/// Rows wanted per suggestion group; 0 skips the group.
+pub(crate) struct SuggestLimits {
+    pub(crate) courses: i32,
+    pub(crate) course_groups: i32,
+    pub(crate) instructors: i32,
+    pub(crate) subjects: i32,
+    pub(crate) buildings: i32,
+}
+
+impl SuggestLimits {
+    fn from_params(params: &SuggestParams) -> Self {
+        let limit = params.limit.clamp(1, 25);
+        let group_limit = |group: Option<i32>| group.unwrap_or(limit).clamp(0, 25);
+        let course_limit = group_limit(params.course_limit);
+        let grouped = params.group_by == Some(SuggestGroupBy::Course);
+        Self {
+            courses: if grouped { 0 } else { course_limit },
+            course_groups: if grouped { course_limit } else { 0 },
+            instructors: group_limit(params.instructor_limit),
+            subjects: group_limit(params.subject_limit),
+            buildings: group_limit(params.building_limit),
+        }
+    }
+}
+
+#[derive(Default, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct SuggestResponse {
+    /// Course suggestions, one per distinct title; empty with `group_by=course`.
     pub courses: Vec<CourseSuggestion>,
+    /// Course suggestions grouped per `group_by=course`; otherwise empty.
+    pub course_groups: Vec<CourseGroupSuggestion>,
     pub instructors: Vec<InstructorSuggestion>,
+    pub subjects: Vec<SubjectSuggestion>,
+    pub buildings: Vec<BuildingSuggestion>,
+}
+
+impl SuggestResponse {
+    fn is_empty(&self) -> bool {
+        self.courses.is_empty()
+            && self.course_groups.is_empty()
+            && self.instructors.is_empty()
+            && self.subjects.is_empty()
+            && self.buildings.is_empty()
+    }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SuggestInstructorsParams {
     pub q: String,
     pub term: Option<String>,
@@ -41,50 +111,152 @@ pub struct SuggestInstructorsParams {
     pub limit: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ResolveInstructorsParams {
     #[serde(default)]
     pub slug: Vec<String>,
 }
 
-/// `GET /api/suggest?term={slug}&q={query}&limit=10`
+/// `GET /api/suggest?term={slug}&q={query}&limit=10&group_by=course&building_limit=0`
+///
+/// The route's rate limit is sized for index lookups. Requests that need the
+/// database (index misses and `group_by=course`) are also charged to a
+/// smaller fallback budget; once that's spent, they get index results only.
+#[utoipa::path(
+    get,
+    path = "/suggest",
+    tag = "search",
+    summary = "Course, instructor, subject, and building suggestions for a partial query",
+    params(
+        SuggestParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = SuggestResponse),
+    ),
+)]
 pub(super) async fn suggest(
     State(state): State<AppState>,
+    subject: Option<Extension<RateLimitSubject>>,
     Query(params): Query<SuggestParams>,
 ) -> Result<Response, ApiError> {
     use crate::banner::models::terms::Term;
 
     let term_code =
         Term::resolve_to_code(&params.term).ok_or_else(|| ApiError::invalid_term(&params.term))?;
-    let limit = params.limit.clamp(1, 25);
+    let limits = SuggestLimits::from_params(&params);
     let q = params.q.trim();
 
     if q.chars().count() < 2 {
         return Ok(with_cache_control(
-            SuggestResponse {
-                courses: vec![],
-                instructors: vec![],
-            },
+            SuggestResponse::default(),
             cache::REFERENCE,
         ));
     }
 
-    let (courses, instructors) = tokio::try_join!(
-        data::courses::suggest_courses(&state.db_pool, &term_code, q, limit),
-        data::courses::suggest_instructors(&state.db_pool, &term_code, q, limit),
-    )
-    .map_err(|e| db_error("Suggest query", e))?;
+    // The in-memory index only matches word prefixes; anything it misses
+    // (typos, terms not loaded yet) goes to the trigram queries.
+    state.suggest_index.ensure_fresh();
+    let indexed = state
+        .suggest_index
+        .snapshot()
+        .term(&term_code)
+        .map(|index| index.suggest(q, &limits))
+        .filter(|response| !response.is_empty());
+
+    let subject = subject.map(|Extension(subject)| subject);
+    let may_query_db = || state.rate_limit.allow_suggest_fallback(subject);
+
+    let response = match indexed {
+        Some(mut response) => {
+            if limits.course_groups > 0 && may_query_db() {
+                response.course_groups = data::courses::suggest_course_groups(
+                    &state.db_pool,
+                    &term_code,
+                    q,
+                    limits.course_groups,
+                )
+                .await
+                .map_err(|e| db_error("Suggest query", e))?;
+            }
+            response
+        }
+        None if may_query_db() => suggest_from_db(&state.db_pool, &term_code, q, &limits)
+            .await
+            .map_err(|e| db_error("Suggest query", e))?,
+        None => SuggestResponse::default(),
+    };
+
+    Ok(with_cache_control(response, cache::REFERENCE))
+}
 
-    Ok(with_cache_control(
-        SuggestResponse {
-            courses,
-            instructors,
+/// Every suggestion group from the trigram queries.
+async fn suggest_from_db(
+    db: &PgPool,
+    term_code: &str,
+    q: &str,
+    limits: &SuggestLimits,
+) -> anyhow::Result<SuggestResponse> {
+    let (courses, course_groups, instructors, subjects, buildings) = tokio::try_join!(
+        async {
+            if limits.courses == 0 {
+                Ok(vec![])
+            } else {
+                data::courses::suggest_courses(db, term_code, q, limits.courses).await
+            }
         },
-        cache::REFERENCE,
-    ))
+        async {
+            if limits.course_groups == 0 {
+                Ok(vec![])
+            } else {
+                data::courses::suggest_course_groups(db, term_code, q, limits.course_groups).await
+            }
+        },
+        async {
+            if limits.instructors == 0 {
+                Ok(vec![])
+            } else {
+                data::courses::suggest_instructors(db, term_code, q, limits.instructors).await
+            }
+        },
+        async {
+            if limits.subjects == 0 {
+                Ok(vec![])
+            } else {
+                data::courses::suggest_subjects(db, term_code, q, limits.subjects).await
+            }
+        },
+        async {
+            if limits.buildings == 0 {
+                Ok(vec![])
+            } else {
+                data::courses::suggest_buildings(db, term_code, q, limits.buildings).await
+            }
+        },
+    )?;
+
+    Ok(SuggestResponse {
+        courses,
+        course_groups,
+        instructors,
+        subjects,
+        buildings,
+    })
 }
 
 /// `GET /api/instructors/suggest?q={query}&term={slug}&limit=10`
+#[utoipa::path(
+    get,
+    path = "/instructors/suggest",
+    tag = "instructors",
+    summary = "Instructor name suggestions",
+    params(
+        SuggestInstructorsParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = Vec<InstructorSuggestion>),
+    ),
+)]
 pub(super) async fn suggest_instructors(
     State(state): State<AppState>,
     Query(params): Query<SuggestInstructorsParams>,
@@ -116,6 +288,18 @@ pub(super) async fn suggest_instructors(
 }
 
 /// `GET /api/instructors/resolve?slug=a&slug=b`
+#[utoipa::path(
+    get,
+    path = "/instructors/resolve",
+    tag = "instructors",
+    summary = "Display names for instructor slugs",
+    params(
+        ResolveInstructorsParams,
+    ),
+    responses(
+        (status = 200, description = "Slug -> display name", body = HashMap<String, String>),
+    ),
+)]
 pub(super) async fn resolve_instructors(
     State(state): State<AppState>,
     axum_extra::extract::Query(params): axum_extra::extract::Query<ResolveInstructorsParams>,
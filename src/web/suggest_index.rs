@@ -0,0 +1,508 @@
+//! In-memory prefix index backing `/api/suggest`.
+//!
+//! Autocomplete fires on nearly every keystroke, so answering it with
+//! trigram queries kept the database busy for results that barely change
+//! between scrapes. This module loads each term's course titles,
+//! instructors, subjects, and buildings once, folds them to lowercase ASCII
+//! words, and keeps a sorted word list per group. A query is answered by
+//! binary-searching its first word and checking the remaining words against
+//! each candidate.
+//!
+//! Like [`ScheduleCache`], the index refreshes hourly in the background with
+//! stale-while-revalidate and singleflight deduplication. Matching is by word
+//! prefix only, so the handler falls back to the trigram queries when the
+//! index finds nothing (typos, or a term that hasn't been loaded yet).
+//!
+//! [`ScheduleCache`]: crate::web::schedule_cache::ScheduleCache
+
+use crate::data::courses::{
+    BuildingSuggestion, CourseSuggestion, InstructorSuggestion, SubjectSuggestion,
+};
+use crate::utils::fmt_duration;
+use crate::web::suggest::{SuggestLimits, SuggestResponse};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::watch;
+use tracing::{debug, error, info};
+use unicode_normalization::UnicodeNormalization;
+
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// A suggestion whose score is filled in per query.
+pub(crate) trait Scored: Clone {
+    fn with_score(&self, score: f32) -> Self;
+}
+
+macro_rules! impl_scored {
+    ($($ty:ty),*) => {
+        $(impl Scored for $ty {
+            fn with_score(&self, score: f32) -> Self {
+                Self {
+                    score,
+                    ..self.clone()
+                }
+            }
+        })*
+    };
+}
+
+impl_scored!(
+    CourseSuggestion,
+    InstructorSuggestion,
+    SubjectSuggestion,
+    BuildingSuggestion
+);
+
+/// One suggestion group's entries and their sorted words.
+#[derive(Debug)]
+pub(crate) struct PrefixIndex<T> {
+    entries: Vec<T>,
+    /// Folded names per entry, e.g. a course's title and `cs 1083`.
+    names: Vec<Box<[Box<str>]>>,
+    /// Every word of every name with the entry it belongs to, sorted by word.
+    words: Vec<(Box<str>, u32)>,
+}
+
+impl<T: Scored> PrefixIndex<T> {
+    /// Build from entries and the names they can be found by. Entries should
+    /// be ordered by preference; equal scores keep that order.
+    pub(crate) fn new(items: Vec<(T, Vec<String>)>) -> Self {
+        let mut entries = Vec::with_capacity(items.len());
+        let mut names = Vec::with_capacity(items.len());
+        let mut words: Vec<(Box<str>, u32)> = Vec::new();
+        for (idx, (entry, entry_names)) in items.into_iter().enumerate() {
+            let folded: Box<[Box<str>]> = entry_names
+                .iter()
+                .map(|name| Box::from(fold(name).as_str()))
+                .collect();
+            words.extend(
+                folded
+                    .iter()
+                    .flat_map(|name| name.split(' '))
+                    .filter(|w| !w.is_empty())
+                    .map(|w| (Box::from(w), idx as u32)),
+            );
+            entries.push(entry);
+            names.push(folded);
+        }
+        words.sort_unstable();
+        Self {
+            entries,
+            names,
+            words,
+        }
+    }
+
+    /// Entries where every query word prefixes one of the entry's words,
+    /// best first. Scores are in `0..=1`: entries with a name starting with
+    /// the query rank above the rest, and shorter names above longer ones.
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<T> {
+        let query = fold(query);
+        let query_words: Vec<&str> = query.split(' ').filter(|w| !w.is_empty()).collect();
+        let Some((first, rest)) = query_words.split_first() else {
+            return Vec::new();
+        };
+        if limit == 0 {
+            return Vec::new();
+        }
+
+        let start = self
+            .words
+            .partition_point(|(word, _)| word.as_ref() < *first);
+        let mut candidates: Vec<u32> = self.words[start..]
+            .iter()
+            .take_while(|(word, _)| word.starts_with(first))
+            .map(|(_, idx)| *idx)
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut scored: Vec<(u32, f32)> = candidates
+            .into_iter()
+            .filter_map(|idx| {
+                let names = &self.names[idx as usize];
+                let matches_rest = rest.iter().all(|q| {
+                    names
+                        .iter()
+                        .flat_map(|name| name.split(' '))
+                        .any(|w| w.starts_with(q))
+                });
+                let best = names
+                    .iter()
+                    .map(|name| score(name, &query))
+                    .fold(0.0, f32::max);
+                matches_rest.then_some((idx, best))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(idx, score)| self.entries[idx as usize].with_score(score))
+            .collect()
+    }
+}
+
+fn score(text: &str, query: &str) -> f32 {
+    let coverage = (query.len() as f32 / text.len().max(1) as f32).min(1.0);
+    if text.starts_with(query) {
+        0.5 + coverage / 2.0
+    } else {
+        coverage / 2.0
+    }
+}
+
+/// Lowercase, strip accents, and collapse everything but letters and digits
+/// into single spaces: `"García López, José"` -> `"garcia lopez jose"`.
+pub(crate) fn fold(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s
+        .nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+    {
+        if c.is_alphanumeric() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with(' ') {
+            out.push(' ');
+        }
+    }
+    if out.ends_with(' ') {
+        out.pop();
+    }
+    out
+}
+
+/// Every suggestion group for one term.
+#[derive(Debug)]
+pub(crate) struct TermIndex {
+    courses: PrefixIndex<CourseSuggestion>,
+    instructors: PrefixIndex<InstructorSuggestion>,
+    subjects: PrefixIndex<SubjectSuggestion>,
+    buildings: PrefixIndex<BuildingSuggestion>,
+}
+
+impl TermIndex {
+    /// Suggestions for every group the limits ask for. `course_groups` is
+    /// always empty: its seat counts change too often to cache here.
+    pub(crate) fn suggest(&self, query: &str, limits: &SuggestLimits) -> SuggestResponse {
+        SuggestResponse {
+            courses: self.courses.search(query, limits.courses as usize),
+            course_groups: Vec::new(),
+            instructors: self.instructors.search(query, limits.instructors as usize),
+            subjects: self.subjects.search(query, limits.subjects as usize),
+            buildings: self.buildings.search(query, limits.buildings as usize),
+        }
+    }
+}
+
+/// The immutable snapshot of every term's index, swapped atomically on refresh.
+#[derive(Debug)]
+pub(crate) struct SuggestSnapshot {
+    terms: HashMap<String, Arc<TermIndex>>,
+    refreshed_at: std::time::Instant,
+}
+
+impl SuggestSnapshot {
+    /// The index for a term, if it has any courses.
+    pub(crate) fn term(&self, term_code: &str) -> Option<Arc<TermIndex>> {
+        self.terms.get(term_code).cloned()
+    }
+}
+
+/// Shared suggest index. Clone-cheap (all `Arc`-wrapped internals).
+#[derive(Clone)]
+pub struct SuggestIndex {
+    /// Current snapshot, updated via `watch` channel for lock-free reads.
+    rx: watch::Receiver<Arc<SuggestSnapshot>>,
+    /// Sender side, held to push new snapshots.
+    tx: Arc<watch::Sender<Arc<SuggestSnapshot>>>,
+    /// Singleflight guard -- true while a refresh task is in flight.
+    refreshing: Arc<AtomicBool>,
+    /// Database pool for refresh queries.
+    pool: PgPool,
+}
+
+impl SuggestIndex {
+    /// Create a new index with an empty initial snapshot.
+    pub(crate) fn new(pool: PgPool) -> Self {
+        let empty = Arc::new(SuggestSnapshot {
+            terms: HashMap::new(),
+            refreshed_at: std::time::Instant::now(),
+        });
+        let (tx, rx) = watch::channel(empty);
+        Self {
+            rx,
+            tx: Arc::new(tx),
+            refreshing: Arc::new(AtomicBool::new(false)),
+            pool,
+        }
+    }
+
+    /// Get the current snapshot. Never blocks on refresh.
+    pub(crate) fn snapshot(&self) -> Arc<SuggestSnapshot> {
+        self.rx.borrow().clone()
+    }
+
+    /// Check freshness and trigger a background refresh if stale.
+    /// Always returns immediately -- the caller uses the current snapshot.
+    pub(crate) fn ensure_fresh(&self) {
+        let snap = self.rx.borrow();
+        if snap.refreshed_at.elapsed() < REFRESH_INTERVAL {
+            return;
+        }
+        if self
+            .refreshing
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            debug!("Suggest index refresh already in flight, skipping");
+            return;
+        }
+        let index = self.clone();
+        tokio::spawn(async move {
+            match load_snapshot(&index.pool).await {
+                Ok(snap) => {
+                    let terms = snap.terms.len();
+                    let _ = index.tx.send(Arc::new(snap));
+                    info!(terms, "Suggest index refreshed");
+                }
+                Err(e) => {
+                    error!(error = %e, "Failed to refresh suggest index");
+                }
+            }
+            index.refreshing.store(false, Ordering::Release);
+        });
+    }
+
+    /// Force an initial load (blocking). Call once at startup.
+    pub(crate) async fn load(&self) -> anyhow::Result<()> {
+        let snap = load_snapshot(&self.pool).await?;
+        let terms = snap.terms.len();
+        let _ = self.tx.send(Arc::new(snap));
+        info!(terms, "Suggest index initially loaded");
+        Ok(())
+    }
+}
+
+/// Per-term entries collected before building the indexes.
+#[derive(Default)]
+struct TermEntries {
+    courses: Vec<(CourseSuggestion, Vec<String>)>,
+    instructors: Vec<(InstructorSuggestion, Vec<String>)>,
+    subjects: Vec<(SubjectSuggestion, Vec<String>)>,
+    buildings: Vec<(BuildingSuggestion, Vec<String>)>,
+}
+
+/// Load every term's suggestion entries. Each query orders rows by section
+/// or room count so busier entries win ties.
+async fn load_snapshot(pool: &PgPool) -> anyhow::Result<SuggestSnapshot> {
+    let start = std::time::Instant::now();
+    let mut terms: HashMap<String, TermEntries> = HashMap::new();
+
+    let courses: Vec<(String, String, String, String, i32)> = sqlx::query_as(
+        r#"
+        SELECT term_code, subject, course_number, title, COUNT(*)::int AS section_count
+        FROM courses
+        GROUP BY term_code, subject, course_number, title
+        ORDER BY section_count DESC, subject, course_number, title
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    for (term, subject, course_number, title, section_count) in courses {
+        let names = vec![
+            title.clone(),
+            format!("{subject} {course_number}"),
+            format!("{subject}{course_number}"),
+        ];
+        terms.entry(term).or_default().courses.push((
+            CourseSuggestion {
+                subject,
+                course_number,
+                title,
+                section_count,
+                score: 0.0,
+            },
+            names,
+        ));
+    }
+
+    let instructors: Vec<(String, i32, String, String, i32)> = sqlx::query_as(
+        r#"
+        SELECT c.term_code, i.id, i.slug, i.display_name,
+               COUNT(DISTINCT c.id)::int AS section_count
+        FROM instructors i
+        JOIN course_instructors ci ON ci.instructor_id = i.id
+        JOIN courses c ON c.id = ci.course_id
+        WHERE i.slug IS NOT NULL
+        GROUP BY c.term_code, i.id, i.slug, i.display_name
+        ORDER BY section_count DESC, i.display_name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    for (term, id, slug, display_name, section_count) in instructors {
+        let names = vec![display_name.clone()];
+        terms.entry(term).or_default().instructors.push((
+            InstructorSuggestion {
+                id,
+                slug,
+                display_name,
+                section_count,
+                score: 0.0,
+            },
+            names,
+        ));
+    }
+
+    let subjects: Vec<(String, String, String, i32)> = sqlx::query_as(
+        r#"
+        SELECT c.term_code, c.subject, COALESCE(rd.description, c.subject),
+               COUNT(*)::int AS section_count
+        FROM courses c
+        LEFT JOIN reference_data rd ON rd.category = 'subject' AND rd.code = c.subject
+        GROUP BY c.term_code, c.subject, rd.description
+        ORDER BY section_count DESC, c.subject
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    for (term, code, description, section_count) in subjects {
+        let names = vec![code.clone(), description.clone()];
+        terms.entry(term).or_default().subjects.push((
+            SubjectSuggestion {
+                code,
+                description,
+                section_count,
+                score: 0.0,
+            },
+            names,
+        ));
+    }
+
+    let buildings: Vec<(String, String, String, i32)> = sqlx::query_as(
+        r#"
+        SELECT c.term_code, cm.building, COALESCE(rd.description, cm.building),
+               COUNT(DISTINCT cm.room)::int AS room_count
+        FROM course_meetings cm
+        JOIN courses c ON c.id = cm.course_id
+        LEFT JOIN reference_data rd ON rd.category = 'building' AND rd.code = cm.building
+        WHERE cm.building IS NOT NULL
+        GROUP BY c.term_code, cm.building, rd.description
+        ORDER BY room_count DESC, cm.building
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    for (term, code, description, room_count) in buildings {
+        let names = vec![code.clone(), description.clone()];
+        terms.entry(term).or_default().buildings.push((
+            BuildingSuggestion {
+                code,
+                description,
+                room_count,
+                score: 0.0,
+            },
+            names,
+        ));
+    }
+
+    let terms: HashMap<String, Arc<TermIndex>> = terms
+        .into_iter()
+        .map(|(code, entries)| {
+            let index = TermIndex {
+                courses: PrefixIndex::new(entries.courses),
+                instructors: PrefixIndex::new(entries.instructors),
+                subjects: PrefixIndex::new(entries.subjects),
+                buildings: PrefixIndex::new(entries.buildings),
+            };
+            (code, Arc::new(index))
+        })
+        .collect();
+
+    debug!(
+        terms = terms.len(),
+        elapsed = fmt_duration(start.elapsed()),
+        "Suggest snapshot built"
+    );
+
+    Ok(SuggestSnapshot {
+        terms,
+        refreshed_at: std::time::Instant::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn course(subject: &str, number: &str, title: &str) -> (CourseSuggestion, Vec<String>) {
+        (
+            CourseSuggestion {
+                subject: subject.to_owned(),
+                course_number: number.to_owned(),
+                title: title.to_owned(),
+                section_count: 1,
+                score: 0.0,
+            },
+            vec![
+                title.to_owned(),
+                format!("{subject} {number}"),
+                format!("{subject}{number}"),
+            ],
+        )
+    }
+
+    fn titles(results: &[CourseSuggestion]) -> Vec<&str> {
+        results.iter().map(|c| c.title.as_str()).collect()
+    }
+
+    #[test]
+    fn fold_strips_accents_and_punctuation() {
+        assert_eq!(fold("García López, José"), "garcia lopez jose");
+        assert_eq!(fold("  CS-1083 "), "cs 1083");
+        assert_eq!(fold("--"), "");
+    }
+
+    #[test]
+    fn every_query_word_must_prefix_a_word() {
+        let index = PrefixIndex::new(vec![
+            course("CS", "1083", "Programming I"),
+            course("CS", "3343", "Data Structures"),
+            course("MAT", "1214", "Calculus I"),
+        ]);
+        assert_eq!(titles(&index.search("data str", 10)), ["Data Structures"]);
+        assert_eq!(titles(&index.search("str data", 10)), ["Data Structures"]);
+        assert_eq!(titles(&index.search("cs1083", 10)), ["Programming I"]);
+        assert_eq!(titles(&index.search("CS 1083", 10)), ["Programming I"]);
+        assert!(index.search("ata", 10).is_empty());
+        assert!(index.search("", 10).is_empty());
+    }
+
+    #[test]
+    fn leading_matches_rank_first() {
+        let index = PrefixIndex::new(vec![
+            course("CS", "4593", "Topics in Calculus"),
+            course("MAT", "1214", "Calculus I"),
+            course("MAT", "1224", "Calculus II"),
+        ]);
+        let results = index.search("calc", 10);
+        assert_eq!(
+            titles(&results),
+            ["Calculus I", "Calculus II", "Topics in Calculus"]
+        );
+        assert!(results[1].score > results[2].score);
+        assert_eq!(index.search("calc", 1).len(), 1);
+        assert!(results.iter().all(|c| (0.0..=1.0).contains(&c.score)));
+    }
+
+    #[test]
+    fn accents_match_either_way() {
+        let index = PrefixIndex::new(vec![course("SPN", "1014", "Introducción al Español")]);
+        assert_eq!(index.search("introduccion", 10).len(), 1);
+        assert_eq!(index.search("Español", 10).len(), 1);
+    }
+}
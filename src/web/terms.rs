@@ -0,0 +1,131 @@
+//! Public per-term HTTP handlers.
+
+use axum::extract::{Path, Query, State};
+use axum::response::Response;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::banner::models::terms::Term;
+use crate::data;
+use crate::data::term_changes::{TermChangeCounts, TermChangeDay};
+use crate::display::CAMPUS_TZ;
+use crate::state::AppState;
+use crate::web::error::{ApiError, db_error};
+use crate::web::routes::{cache, with_cache_control};
+
+/// Window used when `since` is omitted.
+const DEFAULT_WINDOW_DAYS: i64 = 7;
+/// Oldest `since` accepted, to keep the audit scan bounded.
+const MAX_WINDOW_DAYS: i64 = 31;
+
+/// Query params for `GET /api/terms/{term}/changes`.
+#[derive(Debug, Deserialize, Serialize, TS, IntoParams)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct TermChangesParams {
+    /// RFC 3339 timestamp, or a `YYYY-MM-DD` date in campus time. Defaults
+    /// to 7 days ago; at most 31 days back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TermChangesResponse {
+    pub term_code: String,
+    pub term_slug: String,
+    pub since: DateTime<Utc>,
+    pub totals: TermChangeCounts,
+    /// Days with at least one change, newest first.
+    pub days: Vec<TermChangeDay>,
+}
+
+/// Parse `since` as a timestamp or a campus-time date.
+fn parse_since(raw: &str) -> Option<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()?;
+    CAMPUS_TZ
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|ts| ts.with_timezone(&Utc))
+}
+
+/// `GET /api/terms/{term}/changes?since=` -- Sections added and removed,
+/// instructor reassignments, and meeting time changes, grouped by day.
+#[utoipa::path(
+    get,
+    path = "/terms/{term}/changes",
+    tag = "courses",
+    summary = "Sections added and removed, instructor reassignments, and time changes by day",
+    params(
+        ("term" = String, Path, description = "Term code or slug (e.g. 202620 or spring-2026)"),
+        TermChangesParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = TermChangesResponse),
+    ),
+)]
+pub async fn get_term_changes(
+    State(state): State<AppState>,
+    Path(term): Path<String>,
+    Query(params): Query<TermChangesParams>,
+) -> Result<Response, ApiError> {
+    let term: Term = Term::resolve_to_code(&term)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| ApiError::invalid_term(&term))?;
+
+    let now = Utc::now();
+    let since = match params.since.as_deref() {
+        Some(raw) => parse_since(raw).ok_or_else(|| {
+            ApiError::bad_request(format!(
+                "Invalid since '{raw}': expected an RFC 3339 timestamp or YYYY-MM-DD"
+            ))
+        })?,
+        None => now - Duration::days(DEFAULT_WINDOW_DAYS),
+    };
+    if since < now - Duration::days(MAX_WINDOW_DAYS) {
+        return Err(ApiError::bad_request(format!(
+            "since may be at most {MAX_WINDOW_DAYS} days ago"
+        )));
+    }
+
+    let term_code = term.to_string();
+    let days = data::term_changes::list_changes(&state.db_pool, &term_code, None, since)
+        .await
+        .map_err(|e| db_error("Term changes", e))?;
+
+    Ok(with_cache_control(
+        TermChangesResponse {
+            term_slug: term.slug(),
+            term_code,
+            since,
+            totals: data::term_changes::total_counts(&days),
+            days,
+        },
+        cache::FRESHNESS,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn since_accepts_timestamps_and_campus_dates() {
+        assert_eq!(
+            parse_since("2026-03-02T12:00:00Z").unwrap().to_rfc3339(),
+            "2026-03-02T12:00:00+00:00"
+        );
+        // Midnight Central (UTC-6 in early March).
+        assert_eq!(
+            parse_since("2026-03-02").unwrap().to_rfc3339(),
+            "2026-03-02T06:00:00+00:00"
+        );
+        assert!(parse_since("last week").is_none());
+    }
+}
@@ -1,7 +1,8 @@
-//! Timeline API endpoint for enrollment aggregation by subject over time.
+//! Timeline API endpoint for enrollment aggregation over time.
 //!
 //! Accepts multiple time ranges, merges overlaps, aligns to 15-minute
-//! slot boundaries, and returns per-subject enrollment totals for each slot.
+//! slot boundaries, and returns enrollment totals for each slot, split into
+//! one labeled series per subject, course level, or campus.
 //! Only courses whose meeting times overlap a given slot contribute to that
 //! slot's totals -- so the chart reflects the actual class schedule rhythm.
 //!
@@ -10,16 +11,18 @@
 //! stale-while-revalidate semantics.
 
 use axum::{extract::State, response::Json};
-use chrono::{DateTime, Datelike, Duration, NaiveTime, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc};
 use chrono_tz::US::Central;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-use crate::state::AppState;
+use crate::banner::models::terms::Term;
+use crate::state::{AppState, ReferenceCache};
 use crate::web::error::ApiError;
-use crate::web::schedule_cache::weekday_bit;
+use crate::web::schedule_cache::{CachedCourse, TermSchedule, UNKNOWN_GROUP, weekday_bit};
 
 const SLOT_SECONDS: i64 = 15 * 60;
 const SLOT_MINUTES: u16 = 15;
@@ -27,17 +30,68 @@ const SLOT_MINUTES: u16 = 15;
 const MAX_RANGES: usize = 20;
 const MAX_RANGE_SPAN: Duration = Duration::hours(72);
 const MAX_TOTAL_SPAN: Duration = Duration::hours(168);
+const MAX_COMPARE_TERMS: usize = 6;
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct TimelineRequest {
     ranges: Vec<TimeRange>,
+    /// Defaults to `subject`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group_by: Option<TimelineGroupBy>,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+/// What the timeline's series are split by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
+pub enum TimelineGroupBy {
+    #[default]
+    Subject,
+    /// Course level from the course number: 1000, 2000, ...
+    Level,
+    Campus,
+}
+
+impl TimelineGroupBy {
+    /// The series a course contributes to.
+    fn key(self, course: &CachedCourse) -> &Arc<str> {
+        match self {
+            Self::Subject => &course.subject,
+            Self::Level => &course.level,
+            Self::Campus => &course.campus,
+        }
+    }
+
+    /// Display label for a series key.
+    fn label(self, key: &str, ref_cache: &ReferenceCache) -> String {
+        if key == UNKNOWN_GROUP {
+            return "Unknown".to_owned();
+        }
+        match self {
+            Self::Subject => ref_cache.lookup("subject", key).unwrap_or(key).to_owned(),
+            Self::Level => format!("{key}-level"),
+            Self::Campus => ref_cache.lookup("campus", key).unwrap_or(key).to_owned(),
+        }
+    }
+}
+
+/// One labeled series in a timeline response.
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TimelineSeries {
+    /// Key used in each slot's `totals`, e.g. a subject code or `2000`.
+    key: String,
+    label: String,
+}
+
+// The schema is named apart from the meeting `TimeRange`, a time of day.
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[schema(as = TimelineTimeRange)]
 pub struct TimeRange {
     /// ISO-8601 UTC timestamp (e.g., "2024-01-15T10:30:00Z")
     #[ts(type = "string")]
@@ -47,26 +101,63 @@ pub struct TimeRange {
     end: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct TimelineResponse {
-    /// 15-minute slots with per-subject enrollment totals, sorted by time.
+    /// 15-minute slots with per-series enrollment totals, sorted by time.
     slots: Vec<TimelineSlot>,
-    /// All subject codes present in the returned data.
-    subjects: Vec<String>,
+    group_by: TimelineGroupBy,
+    /// All series present in the returned data, sorted by key.
+    series: Vec<TimelineSeries>,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct TimelineSlot {
     /// ISO-8601 UTC timestamp at the start of this 15-minute bucket (e.g., "2024-01-15T10:30:00Z")
     #[ts(type = "string")]
     time: DateTime<Utc>,
-    /// Subject code -> total enrollment in this slot.
+    /// Series key -> total enrollment in this slot.
     #[ts(type = "Record<string, number>")]
-    subjects: BTreeMap<String, i64>,
+    totals: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TimelineCompareRequest {
+    /// Term codes or slugs. The first is the reference term that `ranges`
+    /// are expressed in.
+    terms: Vec<String>,
+    ranges: Vec<TimeRange>,
+    /// Defaults to `subject`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group_by: Option<TimelineGroupBy>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TimelineCompareResponse {
+    /// One timeline per requested term, in request order.
+    terms: Vec<TermTimeline>,
+    group_by: TimelineGroupBy,
+    /// All series present in any term, sorted by key.
+    series: Vec<TimelineSeries>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TermTimeline {
+    term: String,
+    /// Days this term's data was shifted to line up with the reference term.
+    #[ts(type = "number")]
+    offset_days: i64,
+    /// Slots at the reference term's times, with this term's totals.
+    slots: Vec<TimelineSlot>,
 }
 
 /// Floor a timestamp to the nearest 15-minute boundary.
@@ -120,26 +211,19 @@ fn generate_slots(merged: &[AlignedRange]) -> BTreeSet<DateTime<Utc>> {
     slots
 }
 
-/// `POST /api/timeline`
-///
-/// Accepts a JSON body with multiple time ranges. Returns per-subject
-/// enrollment totals bucketed into 15-minute slots. Only courses whose
-/// meeting schedule overlaps a slot contribute to that slot's count.
-pub(crate) async fn timeline(
-    State(state): State<AppState>,
-    Json(body): Json<TimelineRequest>,
-) -> Result<Json<TimelineResponse>, ApiError> {
-    if body.ranges.is_empty() {
+/// Validate request ranges, align them to slot boundaries, and merge them.
+fn align_ranges(ranges: &[TimeRange]) -> Result<Vec<AlignedRange>, ApiError> {
+    if ranges.is_empty() {
         return Err(ApiError::bad_request("At least one range is required"));
     }
-    if body.ranges.len() > MAX_RANGES {
+    if ranges.len() > MAX_RANGES {
         return Err(ApiError::bad_request(format!(
             "Too many ranges (max {MAX_RANGES})"
         )));
     }
 
-    let mut aligned: Vec<AlignedRange> = Vec::with_capacity(body.ranges.len());
-    for r in &body.ranges {
+    let mut aligned: Vec<AlignedRange> = Vec::with_capacity(ranges.len());
+    for r in ranges {
         if r.end <= r.start {
             return Err(ApiError::bad_request(format!(
                 "Range end ({}) must be after start ({})",
@@ -172,17 +256,26 @@ pub(crate) async fn timeline(
         )));
     }
 
-    state.schedule_cache.ensure_fresh();
-    let snapshot = state.schedule_cache.snapshot();
+    Ok(merged)
+}
 
-    let slot_times = generate_slots(&merged);
-    let mut all_subjects: BTreeSet<Arc<str>> = BTreeSet::new();
+/// Sum enrollment per series for each slot.
+///
+/// Courses are matched against `slot + offset`, but slots are reported at
+/// their unshifted time, so timelines with different offsets line up.
+fn aggregate_slots<'a>(
+    slot_times: &BTreeSet<DateTime<Utc>>,
+    courses: impl Iterator<Item = &'a CachedCourse> + Clone,
+    offset: Duration,
+    group_by: TimelineGroupBy,
+) -> (Vec<TimelineSlot>, BTreeSet<Arc<str>>) {
+    let mut all_keys: BTreeSet<Arc<str>> = BTreeSet::new();
 
     let slots: Vec<TimelineSlot> = slot_times
-        .into_iter()
-        .map(|utc_time| {
+        .iter()
+        .map(|&utc_time| {
             // Convert UTC slot to Central time for local day-of-week and time-of-day
-            let local = utc_time.with_timezone(&Central);
+            let local = (utc_time + offset).with_timezone(&Central);
             let local_date = local.date_naive();
             let local_time = local.time();
             let weekday = local.weekday();
@@ -192,40 +285,220 @@ pub(crate) async fn timeline(
 
             // Use Arc<str> keys internally -- Arc::clone is an atomic refcount
             // bump vs String::clone which heap-allocates every time.
-            let mut subject_totals: BTreeMap<Arc<str>, i64> = BTreeMap::new();
+            let mut key_totals: BTreeMap<Arc<str>, i64> = BTreeMap::new();
 
-            for course in &snapshot.courses {
+            for course in courses.clone() {
                 let active = course.schedules.iter().any(|s| {
                     s.active_during(local_date, wday_bit, slot_start_minutes, slot_end_minutes)
                 });
                 if active {
-                    *subject_totals
-                        .entry(Arc::clone(&course.subject))
+                    *key_totals
+                        .entry(Arc::clone(group_by.key(course)))
                         .or_default() += i64::from(course.enrollment);
                 }
             }
 
-            all_subjects.extend(subject_totals.keys().map(Arc::clone));
+            all_keys.extend(key_totals.keys().map(Arc::clone));
 
             // Convert to String keys at the response boundary.
-            let subjects: BTreeMap<String, i64> = subject_totals
+            let totals: BTreeMap<String, i64> = key_totals
                 .into_iter()
                 .map(|(k, v)| (k.to_string(), v))
                 .collect();
 
             TimelineSlot {
                 time: utc_time,
-                subjects,
+                totals,
             }
         })
         .collect();
 
-    let subjects: Vec<String> = all_subjects.into_iter().map(|s| s.to_string()).collect();
+    (slots, all_keys)
+}
 
-    Ok(Json(TimelineResponse { slots, subjects }))
+/// Label every series key seen in a response.
+fn label_series(
+    keys: BTreeSet<Arc<str>>,
+    group_by: TimelineGroupBy,
+    ref_cache: &ReferenceCache,
+) -> Vec<TimelineSeries> {
+    keys.into_iter()
+        .map(|key| TimelineSeries {
+            label: group_by.label(&key, ref_cache),
+            key: key.to_string(),
+        })
+        .collect()
+}
+
+/// `POST /api/timeline`
+///
+/// Accepts a JSON body with multiple time ranges. Returns enrollment totals
+/// per subject, course level, or campus, bucketed into 15-minute slots. Only
+/// courses whose meeting schedule overlaps a slot contribute to that slot's
+/// count.
+#[utoipa::path(
+    post,
+    path = "/timeline",
+    tag = "timeline",
+    summary = "Enrollment by subject in 15-minute slots",
+    request_body = TimelineRequest,
+    responses(
+        (status = 200, description = "Success", body = TimelineResponse),
+    ),
+)]
+pub(crate) async fn timeline(
+    State(state): State<AppState>,
+    Json(body): Json<TimelineRequest>,
+) -> Result<Json<TimelineResponse>, ApiError> {
+    let merged = align_ranges(&body.ranges)?;
+
+    state.schedule_cache.ensure_fresh();
+    let snapshot = state.schedule_cache.snapshot();
+
+    let group_by = body.group_by.unwrap_or_default();
+    let slot_times = generate_slots(&merged);
+    let (slots, keys) =
+        aggregate_slots(&slot_times, snapshot.courses(), Duration::zero(), group_by);
+    let series = label_series(keys, group_by, &*state.reference_cache.read().await);
+
+    Ok(Json(TimelineResponse {
+        slots,
+        group_by,
+        series,
+    }))
+}
+
+/// Whole weeks from `reference` to `target`, rounded to the nearest week so
+/// shifted slots keep their weekday.
+fn week_aligned_offset(reference: NaiveDate, target: NaiveDate) -> Duration {
+    let days = (target - reference).num_days();
+    let weeks = (days as f64 / 7.0).round() as i64;
+    Duration::weeks(weeks)
+}
+
+/// `POST /api/timeline/compare`
+///
+/// Multi-term version of [`timeline`] for year-over-year comparisons. The
+/// ranges are given in the first term's dates; every other term is shifted
+/// by the whole number of weeks between session start dates, so each series
+/// covers the same point in its own semester. Terms are aggregated
+/// concurrently from their cached snapshots and returned on shared slot times.
+#[utoipa::path(
+    post,
+    path = "/timeline/compare",
+    tag = "timeline",
+    summary = "Timeline series for several terms, aligned to the first",
+    request_body = TimelineCompareRequest,
+    responses(
+        (status = 200, description = "Success", body = TimelineCompareResponse),
+    ),
+)]
+pub(crate) async fn timeline_compare(
+    State(state): State<AppState>,
+    Json(body): Json<TimelineCompareRequest>,
+) -> Result<Json<TimelineCompareResponse>, ApiError> {
+    if body.terms.is_empty() {
+        return Err(ApiError::bad_request("At least one term is required"));
+    }
+    if body.terms.len() > MAX_COMPARE_TERMS {
+        return Err(ApiError::bad_request(format!(
+            "Too many terms (max {MAX_COMPARE_TERMS})"
+        )));
+    }
+    let merged = align_ranges(&body.ranges)?;
+    let group_by = body.group_by.unwrap_or_default();
+
+    state.schedule_cache.ensure_fresh();
+    let snapshot = state.schedule_cache.snapshot();
+
+    let mut selected: Vec<(String, Arc<TermSchedule>, NaiveDate)> =
+        Vec::with_capacity(body.terms.len());
+    for term in &body.terms {
+        let code = Term::resolve_to_code(term).ok_or_else(|| ApiError::invalid_term(term))?;
+        if selected.iter().any(|(c, ..)| *c == code) {
+            return Err(ApiError::bad_request(format!("Term {code} listed twice")));
+        }
+        let schedule = snapshot.terms.get(&code).cloned();
+        let Some((schedule, start_date)) =
+            schedule.and_then(|s| s.start_date.map(|start| (s, start)))
+        else {
+            return Err(ApiError::bad_request(format!(
+                "No schedule data for term {code}"
+            )));
+        };
+        selected.push((code, schedule, start_date));
+    }
+
+    let reference_start = selected[0].2;
+    let slot_times = Arc::new(generate_slots(&merged));
+
+    let tasks = selected.into_iter().map(|(term, schedule, start_date)| {
+        let slot_times = Arc::clone(&slot_times);
+        let offset = week_aligned_offset(reference_start, start_date);
+        tokio::task::spawn_blocking(move || {
+            let (slots, keys) =
+                aggregate_slots(&slot_times, schedule.courses.iter(), offset, group_by);
+            (
+                TermTimeline {
+                    term,
+                    offset_days: offset.num_days(),
+                    slots,
+                },
+                keys,
+            )
+        })
+    });
+    let results = futures::future::try_join_all(tasks).await.map_err(|e| {
+        tracing::error!(error = %e, "Timeline aggregation task failed");
+        ApiError::internal_error("Timeline aggregation failed")
+    })?;
+
+    let mut all_keys: BTreeSet<Arc<str>> = BTreeSet::new();
+    let mut terms = Vec::with_capacity(results.len());
+    for (term, keys) in results {
+        all_keys.extend(keys);
+        terms.push(term);
+    }
+    let series = label_series(all_keys, group_by, &*state.reference_cache.read().await);
+
+    Ok(Json(TimelineCompareResponse {
+        terms,
+        group_by,
+        series,
+    }))
 }
 
 /// Convert a `NaiveTime` to minutes since midnight.
 fn time_to_minutes(t: NaiveTime) -> u16 {
     (t.hour() * 60 + t.minute()) as u16
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn series_labels() {
+        let cache = ReferenceCache::new();
+        assert_eq!(TimelineGroupBy::Level.label("2000", &cache), "2000-level");
+        assert_eq!(TimelineGroupBy::Subject.label("CS", &cache), "CS");
+        assert_eq!(
+            TimelineGroupBy::Campus.label(UNKNOWN_GROUP, &cache),
+            "Unknown"
+        );
+    }
+
+    #[test]
+    fn week_aligned_offset_rounds_to_whole_weeks() {
+        let fall_2025 = NaiveDate::from_ymd_opt(2025, 8, 25).unwrap(); // Monday
+        let fall_2024 = NaiveDate::from_ymd_opt(2024, 8, 26).unwrap(); // Monday
+        assert_eq!(
+            week_aligned_offset(fall_2025, fall_2024),
+            Duration::weeks(-52)
+        );
+
+        // A Wednesday start three days later still lines up by weekday.
+        let wednesday = NaiveDate::from_ymd_opt(2025, 8, 27).unwrap();
+        assert_eq!(week_aligned_offset(fall_2025, wednesday), Duration::zero());
+    }
+}
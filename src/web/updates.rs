@@ -0,0 +1,211 @@
+//! Long-poll fallback for live course updates.
+//!
+//! Clients on networks that block WebSockets poll `GET /api/updates/poll`
+//! with the cursor from their previous response. The request returns as soon
+//! as matching course changes are buffered, or empty after the wait expires.
+//! Cursors index the shared [`EventBuffer`](crate::data::events::EventBuffer);
+//! a cursor that fell out of the buffer (or predates a restart) yields
+//! `reset: true`, telling the client to refetch instead of patching.
+//!
+//! A waiting request holds a slot in
+//! [`AppState::stream_connections`](crate::state::AppState::stream_connections),
+//! like a WebSocket, so one address can't pile up open polls.
+
+use std::time::Duration;
+
+use axum::extract::{Query, State};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use tracing::warn;
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::data::events::DomainEvent;
+use crate::state::AppState;
+use crate::web::audit::AuditLogEntry;
+use crate::web::error::ApiError;
+use crate::web::middleware::client_ip::ClientIp;
+use crate::web::routes::{cache, with_cache_control};
+
+/// Default time to hold a request open when nothing matches.
+const DEFAULT_WAIT: Duration = Duration::from_secs(25);
+/// Upper bound on the wait, well under the 60s request timeout layer.
+const MAX_WAIT: Duration = Duration::from_secs(45);
+
+#[derive(Debug, Deserialize, Serialize, TS, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct UpdatesPollParams {
+    /// Cursor from the previous response; omit to get the current head.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(type = "number")]
+    pub cursor: Option<u64>,
+    /// Only return changes to courses in this term.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
+    /// Only return changes to courses in this subject.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Seconds to wait for a change (default 25, max 45).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(type = "number")]
+    pub wait: Option<u64>,
+}
+
+impl UpdatesPollParams {
+    fn matches(&self, entry: &AuditLogEntry) -> bool {
+        let term_ok = self
+            .term
+            .as_deref()
+            .is_none_or(|t| entry.term_code.as_deref() == Some(t));
+        let subject_ok = self
+            .subject
+            .as_deref()
+            .is_none_or(|s| entry.subject.as_deref() == Some(s));
+        term_ok && subject_ok
+    }
+
+    fn wait(&self) -> Duration {
+        self.wait
+            .map_or(DEFAULT_WAIT, Duration::from_secs)
+            .min(MAX_WAIT)
+    }
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UpdatesPollResponse {
+    /// Pass back as `cursor` on the next poll.
+    #[ts(type = "number")]
+    pub cursor: u64,
+    /// The supplied cursor is no longer in the buffer; refetch current state.
+    pub reset: bool,
+    pub entries: Vec<AuditLogEntry>,
+}
+
+/// `GET /api/updates/poll?cursor=` -- Course changes since `cursor`, waiting briefly if none.
+#[utoipa::path(
+    get,
+    path = "/updates/poll",
+    tag = "updates",
+    summary = "Long-poll for course changes",
+    params(
+        UpdatesPollParams,
+    ),
+    responses(
+        (status = 200, description = "Success", body = UpdatesPollResponse),
+    ),
+)]
+pub async fn poll_updates(
+    ClientIp(ip): ClientIp,
+    State(state): State<AppState>,
+    Query(params): Query<UpdatesPollParams>,
+) -> Response {
+    let events = &state.events;
+    // Subscribe before reading so a publish between the read and the wait
+    // still wakes us.
+    let (head, mut head_rx) = events.subscribe();
+
+    let respond = |cursor, reset, entries| {
+        with_cache_control(
+            UpdatesPollResponse {
+                cursor,
+                reset,
+                entries,
+            },
+            cache::LIVE,
+        )
+    };
+
+    let Some(mut cursor) = params.cursor else {
+        return respond(head, false, Vec::new());
+    };
+    if cursor > head {
+        return respond(head, true, Vec::new());
+    }
+
+    // Held until the response is built; the early returns above don't wait.
+    let _guard = match state.stream_connections.try_acquire(ip) {
+        Ok(guard) => guard,
+        Err(e) => {
+            warn!(client_ip = %ip, reason = ?e, "Update poll rejected");
+            return ApiError::from(e).into_response();
+        }
+    };
+
+    let deadline = Instant::now() + params.wait();
+    loop {
+        let mut entries = Vec::new();
+        while let Some(event) = events.read(cursor) {
+            cursor += 1;
+            if let DomainEvent::CourseChanges(event) = event {
+                entries.extend(
+                    event
+                        .audit_entries()
+                        .into_iter()
+                        .filter(|e| params.matches(e)),
+                );
+            }
+        }
+
+        if cursor < events.base_offset() {
+            return respond(*head_rx.borrow(), true, Vec::new());
+        }
+        if !entries.is_empty() {
+            return respond(cursor, false, entries);
+        }
+
+        match tokio::time::timeout_at(deadline, head_rx.changed()).await {
+            Ok(Ok(())) => continue,
+            _ => return respond(cursor, false, Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(term: &str, subject: &str) -> AuditLogEntry {
+        AuditLogEntry {
+            id: 1,
+            course_id: 1,
+            timestamp: String::new(),
+            field_changed: "enrollment".into(),
+            old_value: None,
+            new_value: serde_json::json!(1),
+            subject: Some(subject.into()),
+            course_number: None,
+            crn: None,
+            course_title: None,
+            term_code: Some(term.into()),
+        }
+    }
+
+    #[test]
+    fn filters_by_term_and_subject() {
+        let params = UpdatesPollParams {
+            cursor: None,
+            term: Some("202620".into()),
+            subject: Some("CS".into()),
+            wait: None,
+        };
+        assert!(params.matches(&entry("202620", "CS")));
+        assert!(!params.matches(&entry("202610", "CS")));
+        assert!(!params.matches(&entry("202620", "MAT")));
+    }
+
+    #[test]
+    fn wait_is_capped() {
+        let params = UpdatesPollParams {
+            cursor: None,
+            term: None,
+            subject: None,
+            wait: Some(600),
+        };
+        assert_eq!(params.wait(), MAX_WAIT);
+    }
+}
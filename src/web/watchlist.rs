@@ -0,0 +1,356 @@
+//! Signed-in users' course watches: the web counterpart of `/watch`.
+//!
+//! Watches created here behave like bot-created ones -- the notification
+//! service fires them off course audit events -- but may name a webhook to
+//! receive the alert instead of a Discord DM. Attribute and restriction
+//! watches can also follow a whole department (a subject in one term).
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use ts_rs::TS;
+
+use crate::banner::models::terms::Term;
+use crate::data::watches::{self, WatchType};
+use crate::data::{identities, reference};
+use crate::state::AppState;
+use crate::utils::webhook_url::validate_webhook_url;
+use crate::web::auth::extractors::AuthUser;
+use crate::web::error::{ApiError, OptionNotFoundExt, db_error};
+
+/// Most active watches one user may hold.
+const MAX_WATCHES_PER_USER: i64 = 50;
+
+/// One of the caller's active watches.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WatchlistEntry {
+    pub term: String,
+    pub crn: String,
+    pub subject: String,
+    pub course_number: String,
+    pub title: String,
+    pub watch_type: WatchType,
+    /// When the watch last fired; it won't fire again for 15 minutes.
+    pub notified_at: Option<DateTime<Utc>>,
+    /// Where alerts are posted; `null` means a Discord DM.
+    pub webhook_url: Option<String>,
+}
+
+/// Body for `POST /api/watchlist`.
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AddWatchBody {
+    pub term: String,
+    pub crn: String,
+    /// Defaults to `seatsAvailable`.
+    #[serde(default)]
+    pub watch_type: Option<WatchType>,
+    /// HTTPS endpoint to receive alerts instead of a Discord DM.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+/// Response for `POST /api/watchlist`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AddWatchResponse {
+    /// False when an existing watch was reactivated.
+    pub created: bool,
+}
+
+/// One of the caller's department watches.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DepartmentWatchEntry {
+    pub term: String,
+    pub subject: String,
+    pub watch_type: WatchType,
+    /// Where alerts are posted; `null` means a Discord DM.
+    pub webhook_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for `POST /api/watchlist/departments`.
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AddDepartmentWatchBody {
+    pub term: String,
+    pub subject: String,
+    /// `attributeChange` or `restrictionChange`.
+    pub watch_type: WatchType,
+    /// HTTPS endpoint to receive alerts instead of a Discord DM.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemoveWatchParams {
+    /// Remove only this watch type; all of the course's watches otherwise.
+    #[serde(rename = "type")]
+    pub watch_type: Option<WatchType>,
+}
+
+/// Resolve `{term}` (code or slug) and CRN to a course ID.
+async fn resolve_course(state: &AppState, term: &str, crn: &str) -> Result<i32, ApiError> {
+    let term_code = Term::resolve_to_code(term).ok_or_else(|| ApiError::invalid_term(term))?;
+    crate::data::courses::get_id_by_crn(&state.db_pool, &term_code, crn)
+        .await
+        .map_err(|e| db_error("Course lookup", e))?
+        .or_not_found("Course", crn)
+}
+
+/// Resolve `{term}` (code or slug) and a subject code that must exist.
+async fn resolve_department(
+    state: &AppState,
+    term: &str,
+    subject: &str,
+) -> Result<(String, String), ApiError> {
+    let term_code = Term::resolve_to_code(term).ok_or_else(|| ApiError::invalid_term(term))?;
+    let subject = subject.trim().to_uppercase();
+    let known = reference::has_code(&state.db_pool, "subject", &subject)
+        .await
+        .map_err(|e| db_error("Subject lookup", e))?;
+    if !known {
+        return Err(ApiError::not_found(format!("Unknown subject '{subject}'")));
+    }
+    Ok((term_code, subject))
+}
+
+/// Validate an optional webhook URL, treating blank as absent.
+fn parse_webhook_url(url: Option<&str>) -> Result<Option<String>, ApiError> {
+    url.filter(|u| !u.trim().is_empty())
+        .map(validate_webhook_url)
+        .transpose()
+        .map_err(|e| ApiError::bad_request(e.to_string()))
+}
+
+/// `GET /api/watchlist` -- The caller's active watches.
+#[instrument(skip_all)]
+pub async fn list_watches(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<Vec<WatchlistEntry>>, ApiError> {
+    let items = watches::list_active_watches(&state.db_pool, user.id)
+        .await
+        .map_err(|e| db_error("List watches", e))?;
+
+    let entries = items
+        .into_iter()
+        .filter_map(|item| {
+            Some(WatchlistEntry {
+                watch_type: item.watch_type.parse().ok()?,
+                term: item.term_code,
+                crn: item.crn,
+                subject: item.subject,
+                course_number: item.course_number,
+                title: item.title,
+                notified_at: item.notified_at,
+                webhook_url: item.webhook_url,
+            })
+        })
+        .collect();
+    Ok(Json(entries))
+}
+
+/// Refuse DM notifications for a user with no linked Discord account to
+/// send them to.
+pub(crate) async fn require_discord_dm(state: &AppState, user_id: i64) -> Result<(), ApiError> {
+    let linked = identities::has_discord(&state.db_pool, user_id)
+        .await
+        .map_err(|e| db_error("Check Discord identity", e))?;
+    if linked {
+        Ok(())
+    } else {
+        Err(ApiError::bad_request(
+            "Link a Discord account to get DM notifications",
+        ))
+    }
+}
+
+/// Refuse a new watch once the user holds [`MAX_WATCHES_PER_USER`].
+async fn require_watch_capacity(state: &AppState, user_id: i64) -> Result<(), ApiError> {
+    let active = watches::count_active_watches(&state.db_pool, user_id)
+        .await
+        .map_err(|e| db_error("Count watches", e))?;
+    if active >= MAX_WATCHES_PER_USER {
+        return Err(ApiError::conflict(format!(
+            "You can have at most {MAX_WATCHES_PER_USER} active watches"
+        )));
+    }
+    Ok(())
+}
+
+/// `POST /api/watchlist` -- Watch a section, or reactivate an existing watch.
+#[instrument(skip_all)]
+pub async fn add_watch(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<AddWatchBody>,
+) -> Result<(StatusCode, Json<AddWatchResponse>), ApiError> {
+    let webhook_url = parse_webhook_url(body.webhook_url.as_deref())?;
+    if webhook_url.is_none() {
+        require_discord_dm(&state, user.id).await?;
+    }
+    let watch_type = body.watch_type.unwrap_or(WatchType::SeatsAvailable);
+    let course_id = resolve_course(&state, &body.term, &body.crn).await?;
+    require_watch_capacity(&state, user.id).await?;
+
+    let created = watches::upsert_watch(
+        &state.db_pool,
+        user.id,
+        course_id,
+        &watch_type,
+        webhook_url.as_deref(),
+    )
+    .await
+    .map_err(|e| db_error("Add watch", e))?;
+
+    info!(
+        user_id = user.id,
+        course_id,
+        watch_type = watch_type.as_str(),
+        webhook = webhook_url.is_some(),
+        created,
+        "Watch added from web"
+    );
+
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(AddWatchResponse { created })))
+}
+
+/// `DELETE /api/watchlist/{term}/{crn}?type=` -- Stop watching a section.
+#[instrument(skip_all)]
+pub async fn remove_watch(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((term, crn)): Path<(String, String)>,
+    Query(params): Query<RemoveWatchParams>,
+) -> Result<StatusCode, ApiError> {
+    let course_id = resolve_course(&state, &term, &crn).await?;
+
+    let removed = match &params.watch_type {
+        Some(watch_type) => watches::delete_watch(&state.db_pool, user.id, course_id, watch_type)
+            .await
+            .map(u64::from),
+        None => watches::delete_all_watches_for_course(&state.db_pool, user.id, course_id).await,
+    }
+    .map_err(|e| db_error("Remove watch", e))?;
+
+    if removed == 0 {
+        return Err(ApiError::not_found("No watch found for this course"));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/watchlist/departments` -- The caller's department watches.
+#[instrument(skip_all)]
+pub async fn list_department_watches(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<Vec<DepartmentWatchEntry>>, ApiError> {
+    let items = watches::list_department_watches(&state.db_pool, user.id)
+        .await
+        .map_err(|e| db_error("List department watches", e))?;
+
+    let entries = items
+        .into_iter()
+        .filter_map(|item| {
+            Some(DepartmentWatchEntry {
+                watch_type: item.watch_type.parse().ok()?,
+                term: item.term_code,
+                subject: item.subject,
+                webhook_url: item.webhook_url,
+                created_at: item.created_at,
+            })
+        })
+        .collect();
+    Ok(Json(entries))
+}
+
+/// `POST /api/watchlist/departments` -- Follow attribute or restriction edits
+/// on every section of a subject in a term.
+#[instrument(skip_all)]
+pub async fn add_department_watch(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Json(body): Json<AddDepartmentWatchBody>,
+) -> Result<(StatusCode, Json<AddWatchResponse>), ApiError> {
+    if !body.watch_type.is_department_scoped() {
+        return Err(ApiError::bad_request(
+            "Department watches must be attributeChange or restrictionChange",
+        ));
+    }
+    let webhook_url = parse_webhook_url(body.webhook_url.as_deref())?;
+    if webhook_url.is_none() {
+        require_discord_dm(&state, user.id).await?;
+    }
+    let (term_code, subject) = resolve_department(&state, &body.term, &body.subject).await?;
+    require_watch_capacity(&state, user.id).await?;
+
+    let created = watches::upsert_department_watch(
+        &state.db_pool,
+        user.id,
+        &term_code,
+        &subject,
+        &body.watch_type,
+        webhook_url.as_deref(),
+    )
+    .await
+    .map_err(|e| db_error("Add department watch", e))?;
+
+    info!(
+        user_id = user.id,
+        term_code,
+        subject,
+        watch_type = body.watch_type.as_str(),
+        webhook = webhook_url.is_some(),
+        created,
+        "Department watch added from web"
+    );
+
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(AddWatchResponse { created })))
+}
+
+/// `DELETE /api/watchlist/departments/{term}/{subject}?type=` -- Stop
+/// following a department.
+#[instrument(skip_all)]
+pub async fn remove_department_watch(
+    State(state): State<AppState>,
+    AuthUser(user): AuthUser,
+    Path((term, subject)): Path<(String, String)>,
+    Query(params): Query<RemoveWatchParams>,
+) -> Result<StatusCode, ApiError> {
+    let term_code = Term::resolve_to_code(&term).ok_or_else(|| ApiError::invalid_term(&term))?;
+    let removed = watches::delete_department_watches(
+        &state.db_pool,
+        user.id,
+        &term_code,
+        &subject.trim().to_uppercase(),
+        params.watch_type.as_ref(),
+    )
+    .await
+    .map_err(|e| db_error("Remove department watch", e))?;
+
+    if removed == 0 {
+        return Err(ApiError::not_found("No watch found for this department"));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
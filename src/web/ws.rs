@@ -1,9 +1,13 @@
-//! WebSocket event types and DTOs for admin streams.
+//! WebSocket event types and DTOs for admin streams, and the heartbeat and
+//! eviction policy applied to every stream connection.
 
 use serde::Serialize;
+use std::time::{Duration, Instant};
 use ts_rs::TS;
 
-use crate::data::models::{ScrapeJob, ScrapeJobStatus, ScrapePriority, TargetType};
+use crate::data::models::{
+    ScrapeDiffSummary, ScrapeJob, ScrapeJobStatus, ScrapePriority, TargetType,
+};
 use crate::data::unsigned::Count;
 
 /// A serializable DTO for `ScrapeJob` with computed `status`.
@@ -22,6 +26,10 @@ pub struct ScrapeJobDto {
     pub max_retries: Count,
     pub queued_at: String,
     pub status: ScrapeJobStatus,
+    pub dead_lettered_at: Option<String>,
+    pub last_error: Option<String>,
+    /// Diff from the latest completed run with the same payload, when known.
+    pub last_diff: Option<ScrapeDiffSummary>,
 }
 
 impl From<&ScrapeJob> for ScrapeJobDto {
@@ -38,6 +46,9 @@ impl From<&ScrapeJob> for ScrapeJobDto {
             max_retries: job.max_retries,
             queued_at: job.queued_at.to_rfc3339(),
             status: job.status(),
+            dead_lettered_at: job.dead_lettered_at.map(|t| t.to_rfc3339()),
+            last_error: job.last_error.clone(),
+            last_diff: job.last_diff.as_ref().map(|d| d.0.clone()),
         }
     }
 }
@@ -59,6 +70,8 @@ pub enum ScrapeJobEvent {
     Completed {
         id: i32,
         subject: Option<String>,
+        term: Option<String>,
+        diff: Option<ScrapeDiffSummary>,
     },
     Retried {
         id: i32,
@@ -75,3 +88,130 @@ pub enum ScrapeJobEvent {
         id: i32,
     },
 }
+
+/// Keepalive and lifetime limits for stream connections.
+///
+/// The server pings every `ping_interval`; browsers answer with a pong
+/// automatically. A connection that has sent nothing (pong or otherwise) for
+/// `idle_timeout` is treated as dead, and every connection is closed after
+/// `max_lifetime` so clients reconnect and pick up fresh sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeartbeatPolicy {
+    pub ping_interval: Duration,
+    pub idle_timeout: Duration,
+    pub max_lifetime: Duration,
+}
+
+impl Default for HeartbeatPolicy {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(75),
+            max_lifetime: Duration::from_secs(4 * 60 * 60),
+        }
+    }
+}
+
+/// Why a stream connection ended, as counted in metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The client closed the socket or went away cleanly.
+    Client,
+    /// A send failed; the peer is already gone.
+    SendFailed,
+    /// No traffic, not even pongs, within the idle timeout.
+    IdleTimeout,
+    /// Open longer than the maximum lifetime.
+    MaxLifetime,
+    /// The server's event source shut down.
+    Shutdown,
+}
+
+impl CloseReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::SendFailed => "send_failed",
+            Self::IdleTimeout => "idle_timeout",
+            Self::MaxLifetime => "max_lifetime",
+            Self::Shutdown => "shutdown",
+        }
+    }
+
+    /// Whether the server chose to close the connection.
+    pub fn is_eviction(self) -> bool {
+        matches!(self, Self::IdleTimeout | Self::MaxLifetime)
+    }
+}
+
+/// Tracks one connection's age and last inbound traffic against a policy.
+#[derive(Debug, Clone)]
+pub struct ConnectionClock {
+    policy: HeartbeatPolicy,
+    opened_at: Instant,
+    last_seen: Instant,
+}
+
+impl ConnectionClock {
+    pub fn new(policy: HeartbeatPolicy, now: Instant) -> Self {
+        Self {
+            policy,
+            opened_at: now,
+            last_seen: now,
+        }
+    }
+
+    /// Note inbound traffic from the client.
+    pub fn touch(&mut self, now: Instant) {
+        self.last_seen = now;
+    }
+
+    /// The reason to evict the connection at `now`, if any.
+    pub fn check(&self, now: Instant) -> Option<CloseReason> {
+        if now.duration_since(self.opened_at) >= self.policy.max_lifetime {
+            Some(CloseReason::MaxLifetime)
+        } else if now.duration_since(self.last_seen) >= self.policy.idle_timeout {
+            Some(CloseReason::IdleTimeout)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> HeartbeatPolicy {
+        HeartbeatPolicy {
+            ping_interval: Duration::from_secs(10),
+            idle_timeout: Duration::from_secs(30),
+            max_lifetime: Duration::from_secs(100),
+        }
+    }
+
+    #[test]
+    fn idle_connection_is_evicted_until_touched() {
+        let start = Instant::now();
+        let mut clock = ConnectionClock::new(policy(), start);
+        assert_eq!(clock.check(start + Duration::from_secs(29)), None);
+        assert_eq!(
+            clock.check(start + Duration::from_secs(30)),
+            Some(CloseReason::IdleTimeout)
+        );
+
+        clock.touch(start + Duration::from_secs(25));
+        assert_eq!(clock.check(start + Duration::from_secs(50)), None);
+    }
+
+    #[test]
+    fn max_lifetime_applies_to_active_connections() {
+        let start = Instant::now();
+        let mut clock = ConnectionClock::new(policy(), start);
+        clock.touch(start + Duration::from_secs(99));
+        assert_eq!(
+            clock.check(start + Duration::from_secs(100)),
+            Some(CloseReason::MaxLifetime)
+        );
+    }
+}
@@ -123,7 +123,7 @@ export default defineConfig({
         "format-apply": "cargo fmt --all",
         lint: "cargo clippy --all-features --all-targets -- -D warnings",
         "type-check": "cargo check --all-features",
-        test: "cargo nextest run -E 'not test(export_bindings)'",
+        test: "cargo nextest run --features testing -E 'not test(export_bindings)'",
       },
     },
     security: {
@@ -410,12 +410,12 @@ export default defineConfig({
         if (input === "web") {
           tempoRun(["bun", "run", "--cwd", "web", "test"]);
         } else if (input === "rust") {
-          tempoRun(["cargo", "nextest", "run", "-E", "not test(export_bindings)"]);
+          tempoRun(["cargo", "nextest", "run", "--features", "testing", "-E", "not test(export_bindings)"]);
         } else if (input === "") {
-          tempoRun(["cargo", "nextest", "run", "-E", "not test(export_bindings)"]);
+          tempoRun(["cargo", "nextest", "run", "--features", "testing", "-E", "not test(export_bindings)"]);
           tempoRun(["bun", "run", "--cwd", "web", "test"]);
         } else {
-          tempoRun(["cargo", "nextest", "run", ...input.split(/\s+/)]);
+          tempoRun(["cargo", "nextest", "run", "--features", "testing", ...input.split(/\s+/)]);
         }
         return 0;
       },
@@ -0,0 +1,180 @@
+mod helpers;
+
+use banner::data::admin_bluebook::{
+    LinkDecision, ListBluebookLinksFilter, bulk_review_links, list_links, list_unmatched_links,
+};
+use banner::data::batch::batch_upsert_courses;
+use banner::data::names::NameMatchQuality;
+use helpers::insert_admin;
+use sqlx::PgPool;
+
+async fn insert_instructor(pool: &PgPool, display_name: &str, email: &str) -> i32 {
+    let (id,): (i32,) = sqlx::query_as(
+        "INSERT INTO instructors (display_name, email) VALUES ($1, $2) RETURNING id",
+    )
+    .bind(display_name)
+    .bind(email)
+    .fetch_one(pool)
+    .await
+    .expect("failed to create instructor");
+    id
+}
+
+async fn insert_link(
+    pool: &PgPool,
+    instructor_name: &str,
+    instructor_id: Option<i32>,
+    status: &str,
+) -> i32 {
+    let (id,): (i32,) = sqlx::query_as(
+        "INSERT INTO instructor_bluebook_links (instructor_name, instructor_id, status)
+         VALUES ($1, $2, $3)
+         RETURNING id",
+    )
+    .bind(instructor_name)
+    .bind(instructor_id)
+    .bind(status)
+    .fetch_one(pool)
+    .await
+    .expect("failed to create link");
+    id
+}
+
+async fn insert_evaluation(
+    pool: &PgPool,
+    instructor_name: &str,
+    subject: &str,
+    term: &str,
+    crn: &str,
+) {
+    sqlx::query(
+        "INSERT INTO bluebook_evaluations (subject, course_number, section, term, instructor_name, crn)
+         VALUES ($1, '1100', '001', $2, $3, $4)",
+    )
+    .bind(subject)
+    .bind(term)
+    .bind(instructor_name)
+    .bind(crn)
+    .execute(pool)
+    .await
+    .expect("failed to create evaluation");
+}
+
+async fn status_of(pool: &PgPool, link_id: i32) -> String {
+    let (status,): (String,) =
+        sqlx::query_as("SELECT status FROM instructor_bluebook_links WHERE id = $1")
+            .bind(link_id)
+            .fetch_one(pool)
+            .await
+            .expect("failed to fetch link status");
+    status
+}
+
+#[sqlx::test]
+async fn bulk_approve_skips_unmatched_and_decided_links(pool: PgPool) {
+    let admin = insert_admin(&pool).await;
+    let instructor = insert_instructor(&pool, "Smith, John", "john.smith@utsa.edu").await;
+    let matched = insert_link(&pool, "Smith, John David", Some(instructor), "pending").await;
+    let unmatched = insert_link(&pool, "Doe, Jane", None, "pending").await;
+    let decided = insert_link(&pool, "Roe, Rick", Some(instructor), "rejected").await;
+
+    let response = bulk_review_links(
+        &pool,
+        &[matched, unmatched, decided, 999_999],
+        LinkDecision::Approve,
+        admin,
+    )
+    .await
+    .expect("bulk approve should succeed");
+
+    assert_eq!(response.updated, vec![matched]);
+    let mut expected_skipped = vec![unmatched, decided, 999_999];
+    expected_skipped.sort_unstable();
+    assert_eq!(response.skipped, expected_skipped);
+    assert_eq!(status_of(&pool, matched).await, "approved");
+    assert_eq!(status_of(&pool, unmatched).await, "pending");
+    assert_eq!(status_of(&pool, decided).await, "rejected");
+
+    // Rejection doesn't need a proposed instructor.
+    let response = bulk_review_links(&pool, &[unmatched], LinkDecision::Reject, admin)
+        .await
+        .expect("bulk reject should succeed");
+    assert_eq!(response.updated, vec![unmatched]);
+    assert_eq!(status_of(&pool, unmatched).await, "rejected");
+}
+
+#[sqlx::test]
+async fn pending_links_carry_review_context(pool: PgPool) {
+    let instructor = insert_instructor(&pool, "Smith, John", "john.smith@utsa.edu").await;
+    insert_link(&pool, "Smith, John David", Some(instructor), "pending").await;
+    insert_link(&pool, "Doe, Jane", None, "approved").await;
+    insert_evaluation(&pool, "Smith, John David", "CS", "202410", "").await;
+    insert_evaluation(&pool, "Smith, John David", "MAT", "202510", "").await;
+
+    let response = list_links(
+        &pool,
+        &ListBluebookLinksFilter {
+            status: Some("pending".to_owned()),
+            search: None,
+            page: 1,
+            per_page: 50,
+        },
+    )
+    .await
+    .expect("list should succeed");
+
+    assert_eq!(response.links.len(), 1);
+    let link = &response.links[0];
+    assert_eq!(link.name_match, Some(NameMatchQuality::Partial));
+    assert_eq!(link.eval_subjects, vec!["CS", "MAT"]);
+    assert_eq!(link.eval_terms, vec!["202510", "202410"]);
+    assert!(link.instructor_subjects.is_empty());
+}
+
+#[sqlx::test]
+async fn unmatched_links_suggest_section_instructors(pool: PgPool) {
+    let admin = insert_admin(&pool).await;
+    let instructor = insert_instructor(&pool, "Garcia, Maria", "maria.garcia@utsa.edu").await;
+    let unmatched = insert_link(&pool, "Doe-Garcia, M", None, "pending").await;
+    insert_link(&pool, "Garcia, Maria", Some(instructor), "pending").await;
+
+    let course = helpers::make_course("12345", "202510", "CS", "1083", "Intro", (10, 30, 0, 5));
+    batch_upsert_courses(&[course], &pool)
+        .await
+        .expect("failed to insert course");
+    sqlx::query(
+        "INSERT INTO course_instructors (course_id, instructor_id, banner_id)
+         SELECT id, $1, 'G001' FROM courses WHERE crn = '12345'",
+    )
+    .bind(instructor)
+    .execute(&pool)
+    .await
+    .expect("failed to link course instructor");
+    insert_evaluation(&pool, "Doe-Garcia, M", "CS", "202510", "12345").await;
+
+    let response = list_unmatched_links(&pool, None, 1, 50)
+        .await
+        .expect("list should succeed");
+    assert_eq!(response.total.get(), 1);
+    let entry = &response.links[0];
+    assert_eq!(entry.link.id, unmatched);
+    assert_eq!(entry.suggestions.len(), 1);
+    assert_eq!(entry.suggestions[0].instructor_id, instructor);
+    assert_eq!(entry.suggestions[0].shared_sections.get(), 1);
+
+    // Resolving the link records the reviewer and drops it from the queue.
+    banner::data::admin_bluebook::assign_link(&pool, unmatched, instructor, admin)
+        .await
+        .expect("assign should succeed");
+    let response = list_unmatched_links(&pool, None, 1, 50)
+        .await
+        .expect("list should succeed");
+    assert!(response.links.is_empty());
+
+    let detail = banner::data::admin_bluebook::get_link_detail(&pool, unmatched)
+        .await
+        .expect("detail should succeed");
+    assert_eq!(detail.status, "approved");
+    assert_eq!(detail.resolved_by.as_deref(), Some("admin"));
+    assert!(detail.resolved_at.is_some());
+}
@@ -1,8 +1,69 @@
 mod helpers;
 
+use banner::data::admin_rmp::{
+    CandidateReviewError, ListCandidatesFilter, MAX_BULK_CANDIDATES, MatchDecision,
+    list_candidates, review_candidates,
+};
 use banner::data::rmp::unmatch_instructor;
+use helpers::insert_admin;
 use sqlx::PgPool;
 
+const BREAKDOWN: &str = r#"{"name":1.0,"department":0.5,"reviewCourses":0.0,"subject":0.5,"uniqueness":1.0,"volume":0.4}"#;
+
+async fn insert_instructor(pool: &PgPool, display_name: &str) -> i32 {
+    let (id,): (i32,) = sqlx::query_as(
+        "INSERT INTO instructors (display_name, rmp_match_status) VALUES ($1, 'pending') RETURNING id",
+    )
+    .bind(display_name)
+    .fetch_one(pool)
+    .await
+    .expect("failed to create instructor");
+    id
+}
+
+async fn insert_professor(pool: &PgPool, legacy_id: i32) {
+    sqlx::query(
+        "INSERT INTO rmp_professors (legacy_id, graphql_id, first_name, last_name, num_ratings)
+         VALUES ($1, $2, 'Test', 'Professor', 10)",
+    )
+    .bind(legacy_id)
+    .bind(format!("graphql-{legacy_id}"))
+    .execute(pool)
+    .await
+    .expect("failed to create rmp professor");
+}
+
+async fn insert_candidate(
+    pool: &PgPool,
+    instructor_id: i32,
+    rmp_legacy_id: i32,
+    score: f32,
+) -> i32 {
+    let (id,): (i32,) = sqlx::query_as(
+        "INSERT INTO rmp_match_candidates (instructor_id, rmp_legacy_id, score, score_breakdown)
+         VALUES ($1, $2, $3, $4::jsonb)
+         RETURNING id",
+    )
+    .bind(instructor_id)
+    .bind(rmp_legacy_id)
+    .bind(score)
+    .bind(BREAKDOWN)
+    .fetch_one(pool)
+    .await
+    .expect("failed to create candidate");
+    id
+}
+
+async fn candidate_status(pool: &PgPool, id: i32) -> String {
+    let (status,): (String,) =
+        sqlx::query_as("SELECT status FROM rmp_match_candidates WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .expect("failed to fetch candidate status");
+    status
+}
+
 /// Test that unmatching an instructor resets accepted candidates back to pending.
 ///
 /// When a user unmatches an instructor, accepted candidates should be reset to
@@ -100,3 +161,124 @@ async fn unmatch_resets_accepted_candidates_to_pending(pool: PgPool) {
         "instructor should be unmatched"
     );
 }
+
+#[sqlx::test]
+async fn candidate_queue_lists_pending_by_score(pool: PgPool) {
+    let alice = insert_instructor(&pool, "Smith, Alice").await;
+    let bob = insert_instructor(&pool, "Jones, Bob").await;
+    insert_professor(&pool, 1001).await;
+    insert_professor(&pool, 1002).await;
+    let low = insert_candidate(&pool, alice, 1001, 0.4).await;
+    let high = insert_candidate(&pool, bob, 1002, 0.9).await;
+    let resolved = insert_candidate(&pool, alice, 1002, 0.7).await;
+    sqlx::query("UPDATE rmp_match_candidates SET status = 'rejected' WHERE id = $1")
+        .bind(resolved)
+        .execute(&pool)
+        .await
+        .expect("failed to resolve candidate");
+
+    let filter = ListCandidatesFilter {
+        status: Some("pending".to_owned()),
+        search: None,
+        min_score: None,
+        page: 1,
+        per_page: 50,
+    };
+    let response = list_candidates(&pool, &filter)
+        .await
+        .expect("list should succeed");
+
+    assert_eq!(response.total, 2);
+    let ids: Vec<i32> = response.candidates.iter().map(|c| c.id).collect();
+    assert_eq!(ids, vec![high, low]);
+    assert_eq!(response.candidates[0].instructor_name, "Jones, Bob");
+    assert_eq!(response.candidates[0].score_breakdown.name, 1.0);
+
+    let filter = ListCandidatesFilter {
+        search: Some("alice".to_owned()),
+        ..filter
+    };
+    let response = list_candidates(&pool, &filter)
+        .await
+        .expect("search should succeed");
+    assert_eq!(response.total, 1);
+    assert_eq!(response.candidates[0].id, low);
+}
+
+/// Bulk accept links each pending candidate and confirms its instructor, but
+/// leaves a candidate alone when its RMP profile belongs to someone else.
+#[sqlx::test]
+async fn bulk_accept_links_and_reports_conflicts(pool: PgPool) {
+    let admin = insert_admin(&pool).await;
+    let alice = insert_instructor(&pool, "Smith, Alice").await;
+    let bob = insert_instructor(&pool, "Jones, Bob").await;
+    let carol = insert_instructor(&pool, "Lee, Carol").await;
+    insert_professor(&pool, 2001).await;
+    insert_professor(&pool, 2002).await;
+    let alice_candidate = insert_candidate(&pool, alice, 2001, 0.9).await;
+    let bob_candidate = insert_candidate(&pool, bob, 2002, 0.8).await;
+    // Carol's candidate points at the profile Alice is about to claim.
+    let carol_candidate = insert_candidate(&pool, carol, 2001, 0.6).await;
+
+    let response = review_candidates(
+        &pool,
+        &[alice_candidate, bob_candidate, carol_candidate, 999_999],
+        MatchDecision::Confirm,
+        admin,
+    )
+    .await
+    .expect("bulk accept should succeed");
+
+    assert_eq!(response.updated, vec![alice_candidate, bob_candidate]);
+    assert_eq!(response.conflicts, vec![carol_candidate]);
+    assert_eq!(response.skipped, vec![999_999]);
+    assert_eq!(candidate_status(&pool, alice_candidate).await, "accepted");
+    assert_eq!(candidate_status(&pool, carol_candidate).await, "pending");
+
+    let statuses: Vec<(i32, String)> =
+        sqlx::query_as("SELECT id, rmp_match_status FROM instructors ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .expect("failed to fetch instructor statuses");
+    assert_eq!(
+        statuses,
+        vec![
+            (alice, "confirmed".to_owned()),
+            (bob, "confirmed".to_owned()),
+            (carol, "pending".to_owned()),
+        ]
+    );
+
+    let (links,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM instructor_rmp_links")
+        .fetch_one(&pool)
+        .await
+        .expect("failed to count links");
+    assert_eq!(links, 2);
+
+    // The conflicting candidate can still be rejected; resolved ones are skipped.
+    let response = review_candidates(
+        &pool,
+        &[carol_candidate, alice_candidate],
+        MatchDecision::Reject,
+        admin,
+    )
+    .await
+    .expect("bulk reject should succeed");
+    assert_eq!(response.updated, vec![carol_candidate]);
+    assert_eq!(response.skipped, vec![alice_candidate]);
+    assert_eq!(candidate_status(&pool, carol_candidate).await, "rejected");
+}
+
+#[sqlx::test]
+async fn oversized_review_batches_fail_with_a_typed_error(pool: PgPool) {
+    let admin = insert_admin(&pool).await;
+    let ids: Vec<i32> = (1..=MAX_BULK_CANDIDATES as i32 + 1).collect();
+
+    let err = review_candidates(&pool, &ids, MatchDecision::Reject, admin)
+        .await
+        .expect_err("oversized batch should fail");
+    assert!(matches!(
+        err.downcast_ref::<CandidateReviewError>(),
+        Some(CandidateReviewError::TooManyCandidates { .. })
+    ));
+}
@@ -1,18 +1,28 @@
 //! Tests for various search filters individually and in combination.
 //!
-//! Covers `open_only`, `subject`, `time_start`, `time_end`, day filters,
-//! and multi-filter combinations including pagination.
+//! Covers `open_only`, `waitlist_available`, `subject` (including aliases),
+//! `time_start`, `time_end`, day filters, multi-filter combinations including
+//! offset and cursor pagination, and facet counts.
 
 mod helpers;
 
 use banner::data::batch::batch_upsert_courses;
-use banner::data::courses::{SearchFilter, search_courses};
+use banner::data::courses::{
+    SearchFilter, SearchPage, SearchResults, SortColumn, SortDirection, search_courses,
+    search_courses_page,
+};
+use banner::data::search_index::{refresh_term, stale_terms};
+use banner::data::subject_aliases::upsert_alias;
 use helpers::{MeetingTimeBuilder, make_course, with_meetings};
 use sqlx::PgPool;
 
 /// Run `search_courses` using a `SearchFilter`, returning (CRNs, total_count).
 async fn search(pool: &PgPool, filter: &SearchFilter<'_>) -> (Vec<String>, i64) {
-    let (results, total) = search_courses(pool, filter, 100, 0, None, None)
+    let SearchResults {
+        courses: results,
+        total,
+        ..
+    } = search_courses(pool, filter, 100, 0, None, None)
         .await
         .expect("search_courses failed");
 
@@ -180,6 +190,37 @@ async fn test_filter_open_only(pool: PgPool) {
     assert!(!crns.contains(&"20006".to_owned()), "20006 is full (20/20)");
 }
 
+#[sqlx::test]
+async fn test_filter_waitlist_available(pool: PgPool) {
+    insert_test_courses(&pool).await;
+    // 20002's waitlist fills up; 20009 has no waitlist at all.
+    sqlx::query("UPDATE courses SET wait_count = wait_capacity WHERE crn = '20002'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE courses SET wait_capacity = 0 WHERE crn = '20009'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (crns, total) = search(
+        &pool,
+        &SearchFilter {
+            term_code: "202620",
+            waitlist_available: true,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert_eq!(total, 7, "excludes the full waitlist and the missing one");
+    assert!(
+        !crns.contains(&"20002".to_owned()),
+        "20002 waitlist is full"
+    );
+    assert!(!crns.contains(&"20009".to_owned()), "20009 has no waitlist");
+}
+
 #[sqlx::test]
 async fn test_filter_by_subject(pool: PgPool) {
     insert_test_courses(&pool).await;
@@ -205,6 +246,84 @@ async fn test_filter_by_subject(pool: PgPool) {
     }
 }
 
+#[sqlx::test]
+async fn test_filter_by_subject_follows_aliases(pool: PgPool) {
+    insert_test_courses(&pool).await;
+    upsert_alias(&pool, "PHY", "PHYS", None)
+        .await
+        .expect("upsert_alias failed");
+
+    // The retired code finds sections filed under the current one, and
+    // vice versa.
+    for code in ["PHY", "PHYS"] {
+        let subjects = vec![code.to_owned()];
+        let (crns, total) = search(
+            &pool,
+            &SearchFilter {
+                term_code: "202620",
+                subjects: Some(&subjects),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(total, 1, "{code} should match PHYS 1600");
+        assert_eq!(crns, vec!["20007".to_owned()]);
+    }
+}
+
+#[sqlx::test]
+async fn test_text_query_phrases_and_prefixes(pool: PgPool) {
+    insert_test_courses(&pool).await;
+    refresh_term(&pool, "202620")
+        .await
+        .expect("refresh_term failed");
+
+    let query = |q: &'static str| SearchFilter {
+        term_code: "202620",
+        query: Some(q),
+        ..Default::default()
+    };
+
+    // The word being typed is prefix-matched.
+    let (mut crns, _) = search(&pool, &query("alg")).await;
+    crns.sort();
+    assert_eq!(crns, vec!["20003", "20004", "20005"]);
+
+    // A closed phrase needs its words in order.
+    let (crns, _) = search(&pool, &query("\"linear algebra\"")).await;
+    assert_eq!(crns, vec!["20005"]);
+    let (crns, _) = search(&pool, &query("\"algebra linear\"")).await;
+    assert!(crns.is_empty());
+}
+
+#[sqlx::test]
+async fn test_text_query_matches_catalog_descriptions(pool: PgPool) {
+    insert_test_courses(&pool).await;
+    sqlx::query(
+        "UPDATE courses SET description = 'Covers eigenvectors and matrix factorizations.' \
+         WHERE crn = '20005'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    assert_eq!(stale_terms(&pool).await.unwrap(), vec!["202620"]);
+    refresh_term(&pool, "202620")
+        .await
+        .expect("refresh_term failed");
+    assert!(stale_terms(&pool).await.unwrap().is_empty());
+
+    let (crns, _) = search(
+        &pool,
+        &SearchFilter {
+            term_code: "202620",
+            query: Some("eigenvectors"),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(crns, vec!["20005"]);
+}
+
 #[sqlx::test]
 async fn test_filter_by_multiple_subjects(pool: PgPool) {
     insert_test_courses(&pool).await;
@@ -443,7 +562,11 @@ async fn test_pagination_with_filters(pool: PgPool) {
     };
 
     // First page
-    let (results, total1) = search_courses(&pool, &filter, 2, 0, None, None)
+    let SearchResults {
+        courses: results,
+        total: total1,
+        ..
+    } = search_courses(&pool, &filter, 2, 0, None, None)
         .await
         .expect("search_courses failed");
     let page1_crns: Vec<String> = results.iter().map(|c| c.crn.clone()).collect();
@@ -452,7 +575,11 @@ async fn test_pagination_with_filters(pool: PgPool) {
     assert_eq!(page1_crns.len(), 2, "page 1 returns limit=2 results");
 
     // Second page
-    let (results, total2) = search_courses(&pool, &filter, 2, 2, None, None)
+    let SearchResults {
+        courses: results,
+        total: total2,
+        ..
+    } = search_courses(&pool, &filter, 2, 2, None, None)
         .await
         .expect("search_courses failed");
     let page2_crns: Vec<String> = results.iter().map(|c| c.crn.clone()).collect();
@@ -461,7 +588,11 @@ async fn test_pagination_with_filters(pool: PgPool) {
     assert_eq!(page2_crns.len(), 2, "page 2 returns limit=2 results");
 
     // Third page (remainder)
-    let (results, total3) = search_courses(&pool, &filter, 2, 4, None, None)
+    let SearchResults {
+        courses: results,
+        total: total3,
+        ..
+    } = search_courses(&pool, &filter, 2, 4, None, None)
         .await
         .expect("search_courses failed");
     let page3_crns: Vec<String> = results.iter().map(|c| c.crn.clone()).collect();
@@ -480,3 +611,164 @@ async fn test_pagination_with_filters(pool: PgPool) {
     all_crns.dedup();
     assert_eq!(all_crns.len(), 5, "no duplicate CRNs across pages");
 }
+
+#[sqlx::test]
+async fn test_cursor_pagination_walks_every_match(pool: PgPool) {
+    insert_test_courses(&pool).await;
+
+    let days = vec!["monday".to_owned()];
+    let filter = SearchFilter {
+        term_code: "202620",
+        days: Some(&days),
+        ..Default::default()
+    };
+
+    let (all_crns, _) = search(&pool, &filter).await;
+
+    let mut walked = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = match &cursor {
+            Some(c) => SearchPage::After(c),
+            None => SearchPage::Offset(0),
+        };
+        let SearchResults {
+            courses,
+            next_cursor,
+            ..
+        } = search_courses_page(
+            &pool,
+            &filter,
+            2,
+            page,
+            Some(SortColumn::Title),
+            Some(SortDirection::Desc),
+        )
+        .await
+        .expect("search_courses_page failed");
+        walked.extend(courses.into_iter().map(|c| c.crn));
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(walked.len(), 5, "cursor pages together yield every match");
+    let mut sorted = walked.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), 5, "no duplicate CRNs across cursor pages");
+    let mut expected = all_crns;
+    expected.sort();
+    assert_eq!(sorted, expected);
+}
+
+#[sqlx::test]
+async fn test_malformed_cursor_is_rejected(pool: PgPool) {
+    insert_test_courses(&pool).await;
+
+    let filter = SearchFilter {
+        term_code: "202620",
+        ..Default::default()
+    };
+    let result = search_courses_page(
+        &pool,
+        &filter,
+        2,
+        SearchPage::After("not-a-cursor"),
+        None,
+        None,
+    )
+    .await;
+    assert!(
+        result.is_err(),
+        "garbage cursors are an error, not page one"
+    );
+}
+
+#[sqlx::test]
+async fn test_facets_count_full_result_set(pool: PgPool) {
+    insert_test_courses(&pool).await;
+
+    let days = vec!["monday".to_owned()];
+    let filter = SearchFilter {
+        term_code: "202620",
+        days: Some(&days),
+        ..Default::default()
+    };
+
+    // Facets cover every match, not just the one-row page.
+    let SearchResults { total, facets, .. } = search_courses(&pool, &filter, 1, 0, None, None)
+        .await
+        .expect("search_courses failed");
+
+    // Monday: 20001 CS, 20003 CS, 20004 MATH, 20006 ENG (full), 20007 PHYS
+    assert_eq!(total, 5);
+    let facets = facets.expect("the first page has facets");
+    let subjects: Vec<(&str, i64)> = facets
+        .subjects
+        .iter()
+        .map(|f| (f.code.as_str(), f.count))
+        .collect();
+    assert_eq!(
+        subjects,
+        vec![("CS", 2), ("ENG", 1), ("MATH", 1), ("PHYS", 1)],
+        "most common first, then by code"
+    );
+    assert_eq!((facets.open, facets.closed), (4, 1));
+    assert_eq!(facets.instructional_methods.len(), 1);
+    assert_eq!(facets.instructional_methods[0].count, 5);
+}
+
+#[sqlx::test]
+async fn test_facets_ignore_their_own_filter(pool: PgPool) {
+    insert_test_courses(&pool).await;
+
+    let days = vec!["monday".to_owned()];
+    let subjects = vec!["CS".to_owned()];
+    let filter = SearchFilter {
+        term_code: "202620",
+        days: Some(&days),
+        subjects: Some(&subjects),
+        open_only: true,
+        ..Default::default()
+    };
+
+    let SearchResults { total, facets, .. } = search_courses(&pool, &filter, 1, 0, None, None)
+        .await
+        .expect("search_courses failed");
+    assert_eq!(total, 2, "open Monday CS sections");
+    let facets = facets.expect("the first page has facets");
+
+    // Subjects apply the open filter but not the subject filter.
+    let subjects: Vec<(&str, i64)> = facets
+        .subjects
+        .iter()
+        .map(|f| (f.code.as_str(), f.count))
+        .collect();
+    assert_eq!(subjects, vec![("CS", 2), ("MATH", 1), ("PHYS", 1)]);
+    // Open/closed apply the subject filter but not the open filter.
+    assert_eq!((facets.open, facets.closed), (2, 0));
+    assert_eq!(facets.instructional_methods[0].count, 2);
+}
+
+#[sqlx::test]
+async fn test_later_pages_skip_facets(pool: PgPool) {
+    insert_test_courses(&pool).await;
+
+    let filter = SearchFilter {
+        term_code: "202620",
+        ..Default::default()
+    };
+    let first = search_courses(&pool, &filter, 2, 0, None, None)
+        .await
+        .expect("first page failed");
+    let cursor = first.next_cursor.expect("more pages follow");
+    let second = search_courses_page(&pool, &filter, 2, SearchPage::After(&cursor), None, None)
+        .await
+        .expect("second page failed");
+
+    assert!(first.facets.is_some());
+    assert!(second.facets.is_none());
+    assert_eq!(second.total, first.total, "later pages still count");
+}
@@ -0,0 +1,143 @@
+//! Tests for grouped course suggestions (`group_by=course`) and the subject
+//! and building suggestion groups.
+
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::courses::{
+    suggest_buildings, suggest_course_groups, suggest_courses, suggest_subjects,
+};
+use helpers::{MeetingTimeBuilder, make_course, with_meetings};
+use sqlx::PgPool;
+
+const TERM: &str = "202620";
+
+async fn insert_topics_sections(pool: &PgPool) {
+    let courses = vec![
+        make_course(
+            "30001",
+            TERM,
+            "CS",
+            "4593",
+            "Topics in Security",
+            (30, 30, 4, 10),
+        ),
+        make_course(
+            "30002",
+            TERM,
+            "CS",
+            "4593",
+            "Topics in Security",
+            (20, 30, 0, 10),
+        ),
+        make_course(
+            "30003",
+            TERM,
+            "CS",
+            "4593",
+            "Topics in Graphics",
+            (10, 25, 0, 10),
+        ),
+        make_course(
+            "30004",
+            TERM,
+            "CS",
+            "3443",
+            "Application Programming",
+            (5, 40, 0, 10),
+        ),
+    ];
+    batch_upsert_courses(&courses, pool)
+        .await
+        .expect("batch_upsert_courses failed");
+}
+
+#[sqlx::test]
+async fn grouped_suggestions_collapse_sections(pool: PgPool) {
+    insert_topics_sections(&pool).await;
+
+    let ungrouped = suggest_courses(&pool, TERM, "CS 4593", 10).await.unwrap();
+    assert_eq!(
+        ungrouped
+            .iter()
+            .filter(|c| c.course_number == "4593")
+            .count(),
+        2,
+        "one entry per distinct title"
+    );
+
+    let groups = suggest_course_groups(&pool, TERM, "CS 4593", 10)
+        .await
+        .unwrap();
+    let topics = groups
+        .iter()
+        .find(|g| g.course_number == "4593")
+        .expect("CS 4593 suggested");
+    assert_eq!(
+        groups.iter().filter(|g| g.course_number == "4593").count(),
+        1
+    );
+    assert_eq!(topics.title, "Topics in Security");
+    assert_eq!(topics.title_count, 2);
+    assert_eq!(topics.section_count, 3);
+    assert_eq!(topics.open_sections, 2);
+    assert_eq!(topics.seats_available, 25);
+    assert_eq!(topics.wait_count, 4);
+}
+
+#[sqlx::test]
+async fn subjects_and_buildings_match_names_and_codes(pool: PgPool) {
+    sqlx::query(
+        "INSERT INTO reference_data (category, code, description) VALUES
+         ('subject', 'CS', 'Computer Science'),
+         ('building', 'NPB', 'North Paseo Building')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let meeting = |room: &str| {
+        MeetingTimeBuilder::new()
+            .days([true, false, true, false, false, false, false])
+            .time("0900", "0950")
+            .location("NPB", room)
+            .build()
+    };
+    let courses = vec![
+        with_meetings(
+            make_course("30001", TERM, "CS", "1083", "Programming I", (10, 30, 0, 0)),
+            vec![meeting("1.238")],
+        ),
+        with_meetings(
+            make_course(
+                "30002",
+                TERM,
+                "CS",
+                "1713",
+                "Programming II",
+                (10, 30, 0, 0),
+            ),
+            vec![meeting("1.226")],
+        ),
+        make_course("30003", TERM, "MAT", "1214", "Calculus I", (10, 30, 0, 0)),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+
+    let subjects = suggest_subjects(&pool, TERM, "computer", 10).await.unwrap();
+    assert_eq!(subjects.len(), 1);
+    assert_eq!(subjects[0].code, "CS");
+    assert_eq!(subjects[0].description, "Computer Science");
+    assert_eq!(subjects[0].section_count, 2);
+
+    // Subjects without a reference entry still match by code.
+    let subjects = suggest_subjects(&pool, TERM, "MA", 10).await.unwrap();
+    assert_eq!(subjects[0].code, "MAT");
+    assert_eq!(subjects[0].description, "MAT");
+
+    for query in ["paseo", "NP"] {
+        let buildings = suggest_buildings(&pool, TERM, query, 10).await.unwrap();
+        assert_eq!(buildings.len(), 1, "{query}");
+        assert_eq!(buildings[0].code, "NPB");
+        assert_eq!(buildings[0].description, "North Paseo Building");
+        assert_eq!(buildings[0].room_count, 2);
+    }
+}
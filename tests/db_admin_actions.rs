@@ -0,0 +1,72 @@
+mod helpers;
+
+use banner::data::admin_actions::{AdminActionFilter, NewAdminAction, insert_action, list_actions};
+use serde_json::json;
+use sqlx::PgPool;
+
+fn action<'a>(method: &'a str, path: &'a str, status: u16) -> NewAdminAction<'a> {
+    NewAdminAction {
+        admin_id: 1,
+        admin_username: "admin",
+        method,
+        path,
+        route: None,
+        status,
+        payload: Some(json!({ "isAdmin": true })),
+        result: None,
+        changes: None,
+    }
+}
+
+#[sqlx::test]
+async fn actions_filter_by_method_path_and_outcome(pool: PgPool) {
+    sqlx::query("INSERT INTO users (id, discord_username) VALUES (1, 'admin')")
+        .execute(&pool)
+        .await
+        .expect("failed to insert user");
+
+    insert_action(&pool, &action("PUT", "/api/admin/users/2/admin", 200))
+        .await
+        .unwrap();
+    insert_action(&pool, &action("DELETE", "/api/admin/ip-bans/3", 404))
+        .await
+        .unwrap();
+    insert_action(&pool, &action("POST", "/api/admin/ip-bans", 200))
+        .await
+        .unwrap();
+
+    let all = list_actions(&pool, &AdminActionFilter::default(), 1, 50)
+        .await
+        .unwrap();
+    assert_eq!(all.total.get(), 3);
+    // Newest first.
+    assert_eq!(all.items[0].method, "POST");
+    assert_eq!(all.items[0].admin_id.as_deref(), Some("1"));
+    assert_eq!(all.items[0].payload, Some(json!({ "isAdmin": true })));
+
+    let bans = AdminActionFilter {
+        path_prefix: Some("/api/admin/ip-bans"),
+        ..Default::default()
+    };
+    assert_eq!(
+        list_actions(&pool, &bans, 1, 50).await.unwrap().total.get(),
+        2
+    );
+
+    let failed = AdminActionFilter {
+        success: Some(false),
+        ..Default::default()
+    };
+    let failed = list_actions(&pool, &failed, 1, 50).await.unwrap();
+    assert_eq!(failed.total.get(), 1);
+    assert_eq!(failed.items[0].status, 404);
+
+    let puts = AdminActionFilter {
+        method: Some("put"),
+        ..Default::default()
+    };
+    assert_eq!(
+        list_actions(&pool, &puts, 1, 50).await.unwrap().total.get(),
+        1
+    );
+}
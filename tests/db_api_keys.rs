@@ -0,0 +1,29 @@
+mod helpers;
+
+use banner::data::api_keys::{create_key, find_active_key, hash_key, revoke_key};
+use helpers::insert_user;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn keys_are_found_by_hash_until_revoked(pool: PgPool) {
+    insert_user(&pool, 1).await;
+    let (key, raw) = create_key(&pool, 1, "ci").await.unwrap();
+
+    let stored: String = sqlx::query_scalar("SELECT key_hash FROM api_keys WHERE id = $1")
+        .bind(key.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, hash_key(&raw));
+
+    let found = find_active_key(&pool, &hash_key(&raw)).await.unwrap();
+    assert_eq!(found.map(|k| k.id), Some(key.id));
+
+    assert!(revoke_key(&pool, key.id).await.unwrap());
+    assert!(
+        find_active_key(&pool, &hash_key(&raw))
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
@@ -353,3 +353,186 @@ async fn test_batch_upsert_no_change_no_audit(pool: PgPool) {
         "identical re-upsert should only have the baseline metric"
     );
 }
+
+#[sqlx::test]
+async fn test_batch_upsert_diff_summary(pool: PgPool) {
+    let initial = vec![helpers::make_course(
+        "70001",
+        "202510",
+        "CS",
+        "1083",
+        "Intro to CS",
+        (10, 30, 0, 5),
+    )];
+    let (counts, _) = batch_upsert_courses(&initial, &pool).await.unwrap();
+    let diff = counts.diff.expect("course upserts report a diff");
+    assert_eq!(diff.added.get(), 1);
+    assert_eq!(diff.changed.get(), 0);
+    assert!(diff.fields.is_empty());
+
+    let updated = vec![
+        helpers::make_course(
+            "70001",
+            "202510",
+            "CS",
+            "1083",
+            "Intro to CS",
+            (12, 30, 0, 5),
+        ),
+        helpers::make_course(
+            "70002",
+            "202510",
+            "CS",
+            "2123",
+            "Data Structures",
+            (5, 30, 0, 5),
+        ),
+    ];
+    let (counts, _) = batch_upsert_courses(&updated, &pool).await.unwrap();
+    let diff = counts.diff.unwrap();
+    assert_eq!(diff.added.get(), 1);
+    assert_eq!(diff.changed.get(), 1);
+    assert_eq!(diff.fields.get("enrollment").map(|c| c.get()), Some(1));
+    assert!(!diff.is_noop());
+
+    let (counts, _) = batch_upsert_courses(&updated, &pool).await.unwrap();
+    assert!(counts.diff.unwrap().is_noop());
+}
+
+#[sqlx::test]
+async fn test_course_highlights_skip_seat_churn(pool: PgPool) {
+    let initial = vec![helpers::make_course(
+        "50002",
+        "202510",
+        "CS",
+        "3443",
+        "App Programming",
+        (10, 35, 0, 5),
+    )];
+    batch_upsert_courses(&initial, &pool).await.unwrap();
+
+    let updated = vec![helpers::make_course(
+        "50002",
+        "202510",
+        "CS",
+        "3443",
+        "Application Programming",
+        (20, 35, 2, 5),
+    )];
+    batch_upsert_courses(&updated, &pool).await.unwrap();
+
+    let (course_id,): (i32,) = sqlx::query_as("SELECT id FROM courses WHERE crn = '50002'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let highlights = banner::data::audit::list_course_highlights(&pool, course_id, 10)
+        .await
+        .unwrap();
+
+    let fields: Vec<&str> = highlights
+        .iter()
+        .map(|r| r.field_changed.as_str())
+        .collect();
+    assert_eq!(fields, vec!["title"]);
+}
+
+#[sqlx::test]
+async fn test_batch_upsert_title_cases_and_keeps_raw_title(pool: PgPool) {
+    let courses = vec![helpers::make_course(
+        "50003",
+        "202510",
+        "CS",
+        "4593",
+        "ETHICS OF AI &amp; SOCIETY II",
+        (10, 35, 0, 5),
+    )];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+
+    let (title, raw_title): (String, Option<String>) =
+        sqlx::query_as("SELECT title, raw_title FROM courses WHERE crn = '50003'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(title, "Ethics of AI & Society II");
+    assert_eq!(raw_title.as_deref(), Some("ETHICS OF AI & SOCIETY II"));
+}
+
+#[sqlx::test]
+async fn test_batch_upsert_unchanged_rows_only_bump_last_scraped(pool: PgPool) {
+    let courses = vec![
+        helpers::make_course(
+            "50004",
+            "202510",
+            "CS",
+            "1083",
+            "Intro to CS",
+            (25, 30, 0, 5),
+        ),
+        helpers::make_course(
+            "50005",
+            "202510",
+            "CS",
+            "2123",
+            "Data Structures",
+            (20, 30, 0, 5),
+        ),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+    sqlx::query("UPDATE courses SET last_scraped_at = NOW() - INTERVAL '1 day'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut rescraped = courses.clone();
+    rescraped[1].enrollment = 21;
+    let (counts, events) = batch_upsert_courses(&rescraped, &pool).await.unwrap();
+
+    assert_eq!(counts.courses_changed.get(), 1);
+    assert_eq!(counts.courses_unchanged.get(), 1);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].change.audit_field(), "enrollment");
+
+    let (stale,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM courses WHERE last_scraped_at < NOW() - INTERVAL '1 hour'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(stale, 0, "unchanged rows should still be marked as scraped");
+}
+
+#[sqlx::test]
+async fn test_batch_upsert_keeps_sections_inserted_concurrently(pool: PgPool) {
+    // Another writer inserts the section after the upsert's snapshot but
+    // before its own insert, so the upsert hits the conflict.
+    let mut other = pool.begin().await.unwrap();
+    let (other_id,): (i32,) = sqlx::query_as(
+        "INSERT INTO courses (crn, subject, course_number, title, term_code,
+                              enrollment, max_enrollment, wait_count, wait_capacity, last_scraped_at)
+         VALUES ('60001', 'CS', '1083', 'Intro', '202510', 1, 30, 0, 0, NOW())
+         RETURNING id",
+    )
+    .fetch_one(&mut *other)
+    .await
+    .unwrap();
+
+    let course = helpers::make_course("60001", "202510", "CS", "1083", "Intro", (5, 30, 0, 0));
+    let upsert = tokio::spawn({
+        let pool = pool.clone();
+        async move { batch_upsert_courses(&[course], &pool).await }
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    other.commit().await.unwrap();
+
+    let (_, events) = upsert.await.unwrap().unwrap();
+    assert!(
+        events.iter().any(|e| e.course.id == other_id),
+        "the conflicted section is still reported"
+    );
+    let (enrollment,): (i32,) = sqlx::query_as("SELECT enrollment FROM courses WHERE id = $1")
+        .bind(other_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(enrollment, 5);
+}
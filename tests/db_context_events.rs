@@ -71,7 +71,7 @@ async fn db_context_emits_event_on_job_complete(pool: sqlx::PgPool) {
     assert_eq!(job.unwrap().id, job_id);
 
     // Complete the job (this emits Completed event at cursor + 1)
-    ctx.scrape_jobs().complete(job_id).await.unwrap();
+    ctx.scrape_jobs().complete(job_id, None).await.unwrap();
 
     // Verify Completed event was emitted (at position after Locked)
     let event = events.read(cursor + 1);
@@ -124,7 +124,7 @@ async fn db_context_emits_event_on_job_retry(pool: sqlx::PgPool) {
 }
 
 #[sqlx::test]
-async fn db_context_emits_events_on_job_exhaust(pool: sqlx::PgPool) {
+async fn db_context_emits_events_on_job_dead_letter(pool: sqlx::PgPool) {
     let events = Arc::new(EventBuffer::new(100));
     let ctx = DbContext::new(pool.clone(), events.clone());
 
@@ -147,8 +147,11 @@ async fn db_context_emits_events_on_job_exhaust(pool: sqlx::PgPool) {
     let job = ctx.scrape_jobs().lock_next().await.unwrap();
     assert!(job.is_some());
 
-    // Exhaust the job (emits Exhausted at cursor + 1, then Deleted at cursor + 2)
-    ctx.scrape_jobs().exhaust(job_id).await.unwrap();
+    // Dead-letter the job (emits Exhausted at cursor + 1, then Deleted at cursor + 2)
+    ctx.scrape_jobs()
+        .dead_letter(job_id, Count::new(3), "search timed out")
+        .await
+        .unwrap();
 
     // Verify Exhausted event was emitted (at position after Locked)
     let event1 = events.read(cursor + 1);
@@ -0,0 +1,118 @@
+mod helpers;
+
+use banner::banner::CourseDetails;
+use banner::data::batch::batch_upsert_courses;
+use banner::data::course_details::{get_linked_groups_for_courses, save_course_details};
+use banner::data::courses::get_course_by_crn;
+use banner::web::courses::build_course_response;
+use sqlx::PgPool;
+
+async fn course_id(pool: &PgPool, crn: &str) -> i32 {
+    let (id,): (i32,) = sqlx::query_as("SELECT id FROM courses WHERE crn = $1")
+        .bind(crn)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    id
+}
+
+fn linked(groups: &[&[&str]]) -> CourseDetails {
+    CourseDetails {
+        linked_groups: groups
+            .iter()
+            .map(|group| group.iter().map(|crn| crn.to_string()).collect())
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[sqlx::test]
+async fn linked_groups_are_returned_per_course(pool: PgPool) {
+    let courses = vec![
+        helpers::make_course("93001", "202620", "BIO", "1404", "Biosci I", (0, 30, 0, 0)),
+        helpers::make_course("93002", "202620", "BIO", "1404", "Biosci I", (0, 30, 0, 0)),
+        helpers::make_course("93003", "202620", "CS", "1083", "Intro", (0, 30, 0, 0)),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+    let lecture = course_id(&pool, "93001").await;
+    let other_lecture = course_id(&pool, "93002").await;
+    let unlinked = course_id(&pool, "93003").await;
+
+    save_course_details(&pool, lecture, &linked(&[&["93012", "93011"], &["93013"]]))
+        .await
+        .unwrap();
+    save_course_details(&pool, other_lecture, &linked(&[&["93014"]]))
+        .await
+        .unwrap();
+
+    let groups = get_linked_groups_for_courses(&pool, &[lecture, other_lecture, unlinked])
+        .await
+        .unwrap();
+    // Groups keep Banner's order; CRNs within a group are sorted.
+    assert_eq!(groups[&lecture], [vec!["93011", "93012"], vec!["93013"]]);
+    assert_eq!(groups[&other_lecture], [vec!["93014"]]);
+    assert!(!groups.contains_key(&unlinked));
+
+    assert!(
+        get_linked_groups_for_courses(&pool, &[])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[sqlx::test]
+async fn refetched_details_replace_linked_groups(pool: PgPool) {
+    let courses = vec![helpers::make_course(
+        "93101",
+        "202620",
+        "BIO",
+        "1404",
+        "Biosci I",
+        (0, 30, 0, 0),
+    )];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+    let lecture = course_id(&pool, "93101").await;
+
+    save_course_details(&pool, lecture, &linked(&[&["93111"], &["93112"]]))
+        .await
+        .unwrap();
+    save_course_details(&pool, lecture, &linked(&[&["93113"]]))
+        .await
+        .unwrap();
+
+    let groups = get_linked_groups_for_courses(&pool, &[lecture])
+        .await
+        .unwrap();
+    assert_eq!(groups[&lecture], [vec!["93113"]]);
+}
+
+#[sqlx::test]
+async fn course_responses_expose_linked_groups(pool: PgPool) {
+    let courses = vec![
+        helpers::make_course("93201", "202620", "BIO", "1404", "Biosci I", (0, 30, 0, 0)),
+        helpers::make_course("93202", "202620", "CS", "1083", "Intro", (0, 30, 0, 0)),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+    let lecture = get_course_by_crn(&pool, "93201", "202620")
+        .await
+        .unwrap()
+        .unwrap();
+    let unlinked = get_course_by_crn(&pool, "93202", "202620")
+        .await
+        .unwrap()
+        .unwrap();
+
+    let groups = vec![vec!["93211".to_owned()], vec!["93212".to_owned()]];
+    let json = serde_json::to_value(build_course_response(&lecture, Vec::new(), groups)).unwrap();
+    assert_eq!(
+        json["sectionLink"]["groups"],
+        serde_json::json!([["93211"], ["93212"]])
+    );
+    assert!(json["sectionLink"]["identifier"].is_null());
+
+    // Without an identifier or groups there is no link to report.
+    let json =
+        serde_json::to_value(build_course_response(&unlinked, Vec::new(), Vec::new())).unwrap();
+    assert!(json["sectionLink"].is_null());
+}
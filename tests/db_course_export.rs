@@ -0,0 +1,43 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::courses::{CourseExportKey, course_export_page};
+use sqlx::PgPool;
+
+/// Page through the export two rows at a time, collecting CRNs.
+async fn export_crns(pool: &PgPool, term_code: Option<&str>) -> Vec<String> {
+    let mut crns = Vec::new();
+    let mut after: Option<CourseExportKey> = None;
+    loop {
+        let page = course_export_page(pool, term_code, after.as_ref(), 2)
+            .await
+            .unwrap();
+        crns.extend(page.iter().map(|row| row.crn.clone()));
+        match page.last() {
+            Some(last) if page.len() == 2 => after = Some(last.export_key()),
+            _ => return crns,
+        }
+    }
+}
+
+#[sqlx::test]
+async fn export_pages_walk_sections_in_catalog_order(pool: PgPool) {
+    let courses = vec![
+        helpers::make_course("92003", "202620", "MAT", "1214", "Calculus", (0, 30, 0, 0)),
+        helpers::make_course("92001", "202620", "CS", "3343", "Algorithms", (0, 30, 0, 0)),
+        helpers::make_course("92002", "202620", "CS", "1083", "Intro", (0, 30, 0, 0)),
+        helpers::make_course("92004", "202620", "CS", "1083", "Intro", (0, 30, 0, 0)),
+        helpers::make_course("92005", "202610", "CS", "1083", "Intro", (0, 30, 0, 0)),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+
+    assert_eq!(
+        export_crns(&pool, Some("202620")).await,
+        ["92002", "92004", "92001", "92003"]
+    );
+    // Every term, newest first.
+    assert_eq!(
+        export_crns(&pool, None).await,
+        ["92002", "92004", "92001", "92003", "92005"]
+    );
+}
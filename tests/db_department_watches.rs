@@ -0,0 +1,134 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::events::CourseChange;
+use banner::data::watches::{
+    ChangedCourses, WatchType, count_active_watches, delete_department_watches,
+    find_triggered_department_watches, list_department_watches, upsert_department_watch,
+    upsert_watch,
+};
+use sqlx::PgPool;
+
+async fn course_id(pool: &PgPool, crn: &str) -> i32 {
+    let (id,): (i32,) = sqlx::query_as("SELECT id FROM courses WHERE crn = $1")
+        .bind(crn)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    id
+}
+
+fn edited(field: &str) -> CourseChange {
+    CourseChange::Field {
+        field: field.to_owned(),
+        old: None,
+        new: serde_json::json!([]),
+    }
+}
+
+#[sqlx::test]
+async fn department_watches_fire_for_matching_sections(pool: PgPool) {
+    helpers::insert_user(&pool, 7).await;
+    let courses = vec![
+        helpers::make_course("94001", "202620", "CS", "1083", "Intro", (0, 30, 0, 0)),
+        helpers::make_course("94002", "202620", "CS", "3343", "Algorithms", (0, 30, 0, 0)),
+        helpers::make_course("94003", "202620", "MAT", "1214", "Calculus", (0, 30, 0, 0)),
+        helpers::make_course("94004", "202610", "CS", "1083", "Intro", (0, 30, 0, 0)),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+
+    upsert_department_watch(
+        &pool,
+        7,
+        "202620",
+        "CS",
+        &WatchType::RestrictionChange,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let mut changed = ChangedCourses::default();
+    for crn in ["94001", "94003", "94004"] {
+        changed.record(course_id(&pool, crn).await, &edited("restrictions"));
+    }
+    changed.record(course_id(&pool, "94002").await, &edited("attributes"));
+    changed.dedup();
+
+    // Other subjects, other terms, and other change kinds don't match.
+    let triggered = find_triggered_department_watches(&pool, &changed)
+        .await
+        .unwrap();
+    let crns: Vec<&str> = triggered.iter().map(|w| w.crn.as_str()).collect();
+    assert_eq!(crns, ["94001"]);
+    assert_eq!(triggered[0].watch_type, "restriction_change");
+    assert_eq!(triggered[0].user_id, 7);
+}
+
+#[sqlx::test]
+async fn department_watches_are_listed_counted_and_removed(pool: PgPool) {
+    helpers::insert_user(&pool, 7).await;
+    let courses = vec![helpers::make_course(
+        "94101",
+        "202620",
+        "CS",
+        "1083",
+        "Intro",
+        (0, 30, 0, 0),
+    )];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+    let course = course_id(&pool, "94101").await;
+
+    upsert_watch(&pool, 7, course, &WatchType::SeatsAvailable, None)
+        .await
+        .unwrap();
+    for watch_type in [WatchType::AttributeChange, WatchType::RestrictionChange] {
+        assert!(
+            upsert_department_watch(&pool, 7, "202620", "CS", &watch_type, None)
+                .await
+                .unwrap()
+        );
+    }
+    // Re-adding updates the delivery target instead of duplicating.
+    assert!(
+        !upsert_department_watch(
+            &pool,
+            7,
+            "202620",
+            "CS",
+            &WatchType::AttributeChange,
+            Some("https://example.com/hook"),
+        )
+        .await
+        .unwrap()
+    );
+    assert_eq!(count_active_watches(&pool, 7).await.unwrap(), 3);
+
+    let listed = list_department_watches(&pool, 7).await.unwrap();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(
+        listed[0].webhook_url.as_deref(),
+        Some("https://example.com/hook")
+    );
+
+    let removed =
+        delete_department_watches(&pool, 7, "202620", "CS", Some(&WatchType::AttributeChange))
+            .await
+            .unwrap();
+    assert_eq!(removed, 1);
+    assert_eq!(
+        delete_department_watches(&pool, 7, "202620", "CS", None)
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(list_department_watches(&pool, 7).await.unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn seat_watches_cannot_follow_a_department(pool: PgPool) {
+    helpers::insert_user(&pool, 7).await;
+    let result =
+        upsert_department_watch(&pool, 7, "202620", "CS", &WatchType::SeatsAvailable, None).await;
+    assert!(result.is_err());
+}
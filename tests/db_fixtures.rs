@@ -0,0 +1,62 @@
+//! Integration tests for the synthetic fixture loader.
+
+use banner::banner::{Season, Term};
+use banner::data::DbContext;
+use banner::data::events::EventBuffer;
+use banner::data::fixtures::{self, FixtureOptions};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+fn small_options() -> FixtureOptions {
+    FixtureOptions {
+        seed: 11,
+        latest_term: Term {
+            year: 2026,
+            season: Season::Spring,
+        },
+        terms: 2,
+        subjects: 2,
+        courses_per_subject: 4,
+        instructors_per_subject: 3,
+    }
+}
+
+async fn count(pool: &PgPool, table: &str) -> i64 {
+    let (n,): (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM {table}"))
+        .fetch_one(pool)
+        .await
+        .expect("count query failed");
+    n
+}
+
+#[sqlx::test]
+async fn load_populates_every_source(pool: PgPool) {
+    let db = DbContext::new(pool.clone(), Arc::new(EventBuffer::new(64)));
+    let set = fixtures::generate(&small_options());
+
+    let stats = fixtures::load(&db, &set).await.expect("load failed");
+
+    assert_eq!(count(&pool, "courses").await, stats.courses as i64);
+    assert_eq!(
+        count(&pool, "rmp_professors").await,
+        stats.professors as i64
+    );
+    assert_eq!(count(&pool, "rmp_reviews").await, stats.reviews as i64);
+    assert_eq!(count(&pool, "terms").await, 2);
+    assert!(count(&pool, "instructors").await > 0);
+    assert!(count(&pool, "bluebook_evaluations").await > 0);
+}
+
+#[sqlx::test]
+async fn load_is_idempotent(pool: PgPool) {
+    let db = DbContext::new(pool.clone(), Arc::new(EventBuffer::new(64)));
+    let set = fixtures::generate(&small_options());
+
+    fixtures::load(&db, &set).await.expect("first load failed");
+    let courses = count(&pool, "courses").await;
+    let instructors = count(&pool, "instructors").await;
+
+    fixtures::load(&db, &set).await.expect("second load failed");
+    assert_eq!(count(&pool, "courses").await, courses);
+    assert_eq!(count(&pool, "instructors").await, instructors);
+}
@@ -0,0 +1,144 @@
+mod helpers;
+
+use banner::data::identities::{self, DISCORD, IdentityError, ProviderAccount};
+use sqlx::PgPool;
+
+fn account<'a>(provider: &'a str, subject: &'a str) -> ProviderAccount<'a> {
+    ProviderAccount {
+        provider,
+        subject,
+        display_name: "Jane",
+        email: Some("jane@my.utsa.edu"),
+        avatar_hash: None,
+    }
+}
+
+#[sqlx::test]
+async fn discord_sign_in_links_snowflake_to_a_new_user(pool: PgPool) {
+    let user = identities::sign_in(&pool, &account(DISCORD, "123456789"))
+        .await
+        .unwrap();
+    assert!(user.id < 0);
+    assert_eq!(user.discord_id, Some(123456789));
+
+    let again = identities::sign_in(&pool, &account(DISCORD, "123456789"))
+        .await
+        .unwrap();
+    assert_eq!(again.id, user.id);
+}
+
+#[sqlx::test]
+async fn other_providers_get_local_ids(pool: PgPool) {
+    let first = identities::sign_in(&pool, &account("google", "g-1"))
+        .await
+        .unwrap();
+    let second = identities::sign_in(&pool, &account("microsoft", "m-1"))
+        .await
+        .unwrap();
+    assert!(first.id < 0);
+    assert!(second.id < 0);
+    assert_ne!(first.id, second.id);
+    assert_eq!(first.discord_id, None);
+}
+
+#[sqlx::test]
+async fn concurrent_first_sign_ins_share_one_user(pool: PgPool) {
+    let google = account("google", "g-race");
+    let (a, b) = tokio::join!(
+        identities::sign_in(&pool, &google),
+        identities::sign_in(&pool, &google),
+    );
+    assert_eq!(a.unwrap().id, b.unwrap().id);
+
+    let (users,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(users, 1);
+}
+
+#[sqlx::test]
+async fn bot_resolves_discord_through_linked_identity(pool: PgPool) {
+    let user = identities::sign_in(&pool, &account("google", "g-1"))
+        .await
+        .unwrap();
+    identities::link(&pool, user.id, &account(DISCORD, "555"))
+        .await
+        .unwrap();
+    assert_eq!(
+        identities::ensure_discord_user(&pool, 555, "jane")
+            .await
+            .unwrap(),
+        user.id
+    );
+
+    // Once unlinked, the bot gets a separate user rather than relinking.
+    identities::unlink(&pool, user.id, DISCORD).await.unwrap();
+    let bot_user = identities::ensure_discord_user(&pool, 555, "jane")
+        .await
+        .unwrap();
+    assert_ne!(bot_user, user.id);
+    let linked = identities::list_for_user(&pool, user.id).await.unwrap();
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0].provider, "google");
+}
+
+#[sqlx::test]
+async fn linked_identity_signs_in_to_same_user(pool: PgPool) {
+    let user = identities::sign_in(&pool, &account(DISCORD, "42"))
+        .await
+        .unwrap();
+    identities::link(&pool, user.id, &account("microsoft", "m-42"))
+        .await
+        .unwrap();
+
+    let via_microsoft = identities::sign_in(&pool, &account("microsoft", "m-42"))
+        .await
+        .unwrap();
+    assert_eq!(via_microsoft.id, user.id);
+
+    let linked = identities::list_for_user(&pool, user.id).await.unwrap();
+    let providers: Vec<_> = linked.iter().map(|i| i.provider.as_str()).collect();
+    assert_eq!(providers, ["discord", "microsoft"]);
+}
+
+#[sqlx::test]
+async fn link_rejects_identity_owned_by_another_user(pool: PgPool) {
+    let alice = identities::sign_in(&pool, &account(DISCORD, "1"))
+        .await
+        .unwrap();
+    identities::sign_in(&pool, &account("google", "g-bob"))
+        .await
+        .unwrap();
+
+    let err = identities::link(&pool, alice.id, &account("google", "g-bob"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<IdentityError>(),
+        Some(IdentityError::LinkedElsewhere(_))
+    ));
+}
+
+#[sqlx::test]
+async fn unlink_keeps_last_identity(pool: PgPool) {
+    let user = identities::sign_in(&pool, &account(DISCORD, "7"))
+        .await
+        .unwrap();
+
+    let err = identities::unlink(&pool, user.id, DISCORD)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<IdentityError>(),
+        Some(IdentityError::LastIdentity)
+    ));
+
+    identities::link(&pool, user.id, &account("google", "g-7"))
+        .await
+        .unwrap();
+    identities::unlink(&pool, user.id, DISCORD).await.unwrap();
+    let linked = identities::list_for_user(&pool, user.id).await.unwrap();
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0].provider, "google");
+}
@@ -0,0 +1,68 @@
+use banner::data::instructors::{rebuild_instructor_slugs, resolve_instructor_identifier};
+use sqlx::PgPool;
+
+async fn insert_instructor(pool: &PgPool, name: &str, slug: &str, stale: bool) -> i32 {
+    let (id,): (i32,) = sqlx::query_as(
+        "INSERT INTO instructors (display_name, slug, slug_stale) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(name)
+    .bind(slug)
+    .bind(stale)
+    .fetch_one(pool)
+    .await
+    .expect("failed to create instructor");
+    id
+}
+
+#[sqlx::test]
+async fn rebuild_replaces_stale_slug_and_keeps_old_one_resolving(pool: PgPool) {
+    let id = insert_instructor(&pool, "Doe-Smith, Jane", "doe-jane-abc", true).await;
+
+    let (checked, changes) = rebuild_instructor_slugs(&pool, &[]).await.unwrap();
+    assert_eq!(checked, 1);
+    assert_eq!(changes.len(), 1);
+    let new_slug = &changes[0].new_slug;
+    assert!(new_slug.starts_with("doe-smith-jane-"), "{new_slug}");
+
+    let resolved = resolve_instructor_identifier(&pool, "doe-jane-abc")
+        .await
+        .unwrap();
+    assert_eq!(resolved, Some((id, new_slug.clone())));
+
+    // Flag is cleared, so a second run is a no-op
+    let (checked, _) = rebuild_instructor_slugs(&pool, &[]).await.unwrap();
+    assert_eq!(checked, 0);
+}
+
+#[sqlx::test]
+async fn rebuild_keeps_slug_when_name_still_matches(pool: PgPool) {
+    let id = insert_instructor(&pool, "Doe, Jane", "doe-jane-abc", false).await;
+
+    let (checked, changes) = rebuild_instructor_slugs(&pool, &[id]).await.unwrap();
+    assert_eq!(checked, 1);
+    assert!(changes.is_empty());
+
+    let resolved = resolve_instructor_identifier(&pool, "doe-jane-abc")
+        .await
+        .unwrap();
+    assert_eq!(resolved, Some((id, "doe-jane-abc".to_owned())));
+}
+
+#[sqlx::test]
+async fn renamed_twice_old_slugs_reach_current(pool: PgPool) {
+    let id = insert_instructor(&pool, "Roe, John", "doe-jane-abc", true).await;
+    rebuild_instructor_slugs(&pool, &[]).await.unwrap();
+
+    sqlx::query("UPDATE instructors SET display_name = 'Poe, John' WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (_, changes) = rebuild_instructor_slugs(&pool, &[id]).await.unwrap();
+    let current = changes[0].new_slug.clone();
+
+    let resolved = resolve_instructor_identifier(&pool, "doe-jane-abc")
+        .await
+        .unwrap();
+    assert_eq!(resolved, Some((id, current)));
+}
@@ -0,0 +1,30 @@
+mod helpers;
+
+use banner::data::ip_bans::{expire, insert, list_active};
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn lifted_bans_drop_out_of_the_active_list(pool: PgPool) {
+    sqlx::query("INSERT INTO users (id, discord_username) VALUES (1, 'admin')")
+        .execute(&pool)
+        .await
+        .expect("failed to insert user");
+
+    let ban = insert(
+        &pool,
+        "203.0.113.0/24",
+        Some("scraping"),
+        1,
+        Utc::now() + Duration::hours(1),
+    )
+    .await
+    .unwrap();
+    assert_eq!(ban.created_by.as_deref(), Some("admin"));
+    assert_eq!(list_active(&pool).await.unwrap().len(), 1);
+
+    assert!(expire(&pool, ban.id).await.unwrap());
+    assert!(list_active(&pool).await.unwrap().is_empty());
+    // Already lifted.
+    assert!(!expire(&pool, ban.id).await.unwrap());
+}
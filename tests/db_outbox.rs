@@ -0,0 +1,252 @@
+mod helpers;
+
+use std::time::Duration;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::events::{CourseChange, CourseEvent, SeatField};
+use banner::data::outbox::{self, NewOutboxMessage, OutboxPayload};
+use chrono::Utc;
+use sqlx::PgPool;
+
+fn webhook(dedupe_key: Option<&str>) -> NewOutboxMessage {
+    NewOutboxMessage {
+        payload: OutboxPayload::Webhook {
+            url: "https://example.test/hook".into(),
+            body: serde_json::json!({ "ok": true }),
+        },
+        dedupe_key: dedupe_key.map(str::to_owned),
+    }
+}
+
+async fn status(pool: &PgPool, id: i64) -> (String, i32, Option<String>) {
+    sqlx::query_as("SELECT status, attempts, last_error FROM notification_outbox WHERE id = $1")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn enqueue_drops_duplicate_keys(pool: PgPool) {
+    let enqueued = outbox::enqueue(&pool, &[webhook(Some("a")), webhook(None)])
+        .await
+        .unwrap();
+    assert_eq!(enqueued, 2);
+
+    let again = outbox::enqueue(&pool, &[webhook(Some("a")), webhook(None)])
+        .await
+        .unwrap();
+    assert_eq!(again, 1);
+}
+
+#[sqlx::test]
+async fn claimed_messages_are_leased(pool: PgPool) {
+    outbox::enqueue(&pool, &[webhook(None)]).await.unwrap();
+
+    let first = outbox::claim_due(&pool, 10, Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].attempts, 1);
+    assert_eq!(first[0].payload.0, webhook(None).payload);
+
+    let second = outbox::claim_due(&pool, 10, Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert!(second.is_empty(), "leased message must not be reclaimed");
+}
+
+#[sqlx::test]
+async fn failures_reschedule_or_dead_letter(pool: PgPool) {
+    outbox::enqueue(&pool, &[webhook(None)]).await.unwrap();
+    let claimed = outbox::claim_due(&pool, 10, Duration::from_secs(60))
+        .await
+        .unwrap();
+    let id = claimed[0].id;
+
+    assert!(
+        outbox::mark_failed(
+            &pool,
+            id,
+            claimed[0].locked_until,
+            "timeout",
+            Some(Utc::now())
+        )
+        .await
+        .unwrap()
+    );
+    assert_eq!(
+        status(&pool, id).await,
+        ("pending".into(), 1, Some("timeout".into()))
+    );
+
+    let retried = outbox::claim_due(&pool, 10, Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert_eq!(retried.len(), 1);
+    assert_eq!(retried[0].attempts, 2);
+
+    assert!(
+        outbox::mark_failed(&pool, id, retried[0].locked_until, "410 Gone", None)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        status(&pool, id).await,
+        ("dead".into(), 2, Some("410 Gone".into()))
+    );
+    assert!(
+        outbox::claim_due(&pool, 10, Duration::from_secs(60))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[sqlx::test]
+async fn outcomes_require_the_current_lease(pool: PgPool) {
+    outbox::enqueue(&pool, &[webhook(None)]).await.unwrap();
+    let stale = outbox::claim_due(&pool, 10, Duration::from_secs(60))
+        .await
+        .unwrap()
+        .remove(0);
+
+    // The lease lapses and another dispatcher reclaims the message.
+    sqlx::query("UPDATE notification_outbox SET locked_until = NOW() - INTERVAL '1 second'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let current = outbox::claim_due(&pool, 10, Duration::from_secs(60))
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(current.id, stale.id);
+
+    assert!(
+        !outbox::mark_delivered(&pool, stale.id, stale.locked_until)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !outbox::mark_failed(&pool, stale.id, stale.locked_until, "timeout", None)
+            .await
+            .unwrap()
+    );
+    assert_eq!(status(&pool, stale.id).await, ("pending".into(), 2, None));
+
+    assert!(
+        outbox::mark_delivered(&pool, current.id, current.locked_until)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !outbox::mark_delivered(&pool, current.id, current.locked_until)
+            .await
+            .unwrap(),
+        "a delivered message can't be recorded twice"
+    );
+}
+
+#[sqlx::test]
+async fn delivered_messages_are_pruned_after_retention(pool: PgPool) {
+    outbox::enqueue(&pool, &[webhook(None)]).await.unwrap();
+    let claimed = outbox::claim_due(&pool, 10, Duration::from_secs(60))
+        .await
+        .unwrap();
+    let id = claimed[0].id;
+    assert!(
+        outbox::mark_delivered(&pool, id, claimed[0].locked_until)
+            .await
+            .unwrap()
+    );
+    assert_eq!(status(&pool, id).await.0, "delivered");
+
+    assert_eq!(
+        outbox::prune_delivered(&pool, Duration::from_secs(3600))
+            .await
+            .unwrap(),
+        0
+    );
+    sqlx::query("UPDATE notification_outbox SET delivered_at = NOW() - INTERVAL '2 hours'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        outbox::prune_delivered(&pool, Duration::from_secs(3600))
+            .await
+            .unwrap(),
+        1
+    );
+}
+
+#[sqlx::test]
+async fn course_writes_queue_their_changes(pool: PgPool) {
+    let mut course = helpers::make_course("10001", "202620", "CS", "1083", "Intro", (0, 30, 0, 0));
+    batch_upsert_courses(std::slice::from_ref(&course), &pool)
+        .await
+        .unwrap();
+    course.enrollment = 5;
+    let (_, events) = batch_upsert_courses(&[course], &pool).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].change,
+        CourseChange::Seats {
+            field: SeatField::Enrollment,
+            old: Some(0),
+            new: 5,
+        }
+    );
+
+    let payloads: Vec<(String, serde_json::Value)> =
+        sqlx::query_as("SELECT channel, payload FROM notification_outbox ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        payloads.len(),
+        2,
+        "one message per write that changed something"
+    );
+    let (channel, payload) = &payloads[1];
+    assert_eq!(channel, "course_changes");
+    let queued: Vec<CourseEvent> = serde_json::from_value(payload["events"].clone()).unwrap();
+    assert_eq!(queued, events);
+
+    let (field, new_value): (String, serde_json::Value) =
+        sqlx::query_as("SELECT field_changed, new_value FROM course_audits WHERE id = $1")
+            .bind(events[0].audit_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(field, "enrollment");
+    assert_eq!(new_value, serde_json::json!(5));
+}
+
+#[sqlx::test]
+async fn legacy_course_change_messages_are_rewritten_as_events(pool: PgPool) {
+    let course = helpers::make_course("10002", "202620", "CS", "1083", "Intro", (0, 30, 0, 0));
+    let (_, events) = batch_upsert_courses(&[course], &pool).await.unwrap();
+    sqlx::query("DELETE FROM notification_outbox")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO notification_outbox (channel, payload) VALUES ('course_changes', $1)")
+        .bind(serde_json::json!({ "kind": "course_changes", "audit_ids": [events[0].audit_id] }))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    sqlx::raw_sql(include_str!(
+        "../migrations/20260521000000_course_change_events.sql"
+    ))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (payload,): (sqlx::types::Json<OutboxPayload>,) =
+        sqlx::query_as("SELECT payload FROM notification_outbox")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(payload.0, OutboxPayload::CourseChanges { events });
+}
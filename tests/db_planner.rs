@@ -0,0 +1,86 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::courses::get_courses_by_crns;
+use banner::data::planner::{delete_schedule, get_schedule, list_schedules, replace_schedule};
+use helpers::{insert_user, make_course};
+use sqlx::PgPool;
+
+fn crns(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| (*s).to_owned()).collect()
+}
+
+#[sqlx::test]
+async fn schedules_are_kept_per_user_and_term(pool: PgPool) {
+    insert_user(&pool, 1).await;
+    insert_user(&pool, 2).await;
+
+    assert!(get_schedule(&pool, 1, "202620").await.unwrap().is_none());
+
+    replace_schedule(&pool, 1, "202620", &crns(&["10001", "10002"]))
+        .await
+        .unwrap();
+    replace_schedule(&pool, 1, "202710", &crns(&["20001"]))
+        .await
+        .unwrap();
+    let first = get_schedule(&pool, 1, "202620").await.unwrap().unwrap();
+
+    // Saving again replaces the list and bumps the timestamp.
+    let saved = replace_schedule(&pool, 1, "202620", &crns(&["10003"]))
+        .await
+        .unwrap();
+    assert_eq!(saved.crns, ["10003"]);
+    assert!(saved.updated_at >= first.updated_at);
+
+    assert_eq!(list_schedules(&pool, 1).await.unwrap().len(), 2);
+    assert!(list_schedules(&pool, 2).await.unwrap().is_empty());
+
+    assert!(!delete_schedule(&pool, 2, "202620").await.unwrap());
+    assert!(delete_schedule(&pool, 1, "202620").await.unwrap());
+    assert!(get_schedule(&pool, 1, "202620").await.unwrap().is_none());
+    assert!(get_schedule(&pool, 1, "202710").await.unwrap().is_some());
+}
+
+#[sqlx::test]
+async fn planned_crns_resolve_within_their_term(pool: PgPool) {
+    batch_upsert_courses(
+        &[
+            make_course(
+                "10001",
+                "202620",
+                "MATH",
+                "1214",
+                "Calculus I",
+                (10, 30, 0, 0),
+            ),
+            make_course(
+                "10002",
+                "202620",
+                "CS",
+                "1083",
+                "Intro to CS",
+                (10, 30, 0, 0),
+            ),
+            make_course(
+                "10001",
+                "202710",
+                "CS",
+                "2124",
+                "Data Structures",
+                (10, 30, 0, 0),
+            ),
+        ],
+        &pool,
+    )
+    .await
+    .unwrap();
+
+    let found = get_courses_by_crns(&pool, "202620", &crns(&["10001", "10002", "99999"]))
+        .await
+        .unwrap();
+    let codes: Vec<(&str, &str)> = found
+        .iter()
+        .map(|c| (c.subject.as_str(), c.crn.as_str()))
+        .collect();
+    assert_eq!(codes, [("CS", "10002"), ("MATH", "10001")]);
+}
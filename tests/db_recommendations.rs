@@ -0,0 +1,66 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::recommendations::{get_related, recompute_related};
+use sqlx::PgPool;
+
+const TERM: &str = "202610";
+
+async fn insert_instructor(pool: &PgPool, name: &str, slug: &str) -> i32 {
+    sqlx::query_scalar("INSERT INTO instructors (display_name, slug) VALUES ($1, $2) RETURNING id")
+        .bind(name)
+        .bind(slug)
+        .fetch_one(pool)
+        .await
+        .expect("failed to create instructor")
+}
+
+async fn assign(pool: &PgPool, crn: &str, instructor_id: i32) {
+    sqlx::query(
+        "INSERT INTO course_instructors (course_id, instructor_id, banner_id, is_primary)
+         SELECT id, $2, 'G' || $2, false FROM courses WHERE crn = $1",
+    )
+    .bind(crn)
+    .bind(instructor_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn related_ranks_co_teachers_above_subject_peers(pool: PgPool) {
+    let courses = vec![
+        helpers::make_course("80001", TERM, "CS", "1083", "Intro", (0, 30, 0, 0)),
+        helpers::make_course("80002", TERM, "CS", "2123", "Data", (0, 30, 0, 0)),
+        helpers::make_course("80003", TERM, "CS", "3343", "Algorithms", (0, 30, 0, 0)),
+        helpers::make_course("80004", TERM, "MAT", "1214", "Calculus", (0, 30, 0, 0)),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+
+    let alice = insert_instructor(&pool, "Alice", "alice").await;
+    let bob = insert_instructor(&pool, "Bob", "bob").await;
+    let carol = insert_instructor(&pool, "Carol", "carol").await;
+    let dave = insert_instructor(&pool, "Dave", "dave").await;
+
+    // Alice and Bob co-teach; Carol only shares the subject; Dave shares nothing.
+    assign(&pool, "80001", alice).await;
+    assign(&pool, "80001", bob).await;
+    assign(&pool, "80002", carol).await;
+    assign(&pool, "80004", dave).await;
+    assign(&pool, "80003", alice).await;
+
+    let written = recompute_related(&pool).await.unwrap();
+    assert!(written > 0);
+
+    let related = get_related(&pool, alice, 10).await.unwrap();
+    let slugs: Vec<_> = related.iter().map(|r| r.slug.as_str()).collect();
+    assert_eq!(slugs, ["bob", "carol"]);
+    assert_eq!(related[0].co_taught_sections, 1);
+    assert_eq!(related[0].shared_subjects, ["CS"]);
+    assert_eq!(related[1].co_taught_sections, 0);
+
+    assert!(get_related(&pool, dave, 10).await.unwrap().is_empty());
+
+    // A rebuild replaces rather than duplicates.
+    assert_eq!(recompute_related(&pool).await.unwrap(), written);
+}
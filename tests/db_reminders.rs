@@ -0,0 +1,107 @@
+mod helpers;
+
+use banner::data::identities::ensure_discord_user;
+use banner::data::reminders::{
+    Classification, find_due_reminders, list_reminders, mark_notified, replace_windows,
+    upsert_reminder,
+};
+use chrono::{Duration, Utc};
+use helpers::insert_user;
+use sqlx::PgPool;
+
+async fn insert_term(pool: &PgPool, code: &str) {
+    sqlx::query(
+        "INSERT INTO terms (code, description, year, season, scrape_enabled)
+         VALUES ($1, 'Fall 2026', 2026, 'Fall', false)",
+    )
+    .bind(code)
+    .execute(pool)
+    .await
+    .expect("failed to insert term");
+}
+
+#[sqlx::test]
+async fn reminders_fire_once_their_window_opens(pool: PgPool) {
+    insert_term(&pool, "202710").await;
+    insert_user(&pool, 1).await;
+    insert_user(&pool, 2).await;
+
+    let now = Utc::now();
+    replace_windows(
+        &pool,
+        "202710",
+        &[
+            (Classification::Senior, now - Duration::hours(1)),
+            (Classification::Junior, now + Duration::days(2)),
+        ],
+    )
+    .await
+    .expect("failed to set windows");
+
+    assert!(
+        upsert_reminder(&pool, 1, "202710", Classification::Senior)
+            .await
+            .unwrap()
+    );
+    upsert_reminder(&pool, 2, "202710", Classification::Junior)
+        .await
+        .unwrap();
+    // No window published for freshmen yet.
+    upsert_reminder(&pool, 2, "202710", Classification::Freshman)
+        .await
+        .unwrap();
+
+    let due = find_due_reminders(&pool, now).await.unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].user_id, 1);
+    assert_eq!(due[0].classification, "senior");
+
+    mark_notified(&pool, &[due[0].reminder_id]).await.unwrap();
+    assert!(find_due_reminders(&pool, now).await.unwrap().is_empty());
+
+    // Re-arming clears the sent marker.
+    assert!(
+        !upsert_reminder(&pool, 1, "202710", Classification::Senior)
+            .await
+            .unwrap()
+    );
+    assert_eq!(find_due_reminders(&pool, now).await.unwrap().len(), 1);
+
+    let listed = list_reminders(&pool, 2).await.unwrap();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0].classification, "junior");
+    assert!(listed[0].opens_at.is_some());
+    assert!(listed[1].opens_at.is_none());
+}
+
+#[sqlx::test]
+async fn reminders_address_dms_through_the_discord_identity(pool: PgPool) {
+    insert_term(&pool, "202710").await;
+    // A bot-created user gets a Discord identity; a local account has none.
+    let bot_user = ensure_discord_user(&pool, 1001, "student").await.unwrap();
+    insert_user(&pool, 5).await;
+
+    let now = Utc::now();
+    replace_windows(
+        &pool,
+        "202710",
+        &[(Classification::Senior, now - Duration::hours(1))],
+    )
+    .await
+    .unwrap();
+    for user in [bot_user, 5] {
+        upsert_reminder(&pool, user, "202710", Classification::Senior)
+            .await
+            .unwrap();
+    }
+
+    let due = find_due_reminders(&pool, now).await.unwrap();
+    let discord_id = |user: i64| {
+        due.iter()
+            .find(|r| r.user_id == user)
+            .map(|r| r.discord_id)
+            .unwrap()
+    };
+    assert_eq!(discord_id(bot_user), Some(1001));
+    assert_eq!(discord_id(5), None);
+}
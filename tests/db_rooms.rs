@@ -0,0 +1,65 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::rooms::{BUILDING_CATEGORY, ROOM_CATEGORY, reference_entries};
+use helpers::{MeetingTimeBuilder, make_course, with_meetings};
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn rooms_are_collected_from_meeting_locations(pool: PgPool) {
+    let term = "202620";
+    let meeting = |building: &str, room: &str| {
+        MeetingTimeBuilder::new()
+            .days([true, false, true, false, false, false, false])
+            .time("0900", "0950")
+            .location(building, room)
+            .build()
+    };
+    let courses = vec![
+        with_meetings(
+            make_course("30001", term, "CS", "1100", "Intro to CS", (10, 30, 0, 0)),
+            vec![meeting("NPB", "1.238"), meeting("NPB", "1.226")],
+        ),
+        with_meetings(
+            make_course(
+                "30002",
+                term,
+                "CS",
+                "2200",
+                "Data Structures",
+                (10, 30, 0, 0),
+            ),
+            vec![meeting("NPB", "1.238")],
+        ),
+        // Online sections have no room and are skipped.
+        with_meetings(
+            make_course("30003", term, "CS", "3300", "Algorithms", (10, 30, 0, 0)),
+            vec![MeetingTimeBuilder::new().build()],
+        ),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+
+    let mut entries: Vec<(String, String)> = reference_entries(&pool, term)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| (e.category, e.code))
+        .collect();
+    entries.sort();
+
+    assert_eq!(
+        entries,
+        vec![
+            (BUILDING_CATEGORY.to_owned(), "NPB".to_owned()),
+            (ROOM_CATEGORY.to_owned(), "NPB 1.226".to_owned()),
+            (ROOM_CATEGORY.to_owned(), "NPB 1.238".to_owned()),
+        ]
+    );
+
+    let located: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM course_meetings WHERE room = '1.238'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(located, 2);
+}
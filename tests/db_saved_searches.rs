@@ -0,0 +1,164 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::saved_searches::{
+    create_saved_search, delete_saved_search, find_notifying_searches, list_saved_searches,
+    record_matches,
+};
+use banner::web::courses::SearchParams;
+use banner::web::saved_searches::notify_matches;
+use helpers::{insert_user, make_course};
+use serde_json::json;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn saved_searches_belong_to_their_owner(pool: PgPool) {
+    insert_user(&pool, 1).await;
+    insert_user(&pool, 2).await;
+
+    let filters = json!({ "term": "202620", "subject": ["CS"] });
+    let saved = create_saved_search(&pool, 1, "CS", "202620", &filters, false, &[])
+        .await
+        .unwrap();
+
+    let listed = list_saved_searches(&pool, 1).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].name, "CS");
+    assert_eq!(listed[0].filters.0, filters);
+    assert!(list_saved_searches(&pool, 2).await.unwrap().is_empty());
+
+    assert!(!delete_saved_search(&pool, 2, saved.id).await.unwrap());
+    assert!(delete_saved_search(&pool, 1, saved.id).await.unwrap());
+    assert!(list_saved_searches(&pool, 1).await.unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn subject_scrapes_select_searches_that_could_match(pool: PgPool) {
+    insert_user(&pool, 1).await;
+
+    for (name, term, filters, notify) in [
+        (
+            "cs",
+            "202620",
+            json!({ "term": "202620", "subject": ["CS"] }),
+            true,
+        ),
+        (
+            "any",
+            "202620",
+            json!({ "term": "202620", "subject": [] }),
+            true,
+        ),
+        (
+            "math",
+            "202620",
+            json!({ "term": "202620", "subject": ["MATH"] }),
+            true,
+        ),
+        (
+            "quiet",
+            "202620",
+            json!({ "term": "202620", "subject": ["CS"] }),
+            false,
+        ),
+        (
+            "spring",
+            "202710",
+            json!({ "term": "202710", "subject": ["CS"] }),
+            true,
+        ),
+    ] {
+        create_saved_search(&pool, 1, name, term, &filters, notify, &[])
+            .await
+            .unwrap();
+    }
+
+    let mut names: Vec<String> = find_notifying_searches(&pool, "202620", "CS")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    names.sort();
+    // A search without a subject filter would be re-run after every subject
+    // scrape, so it never notifies.
+    assert_eq!(names, ["cs"]);
+}
+
+#[sqlx::test]
+async fn new_matches_are_measured_against_the_baseline(pool: PgPool) {
+    insert_user(&pool, 1).await;
+    let term = "202620";
+    batch_upsert_courses(
+        &[
+            make_course("10001", term, "CS", "1083", "Intro to CS", (10, 30, 0, 0)),
+            make_course(
+                "10002",
+                term,
+                "CS",
+                "2124",
+                "Data Structures",
+                (30, 30, 0, 0),
+            ),
+        ],
+        &pool,
+    )
+    .await
+    .unwrap();
+
+    let filters = json!({ "term": term, "subject": ["CS"], "openOnly": true });
+    let params: SearchParams = serde_json::from_value(filters.clone()).unwrap();
+    let baseline: Vec<i32> = notify_matches(&pool, term, &params)
+        .await
+        .unwrap()
+        .expect("within the notify limit")
+        .iter()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(baseline.len(), 1, "only the open section matches");
+    let saved = create_saved_search(&pool, 1, "Open CS", term, &filters, true, &baseline)
+        .await
+        .unwrap();
+
+    // A seat opens in the full section.
+    batch_upsert_courses(
+        &[make_course(
+            "10002",
+            term,
+            "CS",
+            "2124",
+            "Data Structures",
+            (29, 30, 0, 0),
+        )],
+        &pool,
+    )
+    .await
+    .unwrap();
+
+    let search = find_notifying_searches(&pool, term, "CS")
+        .await
+        .unwrap()
+        .remove(0);
+    let matches = notify_matches(&pool, term, &params).await.unwrap().unwrap();
+    let new: Vec<&str> = matches
+        .iter()
+        .filter(|c| !search.matched_course_ids.contains(&c.id))
+        .map(|c| c.crn.as_str())
+        .collect();
+    assert_eq!(new, ["10002"]);
+
+    let matched: Vec<i32> = matches.iter().map(|c| c.id).collect();
+    record_matches(&pool, saved.id, &matched, true)
+        .await
+        .unwrap();
+    let search = find_notifying_searches(&pool, term, "CS")
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(search.matched_course_ids.len(), 2);
+    assert!(
+        list_saved_searches(&pool, 1).await.unwrap()[0]
+            .notified_at
+            .is_some()
+    );
+}
@@ -0,0 +1,40 @@
+use banner::data::migrations::{EXPECTED_SCHEMA, find_schema_drift, schema_snapshot};
+use sqlx::PgPool;
+
+/// The checked-in snapshot must describe what the migrations build. Run with
+/// `UPDATE_SCHEMA_SNAPSHOT=1` to rewrite it after adding a migration.
+#[sqlx::test]
+async fn snapshot_matches_migrations(pool: PgPool) {
+    let live = schema_snapshot(&pool).await.unwrap();
+    if std::env::var_os("UPDATE_SCHEMA_SNAPSHOT").is_some() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/data/schema.snapshot");
+        std::fs::write(path, live.join("\n") + "\n").unwrap();
+        return;
+    }
+    let drift = find_schema_drift(EXPECTED_SCHEMA, &live);
+    assert!(
+        drift.is_empty(),
+        "schema snapshot is stale; rerun with UPDATE_SCHEMA_SNAPSHOT=1:\n{drift}"
+    );
+}
+
+#[sqlx::test]
+async fn manual_schema_edits_are_detected(pool: PgPool) {
+    sqlx::query("CREATE INDEX hand_made ON courses (title)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("ALTER TABLE courses ALTER COLUMN title DROP NOT NULL")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let drift = find_schema_drift(EXPECTED_SCHEMA, &schema_snapshot(&pool).await.unwrap());
+    assert!(drift.unexpected.iter().any(|l| l.contains("hand_made")));
+    assert!(
+        drift
+            .missing
+            .iter()
+            .any(|l| l.starts_with("column courses.title "))
+    );
+}
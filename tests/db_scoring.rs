@@ -0,0 +1,86 @@
+use banner::data::scoring::{RecomputeMode, ScoringDecay, recompute_scores};
+use sqlx::PgPool;
+
+/// An instructor linked to an RMP profile with the given rating.
+async fn insert_rated_instructor(pool: &PgPool, legacy_id: i32, rating: f32) -> i32 {
+    sqlx::query(
+        "INSERT INTO rmp_professors (legacy_id, graphql_id, first_name, last_name, avg_rating, num_ratings)
+         VALUES ($1, $1::text, 'Ada', 'Lovelace', $2, 20)",
+    )
+    .bind(legacy_id)
+    .bind(rating)
+    .execute(pool)
+    .await
+    .unwrap();
+    let instructor_id: i32 = sqlx::query_scalar(
+        "INSERT INTO instructors (display_name, slug) VALUES ($1, $1) RETURNING id",
+    )
+    .bind(format!("instructor-{legacy_id}"))
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO instructor_rmp_links (instructor_id, rmp_legacy_id) VALUES ($1, $2)")
+        .bind(instructor_id)
+        .bind(legacy_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    instructor_id
+}
+
+async fn score(pool: &PgPool, instructor_id: i32) -> Option<f32> {
+    sqlx::query_scalar("SELECT display_score FROM instructor_scores WHERE instructor_id = $1")
+        .bind(instructor_id)
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn incremental_recompute_only_touches_changed_instructors(pool: PgPool) {
+    let decay = ScoringDecay::default();
+    let changed = insert_rated_instructor(&pool, 101, 3.0).await;
+    let unlinked = insert_rated_instructor(&pool, 102, 4.0).await;
+
+    // Without a previous full run, incremental falls back to full.
+    let stats = recompute_scores(&pool, &decay, RecomputeMode::Incremental)
+        .await
+        .unwrap();
+    assert_eq!((stats.mode, stats.recomputed), (RecomputeMode::Full, 2));
+
+    let stats = recompute_scores(&pool, &decay, RecomputeMode::Incremental)
+        .await
+        .unwrap();
+    assert_eq!(
+        (stats.mode, stats.recomputed),
+        (RecomputeMode::Incremental, 0)
+    );
+
+    // A sync that changes the rating records a history snapshot.
+    let before = score(&pool, changed).await.unwrap();
+    sqlx::query("UPDATE rmp_professors SET avg_rating = 5.0 WHERE legacy_id = 101")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO rmp_professor_history (legacy_id, avg_rating, num_ratings) VALUES (101, 5.0, 20)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query("DELETE FROM instructor_rmp_links WHERE instructor_id = $1")
+        .bind(unlinked)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let stats = recompute_scores(&pool, &decay, RecomputeMode::Incremental)
+        .await
+        .unwrap();
+    assert_eq!(
+        (stats.mode, stats.recomputed),
+        (RecomputeMode::Incremental, 1)
+    );
+    assert!(score(&pool, changed).await.unwrap() > before);
+    assert_eq!(score(&pool, unlinked).await, None);
+}
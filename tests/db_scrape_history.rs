@@ -0,0 +1,150 @@
+use banner::data::scrape_history;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use sqlx::PgPool;
+
+async fn insert_result(pool: &PgPool, completed_at: DateTime<Utc>, success: bool, changed: i32) {
+    insert_subject_result(pool, "CS", completed_at, success, changed).await;
+}
+
+async fn insert_subject_result(
+    pool: &PgPool,
+    subject: &str,
+    completed_at: DateTime<Utc>,
+    success: bool,
+    changed: i32,
+) {
+    sqlx::query(
+        "INSERT INTO scrape_job_results
+            (target_type, payload, priority, queued_at, started_at, completed_at, duration_ms,
+             success, courses_fetched, courses_changed)
+         VALUES ('Subject', jsonb_build_object('subject', $4::text, 'term', '202610'), 'Medium',
+                 $1, $1, $1, 100, $2, $3, $3)",
+    )
+    .bind(completed_at)
+    .bind(success)
+    .bind(success.then_some(changed))
+    .bind(subject)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn remaining_results(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM scrape_job_results")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn compact_rolls_old_results_into_daily_stats(pool: PgPool) {
+    let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap();
+    insert_result(&pool, at(1, 9), true, 4).await;
+    insert_result(&pool, at(1, 18), false, 0).await;
+    insert_result(&pool, at(2, 12), true, 2).await;
+    insert_result(&pool, at(20, 12), true, 7).await;
+
+    let compacted = scrape_history::compact(&pool, at(10, 0)).await.unwrap();
+    assert_eq!(compacted.results, 3);
+    assert_eq!(remaining_results(&pool).await, 1);
+
+    let (runs, successes, failures, duration, changed): (i32, i32, i32, i64, i64) = sqlx::query_as(
+        "SELECT runs, successes, failures, total_duration_ms, courses_changed
+             FROM scrape_job_daily_stats WHERE day = $1",
+    )
+    .bind(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!((runs, successes, failures), (2, 1, 1));
+    assert_eq!(duration, 200);
+    assert_eq!(changed, 4);
+}
+
+#[sqlx::test]
+async fn compact_adds_to_partly_compacted_days(pool: PgPool) {
+    let at = |hour: u32| Utc.with_ymd_and_hms(2026, 3, 1, hour, 0, 0).unwrap();
+    insert_result(&pool, at(9), true, 4).await;
+    insert_result(&pool, at(10), true, 0).await;
+    scrape_history::compact(&pool, at(12)).await.unwrap();
+
+    // The 10:00 result was kept as the latest; it is compacted now.
+    insert_result(&pool, at(15), true, 3).await;
+    insert_result(&pool, at(20), true, 0).await;
+    let compacted = scrape_history::compact(&pool, at(23)).await.unwrap();
+    assert_eq!(compacted.results, 2);
+
+    let (runs, changed): (i32, i64) =
+        sqlx::query_as("SELECT runs, courses_changed FROM scrape_job_daily_stats")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(runs, 3);
+    assert_eq!(changed, 7);
+
+    // Only the latest result is left past the cutoff: a no-op that still
+    // reports zero.
+    let compacted = scrape_history::compact(&pool, at(23)).await.unwrap();
+    assert_eq!(compacted, scrape_history::Compaction::default());
+}
+
+#[sqlx::test]
+async fn compact_keeps_each_subjects_latest_result(pool: PgPool) {
+    let at = |day: u32| Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+    insert_subject_result(&pool, "CS", at(1), true, 1).await;
+    insert_subject_result(&pool, "CS", at(2), true, 1).await;
+    insert_subject_result(&pool, "MATH", at(3), false, 0).await;
+
+    let compacted = scrape_history::compact(&pool, at(20)).await.unwrap();
+    assert_eq!(compacted.results, 1);
+
+    let kept: Vec<(String, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT payload->>'subject', completed_at FROM scrape_job_results ORDER BY 1",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(kept, vec![("CS".into(), at(2)), ("MATH".into(), at(3))]);
+}
+
+#[sqlx::test]
+async fn compact_purges_old_dead_letters(pool: PgPool) {
+    let at = |day: u32| Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+    for (subject, dead_lettered_at) in [("CS", Some(at(1))), ("MATH", Some(at(15))), ("ENG", None)]
+    {
+        sqlx::query(
+            "INSERT INTO scrape_jobs (target_type, target_payload, priority, execute_at, dead_lettered_at)
+             VALUES ('Subject', jsonb_build_object('subject', $1::text, 'term', '202610'), 'Medium', $2, $3)",
+        )
+        .bind(subject)
+        .bind(at(1))
+        .bind(dead_lettered_at)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let compacted = scrape_history::compact(&pool, at(10)).await.unwrap();
+    assert_eq!(compacted.dead_letters, 1);
+
+    let left: Vec<String> =
+        sqlx::query_scalar("SELECT target_payload->>'subject' FROM scrape_jobs ORDER BY 1")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(left, vec!["ENG", "MATH"]);
+}
+
+#[sqlx::test]
+async fn compaction_status_round_trips(pool: PgPool) {
+    let status = scrape_history::compaction_status(&pool).await.unwrap();
+    assert!(status.last_run_at.is_none());
+
+    let at = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+    scrape_history::record_compaction(&pool, at, 42)
+        .await
+        .unwrap();
+    let status = scrape_history::compaction_status(&pool).await.unwrap();
+    assert_eq!(status.last_run_at, Some(at));
+    assert_eq!(status.last_compacted_rows, Some(42));
+}
@@ -72,6 +72,84 @@ async fn lock_next_skips_locked_jobs(pool: PgPool) {
     assert!(result.is_none(), "locked jobs should be skipped");
 }
 
+#[sqlx::test]
+async fn lock_next_excluding_skips_excluded_types(pool: PgPool) {
+    helpers::insert_scrape_job(
+        &pool,
+        TargetType::CrnList,
+        json!({"crns": ["10001"]}),
+        ScrapePriority::Critical,
+        false,
+        0,
+        3,
+    )
+    .await;
+    let subject_id = helpers::insert_scrape_job(
+        &pool,
+        TargetType::Subject,
+        json!({"subject": "CS"}),
+        ScrapePriority::Low,
+        false,
+        0,
+        3,
+    )
+    .await;
+
+    let ctx = make_ctx(pool);
+    let job = ctx
+        .scrape_jobs()
+        .lock_next_excluding(&[TargetType::CrnList])
+        .await
+        .unwrap()
+        .expect("should return the subject job");
+    assert_eq!(job.id, subject_id);
+
+    let rest = ctx
+        .scrape_jobs()
+        .lock_next_excluding(&[TargetType::CrnList])
+        .await
+        .unwrap();
+    assert!(rest.is_none(), "excluded types should never be locked");
+}
+
+#[sqlx::test]
+async fn lock_next_prefers_terms_with_fewer_running_jobs(pool: PgPool) {
+    for (subject, locked) in [("CS", true), ("MAT", false)] {
+        helpers::insert_scrape_job(
+            &pool,
+            TargetType::Subject,
+            json!({"subject": subject, "term": "202610"}),
+            ScrapePriority::Medium,
+            locked,
+            0,
+            3,
+        )
+        .await;
+    }
+    let other_term = helpers::insert_scrape_job(
+        &pool,
+        TargetType::Subject,
+        json!({"subject": "CS", "term": "202620"}),
+        ScrapePriority::Medium,
+        false,
+        0,
+        3,
+    )
+    .await;
+
+    let ctx = make_ctx(pool);
+    let job = ctx
+        .scrape_jobs()
+        .lock_next()
+        .await
+        .unwrap()
+        .expect("should return a job");
+    assert_eq!(
+        job.id, other_term,
+        "the term with nothing running should go first"
+    );
+}
+
 #[sqlx::test]
 async fn lock_next_skips_future_execute_at(pool: PgPool) {
     // Insert a job with execute_at in the future via raw SQL
@@ -367,3 +445,165 @@ async fn batch_insert_empty_slice(pool: PgPool) {
         .unwrap();
     assert_eq!(count, 0);
 }
+
+#[sqlx::test]
+async fn dead_lettered_jobs_leave_the_queue_until_requeued(pool: PgPool) {
+    use banner::data::models::ScrapeJobStatus;
+    use banner::data::scrape_jobs::{RequeueOutcome, list_dead_letters, list_ordered};
+    use banner::data::unsigned::Count;
+
+    let payload = json!({"subject": "CS", "term": "202620"});
+    let id = helpers::insert_scrape_job(
+        &pool,
+        TargetType::Subject,
+        payload.clone(),
+        ScrapePriority::Medium,
+        false,
+        2,
+        3,
+    )
+    .await;
+
+    let ctx = make_ctx(pool.clone());
+    ctx.scrape_jobs()
+        .dead_letter(id, Count::new(3), "search timed out")
+        .await
+        .unwrap();
+
+    assert!(ctx.scrape_jobs().lock_next().await.unwrap().is_none());
+    assert!(list_ordered(&pool, 10).await.unwrap().is_empty());
+    // The scheduler may queue the target again while the dead letter waits.
+    let existing = ctx
+        .scrape_jobs()
+        .find_existing_payloads(TargetType::Subject, std::slice::from_ref(&payload))
+        .await
+        .unwrap();
+    assert!(existing.is_empty());
+
+    let parked = list_dead_letters(&pool, 10).await.unwrap();
+    assert_eq!(parked.len(), 1);
+    assert_eq!(parked[0].status(), ScrapeJobStatus::DeadLetter);
+    assert_eq!(parked[0].last_error.as_deref(), Some("search timed out"));
+
+    let RequeueOutcome::Requeued(job) = ctx.scrape_jobs().requeue(id).await.unwrap() else {
+        panic!("expected the job to be requeued");
+    };
+    assert_eq!(job.retry_count, Count::new(0));
+    assert!(job.dead_lettered_at.is_none());
+    assert!(matches!(
+        ctx.scrape_jobs().requeue(id).await.unwrap(),
+        RequeueOutcome::NotFound
+    ));
+    assert_eq!(ctx.scrape_jobs().lock_next().await.unwrap().unwrap().id, id);
+}
+
+#[sqlx::test]
+async fn requeue_drops_dead_letter_when_target_is_already_queued(pool: PgPool) {
+    use banner::data::scrape_jobs::RequeueOutcome;
+    use banner::data::unsigned::Count;
+
+    let payload = json!({"subject": "MAT", "term": "202620"});
+    let dead = helpers::insert_scrape_job(
+        &pool,
+        TargetType::Subject,
+        payload.clone(),
+        ScrapePriority::Low,
+        false,
+        0,
+        3,
+    )
+    .await;
+    let ctx = make_ctx(pool.clone());
+    ctx.scrape_jobs()
+        .dead_letter(dead, Count::new(3), "boom")
+        .await
+        .unwrap();
+    helpers::insert_scrape_job(
+        &pool,
+        TargetType::Subject,
+        payload,
+        ScrapePriority::Low,
+        false,
+        0,
+        3,
+    )
+    .await;
+
+    assert!(matches!(
+        ctx.scrape_jobs().requeue(dead).await.unwrap(),
+        RequeueOutcome::Duplicate
+    ));
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM scrape_jobs")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[sqlx::test]
+async fn insert_result_advances_subject_scrape_time(pool: PgPool) {
+    use banner::data::unsigned::{Count, DurationMs};
+
+    let ctx = make_ctx(pool.clone());
+    let log = |success: bool, subject: &'static str| {
+        let ctx = &ctx;
+        async move {
+            ctx.scrape_jobs()
+                .insert_result(
+                    TargetType::Subject,
+                    json!({"subject": subject, "term": "202620"}),
+                    ScrapePriority::Medium,
+                    chrono::Utc::now(),
+                    chrono::Utc::now(),
+                    DurationMs::new(10),
+                    success,
+                    None,
+                    Count::new(0),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+    };
+    log(true, "CS").await;
+    log(false, "MAT").await;
+
+    let rows: Vec<(String,)> =
+        sqlx::query_as("SELECT subject_code FROM subject_scrape_times WHERE term_code = '202620'")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(rows, vec![("CS".to_owned(),)]);
+}
+
+#[sqlx::test]
+async fn manual_scrapes_reject_unknown_subjects(pool: PgPool) {
+    use banner::scraper::{ManualScrapeError, enqueue_subject_scrape};
+
+    sqlx::query(
+        "INSERT INTO terms (code, description, year, season, scrape_enabled)
+         VALUES ('202710', 'Fall 2026', 2026, 'Fall', false)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO reference_data (category, code, description)
+         VALUES ('subject', 'CS', 'Computer Science')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let ctx = make_ctx(pool);
+
+    let err = enqueue_subject_scrape(&ctx, "202710", "NOPE")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ManualScrapeError>(),
+        Some(ManualScrapeError::UnknownSubject(s)) if s == "NOPE"
+    ));
+
+    let job = enqueue_subject_scrape(&ctx, "202710", "cs").await.unwrap();
+    assert!(job.is_some(), "known subjects are queued in any case");
+}
@@ -0,0 +1,94 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::sparklines;
+use chrono::{NaiveDate, TimeZone, Utc};
+use sqlx::PgPool;
+
+async fn insert_metric(pool: &PgPool, course_id: i32, ts: chrono::DateTime<Utc>, enrollment: i32) {
+    sqlx::query(
+        "INSERT INTO course_metrics (course_id, timestamp, enrollment, wait_count, seats_available)
+         VALUES ($1, $2, $3, 0, 0)",
+    )
+    .bind(course_id)
+    .bind(ts)
+    .bind(enrollment)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn rollup_takes_last_sample_per_day_and_carries_gaps(pool: PgPool) {
+    let course = helpers::make_course("70001", "202610", "CS", "1083", "Intro", (5, 30, 0, 0));
+    batch_upsert_courses(&[course], &pool).await.unwrap();
+    let (course_id,): (i32,) = sqlx::query_as("SELECT id FROM courses")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM course_metrics")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap();
+    insert_metric(&pool, course_id, at(7, 9), 10).await;
+    insert_metric(&pool, course_id, at(7, 18), 12).await;
+    // Nothing on the 8th.
+    insert_metric(&pool, course_id, at(9, 12), 15).await;
+
+    let written = sparklines::rollup(&pool, today).await.unwrap();
+    assert_eq!(written, 1);
+
+    let map = sparklines::get_for_courses(&pool, &[course_id])
+        .await
+        .unwrap();
+    assert_eq!(map[&course_id], vec![12, 12, 15, 15]);
+
+    let (start,): (NaiveDate,) =
+        sqlx::query_as("SELECT start_date FROM course_enrollment_sparklines")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(start, NaiveDate::from_ymd_opt(2026, 3, 7).unwrap());
+}
+
+#[sqlx::test]
+async fn rollup_seeds_window_from_earlier_snapshot(pool: PgPool) {
+    let course = helpers::make_course("70002", "202610", "CS", "2123", "Data", (5, 30, 0, 0));
+    batch_upsert_courses(&[course], &pool).await.unwrap();
+    let (course_id,): (i32,) = sqlx::query_as("SELECT id FROM courses")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM course_metrics")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE courses SET last_scraped_at = $1")
+        .bind(Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    // Only the latest snapshot before the window seeds it.
+    for (day, enrollment) in [(5, 20), (2, 12)] {
+        insert_metric(
+            &pool,
+            course_id,
+            Utc.with_ymd_and_hms(2026, 1, day, 0, 0, 0).unwrap(),
+            enrollment,
+        )
+        .await;
+    }
+
+    sparklines::rollup(&pool, today).await.unwrap();
+    let map = sparklines::get_for_courses(&pool, &[course_id])
+        .await
+        .unwrap();
+    let series = &map[&course_id];
+    assert_eq!(series.len(), sparklines::WINDOW_DAYS as usize);
+    assert!(series.iter().all(|&v| v == 20));
+}
@@ -0,0 +1,47 @@
+use banner::data::subject_aliases::{SubjectAliasError, list_aliases, upsert_alias};
+use sqlx::PgPool;
+use std::time::Duration;
+
+#[sqlx::test]
+async fn chained_aliases_are_rejected(pool: PgPool) {
+    upsert_alias(&pool, "STA", "STS", None).await.unwrap();
+
+    let err = upsert_alias(&pool, "STAT", "STA", None).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<SubjectAliasError>(),
+        Some(SubjectAliasError::ChainedTarget(code)) if code == "STA"
+    ));
+
+    let err = upsert_alias(&pool, "STS", "MAT", None).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<SubjectAliasError>(),
+        Some(SubjectAliasError::AliasHasAliases(code)) if code == "STS"
+    ));
+
+    // Repointing an existing alias is still allowed.
+    upsert_alias(&pool, "STA", "MAT", None).await.unwrap();
+}
+
+#[sqlx::test]
+async fn concurrent_renames_cannot_form_a_cycle(pool: PgPool) {
+    // Another writer has added STS -> STA but not committed yet.
+    let mut other = pool.begin().await.unwrap();
+    sqlx::query("INSERT INTO subject_aliases (alias, subject) VALUES ('STS', 'STA')")
+        .execute(&mut *other)
+        .await
+        .unwrap();
+
+    let upsert = tokio::spawn({
+        let pool = pool.clone();
+        async move { upsert_alias(&pool, "STA", "STS", None).await }
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    other.commit().await.unwrap();
+
+    let err = upsert.await.unwrap().unwrap_err();
+    assert!(err.downcast_ref::<SubjectAliasError>().is_some());
+
+    let aliases = list_aliases(&pool).await.unwrap();
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].alias, "STS");
+}
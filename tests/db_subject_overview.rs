@@ -0,0 +1,73 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::subjects::get_overview;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn overview_counts_levels_and_terms(pool: PgPool) {
+    let courses = vec![
+        helpers::make_course("90001", "202610", "CS", "1083", "Intro", (0, 30, 0, 0)),
+        helpers::make_course("90002", "202610", "CS", "1083", "Intro", (0, 30, 0, 0)),
+        helpers::make_course("90003", "202610", "CS", "3343", "Algorithms", (0, 30, 0, 0)),
+        helpers::make_course("90004", "202620", "CS", "1083", "Intro", (0, 30, 0, 0)),
+        helpers::make_course("90005", "202620", "MAT", "1214", "Calculus", (0, 30, 0, 0)),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+
+    let overview = get_overview(&pool, "cs").await.unwrap().expect("CS exists");
+    assert_eq!(overview.code, "CS");
+
+    let levels: Vec<_> = overview
+        .levels
+        .iter()
+        .map(|l| (l.level.as_str(), u32::from(l.course_count)))
+        .collect();
+    assert_eq!(levels, [("1000", 1), ("3000", 1)]);
+
+    let terms: Vec<_> = overview
+        .terms
+        .iter()
+        .map(|t| (t.code.as_str(), u32::from(t.section_count)))
+        .collect();
+    assert_eq!(terms, [("202620", 1), ("202610", 3)]);
+
+    assert!(overview.top_instructors.is_empty());
+    assert!(overview.bluebook.is_none());
+}
+
+#[sqlx::test]
+async fn overview_resolves_aliases_and_misses_unknown(pool: PgPool) {
+    let courses = vec![
+        helpers::make_course(
+            "91001",
+            "202610",
+            "STA",
+            "1053",
+            "Statistics",
+            (0, 30, 0, 0),
+        ),
+        helpers::make_course(
+            "91002",
+            "202620",
+            "STS",
+            "1053",
+            "Statistics",
+            (0, 30, 0, 0),
+        ),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+    sqlx::query("INSERT INTO subject_aliases (alias, subject) VALUES ('STA', 'STS')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let overview = get_overview(&pool, "STA")
+        .await
+        .unwrap()
+        .expect("alias resolves");
+    assert_eq!(overview.code, "STS");
+    assert_eq!(overview.terms.len(), 2);
+
+    assert!(get_overview(&pool, "ZZZ").await.unwrap().is_none());
+}
@@ -0,0 +1,65 @@
+mod helpers;
+
+use banner::data::batch::batch_upsert_courses;
+use banner::data::courses::record_removals;
+use banner::data::term_changes::list_changes;
+use chrono::{Duration, Utc};
+use helpers::MeetingTimeBuilder;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn changes_report_additions_time_changes_and_removals(pool: PgPool) {
+    let since = Utc::now() - Duration::hours(1);
+    let meeting = |begin: &str, end: &str| {
+        MeetingTimeBuilder::new()
+            .days([true, false, true, false, false, false, false])
+            .time(begin, end)
+            .build()
+    };
+    let lecture = helpers::make_course("92001", "202620", "CS", "1083", "Intro", (0, 30, 0, 0));
+    let courses = vec![
+        helpers::with_meetings(lecture.clone(), vec![meeting("1000", "1115")]),
+        helpers::make_course("92002", "202620", "CS", "3343", "Algorithms", (0, 30, 0, 0)),
+        helpers::make_course("92003", "202610", "CS", "3343", "Algorithms", (0, 30, 0, 0)),
+    ];
+    batch_upsert_courses(&courses, &pool).await.unwrap();
+
+    // Same section moved to the afternoon.
+    batch_upsert_courses(
+        &[helpers::with_meetings(
+            lecture,
+            vec![meeting("1300", "1415")],
+        )],
+        &pool,
+    )
+    .await
+    .unwrap();
+
+    let removed = ["92002".to_owned()];
+    assert_eq!(
+        record_removals(&pool, "202620", &removed).await.unwrap(),
+        removed
+    );
+    // Already recorded: not repeated.
+    assert!(
+        record_removals(&pool, "202620", &removed)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let days = list_changes(&pool, "202620", None, since).await.unwrap();
+    assert_eq!(days.len(), 1);
+    let day = &days[0];
+    assert_eq!(day.counts.added.get(), 2);
+    assert_eq!(day.counts.removed.get(), 1);
+    assert_eq!(day.removed[0].crn, "92002");
+    assert_eq!(day.counts.time_changes.get(), 1);
+    assert_eq!(day.time_changes[0].section.crn, "92001");
+    assert_eq!(day.counts.instructor_changes.get(), 0);
+
+    let later = list_changes(&pool, "202620", None, Utc::now())
+        .await
+        .unwrap();
+    assert!(later.is_empty());
+}
@@ -0,0 +1,132 @@
+mod helpers;
+
+use banner::data::terms::{get_subject_scrape_activity, get_term_freshness};
+use sqlx::PgPool;
+
+async fn insert_term(pool: &PgPool, code: &str, enabled: bool) {
+    sqlx::query(
+        "INSERT INTO terms (code, description, year, season, scrape_enabled, last_scraped_at)
+         VALUES ($1, 'Test Term', 2025, 'Fall', $2, now())",
+    )
+    .bind(code)
+    .bind(enabled)
+    .execute(pool)
+    .await
+    .expect("failed to insert term");
+}
+
+async fn insert_subjects(pool: &PgPool, term: &str, subjects: &[&str]) {
+    for subject in subjects {
+        sqlx::query("INSERT INTO term_subjects (term_code, subject_code) VALUES ($1, $2)")
+            .bind(term)
+            .bind(subject)
+            .execute(pool)
+            .await
+            .expect("failed to insert term subject");
+    }
+}
+
+async fn insert_result(pool: &PgPool, term: &str, subject: &str, success: bool, hours_ago: i32) {
+    insert_result_with_changes(pool, term, subject, success, hours_ago, 0).await;
+}
+
+async fn insert_result_with_changes(
+    pool: &PgPool,
+    term: &str,
+    subject: &str,
+    success: bool,
+    hours_ago: i32,
+    courses_changed: i32,
+) {
+    // Mirrors `ScrapeJobOps::insert_result`, which also advances the
+    // subject's last scrape time, but with a backdated completion.
+    sqlx::query(
+        "WITH inserted AS (
+            INSERT INTO scrape_job_results
+                (target_type, payload, priority, queued_at, started_at, completed_at,
+                 duration_ms, success, courses_changed)
+            VALUES ('Subject', jsonb_build_object('subject', $2::text, 'term', $1::text),
+                    'Medium', now() - make_interval(hours => $4),
+                    now() - make_interval(hours => $4), now() - make_interval(hours => $4),
+                    100, $3, $5)
+            RETURNING success, completed_at
+         )
+         INSERT INTO subject_scrape_times (term_code, subject_code, last_scraped_at)
+         SELECT $1, $2, completed_at FROM inserted WHERE success
+         ON CONFLICT (term_code, subject_code) DO UPDATE
+             SET last_scraped_at = GREATEST(
+                 subject_scrape_times.last_scraped_at, EXCLUDED.last_scraped_at
+             )",
+    )
+    .bind(term)
+    .bind(subject)
+    .bind(success)
+    .bind(hours_ago)
+    .bind(courses_changed)
+    .execute(pool)
+    .await
+    .expect("failed to insert scrape result");
+}
+
+#[sqlx::test]
+async fn coverage_counts_recent_successful_subject_scrapes(pool: PgPool) {
+    insert_term(&pool, "202610", true).await;
+    insert_term(&pool, "202520", true).await;
+    insert_term(&pool, "202510", false).await;
+    insert_subjects(&pool, "202610", &["CS", "MAT", "PHY", "BIO"]).await;
+
+    insert_result(&pool, "202610", "CS", true, 1).await;
+    insert_result(&pool, "202610", "CS", true, 2).await;
+    insert_result(&pool, "202610", "MAT", false, 1).await;
+    insert_result(&pool, "202610", "PHY", true, 48).await;
+    // Not one of the term's listed subjects.
+    insert_result(&pool, "202610", "XYZ", true, 1).await;
+
+    let rows = get_term_freshness(&pool, chrono::Duration::hours(24))
+        .await
+        .expect("freshness query should succeed");
+
+    let codes: Vec<&str> = rows.iter().map(|r| r.code.as_str()).collect();
+    assert_eq!(codes, vec!["202610", "202520"]);
+
+    let current = &rows[0];
+    assert_eq!(current.subjects_total, 4);
+    assert_eq!(current.subjects_scraped, 1);
+    assert!(current.last_scraped_at.is_some());
+
+    // No subject list cached yet.
+    assert_eq!(rows[1].subjects_total, 0);
+    assert_eq!(rows[1].subjects_scraped, 0);
+}
+
+#[sqlx::test]
+async fn subject_activity_sums_changes_inside_window(pool: PgPool) {
+    insert_term(&pool, "202610", true).await;
+    insert_subjects(&pool, "202610", &["CS", "MAT"]).await;
+
+    insert_result_with_changes(&pool, "202610", "CS", true, 1, 3).await;
+    insert_result_with_changes(&pool, "202610", "CS", true, 2, 4).await;
+    insert_result_with_changes(&pool, "202610", "CS", true, 48, 50).await;
+    insert_result_with_changes(&pool, "202610", "CS", false, 1, 9).await;
+    insert_result_with_changes(&pool, "202610", "PHY", true, 5, 1).await;
+    // Other terms don't leak in.
+    insert_result_with_changes(&pool, "202520", "CS", true, 1, 7).await;
+
+    let rows = get_subject_scrape_activity(&pool, "202610", chrono::Duration::hours(24))
+        .await
+        .expect("activity query should succeed");
+
+    let subjects: Vec<&str> = rows.iter().map(|r| r.subject_code.as_str()).collect();
+    assert_eq!(subjects, vec!["CS", "MAT", "PHY"]);
+
+    assert_eq!(rows[0].scrapes, 2);
+    assert_eq!(rows[0].courses_changed, 7);
+    assert!(rows[0].last_scraped_at.is_some());
+
+    // Listed but never scraped.
+    assert_eq!(rows[1].scrapes, 0);
+    assert_eq!(rows[1].courses_changed, 0);
+    assert!(rows[1].last_scraped_at.is_none());
+
+    assert_eq!(rows[2].courses_changed, 1);
+}
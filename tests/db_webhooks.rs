@@ -0,0 +1,173 @@
+mod helpers;
+
+use std::time::Duration;
+
+use banner::data::outbox::{self, NewOutboxMessage, OutboxPayload};
+use banner::data::webhooks::{
+    self, DeliveryStatus, WebhookEvent, create_webhook, delete_webhook, find_subscribers,
+    get_signing_target, list_deliveries, list_webhooks, update_webhook,
+};
+use helpers::insert_admin;
+use sqlx::PgPool;
+
+fn delivery(webhook_id: i32, event: WebhookEvent) -> NewOutboxMessage {
+    NewOutboxMessage {
+        payload: OutboxPayload::WebhookSubscription {
+            webhook_id,
+            event: event.as_str().to_owned(),
+            body: serde_json::json!({ "event": event.as_str(), "data": [] }),
+        },
+        dedupe_key: None,
+    }
+}
+
+#[sqlx::test]
+async fn webhooks_are_registered_with_a_secret(pool: PgPool) {
+    let admin = insert_admin(&pool).await;
+
+    let (webhook, secret) = create_webhook(
+        &pool,
+        "https://hooks.example.com/banner",
+        "Seat tracker",
+        &[WebhookEvent::EnrollmentChanged],
+        admin,
+    )
+    .await
+    .unwrap();
+    assert!(secret.starts_with("whsec_"));
+    assert_eq!(webhook.event_types, ["enrollment.changed"]);
+    assert_eq!(webhook.created_by.as_deref(), Some("admin"));
+
+    let target = get_signing_target(&pool, webhook.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(target.secret, secret);
+    assert_eq!(list_webhooks(&pool).await.unwrap().len(), 1);
+
+    assert!(delete_webhook(&pool, webhook.id).await.unwrap());
+    assert!(!delete_webhook(&pool, webhook.id).await.unwrap());
+    assert!(
+        get_signing_target(&pool, webhook.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[sqlx::test]
+async fn only_enabled_subscribers_are_found(pool: PgPool) {
+    let admin = insert_admin(&pool).await;
+    let url = "https://hooks.example.com/banner";
+    let (created, _) = create_webhook(&pool, url, "", &[WebhookEvent::CourseCreated], admin)
+        .await
+        .unwrap();
+    let (both, _) = create_webhook(
+        &pool,
+        url,
+        "",
+        &[
+            WebhookEvent::CourseCreated,
+            WebhookEvent::InstructorAssigned,
+        ],
+        admin,
+    )
+    .await
+    .unwrap();
+
+    let ids = |subs: Vec<webhooks::Subscriber>| subs.into_iter().map(|s| s.id).collect::<Vec<_>>();
+    assert_eq!(
+        ids(find_subscribers(&pool, &[WebhookEvent::CourseCreated])
+            .await
+            .unwrap()),
+        [created.id, both.id]
+    );
+    assert_eq!(
+        ids(find_subscribers(&pool, &[WebhookEvent::InstructorAssigned])
+            .await
+            .unwrap()),
+        [both.id]
+    );
+
+    update_webhook(
+        &pool,
+        both.id,
+        "paused",
+        &[WebhookEvent::InstructorAssigned],
+        false,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert!(
+        find_subscribers(&pool, &[WebhookEvent::InstructorAssigned])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        update_webhook(&pool, 9999, "", &[WebhookEvent::CourseCreated], true)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[sqlx::test]
+async fn delivery_log_is_scoped_to_its_webhook(pool: PgPool) {
+    let admin = insert_admin(&pool).await;
+    let url = "https://hooks.example.com/banner";
+    let events = [WebhookEvent::CourseCreated];
+    let (first, _) = create_webhook(&pool, url, "", &events, admin)
+        .await
+        .unwrap();
+    let (second, _) = create_webhook(&pool, url, "", &events, admin)
+        .await
+        .unwrap();
+
+    outbox::enqueue(
+        &pool,
+        &[
+            delivery(first.id, WebhookEvent::CourseCreated),
+            delivery(first.id, WebhookEvent::CourseCreated),
+            delivery(second.id, WebhookEvent::CourseCreated),
+        ],
+    )
+    .await
+    .unwrap();
+
+    // Dead-letter one of the first webhook's deliveries.
+    let dead = outbox::claim_due(&pool, 10, Duration::from_secs(60))
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|m| match m.payload.0 {
+            OutboxPayload::WebhookSubscription { webhook_id, .. } => webhook_id == first.id,
+            _ => false,
+        })
+        .unwrap();
+    let dead_id = dead.id;
+    outbox::mark_failed(&pool, dead_id, dead.locked_until, "410 Gone", None)
+        .await
+        .unwrap();
+
+    let log = list_deliveries(&pool, first.id, None, 50).await.unwrap();
+    assert_eq!(log.len(), 2);
+    assert!(log.iter().all(|d| d.event == "course.created"));
+    assert_eq!(log[0].body["event"], "course.created");
+
+    let dead: Vec<i64> = list_deliveries(&pool, first.id, Some(DeliveryStatus::Dead), 50)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|d| d.id)
+        .collect();
+    assert_eq!(dead, [dead_id]);
+    assert_eq!(
+        list_deliveries(&pool, second.id, None, 50)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
@@ -224,3 +224,24 @@ pub async fn insert_scrape_job(
 
     id
 }
+
+/// Insert a regular (non-admin) user with the given Discord ID.
+pub async fn insert_user(pool: &PgPool, id: i64) {
+    sqlx::query("INSERT INTO users (id, discord_username) VALUES ($1, 'student')")
+        .bind(id)
+        .execute(pool)
+        .await
+        .expect("failed to insert user");
+}
+
+/// Insert the admin user (`admin`, Discord ID 42), returning its ID.
+pub async fn insert_admin(pool: &PgPool) -> i64 {
+    let (id,): (i64,) = sqlx::query_as(
+        "INSERT INTO users (id, discord_username, is_admin) VALUES (42, 'admin', true)
+         RETURNING id",
+    )
+    .fetch_one(pool)
+    .await
+    .expect("failed to create admin");
+    id
+}
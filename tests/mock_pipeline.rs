@@ -0,0 +1,166 @@
+//! End-to-end scraper runs against the mock upstreams in `banner::testing`.
+
+use std::sync::Arc;
+
+use banner::data::DbContext;
+use banner::data::events::EventBuffer;
+use banner::testing::{MockBanner, MockBlueBook, MockRmp, pipeline};
+use sqlx::PgPool;
+
+const TERM: &str = "202620";
+
+fn db_context(pool: &PgPool) -> DbContext {
+    DbContext::new(pool.clone(), Arc::new(EventBuffer::new(64)))
+}
+
+#[sqlx::test]
+async fn subject_scrape_upserts_mocked_sections(pool: PgPool) {
+    let banner = MockBanner::start().await;
+    let api = banner.api().unwrap();
+    let db = db_context(&pool);
+
+    let counts = pipeline::scrape_subject(&api, &db, "CS", TERM)
+        .await
+        .unwrap();
+    assert_eq!(counts.courses_fetched.get(), 3);
+
+    let rows: Vec<(String, String, i32, Option<i32>)> = sqlx::query_as(
+        "SELECT crn, course_number, enrollment, wait_count FROM courses \
+         WHERE term_code = $1 ORDER BY crn",
+    )
+    .bind(TERM)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        rows,
+        [
+            ("21450".to_owned(), "1083".to_owned(), 58, Some(2)),
+            ("21451".to_owned(), "1083".to_owned(), 60, Some(7)),
+            ("21480".to_owned(), "3343".to_owned(), 41, Some(0)),
+        ]
+    );
+
+    let instructors: Vec<String> =
+        sqlx::query_scalar("SELECT display_name FROM instructors ORDER BY display_name")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(instructors, ["Nguyen, Ada", "Ortiz, Ben"]);
+
+    // Same payload again: the session is reused and nothing changes.
+    let again = pipeline::scrape_subject(&api, &db, "CS", TERM)
+        .await
+        .unwrap();
+    assert_eq!(again.courses_changed.get(), 0);
+    assert_eq!(again.courses_unchanged.get(), 3);
+
+    let empty = pipeline::scrape_subject(&api, &db, "MAT", TERM)
+        .await
+        .unwrap();
+    assert_eq!(empty.courses_fetched.get(), 0);
+}
+
+#[sqlx::test]
+async fn term_sync_inserts_mocked_terms(pool: PgPool) {
+    let banner = MockBanner::start().await;
+    let api = banner.api().unwrap();
+
+    let result = pipeline::sync_terms(&api, &pool).await.unwrap();
+    assert_eq!(result.inserted, 3);
+
+    let archived: Vec<(String, bool)> =
+        sqlx::query_as("SELECT code, is_archived FROM terms ORDER BY code")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        archived,
+        [
+            ("202610".to_owned(), true),
+            ("202620".to_owned(), false),
+            ("202630".to_owned(), false),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn warmed_up_sessions_are_reused() {
+    let banner = MockBanner::start().await;
+    let api = banner.api().unwrap();
+    let term = TERM.parse().unwrap();
+
+    assert_eq!(api.sessions.warm_up(term, 2).await, 2);
+    let stats = api.sessions.stats().await;
+    assert_eq!((stats.idle, stats.created), (2, 2));
+
+    let session = api.sessions.acquire(term).await.unwrap();
+    let stats = api.sessions.stats().await;
+    assert_eq!((stats.idle, stats.in_use), (1, 1));
+    assert_eq!((stats.created, stats.reused), (2, 1));
+
+    drop(session);
+    assert_eq!(api.sessions.stats().await.in_use, 0);
+}
+
+#[sqlx::test]
+async fn rmp_sync_upserts_mocked_professors(pool: PgPool) {
+    let rmp = MockRmp::start().await;
+    let db = db_context(&pool);
+
+    pipeline::sync_rmp(&db, &rmp).await.unwrap();
+
+    let professors: Vec<(i32, String, Option<f32>)> = sqlx::query_as(
+        "SELECT legacy_id, last_name, would_take_again_pct FROM rmp_professors ORDER BY legacy_id",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        professors,
+        [
+            (100001, "Nguyen".to_owned(), Some(91.5)),
+            // RMP reports -1 when nobody answered the question.
+            (100002, "Ortiz".to_owned(), None),
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn bluebook_scrape_upserts_mocked_evaluations(pool: PgPool) {
+    let bluebook = MockBlueBook::start().await;
+
+    let total = pipeline::scrape_bluebook(&pool, &bluebook).await.unwrap();
+    // The fourth section has no ratings and MAT has no courses.
+    assert_eq!(total, 3);
+
+    let evals: Vec<(String, String, String, Option<f32>)> = sqlx::query_as(
+        "SELECT crn, term, instructor_name, course_rating FROM bluebook_evaluations ORDER BY crn",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        evals,
+        [
+            (
+                "11450".to_owned(),
+                "202610".to_owned(),
+                "Nguyen, Ada".to_owned(),
+                Some(4.3)
+            ),
+            (
+                "11480".to_owned(),
+                "202610".to_owned(),
+                "Nguyen, Ada".to_owned(),
+                Some(4.1)
+            ),
+            (
+                "21451".to_owned(),
+                "202520".to_owned(),
+                "Ortiz, Ben".to_owned(),
+                None
+            ),
+        ]
+    );
+}
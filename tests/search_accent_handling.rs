@@ -6,7 +6,9 @@ mod helpers;
 
 use banner::banner::models::meetings::FacultyItem;
 use banner::data::batch::batch_upsert_courses;
-use banner::data::courses::{SearchFilter, search_courses, suggest_courses, suggest_instructors};
+use banner::data::courses::{
+    SearchFilter, SearchResults, search_courses, suggest_courses, suggest_instructors,
+};
 use banner::data::instructors::{PublicInstructorListParams, list_public_instructors};
 use helpers::make_course;
 use sqlx::PgPool;
@@ -132,7 +134,11 @@ async fn test_search_courses_title_unaccented_finds_accented(pool: PgPool) {
         query: Some("Introduccion"),
         ..Default::default()
     };
-    let (results, total) = search_courses(&pool, &filter, 100, 0, None, None)
+    let SearchResults {
+        courses: results,
+        total,
+        ..
+    } = search_courses(&pool, &filter, 100, 0, None, None)
         .await
         .expect("search_courses failed");
 
@@ -156,7 +162,11 @@ async fn test_search_courses_title_unaccented_finds_umlaut(pool: PgPool) {
         query: Some("Etudes"),
         ..Default::default()
     };
-    let (results, total) = search_courses(&pool, &filter, 100, 0, None, None)
+    let SearchResults {
+        courses: results,
+        total,
+        ..
+    } = search_courses(&pool, &filter, 100, 0, None, None)
         .await
         .expect("search_courses failed");
 
@@ -180,7 +190,11 @@ async fn test_search_courses_title_unaccented_finds_algebra(pool: PgPool) {
         query: Some("Algebra"),
         ..Default::default()
     };
-    let (results, total) = search_courses(&pool, &filter, 100, 0, None, None)
+    let SearchResults {
+        courses: results,
+        total,
+        ..
+    } = search_courses(&pool, &filter, 100, 0, None, None)
         .await
         .expect("search_courses failed");
 
@@ -205,7 +219,11 @@ async fn test_search_courses_instructor_filter_unaccented(pool: PgPool) {
         instructors: Some(&slugs),
         ..Default::default()
     };
-    let (results, total) = search_courses(&pool, &filter, 100, 0, None, None)
+    let SearchResults {
+        courses: results,
+        total,
+        ..
+    } = search_courses(&pool, &filter, 100, 0, None, None)
         .await
         .expect("search_courses failed");
 
@@ -230,7 +248,11 @@ async fn test_search_courses_instructor_filter_muller(pool: PgPool) {
         instructors: Some(&slugs),
         ..Default::default()
     };
-    let (results, total) = search_courses(&pool, &filter, 100, 0, None, None)
+    let SearchResults {
+        courses: results,
+        total,
+        ..
+    } = search_courses(&pool, &filter, 100, 0, None, None)
         .await
         .expect("search_courses failed");
 
@@ -354,6 +376,7 @@ async fn test_list_public_instructors_unaccented_search(pool: PgPool) {
             sort: "name_asc".to_owned(),
             page: 1,
             per_page: 24,
+            cursor: None,
         },
     )
     .await
@@ -386,6 +409,7 @@ async fn test_list_public_instructors_unaccented_search_jose(pool: PgPool) {
             sort: "name_asc".to_owned(),
             page: 1,
             per_page: 24,
+            cursor: None,
         },
     )
     .await
@@ -418,6 +442,7 @@ async fn test_list_public_instructors_unaccented_search_francois(pool: PgPool) {
             sort: "name_asc".to_owned(),
             page: 1,
             per_page: 24,
+            cursor: None,
         },
     )
     .await
@@ -436,3 +461,29 @@ async fn test_list_public_instructors_unaccented_search_francois(pool: PgPool) {
         "should find 'Müller, François'"
     );
 }
+
+#[sqlx::test]
+async fn test_list_public_instructors_email_prefix_search(pool: PgPool) {
+    insert_accented_test_data(&pool).await;
+
+    // "sean.ob" only matches the local part of sean.obrien@utsa.edu
+    let response = list_public_instructors(
+        &pool,
+        &PublicInstructorListParams {
+            search: Some("sean.ob".to_owned()),
+            subject: None,
+            sort: "name_asc".to_owned(),
+            page: 1,
+            per_page: 24,
+            cursor: None,
+        },
+    )
+    .await
+    .expect("list_public_instructors failed");
+
+    assert_eq!(response.total, 1, "expected only the email prefix match");
+    assert!(
+        response.instructors[0].display_name.contains("O'Brien"),
+        "should find 'O'Brien, Séan' by email prefix"
+    );
+}
@@ -1,18 +1,37 @@
 import { authStore } from "$lib/auth.svelte";
 import type {
+  AddWatchBody,
+  AddWatchResponse,
   AdminStatusResponse,
+  ApiKey,
+  ApiKeysResponse,
   ApiError,
   ApiErrorCode,
   AssignBody,
+  AttributionResponse,
   AuditLogResponse,
+  AuthProvidersResponse,
   BluebookLinkDetail,
   BluebookMatchResponse,
   BluebookOkResponse,
   BluebookSyncTriggerResponse,
   CodeDescription,
+  CoursePageData,
   CourseResponse,
+  CreateApiKeyBody,
+  CreateSavedSearchBody,
+  CreatedApiKey,
+  DebugCapture,
+  DebugCapturesResponse,
+  DisplayPreferences,
+  FullStatusResponse,
+  ImportDecisionsResponse,
   InstructorDetailResponse,
   InstructorSuggestion,
+  JsonLdCourse,
+  JsonLdPerson,
+  ListAdminActionsParams,
+  ListAdminActionsResponse,
   ListBluebookLinksParams,
   ListBluebookLinksResponse,
   ListInstructorsParams as ListInstructorsParamsGenerated,
@@ -20,28 +39,51 @@ import type {
   MatchBody,
   MetricsParams as MetricsParamsGenerated,
   MetricsResponse,
+  ProviderKind,
   PublicInstructorListResponse,
   PublicInstructorProfileResponse,
+  RebuildSlugsBody,
+  RebuildSlugsResponse,
   RejectCandidateBody,
+  RelatedInstructorsResponse,
+  RenameApiKeyBody,
+  ReplaceScheduleBody,
   RescoreResponse,
+  SavedSearchResponse,
   ScrapeJobsResponse,
   ScraperStatsResponse,
   SearchOptionsResponse,
   SearchParams as SearchParamsGenerated,
   SearchResponse as SearchResponseGenerated,
+  StartCaptureBody,
   StatusResponse,
   SubjectDetailResponse,
+  SubjectOverview,
   SubjectsResponse,
+  SuggestGroupBy,
   SuggestResponse,
+  SyncKind,
+  SyncRunResponse,
+  SyncsResponse,
+  TermChangesResponse,
   TermResponse,
   TermSyncResponse,
   TermUpdateResponse,
   TermsListResponse,
   TimeRange,
+  TimelineCompareRequest,
+  TimelineCompareResponse,
   TimelineRequest,
   TimelineResponse,
   TimeseriesResponse,
+  UpdatesPollParams,
+  UpdatesPollResponse,
   User,
+  UserIdentitiesResponse,
+  UserScheduleResponse,
+  UserSessionsResponse,
+  WatchType,
+  WatchlistEntry,
 } from "$lib/bindings";
 import type Result from "true-myth/result";
 import { err, ok } from "true-myth/result";
@@ -61,6 +103,13 @@ export type ListInstructorsParams = ListInstructorsParamsGenerated;
 
 export type ScraperPeriod = "1h" | "6h" | "24h" | "7d" | "30d";
 
+/** `body` is sent as JSON; `csv` is sent verbatim as `text/csv`. */
+interface RequestOptions {
+  method?: string;
+  body?: unknown;
+  csv?: string;
+}
+
 /**
  * Converts a typed object to URLSearchParams, preserving camelCase keys.
  * Handles arrays, optional values, and primitives.
@@ -128,9 +177,17 @@ export class ApiErrorClass extends Error {
     return this.code === "RATE_LIMITED";
   }
 
-  /** Seconds the client should wait before retrying (from rate limit details). */
+  isMaintenance(): boolean {
+    return this.code === "MAINTENANCE";
+  }
+
+  /** Seconds the client should wait before retrying (from rate limit or maintenance details). */
   get retryAfter(): number | undefined {
-    if (!this.isRateLimited() || typeof this.details !== "object" || this.details === null) {
+    if (
+      !(this.isRateLimited() || this.isMaintenance()) ||
+      typeof this.details !== "object" ||
+      this.details === null
+    ) {
       return undefined;
     }
     const val = (this.details as Record<string, unknown>).retryAfter;
@@ -151,13 +208,16 @@ export class BannerApiClient {
     this.fetchFn = fetchFn;
   }
 
-  private buildInit(options?: { method?: string; body?: unknown }): RequestInit | undefined {
+  private buildInit(options?: RequestOptions): RequestInit | undefined {
     if (!options) return undefined;
     const init: RequestInit = {};
     if (options.method) {
       init.method = options.method;
     }
-    if (options.body !== undefined) {
+    if (options.csv !== undefined) {
+      init.headers = { "Content-Type": "text/csv" };
+      init.body = options.csv;
+    } else if (options.body !== undefined) {
       init.headers = { "Content-Type": "application/json" };
       init.body = JSON.stringify(options.body);
     } else if (options.method) {
@@ -235,7 +295,7 @@ export class BannerApiClient {
 
   private async request<T>(
     endpoint: string,
-    options?: { method?: string; body?: unknown }
+    options?: RequestOptions
   ): Promise<Result<T, ApiErrorClass>> {
     const init = this.buildInit(options);
     const result = await this.fetchWithRetry(`${this.baseUrl}${endpoint}`, init);
@@ -257,7 +317,7 @@ export class BannerApiClient {
 
   private async requestVoid(
     endpoint: string,
-    options?: { method?: string; body?: unknown }
+    options?: RequestOptions
   ): Promise<Result<void, ApiErrorClass>> {
     const init = this.buildInit(options);
     const result = await this.fetchWithRetry(`${this.baseUrl}${endpoint}`, init);
@@ -281,6 +341,14 @@ export class BannerApiClient {
     return this.request<StatusResponse>("/status");
   }
 
+  async getFullStatus(): Promise<Result<FullStatusResponse, ApiErrorClass>> {
+    return this.request<FullStatusResponse>("/status/full");
+  }
+
+  async getAttribution(): Promise<Result<AttributionResponse, ApiErrorClass>> {
+    return this.request<AttributionResponse>("/meta/attribution");
+  }
+
   async searchCourses(
     params: Partial<SearchParams> & { term: string }
   ): Promise<Result<SearchResponse, ApiErrorClass>> {
@@ -294,6 +362,21 @@ export class BannerApiClient {
     );
   }
 
+  async getCoursePageData(
+    term: string,
+    crn: string
+  ): Promise<Result<CoursePageData, ApiErrorClass>> {
+    return this.request<CoursePageData>(
+      `/courses/${encodeURIComponent(term)}/${encodeURIComponent(crn)}/page-data`
+    );
+  }
+
+  async getCourseJsonLd(term: string, crn: string): Promise<Result<JsonLdCourse, ApiErrorClass>> {
+    return this.request<JsonLdCourse>(
+      `/courses/${encodeURIComponent(term)}/${encodeURIComponent(crn)}/json-ld`
+    );
+  }
+
   async getRelatedSections(
     term: string,
     subject: string,
@@ -330,6 +413,18 @@ export class BannerApiClient {
     return result;
   }
 
+  async getSubject(code: string): Promise<Result<SubjectOverview, ApiErrorClass>> {
+    return this.request<SubjectOverview>(`/subjects/${encodeURIComponent(code)}`);
+  }
+
+  async getTermChanges(
+    term: string,
+    since?: string
+  ): Promise<Result<TermChangesResponse, ApiErrorClass>> {
+    const qs = since !== undefined ? `?since=${encodeURIComponent(since)}` : "";
+    return this.request<TermChangesResponse>(`/terms/${encodeURIComponent(term)}/changes${qs}`);
+  }
+
   // Public instructor endpoints
 
   async getInstructors(params?: {
@@ -338,6 +433,7 @@ export class BannerApiClient {
     sort?: string;
     page?: number;
     perPage?: number;
+    cursor?: string;
   }): Promise<Result<PublicInstructorListResponse, ApiErrorClass>> {
     if (!params) {
       return this.request<PublicInstructorListResponse>("/instructors");
@@ -364,6 +460,80 @@ export class BannerApiClient {
     );
   }
 
+  async getRelatedInstructors(
+    slug: string
+  ): Promise<Result<RelatedInstructorsResponse, ApiErrorClass>> {
+    return this.request<RelatedInstructorsResponse>(
+      `/instructors/${encodeURIComponent(slug)}/related`
+    );
+  }
+
+  async getInstructorJsonLd(slug: string): Promise<Result<JsonLdPerson, ApiErrorClass>> {
+    return this.request<JsonLdPerson>(`/instructors/${encodeURIComponent(slug)}/json-ld`);
+  }
+
+  // Watchlist (signed-in users)
+
+  async getWatchlist(): Promise<Result<WatchlistEntry[], ApiErrorClass>> {
+    return this.request<WatchlistEntry[]>("/watchlist");
+  }
+
+  async addWatch(body: AddWatchBody): Promise<Result<AddWatchResponse, ApiErrorClass>> {
+    return this.request<AddWatchResponse>("/watchlist", { method: "POST", body });
+  }
+
+  async removeWatch(
+    term: string,
+    crn: string,
+    watchType?: WatchType
+  ): Promise<Result<void, ApiErrorClass>> {
+    const qs = watchType ? `?type=${watchType}` : "";
+    return this.requestVoid(
+      `/watchlist/${encodeURIComponent(term)}/${encodeURIComponent(crn)}${qs}`,
+      { method: "DELETE" }
+    );
+  }
+
+  // Saved searches (signed-in users)
+
+  async getSavedSearches(): Promise<Result<SavedSearchResponse[], ApiErrorClass>> {
+    return this.request<SavedSearchResponse[]>("/me/searches");
+  }
+
+  async createSavedSearch(
+    body: CreateSavedSearchBody
+  ): Promise<Result<SavedSearchResponse, ApiErrorClass>> {
+    return this.request<SavedSearchResponse>("/me/searches", { method: "POST", body });
+  }
+
+  async deleteSavedSearch(id: number): Promise<Result<void, ApiErrorClass>> {
+    return this.requestVoid(`/me/searches/${id}`, { method: "DELETE" });
+  }
+
+  // Planned schedules (signed-in users)
+
+  async getSchedules(): Promise<Result<UserScheduleResponse[], ApiErrorClass>> {
+    return this.request<UserScheduleResponse[]>("/me/schedule");
+  }
+
+  async getSchedule(term: string): Promise<Result<UserScheduleResponse, ApiErrorClass>> {
+    return this.request<UserScheduleResponse>(`/me/schedule/${encodeURIComponent(term)}`);
+  }
+
+  async replaceSchedule(
+    term: string,
+    body: ReplaceScheduleBody
+  ): Promise<Result<UserScheduleResponse, ApiErrorClass>> {
+    return this.request<UserScheduleResponse>(`/me/schedule/${encodeURIComponent(term)}`, {
+      method: "PUT",
+      body,
+    });
+  }
+
+  async deleteSchedule(term: string): Promise<Result<void, ApiErrorClass>> {
+    return this.requestVoid(`/me/schedule/${encodeURIComponent(term)}`, { method: "DELETE" });
+  }
+
   // Admin endpoints
   async getAdminStatus(): Promise<Result<AdminStatusResponse, ApiErrorClass>> {
     return this.request<AdminStatusResponse>("/admin/status");
@@ -373,8 +543,8 @@ export class BannerApiClient {
     return this.request<User[]>("/admin/users");
   }
 
-  async setUserAdmin(discordId: string, isAdmin: boolean): Promise<Result<User, ApiErrorClass>> {
-    return this.request<User>(`/admin/users/${discordId}/admin`, {
+  async setUserAdmin(userId: string, isAdmin: boolean): Promise<Result<User, ApiErrorClass>> {
+    return this.request<User>(`/admin/users/${userId}/admin`, {
       method: "PUT",
       body: { is_admin: isAdmin },
     });
@@ -434,6 +604,35 @@ export class BannerApiClient {
   /** Stored `Last-Modified` value for audit log conditional requests. */
   private _auditLastModified: string | null = null;
 
+  async getAuthProviders(): Promise<Result<AuthProvidersResponse, ApiErrorClass>> {
+    return this.request<AuthProvidersResponse>("/auth/providers");
+  }
+
+  async getIdentities(): Promise<Result<UserIdentitiesResponse, ApiErrorClass>> {
+    return this.request<UserIdentitiesResponse>("/auth/identities");
+  }
+
+  async getSessions(): Promise<Result<UserSessionsResponse, ApiErrorClass>> {
+    return this.request<UserSessionsResponse>("/auth/sessions");
+  }
+
+  async revokeSession(id: string): Promise<Result<void, ApiErrorClass>> {
+    return this.requestVoid(`/auth/sessions/${encodeURIComponent(id)}`, { method: "DELETE" });
+  }
+
+  async unlinkIdentity(provider: ProviderKind): Promise<Result<void, ApiErrorClass>> {
+    return this.requestVoid(`/auth/identities/${provider}`, { method: "DELETE" });
+  }
+
+  async updateDisplayPreferences(
+    prefs: DisplayPreferences
+  ): Promise<Result<DisplayPreferences, ApiErrorClass>> {
+    return this.request<DisplayPreferences>("/auth/preferences/display", {
+      method: "PUT",
+      body: prefs,
+    });
+  }
+
   async getTimeline(ranges: TimeRange[]): Promise<Result<TimelineResponse, ApiErrorClass>> {
     return this.request<TimelineResponse>("/timeline", {
       method: "POST",
@@ -441,6 +640,30 @@ export class BannerApiClient {
     });
   }
 
+  /** Timeline series for several terms, shifted to line up with the first. */
+  async getTimelineComparison(
+    terms: string[],
+    ranges: TimeRange[]
+  ): Promise<Result<TimelineCompareResponse, ApiErrorClass>> {
+    return this.request<TimelineCompareResponse>("/timeline/compare", {
+      method: "POST",
+      body: { terms, ranges } satisfies TimelineCompareRequest,
+    });
+  }
+
+  /**
+   * Long-poll for course changes since `cursor` (fallback when WebSockets are blocked).
+   *
+   * Resolves after the server's wait window even when nothing changed; on
+   * `reset`, the cursor expired and the caller should refetch.
+   */
+  async pollUpdates(
+    params: UpdatesPollParams = {}
+  ): Promise<Result<UpdatesPollResponse, ApiErrorClass>> {
+    const qs = toURLSearchParams(params).toString();
+    return this.request<UpdatesPollResponse>(`/updates/poll${qs ? `?${qs}` : ""}`);
+  }
+
   async getMetrics(
     params?: Partial<MetricsParams>
   ): Promise<Result<MetricsResponse, ApiErrorClass>> {
@@ -505,6 +728,72 @@ export class BannerApiClient {
     });
   }
 
+  /** URL of the CSV download of every RMP match decision. */
+  rmpDecisionsExportUrl(): string {
+    return `${this.baseUrl}/admin/rmp/decisions?format=csv`;
+  }
+
+  async importRmpDecisions(csv: string): Promise<Result<ImportDecisionsResponse, ApiErrorClass>> {
+    return this.request<ImportDecisionsResponse>("/admin/rmp/decisions?format=csv", {
+      method: "POST",
+      csv,
+    });
+  }
+
+  async rebuildInstructorSlugs(ids: number[] = []): Promise<Result<RebuildSlugsResponse, ApiErrorClass>> {
+    return this.request<RebuildSlugsResponse>("/admin/instructors/rebuild-slugs", {
+      method: "POST",
+      body: { ids } satisfies RebuildSlugsBody,
+    });
+  }
+
+  // API key endpoints
+
+  async getApiKeys(): Promise<Result<ApiKeysResponse, ApiErrorClass>> {
+    return this.request<ApiKeysResponse>("/admin/api-keys");
+  }
+
+  async createApiKey(userId: string, name: string): Promise<Result<CreatedApiKey, ApiErrorClass>> {
+    return this.request<CreatedApiKey>("/admin/api-keys", {
+      method: "POST",
+      body: { userId, name } satisfies CreateApiKeyBody,
+    });
+  }
+
+  async renameApiKey(id: number, name: string): Promise<Result<ApiKey, ApiErrorClass>> {
+    return this.request<ApiKey>(`/admin/api-keys/${id}`, {
+      method: "PUT",
+      body: { name } satisfies RenameApiKeyBody,
+    });
+  }
+
+  async revokeApiKey(id: number): Promise<Result<void, ApiErrorClass>> {
+    return this.requestVoid(`/admin/api-keys/${id}`, { method: "DELETE" });
+  }
+
+  // Debug capture endpoints
+
+  async getAdminActions(
+    params?: Partial<ListAdminActionsParams>
+  ): Promise<Result<ListAdminActionsResponse, ApiErrorClass>> {
+    const qs = params ? toURLSearchParams(params).toString() : "";
+    return this.request<ListAdminActionsResponse>(`/admin/actions${qs ? `?${qs}` : ""}`);
+  }
+
+  async getDebugCaptures(): Promise<Result<DebugCapturesResponse, ApiErrorClass>> {
+    return this.request<DebugCapturesResponse>("/admin/debug-captures");
+  }
+
+  async startDebugCapture(body: StartCaptureBody): Promise<Result<DebugCapture, ApiErrorClass>> {
+    return this.request<DebugCapture>("/admin/debug-captures", { method: "POST", body });
+  }
+
+  async stopDebugCapture(path: string): Promise<Result<void, ApiErrorClass>> {
+    return this.requestVoid(`/admin/debug-captures?path=${encodeURIComponent(path)}`, {
+      method: "DELETE",
+    });
+  }
+
   // Scraper analytics endpoints
 
   async getScraperStats(
@@ -569,6 +858,14 @@ export class BannerApiClient {
     return this.request<BluebookSyncTriggerResponse>("/admin/bluebook/sync", { method: "POST" });
   }
 
+  async getSyncs(): Promise<Result<SyncsResponse, ApiErrorClass>> {
+    return this.request<SyncsResponse>("/admin/syncs");
+  }
+
+  async runSync(kind: SyncKind): Promise<Result<SyncRunResponse, ApiErrorClass>> {
+    return this.request<SyncRunResponse>(`/admin/syncs/${kind}/run`, { method: "POST" });
+  }
+
   async getAdminBluebookLinks(
     params?: Partial<ListBluebookLinksParams>
   ): Promise<Result<ListBluebookLinksResponse, ApiErrorClass>> {
@@ -615,10 +912,12 @@ export class BannerApiClient {
   async suggest(
     term: string,
     query: string,
-    limit?: number
+    limit?: number,
+    groupBy?: SuggestGroupBy
   ): Promise<Result<SuggestResponse, ApiErrorClass>> {
     const params = new URLSearchParams({ term, q: query });
     if (limit !== undefined) params.set("limit", String(limit));
+    if (groupBy) params.set("group_by", groupBy);
     return this.request<SuggestResponse>(`/suggest?${params.toString()}`);
   }
 
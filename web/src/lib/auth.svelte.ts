@@ -33,7 +33,7 @@ class AuthStore {
   setFromServer(user: User | null) {
     if (user) {
       this.state = { mode: "authenticated", user };
-      telemetry.identify(user.discordId, {
+      telemetry.identify(user.id, {
         username: user.discordUsername,
         isAdmin: user.isAdmin,
       });
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One of a user's unexpired sessions, as shown to its owner.
+ */
+export type ActiveSession = { 
+/**
+ * Public session ID; the session token itself is never exposed.
+ */
+id: string, 
+/**
+ * Browser and OS, when recognizable from the user agent.
+ */
+device: string | null, userAgent: string | null, ipAddress: string | null, createdAt: string, lastActiveAt: string, expiresAt: string, 
+/**
+ * Whether this is the session making the request.
+ */
+current: boolean, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WatchType } from "./WatchType";
+
+/**
+ * Body for `POST /api/watchlist/departments`.
+ */
+export type AddDepartmentWatchBody = { term: string, subject: string, 
+/**
+ * `attributeChange` or `restrictionChange`.
+ */
+watchType: WatchType, 
+/**
+ * HTTPS endpoint to receive alerts instead of a Discord DM.
+ */
+webhookUrl: string | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response for `POST /api/watchlist`.
+ */
+export type AddWatchResponse = { 
+/**
+ * False when an existing watch was reactivated.
+ */
+created: boolean, };
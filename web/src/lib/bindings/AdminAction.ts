@@ -0,0 +1,28 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * One recorded admin API call.
+ */
+export type AdminAction = { id: number, 
+/**
+ * Discord ID as a string; `null` once the admin's account is deleted.
+ */
+adminId: string | null, adminUsername: string, method: string, path: string, 
+/**
+ * Matched route template, e.g. `/api/admin/users/{id}/admin`.
+ */
+route: string | null, status: number, 
+/**
+ * Redacted request body.
+ */
+payload: JsonValue | null, 
+/**
+ * Redacted response body of a successful call.
+ */
+result: JsonValue | null, 
+/**
+ * Fields the call changed, as `{"field": {"old": .., "new": ..}}`; only
+ * recorded by handlers that report the target's prior state.
+ */
+changes: JsonValue | null, createdAt: string, };
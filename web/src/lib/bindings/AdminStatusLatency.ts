@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Wall time of each count query in [`AdminStatusResponse`].
+ */
+export type AdminStatusLatency = { usersMs: number, sessionsMs: number, coursesMs: number, scrapeJobsMs: number, };
@@ -1,4 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AdminServiceInfo } from "./AdminServiceInfo";
+import type { AdminStatusLatency } from "./AdminStatusLatency";
+import type { ScrapeHistoryRetention } from "./ScrapeHistoryRetention";
 
-export type AdminStatusResponse = { userCount: number, sessionCount: number, courseCount: number, scrapeJobCount: number, services: Array<AdminServiceInfo>, };
+/**
+ * Counts are `null` when their query failed; the rest of the payload is still served.
+ */
+export type AdminStatusResponse = { userCount: number | null, sessionCount: number | null, courseCount: number | null, scrapeJobCount: number | null, services: Array<AdminServiceInfo>, latency: AdminStatusLatency, scrapeHistory: ScrapeHistoryRetention, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An issued API key, without its secret.
+ */
+export type ApiKey = { id: number, userId: string, name: string, 
+/**
+ * Leading characters of the key, e.g. `bnr_1a2b3c4d`.
+ */
+keyPrefix: string, createdAt: string, lastUsedAt: string | null, revokedAt: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiKey } from "./ApiKey";
+
+/**
+ * Response for `GET /api/admin/api-keys`.
+ */
+export type ApiKeysResponse = { keys: Array<ApiKey>, };
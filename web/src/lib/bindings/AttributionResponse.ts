@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SourceAttribution } from "./SourceAttribution";
+
+/**
+ * Response for `GET /api/meta/attribution`.
+ */
+export type AttributionResponse = { sources: Array<SourceAttribution>, 
+/**
+ * License for the aggregated data, when the operator declares one.
+ */
+license: string | null, notice: string | null, timestamp: string, };
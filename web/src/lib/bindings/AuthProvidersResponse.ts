@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProviderKind } from "./ProviderKind";
+
+/**
+ * Response for `GET /api/auth/providers`.
+ */
+export type AuthProvidersResponse = { providers: Array<ProviderKind>, };
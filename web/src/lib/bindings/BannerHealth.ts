@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CircuitState } from "./CircuitState";
+
+/**
+ * Banner's health as seen by the circuit breaker.
+ */
+export type BannerHealth = { state: CircuitState, consecutiveFailures: number, 
+/**
+ * When the circuit entered its current state.
+ */
+since: string, 
+/**
+ * The failure that most recently opened the circuit.
+ */
+lastError: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Requests rejected for one blocklist pattern since startup.
+ */
+export type BlockedAgentCount = { pattern: string, count: number, };
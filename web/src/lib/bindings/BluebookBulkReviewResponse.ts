@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response for the bulk approve/reject endpoints.
+ */
+export type BluebookBulkReviewResponse = { 
+/**
+ * Links whose status changed.
+ */
+updated: Array<number>, 
+/**
+ * Links left alone: unknown, already decided, or (when approving) with no
+ * proposed instructor.
+ */
+skipped: Array<number>, };
@@ -20,4 +20,8 @@ instructorTeachingYears: Array<number>,
 /**
  * Total course count for the proposed/matched instructor.
  */
-instructorCourseCount: number | null, };
+instructorCourseCount: number | null, 
+/**
+ * Discord username of the admin who approved, rejected, or assigned the link.
+ */
+resolvedBy: string | null, resolvedAt: string | null, };
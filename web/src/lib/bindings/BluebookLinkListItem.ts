@@ -1,6 +1,27 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NameMatchQuality } from "./NameMatchQuality";
 
 /**
  * A BlueBook link row in the paginated list view.
  */
-export type BluebookLinkListItem = { id: number, instructorName: string, subject: string | null, status: string, confidence: number | null, instructorId: number | null, instructorDisplayName: string | null, evalCount: number, };
+export type BluebookLinkListItem = { id: number, instructorName: string, subject: string | null, status: string, confidence: number | null, instructorId: number | null, instructorDisplayName: string | null, evalCount: number, 
+/**
+ * How the BlueBook name compares to the proposed instructor's name.
+ */
+nameMatch: NameMatchQuality | null, 
+/**
+ * Sorted distinct subjects on the evaluations covered by this link.
+ */
+evalSubjects: Array<string>, 
+/**
+ * Distinct evaluation terms, newest first.
+ */
+evalTerms: Array<string>, 
+/**
+ * Sorted distinct subjects the proposed instructor teaches in Banner.
+ */
+instructorSubjects: Array<string>, 
+/**
+ * Discord username of the admin who approved, rejected, or assigned the link.
+ */
+resolvedBy: string | null, resolvedAt: string | null, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NameMatchQuality } from "./NameMatchQuality";
+
+/**
+ * An instructor who taught a section evaluated under an unmatched name.
+ */
+export type BluebookSuggestion = { instructorId: number, displayName: string, 
+/**
+ * Evaluated sections (by CRN and term) this instructor taught.
+ */
+sharedSections: number, nameMatch: NameMatchQuality, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BluebookLinkListItem } from "./BluebookLinkListItem";
+import type { BluebookSuggestion } from "./BluebookSuggestion";
+
+/**
+ * A pending link with no proposed instructor, plus instructors worth checking.
+ */
+export type BluebookUnmatchedLink = { link: BluebookLinkListItem, 
+/**
+ * Most shared sections first.
+ */
+suggestions: Array<BluebookSuggestion>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CommandRegistrationStatus } from "./CommandRegistrationStatus";
+
+/**
+ * Response for `GET /api/admin/bot/commands`.
+ */
+export type BotCommandsResponse = { 
+/**
+ * Hash of the command definitions in this build.
+ */
+fingerprint: string, commandCount: number, registrations: Array<CommandRegistrationStatus>, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A suggested building result for autocomplete.
+ */
+export type BuildingSuggestion = { code: string, 
+/**
+ * The building's name, or its code when no reference entry exists.
+ */
+description: string, 
+/**
+ * Distinct rooms the term's sections meet in.
+ */
+roomCount: number, score: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `POST /api/admin/bluebook/links/bulk-approve` and `bulk-reject`.
+ */
+export type BulkLinkIdsBody = { ids: Array<number>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Candidate ids for a bulk accept or reject.
+ */
+export type CandidateIdsBody = { ids: Array<number>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RmpMatchStatus } from "./RmpMatchStatus";
+import type { ScoreBreakdown } from "./ScoreBreakdown";
+
+/**
+ * A candidate in the cross-instructor review queue.
+ */
+export type CandidateQueueItem = { id: number, instructorId: number, instructorName: string, instructorEmail: string | null, instructorStatus: RmpMatchStatus, rmpLegacyId: number, firstName: string | null, lastName: string | null, department: string | null, avgRating: number | null, numRatings: number | null, score: number, scoreBreakdown: ScoreBreakdown, status: string, createdAt: string, resolvedAt: string | null, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of accepting or rejecting a batch of candidates.
+ */
+export type CandidateReviewResponse = { 
+/**
+ * Candidates that were pending and now carry the decision.
+ */
+updated: Array<number>, 
+/**
+ * Candidates that don't exist or were already resolved.
+ */
+skipped: Array<number>, 
+/**
+ * Candidates whose RMP profile is already linked to another instructor.
+ */
+conflicts: Array<number>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One recorded request/response pair, already redacted.
+ */
+export type CapturedExchange = { at: string, method: string, path: string, query: string | null, requestHeaders: { [key in string]?: string }, 
+/**
+ * `null` when the request had no body; a placeholder when it was too
+ * large or not text.
+ */
+requestBody: string | null, status: number, responseHeaders: { [key in string]?: string }, responseBody: string | null, durationMs: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Student classification, which determines when registration opens.
+ */
+export type Classification = "graduate" | "senior" | "junior" | "sophomore" | "freshman" | "open";
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Last successful registration for one scope.
+ */
+export type CommandRegistration = { 
+/**
+ * Guild ID, or `null` for the global registration.
+ */
+guildId: string | null, 
+/**
+ * Hash of the command definitions that were registered.
+ */
+fingerprint: string, commandCount: number, registeredAt: string, };
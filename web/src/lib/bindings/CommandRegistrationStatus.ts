@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A scope's registration record, compared against the running command set.
+ */
+export type CommandRegistrationStatus = { 
+/**
+ * Whether this scope has the current command definitions.
+ */
+inSync: boolean, 
+/**
+ * Guild ID, or `null` for the global registration.
+ */
+guildId: string | null, 
+/**
+ * Hash of the command definitions that were registered.
+ */
+fingerprint: string, commandCount: number, registeredAt: string, };
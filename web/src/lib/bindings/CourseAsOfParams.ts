@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Query params for `GET /api/courses/{term}/{crn}/as-of`.
+ */
+export type CourseAsOfParams = { 
+/**
+ * RFC 3339 timestamp to reconstruct the course at.
+ */
+timestamp: string, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A course's tracked fields as they stood at a past moment.
+ */
+export type CourseAsOfResponse = { crn: string, termSlug: string, asOf: string, 
+/**
+ * Field values keyed by audit field name (e.g. `enrollment`, `meeting_times`).
+ */
+fields: Record<string, JsonValue>, 
+/**
+ * Fields whose value then differs from the current one.
+ */
+changedFields: Array<string>, 
+/**
+ * When the section was first scraped, if known.
+ */
+firstSeen: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A corequisite course shown on course detail.
+ */
+export type CourseCorequisite = { subject: string, courseNumber: string, title: string | null, };
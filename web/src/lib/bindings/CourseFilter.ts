@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Identifies one section: `{term}:{crn}`. `term` may be a code or a slug
+ * ("spring-2026"); it is normalized to the code when parsed.
+ */
+export type CourseFilter = { term: string, crn: string, };
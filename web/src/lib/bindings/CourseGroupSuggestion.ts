@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A course suggestion with all of its sections collapsed into one entry.
+ */
+export type CourseGroupSuggestion = { subject: string, courseNumber: string, 
+/**
+ * The title most sections share.
+ */
+title: string, 
+/**
+ * Distinct titles across sections; above 1 for topics courses.
+ */
+titleCount: number, sectionCount: number, 
+/**
+ * Sections with at least one open seat.
+ */
+openSections: number, 
+/**
+ * Open seats summed over all sections.
+ */
+seatsAvailable: number, 
+/**
+ * Waitlisted students summed over all sections.
+ */
+waitCount: number, score: number, };
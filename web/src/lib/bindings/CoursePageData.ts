@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuditLogEntry } from "./AuditLogEntry";
+import type { CourseDetailResponse } from "./CourseDetailResponse";
+import type { CourseResponse } from "./CourseResponse";
+
+/**
+ * Everything the course page renders, in one response.
+ */
+export type CoursePageData = { course: CourseDetailResponse, 
+/**
+ * Every section of the same course in the term, this one included.
+ */
+sections: Array<CourseResponse>, 
+/**
+ * Recent notable changes to this section, newest first.
+ */
+auditHighlights: Array<AuditLogEntry>, 
+/**
+ * Daily enrollment over the last 30 days, oldest first.
+ */
+enrollmentHistory: Array<number> | null, };
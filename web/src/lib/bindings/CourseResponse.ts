@@ -22,4 +22,8 @@ primaryLocation: string | null,
 /**
  * Whether a physical (non-INT) building was found in meeting times.
  */
-hasPhysicalLocation: boolean, primaryInstructorId: number | null, instructors: Array<InstructorResponse>, };
+hasPhysicalLocation: boolean, primaryInstructorId: number | null, instructors: Array<InstructorResponse>, 
+/**
+ * Daily enrollment over the last 30 days, oldest first. Only set on list views.
+ */
+enrollmentHistory: Array<number> | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A registration restriction shown on course detail.
+ */
+export type CourseRestriction = { heading: string, value: string, };
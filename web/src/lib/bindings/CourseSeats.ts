@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Current seat counts for one section.
+ */
+export type CourseSeats = { term: string, crn: string, enrollment: number, maxEnrollment: number, waitCount: number, waitCapacity: number, lastScrapedAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A robots.txt group for one user-agent.
+ */
+export type CrawlRule = { userAgent: string, allow: Array<string>, disallow: Array<string>, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CrawlRule } from "./CrawlRule";
+
+/**
+ * Admin-editable crawler policy.
+ */
+export type CrawlerConfig = { rules: Array<CrawlRule>, 
+/**
+ * Emitted as `Crawl-delay` under every group when set.
+ */
+crawlDelaySecs: number | null, 
+/**
+ * Case-insensitive substrings; a matching `User-Agent` gets a 403.
+ */
+blockedUserAgents: Array<string>, 
+/**
+ * Serve schema.org JSON-LD for course and instructor pages, including
+ * instructor ratings.
+ */
+structuredData: boolean, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockedAgentCount } from "./BlockedAgentCount";
+import type { CrawlerConfig } from "./CrawlerConfig";
+
+export type CrawlerConfigResponse = { config: CrawlerConfig, blocked: Array<BlockedAgentCount>, 
+/**
+ * The robots.txt body as currently served.
+ */
+robotsTxt: string, };
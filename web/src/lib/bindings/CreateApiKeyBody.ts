@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `POST /api/admin/api-keys`.
+ */
+export type CreateApiKeyBody = { 
+/**
+ * Discord ID of the user the key acts for.
+ */
+userId: string, name: string, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `POST /api/admin/ip-bans`.
+ */
+export type CreateIpBanBody = { 
+/**
+ * A single address or a CIDR range.
+ */
+cidr: string, reason: string | null, 
+/**
+ * Ban length in seconds, between one minute and 90 days.
+ */
+durationSecs: number, };
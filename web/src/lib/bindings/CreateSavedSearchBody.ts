@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchParams } from "./SearchParams";
+
+/**
+ * Body for `POST /api/me/searches`.
+ */
+export type CreateSavedSearchBody = { name: string, 
+/**
+ * Course search parameters; paging fields are ignored.
+ */
+filters: SearchParams, 
+/**
+ * DM new matches after each scrape. Only searches filtered by subject
+ * and matching at most 200 sections may notify.
+ */
+notify: boolean, };
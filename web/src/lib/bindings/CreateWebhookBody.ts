@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookEvent } from "./WebhookEvent";
+
+/**
+ * Body for `POST /api/admin/webhooks`.
+ */
+export type CreateWebhookBody = { 
+/**
+ * A public HTTPS endpoint.
+ */
+url: string, description: string, events: Array<WebhookEvent>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiKey } from "./ApiKey";
+
+/**
+ * Response for `POST /api/admin/api-keys`.
+ */
+export type CreatedApiKey = { key: ApiKey, 
+/**
+ * The raw key. Shown only in this response.
+ */
+secret: string, };
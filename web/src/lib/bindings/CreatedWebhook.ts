@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Webhook } from "./Webhook";
+
+/**
+ * Response for `POST /api/admin/webhooks`.
+ */
+export type CreatedWebhook = { webhook: Webhook, 
+/**
+ * HMAC signing secret. Shown only in this response.
+ */
+secret: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How current a piece of scraped or synced data is.
+ */
+export type DataFreshness = "unknown" | "fresh" | "stale" | "outdated";
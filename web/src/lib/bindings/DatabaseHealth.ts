@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DatabaseHealth = { healthy: boolean, 
+/**
+ * Round trip of a trivial query; null when it failed.
+ */
+latencyMs: number | null, };
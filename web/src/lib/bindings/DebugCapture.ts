@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CapturedExchange } from "./CapturedExchange";
+
+/**
+ * A capture window on one route and what it has recorded so far.
+ */
+export type DebugCapture = { 
+/**
+ * Exact request path, e.g. `/api/courses/search`.
+ */
+path: string, startedAt: string, 
+/**
+ * Recording stops at this time; entries remain readable for an hour.
+ */
+expiresAt: string, 
+/**
+ * Recording also stops once this many exchanges are held.
+ */
+maxEntries: number, entries: Array<CapturedExchange>, };
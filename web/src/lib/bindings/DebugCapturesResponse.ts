@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DebugCapture } from "./DebugCapture";
+
+/**
+ * Response for `GET /api/admin/debug-captures`.
+ */
+export type DebugCapturesResponse = { captures: Array<DebugCapture>, };
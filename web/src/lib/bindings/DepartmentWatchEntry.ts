@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WatchType } from "./WatchType";
+
+/**
+ * One of the caller's department watches.
+ */
+export type DepartmentWatchEntry = { term: string, subject: string, watchType: WatchType, 
+/**
+ * Where alerts are posted; `null` means a Discord DM.
+ */
+webhookUrl: string | null, createdAt: string, };
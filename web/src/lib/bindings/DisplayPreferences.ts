@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimeFormat } from "./TimeFormat";
+
+/**
+ * How a user wants times rendered.
+ */
+export type DisplayPreferences = { 
+/**
+ * IANA timezone name, e.g. `America/Chicago`.
+ */
+timezone: string, timeFormat: TimeFormat, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `POST /api/admin/scraper/subjects/{subject}/scrape`.
+ */
+export type EnqueueScrapeBody = { 
+/**
+ * Term code to scrape; defaults to the current term.
+ */
+term?: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScrapeJobDto } from "./ScrapeJobDto";
+
+export type EnqueueScrapeResponse = { 
+/**
+ * False when an identical job was already queued.
+ */
+queued: boolean, job: ScrapeJobDto | null, };
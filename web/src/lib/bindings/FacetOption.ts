@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One selectable filter value with the number of matching sections.
+ */
+export type FacetOption = { code: string, description: string, 
+/**
+ * Typed filter string for query params, as in [`CodeDescription`].
+ */
+filterValue: string, count: number, };
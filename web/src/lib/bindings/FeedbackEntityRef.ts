@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Which page the report is about.
+ */
+export type FeedbackEntityRef = { "type": "course", term: string, crn: string, } | { "type": "instructor", instructor: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A feedback row in the admin queue.
+ */
+export type FeedbackItem = { id: number, entityType: string, courseId: number | null, instructorId: number | null, entityLabel: string, category: string, message: string, pageUrl: string | null, 
+/**
+ * User ID as text (exceeds JS safe integer range).
+ */
+reporterId: string | null, status: string, adminNote: string | null, reviewedBy: string | null, createdAt: string, updatedAt: string, };
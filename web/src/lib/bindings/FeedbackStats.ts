@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Per-status counts for the admin queue header.
+ */
+export type FeedbackStats = { open: number, triaged: number, resolved: number, dismissed: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Triage state of a feedback report.
+ */
+export type FeedbackStatus = "open" | "triaged" | "resolved" | "dismissed";
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type FreeRoom = { building: string, 
+/**
+ * Building name from reference data, when known.
+ */
+buildingDescription: string | null, room: string, 
+/**
+ * Start of the room's next meeting that day; `None` if it stays free.
+ */
+freeUntil: string | null, };
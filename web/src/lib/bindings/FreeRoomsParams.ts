@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Query params for `GET /api/rooms/free`.
+ */
+export type FreeRoomsParams = { 
+/**
+ * Term code or slug.
+ */
+term: string, 
+/**
+ * Weekday, e.g. `mon` or `monday`.
+ */
+day: string, 
+/**
+ * Window start as `HH:MM`.
+ */
+start: string, 
+/**
+ * Window end as `HH:MM`; must be after `start`.
+ */
+end: string, 
+/**
+ * Only rooms in this building.
+ */
+building?: string | null, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DayOfWeek } from "./DayOfWeek";
+import type { FreeRoom } from "./FreeRoom";
+
+export type FreeRoomsResponse = { termCode: string, day: DayOfWeek, start: string, end: string, 
+/**
+ * Rooms with no meeting overlapping the window, by building then room.
+ */
+rooms: Array<FreeRoom>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TermFreshness } from "./TermFreshness";
+
+export type FreshnessResponse = { terms: Array<TermFreshness>, 
+/**
+ * Coverage window, in hours.
+ */
+windowHours: number, timestamp: string, };
@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataFreshness } from "./DataFreshness";
+import type { DatabaseHealth } from "./DatabaseHealth";
+import type { MaintenanceConfig } from "./MaintenanceConfig";
+import type { ServiceInfo } from "./ServiceInfo";
+import type { ServiceStatus } from "./ServiceStatus";
+import type { SourceSyncStatus } from "./SourceSyncStatus";
+import type { TermScrapeStatus } from "./TermScrapeStatus";
+
+/**
+ * Response for `GET /api/status/full`: everything a public status page shows.
+ */
+export type FullStatusResponse = { status: ServiceStatus, version: string, commit: string, services: { [key in string]?: ServiceInfo }, 
+/**
+ * Present while maintenance mode is on.
+ */
+maintenance: MaintenanceConfig | null, database: DatabaseHealth, 
+/**
+ * Worst freshness across scrape-enabled terms and external syncs.
+ */
+freshness: DataFreshness, terms: Array<TermScrapeStatus>, syncs: Array<SourceSyncStatus>, timestamp: string, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Aggregated grade distribution for a course or instructor.
+ */
+export type GradeDistribution = { 
+/**
+ * Sections (or course offerings) the counts are summed over.
+ */
+sections: number, a: number, b: number, c: number, d: number, f: number, withdrawn: number, other: number, 
+/**
+ * Mean GPA across all graded students.
+ */
+avgGpa: number | null, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportRowError } from "./ImportRowError";
+
+/**
+ * Response for a bulk decision import.
+ */
+export type ImportDecisionsResponse = { 
+/**
+ * Rows whose decision changed a candidate or link.
+ */
+applied: number, 
+/**
+ * Rows already in the requested state.
+ */
+unchanged: number, 
+/**
+ * Rows left as `auto` or `pending`, which aren't decisions.
+ */
+skipped: number, errors: Array<ImportRowError>, };
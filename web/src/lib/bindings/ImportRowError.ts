@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A row that couldn't be applied.
+ */
+export type ImportRowError = { 
+/**
+ * Spreadsheet row (header is row 1) for CSV, line number for JSONL.
+ */
+row: number, message: string, };
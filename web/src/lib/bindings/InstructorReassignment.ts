@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangedSection } from "./ChangedSection";
+
+export type InstructorReassignment = { section: ChangedSection, 
+/**
+ * Instructor names before the first change that day.
+ */
+from: Array<string>, 
+/**
+ * Instructor names after the last change that day.
+ */
+to: Array<string>, };
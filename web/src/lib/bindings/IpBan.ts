@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A ban on one address or CIDR range.
+ */
+export type IpBan = { id: number, cidr: string, reason: string | null, 
+/**
+ * Username of the admin who added the ban, if they still exist.
+ */
+createdBy: string | null, createdAt: string, expiresAt: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An instructor's composite rating on a 1-5 scale.
+ */
+export type JsonLdAggregateRating = { "@type": "AggregateRating", 
+/**
+ * Rounded to one decimal, e.g. `"4.3"`.
+ */
+ratingValue: string, bestRating: number, worstRating: number, ratingCount: number, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonLdCourseInstance } from "./JsonLdCourseInstance";
+import type { JsonLdOrganization } from "./JsonLdOrganization";
+
+/**
+ * `Course` document for a section page.
+ */
+export type JsonLdCourse = { "@context": "https://schema.org", "@type": "Course", name: string, 
+/**
+ * Subject and number, e.g. `CS 1083`.
+ */
+courseCode: string, description: string, url: string, provider: JsonLdOrganization, numberOfCredits?: number | null, hasCourseInstance: Array<JsonLdCourseInstance>, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonLdInstructor } from "./JsonLdInstructor";
+
+/**
+ * One offering of a course: a section in a term.
+ */
+export type JsonLdCourseInstance = { "@type": "CourseInstance", 
+/**
+ * `Onsite`, `Online`, or `Blended`; omitted when the method is unknown.
+ */
+courseMode?: string | null, 
+/**
+ * First day of class, `YYYY-MM-DD`.
+ */
+startDate?: string | null, 
+/**
+ * Last day of class, `YYYY-MM-DD`.
+ */
+endDate?: string | null, instructor: Array<JsonLdInstructor>, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An instructor named inside a `CourseInstance`.
+ */
+export type JsonLdInstructor = { "@type": "Person", name: string, 
+/**
+ * Profile page, when the instructor has one.
+ */
+url?: string | null, };
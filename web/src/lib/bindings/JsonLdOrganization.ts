@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The university, as the provider of courses and employer of instructors.
+ */
+export type JsonLdOrganization = { "@type": "CollegeOrUniversity", name: string, url: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonLdAggregateRating } from "./JsonLdAggregateRating";
+import type { JsonLdOrganization } from "./JsonLdOrganization";
+
+/**
+ * `Person` document for an instructor page.
+ */
+export type JsonLdPerson = { "@context": "https://schema.org", "@type": "Person", name: string, url: string, jobTitle: string, worksFor: JsonLdOrganization, aggregateRating?: JsonLdAggregateRating | null, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Query params for `GET /api/admin/actions`.
+ */
+export type ListAdminActionsParams = { 
+/**
+ * Discord ID of the acting admin.
+ */
+adminId: string | null, method: string | null, 
+/**
+ * Request path prefix, e.g. `/api/admin/users`.
+ */
+path: string | null, 
+/**
+ * Only successful (`true`) or only failed (`false`) calls.
+ */
+success: boolean | null, since: string | null, until: string | null, page: number | null, perPage: number | null, };
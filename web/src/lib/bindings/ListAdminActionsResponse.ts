@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdminAction } from "./AdminAction";
+
+/**
+ * Response for the paginated admin action list.
+ */
+export type ListAdminActionsResponse = { items: Array<AdminAction>, total: number, page: number, perPage: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BluebookUnmatchedLink } from "./BluebookUnmatchedLink";
+
+/**
+ * Response for the unmatched link queue.
+ */
+export type ListBluebookUnmatchedResponse = { links: Array<BluebookUnmatchedLink>, total: number, page: number, perPage: number, };
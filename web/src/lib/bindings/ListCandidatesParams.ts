@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ListCandidatesParams = { 
+/**
+ * Candidate status to list; defaults to `pending`, `all` lists every status.
+ */
+status: string | null, search: string | null, minScore: number | null, page: number | null, perPage: number | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CandidateQueueItem } from "./CandidateQueueItem";
+
+/**
+ * Response for the paginated candidate queue.
+ */
+export type ListCandidatesResponse = { candidates: Array<CandidateQueueItem>, total: number, page: number, perPage: number, };
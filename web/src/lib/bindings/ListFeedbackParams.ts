@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FeedbackStatus } from "./FeedbackStatus";
+
+/**
+ * Query params for `GET /api/admin/feedback`.
+ */
+export type ListFeedbackParams = { status: FeedbackStatus | null, page: number | null, perPage: number | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FeedbackItem } from "./FeedbackItem";
+import type { FeedbackStats } from "./FeedbackStats";
+
+/**
+ * Response for the paginated admin feedback list.
+ */
+export type ListFeedbackResponse = { items: Array<FeedbackItem>, total: number, page: number, perPage: number, stats: FeedbackStats, };
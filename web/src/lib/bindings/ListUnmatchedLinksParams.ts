@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Query params for `GET /api/admin/bluebook/links/unmatched`.
+ */
+export type ListUnmatchedLinksParams = { search: string | null, page: number | null, perPage: number | null, };
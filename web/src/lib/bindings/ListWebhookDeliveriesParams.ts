@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeliveryStatus } from "./DeliveryStatus";
+
+/**
+ * Query params for `GET /api/admin/webhooks/{id}/deliveries`.
+ */
+export type ListWebhookDeliveriesParams = { status: DeliveryStatus | null, 
+/**
+ * At most 200; defaults to 50.
+ */
+limit?: number, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Admin-editable maintenance switch.
+ */
+export type MaintenanceConfig = { enabled: boolean, 
+/**
+ * Shown to users alongside the 503; a generic message is used when unset.
+ */
+message: string | null, 
+/**
+ * Sent as `Retry-After` on rejected requests.
+ */
+retryAfterSecs: number, 
+/**
+ * When maintenance was last switched on. Set by the server.
+ */
+since: string | null, };
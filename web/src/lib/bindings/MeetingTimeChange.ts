@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangedSection } from "./ChangedSection";
+import type { DbMeetingTime } from "./DbMeetingTime";
+
+export type MeetingTimeChange = { section: ChangedSection, from: Array<DbMeetingTime>, to: Array<DbMeetingTime>, };
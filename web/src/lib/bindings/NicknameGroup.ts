@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A nickname equivalence group row.
+ */
+export type NicknameGroup = { id: number, 
+/**
+ * Normalized, interchangeable first names.
+ */
+names: Array<string>, note: string | null, createdAt: string, updatedAt: string, };
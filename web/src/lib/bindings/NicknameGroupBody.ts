@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `POST /api/admin/nicknames` and `PUT /api/admin/nicknames/{id}`.
+ */
+export type NicknameGroupBody = { 
+/**
+ * Interchangeable first names; normalized server-side.
+ */
+names: Array<string>, note: string | null, };
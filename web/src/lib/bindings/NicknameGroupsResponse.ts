@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NicknameGroup } from "./NicknameGroup";
+
+/**
+ * Response for `GET /api/admin/nicknames`.
+ */
+export type NicknameGroupsResponse = { groups: Array<NicknameGroup>, };
@@ -1,4 +1,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { PublicInstructorListItem } from "./PublicInstructorListItem";
 
-export type PublicInstructorListResponse = { instructors: Array<PublicInstructorListItem>, total: number, page: number, perPage: number, };
+export type PublicInstructorListResponse = { instructors: Array<PublicInstructorListItem>, total: number, page: number, perPage: number, 
+/**
+ * Pass as `cursor` to fetch the next page; `None` on the last page.
+ */
+nextCursor: string | null, };
@@ -1,6 +1,21 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { BlueBookFull } from "./BlueBookFull";
+import type { GradeDistribution } from "./GradeDistribution";
 import type { InstructorRating } from "./InstructorRating";
+import type { RatingBreakdown } from "./RatingBreakdown";
 import type { RmpFull } from "./RmpFull";
+import type { RmpTrend } from "./RmpTrend";
 
-export type PublicInstructorProfile = { id: number, slug: string, displayName: string, email: string | null, firstName: string | null, lastName: string | null, subjects: Array<string>, rmp: RmpFull | null, bluebook: BlueBookFull | null, rating: InstructorRating | null, };
+export type PublicInstructorProfile = { id: number, slug: string, displayName: string, email: string | null, firstName: string | null, lastName: string | null, subjects: Array<string>, rmp: RmpFull | null, 
+/**
+ * Rating history and recent drift for the primary RMP profile.
+ */
+rmpTrend: RmpTrend | null, bluebook: BlueBookFull | null, 
+/**
+ * Grades across the instructor's sections in imported grade releases.
+ */
+grades: GradeDistribution | null, rating: InstructorRating | null, 
+/**
+ * How RMP, BlueBook and the prior combined into `rating`.
+ */
+ratingBreakdown: RatingBreakdown | null, };
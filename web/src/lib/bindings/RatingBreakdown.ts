@@ -0,0 +1,38 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How each input contributed to an instructor's composite score.
+ */
+export type RatingBreakdown = { 
+/**
+ * RMP rating as scored, after recency and decay weighting.
+ */
+rmpWeighted: number | null, 
+/**
+ * RMP ratings counted after decay; old reviews count for less.
+ */
+rmpWeightedCount: number, 
+/**
+ * Fraction of the score's evidence contributed by RMP.
+ */
+rmpShare: number, 
+/**
+ * BlueBook rating as scored, after decay and calibration to the RMP scale.
+ */
+bbWeighted: number | null, 
+/**
+ * BlueBook responses counted after decay.
+ */
+bbWeightedCount: number, 
+/**
+ * Fraction of the score's evidence contributed by BlueBook.
+ */
+bbShare: number, 
+/**
+ * Fraction held by the prior; large when there is little evidence.
+ */
+priorShare: number, 
+/**
+ * Rating points added for grading leniency (negative for lenient graders).
+ */
+gradeShift: number, };
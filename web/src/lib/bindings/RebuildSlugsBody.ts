@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `POST /api/admin/instructors/rebuild-slugs`.
+ */
+export type RebuildSlugsBody = { 
+/**
+ * Instructors to rebuild in addition to those flagged by a name change.
+ */
+ids: Array<number>, };
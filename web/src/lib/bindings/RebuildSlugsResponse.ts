@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SlugChange } from "./SlugChange";
+
+/**
+ * Response for `POST /api/admin/instructors/rebuild-slugs`.
+ */
+export type RebuildSlugsResponse = { 
+/**
+ * Instructors considered; those whose name still yields the same slug
+ * keep it.
+ */
+checked: number, changes: Array<SlugChange>, };
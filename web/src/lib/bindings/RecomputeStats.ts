@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecomputeMode } from "./RecomputeMode";
+
+/**
+ * Outcome of a score recompute.
+ */
+export type RecomputeStats = { 
+/**
+ * The mode that actually ran.
+ */
+mode: RecomputeMode, 
+/**
+ * Instructors whose scores were rewritten.
+ */
+recomputed: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Classification } from "./Classification";
+
+/**
+ * When registration opens for one classification in a term.
+ */
+export type RegistrationWindow = { classification: Classification, opensAt: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Classification } from "./Classification";
+
+/**
+ * One entry in a registration window update.
+ */
+export type RegistrationWindowInput = { classification: Classification, opensAt: string, };
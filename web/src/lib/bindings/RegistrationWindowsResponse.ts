@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RegistrationWindow } from "./RegistrationWindow";
+
+/**
+ * Response for `GET`/`PUT /api/admin/terms/:code/registration-windows`.
+ */
+export type RegistrationWindowsResponse = { term: string, windows: Array<RegistrationWindow>, };
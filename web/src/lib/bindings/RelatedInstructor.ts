@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InstructorRating } from "./InstructorRating";
+
+export type RelatedInstructor = { id: number, slug: string, displayName: string, 
+/**
+ * Subjects both instructors have taught.
+ */
+sharedSubjects: Array<string>, 
+/**
+ * Sections the two instructors were both listed on.
+ */
+coTaughtSections: number, 
+/**
+ * Blended relatedness; only meaningful for ordering.
+ */
+score: number, rating: InstructorRating | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `PUT /api/admin/api-keys/{id}`.
+ */
+export type RenameApiKeyBody = { name: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `PUT /api/me/schedule/{term}`.
+ */
+export type ReplaceScheduleBody = { 
+/**
+ * Replaces the term's plan. Every CRN must exist in the term.
+ */
+crns: Array<string>, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScrapeJobDto } from "./ScrapeJobDto";
+
+/**
+ * Response for `POST /api/admin/scrape-jobs/{id}/requeue`.
+ */
+export type RequeueJobResponse = { 
+/**
+ * False when a live job for the same target already existed and the
+ * dead letter was dropped instead.
+ */
+requeued: boolean, job: ScrapeJobDto | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One recorded RMP aggregate.
+ */
+export type RmpHistoryPoint = { recordedAt: string, avgRating: number | null, numRatings: number, };
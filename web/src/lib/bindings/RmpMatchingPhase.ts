@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Stage of a candidate generation run.
+ */
+export type RmpMatchingPhase = "loading" | "scoring" | "writing" | "completed" | "failed";
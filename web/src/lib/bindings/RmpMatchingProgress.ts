@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RmpMatchingPhase } from "./RmpMatchingPhase";
+
+/**
+ * Cumulative progress of a candidate generation run, streamed to admins.
+ *
+ * Every event carries the full state so a client that joins mid-run can
+ * render the latest one without replaying history.
+ */
+export type RmpMatchingProgress = { phase: RmpMatchingPhase, startedAt: string, 
+/**
+ * Eligible instructors (excludes confirmed/rejected).
+ */
+instructorsTotal: number, instructorsScored: number, professorsLoaded: number, candidatesCreated: number, autoMatched: number, pendingReview: number, error?: string, };
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RmpHistoryPoint } from "./RmpHistoryPoint";
+
+/**
+ * Rating trend for an instructor's RMP profile.
+ */
+export type RmpTrend = { 
+/**
+ * Snapshots, oldest first.
+ */
+history: Array<RmpHistoryPoint>, windowDays: number, 
+/**
+ * Ratings received within the window.
+ */
+recentNumRatings: number, 
+/**
+ * Average of the ratings received within the window.
+ */
+recentAvgRating: number | null, 
+/**
+ * `recent_avg_rating` minus the current overall average; positive means
+ * recent students rate the instructor higher than the all-time average.
+ */
+drift: number | null, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DateRange } from "./DateRange";
+import type { TimeRange } from "./TimeRange";
+
+/**
+ * One section occupying the room.
+ */
+export type RoomBlock = { crn: string, subject: string, courseNumber: string, title: string, timeRange: TimeRange, 
+/**
+ * The weeks this meeting pattern runs.
+ */
+dateRange: DateRange, };
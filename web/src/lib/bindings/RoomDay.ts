@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DayOfWeek } from "./DayOfWeek";
+import type { RoomBlock } from "./RoomBlock";
+
+export type RoomDay = { day: DayOfWeek, 
+/**
+ * Meetings on this weekday, ordered by start time.
+ */
+blocks: Array<RoomBlock>, };
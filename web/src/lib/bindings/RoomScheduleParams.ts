@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Query params for `GET /api/rooms/{building}/{room}/schedule`.
+ */
+export type RoomScheduleParams = { 
+/**
+ * Term code or slug.
+ */
+term: string, };
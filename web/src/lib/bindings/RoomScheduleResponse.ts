@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RoomDay } from "./RoomDay";
+
+export type RoomScheduleResponse = { termCode: string, building: string, 
+/**
+ * Building name from reference data, when known.
+ */
+buildingDescription: string | null, room: string, 
+/**
+ * Weekdays with at least one meeting, Monday first.
+ */
+days: Array<RoomDay>, };
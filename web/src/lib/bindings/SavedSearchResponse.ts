@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchParams } from "./SearchParams";
+
+/**
+ * One of the caller's saved searches.
+ */
+export type SavedSearchResponse = { id: number, name: string, 
+/**
+ * Course search parameters; `term` is always a term code.
+ */
+filters: SearchParams, 
+/**
+ * Whether new matches are sent as a Discord DM.
+ */
+notify: boolean, createdAt: string, 
+/**
+ * When new matches were last sent.
+ */
+notifiedAt: string | null, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Compact summary of what a subject scrape changed, stored on its result row.
+ */
+export type ScrapeDiffSummary = { 
+/**
+ * Courses stored for the first time.
+ */
+added: number, 
+/**
+ * Previously stored courses missing from this fetch.
+ */
+removed: number, 
+/**
+ * Existing courses with at least one field change.
+ */
+changed: number, 
+/**
+ * Number of changes per field (e.g. `"enrollment"`, `"instructors"`).
+ */
+fields: { [key in string]?: number }, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Retention of `scrape_job_results` and the outcome of the last compaction.
+ */
+export type ScrapeHistoryRetention = { 
+/**
+ * Results older than this are rolled into daily stats.
+ */
+retentionDays: number, 
+/**
+ * `null` until the first compaction, or when the lookup failed.
+ */
+lastCompactedAt: string | null, lastCompactedRows: number | null, };
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScrapeDiffSummary } from "./ScrapeDiffSummary";
 import type { ScrapeJobStatus } from "./ScrapeJobStatus";
 import type { ScrapePriority } from "./ScrapePriority";
 import type { TargetType } from "./TargetType";
@@ -7,4 +8,8 @@ import type { JsonValue } from "./serde_json/JsonValue";
 /**
  * A serializable DTO for `ScrapeJob` with computed `status`.
  */
-export type ScrapeJobDto = { id: number, targetType: TargetType, targetPayload: JsonValue, priority: ScrapePriority, executeAt: string, createdAt: string, lockedAt: string | null, retryCount: number, maxRetries: number, queuedAt: string, status: ScrapeJobStatus, };
+export type ScrapeJobDto = { id: number, targetType: TargetType, targetPayload: JsonValue, priority: ScrapePriority, executeAt: string, createdAt: string, lockedAt: string | null, retryCount: number, maxRetries: number, queuedAt: string, status: ScrapeJobStatus, deadLetteredAt: string | null, lastError: string | null, 
+/**
+ * Diff from the latest completed run with the same payload, when known.
+ */
+lastDiff: ScrapeDiffSummary | null, };
@@ -3,4 +3,4 @@
 /**
  * Computed status for a scrape job, derived from existing fields.
  */
-export type ScrapeJobStatus = "processing" | "staleLock" | "exhausted" | "scheduled" | "pending";
+export type ScrapeJobStatus = "processing" | "staleLock" | "exhausted" | "deadLetter" | "scheduled" | "pending";
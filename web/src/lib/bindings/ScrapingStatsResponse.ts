@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubjectScrapeStats } from "./SubjectScrapeStats";
+
+export type ScrapingStatsResponse = { term: string, subjects: Array<SubjectScrapeStats>, 
+/**
+ * Window for `scrapes` and `coursesChanged`, in hours.
+ */
+windowHours: number, timestamp: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FacetOption } from "./FacetOption";
+
+/**
+ * Facet counts over all sections matching the current search. Each facet
+ * ignores its own filter, so selected values keep their alternatives.
+ */
+export type SearchFacetsResponse = { subjects: Array<FacetOption>, campuses: Array<FacetOption>, instructionalMethods: Array<FacetOption>, attributes: Array<FacetOption>, 
+/**
+ * Sections with at least one open seat.
+ */
+open: number, closed: number, };
@@ -2,4 +2,8 @@
 import type { SortColumn } from "./SortColumn";
 import type { SortDirection } from "./SortDirection";
 
-export type SearchParams = { term: string, subject: Array<string>, query: string | null, courseNumberLow: number | null, courseNumberHigh: number | null, openOnly: boolean, instructionalMethod: Array<string>, campus: Array<string>, limit: number, offset: number, sortBy: SortColumn | null, sortDir: SortDirection | null, waitCountMax: number | null, days: Array<string>, timeStart: string | null, timeEnd: string | null, partOfTerm: Array<string>, attributes: Array<string>, creditHourMin: number | null, creditHourMax: number | null, instructor: Array<string>, };
+export type SearchParams = { term: string, subject: Array<string>, query: string | null, courseNumberLow: number | null, courseNumberHigh: number | null, openOnly: boolean, waitlistAvailable: boolean, instructionalMethod: Array<string>, campus: Array<string>, limit: number, offset: number, 
+/**
+ * `nextCursor` from a previous page; takes precedence over `offset`.
+ */
+cursor: string | null, sortBy: SortColumn | null, sortDir: SortDirection | null, waitCountMax: number | null, days: Array<string>, timeStart: string | null, timeEnd: string | null, partOfTerm: Array<string>, attributes: Array<string>, creditHourMin: number | null, creditHourMax: number | null, instructor: Array<string>, };
@@ -1,4 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { CourseResponse } from "./CourseResponse";
+import type { SearchFacetsResponse } from "./SearchFacetsResponse";
 
-export type SearchResponse = { courses: Array<CourseResponse>, totalCount: number, };
+export type SearchResponse = { courses: Array<CourseResponse>, totalCount: number, 
+/**
+ * Only on the first page; later pages reuse the first page's facets.
+ */
+facets: SearchFacetsResponse | null, 
+/**
+ * Pass as `cursor` to fetch the next page; `None` on the last page.
+ */
+nextCursor: string | null, };
@@ -3,4 +3,15 @@
 /**
  * A linked section reference (e.g. lab linked to a lecture).
  */
-export type SectionLink = { identifier: string, };
+export type SectionLink = { 
+/**
+ * Banner's link identifier, if the search results carried one.
+ */
+identifier: string | null, 
+/**
+ * Alternative groups of CRNs that must be registered with this section.
+ *
+ * Exactly one group must be chosen (e.g. one lab for a lecture). Empty
+ * until the detail enrichment job has fetched linked sections.
+ */
+groups: Array<Array<string>>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An instructor whose slug was replaced by [`rebuild_instructor_slugs`].
+ */
+export type SlugChange = { instructorId: number, displayName: string, oldSlug: string | null, newSlug: string, };
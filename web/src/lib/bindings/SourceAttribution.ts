@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One upstream data source.
+ */
+export type SourceAttribution = { 
+/**
+ * Stable identifier: `banner`, `rmp`, or `bluebook`.
+ */
+id: string, name: string, url: string, 
+/**
+ * What the API serves from this source.
+ */
+provides: string, 
+/**
+ * Terms the data is used under.
+ */
+terms: string, 
+/**
+ * When this source was last synced successfully, if ever.
+ */
+lastSyncedAt: string | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataFreshness } from "./DataFreshness";
+import type { SyncKind } from "./SyncKind";
+
+/**
+ * Age of an external-source sync, from the scheduler's success timestamps.
+ */
+export type SourceSyncStatus = { kind: SyncKind, label: string, lastSuccessAt: string | null, ageSecs: number | null, freshness: DataFreshness, };
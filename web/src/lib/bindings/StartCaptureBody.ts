@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `POST /api/admin/debug-captures`.
+ */
+export type StartCaptureBody = { 
+/**
+ * Exact API path to record, without query string.
+ */
+path: string, 
+/**
+ * Defaults to 5 minutes; at most 30.
+ */
+durationSecs: number | null, 
+/**
+ * Defaults to 50; at most 200.
+ */
+maxEntries: number | null, };
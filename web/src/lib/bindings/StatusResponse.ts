@@ -1,5 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MaintenanceConfig } from "./MaintenanceConfig";
 import type { ServiceInfo } from "./ServiceInfo";
 import type { ServiceStatus } from "./ServiceStatus";
 
-export type StatusResponse = { status: ServiceStatus, version: string, commit: string, services: { [key in string]?: ServiceInfo }, };
+export type StatusResponse = { status: ServiceStatus, version: string, commit: string, services: { [key in string]?: ServiceInfo }, 
+/**
+ * Present while maintenance mode is on.
+ */
+maintenance: MaintenanceConfig | null, };
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type StreamErrorCode = "invalidMessage" | "invalidFilter" | "unknownSubscription" | "internalError";
+export type StreamErrorCode = "invalidMessage" | "invalidFilter" | "unknownSubscription" | "internalError" | "forbidden" | "subscriptionLimit" | "rateLimited";
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type StreamKind = "scrapeJobs" | "auditLog" | "scraperStats" | "scraperTimeseries" | "scraperSubjects";
+export type StreamKind = "scrapeJobs" | "auditLog" | "scraperStats" | "scraperTimeseries" | "scraperSubjects" | "rmpMatching" | "bannerHealth" | "course";
@@ -1,8 +1,15 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AuditLogEntry } from "./AuditLogEntry";
+import type { BannerHealth } from "./BannerHealth";
+import type { CourseSeats } from "./CourseSeats";
+import type { RmpMatchingProgress } from "./RmpMatchingProgress";
 import type { ScrapeJobDto } from "./ScrapeJobDto";
 import type { ScraperStatsResponse } from "./ScraperStatsResponse";
 import type { SubjectSummary } from "./SubjectSummary";
 import type { TimeseriesPoint } from "./TimeseriesPoint";
 
-export type StreamSnapshot = { "stream": "scrapeJobs", jobs: Array<ScrapeJobDto>, } | { "stream": "auditLog", entries: Array<AuditLogEntry>, } | { "stream": "scraperStats", stats: ScraperStatsResponse, } | { "stream": "scraperTimeseries", points: Array<TimeseriesPoint>, period: string, bucket: string, } | { "stream": "scraperSubjects", subjects: Array<SubjectSummary>, };
+export type StreamSnapshot = { "stream": "scrapeJobs", jobs: Array<ScrapeJobDto>, } | { "stream": "auditLog", entries: Array<AuditLogEntry>, } | { "stream": "scraperStats", stats: ScraperStatsResponse, } | { "stream": "scraperTimeseries", points: Array<TimeseriesPoint>, period: string, bucket: string, } | { "stream": "scraperSubjects", subjects: Array<SubjectSummary>, } | { "stream": "rmpMatching", 
+/**
+ * Latest progress of the current or most recent run, if still buffered.
+ */
+progress: RmpMatchingProgress | null, } | { "stream": "bannerHealth", health: BannerHealth, } | { "stream": "course", seats: CourseSeats, };
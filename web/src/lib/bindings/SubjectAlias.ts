@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A subject alias row.
+ */
+export type SubjectAlias = { 
+/**
+ * Retired subject code.
+ */
+alias: string, 
+/**
+ * Current code the alias resolves to.
+ */
+subject: string, note: string | null, createdAt: string, };
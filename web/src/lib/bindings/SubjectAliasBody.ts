@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Body for `PUT /api/admin/subject-aliases/{alias}`.
+ */
+export type SubjectAliasBody = { 
+/**
+ * Current subject code the alias resolves to.
+ */
+subject: string, note: string | null, };
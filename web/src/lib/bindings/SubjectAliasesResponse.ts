@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubjectAlias } from "./SubjectAlias";
+
+/**
+ * Response for `GET /api/admin/subject-aliases`.
+ */
+export type SubjectAliasesResponse = { aliases: Array<SubjectAlias>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response-weighted BlueBook course rating across the subject's evaluations.
+ */
+export type SubjectBlueBook = { avgCourseRating: number, totalResponses: number, };
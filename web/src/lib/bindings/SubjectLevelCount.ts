@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Distinct courses at one level (1000, 2000, ...), across all terms.
+ */
+export type SubjectLevelCount = { 
+/**
+ * Leading digit of the course number followed by "000".
+ */
+level: string, courseCount: number, };
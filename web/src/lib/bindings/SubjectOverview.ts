@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubjectBlueBook } from "./SubjectBlueBook";
+import type { SubjectInstructor } from "./SubjectInstructor";
+import type { SubjectLevelCount } from "./SubjectLevelCount";
+import type { SubjectTerm } from "./SubjectTerm";
+
+export type SubjectOverview = { 
+/**
+ * Current subject code; aliases resolve to it.
+ */
+code: string, description: string | null, levels: Array<SubjectLevelCount>, topInstructors: Array<SubjectInstructor>, bluebook: SubjectBlueBook | null, 
+/**
+ * Newest first.
+ */
+terms: Array<SubjectTerm>, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Scrape recency and churn for one subject.
+ */
+export type SubjectScrapeStats = { subject: string, description: string | null, 
+/**
+ * When this subject was last scraped successfully.
+ */
+lastScrapedAt: string | null, 
+/**
+ * Successful scrapes within the window.
+ */
+scrapes: number, 
+/**
+ * Courses changed by those scrapes.
+ */
+coursesChanged: number, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataFreshness } from "./DataFreshness";
+
+export type SubjectScrapeStatus = { subject: string, description: string | null, lastScrapedAt: string | null, freshness: DataFreshness, };
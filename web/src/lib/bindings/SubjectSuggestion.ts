@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A suggested subject result for autocomplete.
+ */
+export type SubjectSuggestion = { code: string, 
+/**
+ * The subject's name, or its code when no reference entry exists.
+ */
+description: string, sectionCount: number, score: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One term the subject has sections in.
+ */
+export type SubjectTerm = { code: string, slug: string, description: string, courseCount: number, sectionCount: number, };
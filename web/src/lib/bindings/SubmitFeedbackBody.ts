@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FeedbackCategory } from "./FeedbackCategory";
+import type { FeedbackEntityRef } from "./FeedbackEntityRef";
+
+/**
+ * Body for `POST /api/feedback`.
+ */
+export type SubmitFeedbackBody = { entity: FeedbackEntityRef, category: FeedbackCategory, message: string, pageUrl: string | null, };
@@ -1,3 +1,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SuggestGroupBy } from "./SuggestGroupBy";
 
-export type SuggestParams = { term: string, q: string, limit: number, };
+export type SuggestParams = { term: string, q: string, limit: number, group_by?: SuggestGroupBy | null, 
+/**
+ * Per-group limits; each defaults to `limit`, and 0 omits the group.
+ */
+course_limit?: number | null, instructor_limit?: number | null, subject_limit?: number | null, building_limit?: number | null, };
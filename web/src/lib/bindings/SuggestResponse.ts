@@ -1,5 +1,16 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BuildingSuggestion } from "./BuildingSuggestion";
+import type { CourseGroupSuggestion } from "./CourseGroupSuggestion";
 import type { CourseSuggestion } from "./CourseSuggestion";
 import type { InstructorSuggestion } from "./InstructorSuggestion";
+import type { SubjectSuggestion } from "./SubjectSuggestion";
 
-export type SuggestResponse = { courses: Array<CourseSuggestion>, instructors: Array<InstructorSuggestion>, };
+export type SuggestResponse = { 
+/**
+ * Course suggestions, one per distinct title; empty with `group_by=course`.
+ */
+courses: Array<CourseSuggestion>, 
+/**
+ * Course suggestions grouped per `group_by=course`; otherwise empty.
+ */
+courseGroups: Array<CourseGroupSuggestion>, instructors: Array<InstructorSuggestion>, subjects: Array<SubjectSuggestion>, buildings: Array<BuildingSuggestion>, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SyncKind } from "./SyncKind";
+import type { SyncOutcome } from "./SyncOutcome";
+
+/**
+ * One periodic sync and its most recent run.
+ */
+export type SyncStatus = { kind: SyncKind, label: string, intervalSecs: number, lastStartedAt: string | null, lastFinishedAt: string | null, lastDurationMs: number | null, lastOutcome: SyncOutcome | null, lastError: string | null, lastSuccessAt: string | null, 
+/**
+ * When the scheduler will next run it; `null` means on its next cycle.
+ */
+nextRunAt: string | null, 
+/**
+ * A forced run was requested and the scheduler hasn't picked it up yet.
+ */
+forcePending: boolean, };
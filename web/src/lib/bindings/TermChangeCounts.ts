@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How many changes of each kind happened, including any not listed.
+ */
+export type TermChangeCounts = { added: number, removed: number, instructorChanges: number, timeChanges: number, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangedSection } from "./ChangedSection";
+import type { InstructorReassignment } from "./InstructorReassignment";
+import type { MeetingTimeChange } from "./MeetingTimeChange";
+import type { TermChangeCounts } from "./TermChangeCounts";
+
+/**
+ * Changes on one calendar day, in campus time.
+ */
+export type TermChangeDay = { date: string, 
+/**
+ * When the last change that day was recorded.
+ */
+lastChanged: string, counts: TermChangeCounts, added: Array<ChangedSection>, removed: Array<ChangedSection>, instructorChanges: Array<InstructorReassignment>, timeChanges: Array<MeetingTimeChange>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Query params for `GET /api/terms/{term}/changes`.
+ */
+export type TermChangesParams = { 
+/**
+ * RFC 3339 timestamp, or a `YYYY-MM-DD` date in campus time. Defaults
+ * to 7 days ago; at most 31 days back.
+ */
+since?: string | null, };
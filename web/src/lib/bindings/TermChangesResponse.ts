@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TermChangeCounts } from "./TermChangeCounts";
+import type { TermChangeDay } from "./TermChangeDay";
+
+export type TermChangesResponse = { termCode: string, termSlug: string, since: string, totals: TermChangeCounts, 
+/**
+ * Days with at least one change, newest first.
+ */
+days: Array<TermChangeDay>, };
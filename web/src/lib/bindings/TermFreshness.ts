@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How current the scraped data for one term is.
+ */
+export type TermFreshness = { term: string, description: string, 
+/**
+ * When any scrape job for this term last succeeded.
+ */
+lastScrapedAt: string | null, subjectsTotal: number, subjectsScraped: number, 
+/**
+ * Percentage (0-100) of subjects scraped within the window; null when
+ * the term's subject list hasn't been fetched yet.
+ */
+coveragePct: number | null, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataFreshness } from "./DataFreshness";
+import type { SubjectScrapeStatus } from "./SubjectScrapeStatus";
+
+export type TermScrapeStatus = { term: string, description: string, 
+/**
+ * When any scrape job for this term last succeeded.
+ */
+lastScrapedAt: string | null, freshness: DataFreshness, subjects: Array<SubjectScrapeStatus>, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimelineSlot } from "./TimelineSlot";
+
+export type TermTimeline = { term: string, 
+/**
+ * Days this term's data was shifted to line up with the reference term.
+ */
+offsetDays: number, 
+/**
+ * Slots at the reference term's times, with this term's totals.
+ */
+slots: Array<TimelineSlot>, };
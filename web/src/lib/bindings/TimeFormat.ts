@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Clock style for rendered times.
+ */
+export type TimeFormat = "twelveHour" | "twentyFourHour";
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimeRange } from "./TimeRange";
+import type { TimelineGroupBy } from "./TimelineGroupBy";
+
+export type TimelineCompareRequest = { 
+/**
+ * Term codes or slugs. The first is the reference term that `ranges`
+ * are expressed in.
+ */
+terms: Array<string>, ranges: Array<TimeRange>, 
+/**
+ * Defaults to `subject`.
+ */
+groupBy?: TimelineGroupBy | null, };
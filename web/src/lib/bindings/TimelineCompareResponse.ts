@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TermTimeline } from "./TermTimeline";
+import type { TimelineGroupBy } from "./TimelineGroupBy";
+import type { TimelineSeries } from "./TimelineSeries";
+
+export type TimelineCompareResponse = { 
+/**
+ * One timeline per requested term, in request order.
+ */
+terms: Array<TermTimeline>, groupBy: TimelineGroupBy, 
+/**
+ * All series present in any term, sorted by key.
+ */
+series: Array<TimelineSeries>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What the timeline's series are split by.
+ */
+export type TimelineGroupBy = "subject" | "level" | "campus";
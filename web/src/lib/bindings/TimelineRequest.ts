@@ -1,4 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { TimeRange } from "./TimeRange";
+import type { TimelineGroupBy } from "./TimelineGroupBy";
 
-export type TimelineRequest = { ranges: Array<TimeRange>, };
+export type TimelineRequest = { ranges: Array<TimeRange>, 
+/**
+ * Defaults to `subject`.
+ */
+groupBy?: TimelineGroupBy | null, };
@@ -1,12 +1,14 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimelineGroupBy } from "./TimelineGroupBy";
+import type { TimelineSeries } from "./TimelineSeries";
 import type { TimelineSlot } from "./TimelineSlot";
 
 export type TimelineResponse = { 
 /**
- * 15-minute slots with per-subject enrollment totals, sorted by time.
+ * 15-minute slots with per-series enrollment totals, sorted by time.
  */
-slots: Array<TimelineSlot>, 
+slots: Array<TimelineSlot>, groupBy: TimelineGroupBy, 
 /**
- * All subject codes present in the returned data.
+ * All series present in the returned data, sorted by key.
  */
-subjects: Array<string>, };
+series: Array<TimelineSeries>, };
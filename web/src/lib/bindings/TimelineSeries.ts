@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One labeled series in a timeline response.
+ */
+export type TimelineSeries = { 
+/**
+ * Key used in each slot's `totals`, e.g. a subject code or `2000`.
+ */
+key: string, label: string, };
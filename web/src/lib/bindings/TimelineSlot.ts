@@ -6,6 +6,6 @@ export type TimelineSlot = {
  */
 time: string, 
 /**
- * Subject code -> total enrollment in this slot.
+ * Series key -> total enrollment in this slot.
  */
-subjects: Record<string, number>, };
+totals: Record<string, number>, };
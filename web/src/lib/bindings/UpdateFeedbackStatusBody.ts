@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FeedbackStatus } from "./FeedbackStatus";
+
+/**
+ * Body for `POST /api/admin/feedback/{id}/status`.
+ */
+export type UpdateFeedbackStatusBody = { status: FeedbackStatus, 
+/**
+ * Replaces the existing admin note when present.
+ */
+note: string | null, };
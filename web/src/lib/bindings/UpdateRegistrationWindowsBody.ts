@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RegistrationWindowInput } from "./RegistrationWindowInput";
+
+/**
+ * Body for `PUT /api/admin/terms/:code/registration-windows`.
+ */
+export type UpdateRegistrationWindowsBody = { 
+/**
+ * Replaces every window for the term.
+ */
+windows: Array<RegistrationWindowInput>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookEvent } from "./WebhookEvent";
+
+/**
+ * Body for `PUT /api/admin/webhooks/{id}`.
+ */
+export type UpdateWebhookBody = { description: string, events: Array<WebhookEvent>, enabled: boolean, };
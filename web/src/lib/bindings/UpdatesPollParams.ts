@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type UpdatesPollParams = { 
+/**
+ * Cursor from the previous response; omit to get the current head.
+ */
+cursor?: number, 
+/**
+ * Only return changes to courses in this term.
+ */
+term?: string | null, 
+/**
+ * Only return changes to courses in this subject.
+ */
+subject?: string | null, 
+/**
+ * Seconds to wait for a change (default 25, max 45).
+ */
+wait?: number, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuditLogEntry } from "./AuditLogEntry";
+
+export type UpdatesPollResponse = { 
+/**
+ * Pass back as `cursor` on the next poll.
+ */
+cursor: number, 
+/**
+ * The supplied cursor is no longer in the buffer; refetch current state.
+ */
+reset: boolean, entries: Array<AuditLogEntry>, };
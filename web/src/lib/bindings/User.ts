@@ -1,6 +1,15 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimeFormat } from "./TimeFormat";
 
 /**
- * A user authenticated via Discord OAuth.
+ * A user, signed in through one or more OAuth providers.
  */
-export type User = { discordId: string, discordUsername: string, discordAvatarHash: string | null, isAdmin: boolean, createdAt: string, updatedAt: string, };
+export type User = { id: string, 
+/**
+ * Snowflake of the linked Discord account, if any.
+ */
+discordId: string | null, discordUsername: string, discordAvatarHash: string | null, isAdmin: boolean, 
+/**
+ * IANA timezone used when rendering times for this user.
+ */
+timezone: string, timeFormat: TimeFormat, createdAt: string, updatedAt: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UserIdentity } from "./UserIdentity";
+
+/**
+ * Response for `GET /api/auth/identities`.
+ */
+export type UserIdentitiesResponse = { identities: Array<UserIdentity>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A linked sign-in method, as shown to its owner.
+ */
+export type UserIdentity = { provider: string, email: string | null, displayName: string | null, linkedAt: string, lastLoginAt: string | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The sections the caller has planned for a term.
+ */
+export type UserScheduleResponse = { term: string, crns: Array<string>, 
+/**
+ * When the plan was last saved; `null` if it never was.
+ */
+updatedAt: string | null, };
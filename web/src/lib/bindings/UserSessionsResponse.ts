@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ActiveSession } from "./ActiveSession";
+
+/**
+ * Response for `GET /api/auth/sessions`.
+ */
+export type UserSessionsResponse = { sessions: Array<ActiveSession>, };
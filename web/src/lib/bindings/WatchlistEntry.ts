@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WatchType } from "./WatchType";
+
+/**
+ * One of the caller's active watches.
+ */
+export type WatchlistEntry = { term: string, crn: string, subject: string, courseNumber: string, title: string, watchType: WatchType, 
+/**
+ * When the watch last fired; it won't fire again for 15 minutes.
+ */
+notifiedAt: string | null, 
+/**
+ * Where alerts are posted; `null` means a Discord DM.
+ */
+webhookUrl: string | null, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A registered endpoint, without its secret.
+ */
+export type Webhook = { id: number, url: string, description: string, eventTypes: Array<WebhookEvent>, 
+/**
+ * Disabled endpoints get no new deliveries; queued ones are dropped.
+ */
+enabled: boolean, 
+/**
+ * Username of the admin who registered the endpoint, if they still exist.
+ */
+createdBy: string | null, createdAt: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookDelivery } from "./WebhookDelivery";
+
+/**
+ * Response for `GET /api/admin/webhooks/{id}/deliveries`.
+ */
+export type WebhookDeliveriesResponse = { deliveries: Array<WebhookDelivery>, };
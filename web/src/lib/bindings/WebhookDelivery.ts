@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * One entry in an endpoint's delivery log.
+ */
+export type WebhookDelivery = { 
+/**
+ * Outbox message id, also sent as `X-Banner-Delivery`.
+ */
+id: number, event: string, status: DeliveryStatus, attempts: number, lastError: string | null, body: JsonValue, createdAt: string, 
+/**
+ * When a pending delivery is next tried.
+ */
+nextAttemptAt: string, deliveredAt: string | null, };
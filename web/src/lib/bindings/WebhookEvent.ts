@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Course events an endpoint can subscribe to.
+ */
+export type WebhookEvent = "course.created" | "enrollment.changed" | "instructor.assigned";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Webhook } from "./Webhook";
+
+/**
+ * Response for `GET /api/admin/webhooks`.
+ */
+export type WebhooksResponse = { webhooks: Array<Webhook>, };
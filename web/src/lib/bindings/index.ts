@@ -1,68 +1,185 @@
+export type { ActiveSession } from "./ActiveSession";
+export type { AddDepartmentWatchBody } from "./AddDepartmentWatchBody";
+export type { AddWatchBody } from "./AddWatchBody";
+export type { AddWatchResponse } from "./AddWatchResponse";
+export type { AdminAction } from "./AdminAction";
 export type { AdminServiceInfo } from "./AdminServiceInfo";
+export type { AdminStatusLatency } from "./AdminStatusLatency";
 export type { AdminStatusResponse } from "./AdminStatusResponse";
 export type { ApiError } from "./ApiError";
 export type { ApiErrorCode } from "./ApiErrorCode";
+export type { ApiKey } from "./ApiKey";
+export type { ApiKeysResponse } from "./ApiKeysResponse";
 export type { AssignBody } from "./AssignBody";
 export type { Attribute } from "./Attribute";
+export type { AttributionResponse } from "./AttributionResponse";
 export type { AuditLogEntry } from "./AuditLogEntry";
 export type { AuditLogFilter } from "./AuditLogFilter";
 export type { AuditLogResponse } from "./AuditLogResponse";
+export type { AuthProvidersResponse } from "./AuthProvidersResponse";
+export type { BannerHealth } from "./BannerHealth";
+export type { BlockedAgentCount } from "./BlockedAgentCount";
 export type { BlueBookBrief } from "./BlueBookBrief";
 export type { BlueBookFull } from "./BlueBookFull";
+export type { BluebookBulkReviewResponse } from "./BluebookBulkReviewResponse";
 export type { BluebookLinkCourse } from "./BluebookLinkCourse";
 export type { BluebookLinkDetail } from "./BluebookLinkDetail";
 export type { BluebookLinkListItem } from "./BluebookLinkListItem";
 export type { BluebookLinkStats } from "./BluebookLinkStats";
 export type { BluebookMatchResponse } from "./BluebookMatchResponse";
 export type { BluebookOkResponse } from "./BluebookOkResponse";
+export type { BluebookSuggestion } from "./BluebookSuggestion";
 export type { BluebookSyncTriggerResponse } from "./BluebookSyncTriggerResponse";
+export type { BluebookUnmatchedLink } from "./BluebookUnmatchedLink";
+export type { BotCommandsResponse } from "./BotCommandsResponse";
+export type { BuildingSuggestion } from "./BuildingSuggestion";
+export type { BulkLinkIdsBody } from "./BulkLinkIdsBody";
 export type { Campus } from "./Campus";
+export type { CandidateIdsBody } from "./CandidateIdsBody";
+export type { CandidateQueueItem } from "./CandidateQueueItem";
 export type { CandidateResponse } from "./CandidateResponse";
+export type { CandidateReviewResponse } from "./CandidateReviewResponse";
+export type { CapturedExchange } from "./CapturedExchange";
+export type { ChangedSection } from "./ChangedSection";
+export type { CircuitState } from "./CircuitState";
+export type { Classification } from "./Classification";
 export type { CodeDescription } from "./CodeDescription";
+export type { CommandRegistration } from "./CommandRegistration";
+export type { CommandRegistrationStatus } from "./CommandRegistrationStatus";
+export type { CourseAsOfParams } from "./CourseAsOfParams";
+export type { CourseAsOfResponse } from "./CourseAsOfResponse";
+export type { CourseCorequisite } from "./CourseCorequisite";
+export type { CourseDetailResponse } from "./CourseDetailResponse";
+export type { CourseFilter } from "./CourseFilter";
+export type { CourseGroupSuggestion } from "./CourseGroupSuggestion";
+export type { CoursePageData } from "./CoursePageData";
 export type { CourseResponse } from "./CourseResponse";
+export type { CourseRestriction } from "./CourseRestriction";
+export type { CourseSeats } from "./CourseSeats";
 export type { CourseSuggestion } from "./CourseSuggestion";
+export type { CrawlRule } from "./CrawlRule";
+export type { CrawlerConfig } from "./CrawlerConfig";
+export type { CrawlerConfigResponse } from "./CrawlerConfigResponse";
+export type { CreateApiKeyBody } from "./CreateApiKeyBody";
+export type { CreateIpBanBody } from "./CreateIpBanBody";
+export type { CreateSavedSearchBody } from "./CreateSavedSearchBody";
+export type { CreateWebhookBody } from "./CreateWebhookBody";
+export type { CreatedApiKey } from "./CreatedApiKey";
+export type { CreatedWebhook } from "./CreatedWebhook";
 export type { CreditHours } from "./CreditHours";
 export type { CrossList } from "./CrossList";
+export type { DataFreshness } from "./DataFreshness";
+export type { DatabaseHealth } from "./DatabaseHealth";
 export type { DateRange } from "./DateRange";
 export type { DayOfWeek } from "./DayOfWeek";
 export type { DbMeetingTime } from "./DbMeetingTime";
 export type { DbTerm } from "./DbTerm";
+export type { DebugCapture } from "./DebugCapture";
+export type { DebugCapturesResponse } from "./DebugCapturesResponse";
+export type { DeliveryStatus } from "./DeliveryStatus";
+export type { DepartmentWatchEntry } from "./DepartmentWatchEntry";
+export type { DisplayPreferences } from "./DisplayPreferences";
+export type { EnqueueScrapeBody } from "./EnqueueScrapeBody";
+export type { EnqueueScrapeResponse } from "./EnqueueScrapeResponse";
 export type { Enrollment } from "./Enrollment";
+export type { FacetOption } from "./FacetOption";
+export type { FeedbackCategory } from "./FeedbackCategory";
+export type { FeedbackEntityRef } from "./FeedbackEntityRef";
+export type { FeedbackItem } from "./FeedbackItem";
+export type { FeedbackStats } from "./FeedbackStats";
+export type { FeedbackStatus } from "./FeedbackStatus";
 export type { FilterRanges } from "./FilterRanges";
+export type { FreeRoom } from "./FreeRoom";
+export type { FreeRoomsParams } from "./FreeRoomsParams";
+export type { FreeRoomsResponse } from "./FreeRoomsResponse";
+export type { FreshnessResponse } from "./FreshnessResponse";
+export type { FullStatusResponse } from "./FullStatusResponse";
+export type { GradeDistribution } from "./GradeDistribution";
 export type { HybridVariant } from "./HybridVariant";
+export type { ImportDecisionsResponse } from "./ImportDecisionsResponse";
+export type { ImportRowError } from "./ImportRowError";
 export type { InstructionalMethod } from "./InstructionalMethod";
 export type { InstructorDetail } from "./InstructorDetail";
 export type { InstructorDetailResponse } from "./InstructorDetailResponse";
 export type { InstructorListItem } from "./InstructorListItem";
 export type { InstructorRating } from "./InstructorRating";
+export type { InstructorReassignment } from "./InstructorReassignment";
 export type { InstructorResponse } from "./InstructorResponse";
 export type { InstructorStats } from "./InstructorStats";
 export type { InstructorSuggestion } from "./InstructorSuggestion";
+export type { IpBan } from "./IpBan";
+export type { IpBansResponse } from "./IpBansResponse";
+export type { JsonLdAggregateRating } from "./JsonLdAggregateRating";
+export type { JsonLdCourse } from "./JsonLdCourse";
+export type { JsonLdCourseInstance } from "./JsonLdCourseInstance";
+export type { JsonLdInstructor } from "./JsonLdInstructor";
+export type { JsonLdOrganization } from "./JsonLdOrganization";
+export type { JsonLdPerson } from "./JsonLdPerson";
 export type { LinkedRmpProfile } from "./LinkedRmpProfile";
+export type { ListAdminActionsParams } from "./ListAdminActionsParams";
+export type { ListAdminActionsResponse } from "./ListAdminActionsResponse";
 export type { ListBluebookLinksParams } from "./ListBluebookLinksParams";
 export type { ListBluebookLinksResponse } from "./ListBluebookLinksResponse";
+export type { ListBluebookUnmatchedResponse } from "./ListBluebookUnmatchedResponse";
+export type { ListCandidatesParams } from "./ListCandidatesParams";
+export type { ListCandidatesResponse } from "./ListCandidatesResponse";
+export type { ListFeedbackParams } from "./ListFeedbackParams";
+export type { ListFeedbackResponse } from "./ListFeedbackResponse";
 export type { ListInstructorsParams } from "./ListInstructorsParams";
 export type { ListInstructorsResponse } from "./ListInstructorsResponse";
+export type { ListUnmatchedLinksParams } from "./ListUnmatchedLinksParams";
+export type { ListWebhookDeliveriesParams } from "./ListWebhookDeliveriesParams";
+export type { MaintenanceConfig } from "./MaintenanceConfig";
 export type { MatchBody } from "./MatchBody";
 export type { MeetingLocation } from "./MeetingLocation";
+export type { MeetingTimeChange } from "./MeetingTimeChange";
 export type { MetricEntry } from "./MetricEntry";
 export type { MetricsParams } from "./MetricsParams";
 export type { MetricsResponse } from "./MetricsResponse";
+export type { NameMatchQuality } from "./NameMatchQuality";
+export type { NicknameGroup } from "./NicknameGroup";
+export type { NicknameGroupBody } from "./NicknameGroupBody";
+export type { NicknameGroupsResponse } from "./NicknameGroupsResponse";
 export type { OkResponse } from "./OkResponse";
 export type { OnlineVariant } from "./OnlineVariant";
 export type { PartOfTerm } from "./PartOfTerm";
+export type { ProviderKind } from "./ProviderKind";
 export type { PublicInstructorListItem } from "./PublicInstructorListItem";
 export type { PublicInstructorListParams } from "./PublicInstructorListParams";
 export type { PublicInstructorListResponse } from "./PublicInstructorListResponse";
 export type { PublicInstructorProfile } from "./PublicInstructorProfile";
 export type { PublicInstructorProfileResponse } from "./PublicInstructorProfileResponse";
+export type { RatingBreakdown } from "./RatingBreakdown";
 export type { RatingSource } from "./RatingSource";
+export type { RebuildSlugsBody } from "./RebuildSlugsBody";
+export type { RebuildSlugsResponse } from "./RebuildSlugsResponse";
+export type { RecomputeMode } from "./RecomputeMode";
+export type { RecomputeStats } from "./RecomputeStats";
+export type { RegistrationWindow } from "./RegistrationWindow";
+export type { RegistrationWindowInput } from "./RegistrationWindowInput";
+export type { RegistrationWindowsResponse } from "./RegistrationWindowsResponse";
 export type { RejectCandidateBody } from "./RejectCandidateBody";
+export type { RelatedInstructor } from "./RelatedInstructor";
+export type { RelatedInstructorsResponse } from "./RelatedInstructorsResponse";
+export type { RenameApiKeyBody } from "./RenameApiKeyBody";
+export type { ReplaceScheduleBody } from "./ReplaceScheduleBody";
+export type { RequeueJobResponse } from "./RequeueJobResponse";
 export type { RescoreResponse } from "./RescoreResponse";
 export type { RmpBrief } from "./RmpBrief";
 export type { RmpFull } from "./RmpFull";
+export type { RmpHistoryPoint } from "./RmpHistoryPoint";
 export type { RmpMatchStatus } from "./RmpMatchStatus";
+export type { RmpMatchingPhase } from "./RmpMatchingPhase";
+export type { RmpMatchingProgress } from "./RmpMatchingProgress";
+export type { RmpTrend } from "./RmpTrend";
+export type { RoomBlock } from "./RoomBlock";
+export type { RoomDay } from "./RoomDay";
+export type { RoomScheduleParams } from "./RoomScheduleParams";
+export type { RoomScheduleResponse } from "./RoomScheduleResponse";
+export type { SavedSearchResponse } from "./SavedSearchResponse";
 export type { ScoreBreakdown } from "./ScoreBreakdown";
+export type { ScrapeDiffSummary } from "./ScrapeDiffSummary";
+export type { ScrapeHistoryRetention } from "./ScrapeHistoryRetention";
 export type { ScrapeJobDto } from "./ScrapeJobDto";
 export type { ScrapeJobEvent } from "./ScrapeJobEvent";
 export type { ScrapeJobStatus } from "./ScrapeJobStatus";
@@ -72,6 +189,8 @@ export type { ScrapePriority } from "./ScrapePriority";
 export type { ScraperStatsFilter } from "./ScraperStatsFilter";
 export type { ScraperStatsResponse } from "./ScraperStatsResponse";
 export type { ScraperTimeseriesFilter } from "./ScraperTimeseriesFilter";
+export type { ScrapingStatsResponse } from "./ScrapingStatsResponse";
+export type { SearchFacetsResponse } from "./SearchFacetsResponse";
 export type { SearchOptionsReference } from "./SearchOptionsReference";
 export type { SearchOptionsResponse } from "./SearchOptionsResponse";
 export type { SearchParams } from "./SearchParams";
@@ -79,8 +198,12 @@ export type { SearchResponse } from "./SearchResponse";
 export type { SectionLink } from "./SectionLink";
 export type { ServiceInfo } from "./ServiceInfo";
 export type { ServiceStatus } from "./ServiceStatus";
+export type { SlugChange } from "./SlugChange";
 export type { SortColumn } from "./SortColumn";
 export type { SortDirection } from "./SortDirection";
+export type { SourceAttribution } from "./SourceAttribution";
+export type { SourceSyncStatus } from "./SourceSyncStatus";
+export type { StartCaptureBody } from "./StartCaptureBody";
 export type { StatsParams } from "./StatsParams";
 export type { StatusResponse } from "./StatusResponse";
 export type { StreamClientMessage } from "./StreamClientMessage";
@@ -90,26 +213,73 @@ export type { StreamFilter } from "./StreamFilter";
 export type { StreamKind } from "./StreamKind";
 export type { StreamServerMessage } from "./StreamServerMessage";
 export type { StreamSnapshot } from "./StreamSnapshot";
+export type { SubjectAlias } from "./SubjectAlias";
+export type { SubjectAliasBody } from "./SubjectAliasBody";
+export type { SubjectAliasesResponse } from "./SubjectAliasesResponse";
+export type { SubjectBlueBook } from "./SubjectBlueBook";
 export type { SubjectDetailParams } from "./SubjectDetailParams";
 export type { SubjectDetailResponse } from "./SubjectDetailResponse";
+export type { SubjectInstructor } from "./SubjectInstructor";
+export type { SubjectLevelCount } from "./SubjectLevelCount";
+export type { SubjectOverview } from "./SubjectOverview";
 export type { SubjectResultEntry } from "./SubjectResultEntry";
+export type { SubjectScrapeStats } from "./SubjectScrapeStats";
+export type { SubjectScrapeStatus } from "./SubjectScrapeStatus";
+export type { SubjectSuggestion } from "./SubjectSuggestion";
 export type { SubjectSummary } from "./SubjectSummary";
+export type { SubjectTerm } from "./SubjectTerm";
 export type { SubjectsResponse } from "./SubjectsResponse";
+export type { SubmitFeedbackBody } from "./SubmitFeedbackBody";
+export type { SubmitFeedbackResponse } from "./SubmitFeedbackResponse";
+export type { SuggestGroupBy } from "./SuggestGroupBy";
 export type { SuggestParams } from "./SuggestParams";
 export type { SuggestResponse } from "./SuggestResponse";
+export type { SyncKind } from "./SyncKind";
+export type { SyncOutcome } from "./SyncOutcome";
+export type { SyncRunResponse } from "./SyncRunResponse";
+export type { SyncStatus } from "./SyncStatus";
+export type { SyncsResponse } from "./SyncsResponse";
 export type { TargetType } from "./TargetType";
 export type { TeachingHistoryCourse } from "./TeachingHistoryCourse";
 export type { TeachingHistoryTerm } from "./TeachingHistoryTerm";
+export type { TermChangeCounts } from "./TermChangeCounts";
+export type { TermChangeDay } from "./TermChangeDay";
+export type { TermChangesParams } from "./TermChangesParams";
+export type { TermChangesResponse } from "./TermChangesResponse";
+export type { TermFreshness } from "./TermFreshness";
 export type { TermResponse } from "./TermResponse";
+export type { TermScrapeStatus } from "./TermScrapeStatus";
 export type { TermSyncResponse } from "./TermSyncResponse";
+export type { TermTimeline } from "./TermTimeline";
 export type { TermUpdateResponse } from "./TermUpdateResponse";
 export type { TermsListResponse } from "./TermsListResponse";
+export type { TimeFormat } from "./TimeFormat";
 export type { TimeRange } from "./TimeRange";
+export type { TimelineCompareRequest } from "./TimelineCompareRequest";
+export type { TimelineCompareResponse } from "./TimelineCompareResponse";
+export type { TimelineGroupBy } from "./TimelineGroupBy";
 export type { TimelineRequest } from "./TimelineRequest";
 export type { TimelineResponse } from "./TimelineResponse";
+export type { TimelineSeries } from "./TimelineSeries";
 export type { TimelineSlot } from "./TimelineSlot";
 export type { TimeseriesParams } from "./TimeseriesParams";
 export type { TimeseriesPoint } from "./TimeseriesPoint";
 export type { TimeseriesResponse } from "./TimeseriesResponse";
 export type { TopCandidateResponse } from "./TopCandidateResponse";
+export type { UpdateFeedbackStatusBody } from "./UpdateFeedbackStatusBody";
+export type { UpdateRegistrationWindowsBody } from "./UpdateRegistrationWindowsBody";
+export type { UpdateWebhookBody } from "./UpdateWebhookBody";
+export type { UpdatesPollParams } from "./UpdatesPollParams";
+export type { UpdatesPollResponse } from "./UpdatesPollResponse";
 export type { User } from "./User";
+export type { UserIdentitiesResponse } from "./UserIdentitiesResponse";
+export type { UserIdentity } from "./UserIdentity";
+export type { UserScheduleResponse } from "./UserScheduleResponse";
+export type { UserSessionsResponse } from "./UserSessionsResponse";
+export type { WatchType } from "./WatchType";
+export type { WatchlistEntry } from "./WatchlistEntry";
+export type { Webhook } from "./Webhook";
+export type { WebhookDeliveriesResponse } from "./WebhookDeliveriesResponse";
+export type { WebhookDelivery } from "./WebhookDelivery";
+export type { WebhookEvent } from "./WebhookEvent";
+export type { WebhooksResponse } from "./WebhooksResponse";
@@ -31,7 +31,9 @@ type EventFor<S extends StreamKind> = S extends "scrapeJobs"
         ? { changed: TimeseriesPoint[] }
         : S extends "scraperSubjects"
           ? { changed: SubjectSummary[]; removed: string[] }
-          : never;
+          : S extends "course"
+            ? { entries: AuditLogEntry[] }
+            : never;
 
 // Extract the event type discriminator
 type EventType<S extends StreamKind> = EventFor<S> extends { type: infer T } ? T : never;
@@ -159,6 +161,9 @@ function extractSnapshotState<S extends StreamKind>(stream: S, snapshot: StreamS
   if (stream === "scraperSubjects" && snapshot.stream === "scraperSubjects") {
     return snapshot.subjects;
   }
+  if (stream === "course" && snapshot.stream === "course") {
+    return snapshot.seats;
+  }
   return null;
 }
 
@@ -181,5 +186,8 @@ function extractEvent<S extends StreamKind>(stream: S, delta: StreamDelta): Even
   if (stream === "scraperSubjects" && delta.stream === "scraperSubjects") {
     return { changed: delta.changed, removed: delta.removed } as EventFor<S>;
   }
+  if (stream === "course" && delta.stream === "course") {
+    return { entries: delta.entries } as EventFor<S>;
+  }
   return null;
 }
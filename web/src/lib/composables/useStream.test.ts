@@ -19,6 +19,7 @@ function mockJob(overrides: Partial<ScrapeJobDto> = {}): ScrapeJobDto {
     lockedAt: null,
     createdAt: "2024-01-01T00:00:00Z",
     status: "pending",
+    lastDiff: null,
     ...overrides,
   };
 }
@@ -183,6 +184,8 @@ describe("reducer patterns for scrape jobs", () => {
         type: "completed",
         id: 1,
         subject: "CS",
+        term: "202620",
+        diff: null,
       };
 
       const result = removeById(jobs, event.id);
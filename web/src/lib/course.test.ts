@@ -386,6 +386,7 @@ function makeCourse(overrides: Partial<CourseResponse> = {}): CourseResponse {
     meetingTimes: [],
     attributes: [],
     instructors: [],
+    enrollmentHistory: null,
     ...overrides,
   };
 }
@@ -237,6 +237,7 @@ export const FILTER_REGISTRY = {
   subject: { urlKey: "subject", serializer: arrayParam() },
   query: { urlKey: "query", serializer: stringParam(), aliases: ["q"], countAsActive: false },
   openOnly: { urlKey: "open", serializer: boolParam() },
+  waitlistAvailable: { urlKey: "waitlist", serializer: boolParam() },
   waitCountMax: { urlKey: "wait_count_max", serializer: intParam() },
   days: { urlKey: "days", serializer: arrayParam() },
   timeStart: { urlKey: "time_start", serializer: stringParam(), group: "time" },
@@ -391,6 +392,7 @@ export function toAPIParams(
     term: meta.term,
     limit: meta.limit,
     offset: meta.offset,
+    cursor: null,
     sortBy,
     sortDir,
   };
@@ -77,6 +77,7 @@ export const courseWithSeats: CourseResponse = {
       },
     },
   ],
+  enrollmentHistory: [14, 16, 16, 19, 20, 22, 22],
 };
 
 /**
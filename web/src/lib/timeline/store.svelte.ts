@@ -86,7 +86,7 @@ async function fetchFromApi(gaps: Range[]): Promise<TimeSlot[]> {
 
   return result.value.slots.map((slot) => ({
     time: new Date(slot.time),
-    subjects: Object.fromEntries(Object.entries(slot.subjects).map(([k, v]) => [k, Number(v)])),
+    subjects: Object.fromEntries(Object.entries(slot.totals).map(([k, v]) => [k, Number(v)])),
   }));
 }
 
@@ -1,6 +1,7 @@
 import { browser } from "$app/environment";
 import type {
   AuditLogFilter,
+  CourseFilter,
   ScrapeJobsFilter,
   ScraperStatsFilter,
   ScraperTimeseriesFilter,
@@ -30,7 +31,9 @@ export type FilterFor<S extends StreamKey> = S extends "scrapeJobs"
         ? ScraperTimeseriesFilter
         : S extends "scraperSubjects"
           ? null
-          : never;
+          : S extends "course"
+            ? CourseFilter
+            : never;
 
 interface SubscriptionHandlers<S extends StreamKey> {
   onSnapshot: (snapshot: SnapshotFor<S>) => void;
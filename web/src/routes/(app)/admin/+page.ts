@@ -3,9 +3,9 @@ import type { PageLoad } from "./$types";
 
 export const load: PageLoad = async ({ fetch }) => {
   const client = new BannerApiClient(undefined, fetch);
-  const result = await client.getAdminStatus();
+  const [result, syncs] = await Promise.all([client.getAdminStatus(), client.getSyncs()]);
   if (result.isErr) {
-    return { status: null, error: result.error.message };
+    return { status: null, syncs: [], error: result.error.message };
   }
-  return { status: result.value, error: null };
+  return { status: result.value, syncs: syncs.isErr ? [] : syncs.value.syncs, error: null };
 };
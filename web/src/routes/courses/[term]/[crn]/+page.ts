@@ -7,24 +7,31 @@ export const prerender = false;
 export const load: PageLoad = async ({ params, fetch }) => {
   const client = new BannerApiClient(undefined, fetch);
 
-  const [courseResult, searchOptionsResult] = await Promise.all([
-    client.getCourse(params.term, params.crn),
+  const [pageResult, searchOptionsResult, jsonLdResult] = await Promise.all([
+    client.getCoursePageData(params.term, params.crn),
     client.getSearchOptions(params.term),
+    client.getCourseJsonLd(params.term, params.crn),
   ]);
 
-  if (courseResult.isErr) {
-    if (courseResult.error.isNotFound()) {
+  if (pageResult.isErr) {
+    if (pageResult.error.isNotFound()) {
       error(404, "Section not found");
     }
-    error(500, courseResult.error.message);
+    error(500, pageResult.error.message);
   }
 
-  const course = courseResult.value;
+  const { course, sections, auditHighlights, enrollmentHistory } = pageResult.value;
   const searchOptions = searchOptionsResult.isOk ? searchOptionsResult.value : null;
+  // Structured data can be switched off in the crawler config.
+  const jsonLd = jsonLdResult.isOk ? jsonLdResult.value : null;
 
   return {
     course,
+    sections,
+    auditHighlights,
+    enrollmentHistory,
     searchOptions,
+    jsonLd,
     term: params.term,
   };
 };
@@ -3,9 +3,9 @@ import type { PageLoad } from "./$types";
 
 export const load: PageLoad = async ({ fetch }) => {
   const client = new BannerApiClient(undefined, fetch);
-  const result = await client.getStatus();
-  if (result.isErr) {
-    return { initialStatus: null };
-  }
-  return { initialStatus: result.value };
+  const [status, fullStatus] = await Promise.all([client.getStatus(), client.getFullStatus()]);
+  return {
+    initialStatus: status.isErr ? null : status.value,
+    fullStatus: fullStatus.isErr ? null : fullStatus.value,
+  };
 };
@@ -7,9 +7,11 @@ export const prerender = false;
 export const load: PageLoad = async ({ params, fetch }) => {
   const client = new BannerApiClient(undefined, fetch);
 
-  const [profileResult, searchOptionsResult] = await Promise.all([
+  const [profileResult, searchOptionsResult, relatedResult, jsonLdResult] = await Promise.all([
     client.getInstructor(params.slug),
     client.getSearchOptions(),
+    client.getRelatedInstructors(params.slug),
+    client.getInstructorJsonLd(params.slug),
   ]);
 
   if (profileResult.isErr) {
@@ -24,6 +26,12 @@ export const load: PageLoad = async ({ params, fetch }) => {
     console.warn("Failed to load search options:", searchOptionsResult.error.message);
   }
   const searchOptions = searchOptionsResult.isOk ? searchOptionsResult.value : null;
+  if (relatedResult.isErr) {
+    console.warn("Failed to load related instructors:", relatedResult.error.message);
+  }
+  const related = relatedResult.isOk ? relatedResult.value.instructors : [];
+  // Structured data can be switched off in the crawler config.
+  const jsonLd = jsonLdResult.isOk ? jsonLdResult.value : null;
 
   // Fetch sections for the instructor's most recent known term
   const allTerms = searchOptions?.terms ?? [];
@@ -45,6 +53,8 @@ export const load: PageLoad = async ({ params, fetch }) => {
     searchOptions,
     initialSections,
     initialTerm: defaultTerm ?? null,
+    related,
+    jsonLd,
     slug: params.slug,
   };
 };
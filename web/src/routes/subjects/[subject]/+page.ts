@@ -8,8 +8,12 @@ export const load: PageLoad = async ({ params, url, fetch }) => {
 
   const termParam = url.searchParams.get("term") ?? undefined;
 
-  const searchOptionsResult = await client.getSearchOptions(termParam);
+  const [searchOptionsResult, overviewResult] = await Promise.all([
+    client.getSearchOptions(termParam),
+    client.getSubject(params.subject),
+  ]);
   const searchOptions = searchOptionsResult.isOk ? searchOptionsResult.value : null;
+  const overview = overviewResult.isOk ? overviewResult.value : null;
 
   // Resolve subject description
   const subjectDescription =
@@ -31,6 +35,7 @@ export const load: PageLoad = async ({ params, url, fetch }) => {
   }
 
   return {
+    overview,
     searchOptions,
     searchResult,
     subject: params.subject,